@@ -1,9 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use arb_bot::types::AtomicMarketState;
+use arb_bot::types::{AtomicMarketState, MarketId};
 
 fn bench_check_arbs_detection(c: &mut Criterion) {
     c.bench_function("check_arbs_detection", |b| {
-        let market = AtomicMarketState::new(0);
+        let market = AtomicMarketState::new(MarketId(0));
         
         // Set up Kalshi prices (0-100, representing $0-$1.00)
         market.kalshi.update_yes(50, 100);   // $0.50
@@ -21,7 +21,7 @@ fn bench_check_arbs_detection(c: &mut Criterion) {
 
 fn bench_orderbook_updates(c: &mut Criterion) {
     c.bench_function("orderbook_yes_updates", |b| {
-        let market = AtomicMarketState::new(0);
+        let market = AtomicMarketState::new(MarketId(0));
         b.iter(|| {
             for i in 0..100 {
                 let price = black_box(((i % 100) as u16));
@@ -33,7 +33,7 @@ fn bench_orderbook_updates(c: &mut Criterion) {
 
 fn bench_orderbook_load(c: &mut Criterion) {
     c.bench_function("orderbook_concurrent_load", |b| {
-        let market = AtomicMarketState::new(0);
+        let market = AtomicMarketState::new(MarketId(0));
         market.kalshi.update_yes(50, 100);
         market.kalshi.update_no(50, 100);
         market.poly.update_yes(49, 100);
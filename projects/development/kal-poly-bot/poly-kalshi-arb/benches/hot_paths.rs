@@ -0,0 +1,287 @@
+// benches/hot_paths.rs
+// Hot-path benchmarks: price observation / correlation analysis, filter predict+update,
+// worker request processing, tick bridge crossings, feed ingestion handoff under the standard and
+// low-latency runtime profiles, backtester tick throughput, and worker / dashboard payload
+// encoding in JSON vs MessagePack vs CBOR (throughput is the payload size, so the byte savings
+// show next to the timings).
+// Compare a run against benches/baseline.json with scripts/check_bench_regressions.py
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use arb_bot::bun_worker_integration::{BunWorker, TickData, WorkerConfig, WorkerRequest};
+use arb_bot::config::RuntimeSection;
+use arb_bot::event_bus::{EventBus, Topic};
+use arb_bot::kalman_filter_suite::KalmanFilterFactory;
+use arb_bot::monitoring_dashboard::{DashboardSnapshot, MonitoringDashboard};
+use arb_bot::latency_arbitrage::{ConvergenceKalman, LatencyArbitrageEngine, MarketTier, PriceObservation};
+use arb_bot::runtime_profile::{IngestQueue, RuntimeProfile, SharedIngestQueue};
+use arb_bot::tick_bridge::TickBridge;
+use arb_bot::tick_sim_backtester::{BacktestConfig, TickSimBacktester};
+use arb_bot::types::{MarketType, Platform};
+use arb_bot::wire_format::WireFormat;
+
+const PROVIDERS: [Platform; 4] = [Platform::Kalshi, Platform::Polymarket, Platform::DraftKings, Platform::FanDuel];
+
+fn observation(market_id: u16, provider: Platform, price: u16, timestamp_ns: u64) -> PriceObservation {
+    PriceObservation {
+        market_id,
+        provider,
+        market_type: MarketType::Moneyline,
+        price,
+        size: 100,
+        timestamp_ns,
+        tier: MarketTier::Tier1,
+        features: None,
+    }
+}
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .expect("bench runtime")
+}
+
+fn bench_price_observation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("latency_arbitrage");
+
+    // Each observation is correlated against every other live feed, so cost scales with feed count
+    for feeds in [4usize, 16, 64] {
+        group.bench_with_input(BenchmarkId::new("add_price_observation", feeds), &feeds, |b, &feeds| {
+            let mut engine = LatencyArbitrageEngine::new();
+            let markets = (feeds / PROVIDERS.len()) as u16;
+            let mut ts = 1_000_000u64;
+            for market_id in 0..markets {
+                for provider in PROVIDERS {
+                    ts += 1_000;
+                    engine.add_price_observation(observation(market_id, provider, 50, ts));
+                }
+            }
+
+            let mut i = 0u64;
+            b.iter(|| {
+                i += 1;
+                ts += 1_000;
+                let market_id = (i % markets as u64) as u16;
+                let provider = PROVIDERS[(i % PROVIDERS.len() as u64) as usize];
+                let price = 45 + (i % 10) as u16;
+                engine.add_price_observation(black_box(observation(market_id, provider, price, ts)));
+                // Keep the signal list bounded so later iterations don't pay for earlier ones
+                if engine.signals.len() > 1024 {
+                    engine.signals.clear();
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_filters(c: &mut Criterion) {
+    let mut group = c.benchmark_group("kalman");
+
+    group.bench_function("convergence_predict_update", |b| {
+        let mut filter = ConvergenceKalman::new();
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            filter.predict(black_box(0.05));
+            filter.update(black_box(((i % 20) as f64 - 10.0) * 0.1));
+        });
+    });
+
+    // Pattern #68 observes four books; the rest take a single measurement
+    for (pattern_id, dim) in [(51u16, 1usize), (56, 1), (68, 4), (75, 1)] {
+        group.bench_with_input(BenchmarkId::new("predict_update", pattern_id), &pattern_id, |b, &pattern_id| {
+            let mut filter = KalmanFilterFactory::create_filter(pattern_id, 0.05).expect("supported pattern");
+            let mut observation = vec![0.0; dim];
+            let mut i = 0u64;
+            b.iter(|| {
+                i += 1;
+                for (j, value) in observation.iter_mut().enumerate() {
+                    *value = 0.5 + ((i + j as u64) % 10) as f64 * 0.01;
+                }
+                filter.predict();
+                black_box(filter.update(black_box(&observation))).expect("observation dimension");
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_worker(c: &mut Criterion) {
+    let rt = runtime();
+    let mut group = c.benchmark_group("bun_worker");
+
+    group.bench_function("process_request", |b| {
+        // Persistence spawns a store write per request; measure the filter path only
+        let mut worker = BunWorker::new(WorkerConfig { enable_persistence: false, ..WorkerConfig::default() });
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            let request = WorkerRequest {
+                pattern_id: 51,
+                market_id: "NBA-LAL-BOS".to_string(),
+                tick: TickData {
+                    price: 220.5 + (i % 10) as f64 * 0.5,
+                    size: 1_000.0,
+                    book: "draftkings".to_string(),
+                    platform: "sportsbook".to_string(),
+                    market_type: "total".to_string(),
+                },
+                timestamp_ns: 1_000_000 + i * 100_000_000,
+                request_id: format!("bench-{}", i),
+            };
+            black_box(rt.block_on(worker.process_request(black_box(request))));
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_tick_bridge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_bridge");
+
+    // One tick through the shared ring into the engine and its signals back out;
+    // the budget for a crossing is 100µs
+    group.bench_function("push_pump", |b| {
+        let bus = std::sync::Arc::new(EventBus::new());
+        let mut signals = bus.subscribe("bench", &[Topic::Signal]);
+        let mut engine = LatencyArbitrageEngine::new().with_event_bus(bus);
+        let mut bridge = TickBridge::new(1024, 1024).expect("bridge");
+        let mut i = 0u64;
+        b.iter(|| {
+            i += 1;
+            let provider = PROVIDERS[(i % PROVIDERS.len() as u64) as usize];
+            bridge.push_tick(&observation((i % 4) as u16, provider, 45 + (i % 10) as u16, 0), 0);
+            black_box(bridge.pump(&mut engine, &mut signals, 1));
+            while bridge.pop_trigger().is_some() {}
+            if engine.signals.len() > 1024 {
+                engine.signals.clear();
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_ingest(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ingest");
+
+    // One tick from a feed thread through the ingestion queue until the loop has taken it:
+    // "standard" parks the loop between ticks, "low_latency" busy-polls on pinned workers.
+    // The two ids are the before/after pair; pinning uses cores 1-2, so run on 3+ idle cores.
+    for (name, low_latency) in [("standard", false), ("low_latency", true)] {
+        let profile = RuntimeProfile::from_config(&RuntimeSection {
+            low_latency,
+            worker_threads: 2,
+            pin_cores: vec![1, 2],
+            ..RuntimeSection::default()
+        });
+        let rt = profile.build_runtime().expect("bench runtime");
+        let queue: SharedIngestQueue<u64> = Arc::new(IngestQueue::new(profile.tick_buffer));
+        let seen = Arc::new(AtomicU64::new(0));
+        let spins = profile.spins_for(MarketTier::Tier1 as usize);
+        let ingestion = rt.spawn({
+            let (queue, seen) = (queue.clone(), seen.clone());
+            async move {
+                let mut batch = Vec::with_capacity(queue.capacity());
+                loop {
+                    queue.ready(spins).await;
+                    queue.drain_into(&mut batch);
+                    for tick in batch.drain(..) {
+                        seen.store(tick, Ordering::Release);
+                    }
+                }
+            }
+        });
+
+        group.bench_function(BenchmarkId::new("handoff", name), |b| {
+            let mut i = seen.load(Ordering::Acquire);
+            b.iter(|| {
+                i += 1;
+                queue.push(i);
+                while seen.load(Ordering::Acquire) != i {
+                    std::hint::spin_loop();
+                }
+            });
+        });
+        ingestion.abort();
+    }
+
+    group.finish();
+}
+
+fn bench_backtester(c: &mut Criterion) {
+    let rt = runtime();
+    let mut group = c.benchmark_group("tick_sim_backtester");
+    group.sample_size(10);
+
+    let load = || {
+        let mut backtester = TickSimBacktester::new(BacktestConfig { seed: Some(7), ..BacktestConfig::default() });
+        rt.block_on(backtester.load_historical_ticks("synthetic")).expect("synthetic ticks");
+        backtester
+    };
+    group.throughput(Throughput::Elements(load().tick_buffer.len() as u64));
+
+    group.bench_function("run_backtest", |b| {
+        b.iter_batched(
+            load,
+            |mut backtester| black_box(rt.block_on(backtester.run_backtest()).expect("backtest")),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn bench_wire(c: &mut Criterion) {
+    let rt = runtime();
+    let mut group = c.benchmark_group("wire");
+
+    let request = WorkerRequest {
+        pattern_id: 51,
+        market_id: "NBA-LAL-BOS".to_string(),
+        tick: TickData {
+            price: 220.5,
+            size: 1_000.0,
+            book: "draftkings".to_string(),
+            platform: "sportsbook".to_string(),
+            market_type: "total".to_string(),
+        },
+        timestamp_ns: 1_000_000,
+        request_id: "bench-1".to_string(),
+    };
+    let snapshot = rt.block_on(MonitoringDashboard::new().generate_snapshot()).expect("dashboard snapshot");
+
+    for format in WireFormat::ALL {
+        let name = format!("{:?}", format).to_lowercase();
+        let encoded = format.encode(&request).expect("encode request");
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_function(BenchmarkId::new("worker_request_encode", &name), |b| {
+            b.iter(|| black_box(format.encode(black_box(&request)).expect("encode")));
+        });
+        group.bench_function(BenchmarkId::new("worker_request_decode", &name), |b| {
+            b.iter(|| black_box(format.decode::<WorkerRequest>(black_box(&encoded)).expect("decode")));
+        });
+
+        let encoded = format.encode(&snapshot).expect("encode snapshot");
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+        group.bench_function(BenchmarkId::new("dashboard_snapshot_encode", &name), |b| {
+            b.iter(|| black_box(format.encode(black_box(&snapshot)).expect("encode")));
+        });
+        group.bench_function(BenchmarkId::new("dashboard_snapshot_decode", &name), |b| {
+            b.iter(|| black_box(format.decode::<DashboardSnapshot>(black_box(&encoded)).expect("decode")));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_price_observation, bench_filters, bench_worker, bench_tick_bridge, bench_ingest, bench_backtester, bench_wire);
+criterion_main!(benches);
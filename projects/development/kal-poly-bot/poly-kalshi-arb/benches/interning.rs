@@ -0,0 +1,143 @@
+// benches/interning.rs
+// Allocation cost of market / player / team IDs over a 1M-tick Pattern #73 replay: the interned
+// `Symbol` keys the engine uses against the `String` keys it used to format and clone per tick,
+// and the full backtester replay. A counting allocator prints allocations per tick next to
+// criterion's timings.
+//
+//     cargo bench --bench interning
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use arb_bot::interner::Symbol;
+use arb_bot::tick_sim_backtester::{BacktestConfig, HistoricalTick, TickSimBacktester};
+use arb_bot::types::{MarketType, Platform};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const TICKS: usize = 1_000_000;
+const TEAMS: usize = 8;
+const PLAYERS_PER_TEAM: usize = 5;
+
+/// Player props and team totals of 8 teams, a millisecond apart
+fn ticks() -> Vec<HistoricalTick> {
+    (0..TICKS).map(|i| {
+        let team = i % TEAMS;
+        let player = (i / TEAMS) % PLAYERS_PER_TEAM;
+        let prop = i % 3 != 0;
+        HistoricalTick {
+            id: i as u64,
+            timestamp_ns: 1_000_000_000 + i as u64 * 1_000_000,
+            market_id: Symbol::intern(&if prop { format!("PROP-T{}-P{}", team, player) } else { format!("TOTAL-T{}", team) }),
+            platform: Platform::DraftKings,
+            market_type: if prop { MarketType::PlayerProp } else { MarketType::Total },
+            price: if prop { 24.5 + (i % 7) as f64 * 0.5 } else { 110.5 + (i % 5) as f64 },
+            size: 1_000.0,
+            player_id: prop.then(|| Symbol::intern(&format!("player_{}_{}", team, player))),
+            team_id: Some(Symbol::intern(&format!("team_{}", team))),
+            raw_data: Vec::new(),
+        }
+    }).collect()
+}
+
+/// Allocations made while `f` runs, per replayed tick
+fn allocations_per_tick<T>(f: impl FnOnce() -> T) -> f64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / TICKS as f64
+}
+
+/// Pattern #73 state keyed the way it was before interning: owned IDs per state and a
+/// `player_team` key formatted (and copied) on every prop update
+fn string_keyed(ticks: &[HistoricalTick]) -> usize {
+    let mut props: HashMap<String, (String, f64)> = HashMap::new();
+    let mut betas: HashMap<String, f64> = HashMap::new();
+    let mut totals: HashMap<String, f64> = HashMap::new();
+    for tick in ticks {
+        let team = tick.team_id.map_or("", |t| t.as_str());
+        match tick.player_id {
+            Some(player) => {
+                let key = format!("{}_{}", player.as_str(), team);
+                props.entry(key.clone()).or_insert_with(|| (tick.market_id.as_str().to_string(), 0.0)).1 = tick.price;
+                *betas.entry(key.to_string()).or_default() += tick.price;
+            }
+            None => *totals.entry(team.to_string()).or_default() = tick.price,
+        }
+    }
+    props.len() + betas.len() + totals.len()
+}
+
+/// The same state keyed by `Symbol`, as `Pattern73Engine` keeps it
+fn symbol_keyed(ticks: &[HistoricalTick]) -> usize {
+    let mut props: HashMap<(Symbol, Symbol), (Symbol, f64)> = HashMap::new();
+    let mut betas: HashMap<(Symbol, Symbol), f64> = HashMap::new();
+    let mut totals: HashMap<Symbol, f64> = HashMap::new();
+    for tick in ticks {
+        let Some(team) = tick.team_id else { continue };
+        match tick.player_id {
+            Some(player) => {
+                props.entry((player, team)).or_insert((tick.market_id, 0.0)).1 = tick.price;
+                *betas.entry((player, team)).or_default() += tick.price;
+            }
+            None => *totals.entry(team).or_default() = tick.price,
+        }
+    }
+    props.len() + betas.len() + totals.len()
+}
+
+fn bench_interning(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().expect("bench runtime");
+    let ticks = ticks();
+    let load = || {
+        let mut backtester = TickSimBacktester::new(BacktestConfig { pattern_id: 73, seed: Some(7), ..BacktestConfig::default() });
+        backtester.tick_buffer.extend(ticks.iter().cloned());
+        backtester
+    };
+
+    let strings = allocations_per_tick(|| string_keyed(&ticks));
+    let symbols = allocations_per_tick(|| symbol_keyed(&ticks));
+    let mut backtester = load();
+    let replay = allocations_per_tick(|| rt.block_on(backtester.run_backtest()).expect("backtest"));
+    println!("interning: {} ticks, allocations per tick: string keys {:.3}, symbol keys {:.3}, backtest replay {:.3}",
+             TICKS, strings, symbols, replay);
+
+    let mut group = c.benchmark_group("interning");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(TICKS as u64));
+    group.bench_function("string_keys", |b| b.iter(|| string_keyed(black_box(&ticks))));
+    group.bench_function("symbol_keys", |b| b.iter(|| symbol_keyed(black_box(&ticks))));
+    group.bench_function("backtest_replay", |b| {
+        b.iter_batched(
+            load,
+            |mut backtester| black_box(rt.block_on(backtester.run_backtest()).expect("backtest")),
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_interning);
+criterion_main!(benches);
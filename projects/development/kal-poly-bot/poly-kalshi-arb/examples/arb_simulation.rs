@@ -0,0 +1,57 @@
+//! End-to-end Arb Simulation Example
+//!
+//! Feeds synthetic Kalshi/Polymarket quotes for two markets through detection, risk
+//! checks (trading breaker + per-pattern bankroll budgets) and simulated execution,
+//! then prints the decision transcript.
+//!
+//! cargo run --example arb_simulation [-- --json]
+
+use std::sync::Arc;
+
+use arb_bot::arb_simulation::{ArbSimulation, SimTick};
+use arb_bot::capital_allocator::{AllocatorConfig, CapitalAllocator};
+use arb_bot::types::Platform;
+
+#[tokio::main]
+async fn main() {
+    let json = std::env::args().any(|a| a == "--json");
+
+    // $2,000 bankroll; no history yet, so every pattern explores with 2% ($40)
+    let allocator = Arc::new(CapitalAllocator::new(AllocatorConfig { bankroll: 2_000.0, ..Default::default() }));
+    let sim = ArbSimulation::new(100)
+        .with_market("EPL-ARS-CHE", "epl")
+        .with_market("NBA-LAL-BOS", "nba")
+        .with_capital_allocator(allocator.clone());
+
+    let ticks = vec![
+        // Arsenal: Polymarket YES drops under Kalshi NO -> cross-venue arb
+        SimTick::new(0, "EPL-ARS-CHE", Platform::Kalshi, 55, 50).with_sizes(5000, 5000),
+        SimTick::new(10, "EPL-ARS-CHE", Platform::Polymarket, 56, 46),
+        SimTick::new(25, "EPL-ARS-CHE", Platform::Polymarket, 40, 62).with_sizes(5000, 5000),
+        // Lakers: same-venue Polymarket arb, but it's lifted before the orders land
+        SimTick::new(30, "NBA-LAL-BOS", Platform::Kalshi, 48, 55),
+        SimTick::new(40, "NBA-LAL-BOS", Platform::Polymarket, 45, 50),
+        SimTick::new(40, "NBA-LAL-BOS", Platform::Polymarket, 51, 50),
+        // Arsenal again: the PolyYesKalshiNo budget is spent
+        SimTick::new(60, "EPL-ARS-CHE", Platform::Polymarket, 41, 61),
+    ];
+
+    let transcript = sim.run(ticks).await;
+
+    if json {
+        for entry in &transcript.entries {
+            println!("{}", serde_json::to_string(entry).expect("transcript serializes"));
+        }
+        return;
+    }
+
+    println!("=== Arb Simulation Transcript ===");
+    for entry in &transcript.entries {
+        println!("{}", entry);
+    }
+    println!();
+    println!("Fills: {}  Rejections: {}  Profit: ${:.2}",
+             transcript.fills(), transcript.rejections(), transcript.profit_cents() as f64 / 100.0);
+    println!("PolyYesKalshiNo deployed: ${:.2} of ${:.2}",
+             allocator.deployed("PolyYesKalshiNo"), allocator.pattern_budget("PolyYesKalshiNo"));
+}
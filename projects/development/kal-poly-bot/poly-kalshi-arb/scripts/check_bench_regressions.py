@@ -0,0 +1,83 @@
+#!/usr/bin/env python3
+"""
+Compare criterion results against benches/baseline.json.
+
+    cargo bench --bench hot_paths
+    python3 scripts/check_bench_regressions.py            # exit 1 on regression, or a missing / unrecorded entry
+    python3 scripts/check_bench_regressions.py --update   # record current means as the baseline
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+BASELINE_FILE = ROOT / "benches" / "baseline.json"
+CRITERION_DIR = ROOT / "target" / "criterion"
+
+
+def current_mean_ns(criterion_dir: Path, bench_id: str):
+    estimates = criterion_dir / bench_id / "new" / "estimates.json"
+    if not estimates.exists():
+        return None
+    with open(estimates) as f:
+        return json.load(f)["mean"]["point_estimate"]
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__, formatter_class=argparse.RawDescriptionHelpFormatter)
+    parser.add_argument("--baseline", type=Path, default=BASELINE_FILE)
+    parser.add_argument("--criterion-dir", type=Path, default=CRITERION_DIR)
+    parser.add_argument("--tolerance", type=float, help="allowed slowdown (0.10 = 10%%); defaults to the baseline file's")
+    parser.add_argument("--update", action="store_true", help="write current means into the baseline file")
+    args = parser.parse_args()
+
+    with open(args.baseline) as f:
+        baseline = json.load(f)
+    tolerance = args.tolerance if args.tolerance is not None else baseline.get("tolerance", 0.10)
+
+    regressions, missing, unrecorded = [], [], []
+    for bench_id, recorded in baseline["benchmarks"].items():
+        current = current_mean_ns(args.criterion_dir, bench_id)
+        if current is None:
+            missing.append(bench_id)
+            print(f"  MISSING   {bench_id}: no criterion results (run cargo bench first)")
+            continue
+
+        if args.update:
+            baseline["benchmarks"][bench_id] = round(current, 1)
+            print(f"  RECORDED  {bench_id}: {current:,.1f} ns")
+            continue
+
+        if recorded is None:
+            unrecorded.append(bench_id)
+            print(f"  UNSET     {bench_id}: {current:,.1f} ns (no baseline recorded; run with --update)")
+            continue
+
+        change = (current - recorded) / recorded
+        status = "REGRESSED" if change > tolerance else "ok"
+        print(f"  {status:<9} {bench_id}: {recorded:,.1f} -> {current:,.1f} ns ({change:+.1%})")
+        if change > tolerance:
+            regressions.append(bench_id)
+
+    if args.update:
+        if missing:
+            print(f"Not updating baseline: {len(missing)} benchmark(s) have no results")
+            return 1
+        with open(args.baseline, "w") as f:
+            json.dump(baseline, f, indent=2)
+            f.write("\n")
+        print(f"Baseline written to {args.baseline}")
+        return 0
+
+    if regressions or missing or unrecorded:
+        print(f"{len(regressions)} regression(s) beyond {tolerance:.0%}, {len(missing)} missing, "
+              f"{len(unrecorded)} without a baseline")
+        return 1
+    print(f"No regressions beyond {tolerance:.0%}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,384 @@
+// src/account_manager.rs
+// Venue accounts - per-account limits, sharp-score estimates and health, with order routing across them
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::{AccountRule, AccountsSection};
+use crate::config_reload::ConfigChanged;
+use crate::error::{RiskRejection, VenueApiError};
+use crate::position_tracker::PositionTracker;
+
+/// How often open exposure is recounted from the tracker
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+/// Consecutive venue refusals before an account is treated as limited
+const REJECTION_LIMIT: u32 = 3;
+/// Weight of each fill in the sharp-score average
+const SHARP_ALPHA: f64 = 0.05;
+/// Edge per contract (cents) a venue sees as fully sharp
+const SHARP_EDGE_CENTS: f64 = 10.0;
+/// Venues that get a default account when none are configured
+const DEFAULT_VENUES: [&str; 2] = ["kalshi", "polymarket"];
+
+/// How an order picks among a venue's accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingPolicy {
+    /// Rotate through accounts so fills spread evenly
+    RoundRobin,
+    /// Send to the account the venue is least likely to have profiled
+    LowestSharpScore,
+}
+
+impl RoutingPolicy {
+    /// `accounts.routing` (validated by the config)
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "lowest_sharp_score" => RoutingPolicy::LowestSharpScore,
+            _ => RoutingPolicy::RoundRobin,
+        }
+    }
+}
+
+/// Whether an account takes orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum AccountHealth {
+    Healthy,
+    /// Refusing orders, flagged by the venue or sharp enough to be next: routed last
+    Limited,
+    /// Parked in config: never routed
+    Suspended,
+}
+
+/// One account's limits and state, as reported to the dashboard and status probe
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccountStatus {
+    pub id: String,
+    pub venue: String,
+    pub health: AccountHealth,
+    /// 0 = looks recreational, 1 = looks like a sharp to the venue
+    pub sharp_score: f64,
+    /// Dollars in open positions
+    pub exposure: f64,
+    pub max_exposure: f64,
+    pub max_order: f64,
+    pub fills: u64,
+    /// Consecutive venue refusals
+    pub rejections: u32,
+}
+
+struct Account {
+    rule: AccountRule,
+    sharp_score: f64,
+    fills: u64,
+    rejections: u32,
+    /// Venue has cut the account's stakes
+    limited: bool,
+    exposure: f64,
+}
+
+impl Account {
+    fn new(rule: AccountRule) -> Self {
+        Self { rule, sharp_score: 0.0, fills: 0, rejections: 0, limited: false, exposure: 0.0 }
+    }
+
+    fn health(&self, sharp_limit: f64) -> AccountHealth {
+        if self.rule.suspended {
+            AccountHealth::Suspended
+        } else if self.limited || self.rejections >= REJECTION_LIMIT || self.sharp_score >= sharp_limit {
+            AccountHealth::Limited
+        } else {
+            AccountHealth::Healthy
+        }
+    }
+
+    /// Dollars the account can take in one order (None = unlimited)
+    fn capacity(&self) -> Option<f64> {
+        let exposure = (self.rule.max_exposure > 0.0).then(|| (self.rule.max_exposure - self.exposure).max(0.0));
+        let order = (self.rule.max_order > 0.0).then_some(self.rule.max_order);
+        match (exposure, order) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// Whether a venue error is the venue refusing the account (not the network or our rate)
+pub fn is_account_refusal(error: &VenueApiError) -> bool {
+    matches!(error,
+        VenueApiError::Unauthorized { .. }
+        | VenueApiError::InvalidOrder { .. }
+        | VenueApiError::Http { status: 400..=499, .. })
+}
+
+/// `[accounts]` - the venue accounts executors route orders through. With none listed each
+/// venue has one unlimited account named after it, on the unsuffixed credentials.
+pub struct AccountManager {
+    accounts: RwLock<BTreeMap<String, Account>>,
+    policy: RwLock<(RoutingPolicy, f64)>,
+    /// Next round-robin position per venue
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+pub type SharedAccountManager = Arc<AccountManager>;
+
+impl AccountManager {
+    pub fn new(section: &AccountsSection) -> Self {
+        let accounts = if section.list.is_empty() {
+            DEFAULT_VENUES.iter()
+                .map(|venue| (venue.to_string(), Account::new(AccountRule { venue: venue.to_string(), ..Default::default() })))
+                .collect()
+        } else {
+            section.list.iter().map(|(id, rule)| (id.clone(), Account::new(rule.clone()))).collect()
+        };
+        Self {
+            accounts: RwLock::new(accounts),
+            policy: RwLock::new((RoutingPolicy::parse(&section.routing), section.sharp_limit)),
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Apply a hot-reloaded `[accounts]` section. Limits and routing change in place; accounts
+    /// added or removed need a restart (their venue clients are built at startup).
+    pub fn apply_config(&self, section: &AccountsSection) {
+        *self.policy.write().unwrap() = (RoutingPolicy::parse(&section.routing), section.sharp_limit);
+        let mut accounts = self.accounts.write().unwrap();
+        for (id, rule) in &section.list {
+            match accounts.get_mut(id) {
+                Some(account) => account.rule = rule.clone(),
+                None => warn!("[ACCOUNTS] {} added in config; restart to route to it", id),
+            }
+        }
+        info!("[ACCOUNTS] Config: routing={} sharp_limit={:.2}", section.routing, section.sharp_limit);
+    }
+
+    /// Accounts configured on `venue`
+    pub fn ids(&self, venue: &str) -> Vec<String> {
+        self.accounts.read().unwrap().iter()
+            .filter(|(_, a)| a.rule.venue == venue)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    pub fn status(&self) -> Vec<AccountStatus> {
+        let sharp_limit = self.policy.read().unwrap().1;
+        self.accounts.read().unwrap().iter()
+            .map(|(id, a)| AccountStatus {
+                id: id.clone(),
+                venue: a.rule.venue.clone(),
+                health: a.health(sharp_limit),
+                sharp_score: a.sharp_score,
+                exposure: a.exposure,
+                max_exposure: a.rule.max_exposure,
+                max_order: a.rule.max_order,
+                fills: a.fills,
+                rejections: a.rejections,
+            })
+            .collect()
+    }
+
+    /// Pick a `venue` account for up to `wanted` contracts at `cost_per_contract` dollars and cap
+    /// the count to what it can take. Healthy accounts go before limited ones; the routing
+    /// policy orders the rest.
+    pub fn route(&self, venue: &str, cost_per_contract: f64, wanted: i64) -> Result<(String, i64), RiskRejection> {
+        let (policy, sharp_limit) = *self.policy.read().unwrap();
+        let accounts = self.accounts.read().unwrap();
+        let on_venue: Vec<(&String, &Account)> = accounts.iter().filter(|(_, a)| a.rule.venue == venue).collect();
+        let n = on_venue.len().max(1);
+        let candidates: Vec<(usize, &String, &Account, i64)> = on_venue.into_iter()
+            .enumerate()
+            .filter(|(_, (_, a))| a.health(sharp_limit) != AccountHealth::Suspended)
+            .filter_map(|(i, (id, a))| {
+                let fits = match a.capacity() {
+                    Some(dollars) if cost_per_contract > 0.0 => (dollars / cost_per_contract).floor() as i64,
+                    _ => wanted,
+                };
+                (fits >= 1).then_some((i, id, a, wanted.min(fits)))
+            })
+            .collect();
+
+        let mut cursors = self.cursors.lock().unwrap();
+        let cursor = cursors.entry(venue.to_string()).or_insert(0);
+        let picked = candidates.iter().min_by(|x, y| {
+            let by_health = x.2.health(sharp_limit).cmp(&y.2.health(sharp_limit));
+            by_health.then_with(|| match policy {
+                RoutingPolicy::RoundRobin => ((x.0 + n - *cursor) % n).cmp(&((y.0 + n - *cursor) % n)),
+                RoutingPolicy::LowestSharpScore => x.2.sharp_score.total_cmp(&y.2.sharp_score),
+            })
+        });
+        match picked {
+            Some(&(i, id, _, contracts)) => {
+                *cursor = i + 1;
+                debug!("[ACCOUNTS] {} x{} -> {}", venue, contracts, id);
+                Ok((id.clone(), contracts))
+            }
+            None => Err(RiskRejection::NoAccount { venue: venue.to_string(), dollars: cost_per_contract * wanted as f64 }),
+        }
+    }
+
+    /// Count `dollars` opened on an account (until the next sync)
+    pub fn open(&self, id: &str, dollars: f64) {
+        if let Some(account) = self.accounts.write().unwrap().get_mut(id) {
+            account.exposure += dollars;
+        }
+    }
+
+    /// A filled order: edge per contract (cents) moves the sharp score; clears refusals
+    pub fn record_fill(&self, id: &str, edge_cents: f64) {
+        let mut accounts = self.accounts.write().unwrap();
+        let Some(account) = accounts.get_mut(id) else { return };
+        let sharpness = (edge_cents / SHARP_EDGE_CENTS).clamp(0.0, 1.0);
+        account.sharp_score += SHARP_ALPHA * (sharpness - account.sharp_score);
+        account.fills += 1;
+        account.rejections = 0;
+    }
+
+    /// The venue refused an order on the account
+    pub fn record_rejection(&self, id: &str) {
+        let mut accounts = self.accounts.write().unwrap();
+        let Some(account) = accounts.get_mut(id) else { return };
+        account.rejections += 1;
+        if account.rejections == REJECTION_LIMIT {
+            warn!("[ACCOUNTS] {} refused {} orders in a row; routing it last", id, REJECTION_LIMIT);
+        }
+    }
+
+    /// The venue cut (or restored) the account's stakes
+    pub fn set_limited(&self, id: &str, limited: bool) {
+        if let Some(account) = self.accounts.write().unwrap().get_mut(id) {
+            if account.limited != limited {
+                warn!("[ACCOUNTS] {} {}", id, if limited { "limited by venue" } else { "limit lifted" });
+            }
+            account.limited = limited;
+        }
+    }
+
+    /// Recount open exposure per account from the tracker's lots
+    pub fn sync(&self, tracker: &PositionTracker) {
+        let mut exposure: HashMap<&str, f64> = HashMap::new();
+        for position in tracker.open_positions() {
+            let legs = [
+                ("kalshi", &position.kalshi_yes), ("kalshi", &position.kalshi_no),
+                ("polymarket", &position.poly_yes), ("polymarket", &position.poly_no),
+            ];
+            for (venue, leg) in legs {
+                for lot in &leg.lots {
+                    *exposure.entry(lot.account.as_deref().unwrap_or(venue)).or_default() += lot.contracts * lot.price;
+                }
+            }
+        }
+        for (id, account) in self.accounts.write().unwrap().iter_mut() {
+            account.exposure = exposure.get(id.as_str()).copied().unwrap_or(0.0);
+            debug!("[ACCOUNTS]   {} exposure=${:.2}", id, account.exposure);
+        }
+    }
+}
+
+/// Recount exposure now and then every minute, so settled positions free account capacity
+pub async fn run_account_sync_loop(accounts: SharedAccountManager, tracker: Arc<tokio::sync::RwLock<PositionTracker>>) {
+    loop {
+        accounts.sync(&*tracker.read().await);
+        tokio::time::sleep(SYNC_INTERVAL).await;
+    }
+}
+
+/// Keep limits and routing in step with `[accounts]` reloads
+pub fn watch_config(accounts: SharedAccountManager, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("accounts") => accounts.apply_config(&change.config.accounts),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[ACCOUNTS] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position_tracker::FillRecord;
+
+    fn section(routing: &str, accounts: &[(&str, &str, f64)]) -> AccountsSection {
+        AccountsSection {
+            routing: routing.to_string(),
+            list: accounts.iter()
+                .map(|&(id, venue, max_exposure)| {
+                    (id.to_string(), AccountRule { venue: venue.to_string(), max_exposure, ..Default::default() })
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_robin_spreads_and_caps_to_capacity() {
+        let accounts = AccountManager::new(&section("round_robin", &[
+            ("k1", "kalshi", 0.0), ("k2", "kalshi", 10.0), ("p1", "polymarket", 0.0),
+        ]));
+        assert_eq!(accounts.route("kalshi", 0.5, 40).unwrap(), ("k1".to_string(), 40));
+        // $10 left at 50¢ a contract takes 20 of the 40
+        assert_eq!(accounts.route("kalshi", 0.5, 40).unwrap(), ("k2".to_string(), 20));
+        assert_eq!(accounts.route("kalshi", 0.5, 40).unwrap().0, "k1");
+
+        accounts.open("k2", 10.0);
+        assert_eq!(accounts.route("kalshi", 0.5, 40).unwrap().0, "k1");
+        assert_eq!(accounts.route("kalshi", 0.5, 40).unwrap().0, "k1");
+        assert_eq!(accounts.route("polymarket", 0.5, 40).unwrap().0, "p1");
+
+        let mut parked = section("round_robin", &[("k1", "kalshi", 0.0)]);
+        parked.list.get_mut("k1").unwrap().suspended = true;
+        accounts.apply_config(&parked);
+        assert!(matches!(accounts.route("kalshi", 0.5, 40), Err(RiskRejection::NoAccount { .. })));
+    }
+
+    #[test]
+    fn test_sharp_and_refused_accounts_route_last() {
+        let accounts = AccountManager::new(&section("lowest_sharp_score", &[
+            ("k1", "kalshi", 0.0), ("k2", "kalshi", 0.0), ("k3", "kalshi", 0.0),
+        ]));
+        // Big edges make k1 look sharp; k2 picks up a little
+        for _ in 0..10 {
+            accounts.record_fill("k1", 10.0);
+        }
+        accounts.record_fill("k2", 2.0);
+        assert_eq!(accounts.route("kalshi", 0.5, 10).unwrap().0, "k3");
+
+        for _ in 0..REJECTION_LIMIT {
+            accounts.record_rejection("k3");
+        }
+        let status = accounts.status();
+        assert_eq!(status[2].health, AccountHealth::Limited);
+        assert_eq!(accounts.route("kalshi", 0.5, 10).unwrap().0, "k2");
+
+        // A fill clears the refusals; a venue limit benches the account again
+        accounts.record_fill("k3", 0.0);
+        assert_eq!(accounts.route("kalshi", 0.5, 10).unwrap().0, "k3");
+        accounts.set_limited("k3", true);
+        assert_eq!(accounts.route("kalshi", 0.5, 10).unwrap().0, "k2");
+    }
+
+    #[test]
+    fn test_default_accounts_and_exposure_sync() {
+        let accounts = AccountManager::new(&AccountsSection::default());
+        assert_eq!(accounts.ids("kalshi"), vec!["kalshi".to_string()]);
+
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&FillRecord::new("A", "a", "kalshi", "yes", 10.0, 0.45, 0.0, "o1"));
+        tracker.record_fill_internal(&FillRecord::new("A", "a", "polymarket", "no", 10.0, 0.50, 0.0, "o2").with_account("p2"));
+        let accounts = AccountManager::new(&section("round_robin", &[("kalshi", "kalshi", 0.0), ("p2", "polymarket", 8.0)]));
+        accounts.sync(&tracker);
+        let status = accounts.status();
+        assert!((status[0].exposure - 4.5).abs() < 1e-9);
+        assert!((status[1].exposure - 5.0).abs() < 1e-9);
+        // $3 left at 50¢ is 6 contracts
+        assert_eq!(accounts.route("polymarket", 0.5, 10).unwrap(), ("p2".to_string(), 6));
+    }
+}
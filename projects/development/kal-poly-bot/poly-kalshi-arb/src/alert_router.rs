@@ -0,0 +1,210 @@
+// src/alert_router.rs
+// Alert router - single fan-in point for structured operational alerts
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+/// Alert severity (ordered: Info < Warning < Critical)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl std::fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertSeverity::Info => write!(f, "INFO"),
+            AlertSeverity::Warning => write!(f, "WARNING"),
+            AlertSeverity::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
+/// A structured alert from any subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    /// Emitting subsystem ("reconciler", "settlement", ...)
+    pub source: String,
+    pub severity: AlertSeverity,
+    /// Machine-readable kind ("missing_fill", "balance_drift", ...)
+    pub kind: String,
+    /// Market the alert concerns, if any
+    pub market_id: Option<String>,
+    /// Strategy the alert concerns, if any (`crate::strategy`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
+    pub message: String,
+    /// RFC3339 timestamp
+    pub timestamp: String,
+}
+
+impl Alert {
+    pub fn new(source: &str, severity: AlertSeverity, kind: &str, message: impl Into<String>) -> Self {
+        Self {
+            source: source.to_string(),
+            severity,
+            kind: kind.to_string(),
+            market_id: None,
+            strategy_id: None,
+            message: message.into(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn with_market(mut self, market_id: &str) -> Self {
+        self.market_id = Some(market_id.to_string());
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy_id: &str) -> Self {
+        self.strategy_id = Some(strategy_id.to_string());
+        self
+    }
+
+    /// Key used to suppress repeats of the same condition
+    fn dedup_key(&self) -> String {
+        format!("{}:{}:{}:{}", self.source, self.kind, self.market_id.as_deref().unwrap_or(""),
+                self.strategy_id.as_deref().unwrap_or(""))
+    }
+}
+
+/// Destination for routed alerts
+pub trait AlertSink: Send + Sync {
+    fn deliver(&self, alert: &Alert);
+}
+
+/// Writes alerts to the tracing log at a matching level
+pub struct LogSink;
+
+impl AlertSink for LogSink {
+    fn deliver(&self, alert: &Alert) {
+        match alert.severity {
+            AlertSeverity::Info => info!("[ALERT] {} {}/{}: {}", alert.severity, alert.source, alert.kind, alert.message),
+            AlertSeverity::Warning => warn!("[ALERT] {} {}/{}: {}", alert.severity, alert.source, alert.kind, alert.message),
+            AlertSeverity::Critical => error!("[ALERT] {} {}/{}: {}", alert.severity, alert.source, alert.kind, alert.message),
+        }
+    }
+}
+
+/// Forwards alerts to an async consumer (dashboard, pager bridge)
+pub struct ChannelSink {
+    tx: mpsc::UnboundedSender<Alert>,
+}
+
+impl ChannelSink {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<Alert>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+}
+
+impl AlertSink for ChannelSink {
+    fn deliver(&self, alert: &Alert) {
+        let _ = self.tx.send(alert.clone());
+    }
+}
+
+/// Routes alerts to sinks by minimum severity, suppressing repeats within a window
+pub struct AlertRouter {
+    sinks: Vec<(AlertSeverity, Arc<dyn AlertSink>)>,
+    dedup_window: Duration,
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl Default for AlertRouter {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300))
+    }
+}
+
+impl AlertRouter {
+    pub fn new(dedup_window: Duration) -> Self {
+        Self {
+            sinks: Vec::new(),
+            dedup_window,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Router with a log sink for everything
+    pub fn with_logging() -> Self {
+        let mut router = Self::default();
+        router.add_sink(AlertSeverity::Info, Arc::new(LogSink));
+        router
+    }
+
+    /// Register a sink receiving alerts at or above `min_severity`
+    pub fn add_sink(&mut self, min_severity: AlertSeverity, sink: Arc<dyn AlertSink>) {
+        self.sinks.push((min_severity, sink));
+    }
+
+    /// Route an alert; returns false if it was suppressed as a repeat
+    pub fn route(&self, alert: Alert) -> bool {
+        let key = alert.dedup_key();
+        {
+            let mut last_sent = self.last_sent.lock().unwrap();
+            let now = Instant::now();
+            if let Some(prev) = last_sent.get(&key) {
+                // Critical alerts escalate immediately, everything else respects the window
+                if now.duration_since(*prev) < self.dedup_window && alert.severity < AlertSeverity::Critical {
+                    return false;
+                }
+            }
+            last_sent.insert(key, now);
+            last_sent.retain(|_, t| now.duration_since(*t) < self.dedup_window);
+        }
+
+        for (min_severity, sink) in &self.sinks {
+            if alert.severity >= *min_severity {
+                sink.deliver(&alert);
+            }
+        }
+        true
+    }
+}
+
+pub type SharedAlertRouter = Arc<AlertRouter>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_by_severity() {
+        let (sink, mut rx) = ChannelSink::new();
+        let mut router = AlertRouter::new(Duration::from_secs(60));
+        router.add_sink(AlertSeverity::Warning, Arc::new(sink));
+
+        assert!(router.route(Alert::new("test", AlertSeverity::Info, "noise", "ignored")));
+        assert!(router.route(Alert::new("test", AlertSeverity::Warning, "drift", "delivered")));
+
+        let got = rx.try_recv().unwrap();
+        assert_eq!(got.kind, "drift");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let (sink, mut rx) = ChannelSink::new();
+        let mut router = AlertRouter::new(Duration::from_secs(60));
+        router.add_sink(AlertSeverity::Info, Arc::new(sink));
+
+        let alert = Alert::new("recon", AlertSeverity::Warning, "missing_fill", "x").with_market("M1");
+        assert!(router.route(alert.clone()));
+        assert!(!router.route(alert.clone()));
+        // Different market or strategy is a different condition
+        assert!(router.route(alert.clone().with_strategy("pattern_73")));
+        assert!(router.route(alert.with_market("M2")));
+        // Critical always goes through
+        let critical = Alert::new("recon", AlertSeverity::Critical, "missing_fill", "x").with_market("M1");
+        assert!(router.route(critical));
+
+        assert_eq!(std::iter::from_fn(|| rx.try_recv().ok()).count(), 4);
+    }
+}
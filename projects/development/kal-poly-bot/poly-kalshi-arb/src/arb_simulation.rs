@@ -0,0 +1,433 @@
+// src/arb_simulation.rs
+// End-to-end arb simulation: synthetic venue ticks -> detection -> risk -> simulated fills, with a decision transcript
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::capital_allocator::SharedCapitalAllocator;
+use crate::circuit_breaker::{CircuitBreakerConfig, TradingCircuitBreaker};
+use crate::clock::{Clock, MockClock, SharedClock};
+use crate::config::RiskSection;
+use crate::error::{ExecutionError, RiskRejection};
+use crate::event_phase::SharedPhaseTracker;
+use crate::provider_registry::ProviderCapabilities;
+use crate::types::{
+    ArbType, AtomicMarketState, FastExecutionRequest, GlobalState, MarketId, MarketPair, MarketType,
+    Nanos, Platform, Price, PriceCents, Size, SizeCents, NO_PRICE,
+};
+
+/// Wall time the simulation starts at (tick offsets count from here)
+pub const SIM_START: Nanos = Nanos(1_700_000_000_000_000_000);
+
+/// One synthetic venue quote
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimTick {
+    /// Offset from the simulation start
+    pub at_ms: u64,
+    /// `pair_id` of a market added with [`ArbSimulation::with_market`]
+    pub market: String,
+    /// Kalshi or Polymarket
+    pub platform: Platform,
+    pub yes_ask: PriceCents,
+    pub no_ask: PriceCents,
+    pub yes_size: SizeCents,
+    pub no_size: SizeCents,
+}
+
+impl SimTick {
+    /// Quote with $10 resting on each side
+    pub fn new(at_ms: u64, market: &str, platform: Platform, yes_ask: PriceCents, no_ask: PriceCents) -> Self {
+        Self { at_ms, market: market.to_string(), platform, yes_ask, no_ask, yes_size: 1000, no_size: 1000 }
+    }
+
+    pub fn with_sizes(mut self, yes_size: SizeCents, no_size: SizeCents) -> Self {
+        self.yes_size = yes_size;
+        self.no_size = no_size;
+        self
+    }
+}
+
+/// What the pipeline did with an opportunity
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum Decision {
+    /// Tick crossed the threshold
+    Detected { arb_type: ArbType, yes_price: PriceCents, no_price: PriceCents, profit_cents: i16 },
+    /// Stopped before execution (liquidity, phase, budget or breaker)
+    Rejected { arb_type: ArbType, reason: String },
+    /// Both legs filled after the execution latency
+    Filled { arb_type: ArbType, contracts: i64, cost_cents: i64, profit_cents: i64, latency_ns: u64 },
+    /// Book moved against a leg before the orders landed
+    Missed { arb_type: ArbType, yes_price: PriceCents, no_price: PriceCents },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptEntry {
+    /// Wall-clock ns
+    pub at_ns: u64,
+    pub market: String,
+    #[serde(flatten)]
+    pub decision: Decision,
+}
+
+impl fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = self.at_ns.saturating_sub(SIM_START.0) as f64 / 1e6;
+        write!(f, "{:>10.3}ms {:<12} ", ms, self.market)?;
+        match &self.decision {
+            Decision::Detected { arb_type, yes_price, no_price, profit_cents } => {
+                write!(f, "DETECTED {:?} y={}¢ n={}¢ edge={}¢", arb_type, yes_price, no_price, profit_cents)
+            }
+            Decision::Rejected { arb_type, reason } => write!(f, "REJECTED {:?}: {}", arb_type, reason),
+            Decision::Filled { arb_type, contracts, cost_cents, profit_cents, latency_ns } => write!(
+                f, "FILLED   {:?} {}x cost={}¢ profit={}¢ after {}µs",
+                arb_type, contracts, cost_cents, profit_cents, latency_ns / 1_000
+            ),
+            Decision::Missed { arb_type, yes_price, no_price } => {
+                write!(f, "MISSED   {:?} book moved to y={}¢ n={}¢", arb_type, yes_price, no_price)
+            }
+        }
+    }
+}
+
+/// Decisions in the order they were made
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Transcript {
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    pub fn decisions(&self, market: &str) -> Vec<&Decision> {
+        self.entries.iter().filter(|e| e.market == market).map(|e| &e.decision).collect()
+    }
+
+    pub fn fills(&self) -> usize {
+        self.entries.iter().filter(|e| matches!(e.decision, Decision::Filled { .. })).count()
+    }
+
+    pub fn rejections(&self) -> usize {
+        self.entries.iter().filter(|e| matches!(e.decision, Decision::Rejected { .. })).count()
+    }
+
+    /// Net simulated profit over all fills
+    pub fn profit_cents(&self) -> i64 {
+        self.entries.iter()
+            .map(|e| match e.decision {
+                Decision::Filled { profit_cents, .. } => profit_cents,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+/// Detected request waiting out its execution latency
+struct Pending {
+    due: Nanos,
+    market: String,
+    req: FastExecutionRequest,
+    contracts: i64,
+}
+
+/// Runs synthetic ticks through the same detection (`check_arbs`), risk checks
+/// (phase gates, pattern budgets, trading breaker) and sizing as the live
+/// engine, filling against the book as it stands once the execution latency
+/// has passed. Time comes from a [`MockClock`] driven by the ticks.
+pub struct ArbSimulation {
+    state: GlobalState,
+    markets: HashMap<String, MarketId>,
+    clock: Arc<MockClock>,
+    threshold_cents: PriceCents,
+    circuit_breaker: Arc<TradingCircuitBreaker>,
+    phases: Option<SharedPhaseTracker>,
+    allocator: Option<SharedCapitalAllocator>,
+    latency: Option<Duration>,
+}
+
+impl ArbSimulation {
+    pub fn new(threshold_cents: PriceCents) -> Self {
+        Self {
+            state: GlobalState::new(),
+            markets: HashMap::new(),
+            clock: MockClock::shared(SIM_START),
+            threshold_cents,
+            circuit_breaker: Arc::new(TradingCircuitBreaker::new(CircuitBreakerConfig::from(&RiskSection::default()))),
+            phases: None,
+            allocator: None,
+            latency: None,
+        }
+    }
+
+    /// Track a moneyline market; its Kalshi ticker is `pair_id` (phase tracker key)
+    pub fn with_market(mut self, pair_id: &str, league: &str) -> Self {
+        let pair = MarketPair {
+            pair_id: pair_id.into(),
+            league: league.into(),
+            market_type: MarketType::Moneyline,
+            description: pair_id.into(),
+            kalshi_event_ticker: pair_id.into(),
+            kalshi_market_ticker: pair_id.into(),
+            poly_slug: pair_id.to_lowercase().into(),
+            poly_yes_token: format!("{}-yes", pair_id).into(),
+            poly_no_token: format!("{}-no", pair_id).into(),
+            line_value: None,
+            team_suffix: None,
+        };
+        if let Some(id) = self.state.add_pair(pair) {
+            self.markets.insert(pair_id.to_string(), id);
+        }
+        self
+    }
+
+    /// Trading breaker limits (defaults to `[risk]` defaults)
+    pub fn with_risk(mut self, risk: &RiskSection) -> Self {
+        self.circuit_breaker = Arc::new(TradingCircuitBreaker::new(CircuitBreakerConfig::from(risk)));
+        self
+    }
+
+    /// Gate on event phases; build the tracker on [`ArbSimulation::clock`]
+    pub fn with_phase_tracker(mut self, phases: SharedPhaseTracker) -> Self {
+        self.phases = Some(phases);
+        self
+    }
+
+    pub fn with_capital_allocator(mut self, allocator: SharedCapitalAllocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Fixed execution latency (default: the slower leg venue's nominal latency)
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Simulation clock, for components that should share its time
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    pub fn circuit_breaker(&self) -> &Arc<TradingCircuitBreaker> {
+        &self.circuit_breaker
+    }
+
+    /// Feed ticks in order; executions still in flight after the last tick settle against the final books
+    pub async fn run(&self, ticks: impl IntoIterator<Item = SimTick>) -> Transcript {
+        let mut transcript = Transcript::default();
+        let mut pending = Vec::new();
+
+        for tick in ticks {
+            let at = Nanos(SIM_START.0 + tick.at_ms * 1_000_000);
+            self.settle(&mut pending, at, &mut transcript).await;
+            self.advance_to(at);
+
+            let Some(&market_id) = self.markets.get(&tick.market) else { continue };
+            let Some(market) = self.state.get_by_id(market_id) else { continue };
+            let book = match tick.platform {
+                Platform::Kalshi => &market.kalshi,
+                Platform::Polymarket => &market.poly,
+                _ => continue,
+            };
+            book.store(tick.yes_ask, tick.no_ask, tick.yes_size, tick.no_size);
+
+            // One execution per market at a time (the engine's in-flight bit)
+            if pending.iter().any(|p: &Pending| p.market == tick.market) {
+                continue;
+            }
+            if let Some(p) = self.detect(&tick.market, market, &mut transcript).await {
+                pending.push(p);
+            }
+        }
+
+        self.settle(&mut pending, Nanos(u64::MAX), &mut transcript).await;
+        transcript
+    }
+
+    fn advance_to(&self, wall: Nanos) {
+        if wall > self.clock.wall_ns() {
+            self.clock.set_wall(wall);
+        }
+    }
+
+    fn record(&self, transcript: &mut Transcript, market: &str, decision: Decision) {
+        transcript.entries.push(TranscriptEntry { at_ns: self.clock.wall_ns().0, market: market.to_string(), decision });
+    }
+
+    async fn detect(&self, name: &str, market: &AtomicMarketState, transcript: &mut Transcript) -> Option<Pending> {
+        let req = arb_request(market, market.check_arbs(self.threshold_cents), self.clock.wall_ns())?;
+        let arb_type = req.arb_type;
+        self.record(transcript, name, Decision::Detected {
+            arb_type,
+            yes_price: req.yes_price.cents(),
+            no_price: req.no_price.cents(),
+            profit_cents: req.profit_cents(),
+        });
+
+        match self.check_risk(name, &req).await {
+            Ok(contracts) => {
+                let latency = self.latency.unwrap_or_else(|| execution_latency(arb_type));
+                Some(Pending { due: Nanos(self.clock.wall_ns().0 + latency.as_nanos() as u64), market: name.to_string(), req, contracts })
+            }
+            Err(e) => {
+                self.record(transcript, name, Decision::Rejected { arb_type, reason: e.to_string() });
+                None
+            }
+        }
+    }
+
+    /// The engine's pre-trade checks, in its order; returns the contracts to send
+    async fn check_risk(&self, name: &str, req: &FastExecutionRequest) -> Result<i64, ExecutionError> {
+        let mut contracts = req.yes_size.min(req.no_size).contracts();
+        if contracts < 1 {
+            return Err(ExecutionError::InsufficientLiquidity { yes_size: req.yes_size, no_size: req.no_size });
+        }
+        if let Some(phases) = &self.phases {
+            phases.check(name)?;
+        }
+        if let Some(allocator) = &self.allocator {
+            let cost_per_contract = (req.yes_price.cents() + req.no_price.cents()) as f64 / 100.0;
+            contracts = allocator.cap_contracts(&format!("{:?}", req.arb_type), cost_per_contract, contracts)?;
+        }
+        self.circuit_breaker.can_execute(name, contracts).await.map_err(RiskRejection::from)?;
+        Ok(contracts)
+    }
+
+    /// Fill executions due by `until` against the current books
+    async fn settle(&self, pending: &mut Vec<Pending>, until: Nanos, transcript: &mut Transcript) {
+        pending.sort_by_key(|p| p.due);
+        while pending.first().is_some_and(|p| p.due <= until) {
+            let p = pending.remove(0);
+            self.advance_to(p.due);
+            let Some(market) = self.markets.get(&p.market).and_then(|&id| self.state.get_by_id(id)) else { continue };
+
+            let arb_type = p.req.arb_type;
+            let (yes_now, no_now, yes_size, no_size) = legs(market, arb_type);
+            let moved = yes_now == NO_PRICE || no_now == NO_PRICE
+                || yes_now > p.req.yes_price.cents() || no_now > p.req.no_price.cents();
+            let contracts = p.contracts.min(Size(yes_size).min(Size(no_size)).contracts());
+            if moved || contracts < 1 {
+                self.record(transcript, &p.market, Decision::Missed { arb_type, yes_price: yes_now, no_price: no_now });
+                continue;
+            }
+
+            let filled = FastExecutionRequest { yes_price: Price(yes_now), no_price: Price(no_now), ..p.req };
+            let cost_cents = contracts * (yes_now + no_now) as i64;
+            let profit_cents = contracts * filled.profit_cents() as i64;
+            self.circuit_breaker.record_success(&p.market, contracts, contracts, profit_cents as f64 / 100.0).await;
+            if let Some(allocator) = &self.allocator {
+                allocator.commit(&format!("{:?}", arb_type), cost_cents as f64 / 100.0);
+            }
+            self.record(transcript, &p.market, Decision::Filled {
+                arb_type,
+                contracts,
+                cost_cents,
+                profit_cents,
+                latency_ns: self.clock.wall_ns().saturating_sub(p.req.detected_ns).0,
+            });
+        }
+    }
+}
+
+/// (yes_ask, no_ask, yes_size, no_size) of the two legs an arb type buys
+fn legs(market: &AtomicMarketState, arb_type: ArbType) -> (PriceCents, PriceCents, SizeCents, SizeCents) {
+    let (k_yes, k_no, k_yes_size, k_no_size) = market.kalshi.load();
+    let (p_yes, p_no, p_yes_size, p_no_size) = market.poly.load();
+    match arb_type {
+        ArbType::PolyYesKalshiNo => (p_yes, k_no, p_yes_size, k_no_size),
+        ArbType::KalshiYesPolyNo => (k_yes, p_no, k_yes_size, p_no_size),
+        ArbType::PolyOnly => (p_yes, p_no, p_yes_size, p_no_size),
+        ArbType::KalshiOnly => (k_yes, k_no, k_yes_size, k_no_size),
+    }
+}
+
+/// Request for the best arb in `check_arbs`' mask, in the feeds' priority order
+fn arb_request(market: &AtomicMarketState, arb_mask: u8, detected_ns: Nanos) -> Option<FastExecutionRequest> {
+    let arb_type = [ArbType::PolyYesKalshiNo, ArbType::KalshiYesPolyNo, ArbType::PolyOnly, ArbType::KalshiOnly]
+        .into_iter()
+        .enumerate()
+        .find(|(bit, _)| arb_mask & (1 << bit) != 0)
+        .map(|(_, arb_type)| arb_type)?;
+    let (yes_price, no_price, yes_size, no_size) = legs(market, arb_type);
+    Some(FastExecutionRequest {
+        market_id: market.market_id,
+        yes_price: Price(yes_price),
+        no_price: Price(no_price),
+        yes_size: Size(yes_size),
+        no_size: Size(no_size),
+        arb_type,
+        detected_ns,
+    })
+}
+
+/// Orders go out in parallel, so the slower venue sets the latency
+fn execution_latency(arb_type: ArbType) -> Duration {
+    let venues: &[Platform] = match arb_type {
+        ArbType::PolyYesKalshiNo | ArbType::KalshiYesPolyNo => &[Platform::Kalshi, Platform::Polymarket],
+        ArbType::PolyOnly => &[Platform::Polymarket],
+        ArbType::KalshiOnly => &[Platform::Kalshi],
+    };
+    let ns = venues.iter().map(|&p| ProviderCapabilities::builtin(p).nominal_latency_ns).max().unwrap_or_default();
+    Duration::from_nanos(ns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sim() -> ArbSimulation {
+        ArbSimulation::new(100).with_market("EPL-ARS", "epl")
+    }
+
+    #[tokio::test]
+    async fn detects_and_fills_cross_venue_arb() {
+        let transcript = sim().run([
+            SimTick::new(0, "EPL-ARS", Platform::Kalshi, 55, 50),
+            // Poly YES 40 + Kalshi NO 50 (+2¢ fee) = 92¢
+            SimTick::new(5, "EPL-ARS", Platform::Polymarket, 40, 62),
+        ]).await;
+
+        let decisions = transcript.decisions("EPL-ARS");
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0], &Decision::Detected {
+            arb_type: ArbType::PolyYesKalshiNo, yes_price: 40, no_price: 50, profit_cents: 8,
+        });
+        // $10 per side = 10 contracts; slower leg is Polymarket at 75µs
+        assert_eq!(decisions[1], &Decision::Filled {
+            arb_type: ArbType::PolyYesKalshiNo, contracts: 10, cost_cents: 900, profit_cents: 80, latency_ns: 75_000,
+        });
+        assert_eq!((transcript.fills(), transcript.profit_cents()), (1, 80));
+    }
+
+    #[tokio::test]
+    async fn book_moving_inside_the_latency_misses() {
+        let transcript = sim().with_latency(Duration::from_millis(2)).run([
+            SimTick::new(0, "EPL-ARS", Platform::Kalshi, 55, 50),
+            SimTick::new(5, "EPL-ARS", Platform::Polymarket, 40, 62),
+            // Poly YES lifted before the orders land
+            SimTick::new(6, "EPL-ARS", Platform::Polymarket, 47, 55),
+        ]).await;
+
+        assert_eq!(transcript.fills(), 0);
+        assert!(matches!(
+            transcript.decisions("EPL-ARS").last(),
+            Some(Decision::Missed { yes_price: 47, no_price: 50, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn risk_limits_reject_before_execution() {
+        // Room for one 10-contract arb (both legs count)
+        let risk = RiskSection { max_position_per_market: 20, max_total_position: 20, ..Default::default() };
+        let transcript = sim().with_risk(&risk).run([
+            SimTick::new(0, "EPL-ARS", Platform::Kalshi, 55, 50),
+            SimTick::new(5, "EPL-ARS", Platform::Polymarket, 40, 62),
+            // Same edge again once the first fill is booked: the per-market limit is used up
+            SimTick::new(10, "EPL-ARS", Platform::Polymarket, 40, 61),
+        ]).await;
+
+        assert_eq!((transcript.fills(), transcript.rejections()), (1, 1));
+        assert!(matches!(transcript.decisions("EPL-ARS").last(), Some(Decision::Rejected { .. })));
+    }
+}
@@ -0,0 +1,509 @@
+// src/audit_log.rs
+// Append-only audit log of trading decisions (signals, risk decisions, order actions, config changes,
+// operator overrides and annotations)
+
+use crate::config::AuditSection;
+use crate::error::StateStoreError;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::journal::crc32;
+use crate::strategy;
+
+const SEGMENT_PREFIX: &str = "audit-";
+const SEGMENT_SUFFIX: &str = ".log";
+const NS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+
+/// Audit log configuration
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    /// One segment file per UTC day is written here
+    pub dir: PathBuf,
+    /// fsync after every append
+    pub fsync: bool,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self::from(&AuditSection::default())
+    }
+}
+
+impl From<&AuditSection> for AuditConfig {
+    fn from(a: &AuditSection) -> Self {
+        Self { dir: PathBuf::from(&a.dir), fsync: a.fsync }
+    }
+}
+
+/// What happened to an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderAction {
+    Submitted,
+    /// Dry run: would have been submitted
+    Simulated,
+    Filled,
+    /// Legs filled unevenly
+    PartiallyFilled,
+    Unfilled,
+    Failed,
+}
+
+/// An audited decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// Opportunity evaluated by a strategy, before risk
+    SignalConsidered {
+        strategy: String,
+        detail: String,
+        /// Cents for price signals, points for line signals
+        edge: f64,
+        confidence: Option<f64>,
+    },
+    RiskDecision {
+        approved: bool,
+        /// Rejection reason
+        reason: Option<String>,
+        contracts: Option<i64>,
+    },
+    Order {
+        action: OrderAction,
+        order_id: Option<String>,
+        contracts: Option<i64>,
+        detail: Option<String>,
+    },
+    ConfigChanged {
+        /// Reload version (None for runtime overrides)
+        version: Option<u64>,
+        sections: Vec<String>,
+        detail: Option<String>,
+    },
+    /// Decision on a signal held for operator review (see src/operator_review.rs)
+    Override {
+        signal_id: u64,
+        /// "approved", "denied" or "expired" (nobody decided within the window)
+        decision: String,
+        /// What automation would have done with the signal
+        automated: String,
+        executed: bool,
+        note: Option<String>,
+    },
+    /// Operator note on a signal
+    Annotation {
+        signal_id: u64,
+        note: String,
+    },
+}
+
+impl AuditEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuditEvent::SignalConsidered { .. } => "signal_considered",
+            AuditEvent::RiskDecision { .. } => "risk_decision",
+            AuditEvent::Order { .. } => "order",
+            AuditEvent::ConfigChanged { .. } => "config_changed",
+            AuditEvent::Override { .. } => "override",
+            AuditEvent::Annotation { .. } => "annotation",
+        }
+    }
+
+    pub fn approved(contracts: Option<i64>) -> Self {
+        AuditEvent::RiskDecision { approved: true, reason: None, contracts }
+    }
+
+    pub fn rejected(reason: impl ToString) -> Self {
+        AuditEvent::RiskDecision { approved: false, reason: Some(reason.to_string()), contracts: None }
+    }
+
+    pub fn order(action: OrderAction, order_id: Option<String>, contracts: Option<i64>, detail: Option<String>) -> Self {
+        AuditEvent::Order { action, order_id, contracts, detail }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub seq: u64,
+    /// Unix nanoseconds (what range queries filter on)
+    pub ts_ns: u64,
+    /// RFC3339, for humans
+    pub ts: String,
+    /// Component or operator that made the decision
+    pub actor: String,
+    pub market: Option<String>,
+    /// Strategy the decision was made for (older records have none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
+    pub event: AuditEvent,
+}
+
+impl AuditRecord {
+    /// The record's strategy, falling back to a considered signal's own for untagged records
+    pub fn strategy(&self) -> Option<&str> {
+        match (&self.strategy_id, &self.event) {
+            (Some(id), _) => Some(id),
+            (None, AuditEvent::SignalConsidered { strategy, .. }) => Some(strategy),
+            _ => None,
+        }
+    }
+}
+
+/// Filter for `AuditLog::query`; empty matches everything
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub market: Option<String>,
+    /// Inclusive Unix-nanosecond bounds
+    pub from_ns: Option<u64>,
+    pub to_ns: Option<u64>,
+    pub actor: Option<String>,
+    /// `AuditEvent::kind`
+    pub kind: Option<String>,
+    /// Any of these strategies (empty = all)
+    pub strategies: Vec<String>,
+    /// Earliest matches first, at most this many
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    pub fn market(market: impl Into<String>) -> Self {
+        Self { market: Some(market.into()), ..Self::default() }
+    }
+
+    pub fn between(mut self, from_ns: u64, to_ns: u64) -> Self {
+        self.from_ns = Some(from_ns);
+        self.to_ns = Some(to_ns);
+        self
+    }
+
+    /// Parse `market=..&from=..&to=..&actor=..&kind=..&strategy=..&limit=..` (strategy repeats);
+    /// times are Unix nanoseconds or RFC3339
+    pub fn from_query_string(query: &str) -> Result<Self, String> {
+        let mut out = Self::default();
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "market" => out.market = Some(value.to_string()),
+                "actor" => out.actor = Some(value.to_string()),
+                "kind" => out.kind = Some(value.to_string()),
+                "strategy" => out.strategies.push(value.to_string()),
+                "from" => out.from_ns = Some(parse_time(value)?),
+                "to" => out.to_ns = Some(parse_time(value)?),
+                "limit" => out.limit = Some(value.parse().map_err(|_| format!("bad limit '{}'", value))?),
+                _ => return Err(format!("unknown query parameter '{}'", key)),
+            }
+        }
+        Ok(out)
+    }
+
+    fn matches(&self, record: &AuditRecord) -> bool {
+        self.from_ns.is_none_or(|from| record.ts_ns >= from)
+            && self.to_ns.is_none_or(|to| record.ts_ns <= to)
+            && self.market.as_ref().is_none_or(|m| record.market.as_ref() == Some(m))
+            && self.actor.as_ref().is_none_or(|a| record.actor == *a)
+            && self.kind.as_ref().is_none_or(|k| record.event.kind() == k)
+            && strategy::matches(&self.strategies, record.strategy())
+    }
+
+    /// Whether a day segment can hold matches
+    fn covers_day(&self, day: u64) -> bool {
+        let (start, end) = (day * NS_PER_DAY, (day + 1) * NS_PER_DAY - 1);
+        self.from_ns.is_none_or(|from| end >= from) && self.to_ns.is_none_or(|to| start <= to)
+    }
+}
+
+pub(crate) fn parse_time(value: &str) -> Result<u64, String> {
+    if let Ok(ns) = value.parse::<u64>() {
+        return Ok(ns);
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|t| t.timestamp_nanos_opt())
+        .map(|ns| ns.max(0) as u64)
+        .ok_or_else(|| format!("bad time '{}' (Unix ns or RFC3339)", value))
+}
+
+struct Writer {
+    file: File,
+    day: u64,
+    next_seq: u64,
+}
+
+/// Append-only, one `<crc32 hex> <record json>` line per decision; segments
+/// rotate daily and are never rewritten
+pub struct AuditLog {
+    config: AuditConfig,
+    clock: SharedClock,
+    writer: Mutex<Writer>,
+}
+
+pub type SharedAuditLog = Arc<AuditLog>;
+
+impl AuditLog {
+    pub fn open(config: AuditConfig) -> Result<Self, StateStoreError> {
+        Self::open_with_clock(config, clock::system())
+    }
+
+    /// Open (or continue) the log; sequence numbers carry on from the newest segment
+    pub fn open_with_clock(config: AuditConfig, clock: SharedClock) -> Result<Self, StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+        let next_seq = match segments(&config.dir)?.last() {
+            Some((_, path)) => read_segment(path)?.last().map_or(1, |r| r.seq + 1),
+            None => 1,
+        };
+        let day = clock.wall_ns().0 / NS_PER_DAY;
+        let file = open_segment(&config.dir, day)?;
+        info!("[AUDIT] Logging to {} (next seq {})", config.dir.display(), next_seq);
+        Ok(Self {
+            writer: Mutex::new(Writer { file, day, next_seq }),
+            config,
+            clock,
+        })
+    }
+
+    /// Append a record; returns its sequence number
+    pub fn append(&self, actor: &str, market: Option<String>, event: AuditEvent) -> Result<u64, StateStoreError> {
+        self.write(None, actor, market, event)
+    }
+
+    /// Append a record made for `strategy_id`
+    pub fn append_for(&self, strategy_id: &str, actor: &str, market: Option<String>, event: AuditEvent) -> Result<u64, StateStoreError> {
+        self.write(Some(strategy_id), actor, market, event)
+    }
+
+    fn write(&self, strategy_id: Option<&str>, actor: &str, market: Option<String>, event: AuditEvent) -> Result<u64, StateStoreError> {
+        let ts_ns = self.clock.wall_ns().0;
+        let mut writer = self.writer.lock().unwrap();
+        let day = ts_ns / NS_PER_DAY;
+        if day != writer.day {
+            writer.file = open_segment(&self.config.dir, day)?;
+            writer.day = day;
+        }
+
+        let seq = writer.next_seq;
+        let ts = chrono::DateTime::from_timestamp_nanos(ts_ns as i64).to_rfc3339();
+        let record = AuditRecord {
+            seq,
+            ts_ns,
+            ts,
+            actor: actor.to_string(),
+            market,
+            strategy_id: strategy_id.map(str::to_string),
+            event,
+        };
+        let json = serde_json::to_string(&record)?;
+        writeln!(writer.file, "{:08x} {}", crc32(json.as_bytes()), json)?;
+        if self.config.fsync {
+            writer.file.sync_data()?;
+        }
+        writer.next_seq += 1;
+        Ok(seq)
+    }
+
+    /// Append, logging instead of failing (for hot paths)
+    pub fn record(&self, actor: &str, market: Option<String>, event: AuditEvent) {
+        if let Err(e) = self.append(actor, market, event) {
+            warn!("[AUDIT] Append failed: {}", e);
+        }
+    }
+
+    /// `append_for`, logging instead of failing (for hot paths)
+    pub fn record_for(&self, strategy_id: &str, actor: &str, market: Option<String>, event: AuditEvent) {
+        if let Err(e) = self.append_for(strategy_id, actor, market, event) {
+            warn!("[AUDIT] Append failed: {}", e);
+        }
+    }
+
+    /// Records matching `query`, in sequence order
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditRecord>, StateStoreError> {
+        // Hold the writer so a concurrent append can't be read half-written
+        let _writer = self.writer.lock().unwrap();
+        let mut out = Vec::new();
+        for (day, path) in segments(&self.config.dir)? {
+            if !query.covers_day(day) {
+                continue;
+            }
+            for record in read_segment(&path)? {
+                if query.matches(&record) {
+                    out.push(record);
+                    if query.limit.is_some_and(|limit| out.len() >= limit) {
+                        return Ok(out);
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Admin endpoint (mount with `Supervisor::add_route("/audit", ..)`):
+    /// `GET /audit?market=..&from=..&to=..&actor=..&kind=..&strategy=..&limit=..`
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        if method != "GET" {
+            return (405, r#"{"error":"method not allowed"}"#.to_string());
+        }
+        let (_, query) = path.split_once('?').unwrap_or((path, ""));
+        let query = match AuditQuery::from_query_string(query) {
+            Ok(query) => query,
+            Err(e) => return (400, serde_json::json!({ "error": e }).to_string()),
+        };
+        match self.query(&AuditQuery { limit: query.limit.or(Some(1000)), ..query }) {
+            Ok(records) => (200, serde_json::to_string_pretty(&records).unwrap_or_default()),
+            Err(e) => (500, serde_json::json!({ "error": e.to_string() }).to_string()),
+        }
+    }
+}
+
+fn segment_name(day: u64) -> String {
+    let date = chrono::DateTime::from_timestamp((day * 86_400) as i64, 0).unwrap_or_default();
+    format!("{}{}{}", SEGMENT_PREFIX, date.format("%Y-%m-%d"), SEGMENT_SUFFIX)
+}
+
+/// Open a day segment for appending; a torn last line is fenced off with a newline
+fn open_segment(dir: &Path, day: u64) -> Result<File, StateStoreError> {
+    let path = dir.join(segment_name(day));
+    let mut file = OpenOptions::new().create(true).read(true).append(true).open(&path)?;
+    let len = file.metadata()?.len();
+    if len > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::Start(len - 1))?;
+        file.read_exact(&mut last)?;
+        if last[0] != b'\n' {
+            warn!("[AUDIT] Torn record at end of {}, fencing", path.display());
+            file.write_all(b"\n")?;
+        }
+    }
+    Ok(file)
+}
+
+/// (UTC day number, path) of every segment, oldest first
+fn segments(dir: &Path) -> Result<Vec<(u64, PathBuf)>, StateStoreError> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let Some(date) = name.strip_prefix(SEGMENT_PREFIX).and_then(|n| n.strip_suffix(SEGMENT_SUFFIX)) else {
+            continue;
+        };
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            let secs = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp();
+            out.push((secs.max(0) as u64 / 86_400, path));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// Valid records of a segment; corrupt lines are skipped (and reported), not fatal
+fn read_segment(path: &Path) -> Result<Vec<AuditRecord>, StateStoreError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut out = Vec::new();
+    let mut corrupt = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        match parse_line(&line) {
+            Some(record) => out.push(record),
+            None => corrupt += 1,
+        }
+    }
+    if corrupt > 0 {
+        warn!("[AUDIT] Skipped {} corrupt records in {}", corrupt, path.display());
+    }
+    Ok(out)
+}
+
+fn parse_line(line: &str) -> Option<AuditRecord> {
+    let (crc_hex, json) = line.split_once(' ')?;
+    let crc = u32::from_str_radix(crc_hex, 16).ok()?;
+    if crc != crc32(json.as_bytes()) {
+        return None;
+    }
+    serde_json::from_str(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::types::Nanos;
+    use std::time::Duration;
+
+    fn test_config(name: &str) -> AuditConfig {
+        let dir = std::env::temp_dir().join(format!("audit_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        AuditConfig { dir, fsync: false }
+    }
+
+    /// 2024-01-01T23:00:00Z
+    const START: u64 = 1_704_150_000 * 1_000_000_000;
+
+    #[test]
+    fn test_append_query_and_daily_rotation() {
+        let config = test_config("query");
+        let clock = MockClock::shared(Nanos(START));
+        let log = AuditLog::open_with_clock(config.clone(), clock.clone()).unwrap();
+
+        log.append("strategy", Some("M1".into()), AuditEvent::SignalConsidered {
+            strategy: "s".into(), detail: "d".into(), edge: 3.0, confidence: None,
+        }).unwrap();
+        log.append_for("s", "risk", Some("M1".into()), AuditEvent::rejected("exposure limit")).unwrap();
+        clock.advance(Duration::from_secs(2 * 3600)); // next UTC day
+        log.append("risk", Some("M2".into()), AuditEvent::approved(Some(5))).unwrap();
+        log.append("operator", None, AuditEvent::ConfigChanged {
+            version: None, sections: vec!["features".into()], detail: Some("DEBUG on".into()),
+        }).unwrap();
+
+        assert_eq!(super::segments(&config.dir).unwrap().len(), 2);
+
+        let m1 = log.query(&AuditQuery::market("M1")).unwrap();
+        assert_eq!(m1.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(m1[1].event, AuditEvent::rejected("exposure limit"));
+
+        let next_day = START + 3600 * 1_000_000_000;
+        let later = log.query(&AuditQuery::default().between(next_day, u64::MAX)).unwrap();
+        assert_eq!(later.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![3, 4]);
+
+        let q = AuditQuery::from_query_string("kind=risk_decision&from=2024-01-01T00:00:00Z&limit=1").unwrap();
+        assert_eq!(log.query(&q).unwrap()[0].seq, 2);
+        assert!(AuditQuery::from_query_string("from=yesterday").is_err());
+
+        let (code, body) = log.handle_admin("GET", "/audit?actor=operator");
+        assert_eq!(code, 200);
+        assert_eq!(serde_json::from_str::<Vec<AuditRecord>>(&body).unwrap()[0].seq, 4);
+
+        // Tagged records, and untagged signals by their own strategy
+        let s = log.query(&AuditQuery::from_query_string("strategy=s").unwrap()).unwrap();
+        assert_eq!(s.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(s[1].strategy_id.as_deref(), Some("s"));
+        assert!(log.query(&AuditQuery::from_query_string("strategy=other").unwrap()).unwrap().is_empty());
+        assert_eq!(log.handle_admin("DELETE", "/audit").0, 405);
+        let _ = std::fs::remove_dir_all(&config.dir);
+    }
+
+    #[test]
+    fn test_reopen_continues_and_skips_torn_lines() {
+        let config = test_config("reopen");
+        let clock = MockClock::shared(Nanos(START));
+        let log = AuditLog::open_with_clock(config.clone(), clock.clone()).unwrap();
+        log.append("risk", None, AuditEvent::approved(None)).unwrap();
+        log.append("risk", None, AuditEvent::approved(None)).unwrap();
+        drop(log);
+
+        // Crash mid-write
+        let (_, path) = super::segments(&config.dir).unwrap().pop().unwrap();
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"deadbeef {\"seq\":3").unwrap();
+
+        let log = AuditLog::open_with_clock(config.clone(), clock).unwrap();
+        assert_eq!(log.append("risk", None, AuditEvent::approved(None)).unwrap(), 3);
+        let all = log.query(&AuditQuery::default()).unwrap();
+        assert_eq!(all.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![1, 2, 3]);
+        let _ = std::fs::remove_dir_all(&config.dir);
+    }
+}
@@ -0,0 +1,636 @@
+// src/backtest_jobs.rs
+// Backtester as a job service - queued submissions, bounded concurrency, persisted results
+//
+// Admin routes (params in the query string, as with /flags):
+//
+//     POST   /backtests?profile=NAME&pattern=ID&from=DATE&to=DATE&seed=N&source=synthetic|archive&speed=max|Nx
+//     GET    /backtests                 all jobs, newest first
+//     GET    /backtests/<id>            state and progress
+//     GET    /backtests/<id>/result     the BacktestResult once completed
+//     DELETE /backtests/<id>            cancel a queued job
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+use crate::backtest_results::SharedBacktestResults;
+use crate::backtester_config::{parse_timestamp_ns, BacktesterControls, DateRange};
+use crate::clock::{self, SharedClock};
+use crate::config::BacktestJobsSection;
+use crate::error::StateStoreError;
+use crate::market_impact::SharedMarketImpact;
+use crate::sizing_curve::SharedSizingCurve;
+use crate::replay_pacing::ReplaySpeed;
+use crate::tick_sim_backtester::{BacktestConfig, BacktestProgress, BacktestResult, TickSimBacktester};
+
+/// Job service settings, from env (BACKTEST_JOBS=1 enables it in the runner)
+#[derive(Debug, Clone)]
+pub struct BacktestJobsConfig {
+    /// Finished jobs are written here as `<id>.json`
+    pub dir: PathBuf,
+    /// Jobs waiting to run before submissions are refused
+    pub max_queued: usize,
+    /// Backtests running at once
+    pub max_concurrent: usize,
+    /// Tick archive replayed by `source=archive` jobs; synthetic data only without it
+    pub archive_dir: Option<PathBuf>,
+}
+
+impl Default for BacktestJobsConfig {
+    fn default() -> Self {
+        Self::from(&BacktestJobsSection::default())
+    }
+}
+
+impl From<&BacktestJobsSection> for BacktestJobsConfig {
+    fn from(b: &BacktestJobsSection) -> Self {
+        Self {
+            dir: PathBuf::from(&b.dir),
+            max_queued: b.max_queued,
+            max_concurrent: b.max_concurrent,
+            archive_dir: None,
+        }
+    }
+}
+
+impl BacktestJobsConfig {
+    /// Replay `source=archive` jobs from this tick store
+    pub fn with_archive_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.archive_dir = Some(dir.into());
+        self
+    }
+}
+
+pub type JobId = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobState::Completed | JobState::Failed | JobState::Cancelled)
+    }
+}
+
+/// What a job runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSpec {
+    pub config: BacktestConfig,
+    /// Tick store directory, or "synthetic"
+    pub data_source: String,
+    /// Sim profile the config was built from
+    pub profile: Option<String>,
+}
+
+/// A submitted backtest; finished jobs are persisted whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestJob {
+    pub id: JobId,
+    pub state: JobState,
+    pub spec: JobSpec,
+    pub submitted_ns: u64,
+    pub started_ns: Option<u64>,
+    pub finished_ns: Option<u64>,
+    pub error: Option<String>,
+    pub result: Option<BacktestResult>,
+}
+
+/// Job view for polling (no result body)
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub id: JobId,
+    pub state: JobState,
+    pub pattern_id: u16,
+    pub profile: Option<String>,
+    pub data_source: String,
+    /// 0..=1; ticks replayed over ticks loaded
+    pub progress: f64,
+    /// Jobs ahead of this one while queued
+    pub queue_position: Option<usize>,
+    pub submitted_ns: u64,
+    pub started_ns: Option<u64>,
+    pub finished_ns: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Runs one job to completion on a blocking thread
+pub type JobRunner = Arc<dyn Fn(&JobSpec, Arc<BacktestProgress>) -> Result<BacktestResult, String> + Send + Sync>;
+
+#[derive(Default)]
+struct Jobs {
+    jobs: BTreeMap<JobId, BacktestJob>,
+    queue: VecDeque<JobId>,
+    progress: BTreeMap<JobId, Arc<BacktestProgress>>,
+    next_id: JobId,
+}
+
+/// Queue, registry and result store for backtest jobs
+pub struct BacktestJobs {
+    config: BacktestJobsConfig,
+    inner: Mutex<Jobs>,
+    wake: Notify,
+    runner: JobRunner,
+    clock: SharedClock,
+    /// Completed results are also published here (optional)
+    results: Option<SharedBacktestResults>,
+    /// Live models the default runner simulates with
+    impact: Option<SharedMarketImpact>,
+    sizing: Option<SharedSizingCurve>,
+}
+
+pub type SharedBacktestJobs = Arc<BacktestJobs>;
+
+impl BacktestJobs {
+    /// Open the result store, reloading jobs finished by earlier runs
+    pub fn open(config: BacktestJobsConfig) -> Result<Self, StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+        let mut inner = Jobs { next_id: 1, ..Jobs::default() };
+        for entry in std::fs::read_dir(&config.dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let job = std::fs::read_to_string(&path)
+                .map_err(StateStoreError::from)
+                .and_then(|text| Ok(serde_json::from_str::<BacktestJob>(&text)?));
+            match job {
+                Ok(job) => {
+                    inner.next_id = inner.next_id.max(job.id + 1);
+                    inner.jobs.insert(job.id, job);
+                }
+                Err(e) => warn!("[BACKTEST] Skipping {}: {}", path.display(), e),
+            }
+        }
+        if !inner.jobs.is_empty() {
+            info!("[BACKTEST] Loaded {} finished jobs from {}", inner.jobs.len(), config.dir.display());
+        }
+
+        Ok(Self {
+            config,
+            inner: Mutex::new(inner),
+            wake: Notify::new(),
+            runner: Arc::new(|spec, progress| run_tick_sim(spec, progress, None, None)),
+            clock: clock::system(),
+            results: None,
+            impact: None,
+            sizing: None,
+        })
+    }
+
+    /// Replace the backtest runner (tests)
+    pub fn with_runner(mut self, runner: JobRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Price simulated fills with the live market impact model
+    pub fn with_market_impact(mut self, impact: SharedMarketImpact) -> Self {
+        self.impact = Some(impact);
+        self.with_tick_sim()
+    }
+
+    /// Size simulated trades on the live `[sizing]` curve
+    pub fn with_sizing_curve(mut self, sizing: SharedSizingCurve) -> Self {
+        self.sizing = Some(sizing);
+        self.with_tick_sim()
+    }
+
+    fn with_tick_sim(self) -> Self {
+        let (impact, sizing) = (self.impact.clone(), self.sizing.clone());
+        self.with_runner(Arc::new(move |spec, progress| run_tick_sim(spec, progress, impact.clone(), sizing.clone())))
+    }
+
+    /// Publish completed results to the results repository the dashboard reads
+    pub fn with_results(mut self, results: SharedBacktestResults) -> Self {
+        self.results = Some(results);
+        self
+    }
+
+    pub fn config(&self) -> &BacktestJobsConfig {
+        &self.config
+    }
+
+    /// Queue a job; None when the queue is full
+    pub fn submit(&self, spec: JobSpec) -> Option<JobId> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.queue.len() >= self.config.max_queued {
+            return None;
+        }
+        let id = inner.next_id;
+        inner.next_id += 1;
+        info!("[BACKTEST] Job {} queued: pattern #{} on {}", id, spec.config.pattern_id, spec.data_source);
+        inner.jobs.insert(id, BacktestJob {
+            id,
+            state: JobState::Queued,
+            spec,
+            submitted_ns: self.clock.wall_ns().0,
+            started_ns: None,
+            finished_ns: None,
+            error: None,
+            result: None,
+        });
+        inner.queue.push_back(id);
+        drop(inner);
+        self.wake.notify_one();
+        Some(id)
+    }
+
+    /// Cancel a queued job; running and finished jobs are left alone
+    pub fn cancel(&self, id: JobId) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(pos) = inner.queue.iter().position(|&queued| queued == id) else {
+            return false;
+        };
+        inner.queue.remove(pos);
+        let now = self.clock.wall_ns().0;
+        if let Some(job) = inner.jobs.get_mut(&id) {
+            job.state = JobState::Cancelled;
+            job.finished_ns = Some(now);
+        }
+        info!("[BACKTEST] Job {} cancelled", id);
+        true
+    }
+
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        let inner = self.inner.lock().unwrap();
+        inner.jobs.get(&id).map(|job| Self::view(&inner, job))
+    }
+
+    /// Every known job, newest first
+    pub fn list(&self) -> Vec<JobStatus> {
+        let inner = self.inner.lock().unwrap();
+        inner.jobs.values().rev().map(|job| Self::view(&inner, job)).collect()
+    }
+
+    pub fn get(&self, id: JobId) -> Option<BacktestJob> {
+        self.inner.lock().unwrap().jobs.get(&id).cloned()
+    }
+
+    /// The most recently completed job
+    pub fn latest_completed(&self) -> Option<BacktestJob> {
+        let inner = self.inner.lock().unwrap();
+        inner.jobs.values()
+            .filter(|job| job.state == JobState::Completed)
+            .max_by_key(|job| job.finished_ns)
+            .cloned()
+    }
+
+    fn view(inner: &Jobs, job: &BacktestJob) -> JobStatus {
+        let progress = match job.state {
+            JobState::Completed => 1.0,
+            JobState::Running => inner.progress.get(&job.id).map_or(0.0, |p| p.fraction()),
+            _ => 0.0,
+        };
+        JobStatus {
+            id: job.id,
+            state: job.state,
+            pattern_id: job.spec.config.pattern_id,
+            profile: job.spec.profile.clone(),
+            data_source: job.spec.data_source.clone(),
+            progress,
+            queue_position: inner.queue.iter().position(|&queued| queued == job.id),
+            submitted_ns: job.submitted_ns,
+            started_ns: job.started_ns,
+            finished_ns: job.finished_ns,
+            error: job.error.clone(),
+        }
+    }
+
+    /// Pop the next queued job and mark it running
+    fn take_next(&self) -> Option<(JobId, JobSpec, Arc<BacktestProgress>)> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.queue.pop_front()?;
+        let progress = Arc::new(BacktestProgress::default());
+        inner.progress.insert(id, progress.clone());
+        let now = self.clock.wall_ns().0;
+        let job = inner.jobs.get_mut(&id)?;
+        job.state = JobState::Running;
+        job.started_ns = Some(now);
+        Some((id, job.spec.clone(), progress))
+    }
+
+    /// Record a job's outcome and persist it
+    fn finish(&self, id: JobId, outcome: Result<BacktestResult, String>) {
+        let job = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.progress.remove(&id);
+            let now = self.clock.wall_ns().0;
+            let Some(job) = inner.jobs.get_mut(&id) else { return };
+            job.finished_ns = Some(now);
+            match outcome {
+                Ok(result) => {
+                    info!("[BACKTEST] Job {} completed: ROI {:.2}% over {} trades", id, result.roi_percent, result.total_trades);
+                    job.state = JobState::Completed;
+                    job.result = Some(result);
+                }
+                Err(e) => {
+                    warn!("[BACKTEST] Job {} failed: {}", id, e);
+                    job.state = JobState::Failed;
+                    job.error = Some(e);
+                }
+            }
+            job.clone()
+        };
+        if let Err(e) = self.persist(&job) {
+            warn!("[BACKTEST] Could not save job {}: {}", id, e);
+        }
+        if let (Some(results), Some(result)) = (&self.results, &job.result) {
+            results.record_backtest(Some(job.id), job.spec.profile.clone(), &job.spec.data_source, result);
+        }
+    }
+
+    fn persist(&self, job: &BacktestJob) -> Result<(), StateStoreError> {
+        // Write-then-rename so a crash never leaves a torn result
+        let path = self.config.dir.join(format!("{}.json", job.id));
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(job)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Build a job from submission query params
+    pub fn parse_submission(&self, query: &str) -> Result<JobSpec> {
+        let params: BTreeMap<&str, &str> = query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+
+        let profile = params.get("profile").map(|p| p.to_string());
+        let mut controls = match &profile {
+            Some(name) => BacktesterControls::from_profile(name)?,
+            None => BacktesterControls::default(),
+        };
+        if let Some(from) = params.get("from") {
+            let start_ns = parse_timestamp_ns(from, false)?;
+            let end_ns = controls.date_range.map_or(u64::MAX, |r| r.end_ns);
+            controls.date_range = Some(DateRange { start_ns, end_ns });
+        }
+        if let Some(to) = params.get("to") {
+            let end_ns = parse_timestamp_ns(to, true)?;
+            let start_ns = controls.date_range.map_or(0, |r| r.start_ns);
+            controls.date_range = Some(DateRange { start_ns, end_ns });
+        }
+        if let Some(range) = controls.date_range {
+            if range.start_ns > range.end_ns {
+                return Err(anyhow!("from must not be after to"));
+            }
+        }
+        if let Some(seed) = params.get("seed") {
+            controls.seed = Some(seed.parse().map_err(|_| anyhow!("seed must be an integer"))?);
+        }
+        if let Some(speed) = params.get("speed") {
+            controls.replay_speed = ReplaySpeed::parse(speed)?;
+        }
+
+        let pattern_id = match params.get("pattern") {
+            Some(p) => p.parse().map_err(|_| anyhow!("pattern must be a pattern id"))?,
+            None => *controls.patterns.first().ok_or_else(|| anyhow!("missing ?pattern="))?,
+        };
+
+        // Only the configured archive is replayable; HTTP callers never name a path
+        let data_source = match params.get("source").copied().unwrap_or("synthetic") {
+            "synthetic" => "synthetic".to_string(),
+            "archive" => self.config.archive_dir.as_ref()
+                .ok_or_else(|| anyhow!("no tick archive configured"))?
+                .display()
+                .to_string(),
+            other => return Err(anyhow!("unknown source '{}' (synthetic|archive)", other)),
+        };
+
+        Ok(JobSpec {
+            config: BacktestConfig::from_controls(&controls, pattern_id),
+            data_source,
+            profile,
+        })
+    }
+
+    /// Admin API: submit, list, poll, fetch results, cancel
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let target = path.trim_start_matches("/backtests").trim_matches('/');
+        let (id, sub) = target.split_once('/').unwrap_or((target, ""));
+        let not_found = || (404, serde_json::json!({ "error": format!("no job '{}'", id) }).to_string());
+
+        match (method, id, sub) {
+            ("POST", "", "") => match self.parse_submission(query) {
+                Ok(spec) => match self.submit(spec) {
+                    Some(id) => (200, serde_json::json!({ "id": id, "state": JobState::Queued }).to_string()),
+                    None => (503, serde_json::json!({
+                        "error": format!("queue full ({} jobs waiting)", self.config.max_queued)
+                    }).to_string()),
+                },
+                Err(e) => (400, serde_json::json!({ "error": e.to_string() }).to_string()),
+            },
+            ("GET", "", "") => (200, serde_json::to_string_pretty(&self.list()).unwrap_or_default()),
+            ("GET", id, "") => match id.parse().ok().and_then(|id| self.status(id)) {
+                Some(status) => (200, serde_json::to_string_pretty(&status).unwrap_or_default()),
+                None => not_found(),
+            },
+            ("GET", id, "result") => match id.parse().ok().and_then(|id| self.get(id)) {
+                Some(BacktestJob { result: Some(result), .. }) => {
+                    (200, serde_json::to_string_pretty(&result).unwrap_or_default())
+                }
+                Some(job) => (404, serde_json::json!({
+                    "error": format!("job {} has no result ({:?})", job.id, job.state)
+                }).to_string()),
+                None => not_found(),
+            },
+            ("DELETE", id, "") if !id.is_empty() => match id.parse().ok().and_then(|id| self.status(id)) {
+                Some(status) if self.cancel(status.id) => {
+                    (200, serde_json::to_string_pretty(&self.status(status.id)).unwrap_or_default())
+                }
+                Some(status) => (400, serde_json::json!({
+                    "error": format!("job {} is {:?}; only queued jobs can be cancelled", status.id, status.state)
+                }).to_string()),
+                None => not_found(),
+            },
+            ("GET", _, _) => (404, r#"{"error":"not found"}"#.to_string()),
+            _ => (405, r#"{"error":"method not allowed"}"#.to_string()),
+        }
+    }
+}
+
+/// Replay a job through the tick simulator on its own single-threaded runtime; without an
+/// impact model fills are priced with the default coefficients, and without a sizing curve
+/// trades scale linearly with strength
+fn run_tick_sim(
+    spec: &JobSpec,
+    progress: Arc<BacktestProgress>,
+    impact: Option<SharedMarketImpact>,
+    sizing: Option<SharedSizingCurve>,
+) -> Result<BacktestResult, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+    runtime.block_on(async {
+        let mut backtester = TickSimBacktester::new(spec.config.clone()).with_progress(progress);
+        if let Some(impact) = impact {
+            backtester = backtester.with_market_impact(impact);
+        }
+        if let Some(sizing) = sizing {
+            backtester = backtester.with_sizing_curve(sizing);
+        }
+        backtester.load_historical_ticks(&spec.data_source).await.map_err(|e| e.to_string())?;
+        backtester.run_backtest().await.map_err(|e| e.to_string())
+    })
+}
+
+/// Drain the queue with `max_concurrent` workers; dropping the future stops the workers, and a
+/// backtest already running still finishes and records its result
+pub async fn run_backtest_jobs(jobs: SharedBacktestJobs) {
+    info!("[BACKTEST] Job service up: {} workers, queue of {}", jobs.config.max_concurrent, jobs.config.max_queued);
+    let mut workers = tokio::task::JoinSet::new();
+    for _ in 0..jobs.config.max_concurrent {
+        let jobs = jobs.clone();
+        workers.spawn(async move {
+            loop {
+                let Some((id, spec, progress)) = jobs.take_next() else {
+                    jobs.wake.notified().await;
+                    continue;
+                };
+                info!("[BACKTEST] Job {} running", id);
+                let worker_jobs = jobs.clone();
+                let run = tokio::task::spawn_blocking(move || {
+                    let outcome = (worker_jobs.runner)(&spec, progress);
+                    worker_jobs.finish(id, outcome);
+                });
+                if let Err(e) = run.await {
+                    jobs.finish(id, Err(format!("backtest aborted: {}", e)));
+                }
+            }
+        });
+    }
+    while workers.join_next().await.is_some() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tick_sim_backtester::ExecutionStats;
+    use std::time::Duration;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("backtest_jobs_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn result(pattern_id: u16) -> BacktestResult {
+        BacktestResult {
+            pattern_id,
+            total_return: 120.0,
+            roi_percent: 1.2,
+            sharpe_ratio: 1.5,
+            max_drawdown: 0.02,
+            total_trades: 10,
+            winning_trades: 7,
+            losing_trades: 3,
+            avg_trade_duration_us: 250.0,
+            final_sharp_score: 0.4,
+            account_limited: false,
+            alpha_half_life_us: 0.0,
+            execution_stats: ExecutionStats {
+                avg_execution_latency_us: 0.8,
+                sla_compliance_percent: 99.0,
+                fill_rate_percent: 95.0,
+                avg_slippage: 0.0,
+            },
+            equity_curve: Vec::new(),
+        }
+    }
+
+    fn jobs(name: &str, max_queued: usize) -> BacktestJobs {
+        let config = BacktestJobsConfig { dir: test_dir(name), max_queued, max_concurrent: 1, archive_dir: None };
+        BacktestJobs::open(config).unwrap()
+            .with_runner(Arc::new(|spec: &JobSpec, _| match spec.config.pattern_id {
+                0 => Err("no such pattern".to_string()),
+                id => Ok(result(id)),
+            }))
+    }
+
+    #[test]
+    fn test_queue_bound_and_cancel() {
+        let jobs = jobs("bound", 2);
+        let spec = jobs.parse_submission("pattern=73").unwrap();
+        let first = jobs.submit(spec.clone()).unwrap();
+        let second = jobs.submit(spec.clone()).unwrap();
+        assert!(jobs.submit(spec.clone()).is_none(), "queue of 2 is full");
+        assert_eq!(jobs.status(second).unwrap().queue_position, Some(1));
+
+        let (status, _) = jobs.handle_admin("POST", "/backtests?pattern=73");
+        assert_eq!(status, 503);
+
+        assert!(jobs.cancel(first));
+        assert_eq!(jobs.status(first).unwrap().state, JobState::Cancelled);
+        assert_eq!(jobs.status(second).unwrap().queue_position, Some(0));
+        assert!(jobs.submit(spec).is_some());
+
+        // Running jobs can't be cancelled
+        let (running, _, _) = jobs.take_next().unwrap();
+        assert_eq!(running, second);
+        assert_eq!(jobs.handle_admin("DELETE", &format!("/backtests/{}", second)).0, 400);
+        let _ = std::fs::remove_dir_all(&jobs.config.dir);
+    }
+
+    #[test]
+    fn test_parse_submission() {
+        let mut jobs = jobs("parse", 4);
+        let spec = jobs.parse_submission("pattern=73&from=2026-01-01&to=2026-01-31&seed=9").unwrap();
+        assert_eq!(spec.config.pattern_id, 73);
+        assert_eq!(spec.config.seed, Some(9));
+        assert_eq!(spec.data_source, "synthetic");
+        assert!(spec.config.start_timestamp_ns < spec.config.end_timestamp_ns);
+
+        assert!(jobs.parse_submission("").is_err(), "default controls name no pattern");
+        assert!(jobs.parse_submission("pattern=73&from=2026-02-01&to=2026-01-01").is_err());
+        assert!(jobs.parse_submission("pattern=73&source=/etc").is_err());
+        assert!(jobs.parse_submission("pattern=73&source=archive").is_err(), "no archive configured");
+        assert_eq!(jobs.parse_submission("pattern=73&speed=20x").unwrap().config.speed, ReplaySpeed::Accelerated(20.0));
+        assert!(jobs.parse_submission("pattern=73&speed=0").is_err());
+
+        jobs.config.archive_dir = Some(PathBuf::from("./data/ticks"));
+        assert_eq!(jobs.parse_submission("pattern=73&source=archive").unwrap().data_source, "./data/ticks");
+        let _ = std::fs::remove_dir_all(&jobs.config.dir);
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_and_results_persist() {
+        let jobs = Arc::new(jobs("persist", 4));
+        let ok = jobs.submit(jobs.parse_submission("pattern=73").unwrap()).unwrap();
+        let failed = jobs.submit(jobs.parse_submission("pattern=0").unwrap()).unwrap();
+        let worker = tokio::spawn(run_backtest_jobs(jobs.clone()));
+
+        for _ in 0..200 {
+            if jobs.list().iter().all(|job| job.state.is_finished()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        worker.abort();
+
+        assert_eq!(jobs.status(ok).unwrap().progress, 1.0);
+        assert_eq!(jobs.status(failed).unwrap().state, JobState::Failed);
+        assert_eq!(jobs.latest_completed().unwrap().id, ok);
+        let (status, body) = jobs.handle_admin("GET", &format!("/backtests/{}/result", ok));
+        assert_eq!(status, 200);
+        assert!(body.contains("\"roi_percent\": 1.2"));
+        assert_eq!(jobs.handle_admin("GET", &format!("/backtests/{}/result", failed)).0, 404);
+
+        // A restart reloads finished jobs and keeps numbering after them
+        let reopened = BacktestJobs::open(jobs.config.clone()).unwrap();
+        assert_eq!(reopened.status(ok).unwrap().state, JobState::Completed);
+        assert_eq!(reopened.get(failed).unwrap().error.as_deref(), Some("no such pattern"));
+        assert_eq!(reopened.submit(jobs.get(ok).unwrap().spec), Some(3));
+        let _ = std::fs::remove_dir_all(&jobs.config.dir);
+    }
+}
@@ -0,0 +1,248 @@
+// src/backtest_results.rs
+// Backtest results repository - completed backtests and pattern verification updates appended
+// to one JSONL file as they happen, the latest few kept in memory for the dashboard's
+// Component #41 panels (and reloaded from the file's tail on restart)
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::backtester_config::PatternVerification;
+use crate::clock::{self, SharedClock};
+use crate::config::BacktestResultsSection;
+use crate::error::StateStoreError;
+use crate::tick_sim_backtester::BacktestResult;
+use crate::types::TimestampNs;
+
+/// Repository settings, from env
+#[derive(Debug, Clone)]
+pub struct BacktestResultsConfig {
+    /// Append-only record file
+    pub path: PathBuf,
+    /// Records of each kind kept in memory (and reloaded on open)
+    pub keep: usize,
+}
+
+impl Default for BacktestResultsConfig {
+    fn default() -> Self {
+        Self::from(&BacktestResultsSection::default())
+    }
+}
+
+impl From<&BacktestResultsSection> for BacktestResultsConfig {
+    fn from(b: &BacktestResultsSection) -> Self {
+        Self { path: PathBuf::from(&b.path), keep: b.keep }
+    }
+}
+
+/// A completed backtest; the equity curve stays with its job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestRecord {
+    /// Job that ran it (None when run outside the job service)
+    pub job_id: Option<u64>,
+    pub profile: Option<String>,
+    pub data_source: String,
+    pub finished_ns: TimestampNs,
+    pub result: BacktestResult,
+}
+
+/// A component's verification as recomputed by one archive replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    pub timestamp_ns: TimestampNs,
+    pub verification: PatternVerification,
+}
+
+/// One line of the record file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Record {
+    Backtest(BacktestRecord),
+    Verification(VerificationRecord),
+}
+
+#[derive(Default)]
+struct Latest {
+    backtests: VecDeque<BacktestRecord>,
+    verifications: VecDeque<VerificationRecord>,
+}
+
+impl Latest {
+    fn push(&mut self, record: Record, keep: usize) {
+        match record {
+            Record::Backtest(r) => push_capped(&mut self.backtests, r, keep),
+            Record::Verification(r) => push_capped(&mut self.verifications, r, keep),
+        }
+    }
+}
+
+fn push_capped<T>(records: &mut VecDeque<T>, record: T, keep: usize) {
+    if records.len() >= keep {
+        records.pop_front();
+    }
+    records.push_back(record);
+}
+
+/// Where the job service and the pattern verifier publish, and the dashboard reads from
+pub struct BacktestResults {
+    config: BacktestResultsConfig,
+    latest: Mutex<Latest>,
+    clock: SharedClock,
+}
+
+pub type SharedBacktestResults = Arc<BacktestResults>;
+
+impl BacktestResults {
+    /// Open the record file, reloading the latest `keep` records of each kind
+    pub fn open(config: BacktestResultsConfig) -> Result<Self, StateStoreError> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut latest = Latest::default();
+        if let Ok(file) = File::open(&config.path) {
+            for line in BufReader::new(file).lines() {
+                match serde_json::from_str::<Record>(&line?) {
+                    Ok(record) => latest.push(record, config.keep),
+                    Err(e) => warn!("[BACKTEST] Skipping result record: {}", e),
+                }
+            }
+            info!("[BACKTEST] Loaded {} backtests and {} verification updates from {}",
+                  latest.backtests.len(), latest.verifications.len(), config.path.display());
+        }
+        Ok(Self { config, latest: Mutex::new(latest), clock: clock::system() })
+    }
+
+    /// Stamp records from another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn append(&self, record: Record) {
+        let written = serde_json::to_string(&record).map_err(StateStoreError::from).and_then(|line| {
+            let mut file = OpenOptions::new().create(true).append(true).open(&self.config.path)?;
+            writeln!(file, "{}", line)?;
+            Ok(())
+        });
+        if let Err(e) = written {
+            warn!("[BACKTEST] Could not save result record: {}", e);
+        }
+        self.latest.lock().unwrap().push(record, self.config.keep);
+    }
+
+    /// Record a completed backtest
+    pub fn record_backtest(&self, job_id: Option<u64>, profile: Option<String>, data_source: &str, result: &BacktestResult) {
+        let mut result = result.clone();
+        result.equity_curve.clear();
+        self.append(Record::Backtest(BacktestRecord {
+            job_id,
+            profile,
+            data_source: data_source.to_string(),
+            finished_ns: self.clock.wall_ns().0,
+            result,
+        }));
+    }
+
+    /// Record the verifications an archive replay recomputed
+    pub fn record_verifications(&self, verifications: &[PatternVerification]) {
+        let timestamp_ns = self.clock.wall_ns().0;
+        for verification in verifications {
+            self.append(Record::Verification(VerificationRecord { timestamp_ns, verification: verification.clone() }));
+        }
+    }
+
+    /// Up to `n` backtests, newest first
+    pub fn latest_backtests(&self, n: usize) -> Vec<BacktestRecord> {
+        self.latest.lock().unwrap().backtests.iter().rev().take(n).cloned().collect()
+    }
+
+    /// The newest backtest
+    pub fn latest_backtest(&self) -> Option<BacktestRecord> {
+        self.latest.lock().unwrap().backtests.back().cloned()
+    }
+
+    /// Up to `n` verification updates, newest first
+    pub fn latest_verifications(&self, n: usize) -> Vec<VerificationRecord> {
+        self.latest.lock().unwrap().verifications.iter().rev().take(n).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtester_config::{get_default_pattern_verifications, VerificationStatus};
+    use crate::clock::MockClock;
+    use crate::tick_sim_backtester::{EquityPoint, ExecutionStats};
+    use crate::types::Nanos;
+    use std::time::Duration;
+
+    fn config(name: &str, keep: usize) -> BacktestResultsConfig {
+        let dir = std::env::temp_dir().join(format!("backtest_results_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        BacktestResultsConfig { path: dir.join("results.jsonl"), keep }
+    }
+
+    fn result(pattern_id: u16, roi_percent: f64) -> BacktestResult {
+        BacktestResult {
+            pattern_id,
+            total_return: roi_percent * 100.0,
+            roi_percent,
+            sharpe_ratio: 1.5,
+            max_drawdown: 0.02,
+            total_trades: 10,
+            winning_trades: 7,
+            losing_trades: 3,
+            avg_trade_duration_us: 250.0,
+            final_sharp_score: 0.4,
+            account_limited: false,
+            alpha_half_life_us: 0.0,
+            execution_stats: ExecutionStats {
+                avg_execution_latency_us: 0.8,
+                sla_compliance_percent: 99.0,
+                fill_rate_percent: 95.0,
+                avg_slippage: 0.0,
+            },
+            equity_curve: vec![EquityPoint { timestamp_ns: 0, equity: 1.0, sharp_score: 0.0, position_count: 0 }],
+        }
+    }
+
+    #[test]
+    fn test_latest_backtests_newest_first_and_capped() {
+        let clock = MockClock::shared(Nanos(1_000));
+        let results = BacktestResults::open(config("latest", 2)).unwrap().with_clock(clock.clone());
+        assert!(results.latest_backtest().is_none());
+        for (i, roi) in [1.0, 2.0, 3.0].into_iter().enumerate() {
+            results.record_backtest(Some(i as u64 + 1), None, "synthetic", &result(73, roi));
+            clock.advance(Duration::from_secs(1));
+        }
+        let latest = results.latest_backtests(5);
+        assert_eq!(latest.iter().map(|r| r.job_id).collect::<Vec<_>>(), vec![Some(3), Some(2)]);
+        assert!(latest[0].result.equity_curve.is_empty());
+        assert_eq!(results.latest_backtest().unwrap().result.roi_percent, 3.0);
+        assert_eq!(results.latest_backtests(1).len(), 1);
+        let _ = std::fs::remove_dir_all(results.config.path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_records_survive_a_restart() {
+        let config = config("reopen", 10);
+        let results = BacktestResults::open(config.clone()).unwrap();
+        results.record_backtest(None, Some("fast".to_string()), "./data/ticks", &result(41, 1.2));
+        let mut verifications = get_default_pattern_verifications();
+        verifications.truncate(2);
+        verifications[0].update_results(4.0, VerificationStatus::Verified);
+        results.record_verifications(&verifications);
+
+        let reopened = BacktestResults::open(config.clone()).unwrap();
+        let backtest = reopened.latest_backtest().unwrap();
+        assert_eq!((backtest.result.pattern_id, backtest.profile.as_deref()), (41, Some("fast")));
+        let updates = reopened.latest_verifications(10);
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1].verification.verification_status, VerificationStatus::Verified);
+        let _ = std::fs::remove_dir_all(config.path.parent().unwrap());
+    }
+}
@@ -1,11 +1,21 @@
 //! Backtester Configuration and Controls
 //!
 //! Sportsbook Feed Hub configuration for Component #41: Tick-Sim-Backtester
-//! Provides SIM_* environment variables and control parameters for backtesting
+//! Provides SIM_* environment variables and control parameters for backtesting,
+//! plus named scenario profiles loaded from a TOML file
 
+use std::collections::BTreeMap;
 use std::env;
+use std::path::Path;
+use anyhow::{anyhow, Context, Result};
 use serde::{Serialize, Deserialize};
 
+use crate::config::BacktesterSection;
+use crate::replay_pacing::ReplaySpeed;
+
+/// Default profile file, versioned alongside strategies (override with SIM_PROFILES_PATH)
+pub const DEFAULT_PROFILES_PATH: &str = "backtest_profiles.toml";
+
 /// Backtester configuration from environment variables
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BacktesterControls {
@@ -17,6 +27,8 @@ pub struct BacktesterControls {
     pub tick_precision: TickPrecision,
     /// Maximum simulation speed multiplier
     pub max_speed_multiplier: f64,
+    /// Replay pacing (capped at `max_speed_multiplier`)
+    pub replay_speed: ReplaySpeed,
     /// Memory limit for tick buffer (MB)
     pub memory_limit_mb: u64,
     /// Enable real-time monitoring
@@ -25,6 +37,54 @@ pub struct BacktesterControls {
     pub log_level: LogLevel,
     /// Data source configuration
     pub data_source: DataSource,
+    /// Profile these controls were loaded from, if any
+    pub profile: Option<String>,
+    /// Replay window (inclusive nanosecond timestamps)
+    pub date_range: Option<DateRange>,
+    /// Pattern IDs to backtest (empty = all)
+    pub patterns: Vec<u16>,
+    /// RNG seed for reproducible runs
+    pub seed: Option<u64>,
+    /// Capital and position limits
+    pub risk: RiskControls,
+}
+
+/// Inclusive replay window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start_ns: u64,
+    pub end_ns: u64,
+}
+
+impl DateRange {
+    pub fn contains(&self, timestamp_ns: u64) -> bool {
+        (self.start_ns..=self.end_ns).contains(&timestamp_ns)
+    }
+}
+
+/// Capital and position limits for a backtest run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskControls {
+    /// Starting capital
+    pub initial_capital: f64,
+    /// Transaction cost per trade (fraction of notional)
+    pub transaction_cost: f64,
+    /// Maximum position size
+    pub max_position_size: f64,
+    /// Simulated account lifespan before limiting
+    pub account_lifespan_days: u32,
+}
+
+impl Default for RiskControls {
+    fn default() -> Self {
+        Self {
+            initial_capital: 10000.0,
+            transaction_cost: 0.001,
+            max_position_size: 1000.0,
+            account_lifespan_days: 30,
+        }
+    }
 }
 
 /// Tick precision levels
@@ -35,6 +95,17 @@ pub enum TickPrecision {
     Nanosecond,
 }
 
+impl TickPrecision {
+    /// "milli" | "micro" | "nano" (anything else is milliseconds)
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "micro" => TickPrecision::Microsecond,
+            "nano" => TickPrecision::Nanosecond,
+            _ => TickPrecision::Millisecond,
+        }
+    }
+}
+
 /// Log levels for simulation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LogLevel {
@@ -45,6 +116,18 @@ pub enum LogLevel {
     Trace,
 }
 
+impl LogLevel {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "error" => LogLevel::Error,
+            "warn" => LogLevel::Warn,
+            "debug" => LogLevel::Debug,
+            "trace" => LogLevel::Trace,
+            _ => LogLevel::Info,
+        }
+    }
+}
+
 /// Data source configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataSource {
@@ -71,6 +154,174 @@ pub enum DataSourceType {
     WebSocket,
 }
 
+impl DataSourceType {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "s3" => DataSourceType::S3,
+            "database" | "db" => DataSourceType::Database,
+            "websocket" | "ws" => DataSourceType::WebSocket,
+            _ => DataSourceType::Local,
+        }
+    }
+}
+
+// =============================================================================
+// SCENARIO PROFILES
+// =============================================================================
+//
+// backtest_profiles.toml:
+//
+//   [profiles.nba-playoffs-2024]
+//   description = "NBA playoffs, pattern #73 only"
+//   seed = 42
+//   patterns = [73]
+//   start = "2024-04-20"            # date (UTC midnight) or RFC3339
+//   end = "2024-06-17"              # date-only end covers the whole day
+//   tick_precision = "micro"
+//   latency_jitter_ms = 8.0
+//
+//   [profiles.nba-playoffs-2024.data_source]
+//   type = "local"
+//   path = "./data/nba_2024"
+//
+//   [profiles.nba-playoffs-2024.risk]
+//   initial_capital = 25000.0
+
+/// One named scenario; unset fields keep the `BacktesterControls` defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BacktestProfile {
+    pub description: String,
+    pub data_source: Option<ProfileDataSource>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub patterns: Vec<u16>,
+    pub seed: Option<u64>,
+    pub risk: Option<RiskControls>,
+    pub tick_precision: Option<String>,
+    pub latency_jitter_ms: Option<f64>,
+    pub sharp_limit_threshold: Option<f64>,
+    pub max_speed_multiplier: Option<f64>,
+    /// "max" or a wall-clock multiplier
+    pub replay_speed: Option<String>,
+    pub memory_limit_mb: Option<u64>,
+    pub log_level: Option<String>,
+}
+
+/// Data source block of a profile (auth tokens stay in SIM_DATA_SOURCE_AUTH_TOKEN)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileDataSource {
+    #[serde(rename = "type", default = "default_source_type")]
+    pub source_type: String,
+    pub path: String,
+    #[serde(default = "default_compression")]
+    pub compression: bool,
+}
+
+fn default_source_type() -> String {
+    "local".to_string()
+}
+
+fn default_compression() -> bool {
+    true
+}
+
+/// A profile file: `[profiles.<name>]` tables
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BacktestProfiles {
+    pub profiles: BTreeMap<String, BacktestProfile>,
+}
+
+impl BacktestProfiles {
+    pub fn parse(src: &str) -> Result<Self> {
+        toml::from_str(src).context("parse backtest profiles")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let src = std::fs::read_to_string(path)
+            .with_context(|| format!("read backtest profiles {}", path.display()))?;
+        Self::parse(&src).with_context(|| path.display().to_string())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Resolve a profile onto the defaults and validate the result
+    pub fn controls(&self, name: &str) -> Result<BacktesterControls> {
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            anyhow!("unknown backtest profile '{}' (have: {})", name, self.names().collect::<Vec<_>>().join(", "))
+        })?;
+        let controls = profile.apply(name, BacktesterControls::default())?;
+        controls.validate().map_err(|e| anyhow!("profile '{}': {}", name, e))?;
+        Ok(controls)
+    }
+}
+
+impl BacktestProfile {
+    fn apply(&self, name: &str, mut controls: BacktesterControls) -> Result<BacktesterControls> {
+        controls.profile = Some(name.to_string());
+        if let Some(ds) = &self.data_source {
+            controls.data_source.source_type = DataSourceType::parse(&ds.source_type);
+            controls.data_source.connection_string = ds.path.clone();
+            controls.data_source.compression = ds.compression;
+        }
+        controls.date_range = match (&self.start, &self.end) {
+            (None, None) => None,
+            (start, end) => Some(DateRange {
+                start_ns: start.as_deref().map(|s| parse_timestamp_ns(s, false)).transpose()?.unwrap_or(0),
+                end_ns: end.as_deref().map(|s| parse_timestamp_ns(s, true)).transpose()?.unwrap_or(u64::MAX),
+            }),
+        };
+        controls.patterns = self.patterns.clone();
+        controls.seed = self.seed;
+        if let Some(risk) = &self.risk {
+            controls.risk = risk.clone();
+        }
+        if let Some(v) = &self.tick_precision {
+            controls.tick_precision = TickPrecision::parse(v);
+        }
+        if let Some(v) = self.latency_jitter_ms {
+            controls.sim_latency_jitter = v;
+        }
+        if let Some(v) = self.sharp_limit_threshold {
+            controls.sharp_limit_threshold = v;
+        }
+        if let Some(v) = self.max_speed_multiplier {
+            controls.max_speed_multiplier = v;
+        }
+        if let Some(v) = &self.replay_speed {
+            controls.replay_speed = ReplaySpeed::parse(v)?;
+        }
+        if let Some(v) = self.memory_limit_mb {
+            controls.memory_limit_mb = v;
+        }
+        if let Some(v) = &self.log_level {
+            controls.log_level = LogLevel::parse(v);
+        }
+        Ok(controls)
+    }
+}
+
+/// RFC3339 timestamp or YYYY-MM-DD (UTC); a date-only `end` covers the whole day
+pub fn parse_timestamp_ns(s: &str, end_of_day: bool) -> Result<u64> {
+    let ns = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
+        dt.timestamp_nanos_opt()
+    } else {
+        let date = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|_| anyhow!("invalid date '{}' (want YYYY-MM-DD or RFC3339)", s))?;
+        let day = if end_of_day { date.succ_opt() } else { Some(date) };
+        day.and_then(|d| d.and_hms_opt(0, 0, 0))
+            .and_then(|dt| dt.and_utc().timestamp_nanos_opt())
+            .map(|ns| if end_of_day { ns - 1 } else { ns })
+    };
+    ns.and_then(|ns| u64::try_from(ns).ok())
+        .ok_or_else(|| anyhow!("date '{}' out of range", s))
+}
+
 impl Default for BacktesterControls {
     fn default() -> Self {
         Self {
@@ -78,6 +329,7 @@ impl Default for BacktesterControls {
             sharp_limit_threshold: 0.65,
             tick_precision: TickPrecision::Millisecond,
             max_speed_multiplier: 1000.0,
+            replay_speed: ReplaySpeed::AsFastAsPossible,
             memory_limit_mb: 2048,
             enable_monitoring: true,
             log_level: LogLevel::Info,
@@ -87,100 +339,38 @@ impl Default for BacktesterControls {
                 auth_token: None,
                 compression: true,
             },
+            profile: None,
+            date_range: None,
+            patterns: Vec::new(),
+            seed: None,
+            risk: RiskControls::default(),
         }
     }
 }
 
-impl BacktesterControls {
-    /// Load configuration from environment variables
-    pub fn from_env() -> Self {
-        let mut config = Self::default();
-
-        // SIM_LATENCY_JITTER
-        if let Ok(val) = env::var("SIM_LATENCY_JITTER") {
-            if let Ok(jitter) = val.parse::<f64>() {
-                config.sim_latency_jitter = jitter;
-            }
-        }
-
-        // SIM_SHARP_LIMIT_THRESHOLD
-        if let Ok(val) = env::var("SIM_SHARP_LIMIT_THRESHOLD") {
-            if let Ok(threshold) = val.parse::<f64>() {
-                config.sharp_limit_threshold = threshold;
-            }
-        }
-
-        // SIM_TICK_PRECISION
-        if let Ok(val) = env::var("SIM_TICK_PRECISION") {
-            config.tick_precision = match val.to_lowercase().as_str() {
-                "micro" => TickPrecision::Microsecond,
-                "nano" => TickPrecision::Nanosecond,
-                _ => TickPrecision::Millisecond,
-            };
-        }
-
-        // SIM_MAX_SPEED_MULTIPLIER
-        if let Ok(val) = env::var("SIM_MAX_SPEED_MULTIPLIER") {
-            if let Ok(multiplier) = val.parse::<f64>() {
-                config.max_speed_multiplier = multiplier;
-            }
-        }
-
-        // SIM_MEMORY_LIMIT_MB
-        if let Ok(val) = env::var("SIM_MEMORY_LIMIT_MB") {
-            if let Ok(limit) = val.parse::<u64>() {
-                config.memory_limit_mb = limit;
-            }
-        }
-
-        // SIM_ENABLE_MONITORING
-        if let Ok(val) = env::var("SIM_ENABLE_MONITORING") {
-            config.enable_monitoring = match val.to_lowercase().as_str() {
-                "false" | "0" | "no" => false,
-                _ => true,
-            };
-        }
-
-        // SIM_LOG_LEVEL
-        if let Ok(val) = env::var("SIM_LOG_LEVEL") {
-            config.log_level = match val.to_lowercase().as_str() {
-                "error" => LogLevel::Error,
-                "warn" => LogLevel::Warn,
-                "debug" => LogLevel::Debug,
-                "trace" => LogLevel::Trace,
-                _ => LogLevel::Info,
-            };
-        }
-
-        // SIM_DATA_SOURCE_TYPE
-        if let Ok(val) = env::var("SIM_DATA_SOURCE_TYPE") {
-            config.data_source.source_type = match val.to_lowercase().as_str() {
-                "s3" => DataSourceType::S3,
-                "database" | "db" => DataSourceType::Database,
-                "websocket" | "ws" => DataSourceType::WebSocket,
-                _ => DataSourceType::Local,
-            };
-        }
-
-        // SIM_DATA_SOURCE_PATH
-        if let Ok(val) = env::var("SIM_DATA_SOURCE_PATH") {
-            config.data_source.connection_string = val;
-        }
-
-        // SIM_DATA_SOURCE_AUTH_TOKEN
-        if let Ok(val) = env::var("SIM_DATA_SOURCE_AUTH_TOKEN") {
-            config.data_source.auth_token = Some(val);
-        }
-
-        // SIM_DATA_SOURCE_COMPRESSION
-        if let Ok(val) = env::var("SIM_DATA_SOURCE_COMPRESSION") {
-            config.data_source.compression = match val.to_lowercase().as_str() {
-                "false" | "0" | "no" => false,
-                _ => true,
-            };
+impl From<&BacktesterSection> for BacktesterControls {
+    fn from(section: &BacktesterSection) -> Self {
+        let defaults = Self::default();
+        Self {
+            sim_latency_jitter: section.sim_latency_jitter,
+            sharp_limit_threshold: section.sharp_limit_threshold,
+            max_speed_multiplier: section.max_speed_multiplier,
+            memory_limit_mb: section.memory_limit_mb,
+            data_source: DataSource {
+                connection_string: section.data_path.clone(),
+                ..defaults.data_source
+            },
+            ..defaults
         }
+    }
+}
 
-        config
+impl BacktesterControls {
+    /// Load a named profile from SIM_PROFILES_PATH (default `backtest_profiles.toml`);
+    /// SIM_* variables are not applied, so the run is reproducible from the file alone
+    pub fn from_profile(name: &str) -> Result<Self> {
+        let path = env::var("SIM_PROFILES_PATH").unwrap_or_else(|_| DEFAULT_PROFILES_PATH.to_string());
+        BacktestProfiles::load(&path)?.controls(name)
     }
 
     /// Validate configuration
@@ -197,6 +387,10 @@ impl BacktesterControls {
             return Err("SIM_MAX_SPEED_MULTIPLIER must be positive".to_string());
         }
 
+        if self.replay_speed.multiplier().is_some_and(|m| !(m.is_finite() && m > 0.0)) {
+            return Err("SIM_REPLAY_SPEED must be max or a positive multiplier".to_string());
+        }
+
         if self.memory_limit_mb < 64 {
             return Err("SIM_MEMORY_LIMIT_MB must be at least 64".to_string());
         }
@@ -205,6 +399,20 @@ impl BacktesterControls {
             return Err("SIM_DATA_SOURCE_PATH cannot be empty".to_string());
         }
 
+        if let Some(range) = self.date_range {
+            if range.start_ns > range.end_ns {
+                return Err("date range start must not be after end".to_string());
+            }
+        }
+
+        if self.risk.initial_capital <= 0.0 || self.risk.max_position_size <= 0.0 {
+            return Err("initial capital and max position size must be positive".to_string());
+        }
+
+        if !(0.0..1.0).contains(&self.risk.transaction_cost) {
+            return Err("transaction cost must be in [0, 1)".to_string());
+        }
+
         Ok(())
     }
 
@@ -239,6 +447,8 @@ impl BacktesterControls {
              SIM_TICK_PRECISION={}\n\n\
              # Maximum simulation speed multiplier\n\
              SIM_MAX_SPEED_MULTIPLIER={}\n\n\
+             # Replay pacing (max, or a wall-clock multiplier such as 10x)\n\
+             SIM_REPLAY_SPEED={}\n\n\
              # Memory limit for tick buffer (MB)\n\
              SIM_MEMORY_LIMIT_MB={}\n\n\
              # Enable real-time monitoring\n\
@@ -252,7 +462,9 @@ impl BacktesterControls {
              # Data source authentication token (optional)\n\
              SIM_DATA_SOURCE_AUTH_TOKEN={}\n\n\
              # Data source compression enabled\n\
-             SIM_DATA_SOURCE_COMPRESSION={}\n",
+             SIM_DATA_SOURCE_COMPRESSION={}\n\n\
+             # RNG seed for reproducible runs (blank = random)\n\
+             SIM_SEED={}\n",
             self.sim_latency_jitter,
             self.sharp_limit_threshold,
             match self.tick_precision {
@@ -261,13 +473,15 @@ impl BacktesterControls {
                 TickPrecision::Nanosecond => "nano",
             },
             self.max_speed_multiplier,
+            self.replay_speed.multiplier().map_or("max".to_string(), |m| format!("{}x", m)),
             self.memory_limit_mb,
             self.enable_monitoring,
             self.log_level,
             self.data_source.source_type,
             self.data_source.connection_string,
             self.data_source.auth_token.as_deref().unwrap_or(""),
-            self.data_source.compression
+            self.data_source.compression,
+            self.seed.map(|s| s.to_string()).unwrap_or_default()
         )
     }
 }
@@ -287,12 +501,18 @@ pub struct PatternVerification {
     pub primary_constraint: String,
     /// Current verified ROI
     pub verified_roi_percent: f64,
+    /// Alpha half-life measured from the archive (None until decay is observed)
+    #[serde(default)]
+    pub verified_half_life_weeks: Option<f64>,
+    /// Replayed signals behind the verified figures
+    #[serde(default)]
+    pub samples: usize,
     /// Verification status
     pub verification_status: VerificationStatus,
 }
 
 /// Verification status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VerificationStatus {
     Pending,
     InProgress,
@@ -311,6 +531,8 @@ impl PatternVerification {
             alpha_half_life_weeks: half_life_weeks,
             primary_constraint: constraint.to_string(),
             verified_roi_percent: 0.0,
+            verified_half_life_weeks: None,
+            samples: 0,
             verification_status: VerificationStatus::Pending,
         }
     }
@@ -339,7 +561,6 @@ pub fn get_default_pattern_verifications() -> Vec<PatternVerification> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::env;
 
     #[test]
     fn test_backtester_controls_default() {
@@ -350,22 +571,20 @@ mod tests {
     }
 
     #[test]
-    fn test_backtester_controls_from_env() {
-        // Set environment variables
-        env::set_var("SIM_LATENCY_JITTER", "10");
-        env::set_var("SIM_SHARP_LIMIT_THRESHOLD", "0.7");
-        env::set_var("SIM_TICK_PRECISION", "micro");
+    fn test_backtester_controls_from_section() {
+        let section = BacktesterSection {
+            sim_latency_jitter: 10.0,
+            sharp_limit_threshold: 0.7,
+            data_path: "/tmp/ticks".to_string(),
+            ..BacktesterSection::default()
+        };
 
-        let config = BacktesterControls::from_env();
+        let config = BacktesterControls::from(&section);
 
         assert_eq!(config.sim_latency_jitter, 10.0);
         assert_eq!(config.sharp_limit_threshold, 0.7);
-        assert!(matches!(config.tick_precision, TickPrecision::Microsecond));
-
-        // Clean up
-        env::remove_var("SIM_LATENCY_JITTER");
-        env::remove_var("SIM_SHARP_LIMIT_THRESHOLD");
-        env::remove_var("SIM_TICK_PRECISION");
+        assert_eq!(config.data_source.connection_string, "/tmp/ticks");
+        assert!(matches!(config.tick_precision, TickPrecision::Millisecond));
     }
 
     #[test]
@@ -413,6 +632,75 @@ mod tests {
         assert!(verification.is_target_met());
     }
 
+    const PROFILES: &str = r#"
+        [profiles.nba-playoffs]
+        description = "NBA playoffs"
+        seed = 42
+        patterns = [73, 75]
+        start = "2024-04-20"
+        end = "2024-04-21"
+        tick_precision = "nano"
+        latency_jitter_ms = 8.0
+        replay_speed = "60x"
+
+        [profiles.nba-playoffs.data_source]
+        type = "s3"
+        path = "s3://ticks/nba"
+
+        [profiles.nba-playoffs.risk]
+        initial_capital = 25000.0
+        max_position_size = 500.0
+
+        [profiles.bare]
+    "#;
+
+    #[test]
+    fn test_profile_resolves_onto_defaults() {
+        let profiles = BacktestProfiles::parse(PROFILES).unwrap();
+        assert_eq!(profiles.names().collect::<Vec<_>>(), vec!["bare", "nba-playoffs"]);
+
+        let c = profiles.controls("nba-playoffs").unwrap();
+        assert_eq!(c.profile.as_deref(), Some("nba-playoffs"));
+        assert_eq!(c.seed, Some(42));
+        assert_eq!(c.patterns, vec![73, 75]);
+        assert!(matches!(c.tick_precision, TickPrecision::Nanosecond));
+        assert_eq!(c.sim_latency_jitter, 8.0);
+        assert_eq!(c.replay_speed, ReplaySpeed::Accelerated(60.0));
+        assert!(matches!(c.data_source.source_type, DataSourceType::S3));
+        assert_eq!(c.data_source.connection_string, "s3://ticks/nba");
+        assert!(c.data_source.compression);
+        assert_eq!(c.risk.initial_capital, 25000.0);
+        assert_eq!(c.risk.max_position_size, 500.0);
+        assert_eq!(c.risk.account_lifespan_days, 30);
+
+        // 2024-04-20T00:00Z through the last nanosecond of 2024-04-21
+        let range = c.date_range.unwrap();
+        assert_eq!(range.start_ns, 1_713_571_200_000_000_000);
+        assert_eq!(range.end_ns, 1_713_744_000_000_000_000 - 1);
+        assert!(range.contains(range.end_ns) && !range.contains(range.end_ns + 1));
+
+        let bare = profiles.controls("bare").unwrap();
+        assert_eq!(bare.date_range, None);
+        assert_eq!(bare.sim_latency_jitter, BacktesterControls::default().sim_latency_jitter);
+        assert_eq!(bare.replay_speed, ReplaySpeed::AsFastAsPossible);
+    }
+
+    #[test]
+    fn test_profile_errors() {
+        let profiles = BacktestProfiles::parse(PROFILES).unwrap();
+        let err = profiles.controls("missing").unwrap_err().to_string();
+        assert!(err.contains("nba-playoffs"), "{}", err);
+
+        assert!(BacktestProfiles::parse("[profiles.x]\nbogus = 1").is_err());
+
+        let bad = BacktestProfiles::parse("[profiles.x]\nstart = \"2024-05-01\"\nend = \"2024-04-01\"").unwrap();
+        assert!(bad.controls("x").is_err());
+        let bad = BacktestProfiles::parse("[profiles.x]\nstart = \"yesterday\"").unwrap();
+        assert!(bad.controls("x").is_err());
+        let bad = BacktestProfiles::parse("[profiles.x]\nreplay_speed = \"0x\"").unwrap();
+        assert!(bad.controls("x").is_err());
+    }
+
     #[test]
     fn test_default_pattern_verifications() {
         let verifications = get_default_pattern_verifications();
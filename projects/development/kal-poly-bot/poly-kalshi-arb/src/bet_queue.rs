@@ -0,0 +1,813 @@
+// src/bet_queue.rs
+// Bet queue consumer - executes the triggers Bun workers publish on the `bet:queue` Redis stream:
+// a consumer group hands out entries, an in-progress lease and a done key per trigger drop
+// duplicates, failed bets retry with backoff then go to a dead-letter stream, and the group's lag
+// is sampled every poll
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::bun_worker_integration::TriggerData;
+use crate::error::{ExecutionError, Retryable, StateStoreError};
+use crate::redis_client::{unexpected, RedisClient, Resp};
+
+/// Stream the Bun tick processor (src/bun/tick_processor.bun.ts) publishes triggers on
+pub const BET_QUEUE_STREAM: &str = "bet:queue";
+
+/// One stream entry: its id and field / value pairs in publish order
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl StreamEntry {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// A trigger as published on the stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BetMessage {
+    pub pattern_id: u16,
+    pub book: String,
+    pub target_price: f64,
+    pub edge: f64,
+    pub confidence: f64,
+    pub size: f64,
+    /// Producer's timestamp for the tick that fired the trigger
+    pub timestamp: u64,
+}
+
+impl BetMessage {
+    /// Parse an entry's fields (pattern, book, target, edge, confidence, size, timestamp)
+    pub fn from_entry(entry: &StreamEntry) -> Result<Self, String> {
+        fn parse<T: std::str::FromStr>(entry: &StreamEntry, name: &str) -> Result<T, String> {
+            let value = entry.field(name).ok_or_else(|| format!("missing field '{}'", name))?;
+            value.parse().map_err(|_| format!("bad {} '{}'", name, value))
+        }
+        Ok(Self {
+            pattern_id: parse(entry, "pattern")?,
+            book: entry.field("book").ok_or("missing field 'book'")?.to_string(),
+            target_price: parse(entry, "target")?,
+            edge: parse(entry, "edge")?,
+            confidence: parse(entry, "confidence")?,
+            size: parse(entry, "size")?,
+            timestamp: parse(entry, "timestamp")?,
+        })
+    }
+
+    /// A Rust worker's trigger, stamped at `timestamp`
+    pub fn from_trigger(trigger: &TriggerData, timestamp: u64) -> Self {
+        Self {
+            pattern_id: trigger.pattern_id,
+            book: trigger.book.clone(),
+            target_price: trigger.target_price,
+            edge: trigger.expected_edge,
+            confidence: trigger.confidence,
+            size: trigger.size,
+            timestamp,
+        }
+    }
+
+    /// Fields in the Bun worker's layout
+    pub fn fields(&self) -> Vec<(String, String)> {
+        [
+            ("pattern", self.pattern_id.to_string()),
+            ("book", self.book.clone()),
+            ("target", self.target_price.to_string()),
+            ("edge", self.edge.to_string()),
+            ("confidence", self.confidence.to_string()),
+            ("size", self.size.to_string()),
+            ("timestamp", self.timestamp.to_string()),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+    }
+
+    /// Same pattern, book and tick - a republished trigger maps to the same key even though
+    /// the stream gives it a new entry id
+    pub fn idempotency_key(&self) -> String {
+        format!("{}:{}:{}", self.pattern_id, self.book, self.timestamp)
+    }
+}
+
+/// Consumer group backlog
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamLag {
+    /// Entries delivered to the group but not acknowledged
+    pub pending: u64,
+    /// Entries in the stream
+    pub length: u64,
+    /// Id of the oldest unacknowledged entry
+    pub oldest_pending: Option<String>,
+}
+
+/// Stream operations the consumer needs (Redis Streams; an in-memory one backs the tests)
+#[async_trait::async_trait]
+pub trait BetStream: Send + Sync {
+    /// Create `group` at the stream's tail, and the stream, unless the group exists
+    async fn create_group(&self, stream: &str, group: &str) -> Result<(), StateStoreError>;
+
+    /// Up to `count` entries for `consumer`: its own unacknowledged ones when `pending`, else
+    /// new ones, waiting up to `block` for them
+    async fn read_group(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        count: usize,
+        block: Duration,
+        pending: bool,
+    ) -> Result<Vec<StreamEntry>, StateStoreError>;
+
+    async fn ack(&self, stream: &str, group: &str, id: &str) -> Result<(), StateStoreError>;
+
+    /// Append an entry; returns its id
+    async fn add(&self, stream: &str, fields: &[(String, String)]) -> Result<String, StateStoreError>;
+
+    /// Claim `key` for `ttl` unless it's set; false when it already is
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, StateStoreError>;
+
+    /// Set `key` for `ttl`, whether or not it's set
+    async fn set_key(&self, key: &str, ttl: Duration) -> Result<(), StateStoreError>;
+
+    async fn key_exists(&self, key: &str) -> Result<bool, StateStoreError>;
+
+    async fn release(&self, key: &str) -> Result<(), StateStoreError>;
+
+    async fn lag(&self, stream: &str, group: &str) -> Result<StreamLag, StateStoreError>;
+}
+
+pub type SharedBetStream = Arc<dyn BetStream>;
+
+/// Places the bets read off the queue
+#[async_trait::async_trait]
+pub trait BetExecutor: Send + Sync {
+    async fn execute(&self, bet: &BetMessage) -> Result<(), ExecutionError>;
+}
+
+pub type SharedBetExecutor = Arc<dyn BetExecutor>;
+
+/// Consumer settings
+#[derive(Debug, Clone)]
+pub struct BetQueueConfig {
+    pub stream: String,
+    pub group: String,
+    /// Keep stable across restarts: entries left unacknowledged are re-read under this name
+    pub consumer: String,
+    /// Where malformed entries and bets out of attempts go
+    pub dead_letter: String,
+    pub batch: usize,
+    /// Longest wait for new entries per poll
+    pub block: Duration,
+    /// Attempts per bet before it's dead-lettered (non-retryable errors get one)
+    pub max_attempts: u32,
+    pub backoff_initial: Duration,
+    pub backoff_max: Duration,
+    /// How long an executed bet's done key is kept
+    pub idempotency_ttl: Duration,
+    /// How long an attempt holds a bet's in-progress key. A consumer that dies mid-bet leaves
+    /// the key to lapse, after which its reclaimed entry is retried
+    pub lease_ttl: Duration,
+}
+
+impl Default for BetQueueConfig {
+    fn default() -> Self {
+        Self {
+            stream: BET_QUEUE_STREAM.to_string(),
+            group: "bet-executors".to_string(),
+            consumer: "executor-1".to_string(),
+            dead_letter: format!("{}:dead", BET_QUEUE_STREAM),
+            batch: 32,
+            block: Duration::from_secs(1),
+            max_attempts: 5,
+            backoff_initial: Duration::from_millis(200),
+            backoff_max: Duration::from_secs(10),
+            idempotency_ttl: Duration::from_secs(24 * 3600),
+            lease_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+impl BetQueueConfig {
+    /// Wait before retry number `attempt` (1-based): doubling from `backoff_initial`, capped
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff_initial.saturating_mul(factor).min(self.backoff_max)
+    }
+}
+
+/// Consumer counters and the latest lag sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BetQueueStats {
+    pub consumed: u64,
+    pub executed: u64,
+    /// Entries whose bet was already executed
+    pub duplicates: u64,
+    /// Entries put off because another attempt holds their in-progress key
+    pub deferred: u64,
+    pub retries: u64,
+    pub dead_lettered: u64,
+    /// Failed bets waiting for their next attempt
+    pub awaiting_retry: usize,
+    /// Entries delivered to the group but not acknowledged
+    pub pending: u64,
+    pub stream_length: u64,
+    /// Age of the oldest unacknowledged entry, from its id's timestamp
+    pub oldest_pending_age_ms: Option<u64>,
+}
+
+/// A failed bet held (unacknowledged) until its next attempt
+struct Retry {
+    entry: StreamEntry,
+    attempts: u32,
+    due: Instant,
+}
+
+/// Reads the bet queue through a consumer group and executes each trigger once
+pub struct BetQueueConsumer {
+    config: BetQueueConfig,
+    stream: SharedBetStream,
+    executor: SharedBetExecutor,
+    retries: Vec<Retry>,
+    stats: Arc<Mutex<BetQueueStats>>,
+    started: bool,
+}
+
+impl BetQueueConsumer {
+    pub fn new(config: BetQueueConfig, stream: SharedBetStream, executor: SharedBetExecutor) -> Self {
+        Self {
+            config,
+            stream,
+            executor,
+            retries: Vec::new(),
+            stats: Arc::new(Mutex::new(BetQueueStats::default())),
+            started: false,
+        }
+    }
+
+    pub fn stats(&self) -> BetQueueStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Stats handle for status probes, readable while `run` owns the consumer
+    pub fn stats_handle(&self) -> Arc<Mutex<BetQueueStats>> {
+        self.stats.clone()
+    }
+
+    /// Poll until the stream fails; stream errors back off and the poll is retried
+    pub async fn run(mut self) {
+        info!("[BET_QUEUE] Consuming {} as {}/{}", self.config.stream, self.config.group, self.config.consumer);
+        let mut failures = 0;
+        loop {
+            match self.poll().await {
+                Ok(_) => failures = 0,
+                Err(e) => {
+                    failures += 1;
+                    let wait = self.config.backoff(failures);
+                    warn!("[BET_QUEUE] Poll failed ({}), retrying in {:?}", e, wait);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// One round: on the first, create the group and re-read entries this consumer left
+    /// unacknowledged; then retry failed bets that are due, read new entries, and sample lag.
+    /// Returns how many entries were handled.
+    pub async fn poll(&mut self) -> Result<usize, StateStoreError> {
+        let mut handled = 0;
+        if !self.started {
+            self.stream.create_group(&self.config.stream, &self.config.group).await?;
+            let unacked = self.read(Duration::ZERO, true).await?;
+            if !unacked.is_empty() {
+                info!("[BET_QUEUE] Re-reading {} unacknowledged entries", unacked.len());
+            }
+            self.stats.lock().unwrap().consumed += unacked.len() as u64;
+            for entry in unacked {
+                self.handle(entry, 0).await?;
+                handled += 1;
+            }
+            self.started = true;
+        }
+
+        let now = Instant::now();
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.retries).into_iter().partition(|r| r.due <= now);
+        self.retries = waiting;
+        for retry in due {
+            self.handle(retry.entry, retry.attempts).await?;
+            handled += 1;
+        }
+
+        let block = match self.retries.iter().map(|r| r.due).min() {
+            Some(next) => self.config.block.min(next.saturating_duration_since(Instant::now())),
+            None => self.config.block,
+        };
+        let fresh = self.read(block, false).await?;
+        self.stats.lock().unwrap().consumed += fresh.len() as u64;
+        for entry in fresh {
+            self.handle(entry, 0).await?;
+            handled += 1;
+        }
+
+        self.sample_lag().await?;
+        Ok(handled)
+    }
+
+    async fn read(&self, block: Duration, pending: bool) -> Result<Vec<StreamEntry>, StateStoreError> {
+        let config = &self.config;
+        self.stream.read_group(&config.stream, &config.group, &config.consumer, config.batch, block, pending).await
+    }
+
+    /// Execute one entry after `attempts` earlier failed attempts. The bet's in-progress key is
+    /// claimed before executing and its done key set only once the executor succeeds; a failure
+    /// releases the in-progress key. An entry whose in-progress key is held is put off, not
+    /// acknowledged, so if its holder died the entry is retried once the lease lapses. A crash
+    /// between a successful execute and the done key errs on betting again.
+    async fn handle(&mut self, entry: StreamEntry, attempts: u32) -> Result<(), StateStoreError> {
+        let bet = match BetMessage::from_entry(&entry) {
+            Ok(bet) => bet,
+            Err(reason) => return self.dead_letter(&entry, &reason, attempts).await,
+        };
+
+        let key = bet.idempotency_key();
+        let lease = format!("{}:inflight:{}", self.config.stream, key);
+        let done = format!("{}:done:{}", self.config.stream, key);
+        if !self.stream.claim(&lease, self.config.lease_ttl).await? {
+            debug!("[BET_QUEUE] {} ({}) is in progress elsewhere; deferring", entry.id, key);
+            self.stats.lock().unwrap().deferred += 1;
+            let due = Instant::now() + self.config.backoff(attempts.max(1));
+            self.retries.push(Retry { entry, attempts, due });
+            return Ok(());
+        }
+        // Checked under the lease: the done key is set before a finished attempt lets go of it
+        if self.stream.key_exists(&done).await? {
+            debug!("[BET_QUEUE] Duplicate {} ({})", entry.id, key);
+            self.stats.lock().unwrap().duplicates += 1;
+            self.stream.release(&lease).await?;
+            return self.stream.ack(&self.config.stream, &self.config.group, &entry.id).await;
+        }
+
+        let result = self.executor.execute(&bet).await;
+        if result.is_ok() {
+            self.stream.set_key(&done, self.config.idempotency_ttl).await?;
+        }
+        self.stream.release(&lease).await?;
+        match result {
+            Ok(()) => {
+                self.stats.lock().unwrap().executed += 1;
+                self.stream.ack(&self.config.stream, &self.config.group, &entry.id).await
+            }
+            Err(e) => {
+                let attempts = attempts + 1;
+                if !e.is_retryable() || attempts >= self.config.max_attempts {
+                    return self.dead_letter(&entry, &e.to_string(), attempts).await;
+                }
+                let wait = e.retry_after().unwrap_or_else(|| self.config.backoff(attempts));
+                warn!("[BET_QUEUE] Bet {} failed (attempt {}): {}; retrying in {:?}", entry.id, attempts, e, wait);
+                self.stats.lock().unwrap().retries += 1;
+                self.retries.push(Retry { entry, attempts, due: Instant::now() + wait });
+                Ok(())
+            }
+        }
+    }
+
+    /// Move an entry to the dead-letter stream with why it failed, then acknowledge it
+    async fn dead_letter(&self, entry: &StreamEntry, reason: &str, attempts: u32) -> Result<(), StateStoreError> {
+        warn!("[BET_QUEUE] Dead-lettering {} after {} attempts: {}", entry.id, attempts, reason);
+        let mut fields = entry.fields.clone();
+        fields.push(("source_id".to_string(), entry.id.clone()));
+        fields.push(("error".to_string(), reason.to_string()));
+        fields.push(("attempts".to_string(), attempts.to_string()));
+        self.stream.add(&self.config.dead_letter, &fields).await?;
+        self.stream.ack(&self.config.stream, &self.config.group, &entry.id).await?;
+        self.stats.lock().unwrap().dead_lettered += 1;
+        Ok(())
+    }
+
+    async fn sample_lag(&self) -> Result<(), StateStoreError> {
+        let lag = self.stream.lag(&self.config.stream, &self.config.group).await?;
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let mut stats = self.stats.lock().unwrap();
+        stats.awaiting_retry = self.retries.len();
+        stats.pending = lag.pending;
+        stats.stream_length = lag.length;
+        stats.oldest_pending_age_ms = lag.oldest_pending.as_deref().and_then(entry_ms).map(|ms| now_ms.saturating_sub(ms));
+        Ok(())
+    }
+}
+
+/// Millisecond timestamp of a stream entry id ("<ms>-<seq>")
+fn entry_ms(id: &str) -> Option<u64> {
+    id.split('-').next()?.parse().ok()
+}
+
+/// XREADGROUP reply: [[stream, [[id, [field, value, ...]], ...]]]
+fn parse_entries(reply: Resp) -> Result<Vec<StreamEntry>, StateStoreError> {
+    let mut entries = Vec::new();
+    for stream in reply.items() {
+        let Some(batch) = stream.items().pop() else { continue };
+        for entry in batch.items() {
+            let mut parts = entry.items().into_iter();
+            let Some(id) = parts.next().and_then(Resp::text) else {
+                return Err(StateStoreError::Corrupt("stream entry without an id".to_string()));
+            };
+            // Entries trimmed from the stream come back without fields; they dead-letter as malformed
+            let values: Vec<String> = parts.next().map(Resp::items).unwrap_or_default()
+                .into_iter()
+                .filter_map(Resp::text)
+                .collect();
+            let fields = values.chunks_exact(2).map(|kv| (kv[0].clone(), kv[1].clone())).collect();
+            entries.push(StreamEntry { id, fields });
+        }
+    }
+    Ok(entries)
+}
+
+/// Redis Streams over one RESP2 connection (reconnected after errors)
+pub struct RedisBetStream {
+    client: RedisClient,
+}
+
+impl RedisBetStream {
+    /// `addr` is host:port; redis://host:port is accepted too
+    pub fn new(addr: &str) -> Self {
+        Self { client: RedisClient::new(addr) }
+    }
+
+    async fn command(&self, args: &[&str]) -> Result<Resp, StateStoreError> {
+        self.client.command(args).await
+    }
+}
+
+#[async_trait::async_trait]
+impl BetStream for RedisBetStream {
+    async fn create_group(&self, stream: &str, group: &str) -> Result<(), StateStoreError> {
+        match self.command(&["XGROUP", "CREATE", stream, group, "$", "MKSTREAM"]).await {
+            Err(StateStoreError::Backend { message, .. }) if message.starts_with("BUSYGROUP") => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    async fn read_group(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        count: usize,
+        block: Duration,
+        pending: bool,
+    ) -> Result<Vec<StreamEntry>, StateStoreError> {
+        let count = count.to_string();
+        let block = block.as_millis().max(1).to_string();
+        let reply = if pending {
+            self.command(&["XREADGROUP", "GROUP", group, consumer, "COUNT", &count, "STREAMS", stream, "0"]).await?
+        } else {
+            self.command(&["XREADGROUP", "GROUP", group, consumer, "COUNT", &count, "BLOCK", &block, "STREAMS", stream, ">"]).await?
+        };
+        parse_entries(reply)
+    }
+
+    async fn ack(&self, stream: &str, group: &str, id: &str) -> Result<(), StateStoreError> {
+        self.command(&["XACK", stream, group, id]).await.map(|_| ())
+    }
+
+    async fn add(&self, stream: &str, fields: &[(String, String)]) -> Result<String, StateStoreError> {
+        let mut args = vec!["XADD", stream, "*"];
+        for (k, v) in fields {
+            args.push(k);
+            args.push(v);
+        }
+        let reply = self.command(&args).await?;
+        reply.clone().text().ok_or_else(|| unexpected("XADD", &reply))
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, StateStoreError> {
+        let ms = ttl.as_millis().max(1).to_string();
+        match self.command(&["SET", key, "1", "NX", "PX", &ms]).await? {
+            Resp::Simple(_) => Ok(true),
+            Resp::Bulk(None) => Ok(false),
+            reply => Err(unexpected("SET NX", &reply)),
+        }
+    }
+
+    async fn set_key(&self, key: &str, ttl: Duration) -> Result<(), StateStoreError> {
+        let ms = ttl.as_millis().max(1).to_string();
+        self.command(&["SET", key, "1", "PX", &ms]).await.map(|_| ())
+    }
+
+    async fn key_exists(&self, key: &str) -> Result<bool, StateStoreError> {
+        let reply = self.command(&["EXISTS", key]).await?;
+        reply.int().map(|n| n > 0).ok_or_else(|| unexpected("EXISTS", &reply))
+    }
+
+    async fn release(&self, key: &str) -> Result<(), StateStoreError> {
+        self.command(&["DEL", key]).await.map(|_| ())
+    }
+
+    async fn lag(&self, stream: &str, group: &str) -> Result<StreamLag, StateStoreError> {
+        let length = self.command(&["XLEN", stream]).await?;
+        let length = length.int().ok_or_else(|| unexpected("XLEN", &length))?;
+        // Summary form: [count, oldest id, newest id, [[consumer, count], ...]]
+        let summary = self.command(&["XPENDING", stream, group]).await?;
+        let mut parts = summary.clone().items().into_iter();
+        let pending = parts.next().and_then(|r| r.int()).ok_or_else(|| unexpected("XPENDING", &summary))?;
+        Ok(StreamLag {
+            pending: pending.max(0) as u64,
+            length: length.max(0) as u64,
+            oldest_pending: parts.next().and_then(Resp::text),
+        })
+    }
+}
+
+/// In-memory stream with consumer-group semantics, for tests and dry runs
+#[derive(Default)]
+pub struct MemoryBetStream {
+    state: Mutex<MemoryStreams>,
+}
+
+#[derive(Default)]
+struct MemoryStreams {
+    streams: HashMap<String, Vec<StreamEntry>>,
+    /// Per (stream, group): entries read so far and unacknowledged ids by consumer
+    groups: HashMap<(String, String), MemoryGroup>,
+    /// Keys and when they expire
+    keys: HashMap<String, Instant>,
+    next_id: u64,
+}
+
+#[derive(Default)]
+struct MemoryGroup {
+    delivered: usize,
+    pending: Vec<(String, String)>,
+}
+
+impl MemoryBetStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self, stream: &str) -> Vec<StreamEntry> {
+        self.state.lock().unwrap().streams.get(stream).cloned().unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl BetStream for MemoryBetStream {
+    async fn create_group(&self, stream: &str, group: &str) -> Result<(), StateStoreError> {
+        let mut state = self.state.lock().unwrap();
+        let delivered = state.streams.entry(stream.to_string()).or_default().len();
+        state.groups.entry((stream.to_string(), group.to_string())).or_insert(MemoryGroup { delivered, pending: Vec::new() });
+        Ok(())
+    }
+
+    async fn read_group(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        count: usize,
+        _block: Duration,
+        pending: bool,
+    ) -> Result<Vec<StreamEntry>, StateStoreError> {
+        let mut state = self.state.lock().unwrap();
+        let entries = state.streams.get(stream).cloned().unwrap_or_default();
+        let group = state.groups.get_mut(&(stream.to_string(), group.to_string()))
+            .ok_or_else(|| StateStoreError::Backend { backend: "memory", message: "NOGROUP".to_string() })?;
+        if pending {
+            let ids: Vec<&String> = group.pending.iter().filter(|(_, c)| c == consumer).map(|(id, _)| id).collect();
+            return Ok(entries.into_iter().filter(|e| ids.contains(&&e.id)).take(count).collect());
+        }
+        let fresh: Vec<StreamEntry> = entries.into_iter().skip(group.delivered).take(count).collect();
+        group.delivered += fresh.len();
+        group.pending.extend(fresh.iter().map(|e| (e.id.clone(), consumer.to_string())));
+        Ok(fresh)
+    }
+
+    async fn ack(&self, stream: &str, group: &str, id: &str) -> Result<(), StateStoreError> {
+        if let Some(group) = self.state.lock().unwrap().groups.get_mut(&(stream.to_string(), group.to_string())) {
+            group.pending.retain(|(pending, _)| pending != id);
+        }
+        Ok(())
+    }
+
+    async fn add(&self, stream: &str, fields: &[(String, String)]) -> Result<String, StateStoreError> {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = format!("{}-0", state.next_id);
+        state.streams.entry(stream.to_string()).or_default().push(StreamEntry { id: id.clone(), fields: fields.to_vec() });
+        Ok(id)
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, StateStoreError> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        if state.keys.get(key).is_some_and(|expiry| *expiry > now) {
+            return Ok(false);
+        }
+        state.keys.insert(key.to_string(), now + ttl);
+        Ok(true)
+    }
+
+    async fn set_key(&self, key: &str, ttl: Duration) -> Result<(), StateStoreError> {
+        self.state.lock().unwrap().keys.insert(key.to_string(), Instant::now() + ttl);
+        Ok(())
+    }
+
+    async fn key_exists(&self, key: &str) -> Result<bool, StateStoreError> {
+        Ok(self.state.lock().unwrap().keys.get(key).is_some_and(|expiry| *expiry > Instant::now()))
+    }
+
+    async fn release(&self, key: &str) -> Result<(), StateStoreError> {
+        self.state.lock().unwrap().keys.remove(key);
+        Ok(())
+    }
+
+    async fn lag(&self, stream: &str, group: &str) -> Result<StreamLag, StateStoreError> {
+        let state = self.state.lock().unwrap();
+        let length = state.streams.get(stream).map_or(0, Vec::len) as u64;
+        let group = state.groups.get(&(stream.to_string(), group.to_string()));
+        Ok(StreamLag {
+            pending: group.map_or(0, |g| g.pending.len()) as u64,
+            length,
+            oldest_pending: group.and_then(|g| g.pending.first()).map(|(id, _)| id.clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis_client::read_resp;
+    use crate::types::MarketId;
+
+    /// Fails the first `failures` attempts per bet with `error`, then succeeds
+    struct ScriptedExecutor {
+        failures: u32,
+        error: fn() -> ExecutionError,
+        attempts: Mutex<HashMap<String, u32>>,
+        executed: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedExecutor {
+        fn new(failures: u32, error: fn() -> ExecutionError) -> Arc<Self> {
+            Arc::new(Self { failures, error, attempts: Mutex::default(), executed: Mutex::default() })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BetExecutor for ScriptedExecutor {
+        async fn execute(&self, bet: &BetMessage) -> Result<(), ExecutionError> {
+            let mut attempts = self.attempts.lock().unwrap();
+            let n = attempts.entry(bet.idempotency_key()).or_default();
+            *n += 1;
+            if *n <= self.failures {
+                return Err((self.error)());
+            }
+            self.executed.lock().unwrap().push(bet.idempotency_key());
+            Ok(())
+        }
+    }
+
+    fn bet(pattern_id: u16, timestamp: u64) -> BetMessage {
+        BetMessage { pattern_id, book: "BOOK-1".to_string(), target_price: 52.5, edge: 0.04, confidence: 0.8, size: 1000.0, timestamp }
+    }
+
+    fn config() -> BetQueueConfig {
+        BetQueueConfig { backoff_initial: Duration::ZERO, ..BetQueueConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn test_each_trigger_executes_once() {
+        let stream = Arc::new(MemoryBetStream::new());
+        let executor = ScriptedExecutor::new(0, || ExecutionError::UnknownMarket(MarketId(0)));
+        let mut consumer = BetQueueConsumer::new(config(), stream.clone(), executor.clone());
+        consumer.poll().await.unwrap();
+
+        // The same tick published twice (e.g. a producer retry) gets two entry ids
+        for message in [bet(75, 1), bet(75, 1), bet(56, 1)] {
+            stream.add(BET_QUEUE_STREAM, &message.fields()).await.unwrap();
+        }
+        assert_eq!(consumer.poll().await.unwrap(), 3);
+        assert_eq!(executor.executed.lock().unwrap().len(), 2);
+
+        let stats = consumer.stats();
+        assert_eq!((stats.consumed, stats.executed, stats.duplicates), (3, 2, 1));
+        assert_eq!((stats.pending, stats.stream_length, stats.oldest_pending_age_ms), (0, 3, None));
+    }
+
+    #[tokio::test]
+    async fn test_failed_bets_retry_then_dead_letter() {
+        let stream = Arc::new(MemoryBetStream::new());
+        let busy = || ExecutionError::AlreadyInFlight(MarketId(0));
+        let executor = ScriptedExecutor::new(2, busy);
+        let config = BetQueueConfig { max_attempts: 3, ..config() };
+        let mut consumer = BetQueueConsumer::new(config.clone(), stream.clone(), executor.clone());
+        consumer.poll().await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &bet(75, 1).fields()).await.unwrap();
+
+        // Two retryable failures, then it goes through; the entry stays pending meanwhile
+        consumer.poll().await.unwrap();
+        assert_eq!((consumer.stats().retries, consumer.stats().awaiting_retry, consumer.stats().pending), (1, 1, 1));
+        assert!(!stream.key_exists("bet:queue:done:75:BOOK-1:1").await.unwrap());
+        assert!(!stream.key_exists("bet:queue:inflight:75:BOOK-1:1").await.unwrap());
+        consumer.poll().await.unwrap();
+        consumer.poll().await.unwrap();
+        assert_eq!(executor.executed.lock().unwrap().len(), 1);
+        assert_eq!((consumer.stats().retries, consumer.stats().pending), (2, 0));
+
+        // Out of attempts, and a non-retryable error on the first, both dead-letter
+        let stuck = ScriptedExecutor::new(u32::MAX, busy);
+        let mut consumer = BetQueueConsumer::new(BetQueueConfig { group: "stuck".to_string(), ..config.clone() }, stream.clone(), stuck);
+        consumer.poll().await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &bet(75, 2).fields()).await.unwrap();
+        for _ in 0..3 {
+            consumer.poll().await.unwrap();
+        }
+        let rejected = ScriptedExecutor::new(1, || ExecutionError::UnknownMarket(MarketId(0)));
+        let mut rejecting = BetQueueConsumer::new(BetQueueConfig { group: "rejecting".to_string(), ..config }, stream.clone(), rejected);
+        rejecting.poll().await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &bet(75, 3).fields()).await.unwrap();
+        rejecting.poll().await.unwrap();
+
+        let dead = stream.entries("bet:queue:dead");
+        let attempts: Vec<_> = dead.iter().map(|e| e.field("attempts").unwrap().to_string()).collect();
+        assert_eq!(attempts, ["3", "1"]);
+        assert_eq!(BetMessage::from_entry(&dead[0]).unwrap(), bet(75, 2));
+        assert_eq!((consumer.stats().dead_lettered, rejecting.stats().dead_lettered), (1, 1));
+        assert_eq!(consumer.stats().pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_entries_dead_letter_and_unacked_entries_are_reread() {
+        let stream = Arc::new(MemoryBetStream::new());
+        let executor = ScriptedExecutor::new(0, || ExecutionError::UnknownMarket(MarketId(0)));
+        stream.create_group(BET_QUEUE_STREAM, "bet-executors").await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &[("pattern".to_string(), "x".to_string())]).await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &bet(56, 9).fields()).await.unwrap();
+        // A consumer that crashed after reading both
+        let read = stream.read_group(BET_QUEUE_STREAM, "bet-executors", "executor-1", 10, Duration::ZERO, false).await.unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(stream.lag(BET_QUEUE_STREAM, "bet-executors").await.unwrap().pending, 2);
+
+        let mut consumer = BetQueueConsumer::new(config(), stream.clone(), executor.clone());
+        assert_eq!(consumer.poll().await.unwrap(), 2);
+        assert_eq!(executor.executed.lock().unwrap().as_slice(), ["56:BOOK-1:9"]);
+        let dead = stream.entries("bet:queue:dead");
+        assert_eq!(dead[0].field("error"), Some("bad pattern 'x'"));
+        assert_eq!(consumer.stats().pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stale_in_progress_key_defers_then_retries() {
+        let stream = Arc::new(MemoryBetStream::new());
+        let executor = ScriptedExecutor::new(0, || ExecutionError::UnknownMarket(MarketId(0)));
+        stream.create_group(BET_QUEUE_STREAM, "bet-executors").await.unwrap();
+        stream.add(BET_QUEUE_STREAM, &bet(75, 4).fields()).await.unwrap();
+        // A consumer that died mid-bet: the entry is pending and its in-progress key still held
+        stream.read_group(BET_QUEUE_STREAM, "bet-executors", "executor-1", 10, Duration::ZERO, false).await.unwrap();
+        stream.claim("bet:queue:inflight:75:BOOK-1:4", Duration::from_millis(30)).await.unwrap();
+
+        let config = BetQueueConfig { backoff_initial: Duration::from_millis(10), ..config() };
+        let mut consumer = BetQueueConsumer::new(config, stream.clone(), executor.clone());
+        consumer.poll().await.unwrap();
+        assert!(executor.executed.lock().unwrap().is_empty());
+        assert_eq!((consumer.stats().deferred, consumer.stats().pending, consumer.stats().awaiting_retry), (1, 1, 1));
+        assert!(!stream.key_exists("bet:queue:done:75:BOOK-1:4").await.unwrap());
+
+        // Once the lease lapses the entry is retried, executed once, and marked done
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        consumer.poll().await.unwrap();
+        assert_eq!(executor.executed.lock().unwrap().as_slice(), ["75:BOOK-1:4"]);
+        assert!(stream.key_exists("bet:queue:done:75:BOOK-1:4").await.unwrap());
+        assert!(!stream.key_exists("bet:queue:inflight:75:BOOK-1:4").await.unwrap());
+        let stats = consumer.stats();
+        assert_eq!((stats.consumed, stats.executed, stats.pending, stats.awaiting_retry), (1, 1, 0, 0));
+    }
+
+    #[test]
+    fn test_backoff_doubles_to_cap() {
+        let config = BetQueueConfig { backoff_initial: Duration::from_millis(200), backoff_max: Duration::from_secs(1), ..BetQueueConfig::default() };
+        let waits: Vec<_> = (1..=5).map(|n| config.backoff(n).as_millis()).collect();
+        assert_eq!(waits, [200, 400, 800, 1000, 1000]);
+        assert_eq!(entry_ms("1700000000123-4"), Some(1_700_000_000_123));
+    }
+
+    #[tokio::test]
+    async fn test_resp_stream_replies_parse() {
+        let reply = b"*1\r\n*2\r\n$9\r\nbet:queue\r\n*2\r\n*2\r\n$3\r\n1-0\r\n*4\r\n$7\r\npattern\r\n$2\r\n75\r\n$4\r\nbook\r\n$2\r\nB1\r\n*2\r\n$3\r\n2-0\r\n*-1\r\n";
+        let entries = parse_entries(read_resp(&mut &reply[..]).await.unwrap()).unwrap();
+        assert_eq!(entries, vec![
+            StreamEntry { id: "1-0".to_string(), fields: vec![("pattern".to_string(), "75".to_string()), ("book".to_string(), "B1".to_string())] },
+            StreamEntry { id: "2-0".to_string(), fields: Vec::new() },
+        ]);
+        assert_eq!(parse_entries(read_resp(&mut &b"*-1\r\n"[..]).await.unwrap()).unwrap(), Vec::new());
+    }
+}
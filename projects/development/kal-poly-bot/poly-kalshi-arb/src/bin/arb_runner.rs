@@ -0,0 +1,186 @@
+// src/bin/arb_runner.rs
+// Strategy orchestrator - runs feeds -> latency arbitrage -> risk -> execution -> monitoring
+// under the supervisor, wired together by the event bus (see runner::run) until Ctrl-C / SIGTERM.
+//
+// Feed source is the seeded synthetic market generator until venue FeedClients land.
+//
+//   ARB_RUNNER_STATUS_ADDR   status endpoint (default 127.0.0.1:9464; GET /status, /health,
+//                            /healthz component heartbeats and watchdog escalation,
+//                            /flags admin - see feature_flags::FeatureFlags::handle_admin,
+//                            /audit query - see audit_log::AuditLog::handle_admin,
+//                            /archive/ticks, /archive/signals - see tick_store::handle_admin,
+//                            /history/prices, /history/latency, /history/equity downsampled
+//                            chart series - see downsample::HistoryService::handle_admin,
+//                            /dashboard latest snapshot - see monitoring_dashboard::handle_admin;
+//                            /audit, /archive/signals, /history/equity and /dashboard take
+//                            `strategy=` (e.g. pattern_73, latency_arbitrage - see strategy),
+//                            /sensitivities portfolio P&L per home win / total / star
+//                            availability move per game - see sensitivity::SensitivityService)
+//   AUDIT=1                  record signals, risk decisions, orders and config changes
+//                            (AUDIT_DIR, AUDIT_FSYNC - see audit_log::AuditConfig)
+//   TCA=1                    derive per tier/venue minimum edge and square-root market impact
+//                            from the fill history, daily; impact caps risk order sizes and prices
+//                            backtest job fills (TCA_DIR - see tca::TcaConfig,
+//                            edge_thresholds::EdgeThresholds, market_impact::MarketImpact)
+//   PAPER_FILLS=1            fill latency arb orders from the recorded books with modeled latency
+//                            and queue priority (PAPER_ORDER_SIZE - see paper_fills::PaperFillConfig)
+//   TICK_STORE=1             archive ticks and signals as Parquet segments
+//                            (TICK_STORE_DIR, .. - see tick_store::TickStoreConfig); replays the
+//                            archive every 6h to verify pattern ROI/half-life and freeze failing
+//                            components (see pattern_verifier::PatternVerifier)
+//   BACKTEST_JOBS=1          backtest job service on /backtests: submit, poll progress, fetch results;
+//                            bounded queue and worker count, finished jobs kept on disk
+//                            (BACKTEST_JOBS_DIR, BACKTEST_MAX_QUEUED, BACKTEST_MAX_CONCURRENT -
+//                            see backtest_jobs::BacktestJobs::handle_admin); `source=archive`
+//                            jobs replay the tick store when TICK_STORE=1. With either, completed
+//                            backtests and verification updates are appended to a results file the
+//                            dashboard's backtester panels read (BACKTEST_RESULTS_PATH,
+//                            BACKTEST_RESULTS_KEEP - see backtest_results::BacktestResults)
+//   STUDIES=1                optimization studies on /studies: each study's top filter parameter
+//                            sets compared by out-of-sample equity (also on the dashboard); POST
+//                            .../promote?rank=N hot-reloads one into [worker.filters], audited
+//                            (STUDIES_DIR, STUDIES_TOP_N - see optimization_studies::StudyStore)
+//   SLA_DEGRADATION=1        when Tier 1 model latencies miss their registry SLAs, shed Tier 3/4
+//                            models, then widen trigger thresholds and shrink sizes; steps back as
+//                            compliance recovers, each change alerted (SLA_WINDOW, SLA_SHED_BELOW,
+//                            SLA_DEFENSIVE_BELOW, SLA_RECOVER_CHECKS - see sla_degradation::SlaMonitor;
+//                            state on the sla_degradation probe and the dashboard). Tier 1 patterns'
+//                            detection-to-order p99 counts too (DECISION_SLA_MS = Tier 1..4 targets,
+//                            DECISION_SLA_MIN_SAMPLES - see decision_latency; per-pattern
+//                            histograms on the dashboard either way)
+//   [features.shadow]        dark-launch components: their latency signals are paper-filled, never
+//                            sent live, and scored; after promote_after_days with min_trades,
+//                            min_sharpe and max_drawdown met they go live through the feature flags,
+//                            alerted (records in shadow_patterns.json - see shadow_mode::ShadowBook;
+//                            progress on the shadow_mode probe, POST /flags/<id>/promote by hand)
+//   [execution.review]       latency signals below below_confidence (of the listed patterns) wait
+//                            window_ms for POST /review/<signal_id>/approve|deny|annotate?note=..;
+//                            decisions and notes are audited, withheld signals paper-filled and
+//                            every decision scored against automation (see
+//                            operator_review::ReviewQueue; held signals on the dashboard, summary
+//                            on the operator_review probe)
+//   [market_making] enabled  quote wide, thin Kalshi books of the configured leagues around the
+//                            MM-compression filter's fair value (Kalshi credentials from the
+//                            secrets chain - see market_maker::MarketMaker); read at startup,
+//                            `enabled = false` on reload pulls every quote. Socket messages are
+//                            schema-checked ([feeds] schema_mode, quarantine_dir - see
+//                            feed_schema::FeedSchemas; drift counters on the feed_schema probe).
+//                            Book mids pass #76's [worker.prefilters] rule (median_of_3, ewma_alpha,
+//                            min_change; a tier1 rule covers it) before the filter - suppressed mids
+//                            don't count towards warmup (see observation_prefilter; counters on the
+//                            observation_prefilters probe)
+//   [patterns.aging]         open lots of patterns with a half_life_ms ([patterns.enabled]) in the
+//                            bot's saved positions are flagged past flag_after_half_lives of it,
+//                            alerted and listed on the dashboard (the bot itself sells them as
+//                            timeout exits with `liquidate` - see position_aging::PositionSweeper)
+//   [maintenance]            venue maintenance windows (cron or one-off, per venue): signals touching
+//                            a venue are skipped from block_before_mins ahead of its window until it
+//                            ends; saved positions on it are alerted from alert_before_mins ahead
+//                            (see maintenance::MaintenanceCalendar; upcoming windows on the
+//                            maintenance probe)
+//   [sizing]                 backtest jobs size trades on this curve of opportunity strength and
+//                            confidence (linear, piecewise through points, or logistic), the same
+//                            one the bot's worker and dispatcher use; reloadable (see
+//                            sizing_curve::SizingCurve)
+//   [logging]                JSON (default) or text output, per-module levels and hot-path
+//                            throttling, reloadable (LOG_FORMAT, LOG_LEVEL; RUST_LOG replaces
+//                            the levels - see logging::init)
+//   [feeds] heartbeat_*      feed staleness checks scale with the busiest subscribed market: per-tier
+//                            in-play, pre-game and idle intervals, overridable per provider
+//                            (heartbeat_providers - see heartbeat::HeartbeatPolicy)
+//   [feeds] *reconnect*      lost feeds retry after ws_reconnect_delay_secs, doubling up to
+//                            max_reconnect_delay_secs with reconnect_jitter; max_reconnect_attempts
+//                            failures in a row open the feed's circuit for reconnect_cooldown_secs.
+//                            Subscribed markets are resubscribed on every connect (see
+//                            feed_aggregator::ReconnectPolicy)
+//   [feeds] outlier_*        ticks jumping more than outlier_sigma deviations, with non-positive
+//                            sizes or stamped in the future are quarantined before the engines
+//                            see them (see tick_sanitizer::TickSanitizer; counters on the
+//                            tick_sanitizer probe)
+//   NTP_SERVER               host:port to measure the local clock against (NTP_INTERVAL_SECS); provider
+//                            timestamps are always corrected by each provider's estimated clock offset
+//                            and skew (CLOCK_SYNC_WINDOW - see clock_sync::ClockSync; estimates on the
+//                            clock_sync probe)
+//   [runtime] low_latency    pin the tokio workers (pin_cores, worker_threads), busy-poll the Tier 1
+//                            ingestion queue (busy_poll_tiers, busy_poll_spins) and pre-allocate
+//                            tick_buffer ticks per queue; read at startup (LOW_LATENCY - see
+//                            runtime_profile::RuntimeProfile; queue counters on the feed_ingest probe)
+//   ARB_RUNNER_SEED          synthetic feed seed (default 42)
+//   ARB_RUNNER_TICK_MS       synthetic feed tick interval (default 100)
+
+use anyhow::{Context, Result};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use arb_bot::config::{AppConfig, CliArgs};
+use arb_bot::logging;
+use arb_bot::runner::{self, SyntheticFeed};
+use arb_bot::runtime_profile::RuntimeProfile;
+
+const DEFAULT_STATUS_ADDR: &str = "127.0.0.1:9464";
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+    let cli = CliArgs::parse(std::env::args().skip(1))?;
+    let config_path = AppConfig::config_path(&cli);
+    let app_config = AppConfig::load_with(config_path.as_deref(), &cli)?;
+    logging::init(&app_config.logging);
+    info!("Arb Runner");
+
+    // The runtime follows [runtime]: pinned workers and busy-polled Tier 1 ingestion when low_latency is set
+    let profile = RuntimeProfile::from_config(&app_config.runtime);
+    profile.build_runtime()?.block_on(async move {
+        let status_addr = std::env::var("ARB_RUNNER_STATUS_ADDR").unwrap_or_else(|_| DEFAULT_STATUS_ADDR.to_string());
+        let status = TcpListener::bind(&status_addr).await
+            .with_context(|| format!("binding status endpoint {}", status_addr))?;
+        let defaults = SyntheticFeed::default();
+        let feed = SyntheticFeed {
+            seed: env_or("ARB_RUNNER_SEED", defaults.seed),
+            tick_interval_ms: env_or("ARB_RUNNER_TICK_MS", defaults.tick_interval_ms),
+        };
+        runner::run(app_config, config_path, cli, profile, feed, status, shutdown_signal()).await
+    })
+}
+
+async fn shutdown_signal() {
+    let mut terminate = terminate_signal();
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => info!("[RUNNER] Ctrl-C received, shutting down"),
+        _ = recv_terminate(&mut terminate) => info!("[RUNNER] SIGTERM received, shutting down"),
+    }
+}
+
+#[cfg(unix)]
+type Terminate = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type Terminate = ();
+
+#[cfg(unix)]
+fn terminate_signal() -> Terminate {
+    use tokio::signal::unix::{signal, SignalKind};
+    signal(SignalKind::terminate())
+        .map_err(|e| warn!("[RUNNER] SIGTERM handler unavailable: {}", e))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn terminate_signal() -> Terminate {}
+
+#[cfg(unix)]
+async fn recv_terminate(terminate: &mut Terminate) {
+    match terminate {
+        Some(sig) => {
+            sig.recv().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn recv_terminate(_terminate: &mut Terminate) {
+    std::future::pending().await
+}
@@ -0,0 +1,75 @@
+// src/bin/intent_diff.rs
+// Dry-run a config change against a day of archived signals: which trades the candidate
+// config would add, remove or resize compared with the baseline.
+//
+//   intent_diff --baseline arb.toml --candidate proposed.toml [--day YYYY-MM-DD] [--archive DIR]
+//               [--contracts N] [--json]
+//
+//   --baseline, --candidate  config files; only the files are read (no env or flag layers), so
+//                            both sides differ by exactly what the files do
+//   --day                    UTC day to replay (default yesterday)
+//   --archive                tick store holding `signals-*` segments (default the baseline's
+//                            [tick_store] dir)
+//   --contracts              contracts each signal asks for before limits apply (default 10)
+//   --json                   print the full diff as JSON instead of the text report
+//
+// [risk] position limits and [patterns] rules are evaluated as in intent_diff::diff.
+
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveDate, Utc};
+use std::path::{Path, PathBuf};
+
+use arb_bot::config::AppConfig;
+use arb_bot::intent_diff::{self, DEFAULT_CONTRACTS};
+use arb_bot::tick_store::TickStoreConfig;
+
+const USAGE: &str = "usage: intent_diff --baseline <toml> --candidate <toml> [--day YYYY-MM-DD] [--archive DIR] [--contracts N] [--json]";
+
+/// Config from the file alone, validated like a startup load
+fn load(path: &Path) -> Result<AppConfig> {
+    let src = std::fs::read_to_string(path).with_context(|| format!("read config {}", path.display()))?;
+    AppConfig::layered(Some(&src), &|_| None, &[]).with_context(|| format!("config {}", path.display()))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut baseline = None;
+    let mut candidate = None;
+    let mut day = Utc::now().date_naive().pred_opt().context("no previous day")?;
+    let mut archive = None;
+    let mut contracts = DEFAULT_CONTRACTS;
+    let mut json = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--json" {
+            json = true;
+            continue;
+        }
+        let Some(value) = args.next() else { bail!("{} needs a value\n{}", arg, USAGE) };
+        match arg.as_str() {
+            "--baseline" => baseline = Some(PathBuf::from(value)),
+            "--candidate" => candidate = Some(PathBuf::from(value)),
+            "--day" => day = NaiveDate::parse_from_str(&value, "%Y-%m-%d").with_context(|| format!("bad day '{}'", value))?,
+            "--archive" => archive = Some(PathBuf::from(value)),
+            "--contracts" => contracts = value.parse().ok().filter(|&n| n > 0).with_context(|| format!("bad contracts '{}'", value))?,
+            _ => bail!("unknown argument '{}'\n{}", arg, USAGE),
+        }
+    }
+    let (Some(baseline), Some(candidate)) = (baseline, candidate) else { bail!(USAGE) };
+
+    let from = day.and_hms_opt(0, 0, 0).context("day start")?.and_utc();
+    let from_ns = from.timestamp_nanos_opt().context("day out of range")? as u64;
+    let to_ns = from_ns + 86_400 * 1_000_000_000 - 1;
+    let (baseline_config, candidate_config) = (load(&baseline)?, load(&candidate)?);
+    let archive = archive.unwrap_or_else(|| TickStoreConfig::from(&baseline_config.tick_store).dir);
+    let diff = intent_diff::diff_archive(&archive, from_ns, to_ns, &baseline_config, &candidate_config, contracts).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        println!("{} vs {} on {} ({})", baseline.display(), candidate.display(), day, archive.display());
+        print!("{}", diff);
+    }
+    Ok(())
+}
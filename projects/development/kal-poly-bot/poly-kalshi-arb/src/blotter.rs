@@ -0,0 +1,462 @@
+// src/blotter.rs
+// Trade blotter - every fill with fees, venue and pattern tag as a daily CSV, plus an optional
+// FIX 4.4-style ExecutionReport drop copy for external books and tax tooling
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::config::BlotterSection;
+use crate::error::StateStoreError;
+use crate::position_tracker::FillRecord;
+
+const SEGMENT_PREFIX: &str = "blotter-";
+const SEGMENT_SUFFIX: &str = ".csv";
+const NS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+
+pub const CSV_HEADER: &str =
+    "trade_date,timestamp,exec_id,order_id,venue,market_id,description,side,action,contracts,price,notional,fees,net_amount,pattern";
+
+/// FIX field delimiter
+const SOH: char = '\x01';
+/// Wait before reconnecting a failed drop-copy sink
+const DROP_COPY_RETRY: Duration = Duration::from_secs(30);
+const DROP_COPY_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Blotter configuration
+#[derive(Debug, Clone)]
+pub struct BlotterConfig {
+    /// One CSV per UTC trade date is written here
+    pub dir: PathBuf,
+    /// `tcp://host:port` or a file / named pipe receiving FIX drop copies
+    pub drop_copy: Option<String>,
+    /// FIX SenderCompID (49) / TargetCompID (56)
+    pub sender_comp_id: String,
+    pub target_comp_id: String,
+}
+
+impl Default for BlotterConfig {
+    fn default() -> Self {
+        Self::from(&BlotterSection::default())
+    }
+}
+
+impl From<&BlotterSection> for BlotterConfig {
+    fn from(b: &BlotterSection) -> Self {
+        Self {
+            dir: PathBuf::from(&b.dir),
+            drop_copy: Some(b.drop_copy.clone()).filter(|v| !v.is_empty()),
+            sender_comp_id: b.sender_comp_id.clone(),
+            target_comp_id: b.target_comp_id.clone(),
+        }
+    }
+}
+
+/// One blotter line
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlotterRow {
+    /// YYYY-MM-DD (UTC)
+    pub trade_date: String,
+    /// RFC3339 fill time
+    pub timestamp: String,
+    /// Unique within the trade date
+    pub exec_id: String,
+    pub order_id: String,
+    pub venue: String,
+    pub market_id: String,
+    pub description: String,
+    pub side: String,
+    pub action: String,
+    pub contracts: f64,
+    /// Dollars per contract
+    pub price: f64,
+    pub fees: f64,
+    pub pattern: Option<String>,
+}
+
+impl BlotterRow {
+    pub fn from_fill(fill: &FillRecord, trade_date: &str, exec_id: String) -> Self {
+        Self {
+            trade_date: trade_date.to_string(),
+            timestamp: fill.timestamp.clone(),
+            exec_id,
+            order_id: fill.order_id.clone(),
+            venue: fill.platform.clone(),
+            market_id: fill.market_id.clone(),
+            description: fill.description.clone(),
+            side: fill.side.clone(),
+            action: fill.action.clone(),
+            contracts: fill.contracts,
+            price: fill.price,
+            fees: fill.fees,
+            pattern: fill.pattern.clone(),
+        }
+    }
+
+    pub fn notional(&self) -> f64 {
+        self.contracts * self.price
+    }
+
+    /// Cash impact: buys pay notional plus fees, sells receive notional less fees
+    pub fn net_amount(&self) -> f64 {
+        if self.action == "sell" {
+            self.notional() - self.fees
+        } else {
+            -(self.notional() + self.fees)
+        }
+    }
+
+    /// CSV line in `CSV_HEADER` order (no trailing newline)
+    pub fn to_csv(&self) -> String {
+        [
+            csv_field(&self.trade_date),
+            csv_field(&self.timestamp),
+            csv_field(&self.exec_id),
+            csv_field(&self.order_id),
+            csv_field(&self.venue),
+            csv_field(&self.market_id),
+            csv_field(&self.description),
+            csv_field(&self.side),
+            csv_field(&self.action),
+            format!("{}", self.contracts),
+            format!("{:.4}", self.price),
+            format!("{:.4}", self.notional()),
+            format!("{:.4}", self.fees),
+            format!("{:.4}", self.net_amount()),
+            csv_field(self.pattern.as_deref().unwrap_or("")),
+        ]
+        .join(",")
+    }
+
+    /// FIX 4.4 ExecutionReport (35=8, ExecType F) for this fill; each fill is reported as
+    /// its own complete execution
+    pub fn to_fix(&self, seq: u64, sender: &str, target: &str, sending_time: &str) -> String {
+        let side = if self.action == "sell" { "2" } else { "1" };
+        let transact_time = chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|t| fix_time(t.with_timezone(&chrono::Utc)))
+            .unwrap_or_else(|_| sending_time.to_string());
+        let mut body = vec![
+            ("35", "8".to_string()),
+            ("49", sender.to_string()),
+            ("56", target.to_string()),
+            ("34", seq.to_string()),
+            ("52", sending_time.to_string()),
+            ("37", self.order_id.clone()),
+            ("17", self.exec_id.clone()),
+            ("150", "F".to_string()),
+            ("39", "2".to_string()),
+            ("55", self.market_id.clone()),
+            ("54", side.to_string()),
+            ("38", self.contracts.to_string()),
+            ("32", self.contracts.to_string()),
+            ("31", format!("{:.4}", self.price)),
+            ("14", self.contracts.to_string()),
+            ("151", "0".to_string()),
+            ("6", format!("{:.4}", self.price)),
+            ("12", format!("{:.4}", self.fees)),
+            ("13", "3".to_string()),
+            ("15", "USD".to_string()),
+            ("30", self.venue.clone()),
+            ("60", transact_time),
+            ("58", self.description.clone()),
+            // User-defined: contract outcome side and strategy pattern
+            ("5001", self.side.clone()),
+        ];
+        if let Some(pattern) = &self.pattern {
+            body.push(("5002", pattern.clone()));
+        }
+        // Values can't carry the delimiter
+        let body: String = body.iter()
+            .map(|(tag, value)| format!("{}={}{}", tag, value.replace(SOH, " "), SOH))
+            .collect();
+        let head = format!("8=FIX.4.4{}9={}{}", SOH, body.len(), SOH);
+        let checksum = (head.bytes().chain(body.bytes()).map(u32::from).sum::<u32>() % 256) as u8;
+        format!("{}{}10={:03}{}", head, body, checksum, SOH)
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// FIX UTCTimestamp with milliseconds
+fn fix_time(t: chrono::DateTime<chrono::Utc>) -> String {
+    t.format("%Y%m%d-%H:%M:%S%.3f").to_string()
+}
+
+/// Where drop copies are written
+enum DropCopySink {
+    Tcp(TcpStream),
+    File(File),
+}
+
+impl DropCopySink {
+    fn open(target: &str) -> std::io::Result<Self> {
+        match target.strip_prefix("tcp://") {
+            Some(addr) => {
+                let addr = addr.to_socket_addrs()?.next()
+                    .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, format!("no address for {}", addr)))?;
+                let stream = TcpStream::connect_timeout(&addr, DROP_COPY_CONNECT_TIMEOUT)?;
+                stream.set_write_timeout(Some(DROP_COPY_CONNECT_TIMEOUT))?;
+                stream.set_nodelay(true)?;
+                Ok(DropCopySink::Tcp(stream))
+            }
+            None => Ok(DropCopySink::File(OpenOptions::new().create(true).append(true).open(target)?)),
+        }
+    }
+
+    fn write(&mut self, message: &str) -> std::io::Result<()> {
+        match self {
+            DropCopySink::Tcp(stream) => stream.write_all(message.as_bytes()),
+            DropCopySink::File(file) => file.write_all(message.as_bytes()),
+        }
+    }
+}
+
+/// FIX session state for the drop copy; MsgSeqNum restarts at 1 per connection
+struct DropCopy {
+    target: String,
+    sink: Option<DropCopySink>,
+    seq: u64,
+    retry_at: Option<Instant>,
+}
+
+impl DropCopy {
+    fn send(&mut self, row: &BlotterRow, config: &BlotterConfig, sending_time: &str) {
+        if self.sink.is_none() {
+            if self.retry_at.is_some_and(|t| Instant::now() < t) {
+                return;
+            }
+            match DropCopySink::open(&self.target) {
+                Ok(sink) => {
+                    info!("[BLOTTER] Drop copy connected to {}", self.target);
+                    self.sink = Some(sink);
+                    self.seq = 0;
+                    self.retry_at = None;
+                }
+                Err(e) => {
+                    warn!("[BLOTTER] Drop copy {} unavailable: {} (retry in {:?})", self.target, e, DROP_COPY_RETRY);
+                    self.retry_at = Some(Instant::now() + DROP_COPY_RETRY);
+                    return;
+                }
+            }
+        }
+        self.seq += 1;
+        let message = row.to_fix(self.seq, &config.sender_comp_id, &config.target_comp_id, sending_time);
+        if let Some(Err(e)) = self.sink.as_mut().map(|sink| sink.write(&message)) {
+            warn!("[BLOTTER] Drop copy write failed, exec {} not sent: {}", row.exec_id, e);
+            self.sink = None;
+            self.retry_at = Some(Instant::now() + DROP_COPY_RETRY);
+        }
+    }
+}
+
+struct Writer {
+    file: File,
+    day: u64,
+    /// Fills in this day's segment (drives exec ids)
+    fills: u64,
+    fees: f64,
+}
+
+/// Append-only fill blotter, one CSV per UTC trade date; the segment for a finished day
+/// is the end-of-day blotter
+pub struct Blotter {
+    config: BlotterConfig,
+    clock: SharedClock,
+    writer: Mutex<Option<Writer>>,
+    drop_copy: Option<Mutex<DropCopy>>,
+}
+
+pub type SharedBlotter = Arc<Blotter>;
+
+impl Blotter {
+    pub fn open(config: BlotterConfig) -> Result<Self, StateStoreError> {
+        Self::open_with_clock(config, clock::system())
+    }
+
+    pub fn open_with_clock(config: BlotterConfig, clock: SharedClock) -> Result<Self, StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+        info!("[BLOTTER] Recording to {}", config.dir.display());
+        let drop_copy = config.drop_copy.clone().map(|target| {
+            info!("[BLOTTER] FIX drop copy to {}", target);
+            Mutex::new(DropCopy { target, sink: None, seq: 0, retry_at: None })
+        });
+        Ok(Self { config, clock, writer: Mutex::new(None), drop_copy })
+    }
+
+    /// Blotter file for a UTC trade date
+    pub fn path_for(&self, date: chrono::NaiveDate) -> PathBuf {
+        self.config.dir.join(format!("{}{}{}", SEGMENT_PREFIX, date.format("%Y-%m-%d"), SEGMENT_SUFFIX))
+    }
+
+    /// Append a fill (and send its drop copy); returns the blotter row
+    pub fn append(&self, fill: &FillRecord) -> Result<BlotterRow, StateStoreError> {
+        let now_ns = self.clock.wall_ns().0;
+        let fill_ns = chrono::DateTime::parse_from_rfc3339(&fill.timestamp).ok()
+            .and_then(|t| t.timestamp_nanos_opt())
+            .map_or(now_ns, |ns| ns.max(0) as u64);
+        let day = fill_ns / NS_PER_DAY;
+        let date = date_of(day);
+
+        let row = {
+            let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+            let writer = match writer.take() {
+                Some(current) if current.day == day => writer.insert(current),
+                done => {
+                    if let Some(done) = done {
+                        info!("[BLOTTER] {} closed: {} fills, ${:.2} fees", date_of(done.day), done.fills, done.fees);
+                    }
+                    writer.insert(self.open_day(day)?)
+                }
+            };
+            writer.fills += 1;
+            writer.fees += fill.fees;
+            let exec_id = format!("{}-{:06}", date.format("%Y%m%d"), writer.fills);
+            let row = BlotterRow::from_fill(fill, &date.format("%Y-%m-%d").to_string(), exec_id);
+            writeln!(writer.file, "{}", row.to_csv())?;
+            row
+        };
+
+        if let Some(drop_copy) = &self.drop_copy {
+            let sending_time = fix_time(chrono::DateTime::from_timestamp_nanos(now_ns as i64));
+            drop_copy.lock().unwrap_or_else(|e| e.into_inner()).send(&row, &self.config, &sending_time);
+        }
+        Ok(row)
+    }
+
+    /// Append, logging instead of failing (for the position writer loop)
+    pub fn record(&self, fill: &FillRecord) {
+        if let Err(e) = self.append(fill) {
+            warn!("[BLOTTER] Append failed: {}", e);
+        }
+    }
+
+    /// Open (or resume) a day's segment, continuing its exec id sequence
+    fn open_day(&self, day: u64) -> Result<Writer, StateStoreError> {
+        let path = self.path_for(date_of(day));
+        let fills = match File::open(&path) {
+            Ok(file) => count_rows(file)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+        Ok(Writer { file, day, fills, fees: 0.0 })
+    }
+}
+
+fn date_of(day: u64) -> chrono::NaiveDate {
+    chrono::DateTime::from_timestamp((day * 86_400) as i64, 0).unwrap_or_default().date_naive()
+}
+
+/// Data rows of an existing segment (header and blank lines excluded)
+fn count_rows(file: File) -> Result<u64, StateStoreError> {
+    let mut rows = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if !line.is_empty() && line != CSV_HEADER {
+            rows += 1;
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::types::Nanos;
+
+    fn fill(ts: &str, action: &str, fees: f64) -> FillRecord {
+        let mut fill = FillRecord::new("KXEPL-ARS", "Arsenal, to win", "kalshi", "yes", 10.0, 0.45, fees, "ord-1")
+            .with_pattern("PolyYesKalshiNo");
+        fill.action = action.to_string();
+        fill.timestamp = ts.to_string();
+        fill
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("blotter_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_csv_row_quotes_and_cash_amounts() {
+        let buy = BlotterRow::from_fill(&fill("2024-01-01T23:00:00Z", "buy", 0.07), "2024-01-01", "20240101-000001".into());
+        assert_eq!(
+            buy.to_csv(),
+            "2024-01-01,2024-01-01T23:00:00Z,20240101-000001,ord-1,kalshi,KXEPL-ARS,\"Arsenal, to win\",yes,buy,10,0.4500,4.5000,0.0700,-4.5700,PolyYesKalshiNo"
+        );
+        let sell = BlotterRow { action: "sell".into(), ..buy };
+        assert!((sell.net_amount() - 4.43).abs() < 1e-9);
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_fix_execution_report_framing() {
+        let row = BlotterRow::from_fill(&fill("2024-01-01T23:00:00.250Z", "sell", 0.07), "2024-01-01", "20240101-000002".into());
+        let message = row.to_fix(7, "ARBBOT", "BOOKS", "20240101-23:00:01.000");
+        let fields: Vec<(&str, &str)> = message.split(SOH).filter(|f| !f.is_empty())
+            .map(|f| f.split_once('=').unwrap())
+            .collect();
+        let get = |tag: &str| fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v);
+
+        assert_eq!(&fields[..3].iter().map(|(t, _)| *t).collect::<Vec<_>>(), &["8", "9", "35"]);
+        assert_eq!((get("35"), get("34"), get("54"), get("17")), (Some("8"), Some("7"), Some("2"), Some("20240101-000002")));
+        assert_eq!((get("31"), get("12"), get("30")), (Some("0.4500"), Some("0.0700"), Some("kalshi")));
+        assert_eq!((get("60"), get("5002")), (Some("20240101-23:00:00.250"), Some("PolyYesKalshiNo")));
+
+        let body_start = message.find("35=").unwrap();
+        let trailer = message.rfind("10=").unwrap();
+        assert_eq!(get("9").unwrap().parse::<usize>().unwrap(), trailer - body_start);
+        let checksum = message[..trailer].bytes().map(u32::from).sum::<u32>() % 256;
+        assert_eq!(get("10"), Some(format!("{:03}", checksum).as_str()));
+    }
+
+    #[test]
+    fn test_daily_segments_resume_exec_ids_and_drop_copy() {
+        let dir = test_dir("daily");
+        let drop_copy = dir.join("dropcopy.fix");
+        let config = BlotterConfig {
+            dir: dir.clone(),
+            drop_copy: Some(drop_copy.to_string_lossy().into_owned()),
+            ..BlotterConfig::default()
+        };
+        let clock = MockClock::shared(Nanos(1_704_150_000 * 1_000_000_000));
+        let blotter = Blotter::open_with_clock(config.clone(), clock.clone()).unwrap();
+        blotter.append(&fill("2024-01-01T23:00:00Z", "buy", 0.07)).unwrap();
+        blotter.append(&fill("2024-01-01T23:30:00Z", "buy", 0.07)).unwrap();
+        let next_day = blotter.append(&fill("2024-01-02T00:10:00Z", "sell", 0.07)).unwrap();
+        assert_eq!(next_day.exec_id, "20240102-000001");
+
+        // A restart picks up the day's sequence where the file left off
+        let reopened = Blotter::open_with_clock(BlotterConfig { drop_copy: None, ..config }, clock).unwrap();
+        let resumed = reopened.append(&fill("2024-01-01T23:59:00Z", "sell", 0.07)).unwrap();
+        assert_eq!(resumed.exec_id, "20240101-000003");
+
+        let day1 = std::fs::read_to_string(reopened.path_for(chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())).unwrap();
+        let day1: Vec<&str> = day1.lines().collect();
+        assert_eq!(day1.len(), 4);
+        assert_eq!(day1[0], CSV_HEADER);
+        assert!(day1[3].ends_with(",4.4300,PolyYesKalshiNo"));
+
+        let copies = std::fs::read_to_string(&drop_copy).unwrap();
+        let seqs: Vec<&str> = copies.split(SOH).filter_map(|f| f.strip_prefix("34=")).collect();
+        assert_eq!(seqs, vec!["1", "2", "3"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
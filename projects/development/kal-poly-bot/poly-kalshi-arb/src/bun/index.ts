@@ -23,6 +23,13 @@ export {
   type MarketTick,
 } from "./tick_processor.bun.ts";
 
+// Shared-memory bridge to the Rust engine
+export {
+  TickBridgeClient,
+  type BridgeTick,
+  type BridgeTrigger,
+} from "./tick_bridge.bun.ts";
+
 // Server and deployment
 export { default as EdgeWorker, queue, scheduled } from "./edge_worker.bun.ts";
 export { default as WebSocketServer } from "./websocket_server.bun.ts";
@@ -0,0 +1,191 @@
+/**
+ * Shared-Memory Tick Bridge - Bun side
+ *
+ * Writes ticks into, and reads triggers out of, the SharedArrayBuffer rings laid out
+ * by src/tick_bridge.rs, so ticks reach the Rust LatencyArbitrageEngine without
+ * per-message JSON. The offsets and wire codes below must match that file.
+ *
+ *   const native = require("./arb_bot.node");
+ *   const shared = new SharedArrayBuffer(native.TickBridge.bytesFor(4096, 256));
+ *   const engine = new native.TickBridge(new Uint8Array(shared), 4096, 256);
+ *   const bridge = new TickBridgeClient(shared);
+ *   bridge.pushTick(tick);
+ *   engine.pump();
+ *   for (const trigger of bridge.drainTriggers()) { ... }
+ */
+
+export const TICK_BRIDGE_MAGIC = 0x31524254; // "TBR1"
+export const TICK_BRIDGE_VERSION = 1;
+
+const HEADER_BYTES = 320;
+const TICK_BYTES = 24;
+const TRIGGER_BYTES = 56;
+
+// Header offsets as Int32Array indices (byte offset / 4)
+const MAGIC_AT = 0;
+const VERSION_AT = 1;
+const TICK_CAPACITY_AT = 2;
+const TRIGGER_CAPACITY_AT = 3;
+const TICK_HEAD_AT = 16;
+const TICKS_DROPPED_AT = 17;
+const TICK_TAIL_AT = 32;
+const TRIGGER_HEAD_AT = 48;
+const TRIGGER_TAIL_AT = 64;
+
+// Wire codes are indices into these lists (tiers are 1-4)
+export const PLATFORMS = [
+  "KALSHI",
+  "POLYMARKET",
+  "DRAFTKINGS",
+  "FANDUEL",
+  "BETMGM",
+  "CAESARS",
+  "POINTSBET",
+  "BARSTOOL",
+  "ESPN",
+] as const;
+export const MARKET_TYPES = [
+  "moneyline",
+  "spread",
+  "total",
+  "btts",
+  "team_total",
+  "quarter_total",
+  "half_total",
+  "player_prop",
+  "alt_line",
+  "combo",
+] as const;
+
+export type BridgePlatform = (typeof PLATFORMS)[number];
+export type BridgeMarketType = (typeof MARKET_TYPES)[number];
+
+export interface BridgeTick {
+  marketId: number;
+  provider: BridgePlatform;
+  marketType: BridgeMarketType;
+  /** Cents, 1-99 */
+  price: number;
+  /** Cents */
+  size: number;
+  tier: 1 | 2 | 3 | 4;
+}
+
+export interface BridgeObservation {
+  marketId: number;
+  provider: BridgePlatform;
+  marketType: BridgeMarketType;
+  price: number;
+  size: number;
+  /** Unix epoch nanoseconds (precise to a few hundred ns as a number) */
+  timestampNs: number;
+  tier: number;
+}
+
+export interface BridgeTrigger {
+  fast: BridgeObservation;
+  slow: BridgeObservation;
+  disparityCents: number;
+  expectedConvergenceNs: number;
+  patternId: number | null;
+  confidence: number;
+}
+
+/** Unix epoch nanoseconds, for the crossing-latency stamp */
+function wallNs(): number {
+  return (performance.timeOrigin + performance.now()) * 1e6;
+}
+
+/**
+ * Producer of the tick ring and consumer of the trigger ring. One client per bridge:
+ * each ring has exactly one writer.
+ */
+export class TickBridgeClient {
+  private header: Int32Array;
+  private view: DataView;
+  private tickCapacity: number;
+  private triggerCapacity: number;
+  private triggersAt: number;
+
+  constructor(shared: SharedArrayBuffer) {
+    this.header = new Int32Array(shared, 0, HEADER_BYTES / 4);
+    this.view = new DataView(shared);
+    if (Atomics.load(this.header, MAGIC_AT) !== TICK_BRIDGE_MAGIC) {
+      throw new Error("no tick bridge in this buffer; construct the native TickBridge first");
+    }
+    const version = Atomics.load(this.header, VERSION_AT);
+    if (version !== TICK_BRIDGE_VERSION) {
+      throw new Error(`tick bridge version ${version} (expected ${TICK_BRIDGE_VERSION})`);
+    }
+    this.tickCapacity = Atomics.load(this.header, TICK_CAPACITY_AT);
+    this.triggerCapacity = Atomics.load(this.header, TRIGGER_CAPACITY_AT);
+    this.triggersAt = HEADER_BYTES + this.tickCapacity * TICK_BYTES;
+  }
+
+  /** Queue a tick for the engine; false (and counted as dropped) when the ring is full */
+  pushTick(tick: BridgeTick): boolean {
+    const head = Atomics.load(this.header, TICK_HEAD_AT) >>> 0;
+    const tail = Atomics.load(this.header, TICK_TAIL_AT) >>> 0;
+    if (((head - tail) >>> 0) >= this.tickCapacity) {
+      Atomics.add(this.header, TICKS_DROPPED_AT, 1);
+      return false;
+    }
+
+    const at = HEADER_BYTES + (head & (this.tickCapacity - 1)) * TICK_BYTES;
+    const view = this.view;
+    const sent = wallNs();
+    view.setUint32(at, sent % 2 ** 32, true);
+    view.setUint32(at + 4, Math.floor(sent / 2 ** 32), true);
+    view.setUint16(at + 8, tick.marketId, true);
+    view.setUint16(at + 10, tick.price, true);
+    view.setUint16(at + 12, tick.size, true);
+    view.setUint8(at + 14, PLATFORMS.indexOf(tick.provider));
+    view.setUint8(at + 15, MARKET_TYPES.indexOf(tick.marketType));
+    view.setUint8(at + 16, tick.tier);
+
+    // Publishes the slot to Rust
+    Atomics.store(this.header, TICK_HEAD_AT, (head + 1) | 0);
+    return true;
+  }
+
+  /** Triggers the engine has produced since the last drain, oldest first */
+  drainTriggers(max = Infinity): BridgeTrigger[] {
+    const triggers: BridgeTrigger[] = [];
+    let tail = Atomics.load(this.header, TRIGGER_TAIL_AT) >>> 0;
+    const head = Atomics.load(this.header, TRIGGER_HEAD_AT) >>> 0;
+
+    while (tail !== head && triggers.length < max) {
+      const at = this.triggersAt + (tail & (this.triggerCapacity - 1)) * TRIGGER_BYTES;
+      triggers.push(this.readTrigger(at));
+      tail = (tail + 1) >>> 0;
+    }
+    // Hands the slots back to Rust
+    Atomics.store(this.header, TRIGGER_TAIL_AT, tail | 0);
+    return triggers;
+  }
+
+  private readTrigger(at: number): BridgeTrigger {
+    const view = this.view;
+    const u64 = (offset: number) =>
+      view.getUint32(at + offset, true) + view.getUint32(at + offset + 4, true) * 2 ** 32;
+    const side = (i: number): BridgeObservation => ({
+      marketId: view.getUint16(at + 32 + 2 * i, true),
+      provider: PLATFORMS[view.getUint8(at + 48 + i)],
+      marketType: MARKET_TYPES[view.getUint8(at + 50 + i)],
+      price: view.getUint16(at + 36 + 2 * i, true),
+      size: view.getUint16(at + 44 + 2 * i, true),
+      timestampNs: u64(16 + 8 * i),
+      tier: view.getUint8(at + 52 + i),
+    });
+    const patternId = view.getUint16(at + 42, true);
+
+    return {
+      fast: side(0),
+      slow: side(1),
+      disparityCents: view.getInt16(at + 40, true),
+      expectedConvergenceNs: u64(0),
+      patternId: patternId === 0 ? null : patternId,
+      confidence: view.getFloat64(at + 8, true),
+    };
+  }
+}
@@ -3,10 +3,21 @@
 //! Edge-deployed worker for real-time Kalman filter processing with Redis state management.
 //! Optimized for sub-10ms latency budget with async KV operations and fire-and-forget state updates.
 
+use crate::bet_queue::{BetMessage, BetStream, RedisBetStream, BET_QUEUE_STREAM};
+use crate::capital_allocator::SharedCapitalAllocator;
+use crate::pattern_policy::SharedPatternPolicy;
+use crate::risk_state::{Guardrail, SharedRiskStateView, SuppressReason};
+use crate::sizing_curve::SharedSizingCurve;
+use crate::sla_degradation::SharedSlaMonitor;
+use crate::config::{FilterTuning, WorkerSection};
 use crate::kalman_filter_suite::*;
+use crate::observation_prefilter::SharedObservationPrefilters;
 use crate::types::{TimestampNs, PriceCents, MarketType, Platform};
+use crate::error::WireError;
+use crate::wire_format::WireFormat;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{info, warn, debug, error};
 
 /// Bun Worker request payload
@@ -130,6 +141,18 @@ pub struct BunWorker {
     pub metrics: WorkerMetrics,
     /// Worker configuration
     pub config: WorkerConfig,
+    /// Per-pattern bankroll budgets (sizes against a fixed capital without one)
+    pub allocator: Option<SharedCapitalAllocator>,
+    /// `[patterns.enabled]` rules triggers must pass
+    pub patterns: Option<SharedPatternPolicy>,
+    /// Session P&L, kill switch and budgets; triggers are suppressed or downsized by its guardrails
+    pub risk_state: Option<SharedRiskStateView>,
+    /// Latency SLA policy: records filter latencies, sheds Tier 3/4 patterns and widens/shrinks triggers
+    pub sla: Option<SharedSlaMonitor>,
+    /// Median / EWMA / minimum-change pre-filters run on each tick before the filter update
+    pub prefilters: Option<SharedObservationPrefilters>,
+    /// `[sizing]` curve scaling trigger sizes by edge strength and confidence
+    pub sizing: Option<SharedSizingCurve>,
 }
 
 /// Worker performance metrics
@@ -147,6 +170,10 @@ pub struct WorkerMetrics {
     pub cache_hits: u64,
     /// Cache misses
     pub cache_misses: u64,
+    /// Triggers dropped by risk guardrails, by reason
+    pub suppressed_triggers: HashMap<SuppressReason, u64>,
+    /// Triggers sent at a reduced size by risk guardrails
+    pub downsized_triggers: u64,
 }
 
 /// Worker configuration
@@ -162,6 +189,8 @@ pub struct WorkerConfig {
     pub trigger_threshold: f64,
     /// Position sizing mode
     pub position_sizing: PositionSizing,
+    /// Per-pattern filter tuning from `[worker.filters]` (dt and trigger threshold)
+    pub filters: BTreeMap<String, FilterTuning>,
 }
 
 /// Position sizing strategy
@@ -183,6 +212,7 @@ impl Default for WorkerConfig {
             cache_size_limit: 1000,
             trigger_threshold: 0.5,
             position_sizing: PositionSizing::Kelly { multiplier: 0.5 },
+            filters: BTreeMap::new(),
         }
     }
 }
@@ -283,9 +313,78 @@ impl BunWorker {
             ),
             metrics: WorkerMetrics::default(),
             config,
+            allocator: None,
+            patterns: None,
+            risk_state: None,
+            sla: None,
+            prefilters: None,
+            sizing: None,
         }
     }
 
+    /// Size triggers from each pattern's allocated budget
+    pub fn with_capital_allocator(mut self, allocator: SharedCapitalAllocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Only trigger patterns `[patterns.enabled]` admits, sized within their capital cap
+    pub fn with_pattern_policy(mut self, patterns: SharedPatternPolicy) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Hold triggers to the session's risk guardrails (daily loss, kill switch, pattern budgets)
+    pub fn with_risk_state(mut self, risk_state: SharedRiskStateView) -> Self {
+        self.risk_state = Some(risk_state);
+        self
+    }
+
+    /// Report filter latencies to the SLA policy and follow its degradation level
+    pub fn with_sla_monitor(mut self, sla: SharedSlaMonitor) -> Self {
+        self.sla = Some(sla);
+        self
+    }
+
+    /// Smooth or drop each tick per `[worker.prefilters]` before it updates its pattern's filter
+    pub fn with_prefilters(mut self, prefilters: SharedObservationPrefilters) -> Self {
+        self.prefilters = Some(prefilters);
+        self
+    }
+
+    /// Scale trigger sizes on the `[sizing]` curve shared with the backtester and dispatcher
+    pub fn with_sizing_curve(mut self, sizing: SharedSizingCurve) -> Self {
+        self.sizing = Some(sizing);
+        self
+    }
+
+    /// Apply hot-reloaded worker tunables (trigger threshold, time budget, cache size, filter
+    /// tuning, pre-filters)
+    pub fn apply_config(&mut self, worker: &WorkerSection) {
+        self.config.max_processing_time_us = worker.max_processing_time_us;
+        self.config.enable_persistence = worker.enable_persistence;
+        self.config.cache_size_limit = worker.cache_size_limit;
+        self.config.trigger_threshold = worker.trigger_threshold;
+        self.config.filters = worker.filters.clone();
+        if let Some(prefilters) = &self.prefilters {
+            prefilters.apply_config(&worker.prefilters);
+        }
+    }
+
+    /// Filter time step for a pattern: its promoted tuning, else the 50ms default
+    fn filter_dt(&self, pattern_id: u16) -> f64 {
+        self.config.filters.get(&pattern_id.to_string()).map_or(0.05, |tuning| tuning.dt)
+    }
+
+    /// Edge a pattern needs to trigger: its tuned threshold, else `trigger_threshold`; widened
+    /// while the SLA policy is defensive
+    fn trigger_threshold(&self, pattern_id: u16) -> f64 {
+        let threshold = self.config.filters
+            .get(&pattern_id.to_string())
+            .map_or(self.config.trigger_threshold, |tuning| tuning.velocity_threshold);
+        threshold * self.sla.as_ref().map_or(1.0, |sla| sla.threshold_multiplier())
+    }
+
     /// Process worker request (main entry point)
     pub async fn process_request(&mut self, request: WorkerRequest) -> WorkerResponse {
         let start_time = std::time::Instant::now();
@@ -316,8 +415,9 @@ impl BunWorker {
             }
         };
 
-        // Process tick through filter
+        // Process tick through filter, then hold the trigger to the risk guardrails
         let trigger = self.process_tick_with_filter(&mut filter, &request).await;
+        let trigger = trigger.and_then(|trigger| self.apply_guardrails(trigger));
 
         // Save filter state (async, fire-and-forget)
         if self.config.enable_persistence {
@@ -331,6 +431,9 @@ impl BunWorker {
         // Update metrics
         let processing_time = start_time.elapsed().as_micros() as f64;
         self.update_metrics(processing_time, trigger.is_some());
+        if let Some(sla) = &self.sla {
+            sla.record(request.pattern_id, processing_time / 1000.0);
+        }
 
         // Create response
         WorkerResponse {
@@ -367,12 +470,13 @@ impl BunWorker {
     async fn load_or_create_filter(&mut self, request: &WorkerRequest) -> Result<Box<dyn KalmanFilterTrait>, WorkerStatus> {
         // Try to load existing state
         let existing_state = self.state_manager.load_filter_state(request.pattern_id, &request.market_id);
+        let dt = self.filter_dt(request.pattern_id);
 
         if let Some(state) = existing_state {
             self.metrics.cache_hits += 1;
 
             // Create filter and restore state
-            match self.filter_factory.create_filter(request.pattern_id, 0.05) {
+            match self.filter_factory.create_filter(request.pattern_id, dt) {
                 Ok(mut filter) => {
                     if let Err(e) = self.restore_filter_state(&mut filter, &state) {
                         warn!("Failed to restore filter state: {}", e);
@@ -389,7 +493,7 @@ impl BunWorker {
             self.metrics.cache_misses += 1;
 
             // Create new filter
-            match self.filter_factory.create_filter(request.pattern_id, 0.05) {
+            match self.filter_factory.create_filter(request.pattern_id, dt) {
                 Ok(filter) => Ok(filter),
                 Err(e) => {
                     error!("Failed to create filter: {}", e);
@@ -401,11 +505,17 @@ impl BunWorker {
 
     /// Process tick through filter and generate trigger if applicable
     async fn process_tick_with_filter(&self, filter: &mut Box<dyn KalmanFilterTrait>, request: &WorkerRequest) -> Option<TriggerData> {
+        // Suppressed ticks (duplicates, sub-threshold wiggles) neither step nor update the filter
+        let price = match &self.prefilters {
+            Some(prefilters) => prefilters.filter(request.pattern_id, &request.market_id, request.tick.price)?,
+            None => request.tick.price,
+        };
+
         // Predict next state
         filter.predict();
 
         // Update with observation
-        let observation = vec![request.tick.price];
+        let observation = vec![price];
         if let Err(e) = filter.update(&observation) {
             warn!("Filter update failed: {}", e);
             return None;
@@ -428,7 +538,8 @@ impl BunWorker {
         // Calculate edge
         let edge = (position - request.tick.price).abs();
 
-        if edge < self.config.trigger_threshold {
+        let threshold = self.trigger_threshold(request.pattern_id);
+        if edge < threshold {
             return None;
         }
 
@@ -439,8 +550,30 @@ impl BunWorker {
             0.5
         };
 
+        // Disabled patterns still update their filters, so re-enabling them starts warm
+        let pattern = request.pattern_id.to_string();
+        if self.sla.as_ref().is_some_and(|sla| sla.sheds(request.pattern_id)) {
+            debug!("Trigger suppressed for pattern {}: tier shed by SLA degradation", pattern);
+            return None;
+        }
+        if let Some(patterns) = &self.patterns {
+            if let Err(rejection) = patterns.admit(&pattern, Some(confidence), &[&request.tick.book]) {
+                debug!("Trigger suppressed for pattern {}: {}", pattern, rejection);
+                return None;
+            }
+        }
+
         // Calculate position size
-        let size = self.calculate_position_size(edge, confidence);
+        let mut size = self.calculate_position_size(request.pattern_id, edge, confidence);
+        if let Some(sizing) = &self.sizing {
+            size *= sizing.multiplier(sizing.worker_strength(edge, threshold), confidence);
+        }
+        if let Some(left) = self.patterns.as_ref().and_then(|patterns| patterns.available(&pattern)) {
+            size = size.min(left);
+        }
+        if let Some(sla) = &self.sla {
+            size *= sla.size_multiplier();
+        }
 
         // Window duration based on pattern
         let window_duration = match request.pattern_id {
@@ -462,20 +595,42 @@ impl BunWorker {
         })
     }
 
+    /// Suppress or downsize a trigger while risk guardrails are active (counted in the metrics)
+    fn apply_guardrails(&mut self, mut trigger: TriggerData) -> Option<TriggerData> {
+        let Some(risk_state) = &self.risk_state else { return Some(trigger) };
+        match risk_state.check(&trigger.pattern_id.to_string(), trigger.size) {
+            Guardrail::Clear => Some(trigger),
+            Guardrail::Downsize(scale) => {
+                debug!("Trigger for pattern {} downsized to {:.0}%", trigger.pattern_id, scale * 100.0);
+                trigger.size *= scale;
+                self.metrics.downsized_triggers += 1;
+                Some(trigger)
+            }
+            Guardrail::Suppress(reason) => {
+                debug!("Trigger suppressed for pattern {}: {}", trigger.pattern_id, reason.as_str());
+                *self.metrics.suppressed_triggers.entry(reason).or_default() += 1;
+                None
+            }
+        }
+    }
+
     /// Calculate position size based on configuration
-    fn calculate_position_size(&self, edge: f64, confidence: f64) -> f64 {
+    fn calculate_position_size(&self, pattern_id: u16, edge: f64, confidence: f64) -> f64 {
+        // Remaining budget of the pattern when allocated, else fixed capital
+        let budget = |default: f64| match &self.allocator {
+            Some(allocator) => allocator.available(&pattern_id.to_string()),
+            None => default,
+        };
         match &self.config.position_sizing {
             PositionSizing::Fixed(size) => *size,
             PositionSizing::Kelly { multiplier } => {
                 // Simplified Kelly calculation
                 let win_rate = confidence;
                 let kelly_fraction = (win_rate * 2.0 - 1.0) * multiplier; // Assuming even odds
-                (kelly_fraction * 1000.0).max(10.0).min(1000.0) // Cap between $10-$1000
+                let capital = budget(1000.0);
+                (kelly_fraction * capital).max(10.0).min(capital) // Floor of $10 within the budget
             },
-            PositionSizing::Percentage(percent) => {
-                // Assume $10,000 capital
-                10000.0 * percent
-            }
+            PositionSizing::Percentage(percent) => budget(10000.0) * percent,
         }
     }
 
@@ -550,6 +705,9 @@ impl Default for FilterState {
 pub async fn bun_fetch_handler(req: WorkerRequest, env: WorkerEnvironment) -> WorkerResponse {
     let config = WorkerConfig::default();
     let mut worker = BunWorker::new(config);
+    if let Some(risk_state) = &env.risk_state {
+        worker = worker.with_risk_state(risk_state.clone());
+    }
 
     // Process request
     let response = worker.process_request(req).await;
@@ -564,22 +722,48 @@ pub async fn bun_fetch_handler(req: WorkerRequest, env: WorkerEnvironment) -> Wo
     response
 }
 
+/// Fetch handler for encoded payloads: the request body is decoded per its `Content-Type` and
+/// the response encoded as the caller's `Accept` asks (JSON, MessagePack or CBOR)
+pub async fn bun_fetch_encoded(
+    body: &[u8],
+    content_type: Option<&str>,
+    accept: Option<&str>,
+    env: WorkerEnvironment,
+) -> Result<(WireFormat, Vec<u8>), WireError> {
+    let req: WorkerRequest = WireFormat::of_body(content_type)?.decode(body)?;
+    let response = bun_fetch_handler(req, env).await;
+    let format = WireFormat::negotiate(accept);
+    Ok((format, format.encode(&response)?))
+}
+
 /// Worker environment (mock for demonstration)
 #[derive(Debug, Clone)]
 pub struct WorkerEnvironment {
-    /// Bet queue URL
+    /// Redis address of the bet queue (redis://host:port; empty = log triggers only)
     pub bet_queue_url: String,
     /// KV store for state
     pub kv_store: MockRedisClient,
+    /// Risk guardrails triggers are held to (None = ungated)
+    pub risk_state: Option<SharedRiskStateView>,
 }
 
-/// Send trigger to bet queue
+/// Send trigger to bet queue: published on the `bet:queue` stream at `bet_queue_url` in the
+/// Bun worker's field layout, for `bet_queue::BetQueueConsumer` to execute. Without a URL the
+/// trigger is only logged.
 async fn send_to_bet_queue(trigger: &TriggerData, env: &WorkerEnvironment) -> Result<(), String> {
-    // In real implementation, this would send to Redis queue or message broker
     info!("Sending trigger to bet queue: pattern {} target {:.2} confidence {:.2}",
           trigger.pattern_id, trigger.target_price, trigger.confidence);
+    if env.bet_queue_url.is_empty() {
+        return Ok(());
+    }
 
-    // Mock implementation
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+    let message = BetMessage::from_trigger(trigger, now_ms);
+    let id = RedisBetStream::new(&env.bet_queue_url)
+        .add(BET_QUEUE_STREAM, &message.fields())
+        .await
+        .map_err(|e| e.to_string())?;
+    debug!("Trigger queued as {}", id);
     Ok(())
 }
 
@@ -717,13 +901,13 @@ mod tests {
         let config = WorkerConfig::default();
         let worker = BunWorker::new(config);
 
-        let fixed_size = worker.calculate_position_size(1.0, 0.6);
+        let fixed_size = worker.calculate_position_size(51, 1.0, 0.6);
         assert!(fixed_size > 0.0);
 
-        let kelly_size = worker.calculate_position_size(2.0, 0.8);
+        let kelly_size = worker.calculate_position_size(51, 2.0, 0.8);
         assert!(kelly_size > 0.0);
 
-        let percentage_size = worker.calculate_position_size(1.5, 0.7);
+        let percentage_size = worker.calculate_position_size(51, 1.5, 0.7);
         assert!(percentage_size > 0.0);
     }
 
@@ -750,4 +934,41 @@ mod tests {
         // This would normally use a real filter, but for testing we'll mock it
         // The trigger evaluation logic is tested indirectly through the worker processing
     }
+
+    #[tokio::test]
+    async fn test_encoded_fetch_round_trip() {
+        let request = WorkerRequest {
+            pattern_id: 51,
+            market_id: "test_market".to_string(),
+            tick: TickData {
+                price: 100.0,
+                size: 1000.0,
+                book: "test_book".to_string(),
+                platform: "test_platform".to_string(),
+                market_type: "test_type".to_string(),
+            },
+            timestamp_ns: 123456789,
+            request_id: "test_req".to_string(),
+        };
+        let env = WorkerEnvironment {
+            bet_queue_url: String::new(),
+            kv_store: MockRedisClient::new(),
+            risk_state: None,
+        };
+
+        for format in WireFormat::ALL {
+            let body = format.encode(&request).unwrap();
+            let decoded: WorkerRequest = format.decode(&body).unwrap();
+            assert_eq!(decoded.request_id, request.request_id);
+            assert_eq!(decoded.tick.price, request.tick.price);
+
+            let (answered, bytes) = bun_fetch_encoded(&body, Some(format.content_type()), Some(format.content_type()), env.clone())
+                .await
+                .unwrap();
+            assert_eq!(answered, format);
+            let response: WorkerResponse = format.decode(&bytes).unwrap();
+            assert_eq!(response.request_id, "test_req");
+        }
+        assert!(bun_fetch_encoded(b"{}", Some("text/plain"), None, env).await.is_err());
+    }
 }
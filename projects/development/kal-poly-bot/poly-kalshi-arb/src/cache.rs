@@ -1,12 +1,23 @@
 // src/cache.rs
 // Team code mapping cache - maps Polymarket codes to Kalshi codes
+// Tiered cache - in-memory LRU with TTL, optional Redis second tier, metrics
 
 use anyhow::Result;
+
+use crate::error::StateStoreError;
+use crate::redis_client::{RedisClient, Resp};
+use futures_util::future::BoxFuture;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 const CACHE_FILE: &str = "kalshi_team_cache.json";
+/// Second-tier calls slower than this count as failures (the lookup degrades to a miss)
+const DEFAULT_L2_TIMEOUT: Duration = Duration::from_millis(250);
 
 /// Team code cache - bidirectional mapping between Poly and Kalshi team codes
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -68,12 +79,12 @@ impl TeamCache {
     }
 
     /// Save cache to JSON file
-    pub fn save(&self) -> Result<()> {
+    pub fn save(&self) -> Result<(), StateStoreError> {
         self.save_to(CACHE_FILE)
     }
 
     /// Save to specific path
-    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<(), StateStoreError> {
         let json = serde_json::to_string_pretty(&self)?;
         std::fs::write(path, json)?;
         Ok(())
@@ -138,6 +149,404 @@ impl TeamCache {
     }
 }
 
+// === Tiered Cache ===
+
+/// Typed cache namespaces (key prefix + defaults)
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheNamespace {
+    /// Venue market metadata fetched during discovery
+    DiscoveryMetadata,
+    /// Polymarket neg_risk flag per token
+    NegRisk,
+    /// Serialized filter states (Kalman suite)
+    FilterState,
+    /// Player statistics for prop patterns
+    PlayerStats,
+}
+
+impl CacheNamespace {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            CacheNamespace::DiscoveryMetadata => "disc",
+            CacheNamespace::NegRisk => "negrisk",
+            CacheNamespace::FilterState => "filter",
+            CacheNamespace::PlayerStats => "player",
+        }
+    }
+
+    /// Default config for the namespace
+    pub fn config(&self) -> TieredCacheConfig {
+        match self {
+            CacheNamespace::DiscoveryMetadata => TieredCacheConfig::new(10_000, Some(Duration::from_secs(3600))),
+            // neg_risk never changes for a token
+            CacheNamespace::NegRisk => TieredCacheConfig::new(50_000, None),
+            CacheNamespace::FilterState => TieredCacheConfig::new(5_000, Some(Duration::from_secs(300))),
+            CacheNamespace::PlayerStats => TieredCacheConfig::new(20_000, Some(Duration::from_secs(900))),
+        }
+    }
+}
+
+/// Tiered cache configuration
+#[derive(Debug, Clone)]
+pub struct TieredCacheConfig {
+    /// Total weight held in memory before LRU eviction (entries, unless a weigher is set)
+    pub max_weight: usize,
+    /// Default TTL (None = no expiry)
+    pub default_ttl: Option<Duration>,
+    /// Upper bound on each second-tier call; a stalled store must not block the caller
+    pub l2_timeout: Duration,
+}
+
+impl TieredCacheConfig {
+    pub fn new(max_weight: usize, default_ttl: Option<Duration>) -> Self {
+        Self { max_weight, default_ttl, l2_timeout: DEFAULT_L2_TIMEOUT }
+    }
+}
+
+/// Hit/miss/evict counters
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    pub hits: AtomicU64,
+    pub misses: AtomicU64,
+    /// Hits served by the second tier
+    pub l2_hits: AtomicU64,
+    pub evictions: AtomicU64,
+    pub expirations: AtomicU64,
+}
+
+/// Point-in-time copy of `CacheMetrics`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct CacheMetricsSnapshot {
+    pub hits: u64,
+    pub misses: u64,
+    pub l2_hits: u64,
+    pub evictions: u64,
+    pub expirations: u64,
+}
+
+#[allow(dead_code)]
+impl CacheMetricsSnapshot {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+    }
+}
+
+impl CacheMetrics {
+    pub fn snapshot(&self) -> CacheMetricsSnapshot {
+        CacheMetricsSnapshot {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            l2_hits: self.l2_hits.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            expirations: self.expirations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Second-tier store holding serialized values
+#[allow(dead_code)]
+pub trait CacheBackend: Send + Sync {
+    fn get(&self, key: &str) -> BoxFuture<'_, Result<Option<String>, StateStoreError>>;
+    fn set(&self, key: &str, value: String, ttl: Option<Duration>) -> BoxFuture<'_, Result<(), StateStoreError>>;
+    fn delete(&self, key: &str) -> BoxFuture<'_, Result<(), StateStoreError>>;
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: Option<Instant>,
+    weight: usize,
+    /// Recency stamp (key into `LruState::order`)
+    stamp: u64,
+}
+
+struct LruState<V> {
+    entries: HashMap<String, Entry<V>>,
+    /// stamp -> key, oldest first
+    order: BTreeMap<u64, String>,
+    next_stamp: u64,
+    weight: usize,
+}
+
+impl<V> LruState<V> {
+    fn touch(&mut self, key: &str) {
+        let stamp = self.next_stamp;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.order.remove(&entry.stamp);
+            entry.stamp = stamp;
+            self.order.insert(stamp, key.to_string());
+            self.next_stamp += 1;
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Entry<V>> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.stamp);
+        self.weight -= entry.weight;
+        Some(entry)
+    }
+}
+
+/// In-memory LRU with per-entry TTL, optionally backed by a shared second tier
+pub struct TieredCache<V> {
+    namespace: CacheNamespace,
+    config: TieredCacheConfig,
+    state: Mutex<LruState<V>>,
+    weigher: fn(&V) -> usize,
+    l2: Option<Arc<dyn CacheBackend>>,
+    metrics: CacheMetrics,
+}
+
+#[allow(dead_code)]
+impl<V: Clone + Serialize + DeserializeOwned + Send + Sync> TieredCache<V> {
+    pub fn new(namespace: CacheNamespace) -> Self {
+        Self::with_config(namespace, namespace.config())
+    }
+
+    pub fn with_config(namespace: CacheNamespace, config: TieredCacheConfig) -> Self {
+        Self {
+            namespace,
+            config,
+            state: Mutex::new(LruState {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                next_stamp: 0,
+                weight: 0,
+            }),
+            weigher: |_| 1,
+            l2: None,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    /// Size-based eviction: weigh entries (e.g. approximate bytes) instead of counting them
+    pub fn with_weigher(mut self, weigher: fn(&V) -> usize) -> Self {
+        self.weigher = weigher;
+        self
+    }
+
+    /// Back the in-memory tier with a shared store (e.g. Redis)
+    pub fn with_backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.l2 = Some(backend);
+        self
+    }
+
+    pub fn metrics(&self) -> CacheMetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn l2_key(&self, key: &str) -> String {
+        format!("{}:{}", self.namespace.prefix(), key)
+    }
+
+    /// Run a second-tier call under `l2_timeout`
+    async fn l2_call<T>(&self, call: BoxFuture<'_, Result<T, StateStoreError>>) -> Result<T, StateStoreError> {
+        tokio::time::timeout(self.config.l2_timeout, call).await.unwrap_or_else(|_| {
+            Err(StateStoreError::Io(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("second tier timed out after {:?}", self.config.l2_timeout),
+            )))
+        })
+    }
+
+    /// In-memory lookup only
+    pub fn get_hot(&self, key: &str) -> Option<V> {
+        let mut state = self.state.lock().unwrap();
+        let expired = match state.entries.get(key) {
+            None => {
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            Some(entry) => entry.expires_at.is_some_and(|t| t <= Instant::now()),
+        };
+        if expired {
+            state.remove(key);
+            self.metrics.expirations.fetch_add(1, Ordering::Relaxed);
+            self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        state.touch(key);
+        self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+        state.entries.get(key).map(|e| e.value.clone())
+    }
+
+    /// In-memory insert with the namespace default TTL
+    pub fn insert_hot(&self, key: &str, value: V) {
+        self.insert_hot_with_ttl(key, value, self.config.default_ttl);
+    }
+
+    pub fn insert_hot_with_ttl(&self, key: &str, value: V, ttl: Option<Duration>) {
+        let weight = (self.weigher)(&value);
+        let mut state = self.state.lock().unwrap();
+        state.remove(key);
+
+        let stamp = state.next_stamp;
+        state.next_stamp += 1;
+        state.order.insert(stamp, key.to_string());
+        state.weight += weight;
+        state.entries.insert(key.to_string(), Entry {
+            value,
+            expires_at: ttl.map(|t| Instant::now() + t),
+            weight,
+            stamp,
+        });
+
+        // Evict least-recently-used until under budget (never the entry just written)
+        while state.weight > self.config.max_weight && state.entries.len() > 1 {
+            let Some((_, oldest)) = state.order.pop_first() else { break };
+            if let Some(entry) = state.entries.remove(&oldest) {
+                state.weight -= entry.weight;
+                self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn invalidate_hot(&self, key: &str) {
+        self.state.lock().unwrap().remove(key);
+    }
+
+    /// Drop all expired entries (call periodically for TTL-heavy namespaces)
+    pub fn purge_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let expired: Vec<String> = state.entries.iter()
+            .filter(|(_, e)| e.expires_at.is_some_and(|t| t <= now))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in &expired {
+            state.remove(key);
+        }
+        self.metrics.expirations.fetch_add(expired.len() as u64, Ordering::Relaxed);
+        expired.len()
+    }
+
+    /// Replace the in-memory tier wholesale (e.g. loading a warm cache file)
+    pub fn load_hot(&self, entries: impl IntoIterator<Item = (String, V)>) -> usize {
+        let mut count = 0;
+        for (k, v) in entries {
+            self.insert_hot(&k, v);
+            count += 1;
+        }
+        count
+    }
+
+    /// Lookup through both tiers; second-tier hits are promoted to memory
+    pub async fn get(&self, key: &str) -> Option<V> {
+        if let Some(v) = self.get_hot(key) {
+            return Some(v);
+        }
+        let l2 = self.l2.as_ref()?;
+        match self.l2_call(l2.get(&self.l2_key(key))).await {
+            Ok(Some(raw)) => match serde_json::from_str::<V>(&raw) {
+                Ok(v) => {
+                    self.metrics.l2_hits.fetch_add(1, Ordering::Relaxed);
+                    self.insert_hot(key, v.clone());
+                    Some(v)
+                }
+                Err(e) => {
+                    tracing::warn!("[CACHE] Bad {} entry for {}: {}", self.namespace.prefix(), key, e);
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                tracing::debug!("[CACHE] L2 get failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Write through both tiers
+    pub async fn insert(&self, key: &str, value: V) {
+        if let Some(l2) = &self.l2 {
+            match serde_json::to_string(&value) {
+                Ok(raw) => {
+                    if let Err(e) = self.l2_call(l2.set(&self.l2_key(key), raw, self.config.default_ttl)).await {
+                        tracing::debug!("[CACHE] L2 set failed: {}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("[CACHE] Serialize {} failed: {}", key, e),
+            }
+        }
+        self.insert_hot(key, value);
+    }
+
+    pub async fn invalidate(&self, key: &str) {
+        self.invalidate_hot(key);
+        if let Some(l2) = &self.l2 {
+            let _ = self.l2_call(l2.delete(&self.l2_key(key))).await;
+        }
+    }
+
+    /// Get or compute-and-insert
+    pub async fn get_or_try_insert<F, Fut>(&self, key: &str, fetch: F) -> Result<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<V>>,
+    {
+        if let Some(v) = self.get(key).await {
+            return Ok(v);
+        }
+        let v = fetch().await?;
+        self.insert(key, v.clone()).await;
+        Ok(v)
+    }
+}
+
+/// Redis second tier (GET / SET PX / DEL)
+pub struct RedisBackend {
+    client: RedisClient,
+}
+
+impl RedisBackend {
+    /// `addr` is host:port; REDIS_URL=redis://host:port is accepted too
+    pub fn new(addr: &str) -> Self {
+        Self { client: RedisClient::new(addr) }
+    }
+
+    async fn command(&self, args: &[&str]) -> Result<Option<String>, StateStoreError> {
+        self.client.command(args).await.map(Resp::text)
+    }
+}
+
+impl CacheBackend for RedisBackend {
+    fn get(&self, key: &str) -> BoxFuture<'_, Result<Option<String>, StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move { self.command(&["GET", &key]).await })
+    }
+
+    fn set(&self, key: &str, value: String, ttl: Option<Duration>) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            match ttl {
+                Some(ttl) => {
+                    let ms = ttl.as_millis().max(1).to_string();
+                    self.command(&["SET", &key, &value, "PX", &ms]).await?
+                }
+                None => self.command(&["SET", &key, &value]).await?,
+            };
+            Ok(())
+        })
+    }
+
+    fn delete(&self, key: &str) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            self.command(&["DEL", &key]).await?;
+            Ok(())
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,4 +561,66 @@ mod tests {
         assert_eq!(cache.poly_to_kalshi("epl", "CHE"), Some("cfc".to_string()));
         assert_eq!(cache.kalshi_to_poly("epl", "cfc"), Some("che".to_string()));
     }
+
+    #[test]
+    fn test_lru_eviction_and_metrics() {
+        let cache: TieredCache<u32> = TieredCache::with_config(
+            CacheNamespace::PlayerStats, TieredCacheConfig::new(2, None));
+        cache.insert_hot("a", 1);
+        cache.insert_hot("b", 2);
+        assert_eq!(cache.get_hot("a"), Some(1)); // a is now most recent
+        cache.insert_hot("c", 3);                // evicts b
+
+        assert_eq!(cache.get_hot("b"), None);
+        assert_eq!(cache.get_hot("a"), Some(1));
+        assert_eq!(cache.get_hot("c"), Some(3));
+        let m = cache.metrics();
+        assert_eq!((m.hits, m.misses, m.evictions), (3, 1, 1));
+    }
+
+    #[test]
+    fn test_ttl_and_weight() {
+        let cache: TieredCache<String> = TieredCache::with_config(
+            CacheNamespace::DiscoveryMetadata, TieredCacheConfig::new(10, None))
+            .with_weigher(|s| s.len());
+        cache.insert_hot_with_ttl("gone", "x".into(), Some(Duration::from_millis(0)));
+        assert_eq!(cache.get_hot("gone"), None);
+        assert_eq!(cache.metrics().expirations, 1);
+
+        cache.insert_hot("a", "123456".into());
+        cache.insert_hot("b", "123456".into()); // 12 > 10 -> evict a
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get_hot("b").is_some());
+    }
+
+    struct MemBackend(Mutex<HashMap<String, String>>);
+
+    impl CacheBackend for MemBackend {
+        fn get(&self, key: &str) -> BoxFuture<'_, Result<Option<String>, StateStoreError>> {
+            let v = self.0.lock().unwrap().get(key).cloned();
+            Box::pin(async move { Ok(v) })
+        }
+        fn set(&self, key: &str, value: String, _ttl: Option<Duration>) -> BoxFuture<'_, Result<(), StateStoreError>> {
+            self.0.lock().unwrap().insert(key.to_string(), value);
+            Box::pin(async { Ok(()) })
+        }
+        fn delete(&self, key: &str) -> BoxFuture<'_, Result<(), StateStoreError>> {
+            self.0.lock().unwrap().remove(key);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_second_tier_promotion() {
+        let backend = Arc::new(MemBackend(Mutex::new(HashMap::new())));
+        let writer: TieredCache<bool> = TieredCache::new(CacheNamespace::NegRisk).with_backend(backend.clone());
+        writer.insert("tok", true).await;
+        assert!(backend.0.lock().unwrap().contains_key("negrisk:tok"));
+
+        // A fresh process shares the second tier
+        let reader: TieredCache<bool> = TieredCache::new(CacheNamespace::NegRisk).with_backend(backend);
+        assert_eq!(reader.get("tok").await, Some(true));
+        assert_eq!(reader.metrics().l2_hits, 1);
+        assert_eq!(reader.get_hot("tok"), Some(true));
+    }
 }
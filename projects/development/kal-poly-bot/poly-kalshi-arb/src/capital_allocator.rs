@@ -0,0 +1,466 @@
+// src/capital_allocator.rs
+// Bankroll allocation across patterns - fractional Kelly on realized daily P&L with a correlation haircut
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tracing::{debug, info};
+
+use crate::clock::{self, SharedClock};
+use crate::config::AllocatorSection;
+use crate::error::RiskRejection;
+use crate::position_tracker::{PositionTracker, RealizedLot};
+
+const DAY_SECS: i64 = 86_400;
+
+/// Allocator tuning (from `[allocator]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocatorConfig {
+    /// Dollars divided across patterns (0 = off; sizing is left to the other limits)
+    pub bankroll: f64,
+    pub kelly_fraction: f64,
+    pub lookback_days: u32,
+    pub min_active_days: u32,
+    pub max_share: f64,
+    pub exploration_share: f64,
+    pub rebalance_interval: Duration,
+}
+
+impl From<&AllocatorSection> for AllocatorConfig {
+    fn from(a: &AllocatorSection) -> Self {
+        Self {
+            bankroll: a.bankroll,
+            kelly_fraction: a.kelly_fraction,
+            lookback_days: a.lookback_days,
+            min_active_days: a.min_active_days,
+            max_share: a.max_share,
+            exploration_share: a.exploration_share,
+            rebalance_interval: Duration::from_secs(a.rebalance_secs),
+        }
+    }
+}
+
+impl Default for AllocatorConfig {
+    fn default() -> Self {
+        Self::from(&AllocatorSection::default())
+    }
+}
+
+impl AllocatorConfig {
+    pub fn enabled(&self) -> bool {
+        self.bankroll > 0.0
+    }
+}
+
+/// One pattern's slice of the bankroll
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PatternBudget {
+    /// Share of the bankroll after the Kelly fraction, haircut and caps
+    pub share: f64,
+    /// `share * bankroll` (dollars)
+    pub budget: f64,
+    /// Full-Kelly fraction of the daily return series (None = exploring, too little history)
+    pub kelly: Option<f64>,
+    /// Multiplier for overlap with other funded patterns, in (0, 1]
+    pub haircut: f64,
+    /// Daily realized P&L over the lookback, idle days included (dollars)
+    pub mean_daily_pnl: f64,
+    pub daily_pnl_stdev: f64,
+    /// Days in the lookback with at least one closed lot
+    pub active_days: u32,
+}
+
+/// Published allocation, replaced on every rebalance
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Allocation {
+    /// Unix ns of the rebalance (0 = not rebalanced yet)
+    pub at_ns: u64,
+    pub bankroll: f64,
+    pub patterns: BTreeMap<String, PatternBudget>,
+    /// Dollars per venue: each pattern's budget split by where it deployed capital in the lookback
+    pub venues: BTreeMap<String, f64>,
+    /// Bankroll no pattern was given
+    pub unallocated: f64,
+}
+
+struct PatternHistory {
+    daily_pnl: Vec<f64>,
+    active: Vec<bool>,
+    /// Entry cost of the closed lots per venue
+    venue_cost: BTreeMap<String, f64>,
+}
+
+/// Size every tagged pattern from its realized lots over the `lookback_days` ending on
+/// `today` (unix day). Returns are daily P&L over the capital a pattern deploys on an
+/// active day, so the Kelly fraction `mean / variance` is in units of bankroll.
+pub fn allocate(config: &AllocatorConfig, lots: &[&RealizedLot], today: i64) -> Allocation {
+    let days = config.lookback_days.max(2) as usize;
+    let first = today - days as i64 + 1;
+
+    let mut history: BTreeMap<&str, PatternHistory> = BTreeMap::new();
+    for lot in lots {
+        let Some(pattern) = lot.pattern.as_deref() else { continue };
+        let Ok(closed) = chrono::DateTime::parse_from_rfc3339(&lot.closed_at) else { continue };
+        let day = closed.timestamp().div_euclid(DAY_SECS);
+        if !(first..=today).contains(&day) {
+            continue;
+        }
+        let h = history.entry(pattern).or_insert_with(|| PatternHistory {
+            daily_pnl: vec![0.0; days],
+            active: vec![false; days],
+            venue_cost: BTreeMap::new(),
+        });
+        let idx = (day - first) as usize;
+        h.daily_pnl[idx] += lot.pnl;
+        h.active[idx] = true;
+        *h.venue_cost.entry(lot.platform.clone()).or_insert(0.0) += lot.contracts * lot.entry_price;
+    }
+
+    let mut patterns = BTreeMap::new();
+    for (&name, h) in &history {
+        let (mean, var) = mean_var(&h.daily_pnl);
+        let active_days = h.active.iter().filter(|a| **a).count() as u32;
+        let deployed_per_day = h.venue_cost.values().sum::<f64>() / active_days.max(1) as f64;
+        let kelly = (active_days >= config.min_active_days).then(|| {
+            if var > 1e-12 {
+                mean * deployed_per_day / var
+            } else if mean > 0.0 {
+                // Riskless series: take the cap
+                config.max_share / config.kelly_fraction
+            } else {
+                0.0
+            }
+        });
+        let share = match kelly {
+            Some(k) => (config.kelly_fraction * k).clamp(0.0, config.max_share),
+            None => config.exploration_share,
+        };
+        patterns.insert(name.to_string(), PatternBudget {
+            share,
+            budget: 0.0,
+            kelly,
+            haircut: 1.0,
+            mean_daily_pnl: mean,
+            daily_pnl_stdev: var.sqrt(),
+            active_days,
+        });
+    }
+
+    // Patterns that win and lose on the same days are one bet: shrink each by its
+    // positive correlation with the other funded patterns
+    let funded: Vec<&str> = patterns.iter()
+        .filter(|(_, b)| b.kelly.is_some() && b.share > 0.0)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let haircuts: Vec<(String, f64)> = funded.iter()
+        .map(|&i| {
+            let overlap: f64 = funded.iter()
+                .filter(|&&j| j != i)
+                .map(|&j| correlation(&history[i].daily_pnl, &history[j].daily_pnl).max(0.0))
+                .sum();
+            (i.to_string(), 1.0 / (1.0 + overlap))
+        })
+        .collect();
+    for (name, haircut) in haircuts {
+        let budget = patterns.get_mut(&name).expect("funded pattern");
+        budget.haircut = haircut;
+        budget.share *= haircut;
+    }
+
+    let total: f64 = patterns.values().map(|b| b.share).sum();
+    let scale = if total > 1.0 { 1.0 / total } else { 1.0 };
+    let mut venues = BTreeMap::new();
+    for (name, b) in patterns.iter_mut() {
+        b.share *= scale;
+        b.budget = b.share * config.bankroll;
+        let cost = &history[name.as_str()].venue_cost;
+        let total_cost: f64 = cost.values().sum();
+        if total_cost > 0.0 {
+            for (venue, c) in cost {
+                *venues.entry(venue.clone()).or_insert(0.0) += b.budget * c / total_cost;
+            }
+        }
+    }
+
+    let allocated: f64 = patterns.values().map(|b| b.budget).sum();
+    Allocation {
+        at_ns: 0,
+        bankroll: config.bankroll,
+        patterns,
+        venues,
+        unallocated: (config.bankroll - allocated).max(0.0),
+    }
+}
+
+/// Mean and sample variance
+fn mean_var(xs: &[f64]) -> (f64, f64) {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+    (mean, var)
+}
+
+/// Pearson correlation (0 when either series is flat)
+fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let (ma, va) = mean_var(a);
+    let (mb, vb) = mean_var(b);
+    if va <= 1e-12 || vb <= 1e-12 {
+        return 0.0;
+    }
+    let cov = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum::<f64>() / (a.len() as f64 - 1.0).max(1.0);
+    cov / (va * vb).sqrt()
+}
+
+/// Dollars in open lots per pattern tag
+fn open_capital(tracker: &PositionTracker) -> HashMap<String, f64> {
+    let mut out = HashMap::new();
+    for position in tracker.open_positions() {
+        for leg in [&position.kalshi_yes, &position.kalshi_no, &position.poly_yes, &position.poly_no] {
+            for lot in &leg.lots {
+                if let Some(pattern) = &lot.pattern {
+                    *out.entry(pattern.clone()).or_insert(0.0) += lot.contracts * lot.price;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Per-pattern budgets for execution and worker sizing
+pub struct CapitalAllocator {
+    config: RwLock<AllocatorConfig>,
+    clock: SharedClock,
+    allocation: RwLock<Arc<Allocation>>,
+    /// Dollars committed per pattern: open lots at the last rebalance plus fills since
+    deployed: Mutex<HashMap<String, f64>>,
+}
+
+pub type SharedCapitalAllocator = Arc<CapitalAllocator>;
+
+impl CapitalAllocator {
+    pub fn new(config: AllocatorConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            clock: clock::system(),
+            allocation: RwLock::new(Arc::new(Allocation::default())),
+            deployed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Apply a hot-reloaded `[allocator]` section (budgets follow at the next rebalance)
+    pub fn apply_config(&self, section: &AllocatorSection) {
+        *self.config.write().unwrap() = AllocatorConfig::from(section);
+        info!("[ALLOC] Config: bankroll=${:.0} kelly_fraction={} max_share={}",
+              section.bankroll, section.kelly_fraction, section.max_share);
+    }
+
+    pub fn allocation(&self) -> Arc<Allocation> {
+        self.allocation.read().unwrap().clone()
+    }
+
+    /// Dollars the pattern may hold; patterns the last rebalance didn't see get the exploration share
+    pub fn pattern_budget(&self, pattern: &str) -> f64 {
+        let config = self.config.read().unwrap();
+        match self.allocation.read().unwrap().patterns.get(pattern) {
+            Some(budget) => budget.budget,
+            None => config.bankroll * config.exploration_share,
+        }
+    }
+
+    pub fn venue_budget(&self, venue: &str) -> f64 {
+        self.allocation.read().unwrap().venues.get(venue).copied().unwrap_or(0.0)
+    }
+
+    pub fn deployed(&self, pattern: &str) -> f64 {
+        self.deployed.lock().unwrap().get(pattern).copied().unwrap_or(0.0)
+    }
+
+    /// Budget left for new positions
+    pub fn available(&self, pattern: &str) -> f64 {
+        (self.pattern_budget(pattern) - self.deployed(pattern)).max(0.0)
+    }
+
+    /// Contracts at `cost_per_contract` dollars that fit the pattern's remaining budget
+    /// (`wanted` unchanged when the allocator is off)
+    pub fn cap_contracts(&self, pattern: &str, cost_per_contract: f64, wanted: i64) -> Result<i64, RiskRejection> {
+        if !self.config.read().unwrap().enabled() || cost_per_contract <= 0.0 {
+            return Ok(wanted);
+        }
+        let budget = self.pattern_budget(pattern);
+        let deployed = self.deployed(pattern);
+        let fits = ((budget - deployed) / cost_per_contract).floor().max(0.0) as i64;
+        if fits < 1 {
+            return Err(RiskRejection::BudgetExhausted { pattern: pattern.to_string(), budget, deployed });
+        }
+        Ok(wanted.min(fits))
+    }
+
+    /// Count capital spent by a fill against the pattern's budget
+    pub fn commit(&self, pattern: &str, dollars: f64) {
+        *self.deployed.lock().unwrap().entry(pattern.to_string()).or_insert(0.0) += dollars;
+    }
+
+    /// Refit budgets on the lookback window and re-sync deployed capital with open lots
+    pub fn rebalance(&self, tracker: &PositionTracker) -> Arc<Allocation> {
+        let config = self.config.read().unwrap().clone();
+        let now = self.clock.wall_ns();
+        let today = (now.0 / 1_000_000_000) as i64 / DAY_SECS;
+        let from = chrono::DateTime::from_timestamp((today - config.lookback_days as i64 + 1) * DAY_SECS, 0)
+            .unwrap_or_default();
+
+        let mut allocation = allocate(&config, &tracker.realized_lots(from..), today);
+        allocation.at_ns = now.0;
+        let allocation = Arc::new(allocation);
+
+        *self.deployed.lock().unwrap() = open_capital(tracker);
+        *self.allocation.write().unwrap() = allocation.clone();
+
+        info!("[ALLOC] Rebalanced ${:.0}: {} patterns, ${:.0} unallocated",
+              allocation.bankroll, allocation.patterns.len(), allocation.unallocated);
+        for (pattern, b) in &allocation.patterns {
+            debug!("[ALLOC]   {} share={:.3} budget=${:.0} kelly={:?} haircut={:.2} days={}",
+                   pattern, b.share, b.budget, b.kelly, b.haircut, b.active_days);
+        }
+        allocation
+    }
+}
+
+/// Rebalance now and then every `rebalance_secs` (re-read each round so reloads apply)
+pub async fn run_rebalance_loop(allocator: SharedCapitalAllocator, tracker: Arc<tokio::sync::RwLock<PositionTracker>>) {
+    loop {
+        allocator.rebalance(&*tracker.read().await);
+        let interval = allocator.config.read().unwrap().rebalance_interval;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::position_tracker::FillRecord;
+    use crate::types::Nanos;
+
+    const TODAY: i64 = 20_000;
+
+    fn config() -> AllocatorConfig {
+        AllocatorConfig {
+            bankroll: 10_000.0,
+            lookback_days: 10,
+            ..Default::default()
+        }
+    }
+
+    /// Lot closed on `day` that tied up `cost` dollars
+    fn lot(pattern: Option<&str>, platform: &str, day: i64, pnl: f64, cost: f64) -> RealizedLot {
+        RealizedLot {
+            market_id: "KXEPL-A".to_string(),
+            platform: platform.to_string(),
+            side: "yes".to_string(),
+            contracts: cost / 0.5,
+            entry_price: 0.5,
+            exit_price: 0.5,
+            fees: 0.0,
+            pnl,
+            pattern: pattern.map(str::to_string),
+            closed_at: chrono::DateTime::from_timestamp(day * DAY_SECS + 3600, 0).unwrap().to_rfc3339(),
+            exit_reason: None,
+        }
+    }
+
+    /// +100 / -80 on alternating days over the lookback, $1000 deployed per day
+    fn alternating(pattern: &str, platform: &str, flip: bool) -> Vec<RealizedLot> {
+        (0..10)
+            .map(|i| {
+                let up = (i % 2 == 0) != flip;
+                lot(Some(pattern), platform, TODAY - i, if up { 100.0 } else { -80.0 }, 1000.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fractional_kelly_with_exploration_for_new_patterns() {
+        let mut lots = alternating("A", "kalshi", false);
+        lots.push(lot(Some("A"), "kalshi", TODAY - 30, 5000.0, 1000.0)); // outside the lookback
+        lots.extend((0..6).map(|i| lot(Some("B"), "kalshi", TODAY - i, -50.0, 1000.0)));
+        lots.extend((0..2).map(|i| lot(Some("C"), "polymarket", TODAY - i, 500.0, 1000.0)));
+        lots.push(lot(None, "kalshi", TODAY, 1000.0, 1000.0));
+        let refs: Vec<&RealizedLot> = lots.iter().collect();
+
+        let allocation = allocate(&config(), &refs, TODAY);
+        assert_eq!(allocation.patterns.len(), 3);
+
+        // mean 10, variance 90^2 * 10/9 = 9000, $1000 per day -> full Kelly 1.11, quarter Kelly 0.278
+        let a = &allocation.patterns["A"];
+        assert!((a.kelly.unwrap() - 10.0 * 1000.0 / 9000.0).abs() < 1e-9);
+        assert!((a.share - 0.25 * 10.0 / 9.0).abs() < 1e-9);
+        assert_eq!((a.active_days, a.haircut), (10, 1.0));
+
+        // Losing pattern gets nothing; two active days is still exploring
+        assert_eq!(allocation.patterns["B"].share, 0.0);
+        let c = &allocation.patterns["C"];
+        assert_eq!((c.kelly, c.share, c.budget), (None, 0.02, 200.0));
+
+        let allocated: f64 = allocation.patterns.values().map(|b| b.budget).sum();
+        assert!((allocation.unallocated - (10_000.0 - allocated)).abs() < 1e-6);
+        assert!((allocation.venues["polymarket"] - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn correlated_patterns_share_a_haircut_and_totals_scale_to_bankroll() {
+        let config = AllocatorConfig { kelly_fraction: 1.0, max_share: 1.0, ..config() };
+        let mut lots = alternating("A", "kalshi", false);
+        lots.extend(alternating("B", "kalshi", false));
+        // Wins on the days A and B lose: a hedge, not an overlap
+        lots.extend(alternating("D", "polymarket", true));
+        let refs: Vec<&RealizedLot> = lots.iter().collect();
+
+        let allocation = allocate(&config, &refs, TODAY);
+        let (a, b, d) = (&allocation.patterns["A"], &allocation.patterns["B"], &allocation.patterns["D"]);
+        assert!((a.haircut - 0.5).abs() < 1e-9 && (b.haircut - 0.5).abs() < 1e-9);
+        assert!((d.haircut - 1.0).abs() < 1e-9);
+
+        // Capped at 1.0 each, haircut to 0.5 / 0.5 / 1.0, then scaled to sum to 1
+        assert!((a.share - 0.25).abs() < 1e-9 && (b.share - 0.25).abs() < 1e-9);
+        assert!((d.share - 0.5).abs() < 1e-9);
+        assert!(allocation.unallocated.abs() < 1e-6);
+        assert!((allocation.venues["kalshi"] - 5_000.0).abs() < 1e-6);
+        assert!((allocation.venues["polymarket"] - 5_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn caps_contracts_to_remaining_budget() {
+        let clock = MockClock::shared(Nanos(TODAY as u64 * DAY_SECS as u64 * 1_000_000_000));
+        let allocator = CapitalAllocator::new(config()).with_clock(clock);
+
+        // $50 already open under the pattern
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(
+            &FillRecord::new("KXEPL-A", "A", "kalshi", "yes", 100.0, 0.5, 0.0, "o1").with_pattern("KalshiOnly"),
+        );
+        let allocation = allocator.rebalance(&tracker);
+        assert_eq!(allocation.at_ns, TODAY as u64 * DAY_SECS as u64 * 1_000_000_000);
+        assert_eq!(allocator.deployed("KalshiOnly"), 50.0);
+
+        // Unseen pattern explores with 2% of $10k; $150 left at 95¢ a pair
+        assert_eq!(allocator.pattern_budget("KalshiOnly"), 200.0);
+        assert_eq!(allocator.cap_contracts("KalshiOnly", 0.95, 1000).unwrap(), 157);
+        assert_eq!(allocator.cap_contracts("KalshiOnly", 0.95, 20).unwrap(), 20);
+
+        allocator.commit("KalshiOnly", 150.0);
+        assert!(matches!(
+            allocator.cap_contracts("KalshiOnly", 0.95, 20),
+            Err(RiskRejection::BudgetExhausted { .. })
+        ));
+
+        // Off: no cap
+        allocator.apply_config(&AllocatorSection::default());
+        assert_eq!(allocator.cap_contracts("KalshiOnly", 0.95, 20).unwrap(), 20);
+    }
+}
@@ -1,11 +1,21 @@
 // src/circuit_breaker.rs
 // Safety circuit breakers - halt trading on various conditions
+// Generic keyed breaker - wraps async calls per venue/feed with sliding-window failure rates
 
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::Hash;
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{error, warn, info};
 
+use crate::config::BreakerSection;
+use crate::config::{GroupRule, RiskSection};
+use crate::market_hierarchy::MarketHierarchy;
+
 /// Circuit breaker configuration from environment
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
@@ -28,37 +38,15 @@ pub struct CircuitBreakerConfig {
     pub enabled: bool,
 }
 
-impl CircuitBreakerConfig {
-    pub fn from_env() -> Self {
+impl From<&RiskSection> for CircuitBreakerConfig {
+    fn from(risk: &RiskSection) -> Self {
         Self {
-            max_position_per_market: std::env::var("CB_MAX_POSITION_PER_MARKET")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(50000),
-            
-            max_total_position: std::env::var("CB_MAX_TOTAL_POSITION")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(100000),
-            
-            max_daily_loss: std::env::var("CB_MAX_DAILY_LOSS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(500.0),
-            
-            max_consecutive_errors: std::env::var("CB_MAX_CONSECUTIVE_ERRORS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(5),
-            
-            cooldown_secs: std::env::var("CB_COOLDOWN_SECS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(300), // 5 minutes default
-            
-            enabled: std::env::var("CB_ENABLED")
-                .map(|v| v == "1" || v == "true")
-                .unwrap_or(true), // Enabled by default for safety
+            max_position_per_market: risk.max_position_per_market,
+            max_total_position: risk.max_total_position,
+            max_daily_loss: risk.max_daily_loss,
+            max_consecutive_errors: risk.max_consecutive_errors,
+            cooldown_secs: risk.cooldown_secs,
+            enabled: risk.enabled,
         }
     }
 }
@@ -67,6 +55,10 @@ impl CircuitBreakerConfig {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TripReason {
     MaxPositionPerMarket { market: String, position: i64, limit: i64 },
+    /// Own contracts plus correlation-weighted sibling contracts over the per-market limit
+    MaxCorrelatedPosition { market: String, position: i64, limit: i64 },
+    /// Contracts across a Kalshi series or event over its `[risk.groups]` limit
+    MaxGroupPosition { group: String, position: i64, limit: i64 },
     MaxTotalPosition { position: i64, limit: i64 },
     MaxDailyLoss { loss: f64, limit: f64 },
     ConsecutiveErrors { count: u32, limit: u32 },
@@ -79,6 +71,12 @@ impl std::fmt::Display for TripReason {
             TripReason::MaxPositionPerMarket { market, position, limit } => {
                 write!(f, "Max position per market: {} has {} contracts (limit: {})", market, position, limit)
             }
+            TripReason::MaxCorrelatedPosition { market, position, limit } => {
+                write!(f, "Max correlated position: {} has {} contracts incl. correlated siblings (limit: {})", market, position, limit)
+            }
+            TripReason::MaxGroupPosition { group, position, limit } => {
+                write!(f, "Max group position: {} has {} contracts (limit: {})", group, position, limit)
+            }
             TripReason::MaxTotalPosition { position, limit } => {
                 write!(f, "Max total position: {} contracts (limit: {})", position, limit)
             }
@@ -95,6 +93,8 @@ impl std::fmt::Display for TripReason {
     }
 }
 
+impl std::error::Error for TripReason {}
+
 /// Position tracking for a single market
 #[derive(Debug, Default)]
 pub struct MarketPosition {
@@ -116,9 +116,10 @@ impl MarketPosition {
     }
 }
 
-/// Circuit breaker state
-pub struct CircuitBreaker {
-    config: CircuitBreakerConfig,
+/// Trading halt breaker state (position, loss and error limits)
+pub struct TradingCircuitBreaker {
+    /// Limits (swappable at runtime via `apply_config`)
+    config: std::sync::RwLock<CircuitBreakerConfig>,
     
     /// Whether trading is currently halted
     halted: AtomicBool,
@@ -137,9 +138,12 @@ pub struct CircuitBreaker {
     
     /// Positions per market
     positions: RwLock<std::collections::HashMap<String, MarketPosition>>,
+
+    /// Series/event limits and correlations inherited by registered markets
+    hierarchy: std::sync::RwLock<MarketHierarchy>,
 }
 
-impl CircuitBreaker {
+impl TradingCircuitBreaker {
     pub fn new(config: CircuitBreakerConfig) -> Self {
         info!("[CB] Circuit breaker initialized:");
         info!("[CB]   Enabled: {}", config.enabled);
@@ -150,28 +154,57 @@ impl CircuitBreaker {
         info!("[CB]   Cooldown: {}s", config.cooldown_secs);
         
         Self {
-            config,
+            config: std::sync::RwLock::new(config),
             halted: AtomicBool::new(false),
             tripped_at: RwLock::new(None),
             trip_reason: RwLock::new(None),
             consecutive_errors: AtomicI64::new(0),
             daily_pnl_cents: AtomicI64::new(0),
             positions: RwLock::new(std::collections::HashMap::new()),
+            hierarchy: std::sync::RwLock::new(MarketHierarchy::default()),
         }
     }
+
+    /// Enforce `[risk.groups]` series/event limits and correlations on the registered markets
+    pub fn with_hierarchy(self, hierarchy: MarketHierarchy) -> Self {
+        *self.hierarchy.write().unwrap() = hierarchy;
+        self
+    }
+
+    /// Swap in reloaded `[risk.groups]` (hot reload)
+    pub fn apply_group_rules(&self, rules: BTreeMap<String, GroupRule>) {
+        info!("[CB] Group rules updated: {} series/events", rules.len());
+        self.hierarchy.write().unwrap().set_rules(rules);
+    }
     
+    /// Current limits
+    pub fn config(&self) -> CircuitBreakerConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Swap in new limits (hot reload); a trip in progress is kept
+    pub fn apply_config(&self, config: CircuitBreakerConfig) {
+        info!("[CB] Limits updated: per-market {} / total {} contracts, daily loss ${:.2}, errors {}",
+              config.max_position_per_market, config.max_total_position,
+              config.max_daily_loss, config.max_consecutive_errors);
+        *self.config.write().unwrap() = config;
+    }
+
     /// Check if trading is allowed
     #[allow(dead_code)]
     pub fn is_trading_allowed(&self) -> bool {
-        if !self.config.enabled {
+        if !self.config().enabled {
             return true;
         }
         !self.halted.load(Ordering::SeqCst)
     }
     
     /// Check if we can execute a trade for a specific market
+    /// `contracts` is the size per leg; both legs count toward the position limits,
+    /// matching what `record_success` adds
     pub async fn can_execute(&self, market_id: &str, contracts: i64) -> Result<(), TripReason> {
-        if !self.config.enabled {
+        let config = self.config();
+        if !config.enabled {
             return Ok(());
         }
         
@@ -182,34 +215,71 @@ impl CircuitBreaker {
         
         // Check position limits
         let positions = self.positions.read().await;
+        let added = contracts * 2;
+        let hierarchy = self.hierarchy.read().unwrap();
+        let rule = hierarchy.rule_for(market_id);
         
-        // Per-market limit
-        if let Some(pos) = positions.get(market_id) {
-            let new_position = pos.total_contracts() + contracts;
-            if new_position > self.config.max_position_per_market {
-                return Err(TripReason::MaxPositionPerMarket {
+        // Per-market limit (a market's first trade counts too); its series/event may set its own
+        let market_limit = if rule.max_position_per_market > 0 { rule.max_position_per_market } else { config.max_position_per_market };
+        let current = positions.get(market_id).map(|p| p.total_contracts()).unwrap_or(0);
+        let new_position = current + added;
+        if new_position > market_limit {
+            return Err(TripReason::MaxPositionPerMarket {
+                market: market_id.to_string(),
+                position: new_position,
+                limit: market_limit,
+            });
+        }
+
+        // Correlated siblings count toward the market's own limit at their declared correlation
+        if rule.correlation > 0.0 {
+            let correlated: f64 = positions.iter()
+                .filter(|(id, _)| id.as_str() != market_id)
+                .map(|(id, p)| hierarchy.correlation(market_id, id) * p.total_contracts() as f64)
+                .sum();
+            let exposure = new_position + correlated.round() as i64;
+            if exposure > market_limit {
+                return Err(TripReason::MaxCorrelatedPosition {
                     market: market_id.to_string(),
-                    position: new_position,
-                    limit: self.config.max_position_per_market,
+                    position: exposure,
+                    limit: market_limit,
+                });
+            }
+        }
+
+        // Series/event limits across all of the group's markets
+        for (group, group_rule) in hierarchy.groups_of(market_id) {
+            if group_rule.max_position <= 0 {
+                continue;
+            }
+            let held: i64 = positions.iter()
+                .filter(|(id, _)| hierarchy.in_group(id, group))
+                .map(|(_, p)| p.total_contracts())
+                .sum();
+            if held + added > group_rule.max_position {
+                return Err(TripReason::MaxGroupPosition {
+                    group: group.to_string(),
+                    position: held + added,
+                    limit: group_rule.max_position,
                 });
             }
         }
         
         // Total position limit
         let total: i64 = positions.values().map(|p| p.total_contracts()).sum();
-        if total + contracts > self.config.max_total_position {
+        if total + added > config.max_total_position {
             return Err(TripReason::MaxTotalPosition {
-                position: total + contracts,
-                limit: self.config.max_total_position,
+                position: total + added,
+                limit: config.max_total_position,
             });
         }
         
         // Daily loss limit
         let daily_loss = -self.daily_pnl_cents.load(Ordering::SeqCst) as f64 / 100.0;
-        if daily_loss > self.config.max_daily_loss {
+        if daily_loss > config.max_daily_loss {
             return Err(TripReason::MaxDailyLoss {
                 loss: daily_loss,
-                limit: self.config.max_daily_loss,
+                limit: config.max_daily_loss,
             });
         }
         
@@ -232,18 +302,30 @@ impl CircuitBreaker {
         pos.poly_no += poly_contracts;
     }
     
+    /// Drop a market's position counters once it has settled
+    /// Frees per-market and total position capacity for new trades
+    pub async fn release_market(&self, market_id: &str) -> Option<MarketPosition> {
+        self.positions.write().await.remove(market_id)
+    }
+
     /// Record an error
     pub async fn record_error(&self) {
         let errors = self.consecutive_errors.fetch_add(1, Ordering::SeqCst) + 1;
+        let limit = self.config().max_consecutive_errors;
         
-        if errors >= self.config.max_consecutive_errors as i64 {
+        if errors >= limit as i64 {
             self.trip(TripReason::ConsecutiveErrors {
                 count: errors as u32,
-                limit: self.config.max_consecutive_errors,
+                limit,
             }).await;
         }
     }
     
+    /// Realized P&L since the last daily reset (dollars)
+    pub fn daily_pnl(&self) -> f64 {
+        self.daily_pnl_cents.load(Ordering::SeqCst) as f64 / 100.0
+    }
+
     /// Record P&L update (for tracking without execution)
     #[allow(dead_code)]
     pub fn record_pnl(&self, pnl: f64) {
@@ -253,11 +335,11 @@ impl CircuitBreaker {
 
     /// Trip the circuit breaker
     pub async fn trip(&self, reason: TripReason) {
-        if !self.config.enabled {
+        if !self.config().enabled {
             return;
         }
         
-        error!("🚨 CIRCUIT BREAKER TRIPPED: {}", reason);
+        error!("[CB] CIRCUIT BREAKER TRIPPED: {}", reason);
         
         self.halted.store(true, Ordering::SeqCst);
         *self.tripped_at.write().await = Some(Instant::now());
@@ -297,7 +379,7 @@ impl CircuitBreaker {
 
         let tripped_at = self.tripped_at.read().await;
         if let Some(tripped) = *tripped_at {
-            if tripped.elapsed() > Duration::from_secs(self.config.cooldown_secs) {
+            if tripped.elapsed() > Duration::from_secs(self.config().cooldown_secs) {
                 drop(tripped_at); // Release read lock before reset
                 self.reset().await;
                 return true;
@@ -314,7 +396,7 @@ impl CircuitBreaker {
         let total_position: i64 = positions.values().map(|p| p.total_contracts()).sum();
         
         CircuitBreakerStatus {
-            enabled: self.config.enabled,
+            enabled: self.config().enabled,
             halted: self.halted.load(Ordering::SeqCst),
             trip_reason: self.trip_reason.read().await.clone(),
             consecutive_errors: self.consecutive_errors.load(Ordering::SeqCst) as u32,
@@ -357,6 +439,326 @@ impl std::fmt::Display for CircuitBreakerStatus {
     }
 }
 
+// === Generic Keyed Circuit Breaker ===
+
+/// Keyed breaker configuration
+#[derive(Debug, Clone)]
+pub struct BreakerConfig {
+    /// Sliding window over which the failure rate is measured
+    pub window: Duration,
+    /// Calls in the window before the failure rate is evaluated
+    pub min_calls: u32,
+    /// Open when failures / calls reaches this rate
+    pub failure_rate: f64,
+    /// How long the circuit stays open before probing
+    pub open_for: Duration,
+    /// Probe calls admitted while half-open; all must succeed to close
+    pub half_open_probes: u32,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self::from(&BreakerSection::default())
+    }
+}
+
+impl From<&BreakerSection> for BreakerConfig {
+    fn from(b: &BreakerSection) -> Self {
+        Self {
+            window: Duration::from_secs(b.window_secs),
+            min_calls: b.min_calls,
+            failure_rate: b.failure_rate,
+            open_for: Duration::from_secs(b.open_secs),
+            half_open_probes: b.half_open_probes,
+        }
+    }
+}
+
+/// Per-key circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl std::fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakerState::Closed => write!(f, "CLOSED"),
+            BreakerState::Open => write!(f, "OPEN"),
+            BreakerState::HalfOpen => write!(f, "HALF_OPEN"),
+        }
+    }
+}
+
+/// State transitions and rejections, delivered to registered hooks
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakerEvent<K> {
+    Opened { key: K, failure_rate: f64 },
+    HalfOpened { key: K },
+    Closed { key: K },
+    Rejected { key: K },
+}
+
+/// Error from a wrapped call
+#[derive(Debug)]
+pub enum BreakerError<E> {
+    /// Call was not attempted; the circuit for `key` is open
+    Open { key: String },
+    /// Call ran and failed
+    Inner(E),
+}
+
+impl<E: Display> Display for BreakerError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreakerError::Open { key } => write!(f, "circuit open for {}", key),
+            BreakerError::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + Display> std::error::Error for BreakerError<E> {}
+
+type EventHook<K> = Arc<dyn Fn(&BreakerEvent<K>) + Send + Sync>;
+
+struct Circuit {
+    state: BreakerState,
+    /// (when, failed) outcomes inside the window
+    outcomes: VecDeque<(Instant, bool)>,
+    opened_at: Option<Instant>,
+    probes_issued: u32,
+    probe_successes: u32,
+}
+
+impl Circuit {
+    fn new() -> Self {
+        Self {
+            state: BreakerState::Closed,
+            outcomes: VecDeque::new(),
+            opened_at: None,
+            probes_issued: 0,
+            probe_successes: 0,
+        }
+    }
+
+    fn trim(&mut self, window: Duration, now: Instant) {
+        while let Some(&(at, _)) = self.outcomes.front() {
+            if now.duration_since(at) > window {
+                self.outcomes.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn failure_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = self.outcomes.iter().filter(|(_, failed)| *failed).count();
+        failures as f64 / self.outcomes.len() as f64
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.state = BreakerState::Open;
+        self.opened_at = Some(now);
+        self.probes_issued = 0;
+        self.probe_successes = 0;
+    }
+}
+
+/// Circuit breaker keyed by venue, feed, endpoint, ...
+pub struct CircuitBreaker<K> {
+    config: BreakerConfig,
+    circuits: Mutex<HashMap<K, Circuit>>,
+    hooks: Vec<EventHook<K>>,
+}
+
+/// Admission for one call; releases an unused half-open probe slot if dropped unrecorded
+struct Permit<'a, K: Eq + Hash + Clone + Display> {
+    breaker: &'a CircuitBreaker<K>,
+    key: K,
+    recorded: bool,
+}
+
+impl<K: Eq + Hash + Clone + Display> Drop for Permit<'_, K> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            let mut circuits = self.breaker.circuits.lock().unwrap();
+            if let Some(c) = circuits.get_mut(&self.key) {
+                if c.state == BreakerState::HalfOpen {
+                    c.probes_issued = c.probes_issued.saturating_sub(1);
+                }
+            }
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<K: Eq + Hash + Clone + Display> CircuitBreaker<K> {
+    pub fn new(config: BreakerConfig) -> Self {
+        Self { config, circuits: Mutex::new(HashMap::new()), hooks: Vec::new() }
+    }
+
+    /// Register an event hook (called outside the breaker lock)
+    pub fn on_event(mut self, hook: impl Fn(&BreakerEvent<K>) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    fn emit(&self, events: Vec<BreakerEvent<K>>) {
+        for event in &events {
+            match event {
+                BreakerEvent::Opened { key, failure_rate } => {
+                    warn!("[CB] {} circuit OPEN (failure rate {:.0}%)", key, failure_rate * 100.0)
+                }
+                BreakerEvent::HalfOpened { key } => info!("[CB] {} circuit HALF_OPEN, probing", key),
+                BreakerEvent::Closed { key } => info!("[CB] {} circuit CLOSED", key),
+                BreakerEvent::Rejected { .. } => {}
+            }
+            for hook in &self.hooks {
+                hook(event);
+            }
+        }
+    }
+
+    /// Current state (an elapsed open period reports HalfOpen)
+    pub fn state(&self, key: &K) -> BreakerState {
+        let circuits = self.circuits.lock().unwrap();
+        match circuits.get(key) {
+            None => BreakerState::Closed,
+            Some(c) if c.state == BreakerState::Open
+                && c.opened_at.is_some_and(|t| t.elapsed() >= self.config.open_for) => BreakerState::HalfOpen,
+            Some(c) => c.state,
+        }
+    }
+
+    /// Failure rate over the current window
+    pub fn failure_rate(&self, key: &K) -> f64 {
+        let mut circuits = self.circuits.lock().unwrap();
+        match circuits.get_mut(key) {
+            Some(c) => {
+                c.trim(self.config.window, Instant::now());
+                c.failure_rate()
+            }
+            None => 0.0,
+        }
+    }
+
+    /// All known keys and their states
+    pub fn snapshot(&self) -> Vec<(K, BreakerState)> {
+        let keys: Vec<K> = self.circuits.lock().unwrap().keys().cloned().collect();
+        keys.into_iter().map(|k| { let s = self.state(&k); (k, s) }).collect()
+    }
+
+    /// Admit a call for `key`; in half-open this consumes a probe slot
+    pub fn allow(&self, key: &K) -> bool {
+        let mut events = Vec::new();
+        let allowed = {
+            let mut circuits = self.circuits.lock().unwrap();
+            let c = circuits.entry(key.clone()).or_insert_with(Circuit::new);
+            if c.state == BreakerState::Open
+                && c.opened_at.is_some_and(|t| t.elapsed() >= self.config.open_for)
+            {
+                c.state = BreakerState::HalfOpen;
+                events.push(BreakerEvent::HalfOpened { key: key.clone() });
+            }
+            match c.state {
+                BreakerState::Closed => true,
+                BreakerState::Open => false,
+                BreakerState::HalfOpen => {
+                    if c.probes_issued < self.config.half_open_probes {
+                        c.probes_issued += 1;
+                        true
+                    } else {
+                        false
+                    }
+                }
+            }
+        };
+        if !allowed {
+            events.push(BreakerEvent::Rejected { key: key.clone() });
+        }
+        self.emit(events);
+        allowed
+    }
+
+    /// Record the outcome of an admitted call
+    pub fn record(&self, key: &K, success: bool) {
+        let mut events = Vec::new();
+        {
+            let now = Instant::now();
+            let mut circuits = self.circuits.lock().unwrap();
+            let c = circuits.entry(key.clone()).or_insert_with(Circuit::new);
+            match c.state {
+                BreakerState::HalfOpen => {
+                    if success {
+                        c.probe_successes += 1;
+                        if c.probe_successes >= self.config.half_open_probes {
+                            c.state = BreakerState::Closed;
+                            c.outcomes.clear();
+                            c.opened_at = None;
+                            events.push(BreakerEvent::Closed { key: key.clone() });
+                        }
+                    } else {
+                        c.open(now);
+                        events.push(BreakerEvent::Opened { key: key.clone(), failure_rate: 1.0 });
+                    }
+                }
+                BreakerState::Closed => {
+                    c.outcomes.push_back((now, !success));
+                    c.trim(self.config.window, now);
+                    let rate = c.failure_rate();
+                    if c.outcomes.len() as u32 >= self.config.min_calls && rate >= self.config.failure_rate {
+                        c.open(now);
+                        events.push(BreakerEvent::Opened { key: key.clone(), failure_rate: rate });
+                    }
+                }
+                // Late result from a call admitted before the circuit opened
+                BreakerState::Open => {}
+            }
+        }
+        self.emit(events);
+    }
+
+    /// Force a key back to closed (operator reset)
+    pub fn reset(&self, key: &K) {
+        let removed = self.circuits.lock().unwrap().remove(key).is_some();
+        if removed {
+            self.emit(vec![BreakerEvent::Closed { key: key.clone() }]);
+        }
+    }
+
+    /// Run `fut` under the breaker; any `Err` counts as a failure
+    pub async fn call<T, E, F>(&self, key: K, fut: F) -> Result<T, BreakerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+    {
+        self.call_with(key, fut, |r| r.is_err()).await
+    }
+
+    /// Run `fut` under the breaker with a custom failure classifier
+    /// (e.g. treat HTTP 5xx responses as failures, 4xx as successes)
+    pub async fn call_with<T, E, F, C>(&self, key: K, fut: F, is_failure: C) -> Result<T, BreakerError<E>>
+    where
+        F: Future<Output = Result<T, E>>,
+        C: FnOnce(&Result<T, E>) -> bool,
+    {
+        if !self.allow(&key) {
+            return Err(BreakerError::Open { key: key.to_string() });
+        }
+        let mut permit = Permit { breaker: self, key, recorded: false };
+        let result = fut.await;
+        self.record(&permit.key, !is_failure(&result));
+        permit.recorded = true;
+        result.map_err(BreakerError::Inner)
+    }
+}
+
+pub type SharedBreaker<K> = Arc<CircuitBreaker<K>>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -372,7 +774,7 @@ mod tests {
             enabled: true,
         };
         
-        let cb = CircuitBreaker::new(config);
+        let cb = TradingCircuitBreaker::new(config);
         
         // Should allow initial trade
         assert!(cb.can_execute("market1", 5).await.is_ok());
@@ -384,6 +786,44 @@ mod tests {
         let result = cb.can_execute("market1", 10).await;
         assert!(matches!(result, Err(TripReason::MaxPositionPerMarket { .. })));
     }
+
+    #[tokio::test]
+    async fn test_series_and_event_limits_are_inherited() {
+        let config = CircuitBreakerConfig {
+            max_position_per_market: 100,
+            max_total_position: 1000,
+            max_daily_loss: 100.0,
+            max_consecutive_errors: 3,
+            cooldown_secs: 60,
+            enabled: true,
+        };
+        let mut rules = BTreeMap::new();
+        rules.insert("KXNBAGAME".to_string(), GroupRule { max_position: 120, ..GroupRule::default() });
+        rules.insert("KXNBAGAME-25DEC01LALBOS".to_string(), GroupRule { correlation: 0.5, ..GroupRule::default() });
+        let mut hierarchy = MarketHierarchy::new(rules);
+        for (id, ticker) in [
+            ("lal", "KXNBAGAME-25DEC01LALBOS-LAL"),
+            ("bos", "KXNBAGAME-25DEC01LALBOS-BOS"),
+            ("nyk", "KXNBAGAME-25DEC02NYKMIA-NYK"),
+        ] {
+            hierarchy.register(id, crate::types::KalshiPath::from_market_ticker(ticker).unwrap());
+        }
+        let cb = TradingCircuitBreaker::new(config).with_hierarchy(hierarchy);
+
+        cb.record_success("lal", 40, 40, 0.0).await;
+        // 20 own + half of LAL's 80 fits under 100; 70 own + 40 does not
+        assert!(cb.can_execute("bos", 10).await.is_ok());
+        assert!(matches!(cb.can_execute("bos", 35).await, Err(TripReason::MaxCorrelatedPosition { position: 110, .. })));
+        // Another game is uncorrelated but shares the series' 120-contract cap
+        assert!(cb.can_execute("nyk", 20).await.is_ok());
+        assert!(matches!(
+            cb.can_execute("nyk", 25).await,
+            Err(TripReason::MaxGroupPosition { position: 130, limit: 120, .. })
+        ));
+
+        cb.apply_group_rules(BTreeMap::new());
+        assert!(cb.can_execute("nyk", 25).await.is_ok());
+    }
     
     #[tokio::test]
     async fn test_consecutive_errors() {
@@ -396,7 +836,7 @@ mod tests {
             enabled: true,
         };
         
-        let cb = CircuitBreaker::new(config);
+        let cb = TradingCircuitBreaker::new(config);
         
         // Record errors
         cb.record_error().await;
@@ -407,4 +847,70 @@ mod tests {
         cb.record_error().await;
         assert!(!cb.is_trading_allowed());
     }
-}
\ No newline at end of file
+
+    fn breaker_config() -> BreakerConfig {
+        BreakerConfig {
+            window: Duration::from_secs(60),
+            min_calls: 4,
+            failure_rate: 0.5,
+            open_for: Duration::from_millis(30),
+            half_open_probes: 2,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_on_failure_rate() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let cb = CircuitBreaker::new(breaker_config())
+            .on_event(move |e: &BreakerEvent<&'static str>| sink.lock().unwrap().push(e.clone()));
+
+        // 1 failure in 3 calls stays closed; 2 of 4 (50%) opens
+        for ok in [true, false, true] {
+            let _ = cb.call("kalshi", async move { if ok { Ok(()) } else { Err("boom") } }).await;
+        }
+        assert_eq!(cb.state(&"kalshi"), BreakerState::Closed);
+        let _ = cb.call("kalshi", async { Err::<(), _>("boom") }).await;
+        assert_eq!(cb.state(&"kalshi"), BreakerState::Open);
+
+        // Rejected without running the future
+        let r = cb.call("kalshi", async { Ok::<_, &str>(1) }).await;
+        assert!(matches!(r, Err(BreakerError::Open { .. })));
+        // Other keys are independent
+        assert!(cb.call("poly", async { Ok::<_, &str>(1) }).await.is_ok());
+
+        let events = events.lock().unwrap();
+        assert!(matches!(events[0], BreakerEvent::Opened { key: "kalshi", failure_rate } if failure_rate == 0.5));
+        assert!(matches!(events[1], BreakerEvent::Rejected { key: "kalshi" }));
+    }
+
+    #[tokio::test]
+    async fn test_breaker_half_open_probe_budget() {
+        let cb: CircuitBreaker<&'static str> = CircuitBreaker::new(breaker_config());
+        for _ in 0..4 {
+            assert!(cb.allow(&"feed"));
+            cb.record(&"feed", false);
+        }
+        assert_eq!(cb.state(&"feed"), BreakerState::Open);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        // Two probes admitted, the third rejected until they resolve
+        assert!(cb.allow(&"feed"));
+        assert!(cb.allow(&"feed"));
+        assert!(!cb.allow(&"feed"));
+        cb.record(&"feed", true);
+        assert_eq!(cb.state(&"feed"), BreakerState::HalfOpen);
+        cb.record(&"feed", true);
+        assert_eq!(cb.state(&"feed"), BreakerState::Closed);
+
+        // A failed probe reopens immediately
+        for _ in 0..4 {
+            cb.allow(&"feed");
+            cb.record(&"feed", false);
+        }
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert!(cb.allow(&"feed"));
+        cb.record(&"feed", false);
+        assert_eq!(cb.state(&"feed"), BreakerState::Open);
+    }
+}
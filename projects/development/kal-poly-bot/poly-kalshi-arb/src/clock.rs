@@ -0,0 +1,184 @@
+// src/clock.rs
+// Crate-wide clock - correlated monotonic + wall-clock stamps, mockable for tests and the backtester
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::types::Nanos;
+
+/// Monotonic and wall-clock readings taken together
+///
+/// `mono` is for intervals, deadlines and latency (never jumps); `wall` is
+/// Unix-epoch time for logs, persistence and provider timestamps. Never
+/// subtract one kind from the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    pub mono: Nanos,
+    pub wall: Nanos,
+}
+
+impl Stamp {
+    /// Monotonic time elapsed since an earlier stamp (zero if out of order)
+    pub fn since(&self, earlier: &Stamp) -> Nanos {
+        self.mono.saturating_sub(earlier.mono)
+    }
+
+    /// Wall time of another monotonic reading from the same clock
+    pub fn wall_at(&self, mono: Nanos) -> Nanos {
+        if mono >= self.mono {
+            Nanos(self.wall.0.saturating_add(mono.0 - self.mono.0))
+        } else {
+            self.wall.saturating_sub(Nanos(self.mono.0 - mono.0))
+        }
+    }
+}
+
+/// Source of time; inject a `MockClock` in tests and replays
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Monotonic nanoseconds since the clock's epoch
+    fn mono_ns(&self) -> Nanos;
+
+    /// Nanoseconds since the Unix epoch
+    fn wall_ns(&self) -> Nanos;
+
+    fn now(&self) -> Stamp {
+        Stamp { mono: self.mono_ns(), wall: self.wall_ns() }
+    }
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The process clock as a `SharedClock`
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock::new())
+}
+
+// === Real clock ===
+
+static PROCESS_CLOCK: OnceLock<SystemClock> = OnceLock::new();
+
+/// Real time; every instance shares one process-wide monotonic epoch, so
+/// readings from different modules (WS detection vs execution) compare directly
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        *PROCESS_CLOCK.get_or_init(|| SystemClock { start: Instant::now() })
+    }
+
+    /// Monotonic nanoseconds (inherent for the hot path; avoids dyn dispatch)
+    #[inline(always)]
+    pub fn now_ns(&self) -> Nanos {
+        Nanos(self.start.elapsed().as_nanos() as u64)
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    #[inline]
+    fn mono_ns(&self) -> Nanos {
+        self.now_ns()
+    }
+
+    fn wall_ns(&self) -> Nanos {
+        Nanos(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64)
+    }
+}
+
+// === Mock clock ===
+
+/// Manually driven clock; time only moves when told to
+#[derive(Debug, Default)]
+pub struct MockClock {
+    mono: AtomicU64,
+    wall: AtomicU64,
+}
+
+impl MockClock {
+    /// Starts at monotonic zero and the given wall time
+    pub fn new(wall_start: Nanos) -> Self {
+        Self { mono: AtomicU64::new(0), wall: AtomicU64::new(wall_start.0) }
+    }
+
+    pub fn shared(wall_start: Nanos) -> Arc<Self> {
+        Arc::new(Self::new(wall_start))
+    }
+
+    /// Move both readings forward
+    pub fn advance(&self, by: Duration) {
+        let ns = by.as_nanos() as u64;
+        self.mono.fetch_add(ns, Ordering::AcqRel);
+        self.wall.fetch_add(ns, Ordering::AcqRel);
+    }
+
+    /// Jump to a wall time (e.g. the next replayed tick); monotonic time
+    /// advances by the same amount and never goes backwards
+    pub fn set_wall(&self, wall: Nanos) {
+        let prev = self.wall.swap(wall.0, Ordering::AcqRel);
+        self.mono.fetch_add(wall.0.saturating_sub(prev), Ordering::AcqRel);
+    }
+}
+
+impl Clock for MockClock {
+    fn mono_ns(&self) -> Nanos {
+        Nanos(self.mono.load(Ordering::Acquire))
+    }
+
+    fn wall_ns(&self) -> Nanos {
+        Nanos(self.wall.load(Ordering::Acquire))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clocks_share_epoch() {
+        let a = SystemClock::new();
+        std::thread::sleep(Duration::from_millis(2));
+        let b = SystemClock::new();
+        // A later instance must not restart at zero
+        let (ta, tb) = (a.now_ns(), b.now_ns());
+        assert!(tb >= ta && ta >= Nanos(2_000_000));
+
+        let stamp = a.now();
+        assert!(stamp.wall > Nanos(1_600_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_mock_clock_drives_both_readings() {
+        let clock = MockClock::new(Nanos(1_000));
+        assert_eq!(clock.now(), Stamp { mono: Nanos(0), wall: Nanos(1_000) });
+
+        clock.advance(Duration::from_nanos(500));
+        assert_eq!(clock.now(), Stamp { mono: Nanos(500), wall: Nanos(1_500) });
+
+        clock.set_wall(Nanos(2_000));
+        assert_eq!(clock.now(), Stamp { mono: Nanos(1_000), wall: Nanos(2_000) });
+
+        // Wall may be rewound (out-of-order replay); monotonic holds still
+        clock.set_wall(Nanos(1_800));
+        assert_eq!(clock.now(), Stamp { mono: Nanos(1_000), wall: Nanos(1_800) });
+    }
+
+    #[test]
+    fn test_stamp_projection() {
+        let earlier = Stamp { mono: Nanos(100), wall: Nanos(10_100) };
+        let later = Stamp { mono: Nanos(250), wall: Nanos(10_260) };
+        assert_eq!(later.since(&earlier), Nanos(150));
+        assert_eq!(earlier.since(&later), Nanos(0));
+        assert_eq!(later.wall_at(Nanos(300)), Nanos(10_310));
+        assert_eq!(later.wall_at(Nanos(200)), Nanos(10_210));
+    }
+}
@@ -0,0 +1,302 @@
+// src/clock_sync.rs
+// Clock sync - per-provider clock offset and skew against the local wall clock, estimated from
+// provider-stamped updates (one-way, anchored by the feed's ping) and from SNTP exchanges; the
+// feed aggregator rewrites `PriceUpdate::provider_timestamp` onto local time with it, so
+// cross-provider latency and half-life math isn't biased by a venue's drifting clock
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::UdpSocket;
+use tracing::{info, warn};
+
+use crate::config::ClockSyncSection;
+use crate::provider_registry::ProviderId;
+use crate::types::{Nanos, TimestampNs};
+
+/// Skew is only fitted across at least this much local time
+const MIN_SKEW_SPAN_NS: u64 = 10_000_000_000;
+/// Seconds from the NTP epoch (1900) to the Unix epoch
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+/// Clock sync settings
+#[derive(Debug, Clone)]
+pub struct ClockSyncConfig {
+    /// SNTP server for the local clock's reference offset ("" = none)
+    pub ntp_server: String,
+    pub ntp_interval: Duration,
+    /// Samples kept per provider
+    pub window: usize,
+}
+
+impl Default for ClockSyncConfig {
+    fn default() -> Self {
+        Self::from(&ClockSyncSection::default())
+    }
+}
+
+impl From<&ClockSyncSection> for ClockSyncConfig {
+    fn from(c: &ClockSyncSection) -> Self {
+        Self {
+            ntp_server: c.ntp_server.clone(),
+            ntp_interval: Duration::from_secs(c.ntp_interval_secs),
+            window: c.window,
+        }
+    }
+}
+
+/// One measurement of a remote clock against the local wall clock (all Unix ns)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSample {
+    /// NTP-style exchange: local send, remote receive, remote send, local receive
+    RoundTrip { t0: u64, t1: u64, t2: u64, t3: u64 },
+    /// A provider-stamped update, with the feed's expected one-way transit (half its ping)
+    OneWay { remote: u64, received: u64, transit: u64 },
+}
+
+impl ClockSample {
+    pub fn local_ns(&self) -> u64 {
+        match *self {
+            ClockSample::RoundTrip { t3, .. } => t3,
+            ClockSample::OneWay { received, .. } => received,
+        }
+    }
+
+    /// Remote minus local time
+    pub fn offset_ns(&self) -> i64 {
+        match *self {
+            ClockSample::RoundTrip { t0, t1, t2, t3 } => ((t1 as i64 - t0 as i64) + (t2 as i64 - t3 as i64)) / 2,
+            ClockSample::OneWay { remote, received, transit } => remote as i64 + transit as i64 - received as i64,
+        }
+    }
+
+    /// Network time the sample spent beyond the remote's own processing (round trips only)
+    fn delay_ns(&self) -> Option<u64> {
+        match *self {
+            ClockSample::RoundTrip { t0, t1, t2, t3 } => Some(t3.saturating_sub(t0).saturating_sub(t2.saturating_sub(t1))),
+            ClockSample::OneWay { .. } => None,
+        }
+    }
+}
+
+/// Where a remote clock stands against ours
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ClockEstimate {
+    /// Remote minus local at `at_ns`
+    pub offset_ns: i64,
+    /// Remote clock's drift against ours (parts per million, + = remote runs fast)
+    pub skew_ppm: f64,
+    /// Local wall time the offset was measured at
+    pub at_ns: u64,
+    pub samples: usize,
+}
+
+impl ClockEstimate {
+    /// Offset extrapolated to local wall time `local_ns`
+    pub fn offset_at(&self, local_ns: u64) -> i64 {
+        let elapsed = local_ns as f64 - self.at_ns as f64;
+        self.offset_ns + (elapsed * self.skew_ppm / 1e6).round() as i64
+    }
+}
+
+/// Best sample of a slice: least round-trip delay, or for one-way samples the least apparent
+/// transit (queueing only ever delays an update, so the earliest-looking one is truest)
+fn best(samples: &[ClockSample]) -> Option<ClockSample> {
+    samples.iter().copied().min_by_key(|s| match (s.delay_ns(), s) {
+        (Some(delay), _) => delay as i64,
+        (None, ClockSample::OneWay { remote, received, .. }) => *received as i64 - *remote as i64,
+        _ => i64::MAX,
+    })
+}
+
+/// Offset from the best sample of the recent half, skew from the best of each half
+fn estimate(samples: &VecDeque<ClockSample>) -> Option<ClockEstimate> {
+    let samples: Vec<ClockSample> = samples.iter().copied().collect();
+    let (older, recent) = samples.split_at(samples.len() / 2);
+    let latest = best(recent)?;
+    let skew_ppm = match best(older) {
+        Some(first) if latest.local_ns().saturating_sub(first.local_ns()) >= MIN_SKEW_SPAN_NS => {
+            let drift = (latest.offset_ns() - first.offset_ns()) as f64;
+            drift / (latest.local_ns() - first.local_ns()) as f64 * 1e6
+        }
+        _ => 0.0,
+    };
+    Some(ClockEstimate { offset_ns: latest.offset_ns(), skew_ppm, at_ns: latest.local_ns(), samples: samples.len() })
+}
+
+/// One row of the clock sync probe
+#[derive(Debug, Clone, Serialize)]
+pub struct ClockStatus {
+    /// Provider name, or "reference" for the NTP offset of the local clock
+    pub clock: String,
+    #[serde(flatten)]
+    pub estimate: ClockEstimate,
+}
+
+/// Per-provider clock estimates; providers without their own samples fall back on the NTP
+/// reference (venues are assumed to keep UTC)
+#[derive(Debug)]
+pub struct ClockSync {
+    window: usize,
+    providers: Mutex<HashMap<ProviderId, VecDeque<ClockSample>>>,
+    reference: Mutex<VecDeque<ClockSample>>,
+}
+
+pub type SharedClockSync = Arc<ClockSync>;
+
+impl ClockSync {
+    pub fn new(config: &ClockSyncConfig) -> Self {
+        Self {
+            window: config.window.max(2),
+            providers: Mutex::new(HashMap::new()),
+            reference: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn push(window: usize, samples: &mut VecDeque<ClockSample>, sample: ClockSample) {
+        if samples.len() == window {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    pub fn record(&self, provider: ProviderId, sample: ClockSample) {
+        let mut providers = self.providers.lock().unwrap();
+        Self::push(self.window, providers.entry(provider).or_default(), sample);
+    }
+
+    /// A sample of the local clock against true time (NTP)
+    pub fn record_reference(&self, sample: ClockSample) {
+        Self::push(self.window, &mut self.reference.lock().unwrap(), sample);
+    }
+
+    pub fn estimate(&self, provider: ProviderId) -> Option<ClockEstimate> {
+        self.providers.lock().unwrap().get(&provider).and_then(estimate)
+            .or_else(|| self.reference_estimate())
+    }
+
+    pub fn reference_estimate(&self) -> Option<ClockEstimate> {
+        estimate(&self.reference.lock().unwrap())
+    }
+
+    /// Record a provider-stamped update and return its timestamp on the local wall clock
+    pub fn observe(&self, provider: ProviderId, remote: TimestampNs, received: Nanos, transit_ns: u64) -> TimestampNs {
+        self.record(provider, ClockSample::OneWay { remote, received: received.0, transit: transit_ns });
+        self.correct(provider, remote, received)
+    }
+
+    /// A provider timestamp moved onto the local wall clock (unchanged without an estimate)
+    pub fn correct(&self, provider: ProviderId, remote: TimestampNs, received: Nanos) -> TimestampNs {
+        match self.estimate(provider) {
+            Some(estimate) => (remote as i64 - estimate.offset_at(received.0)).max(0) as u64,
+            None => remote,
+        }
+    }
+
+    pub fn status(&self) -> Vec<ClockStatus> {
+        let mut status: Vec<ClockStatus> = self.providers.lock().unwrap().iter()
+            .filter_map(|(provider, samples)| Some(ClockStatus { clock: provider.to_string(), estimate: estimate(samples)? }))
+            .collect();
+        status.sort_by(|a, b| a.clock.cmp(&b.clock));
+        if let Some(estimate) = self.reference_estimate() {
+            status.push(ClockStatus { clock: "reference".to_string(), estimate });
+        }
+        status
+    }
+}
+
+fn wall_ns() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64
+}
+
+/// NTP 64-bit timestamp (seconds since 1900, 32.32 fixed point) as Unix ns
+fn ntp_to_unix_ns(bytes: &[u8]) -> u64 {
+    let secs = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let frac = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    secs.saturating_sub(NTP_UNIX_OFFSET_SECS) * 1_000_000_000 + ((frac * 1_000_000_000) >> 32)
+}
+
+/// One SNTP (RFC 4330) exchange with `server` ("host:port")
+pub async fn sntp_sample(server: &str, timeout: Duration) -> Result<ClockSample> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(server).await.with_context(|| format!("resolve NTP server {}", server))?;
+    let mut request = [0u8; 48];
+    request[0] = 0b00_100_011; // no leap warning, version 4, client mode
+    let t0 = wall_ns();
+    socket.send(&request).await?;
+    let mut response = [0u8; 48];
+    let len = tokio::time::timeout(timeout, socket.recv(&mut response)).await
+        .with_context(|| format!("NTP server {} timed out", server))??;
+    let t3 = wall_ns();
+    if len < 48 || response[0] & 0x07 != 4 || response[1] == 0 {
+        bail!("NTP server {} sent no usable time (mode {}, stratum {})", server, response[0] & 0x07, response[1]);
+    }
+    Ok(ClockSample::RoundTrip { t0, t1: ntp_to_unix_ns(&response[32..40]), t2: ntp_to_unix_ns(&response[40..48]), t3 })
+}
+
+/// Sample the NTP reference every `ntp_interval` (no-op without a server)
+pub async fn run_ntp_sync(sync: SharedClockSync, config: ClockSyncConfig) {
+    if config.ntp_server.is_empty() {
+        return;
+    }
+    info!("[CLOCK] Syncing against NTP server {}", config.ntp_server);
+    loop {
+        match sntp_sample(&config.ntp_server, Duration::from_secs(2)).await {
+            Ok(sample) => sync.record_reference(sample),
+            Err(e) => warn!("[CLOCK] NTP query failed: {}", e),
+        }
+        tokio::time::sleep(config.ntp_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Platform;
+
+    const T: u64 = 1_700_000_000_000_000_000;
+    const MS: u64 = 1_000_000;
+
+    #[test]
+    fn test_one_way_offset_and_skew() {
+        let sync = ClockSync::new(&ClockSyncConfig::default());
+        let kalshi = ProviderId::from(Platform::Kalshi);
+        // Kalshi's clock runs 40ms fast and gains 100ppm; transit is 5ms plus up to 12ms queueing
+        for i in 0..60u64 {
+            let local_sent = T + i * 1_000 * MS;
+            let offset = 40 * MS + i * 1_000 * MS / 10_000;
+            let received = local_sent + 5 * MS + (i * 7 % 13) * MS;
+            sync.record(kalshi, ClockSample::OneWay { remote: local_sent + offset, received, transit: 5 * MS });
+        }
+        let estimate = sync.estimate(kalshi).unwrap();
+        assert!((estimate.skew_ppm - 100.0).abs() < 5.0, "{:?}", estimate);
+        let at_end = T + 59_000 * MS;
+        assert!((estimate.offset_at(at_end) - (40 * MS + 5_900_000) as i64).abs() < MS as i64, "{:?}", estimate);
+
+        // A stamped update moves back onto local time: sent 5ms before it arrived
+        let sent = T + 60_000 * MS;
+        let remote = sent + 40 * MS + 6 * MS;
+        let corrected = sync.correct(kalshi, remote, Nanos(sent + 5 * MS));
+        assert!((corrected as i64 - sent as i64).abs() < MS as i64, "{}", corrected as i64 - sent as i64);
+    }
+
+    #[test]
+    fn test_round_trips_and_reference_fallback() {
+        let sync = ClockSync::new(&ClockSyncConfig::default());
+        // Local clock 3ms behind the server; the slow exchange is ignored in favour of the quick one
+        sync.record_reference(ClockSample::RoundTrip { t0: T, t1: T + 3 * MS + 40 * MS, t2: T + 3 * MS + 40 * MS, t3: T + 50 * MS });
+        sync.record_reference(ClockSample::RoundTrip { t0: T, t1: T + 3 * MS + MS, t2: T + 3 * MS + MS, t3: T + 2 * MS });
+        let reference = sync.reference_estimate().unwrap();
+        assert_eq!(reference.offset_ns, 3 * MS as i64);
+
+        // A provider with no samples of its own is taken to keep true time
+        let poly = ProviderId::from(Platform::Polymarket);
+        assert_eq!(sync.correct(poly, T + 10 * MS, Nanos(T)), T + 7 * MS);
+        assert_eq!(sync.status().last().unwrap().clock, "reference");
+
+        let bytes = [0xE9, 0x3A, 0x8C, 0x80, 0x80, 0, 0, 0];
+        assert_eq!(ntp_to_unix_ns(&bytes), (0xE93A8C80u64 - NTP_UNIX_OFFSET_SECS) * 1_000_000_000 + 500_000_000);
+    }
+}
@@ -1,6 +1,16 @@
 // src/config.rs
 // Configuration constants and league mappings
 
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::event_phase::EventPhase;
+use crate::execution_playbook::{ExecutionPlaybook, OrderStyle};
+use crate::schedule::Schedule;
+use crate::secrets::Secret;
+
 /// Kalshi WebSocket URL
 pub const KALSHI_WS_URL: &str = "wss://api.elections.kalshi.com/trade-api/ws/v2";
 
@@ -10,9 +20,136 @@ pub const KALSHI_API_BASE: &str = "https://api.elections.kalshi.com/trade-api/v2
 /// Polymarket WebSocket URL
 pub const POLYMARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
 
+/// Polymarket user-channel WebSocket URL (order/trade updates, authenticated)
+pub const POLYMARKET_USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+
+/// Default Polygon JSON-RPC endpoint (override with POLYGON_RPC_URL)
+pub const POLYGON_RPC_URL: &str = "https://polygon-rpc.com";
+
 /// Gamma API base URL (Polymarket market data)
 pub const GAMMA_API_BASE: &str = "https://gamma-api.polymarket.com";
 
+/// Polymarket data API (positions, activity)
+pub const POLY_DATA_API_BASE: &str = "https://data-api.polymarket.com";
+
+// === Venue environments (production vs sandbox) ===
+
+/// Kalshi demo environment (paper funds, same API surface)
+pub const KALSHI_DEMO_WS_URL: &str = "wss://demo-api.kalshi.co/trade-api/ws/v2";
+pub const KALSHI_DEMO_API_BASE: &str = "https://demo-api.kalshi.co/trade-api/v2";
+
+/// Polymarket CLOB hosts
+pub const POLY_CLOB_HOST: &str = "https://clob.polymarket.com";
+pub const POLY_CLOB_STAGING_HOST: &str = "https://clob-staging.polymarket.com";
+
+/// Polymarket staging WebSockets
+pub const POLYMARKET_STAGING_WS_URL: &str = "wss://ws-subscriptions-clob-staging.polymarket.com/ws/market";
+pub const POLYMARKET_STAGING_USER_WS_URL: &str = "wss://ws-subscriptions-clob-staging.polymarket.com/ws/user";
+
+/// Polygon mainnet / Amoy testnet
+pub const POLYGON_CHAIN_ID: u64 = 137;
+pub const AMOY_CHAIN_ID: u64 = 80002;
+pub const AMOY_RPC_URL: &str = "https://rpc-amoy.polygon.technology";
+
+/// Which deployment of a venue to target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VenueEnv {
+    Production,
+    Sandbox,
+}
+
+impl VenueEnv {
+    /// "demo" / "sandbox" / "testnet" / "staging" select the sandbox; anything else is production
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "demo" | "sandbox" | "testnet" | "staging" | "amoy" => VenueEnv::Sandbox,
+            _ => VenueEnv::Production,
+        }
+    }
+
+    fn from_env(key: &str) -> Self {
+        std::env::var(key).map(|v| Self::parse(&v)).unwrap_or(VenueEnv::Production)
+    }
+
+    pub fn is_sandbox(self) -> bool {
+        self == VenueEnv::Sandbox
+    }
+}
+
+static KALSHI_ENV: std::sync::OnceLock<VenueEnv> = std::sync::OnceLock::new();
+static POLY_ENV: std::sync::OnceLock<VenueEnv> = std::sync::OnceLock::new();
+
+/// Kalshi environment (set KALSHI_ENV=demo for the demo exchange)
+pub fn kalshi_env() -> VenueEnv {
+    *KALSHI_ENV.get_or_init(|| VenueEnv::from_env("KALSHI_ENV"))
+}
+
+/// Polymarket environment (set POLY_ENV=testnet for staging CLOB + Amoy)
+/// Gamma and the data API have no sandbox, so market metadata always reads production
+pub fn polymarket_env() -> VenueEnv {
+    *POLY_ENV.get_or_init(|| VenueEnv::from_env("POLY_ENV"))
+}
+
+static KALSHI_API_OVERRIDE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+static KALSHI_WS_OVERRIDE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+static POLY_CLOB_OVERRIDE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+static POLY_WS_OVERRIDE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+static POLY_USER_WS_OVERRIDE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+
+/// Endpoint pinned by env var (KALSHI_API_URL, KALSHI_WS_URL, POLY_CLOB_URL, POLY_WS_URL,
+/// POLY_USER_WS_URL), read once; wins over KALSHI_ENV / POLY_ENV. Points a venue at a local
+/// fake (tests, see src/fake_venues.rs) or a proxy
+fn endpoint_override(lock: &'static std::sync::OnceLock<Option<String>>, key: &str) -> Option<&'static str> {
+    lock.get_or_init(|| std::env::var(key).ok().filter(|v| !v.trim().is_empty()).map(|v| v.trim_end_matches('/').to_string()))
+        .as_deref()
+}
+
+pub fn kalshi_api_base() -> &'static str {
+    if let Some(url) = endpoint_override(&KALSHI_API_OVERRIDE, "KALSHI_API_URL") {
+        return url;
+    }
+    if kalshi_env().is_sandbox() { KALSHI_DEMO_API_BASE } else { KALSHI_API_BASE }
+}
+
+pub fn kalshi_ws_url() -> &'static str {
+    if let Some(url) = endpoint_override(&KALSHI_WS_OVERRIDE, "KALSHI_WS_URL") {
+        return url;
+    }
+    if kalshi_env().is_sandbox() { KALSHI_DEMO_WS_URL } else { KALSHI_WS_URL }
+}
+
+pub fn poly_clob_host() -> &'static str {
+    if let Some(url) = endpoint_override(&POLY_CLOB_OVERRIDE, "POLY_CLOB_URL") {
+        return url;
+    }
+    if polymarket_env().is_sandbox() { POLY_CLOB_STAGING_HOST } else { POLY_CLOB_HOST }
+}
+
+pub fn polymarket_ws_url() -> &'static str {
+    if let Some(url) = endpoint_override(&POLY_WS_OVERRIDE, "POLY_WS_URL") {
+        return url;
+    }
+    if polymarket_env().is_sandbox() { POLYMARKET_STAGING_WS_URL } else { POLYMARKET_WS_URL }
+}
+
+pub fn polymarket_user_ws_url() -> &'static str {
+    if let Some(url) = endpoint_override(&POLY_USER_WS_OVERRIDE, "POLY_USER_WS_URL") {
+        return url;
+    }
+    if polymarket_env().is_sandbox() { POLYMARKET_STAGING_USER_WS_URL } else { POLYMARKET_USER_WS_URL }
+}
+
+pub fn polygon_chain_id() -> u64 {
+    if polymarket_env().is_sandbox() { AMOY_CHAIN_ID } else { POLYGON_CHAIN_ID }
+}
+
+/// RPC endpoint: POLYGON_RPC_URL override, else the default for the active chain
+pub fn polygon_rpc_url() -> String {
+    std::env::var("POLYGON_RPC_URL").unwrap_or_else(|_| {
+        if polymarket_env().is_sandbox() { AMOY_RPC_URL } else { POLYGON_RPC_URL }.to_string()
+    })
+}
+
 /// Arb threshold: alert when total cost < this (e.g., 0.995 = 0.5% profit)
 pub const ARB_THRESHOLD: f64 = 0.995;
 
@@ -177,4 +314,2213 @@ pub fn get_league_config(league: &str) -> Option<LeagueConfig> {
     get_league_configs()
         .into_iter()
         .find(|c| c.league_code == league || c.poly_prefix == league)
-}
\ No newline at end of file
+}
+// === Layered application config ===
+//
+// defaults -> TOML file -> env overrides -> CLI flags, validated into one `AppConfig`.
+// File: --config <path>, else ARB_CONFIG, else ./arb.toml if present.
+// CLI: --<section>.<key>=<value> (or --<section>.<key> <value>).
+
+/// Default config file picked up when present
+pub const DEFAULT_CONFIG_PATH: &str = "arb.toml";
+
+/// Risk limits (trading circuit breaker + provider breakers)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskSection {
+    pub enabled: bool,
+    pub max_position_per_market: i64,
+    pub max_total_position: i64,
+    pub max_daily_loss: f64,
+    pub max_consecutive_errors: u32,
+    pub cooldown_secs: u64,
+    pub provider_failure_threshold: u32,
+    pub circuit_reset_secs: u64,
+    /// Event phases new arb exposure may be opened in
+    pub phases: Vec<EventPhase>,
+    /// Consecutive losing arbs before a market cools down (0 = never; see src/market_cooldown.rs)
+    pub market_loss_streak: u32,
+    /// Consecutive venue-refused orders before a market cools down (0 = never)
+    pub market_rejection_streak: u32,
+    /// Consecutive unfilled arbs (suspected limiting) before a market cools down (0 = never)
+    pub market_limit_streak: u32,
+    /// First cooldown; each repeat before a winning arb doubles it
+    pub market_cooldown_secs: u64,
+    pub market_cooldown_max_secs: u64,
+    /// Limits and correlations keyed by Kalshi series (`KXEPLGAME`) or event ticker
+    /// (`KXEPLGAME-25DEC27CFCAVL`), inherited by the markets under them; an event's
+    /// fields override its series' (see src/market_hierarchy.rs)
+    pub groups: BTreeMap<String, GroupRule>,
+}
+
+impl Default for RiskSection {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_position_per_market: 50000,
+            max_total_position: 100000,
+            max_daily_loss: 500.0,
+            max_consecutive_errors: 5,
+            cooldown_secs: 300,
+            provider_failure_threshold: 5,
+            circuit_reset_secs: 300,
+            phases: TRADING_PHASES.to_vec(),
+            market_loss_streak: 3,
+            market_rejection_streak: 5,
+            market_limit_streak: 3,
+            market_cooldown_secs: 60,
+            market_cooldown_max_secs: 3600,
+            groups: BTreeMap::new(),
+        }
+    }
+}
+
+/// Execution mode and venue targets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutionSection {
+    pub dry_run: bool,
+    pub arb_threshold: f64,
+    /// "production" or a sandbox alias (see `VenueEnv::parse`)
+    pub kalshi_env: String,
+    pub poly_env: String,
+    /// "fifo" or "average"
+    pub cost_method: String,
+    /// Arbs executing at once; when more signals are pending the best-ranked run first
+    pub max_concurrent: u32,
+    /// Dollars of collateral in-flight arbs may tie up (0 = unlimited)
+    pub capital_budget: f64,
+    /// Age at which a pending signal's fill probability halves
+    pub signal_half_life_ms: u64,
+    /// Event phases arbs are executed in
+    pub phases: Vec<EventPhase>,
+    /// Order type, leg ordering, timeout and remainder policy per pattern id (as in
+    /// `[patterns.enabled]`); unlisted patterns trade concurrent IOC legs
+    pub playbooks: BTreeMap<String, ExecutionPlaybook>,
+    /// Operator review of low-confidence signals (see src/operator_review.rs)
+    pub review: ReviewSection,
+}
+
+impl Default for ExecutionSection {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            arb_threshold: ARB_THRESHOLD,
+            kalshi_env: "production".to_string(),
+            poly_env: "production".to_string(),
+            cost_method: "fifo".to_string(),
+            max_concurrent: 4,
+            capital_budget: 0.0,
+            signal_half_life_ms: 250,
+            phases: TRADING_PHASES.to_vec(),
+            playbooks: BTreeMap::new(),
+            review: ReviewSection::default(),
+        }
+    }
+}
+
+/// Signals held for an operator's approve / deny before they execute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReviewSection {
+    /// Signals below this confidence are held (0 = review off)
+    pub below_confidence: f64,
+    /// Only signals of these pattern ids (as in `[patterns.enabled]`) are held (empty = all)
+    pub patterns: Vec<String>,
+    /// How long a held signal waits for a decision
+    pub window_ms: u64,
+    /// Execute signals nobody decided in time, as automation would (otherwise they are dropped)
+    pub execute_on_expiry: bool,
+}
+
+impl Default for ReviewSection {
+    fn default() -> Self {
+        Self {
+            below_confidence: 0.0,
+            patterns: Vec::new(),
+            window_ms: 5000,
+            execute_on_expiry: false,
+        }
+    }
+}
+
+/// Feed connections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeedsSection {
+    /// League codes to monitor (empty = all)
+    pub enabled_leagues: Vec<String>,
+    pub ws_reconnect_delay_secs: u64,
+    pub poly_ping_interval_secs: u64,
+    /// Feed heartbeat check interval while no subscribed market is live or about to start
+    pub heartbeat_interval_ms: u64,
+    /// Check interval while a market of Tier 1..4 is in play, busiest tier first
+    pub heartbeat_in_play_ms: Vec<u64>,
+    /// Check interval within `pre_game_window_mins` of a start, at halftime or while suspended
+    pub heartbeat_pre_game_ms: u64,
+    pub pre_game_window_mins: u64,
+    /// A feed is stale after this many check intervals without a heartbeat
+    pub stale_heartbeats: u32,
+    /// Per-provider overrides keyed by provider name (e.g. `kalshi`, or a plugin's name)
+    pub heartbeat_providers: BTreeMap<String, HeartbeatRule>,
+    /// Failed reconnects in a row before a feed's reconnect circuit opens
+    pub max_reconnect_attempts: u32,
+    /// Reconnect backoff doubles from `ws_reconnect_delay_secs` up to this
+    pub max_reconnect_delay_secs: u64,
+    /// Each backoff delay is moved by up to this fraction either way
+    pub reconnect_jitter: f64,
+    /// An open reconnect circuit retries once after this long
+    pub reconnect_cooldown_secs: u64,
+    /// "lenient" processes messages with unknown fields (counting the drift), "strict" rejects them
+    pub schema_mode: String,
+    /// Rejected messages are appended here per provider (empty = keep none)
+    pub quarantine_dir: String,
+    /// Per-provider quarantine file cap
+    pub quarantine_max_mb: u64,
+    /// Ticks moving more than this many standard deviations of recent moves are quarantined (0 = off)
+    pub outlier_sigma: f64,
+    /// ...as long as the move is at least this many cents
+    pub outlier_min_jump_cents: f64,
+    /// Recent moves per provider and market the deviation is taken over
+    pub outlier_window: usize,
+    /// Ticks stamped further ahead of the local clock than this are quarantined
+    pub max_clock_skew_ms: u64,
+    /// Same-market, same-provider updates arriving within this of the first are merged before
+    /// the latency engine sees them (0 = off)
+    pub coalesce_window_ms: u64,
+    /// Extra socket URLs per provider name (e.g. `kalshi`), connected alongside the default one;
+    /// the first copy of each update is kept (see multi_region::MultiRegionFeed)
+    pub endpoints: BTreeMap<String, Vec<String>>,
+    /// How long an update is remembered for dropping its slower copies
+    pub dedup_window_ms: u64,
+}
+
+impl Default for FeedsSection {
+    fn default() -> Self {
+        Self {
+            enabled_leagues: ENABLED_LEAGUES.iter().map(|s| s.to_string()).collect(),
+            ws_reconnect_delay_secs: WS_RECONNECT_DELAY_SECS,
+            poly_ping_interval_secs: POLY_PING_INTERVAL_SECS,
+            heartbeat_interval_ms: 30000,
+            heartbeat_in_play_ms: vec![1000, 2000, 5000, 10000],
+            heartbeat_pre_game_ms: 10000,
+            pre_game_window_mins: 60,
+            stale_heartbeats: 2,
+            heartbeat_providers: BTreeMap::new(),
+            max_reconnect_attempts: 10,
+            max_reconnect_delay_secs: 60,
+            reconnect_jitter: 0.2,
+            reconnect_cooldown_secs: 300,
+            schema_mode: "lenient".to_string(),
+            quarantine_dir: "./data/quarantine".to_string(),
+            quarantine_max_mb: 64,
+            outlier_sigma: 6.0,
+            outlier_min_jump_cents: 5.0,
+            outlier_window: 100,
+            max_clock_skew_ms: 2000,
+            coalesce_window_ms: 0,
+            endpoints: BTreeMap::new(),
+            dedup_window_ms: 2000,
+        }
+    }
+}
+
+/// Heartbeat intervals for one feed provider; unset (0 / empty) fields use the `[feeds]` values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeartbeatRule {
+    pub idle_ms: u64,
+    pub in_play_ms: Vec<u64>,
+    pub pre_game_ms: u64,
+}
+
+/// Bun worker pattern processing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkerSection {
+    pub max_processing_time_us: f64,
+    pub enable_persistence: bool,
+    pub cache_size_limit: usize,
+    pub trigger_threshold: f64,
+    /// Filter tuning per pattern id (`"51"`), usually promoted from an optimization study
+    /// (see src/optimization_studies.rs); patterns without one use the filter defaults
+    pub filters: BTreeMap<String, FilterTuning>,
+    /// Observation pre-filters per pattern id (`"76"`) or ML tier (`"tier2"`, covering that
+    /// tier's feature-flag components), applied before each filter update; a pattern's own
+    /// rule wins over its tier's (see src/observation_prefilter.rs)
+    pub prefilters: BTreeMap<String, PrefilterRule>,
+}
+
+impl Default for WorkerSection {
+    fn default() -> Self {
+        Self {
+            max_processing_time_us: 10000.0,
+            enable_persistence: true,
+            cache_size_limit: 1000,
+            trigger_threshold: 0.5,
+            filters: BTreeMap::new(),
+            prefilters: BTreeMap::new(),
+        }
+    }
+}
+
+/// Smoothing and de-duplication of a pattern's observations before its filter sees them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrefilterRule {
+    /// Replace each observation with the median of it and the two before (absorbs one-tick bounces)
+    pub median_of_3: bool,
+    /// Weight of a new observation in an exponential moving average (0 = off)
+    pub ewma_alpha: f64,
+    /// Drop observations moving less than this from the last one passed on (0 = off)
+    pub min_change: f64,
+}
+
+/// Kalman filter hyperparameters for one pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterTuning {
+    /// Filter time step (seconds)
+    pub dt: f64,
+    /// Process noise in the steam regime
+    pub q_steam: f64,
+    /// Process noise in the quiet regime
+    pub q_quiet: f64,
+    /// Observation noise
+    pub r_noise: f64,
+    /// Edge that triggers the pattern, in place of `trigger_threshold`
+    pub velocity_threshold: f64,
+    /// Pattern-specific extras
+    pub pattern_params: BTreeMap<String, f64>,
+}
+
+impl Default for FilterTuning {
+    fn default() -> Self {
+        Self {
+            dt: 0.05,
+            q_steam: 0.5,
+            q_quiet: 0.01,
+            r_noise: 0.05,
+            velocity_threshold: 0.3,
+            pattern_params: BTreeMap::new(),
+        }
+    }
+}
+
+/// Tick-sim backtester controls
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BacktesterSection {
+    pub sim_latency_jitter: f64,
+    pub sharp_limit_threshold: f64,
+    pub max_speed_multiplier: f64,
+    pub memory_limit_mb: u64,
+    pub data_path: String,
+}
+
+impl Default for BacktesterSection {
+    fn default() -> Self {
+        Self {
+            sim_latency_jitter: 5.0,
+            sharp_limit_threshold: 0.65,
+            max_speed_multiplier: 1000.0,
+            memory_limit_mb: 2048,
+            data_path: "./data/historical_ticks".to_string(),
+        }
+    }
+}
+
+/// Pattern detection thresholds (Pattern #73 beta skew)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PatternsSection {
+    /// Minimum gap for an opportunity (points)
+    pub min_gap_threshold: f64,
+    pub min_gap_percent: f64,
+    pub max_half_life_ms: f64,
+    pub min_usage_rate: f64,
+    /// Patterns allowed to trade, keyed by id (`"73"` for worker patterns, `"PolyYesKalshiNo"`
+    /// for arb types). Empty = every pattern, unlimited (see src/pattern_policy.rs)
+    pub enabled: BTreeMap<String, PatternRule>,
+    /// Stale-position sweeper (see src/position_aging.rs)
+    pub aging: AgingSection,
+}
+
+impl Default for PatternsSection {
+    fn default() -> Self {
+        Self {
+            min_gap_threshold: 0.5,
+            min_gap_percent: 0.02,
+            max_half_life_ms: 5000.0,
+            min_usage_rate: 0.2,
+            enabled: BTreeMap::new(),
+            aging: AgingSection::default(),
+        }
+    }
+}
+
+/// Open lots outliving their pattern's convergence: flagged past one multiple of its
+/// `half_life_ms`, optionally sold past another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgingSection {
+    /// Half-lives after which a lot is flagged stale (0 = sweeper off)
+    pub flag_after_half_lives: f64,
+    /// Sell stale lots on their venue, booked as timeout exits (otherwise only flagged)
+    pub liquidate: bool,
+    /// Half-lives after which a stale lot is sold (at least `flag_after_half_lives`)
+    pub liquidate_after_half_lives: f64,
+    /// Lowest price (dollars) a timeout exit sells at
+    pub min_exit_price: f64,
+    /// Seconds between sweeps
+    pub interval_secs: u64,
+}
+
+impl Default for AgingSection {
+    fn default() -> Self {
+        Self {
+            flag_after_half_lives: 10.0,
+            liquidate: false,
+            liquidate_after_half_lives: 20.0,
+            min_exit_price: 0.01,
+            interval_secs: 30,
+        }
+    }
+}
+
+/// Scheduled venue downtime: new positions on a venue are refused from `block_before_mins`
+/// ahead of one of its windows until the window ends; open positions held into one are alerted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenanceSection {
+    pub block_before_mins: u64,
+    /// Open positions on a venue are alerted this long before its window starts
+    pub alert_before_mins: u64,
+    /// Also take the maintenance windows Kalshi publishes in its exchange schedule
+    pub kalshi_api: bool,
+    /// Minutes between exchange schedule refreshes
+    pub refresh_mins: u64,
+    pub windows: Vec<MaintenanceRule>,
+}
+
+impl Default for MaintenanceSection {
+    fn default() -> Self {
+        Self { block_before_mins: 15, alert_before_mins: 60, kalshi_api: false, refresh_mins: 60, windows: Vec::new() }
+    }
+}
+
+/// Position size multiplier by signal strength (0-1) and confidence, the same curve in the
+/// backtester, worker and live dispatcher (see src/sizing_curve.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SizingSection {
+    /// "linear" (multiplier = strength), "piecewise" (through `points`) or "logistic"
+    pub curve: String,
+    /// (strength, multiplier) knots of the piecewise curve, by strength; flat past either end
+    pub points: Vec<[f64; 2]>,
+    /// Strength the logistic curve gives half size at
+    pub midpoint: f64,
+    /// Slope of the logistic curve
+    pub steepness: f64,
+    /// Share of the multiplier scaled by confidence (0 = confidence ignored)
+    pub confidence_weight: f64,
+    /// Worker edge at full strength, in multiples of its trigger threshold
+    pub worker_full_strength: f64,
+    /// Arb profit per contract (cents) at full strength
+    pub arb_full_strength_cents: f64,
+}
+
+impl Default for SizingSection {
+    fn default() -> Self {
+        Self {
+            curve: "linear".to_string(),
+            points: vec![[0.5, 0.25], [1.0, 1.0]],
+            midpoint: 0.7,
+            steepness: 10.0,
+            confidence_weight: 0.0,
+            worker_full_strength: 1.0,
+            arb_full_strength_cents: 1.0,
+        }
+    }
+}
+
+/// One venue's maintenance window: recurring (`cron`, five fields and a zone - see
+/// `schedule::Schedule`) or one-off (`start`, RFC3339)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenanceRule {
+    pub venue: String,
+    pub cron: String,
+    pub start: String,
+    pub duration_mins: u64,
+}
+
+/// Limits and correlation of a Kalshi series or event; 0 inherits (the series' rule, then `[risk]`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GroupRule {
+    /// Contracts across all of the group's markets
+    pub max_position: i64,
+    /// Per-market limit for the group's markets
+    pub max_position_per_market: i64,
+    /// Correlation (0-1) between two of its markets: a sibling's contracts count toward a
+    /// market's own limit at this weight
+    pub correlation: f64,
+}
+
+/// Limits for one enabled pattern
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PatternRule {
+    /// Open positions at once (0 = unlimited)
+    pub max_concurrent: u32,
+    /// Dollars in open positions (0 = unlimited)
+    pub max_capital: f64,
+    /// Lowest signal confidence traded; signals without one (cross-venue arbs) skip the check
+    pub min_confidence: f64,
+    /// Venues any leg may trade on, case-insensitive (empty = all)
+    pub venues: Vec<String>,
+    /// How long the pattern's trades take to converge (ms); open lots are aged against it by
+    /// `[patterns.aging]` (0 = never stale)
+    pub half_life_ms: f64,
+}
+
+/// Monitoring dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DashboardSection {
+    pub enabled: bool,
+    pub update_interval_ms: u64,
+}
+
+impl Default for DashboardSection {
+    fn default() -> Self {
+        Self { enabled: false, update_interval_ms: 1000 }
+    }
+}
+
+/// Bankroll allocation across patterns (see src/capital_allocator.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AllocatorSection {
+    /// Dollars divided across patterns (0 = allocator off)
+    pub bankroll: f64,
+    /// Multiplier on each pattern's full-Kelly fraction
+    pub kelly_fraction: f64,
+    /// Days of realized P&L the allocation is fitted on
+    pub lookback_days: u32,
+    /// Days with closed lots before a pattern is Kelly-sized instead of given the exploration share
+    pub min_active_days: u32,
+    /// Largest share of the bankroll any one pattern may hold
+    pub max_share: f64,
+    /// Share for patterns without enough history
+    pub exploration_share: f64,
+    pub rebalance_secs: u64,
+}
+
+impl Default for AllocatorSection {
+    fn default() -> Self {
+        Self {
+            bankroll: 0.0,
+            kelly_fraction: 0.25,
+            lookback_days: 30,
+            min_active_days: 5,
+            max_share: 0.4,
+            exploration_share: 0.02,
+            rebalance_secs: 3600,
+        }
+    }
+}
+
+/// Venue accounts orders are routed across (see src/account_manager.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccountsSection {
+    /// `round_robin` or `lowest_sharp_score`
+    pub routing: String,
+    /// Sharp-score estimate at which an account counts as close to being limited (routed last)
+    pub sharp_limit: f64,
+    /// Accounts keyed by id; credentials are the venue secrets suffixed `_<ID>`, e.g.
+    /// `KALSHI_API_KEY_ID_K2`. Empty = one account per venue on the unsuffixed secrets
+    pub list: BTreeMap<String, AccountRule>,
+}
+
+impl Default for AccountsSection {
+    fn default() -> Self {
+        Self {
+            routing: "round_robin".to_string(),
+            sharp_limit: 0.65,
+            list: BTreeMap::new(),
+        }
+    }
+}
+
+/// Limits for one venue account
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccountRule {
+    /// `kalshi` or `polymarket`
+    pub venue: String,
+    /// Dollars in open positions (0 = unlimited)
+    pub max_exposure: f64,
+    /// Dollars per order (0 = unlimited)
+    pub max_order: f64,
+    /// Keep the account configured but never route to it
+    pub suspended: bool,
+}
+
+/// Two-sided quoting on wide, thin Kalshi books (see src/market_maker.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarketMakingSection {
+    pub enabled: bool,
+    /// Books at least this wide (YES ask - YES bid, cents) are quoted...
+    pub min_spread_cents: u16,
+    /// ...while neither touch shows more than this (cents of notional)
+    pub max_touch_size: i64,
+    /// Distance of each quote from the inventory-skewed fair value (cents)
+    pub half_spread_cents: f64,
+    /// Contracts per quote
+    pub quote_size: i64,
+    /// Net contracts per market before the side adding to it is pulled
+    /// (never above `risk.max_position_per_market`)
+    pub max_inventory: i64,
+    /// Cents both quotes shift against the inventory at `max_inventory`
+    pub skew_cents: f64,
+    /// Quotes stay pulled this long after the regime detector last saw steam
+    pub steam_pull_secs: u64,
+    /// Resting quotes are replaced once their target moves this many cents
+    pub requote_cents: u16,
+}
+
+impl Default for MarketMakingSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_spread_cents: 6,
+            max_touch_size: 5000,
+            half_spread_cents: 2.0,
+            quote_size: 10,
+            max_inventory: 50,
+            skew_cents: 3.0,
+            steam_pull_secs: 30,
+            requote_cents: 1,
+        }
+    }
+}
+
+/// Log output, verbosity and hot-path throttling (see src/logging.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingSection {
+    /// "json" (one object per line, for collectors) or "text"
+    pub format: String,
+    /// Level for the bot's own modules; dependencies log at warn unless listed in `modules`
+    pub level: String,
+    /// Per-module levels, e.g. `"arb_bot::kalshi" = "debug"` (RUST_LOG, when set, replaces all of these)
+    pub modules: BTreeMap<String, String>,
+    /// Hot-path logs (per tick, signal or rejection) emit at most once per callsite per interval
+    pub hot_path_interval_ms: u64,
+}
+
+impl Default for LoggingSection {
+    fn default() -> Self {
+        Self {
+            format: "json".to_string(),
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            hot_path_interval_ms: 1000,
+        }
+    }
+}
+
+/// Feature flags for components #71-#88 (see src/feature_flags.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesSection {
+    pub premium: bool,
+    pub beta_features: bool,
+    /// Experimental components (e.g. #77 Regulatory Delay)
+    pub debug: bool,
+    /// Per-component switches keyed by component id, e.g. `"77" = true`
+    pub components: BTreeMap<String, bool>,
+    /// Dark-launched components and when they go live (see src/shadow_mode.rs)
+    pub shadow: ShadowSection,
+}
+
+impl Default for FeaturesSection {
+    fn default() -> Self {
+        Self {
+            premium: true,
+            beta_features: true,
+            debug: false,
+            components: BTreeMap::new(),
+            shadow: ShadowSection::default(),
+        }
+    }
+}
+
+/// Components whose signals only paper-fill until their hypothetical record earns promotion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShadowSection {
+    /// Component ids in shadow, e.g. `[77]`
+    pub components: Vec<u16>,
+    /// Days in shadow before a component may be promoted
+    pub promote_after_days: f64,
+    /// Hypothetical trades needed before promotion
+    pub min_trades: u32,
+    /// Lowest per-trade Sharpe of the hypothetical equity curve that promotes
+    pub min_sharpe: f64,
+    /// Highest peak-to-trough drawdown (fraction of the peak) that promotes
+    pub max_drawdown: f64,
+    /// Dollars the hypothetical equity curve starts from
+    pub starting_capital: f64,
+}
+
+impl Default for ShadowSection {
+    fn default() -> Self {
+        Self {
+            components: Vec::new(),
+            promote_after_days: 14.0,
+            min_trades: 50,
+            min_sharpe: 0.1,
+            max_drawdown: 0.15,
+            starting_capital: 1000.0,
+        }
+    }
+}
+
+/// Low-latency runtime profile (see src/runtime_profile.rs); read at startup only
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuntimeSection {
+    /// Pin the tokio workers, busy-poll Tier 1 ingestion and pre-allocate tick buffers
+    pub low_latency: bool,
+    /// Worker threads (0 = one per pinned core, or per CPU when `pin_cores` is empty)
+    pub worker_threads: usize,
+    /// Cores the workers are pinned to, in start order (empty = cores 0..worker_threads)
+    pub pin_cores: Vec<usize>,
+    /// Markets of this many tiers, busiest first, are busy-polled (1 = Tier 1 only)
+    pub busy_poll_tiers: usize,
+    /// Empty polls spun before yielding the worker to other tasks
+    pub busy_poll_spins: u32,
+    /// Ticks pre-allocated per ingestion queue; a full queue drops new ticks
+    pub tick_buffer: usize,
+}
+
+impl Default for RuntimeSection {
+    fn default() -> Self {
+        Self {
+            low_latency: false,
+            worker_threads: 0,
+            pin_cores: Vec::new(),
+            busy_poll_tiers: 1,
+            busy_poll_spins: 1000,
+            tick_buffer: 4096,
+        }
+    }
+}
+
+/// Redis shared by the cache's remote tier and the bet queue (see src/redis_client.rs)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedisSection {
+    /// host:port or redis://host:port ("" = no Redis; caches stay in-process)
+    pub url: String,
+}
+
+/// Shared per-venue request budgets (see src/request_scheduler.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulerSection {
+    /// Sustained requests per second and bucket capacity, per venue
+    pub kalshi_rps: f64,
+    pub kalshi_burst: f64,
+    pub poly_rps: f64,
+    pub poly_burst: f64,
+}
+
+impl Default for SchedulerSection {
+    fn default() -> Self {
+        Self { kalshi_rps: 18.0, kalshi_burst: 5.0, poly_rps: 40.0, poly_burst: 10.0 }
+    }
+}
+
+/// Venue API and feed breakers (see `circuit_breaker::BreakerConfig`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreakerSection {
+    /// Sliding window over which the failure rate is measured
+    pub window_secs: u64,
+    /// Calls in the window before the failure rate is evaluated
+    pub min_calls: u32,
+    /// Open when failures / calls reaches this rate
+    pub failure_rate: f64,
+    /// How long the circuit stays open before probing
+    pub open_secs: u64,
+    /// Probe calls admitted while half-open; all must succeed to close
+    pub half_open_probes: u32,
+}
+
+impl Default for BreakerSection {
+    fn default() -> Self {
+        Self { window_secs: 60, min_calls: 10, failure_rate: 0.5, open_secs: 30, half_open_probes: 3 }
+    }
+}
+
+/// Provider clock offset estimation (see src/clock_sync.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClockSyncSection {
+    /// SNTP server (host:port, e.g. "time.google.com:123") for the local clock's reference offset ("" = none)
+    pub ntp_server: String,
+    pub ntp_interval_secs: u64,
+    /// Samples kept per provider
+    pub window: usize,
+}
+
+impl Default for ClockSyncSection {
+    fn default() -> Self {
+        Self { ntp_server: String::new(), ntp_interval_secs: 64, window: 128 }
+    }
+}
+
+/// Detection-to-order latency SLAs (see src/decision_latency.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DecisionLatencySection {
+    /// p99 SLA (ms) of Tier 1..4
+    pub tier_p99_ms: [f64; MARKET_TIERS],
+    /// Recent samples a pattern (or tier) needs before it is judged
+    pub min_samples: u64,
+}
+
+impl Default for DecisionLatencySection {
+    fn default() -> Self {
+        Self { tier_p99_ms: [100.0, 250.0, 500.0, 1000.0], min_samples: 20 }
+    }
+}
+
+/// Shedding and defensive sizing when Tier 1 misses its latency SLA (see src/sla_degradation.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SlaSection {
+    pub enabled: bool,
+    /// Latency samples kept per component
+    pub window: usize,
+    /// Recovered evaluations in a row before stepping down one level
+    pub recover_checks: u32,
+    /// Tier 1 compliance under which Tier 3/4 are shed
+    pub shed_below: f64,
+    /// Tier 1 compliance under which thresholds widen and sizes shrink as well
+    pub defensive_below: f64,
+}
+
+impl Default for SlaSection {
+    fn default() -> Self {
+        Self { enabled: false, window: 200, recover_checks: 3, shed_below: 0.95, defensive_below: 0.80 }
+    }
+}
+
+/// Resolution polling (see src/settlement.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SettlementSection {
+    /// How often open positions are checked for resolution
+    pub poll_secs: u64,
+    /// Unmatched contracts above this are reported as held into resolution
+    pub max_unmatched: f64,
+}
+
+impl Default for SettlementSection {
+    fn default() -> Self {
+        Self { poll_secs: 60, max_unmatched: 0.5 }
+    }
+}
+
+/// Subsystem start and stop deadlines (see src/supervisor.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SupervisorSection {
+    pub startup_timeout_secs: u64,
+    pub shutdown_grace_secs: u64,
+}
+
+impl Default for SupervisorSection {
+    fn default() -> Self {
+        Self { startup_timeout_secs: 30, shutdown_grace_secs: 10 }
+    }
+}
+
+/// Paper fills against the live books instead of the simulated coin flip (see src/paper_fills.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaperFillsSection {
+    pub enabled: bool,
+    /// Order size per leg (cents of notional)
+    pub order_size: u16,
+}
+
+impl Default for PaperFillsSection {
+    fn default() -> Self {
+        Self { enabled: false, order_size: 1000 }
+    }
+}
+
+/// Write-ahead journal of fills and arbs (see src/journal.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JournalSection {
+    pub enabled: bool,
+    pub dir: String,
+    /// Records appended before a snapshot + compaction is due
+    pub compact_every: u64,
+    /// fsync after every append (slower, survives power loss)
+    pub fsync: bool,
+}
+
+impl Default for JournalSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/journal".to_string(), compact_every: 5_000, fsync: false }
+    }
+}
+
+/// Audit trail of operator and risk decisions (see src/audit_log.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditSection {
+    pub enabled: bool,
+    /// One segment file per UTC day is written here
+    pub dir: String,
+    /// fsync after every append
+    pub fsync: bool,
+}
+
+impl Default for AuditSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/audit".to_string(), fsync: false }
+    }
+}
+
+/// Trade blotter and FIX drop copy (see src/blotter.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlotterSection {
+    pub enabled: bool,
+    /// One CSV per UTC trade date is written here
+    pub dir: String,
+    /// `tcp://host:port` or a file / named pipe receiving FIX drop copies ("" = none)
+    pub drop_copy: String,
+    /// FIX SenderCompID (49) / TargetCompID (56)
+    pub sender_comp_id: String,
+    pub target_comp_id: String,
+}
+
+impl Default for BlotterSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "./data/blotter".to_string(),
+            drop_copy: String::new(),
+            sender_comp_id: "ARBBOT".to_string(),
+            target_comp_id: "DROPCOPY".to_string(),
+        }
+    }
+}
+
+/// Transaction cost analysis records (see src/tca.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TcaSection {
+    pub enabled: bool,
+    /// One segment file per UTC day is written here
+    pub dir: String,
+}
+
+impl Default for TcaSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/tca".to_string() }
+    }
+}
+
+/// Sportsbook odds change capture (see src/odds_capture.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OddsCaptureSection {
+    pub enabled: bool,
+    pub dir: String,
+    /// Rows buffered before a segment is written
+    pub flush_rows: usize,
+}
+
+impl Default for OddsCaptureSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/odds_changes".to_string(), flush_rows: 10_000 }
+    }
+}
+
+/// Parquet tick and signal archive (see src/tick_store.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TickStoreSection {
+    pub enabled: bool,
+    /// Directory holding `ticks-*` and `signals-*` segments
+    pub dir: String,
+    /// Rows buffered before a segment is written
+    pub flush_rows: usize,
+    /// Rows per Parquet row group (the unit of time-range pruning)
+    pub row_group_rows: usize,
+    /// zstd level (1-22)
+    pub zstd_level: i32,
+}
+
+impl Default for TickStoreSection {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: "./data/ticks".to_string(),
+            flush_rows: 100_000,
+            row_group_rows: 16_384,
+            zstd_level: 3,
+        }
+    }
+}
+
+/// Backtests submitted from the dashboard (see src/backtest_jobs.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BacktestJobsSection {
+    pub enabled: bool,
+    /// Finished jobs are written here as `<id>.json`
+    pub dir: String,
+    /// Jobs waiting to run before submissions are refused
+    pub max_queued: usize,
+    /// Backtests running at once
+    pub max_concurrent: usize,
+}
+
+impl Default for BacktestJobsSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/backtests".to_string(), max_queued: 16, max_concurrent: 2 }
+    }
+}
+
+/// Completed backtests and verification updates (see src/backtest_results.rs); kept
+/// whenever the tick store or backtest jobs are on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BacktestResultsSection {
+    /// Append-only record file
+    pub path: String,
+    /// Records of each kind kept in memory (and reloaded on open)
+    pub keep: usize,
+}
+
+impl Default for BacktestResultsSection {
+    fn default() -> Self {
+        Self { path: "./data/backtest_results.jsonl".to_string(), keep: 50 }
+    }
+}
+
+/// Hyperparameter studies (see src/optimization_studies.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StudiesSection {
+    pub enabled: bool,
+    /// Studies are written here as `<id>.json`
+    pub dir: String,
+    /// Candidates kept per study
+    pub top_n: usize,
+}
+
+impl Default for StudiesSection {
+    fn default() -> Self {
+        Self { enabled: false, dir: "./data/studies".to_string(), top_n: 5 }
+    }
+}
+
+/// Credentials and the backends holding them, from the env only (never the config file or
+/// flags); venue clients resolve theirs through `secrets::SecretsChain::from_config`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretsSection {
+    pub kalshi_api_key_id: Secret,
+    pub kalshi_private_key_path: Secret,
+    pub poly_private_key: Secret,
+    pub poly_funder: Secret,
+    /// Backends tried in order (SECRETS_BACKENDS, default "env,file,keychain,vault")
+    pub backends: String,
+    /// Directory of the file backend (SECRETS_DIR)
+    pub dir: String,
+    /// Service name of the keychain backend (SECRETS_KEYCHAIN_SERVICE)
+    pub keychain_service: String,
+    /// Vault is skipped unless VAULT_ADDR and VAULT_TOKEN are both set
+    pub vault_addr: String,
+    pub vault_token: Secret,
+    /// KV v2 mount and secret path (VAULT_MOUNT, VAULT_SECRET_PATH)
+    pub vault_mount: String,
+    pub vault_path: String,
+}
+
+impl Default for SecretsSection {
+    fn default() -> Self {
+        Self::from_env(&|_| None)
+    }
+}
+
+impl SecretsSection {
+    fn from_env(env: &dyn Fn(&str) -> Option<String>) -> Self {
+        let get = |key: &str| Secret::new(env(key).unwrap_or_default());
+        let or = |key: &str, default: &str| env(key).unwrap_or_else(|| default.to_string());
+        Self {
+            kalshi_api_key_id: get("KALSHI_API_KEY_ID"),
+            kalshi_private_key_path: env("KALSHI_PRIVATE_KEY_PATH")
+                .or_else(|| env("KALSHI_PRIVATE_KEY_FILE"))
+                .map(Secret::new)
+                .unwrap_or_default(),
+            poly_private_key: get("POLY_PRIVATE_KEY"),
+            poly_funder: get("POLY_FUNDER"),
+            backends: or("SECRETS_BACKENDS", "env,file,keychain,vault"),
+            dir: or("SECRETS_DIR", "secrets"),
+            keychain_service: or("SECRETS_KEYCHAIN_SERVICE", "arb-bot"),
+            vault_addr: or("VAULT_ADDR", ""),
+            vault_token: get("VAULT_TOKEN"),
+            vault_mount: or("VAULT_MOUNT", "secret"),
+            vault_path: or("VAULT_SECRET_PATH", "arb-bot"),
+        }
+    }
+}
+
+/// Validated application configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub risk: RiskSection,
+    pub execution: ExecutionSection,
+    pub feeds: FeedsSection,
+    pub worker: WorkerSection,
+    pub patterns: PatternsSection,
+    pub backtester: BacktesterSection,
+    pub dashboard: DashboardSection,
+    pub allocator: AllocatorSection,
+    pub accounts: AccountsSection,
+    pub market_making: MarketMakingSection,
+    pub logging: LoggingSection,
+    pub features: FeaturesSection,
+    pub runtime: RuntimeSection,
+    pub maintenance: MaintenanceSection,
+    pub sizing: SizingSection,
+    pub redis: RedisSection,
+    pub scheduler: SchedulerSection,
+    pub breaker: BreakerSection,
+    pub clock_sync: ClockSyncSection,
+    pub decision_latency: DecisionLatencySection,
+    pub sla: SlaSection,
+    pub settlement: SettlementSection,
+    pub supervisor: SupervisorSection,
+    pub paper_fills: PaperFillsSection,
+    pub journal: JournalSection,
+    pub audit: AuditSection,
+    pub blotter: BlotterSection,
+    pub tca: TcaSection,
+    pub odds_capture: OddsCaptureSection,
+    pub tick_store: TickStoreSection,
+    pub backtest_jobs: BacktestJobsSection,
+    pub backtest_results: BacktestResultsSection,
+    pub studies: StudiesSection,
+    #[serde(skip)]
+    pub secrets: SecretsSection,
+}
+
+/// Names accepted for `execution.kalshi_env` / `execution.poly_env`
+const KNOWN_ENVS: &[&str] = &["production", "prod", "live", "demo", "sandbox", "testnet", "staging", "amoy"];
+
+/// Tier keys accepted in `[worker.prefilters]`
+const PREFILTER_TIERS: &[&str] = &["tier1", "tier2", "tier3", "tier4"];
+/// Names accepted for `execution.cost_method`
+const COST_METHODS: &[&str] = &["fifo", "average", "avg"];
+/// Names accepted for `sizing.curve`
+const SIZING_CURVES: &[&str] = &["linear", "piecewise", "logistic"];
+
+/// Names accepted for `accounts.routing`
+const ROUTING_POLICIES: &[&str] = &["round_robin", "lowest_sharp_score"];
+
+/// Venues accounts can be opened on (`accounts.list.<id>.venue`)
+const ACCOUNT_VENUES: &[&str] = &["kalshi", "polymarket"];
+
+/// Names accepted for `feeds.schema_mode`
+const SCHEMA_MODES: &[&str] = &["strict", "lenient"];
+
+/// Entries in `feeds.heartbeat_in_play_ms`, one per market tier
+const MARKET_TIERS: usize = 4;
+
+/// Upper bound on `feeds.coalesce_window_ms`; a wider window alone would blow the 10ms tick budget
+const MAX_COALESCE_WINDOW_MS: u64 = 10;
+
+/// Names accepted for `logging.format`
+const LOG_FORMATS: &[&str] = &["json", "text"];
+
+/// Names accepted for `logging.level` and `logging.modules.<target>`
+const LOG_LEVELS: &[&str] = &["off", "error", "warn", "info", "debug", "trace"];
+
+/// Default `risk.phases` / `execution.phases`: whenever the venues are trading
+const TRADING_PHASES: [EventPhase; 3] = [EventPhase::PreGame, EventPhase::InPlay, EventPhase::Halftime];
+
+/// Checked-in copy of `AppConfig::json_schema()`, read by the Bun plugin's strategy-config
+/// loader (relative to the crate root)
+pub const SCHEMA_PATH: &str = "schema/app-config.schema.json";
+
+/// Legacy env vars mapped onto config paths
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("CB_ENABLED", "risk.enabled"),
+    ("CB_MAX_POSITION_PER_MARKET", "risk.max_position_per_market"),
+    ("CB_MAX_TOTAL_POSITION", "risk.max_total_position"),
+    ("CB_MAX_DAILY_LOSS", "risk.max_daily_loss"),
+    ("CB_MAX_CONSECUTIVE_ERRORS", "risk.max_consecutive_errors"),
+    ("CB_COOLDOWN_SECS", "risk.cooldown_secs"),
+    ("RISK_PHASES", "risk.phases"),
+    ("DRY_RUN", "execution.dry_run"),
+    ("ARB_THRESHOLD", "execution.arb_threshold"),
+    ("KALSHI_ENV", "execution.kalshi_env"),
+    ("POLY_ENV", "execution.poly_env"),
+    ("POSITION_COST_METHOD", "execution.cost_method"),
+    ("EXEC_MAX_CONCURRENT", "execution.max_concurrent"),
+    ("EXEC_CAPITAL_BUDGET", "execution.capital_budget"),
+    ("EXEC_PHASES", "execution.phases"),
+    ("ENABLED_LEAGUES", "feeds.enabled_leagues"),
+    ("FEED_SCHEMA_MODE", "feeds.schema_mode"),
+    ("WORKER_TRIGGER_THRESHOLD", "worker.trigger_threshold"),
+    ("WORKER_MAX_PROCESSING_US", "worker.max_processing_time_us"),
+    ("PATTERN_MIN_GAP", "patterns.min_gap_threshold"),
+    ("SIM_LATENCY_JITTER", "backtester.sim_latency_jitter"),
+    ("SIM_SHARP_LIMIT_THRESHOLD", "backtester.sharp_limit_threshold"),
+    ("SIM_MAX_SPEED_MULTIPLIER", "backtester.max_speed_multiplier"),
+    ("SIM_MEMORY_LIMIT_MB", "backtester.memory_limit_mb"),
+    ("SIM_DATA_SOURCE_PATH", "backtester.data_path"),
+    ("DASHBOARD_ENABLED", "dashboard.enabled"),
+    ("DASHBOARD_INTERVAL_MS", "dashboard.update_interval_ms"),
+    ("ALLOC_BANKROLL", "allocator.bankroll"),
+    ("ALLOC_KELLY_FRACTION", "allocator.kelly_fraction"),
+    ("ALLOC_REBALANCE_SECS", "allocator.rebalance_secs"),
+    ("ACCOUNT_ROUTING", "accounts.routing"),
+    ("MM_ENABLED", "market_making.enabled"),
+    ("LOG_FORMAT", "logging.format"),
+    ("LOG_LEVEL", "logging.level"),
+    ("FEATURE_PREMIUM", "features.premium"),
+    ("FEATURE_BETA", "features.beta_features"),
+    ("FEATURE_DEBUG", "features.debug"),
+    ("LOW_LATENCY", "runtime.low_latency"),
+    ("REDIS_URL", "redis.url"),
+    ("SCHED_KALSHI_RPS", "scheduler.kalshi_rps"),
+    ("SCHED_KALSHI_BURST", "scheduler.kalshi_burst"),
+    ("SCHED_POLY_RPS", "scheduler.poly_rps"),
+    ("SCHED_POLY_BURST", "scheduler.poly_burst"),
+    ("BREAKER_WINDOW_SECS", "breaker.window_secs"),
+    ("BREAKER_MIN_CALLS", "breaker.min_calls"),
+    ("BREAKER_FAILURE_RATE", "breaker.failure_rate"),
+    ("BREAKER_OPEN_SECS", "breaker.open_secs"),
+    ("BREAKER_HALF_OPEN_PROBES", "breaker.half_open_probes"),
+    ("NTP_SERVER", "clock_sync.ntp_server"),
+    ("NTP_INTERVAL_SECS", "clock_sync.ntp_interval_secs"),
+    ("CLOCK_SYNC_WINDOW", "clock_sync.window"),
+    ("DECISION_SLA_MS", "decision_latency.tier_p99_ms"),
+    ("DECISION_SLA_MIN_SAMPLES", "decision_latency.min_samples"),
+    ("SLA_DEGRADATION", "sla.enabled"),
+    ("SLA_WINDOW", "sla.window"),
+    ("SLA_RECOVER_CHECKS", "sla.recover_checks"),
+    ("SLA_SHED_BELOW", "sla.shed_below"),
+    ("SLA_DEFENSIVE_BELOW", "sla.defensive_below"),
+    ("SETTLEMENT_POLL_SECS", "settlement.poll_secs"),
+    ("SETTLEMENT_MAX_UNMATCHED", "settlement.max_unmatched"),
+    ("SUPERVISOR_STARTUP_TIMEOUT_SECS", "supervisor.startup_timeout_secs"),
+    ("SUPERVISOR_SHUTDOWN_GRACE_SECS", "supervisor.shutdown_grace_secs"),
+    ("PAPER_FILLS", "paper_fills.enabled"),
+    ("PAPER_ORDER_SIZE", "paper_fills.order_size"),
+    ("JOURNAL", "journal.enabled"),
+    ("JOURNAL_DIR", "journal.dir"),
+    ("JOURNAL_COMPACT_EVERY", "journal.compact_every"),
+    ("JOURNAL_FSYNC", "journal.fsync"),
+    ("AUDIT", "audit.enabled"),
+    ("AUDIT_DIR", "audit.dir"),
+    ("AUDIT_FSYNC", "audit.fsync"),
+    ("BLOTTER", "blotter.enabled"),
+    ("BLOTTER_DIR", "blotter.dir"),
+    ("BLOTTER_DROP_COPY", "blotter.drop_copy"),
+    ("BLOTTER_FIX_SENDER", "blotter.sender_comp_id"),
+    ("BLOTTER_FIX_TARGET", "blotter.target_comp_id"),
+    ("TCA", "tca.enabled"),
+    ("TCA_DIR", "tca.dir"),
+    ("ODDS_CAPTURE", "odds_capture.enabled"),
+    ("ODDS_CAPTURE_DIR", "odds_capture.dir"),
+    ("ODDS_CAPTURE_FLUSH_ROWS", "odds_capture.flush_rows"),
+    ("TICK_STORE", "tick_store.enabled"),
+    ("TICK_STORE_DIR", "tick_store.dir"),
+    ("TICK_STORE_FLUSH_ROWS", "tick_store.flush_rows"),
+    ("TICK_STORE_ROW_GROUP_ROWS", "tick_store.row_group_rows"),
+    ("TICK_STORE_ZSTD_LEVEL", "tick_store.zstd_level"),
+    ("BACKTEST_JOBS", "backtest_jobs.enabled"),
+    ("BACKTEST_JOBS_DIR", "backtest_jobs.dir"),
+    ("BACKTEST_MAX_QUEUED", "backtest_jobs.max_queued"),
+    ("BACKTEST_MAX_CONCURRENT", "backtest_jobs.max_concurrent"),
+    ("BACKTEST_RESULTS_PATH", "backtest_results.path"),
+    ("BACKTEST_RESULTS_KEEP", "backtest_results.keep"),
+    ("STUDIES", "studies.enabled"),
+    ("STUDIES_DIR", "studies.dir"),
+    ("STUDIES_TOP_N", "studies.top_n"),
+];
+
+/// Parsed command-line flags
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub config_path: Option<PathBuf>,
+    /// (dotted path, raw value)
+    pub overrides: Vec<(String, String)>,
+}
+
+impl CliArgs {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut out = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                bail!("unexpected argument: {}", arg);
+            };
+            let (key, value) = match flag.split_once('=') {
+                Some((k, v)) => (k.to_string(), v.to_string()),
+                None => {
+                    let v = args.next().ok_or_else(|| anyhow!("--{} needs a value", flag))?;
+                    (flag.to_string(), v)
+                }
+            };
+            if key == "config" {
+                out.config_path = Some(PathBuf::from(value));
+            } else if key.contains('.') {
+                out.overrides.push((key, value));
+            } else {
+                bail!("unknown flag --{} (use --<section>.<key>=<value>)", key);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl AppConfig {
+    /// Load from process args, env and config file
+    pub fn load() -> Result<Self> {
+        let cli = CliArgs::parse(std::env::args().skip(1))?;
+        Self::load_with(Self::config_path(&cli).as_deref(), &cli)
+    }
+
+    /// Config file in effect: --config, else ARB_CONFIG, else ./arb.toml if present
+    pub fn config_path(cli: &CliArgs) -> Option<PathBuf> {
+        cli.config_path.clone()
+            .or_else(|| std::env::var("ARB_CONFIG").ok().map(PathBuf::from))
+            .or_else(|| {
+                let p = PathBuf::from(DEFAULT_CONFIG_PATH);
+                p.exists().then_some(p)
+            })
+    }
+
+    /// Load from an explicit file (if any) plus process env and parsed flags
+    pub fn load_with(path: Option<&std::path::Path>, cli: &CliArgs) -> Result<Self> {
+        let file = match path {
+            Some(p) => Some(std::fs::read_to_string(p)
+                .with_context(|| format!("read config {}", p.display()))?),
+            None => None,
+        };
+        Self::layered(file.as_deref(), &|k| std::env::var(k).ok(), &cli.overrides)
+    }
+
+    /// Apply the layers in order: defaults -> TOML -> env -> CLI
+    pub fn layered(
+        toml_src: Option<&str>,
+        env: &dyn Fn(&str) -> Option<String>,
+        cli: &[(String, String)],
+    ) -> Result<Self> {
+        let mut tree = serde_json::to_value(Self::default())?;
+        if let Some(src) = toml_src {
+            let file: toml::Value = toml::from_str(src).context("parse config TOML")?;
+            merge_value(&mut tree, serde_json::to_value(file)?);
+        }
+        for (var, path) in ENV_OVERRIDES {
+            if let Some(raw) = env(var) {
+                set_path(&mut tree, path, &raw).with_context(|| format!("env {}", var))?;
+            }
+        }
+        for (path, raw) in cli {
+            set_path(&mut tree, path, raw).with_context(|| format!("flag --{}", path))?;
+        }
+
+        let mut config: Self = serde_json::from_value(tree).context("invalid configuration")?;
+        config.secrets = SecretsSection::from_env(env);
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Pin the venue environments to this config (call before any client is built)
+    pub fn apply_venue_envs(&self) {
+        let _ = KALSHI_ENV.set(VenueEnv::parse(&self.execution.kalshi_env));
+        let _ = POLY_ENV.set(VenueEnv::parse(&self.execution.poly_env));
+    }
+
+    /// Check ranges and enumerations; reports every problem at once
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        let r = &self.risk;
+        if r.max_position_per_market <= 0 || r.max_total_position <= 0 {
+            errors.push("risk position limits must be positive".to_string());
+        }
+        if r.max_position_per_market > r.max_total_position {
+            errors.push("risk.max_position_per_market exceeds risk.max_total_position".to_string());
+        }
+        if r.max_daily_loss <= 0.0 {
+            errors.push("risk.max_daily_loss must be positive".to_string());
+        }
+        if r.max_consecutive_errors == 0 || r.provider_failure_threshold == 0 {
+            errors.push("risk error thresholds must be at least 1".to_string());
+        }
+        if r.phases.is_empty() {
+            errors.push("risk.phases must list at least one phase".to_string());
+        }
+        if r.market_cooldown_secs == 0 || r.market_cooldown_max_secs < r.market_cooldown_secs {
+            errors.push("risk.market_cooldown_secs must be positive and at most risk.market_cooldown_max_secs".to_string());
+        }
+        for (group, rule) in &r.groups {
+            if group.is_empty() {
+                errors.push("risk.groups: empty series/event ticker".to_string());
+            }
+            if rule.max_position < 0 || rule.max_position_per_market < 0 {
+                errors.push(format!("risk.groups.{} limits must not be negative", group));
+            }
+            if !(0.0..=1.0).contains(&rule.correlation) {
+                errors.push(format!("risk.groups.{}.correlation not in [0, 1]", group));
+            }
+        }
+
+        let e = &self.execution;
+        if !(e.arb_threshold > 0.0 && e.arb_threshold <= 1.0) {
+            errors.push(format!("execution.arb_threshold {} not in (0, 1]", e.arb_threshold));
+        }
+        for (key, value) in [("kalshi_env", &e.kalshi_env), ("poly_env", &e.poly_env)] {
+            if !KNOWN_ENVS.contains(&value.trim().to_lowercase().as_str()) {
+                errors.push(format!("execution.{} unknown environment '{}'", key, value));
+            }
+        }
+        if !COST_METHODS.contains(&e.cost_method.to_lowercase().as_str()) {
+            errors.push(format!("execution.cost_method '{}' (expected fifo|average)", e.cost_method));
+        }
+        if e.max_concurrent == 0 || e.signal_half_life_ms == 0 {
+            errors.push("execution.max_concurrent and execution.signal_half_life_ms must be positive".to_string());
+        }
+        if e.capital_budget < 0.0 {
+            errors.push("execution.capital_budget must not be negative".to_string());
+        }
+        if e.phases.is_empty() {
+            errors.push("execution.phases must list at least one phase".to_string());
+        }
+        for (pattern, playbook) in &e.playbooks {
+            if pattern.is_empty() {
+                errors.push("execution.playbooks: empty pattern id".to_string());
+            }
+            if playbook.order_type == OrderStyle::Passive && playbook.timeout_ms == 0 {
+                errors.push(format!("execution.playbooks.{}: passive orders need a timeout_ms", pattern));
+            }
+        }
+        if !(0.0..=1.0).contains(&e.review.below_confidence) || e.review.window_ms == 0 {
+            errors.push("execution.review: below_confidence must be in [0, 1] and window_ms positive".to_string());
+        }
+
+        for league in &self.feeds.enabled_leagues {
+            if get_league_config(league).is_none() {
+                errors.push(format!("feeds.enabled_leagues: unknown league '{}'", league));
+            }
+        }
+        if self.feeds.poly_ping_interval_secs == 0 {
+            errors.push("feeds.poly_ping_interval_secs must be positive".to_string());
+        }
+        if !SCHEMA_MODES.contains(&self.feeds.schema_mode.as_str()) {
+            errors.push(format!("feeds.schema_mode '{}' not one of {:?}", self.feeds.schema_mode, SCHEMA_MODES));
+        }
+        let f = &self.feeds;
+        if f.heartbeat_interval_ms == 0 || f.heartbeat_pre_game_ms == 0 {
+            errors.push("feeds heartbeat intervals must be positive".to_string());
+        }
+        if f.heartbeat_in_play_ms.len() != MARKET_TIERS || f.heartbeat_in_play_ms.contains(&0) {
+            errors.push(format!("feeds.heartbeat_in_play_ms needs {} positive intervals (Tier 1..4)", MARKET_TIERS));
+        }
+        if f.outlier_sigma < 0.0 || f.outlier_min_jump_cents < 0.0 {
+            errors.push("feeds outlier thresholds must not be negative".to_string());
+        }
+        if f.outlier_window < 2 {
+            errors.push("feeds.outlier_window must be at least 2".to_string());
+        }
+        if f.max_reconnect_attempts == 0 || f.max_reconnect_delay_secs < f.ws_reconnect_delay_secs {
+            errors.push("feeds reconnects need max_reconnect_attempts >= 1 and max_reconnect_delay_secs >= ws_reconnect_delay_secs".to_string());
+        }
+        if !(0.0..1.0).contains(&f.reconnect_jitter) {
+            errors.push("feeds.reconnect_jitter must be in [0, 1)".to_string());
+        }
+        if f.coalesce_window_ms > MAX_COALESCE_WINDOW_MS {
+            errors.push(format!("feeds.coalesce_window_ms must be at most {} (the tick processing budget)", MAX_COALESCE_WINDOW_MS));
+        }
+        if f.stale_heartbeats == 0 {
+            errors.push("feeds.stale_heartbeats must be at least 1".to_string());
+        }
+        for (provider, urls) in &f.endpoints {
+            if provider.is_empty() {
+                errors.push("feeds.endpoints: empty provider name".to_string());
+            }
+            if let Some(url) = urls.iter().find(|u| !u.starts_with("ws://") && !u.starts_with("wss://")) {
+                errors.push(format!("feeds.endpoints.{}: '{}' is not a ws:// or wss:// URL", provider, url));
+            }
+        }
+        if !f.endpoints.is_empty() && f.dedup_window_ms == 0 {
+            errors.push("feeds.dedup_window_ms must be at least 1 with feeds.endpoints".to_string());
+        }
+        for (provider, rule) in &f.heartbeat_providers {
+            if provider.is_empty() {
+                errors.push("feeds.heartbeat_providers: empty provider name".to_string());
+            }
+            if !rule.in_play_ms.is_empty() && (rule.in_play_ms.len() != MARKET_TIERS || rule.in_play_ms.contains(&0)) {
+                errors.push(format!(
+                    "feeds.heartbeat_providers.{}.in_play_ms needs {} positive intervals (Tier 1..4)",
+                    provider, MARKET_TIERS
+                ));
+            }
+        }
+
+        let m = &self.maintenance;
+        if m.kalshi_api && m.refresh_mins == 0 {
+            errors.push("maintenance.refresh_mins must be at least 1".to_string());
+        }
+        for (i, rule) in m.windows.iter().enumerate() {
+            if !ACCOUNT_VENUES.contains(&rule.venue.as_str()) {
+                errors.push(format!("maintenance.windows[{}].venue '{}' not one of {:?}", i, rule.venue, ACCOUNT_VENUES));
+            }
+            match (rule.cron.is_empty(), rule.start.is_empty()) {
+                (false, true) => {
+                    if let Err(e) = rule.cron.parse::<Schedule>() {
+                        errors.push(format!("maintenance.windows[{}].cron: {}", i, e));
+                    }
+                }
+                (true, false) => {
+                    if chrono::DateTime::parse_from_rfc3339(&rule.start).is_err() {
+                        errors.push(format!("maintenance.windows[{}].start '{}' is not RFC3339", i, rule.start));
+                    }
+                }
+                _ => errors.push(format!("maintenance.windows[{}] needs exactly one of cron and start", i)),
+            }
+            if rule.duration_mins == 0 {
+                errors.push(format!("maintenance.windows[{}].duration_mins must be at least 1", i));
+            }
+        }
+
+        let sz = &self.sizing;
+        if !SIZING_CURVES.contains(&sz.curve.as_str()) {
+            errors.push(format!("sizing.curve '{}' not one of {:?}", sz.curve, SIZING_CURVES));
+        }
+        if sz.curve == "piecewise" {
+            if sz.points.len() < 2 {
+                errors.push("sizing.points needs at least 2 knots for the piecewise curve".to_string());
+            }
+            if sz.points.windows(2).any(|w| w[1][0] <= w[0][0]) {
+                errors.push("sizing.points must be in increasing strength".to_string());
+            }
+        }
+        if sz.points.iter().any(|[strength, multiplier]| !(0.0..=1.0).contains(strength) || !(0.0..=1.0).contains(multiplier)) {
+            errors.push("sizing.points: strength and multiplier must be in [0, 1]".to_string());
+        }
+        if sz.steepness <= 0.0 {
+            errors.push("sizing.steepness must be positive".to_string());
+        }
+        if !(0.0..=1.0).contains(&sz.confidence_weight) {
+            errors.push("sizing.confidence_weight not in [0, 1]".to_string());
+        }
+        if sz.worker_full_strength <= 0.0 || sz.arb_full_strength_cents <= 0.0 {
+            errors.push("sizing.worker_full_strength and sizing.arb_full_strength_cents must be positive".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.worker.trigger_threshold) {
+            errors.push("worker.trigger_threshold not in [0, 1]".to_string());
+        }
+        if self.worker.max_processing_time_us <= 0.0 {
+            errors.push("worker.max_processing_time_us must be positive".to_string());
+        }
+        for (id, tuning) in &self.worker.filters {
+            if id.parse::<u16>().is_err() {
+                errors.push(format!("worker.filters: '{}' is not a pattern id", id));
+            }
+            if tuning.dt <= 0.0 || tuning.q_steam <= 0.0 || tuning.q_quiet <= 0.0 || tuning.r_noise <= 0.0 {
+                errors.push(format!("worker.filters.{}: dt and noise terms must be positive", id));
+            }
+            if tuning.velocity_threshold < 0.0 {
+                errors.push(format!("worker.filters.{}.velocity_threshold must not be negative", id));
+            }
+        }
+        for (key, rule) in &self.worker.prefilters {
+            if key.parse::<u16>().is_err() && !PREFILTER_TIERS.contains(&key.as_str()) {
+                errors.push(format!("worker.prefilters: '{}' is not a pattern id or tier1..tier4", key));
+            }
+            if !(0.0..=1.0).contains(&rule.ewma_alpha) || rule.min_change < 0.0 {
+                errors.push(format!("worker.prefilters.{}: ewma_alpha must be in [0, 1] and min_change >= 0", key));
+            }
+        }
+        let p = &self.patterns;
+        if p.min_gap_threshold < 0.0 || !(0.0..=1.0).contains(&p.min_gap_percent) {
+            errors.push("patterns gap thresholds out of range".to_string());
+        }
+        if p.max_half_life_ms <= 0.0 {
+            errors.push("patterns.max_half_life_ms must be positive".to_string());
+        }
+        for (id, rule) in &p.enabled {
+            if id.trim().is_empty() {
+                errors.push("patterns.enabled: empty pattern id".to_string());
+            }
+            if !(0.0..=1.0).contains(&rule.min_confidence) {
+                errors.push(format!("patterns.enabled.{}.min_confidence not in [0, 1]", id));
+            }
+            if rule.max_capital < 0.0 {
+                errors.push(format!("patterns.enabled.{}.max_capital must not be negative", id));
+            }
+            if rule.venues.iter().any(|v| v.trim().is_empty()) {
+                errors.push(format!("patterns.enabled.{}.venues: empty venue name", id));
+            }
+            if rule.half_life_ms < 0.0 {
+                errors.push(format!("patterns.enabled.{}.half_life_ms must not be negative", id));
+            }
+        }
+        let aging = &p.aging;
+        if aging.flag_after_half_lives < 0.0 || aging.liquidate_after_half_lives < aging.flag_after_half_lives {
+            errors.push("patterns.aging: flag_after_half_lives must be >= 0 and <= liquidate_after_half_lives".to_string());
+        }
+        if !(0.01..=0.99).contains(&aging.min_exit_price) || aging.interval_secs == 0 {
+            errors.push("patterns.aging: min_exit_price must be in 0.01-0.99 and interval_secs positive".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.backtester.sharp_limit_threshold) {
+            errors.push("backtester.sharp_limit_threshold not in [0, 1]".to_string());
+        }
+        if self.backtester.max_speed_multiplier <= 0.0 {
+            errors.push("backtester.max_speed_multiplier must be positive".to_string());
+        }
+        if self.dashboard.update_interval_ms < 100 {
+            errors.push("dashboard.update_interval_ms must be >= 100".to_string());
+        }
+        let a = &self.allocator;
+        if a.bankroll < 0.0 {
+            errors.push("allocator.bankroll must not be negative".to_string());
+        }
+        for (key, value) in [("kelly_fraction", a.kelly_fraction), ("max_share", a.max_share)] {
+            if !(value > 0.0 && value <= 1.0) {
+                errors.push(format!("allocator.{} {} not in (0, 1]", key, value));
+            }
+        }
+        if !(0.0..=a.max_share).contains(&a.exploration_share) {
+            errors.push("allocator.exploration_share not in [0, allocator.max_share]".to_string());
+        }
+        if a.min_active_days < 2 || a.min_active_days > a.lookback_days {
+            errors.push("allocator.min_active_days must be in [2, allocator.lookback_days]".to_string());
+        }
+        if a.rebalance_secs == 0 {
+            errors.push("allocator.rebalance_secs must be positive".to_string());
+        }
+        let accounts = &self.accounts;
+        if !ROUTING_POLICIES.contains(&accounts.routing.as_str()) {
+            errors.push(format!("accounts.routing '{}' not one of {:?}", accounts.routing, ROUTING_POLICIES));
+        }
+        if !(0.0..=1.0).contains(&accounts.sharp_limit) {
+            errors.push("accounts.sharp_limit not in [0, 1]".to_string());
+        }
+        for (id, rule) in &accounts.list {
+            if id.trim().is_empty() {
+                errors.push("accounts.list: empty account id".to_string());
+            }
+            if !ACCOUNT_VENUES.contains(&rule.venue.as_str()) {
+                errors.push(format!("accounts.list.{}.venue '{}' not one of {:?}", id, rule.venue, ACCOUNT_VENUES));
+            }
+            if rule.max_exposure < 0.0 || rule.max_order < 0.0 {
+                errors.push(format!("accounts.list.{} limits must not be negative", id));
+            }
+        }
+        let mm = &self.market_making;
+        if mm.min_spread_cents < 2 {
+            errors.push("market_making.min_spread_cents must be at least 2".to_string());
+        }
+        if mm.max_touch_size <= 0 {
+            errors.push("market_making.max_touch_size must be positive".to_string());
+        }
+        if mm.half_spread_cents <= 0.0 || mm.skew_cents < 0.0 {
+            errors.push("market_making.half_spread_cents must be positive and skew_cents not negative".to_string());
+        }
+        if mm.quote_size < 1 || mm.max_inventory < mm.quote_size {
+            errors.push("market_making.quote_size must be in [1, market_making.max_inventory]".to_string());
+        }
+        if mm.requote_cents < 1 {
+            errors.push("market_making.requote_cents must be at least 1".to_string());
+        }
+        let logging = &self.logging;
+        if !LOG_FORMATS.contains(&logging.format.as_str()) {
+            errors.push(format!("logging.format '{}' not one of {:?}", logging.format, LOG_FORMATS));
+        }
+        if !LOG_LEVELS.contains(&logging.level.as_str()) {
+            errors.push(format!("logging.level '{}' not one of {:?}", logging.level, LOG_LEVELS));
+        }
+        for (target, level) in &logging.modules {
+            if target.trim().is_empty() || target.contains([',', '=']) {
+                errors.push(format!("logging.modules: '{}' is not a module path", target));
+            }
+            if !LOG_LEVELS.contains(&level.as_str()) {
+                errors.push(format!("logging.modules.{} '{}' not one of {:?}", target, level, LOG_LEVELS));
+            }
+        }
+        let rt = &self.runtime;
+        if rt.tick_buffer == 0 {
+            errors.push("runtime.tick_buffer must be positive".to_string());
+        }
+        if rt.busy_poll_tiers > MARKET_TIERS {
+            errors.push(format!("runtime.busy_poll_tiers must be at most {} (Tier 1..4)", MARKET_TIERS));
+        }
+        if rt.pin_cores.iter().enumerate().any(|(i, core)| rt.pin_cores[..i].contains(core)) {
+            errors.push("runtime.pin_cores: a core is listed twice".to_string());
+        }
+        for id in self.features.components.keys() {
+            if !id.parse::<u16>().is_ok_and(|id| (71..=88).contains(&id)) {
+                errors.push(format!("features.components: '{}' is not a component id in 71-88", id));
+            }
+        }
+        let shadow = &self.features.shadow;
+        for id in &shadow.components {
+            if !(71..=88).contains(id) {
+                errors.push(format!("features.shadow.components: {} is not a component id in 71-88", id));
+            }
+        }
+        if shadow.promote_after_days < 0.0 || !(0.0..=1.0).contains(&shadow.max_drawdown) || shadow.starting_capital <= 0.0 {
+            errors.push("features.shadow: promote_after_days must be >= 0, max_drawdown in 0-1 and starting_capital > 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.breaker.failure_rate) || self.breaker.half_open_probes < 1 {
+            errors.push("breaker: failure_rate must be in 0-1 and half_open_probes at least 1".to_string());
+        }
+        if self.clock_sync.window < 2 {
+            errors.push("clock_sync.window must be at least 2".to_string());
+        }
+        if self.decision_latency.tier_p99_ms.iter().any(|ms| !ms.is_finite() || *ms <= 0.0) {
+            errors.push("decision_latency.tier_p99_ms must all be positive".to_string());
+        }
+        let sla = &self.sla;
+        if sla.window < 1 || sla.recover_checks < 1 {
+            errors.push("sla.window and sla.recover_checks must be at least 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&sla.shed_below) || !(0.0..=sla.shed_below).contains(&sla.defensive_below) {
+            errors.push("sla: shed_below must be in 0-1 and defensive_below in [0, shed_below]".to_string());
+        }
+        if !(1..=22).contains(&self.tick_store.zstd_level) {
+            errors.push("tick_store.zstd_level must be in 1-22".to_string());
+        }
+        if self.backtest_jobs.max_concurrent < 1 {
+            errors.push("backtest_jobs.max_concurrent must be at least 1".to_string());
+        }
+        if self.backtest_results.keep < 1 || self.studies.top_n < 1 {
+            errors.push("backtest_results.keep and studies.top_n must be at least 1".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            bail!("invalid configuration:\n  {}", errors.join("\n  "))
+        }
+    }
+}
+
+impl AppConfig {
+    /// JSON Schema (draft 2020-12) for the config tree. Types and defaults come from
+    /// `AppConfig::default()`, so the schema follows the structs; enumerations and ranges
+    /// that `validate` checks are added per path. TypeScript strategy configs are
+    /// validated against it, so keys and types are single-sourced here.
+    pub fn json_schema() -> serde_json::Value {
+        let defaults = serde_json::to_value(Self::default()).expect("defaults serialize");
+        let mut schema = schema_of(&defaults);
+        schema["$schema"] = serde_json::json!("https://json-schema.org/draft/2020-12/schema");
+        schema["title"] = serde_json::json!("AppConfig");
+
+        let leagues: Vec<&str> = get_league_configs().iter().map(|l| l.league_code).collect();
+        let components: Vec<String> = (71..=88).map(|id: u16| id.to_string()).collect();
+        let phases = serde_json::json!({ "items": { "type": "string", "enum": EventPhase::ALL }, "minItems": 1 });
+        let mut pattern_rule = schema_of(&serde_json::to_value(PatternRule::default()).expect("defaults serialize"));
+        merge_value(&mut pattern_rule, serde_json::json!({ "properties": {
+            "max_capital": { "minimum": 0 },
+            "min_confidence": { "minimum": 0, "maximum": 1 },
+            "venues": { "items": { "type": "string", "minLength": 1 } },
+            "half_life_ms": { "minimum": 0 },
+        } }));
+        let mut group_rule = schema_of(&serde_json::to_value(GroupRule::default()).expect("defaults serialize"));
+        merge_value(&mut group_rule, serde_json::json!({ "properties": {
+            "max_position": { "minimum": 0 },
+            "max_position_per_market": { "minimum": 0 },
+            "correlation": { "minimum": 0, "maximum": 1 },
+        } }));
+        let mut playbook = schema_of(&serde_json::to_value(ExecutionPlaybook::default()).expect("defaults serialize"));
+        merge_value(&mut playbook, serde_json::json!({ "properties": {
+            "order_type": { "enum": ["ioc", "passive"] },
+            "leg_order": { "enum": ["concurrent", "kalshi_first", "poly_first"] },
+            "remainder": { "enum": ["cancel", "cross", "hold"] },
+        } }));
+        let mut account_rule = schema_of(&serde_json::to_value(AccountRule::default()).expect("defaults serialize"));
+        merge_value(&mut account_rule, serde_json::json!({ "properties": {
+            "venue": { "enum": ACCOUNT_VENUES },
+            "max_exposure": { "minimum": 0 },
+            "max_order": { "minimum": 0 },
+        } }));
+        let mut filter_tuning = schema_of(&serde_json::to_value(FilterTuning::default()).expect("defaults serialize"));
+        merge_value(&mut filter_tuning, serde_json::json!({ "properties": {
+            "dt": { "exclusiveMinimum": 0 },
+            "q_steam": { "exclusiveMinimum": 0 },
+            "q_quiet": { "exclusiveMinimum": 0 },
+            "r_noise": { "exclusiveMinimum": 0 },
+            "velocity_threshold": { "minimum": 0 },
+            "pattern_params": { "additionalProperties": { "type": "number" } },
+        } }));
+        let mut prefilter_rule = schema_of(&serde_json::to_value(PrefilterRule::default()).expect("defaults serialize"));
+        merge_value(&mut prefilter_rule, serde_json::json!({ "properties": {
+            "ewma_alpha": { "minimum": 0, "maximum": 1 },
+            "min_change": { "minimum": 0 },
+        } }));
+        let mut maintenance_rule = schema_of(&serde_json::to_value(MaintenanceRule::default()).expect("defaults serialize"));
+        merge_value(&mut maintenance_rule, serde_json::json!({ "properties": {
+            "venue": { "enum": ACCOUNT_VENUES },
+            "duration_mins": { "minimum": 1 },
+        } }));
+        let tier_intervals = serde_json::json!({
+            "items": { "type": "integer", "minimum": 1 },
+            "minItems": MARKET_TIERS,
+            "maxItems": MARKET_TIERS,
+        });
+        let mut heartbeat_rule = schema_of(&serde_json::to_value(HeartbeatRule::default()).expect("defaults serialize"));
+        merge_value(&mut heartbeat_rule, serde_json::json!({ "properties": {
+            "in_play_ms": { "items": { "type": "integer", "minimum": 1 }, "maxItems": MARKET_TIERS },
+        } }));
+        let refinements = [
+            ("risk.max_position_per_market", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("risk.max_total_position", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("risk.max_daily_loss", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("risk.max_consecutive_errors", serde_json::json!({ "minimum": 1 })),
+            ("risk.provider_failure_threshold", serde_json::json!({ "minimum": 1 })),
+            ("risk.phases", phases.clone()),
+            ("risk.market_cooldown_secs", serde_json::json!({ "minimum": 1 })),
+            ("risk.market_cooldown_max_secs", serde_json::json!({ "minimum": 1 })),
+            (
+                "risk.groups",
+                serde_json::json!({
+                    "additionalProperties": group_rule,
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("execution.arb_threshold", serde_json::json!({ "exclusiveMinimum": 0, "maximum": 1 })),
+            ("execution.kalshi_env", serde_json::json!({ "enum": KNOWN_ENVS })),
+            ("execution.poly_env", serde_json::json!({ "enum": KNOWN_ENVS })),
+            ("execution.cost_method", serde_json::json!({ "enum": COST_METHODS })),
+            ("execution.max_concurrent", serde_json::json!({ "minimum": 1 })),
+            ("execution.capital_budget", serde_json::json!({ "minimum": 0 })),
+            ("execution.signal_half_life_ms", serde_json::json!({ "minimum": 1 })),
+            ("execution.phases", phases),
+            (
+                "execution.playbooks",
+                serde_json::json!({
+                    "additionalProperties": playbook,
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("execution.review.below_confidence", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("execution.review.window_ms", serde_json::json!({ "minimum": 1 })),
+            ("feeds.enabled_leagues", serde_json::json!({ "items": { "type": "string", "enum": leagues } })),
+            ("feeds.poly_ping_interval_secs", serde_json::json!({ "minimum": 1 })),
+            ("feeds.schema_mode", serde_json::json!({ "enum": SCHEMA_MODES })),
+            ("feeds.heartbeat_interval_ms", serde_json::json!({ "minimum": 1 })),
+            ("feeds.heartbeat_in_play_ms", tier_intervals),
+            ("feeds.heartbeat_pre_game_ms", serde_json::json!({ "minimum": 1 })),
+            ("feeds.stale_heartbeats", serde_json::json!({ "minimum": 1 })),
+            ("feeds.outlier_sigma", serde_json::json!({ "minimum": 0 })),
+            ("feeds.outlier_min_jump_cents", serde_json::json!({ "minimum": 0 })),
+            ("feeds.outlier_window", serde_json::json!({ "minimum": 2 })),
+            ("feeds.max_reconnect_attempts", serde_json::json!({ "minimum": 1 })),
+            ("feeds.reconnect_jitter", serde_json::json!({ "minimum": 0, "exclusiveMaximum": 1 })),
+            ("feeds.coalesce_window_ms", serde_json::json!({ "maximum": MAX_COALESCE_WINDOW_MS })),
+            (
+                "feeds.endpoints",
+                serde_json::json!({
+                    "additionalProperties": { "type": "array", "items": { "type": "string", "pattern": "^wss?://" } },
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            (
+                "feeds.heartbeat_providers",
+                serde_json::json!({
+                    "additionalProperties": heartbeat_rule,
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("maintenance.windows", serde_json::json!({ "items": maintenance_rule })),
+            ("sizing.curve", serde_json::json!({ "enum": SIZING_CURVES })),
+            (
+                "sizing.points",
+                serde_json::json!({
+                    "items": {
+                        "type": "array",
+                        "items": { "type": "number", "minimum": 0, "maximum": 1 },
+                        "minItems": 2,
+                        "maxItems": 2,
+                    },
+                }),
+            ),
+            ("sizing.midpoint", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("sizing.steepness", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("sizing.confidence_weight", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("sizing.worker_full_strength", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("sizing.arb_full_strength_cents", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("worker.trigger_threshold", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("worker.max_processing_time_us", serde_json::json!({ "exclusiveMinimum": 0 })),
+            (
+                "worker.filters",
+                serde_json::json!({
+                    "additionalProperties": filter_tuning,
+                    "propertyNames": { "pattern": "^[0-9]+$" },
+                }),
+            ),
+            (
+                "worker.prefilters",
+                serde_json::json!({
+                    "additionalProperties": prefilter_rule,
+                    "propertyNames": { "pattern": "^([0-9]+|tier[1-4])$" },
+                }),
+            ),
+            ("patterns.min_gap_threshold", serde_json::json!({ "minimum": 0 })),
+            ("patterns.min_gap_percent", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("patterns.max_half_life_ms", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("patterns.aging.flag_after_half_lives", serde_json::json!({ "minimum": 0 })),
+            ("patterns.aging.min_exit_price", serde_json::json!({ "minimum": 0.01, "maximum": 0.99 })),
+            ("patterns.aging.interval_secs", serde_json::json!({ "minimum": 1 })),
+            (
+                "patterns.enabled",
+                serde_json::json!({
+                    "additionalProperties": pattern_rule,
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("backtester.sharp_limit_threshold", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("backtester.max_speed_multiplier", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("dashboard.update_interval_ms", serde_json::json!({ "minimum": 100 })),
+            ("allocator.bankroll", serde_json::json!({ "minimum": 0 })),
+            ("allocator.kelly_fraction", serde_json::json!({ "exclusiveMinimum": 0, "maximum": 1 })),
+            ("allocator.lookback_days", serde_json::json!({ "minimum": 2 })),
+            ("allocator.min_active_days", serde_json::json!({ "minimum": 2 })),
+            ("allocator.max_share", serde_json::json!({ "exclusiveMinimum": 0, "maximum": 1 })),
+            ("allocator.exploration_share", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("allocator.rebalance_secs", serde_json::json!({ "minimum": 1 })),
+            ("accounts.routing", serde_json::json!({ "enum": ROUTING_POLICIES })),
+            ("accounts.sharp_limit", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            (
+                "accounts.list",
+                serde_json::json!({
+                    "additionalProperties": account_rule,
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("market_making.min_spread_cents", serde_json::json!({ "minimum": 2 })),
+            ("market_making.max_touch_size", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("market_making.half_spread_cents", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("market_making.quote_size", serde_json::json!({ "minimum": 1 })),
+            ("market_making.max_inventory", serde_json::json!({ "minimum": 1 })),
+            ("market_making.skew_cents", serde_json::json!({ "minimum": 0 })),
+            ("market_making.requote_cents", serde_json::json!({ "minimum": 1 })),
+            ("logging.format", serde_json::json!({ "enum": LOG_FORMATS })),
+            ("logging.level", serde_json::json!({ "enum": LOG_LEVELS })),
+            (
+                "logging.modules",
+                serde_json::json!({
+                    "additionalProperties": { "type": "string", "enum": LOG_LEVELS },
+                    "propertyNames": { "minLength": 1 },
+                }),
+            ),
+            ("runtime.pin_cores", serde_json::json!({ "items": { "type": "integer", "minimum": 0 }, "uniqueItems": true })),
+            ("runtime.busy_poll_tiers", serde_json::json!({ "minimum": 0, "maximum": MARKET_TIERS })),
+            ("runtime.tick_buffer", serde_json::json!({ "minimum": 1 })),
+            (
+                "features.components",
+                serde_json::json!({
+                    "additionalProperties": { "type": "boolean" },
+                    "propertyNames": { "enum": components },
+                }),
+            ),
+            (
+                "features.shadow.components",
+                serde_json::json!({ "items": { "type": "integer", "minimum": 71, "maximum": 88 }, "uniqueItems": true }),
+            ),
+            ("features.shadow.promote_after_days", serde_json::json!({ "minimum": 0 })),
+            ("features.shadow.max_drawdown", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("features.shadow.starting_capital", serde_json::json!({ "exclusiveMinimum": 0 })),
+            ("breaker.failure_rate", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("breaker.half_open_probes", serde_json::json!({ "minimum": 1 })),
+            ("clock_sync.window", serde_json::json!({ "minimum": 2 })),
+            ("decision_latency.tier_p99_ms", serde_json::json!({ "items": { "type": "number", "exclusiveMinimum": 0 } })),
+            ("sla.window", serde_json::json!({ "minimum": 1 })),
+            ("sla.recover_checks", serde_json::json!({ "minimum": 1 })),
+            ("sla.shed_below", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("sla.defensive_below", serde_json::json!({ "minimum": 0, "maximum": 1 })),
+            ("tick_store.zstd_level", serde_json::json!({ "minimum": 1, "maximum": 22 })),
+            ("backtest_jobs.max_concurrent", serde_json::json!({ "minimum": 1 })),
+            ("backtest_results.keep", serde_json::json!({ "minimum": 1 })),
+            ("studies.top_n", serde_json::json!({ "minimum": 1 })),
+        ];
+        for (path, refinement) in refinements {
+            let mut node = &mut schema;
+            for part in path.split('.') {
+                node = &mut node["properties"][part];
+            }
+            merge_value(node, refinement);
+        }
+        schema
+    }
+}
+
+/// Schema for a default value: objects list their keys and reject unknown ones (as
+/// `deny_unknown_fields` does), scalars take the type of the default
+fn schema_of(value: &serde_json::Value) -> serde_json::Value {
+    use serde_json::{json, Value};
+    match value {
+        Value::Object(fields) => {
+            let properties: serde_json::Map<String, Value> =
+                fields.iter().map(|(key, field)| (key.clone(), schema_of(field))).collect();
+            json!({ "type": "object", "properties": properties, "additionalProperties": false })
+        }
+        Value::Array(_) => json!({ "type": "array", "default": value }),
+        Value::Bool(_) => json!({ "type": "boolean", "default": value }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number", "default": value }),
+        Value::Number(_) => json!({ "type": "integer", "default": value }),
+        Value::String(_) => json!({ "type": "string", "default": value }),
+        Value::Null => json!({}),
+    }
+}
+
+/// Deep-merge `overlay` into `base` (objects recursively, everything else replaced)
+fn merge_value(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (k, v) in overlay {
+                match base.get_mut(&k) {
+                    Some(existing) => merge_value(existing, v),
+                    None => {
+                        base.insert(k, v);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Set a dotted path from a raw string, coerced to the type already at that path
+fn set_path(tree: &mut serde_json::Value, path: &str, raw: &str) -> Result<()> {
+    let mut node = tree;
+    for part in path.split('.') {
+        node = node.get_mut(part).ok_or_else(|| anyhow!("unknown config key '{}'", path))?;
+    }
+    let raw = raw.trim();
+    *node = match node {
+        serde_json::Value::Bool(_) => serde_json::Value::Bool(match raw.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => bail!("'{}' is not a boolean", raw),
+        }),
+        // Integer vs float is settled when the tree is deserialized
+        serde_json::Value::Number(_) => match raw.parse::<i64>() {
+            Ok(i) => serde_json::json!(i),
+            Err(_) => serde_json::json!(raw.parse::<f64>()
+                .map_err(|_| anyhow!("'{}' is not a number", raw))?),
+        },
+        // Lists of numbers (judged by the first default) stay numeric
+        serde_json::Value::Array(items) => {
+            let numeric = items.first().is_some_and(serde_json::Value::is_number);
+            serde_json::Value::Array(
+                raw.split(',').map(str::trim).filter(|s| !s.is_empty())
+                    .map(|s| if numeric {
+                        s.parse::<f64>().map(|n| serde_json::json!(n)).map_err(|_| anyhow!("'{}' is not a number", s))
+                    } else {
+                        Ok(serde_json::Value::String(s.to_string()))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        }
+        serde_json::Value::Object(_) => bail!("'{}' is a section, not a value", path),
+        _ => serde_json::Value::String(raw.to_string()),
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: std::collections::HashMap<String, String> =
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |k| map.get(k).cloned()
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        let toml = r#"
+            [risk]
+            max_daily_loss = 250.0
+            max_consecutive_errors = 3
+
+            [feeds]
+            enabled_leagues = ["nba", "nfl"]
+        "#;
+        let env = env_of(&[("CB_MAX_CONSECUTIVE_ERRORS", "7"), ("DRY_RUN", "0"), ("POLY_PRIVATE_KEY", "0xdead")]);
+        let cli = vec![("risk.max_daily_loss".to_string(), "100".to_string())];
+        let cfg = AppConfig::layered(Some(toml), &env, &cli).unwrap();
+
+        assert_eq!(cfg.risk.max_daily_loss, 100.0);      // CLI beats file
+        assert_eq!(cfg.risk.max_consecutive_errors, 7);  // env beats file
+        assert_eq!(cfg.risk.cooldown_secs, 300);         // default survives
+        assert_eq!(cfg.feeds.enabled_leagues, vec!["nba", "nfl"]);
+        assert_eq!(cfg.feeds.schema_mode, "lenient");
+        assert!(!cfg.execution.dry_run);
+
+        // Secrets never appear in debug output
+        assert_eq!(cfg.secrets.poly_private_key.expose(), "0xdead");
+        let dump = format!("{:?}", cfg);
+        assert!(!dump.contains("0xdead"));
+        assert!(dump.contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_legacy_module_env_vars_route_into_sections() {
+        let env = env_of(&[
+            ("AUDIT", "1"),
+            ("DECISION_SLA_MS", "50,100,200,400"),
+            ("BLOTTER_DROP_COPY", "127.0.0.1:9878"),
+            ("SCHED_KALSHI_RPS", "9"),
+            ("SECRETS_BACKENDS", "env,vault"),
+        ]);
+        let cfg = AppConfig::layered(None, &env, &[]).unwrap();
+
+        assert!(cfg.audit.enabled);
+        assert_eq!(cfg.decision_latency.tier_p99_ms, [50.0, 100.0, 200.0, 400.0]);
+        assert_eq!(cfg.blotter.drop_copy, "127.0.0.1:9878");
+        assert_eq!(cfg.scheduler.kalshi_rps, 9.0);
+        assert_eq!(cfg.secrets.backends, "env,vault");
+        assert!(!cfg.journal.enabled);
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_values() {
+        let none = env_of(&[]);
+        // Typo in file
+        assert!(AppConfig::layered(Some("[risk]\nmax_daily_los = 1.0"), &none, &[]).is_err());
+        // Type mismatch from env
+        assert!(AppConfig::layered(None, &env_of(&[("CB_MAX_DAILY_LOSS", "lots")]), &[]).is_err());
+        // Range checks are collected together
+        let cli = vec![
+            ("execution.arb_threshold".to_string(), "1.5".to_string()),
+            ("feeds.enabled_leagues".to_string(), "nba,cricket".to_string()),
+        ];
+        let err = AppConfig::layered(None, &none, &cli).unwrap_err().to_string();
+        assert!(err.contains("arb_threshold") && err.contains("cricket"));
+        assert!(AppConfig::layered(None, &env_of(&[("FEED_SCHEMA_MODE", "loose")]), &[]).is_err());
+        let heartbeats = "[feeds.heartbeat_providers.kalshi]\nin_play_ms = [500, 1000]";
+        assert!(AppConfig::layered(Some(heartbeats), &none, &[]).unwrap_err().to_string().contains("kalshi.in_play_ms"));
+        let endpoints = "[feeds.endpoints]\nkalshi = [\"https://eu.example\"]";
+        assert!(AppConfig::layered(Some(endpoints), &none, &[]).unwrap_err().to_string().contains("feeds.endpoints.kalshi"));
+        let sizing = "[sizing]\ncurve = \"piecewise\"\npoints = [[0.8, 0.5], [0.6, 1.0]]";
+        assert!(AppConfig::layered(Some(sizing), &none, &[]).unwrap_err().to_string().contains("increasing strength"));
+        let pins = "[runtime]\nlow_latency = true\npin_cores = [2, 3, 2]";
+        assert!(AppConfig::layered(Some(pins), &none, &[]).unwrap_err().to_string().contains("pin_cores"));
+        let playbook = "[execution.playbooks.73]\norder_type = \"passive\"";
+        assert!(AppConfig::layered(Some(playbook), &none, &[]).unwrap_err().to_string().contains("playbooks.73"));
+        let review = "[execution.review]\nbelow_confidence = 0.6\nwindow_ms = 0";
+        assert!(AppConfig::layered(Some(review), &none, &[]).unwrap_err().to_string().contains("execution.review"));
+        let prefilter = "[worker.prefilters.tier5]\newma_alpha = 1.5";
+        let err = AppConfig::layered(Some(prefilter), &none, &[]).unwrap_err().to_string();
+        assert!(err.contains("'tier5'") && err.contains("prefilters.tier5: ewma_alpha"));
+        let reconnects = "[feeds]\nws_reconnect_delay_secs = 30\nmax_reconnect_delay_secs = 10\nreconnect_jitter = 1.0";
+        let err = AppConfig::layered(Some(reconnects), &none, &[]).unwrap_err().to_string();
+        assert!(err.contains("max_reconnect_delay_secs") && err.contains("reconnect_jitter"));
+        let maintenance = "[[maintenance.windows]]\nvenue = \"kalshi\"\ncron = \"0 3 * * 4\"\nstart = \"2026-01-01T00:00:00Z\"\nduration_mins = 60";
+        assert!(AppConfig::layered(Some(maintenance), &none, &[]).unwrap_err().to_string().contains("exactly one of cron and start"));
+
+        let args = CliArgs::parse(["--config", "x.toml", "--risk.enabled=false"].map(String::from)).unwrap();
+        assert_eq!(args.config_path, Some(PathBuf::from("x.toml")));
+        assert_eq!(args.overrides, vec![("risk.enabled".to_string(), "false".to_string())]);
+        assert!(CliArgs::parse(["--verbose=1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_json_schema_is_checked_in() {
+        let schema = AppConfig::json_schema();
+        assert_eq!(schema["properties"]["risk"]["properties"]["max_daily_loss"]["type"], "number");
+        assert_eq!(schema["properties"]["risk"]["properties"]["cooldown_secs"]["default"], 300);
+        assert_eq!(schema["properties"]["risk"]["additionalProperties"], false);
+        assert!(schema["properties"].get("secrets").is_none());
+
+        // UPDATE_SCHEMA=1 cargo test test_json_schema rewrites the checked-in copy
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(SCHEMA_PATH);
+        let rendered = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+        if std::env::var_os("UPDATE_SCHEMA").is_some() {
+            std::fs::write(&path, &rendered).unwrap();
+        }
+        let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(checked_in == rendered, "{} is stale; rerun with UPDATE_SCHEMA=1", SCHEMA_PATH);
+    }
+
+    #[test]
+    fn test_feature_flags_section() {
+        let toml = "[features]\nbeta_features = false\n\n[features.components]\n77 = true\n";
+        let cfg = AppConfig::layered(Some(toml), &env_of(&[("FEATURE_DEBUG", "1")]), &[]).unwrap();
+        assert!(cfg.features.premium && cfg.features.debug && !cfg.features.beta_features);
+        assert_eq!(cfg.features.components.get("77"), Some(&true));
+
+        let bad = "[features.components]\n12 = true\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+
+        let shadow = "[features.shadow]\ncomponents = [77]\npromote_after_days = 7\n";
+        let cfg = AppConfig::layered(Some(shadow), &env_of(&[]), &[]).unwrap();
+        assert_eq!(cfg.features.shadow.components, vec![77]);
+        assert_eq!((cfg.features.shadow.promote_after_days, cfg.features.shadow.min_trades), (7.0, 50));
+        let bad = "[features.shadow]\ncomponents = [12]\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+
+        let rules = "[patterns.enabled.73]\nmax_concurrent = 2\nvenues = [\"pinnacle\"]\n\n[patterns.enabled.PolyOnly]\n";
+        let cfg = AppConfig::layered(Some(rules), &env_of(&[]), &[]).unwrap();
+        assert_eq!(cfg.patterns.enabled["73"].max_concurrent, 2);
+        assert_eq!(cfg.patterns.enabled["PolyOnly"], PatternRule::default());
+        let bad = "[patterns.enabled.73]\nmin_confidence = 1.5\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+        let aging = "[patterns.enabled.75]\nhalf_life_ms = 800.0\n\n[patterns.aging]\nliquidate = true\n";
+        let cfg = AppConfig::layered(Some(aging), &env_of(&[]), &[]).unwrap();
+        assert_eq!(cfg.patterns.enabled["75"].half_life_ms, 800.0);
+        assert!(cfg.patterns.aging.liquidate && cfg.patterns.aging.liquidate_after_half_lives == 20.0);
+        let bad = "[patterns.aging]\nflag_after_half_lives = 30.0\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+
+        let accounts = "[accounts]\nrouting = \"lowest_sharp_score\"\n\n[accounts.list.k2]\nvenue = \"kalshi\"\nmax_exposure = 500.0\n";
+        let cfg = AppConfig::layered(Some(accounts), &env_of(&[]), &[]).unwrap();
+        assert_eq!(cfg.accounts.routing, "lowest_sharp_score");
+        assert_eq!(cfg.accounts.list["k2"].max_exposure, 500.0);
+        let bad = "[accounts.list.b1]\nvenue = \"bet365\"\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+        assert!(AppConfig::layered(None, &env_of(&[("ACCOUNT_ROUTING", "random")]), &[]).is_err());
+
+        let cfg = AppConfig::layered(Some("[market_making]\nskew_cents = 5.0\n"), &env_of(&[("MM_ENABLED", "true")]), &[]).unwrap();
+        assert!(cfg.market_making.enabled);
+        assert_eq!(cfg.market_making.skew_cents, 5.0);
+        let bad = "[market_making]\nquote_size = 80\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+
+        let logging = "[logging.modules]\n\"arb_bot::kalshi\" = \"debug\"\n";
+        let cfg = AppConfig::layered(Some(logging), &env_of(&[("LOG_FORMAT", "text")]), &[]).unwrap();
+        assert_eq!(cfg.logging.format, "text");
+        assert_eq!(cfg.logging.modules["arb_bot::kalshi"], "debug");
+        assert!(AppConfig::layered(None, &env_of(&[("LOG_LEVEL", "loud")]), &[]).is_err());
+        let bad = "[logging.modules]\n\"arb_bot::kalshi\" = \"verbose\"\n";
+        assert!(AppConfig::layered(Some(bad), &env_of(&[]), &[]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+// src/config_reload.rs
+// Hot reload of runtime-tunable config sections (SIGHUP or config file change)
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::config::{AppConfig, CliArgs};
+
+/// Sections applied at runtime; anything else needs a restart
+pub const TUNABLE_SECTIONS: &[&str] = &["risk", "patterns", "worker", "dashboard", "allocator", "accounts", "market_making", "logging", "features", "maintenance", "sizing"];
+
+/// Published after a reload is validated and applied
+#[derive(Debug, Clone)]
+pub struct ConfigChanged {
+    /// Increments on every applied reload (initial config is 0)
+    pub version: u64,
+    /// Tunable sections that changed
+    pub sections: Vec<&'static str>,
+    pub config: Arc<AppConfig>,
+}
+
+impl ConfigChanged {
+    pub fn touches(&self, section: &str) -> bool {
+        self.sections.contains(&section)
+    }
+}
+
+/// Holds the live config and re-reads it on demand
+pub struct ConfigReloader {
+    path: Option<PathBuf>,
+    cli: CliArgs,
+    current: RwLock<Arc<AppConfig>>,
+    version: AtomicU64,
+    tx: broadcast::Sender<ConfigChanged>,
+}
+
+impl ConfigReloader {
+    pub fn new(initial: AppConfig, path: Option<PathBuf>, cli: CliArgs) -> Self {
+        let (tx, _) = broadcast::channel(16);
+        Self {
+            path,
+            cli,
+            current: RwLock::new(Arc::new(initial)),
+            version: AtomicU64::new(0),
+            tx,
+        }
+    }
+
+    pub fn current(&self) -> Arc<AppConfig> {
+        self.current.read().unwrap().clone()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ConfigChanged> {
+        self.tx.subscribe()
+    }
+
+    /// Re-read and apply; the live config is untouched unless the whole candidate validates
+    pub fn reload(&self) -> Result<Option<ConfigChanged>> {
+        let candidate = AppConfig::load_with(self.path.as_deref(), &self.cli)?;
+        self.apply(candidate)
+    }
+
+    /// Merge the tunable sections of a validated candidate into the live config
+    pub fn apply(&self, candidate: AppConfig) -> Result<Option<ConfigChanged>> {
+        candidate.validate()?;
+
+        let mut current = self.current.write().unwrap();
+        let mut next = (**current).clone();
+        let mut sections = Vec::new();
+
+        if candidate.risk != next.risk {
+            next.risk = candidate.risk.clone();
+            sections.push("risk");
+        }
+        if candidate.patterns != next.patterns {
+            next.patterns = candidate.patterns.clone();
+            sections.push("patterns");
+        }
+        if candidate.worker != next.worker {
+            next.worker = candidate.worker.clone();
+            sections.push("worker");
+        }
+        if candidate.dashboard != next.dashboard {
+            next.dashboard = candidate.dashboard.clone();
+            sections.push("dashboard");
+        }
+        if candidate.allocator != next.allocator {
+            next.allocator = candidate.allocator.clone();
+            sections.push("allocator");
+        }
+        if candidate.accounts != next.accounts {
+            next.accounts = candidate.accounts.clone();
+            sections.push("accounts");
+        }
+        if candidate.market_making != next.market_making {
+            next.market_making = candidate.market_making.clone();
+            sections.push("market_making");
+        }
+        if candidate.logging != next.logging {
+            next.logging = candidate.logging.clone();
+            sections.push("logging");
+        }
+        if candidate.features != next.features {
+            next.features = candidate.features.clone();
+            sections.push("features");
+        }
+        if candidate.maintenance != next.maintenance {
+            next.maintenance = candidate.maintenance.clone();
+            sections.push("maintenance");
+        }
+        if candidate.sizing != next.sizing {
+            next.sizing = candidate.sizing.clone();
+            sections.push("sizing");
+        }
+
+        let mut fixed = Vec::new();
+        if candidate.execution != next.execution { fixed.push("execution"); }
+        if candidate.feeds != next.feeds { fixed.push("feeds"); }
+        if candidate.backtester != next.backtester { fixed.push("backtester"); }
+        // Stores, clients and monitors built once at startup
+        let startup = [
+            ("redis", candidate.redis != next.redis),
+            ("scheduler", candidate.scheduler != next.scheduler),
+            ("breaker", candidate.breaker != next.breaker),
+            ("clock_sync", candidate.clock_sync != next.clock_sync),
+            ("decision_latency", candidate.decision_latency != next.decision_latency),
+            ("sla", candidate.sla != next.sla),
+            ("settlement", candidate.settlement != next.settlement),
+            ("supervisor", candidate.supervisor != next.supervisor),
+            ("paper_fills", candidate.paper_fills != next.paper_fills),
+            ("journal", candidate.journal != next.journal),
+            ("audit", candidate.audit != next.audit),
+            ("blotter", candidate.blotter != next.blotter),
+            ("tca", candidate.tca != next.tca),
+            ("odds_capture", candidate.odds_capture != next.odds_capture),
+            ("tick_store", candidate.tick_store != next.tick_store),
+            ("backtest_jobs", candidate.backtest_jobs != next.backtest_jobs),
+            ("backtest_results", candidate.backtest_results != next.backtest_results),
+            ("studies", candidate.studies != next.studies),
+        ];
+        fixed.extend(startup.iter().filter(|(_, changed)| *changed).map(|(section, _)| *section));
+        if !fixed.is_empty() {
+            warn!("[CONFIG] Ignoring changes to {:?} (restart required)", fixed);
+        }
+
+        if sections.is_empty() {
+            return Ok(None);
+        }
+
+        let config = Arc::new(next);
+        *current = config.clone();
+        drop(current);
+
+        let version = self.version.fetch_add(1, Ordering::SeqCst) + 1;
+        info!("[CONFIG] Applied v{}: {:?}", version, sections);
+        let event = ConfigChanged { version, sections, config };
+        let _ = self.tx.send(event.clone());
+        Ok(Some(event))
+    }
+
+    fn mtime(&self) -> Option<SystemTime> {
+        let path = self.path.as_ref()?;
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn reload_logged(&self, trigger: &str) {
+        match self.reload() {
+            Ok(Some(_)) => {}
+            Ok(None) => info!("[CONFIG] {} reload: no tunable changes", trigger),
+            Err(e) => warn!("[CONFIG] {} reload rejected, keeping current config: {:#}", trigger, e),
+        }
+    }
+
+    /// Watch for SIGHUP and config file modifications (polled every `poll`)
+    pub async fn run(self: Arc<Self>, poll: Duration) {
+        let mut last_mtime = self.mtime();
+        let mut hangup = hangup_signal();
+        let mut interval = tokio::time::interval(poll);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = recv_hangup(&mut hangup) => self.reload_logged("SIGHUP"),
+                _ = interval.tick() => {
+                    let mtime = self.mtime();
+                    if mtime != last_mtime {
+                        last_mtime = mtime;
+                        self.reload_logged("file change");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+type Hangup = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type Hangup = ();
+
+#[cfg(unix)]
+fn hangup_signal() -> Hangup {
+    use tokio::signal::unix::{signal, SignalKind};
+    signal(SignalKind::hangup())
+        .map_err(|e| warn!("[CONFIG] SIGHUP handler unavailable: {}", e))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn hangup_signal() -> Hangup {}
+
+#[cfg(unix)]
+async fn recv_hangup(hangup: &mut Hangup) {
+    match hangup {
+        Some(sig) => {
+            sig.recv().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn recv_hangup(_: &mut Hangup) {
+    std::future::pending().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_tunable_and_reject_invalid() {
+        let reloader = ConfigReloader::new(AppConfig::default(), None, CliArgs::default());
+        let mut rx = reloader.subscribe();
+
+        let mut candidate = AppConfig::default();
+        candidate.risk.max_daily_loss = 200.0;
+        candidate.execution.dry_run = false; // not tunable
+        let event = reloader.apply(candidate).unwrap().unwrap();
+        assert_eq!(event.version, 1);
+        assert_eq!(event.sections, vec!["risk"]);
+        assert_eq!(rx.try_recv().unwrap().config.risk.max_daily_loss, 200.0);
+        assert!(reloader.current().execution.dry_run);
+
+        // Invalid candidate leaves everything as it was
+        let mut bad = (*reloader.current()).clone();
+        bad.patterns.min_gap_threshold = 1.0;
+        bad.worker.trigger_threshold = 2.0;
+        assert!(reloader.apply(bad).is_err());
+        assert_eq!(reloader.current().patterns.min_gap_threshold, 0.5);
+        assert!(rx.try_recv().is_err());
+
+        // No-op reload publishes nothing
+        assert!(reloader.apply((*reloader.current()).clone()).unwrap().is_none());
+    }
+}
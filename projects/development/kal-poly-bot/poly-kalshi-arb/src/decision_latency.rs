@@ -0,0 +1,344 @@
+// src/decision_latency.rs
+// Detection-to-order latency per pattern - log-bucketed (HDR-style) histograms checked against a
+// p99 SLA per market tier; Tier 1 compliance feeds the SLA degradation policy
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::DecisionLatencySection;
+
+/// Values below 2^7 µs are exact, above that each doubling is split into 64 buckets (<1.6% error)
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKET_HALF: u64 = 1 << (SUB_BUCKET_BITS - 1);
+/// Latencies above this are counted as this (one minute)
+const MAX_TRACKABLE_US: u64 = 60_000_000;
+
+fn bucket_of(us: u64) -> usize {
+    let us = us.min(MAX_TRACKABLE_US);
+    if us < 2 * SUB_BUCKET_HALF {
+        return us as usize;
+    }
+    // `us >> shift` lands in [64, 128)
+    let shift = 64 - us.leading_zeros() - SUB_BUCKET_BITS;
+    (2 * SUB_BUCKET_HALF + (u64::from(shift) - 1) * SUB_BUCKET_HALF + ((us >> shift) - SUB_BUCKET_HALF)) as usize
+}
+
+/// Highest latency (µs) counted in a bucket
+fn bucket_high(index: usize) -> u64 {
+    let index = index as u64;
+    if index < 2 * SUB_BUCKET_HALF {
+        return index;
+    }
+    let k = index - 2 * SUB_BUCKET_HALF;
+    let shift = k / SUB_BUCKET_HALF + 1;
+    ((k % SUB_BUCKET_HALF + SUB_BUCKET_HALF + 1) << shift) - 1
+}
+
+/// Latency histogram in microseconds with ~2 significant digits of precision
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    total: u64,
+    sum_us: u64,
+    max_us: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self { counts: vec![0; bucket_of(MAX_TRACKABLE_US) + 1], total: 0, sum_us: 0, max_us: 0 }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_ns(&mut self, latency_ns: u64) {
+        let us = (latency_ns / 1_000).min(MAX_TRACKABLE_US);
+        self.counts[bucket_of(us)] += 1;
+        self.total += 1;
+        self.sum_us += us;
+        self.max_us = self.max_us.max(us);
+    }
+
+    pub fn len(&self) -> u64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn max_us(&self) -> u64 {
+        self.max_us
+    }
+
+    pub fn mean_us(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.sum_us as f64 / self.total as f64 }
+    }
+
+    /// Latency at quantile `q` (0..=1), reported as the top of its bucket; 0 when empty
+    pub fn quantile_us(&self, q: f64) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * self.total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_high(index).min(self.max_us);
+            }
+        }
+        self.max_us
+    }
+
+    /// Samples in buckets at or below the one holding `us`
+    pub fn count_at_or_below(&self, us: u64) -> u64 {
+        self.counts[..=bucket_of(us)].iter().sum()
+    }
+
+    pub fn add(&mut self, other: &LatencyHistogram) {
+        for (count, added) in self.counts.iter_mut().zip(&other.counts) {
+            *count += added;
+        }
+        self.total += other.total;
+        self.sum_us += other.sum_us;
+        self.max_us = self.max_us.max(other.max_us);
+    }
+}
+
+/// p99 detection-to-order targets per market tier
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionLatencyConfig {
+    /// p99 SLA (ms) of Tier 1..4
+    pub tier_p99_ms: [f64; 4],
+    /// Recent samples a pattern (or tier) needs before it is judged
+    pub min_samples: u64,
+}
+
+impl Default for DecisionLatencyConfig {
+    fn default() -> Self {
+        Self::from(&DecisionLatencySection::default())
+    }
+}
+
+impl From<&DecisionLatencySection> for DecisionLatencyConfig {
+    fn from(d: &DecisionLatencySection) -> Self {
+        Self { tier_p99_ms: d.tier_p99_ms, min_samples: d.min_samples }
+    }
+}
+
+impl DecisionLatencyConfig {
+    /// SLA of a market tier (1..4; anything else is held to Tier 4)
+    pub fn sla_ms(&self, tier: u8) -> f64 {
+        self.tier_p99_ms[usize::from(tier.clamp(1, 4)) - 1]
+    }
+}
+
+/// Decision-path latency of one pattern, for the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternLatency {
+    pub pattern: String,
+    pub tier: u8,
+    pub sla_ms: f64,
+    /// Samples since start
+    pub samples: u64,
+    pub p50_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// p99 over the recent window (the last two SLA evaluations)
+    pub recent_p99_ms: f64,
+    /// Share of recent samples within the SLA
+    pub recent_compliance: f64,
+    /// Recent p99 over the SLA with at least `min_samples` recent samples
+    pub breached: bool,
+}
+
+#[derive(Debug, Default)]
+struct PatternWindow {
+    tier: u8,
+    total: LatencyHistogram,
+    current: LatencyHistogram,
+    previous: LatencyHistogram,
+    breached: bool,
+}
+
+impl PatternWindow {
+    fn recent(&self) -> LatencyHistogram {
+        let mut recent = self.current.clone();
+        recent.add(&self.previous);
+        recent
+    }
+}
+
+/// Detection-to-order histograms per pattern; the recent window rolls on each `rotate`
+/// (the SLA monitor's evaluations or `run_decision_windows`), the totals never reset
+pub struct DecisionLatencyTracker {
+    config: DecisionLatencyConfig,
+    patterns: Mutex<BTreeMap<String, PatternWindow>>,
+}
+
+pub type SharedDecisionLatency = Arc<DecisionLatencyTracker>;
+
+fn ms(us: u64) -> f64 {
+    us as f64 / 1_000.0
+}
+
+impl DecisionLatencyTracker {
+    pub fn new(config: DecisionLatencyConfig) -> Self {
+        Self { config, patterns: Mutex::new(BTreeMap::new()) }
+    }
+
+    pub fn config(&self) -> &DecisionLatencyConfig {
+        &self.config
+    }
+
+    /// Record one order sent `latency_ns` after its signal was detected
+    pub fn record(&self, pattern: &str, tier: u8, latency_ns: u64) {
+        let mut patterns = self.patterns.lock().unwrap();
+        // Keyed lookup first so the hot path doesn't allocate the key
+        if !patterns.contains_key(pattern) {
+            patterns.insert(pattern.to_string(), PatternWindow::default());
+        }
+        if let Some(window) = patterns.get_mut(pattern) {
+            window.tier = tier;
+            window.total.record_ns(latency_ns);
+            window.current.record_ns(latency_ns);
+        }
+    }
+
+    /// Share of a tier's recent samples within its SLA; None below `min_samples`
+    pub fn tier_compliance(&self, tier: u8) -> Option<f64> {
+        let sla_us = (self.config.sla_ms(tier) * 1_000.0) as u64;
+        let patterns = self.patterns.lock().unwrap();
+        let (within, total) = patterns.values()
+            .filter(|window| window.tier == tier)
+            .fold((0, 0), |(within, total), window| {
+                let recent = window.recent();
+                (within + recent.count_at_or_below(sla_us), total + recent.len())
+            });
+        (total >= self.config.min_samples.max(1)).then(|| within as f64 / total as f64)
+    }
+
+    /// Close the current window: flag patterns whose recent p99 crossed their SLA either way
+    pub fn rotate(&self) {
+        let mut patterns = self.patterns.lock().unwrap();
+        for (pattern, window) in patterns.iter_mut() {
+            let sla_ms = self.config.sla_ms(window.tier);
+            let recent = window.recent();
+            if recent.len() >= self.config.min_samples.max(1) {
+                let p99_ms = ms(recent.quantile_us(0.99));
+                let breached = p99_ms > sla_ms;
+                if breached && !window.breached {
+                    warn!("[DECISION] Pattern {} p99 {:.1}ms over its Tier {} SLA of {:.0}ms", pattern, p99_ms, window.tier, sla_ms);
+                } else if !breached && window.breached {
+                    info!("[DECISION] Pattern {} p99 {:.1}ms back within its Tier {} SLA", pattern, p99_ms, window.tier);
+                }
+                window.breached = breached;
+            }
+            window.previous = std::mem::take(&mut window.current);
+        }
+    }
+
+    pub fn summary(&self) -> Vec<PatternLatency> {
+        let patterns = self.patterns.lock().unwrap();
+        patterns.iter()
+            .map(|(pattern, window)| {
+                let sla_ms = self.config.sla_ms(window.tier);
+                let recent = window.recent();
+                let within = recent.count_at_or_below((sla_ms * 1_000.0) as u64);
+                PatternLatency {
+                    pattern: pattern.clone(),
+                    tier: window.tier,
+                    sla_ms,
+                    samples: window.total.len(),
+                    p50_ms: ms(window.total.quantile_us(0.5)),
+                    p99_ms: ms(window.total.quantile_us(0.99)),
+                    max_ms: ms(window.total.max_us()),
+                    recent_p99_ms: ms(recent.quantile_us(0.99)),
+                    recent_compliance: if recent.is_empty() { 1.0 } else { within as f64 / recent.len() as f64 },
+                    breached: window.breached,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Roll the recent window every `interval` (when no SLA monitor evaluates the tracker)
+pub async fn run_decision_windows(tracker: SharedDecisionLatency, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        tracker.rotate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1_000_000;
+
+    #[test]
+    fn test_histogram_quantiles_within_bucket_precision() {
+        assert_eq!(bucket_high(bucket_of(127)), 127);
+        for us in [128, 255, 256, 1_000, 123_456, MAX_TRACKABLE_US] {
+            let high = bucket_high(bucket_of(us));
+            assert!(high >= us && (high - us) as f64 <= us as f64 / 64.0, "{} -> {}", us, high);
+        }
+
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.quantile_us(0.99), 0);
+        for i in 1..=1000 {
+            histogram.record_ns(i * 10_000); // 10us..10ms
+        }
+        let p50 = histogram.quantile_us(0.5) as f64;
+        let p99 = histogram.quantile_us(0.99) as f64;
+        assert!((p50 - 5_000.0).abs() / 5_000.0 < 0.02, "p50 {}", p50);
+        assert!((p99 - 9_900.0).abs() / 9_900.0 < 0.02, "p99 {}", p99);
+        assert_eq!(histogram.quantile_us(1.0), 10_000);
+        assert!((histogram.mean_us() - 5_005.0).abs() < 1e-9);
+        assert_eq!(histogram.count_at_or_below(100), 10);
+    }
+
+    #[test]
+    fn test_creep_flags_only_the_slow_pattern() {
+        let tracker = DecisionLatencyTracker::new(DecisionLatencyConfig { min_samples: 10, ..DecisionLatencyConfig::default() });
+        for _ in 0..50 {
+            tracker.record("PolyYesKalshiNo", 1, 2 * MS);
+            tracker.record("75", 1, 2 * MS);
+            tracker.record("73", 3, 400 * MS); // within Tier 3's 500ms
+        }
+        tracker.rotate();
+        assert_eq!(tracker.tier_compliance(1), Some(1.0));
+        assert!(tracker.summary().iter().all(|p| !p.breached));
+
+        // Pattern 75 creeps past Tier 1's 100ms while the arb path stays fast
+        for _ in 0..50 {
+            tracker.record("PolyYesKalshiNo", 1, 2 * MS);
+            tracker.record("75", 1, 150 * MS);
+        }
+        tracker.rotate();
+        let summary = tracker.summary();
+        let by = |p: &str| summary.iter().find(|s| s.pattern == p).unwrap().clone();
+        assert!(by("75").breached && !by("PolyYesKalshiNo").breached && !by("73").breached);
+        assert_eq!(by("75").samples, 100);
+        assert_eq!(by("75").recent_compliance, 0.0);
+        assert!((tracker.tier_compliance(1).unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(by("73").sla_ms, 500.0);
+
+        // Two clean windows clear the breach
+        for _ in 0..2 {
+            for _ in 0..20 {
+                tracker.record("75", 1, 2 * MS);
+            }
+            tracker.rotate();
+        }
+        assert!(tracker.summary().iter().all(|p| !p.breached));
+        assert_eq!(tracker.tier_compliance(4), None);
+    }
+}
@@ -1,21 +1,24 @@
 // src/discovery.rs
 // Market discovery - matches Kalshi events to Polymarket markets
+// plus periodic cross-venue catalog matching into the MarketIdRegistry
 
 use anyhow::Result;
+use futures_util::future::BoxFuture;
 use futures_util::{stream, StreamExt};
 use governor::{Quota, RateLimiter, state::NotKeyed, clock::DefaultClock, middleware::NoOpMiddleware};
 use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU32;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::sync::Semaphore;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{RwLock, Semaphore};
 use tracing::{info, warn};
 
-use crate::cache::TeamCache;
+use crate::cache::{CacheBackend, CacheNamespace, TeamCache, TieredCache};
 use crate::config::{LeagueConfig, get_league_configs, get_league_config};
 use crate::kalshi::KalshiApiClient;
 use crate::polymarket::GammaClient;
-use crate::types::{MarketPair, MarketType, DiscoveryResult, KalshiMarket, KalshiEvent};
+use crate::types::{MarketPair, MarketType, DiscoveryResult, KalshiMarket, KalshiEvent, Platform};
 
 /// Max concurrent Gamma API requests
 const GAMMA_CONCURRENCY: usize = 20;
@@ -83,6 +86,14 @@ impl DiscoveryCache {
     }
 }
 
+/// Pairs from the last saved discovery cache, stale or not (empty when there is none)
+pub fn cached_pairs() -> Vec<MarketPair> {
+    std::fs::read_to_string(DISCOVERY_CACHE_PATH).ok()
+        .and_then(|data| serde_json::from_str::<DiscoveryCache>(&data).ok())
+        .map(|cache| cache.pairs)
+        .unwrap_or_default()
+}
+
 fn current_unix_secs() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -98,6 +109,8 @@ pub struct DiscoveryClient {
     kalshi_limiter: Arc<KalshiRateLimiter>,
     kalshi_semaphore: Arc<Semaphore>,  // Global concurrency limit for Kalshi
     gamma_semaphore: Arc<Semaphore>,
+    /// Gamma slug -> (yes_token, no_token)
+    slug_tokens: Arc<TieredCache<(String, String)>>,
 }
 
 impl DiscoveryClient {
@@ -113,9 +126,16 @@ impl DiscoveryClient {
             kalshi_limiter,
             kalshi_semaphore: Arc::new(Semaphore::new(KALSHI_GLOBAL_CONCURRENCY)),
             gamma_semaphore: Arc::new(Semaphore::new(GAMMA_CONCURRENCY)),
+            slug_tokens: Arc::new(TieredCache::new(CacheNamespace::DiscoveryMetadata)),
         }
     }
 
+    /// Share slug lookups with other processes through `backend` (e.g. Redis)
+    pub fn with_cache_backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.slug_tokens = Arc::new(TieredCache::new(CacheNamespace::DiscoveryMetadata).with_backend(backend));
+        self
+    }
+
     /// Load cache from disk (async)
     async fn load_cache() -> Option<DiscoveryCache> {
         let data = tokio::fs::read_to_string(DISCOVERY_CACHE_PATH).await.ok()?;
@@ -143,7 +163,7 @@ impl DiscoveryClient {
         match cached {
             Some(cache) if !cache.is_expired() => {
                 // Cache is fresh - use it directly
-                info!("📂 Loaded {} pairs from cache (age: {}s)",
+                info!("[DISCOVERY] Loaded {} pairs from cache (age: {}s)",
                       cache.pairs.len(), cache.age_secs());
                 return DiscoveryResult {
                     pairs: cache.pairs,
@@ -155,12 +175,12 @@ impl DiscoveryClient {
             }
             Some(cache) => {
                 // Cache is stale - do incremental discovery
-                info!("📂 Cache expired (age: {}s), doing incremental refresh...", cache.age_secs());
+                info!("[DISCOVERY] Cache expired (age: {}s), doing incremental refresh...", cache.age_secs());
                 return self.discover_incremental(leagues, cache).await;
             }
             None => {
                 // No cache - do full discovery
-                info!("📂 No cache found, doing full discovery...");
+                info!("[DISCOVERY] No cache found, doing full discovery...");
             }
         }
 
@@ -171,9 +191,9 @@ impl DiscoveryClient {
         if !result.pairs.is_empty() {
             let cache = DiscoveryCache::new(result.pairs.clone());
             if let Err(e) = Self::save_cache(&cache).await {
-                warn!("Failed to save discovery cache: {}", e);
+                warn!("[DISCOVERY] Failed to save discovery cache: {}", e);
             } else {
-                info!("💾 Saved {} pairs to cache", result.pairs.len());
+                info!("[DISCOVERY] Saved {} pairs to cache", result.pairs.len());
             }
         }
 
@@ -182,16 +202,16 @@ impl DiscoveryClient {
 
     /// Force full discovery (ignores cache)
     pub async fn discover_all_force(&self, leagues: &[&str]) -> DiscoveryResult {
-        info!("🔄 Forced full discovery (ignoring cache)...");
+        info!("[DISCOVERY] Forced full discovery (ignoring cache)...");
         let result = self.discover_full(leagues).await;
 
         // Save to cache
         if !result.pairs.is_empty() {
             let cache = DiscoveryCache::new(result.pairs.clone());
             if let Err(e) = Self::save_cache(&cache).await {
-                warn!("Failed to save discovery cache: {}", e);
+                warn!("[DISCOVERY] Failed to save discovery cache: {}", e);
             } else {
-                info!("💾 Saved {} pairs to cache", result.pairs.len());
+                info!("[DISCOVERY] Saved {} pairs to cache", result.pairs.len());
             }
         }
 
@@ -258,17 +278,17 @@ impl DiscoveryClient {
         }
 
         if new_count > 0 {
-            info!("🆕 Found {} new market pairs", new_count);
+            info!("[DISCOVERY] Found {} new market pairs", new_count);
 
             // Update cache
             let new_cache = DiscoveryCache::new(all_pairs.clone());
             if let Err(e) = Self::save_cache(&new_cache).await {
-                warn!("Failed to update discovery cache: {}", e);
+                warn!("[DISCOVERY] Failed to update discovery cache: {}", e);
             } else {
-                info!("💾 Updated cache with {} total pairs", all_pairs.len());
+                info!("[DISCOVERY] Updated cache with {} total pairs", all_pairs.len());
             }
         } else {
-            info!("✅ No new markets found, using {} cached pairs", all_pairs.len());
+            info!("[DISCOVERY] No new markets found, using {} cached pairs", all_pairs.len());
 
             // Just update timestamp to extend TTL
             let refreshed_cache = DiscoveryCache::new(all_pairs.clone());
@@ -287,7 +307,7 @@ impl DiscoveryClient {
     /// Discover all market types for a single league (PARALLEL)
     /// If cache is provided, only discovers markets not already in cache
     async fn discover_league(&self, config: &LeagueConfig, cache: Option<&DiscoveryCache>) -> DiscoveryResult {
-        info!("🔍 Discovering {} markets...", config.league_code);
+        info!("[DISCOVERY] Discovering {} markets...", config.league_code);
 
         let market_types = [MarketType::Moneyline, MarketType::Spread, MarketType::Total, MarketType::Btts];
 
@@ -307,7 +327,7 @@ impl DiscoveryClient {
                 Ok(pairs) => {
                     let count = pairs.len();
                     if count > 0 {
-                        info!("  ✅ {} {}: {} pairs", config.league_code, market_type, count);
+                        info!("[DISCOVERY] {} {}: {} pairs", config.league_code, market_type, count);
                     }
                     result.poly_matches += count;
                     result.pairs.extend(pairs);
@@ -327,6 +347,7 @@ impl DiscoveryClient {
             MarketType::Spread => config.kalshi_series_spread,
             MarketType::Total => config.kalshi_series_total,
             MarketType::Btts => config.kalshi_series_btts,
+            _ => None,
         }
     }
     
@@ -357,7 +378,7 @@ impl DiscoveryClient {
                 let parsed = match parse_kalshi_event_ticker(&event.event_ticker) {
                     Some(p) => p,
                     None => {
-                        warn!("  ⚠️ Could not parse event ticker {}", event.event_ticker);
+                        warn!("[DISCOVERY] Could not parse event ticker {}", event.event_ticker);
                         return None;
                     }
                 };
@@ -400,7 +421,7 @@ impl DiscoveryClient {
                     }
                 }
                 Err(e) => {
-                    warn!("  ⚠️ Failed to get markets for {}: {}", event.event_ticker, e);
+                    warn!("[DISCOVERY] Failed to get markets for {}: {}", event.event_ticker, e);
                 }
             }
         }
@@ -426,9 +447,21 @@ impl DiscoveryClient {
             .map(|task| {
                 let gamma = self.gamma.clone();
                 let semaphore = self.gamma_semaphore.clone();
+                let slug_tokens = self.slug_tokens.clone();
                 async move {
-                    let _permit = semaphore.acquire().await.ok()?;
-                    match gamma.lookup_market(&task.poly_slug).await {
+                    let lookup = match slug_tokens.get(&task.poly_slug).await {
+                        Some(tokens) => Ok(Some(tokens)),
+                        None => {
+                            let _permit = semaphore.acquire().await.ok()?;
+                            let result = gamma.lookup_market(&task.poly_slug).await;
+                            // Only cache hits; unlisted slugs may appear on the next pass
+                            if let Ok(Some(tokens)) = &result {
+                                slug_tokens.insert(&task.poly_slug, tokens.clone()).await;
+                            }
+                            result
+                        }
+                    };
+                    match lookup {
                         Ok(Some((yes_token, no_token))) => {
                             let team_suffix = extract_team_suffix(&task.market.ticker);
                             Some(MarketPair {
@@ -447,7 +480,7 @@ impl DiscoveryClient {
                         }
                         Ok(None) => None,
                         Err(e) => {
-                            warn!("  ⚠️ Gamma lookup failed for {}: {}", task.poly_slug, e);
+                            warn!("[DISCOVERY] Gamma lookup failed for {}: {}", task.poly_slug, e);
                             None
                         }
                     }
@@ -517,8 +550,576 @@ impl DiscoveryClient {
             MarketType::Btts => {
                 format!("{}-btts", base)
             }
+            other => format!("{}-{}", base, other),
+        }
+    }
+}
+
+// === Cross-Venue Catalog Matching ===
+
+/// Links at or above this score are registered automatically
+const AUTO_LINK_CONFIDENCE: f64 = 0.85;
+
+/// Links between this and AUTO_LINK_CONFIDENCE go to manual review
+const REVIEW_CONFIDENCE: f64 = 0.60;
+
+/// Close times further apart than this cannot be the same event
+const MAX_CLOSE_TIME_SKEW_SECS: i64 = 36 * 60 * 60;
+
+/// Gamma catalog page size
+const GAMMA_PAGE_SIZE: usize = 500;
+
+/// Registry file path
+const MARKET_REGISTRY_PATH: &str = ".market_registry.json";
+
+/// Venue-agnostic market descriptor used for equivalence matching
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub platform: Platform,
+    /// Venue-native market ID (Kalshi ticker, Poly condition ID, book market ID)
+    pub venue_market_id: String,
+    pub title: String,
+    /// Normalized team names/codes (lowercase alphanumerics)
+    pub teams: Vec<String>,
+    /// Outcome the YES side pays on (team, "draw", "over", ...)
+    pub outcome: Option<String>,
+    pub line: Option<f64>,
+    pub market_type: MarketType,
+    /// Unix seconds
+    pub close_time: Option<i64>,
+}
+
+impl CatalogEntry {
+    pub fn new(platform: Platform, venue_market_id: &str, title: &str, market_type: MarketType) -> Self {
+        Self {
+            platform,
+            venue_market_id: venue_market_id.to_string(),
+            title: title.to_string(),
+            teams: extract_teams(title),
+            outcome: None,
+            line: None,
+            market_type,
+            close_time: None,
+        }
+    }
+}
+
+/// Source of market catalogs (one per venue / sportsbook adapter)
+pub trait CatalogSource: Send + Sync {
+    fn platform(&self) -> Platform;
+    fn fetch_catalog(&self) -> BoxFuture<'_, Result<Vec<CatalogEntry>>>;
+}
+
+/// Kalshi catalog: every open market in the configured league series
+pub struct KalshiCatalogSource {
+    kalshi: Arc<KalshiApiClient>,
+    leagues: Vec<LeagueConfig>,
+}
+
+impl KalshiCatalogSource {
+    pub fn new(kalshi: Arc<KalshiApiClient>, leagues: Vec<LeagueConfig>) -> Self {
+        Self { kalshi, leagues }
+    }
+
+    async fn fetch(&self) -> Result<Vec<CatalogEntry>> {
+        let mut out = Vec::new();
+        for config in &self.leagues {
+            let series_by_type = [
+                (MarketType::Moneyline, Some(config.kalshi_series_game)),
+                (MarketType::Spread, config.kalshi_series_spread),
+                (MarketType::Total, config.kalshi_series_total),
+                (MarketType::Btts, config.kalshi_series_btts),
+            ];
+            for (market_type, series) in series_by_type {
+                let Some(series) = series else { continue };
+                let events = match self.kalshi.get_events(series, 50).await {
+                    Ok(e) => e,
+                    Err(e) => {
+                        warn!("[CATALOG] Kalshi {} events failed: {}", series, e);
+                        continue;
+                    }
+                };
+                for event in events {
+                    let markets = match self.kalshi.get_markets(&event.event_ticker).await {
+                        Ok(m) => m,
+                        Err(e) => {
+                            warn!("[CATALOG] Kalshi {} markets failed: {}", event.event_ticker, e);
+                            continue;
+                        }
+                    };
+                    for market in markets {
+                        let mut entry = CatalogEntry::new(Platform::Kalshi, &market.ticker, &event.title, market_type);
+                        entry.outcome = market.yes_sub_title.as_deref()
+                            .map(normalize_token)
+                            .or_else(|| extract_team_suffix(&market.ticker).map(|s| normalize_token(&s)));
+                        entry.line = market.floor_strike;
+                        entry.close_time = market.close_time.as_deref().and_then(parse_rfc3339_secs);
+                        out.push(entry);
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl CatalogSource for KalshiCatalogSource {
+    fn platform(&self) -> Platform {
+        Platform::Kalshi
+    }
+
+    fn fetch_catalog(&self) -> BoxFuture<'_, Result<Vec<CatalogEntry>>> {
+        Box::pin(self.fetch())
+    }
+}
+
+/// Polymarket catalog: all active Gamma markets
+pub struct PolymarketCatalogSource {
+    gamma: Arc<GammaClient>,
+    max_pages: usize,
+}
+
+impl PolymarketCatalogSource {
+    pub fn new(gamma: Arc<GammaClient>, max_pages: usize) -> Self {
+        Self { gamma, max_pages }
+    }
+
+    async fn fetch(&self) -> Result<Vec<CatalogEntry>> {
+        let mut out = Vec::new();
+        for page in 0..self.max_pages {
+            let markets = self.gamma.list_active_markets(page * GAMMA_PAGE_SIZE, GAMMA_PAGE_SIZE).await?;
+            let done = markets.len() < GAMMA_PAGE_SIZE;
+            for market in markets {
+                let (Some(question), Some(condition_id)) = (market.question.as_deref(), market.condition_id.as_deref()) else {
+                    continue;
+                };
+                let market_type = infer_market_type(question);
+                let mut entry = CatalogEntry::new(Platform::Polymarket, condition_id, question, market_type);
+                entry.line = extract_line(question);
+                entry.outcome = market.outcomes.as_deref()
+                    .and_then(|o| serde_json::from_str::<Vec<String>>(o).ok())
+                    .and_then(|o| o.into_iter().next())
+                    .map(|o| normalize_token(&o))
+                    .filter(|o| o != "yes");
+                entry.close_time = market.end_date.as_deref().and_then(parse_rfc3339_secs);
+                out.push(entry);
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl CatalogSource for PolymarketCatalogSource {
+    fn platform(&self) -> Platform {
+        Platform::Polymarket
+    }
+
+    fn fetch_catalog(&self) -> BoxFuture<'_, Result<Vec<CatalogEntry>>> {
+        Box::pin(self.fetch())
+    }
+}
+
+/// How a link entered the registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkSource {
+    Auto,
+    ManualReview,
+}
+
+/// Equivalent markets across venues (same event / outcome / line)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketLink {
+    pub link_id: String,
+    pub market_type: MarketType,
+    pub line: Option<f64>,
+    /// (venue, venue market ID) for every linked leg
+    pub legs: Vec<(Platform, String)>,
+    pub confidence: f64,
+    pub source: LinkSource,
+    pub created_at_secs: u64,
+}
+
+impl MarketLink {
+    pub fn leg(&self, platform: Platform) -> Option<&str> {
+        self.legs.iter().find(|(p, _)| *p == platform).map(|(_, id)| id.as_str())
+    }
+}
+
+/// Canonical cross-venue market identity
+/// Every (venue, venue market ID) maps to at most one link
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MarketIdRegistry {
+    links: HashMap<String, MarketLink>,
+    #[serde(skip)]
+    by_leg: HashMap<(Platform, String), String>,
+}
+
+impl MarketIdRegistry {
+    pub fn load() -> Self {
+        let mut registry: Self = std::fs::read_to_string(MARKET_REGISTRY_PATH)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        registry.rebuild_index();
+        registry
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::write(MARKET_REGISTRY_PATH, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn rebuild_index(&mut self) {
+        self.by_leg.clear();
+        for link in self.links.values() {
+            for (platform, id) in &link.legs {
+                self.by_leg.insert((*platform, id.clone()), link.link_id.clone());
+            }
+        }
+    }
+
+    /// Insert or merge a link; legs already linked elsewhere are merged into that link
+    pub fn insert(&mut self, mut link: MarketLink) -> String {
+        let existing = link.legs.iter()
+            .find_map(|(p, id)| self.by_leg.get(&(*p, id.clone())).cloned());
+
+        if let Some(link_id) = existing {
+            let target = self.links.get_mut(&link_id).expect("index out of sync");
+            for leg in link.legs.drain(..) {
+                if target.leg(leg.0).is_none() {
+                    self.by_leg.insert((leg.0, leg.1.clone()), link_id.clone());
+                    target.legs.push(leg);
+                }
+            }
+            target.confidence = target.confidence.min(link.confidence);
+            return link_id;
+        }
+
+        for (platform, id) in &link.legs {
+            self.by_leg.insert((*platform, id.clone()), link.link_id.clone());
+        }
+        let link_id = link.link_id.clone();
+        self.links.insert(link_id.clone(), link);
+        link_id
+    }
+
+    pub fn link_for(&self, platform: Platform, venue_market_id: &str) -> Option<&MarketLink> {
+        let link_id = self.by_leg.get(&(platform, venue_market_id.to_string()))?;
+        self.links.get(link_id)
+    }
+
+    pub fn is_linked(&self, platform: Platform, venue_market_id: &str) -> bool {
+        self.by_leg.contains_key(&(platform, venue_market_id.to_string()))
+    }
+
+    pub fn links(&self) -> impl Iterator<Item = &MarketLink> {
+        self.links.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+}
+
+/// Low-confidence candidate awaiting an operator decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewItem {
+    pub anchor: CatalogEntry,
+    pub candidate: CatalogEntry,
+    pub score: MatchScore,
+}
+
+/// Manual-review queue for matches between REVIEW_CONFIDENCE and AUTO_LINK_CONFIDENCE
+#[derive(Debug, Default)]
+pub struct ReviewQueue {
+    items: Vec<ReviewItem>,
+    /// Pairs an operator rejected - never re-queued
+    rejected: HashSet<(String, String)>,
+}
+
+impl ReviewQueue {
+    fn key(item: &ReviewItem) -> (String, String) {
+        (item.anchor.venue_market_id.clone(), item.candidate.venue_market_id.clone())
+    }
+
+    /// Queue an item unless it is already pending or was rejected
+    pub fn push(&mut self, item: ReviewItem) -> bool {
+        let key = Self::key(&item);
+        if self.rejected.contains(&key) || self.items.iter().any(|i| Self::key(i) == key) {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+
+    pub fn pending(&self) -> &[ReviewItem] {
+        &self.items
+    }
+
+    /// Approve a pending item and register it
+    pub fn approve(&mut self, index: usize, registry: &mut MarketIdRegistry) -> Option<String> {
+        if index >= self.items.len() {
+            return None;
+        }
+        let item = self.items.remove(index);
+        let link = build_link(&item.anchor, &item.candidate, item.score.total, LinkSource::ManualReview);
+        Some(registry.insert(link))
+    }
+
+    /// Reject a pending item (remembered so it is not re-queued)
+    pub fn reject(&mut self, index: usize) -> bool {
+        if index >= self.items.len() {
+            return false;
+        }
+        let item = self.items.remove(index);
+        self.rejected.insert(Self::key(&item));
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Breakdown of an equivalence score (each component 0.0-1.0)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MatchScore {
+    pub teams: f64,
+    pub title: f64,
+    pub close_time: f64,
+    pub outcome: f64,
+    pub total: f64,
+}
+
+/// Score how likely two catalog entries describe the same market
+/// Hard constraints (type, line, close-time window) short-circuit to 0
+pub fn score_match(a: &CatalogEntry, b: &CatalogEntry) -> MatchScore {
+    if a.market_type != b.market_type {
+        return MatchScore::default();
+    }
+    match (a.line, b.line) {
+        (Some(x), Some(y)) if (x - y).abs() > 0.01 => return MatchScore::default(),
+        _ => {}
+    }
+
+    let close_time = match (a.close_time, b.close_time) {
+        (Some(x), Some(y)) => {
+            let skew = (x - y).abs();
+            if skew > MAX_CLOSE_TIME_SKEW_SECS {
+                return MatchScore::default();
+            }
+            1.0 - skew as f64 / MAX_CLOSE_TIME_SKEW_SECS as f64
+        }
+        _ => 0.5,
+    };
+
+    let teams = jaccard(&a.teams, &b.teams);
+    let title = bigram_dice(&normalize_title(&a.title), &normalize_title(&b.title));
+    let outcome = match (&a.outcome, &b.outcome) {
+        (Some(x), Some(y)) if x == y || x.starts_with(y.as_str()) || y.starts_with(x.as_str()) => 1.0,
+        (Some(_), Some(_)) => 0.0,
+        _ => 0.5,
+    };
+
+    let total = 0.45 * teams + 0.25 * title + 0.20 * close_time + 0.10 * outcome;
+    MatchScore { teams, title, close_time, outcome, total }
+}
+
+fn build_link(anchor: &CatalogEntry, candidate: &CatalogEntry, confidence: f64, source: LinkSource) -> MarketLink {
+    MarketLink {
+        link_id: format!("{}:{}", anchor.market_type, anchor.venue_market_id),
+        market_type: anchor.market_type,
+        line: anchor.line.or(candidate.line),
+        legs: vec![
+            (anchor.platform, anchor.venue_market_id.clone()),
+            (candidate.platform, candidate.venue_market_id.clone()),
+        ],
+        confidence,
+        source,
+        created_at_secs: current_unix_secs(),
+    }
+}
+
+/// Outcome of one matching pass
+#[derive(Debug, Default)]
+pub struct MatchOutcome {
+    pub linked: usize,
+    pub queued: usize,
+    pub unmatched: usize,
+}
+
+/// Match every candidate against the anchor catalog (Kalshi), best score wins
+pub fn match_catalogs(
+    anchor: &[CatalogEntry],
+    candidates: &[CatalogEntry],
+    registry: &mut MarketIdRegistry,
+    review: &mut ReviewQueue,
+) -> MatchOutcome {
+    let mut by_type: HashMap<MarketType, Vec<&CatalogEntry>> = HashMap::new();
+    for entry in anchor {
+        by_type.entry(entry.market_type).or_default().push(entry);
+    }
+
+    let mut outcome = MatchOutcome::default();
+    for candidate in candidates {
+        if registry.is_linked(candidate.platform, &candidate.venue_market_id) {
+            continue;
+        }
+        let best = by_type.get(&candidate.market_type)
+            .into_iter()
+            .flatten()
+            .map(|a| (*a, score_match(a, candidate)))
+            .max_by(|x, y| x.1.total.total_cmp(&y.1.total));
+
+        match best {
+            Some((a, score)) if score.total >= AUTO_LINK_CONFIDENCE => {
+                registry.insert(build_link(a, candidate, score.total, LinkSource::Auto));
+                outcome.linked += 1;
+            }
+            Some((a, score)) if score.total >= REVIEW_CONFIDENCE => {
+                let queued = review.push(ReviewItem { anchor: a.clone(), candidate: candidate.clone(), score });
+                if queued {
+                    outcome.queued += 1;
+                }
+            }
+            _ => outcome.unmatched += 1,
+        }
+    }
+    outcome
+}
+
+/// Periodically refresh all catalogs and link equivalent markets
+/// The first source is the anchor every other venue is matched against
+pub async fn run_catalog_sync(
+    sources: Vec<Arc<dyn CatalogSource>>,
+    registry: Arc<RwLock<MarketIdRegistry>>,
+    review: Arc<RwLock<ReviewQueue>>,
+    every: Duration,
+) {
+    let mut interval = tokio::time::interval(every);
+    loop {
+        interval.tick().await;
+
+        let catalogs = futures_util::future::join_all(sources.iter().map(|s| s.fetch_catalog())).await;
+        let mut catalogs = sources.iter().zip(catalogs).filter_map(|(source, result)| match result {
+            Ok(entries) => Some(entries),
+            Err(e) => {
+                warn!("[CATALOG] {} fetch failed: {}", source.platform(), e);
+                None
+            }
+        });
+
+        let Some(anchor) = catalogs.next() else { continue };
+        let mut registry_guard = registry.write().await;
+        let mut review_guard = review.write().await;
+        for candidates in catalogs {
+            let outcome = match_catalogs(&anchor, &candidates, &mut registry_guard, &mut review_guard);
+            info!("[CATALOG] linked={} queued={} unmatched={} (registry={}, review={})",
+                  outcome.linked, outcome.queued, outcome.unmatched,
+                  registry_guard.len(), review_guard.len());
+        }
+        if let Err(e) = registry_guard.save() {
+            warn!("[CATALOG] Failed to save market registry: {}", e);
+        }
+    }
+}
+
+/// Lowercase alphanumerics only
+fn normalize_token(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+/// Lowercased title with filler words dropped
+fn normalize_title(title: &str) -> String {
+    const STOP: &[&str] = &["will", "the", "win", "vs", "v", "at", "beat", "on", "fc", "game", "match"];
+    title.split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| !w.is_empty() && !STOP.contains(&w.as_str()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Pull the two sides out of "A vs B" / "A @ B" / "A at B" style titles
+fn extract_teams(title: &str) -> Vec<String> {
+    let lower = title.to_lowercase();
+    let head = lower.split(['?', ':', '(']).next().unwrap_or("");
+    for sep in [" vs. ", " vs ", " v ", " @ ", " at "] {
+        if let Some((a, b)) = head.split_once(sep) {
+            let a = a.trim_start_matches("will ").trim();
+            let b = b.trim();
+            let mut teams = vec![normalize_token(a), normalize_token(b)];
+            teams.retain(|t| !t.is_empty());
+            teams.sort();
+            return teams;
+        }
+    }
+    Vec::new()
+}
+
+/// Guess market type from a free-text question
+fn infer_market_type(question: &str) -> MarketType {
+    let q = question.to_lowercase();
+    if q.contains("both teams to score") {
+        MarketType::Btts
+    } else if q.contains("spread") || q.contains("handicap") {
+        MarketType::Spread
+    } else if q.contains("o/u") || q.contains("over/under") || q.contains("total") {
+        MarketType::Total
+    } else {
+        MarketType::Moneyline
+    }
+}
+
+/// First decimal number in the text ("O/U 2.5" -> 2.5, "(-1.5)" -> -1.5)
+fn extract_line(text: &str) -> Option<f64> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .filter(|t| t.contains('.'))
+        .find_map(|t| t.trim_matches('.').parse::<f64>().ok())
+}
+
+fn parse_rfc3339_secs(s: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(s).ok().map(|d| d.timestamp())
+}
+
+fn jaccard(a: &[String], b: &[String]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let hits = a.iter().filter(|x| b.iter().any(|y| y == *x || y.contains(x.as_str()) || x.contains(y.as_str()))).count();
+    let union = a.len() + b.len() - hits;
+    hits as f64 / union as f64
+}
+
+/// Sørensen-Dice coefficient over character bigrams
+fn bigram_dice(a: &str, b: &str) -> f64 {
+    fn bigrams(s: &str) -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+    let (x, mut y) = (bigrams(a), bigrams(b));
+    if x.is_empty() || y.is_empty() {
+        return 0.0;
+    }
+    let total = x.len() + y.len();
+    let mut hits = 0;
+    for bg in &x {
+        if let Some(pos) = y.iter().position(|o| o == bg) {
+            y.swap_remove(pos);
+            hits += 1;
         }
     }
+    2.0 * hits as f64 / total as f64
 }
 
 // === Helpers ===
@@ -668,4 +1269,83 @@ mod tests {
         assert_eq!(kalshi_date_to_iso("25DEC27"), "2025-12-27");
         assert_eq!(kalshi_date_to_iso("25JAN01"), "2025-01-01");
     }
+
+    fn entry(platform: Platform, id: &str, title: &str, close: i64) -> CatalogEntry {
+        let mut e = CatalogEntry::new(platform, id, title, MarketType::Moneyline);
+        e.close_time = Some(close);
+        e
+    }
+
+    #[test]
+    fn test_extract_teams_and_line() {
+        assert_eq!(extract_teams("Chelsea vs Aston Villa"), vec!["astonvilla", "chelsea"]);
+        assert_eq!(extract_teams("Will Lakers @ Celtics: winner?"), vec!["celtics", "lakers"]);
+        assert_eq!(extract_line("Arsenal vs Spurs: O/U 2.5"), Some(2.5));
+        assert_eq!(infer_market_type("Arsenal vs Spurs: O/U 2.5"), MarketType::Total);
+    }
+
+    #[test]
+    fn test_score_match_hard_constraints() {
+        let a = entry(Platform::Kalshi, "K1", "Chelsea vs Aston Villa", 1_000_000);
+        let b = entry(Platform::Polymarket, "P1", "Chelsea vs. Aston Villa", 1_000_000 + 3600);
+        assert!(score_match(&a, &b).total >= AUTO_LINK_CONFIDENCE);
+
+        // Close times two days apart - different fixture
+        let far = entry(Platform::Polymarket, "P2", "Chelsea vs Aston Villa", 1_000_000 + 48 * 3600);
+        assert_eq!(score_match(&a, &far).total, 0.0);
+
+        // Different line
+        let mut la = a.clone();
+        let mut lb = b.clone();
+        la.line = Some(2.5);
+        lb.line = Some(3.5);
+        assert_eq!(score_match(&la, &lb).total, 0.0);
+    }
+
+    #[test]
+    fn test_match_catalogs_links_and_queues() {
+        let anchor = vec![
+            entry(Platform::Kalshi, "K1", "Chelsea vs Aston Villa", 1_000_000),
+            entry(Platform::Kalshi, "K2", "Lakers vs Celtics", 2_000_000),
+        ];
+        let candidates = vec![
+            entry(Platform::Polymarket, "P1", "Chelsea vs Aston Villa", 1_000_000),
+            // Same teams, close time 30h off -> review band
+            entry(Platform::Polymarket, "P2", "LA Lakers vs Boston Celtics", 2_000_000 + 30 * 3600),
+            entry(Platform::Polymarket, "P3", "Real Madrid vs Barcelona", 3_000_000),
+        ];
+
+        let mut registry = MarketIdRegistry::default();
+        let mut review = ReviewQueue::default();
+        let outcome = match_catalogs(&anchor, &candidates, &mut registry, &mut review);
+
+        assert_eq!(outcome.linked, 1);
+        assert_eq!(outcome.queued, 1);
+        assert_eq!(outcome.unmatched, 1);
+        assert_eq!(registry.link_for(Platform::Polymarket, "P1").unwrap().leg(Platform::Kalshi), Some("K1"));
+
+        // Rejected items are not re-queued on the next pass
+        assert!(review.reject(0));
+        let outcome = match_catalogs(&anchor, &candidates, &mut registry, &mut review);
+        assert_eq!(outcome.linked, 0);
+        assert_eq!(outcome.queued, 0);
+        assert!(review.is_empty());
+    }
+
+    #[test]
+    fn test_registry_merges_shared_legs() {
+        let mut registry = MarketIdRegistry::default();
+        let k = entry(Platform::Kalshi, "K1", "A vs B", 0);
+        let p = entry(Platform::Polymarket, "P1", "A vs B", 0);
+        let d = entry(Platform::DraftKings, "D1", "A vs B", 0);
+
+        let id1 = registry.insert(build_link(&k, &p, 0.9, LinkSource::Auto));
+        let id2 = registry.insert(build_link(&k, &d, 0.7, LinkSource::ManualReview));
+
+        assert_eq!(id1, id2);
+        assert_eq!(registry.len(), 1);
+        let link = registry.link_for(Platform::DraftKings, "D1").unwrap();
+        assert_eq!(link.legs.len(), 3);
+        assert!((link.confidence - 0.7).abs() < 1e-9);
+    }
 }
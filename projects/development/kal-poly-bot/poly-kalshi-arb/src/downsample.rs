@@ -0,0 +1,361 @@
+// src/downsample.rs
+// Time-series downsampling for long-horizon charts - LTTB lines and OHLC buckets over
+// archived prices, feed latency and realized equity
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::position_tracker::PositionTracker;
+use crate::provider_registry::ProviderId;
+use crate::strategy;
+use crate::tick_store::{scan, ArchiveQuery, TickRow};
+
+/// Points returned when a query doesn't ask
+const DEFAULT_POINTS: usize = 500;
+/// Upper bound on requested points
+const MAX_POINTS: usize = 10_000;
+
+/// One sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub t_ns: u64,
+    pub value: f64,
+}
+
+/// Aggregate of one time bucket
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Bucket start (Unix nanoseconds)
+    pub t_ns: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Raw samples in the bucket
+    pub count: usize,
+}
+
+/// Largest-triangle-three-buckets: keep `threshold` points (first and last included)
+/// that preserve the visual shape. Input must be time-ordered.
+pub fn lttb(points: &[Point], threshold: usize) -> Vec<Point> {
+    let n = points.len();
+    if threshold >= n || threshold < 3 {
+        return points.to_vec();
+    }
+
+    // Relative time keeps f64 precision with nanosecond timestamps
+    let origin = points[0].t_ns;
+    let x = |p: &Point| (p.t_ns - origin) as f64;
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+
+    let mut out = Vec::with_capacity(threshold);
+    out.push(points[0]);
+    let mut anchor = 0;
+    for bucket in 0..threshold - 2 {
+        // Average of the next bucket is the third triangle vertex
+        let next_start = ((bucket + 1) as f64 * every) as usize + 1;
+        let next_end = (((bucket + 2) as f64 * every) as usize + 1).min(n);
+        let next = &points[next_start..next_end.max(next_start + 1).min(n)];
+        let avg_x = next.iter().map(x).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.value).sum::<f64>() / next.len() as f64;
+
+        let start = (bucket as f64 * every) as usize + 1;
+        let end = next_start.min(n - 1);
+        let (ax, ay) = (x(&points[anchor]), points[anchor].value);
+        let chosen = (start..end)
+            .max_by(|&i, &j| {
+                let area = |k: usize| ((ax - avg_x) * (points[k].value - ay) - (ax - x(&points[k])) * (avg_y - ay)).abs();
+                area(i).total_cmp(&area(j))
+            })
+            .unwrap_or(start);
+        out.push(points[chosen]);
+        anchor = chosen;
+    }
+    out.push(points[n - 1]);
+    out
+}
+
+/// Open/high/low/close over `buckets` equal slices of [from_ns, to_ns]; empty buckets are omitted.
+/// Input must be time-ordered.
+pub fn ohlc(points: &[Point], from_ns: u64, to_ns: u64, buckets: usize) -> Vec<Candle> {
+    if buckets == 0 || to_ns < from_ns {
+        return Vec::new();
+    }
+    let width = ((to_ns - from_ns) / buckets as u64).saturating_add(1);
+    let mut out: Vec<Candle> = Vec::new();
+    for p in points.iter().filter(|p| p.t_ns >= from_ns && p.t_ns <= to_ns) {
+        let start = from_ns + (p.t_ns - from_ns) / width * width;
+        match out.last_mut() {
+            Some(c) if c.t_ns == start => {
+                c.high = c.high.max(p.value);
+                c.low = c.low.min(p.value);
+                c.close = p.value;
+                c.count += 1;
+            }
+            _ => out.push(Candle { t_ns: start, open: p.value, high: p.value, low: p.value, close: p.value, count: 1 }),
+        }
+    }
+    out
+}
+
+/// A chartable archived series
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "series", rename_all = "snake_case")]
+pub enum Series {
+    /// YES price (cents) of one market, optionally one provider
+    Prices { market: String, platform: Option<ProviderId> },
+    /// Feed latency (ms, receive minus provider timestamp)
+    Latency { market: Option<String>, platform: Option<ProviderId> },
+    /// Cumulative realized P&L (dollars), optionally of one strategy's lots
+    Equity { strategy: Option<String> },
+}
+
+/// Result of `HistoryService::downsample`
+#[derive(Debug, Clone, Serialize)]
+pub struct Downsampled {
+    #[serde(flatten)]
+    pub series: Series,
+    pub from_ns: Option<u64>,
+    pub to_ns: Option<u64>,
+    /// Samples in range before downsampling
+    pub raw_points: usize,
+    /// LTTB line
+    pub line: Vec<Point>,
+    /// OHLC buckets over the same span
+    pub candles: Vec<Candle>,
+}
+
+/// Reads archived series and downsamples them for the dashboard history endpoints
+#[derive(Debug, Clone, Default)]
+pub struct HistoryService {
+    tick_dir: Option<PathBuf>,
+    positions_file: Option<PathBuf>,
+}
+
+impl HistoryService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prices and latency come from this tick archive (see `tick_store`)
+    pub fn with_tick_store(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.tick_dir = Some(dir.into());
+        self
+    }
+
+    /// Equity comes from the realized lots in this saved position file
+    pub fn with_positions_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.positions_file = Some(path.into());
+        self
+    }
+
+    /// Time-ordered samples of `series` within the (inclusive) bounds
+    pub fn raw(&self, series: &Series, from_ns: Option<u64>, to_ns: Option<u64>) -> Result<Vec<Point>> {
+        let in_range = |t: u64| from_ns.is_none_or(|f| t >= f) && to_ns.is_none_or(|to| t <= to);
+        match series {
+            Series::Prices { market, platform } => Ok(self.ticks(Some(market), platform.as_ref(), from_ns, to_ns)?
+                .iter()
+                .map(|r| Point { t_ns: r.timestamp_ns, value: r.yes_price as f64 })
+                .collect()),
+            Series::Latency { market, platform } => Ok(self.ticks(market.as_ref(), platform.as_ref(), from_ns, to_ns)?
+                .iter()
+                .filter_map(|r| r.provider_timestamp_ns.map(|sent| Point {
+                    t_ns: r.timestamp_ns,
+                    value: r.timestamp_ns.saturating_sub(sent) as f64 / 1e6,
+                }))
+                .collect()),
+            Series::Equity { strategy } => {
+                let path = self.positions_file.as_ref().ok_or_else(|| anyhow!("no position file configured"))?;
+                let tracker = PositionTracker::load_from(path);
+                let mut closes: Vec<(u64, f64)> = tracker.realized_lots(..).into_iter()
+                    .filter(|lot| strategy.as_ref().is_none_or(|s| strategy::of_position_tag(lot.pattern.as_deref()) == *s))
+                    .filter_map(|lot| {
+                        let closed = chrono::DateTime::parse_from_rfc3339(&lot.closed_at).ok()?;
+                        Some((closed.timestamp_nanos_opt()? as u64, lot.pnl))
+                    })
+                    .collect();
+                closes.sort_by_key(|(t, _)| *t);
+                // Lots closed before the window still count toward the equity level
+                let mut equity = 0.0;
+                Ok(closes.into_iter()
+                    .filter_map(|(t, pnl)| {
+                        equity += pnl;
+                        in_range(t).then_some(Point { t_ns: t, value: equity })
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    fn ticks(&self, market: Option<&String>, platform: Option<&ProviderId>, from_ns: Option<u64>, to_ns: Option<u64>) -> Result<Vec<TickRow>> {
+        let dir = self.tick_dir.as_ref().ok_or_else(|| anyhow!("no tick archive configured"))?;
+        let query = ArchiveQuery {
+            from_ns,
+            to_ns,
+            markets: market.into_iter().cloned().collect(),
+            platforms: platform.into_iter().cloned().collect(),
+            ..ArchiveQuery::default()
+        };
+        Ok(scan::<TickRow>(dir, &query)?.rows)
+    }
+
+    /// `series` over [from_ns, to_ns] reduced to at most `points` line points and buckets
+    pub fn downsample(&self, series: &Series, from_ns: Option<u64>, to_ns: Option<u64>, points: usize) -> Result<Downsampled> {
+        let raw = self.raw(series, from_ns, to_ns)?;
+        let span = match (raw.first(), raw.last()) {
+            (Some(first), Some(last)) => Some((from_ns.unwrap_or(first.t_ns), to_ns.unwrap_or(last.t_ns))),
+            _ => None,
+        };
+        Ok(Downsampled {
+            series: series.clone(),
+            from_ns,
+            to_ns,
+            raw_points: raw.len(),
+            line: lttb(&raw, points),
+            candles: span.map(|(from, to)| ohlc(&raw, from, to, points)).unwrap_or_default(),
+        })
+    }
+
+    /// Admin route: GET /history/prices?market=..[&platform=..], /history/latency[?market=..&platform=..]
+    /// or /history/equity[?strategy=..], each taking `from`, `to` (Unix nanoseconds or RFC3339) and `points`
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        if method != "GET" {
+            return (405, r#"{"error":"method not allowed"}"#.to_string());
+        }
+        let (route, query) = path.split_once('?').unwrap_or((path, ""));
+        let (series, from_ns, to_ns, points) = match parse_query(route.trim_end_matches('/'), query) {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => return (404, r#"{"error":"not found"}"#.to_string()),
+            Err(e) => return (400, serde_json::json!({ "error": e }).to_string()),
+        };
+        match self.downsample(&series, from_ns, to_ns, points).map(|d| serde_json::to_string(&d)) {
+            Ok(Ok(body)) => (200, body),
+            Ok(Err(e)) => (500, serde_json::json!({ "error": e.to_string() }).to_string()),
+            Err(e) => (500, serde_json::json!({ "error": format!("{:#}", e) }).to_string()),
+        }
+    }
+}
+
+type ParsedQuery = (Series, Option<u64>, Option<u64>, usize);
+
+/// None for an unknown series path
+fn parse_query(route: &str, query: &str) -> Result<Option<ParsedQuery>, String> {
+    let mut points = DEFAULT_POINTS;
+    // Everything but `points` is an archive filter
+    let mut filter = Vec::new();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        match pair.split_once('=') {
+            Some(("points", value)) => {
+                points = value.parse().map_err(|_| format!("bad points '{}'", value))?;
+            }
+            _ => filter.push(pair),
+        }
+    }
+    let filter = ArchiveQuery::from_query_string(&filter.join("&"))?;
+    if filter.limit.is_some() {
+        return Err("use points, not limit".to_string());
+    }
+    if filter.markets.len() > 1 || filter.platforms.len() > 1 || filter.strategies.len() > 1 {
+        return Err("one market, platform and strategy per series".to_string());
+    }
+    let market = filter.markets.into_iter().next();
+    let platform = filter.platforms.into_iter().next();
+    let strategy = filter.strategies.into_iter().next();
+    if strategy.is_some() && route != "/history/equity" {
+        return Err("strategy only applies to equity".to_string());
+    }
+
+    let series = match route {
+        "/history/prices" => Series::Prices { market: market.ok_or("prices need a market")?, platform },
+        "/history/latency" => Series::Latency { market, platform },
+        "/history/equity" => Series::Equity { strategy },
+        _ => return Ok(None),
+    };
+    Ok(Some((series, filter.from_ns, filter.to_ns, points.clamp(3, MAX_POINTS))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tick_store::{SegmentWriter, TickStoreConfig};
+    use crate::types::{MarketType, Platform};
+
+    fn series(values: &[f64]) -> Vec<Point> {
+        values.iter().enumerate().map(|(i, v)| Point { t_ns: 1_000 + i as u64 * 10, value: *v }).collect()
+    }
+
+    #[test]
+    fn test_lttb_keeps_endpoints_and_spikes() {
+        let mut values = vec![50.0; 1_000];
+        values[137] = 90.0;
+        values[612] = 10.0;
+        let raw = series(&values);
+
+        let line = lttb(&raw, 50);
+        assert_eq!(line.len(), 50);
+        assert_eq!(line.first(), raw.first());
+        assert_eq!(line.last(), raw.last());
+        assert!(line.contains(&raw[137]) && line.contains(&raw[612]), "Spikes survive downsampling");
+        assert!(line.windows(2).all(|w| w[0].t_ns < w[1].t_ns));
+
+        assert_eq!(lttb(&raw[..10], 50).len(), 10);
+    }
+
+    #[test]
+    fn test_ohlc_buckets() {
+        let raw = series(&[5.0, 7.0, 3.0, 6.0, 8.0, 4.0]);
+        // Six samples 10ns apart over [1000, 1050] in two buckets of 26ns
+        let candles = ohlc(&raw, 1_000, 1_050, 2);
+        assert_eq!(candles, vec![
+            Candle { t_ns: 1_000, open: 5.0, high: 7.0, low: 3.0, close: 3.0, count: 3 },
+            Candle { t_ns: 1_026, open: 6.0, high: 8.0, low: 4.0, close: 4.0, count: 3 },
+        ]);
+        // Empty buckets are skipped
+        assert_eq!(ohlc(&raw, 0, 1_050, 100).len(), 6);
+    }
+
+    #[test]
+    fn test_history_endpoints_over_tick_archive() {
+        let dir = std::env::temp_dir().join(format!("downsample_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut writer = SegmentWriter::<TickRow>::new(TickStoreConfig { dir: dir.clone(), flush_rows: 10_000, ..TickStoreConfig::default() });
+        for i in 0..2_000u64 {
+            let platform = if i % 2 == 0 { Platform::Kalshi } else { Platform::Polymarket };
+            writer.append(TickRow {
+                timestamp_ns: 3_000_000 + i * 1_000_000,
+                provider_timestamp_ns: Some(1_000_000 + i * 1_000_000),
+                market_id: "7".to_string(),
+                platform: platform.into(),
+                market_type: MarketType::Moneyline,
+                yes_price: 40 + (i % 10) as u16,
+                no_price: 60 - (i % 10) as u16,
+                yes_size: 100,
+                no_size: 100,
+                features: None,
+            }).unwrap();
+        }
+        writer.flush().unwrap();
+        let history = HistoryService::new().with_tick_store(&dir);
+
+        let (code, body) = history.handle_admin("GET", "/history/prices?market=7&platform=kalshi&points=100");
+        assert_eq!(code, 200, "{}", body);
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["series"], "prices");
+        assert_eq!(body["raw_points"], 1_000);
+        assert_eq!(body["line"].as_array().unwrap().len(), 100);
+        let candles = body["candles"].as_array().unwrap();
+        assert!(candles.len() <= 100 && candles.iter().all(|c| c["high"].as_f64() <= Some(49.0)));
+
+        let latency = history.downsample(&Series::Latency { market: None, platform: None }, Some(500_000_000), None, 10).unwrap();
+        assert_eq!(latency.raw_points, 1_503);
+        assert!(latency.line.iter().all(|p| p.value == 2.0));
+
+        assert_eq!(history.handle_admin("GET", "/history/prices?points=10").0, 400);
+        assert_eq!(history.handle_admin("GET", "/history/latency?strategy=pattern_73").0, 400);
+        assert_eq!(history.handle_admin("GET", "/history/nope").0, 404);
+        assert_eq!(history.handle_admin("GET", "/history/equity").0, 500, "No position file configured");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,215 @@
+// src/edge_thresholds.rs
+// Minimum actionable edge per market tier and venue - fees, median slippage and decay over execution latency from TCA
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::error::StateStoreError;
+use crate::latency_arbitrage::MarketTier;
+use crate::tca::{SharedTcaStore, TcaFill, TcaStore};
+use crate::types::{kalshi_fee_cents, Platform};
+
+/// Disparity filter used until a venue has enough fills (the old global threshold)
+pub const DEFAULT_MIN_EDGE_CENTS: i16 = 2;
+/// Ceiling for venues whose costs and latency leave almost nothing
+const MAX_MIN_EDGE_CENTS: i16 = 50;
+/// Fills a venue needs in the lookback before its own costs replace the default
+const MIN_FILLS: usize = 20;
+const LOOKBACK_DAYS: u64 = 7;
+const REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+const NS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+
+const TIERS: [MarketTier; 4] = [MarketTier::Tier1, MarketTier::Tier2, MarketTier::Tier3, MarketTier::Tier4];
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] }
+}
+
+/// What trading on one venue costs, from its TCA fills (cents per contract)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VenueCosts {
+    pub fills: usize,
+    /// Fee at the median fill price
+    pub fee_cents: f64,
+    /// Median fill vs the touch at submission
+    pub slippage_cents: f64,
+    /// Median signal-to-submission time
+    pub latency_ns: u64,
+}
+
+impl VenueCosts {
+    pub fn from_fills(platform: Platform, fills: &[&TcaFill]) -> Self {
+        let mut prices: Vec<f64> = fills.iter().map(|f| f.fill_price).collect();
+        let mut slippage: Vec<f64> = fills.iter().map(|f| f.slippage_vs_arrival()).collect();
+        let mut latency: Vec<f64> = fills.iter().map(|f| f.decision_to_arrival_ns as f64).collect();
+        let fee_cents = match platform {
+            Platform::Kalshi => kalshi_fee_cents(median(&mut prices).round().clamp(0.0, 100.0) as u16) as f64,
+            _ => 0.0,
+        };
+        Self {
+            fills: fills.len(),
+            fee_cents,
+            slippage_cents: median(&mut slippage),
+            latency_ns: median(&mut latency) as u64,
+        }
+    }
+
+    /// Smallest disparity that still covers fees and slippage once it has decayed over the
+    /// execution latency at the tier's half-life
+    pub fn min_edge_cents(&self, tier: MarketTier) -> i16 {
+        let cost = self.fee_cents + self.slippage_cents.max(0.0);
+        let remaining = tier.edge_remaining(self.latency_ns).max(1e-6);
+        ((cost / remaining).ceil() as i16).clamp(1, MAX_MIN_EDGE_CENTS)
+    }
+}
+
+/// Signal filter thresholds the latency engine applies to the venue it would trade
+pub struct EdgeThresholds {
+    table: RwLock<HashMap<(MarketTier, Platform), i16>>,
+    costs: RwLock<HashMap<Platform, VenueCosts>>,
+}
+
+pub type SharedEdgeThresholds = Arc<EdgeThresholds>;
+
+impl Default for EdgeThresholds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EdgeThresholds {
+    pub fn new() -> Self {
+        Self { table: RwLock::new(HashMap::new()), costs: RwLock::new(HashMap::new()) }
+    }
+
+    /// Minimum disparity (cents) worth signalling on `platform` in a `tier` market
+    pub fn min_edge_cents(&self, tier: MarketTier, platform: Platform) -> i16 {
+        self.table.read().unwrap().get(&(tier, platform)).copied().unwrap_or(DEFAULT_MIN_EDGE_CENTS)
+    }
+
+    /// Costs behind the current thresholds, for venues with enough fills
+    pub fn costs(&self) -> HashMap<Platform, VenueCosts> {
+        self.costs.read().unwrap().clone()
+    }
+
+    /// Rebuild the table from fills; venues with fewer than `MIN_FILLS` fall back to the default
+    pub fn refresh(&self, fills: &[TcaFill]) {
+        let mut by_venue: HashMap<Platform, Vec<&TcaFill>> = HashMap::new();
+        for fill in fills {
+            by_venue.entry(fill.platform).or_default().push(fill);
+        }
+
+        let mut table = HashMap::new();
+        let mut costs = HashMap::new();
+        for (platform, venue_fills) in by_venue {
+            if venue_fills.len() < MIN_FILLS {
+                continue;
+            }
+            let venue = VenueCosts::from_fills(platform, &venue_fills);
+            for tier in TIERS {
+                table.insert((tier, platform), venue.min_edge_cents(tier));
+            }
+            info!("[EDGE] {} fills={} fee={:.1}¢ slippage={:.2}¢ latency={:.1}ms -> min edge {:?}¢",
+                  platform, venue.fills, venue.fee_cents, venue.slippage_cents, venue.latency_ns as f64 / 1e6,
+                  TIERS.map(|t| venue.min_edge_cents(t)));
+            costs.insert(platform, venue);
+        }
+        *self.table.write().unwrap() = table;
+        *self.costs.write().unwrap() = costs;
+    }
+
+    /// Refresh from the trailing week of fills in the TCA store
+    pub fn refresh_from(&self, store: &TcaStore) -> Result<(), StateStoreError> {
+        let to_ns = store.now_ns() + 1;
+        let fills = store.load(to_ns.saturating_sub(LOOKBACK_DAYS * NS_PER_DAY), to_ns)?;
+        self.refresh(&fills);
+        Ok(())
+    }
+}
+
+/// Refresh thresholds now and then once a day
+pub async fn run_edge_refresh_loop(thresholds: SharedEdgeThresholds, store: SharedTcaStore) {
+    loop {
+        if let Err(e) = thresholds.refresh_from(&store) {
+            warn!("[EDGE] Refresh failed, keeping previous thresholds: {}", e);
+        }
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(platform: Platform, fill_price: f64, arrival: f64, latency_ms: u64) -> TcaFill {
+        TcaFill {
+            ts_ns: 0,
+            market_id: "M1".into(),
+            platform,
+            tier: None,
+            side: "yes".into(),
+            action: "buy".into(),
+            pattern: "74".into(),
+            strategy_id: None,
+            contracts: 10.0,
+            decision_price: arrival,
+            arrival_price: arrival,
+            arrival_mid: arrival - 1.0,
+            fill_price,
+            decision_to_arrival_ns: latency_ms * 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_venue_costs_use_medians_and_fees() {
+        let fills = [fill(Platform::Kalshi, 51.0, 50.0, 10), fill(Platform::Kalshi, 50.0, 50.0, 30), fill(Platform::Kalshi, 54.0, 50.0, 20)];
+        let costs = VenueCosts::from_fills(Platform::Kalshi, &fills.iter().collect::<Vec<_>>());
+        assert_eq!(costs.fills, 3);
+        assert_eq!(costs.slippage_cents, 1.0);
+        assert_eq!(costs.latency_ns, 20_000_000);
+        assert_eq!(costs.fee_cents, kalshi_fee_cents(51) as f64);
+
+        let fills = [fill(Platform::Polymarket, 50.0, 50.0, 5), fill(Platform::Polymarket, 49.0, 50.0, 5)];
+        let costs = VenueCosts::from_fills(Platform::Polymarket, &fills.iter().collect::<Vec<_>>());
+        assert_eq!(costs.fee_cents, 0.0);
+        assert_eq!(costs.slippage_cents, -0.5);
+    }
+
+    #[test]
+    fn test_min_edge_grows_as_latency_eats_the_edge() {
+        let costs = VenueCosts { fills: 50, fee_cents: 1.0, slippage_cents: 1.5, latency_ns: 300_000_000 };
+        // One Tier1 half-life gone: 2.5¢ of cost needs a 5¢ disparity
+        assert_eq!(costs.min_edge_cents(MarketTier::Tier1), 5);
+        // Tier4 has barely decayed in 300ms
+        assert_eq!(costs.min_edge_cents(MarketTier::Tier4), 3);
+        // Price improvement never pushes the filter below 1¢
+        let free = VenueCosts { slippage_cents: -2.0, fee_cents: 0.0, ..costs };
+        assert_eq!(free.min_edge_cents(MarketTier::Tier1), 1);
+        let stale = VenueCosts { latency_ns: 60_000_000_000, ..costs };
+        assert_eq!(stale.min_edge_cents(MarketTier::Tier1), MAX_MIN_EDGE_CENTS);
+    }
+
+    #[test]
+    fn test_refresh_replaces_default_only_with_enough_fills() {
+        let thresholds = EdgeThresholds::new();
+        let mut fills: Vec<TcaFill> = (0..MIN_FILLS).map(|_| fill(Platform::DraftKings, 53.0, 50.0, 300)).collect();
+        fills.extend((0..MIN_FILLS - 1).map(|_| fill(Platform::FanDuel, 60.0, 50.0, 300)));
+        thresholds.refresh(&fills);
+
+        assert_eq!(thresholds.min_edge_cents(MarketTier::Tier1, Platform::DraftKings), 6);
+        assert_eq!(thresholds.min_edge_cents(MarketTier::Tier1, Platform::FanDuel), DEFAULT_MIN_EDGE_CENTS);
+        assert_eq!(thresholds.costs().len(), 1);
+
+        // A quiet week drops back to the default
+        thresholds.refresh(&[]);
+        assert_eq!(thresholds.min_edge_cents(MarketTier::Tier1, Platform::DraftKings), DEFAULT_MIN_EDGE_CENTS);
+    }
+}
@@ -0,0 +1,408 @@
+// src/error.rs
+// Crate-wide error taxonomy with retryability classification
+
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::circuit_breaker::{BreakerError, TripReason};
+use crate::event_phase::EventPhase;
+use crate::provider_registry::ProviderId;
+use crate::types::{MarketId, Platform, Size};
+
+/// Whether an operation that failed with this error is worth retrying
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+
+    /// Server- or breaker-suggested wait before retrying
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Top-level error for callers that handle every subsystem
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Feed(#[from] FeedError),
+    #[error(transparent)]
+    Venue(#[from] VenueApiError),
+    #[error(transparent)]
+    Risk(#[from] RiskRejection),
+    #[error(transparent)]
+    Execution(#[from] ExecutionError),
+    #[error(transparent)]
+    StateStore(#[from] StateStoreError),
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error("serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl Retryable for Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Error::Feed(e) => e.is_retryable(),
+            Error::Venue(e) => e.is_retryable(),
+            Error::Risk(e) => e.is_retryable(),
+            Error::Execution(e) => e.is_retryable(),
+            Error::StateStore(e) => e.is_retryable(),
+            Error::Secrets(e) => e.is_retryable(),
+            Error::Serialization(_) => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::Venue(e) => e.retry_after(),
+            Error::Execution(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+// === Market data feeds ===
+
+#[derive(Debug, Error)]
+pub enum FeedError {
+    #[error("{provider} feed connect failed: {message}")]
+    Connect { provider: ProviderId, message: String },
+    #[error("{provider} feed disconnected")]
+    Disconnected { provider: ProviderId },
+    #[error("{provider} feed timed out after {after:?}")]
+    Timeout { provider: ProviderId, after: Duration },
+    #[error("{provider} feed protocol error: {message}")]
+    Protocol { provider: ProviderId, message: String },
+    #[error("{provider} {message_type} message failed schema check: {reason}")]
+    Schema { provider: ProviderId, message_type: String, reason: String },
+    #[error("{provider} feed circuit open")]
+    CircuitOpen { provider: ProviderId },
+    #[error("{provider} feed not supported")]
+    Unsupported { provider: ProviderId },
+}
+
+impl FeedError {
+    /// Flatten a breaker-wrapped feed call
+    pub fn breaker(provider: impl Into<ProviderId>, e: BreakerError<FeedError>) -> Self {
+        match e {
+            BreakerError::Open { .. } => FeedError::CircuitOpen { provider: provider.into() },
+            BreakerError::Inner(e) => e,
+        }
+    }
+
+    pub fn provider(&self) -> ProviderId {
+        match self {
+            FeedError::Connect { provider, .. }
+            | FeedError::Disconnected { provider }
+            | FeedError::Timeout { provider, .. }
+            | FeedError::Protocol { provider, .. }
+            | FeedError::Schema { provider, .. }
+            | FeedError::CircuitOpen { provider }
+            | FeedError::Unsupported { provider } => *provider,
+        }
+    }
+}
+
+impl Retryable for FeedError {
+    fn is_retryable(&self) -> bool {
+        !matches!(self, FeedError::Protocol { .. } | FeedError::Schema { .. } | FeedError::Unsupported { .. })
+    }
+}
+
+// === Venue REST APIs ===
+
+#[derive(Debug, Error)]
+pub enum VenueApiError {
+    #[error("{venue} API rate limited")]
+    RateLimited { venue: Platform, retry_after: Option<Duration> },
+    #[error("{venue} API error {status}: {body}")]
+    Http { venue: Platform, status: u16, body: String },
+    #[error("{venue} API unauthorized {status}: {body}")]
+    Unauthorized { venue: Platform, status: u16, body: String },
+    #[error("{venue} API transport error: {source}")]
+    Transport { venue: Platform, #[source] source: reqwest::Error },
+    #[error("{venue} API response decode failed: {message}")]
+    Decode { venue: Platform, message: String },
+    #[error("{venue} request signing failed: {message}")]
+    Signing { venue: Platform, message: String },
+    #[error("{venue} order invalid: {message}")]
+    InvalidOrder { venue: Platform, message: String },
+    #[error("{venue} API circuit open")]
+    CircuitOpen { venue: Platform },
+}
+
+impl VenueApiError {
+    /// Classify a non-success HTTP status
+    pub fn from_status(venue: Platform, status: reqwest::StatusCode, body: String, retry_after: Option<Duration>) -> Self {
+        match status.as_u16() {
+            429 => VenueApiError::RateLimited { venue, retry_after },
+            401 | 403 => VenueApiError::Unauthorized { venue, status: status.as_u16(), body },
+            code => VenueApiError::Http { venue, status: code, body },
+        }
+    }
+
+    /// Consume a non-success response into an error (reads the body)
+    pub async fn from_response(venue: Platform, resp: reqwest::Response) -> Self {
+        let status = resp.status();
+        let retry_after = crate::request_scheduler::retry_after(&resp);
+        let body = resp.text().await.unwrap_or_default();
+        Self::from_status(venue, status, body, retry_after)
+    }
+
+    /// Split reqwest failures into decode vs transport
+    pub fn reqwest(venue: Platform, e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            VenueApiError::Decode { venue, message: e.to_string() }
+        } else {
+            VenueApiError::Transport { venue, source: e }
+        }
+    }
+
+    pub fn breaker(venue: Platform, e: BreakerError<reqwest::Error>) -> Self {
+        match e {
+            BreakerError::Open { .. } => VenueApiError::CircuitOpen { venue },
+            BreakerError::Inner(e) => Self::reqwest(venue, e),
+        }
+    }
+
+    pub fn signing(venue: Platform, e: impl std::fmt::Display) -> Self {
+        VenueApiError::Signing { venue, message: e.to_string() }
+    }
+
+    pub fn venue(&self) -> Platform {
+        match self {
+            VenueApiError::RateLimited { venue, .. }
+            | VenueApiError::Http { venue, .. }
+            | VenueApiError::Unauthorized { venue, .. }
+            | VenueApiError::Transport { venue, .. }
+            | VenueApiError::Decode { venue, .. }
+            | VenueApiError::Signing { venue, .. }
+            | VenueApiError::InvalidOrder { venue, .. }
+            | VenueApiError::CircuitOpen { venue } => *venue,
+        }
+    }
+}
+
+impl Retryable for VenueApiError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            VenueApiError::RateLimited { .. } | VenueApiError::CircuitOpen { .. } => true,
+            VenueApiError::Http { status, .. } => *status >= 500 || *status == 408,
+            VenueApiError::Transport { source, .. } => {
+                source.is_timeout() || source.is_connect() || source.is_request()
+            }
+            VenueApiError::Unauthorized { .. }
+            | VenueApiError::Decode { .. }
+            | VenueApiError::Signing { .. }
+            | VenueApiError::InvalidOrder { .. } => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            VenueApiError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+// === Risk checks ===
+
+#[derive(Debug, Clone, Error)]
+pub enum RiskRejection {
+    /// Trading circuit breaker limit or halt
+    #[error(transparent)]
+    Limit(#[from] TripReason),
+    #[error("{provider} circuit open")]
+    CircuitOpen { provider: Platform },
+    #[error("{provider} exposure {exposure_cents}¢ at limit {limit_cents}¢")]
+    ExposureLimit { provider: Platform, exposure_cents: i64, limit_cents: i64 },
+    #[error("edge decayed below half-life threshold")]
+    HalfLifeDecay,
+    #[error("{provider} failing")]
+    ProviderFailure { provider: Platform },
+    #[error("market phase {} not allowed", phase.map_or("unknown", EventPhase::as_str))]
+    PhaseGated { phase: Option<EventPhase> },
+    #[error("{pattern} budget ${budget:.2} exhausted (${deployed:.2} deployed)")]
+    BudgetExhausted { pattern: String, budget: f64, deployed: f64 },
+    #[error("pattern {pattern} not enabled")]
+    PatternDisabled { pattern: String },
+    #[error("{pattern} confidence {confidence:.2} below {min:.2}")]
+    LowConfidence { pattern: String, confidence: f64, min: f64 },
+    #[error("{pattern} not allowed on {venue}")]
+    VenueNotAllowed { pattern: String, venue: String },
+    #[error("{pattern} at {open} concurrent positions")]
+    ConcurrencyLimit { pattern: String, open: u32 },
+    #[error("market {market} cooling down ({}s left)", remaining.as_secs())]
+    MarketCooldown { market: String, remaining: Duration },
+    #[error("no {venue} account can take ${dollars:.2}")]
+    NoAccount { venue: String, dollars: f64 },
+    #[error("{venue} maintenance from {start}")]
+    Maintenance { venue: String, start: String },
+}
+
+impl Retryable for RiskRejection {
+    fn is_retryable(&self) -> bool {
+        // Breaker probes re-admit traffic on their own; limits need fills to clear
+        matches!(self, RiskRejection::CircuitOpen { .. })
+    }
+}
+
+// === Order execution ===
+
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    #[error("market {0} already in flight")]
+    AlreadyInFlight(MarketId),
+    #[error("unknown market {0}")]
+    UnknownMarket(MarketId),
+    #[error("profit {profit_cents}¢ below threshold")]
+    ProfitBelowThreshold { profit_cents: i16 },
+    #[error("insufficient liquidity (yes={yes_size} no={no_size})")]
+    InsufficientLiquidity { yes_size: Size, no_size: Size },
+    #[error("no matched fill (yes={yes_filled} no={no_filled})")]
+    Unfilled { yes_filled: i64, no_filled: i64 },
+    #[error("risk rejected: {0}")]
+    Risk(#[from] RiskRejection),
+    #[error(transparent)]
+    Venue(#[from] VenueApiError),
+}
+
+impl Retryable for ExecutionError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ExecutionError::AlreadyInFlight(_) | ExecutionError::Unfilled { .. } => true,
+            ExecutionError::Risk(e) => e.is_retryable(),
+            ExecutionError::Venue(e) => e.is_retryable(),
+            ExecutionError::UnknownMarket(_)
+            | ExecutionError::ProfitBelowThreshold { .. }
+            | ExecutionError::InsufficientLiquidity { .. } => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ExecutionError::Venue(e) => e.retry_after(),
+            _ => None,
+        }
+    }
+}
+
+// === Persistence (journal, position files, cache tiers) ===
+
+#[derive(Debug, Error)]
+pub enum StateStoreError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization failed: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error("corrupt state: {0}")]
+    Corrupt(String),
+    #[error("{backend} backend error: {message}")]
+    Backend { backend: &'static str, message: String },
+}
+
+impl Retryable for StateStoreError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            StateStoreError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::BrokenPipe
+            ),
+            StateStoreError::Backend { .. } => true,
+            StateStoreError::Serde(_) | StateStoreError::Corrupt(_) => false,
+        }
+    }
+}
+
+// === Wire encoding (status endpoint and worker payloads) ===
+
+#[derive(Debug, Error)]
+pub enum WireError {
+    #[error("JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("MessagePack encode: {0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    #[error("MessagePack decode: {0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    #[error("CBOR: {0}")]
+    Cbor(String),
+    #[error("unsupported media type '{0}'")]
+    UnsupportedMediaType(String),
+}
+
+// === Credentials ===
+
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error("secret {key} not found in any backend")]
+    Missing { key: String },
+    #[error("{path} is readable by group/other (mode {mode:o}); chmod 600 it")]
+    InsecurePermissions { path: String, mode: u32 },
+    #[error("secret {key} is malformed: {message}")]
+    Malformed { key: String, message: String },
+    #[error("{backend} secrets backend error: {message}")]
+    Backend { backend: &'static str, message: String },
+    #[error("I/O error reading {path}: {source}")]
+    Io { path: String, #[source] source: std::io::Error },
+}
+
+impl Retryable for SecretsError {
+    fn is_retryable(&self) -> bool {
+        // Remote stores (Vault) can blip; local misconfiguration won't fix itself
+        matches!(self, SecretsError::Backend { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_classification() {
+        let limited = VenueApiError::from_status(
+            Platform::Kalshi, reqwest::StatusCode::TOO_MANY_REQUESTS, String::new(), Some(Duration::from_secs(2)));
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(2)));
+
+        let server = VenueApiError::from_status(
+            Platform::Polymarket, reqwest::StatusCode::BAD_GATEWAY, "oops".into(), None);
+        assert!(server.is_retryable());
+        let bad = VenueApiError::from_status(
+            Platform::Polymarket, reqwest::StatusCode::BAD_REQUEST, "bad price".into(), None);
+        assert!(!bad.is_retryable());
+        assert!(matches!(
+            VenueApiError::from_status(Platform::Kalshi, reqwest::StatusCode::UNAUTHORIZED, String::new(), None),
+            VenueApiError::Unauthorized { status: 401, .. }
+        ));
+
+        // Classification survives wrapping
+        let exec: ExecutionError = limited.into();
+        assert!(exec.is_retryable());
+        let top: Error = exec.into();
+        assert_eq!(top.retry_after(), Some(Duration::from_secs(2)));
+
+        let limit: Error = ExecutionError::from(RiskRejection::from(TripReason::ManualHalt)).into();
+        assert!(!limit.is_retryable());
+
+        let io = StateStoreError::from(std::io::Error::from(std::io::ErrorKind::Interrupted));
+        assert!(io.is_retryable());
+        assert!(!StateStoreError::Corrupt("bad crc".into()).is_retryable());
+    }
+
+    #[test]
+    fn test_breaker_errors_map_to_circuit_open() {
+        let open = BreakerError::Open { key: Platform::DraftKings.to_string() };
+        let feed = FeedError::breaker(Platform::DraftKings, open);
+        assert!(matches!(feed, FeedError::CircuitOpen { provider: ProviderId::Builtin(Platform::DraftKings) }));
+        assert!(feed.is_retryable());
+        assert_eq!(feed.to_string(), "DRAFTKINGS feed circuit open");
+
+        let venue = VenueApiError::breaker(Platform::Kalshi, BreakerError::Open { key: "KALSHI".into() });
+        assert!(matches!(venue, VenueApiError::CircuitOpen { venue: Platform::Kalshi }));
+    }
+}
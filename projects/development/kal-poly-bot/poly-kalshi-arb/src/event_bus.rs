@@ -0,0 +1,699 @@
+// src/event_bus.rs
+// Typed in-process event bus - publish/subscribe with bounded per-subscriber mailboxes and lag metrics
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::{broadcast, Notify};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSink};
+use crate::audit_log::{AuditEvent, OrderAction, SharedAuditLog};
+use crate::clock::{self, SharedClock, Stamp};
+use crate::config_reload::ConfigChanged;
+use crate::feed_aggregator::{FeedStatus, LatencyBreakdown, PriceUpdate};
+use crate::latency_arbitrage::LatencySignal;
+use crate::latency_execution::{LatencyExecutionRequest, LatencyExecutionResult};
+use crate::pattern_73_beta_skew::BetaSkewOpportunity;
+use crate::provider_registry::ProviderId;
+use crate::strategy;
+use crate::tick_store::{SegmentWriter, SignalRow, TickRow, TickStoreConfig};
+use crate::types::SignalId;
+
+/// Event categories a subscriber can filter on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Topic {
+    Tick,
+    Feed,
+    Signal,
+    Opportunity,
+    Order,
+    Fill,
+    Alert,
+    Config,
+}
+
+impl std::fmt::Display for Topic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Everything that crosses a module boundary
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// Normalized price update from a venue feed
+    Tick(PriceUpdate),
+    /// Feed connection state change
+    Feed { provider: ProviderId, status: FeedStatus, latency_ns: u64 },
+    /// A feed's latency percentiles (round trip / network / processing), each heartbeat check
+    FeedLatency { provider: ProviderId, latency: LatencyBreakdown },
+    /// Cross-venue latency disparity
+    Signal(LatencySignal),
+    /// Pattern #73 beta-skew opportunity
+    Opportunity(BetaSkewOpportunity),
+    /// Execution scheduled for a signal
+    Order { signal_id: SignalId, request: LatencyExecutionRequest },
+    /// Execution finished (filled, failed or cancelled)
+    Fill(LatencyExecutionResult),
+    Alert(Alert),
+    ConfigChanged(ConfigChanged),
+}
+
+impl Event {
+    pub fn topic(&self) -> Topic {
+        match self {
+            Event::Tick(_) => Topic::Tick,
+            Event::Feed { .. } | Event::FeedLatency { .. } => Topic::Feed,
+            Event::Signal(_) => Topic::Signal,
+            Event::Opportunity(_) => Topic::Opportunity,
+            Event::Order { .. } => Topic::Order,
+            Event::Fill(_) => Topic::Fill,
+            Event::Alert(_) => Topic::Alert,
+            Event::ConfigChanged(_) => Topic::Config,
+        }
+    }
+}
+
+/// A published event as seen by one subscriber
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    /// Bus-wide publish order
+    pub seq: u64,
+    pub published: Stamp,
+    /// Shared by every subscriber that received it
+    pub event: Arc<Event>,
+}
+
+/// What a full mailbox does with the next event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Overflow {
+    /// Evict the oldest queued event (views that only need recent state)
+    DropOldest,
+    /// Reject the incoming event (consumers that must process in order)
+    DropNewest,
+}
+
+/// Mailbox sizing for a subscriber
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MailboxConfig {
+    pub capacity: usize,
+    pub overflow: Overflow,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self { capacity: 1024, overflow: Overflow::DropOldest }
+    }
+}
+
+/// Delivery and lag counters for one subscriber
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriberStats {
+    pub name: String,
+    pub topics: Vec<Topic>,
+    pub capacity: usize,
+    /// Events waiting in the mailbox
+    pub depth: usize,
+    pub high_water: usize,
+    /// Events accepted into the mailbox
+    pub delivered: u64,
+    /// Events taken out by the subscriber
+    pub received: u64,
+    /// Events lost to overflow
+    pub dropped: u64,
+    /// Publish-to-receive delay of the last event taken
+    pub last_lag_ns: u64,
+    pub max_lag_ns: u64,
+    pub avg_lag_ns: f64,
+}
+
+impl SubscriberStats {
+    /// Losing events or more than half full
+    pub fn is_lagging(&self) -> bool {
+        self.dropped > 0 || self.depth * 2 > self.capacity
+    }
+}
+
+struct Mailbox {
+    name: String,
+    topics: Vec<Topic>,
+    config: MailboxConfig,
+    queue: Mutex<VecDeque<Envelope>>,
+    notify: Notify,
+    /// Subscriber dropped; the bus prunes the mailbox on next publish
+    detached: AtomicBool,
+    /// Bus dropped; `recv` returns `None` once the queue is drained
+    closed: AtomicBool,
+    high_water: AtomicU64,
+    delivered: AtomicU64,
+    received: AtomicU64,
+    dropped: AtomicU64,
+    last_lag_ns: AtomicU64,
+    max_lag_ns: AtomicU64,
+    total_lag_ns: AtomicU64,
+}
+
+impl Mailbox {
+    fn wants(&self, topic: Topic) -> bool {
+        self.topics.is_empty() || self.topics.contains(&topic)
+    }
+
+    fn push(&self, envelope: &Envelope) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        let full = queue.len() >= self.config.capacity;
+        let accepted = match (full, self.config.overflow) {
+            (false, _) => true,
+            (true, Overflow::DropOldest) => {
+                queue.pop_front();
+                true
+            }
+            (true, Overflow::DropNewest) => false,
+        };
+        if accepted {
+            queue.push_back(envelope.clone());
+            self.delivered.fetch_add(1, Ordering::Relaxed);
+            self.high_water.fetch_max(queue.len() as u64, Ordering::Relaxed);
+        }
+        drop(queue);
+
+        if full {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                warn!("[BUS] Subscriber '{}' is lagging: {} events dropped (capacity {})",
+                      self.name, dropped, self.config.capacity);
+            }
+        }
+        if accepted {
+            self.notify.notify_one();
+        }
+        accepted
+    }
+
+    fn pop(&self, clock: &SharedClock) -> Option<Envelope> {
+        let envelope = self.queue.lock().unwrap().pop_front()?;
+        let lag = clock.mono_ns().saturating_sub(envelope.published.mono).0;
+        self.received.fetch_add(1, Ordering::Relaxed);
+        self.last_lag_ns.store(lag, Ordering::Relaxed);
+        self.max_lag_ns.fetch_max(lag, Ordering::Relaxed);
+        self.total_lag_ns.fetch_add(lag, Ordering::Relaxed);
+        Some(envelope)
+    }
+
+    fn stats(&self) -> SubscriberStats {
+        let received = self.received.load(Ordering::Relaxed);
+        let total_lag = self.total_lag_ns.load(Ordering::Relaxed);
+        SubscriberStats {
+            name: self.name.clone(),
+            topics: self.topics.clone(),
+            capacity: self.config.capacity,
+            depth: self.queue.lock().unwrap().len(),
+            high_water: self.high_water.load(Ordering::Relaxed) as usize,
+            delivered: self.delivered.load(Ordering::Relaxed),
+            received,
+            dropped: self.dropped.load(Ordering::Relaxed),
+            last_lag_ns: self.last_lag_ns.load(Ordering::Relaxed),
+            max_lag_ns: self.max_lag_ns.load(Ordering::Relaxed),
+            avg_lag_ns: if received > 0 { total_lag as f64 / received as f64 } else { 0.0 },
+        }
+    }
+}
+
+/// Fan-out point between producers (feeds, engines, execution) and consumers
+/// (dashboard, risk); publishing never blocks, a slow subscriber only loses
+/// its own events
+pub struct EventBus {
+    clock: SharedClock,
+    mailbox: MailboxConfig,
+    seq: AtomicU64,
+    subscribers: RwLock<Vec<Arc<Mailbox>>>,
+}
+
+pub type SharedEventBus = Arc<EventBus>;
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("published", &self.published())
+            .field("subscribers", &self.subscribers.read().unwrap().len())
+            .finish()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self {
+            clock: clock::system(),
+            mailbox: MailboxConfig::default(),
+            seq: AtomicU64::new(0),
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Stamp events from another clock (tests, backtest replay)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Mailbox used by `subscribe`
+    pub fn with_mailbox(mut self, mailbox: MailboxConfig) -> Self {
+        self.mailbox = mailbox;
+        self
+    }
+
+    /// Subscribe to the given topics (empty = everything) with the default mailbox
+    pub fn subscribe(&self, name: &str, topics: &[Topic]) -> Subscription {
+        self.subscribe_with(name, topics, self.mailbox)
+    }
+
+    pub fn subscribe_with(&self, name: &str, topics: &[Topic], config: MailboxConfig) -> Subscription {
+        let mailbox = Arc::new(Mailbox {
+            name: name.to_string(),
+            topics: topics.to_vec(),
+            config: MailboxConfig { capacity: config.capacity.max(1), ..config },
+            queue: Mutex::new(VecDeque::new()),
+            notify: Notify::new(),
+            detached: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            high_water: AtomicU64::new(0),
+            delivered: AtomicU64::new(0),
+            received: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            last_lag_ns: AtomicU64::new(0),
+            max_lag_ns: AtomicU64::new(0),
+            total_lag_ns: AtomicU64::new(0),
+        });
+        self.subscribers.write().unwrap().push(mailbox.clone());
+        info!("[BUS] '{}' subscribed to {}", name,
+              if topics.is_empty() { "all topics".to_string() } else { format!("{:?}", topics) });
+        Subscription { mailbox, clock: self.clock.clone() }
+    }
+
+    /// Deliver to every interested subscriber; returns how many accepted it
+    pub fn publish(&self, event: Event) -> usize {
+        let topic = event.topic();
+        let envelope = Envelope {
+            seq: self.seq.fetch_add(1, Ordering::Relaxed),
+            published: self.clock.now(),
+            event: Arc::new(event),
+        };
+
+        let mut accepted = 0;
+        let mut stale = false;
+        for mailbox in self.subscribers.read().unwrap().iter() {
+            if mailbox.detached.load(Ordering::Acquire) {
+                stale = true;
+            } else if mailbox.wants(topic) && mailbox.push(&envelope) {
+                accepted += 1;
+            }
+        }
+        if stale {
+            self.subscribers.write().unwrap().retain(|m| !m.detached.load(Ordering::Acquire));
+        }
+        accepted
+    }
+
+    /// Events published so far
+    pub fn published(&self) -> u64 {
+        self.seq.load(Ordering::Relaxed)
+    }
+
+    /// Per-subscriber delivery and lag counters
+    pub fn stats(&self) -> Vec<SubscriberStats> {
+        self.subscribers.read().unwrap().iter()
+            .filter(|m| !m.detached.load(Ordering::Acquire))
+            .map(|m| m.stats())
+            .collect()
+    }
+}
+
+impl Drop for EventBus {
+    fn drop(&mut self) {
+        for mailbox in self.subscribers.get_mut().unwrap().iter() {
+            mailbox.closed.store(true, Ordering::Release);
+            mailbox.notify.notify_one();
+        }
+    }
+}
+
+/// Receiving end of one subscriber's mailbox; dropping it unsubscribes
+pub struct Subscription {
+    mailbox: Arc<Mailbox>,
+    clock: SharedClock,
+}
+
+impl Subscription {
+    pub fn name(&self) -> &str {
+        &self.mailbox.name
+    }
+
+    /// Next event; `None` once the bus is gone and the mailbox is drained
+    pub async fn recv(&mut self) -> Option<Envelope> {
+        loop {
+            if let Some(envelope) = self.try_recv() {
+                return Some(envelope);
+            }
+            if self.mailbox.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            self.mailbox.notify.notified().await;
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Option<Envelope> {
+        self.mailbox.pop(&self.clock)
+    }
+
+    pub fn stats(&self) -> SubscriberStats {
+        self.mailbox.stats()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.mailbox.detached.store(true, Ordering::Release);
+    }
+}
+
+/// A consumer driven from a subscription
+pub trait EventHandler: Send + Sync + 'static {
+    fn handle_event(&mut self, event: &Event);
+}
+
+/// Feed a subscription into a shared handler until the bus shuts down;
+/// events already queued are applied under one write lock
+pub fn spawn_handler<H: EventHandler>(handler: Arc<tokio::sync::RwLock<H>>, mut subscription: Subscription) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(first) = subscription.recv().await {
+            let mut handler = handler.write().await;
+            handler.handle_event(&first.event);
+            while let Some(next) = subscription.try_recv() {
+                handler.handle_event(&next.event);
+            }
+        }
+        info!("[BUS] '{}' handler stopped", subscription.name());
+    })
+}
+
+/// Alert sink publishing routed alerts onto the bus
+pub struct BusSink {
+    bus: SharedEventBus,
+}
+
+impl BusSink {
+    pub fn new(bus: SharedEventBus) -> Self {
+        Self { bus }
+    }
+}
+
+impl AlertSink for BusSink {
+    fn deliver(&self, alert: &Alert) {
+        self.bus.publish(Event::Alert(alert.clone()));
+    }
+}
+
+/// Republish applied config reloads as `Event::ConfigChanged`
+pub fn forward_config_changes(mut config_rx: broadcast::Receiver<ConfigChanged>, bus: SharedEventBus) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) => {
+                    bus.publish(Event::ConfigChanged(change));
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("[BUS] Config forwarder skipped {} reloads", n);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Audits bus traffic: signals, opportunities, orders, fills and config reloads
+pub struct AuditSubscriber {
+    log: SharedAuditLog,
+}
+
+impl AuditSubscriber {
+    pub fn new(log: SharedAuditLog) -> Self {
+        Self { log }
+    }
+}
+
+impl EventHandler for AuditSubscriber {
+    fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Signal(signal) => self.log.record_for(
+                &signal.strategy_id,
+                "latency_arbitrage",
+                Some(signal.fast_market.market_id.to_string()),
+                AuditEvent::SignalConsidered {
+                    strategy: signal.strategy_id.clone(),
+                    detail: format!("{} leads {} on market {}", signal.fast_market.provider,
+                                    signal.slow_market.provider, signal.slow_market.market_id),
+                    edge: signal.disparity_cents as f64,
+                    confidence: Some(signal.confidence),
+                },
+            ),
+            Event::Opportunity(opportunity) => self.log.record_for(
+                &strategy::for_pattern(Some(73)),
+                "pattern_73",
+                Some(opportunity.team_total_market.to_string()),
+                AuditEvent::SignalConsidered {
+                    strategy: strategy::for_pattern(Some(73)),
+                    detail: format!("{} -> {}", opportunity.player_prop_market, opportunity.team_total_market),
+                    edge: opportunity.gap,
+                    confidence: None,
+                },
+            ),
+            Event::Order { signal_id, request } => self.log.record_for(
+                &request.signal.strategy_id,
+                "latency_execution",
+                Some(request.signal.fast_market.market_id.to_string()),
+                AuditEvent::order(OrderAction::Submitted, Some(signal_id.to_string()), None, None),
+            ),
+            Event::Fill(result) => {
+                let action = if result.success { OrderAction::Filled } else { OrderAction::Failed };
+                self.log.record_for(
+                    &result.strategy_id,
+                    "latency_execution",
+                    None,
+                    AuditEvent::order(action, Some(result.signal_id.to_string()), None, result.error_message.clone()),
+                );
+            }
+            Event::ConfigChanged(change) => self.log.record(
+                "config_reload",
+                None,
+                AuditEvent::ConfigChanged {
+                    version: Some(change.version),
+                    sections: change.sections.iter().map(|s| s.to_string()).collect(),
+                    detail: None,
+                },
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Archives ticks and latency signals to the columnar tick store
+pub struct ArchiveSubscriber {
+    ticks: SegmentWriter<TickRow>,
+    signals: SegmentWriter<SignalRow>,
+    clock: SharedClock,
+}
+
+impl ArchiveSubscriber {
+    pub fn new(config: TickStoreConfig) -> Self {
+        Self::with_clock(config, clock::system())
+    }
+
+    /// Signals are stamped with this clock's wall time as they are archived
+    pub fn with_clock(config: TickStoreConfig, clock: SharedClock) -> Self {
+        Self {
+            ticks: SegmentWriter::new(config.clone()),
+            signals: SegmentWriter::new(config),
+            clock,
+        }
+    }
+
+    /// Write out buffered rows (on shutdown)
+    pub fn flush(&mut self) {
+        if let Err(e) = self.ticks.flush() {
+            warn!("[TICKS] Tick flush failed: {:#}", e);
+        }
+        if let Err(e) = self.signals.flush() {
+            warn!("[TICKS] Signal flush failed: {:#}", e);
+        }
+    }
+}
+
+impl EventHandler for ArchiveSubscriber {
+    fn handle_event(&mut self, event: &Event) {
+        let result = match event {
+            Event::Tick(update) => self.ticks.append(TickRow::from(update)),
+            Event::Signal(signal) => self.signals.append(SignalRow::from_signal(signal, self.clock.wall_ns().0)),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("[TICKS] Archive append failed: {:#}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert_router::AlertSeverity;
+    use crate::clock::MockClock;
+    use crate::types::{Nanos, Platform};
+    use std::time::Duration;
+
+    fn alert(kind: &str) -> Event {
+        Event::Alert(Alert::new("test", AlertSeverity::Info, kind, "x"))
+    }
+
+    fn feed(provider: Platform) -> Event {
+        Event::Feed { provider: provider.into(), status: FeedStatus::Connected, latency_ns: 1_000 }
+    }
+
+    fn kind(envelope: &Envelope) -> String {
+        match &*envelope.event {
+            Event::Alert(a) => a.kind.clone(),
+            other => format!("{}", other.topic()),
+        }
+    }
+
+    #[test]
+    fn test_topic_filtering_and_fanout() {
+        let bus = EventBus::new();
+        let mut alerts = bus.subscribe("alerts", &[Topic::Alert]);
+        let mut everything = bus.subscribe("all", &[]);
+
+        assert_eq!(bus.publish(alert("a")), 2);
+        assert_eq!(bus.publish(feed(Platform::Kalshi)), 1);
+        assert_eq!(bus.publish(alert("b")), 2);
+
+        let got: Vec<String> = std::iter::from_fn(|| alerts.try_recv()).map(|e| kind(&e)).collect();
+        assert_eq!(got, vec!["a", "b"]);
+
+        let seqs: Vec<u64> = std::iter::from_fn(|| everything.try_recv()).map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![0, 1, 2]);
+        assert_eq!(bus.published(), 3);
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let bus = EventBus::new();
+        let mut recent = bus.subscribe_with("recent", &[], MailboxConfig { capacity: 2, overflow: Overflow::DropOldest });
+        let mut ordered = bus.subscribe_with("ordered", &[], MailboxConfig { capacity: 2, overflow: Overflow::DropNewest });
+
+        for k in ["a", "b", "c", "d"] {
+            bus.publish(alert(k));
+        }
+
+        let got: Vec<String> = std::iter::from_fn(|| recent.try_recv()).map(|e| kind(&e)).collect();
+        assert_eq!(got, vec!["c", "d"]);
+        let got: Vec<String> = std::iter::from_fn(|| ordered.try_recv()).map(|e| kind(&e)).collect();
+        assert_eq!(got, vec!["a", "b"]);
+
+        for stats in bus.stats() {
+            assert_eq!(stats.dropped, 2);
+            assert_eq!(stats.high_water, 2);
+            assert!(stats.is_lagging());
+        }
+        assert_eq!(recent.stats().delivered, 4);
+        assert_eq!(ordered.stats().delivered, 2);
+    }
+
+    #[test]
+    fn test_lag_metrics() {
+        let clock = MockClock::shared(Nanos(1_000_000));
+        let bus = EventBus::new().with_clock(clock.clone());
+        let mut sub = bus.subscribe("slow", &[]);
+
+        bus.publish(alert("a"));
+        clock.advance(Duration::from_micros(10));
+        bus.publish(alert("b"));
+        clock.advance(Duration::from_micros(30));
+
+        assert_eq!(sub.stats().depth, 2);
+        sub.try_recv().unwrap();
+        sub.try_recv().unwrap();
+
+        let stats = sub.stats();
+        assert_eq!(stats.received, 2);
+        assert_eq!(stats.last_lag_ns, 30_000);
+        assert_eq!(stats.max_lag_ns, 40_000);
+        assert_eq!(stats.avg_lag_ns, 35_000.0);
+        assert_eq!(stats.depth, 0);
+        assert!(!stats.is_lagging());
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        alerts: usize,
+        feeds: usize,
+    }
+
+    impl EventHandler for Counter {
+        fn handle_event(&mut self, event: &Event) {
+            match event {
+                Event::Alert(_) => self.alerts += 1,
+                Event::Feed { .. } => self.feeds += 1,
+                _ => {}
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawned_handler_applies_events() {
+        let bus = Arc::new(EventBus::new());
+        let counter = Arc::new(tokio::sync::RwLock::new(Counter::default()));
+        let task = spawn_handler(counter.clone(), bus.subscribe("counter", &[Topic::Alert]));
+
+        bus.publish(alert("a"));
+        bus.publish(feed(Platform::Polymarket));
+        bus.publish(alert("b"));
+        drop(bus);
+        task.await.unwrap();
+
+        let counter = counter.read().await;
+        assert_eq!((counter.alerts, counter.feeds), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_and_shutdown() {
+        let bus = Arc::new(EventBus::new());
+        let dropped = bus.subscribe("gone", &[]);
+        let mut sub = bus.subscribe("live", &[]);
+        drop(dropped);
+
+        assert_eq!(bus.publish(alert("a")), 1);
+        assert_eq!(bus.stats().len(), 1);
+
+        let publisher = bus.clone();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            publisher.publish(alert("b"));
+        });
+
+        assert_eq!(kind(&sub.recv().await.unwrap()), "a");
+        assert_eq!(kind(&sub.recv().await.unwrap()), "b");
+        task.await.unwrap();
+
+        // Queued events survive the bus; then the stream ends
+        bus.publish(alert("c"));
+        drop(bus);
+        assert_eq!(kind(&sub.recv().await.unwrap()), "c");
+        assert!(sub.recv().await.is_none());
+    }
+}
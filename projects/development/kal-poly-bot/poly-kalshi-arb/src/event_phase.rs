@@ -0,0 +1,404 @@
+// src/event_phase.rs
+// Event phases - pre-game / in-play / halftime / suspended / settled per market, from schedule and venue status
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::config::{ExecutionSection, RiskSection};
+use crate::error::RiskRejection;
+use crate::feature_flags;
+use crate::kalshi::KalshiApiClient;
+use crate::types::{MarketPair, Nanos};
+
+/// Where a game is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventPhase {
+    PreGame,
+    InPlay,
+    Halftime,
+    /// Venue has paused trading
+    Suspended,
+    Settled,
+}
+
+impl EventPhase {
+    pub const ALL: [EventPhase; 5] = [
+        EventPhase::PreGame,
+        EventPhase::InPlay,
+        EventPhase::Halftime,
+        EventPhase::Suspended,
+        EventPhase::Settled,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventPhase::PreGame => "pre_game",
+            EventPhase::InPlay => "in_play",
+            EventPhase::Halftime => "halftime",
+            EventPhase::Suspended => "suspended",
+            EventPhase::Settled => "settled",
+        }
+    }
+
+    /// Scheduled phase `now` for a game starting at `start` (both Unix ns)
+    pub fn scheduled(timing: &LeagueTiming, start: Nanos, now: Nanos) -> EventPhase {
+        if now < start {
+            return EventPhase::PreGame;
+        }
+        let elapsed = now.saturating_sub(start).as_duration();
+        match timing.halftime {
+            Some((offset, length)) if elapsed >= offset && elapsed < offset + length => EventPhase::Halftime,
+            _ => EventPhase::InPlay,
+        }
+    }
+}
+
+impl std::fmt::Display for EventPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Set of phases a component is valid in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PhaseSet(u8);
+
+impl PhaseSet {
+    pub const IN_PLAY: PhaseSet = PhaseSet::of(&[EventPhase::InPlay]);
+    /// In-play including the break, for models carrying state across periods
+    pub const LIVE: PhaseSet = PhaseSet::of(&[EventPhase::InPlay, EventPhase::Halftime]);
+    /// Any phase the venues are trading in
+    pub const TRADING: PhaseSet = PhaseSet::of(&[EventPhase::PreGame, EventPhase::InPlay, EventPhase::Halftime]);
+
+    pub const fn of(phases: &[EventPhase]) -> PhaseSet {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < phases.len() {
+            bits |= 1 << phases[i] as u8;
+            i += 1;
+        }
+        PhaseSet(bits)
+    }
+
+    pub const fn contains(self, phase: EventPhase) -> bool {
+        self.0 & (1 << phase as u8) != 0
+    }
+
+    /// An unknown phase (no schedule yet) only passes sets valid both before and during the game
+    pub const fn allows(self, phase: Option<EventPhase>) -> bool {
+        match phase {
+            Some(phase) => self.contains(phase),
+            None => self.contains(EventPhase::PreGame) && self.contains(EventPhase::InPlay),
+        }
+    }
+}
+
+impl From<&[EventPhase]> for PhaseSet {
+    fn from(phases: &[EventPhase]) -> Self {
+        PhaseSet::of(phases)
+    }
+}
+
+/// Typical game shape for a league
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeagueTiming {
+    /// Start to final whistle, stoppages included
+    pub duration: Duration,
+    /// Break as (offset from start, length)
+    pub halftime: Option<(Duration, Duration)>,
+}
+
+const fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+const SOCCER: LeagueTiming = LeagueTiming { duration: minutes(115), halftime: Some((minutes(47), minutes(15))) };
+
+pub fn league_timing(league: &str) -> LeagueTiming {
+    match league {
+        "epl" | "bundesliga" | "laliga" | "seriea" | "ligue1" | "ucl" | "uel" | "eflc" | "mls" => SOCCER,
+        "nba" => LeagueTiming { duration: minutes(150), halftime: Some((minutes(70), minutes(15))) },
+        "nfl" => LeagueTiming { duration: minutes(195), halftime: Some((minutes(90), minutes(13))) },
+        "ncaaf" => LeagueTiming { duration: minutes(210), halftime: Some((minutes(95), minutes(20))) },
+        // Intermissions / innings breaks are short and unscheduled
+        "nhl" => LeagueTiming { duration: minutes(150), halftime: None },
+        _ => LeagueTiming { duration: minutes(180), halftime: None },
+    }
+}
+
+/// Venue-reported trading status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedStatus {
+    #[default]
+    Open,
+    Suspended,
+    Settled,
+}
+
+impl FeedStatus {
+    /// Kalshi market status ("active", "closed", "determined", ...)
+    pub fn from_kalshi(status: &str) -> FeedStatus {
+        match status {
+            "determined" | "settled" | "finalized" => FeedStatus::Settled,
+            "closed" | "inactive" | "paused" => FeedStatus::Suspended,
+            _ => FeedStatus::Open,
+        }
+    }
+}
+
+/// Phase sets execution must pass: risk (hot-reloadable) and execution (restart)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseGates {
+    pub risk: PhaseSet,
+    pub execution: PhaseSet,
+}
+
+impl PhaseGates {
+    pub fn new(risk: &RiskSection, execution: &ExecutionSection) -> Self {
+        Self {
+            risk: PhaseSet::from(risk.phases.as_slice()),
+            execution: PhaseSet::from(execution.phases.as_slice()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MarketPhase {
+    timing: LeagueTiming,
+    /// Scheduled start (Unix ns)
+    start: Option<Nanos>,
+    status: FeedStatus,
+}
+
+/// Per-market phase, keyed by Kalshi market ticker
+pub struct PhaseTracker {
+    clock: SharedClock,
+    gates: RwLock<PhaseGates>,
+    markets: RwLock<HashMap<String, MarketPhase>>,
+}
+
+pub type SharedPhaseTracker = Arc<PhaseTracker>;
+
+impl std::fmt::Debug for PhaseTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhaseTracker")
+            .field("gates", &*self.gates.read().unwrap())
+            .field("markets", &self.markets.read().unwrap().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PhaseTracker {
+    pub fn new(gates: PhaseGates) -> Self {
+        Self {
+            clock: clock::system(),
+            gates: RwLock::new(gates),
+            markets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Apply a hot-reloaded `[risk]` section
+    pub fn apply_risk_config(&self, risk: &RiskSection) {
+        self.gates.write().unwrap().risk = PhaseSet::from(risk.phases.as_slice());
+        info!("[PHASE] Risk phases: {:?}", risk.phases);
+    }
+
+    pub fn set_schedule(&self, market: &str, league: &str, start: Option<Nanos>) {
+        let mut markets = self.markets.write().unwrap();
+        let entry = markets.entry(market.to_string()).or_insert(MarketPhase {
+            timing: league_timing(league),
+            start: None,
+            status: FeedStatus::Open,
+        });
+        entry.timing = league_timing(league);
+        entry.start = start;
+    }
+
+    /// Record venue status; only known markets are tracked
+    pub fn set_feed_status(&self, market: &str, status: FeedStatus) {
+        if let Some(entry) = self.markets.write().unwrap().get_mut(market) {
+            if entry.status != status {
+                debug!("[PHASE] {} feed {:?} -> {:?}", market, entry.status, status);
+            }
+            entry.status = status;
+        }
+    }
+
+    /// Current phase, `None` while the market or its start time is unknown
+    pub fn phase(&self, market: &str) -> Option<EventPhase> {
+        let entry = *self.markets.read().unwrap().get(market)?;
+        match entry.status {
+            FeedStatus::Settled => Some(EventPhase::Settled),
+            FeedStatus::Suspended => Some(EventPhase::Suspended),
+            FeedStatus::Open => entry.start
+                .map(|start| EventPhase::scheduled(&entry.timing, start, self.clock.wall_ns())),
+        }
+    }
+
+    /// Time until the scheduled start, `None` once started or while unscheduled
+    pub fn starts_in(&self, market: &str) -> Option<Duration> {
+        let start = self.markets.read().unwrap().get(market)?.start?;
+        let now = self.clock.wall_ns();
+        (now < start).then(|| start.saturating_sub(now).as_duration())
+    }
+
+    /// Whether pattern `id` may fire on `market`; components outside the registry are never gated
+    pub fn component_allowed(&self, market: &str, id: u16) -> bool {
+        feature_flags::component(id).is_none_or(|spec| spec.phases.allows(self.phase(market)))
+    }
+
+    /// Risk and execution phase gates for an arb on `market`
+    pub fn check(&self, market: &str) -> Result<(), RiskRejection> {
+        let phase = self.phase(market);
+        let gates = *self.gates.read().unwrap();
+        if gates.risk.allows(phase) && gates.execution.allows(phase) {
+            Ok(())
+        } else {
+            Err(RiskRejection::PhaseGated { phase })
+        }
+    }
+}
+
+/// Kalshi expected expiration less the league's game length; Kalshi publishes no start time
+fn estimated_start(expected_expiration: &str, timing: &LeagueTiming) -> Option<Nanos> {
+    let expiration = chrono::DateTime::parse_from_rfc3339(expected_expiration).ok()?;
+    let expiration_ns = u64::try_from(expiration.timestamp_nanos_opt()?).ok()?;
+    Some(Nanos(expiration_ns).saturating_sub(Nanos::from(timing.duration)))
+}
+
+/// Refresh schedules and venue status for every pair, one Kalshi call per event
+pub async fn refresh_phases(tracker: &PhaseTracker, kalshi: &KalshiApiClient, pairs: &[MarketPair]) -> usize {
+    let mut by_event: HashMap<&str, Vec<&MarketPair>> = HashMap::new();
+    for pair in pairs {
+        by_event.entry(&pair.kalshi_event_ticker).or_default().push(pair);
+    }
+
+    let mut updated = 0;
+    for (event, event_pairs) in by_event {
+        let markets = match kalshi.get_markets(event).await {
+            Ok(markets) => markets,
+            Err(e) => {
+                warn!("[PHASE] {} refresh failed: {}", event, e);
+                continue;
+            }
+        };
+        for pair in event_pairs {
+            let Some(market) = markets.iter().find(|m| *m.ticker == *pair.kalshi_market_ticker) else {
+                continue;
+            };
+            let timing = league_timing(&pair.league);
+            let start = market.expected_expiration_time.as_deref().and_then(|t| estimated_start(t, &timing));
+            tracker.set_schedule(&market.ticker, &pair.league, start);
+            tracker.set_feed_status(&market.ticker, FeedStatus::from_kalshi(market.status.as_deref().unwrap_or("")));
+            updated += 1;
+        }
+    }
+    updated
+}
+
+/// Keep phases current; the first pass runs immediately
+pub async fn run_phase_refresh(
+    tracker: SharedPhaseTracker,
+    kalshi: Arc<KalshiApiClient>,
+    pairs: Vec<MarketPair>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        let updated = refresh_phases(&tracker, &kalshi, &pairs).await;
+        debug!("[PHASE] Refreshed {}/{} markets", updated, pairs.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    const START: u64 = 1_700_000_000_000_000_000;
+
+    fn tracker() -> (PhaseTracker, Arc<MockClock>) {
+        let clock = MockClock::shared(Nanos(START));
+        let gates = PhaseGates::new(&RiskSection::default(), &ExecutionSection::default());
+        (PhaseTracker::new(gates).with_clock(clock.clone()), clock)
+    }
+
+    #[test]
+    fn test_schedule_drives_phase() {
+        let (tracker, clock) = tracker();
+        assert_eq!(tracker.phase("KXEPL-A"), None);
+
+        let kickoff = Nanos(START).checked_add(Nanos::from(minutes(30))).unwrap();
+        tracker.set_schedule("KXEPL-A", "epl", Some(kickoff));
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::PreGame));
+        assert_eq!(tracker.starts_in("KXEPL-A"), Some(minutes(30)));
+
+        clock.advance(minutes(31));
+        assert_eq!(tracker.starts_in("KXEPL-A"), None);
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::InPlay));
+        clock.advance(minutes(50));
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::Halftime));
+        clock.advance(minutes(15));
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::InPlay));
+
+        // Venue status overrides the schedule
+        tracker.set_feed_status("KXEPL-A", FeedStatus::Suspended);
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::Suspended));
+        tracker.set_feed_status("KXEPL-A", FeedStatus::from_kalshi("finalized"));
+        assert_eq!(tracker.phase("KXEPL-A"), Some(EventPhase::Settled));
+    }
+
+    #[test]
+    fn test_in_play_patterns_wait_for_kickoff() {
+        let (tracker, clock) = tracker();
+        // #75 is in-play only; #74 runs whenever the venues trade
+        assert!(!tracker.component_allowed("KXNBA-A", 75));
+        assert!(tracker.component_allowed("KXNBA-A", 74));
+        assert!(tracker.component_allowed("KXNBA-A", 51));
+
+        tracker.set_schedule("KXNBA-A", "nba", Some(Nanos(START + 1)));
+        assert!(!tracker.component_allowed("KXNBA-A", 75));
+        clock.advance(minutes(5));
+        assert!(tracker.component_allowed("KXNBA-A", 75));
+
+        tracker.set_feed_status("KXNBA-A", FeedStatus::Suspended);
+        assert!(!tracker.component_allowed("KXNBA-A", 75));
+        assert!(!tracker.component_allowed("KXNBA-A", 74));
+    }
+
+    #[test]
+    fn test_risk_gate_reloads() {
+        let (tracker, _clock) = tracker();
+        tracker.set_schedule("KXNFL-A", "nfl", Some(Nanos(START - 1)));
+        assert!(tracker.check("KXNFL-A").is_ok());
+        assert!(tracker.check("unscheduled").is_ok());
+
+        let risk = RiskSection { phases: vec![EventPhase::PreGame], ..RiskSection::default() };
+        tracker.apply_risk_config(&risk);
+        assert!(matches!(
+            tracker.check("KXNFL-A"),
+            Err(RiskRejection::PhaseGated { phase: Some(EventPhase::InPlay) })
+        ));
+        assert!(tracker.check("unscheduled").is_err());
+    }
+
+    #[test]
+    fn test_estimated_start() {
+        let timing = league_timing("nba");
+        let start = estimated_start("2025-12-28T03:30:00Z", &timing).unwrap();
+        let expiration = chrono::DateTime::parse_from_rfc3339("2025-12-28T01:00:00Z").unwrap();
+        assert_eq!(start.0, expiration.timestamp_nanos_opt().unwrap() as u64);
+        assert_eq!(estimated_start("soon", &timing), None);
+    }
+}
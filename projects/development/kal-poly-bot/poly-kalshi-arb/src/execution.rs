@@ -1,60 +1,124 @@
 // src/execution.rs
 // Execution Engine
 
-use anyhow::{Result, anyhow};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tracing::{info, warn, error};
+use tracing::{info, warn, error, Level};
 
 use crate::kalshi::KalshiApiClient;
 use crate::polymarket_clob::SharedAsyncClient;
 use crate::types::{
-    ArbType, MarketPair,
-    FastExecutionRequest, GlobalState,
-    cents_to_price,
+    ArbType, MarketPair, MarketId, Nanos, Platform, Price, PriceCents,
+    FastExecutionRequest, GlobalState, MAX_MARKETS,
 };
-use crate::circuit_breaker::CircuitBreaker;
+use crate::account_manager::{is_account_refusal, SharedAccountManager};
+use crate::capital_allocator::SharedCapitalAllocator;
+use crate::circuit_breaker::TradingCircuitBreaker;
+use crate::clock::{self, SharedClock};
+use crate::decision_latency::SharedDecisionLatency;
+use crate::error::{ExecutionError, RiskRejection, VenueApiError};
 use crate::position_tracker::{FillRecord, PositionChannel};
+use crate::journal::{Journal, JournalEvent, SharedJournal};
+use crate::audit_log::{AuditEvent, OrderAction, SharedAuditLog};
+use crate::event_phase::SharedPhaseTracker;
+use crate::execution_playbook::{ExecutionPlaybook, LegOrder, OrderStyle, PlaybookRegistry, RemainderPolicy};
+use crate::maintenance::SharedMaintenanceCalendar;
+use crate::market_cooldown::{SharedCooldownManager, TradeOutcome};
+use crate::order_manager::{OrderContext, SharedOrderManager};
+use crate::pattern_policy::{arb_venues, SharedPatternPolicy};
+use crate::strategy;
+use crate::tca::{self, SharedTcaStore, TcaFill};
 
 // =============================================================================
 // EXECUTION ENGINE
 // =============================================================================
 
-/// Monotonic nanosecond clock for latency measurement
-pub struct NanoClock {
-    start: Instant,
+/// One in-flight bit per market slot
+const IN_FLIGHT_WORDS: usize = MAX_MARKETS / 64;
+
+/// Discount below entry when dumping unmatched exposure
+const CLOSE_DISCOUNT: Price = Price(10);
+
+/// Market tier cross-venue arbs are held to for decision latency (they race core-market moves)
+const ARB_TIER: u8 = 1;
+
+/// Book state when the orders went out (TCA arrival benchmark)
+struct Arrival {
+    mono: Nanos,
+    /// (yes_ask, no_ask)
+    kalshi: (PriceCents, PriceCents),
+    poly: (PriceCents, PriceCents),
 }
 
-impl NanoClock {
-    pub fn new() -> Self {
-        Self { start: Instant::now() }
-    }
+/// Venue account each leg was routed through (None = the engine's own clients)
+#[derive(Debug, Clone, Default)]
+struct LegAccounts {
+    kalshi: Option<String>,
+    poly: Option<String>,
+}
 
-    #[inline(always)]
-    pub fn now_ns(&self) -> u64 {
-        self.start.elapsed().as_nanos() as u64
+impl LegAccounts {
+    /// Account for a venue named as in fill records
+    fn for_venue(&self, venue: &str) -> Option<&str> {
+        match venue {
+            "kalshi" => self.kalshi.as_deref(),
+            _ => self.poly.as_deref(),
+        }
     }
 }
 
-impl Default for NanoClock {
-    fn default() -> Self {
-        Self::new()
+/// One buy of an arb at the signal price
+#[derive(Debug, Clone, Copy)]
+enum ArbLeg<'a> {
+    Kalshi { ticker: &'a str, side: &'static str, price: Price },
+    Poly { token: &'a str, side: &'static str, price: Price },
+}
+
+impl std::fmt::Display for ArbLeg<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArbLeg::Kalshi { side, .. } => write!(f, "Kalshi {}", side.to_uppercase()),
+            ArbLeg::Poly { side, .. } => write!(f, "Poly {}", side.to_uppercase()),
+        }
     }
 }
 
+/// What one leg filled (cost in cents)
+#[derive(Debug, Default)]
+struct LegFill {
+    filled: i64,
+    cost: i64,
+    order_id: String,
+}
+
 /// Execution engine
 pub struct ExecutionEngine {
     kalshi: Arc<KalshiApiClient>,
     poly_async: Arc<SharedAsyncClient>,
     state: Arc<GlobalState>,
-    circuit_breaker: Arc<CircuitBreaker>,
+    circuit_breaker: Arc<TradingCircuitBreaker>,
     position_channel: PositionChannel,
-    in_flight: Arc<[AtomicU64; 8]>,
-    clock: NanoClock,
+    in_flight: Arc<[AtomicU64; IN_FLIGHT_WORDS]>,
+    clock: SharedClock,
     pub dry_run: bool,
     test_mode: bool,
+    journal: Option<SharedJournal>,
+    audit: Option<SharedAuditLog>,
+    order_manager: Option<SharedOrderManager>,
+    tca: Option<SharedTcaStore>,
+    phases: Option<SharedPhaseTracker>,
+    allocator: Option<SharedCapitalAllocator>,
+    patterns: Option<SharedPatternPolicy>,
+    cooldowns: Option<SharedCooldownManager>,
+    maintenance: Option<SharedMaintenanceCalendar>,
+    accounts: Option<SharedAccountManager>,
+    playbooks: PlaybookRegistry,
+    decision_latency: Option<SharedDecisionLatency>,
+    kalshi_accounts: HashMap<String, Arc<KalshiApiClient>>,
+    poly_accounts: HashMap<String, Arc<SharedAsyncClient>>,
 }
 
 impl ExecutionEngine {
@@ -62,7 +126,7 @@ impl ExecutionEngine {
         kalshi: Arc<KalshiApiClient>,
         poly_async: Arc<SharedAsyncClient>,
         state: Arc<GlobalState>,
-        circuit_breaker: Arc<CircuitBreaker>,
+        circuit_breaker: Arc<TradingCircuitBreaker>,
         position_channel: PositionChannel,
         dry_run: bool,
     ) -> Self {
@@ -77,118 +141,411 @@ impl ExecutionEngine {
             circuit_breaker,
             position_channel,
             in_flight: Arc::new(std::array::from_fn(|_| AtomicU64::new(0))),
-            clock: NanoClock::new(),
+            clock: clock::system(),
             dry_run,
             test_mode,
+            journal: None,
+            audit: None,
+            order_manager: None,
+            tca: None,
+            phases: None,
+            allocator: None,
+            patterns: None,
+            cooldowns: None,
+            maintenance: None,
+            accounts: None,
+            playbooks: PlaybookRegistry::default(),
+            decision_latency: None,
+            kalshi_accounts: HashMap::new(),
+            poly_accounts: HashMap::new(),
+        }
+    }
+
+    /// Measure latency against another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Journal order submissions/results for crash recovery
+    pub fn with_journal(mut self, journal: SharedJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    fn journal(&self, event: JournalEvent) {
+        if let Some(journal) = &self.journal {
+            Journal::record(journal, event);
+        }
+    }
+
+    /// Audit opportunities, risk decisions and order actions
+    pub fn with_audit_log(mut self, audit: SharedAuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    fn audit(&self, market_id: &str, event: AuditEvent) {
+        if let Some(audit) = &self.audit {
+            audit.record_for(strategy::CROSS_VENUE_ARB, "execution", Some(market_id.to_string()), event);
+        }
+    }
+
+    /// Hand Polymarket orders to the user-channel order manager
+    pub fn with_order_manager(mut self, orders: SharedOrderManager) -> Self {
+        self.order_manager = Some(orders);
+        self
+    }
+
+    /// Claim our Polymarket legs so stream fills beyond `booked` (e.g. the excess leg) get booked once
+    fn claim_poly_orders(&self, req: &FastExecutionRequest, pair: &MarketPair, yes_order_id: &str, no_order_id: &str, booked: i64) {
+        let Some(orders) = &self.order_manager else { return };
+        // Cross-platform results carry the Poly leg in the second slot
+        let legs: &[(&str, &'static str)] = match req.arb_type {
+            ArbType::PolyYesKalshiNo => &[(no_order_id, "yes")],
+            ArbType::KalshiYesPolyNo => &[(no_order_id, "no")],
+            ArbType::PolyOnly => &[(yes_order_id, "yes"), (no_order_id, "no")],
+            ArbType::KalshiOnly => &[],
+        };
+        let mut orders = orders.lock().unwrap_or_else(|e| e.into_inner());
+        for &(order_id, side) in legs.iter().filter(|(id, _)| !id.is_empty()) {
+            let context = OrderContext {
+                market_id: pair.pair_id.to_string(),
+                description: pair.description.to_string(),
+                side,
+            };
+            orders.track(order_id, context, "buy", booked as f64);
+        }
+    }
+
+    /// Record post-trade slippage of every filled leg
+    pub fn with_tca(mut self, tca: SharedTcaStore) -> Self {
+        self.tca = Some(tca);
+        self
+    }
+
+    /// Reject arbs outside the risk / execution event phases
+    pub fn with_phase_tracker(mut self, phases: SharedPhaseTracker) -> Self {
+        self.phases = Some(phases);
+        self
+    }
+
+    /// Cap each arb at its pattern's remaining bankroll budget
+    pub fn with_capital_allocator(mut self, allocator: SharedCapitalAllocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Enforce `[patterns.enabled]` (enabled arb types, venues, positions and capital)
+    pub fn with_pattern_policy(mut self, patterns: SharedPatternPolicy) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Execute each pattern per its `[execution.playbooks]` entry instead of concurrent IOC legs
+    pub fn with_playbooks(mut self, playbooks: PlaybookRegistry) -> Self {
+        self.playbooks = playbooks;
+        self
+    }
+
+    /// Record each approved arb's detection-to-order latency under its arb type
+    pub fn with_decision_latency(mut self, decision_latency: SharedDecisionLatency) -> Self {
+        self.decision_latency = Some(decision_latency);
+        self
+    }
+
+    /// Skip markets on cooldown and feed each arb's outcome to their streaks
+    pub fn with_cooldowns(mut self, cooldowns: SharedCooldownManager) -> Self {
+        self.cooldowns = Some(cooldowns);
+        self
+    }
+
+    /// Refuse new arbs on venues about to go down for (or in) scheduled maintenance
+    pub fn with_maintenance(mut self, maintenance: SharedMaintenanceCalendar) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Route each leg through a venue account (`[accounts]`), with a client per account id.
+    /// Accounts without a client here use the engine's default client.
+    pub fn with_accounts(
+        mut self,
+        accounts: SharedAccountManager,
+        kalshi: HashMap<String, Arc<KalshiApiClient>>,
+        poly: HashMap<String, Arc<SharedAsyncClient>>,
+    ) -> Self {
+        self.accounts = Some(accounts);
+        self.kalshi_accounts = kalshi;
+        self.poly_accounts = poly;
+        self
+    }
+
+    fn kalshi_client(&self, legs: &LegAccounts) -> &Arc<KalshiApiClient> {
+        legs.kalshi.as_ref().and_then(|id| self.kalshi_accounts.get(id)).unwrap_or(&self.kalshi)
+    }
+
+    fn poly_client(&self, legs: &LegAccounts) -> &Arc<SharedAsyncClient> {
+        legs.poly.as_ref().and_then(|id| self.poly_accounts.get(id)).unwrap_or(&self.poly_async)
+    }
+
+    /// Pick an account on each venue the arb trades, capping contracts to what every one can take
+    fn route_accounts(&self, req: &FastExecutionRequest, contracts: i64) -> Result<(i64, LegAccounts), RiskRejection> {
+        let mut legs = LegAccounts::default();
+        let Some(accounts) = &self.accounts else { return Ok((contracts, legs)) };
+        let (yes, no) = (req.yes_price.cents() as f64 / 100.0, req.no_price.cents() as f64 / 100.0);
+        // Dollars per contract on (kalshi, polymarket)
+        let (kalshi_cost, poly_cost) = match req.arb_type {
+            ArbType::PolyYesKalshiNo => (no, yes),
+            ArbType::KalshiYesPolyNo => (yes, no),
+            ArbType::PolyOnly => (0.0, yes + no),
+            ArbType::KalshiOnly => (yes + no, 0.0),
+        };
+        let mut contracts = contracts;
+        if kalshi_cost > 0.0 {
+            let (id, fits) = accounts.route("kalshi", kalshi_cost, contracts)?;
+            legs.kalshi = Some(id);
+            contracts = fits;
+        }
+        if poly_cost > 0.0 {
+            let (id, fits) = accounts.route("polymarket", poly_cost, contracts)?;
+            legs.poly = Some(id);
+            contracts = fits;
+        }
+        Ok((contracts, legs))
+    }
+
+    /// Count a venue refusal against the account that sent the order
+    fn record_refusal<T>(&self, account: Option<&str>, result: &Result<T, VenueApiError>) {
+        if let (Some(accounts), Some(id), Err(e)) = (&self.accounts, account, result) {
+            if is_account_refusal(e) {
+                accounts.record_rejection(id);
+            }
+        }
+    }
+
+    fn record_cooldown(&self, market: &str, result: &ExecutionResult) {
+        if let (Some(cooldowns), Some(outcome)) = (&self.cooldowns, cooldown_outcome(result)) {
+            cooldowns.record(market, outcome);
+        }
+    }
+
+    fn record_tca(&self, req: &FastExecutionRequest, pair: &MarketPair, arrival: Arrival, slots: [(i64, i64); 2]) {
+        let Some(store) = &self.tca else { return };
+        // Cross-platform results carry the Kalshi leg in the first slot and the Poly leg in the second
+        let legs = match req.arb_type {
+            ArbType::PolyYesKalshiNo => [(Platform::Kalshi, "no", req.no_price), (Platform::Polymarket, "yes", req.yes_price)],
+            ArbType::KalshiYesPolyNo => [(Platform::Kalshi, "yes", req.yes_price), (Platform::Polymarket, "no", req.no_price)],
+            ArbType::PolyOnly => [(Platform::Polymarket, "yes", req.yes_price), (Platform::Polymarket, "no", req.no_price)],
+            ArbType::KalshiOnly => [(Platform::Kalshi, "yes", req.yes_price), (Platform::Kalshi, "no", req.no_price)],
+        };
+        let ts_ns = store.now_ns();
+        for ((platform, side, decision), (filled, cost)) in legs.into_iter().zip(slots) {
+            if filled <= 0 {
+                continue;
+            }
+            let (yes_ask, no_ask) = if platform == Platform::Kalshi { arrival.kalshi } else { arrival.poly };
+            let Some((arrival_price, arrival_mid)) = tca::touch_and_mid(yes_ask, no_ask, side) else { continue };
+            store.record(&TcaFill {
+                ts_ns,
+                market_id: pair.pair_id.to_string(),
+                platform,
+                // Cross-platform requests don't carry the signal's tier
+                tier: None,
+                side: side.to_string(),
+                action: "buy".to_string(),
+                pattern: format!("{:?}", req.arb_type),
+                strategy_id: Some(strategy::CROSS_VENUE_ARB.to_string()),
+                contracts: filled as f64,
+                decision_price: decision.cents() as f64,
+                arrival_price,
+                arrival_mid,
+                fill_price: cost as f64 / filled as f64,
+                decision_to_arrival_ns: arrival.mono.saturating_sub(req.detected_ns).0,
+            });
         }
     }
 
     /// Process an execution request
     #[inline]
-    pub async fn process(&self, req: FastExecutionRequest) -> Result<ExecutionResult> {
+    pub async fn process(&self, req: FastExecutionRequest) -> Result<ExecutionResult, ExecutionError> {
         let market_id = req.market_id;
 
-        // Deduplication check (512 markets via 8x u64 bitmask)
-        if market_id < 512 {
-            let slot = (market_id / 64) as usize;
-            let bit = market_id % 64;
-            let mask = 1u64 << bit;
-            let prev = self.in_flight[slot].fetch_or(mask, Ordering::AcqRel);
-            if prev & mask != 0 {
-                return Ok(ExecutionResult {
-                    market_id,
-                    success: false,
-                    profit_cents: 0,
-                    latency_ns: self.clock.now_ns() - req.detected_ns,
-                    error: Some("Already in-flight"),
-                });
-            }
+        // Deduplication check (every market slot via u64 bitmask)
+        let (slot, mask) = in_flight_bit(market_id);
+        let prev = self.in_flight[slot].fetch_or(mask, Ordering::AcqRel);
+        if prev & mask != 0 {
+            return Ok(ExecutionResult {
+                market_id,
+                success: false,
+                profit_cents: 0,
+                latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                error: Some(ExecutionError::AlreadyInFlight(market_id)),
+            });
         }
 
         // Get market pair 
-        let market = self.state.get_by_id(market_id)
-            .ok_or_else(|| anyhow!("Unknown market_id {}", market_id))?;
-
-        let pair = market.pair.as_ref()
-            .ok_or_else(|| anyhow!("No pair for market_id {}", market_id))?;
+        let Some(pair) = self.state.get_by_id(market_id).and_then(|m| m.pair.as_ref()) else {
+            self.release_in_flight(market_id);
+            return Err(ExecutionError::UnknownMarket(market_id));
+        };
 
         // Calculate profit
         let profit_cents = req.profit_cents();
+        self.audit(&pair.pair_id, AuditEvent::SignalConsidered {
+            strategy: format!("{:?}", req.arb_type),
+            detail: format!("y={} n={} yes_size={} no_size={}", req.yes_price, req.no_price, req.yes_size, req.no_size),
+            edge: profit_cents as f64,
+            confidence: None,
+        });
         if profit_cents < 1 {
             self.release_in_flight(market_id);
+            let error = ExecutionError::ProfitBelowThreshold { profit_cents };
+            self.audit(&pair.pair_id, AuditEvent::rejected(&error));
             return Ok(ExecutionResult {
                 market_id,
                 success: false,
                 profit_cents: 0,
-                latency_ns: self.clock.now_ns() - req.detected_ns,
-                error: Some("Profit below threshold"),
+                latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                error: Some(error),
             });
         }
 
         // Calculate max contracts from size (min of both sides)
-        let mut max_contracts = (req.yes_size.min(req.no_size) / 100) as i64;
+        let mut max_contracts = req.yes_size.min(req.no_size).contracts();
 
         // SAFETY: In test mode, cap at 10 contracts
         // Note: Polymarket has $1 minimum spend, so at 40¢ price, 1 contract = $0.40 (rejected!)
         // 10 contracts ensures we meet the minimum at any reasonable price
         if self.test_mode && max_contracts > 10 {
-            warn!("[EXEC] ⚠️ TEST_MODE: Capping contracts from {} to 10", max_contracts);
+            warn!(market_id = %pair.pair_id, "[EXEC] TEST_MODE: Capping contracts from {} to 10", max_contracts);
             max_contracts = 10;
         }
 
         if max_contracts < 1 {
-            warn!(
-                "[EXEC] Liquidity fail: {:?} | yes_size={}¢ no_size={}¢",
-                req.arb_type, req.yes_size, req.no_size
+            crate::hot_path!(
+                Level::WARN,
+                market_id = %pair.pair_id,
+                arb_type = ?req.arb_type,
+                yes_size = %req.yes_size,
+                no_size = %req.no_size,
+                "[EXEC] Liquidity fail"
             );
             self.release_in_flight(market_id);
+            let error = ExecutionError::InsufficientLiquidity {
+                yes_size: req.yes_size,
+                no_size: req.no_size,
+            };
+            self.audit(&pair.pair_id, AuditEvent::rejected(&error));
             return Ok(ExecutionResult {
                 market_id,
                 success: false,
                 profit_cents: 0,
-                latency_ns: self.clock.now_ns() - req.detected_ns,
-                error: Some("Insufficient liquidity"),
+                latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                error: Some(error),
             });
         }
 
-        // Circuit breaker check
-        if let Err(_reason) = self.circuit_breaker.can_execute(&pair.pair_id, max_contracts).await {
-            self.release_in_flight(market_id);
-            return Ok(ExecutionResult {
-                market_id,
-                success: false,
-                profit_cents: 0,
-                latency_ns: self.clock.now_ns() - req.detected_ns,
-                error: Some("Circuit breaker"),
-            });
-        }
+        // Market cooldown, venue maintenance, event phase, pattern policy, pattern budget, account and circuit breaker checks
+        let pattern = format!("{:?}", req.arb_type);
+        let playbook = self.playbooks.get(&pattern);
+        let cost_per_contract = (req.yes_price.cents() + req.no_price.cents()) as f64 / 100.0;
+        let cooldown_check = match &self.cooldowns {
+            Some(cooldowns) => cooldowns.check(&pair.pair_id),
+            None => Ok(()),
+        };
+        let maintenance_check = cooldown_check.and_then(|()| match &self.maintenance {
+            Some(maintenance) => maintenance.check(arb_venues(req.arb_type)),
+            None => Ok(()),
+        });
+        let phase_check = maintenance_check.and_then(|()| match &self.phases {
+            Some(phases) => phases.check(&pair.kalshi_market_ticker),
+            None => Ok(()),
+        });
+        let policy_check = phase_check.and_then(|()| match &self.patterns {
+            Some(patterns) => {
+                patterns.cap_contracts(&pattern, None, arb_venues(req.arb_type), cost_per_contract, max_contracts)
+            }
+            None => Ok(max_contracts),
+        });
+        let budget_check = policy_check.and_then(|contracts| match &self.allocator {
+            Some(allocator) => allocator.cap_contracts(&pattern, cost_per_contract, contracts),
+            None => Ok(contracts),
+        });
+        let account_check = budget_check.and_then(|contracts| self.route_accounts(&req, contracts));
+        let risk_check = match account_check {
+            Ok((contracts, legs)) => {
+                max_contracts = contracts;
+                self.circuit_breaker.can_execute(&pair.pair_id, max_contracts).await
+                    .map(|()| legs)
+                    .map_err(RiskRejection::from)
+            }
+            Err(rejection) => Err(rejection),
+        };
+        let legs = match risk_check {
+            Ok(legs) => legs,
+            Err(rejection) => {
+                self.release_in_flight(market_id);
+                self.audit(&pair.pair_id, AuditEvent::rejected(&rejection));
+                return Ok(ExecutionResult {
+                    market_id,
+                    success: false,
+                    profit_cents: 0,
+                    latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                    error: Some(rejection.into()),
+                });
+            }
+        };
+        self.audit(&pair.pair_id, AuditEvent::approved(Some(max_contracts)));
 
-        let latency_to_exec = self.clock.now_ns() - req.detected_ns;
+        let latency_to_exec = self.clock.mono_ns().saturating_sub(req.detected_ns);
+        if let Some(decision_latency) = &self.decision_latency {
+            decision_latency.record(&pattern, ARB_TIER, latency_to_exec.0);
+        }
         info!(
-            "[EXEC] 🎯 {} | {:?} y={}¢ n={}¢ | profit={}¢ | {}x | {}µs",
-            pair.description,
-            req.arb_type,
-            req.yes_price,
-            req.no_price,
+            market_id = %pair.pair_id,
+            arb_type = ?req.arb_type,
+            yes_price = %req.yes_price,
+            no_price = %req.no_price,
             profit_cents,
-            max_contracts,
-            latency_to_exec / 1000
+            contracts = max_contracts,
+            order_type = ?playbook.order_type,
+            latency_us = latency_to_exec.as_micros(),
+            "[EXEC] {}",
+            pair.description
         );
 
         if self.dry_run {
-            info!("[EXEC] 🏃 DRY RUN - would execute {} contracts", max_contracts);
+            info!(market_id = %pair.pair_id, "[EXEC] DRY RUN - would execute {} contracts", max_contracts);
+            self.audit(&pair.pair_id, AuditEvent::order(OrderAction::Simulated, None, Some(max_contracts), None));
             self.release_in_flight_delayed(market_id);
             return Ok(ExecutionResult {
                 market_id,
                 success: true,
                 profit_cents,
                 latency_ns: latency_to_exec,
-                error: Some("DRY_RUN"),
+                error: None,
             });
         }
 
+        let arb_id = format!("{}@{}", pair.pair_id, self.clock.wall_ns().0);
+        self.journal(JournalEvent::ArbSubmitted {
+            arb_id: arb_id.clone(),
+            market_id: pair.pair_id.to_string(),
+            arb_type: format!("{:?}", req.arb_type),
+            contracts: max_contracts,
+            yes_price: req.yes_price.cents(),
+            no_price: req.no_price.cents(),
+        });
+        self.audit(&pair.pair_id, AuditEvent::order(OrderAction::Submitted, None, Some(max_contracts), None));
+
+        let arrival = self.tca.as_ref().and_then(|_| self.state.get_by_id(market_id)).map(|market| {
+            let (kalshi_yes, kalshi_no, _, _) = market.kalshi.load();
+            let (poly_yes, poly_no, _, _) = market.poly.load();
+            Arrival { mono: self.clock.mono_ns(), kalshi: (kalshi_yes, kalshi_no), poly: (poly_yes, poly_no) }
+        });
+
         // Execute both legs concurrently 
-        let result = self.execute_both_legs_async(&req, pair, max_contracts).await;
+        let result = self.execute_both_legs_async(&req, pair, max_contracts, &legs, playbook).await;
 
         // Release in-flight after delay
         self.release_in_flight_delayed(market_id);
@@ -196,8 +553,29 @@ impl ExecutionEngine {
         match result {
             // Note: For same-platform arbs (PolyOnly/KalshiOnly), these are YES/NO fills, not platform fills
             Ok((yes_filled, no_filled, yes_cost, no_cost, yes_order_id, no_order_id)) => {
+                self.journal(JournalEvent::ArbCompleted {
+                    arb_id,
+                    market_id: pair.pair_id.to_string(),
+                    yes_order_id: yes_order_id.clone(),
+                    no_order_id: no_order_id.clone(),
+                    yes_filled,
+                    no_filled,
+                    yes_cost,
+                    no_cost,
+                });
                 let matched = yes_filled.min(no_filled);
                 let success = matched > 0;
+                let action = match (yes_filled, no_filled) {
+                    (0, 0) => OrderAction::Unfilled,
+                    (y, n) if y == n => OrderAction::Filled,
+                    _ => OrderAction::PartiallyFilled,
+                };
+                self.audit(&pair.pair_id, AuditEvent::order(
+                    action,
+                    Some(format!("{}/{}", yes_order_id, no_order_id)),
+                    Some(matched),
+                    Some(format!("yes_filled={} no_filled={} yes_cost={}¢ no_cost={}¢", yes_filled, no_filled, yes_cost, no_cost)),
+                ));
                 let actual_profit = matched as i16 * 100 - (yes_cost + no_cost) as i16;
 
                 // === AUTO-CLOSE MISMATCHED EXPOSURE (non-blocking) ===
@@ -209,31 +587,35 @@ impl ExecutionEngine {
                         ArbType::PolyOnly => ("P_yes", "P_no"),
                         ArbType::KalshiOnly => ("K_yes", "K_no"),
                     };
-                    warn!("[EXEC] ⚠️ Fill mismatch: {}={} {}={} (excess={})",
+                    warn!(market_id = %pair.pair_id, "[EXEC] Fill mismatch: {}={} {}={} (excess={})",
                         leg1_name, yes_filled, leg2_name, no_filled, excess);
 
-                    // Spawn auto-close in background (don't block hot path with 2s sleep)
-                    let kalshi = self.kalshi.clone();
-                    let poly_async = self.poly_async.clone();
-                    let arb_type = req.arb_type;
-                    let yes_price = req.yes_price;
-                    let no_price = req.no_price;
-                    let poly_yes_token = pair.poly_yes_token.clone();
-                    let poly_no_token = pair.poly_no_token.clone();
-                    let kalshi_ticker = pair.kalshi_market_ticker.clone();
-                    let original_cost_per_contract = if yes_filled > no_filled {
-                        if yes_filled > 0 { yes_cost / yes_filled } else { 0 }
+                    if !playbook.unwinds() {
+                        info!(market_id = %pair.pair_id, "[EXEC] Holding {} unmatched ({} playbook)", excess, pattern);
                     } else {
-                        if no_filled > 0 { no_cost / no_filled } else { 0 }
-                    };
+                        // Spawn auto-close in background (don't block hot path with 2s sleep)
+                        let kalshi = self.kalshi_client(&legs).clone();
+                        let poly_async = self.poly_client(&legs).clone();
+                        let arb_type = req.arb_type;
+                        let yes_price = req.yes_price;
+                        let no_price = req.no_price;
+                        let poly_yes_token = pair.poly_yes_token.clone();
+                        let poly_no_token = pair.poly_no_token.clone();
+                        let kalshi_ticker = pair.kalshi_market_ticker.clone();
+                        let original_cost_per_contract = if yes_filled > no_filled {
+                            if yes_filled > 0 { yes_cost / yes_filled } else { 0 }
+                        } else {
+                            if no_filled > 0 { no_cost / no_filled } else { 0 }
+                        };
 
-                    tokio::spawn(async move {
-                        Self::auto_close_background(
-                            kalshi, poly_async, arb_type, yes_filled, no_filled,
-                            yes_price, no_price, poly_yes_token, poly_no_token,
-                            kalshi_ticker, original_cost_per_contract
-                        ).await;
-                    });
+                        tokio::spawn(async move {
+                            Self::auto_close_background(
+                                kalshi, poly_async, arb_type, yes_filled, no_filled,
+                                yes_price, no_price, poly_yes_token, poly_no_token,
+                                kalshi_ticker, original_cost_per_contract
+                            ).await;
+                        });
+                    }
                 }
 
                 if success {
@@ -248,210 +630,167 @@ impl ExecutionEngine {
                         ArbType::KalshiOnly => ("kalshi", "yes", "kalshi", "no"),
                     };
 
-                    self.position_channel.record_fill(FillRecord::new(
+                    let tag = |fill: FillRecord, venue: &str| match legs.for_venue(venue) {
+                        Some(account) => fill.with_account(account),
+                        None => fill,
+                    };
+                    self.position_channel.record_fill(tag(FillRecord::new(
                         &pair.pair_id, &pair.description, platform1, side1,
                         matched as f64, yes_cost as f64 / 100.0 / yes_filled.max(1) as f64,
                         0.0, &yes_order_id,
-                    ));
-                    self.position_channel.record_fill(FillRecord::new(
+                    ).with_pattern(&pattern), platform1));
+                    self.position_channel.record_fill(tag(FillRecord::new(
                         &pair.pair_id, &pair.description, platform2, side2,
                         matched as f64, no_cost as f64 / 100.0 / no_filled.max(1) as f64,
                         0.0, &no_order_id,
-                    ));
+                    ).with_pattern(&pattern), platform2));
+                    if let Some(accounts) = &self.accounts {
+                        for (venue, cost) in [(platform1, yes_cost), (platform2, no_cost)] {
+                            if let Some(account) = legs.for_venue(venue) {
+                                accounts.open(account, cost as f64 / 100.0);
+                            }
+                        }
+                        let edge_cents = actual_profit as f64 / matched as f64;
+                        for account in [legs.kalshi.as_deref(), legs.poly.as_deref()].into_iter().flatten() {
+                            accounts.record_fill(account, edge_cents);
+                        }
+                    }
+                    if let Some(patterns) = &self.patterns {
+                        patterns.open(&pattern, (yes_cost + no_cost) as f64 / 100.0);
+                    }
+                }
+                if let Some(allocator) = &self.allocator {
+                    allocator.commit(&pattern, (yes_cost + no_cost) as f64 / 100.0);
+                }
+                self.claim_poly_orders(&req, pair, &yes_order_id, &no_order_id, matched);
+                if let Some(arrival) = arrival {
+                    self.record_tca(&req, pair, arrival, [(yes_filled, yes_cost), (no_filled, no_cost)]);
                 }
 
-                Ok(ExecutionResult {
+                let result = ExecutionResult {
                     market_id,
                     success,
                     profit_cents: actual_profit,
-                    latency_ns: self.clock.now_ns() - req.detected_ns,
-                    error: if success { None } else { Some("Partial/no fill") },
-                })
+                    latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                    error: (!success).then_some(ExecutionError::Unfilled { yes_filled, no_filled }),
+                };
+                self.record_cooldown(&pair.pair_id, &result);
+                Ok(result)
             }
-            Err(_e) => {
+            Err(e) => {
+                self.journal(JournalEvent::ArbFailed {
+                    arb_id,
+                    market_id: pair.pair_id.to_string(),
+                    error: e.to_string(),
+                });
+                self.audit(&pair.pair_id, AuditEvent::order(OrderAction::Failed, None, Some(max_contracts), Some(e.to_string())));
                 self.circuit_breaker.record_error().await;
-                Ok(ExecutionResult {
+                let result = ExecutionResult {
                     market_id,
                     success: false,
                     profit_cents: 0,
-                    latency_ns: self.clock.now_ns() - req.detected_ns,
-                    error: Some("Execution failed"),
-                })
+                    latency_ns: self.clock.mono_ns().saturating_sub(req.detected_ns),
+                    error: Some(e),
+                };
+                self.record_cooldown(&pair.pair_id, &result);
+                Ok(result)
             }
         }
     }
 
+    /// Send both legs as the pattern's playbook says: at once or one after the other (the second
+    /// sized to the first's fill), IOC or resting, crossing a resting leg's unfilled rest if asked
     async fn execute_both_legs_async(
         &self,
         req: &FastExecutionRequest,
         pair: &MarketPair,
         contracts: i64,
-    ) -> Result<(i64, i64, i64, i64, String, String)> {
-        match req.arb_type {
-            // === CROSS-PLATFORM: Poly YES + Kalshi NO ===
-            ArbType::PolyYesKalshiNo => {
-                let kalshi_fut = self.kalshi.buy_ioc(
-                    &pair.kalshi_market_ticker,
-                    "no",
-                    req.no_price as i64,
-                    contracts,
-                );
-                let poly_fut = self.poly_async.buy_fak(
-                    &pair.poly_yes_token,
-                    cents_to_price(req.yes_price),
-                    contracts as f64,
-                );
-                let (kalshi_res, poly_res) = tokio::join!(kalshi_fut, poly_fut);
-                self.extract_cross_results(kalshi_res, poly_res)
-            }
-
-            // === CROSS-PLATFORM: Kalshi YES + Poly NO ===
-            ArbType::KalshiYesPolyNo => {
-                let kalshi_fut = self.kalshi.buy_ioc(
-                    &pair.kalshi_market_ticker,
-                    "yes",
-                    req.yes_price as i64,
-                    contracts,
-                );
-                let poly_fut = self.poly_async.buy_fak(
-                    &pair.poly_no_token,
-                    cents_to_price(req.no_price),
-                    contracts as f64,
-                );
-                let (kalshi_res, poly_res) = tokio::join!(kalshi_fut, poly_fut);
-                self.extract_cross_results(kalshi_res, poly_res)
-            }
-
-            // === SAME-PLATFORM: Poly YES + Poly NO ===
-            ArbType::PolyOnly => {
-                let yes_fut = self.poly_async.buy_fak(
-                    &pair.poly_yes_token,
-                    cents_to_price(req.yes_price),
-                    contracts as f64,
-                );
-                let no_fut = self.poly_async.buy_fak(
-                    &pair.poly_no_token,
-                    cents_to_price(req.no_price),
-                    contracts as f64,
-                );
-                let (yes_res, no_res) = tokio::join!(yes_fut, no_fut);
-                self.extract_poly_only_results(yes_res, no_res)
-            }
-
-            // === SAME-PLATFORM: Kalshi YES + Kalshi NO ===
-            ArbType::KalshiOnly => {
-                let yes_fut = self.kalshi.buy_ioc(
-                    &pair.kalshi_market_ticker,
-                    "yes",
-                    req.yes_price as i64,
-                    contracts,
-                );
-                let no_fut = self.kalshi.buy_ioc(
-                    &pair.kalshi_market_ticker,
-                    "no",
-                    req.no_price as i64,
-                    contracts,
-                );
-                let (yes_res, no_res) = tokio::join!(yes_fut, no_fut);
-                self.extract_kalshi_only_results(yes_res, no_res)
-            }
-        }
-    }
-
-    /// Extract results from cross-platform execution
-    fn extract_cross_results(
-        &self,
-        kalshi_res: Result<crate::kalshi::KalshiOrderResponse>,
-        poly_res: Result<crate::polymarket_clob::PolyFillAsync>,
-    ) -> Result<(i64, i64, i64, i64, String, String)> {
-        let (kalshi_filled, kalshi_cost, kalshi_order_id) = match kalshi_res {
-            Ok(resp) => {
-                let filled = resp.order.filled_count();
-                let cost = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
-                (filled, cost, resp.order.order_id)
-            }
-            Err(e) => {
-                warn!("[EXEC] Kalshi failed: {}", e);
-                (0, 0, String::new())
-            }
-        };
-
-        let (poly_filled, poly_cost, poly_order_id) = match poly_res {
-            Ok(fill) => {
-                ((fill.filled_size as i64), (fill.fill_cost * 100.0) as i64, fill.order_id)
-            }
-            Err(e) => {
-                warn!("[EXEC] Poly failed: {}", e);
-                (0, 0, String::new())
+        legs: &LegAccounts,
+        playbook: ExecutionPlaybook,
+    ) -> Result<(i64, i64, i64, i64, String, String), ExecutionError> {
+        let [first, second] = arb_legs(req, pair);
+        let (first, second) = match lead_slot(playbook.leg_order, &[first, second]) {
+            None => tokio::join!(
+                self.buy_leg(first, contracts, playbook, legs),
+                self.buy_leg(second, contracts, playbook, legs),
+            ),
+            Some(lead) => {
+                let (lead_leg, lag_leg) = if lead == 0 { (first, second) } else { (second, first) };
+                let lead_fill = self.buy_leg(lead_leg, contracts, playbook, legs).await;
+                let lag_fill = if lead_fill.filled > 0 {
+                    self.buy_leg(lag_leg, lead_fill.filled, playbook, legs).await
+                } else {
+                    info!("[EXEC] {} unfilled, {} not sent", lead_leg, lag_leg);
+                    LegFill::default()
+                };
+                if lead == 0 { (lead_fill, lag_fill) } else { (lag_fill, lead_fill) }
             }
         };
-
-        Ok((kalshi_filled, poly_filled, kalshi_cost, poly_cost, kalshi_order_id, poly_order_id))
+        Ok((first.filled, second.filled, first.cost, second.cost, first.order_id, second.order_id))
     }
 
-    /// Extract results from Poly-only execution (same-platform)
-    fn extract_poly_only_results(
-        &self,
-        yes_res: Result<crate::polymarket_clob::PolyFillAsync>,
-        no_res: Result<crate::polymarket_clob::PolyFillAsync>,
-    ) -> Result<(i64, i64, i64, i64, String, String)> {
-        let (yes_filled, yes_cost, yes_order_id) = match yes_res {
-            Ok(fill) => {
-                ((fill.filled_size as i64), (fill.fill_cost * 100.0) as i64, fill.order_id)
+    /// One leg under the playbook, crossing a passive order's unfilled rest when the remainder
+    /// policy says so
+    async fn buy_leg(&self, leg: ArbLeg<'_>, contracts: i64, playbook: ExecutionPlaybook, legs: &LegAccounts) -> LegFill {
+        let mut fill = self.send_leg(leg, contracts, playbook, legs).await;
+        let rest = contracts - fill.filled;
+        if rest > 0 && playbook.order_type == OrderStyle::Passive && playbook.remainder == RemainderPolicy::Cross {
+            let ioc = ExecutionPlaybook { order_type: OrderStyle::Ioc, ..playbook };
+            let crossed = self.send_leg(leg, rest, ioc, legs).await;
+            fill.filled += crossed.filled;
+            fill.cost += crossed.cost;
+            if fill.order_id.is_empty() {
+                fill.order_id = crossed.order_id;
             }
-            Err(e) => {
-                warn!("[EXEC] Poly YES failed: {}", e);
-                (0, 0, String::new())
-            }
-        };
-
-        let (no_filled, no_cost, no_order_id) = match no_res {
-            Ok(fill) => {
-                ((fill.filled_size as i64), (fill.fill_cost * 100.0) as i64, fill.order_id)
-            }
-            Err(e) => {
-                warn!("[EXEC] Poly NO failed: {}", e);
-                (0, 0, String::new())
-            }
-        };
-
-        // For same-platform, return YES as "kalshi" slot and NO as "poly" slot
-        // This keeps the existing result handling logic working
-        Ok((yes_filled, no_filled, yes_cost, no_cost, yes_order_id, no_order_id))
+        }
+        fill
     }
 
-    /// Extract results from Kalshi-only execution (same-platform)
-    fn extract_kalshi_only_results(
-        &self,
-        yes_res: Result<crate::kalshi::KalshiOrderResponse>,
-        no_res: Result<crate::kalshi::KalshiOrderResponse>,
-    ) -> Result<(i64, i64, i64, i64, String, String)> {
-        let (yes_filled, yes_cost, yes_order_id) = match yes_res {
-            Ok(resp) => {
-                let filled = resp.order.filled_count();
-                let cost = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
-                (filled, cost, resp.order.order_id)
-            }
-            Err(e) => {
-                warn!("[EXEC] Kalshi YES failed: {}", e);
-                (0, 0, String::new())
-            }
-        };
-
-        let (no_filled, no_cost, no_order_id) = match no_res {
-            Ok(resp) => {
-                let filled = resp.order.filled_count();
-                let cost = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
-                (filled, cost, resp.order.order_id)
+    async fn send_leg(&self, leg: ArbLeg<'_>, contracts: i64, playbook: ExecutionPlaybook, legs: &LegAccounts) -> LegFill {
+        match leg {
+            ArbLeg::Kalshi { ticker, side, price } => {
+                let kalshi = self.kalshi_client(legs);
+                let res = match playbook.order_type {
+                    OrderStyle::Ioc => kalshi.buy_ioc(ticker, side, price.cents() as i64, contracts).await,
+                    OrderStyle::Passive => {
+                        kalshi.buy_passive(ticker, side, price.cents() as i64, contracts, playbook.timeout()).await
+                    }
+                };
+                self.record_refusal(legs.kalshi.as_deref(), &res);
+                match res {
+                    Ok(resp) => LegFill {
+                        filled: resp.order.filled_count(),
+                        cost: resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0),
+                        order_id: resp.order.order_id,
+                    },
+                    Err(e) => {
+                        warn!("[EXEC] {} failed: {}", leg, e);
+                        LegFill::default()
+                    }
+                }
             }
-            Err(e) => {
-                warn!("[EXEC] Kalshi NO failed: {}", e);
-                (0, 0, String::new())
+            ArbLeg::Poly { token, price, .. } => {
+                let poly = self.poly_client(legs);
+                let res = match playbook.order_type {
+                    OrderStyle::Ioc => poly.buy_fak(token, price.as_probability(), contracts as f64).await,
+                    OrderStyle::Passive => {
+                        poly.buy_passive(token, price.as_probability(), contracts as f64, playbook.timeout()).await
+                    }
+                };
+                self.record_refusal(legs.poly.as_deref(), &res);
+                match res {
+                    Ok(fill) => LegFill {
+                        filled: fill.filled_size as i64,
+                        cost: (fill.fill_cost * 100.0) as i64,
+                        order_id: fill.order_id,
+                    },
+                    Err(e) => {
+                        warn!("[EXEC] {} failed: {}", leg, e);
+                        LegFill::default()
+                    }
+                }
             }
-        };
-
-        // For same-platform, return YES as "kalshi" slot and NO as "poly" slot
-        Ok((yes_filled, no_filled, yes_cost, no_cost, yes_order_id, no_order_id))
+        }
     }
 
     /// Background auto-close for mismatched fills
@@ -461,8 +800,8 @@ impl ExecutionEngine {
         arb_type: ArbType,
         yes_filled: i64,
         no_filled: i64,
-        yes_price: u16,
-        no_price: u16,
+        yes_price: Price,
+        no_price: Price,
         poly_yes_token: Arc<str>,
         poly_no_token: Arc<str>,
         kalshi_ticker: Arc<str>,
@@ -477,10 +816,10 @@ impl ExecutionEngine {
         let log_close_pnl = |platform: &str, closed: i64, proceeds: i64| {
             if closed > 0 {
                 let close_pnl = proceeds - (original_cost_per_contract * excess);
-                info!("[EXEC] ✅ Closed {} {} contracts for {}¢ (P&L: {}¢)",
+                info!("[EXEC] Closed {} {} contracts for {}¢ (P&L: {}¢)",
                     closed, platform, proceeds, close_pnl);
             } else {
-                warn!("[EXEC] ⚠️ Failed to close {} excess - 0 filled", platform);
+                warn!("[EXEC] Failed to close {} excess - 0 filled", platform);
             }
         };
 
@@ -491,54 +830,54 @@ impl ExecutionEngine {
                 } else {
                     (&poly_no_token, "no", no_price)
                 };
-                let close_price = cents_to_price((price as i16).saturating_sub(10).max(1) as u16);
+                let close_price = unwind_price(price).as_probability();
 
-                info!("[EXEC] 🔄 Waiting 2s for Poly settlement before auto-close ({} {} contracts)", excess, side);
+                info!("[EXEC] Waiting 2s for Poly settlement before auto-close ({} {} contracts)", excess, side);
                 tokio::time::sleep(Duration::from_secs(2)).await;
 
                 match poly_async.sell_fak(token, close_price, excess as f64).await {
                     Ok(fill) => log_close_pnl("Poly", fill.filled_size as i64, (fill.fill_cost * 100.0) as i64),
-                    Err(e) => warn!("[EXEC] ⚠️ Failed to close Poly excess: {}", e),
+                    Err(e) => warn!("[EXEC] Failed to close Poly excess: {}", e),
                 }
             }
 
             ArbType::KalshiOnly => {
                 let (side, price) = if yes_filled > no_filled {
-                    ("yes", yes_price as i64)
+                    ("yes", yes_price)
                 } else {
-                    ("no", no_price as i64)
+                    ("no", no_price)
                 };
-                let close_price = price.saturating_sub(10).max(1);
+                let close_price = unwind_price(price).cents() as i64;
 
                 match kalshi.sell_ioc(&kalshi_ticker, side, close_price, excess).await {
                     Ok(resp) => {
                         let proceeds = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
                         log_close_pnl("Kalshi", resp.order.filled_count(), proceeds);
                     }
-                    Err(e) => warn!("[EXEC] ⚠️ Failed to close Kalshi excess: {}", e),
+                    Err(e) => warn!("[EXEC] Failed to close Kalshi excess: {}", e),
                 }
             }
 
             ArbType::PolyYesKalshiNo => {
                 if yes_filled > no_filled {
                     // Poly YES excess
-                    let close_price = cents_to_price((yes_price as i16).saturating_sub(10).max(1) as u16);
-                    info!("[EXEC] 🔄 Waiting 2s for Poly settlement before auto-close ({} yes contracts)", excess);
+                    let close_price = unwind_price(yes_price).as_probability();
+                    info!("[EXEC] Waiting 2s for Poly settlement before auto-close ({} yes contracts)", excess);
                     tokio::time::sleep(Duration::from_secs(2)).await;
 
                     match poly_async.sell_fak(&poly_yes_token, close_price, excess as f64).await {
                         Ok(fill) => log_close_pnl("Poly", fill.filled_size as i64, (fill.fill_cost * 100.0) as i64),
-                        Err(e) => warn!("[EXEC] ⚠️ Failed to close Poly excess: {}", e),
+                        Err(e) => warn!("[EXEC] Failed to close Poly excess: {}", e),
                     }
                 } else {
                     // Kalshi NO excess
-                    let close_price = (no_price as i64).saturating_sub(10).max(1);
+                    let close_price = unwind_price(no_price).cents() as i64;
                     match kalshi.sell_ioc(&kalshi_ticker, "no", close_price, excess).await {
                         Ok(resp) => {
                             let proceeds = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
                             log_close_pnl("Kalshi", resp.order.filled_count(), proceeds);
                         }
-                        Err(e) => warn!("[EXEC] ⚠️ Failed to close Kalshi excess: {}", e),
+                        Err(e) => warn!("[EXEC] Failed to close Kalshi excess: {}", e),
                     }
                 }
             }
@@ -546,23 +885,23 @@ impl ExecutionEngine {
             ArbType::KalshiYesPolyNo => {
                 if yes_filled > no_filled {
                     // Kalshi YES excess
-                    let close_price = (yes_price as i64).saturating_sub(10).max(1);
+                    let close_price = unwind_price(yes_price).cents() as i64;
                     match kalshi.sell_ioc(&kalshi_ticker, "yes", close_price, excess).await {
                         Ok(resp) => {
                             let proceeds = resp.order.taker_fill_cost.unwrap_or(0) + resp.order.maker_fill_cost.unwrap_or(0);
                             log_close_pnl("Kalshi", resp.order.filled_count(), proceeds);
                         }
-                        Err(e) => warn!("[EXEC] ⚠️ Failed to close Kalshi excess: {}", e),
+                        Err(e) => warn!("[EXEC] Failed to close Kalshi excess: {}", e),
                     }
                 } else {
                     // Poly NO excess
-                    let close_price = cents_to_price((no_price as i16).saturating_sub(10).max(1) as u16);
-                    info!("[EXEC] 🔄 Waiting 2s for Poly settlement before auto-close ({} no contracts)", excess);
+                    let close_price = unwind_price(no_price).as_probability();
+                    info!("[EXEC] Waiting 2s for Poly settlement before auto-close ({} no contracts)", excess);
                     tokio::time::sleep(Duration::from_secs(2)).await;
 
                     match poly_async.sell_fak(&poly_no_token, close_price, excess as f64).await {
                         Ok(fill) => log_close_pnl("Poly", fill.filled_size as i64, (fill.fill_cost * 100.0) as i64),
-                        Err(e) => warn!("[EXEC] ⚠️ Failed to close Poly excess: {}", e),
+                        Err(e) => warn!("[EXEC] Failed to close Poly excess: {}", e),
                     }
                 }
             }
@@ -570,37 +909,85 @@ impl ExecutionEngine {
     }
 
     #[inline(always)]
-    fn release_in_flight(&self, market_id: u16) {
-        if market_id < 512 {
-            let slot = (market_id / 64) as usize;
-            let bit = market_id % 64;
-            let mask = !(1u64 << bit);
-            self.in_flight[slot].fetch_and(mask, Ordering::Release);
-        }
+    fn release_in_flight(&self, market_id: MarketId) {
+        let (slot, mask) = in_flight_bit(market_id);
+        self.in_flight[slot].fetch_and(!mask, Ordering::Release);
     }
 
-    fn release_in_flight_delayed(&self, market_id: u16) {
-        if market_id < 512 {
-            let in_flight = self.in_flight.clone();
-            let slot = (market_id / 64) as usize;
-            let bit = market_id % 64;
-            tokio::spawn(async move {
-                tokio::time::sleep(Duration::from_secs(10)).await;
-                let mask = !(1u64 << bit);
-                in_flight[slot].fetch_and(mask, Ordering::Release);
-            });
+    fn release_in_flight_delayed(&self, market_id: MarketId) {
+        let in_flight = self.in_flight.clone();
+        let (slot, mask) = in_flight_bit(market_id);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            in_flight[slot].fetch_and(!mask, Ordering::Release);
+        });
+    }
+}
+
+/// An arb's legs in result-slot order: cross-platform arbs carry the Kalshi leg first and the
+/// Poly leg second, same-platform arbs YES then NO
+fn arb_legs<'a>(req: &FastExecutionRequest, pair: &'a MarketPair) -> [ArbLeg<'a>; 2] {
+    let kalshi = |side, price| ArbLeg::Kalshi { ticker: &pair.kalshi_market_ticker, side, price };
+    match req.arb_type {
+        ArbType::PolyYesKalshiNo => [
+            kalshi("no", req.no_price),
+            ArbLeg::Poly { token: &pair.poly_yes_token, side: "yes", price: req.yes_price },
+        ],
+        ArbType::KalshiYesPolyNo => [
+            kalshi("yes", req.yes_price),
+            ArbLeg::Poly { token: &pair.poly_no_token, side: "no", price: req.no_price },
+        ],
+        ArbType::PolyOnly => [
+            ArbLeg::Poly { token: &pair.poly_yes_token, side: "yes", price: req.yes_price },
+            ArbLeg::Poly { token: &pair.poly_no_token, side: "no", price: req.no_price },
+        ],
+        ArbType::KalshiOnly => [kalshi("yes", req.yes_price), kalshi("no", req.no_price)],
+    }
+}
+
+/// Slot of the leg sent first (None = both at once); single-venue arbs lead with YES
+fn lead_slot(order: LegOrder, legs: &[ArbLeg<'_>; 2]) -> Option<usize> {
+    match (order, legs) {
+        (LegOrder::Concurrent, _) => None,
+        (LegOrder::PolyFirst, [ArbLeg::Kalshi { .. }, ArbLeg::Poly { .. }]) => Some(1),
+        _ => Some(0),
+    }
+}
+
+/// Word index and bit mask for a market's in-flight flag
+#[inline(always)]
+fn in_flight_bit(market_id: MarketId) -> (usize, u64) {
+    (market_id.index() / 64, 1u64 << (market_id.index() % 64))
+}
+
+/// What an attempted arb says about its market. Our own risk limits and venue-wide
+/// failures (rate limits, transport, auth) are not the market's fault and don't count.
+fn cooldown_outcome(result: &ExecutionResult) -> Option<TradeOutcome> {
+    match &result.error {
+        None if result.profit_cents < 0 => Some(TradeOutcome::Loss),
+        None => Some(TradeOutcome::Win),
+        Some(ExecutionError::Unfilled { .. }) => Some(TradeOutcome::Limited),
+        Some(ExecutionError::Venue(VenueApiError::InvalidOrder { .. } | VenueApiError::Http { .. })) => {
+            Some(TradeOutcome::Rejected)
         }
+        Some(_) => None,
     }
 }
 
+/// Aggressive exit price for unwinding an unmatched leg (never below 1¢)
+#[inline(always)]
+fn unwind_price(entry: Price) -> Price {
+    entry.saturating_sub(CLOSE_DISCOUNT).max(Price(1))
+}
+
 /// Execution result
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug)]
 pub struct ExecutionResult {
-    pub market_id: u16,
+    pub market_id: MarketId,
     pub success: bool,
     pub profit_cents: i16,
-    pub latency_ns: u64,
-    pub error: Option<&'static str>,
+    pub latency_ns: Nanos,
+    pub error: Option<ExecutionError>,
 }
 
 /// Create execution channel
@@ -620,27 +1007,32 @@ pub async fn run_execution_loop(
 
         // Process immediately in spawned task
         tokio::spawn(async move {
-            match engine.process(req).await {
-                Ok(result) if result.success => {
-                    info!(
-                        "[EXEC] ✅ market_id={} profit={}¢ latency={}µs",
-                        result.market_id, result.profit_cents, result.latency_ns / 1000
-                    );
-                }
-                Ok(result) => {
-                    if result.error != Some("Already in-flight") {
-                        warn!(
-                            "[EXEC] ⚠️ market_id={}: {:?}",
-                            result.market_id, result.error
-                        );
-                    }
-                }
-                Err(e) => {
-                    error!("[EXEC] ❌ Error: {}", e);
-                }
-            }
+            log_execution_result(&engine.process(req).await);
         });
     }
 
     info!("[EXEC] Execution engine stopped");
+}
+
+/// Log the outcome of one processed request
+pub fn log_execution_result(outcome: &Result<ExecutionResult, ExecutionError>) {
+    match outcome {
+        Ok(result) if result.success => {
+            info!(
+                slot = %result.market_id,
+                profit_cents = result.profit_cents,
+                latency_us = result.latency_ns.as_micros(),
+                "[EXEC] Executed"
+            );
+        }
+        Ok(ExecutionResult { error: Some(ExecutionError::AlreadyInFlight(_)), .. }) => {}
+        Ok(result) => {
+            if let Some(err) = &result.error {
+                crate::hot_path!(Level::WARN, slot = %result.market_id, error = %err, "[EXEC] Rejected");
+            }
+        }
+        Err(e) => {
+            error!(error = %e, "[EXEC] Error");
+        }
+    }
 }
\ No newline at end of file
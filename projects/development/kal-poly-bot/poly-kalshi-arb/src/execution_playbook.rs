@@ -0,0 +1,148 @@
+// src/execution_playbook.rs
+// Execution playbooks - per-pattern order type, leg ordering, resting timeout and remainder policy
+// (`[execution.playbooks]`), looked up by the execution engine for every arb
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::config::ExecutionSection;
+
+/// How each leg is sent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStyle {
+    /// Take what is there now, cancel the rest (Kalshi IOC, Polymarket FAK)
+    #[default]
+    Ioc,
+    /// Rest a limit at the signal price for `timeout_ms`, then cancel what is left
+    Passive,
+}
+
+/// Which leg goes out first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LegOrder {
+    /// Both legs at once
+    #[default]
+    Concurrent,
+    /// Kalshi leg first; the other leg is sized to its fill (YES first on a single venue)
+    KalshiFirst,
+    /// Polymarket leg first; the other leg is sized to its fill (YES first on a single venue)
+    PolyFirst,
+}
+
+impl LegOrder {
+    pub fn is_sequential(self) -> bool {
+        self != LegOrder::Concurrent
+    }
+}
+
+/// What happens to the part of an arb that did not fill
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemainderPolicy {
+    /// Drop the unfilled rest and unwind any unmatched leg
+    #[default]
+    Cancel,
+    /// Send a leg's unfilled rest as IOC at the signal price, then unwind any unmatched leg
+    Cross,
+    /// Drop the unfilled rest and keep any unmatched leg open
+    Hold,
+}
+
+/// Execution tactics for one pattern; the default is the engine's original behavior
+/// (concurrent IOC legs, unmatched exposure unwound)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutionPlaybook {
+    pub order_type: OrderStyle,
+    pub leg_order: LegOrder,
+    /// How long passive orders rest before the unfilled rest is cancelled
+    pub timeout_ms: u64,
+    pub remainder: RemainderPolicy,
+}
+
+impl ExecutionPlaybook {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    /// Whether a leg imbalance left after execution is dumped
+    pub fn unwinds(&self) -> bool {
+        self.remainder != RemainderPolicy::Hold
+    }
+}
+
+/// Playbooks keyed by pattern id as in `[patterns.enabled]` (`"56"` for worker patterns,
+/// `"PolyYesKalshiNo"` for arb types); unlisted patterns get the default playbook
+#[derive(Debug, Clone, Default)]
+pub struct PlaybookRegistry {
+    playbooks: BTreeMap<String, ExecutionPlaybook>,
+}
+
+impl PlaybookRegistry {
+    pub fn new(playbooks: BTreeMap<String, ExecutionPlaybook>) -> Self {
+        Self { playbooks }
+    }
+
+    pub fn from_section(section: &ExecutionSection) -> Self {
+        Self::new(section.playbooks.clone())
+    }
+
+    pub fn get(&self, pattern: &str) -> ExecutionPlaybook {
+        self.playbooks.get(pattern).copied().unwrap_or_default()
+    }
+
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.playbooks.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlisted_patterns_get_default_playbook() {
+        let mut playbooks = BTreeMap::new();
+        playbooks.insert("56".to_string(), ExecutionPlaybook {
+            leg_order: LegOrder::KalshiFirst,
+            ..ExecutionPlaybook::default()
+        });
+        let registry = PlaybookRegistry::new(playbooks);
+        assert_eq!(registry.get("56").leg_order, LegOrder::KalshiFirst);
+        assert_eq!(registry.get("73"), ExecutionPlaybook::default());
+        assert_eq!(registry.patterns().collect::<Vec<_>>(), vec!["56"]);
+
+        let default = ExecutionPlaybook::default();
+        assert_eq!((default.order_type, default.leg_order), (OrderStyle::Ioc, LegOrder::Concurrent));
+        assert!(default.unwinds() && !default.leg_order.is_sequential());
+    }
+
+    #[test]
+    fn test_playbook_toml() {
+        let section: ExecutionSection = toml::from_str(
+            r#"
+            [playbooks.73]
+            order_type = "passive"
+            timeout_ms = 1500
+            remainder = "cross"
+
+            [playbooks.PolyYesKalshiNo]
+            leg_order = "poly_first"
+            remainder = "hold"
+            "#,
+        ).unwrap();
+        let registry = PlaybookRegistry::from_section(&section);
+        let passive = registry.get("73");
+        assert_eq!(passive.order_type, OrderStyle::Passive);
+        assert_eq!(passive.timeout(), Duration::from_millis(1500));
+        assert_eq!(passive.remainder, RemainderPolicy::Cross);
+        assert_eq!(passive.leg_order, LegOrder::Concurrent);
+        let held = registry.get("PolyYesKalshiNo");
+        assert!(held.leg_order.is_sequential() && !held.unwinds());
+
+        assert!(toml::from_str::<ExecutionSection>("[playbooks.56]\norder_type = \"gtc\"").is_err());
+    }
+}
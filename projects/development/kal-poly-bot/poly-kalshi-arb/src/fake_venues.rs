@@ -0,0 +1,610 @@
+// src/fake_venues.rs
+// Fake venues - one in-process axum server standing in for Kalshi and Polymarket: both WS book
+// feeds and the REST order endpoints the execution engine calls, matching orders against
+// scripted books and recording every order (feature "fake-venues"; see tests/e2e_pipeline.rs)
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use base64::{engine::general_purpose::URL_SAFE, Engine as _};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::types::Platform;
+
+/// Kalshi book for one market as its orderbook snapshot carries it: bids per side,
+/// (price cents, contracts). YES is bought against NO bids and vice versa
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KalshiBook {
+    pub yes_bids: Vec<(i64, i64)>,
+    pub no_bids: Vec<(i64, i64)>,
+}
+
+impl KalshiBook {
+    /// Take up to `count` contracts at `price` or better; returns (filled, cost cents)
+    fn take(&mut self, action: &str, side: &str, price: i64, count: i64) -> (i64, i64) {
+        // Buying a side lifts the other side's bids at 100 - bid; selling hits its own bids
+        let (levels, complement) = match (action, side) {
+            ("buy", "yes") => (&mut self.no_bids, true),
+            ("buy", _) => (&mut self.yes_bids, true),
+            (_, "yes") => (&mut self.yes_bids, false),
+            _ => (&mut self.no_bids, false),
+        };
+        levels.sort_by_key(|(bid, _)| std::cmp::Reverse(*bid));
+        let (mut filled, mut cost) = (0, 0);
+        for (bid, qty) in levels.iter_mut() {
+            let level_price = if complement { 100 - *bid } else { *bid };
+            let crosses = if complement { level_price <= price } else { level_price >= price };
+            if filled == count || !crosses {
+                break;
+            }
+            let take = (*qty).min(count - filled);
+            *qty -= take;
+            filled += take;
+            cost += take * level_price;
+        }
+        levels.retain(|(_, qty)| *qty > 0);
+        (filled, cost)
+    }
+}
+
+/// Polymarket book for one token, (price, shares)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PolyBook {
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl PolyBook {
+    /// Take up to `size` shares at `price` or better; returns (filled, cost dollars)
+    fn take(&mut self, buy: bool, price: f64, size: f64) -> (f64, f64) {
+        let levels = if buy { &mut self.asks } else { &mut self.bids };
+        if buy {
+            levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        } else {
+            levels.sort_by(|a, b| b.0.total_cmp(&a.0));
+        }
+        let (mut filled, mut cost) = (0.0, 0.0);
+        for (level_price, qty) in levels.iter_mut() {
+            let crosses = if buy { *level_price <= price + 1e-9 } else { *level_price >= price - 1e-9 };
+            if filled >= size || !crosses {
+                break;
+            }
+            let take = qty.min(size - filled);
+            *qty -= take;
+            filled += take;
+            cost += take * *level_price;
+        }
+        levels.retain(|(_, qty)| *qty > 0.0);
+        (filled, cost)
+    }
+}
+
+/// Where an order ended up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FakeOrderStatus {
+    Filled,
+    /// Unfilled rest left on the book (non-IOC orders) until cancelled
+    Resting,
+    /// IOC rest dropped, or a resting order cancelled
+    Cancelled,
+    /// Refused by `reject_orders`
+    Rejected,
+}
+
+/// An order a fake venue received
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeOrder {
+    pub venue: Platform,
+    pub order_id: String,
+    /// Kalshi market ticker or Polymarket token id
+    pub market: String,
+    /// Kalshi "yes" / "no" (a Polymarket token is a single outcome)
+    pub side: Option<String>,
+    /// "buy" / "sell"
+    pub action: String,
+    pub price_cents: i64,
+    pub size: f64,
+    pub filled: f64,
+    pub fill_cost_cents: i64,
+    pub status: FakeOrderStatus,
+}
+
+/// Market whose book changed, pushed to subscribed feeds
+#[derive(Debug, Clone)]
+enum BookUpdate {
+    Kalshi(String),
+    Poly(String),
+}
+
+#[derive(Debug, Default)]
+struct Books {
+    kalshi: HashMap<String, KalshiBook>,
+    poly: HashMap<String, PolyBook>,
+    /// Kalshi tickers / Polymarket tokens whose orders are refused
+    rejecting: HashSet<String>,
+    orders: Vec<FakeOrder>,
+}
+
+impl Books {
+    fn record(&mut self, mut order: FakeOrder) -> FakeOrder {
+        order.order_id = format!("fake-{}", self.orders.len() + 1);
+        self.orders.push(order.clone());
+        order
+    }
+
+    fn order_mut(&mut self, venue: Platform, order_id: &str) -> Option<&mut FakeOrder> {
+        self.orders.iter_mut().find(|o| o.venue == venue && o.order_id == order_id)
+    }
+}
+
+struct Venue {
+    books: Mutex<Books>,
+    updates: broadcast::Sender<BookUpdate>,
+}
+
+type SharedVenue = Arc<Venue>;
+
+/// Running fake venues; the server stops on drop
+pub struct FakeVenues {
+    addr: SocketAddr,
+    venue: SharedVenue,
+    server: JoinHandle<()>,
+}
+
+impl FakeVenues {
+    /// Bind on an ephemeral localhost port and serve on the current runtime
+    pub async fn start() -> Result<Self> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.context("bind fake venues")?;
+        let addr = listener.local_addr()?;
+        let (updates, _) = broadcast::channel(256);
+        let venue = Arc::new(Venue { books: Mutex::new(Books::default()), updates });
+        let app = router(venue.clone());
+        let server = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::error!("[FAKE] Server stopped: {}", e);
+            }
+        });
+        Ok(Self { addr, venue, server })
+    }
+
+    /// Serve from a runtime on a dedicated thread, so the fakes outlive the runtime of any one
+    /// test (the endpoint overrides are read once per process, so tests share one instance)
+    pub fn start_detached() -> Result<Self> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::Builder::new().name("fake-venues".into()).spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_multi_thread().worker_threads(2).enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    let _ = tx.send(Err(e.into()));
+                    return;
+                }
+            };
+            runtime.block_on(async move {
+                let _ = tx.send(Self::start().await);
+                std::future::pending::<()>().await;
+            });
+        })?;
+        rx.recv().context("fake venues thread exited")?
+    }
+
+    pub fn kalshi_api_url(&self) -> String {
+        format!("http://{}/kalshi/trade-api/v2", self.addr)
+    }
+
+    pub fn kalshi_ws_url(&self) -> String {
+        format!("ws://{}/kalshi/trade-api/ws/v2", self.addr)
+    }
+
+    pub fn poly_clob_url(&self) -> String {
+        format!("http://{}/poly", self.addr)
+    }
+
+    pub fn poly_ws_url(&self) -> String {
+        format!("ws://{}/poly/ws/market", self.addr)
+    }
+
+    /// Point the venue endpoint overrides (`config::kalshi_api_base` and friends) here; they
+    /// are read on first use, so call this before anything connects
+    pub fn export_env(&self) {
+        std::env::set_var("KALSHI_API_URL", self.kalshi_api_url());
+        std::env::set_var("KALSHI_WS_URL", self.kalshi_ws_url());
+        std::env::set_var("POLY_CLOB_URL", self.poly_clob_url());
+        std::env::set_var("POLY_WS_URL", self.poly_ws_url());
+    }
+
+    /// Replace a Kalshi market's book and push it to subscribed feeds
+    pub fn set_kalshi_book(&self, ticker: &str, book: KalshiBook) {
+        self.venue.books.lock().unwrap().kalshi.insert(ticker.to_string(), book);
+        let _ = self.venue.updates.send(BookUpdate::Kalshi(ticker.to_string()));
+    }
+
+    /// Replace a Polymarket token's book and push it to subscribed feeds
+    pub fn set_poly_book(&self, token: &str, book: PolyBook) {
+        self.venue.books.lock().unwrap().poly.insert(token.to_string(), book);
+        let _ = self.venue.updates.send(BookUpdate::Poly(token.to_string()));
+    }
+
+    /// Refuse every order on a Kalshi ticker or Polymarket token (HTTP 400)
+    pub fn reject_orders(&self, market: &str) {
+        self.venue.books.lock().unwrap().rejecting.insert(market.to_string());
+    }
+
+    pub fn kalshi_book(&self, ticker: &str) -> Option<KalshiBook> {
+        self.venue.books.lock().unwrap().kalshi.get(ticker).cloned()
+    }
+
+    pub fn poly_book(&self, token: &str) -> Option<PolyBook> {
+        self.venue.books.lock().unwrap().poly.get(token).cloned()
+    }
+
+    /// Every order received, oldest first
+    pub fn orders(&self) -> Vec<FakeOrder> {
+        self.venue.books.lock().unwrap().orders.clone()
+    }
+
+    /// Orders on one Kalshi ticker or Polymarket token
+    pub fn orders_for(&self, market: &str) -> Vec<FakeOrder> {
+        self.orders().into_iter().filter(|o| o.market == market).collect()
+    }
+}
+
+impl Drop for FakeVenues {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+fn router(venue: SharedVenue) -> Router {
+    Router::new()
+        .route("/kalshi/trade-api/ws/v2", get(kalshi_ws))
+        .route("/kalshi/trade-api/v2/portfolio/orders", axum::routing::post(kalshi_create_order))
+        .route("/kalshi/trade-api/v2/portfolio/orders/:id", get(kalshi_get_order).delete(kalshi_cancel_order))
+        .route("/poly/ws/market", get(poly_ws))
+        .route("/poly/auth/derive-api-key", get(poly_derive_api_key))
+        .route("/poly/neg-risk", get(|| async { Json(json!({ "neg_risk": false })) }))
+        .route("/poly/order", axum::routing::post(poly_post_order).delete(poly_cancel_order))
+        .route("/poly/data/order/:id", get(poly_get_order))
+        .with_state(venue)
+}
+
+fn rejected(message: &str) -> Response {
+    (StatusCode::BAD_REQUEST, Json(json!({ "error": { "code": "invalid_order", "message": message } }))).into_response()
+}
+
+// === Kalshi ===
+
+fn kalshi_snapshot(ticker: &str, book: &KalshiBook, seq: u64) -> String {
+    json!({
+        "type": "orderbook_snapshot",
+        "sid": 1,
+        "seq": seq,
+        "msg": {
+            "market_ticker": ticker,
+            "yes": book.yes_bids.iter().map(|(p, q)| [*p, *q]).collect::<Vec<_>>(),
+            "no": book.no_bids.iter().map(|(p, q)| [*p, *q]).collect::<Vec<_>>(),
+        },
+    })
+    .to_string()
+}
+
+fn kalshi_order_json(order: &FakeOrder) -> Value {
+    let status = match order.status {
+        FakeOrderStatus::Filled => "executed",
+        FakeOrderStatus::Resting => "resting",
+        FakeOrderStatus::Cancelled | FakeOrderStatus::Rejected => "canceled",
+    };
+    let price = |side: &str| (order.side.as_deref() == Some(side)).then_some(order.price_cents);
+    json!({
+        "order": {
+            "order_id": order.order_id,
+            "ticker": order.market,
+            "status": status,
+            "remaining_count": if order.status == FakeOrderStatus::Resting { (order.size - order.filled) as i64 } else { 0 },
+            "action": order.action,
+            "side": order.side,
+            "type": "limit",
+            "yes_price": price("yes"),
+            "no_price": price("no"),
+            "taker_fill_count": order.filled as i64,
+            "taker_fill_cost": order.fill_cost_cents,
+        }
+    })
+}
+
+async fn kalshi_ws(ws: WebSocketUpgrade, State(venue): State<SharedVenue>) -> Response {
+    ws.on_upgrade(move |socket| kalshi_feed(socket, venue))
+}
+
+async fn kalshi_feed(mut socket: WebSocket, venue: SharedVenue) {
+    let mut updates = venue.updates.subscribe();
+    // The client's first frame is its subscribe command
+    let tickers: HashSet<String> = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<Value>(&text).ok()
+            .and_then(|cmd| cmd["params"]["market_tickers"].as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect(),
+        _ => return,
+    };
+    let ack = json!({ "type": "subscribed", "id": 1, "msg": { "channel": "orderbook_delta", "sid": 1 } });
+    if socket.send(Message::Text(ack.to_string())).await.is_err() {
+        return;
+    }
+    let mut seq = 0;
+    let initial: Vec<String> = {
+        let books = venue.books.lock().unwrap();
+        tickers.iter()
+            .filter_map(|t| books.kalshi.get(t).map(|book| (t, book)))
+            .map(|(t, book)| {
+                seq += 1;
+                kalshi_snapshot(t, book, seq)
+            })
+            .collect()
+    };
+    for snapshot in initial {
+        if socket.send(Message::Text(snapshot)).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(BookUpdate::Kalshi(ticker)) if tickers.contains(&ticker) => {
+                    let book = venue.books.lock().unwrap().kalshi.get(&ticker).cloned();
+                    if let Some(book) = book {
+                        seq += 1;
+                        if socket.send(Message::Text(kalshi_snapshot(&ticker, &book, seq))).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                _ => {}
+            },
+        }
+    }
+}
+
+async fn kalshi_create_order(State(venue): State<SharedVenue>, Json(req): Json<Value>) -> Response {
+    let ticker = req["ticker"].as_str().unwrap_or_default().to_string();
+    let action = req["action"].as_str().unwrap_or("buy").to_string();
+    let side = req["side"].as_str().unwrap_or("yes").to_string();
+    let count = req["count"].as_i64().unwrap_or(0);
+    let price = req[format!("{}_price", side).as_str()].as_i64().unwrap_or(0);
+    let ioc = req["time_in_force"].as_str() == Some("immediate_or_cancel");
+
+    let mut books = venue.books.lock().unwrap();
+    let mut order = FakeOrder {
+        venue: Platform::Kalshi,
+        order_id: String::new(),
+        market: ticker.clone(),
+        side: Some(side.clone()),
+        action: action.clone(),
+        price_cents: price,
+        size: count as f64,
+        filled: 0.0,
+        fill_cost_cents: 0,
+        status: FakeOrderStatus::Rejected,
+    };
+    if books.rejecting.contains(&ticker) {
+        books.record(order);
+        return rejected("order refused by fake venue");
+    }
+    let (filled, cost) = books.kalshi.get_mut(&ticker).map_or((0, 0), |book| book.take(&action, &side, price, count));
+    order.filled = filled as f64;
+    order.fill_cost_cents = cost;
+    order.status = match (filled == count, ioc) {
+        (true, _) => FakeOrderStatus::Filled,
+        (false, true) => FakeOrderStatus::Cancelled,
+        (false, false) => FakeOrderStatus::Resting,
+    };
+    let order = books.record(order);
+    Json(kalshi_order_json(&order)).into_response()
+}
+
+async fn kalshi_get_order(State(venue): State<SharedVenue>, Path(id): Path<String>) -> Response {
+    match venue.books.lock().unwrap().order_mut(Platform::Kalshi, &id) {
+        Some(order) => Json(kalshi_order_json(order)).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn kalshi_cancel_order(State(venue): State<SharedVenue>, Path(id): Path<String>) -> Response {
+    match venue.books.lock().unwrap().order_mut(Platform::Kalshi, &id) {
+        Some(order) if order.status == FakeOrderStatus::Resting => {
+            order.status = FakeOrderStatus::Cancelled;
+            Json(kalshi_order_json(order)).into_response()
+        }
+        _ => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+// === Polymarket ===
+
+fn poly_book_json(token: &str, book: &PolyBook) -> Value {
+    let levels = |levels: &[(f64, f64)]| {
+        levels.iter().map(|(p, s)| json!({ "price": format!("{:.2}", p), "size": format!("{}", s) })).collect::<Vec<_>>()
+    };
+    json!({ "event_type": "book", "asset_id": token, "bids": levels(&book.bids), "asks": levels(&book.asks) })
+}
+
+fn poly_order_json(order: &FakeOrder) -> Value {
+    let status = match order.status {
+        FakeOrderStatus::Filled => "MATCHED",
+        FakeOrderStatus::Resting => "LIVE",
+        FakeOrderStatus::Cancelled | FakeOrderStatus::Rejected => "CANCELED",
+    };
+    json!({
+        "id": order.order_id,
+        "status": status,
+        "price": format!("{:.2}", order.price_cents as f64 / 100.0),
+        "side": order.action.to_uppercase(),
+        "size_matched": format!("{}", order.filled),
+        "original_size": format!("{}", order.size),
+        "asset_id": order.market,
+    })
+}
+
+async fn poly_ws(ws: WebSocketUpgrade, State(venue): State<SharedVenue>) -> Response {
+    ws.on_upgrade(move |socket| poly_feed(socket, venue))
+}
+
+async fn poly_feed(mut socket: WebSocket, venue: SharedVenue) {
+    let mut updates = venue.updates.subscribe();
+    let tokens: HashSet<String> = match socket.recv().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<Value>(&text).ok()
+            .and_then(|cmd| cmd["assets_ids"].as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect(),
+        _ => return,
+    };
+    // Initial books go out as one batch, like the real feed
+    let initial: Vec<Value> = {
+        let books = venue.books.lock().unwrap();
+        tokens.iter().filter_map(|t| books.poly.get(t).map(|book| poly_book_json(t, book))).collect()
+    };
+    if !initial.is_empty() && socket.send(Message::Text(Value::Array(initial).to_string())).await.is_err() {
+        return;
+    }
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(BookUpdate::Poly(token)) if tokens.contains(&token) => {
+                    let book = venue.books.lock().unwrap().poly.get(&token).cloned();
+                    if let Some(book) = book {
+                        let batch = Value::Array(vec![poly_book_json(&token, &book)]);
+                        if socket.send(Message::Text(batch.to_string())).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                _ => {}
+            },
+        }
+    }
+}
+
+async fn poly_derive_api_key() -> Json<Value> {
+    Json(json!({
+        "apiKey": "fake-api-key",
+        "secret": URL_SAFE.encode(b"fake-venue-api-secret"),
+        "passphrase": "fake-passphrase",
+    }))
+}
+
+async fn poly_post_order(State(venue): State<SharedVenue>, body: String) -> Response {
+    let Ok(req) = serde_json::from_str::<Value>(&body) else { return rejected("malformed order") };
+    let signed = &req["order"];
+    let token = signed["tokenId"].as_str().unwrap_or_default().to_string();
+    let buy = signed["side"].as_str() == Some("BUY");
+    let amount = |key: &str| signed[key].as_str().and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+    let (maker, taker) = (amount("makerAmount"), amount("takerAmount"));
+    // BUY pays size x price for size shares; SELL gives size shares for size x price
+    let (size, notional) = if buy { (taker, maker) } else { (maker, taker) };
+    let price = if size > 0.0 { notional / size } else { 0.0 };
+    let size = size / 1_000_000.0;
+    let resting = matches!(req["orderType"].as_str(), Some("GTC") | Some("GTD"));
+
+    let mut books = venue.books.lock().unwrap();
+    let mut order = FakeOrder {
+        venue: Platform::Polymarket,
+        order_id: String::new(),
+        market: token.clone(),
+        side: None,
+        action: if buy { "buy" } else { "sell" }.to_string(),
+        price_cents: (price * 100.0).round() as i64,
+        size,
+        filled: 0.0,
+        fill_cost_cents: 0,
+        status: FakeOrderStatus::Rejected,
+    };
+    if books.rejecting.contains(&token) {
+        books.record(order);
+        return rejected("order refused by fake venue");
+    }
+    let (filled, cost) = books.poly.get_mut(&token).map_or((0.0, 0.0), |book| book.take(buy, price, size));
+    order.filled = filled;
+    order.fill_cost_cents = (cost * 100.0).round() as i64;
+    order.status = match (filled >= size, resting) {
+        (true, _) => FakeOrderStatus::Filled,
+        (false, false) => FakeOrderStatus::Cancelled,
+        (false, true) => FakeOrderStatus::Resting,
+    };
+    let order = books.record(order);
+    let status = match order.status {
+        FakeOrderStatus::Resting => "live",
+        _ if order.filled > 0.0 => "matched",
+        _ => "unmatched",
+    };
+    Json(json!({ "success": true, "orderID": order.order_id, "status": status })).into_response()
+}
+
+async fn poly_get_order(State(venue): State<SharedVenue>, Path(id): Path<String>) -> Response {
+    match venue.books.lock().unwrap().order_mut(Platform::Polymarket, &id) {
+        Some(order) => Json(poly_order_json(order)).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn poly_cancel_order(State(venue): State<SharedVenue>, body: String) -> Response {
+    let id = serde_json::from_str::<Value>(&body).ok()
+        .and_then(|v| v["orderID"].as_str().map(str::to_string))
+        .unwrap_or_default();
+    match venue.books.lock().unwrap().order_mut(Platform::Polymarket, &id) {
+        Some(order) if order.status == FakeOrderStatus::Resting => {
+            order.status = FakeOrderStatus::Cancelled;
+            Json(json!({ "canceled": [id], "not_canceled": {} })).into_response()
+        }
+        _ => Json(json!({ "canceled": [], "not_canceled": { (id): "order not live" } })).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kalshi_book_takes_best_levels_within_limit() {
+        let mut book = KalshiBook { yes_bids: vec![(30, 5)], no_bids: vec![(55, 4), (60, 3)] };
+        // YES asks are 40 (NO bid 60) then 45 (NO bid 55)
+        assert_eq!(book.take("buy", "yes", 42, 10), (3, 120));
+        assert_eq!(book.take("buy", "yes", 45, 10), (4, 180));
+        assert!(book.no_bids.is_empty());
+        // Selling YES hits the YES bids at or above the limit
+        assert_eq!(book.take("sell", "yes", 31, 2), (0, 0));
+        assert_eq!(book.take("sell", "yes", 30, 2), (2, 60));
+        assert_eq!(book.yes_bids, vec![(30, 3)]);
+    }
+
+    #[test]
+    fn test_poly_book_takes_best_levels_within_limit() {
+        let mut book = PolyBook { bids: vec![(0.40, 10.0)], asks: vec![(0.52, 5.0), (0.50, 5.0)] };
+        let (filled, cost) = book.take(true, 0.51, 8.0);
+        assert_eq!(filled, 5.0);
+        assert!((cost - 2.5).abs() < 1e-9);
+        assert_eq!(book.take(false, 0.40, 20.0).0, 10.0);
+        assert!(book.bids.is_empty() && book.asks == vec![(0.52, 5.0)]);
+    }
+}
@@ -0,0 +1,351 @@
+// src/fault_injection.rs
+// Test-only fault injection (feature "fault-injection") - drop/delay/duplicate feed messages,
+// fail venue API calls with specific error classes, fail feed connects and stall the state store
+
+use futures_util::future::BoxFuture;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::debug;
+
+use crate::cache::CacheBackend;
+use crate::error::{FeedError, StateStoreError, VenueApiError};
+use crate::feed_aggregator::{FeedClient, PriceUpdate};
+use crate::provider_registry::ProviderId;
+use crate::types::Platform;
+
+/// Per-message feed faults, applied in arrival order
+#[derive(Debug, Clone, Default)]
+pub struct FeedFaults {
+    /// Probability a message is dropped
+    pub drop_rate: f64,
+    /// Probability a delivered message is delivered twice
+    pub duplicate_rate: f64,
+    /// Extra delivery latency per message (order is preserved)
+    pub delay: Option<Duration>,
+}
+
+/// Venue API error class an injected call fails with
+#[derive(Debug, Clone, PartialEq)]
+pub enum VenueFault {
+    RateLimited { retry_after: Option<Duration> },
+    ServerError { status: u16 },
+    Unauthorized,
+    Decode,
+    InvalidOrder,
+}
+
+impl VenueFault {
+    pub fn into_error(self, venue: Platform) -> VenueApiError {
+        match self {
+            VenueFault::RateLimited { retry_after } => VenueApiError::RateLimited { venue, retry_after },
+            VenueFault::ServerError { status } => VenueApiError::Http { venue, status, body: "injected".into() },
+            VenueFault::Unauthorized => VenueApiError::Unauthorized { venue, status: 401, body: "injected".into() },
+            VenueFault::Decode => VenueApiError::Decode { venue, message: "injected".into() },
+            VenueFault::InvalidOrder => VenueApiError::InvalidOrder { venue, message: "injected".into() },
+        }
+    }
+}
+
+/// State store fault, applied to every backend call while set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StoreFault {
+    /// Hold each call this long before passing it through
+    Stall(Duration),
+    /// Fail each call with a backend error
+    Fail,
+}
+
+/// Counts of injected faults
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    pub feed_delivered: u64,
+    pub feed_dropped: u64,
+    pub feed_duplicated: u64,
+    pub connect_failures: u64,
+    pub venue_failures: u64,
+    pub store_stalls: u64,
+    pub store_failures: u64,
+}
+
+struct FaultState {
+    rng: StdRng,
+    feed: FeedFaults,
+    connect_failures: HashMap<ProviderId, u32>,
+    venue_faults: HashMap<Platform, VecDeque<VenueFault>>,
+    store: Option<StoreFault>,
+    stats: FaultStats,
+}
+
+/// Seeded, shared fault plan; wrap clients and backends with it and adjust faults mid-test
+pub struct FaultInjector {
+    state: Mutex<FaultState>,
+}
+
+pub type SharedFaultInjector = Arc<FaultInjector>;
+
+impl FaultInjector {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(FaultState {
+                rng: StdRng::seed_from_u64(seed),
+                feed: FeedFaults::default(),
+                connect_failures: HashMap::new(),
+                venue_faults: HashMap::new(),
+                store: None,
+                stats: FaultStats::default(),
+            }),
+        }
+    }
+
+    pub fn set_feed_faults(&self, faults: FeedFaults) {
+        self.state.lock().unwrap().feed = faults;
+    }
+
+    /// Fail the next `times` connect attempts for `provider`
+    pub fn fail_connects(&self, provider: impl Into<ProviderId>, times: u32) {
+        *self.state.lock().unwrap().connect_failures.entry(provider.into()).or_insert(0) += times;
+    }
+
+    /// Fail the next `times` calls to `venue` with `fault`
+    pub fn fail_venue(&self, venue: Platform, fault: VenueFault, times: usize) {
+        let mut state = self.state.lock().unwrap();
+        let queue = state.venue_faults.entry(venue).or_default();
+        queue.extend(std::iter::repeat_n(fault, times));
+    }
+
+    /// Set (or clear) the state store fault
+    pub fn set_store_fault(&self, fault: Option<StoreFault>) {
+        self.state.lock().unwrap().store = fault;
+    }
+
+    pub fn stats(&self) -> FaultStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    /// Run a venue call, or fail it with the next queued fault for `venue` without running it
+    pub async fn venue_call<T, F>(&self, venue: Platform, call: F) -> Result<T, VenueApiError>
+    where
+        F: Future<Output = Result<T, VenueApiError>>,
+    {
+        let fault = {
+            let mut state = self.state.lock().unwrap();
+            let fault = state.venue_faults.get_mut(&venue).and_then(|q| q.pop_front());
+            if fault.is_some() {
+                state.stats.venue_failures += 1;
+            }
+            fault
+        };
+        match fault {
+            Some(fault) => {
+                debug!("[FAULT] {} call fails with {:?}", venue, fault);
+                Err(fault.into_error(venue))
+            }
+            None => call.await,
+        }
+    }
+
+    fn take_connect_failure(&self, provider: ProviderId) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.connect_failures.get_mut(&provider) {
+            Some(left) if *left > 0 => {
+                *left -= 1;
+                state.stats.connect_failures += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Copies to deliver (0 = dropped, 2 = duplicated) and the delivery delay for one message
+    fn feed_decision(&self) -> (usize, Option<Duration>) {
+        let mut state = self.state.lock().unwrap();
+        let FeedFaults { drop_rate, duplicate_rate, delay } = state.feed.clone();
+        if state.rng.gen_bool(drop_rate.clamp(0.0, 1.0)) {
+            state.stats.feed_dropped += 1;
+            return (0, delay);
+        }
+        let copies = if state.rng.gen_bool(duplicate_rate.clamp(0.0, 1.0)) {
+            state.stats.feed_duplicated += 1;
+            2
+        } else {
+            1
+        };
+        state.stats.feed_delivered += copies as u64;
+        (copies, delay)
+    }
+
+    /// Re-deliver a price stream through the feed faults
+    pub fn pipe_feed(self: &Arc<Self>, mut rx: mpsc::UnboundedReceiver<PriceUpdate>) -> mpsc::UnboundedReceiver<PriceUpdate> {
+        let (tx, out) = mpsc::unbounded_channel();
+        let injector = self.clone();
+        tokio::spawn(async move {
+            while let Some(update) = rx.recv().await {
+                let received = Instant::now();
+                let (copies, delay) = injector.feed_decision();
+                if copies == 0 {
+                    continue;
+                }
+                if let Some(delay) = delay {
+                    tokio::time::sleep_until(received + delay).await;
+                }
+                for _ in 0..copies {
+                    if tx.send(update.clone()).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        out
+    }
+
+    async fn store_gate(&self) -> Result<(), StateStoreError> {
+        let fault = {
+            let mut state = self.state.lock().unwrap();
+            match state.store {
+                Some(StoreFault::Stall(_)) => state.stats.store_stalls += 1,
+                Some(StoreFault::Fail) => state.stats.store_failures += 1,
+                None => {}
+            }
+            state.store
+        };
+        match fault {
+            Some(StoreFault::Stall(duration)) => {
+                tokio::time::sleep(duration).await;
+                Ok(())
+            }
+            Some(StoreFault::Fail) => Err(StateStoreError::Backend {
+                backend: "fault_injection",
+                message: "injected store failure".into(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Feed client wrapper: injected connect failures and a faulted price stream
+pub struct FaultyFeedClient<C> {
+    inner: C,
+    injector: SharedFaultInjector,
+}
+
+impl<C: FeedClient> FaultyFeedClient<C> {
+    pub fn new(inner: C, injector: SharedFaultInjector) -> Self {
+        Self { inner, injector }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: FeedClient> FeedClient for FaultyFeedClient<C> {
+    fn provider(&self) -> ProviderId {
+        self.inner.provider()
+    }
+
+    async fn connect(&mut self) -> Result<(), FeedError> {
+        let provider = self.inner.provider();
+        if self.injector.take_connect_failure(provider) {
+            return Err(FeedError::Connect { provider, message: "injected connect failure".into() });
+        }
+        self.inner.connect().await
+    }
+
+    async fn disconnect(&mut self) -> Result<(), FeedError> {
+        self.inner.disconnect().await
+    }
+
+    fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+        self.injector.pipe_feed(self.inner.price_stream())
+    }
+
+    async fn ping(&mut self) -> Result<u64, FeedError> {
+        self.inner.ping().await
+    }
+
+    async fn subscribe(&mut self, markets: &[String]) -> Result<(), FeedError> {
+        self.inner.subscribe(markets).await
+    }
+
+    async fn resync(&mut self, markets: &[String]) -> Result<(), FeedError> {
+        self.inner.resync(markets).await
+    }
+
+    fn sequence_gaps(&self) -> u64 {
+        self.inner.sequence_gaps()
+    }
+}
+
+/// Cache backend wrapper: stalls or fails calls while a store fault is set
+pub struct FaultyBackend {
+    inner: Arc<dyn CacheBackend>,
+    injector: SharedFaultInjector,
+}
+
+impl FaultyBackend {
+    pub fn new(inner: Arc<dyn CacheBackend>, injector: SharedFaultInjector) -> Self {
+        Self { inner, injector }
+    }
+}
+
+impl CacheBackend for FaultyBackend {
+    fn get(&self, key: &str) -> BoxFuture<'_, Result<Option<String>, StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            self.injector.store_gate().await?;
+            self.inner.get(&key).await
+        })
+    }
+
+    fn set(&self, key: &str, value: String, ttl: Option<Duration>) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            self.injector.store_gate().await?;
+            self.inner.set(&key, value, ttl).await
+        })
+    }
+
+    fn delete(&self, key: &str) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            self.injector.store_gate().await?;
+            self.inner.delete(&key).await
+        })
+    }
+}
+
+/// In-process second tier for fault tests (no TTL handling)
+#[derive(Default)]
+pub struct MemoryBackend {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryBackend {
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.lock().unwrap().contains_key(key)
+    }
+}
+
+impl CacheBackend for MemoryBackend {
+    fn get(&self, key: &str) -> BoxFuture<'_, Result<Option<String>, StateStoreError>> {
+        let value = self.entries.lock().unwrap().get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set(&self, key: &str, value: String, _ttl: Option<Duration>) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        self.entries.lock().unwrap().insert(key.to_string(), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete(&self, key: &str) -> BoxFuture<'_, Result<(), StateStoreError>> {
+        self.entries.lock().unwrap().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
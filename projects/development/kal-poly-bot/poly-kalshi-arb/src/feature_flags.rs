@@ -0,0 +1,467 @@
+// src/feature_flags.rs
+// Feature flags gating pattern engines, ML tiers and experimental components #71-#88
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::{FeaturesSection, ShadowSection};
+use crate::config_reload::ConfigChanged;
+use crate::event_phase::PhaseSet;
+
+/// Top-level flag a component is released under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FeatureFlag {
+    /// Tier 1 high-frequency models
+    Premium,
+    /// Tier 2/3 and behavioral models, pattern engines
+    BetaFeatures,
+    /// Experimental components
+    Debug,
+}
+
+impl FeatureFlag {
+    pub const ALL: [FeatureFlag; 3] = [FeatureFlag::Premium, FeatureFlag::BetaFeatures, FeatureFlag::Debug];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FeatureFlag::Premium => "PREMIUM",
+            FeatureFlag::BetaFeatures => "BETA_FEATURES",
+            FeatureFlag::Debug => "DEBUG",
+        }
+    }
+
+    fn configured(self, config: &FeaturesSection) -> bool {
+        match self {
+            FeatureFlag::Premium => config.premium,
+            FeatureFlag::BetaFeatures => config.beta_features,
+            FeatureFlag::Debug => config.debug,
+        }
+    }
+}
+
+impl std::fmt::Display for FeatureFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A gated component
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentSpec {
+    pub id: u16,
+    pub name: &'static str,
+    /// ML tier 1-4 (0 = behavioral)
+    pub tier: u8,
+    pub flag: FeatureFlag,
+    pub experimental: bool,
+    /// Event phases the component may fire in
+    pub phases: PhaseSet,
+    /// Latency SLA per evaluation (ms)
+    pub sla_ms: f64,
+}
+
+const fn spec(id: u16, name: &'static str, tier: u8, flag: FeatureFlag, experimental: bool, phases: PhaseSet, sla_ms: f64) -> ComponentSpec {
+    ComponentSpec { id, name, tier, flag, experimental, phases, sla_ms }
+}
+
+/// Components #71-#88 and the flag each ships under
+pub const COMPONENTS: &[ComponentSpec] = &[
+    spec(71, "Asymmetric Prop", 2, FeatureFlag::BetaFeatures, false, PhaseSet::TRADING, 1300.0),
+    spec(73, "Prop Beta Skew", 3, FeatureFlag::BetaFeatures, false, PhaseSet::TRADING, 1850.0),
+    spec(74, "Provider Glitch", 2, FeatureFlag::BetaFeatures, false, PhaseSet::TRADING, 800.0),
+    spec(75, "Velocity Convexity", 1, FeatureFlag::Premium, false, PhaseSet::IN_PLAY, 200.0),
+    spec(76, "MM Compression", 1, FeatureFlag::Premium, false, PhaseSet::IN_PLAY, 150.0),
+    spec(77, "Regulatory Delay", 4, FeatureFlag::Debug, true, PhaseSet::IN_PLAY, 5000.0),
+    spec(79, "Bayesian Emotional Carryover", 0, FeatureFlag::BetaFeatures, false, PhaseSet::LIVE, 96000.0),
+    spec(82, "Momentum Transfer", 0, FeatureFlag::BetaFeatures, false, PhaseSet::IN_PLAY, 96000.0),
+    spec(85, "Liquidity Mirage", 1, FeatureFlag::Premium, false, PhaseSet::TRADING, 100.0),
+    spec(88, "Source ID Classifier", 3, FeatureFlag::BetaFeatures, false, PhaseSet::TRADING, 900.0),
+];
+
+pub fn component(id: u16) -> Option<&'static ComponentSpec> {
+    COMPONENTS.iter().find(|c| c.id == id)
+}
+
+/// What a runtime override applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlagTarget {
+    Flag(FeatureFlag),
+    Component(u16),
+}
+
+impl std::str::FromStr for FlagTarget {
+    type Err = anyhow::Error;
+
+    /// `PREMIUM` / `beta_features` / `debug`, or a component id like `77`
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(id) = s.parse::<u16>() {
+            return component(id)
+                .map(|c| FlagTarget::Component(c.id))
+                .ok_or_else(|| anyhow!("unknown component #{}", id));
+        }
+        FeatureFlag::ALL.into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(s))
+            .map(FlagTarget::Flag)
+            .ok_or_else(|| anyhow!("unknown feature flag '{}'", s))
+    }
+}
+
+/// Where an effective value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    Config,
+    Override,
+    /// Shed by the SLA degradation policy
+    Degraded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagStatus {
+    pub enabled: bool,
+    pub source: FlagSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentFlagStatus {
+    pub id: u16,
+    pub name: String,
+    pub tier: u8,
+    pub flag: FeatureFlag,
+    pub experimental: bool,
+    pub enabled: bool,
+    pub source: FlagSource,
+    /// Dark-launched: signals are paper-filled only (see src/shadow_mode.rs)
+    #[serde(default)]
+    pub shadow: bool,
+}
+
+/// Effective flag state for telemetry and the admin endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagsSnapshot {
+    pub flags: BTreeMap<FeatureFlag, FlagStatus>,
+    pub components: Vec<ComponentFlagStatus>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub shed_tiers: BTreeSet<u8>,
+    /// Shadow components promoted to live trading since start
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub promoted: BTreeSet<u16>,
+}
+
+#[derive(Debug, Default)]
+struct FlagState {
+    config: FeaturesSection,
+    overrides: HashMap<FlagTarget, bool>,
+    /// ML tiers switched off by the SLA degradation policy
+    shed_tiers: BTreeSet<u8>,
+    /// Shadow components promoted to live
+    promoted: BTreeSet<u16>,
+}
+
+impl FlagState {
+    fn flag(&self, flag: FeatureFlag) -> FlagStatus {
+        match self.overrides.get(&FlagTarget::Flag(flag)) {
+            Some(&enabled) => FlagStatus { enabled, source: FlagSource::Override },
+            None => FlagStatus { enabled: flag.configured(&self.config), source: FlagSource::Config },
+        }
+    }
+
+    fn shadowed(&self, id: u16) -> bool {
+        self.config.shadow.components.contains(&id) && !self.promoted.contains(&id)
+    }
+
+    /// Component override, then a shed tier, then `[features.components]`, then its flag
+    fn component(&self, spec: &ComponentSpec) -> FlagStatus {
+        if let Some(&enabled) = self.overrides.get(&FlagTarget::Component(spec.id)) {
+            return FlagStatus { enabled, source: FlagSource::Override };
+        }
+        if self.shed_tiers.contains(&spec.tier) {
+            return FlagStatus { enabled: false, source: FlagSource::Degraded };
+        }
+        match self.config.components.get(&spec.id.to_string()) {
+            Some(&enabled) => FlagStatus { enabled, source: FlagSource::Config },
+            None => self.flag(spec.flag),
+        }
+    }
+}
+
+/// Config-backed flags with runtime overrides (overrides survive config reloads)
+#[derive(Debug, Default)]
+pub struct FeatureFlags {
+    state: RwLock<FlagState>,
+}
+
+pub type SharedFeatureFlags = Arc<FeatureFlags>;
+
+impl FeatureFlags {
+    pub fn new(config: &FeaturesSection) -> Self {
+        Self {
+            state: RwLock::new(FlagState { config: config.clone(), ..FlagState::default() }),
+        }
+    }
+
+    /// Apply a hot-reloaded `[features]` section
+    pub fn apply_config(&self, config: &FeaturesSection) {
+        self.state.write().unwrap().config = config.clone();
+        info!("[FLAGS] Config applied");
+    }
+
+    pub fn flag_enabled(&self, flag: FeatureFlag) -> bool {
+        self.state.read().unwrap().flag(flag).enabled
+    }
+
+    /// Components outside the registry are never gated
+    pub fn component_enabled(&self, id: u16) -> bool {
+        match component(id) {
+            Some(spec) => self.state.read().unwrap().component(spec).enabled,
+            None => true,
+        }
+    }
+
+    /// Whether a component is dark-launched: it runs and signals, but nothing it signals is sent live
+    pub fn component_shadowed(&self, id: u16) -> bool {
+        self.state.read().unwrap().shadowed(id)
+    }
+
+    /// Enabled and out of shadow, so its signals may trade
+    pub fn component_live(&self, id: u16) -> bool {
+        self.component_enabled(id) && !self.component_shadowed(id)
+    }
+
+    /// Components still in shadow
+    pub fn shadowed_components(&self) -> Vec<u16> {
+        let state = self.state.read().unwrap();
+        state.config.shadow.components.iter().copied().filter(|&id| state.shadowed(id)).collect()
+    }
+
+    /// Promotion criteria of `[features.shadow]`
+    pub fn shadow_policy(&self) -> ShadowSection {
+        self.state.read().unwrap().config.shadow.clone()
+    }
+
+    /// Take a component out of shadow (promotions survive config reloads); false if it wasn't shadowed
+    pub fn promote(&self, id: u16) -> bool {
+        let mut state = self.state.write().unwrap();
+        if !state.shadowed(id) {
+            return false;
+        }
+        state.promoted.insert(id);
+        warn!("[FLAGS] Component #{} promoted from shadow to live", id);
+        true
+    }
+
+    /// Set (`Some`) or clear (`None`) a runtime override
+    pub fn set_override(&self, target: FlagTarget, enabled: Option<bool>) {
+        let mut state = self.state.write().unwrap();
+        match enabled {
+            Some(enabled) => {
+                state.overrides.insert(target, enabled);
+                warn!("[FLAGS] Override {:?} = {}", target, enabled);
+            }
+            None => {
+                state.overrides.remove(&target);
+                info!("[FLAGS] Override {:?} cleared", target);
+            }
+        }
+    }
+
+    /// Switch off every component of `tiers` (an empty slice restores them); component
+    /// overrides still win, so an operator can pin one model on through a degradation
+    pub fn set_shed_tiers(&self, tiers: &[u8]) {
+        let tiers: BTreeSet<u8> = tiers.iter().copied().collect();
+        let mut state = self.state.write().unwrap();
+        if state.shed_tiers != tiers {
+            info!("[FLAGS] Shed tiers {:?} -> {:?}", state.shed_tiers, tiers);
+            state.shed_tiers = tiers;
+        }
+    }
+
+    pub fn snapshot(&self) -> FlagsSnapshot {
+        let state = self.state.read().unwrap();
+        FlagsSnapshot {
+            flags: FeatureFlag::ALL.into_iter().map(|f| (f, state.flag(f))).collect(),
+            components: COMPONENTS.iter()
+                .map(|spec| {
+                    let status = state.component(spec);
+                    ComponentFlagStatus {
+                        id: spec.id,
+                        name: spec.name.to_string(),
+                        tier: spec.tier,
+                        flag: spec.flag,
+                        experimental: spec.experimental,
+                        enabled: status.enabled,
+                        source: status.source,
+                        shadow: state.shadowed(spec.id),
+                    }
+                })
+                .collect(),
+            shed_tiers: state.shed_tiers.clone(),
+            promoted: state.promoted.clone(),
+        }
+    }
+
+    /// Admin endpoint (mount with `Supervisor::add_route("/flags", ..)`):
+    ///
+    ///   GET    /flags                      effective state
+    ///   PUT    /flags/<flag|id>?enabled=b  set override
+    ///   DELETE /flags/<flag|id>            clear override
+    ///   POST   /flags/<id>/promote         take a shadow component live
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let target = path.trim_start_matches("/flags").trim_matches('/');
+
+        let result = match (method, target) {
+            ("GET", "") => Ok(()),
+            ("POST", target) if target.ends_with("/promote") => {
+                match target.trim_end_matches("/promote").parse::<FlagTarget>() {
+                    Ok(FlagTarget::Component(id)) if self.promote(id) => Ok(()),
+                    Ok(FlagTarget::Component(id)) => Err(anyhow!("component #{} is not in shadow", id)),
+                    Ok(_) => Err(anyhow!("only components can be promoted")),
+                    Err(e) => Err(e),
+                }
+            }
+            ("PUT" | "POST", target) if !target.is_empty() => target.parse::<FlagTarget>().and_then(|target| {
+                let enabled = query.split('&')
+                    .find_map(|pair| pair.strip_prefix("enabled="))
+                    .ok_or_else(|| anyhow!("missing ?enabled=true|false"))?
+                    .parse::<bool>()
+                    .map_err(|_| anyhow!("enabled must be true or false"))?;
+                self.set_override(target, Some(enabled));
+                Ok(())
+            }),
+            ("DELETE", target) if !target.is_empty() => target.parse::<FlagTarget>().map(|target| {
+                self.set_override(target, None);
+            }),
+            ("GET", _) => return (404, r#"{"error":"not found"}"#.to_string()),
+            _ => return (405, r#"{"error":"method not allowed"}"#.to_string()),
+        };
+
+        match result {
+            Ok(()) => (200, serde_json::to_string_pretty(&self.snapshot()).unwrap_or_default()),
+            Err(e) => (400, serde_json::json!({ "error": e.to_string() }).to_string()),
+        }
+    }
+}
+
+/// Keep flags in step with `[features]` reloads
+pub fn watch_config(flags: SharedFeatureFlags, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("features") => flags.apply_config(&change.config.features),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[FLAGS] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        let mut config = FeaturesSection::default();
+        let flags = FeatureFlags::new(&config);
+        assert!(flags.component_enabled(75));
+        assert!(flags.component_enabled(73));
+        assert!(!flags.component_enabled(77)); // DEBUG off by default
+        assert!(flags.component_enabled(51)); // not gated
+
+        // Component entry beats its flag
+        config.beta_features = false;
+        config.components.insert("88".to_string(), true);
+        flags.apply_config(&config);
+        assert!(!flags.component_enabled(73));
+        assert!(flags.component_enabled(88));
+
+        // Runtime overrides beat config and survive reloads
+        flags.set_override(FlagTarget::Flag(FeatureFlag::BetaFeatures), Some(true));
+        flags.set_override(FlagTarget::Component(75), Some(false));
+        flags.apply_config(&config);
+        assert!(flags.component_enabled(73));
+        assert!(!flags.component_enabled(75));
+
+        flags.set_override(FlagTarget::Flag(FeatureFlag::BetaFeatures), None);
+        assert!(!flags.component_enabled(73));
+
+        let snapshot = flags.snapshot();
+        assert_eq!(snapshot.flags[&FeatureFlag::BetaFeatures].source, FlagSource::Config);
+        let c75 = snapshot.components.iter().find(|c| c.id == 75).unwrap();
+        assert!(!c75.enabled && c75.source == FlagSource::Override);
+    }
+
+    #[test]
+    fn test_shed_tiers_sit_between_overrides_and_config() {
+        let flags = FeatureFlags::new(&FeaturesSection { debug: true, ..FeaturesSection::default() });
+        flags.set_override(FlagTarget::Component(88), Some(true));
+        flags.set_shed_tiers(&[3, 4]);
+        assert!(!flags.component_enabled(73));
+        assert!(!flags.component_enabled(77));
+        assert!(flags.component_enabled(88)); // pinned on by an operator
+        assert!(flags.component_enabled(75));
+
+        let snapshot = flags.snapshot();
+        assert_eq!(snapshot.shed_tiers.iter().copied().collect::<Vec<_>>(), vec![3, 4]);
+        let c73 = snapshot.components.iter().find(|c| c.id == 73).unwrap();
+        assert_eq!(c73.source, FlagSource::Degraded);
+
+        flags.set_shed_tiers(&[]);
+        assert!(flags.component_enabled(73) && flags.component_enabled(77));
+    }
+
+    #[test]
+    fn test_admin_endpoint() {
+        let flags = FeatureFlags::new(&FeaturesSection::default());
+
+        let (code, body) = flags.handle_admin("PUT", "/flags/debug?enabled=true");
+        assert_eq!(code, 200);
+        let snapshot: FlagsSnapshot = serde_json::from_str(&body).unwrap();
+        assert!(snapshot.flags[&FeatureFlag::Debug].enabled);
+        assert!(flags.component_enabled(77));
+
+        assert_eq!(flags.handle_admin("PUT", "/flags/77?enabled=false").0, 200);
+        assert!(!flags.component_enabled(77));
+        assert_eq!(flags.handle_admin("DELETE", "/flags/77").0, 200);
+        assert!(flags.component_enabled(77));
+
+        assert_eq!(flags.handle_admin("GET", "/flags").0, 200);
+        assert_eq!(flags.handle_admin("PUT", "/flags/12?enabled=true").0, 400);
+        assert_eq!(flags.handle_admin("PUT", "/flags/premium").0, 400);
+        assert_eq!(flags.handle_admin("PUT", "/flags/nope?enabled=true").0, 400);
+        assert_eq!(flags.handle_admin("PATCH", "/flags/premium").0, 405);
+    }
+
+    #[test]
+    fn test_shadow_components_run_but_are_not_live_until_promoted() {
+        let mut config = FeaturesSection::default();
+        config.shadow.components = vec![73, 74];
+        let flags = FeatureFlags::new(&config);
+        assert!(flags.component_enabled(73) && flags.component_shadowed(73) && !flags.component_live(73));
+        assert!(flags.component_live(75));
+        assert!(!flags.component_live(77)); // not shadowed, just off
+
+        assert!(flags.promote(73));
+        assert!(!flags.promote(73) && !flags.promote(75));
+        flags.apply_config(&config); // promotion survives the reload
+        assert!(flags.component_live(73));
+        assert_eq!(flags.shadowed_components(), vec![74]);
+
+        let snapshot = flags.snapshot();
+        assert!(snapshot.components.iter().find(|c| c.id == 74).unwrap().shadow);
+        assert_eq!(snapshot.promoted.iter().copied().collect::<Vec<_>>(), vec![73]);
+
+        assert_eq!(flags.handle_admin("POST", "/flags/74/promote").0, 200);
+        assert!(flags.component_live(74));
+        assert_eq!(flags.handle_admin("POST", "/flags/74/promote").0, 400);
+        assert_eq!(flags.handle_admin("POST", "/flags/debug/promote").0, 400);
+    }
+}
@@ -4,14 +4,31 @@
 //! timestamping for latency arbitrage detection. Supports concurrent
 //! WebSocket connections and nanosecond-precision latency measurement.
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{Duration, Instant};
+use rand::Rng;
 use tracing::{info, warn, error};
 
 use crate::types::*;
+use crate::circuit_breaker::{BreakerConfig, BreakerError, CircuitBreaker, SharedBreaker};
+use crate::error::FeedError;
+use crate::provider_registry::ProviderId;
+use crate::clock::{self, SharedClock, Stamp};
+use crate::clock_sync::SharedClockSync;
+use crate::config::FeedsSection;
+use crate::event_bus::{Event, SharedEventBus};
+use crate::event_phase::EventPhase;
+use crate::heartbeat::{Activity, HeartbeatPolicy};
 use crate::latency_arbitrage::{LatencyArbitrageEngine, PriceObservation, MarketTier};
+use crate::market_metadata::{MarketMetadataRegistry, SharedMarketMetadata};
+use crate::microstructure::{BookFeatures, MicrostructureTracker, TopOfBook};
+use crate::odds_capture::{OddsCaptureHandle, OddsChangeDetector};
+use crate::quote_normalizer::{BinaryQuote, Quote, QuoteNormalizer, VenueRule};
+use crate::tick_sanitizer::{SharedTickSanitizer, Tick};
 
 /// Feed connection status
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,49 +37,385 @@ pub enum FeedStatus {
     Connecting,
     Disconnected,
     Error,
+    /// Connected, but a sequence gap means its books may be stale; its updates are held
+    /// back until a snapshot resync lands
+    Degraded,
+}
+
+/// Where a feed connection is in its reconnect cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectState {
+    /// Connected, or never connected yet
+    Idle,
+    /// Lost or failed; the next attempt is due at `retry_at`
+    Backoff { retry_at: Instant },
+    /// An attempt is in flight (or, for feeds whose client the aggregator doesn't own, left to its owner)
+    Reconnecting,
+    /// `max_attempts` attempts in a row failed; one more is allowed at `retry_at`
+    CircuitOpen { retry_at: Instant },
+}
+
+/// Exponential reconnect backoff with jitter, and the attempts after which a feed's circuit opens
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Fraction each delay is moved by at most, either way
+    pub jitter: f64,
+    pub max_attempts: u32,
+    /// How long an open circuit waits before its next attempt
+    pub cooldown: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Backoff settings from the `[feeds]` section
+    pub fn from_feeds(feeds: &FeedsSection) -> Self {
+        Self {
+            base_delay: Duration::from_secs(feeds.ws_reconnect_delay_secs),
+            max_delay: Duration::from_secs(feeds.max_reconnect_delay_secs),
+            jitter: feeds.reconnect_jitter,
+            max_attempts: feeds.max_reconnect_attempts.max(1),
+            cooldown: Duration::from_secs(feeds.reconnect_cooldown_secs),
+        }
+    }
+
+    /// Delay before retrying after `attempt` failures in a row; `sample` in [-1, 1] scales the jitter
+    pub fn delay(&self, attempt: u32, sample: f64) -> Duration {
+        let doubled = self.base_delay.saturating_mul(1u32 << attempt.saturating_sub(1).min(31));
+        doubled.min(self.max_delay).mul_f64((1.0 + self.jitter * sample.clamp(-1.0, 1.0)).max(0.0))
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(5),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.2,
+            max_attempts: 10,
+            cooldown: Duration::from_secs(300),
+        }
+    }
 }
 
 /// Individual feed connection
 pub struct FeedConnection {
-    pub provider: Platform,
+    pub provider: ProviderId,
     pub status: FeedStatus,
     pub last_heartbeat: Instant,
-    pub reconnect_attempts: u32,
+    pub reconnect_attempts: u32, // Failed attempts in a row
     pub latency_ns: u64, // Round-trip latency measurement
+    pub reconnect: ReconnectState,
+    /// Markets subscribed on this feed, resubscribed after every reconnect
+    pub subscriptions: BTreeSet<String>,
+}
+
+impl FeedConnection {
+    pub fn new(provider: ProviderId) -> Self {
+        Self {
+            provider,
+            status: FeedStatus::Disconnected,
+            last_heartbeat: Instant::now(),
+            reconnect_attempts: 0,
+            latency_ns: 0,
+            reconnect: ReconnectState::Idle,
+            subscriptions: BTreeSet::new(),
+        }
+    }
+
+    /// The feed went quiet or dropped: retry straight away (unless already backing off)
+    pub fn record_lost(&mut self, now: Instant) {
+        if self.reconnect == ReconnectState::Idle {
+            self.reconnect = ReconnectState::Backoff { retry_at: now };
+        }
+    }
+
+    /// An attempt failed: back off, or open the circuit once `max_attempts` have failed in a row
+    pub fn record_failure(&mut self, now: Instant, policy: &ReconnectPolicy, jitter_sample: f64) -> ReconnectState {
+        self.reconnect_attempts += 1;
+        self.reconnect = if self.reconnect_attempts >= policy.max_attempts {
+            ReconnectState::CircuitOpen { retry_at: now + policy.cooldown }
+        } else {
+            ReconnectState::Backoff { retry_at: now + policy.delay(self.reconnect_attempts, jitter_sample) }
+        };
+        self.reconnect
+    }
+
+    pub fn record_connected(&mut self) {
+        self.reconnect_attempts = 0;
+        self.reconnect = ReconnectState::Idle;
+    }
+
+    /// Whether a reconnect attempt is due at `now`
+    pub fn retry_due(&self, now: Instant) -> bool {
+        match self.reconnect {
+            ReconnectState::Backoff { retry_at } | ReconnectState::CircuitOpen { retry_at } => retry_at <= now,
+            ReconnectState::Idle | ReconnectState::Reconnecting => false,
+        }
+    }
 }
 
 /// Aggregated price update message
 #[derive(Debug, Clone)]
 pub struct PriceUpdate {
     pub market_id: u16,
-    pub provider: Platform,
+    pub provider: ProviderId,
     pub market_type: MarketType,
     pub yes_price: PriceCents,
     pub no_price: PriceCents,
     pub yes_size: SizeCents,
     pub no_size: SizeCents,
-    pub received_timestamp: TimestampNs, // When we received it
-    pub provider_timestamp: Option<TimestampNs>, // Provider's timestamp if available
+    pub received: Stamp, // When we received it (process clock)
+    pub provider_timestamp: Option<TimestampNs>, // Provider's wall-clock timestamp if available
+    pub sequence: Option<u64>, // Provider-assigned sequence, the same on every endpoint (None if the feed has none)
+    pub features: Option<BookFeatures>, // Filled in by the aggregator when the book has both sides
+}
+
+impl PriceUpdate {
+    /// YES-side top of book (None if either side is empty)
+    pub fn top_of_book(&self) -> Option<TopOfBook> {
+        TopOfBook::from_binary(self.yes_price, self.no_price, self.yes_size, self.no_size)
+    }
+
+    /// YES-side tick for the sanity checks
+    pub fn to_tick(&self) -> Tick {
+        Tick {
+            provider: self.provider,
+            market_id: self.market_id,
+            price: self.yes_price as f64,
+            size: self.yes_size as f64,
+            timestamp_ns: self.provider_timestamp,
+        }
+    }
+
+    /// YES-side observation for latency analysis (built-in venues only). Observations are
+    /// YES-only on purpose: a binary market's NO ask mirrors its YES ask around 100¢, so a
+    /// move shows up on both sides at once and the engine compares venues on one side only -
+    /// a second NO observation would double every disparity it finds.
+    pub fn to_observation(&self, tier: MarketTier) -> Option<PriceObservation> {
+        Some(PriceObservation {
+            market_id: self.market_id,
+            provider: self.provider.platform()?,
+            market_type: self.market_type,
+            price: self.yes_price,
+            size: self.yes_size,
+            timestamp_ns: self.received.mono.0,
+            tier,
+            features: self.features,
+        })
+    }
+}
+
+/// Throttles rapid consecutive updates of the same market from the same provider. A key's
+/// first update passes straight through and opens a `window`; later ones within it are held
+/// and merged - the latest book, while `received` and `provider_timestamp` keep the earliest
+/// held values so latency is measured from first sight - and released when it closes, which
+/// opens the next window
+pub struct UpdateCoalescer {
+    window: Duration,
+    /// Updates that opened a window, with their arrival instants
+    ready: VecDeque<(PriceUpdate, Instant)>,
+    pending: HashMap<(u16, ProviderId), PriceUpdate>,
+    /// Held keys in arrival order with the instant each is due
+    due: VecDeque<((u16, ProviderId), Instant)>,
+    /// When each key's current window closes
+    windows: HashMap<(u16, ProviderId), Instant>,
+    merged: u64,
+}
+
+impl UpdateCoalescer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            ready: VecDeque::new(),
+            pending: HashMap::new(),
+            due: VecDeque::new(),
+            windows: HashMap::new(),
+            merged: 0,
+        }
+    }
+
+    /// Queue an update arriving at `now`: released at once unless its key's window is open,
+    /// else merged into the key's held update
+    pub fn push(&mut self, update: PriceUpdate, now: Instant) {
+        let key = (update.market_id, update.provider);
+        if let Some(pending) = self.pending.get_mut(&key) {
+            let received = if update.received.mono < pending.received.mono { update.received } else { pending.received };
+            let provider_timestamp = match (pending.provider_timestamp, update.provider_timestamp) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            *pending = PriceUpdate { received, provider_timestamp, ..update };
+            self.merged += 1;
+            return;
+        }
+        match self.windows.get(&key) {
+            Some(&closes) if closes > now => {
+                self.pending.insert(key, update);
+                self.due.push_back((key, closes));
+            }
+            _ => {
+                self.windows.insert(key, now + self.window);
+                self.ready.push_back((update, now));
+            }
+        }
+    }
+
+    /// When the oldest queued update is due
+    pub fn next_due(&self) -> Option<Instant> {
+        let ready = self.ready.front().map(|(_, arrived)| *arrived);
+        let held = self.due.front().map(|(_, due)| *due);
+        ready.into_iter().chain(held).min()
+    }
+
+    /// Updates due by `now`: those that opened a window, then held ones whose window closed
+    pub fn take_due(&mut self, now: Instant) -> Vec<PriceUpdate> {
+        let mut out: Vec<PriceUpdate> = self.ready.drain(..).map(|(update, _)| update).collect();
+        while let Some((key, due)) = self.due.front().filter(|(_, due)| *due <= now) {
+            let (key, due) = (*key, *due);
+            self.due.pop_front();
+            self.windows.insert(key, due + self.window);
+            out.extend(self.pending.remove(&key));
+        }
+        out
+    }
+
+    /// Every queued update, due or not, in the same order as `take_due`
+    pub fn drain(&mut self) -> Vec<PriceUpdate> {
+        let mut out: Vec<PriceUpdate> = self.ready.drain(..).map(|(update, _)| update).collect();
+        let keys: Vec<_> = self.due.drain(..).map(|(key, _)| key).collect();
+        out.extend(keys.iter().filter_map(|key| self.pending.remove(key)));
+        self.windows.clear();
+        out
+    }
+
+    /// Updates folded into an earlier pending one so far
+    pub fn merged(&self) -> u64 {
+        self.merged
+    }
+}
+
+/// Outcome of checking an update's sequence number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// Next in line, or unsequenced
+    InOrder,
+    /// At or behind the last one seen (a replay or a late duplicate)
+    Stale,
+    /// Numbers were skipped: deltas were missed and the feed needs a snapshot
+    Gap { expected: u64, got: u64 },
+    /// The feed is awaiting a resync after an earlier gap
+    Held,
+}
+
+/// Per-provider sequence continuity. Feeds stamping `PriceUpdate::sequence` are checked
+/// update by update; clients that can only see gaps on their own connection report a
+/// running count instead (`FeedClient::sequence_gaps`). Either way the provider stays
+/// pending until `resynced`.
+#[derive(Debug, Default)]
+pub struct SequenceTracker {
+    last: HashMap<ProviderId, u64>,
+    /// Gap counts as last reported by clients
+    reported: HashMap<ProviderId, u64>,
+    pending: HashSet<ProviderId>,
+    gaps: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check an update's sequence number against the provider's last; everything from a
+    /// provider awaiting a resync is held
+    pub fn observe(&mut self, provider: ProviderId, sequence: Option<u64>) -> SequenceCheck {
+        if self.pending.contains(&provider) {
+            return SequenceCheck::Held;
+        }
+        let Some(sequence) = sequence else {
+            return SequenceCheck::InOrder;
+        };
+        match self.last.insert(provider, sequence) {
+            Some(last) if sequence <= last => {
+                self.last.insert(provider, last);
+                SequenceCheck::Stale
+            }
+            Some(last) if sequence > last + 1 => {
+                self.flag(provider);
+                SequenceCheck::Gap { expected: last + 1, got: sequence }
+            }
+            _ => SequenceCheck::InOrder,
+        }
+    }
+
+    /// Take a client's running gap count; true when it has grown since the last report
+    pub fn report_gaps(&mut self, provider: ProviderId, total: u64) -> bool {
+        let before = self.reported.insert(provider, total).unwrap_or(0);
+        if total > before {
+            self.flag(provider);
+            return true;
+        }
+        false
+    }
+
+    fn flag(&mut self, provider: ProviderId) {
+        self.pending.insert(provider);
+        self.gaps += 1;
+    }
+
+    /// Providers awaiting a resync
+    pub fn pending(&self) -> Vec<ProviderId> {
+        self.pending.iter().copied().collect()
+    }
+
+    pub fn is_pending(&self, provider: ProviderId) -> bool {
+        self.pending.contains(&provider)
+    }
+
+    /// A snapshot landed (or the feed reconnected): the next sequenced update starts afresh
+    pub fn resynced(&mut self, provider: ProviderId) {
+        self.pending.remove(&provider);
+        self.last.remove(&provider);
+    }
+
+    /// Gaps detected so far, over all providers
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
 }
 
 /// Feed aggregator configuration
 #[derive(Debug, Clone)]
 pub struct FeedAggregatorConfig {
-    pub max_reconnect_attempts: u32,
-    pub reconnect_delay_ms: u64,
-    pub heartbeat_interval_ms: u64,
+    pub reconnect: ReconnectPolicy,
+    pub heartbeat: HeartbeatPolicy, // Check interval / staleness per provider, by market activity
     pub latency_sample_window: usize, // Rolling window for latency stats
     pub enable_latency_tracking: bool,
+    pub microstructure_window: usize, // Quote changes in the trade-flow imbalance window
+    pub coalesce_window: Duration, // Same-market, same-provider updates merged within this (zero = off)
+}
+
+impl FeedAggregatorConfig {
+    /// Reconnect and heartbeat settings from the `[feeds]` section
+    pub fn from_feeds(feeds: &FeedsSection) -> Self {
+        Self {
+            reconnect: ReconnectPolicy::from_feeds(feeds),
+            heartbeat: HeartbeatPolicy::from_config(feeds),
+            coalesce_window: Duration::from_millis(feeds.coalesce_window_ms),
+            ..Self::default()
+        }
+    }
 }
 
 impl Default for FeedAggregatorConfig {
     fn default() -> Self {
         Self {
-            max_reconnect_attempts: 10,
-            reconnect_delay_ms: 5000,
-            heartbeat_interval_ms: 30000,
+            reconnect: ReconnectPolicy::default(),
+            heartbeat: HeartbeatPolicy::default(),
             latency_sample_window: 100,
             enable_latency_tracking: true,
+            microstructure_window: 50,
+            coalesce_window: Duration::ZERO,
         }
     }
 }
@@ -72,52 +425,175 @@ pub struct FeedAggregator {
     /// Configuration
     config: FeedAggregatorConfig,
     /// Active feed connections
-    connections: HashMap<Platform, FeedConnection>,
+    connections: HashMap<ProviderId, FeedConnection>,
+    /// Clients handed over with `add_client`, reconnected by `check_connections`
+    clients: HashMap<ProviderId, Box<dyn FeedClient>>,
     /// Price update channel sender
     update_tx: mpsc::UnboundedSender<PriceUpdate>,
     /// Latency arbitrage engine
     latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
-    /// Market tier mappings for latency analysis
-    market_tiers: HashMap<u16, MarketTier>,
-    /// Latency statistics per provider
-    latency_stats: HashMap<Platform, LatencyStats>,
+    /// Market type / event / tier per market, shared with the latency engine (`with_market_metadata`)
+    market_metadata: SharedMarketMetadata,
+    /// Phase-derived activity per market; the busiest sets the heartbeat interval
+    market_activity: HashMap<u16, Activity>,
+    /// Latency statistics per provider (processing latency is recorded by `process_updates_coalesced`)
+    latency_stats: SharedLatencyStats,
+    /// Per-venue breaker around connect/ping (shareable with venue API clients)
+    feed_breaker: SharedBreaker<Platform>,
+    /// Breaker for plugin providers, which have no venue to share with
+    plugin_breaker: SharedBreaker<ProviderId>,
+    /// Bus ticks and status changes are published on (optional)
+    event_bus: Option<SharedEventBus>,
+    /// Imbalance / microprice / trade-flow state per market and provider
+    microstructure: Mutex<MicrostructureTracker>,
+    /// Venue quote conventions and rounding, for feeds that don't quote in cents
+    quotes: QuoteNormalizer,
+    /// Sanity bounds; failing ticks are quarantined before features, the bus or the engine see them
+    sanitizer: Option<SharedTickSanitizer>,
+    /// Per-provider clock offset/skew; provider timestamps are moved onto the local wall clock
+    clock_sync: Option<SharedClockSync>,
+    /// Sequence continuity per provider; feeds with a gap are held back until resynced
+    sequences: Mutex<SequenceTracker>,
+    /// Processing latency is measured against updates' `received` stamps on this clock
+    clock: SharedClock,
 }
 
+/// Rolling window of latency samples, summarized as percentiles
+#[derive(Debug, Clone)]
+pub struct LatencyWindow {
+    samples: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl LatencyWindow {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn record(&mut self, latency_ns: u64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency_ns);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.samples.iter().min().copied()
+    }
+
+    /// Nearest-rank percentile (`q` in 0-1) over the window
+    pub fn percentile(&self, q: f64) -> Option<u64> {
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        nearest_rank(&sorted, q)
+    }
+
+    /// Percentiles of the window (all zero while it's empty)
+    pub fn summary(&self) -> LatencySummary {
+        let mut sorted: Vec<u64> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let at = |q| nearest_rank(&sorted, q).unwrap_or(0);
+        LatencySummary {
+            samples: sorted.len(),
+            min_ns: at(0.0),
+            p50_ns: at(0.50),
+            p95_ns: at(0.95),
+            p99_ns: at(0.99),
+            max_ns: at(1.0),
+            mean_ns: if sorted.is_empty() { 0.0 } else { sorted.iter().sum::<u64>() as f64 / sorted.len() as f64 },
+        }
+    }
+}
+
+fn nearest_rank(sorted: &[u64], q: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (q.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// One latency component over its window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub min_ns: u64,
+    pub p50_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+    pub mean_ns: f64,
+}
+
+/// Where a feed's latency is spent, as published for the dashboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyBreakdown {
+    /// Ping round trips
+    pub round_trip: LatencySummary,
+    /// Provider timestamp to receipt (feeds that stamp their updates)
+    pub network: LatencySummary,
+    /// Receipt to the latency engine, queueing and coalescing included
+    pub processing: LatencySummary,
+}
+
+/// Latency of one feed, split by where it's spent
 #[derive(Debug, Clone)]
 pub struct LatencyStats {
-    pub samples: Vec<u64>, // Rolling window of latency measurements
-    pub min_latency_ns: u64,
-    pub max_latency_ns: u64,
-    pub avg_latency_ns: f64,
+    /// Ping round trips; half the best is taken as one-way transit for clock sync
+    pub round_trip: LatencyWindow,
+    /// `provider_timestamp` (clock-synced when sync is on) to `received.wall`
+    pub network: LatencyWindow,
+    /// `received.mono` until the update reached the latency engine (process clock)
+    pub processing: LatencyWindow,
     pub last_updated: Instant,
 }
 
 impl LatencyStats {
-    pub fn new() -> Self {
+    pub fn new(window_size: usize) -> Self {
         Self {
-            samples: Vec::new(),
-            min_latency_ns: u64::MAX,
-            max_latency_ns: 0,
-            avg_latency_ns: 0.0,
+            round_trip: LatencyWindow::new(window_size),
+            network: LatencyWindow::new(window_size),
+            processing: LatencyWindow::new(window_size),
             last_updated: Instant::now(),
         }
     }
 
-    pub fn add_sample(&mut self, latency_ns: u64, window_size: usize) {
-        self.samples.push(latency_ns);
-        if self.samples.len() > window_size {
-            self.samples.remove(0);
-        }
+    pub fn record_round_trip(&mut self, latency_ns: u64) {
+        self.round_trip.record(latency_ns);
+        self.last_updated = Instant::now();
+    }
 
-        self.min_latency_ns = self.min_latency_ns.min(latency_ns);
-        self.max_latency_ns = self.max_latency_ns.max(latency_ns);
+    pub fn record_network(&mut self, latency_ns: u64) {
+        self.network.record(latency_ns);
+        self.last_updated = Instant::now();
+    }
 
-        let sum: u64 = self.samples.iter().sum();
-        self.avg_latency_ns = sum as f64 / self.samples.len() as f64;
+    pub fn record_processing(&mut self, latency_ns: u64) {
+        self.processing.record(latency_ns);
         self.last_updated = Instant::now();
     }
+
+    pub fn breakdown(&self) -> LatencyBreakdown {
+        LatencyBreakdown {
+            round_trip: self.round_trip.summary(),
+            network: self.network.summary(),
+            processing: self.processing.summary(),
+        }
+    }
 }
 
+/// Per-provider latency, shared with the update processing task
+pub type SharedLatencyStats = Arc<Mutex<HashMap<ProviderId, LatencyStats>>>;
+
 impl FeedAggregator {
     /// Create new feed aggregator
     pub fn new(
@@ -127,66 +603,418 @@ impl FeedAggregator {
         let (update_tx, update_rx) = mpsc::unbounded_channel();
 
         let aggregator = Self {
+            microstructure: Mutex::new(MicrostructureTracker::new(config.microstructure_window)),
             config,
             connections: HashMap::new(),
+            clients: HashMap::new(),
             update_tx,
             latency_engine,
-            market_tiers: HashMap::new(),
-            latency_stats: HashMap::new(),
+            market_metadata: Arc::new(MarketMetadataRegistry::default()),
+            market_activity: HashMap::new(),
+            latency_stats: Arc::new(Mutex::new(HashMap::new())),
+            feed_breaker: Arc::new(CircuitBreaker::new(BreakerConfig::default())),
+            plugin_breaker: Arc::new(CircuitBreaker::new(BreakerConfig::default())),
+            event_bus: None,
+            quotes: QuoteNormalizer::new(),
+            sanitizer: None,
+            clock_sync: None,
+            sequences: Mutex::new(SequenceTracker::new()),
+            clock: clock::system(),
         };
 
         (aggregator, update_rx)
     }
 
-    /// Add a provider feed connection
-    pub fn add_provider(&mut self, provider: Platform) {
-        let connection = FeedConnection {
-            provider,
-            status: FeedStatus::Disconnected,
-            last_heartbeat: Instant::now(),
-            reconnect_attempts: 0,
-            latency_ns: 0,
+    /// Add a provider feed connection (built-in venue or registered plugin)
+    pub fn add_provider(&mut self, provider: impl Into<ProviderId>) {
+        let provider = provider.into();
+        self.connections.insert(provider, FeedConnection::new(provider));
+        self.latency_stats.lock().unwrap().insert(provider, LatencyStats::new(self.config.latency_sample_window));
+
+        info!("Added feed provider: {}", provider);
+    }
+
+    /// Hand a client over so `check_connections` can reconnect it (take its price stream first)
+    pub fn add_client(&mut self, client: Box<dyn FeedClient>) {
+        let provider = client.provider();
+        if !self.connections.contains_key(&provider) {
+            self.add_provider(provider);
+        }
+        self.clients.insert(provider, client);
+    }
+
+    /// Connect a client handed over with `add_client`
+    pub async fn connect_provider(&mut self, provider: impl Into<ProviderId>) -> bool {
+        let provider = provider.into();
+        let Some(mut client) = self.clients.remove(&provider) else {
+            return false;
         };
+        let connected = self.connect_client(client.as_mut()).await;
+        self.clients.insert(provider, client);
+        connected
+    }
 
-        self.connections.insert(provider, connection);
-        self.latency_stats.insert(provider, LatencyStats::new());
+    /// Subscribe markets on a feed; they are resubscribed after every reconnect.
+    /// Owned, connected clients subscribe now, others on their next connect.
+    pub async fn subscribe(&mut self, provider: impl Into<ProviderId>, markets: &[String]) -> Result<(), FeedError> {
+        let provider = provider.into();
+        let Some(conn) = self.connections.get_mut(&provider) else {
+            return Err(FeedError::Unsupported { provider });
+        };
+        conn.subscriptions.extend(markets.iter().cloned());
+        let connected = matches!(conn.status, FeedStatus::Connected | FeedStatus::Degraded);
+        match self.clients.get_mut(&provider) {
+            Some(client) if connected => client.subscribe(markets).await,
+            _ => Ok(()),
+        }
+    }
 
-        info!("Added feed provider: {}", provider);
+    /// A feed's connection state (status, reconnect cycle, subscriptions)
+    pub fn connection(&self, provider: impl Into<ProviderId>) -> Option<&FeedConnection> {
+        self.connections.get(&provider.into())
+    }
+
+    /// Trip thresholds for the feed and plugin breakers (replaces a breaker set by `with_breaker`)
+    pub fn with_breaker_config(mut self, config: BreakerConfig) -> Self {
+        self.feed_breaker = Arc::new(CircuitBreaker::new(config.clone()));
+        self.plugin_breaker = Arc::new(CircuitBreaker::new(config));
+        self
     }
 
-    /// Set market tier for latency analysis
+    /// Share a breaker with other components (e.g. venue API clients)
+    pub fn with_breaker(mut self, breaker: SharedBreaker<Platform>) -> Self {
+        self.feed_breaker = breaker;
+        self
+    }
+
+    /// Publish ticks and connection status changes on the event bus
+    pub fn with_event_bus(mut self, bus: SharedEventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Replace the default venue quote rules
+    pub fn with_quote_normalizer(mut self, quotes: QuoteNormalizer) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Quarantine ticks that fail the sanity bounds (the same sanitizer can guard other engines)
+    pub fn with_sanitizer(mut self, sanitizer: SharedTickSanitizer) -> Self {
+        self.sanitizer = Some(sanitizer);
+        self
+    }
+
+    /// Measure processing latency on the clock the feed clients stamp `received` with (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Correct provider timestamps for each provider's clock offset and drift
+    pub fn with_clock_sync(mut self, sync: SharedClockSync) -> Self {
+        self.clock_sync = Some(sync);
+        self
+    }
+
+    /// Share market metadata with the latency engine (`LatencyArbitrageEngine::with_market_metadata`)
+    pub fn with_market_metadata(mut self, metadata: SharedMarketMetadata) -> Self {
+        self.market_metadata = metadata;
+        self
+    }
+
+    pub fn market_metadata(&self) -> &SharedMarketMetadata {
+        &self.market_metadata
+    }
+
+    pub fn quote_normalizer(&self) -> &QuoteNormalizer {
+        &self.quotes
+    }
+
+    /// Send a venue's native binary quote, normalized to cents (malformed quotes are dropped)
+    pub fn send_quote(
+        &self,
+        market_id: u16,
+        provider: Platform,
+        market_type: MarketType,
+        quote: &BinaryQuote,
+        received: Stamp,
+        provider_timestamp: Option<TimestampNs>,
+    ) -> Result<(), mpsc::error::SendError<PriceUpdate>> {
+        let Some((yes_price, no_price)) = self.quotes.binary(provider, quote) else {
+            warn!("Dropping malformed {:?} quote from {}", quote.yes, provider);
+            return Ok(());
+        };
+        self.send_price_update(PriceUpdate {
+            market_id,
+            provider: provider.into(),
+            market_type,
+            yes_price,
+            no_price,
+            yes_size: quote.yes_size,
+            no_size: quote.no_size,
+            received,
+            provider_timestamp,
+            sequence: None,
+            features: None,
+        })
+    }
+
+    /// Connect a feed client through the breaker and resubscribe the feed's markets;
+    /// open circuits skip the attempt
+    pub async fn connect_client(&mut self, client: &mut dyn FeedClient) -> bool {
+        let provider = client.provider();
+        self.update_connection_status(provider, FeedStatus::Connecting, None);
+        let result = match provider {
+            ProviderId::Builtin(platform) => self.feed_breaker.clone().call(platform, client.connect()).await,
+            plugin => self.plugin_breaker.clone().call(plugin, client.connect()).await,
+        };
+        match result {
+            Ok(()) => {
+                let markets: Vec<String> = self.connections.get(&provider)
+                    .map(|conn| conn.subscriptions.iter().cloned().collect())
+                    .unwrap_or_default();
+                if !markets.is_empty() {
+                    if let Err(e) = client.subscribe(&markets).await {
+                        warn!("Feed resubscribe failed: {}", e);
+                        self.update_connection_status(provider, FeedStatus::Error, None);
+                        return false;
+                    }
+                    info!("Resubscribed {} markets on {}", markets.len(), provider);
+                }
+                // A fresh connection starts from fresh snapshots
+                self.sequences.lock().unwrap().resynced(provider);
+                self.update_connection_status(provider, FeedStatus::Connected, None);
+                true
+            }
+            Err(BreakerError::Open { .. }) => {
+                self.update_connection_status(provider, FeedStatus::Disconnected, None);
+                false
+            }
+            Err(BreakerError::Inner(e)) => {
+                warn!("Feed connect failed: {}", e);
+                self.update_connection_status(provider, FeedStatus::Error, None);
+                false
+            }
+        }
+    }
+
+    /// Ping a feed client through the breaker and record the round-trip
+    pub async fn ping_client(&mut self, client: &mut dyn FeedClient) -> Option<u64> {
+        let provider = client.provider();
+        if !provider.capabilities().ping {
+            return self.measure_latency(provider).await;
+        }
+        let result = match provider {
+            ProviderId::Builtin(platform) => self.feed_breaker.clone().call(platform, client.ping()).await,
+            plugin => self.plugin_breaker.clone().call(plugin, client.ping()).await,
+        };
+        match result {
+            Ok(latency_ns) => {
+                self.update_connection_status(provider, FeedStatus::Connected, Some(latency_ns));
+                Some(latency_ns)
+            }
+            Err(BreakerError::Open { .. }) => None,
+            Err(BreakerError::Inner(e)) => {
+                warn!("Feed ping failed: {}", e);
+                self.update_connection_status(provider, FeedStatus::Error, None);
+                None
+            }
+        }
+    }
+
+    /// Pin a market's tier for latency analysis, overriding its classification
     pub fn set_market_tier(&mut self, market_id: u16, tier: MarketTier) {
-        self.market_tiers.insert(market_id, tier);
+        self.market_metadata.set_tier(market_id, tier);
+    }
+
+    /// Record a market's phase (and time to its scheduled start) for heartbeat scaling
+    pub fn set_market_phase(&mut self, market_id: u16, phase: Option<EventPhase>, starts_in: Option<Duration>) {
+        let tier = match self.market_metadata.tier(market_id) {
+            Some(MarketTier::Tier1) => 0,
+            Some(MarketTier::Tier2) => 1,
+            Some(MarketTier::Tier3) => 2,
+            Some(MarketTier::Tier4) | None => 3,
+        };
+        let before = self.activity();
+        let activity = self.config.heartbeat.activity(tier, phase, starts_in);
+        self.market_activity.insert(market_id, activity);
+        let after = self.activity();
+        if after != before {
+            info!("Feed heartbeat activity {:?} -> {:?}", before, after);
+        }
     }
 
-    /// Send price update to aggregator
-    pub fn send_price_update(&self, update: PriceUpdate) -> Result<(), mpsc::error::SendError<PriceUpdate>> {
+    /// Busiest activity across tracked markets
+    pub fn activity(&self) -> Activity {
+        self.market_activity.values().min().copied().unwrap_or(Activity::Idle)
+    }
+
+    /// How often `check_connections` should run for `provider` right now
+    pub fn heartbeat_interval(&self, provider: impl Into<ProviderId>) -> Duration {
+        self.config.heartbeat.interval(&provider.into().to_string(), self.activity())
+    }
+
+    /// Shortest check interval over connected providers (idle interval when there are none)
+    pub fn next_check_in(&self) -> Duration {
+        self.connections.keys()
+            .map(|&provider| self.heartbeat_interval(provider))
+            .min()
+            .unwrap_or_else(|| self.config.heartbeat.interval("", Activity::Idle))
+    }
+
+    /// Count activity from a provider as a heartbeat
+    pub fn record_heartbeat(&mut self, provider: impl Into<ProviderId>) {
+        if let Some(conn) = self.connections.get_mut(&provider.into()) {
+            conn.last_heartbeat = Instant::now();
+        }
+    }
+
+    /// Send price update to aggregator, attaching microstructure features
+    pub fn send_price_update(&self, mut update: PriceUpdate) -> Result<(), mpsc::error::SendError<PriceUpdate>> {
+        // Built-ins quote everything; plugins declare what they carry
+        if !update.provider.is_builtin() && !update.provider.capabilities().supports(update.market_type) {
+            warn!("Dropping {} update from {}: market type not in its capabilities", update.market_type, update.provider);
+            return Ok(());
+        }
+        match self.sequences.lock().unwrap().observe(update.provider, update.sequence) {
+            SequenceCheck::InOrder => {}
+            SequenceCheck::Gap { expected, got } => {
+                warn!("Sequence gap on {}: expected {}, got {}; holding its updates until resynced", update.provider, expected, got);
+                return Ok(());
+            }
+            SequenceCheck::Stale | SequenceCheck::Held => return Ok(()),
+        }
+        if let (Some(sync), Some(remote)) = (&self.clock_sync, update.provider_timestamp) {
+            // One-way transit taken as half the feed's best ping, else the provider's nominal latency
+            let best_ping = self.latency_stats.lock().unwrap().get(&update.provider).and_then(|stats| stats.round_trip.min());
+            let transit = match best_ping {
+                Some(rtt) => rtt / 2,
+                None => update.provider.capabilities().nominal_latency_ns,
+            };
+            update.provider_timestamp = Some(sync.observe(update.provider, remote, update.received.wall, transit));
+        }
+        // Network latency; stamps ahead of receipt (unsynced skew) say nothing about it
+        if let Some(sent) = update.provider_timestamp.filter(|&sent| sent <= update.received.wall.0) {
+            if let Some(stats) = self.latency_stats.lock().unwrap().get_mut(&update.provider) {
+                stats.record_network(update.received.wall.0 - sent);
+            }
+        }
+        if let Some(sanitizer) = &self.sanitizer {
+            if sanitizer.check(&update.to_tick()).is_err() {
+                return Ok(());
+            }
+        }
+        self.market_metadata.observe(update.market_id, update.market_type, update.yes_size, update.no_size);
+        if update.features.is_none() {
+            if let Some(book) = update.top_of_book() {
+                let mut tracker = self.microstructure.lock().unwrap();
+                update.features = Some(tracker.observe(update.market_id, update.provider, book));
+            }
+        }
+        if let Some(bus) = &self.event_bus {
+            bus.publish(Event::Tick(update.clone()));
+        }
         self.update_tx.send(update)
     }
 
-    /// Process incoming price updates (call this in a task)
-    pub async fn process_updates(mut update_rx: mpsc::UnboundedReceiver<PriceUpdate>, latency_engine: Arc<RwLock<LatencyArbitrageEngine>>) {
-        while let Some(update) = update_rx.recv().await {
+    /// Process incoming price updates (spawn the returned future as a task), coalesced
+    /// within `FeedAggregatorConfig::coalesce_window`
+    pub fn process_updates(&self, update_rx: mpsc::UnboundedReceiver<PriceUpdate>) -> impl Future<Output = ()> {
+        self.process_updates_with_capture(update_rx, None)
+    }
+
+    /// Stats handle for `process_updates_coalesced` to record processing latency into
+    pub fn latency_stats_handle(&self) -> SharedLatencyStats {
+        self.latency_stats.clone()
+    }
+
+    /// Process incoming price updates, recording every line move to the odds capture store
+    pub fn process_updates_with_capture(
+        &self,
+        update_rx: mpsc::UnboundedReceiver<PriceUpdate>,
+        capture: Option<OddsCaptureHandle>,
+    ) -> impl Future<Output = ()> {
+        Self::process_updates_coalesced(
+            update_rx,
+            self.latency_engine.clone(),
+            capture,
+            self.config.coalesce_window,
+            Some(self.latency_stats_handle()),
+            self.clock.clone(),
+        )
+    }
+
+    /// Process incoming price updates, merging bursts per market and provider within
+    /// `coalesce_window` (`FeedAggregatorConfig::coalesce_window`) before the engine sees them.
+    /// The odds capture still records every update as it arrives. With `latency_stats`
+    /// (`latency_stats_handle`) each update's processing latency is recorded, from its
+    /// `received` stamp to `clock`'s now.
+    pub async fn process_updates_coalesced(
+        mut update_rx: mpsc::UnboundedReceiver<PriceUpdate>,
+        latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
+        capture: Option<OddsCaptureHandle>,
+        coalesce_window: Duration,
+        latency_stats: Option<SharedLatencyStats>,
+        clock: SharedClock,
+    ) {
+        let mut detector = OddsChangeDetector::new();
+        let mut coalescer = UpdateCoalescer::new(coalesce_window);
+
+        loop {
+            let next_due = coalescer.next_due();
+            tokio::select! {
+                received = update_rx.recv() => {
+                    let Some(update) = received else {
+                        Self::observe_updates(&latency_engine, coalescer.drain(), latency_stats.as_ref(), &clock).await;
+                        if coalescer.merged() > 0 {
+                            info!("Coalesced {} price updates", coalescer.merged());
+                        }
+                        return;
+                    };
+                    if let (Some(capture), Some(platform)) = (&capture, update.provider.platform()) {
+                        let market_id = update.market_id.to_string();
+                        let sides = [("yes", update.yes_price, update.yes_size), ("no", update.no_price, update.no_size)];
+                        for (side, price, size) in sides {
+                            if price == 0 {
+                                continue;
+                            }
+                            if let Some(change) = detector.observe(
+                                platform, &market_id, update.market_type, side,
+                                price as f64, size as f64,
+                                update.provider_timestamp.unwrap_or(update.received.wall.0),
+                            ) {
+                                capture.record(change);
+                            }
+                        }
+                    }
+                    coalescer.push(update, Instant::now());
+                }
+                _ = tokio::time::sleep_until(next_due.unwrap_or_else(Instant::now)), if next_due.is_some() => {}
+            }
+            let due = coalescer.take_due(Instant::now());
+            Self::observe_updates(&latency_engine, due, latency_stats.as_ref(), &clock).await;
+        }
+    }
+
+    /// Hand (coalesced) updates to the latency engine
+    async fn observe_updates(
+        latency_engine: &RwLock<LatencyArbitrageEngine>,
+        updates: Vec<PriceUpdate>,
+        latency_stats: Option<&SharedLatencyStats>,
+        clock: &SharedClock,
+    ) {
+        for update in updates {
             // Measure processing latency
             let process_start = Instant::now();
 
-            // Convert to PriceObservation for latency analysis
-            let tier = MarketTier::Tier1; // TODO: Get from market_tiers mapping
-
-            let obs = PriceObservation {
-                market_id: update.market_id,
-                provider: update.provider,
-                market_type: update.market_type,
-                price: update.yes_price, // TODO: Handle both sides
-                size: update.yes_size,
-                timestamp_ns: update.received_timestamp,
-                tier,
-            };
-
-            // Add to latency engine
+            // Convert to PriceObservation at the market's tier and add to the latency engine
             {
                 let mut engine = latency_engine.write().await;
-                engine.add_price_observation(obs);
+                let tier = engine.market_tier(update.market_id, update.market_type);
+                if let Some(obs) = update.to_observation(tier) {
+                    engine.add_price_observation(obs);
+                }
             }
 
             // Log processing latency
@@ -194,99 +1022,223 @@ impl FeedAggregator {
             if process_duration > 10_000_000 { // >10ms warning
                 warn!("Slow price processing: {}ns for {} update", process_duration, update.provider);
             }
+            if let Some(stats) = latency_stats {
+                let since_receipt = clock.mono_ns().saturating_sub(update.received.mono).0;
+                if let Some(stats) = stats.lock().unwrap().get_mut(&update.provider) {
+                    stats.record_processing(since_receipt);
+                }
+            }
         }
     }
 
     /// Get current latency statistics
-    pub fn get_latency_stats(&self, provider: Platform) -> Option<&LatencyStats> {
-        self.latency_stats.get(&provider)
+    pub fn get_latency_stats(&self, provider: impl Into<ProviderId>) -> Option<LatencyStats> {
+        self.latency_stats.lock().unwrap().get(&provider.into()).cloned()
+    }
+
+    /// Publish each feed's latency breakdown on the bus (for the dashboard)
+    pub fn publish_latency(&self) {
+        let Some(bus) = &self.event_bus else { return };
+        let breakdowns: Vec<(ProviderId, LatencyBreakdown)> = self.latency_stats.lock().unwrap().iter()
+            .map(|(provider, stats)| (*provider, stats.breakdown()))
+            .collect();
+        for (provider, latency) in breakdowns {
+            bus.publish(Event::FeedLatency { provider, latency });
+        }
     }
 
     /// Update connection status
-    pub fn update_connection_status(&mut self, provider: Platform, status: FeedStatus, latency_ns: Option<u64>) {
+    pub fn update_connection_status(&mut self, provider: impl Into<ProviderId>, status: FeedStatus, latency_ns: Option<u64>) {
+        let provider = provider.into();
         if let Some(conn) = self.connections.get_mut(&provider) {
             conn.status = status;
             conn.last_heartbeat = Instant::now();
 
             if let Some(latency) = latency_ns {
                 conn.latency_ns = latency;
-                if let Some(stats) = self.latency_stats.get_mut(&provider) {
-                    stats.add_sample(latency, self.config.latency_sample_window);
+                if let Some(stats) = self.latency_stats.lock().unwrap().get_mut(&provider) {
+                    stats.record_round_trip(latency);
                 }
             }
 
             match status {
                 FeedStatus::Connected => {
                     info!("Feed connected: {} (latency: {}ns)", provider, conn.latency_ns);
-                    conn.reconnect_attempts = 0;
+                    conn.record_connected();
                 }
                 FeedStatus::Disconnected => {
                     warn!("Feed disconnected: {}", provider);
                 }
                 FeedStatus::Error => {
-                    error!("Feed error: {}", provider);
-                    conn.reconnect_attempts += 1;
+                    let jitter = rand::thread_rng().gen_range(-1.0..=1.0);
+                    match conn.record_failure(Instant::now(), &self.config.reconnect, jitter) {
+                        ReconnectState::CircuitOpen { .. } => error!(
+                            "Feed error: {} ({} failures in a row, reconnect circuit open for {:?})",
+                            provider, conn.reconnect_attempts, self.config.reconnect.cooldown,
+                        ),
+                        _ => error!("Feed error: {} (attempt {})", provider, conn.reconnect_attempts),
+                    }
                 }
                 FeedStatus::Connecting => {
                     info!("Connecting to feed: {}", provider);
                 }
+                FeedStatus::Degraded => {
+                    warn!("Feed degraded: {} (sequence gap, resyncing)", provider);
+                }
+            }
+
+            if let Some(bus) = &self.event_bus {
+                bus.publish(Event::Feed { provider, status, latency_ns: conn.latency_ns });
             }
         }
     }
 
     /// Get connection status summary
-    pub fn get_status_summary(&self) -> HashMap<Platform, (FeedStatus, u64)> {
+    pub fn get_status_summary(&self) -> HashMap<ProviderId, (FeedStatus, u64)> {
         self.connections.iter()
             .map(|(provider, conn)| (*provider, (conn.status, conn.latency_ns)))
             .collect()
     }
 
-    /// Check for stale connections and trigger reconnects
+    /// Check for stale connections and reconnect those due; the staleness threshold
+    /// tightens while a subscribed market is live (see `heartbeat::HeartbeatPolicy`).
+    /// Retries back off exponentially with jitter until `max_attempts` failures in a row open
+    /// the feed's reconnect circuit, which then tries once per cooldown. Feeds without an
+    /// owned client (`add_client`) are marked Connecting for their owner to reconnect.
+    /// Feeds with a sequence gap are resynced first (see `resync_gaps`); latency breakdowns
+    /// are published last (`publish_latency`).
     pub async fn check_connections(&mut self) {
+        self.resync_gaps().await;
         let now = Instant::now();
-        let stale_threshold = Duration::from_millis(self.config.heartbeat_interval_ms * 2);
+        let activity = self.activity();
+
+        let stale: Vec<(ProviderId, Duration)> = self.connections.values()
+            .filter(|conn| matches!(conn.status, FeedStatus::Connected | FeedStatus::Degraded))
+            .map(|conn| (conn.provider, self.config.heartbeat.stale_after(&conn.provider.to_string(), activity)))
+            .filter(|(provider, threshold)| now.duration_since(self.connections[provider].last_heartbeat) > *threshold)
+            .collect();
 
-        for (provider, conn) in &mut self.connections {
-            if now.duration_since(conn.last_heartbeat) > stale_threshold {
-                if conn.status == FeedStatus::Connected {
-                    warn!("Feed heartbeat timeout: {}", provider);
-                    self.update_connection_status(*provider, FeedStatus::Disconnected, None);
+        for (provider, threshold) in stale {
+            warn!("Feed heartbeat timeout: {} (silent > {:?}, {:?})", provider, threshold, activity);
+            self.update_connection_status(provider, FeedStatus::Disconnected, None);
+            if let Some(conn) = self.connections.get_mut(&provider) {
+                conn.record_lost(now);
+            }
+        }
+
+        let due: Vec<ProviderId> = self.connections.values()
+            .filter(|conn| conn.retry_due(now))
+            .map(|conn| conn.provider)
+            .collect();
+
+        for provider in due {
+            if let Some(conn) = self.connections.get_mut(&provider) {
+                info!("Reconnecting feed: {} (attempt {})", provider, conn.reconnect_attempts + 1);
+                conn.reconnect = ReconnectState::Reconnecting;
+            }
+            let Some(mut client) = self.clients.remove(&provider) else {
+                self.update_connection_status(provider, FeedStatus::Connecting, None);
+                continue;
+            };
+            if let Err(e) = client.disconnect().await {
+                warn!("Feed disconnect before reconnect failed: {}", e);
+            }
+            let connected = self.connect_client(client.as_mut()).await;
+            self.clients.insert(provider, client);
+            // Skipped by an open breaker: still a failed attempt
+            let still_pending = self.connections.get(&provider).is_some_and(|c| c.reconnect == ReconnectState::Reconnecting);
+            if !connected && still_pending {
+                let jitter = rand::thread_rng().gen_range(-1.0..=1.0);
+                if let Some(conn) = self.connections.get_mut(&provider) {
+                    conn.record_failure(Instant::now(), &self.config.reconnect, jitter);
                 }
+            }
+        }
+        self.publish_latency();
+    }
 
-                // Trigger reconnect if under max attempts
-                if conn.reconnect_attempts < self.config.max_reconnect_attempts {
-                    self.update_connection_status(*provider, FeedStatus::Connecting, None);
-                    // TODO: Actually trigger reconnect logic
+    /// Mark feeds with a sequence gap Degraded and refetch their snapshots
+    /// (`FeedClient::resync`); a feed whose client can't resync is reconnected instead, which
+    /// resubscribes from fresh snapshots. Feeds without an owned client stay Degraded until
+    /// their owner calls `sequence_resynced`.
+    pub async fn resync_gaps(&mut self) {
+        let pending = {
+            let mut sequences = self.sequences.lock().unwrap();
+            for (provider, client) in &self.clients {
+                if sequences.report_gaps(*provider, client.sequence_gaps()) {
+                    warn!("Sequence gap reported by {}", provider);
                 }
             }
+            sequences.pending()
+        };
+
+        for provider in pending {
+            let status = match self.connections.get(&provider) {
+                Some(conn) => conn.status,
+                None => continue,
+            };
+            // Lost feeds resync by reconnecting
+            if !matches!(status, FeedStatus::Connected | FeedStatus::Degraded) {
+                continue;
+            }
+            if status == FeedStatus::Connected {
+                self.update_connection_status(provider, FeedStatus::Degraded, None);
+            }
+            let Some(mut client) = self.clients.remove(&provider) else {
+                continue;
+            };
+            let markets: Vec<String> = self.connections[&provider].subscriptions.iter().cloned().collect();
+            match client.resync(&markets).await {
+                Ok(()) => {
+                    info!("Resynced {} from snapshots", provider);
+                    self.sequence_resynced(provider);
+                }
+                Err(e) => {
+                    warn!("Feed resync failed: {}; reconnecting", e);
+                    self.update_connection_status(provider, FeedStatus::Disconnected, None);
+                    if let Some(conn) = self.connections.get_mut(&provider) {
+                        conn.record_lost(Instant::now());
+                    }
+                }
+            }
+            self.clients.insert(provider, client);
         }
     }
 
+    /// A Degraded feed's snapshots have been refetched: its updates flow again
+    pub fn sequence_resynced(&mut self, provider: impl Into<ProviderId>) {
+        let provider = provider.into();
+        self.sequences.lock().unwrap().resynced(provider);
+        if self.connections.get(&provider).is_some_and(|conn| conn.status == FeedStatus::Degraded) {
+            self.update_connection_status(provider, FeedStatus::Connected, None);
+        }
+    }
+
+    /// Sequence gaps detected so far, over all feeds
+    pub fn sequence_gaps(&self) -> u64 {
+        self.sequences.lock().unwrap().gaps()
+    }
+
     /// Measure round-trip latency to provider
-    pub async fn measure_latency(&mut self, provider: Platform) -> Option<u64> {
+    pub async fn measure_latency(&mut self, provider: impl Into<ProviderId>) -> Option<u64> {
         if !self.config.enable_latency_tracking {
             return None;
         }
+        let provider = provider.into();
 
         let start = Instant::now();
 
         // TODO: Send ping/pong or measure actual message round-trip
-        // For now, simulate based on provider
-        let simulated_latency_ns = match provider {
-            Platform::Kalshi => 50_000,      // 50μs
-            Platform::Polymarket => 75_000,  // 75μs
-            Platform::DraftKings => 200_000, // 200μs (typical sportsbook)
-            Platform::FanDuel => 180_000,    // 180μs
-            _ => 150_000, // 150μs default
-        };
+        // For now, simulate from the provider's nominal latency
+        let simulated_latency_ns = provider.capabilities().nominal_latency_ns;
 
         // Simulate network delay
         tokio::time::sleep(Duration::from_nanos(simulated_latency_ns)).await;
 
         let measured = start.elapsed().as_nanos() as u64;
 
-        if let Some(stats) = self.latency_stats.get_mut(&provider) {
-            stats.add_sample(measured, self.config.latency_sample_window);
+        if let Some(stats) = self.latency_stats.lock().unwrap().get_mut(&provider) {
+            stats.record_round_trip(measured);
         }
 
         Some(measured)
@@ -302,20 +1254,40 @@ impl FeedAggregator {
 /// WebSocket feed client trait for different providers
 #[async_trait::async_trait]
 pub trait FeedClient: Send + Sync {
-    /// Provider this client handles
-    fn provider(&self) -> Platform;
+    /// Provider this client handles; third-party feeds return the id from
+    /// `provider_registry::providers().register(..)`
+    fn provider(&self) -> ProviderId;
 
     /// Connect to the feed
-    async fn connect(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn connect(&mut self) -> Result<(), FeedError>;
 
     /// Disconnect from the feed
-    async fn disconnect(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    async fn disconnect(&mut self) -> Result<(), FeedError>;
 
     /// Get price update stream
     fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate>;
 
     /// Send ping for latency measurement
-    async fn ping(&mut self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+    async fn ping(&mut self) -> Result<u64, FeedError>;
+
+    /// Subscribe markets on the open connection; called again with every subscribed market
+    /// after a reconnect. Feeds fixed to their markets at construction need not override it.
+    async fn subscribe(&mut self, _markets: &[String]) -> Result<(), FeedError> {
+        Ok(())
+    }
+
+    /// Refetch snapshots of `markets` (every market the client carries when empty) and replay
+    /// them into the price stream after a sequence gap. Feeds without a snapshot endpoint keep
+    /// the default and are reconnected instead.
+    async fn resync(&mut self, _markets: &[String]) -> Result<(), FeedError> {
+        Err(FeedError::Unsupported { provider: self.provider() })
+    }
+
+    /// Gaps seen so far in sequence numbers local to this client's connections (not stamped
+    /// on updates); feeds whose updates carry `sequence` need not override it
+    fn sequence_gaps(&self) -> u64 {
+        0
+    }
 }
 
 impl Default for FeedAggregator {
@@ -324,3 +1296,527 @@ impl Default for FeedAggregator {
         engine
     }
 }
+
+// === Sportsbook odds adapters ===
+
+/// Odds as a sportsbook quotes one side
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Odds {
+    /// Moneyline style, e.g. -110 / +150
+    American(i32),
+    /// European style, stake included (1.91 is about -110)
+    Decimal(f64),
+}
+
+impl Odds {
+    /// Decimal odds (None when malformed: American between -100 and +100, decimal at or under 1)
+    pub fn to_decimal(self) -> Option<f64> {
+        match self {
+            Odds::American(odds) if odds >= 100 => Some(1.0 + odds as f64 / 100.0),
+            Odds::American(odds) if odds <= -100 => Some(1.0 + 100.0 / -odds as f64),
+            Odds::American(_) => None,
+            Odds::Decimal(odds) => (odds.is_finite() && odds > 1.0).then_some(odds),
+        }
+    }
+
+    /// American odds, rounded to the nearest whole number for decimal quotes
+    pub fn to_american(self) -> Option<i32> {
+        let decimal = self.to_decimal()?;
+        Some(match self {
+            Odds::American(odds) => odds,
+            Odds::Decimal(_) if decimal >= 2.0 => ((decimal - 1.0) * 100.0).round() as i32,
+            Odds::Decimal(_) => (-100.0 / (decimal - 1.0)).round() as i32,
+        })
+    }
+
+    /// Implied probability, juice included
+    pub fn implied(self) -> Option<f64> {
+        self.to_decimal().map(|decimal| 1.0 / decimal)
+    }
+
+    /// The quote the normalizer rounds onto the aggregator's cent scale
+    pub fn to_quote(self) -> Option<Quote> {
+        match self {
+            Odds::American(odds) => self.to_decimal().map(|_| Quote::American(odds)),
+            Odds::Decimal(_) => self.implied().map(Quote::Decimal),
+        }
+    }
+}
+
+/// One two-way sportsbook market in the normalized odds schema; YES is the home / over side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SportsbookLine {
+    /// The provider's event id
+    pub event_id: String,
+    pub market_type: MarketType,
+    /// Points on the YES side: the home spread (-3.5) or the total (220.5); None for moneylines
+    pub line: Option<f64>,
+    pub yes: Odds,
+    pub no: Odds,
+    /// Largest stake the book takes per side (dollars), when the provider says
+    pub max_stake: Option<f64>,
+    /// Provider's last-change time (Unix ns)
+    pub updated_ns: Option<TimestampNs>,
+}
+
+impl SportsbookLine {
+    /// How far the two implied probabilities sum past 1 (about 0.0476 on -110 / -110)
+    pub fn juice(&self) -> Option<f64> {
+        Some(self.yes.implied()? + self.no.implied()? - 1.0)
+    }
+
+    /// YES probability with the juice removed proportionally
+    pub fn fair_yes(&self) -> Option<f64> {
+        let (yes, no) = (self.yes.implied()?, self.no.implied()?);
+        Some(yes / (yes + no))
+    }
+
+    /// Both sides as the aggregator's binary quote (None when either side is malformed)
+    pub fn to_binary_quote(&self) -> Option<BinaryQuote> {
+        let size = self.max_stake.map_or(0, |dollars| (dollars * 100.0).round().clamp(0.0, SizeCents::MAX as f64) as SizeCents);
+        Some(BinaryQuote { yes: self.yes.to_quote()?, no: Some(self.no.to_quote()?), yes_size: size, no_size: size })
+    }
+}
+
+/// Line points in hundredths, so 3.5 and 3.50 key the same market
+fn line_key(line: Option<f64>) -> Option<i64> {
+    line.map(|points| (points * 100.0).round() as i64)
+}
+
+/// Which aggregator market each provider line feeds; a line that moves to unmapped points is dropped
+#[derive(Debug, Clone, Default)]
+pub struct SportsbookMarkets {
+    ids: HashMap<(String, MarketType, Option<i64>), u16>,
+}
+
+impl SportsbookMarkets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_market(mut self, event_id: &str, market_type: MarketType, line: Option<f64>, market_id: u16) -> Self {
+        self.ids.insert((event_id.to_string(), market_type, line_key(line)), market_id);
+        self
+    }
+
+    pub fn market_id(&self, line: &SportsbookLine) -> Option<u16> {
+        self.ids.get(&(line.event_id.clone(), line.market_type, line_key(line.line))).copied()
+    }
+}
+
+/// A third-party odds provider: fetches its lines in the normalized schema. Run it behind the
+/// `FeedClient` trait with `SportsbookFeedClient`
+#[async_trait::async_trait]
+pub trait SportsbookFeedAdapter: Send + Sync + 'static {
+    /// A built-in book, or the id `provider_registry::providers().register(..)` returned
+    fn provider(&self) -> ProviderId;
+
+    /// Current lines for every event the adapter covers
+    async fn fetch_lines(&self) -> Result<Vec<SportsbookLine>, FeedError>;
+
+    /// How often `fetch_lines` is polled
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+}
+
+/// Normalizes fetched lines into price updates, emitting only the ones that moved
+struct LinePump {
+    provider: ProviderId,
+    markets: SportsbookMarkets,
+    rule: VenueRule,
+    clock: SharedClock,
+    updates: mpsc::UnboundedSender<PriceUpdate>,
+    last: HashMap<u16, (PriceCents, PriceCents, SizeCents)>,
+}
+
+impl LinePump {
+    /// False once nobody reads the price stream
+    fn emit(&mut self, lines: &[SportsbookLine]) -> bool {
+        let received = self.clock.now();
+        for line in lines {
+            let Some(market_id) = self.markets.market_id(line) else { continue };
+            let Some(quote) = line.to_binary_quote() else {
+                warn!("Dropping malformed {} line from {}: {:?} / {:?}", line.event_id, self.provider, line.yes, line.no);
+                continue;
+            };
+            let Some((yes_price, no_price)) = self.rule.binary(&quote) else { continue };
+            if self.last.insert(market_id, (yes_price, no_price, quote.yes_size)) == Some((yes_price, no_price, quote.yes_size)) {
+                continue;
+            }
+            let update = PriceUpdate {
+                market_id,
+                provider: self.provider,
+                market_type: line.market_type,
+                yes_price,
+                no_price,
+                yes_size: quote.yes_size,
+                no_size: quote.no_size,
+                received,
+                provider_timestamp: line.updated_ns,
+                sequence: None,
+                features: None,
+            };
+            if self.updates.send(update).is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+async fn run_line_pump<A: SportsbookFeedAdapter>(adapter: Arc<A>, mut pump: LinePump) {
+    loop {
+        tokio::time::sleep(adapter.poll_interval()).await;
+        match adapter.fetch_lines().await {
+            Ok(lines) => {
+                if !pump.emit(&lines) {
+                    return;
+                }
+            }
+            Err(e) => warn!("Sportsbook fetch failed: {}", e),
+        }
+    }
+}
+
+/// `FeedClient` over any sportsbook adapter: polls it, rounds the odds onto the cent scale with
+/// the provider's quote rule and streams the lines that moved
+pub struct SportsbookFeedClient<A> {
+    adapter: Arc<A>,
+    markets: SportsbookMarkets,
+    quotes: QuoteNormalizer,
+    clock: SharedClock,
+    update_tx: mpsc::UnboundedSender<PriceUpdate>,
+    update_rx: Option<mpsc::UnboundedReceiver<PriceUpdate>>,
+    poller: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl<A: SportsbookFeedAdapter> SportsbookFeedClient<A> {
+    pub fn new(adapter: A, markets: SportsbookMarkets) -> Self {
+        let (update_tx, update_rx) = mpsc::unbounded_channel();
+        Self {
+            adapter: Arc::new(adapter),
+            markets,
+            quotes: QuoteNormalizer::new(),
+            clock: clock::system(),
+            update_tx,
+            update_rx: Some(update_rx),
+            poller: None,
+        }
+    }
+
+    /// Same venue rules as the aggregator (see `FeedAggregator::quote_normalizer`)
+    pub fn with_quote_normalizer(mut self, quotes: QuoteNormalizer) -> Self {
+        self.quotes = quotes;
+        self
+    }
+
+    /// Stamp `received` from another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn adapter(&self) -> &A {
+        &self.adapter
+    }
+}
+
+#[async_trait::async_trait]
+impl<A: SportsbookFeedAdapter> FeedClient for SportsbookFeedClient<A> {
+    fn provider(&self) -> ProviderId {
+        self.adapter.provider()
+    }
+
+    /// The first fetch runs here, so a provider that is down fails the connect
+    async fn connect(&mut self) -> Result<(), FeedError> {
+        if self.poller.as_ref().is_some_and(|p| !p.is_finished()) {
+            return Ok(());
+        }
+        let provider = self.adapter.provider();
+        let mut pump = LinePump {
+            provider,
+            markets: self.markets.clone(),
+            rule: self.quotes.provider_rule(provider),
+            clock: self.clock.clone(),
+            updates: self.update_tx.clone(),
+            last: HashMap::new(),
+        };
+        let lines = self.adapter.fetch_lines().await?;
+        pump.emit(&lines);
+        info!("Sportsbook feed {} connected with {} lines", provider, lines.len());
+        self.poller = Some(tokio::spawn(run_line_pump(self.adapter.clone(), pump)));
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), FeedError> {
+        if let Some(poller) = self.poller.take() {
+            poller.abort();
+        }
+        Ok(())
+    }
+
+    /// Updates of every connection; the first call takes the stream, later calls get a closed one
+    fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+        self.update_rx.take().unwrap_or_else(|| mpsc::unbounded_channel().1)
+    }
+
+    /// Odds APIs have no ping frame; a fetch is timed instead
+    async fn ping(&mut self) -> Result<u64, FeedError> {
+        let start = Instant::now();
+        self.adapter.fetch_lines().await?;
+        Ok(start.elapsed().as_nanos() as u64)
+    }
+}
+
+impl<A> Drop for SportsbookFeedClient<A> {
+    fn drop(&mut self) {
+        if let Some(poller) = self.poller.take() {
+            poller.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odds_conversions_and_juice() {
+        assert_eq!(Odds::American(150).to_decimal(), Some(2.5));
+        assert_eq!(Odds::American(-200).to_decimal(), Some(1.5));
+        assert_eq!(Odds::Decimal(1.91).to_american(), Some(-110));
+        assert_eq!(Odds::Decimal(3.2).to_american(), Some(220));
+        assert_eq!(Odds::American(-120).to_american(), Some(-120));
+        assert_eq!(Odds::American(50).to_decimal(), None);
+        assert_eq!(Odds::Decimal(0.9).implied(), None);
+
+        let line = SportsbookLine {
+            event_id: "NBA-1".into(),
+            market_type: MarketType::Spread,
+            line: Some(-3.5),
+            yes: Odds::American(-110),
+            no: Odds::Decimal(1.0 + 100.0 / 110.0),
+            max_stake: Some(250.0),
+            updated_ns: None,
+        };
+        assert!((line.juice().unwrap() - 0.0476).abs() < 1e-4);
+        assert!((line.fair_yes().unwrap() - 0.5).abs() < 1e-9);
+        // Both sides round up onto the cent scale, vig kept
+        assert_eq!(VenueRule::SPORTSBOOK.binary(&line.to_binary_quote().unwrap()), Some((53, 53)));
+        assert_eq!(line.to_binary_quote().unwrap().yes_size, 25_000);
+    }
+
+    fn update(market_id: u16, platform: Platform, yes_price: PriceCents, mono: u64, provider_ns: Option<TimestampNs>) -> PriceUpdate {
+        PriceUpdate {
+            market_id,
+            provider: platform.into(),
+            market_type: MarketType::Moneyline,
+            yes_price,
+            no_price: 100 - yes_price,
+            yes_size: 1_000,
+            no_size: 1_000,
+            received: Stamp { mono: Nanos(mono), wall: Nanos(1_000_000 + mono) },
+            provider_timestamp: provider_ns,
+            sequence: None,
+            features: None,
+        }
+    }
+
+    #[test]
+    fn test_reconnect_backoff_and_circuit() {
+        assert_eq!(ReconnectPolicy::from_feeds(&FeedsSection::default()), ReconnectPolicy::default());
+        let s = Duration::from_secs;
+        let policy = ReconnectPolicy { base_delay: s(1), max_delay: s(8), jitter: 0.5, max_attempts: 4, cooldown: s(60) };
+        let delays: Vec<Duration> = (1..=6).map(|attempt| policy.delay(attempt, 0.0)).collect();
+        assert_eq!(delays, vec![s(1), s(2), s(4), s(8), s(8), s(8)]);
+        assert_eq!((policy.delay(1, 1.0), policy.delay(1, -3.0)), (Duration::from_millis(1500), Duration::from_millis(500)));
+
+        let now = Instant::now();
+        let mut conn = FeedConnection::new(Platform::Kalshi.into());
+        assert!(!conn.retry_due(now));
+        conn.record_lost(now);
+        assert!(conn.retry_due(now));
+        for (attempt, delay) in [(1, s(1)), (2, s(2)), (3, s(4))] {
+            assert_eq!(conn.record_failure(now, &policy, 0.0), ReconnectState::Backoff { retry_at: now + delay });
+            assert_eq!(conn.reconnect_attempts, attempt);
+        }
+        // The fourth failure in a row opens the circuit until the cooldown passes
+        assert_eq!(conn.record_failure(now, &policy, 0.0), ReconnectState::CircuitOpen { retry_at: now + s(60) });
+        assert!(!conn.retry_due(now + s(59)) && conn.retry_due(now + s(60)));
+        // ...and stays open while attempts keep failing
+        assert!(matches!(conn.record_failure(now, &policy, 0.0), ReconnectState::CircuitOpen { .. }));
+        conn.record_connected();
+        assert_eq!((conn.reconnect, conn.reconnect_attempts), (ReconnectState::Idle, 0));
+    }
+
+    #[test]
+    fn test_sequence_gaps_hold_feed_until_resynced() {
+        let (kalshi, poly): (ProviderId, ProviderId) = (Platform::Kalshi.into(), Platform::Polymarket.into());
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.observe(kalshi, Some(7)), SequenceCheck::InOrder);
+        assert_eq!(tracker.observe(kalshi, Some(8)), SequenceCheck::InOrder);
+        assert_eq!(tracker.observe(kalshi, Some(8)), SequenceCheck::Stale);
+        assert_eq!(tracker.observe(kalshi, Some(11)), SequenceCheck::Gap { expected: 9, got: 11 });
+        // Everything from the feed is held until its snapshots land, other feeds flow
+        assert_eq!(tracker.observe(kalshi, Some(12)), SequenceCheck::Held);
+        assert_eq!(tracker.observe(kalshi, None), SequenceCheck::Held);
+        assert_eq!(tracker.observe(poly, None), SequenceCheck::InOrder);
+        assert_eq!(tracker.pending(), vec![kalshi]);
+        tracker.resynced(kalshi);
+        assert_eq!(tracker.observe(kalshi, Some(40)), SequenceCheck::InOrder);
+
+        // Clients checking their own connections report a running count
+        assert!(!tracker.report_gaps(poly, 0));
+        assert!(tracker.report_gaps(poly, 2));
+        assert!(!tracker.report_gaps(poly, 2));
+        assert!(tracker.is_pending(poly) && !tracker.is_pending(kalshi));
+        assert_eq!(tracker.gaps(), 2);
+    }
+
+    #[test]
+    fn test_latency_breakdown_percentiles() {
+        let mut stats = LatencyStats::new(100);
+        assert_eq!(stats.breakdown(), LatencyBreakdown::default());
+        for ms in 1..=100u64 {
+            stats.record_network(ms * 1_000_000);
+            stats.record_processing(ms * 1_000);
+        }
+        stats.record_round_trip(4_000_000);
+        let latency = stats.breakdown();
+        assert_eq!((latency.network.p50_ns, latency.network.p95_ns, latency.network.p99_ns), (50_000_000, 95_000_000, 99_000_000));
+        assert_eq!((latency.processing.min_ns, latency.processing.max_ns), (1_000, 100_000));
+        assert!((latency.processing.mean_ns - 50_500.0).abs() < 1e-6);
+        assert_eq!((latency.round_trip.samples, latency.round_trip.p99_ns), (1, 4_000_000));
+
+        // The window rolls: the oldest samples fall out
+        let mut window = LatencyWindow::new(3);
+        for ns in [900, 10, 20, 30] {
+            window.record(ns);
+        }
+        assert_eq!((window.len(), window.min(), window.percentile(1.0)), (3, Some(10), Some(30)));
+        assert_eq!(window.percentile(0.5), Some(20));
+    }
+
+    #[test]
+    fn test_coalescer_merges_bursts_keeping_earliest_stamps() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut coalescer = UpdateCoalescer::new(ms(5));
+        // A key's first update goes straight through
+        coalescer.push(update(1, Platform::Kalshi, 50, 100, None), start);
+        assert_eq!(coalescer.take_due(start).iter().map(|u| u.yes_price).collect::<Vec<_>>(), vec![50]);
+
+        coalescer.push(update(1, Platform::Polymarket, 48, 150, Some(900)), start + ms(1));
+        coalescer.push(update(1, Platform::Kalshi, 52, 200, Some(700)), start + ms(2));
+        coalescer.push(update(1, Platform::Kalshi, 53, 300, Some(800)), start + ms(3));
+        assert_eq!(coalescer.merged(), 1);
+        assert_eq!(coalescer.next_due(), Some(start + ms(1)));
+        let due = coalescer.take_due(start + ms(4));
+        assert_eq!(due.iter().map(|u| u.provider).collect::<Vec<_>>(), vec![ProviderId::from(Platform::Polymarket)]);
+        assert_eq!(coalescer.next_due(), Some(start + ms(5)));
+
+        // The latest book, stamped when the first held update arrived
+        let due = coalescer.take_due(start + ms(5));
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].provider, due[0].yes_price, due[0].no_price), (Platform::Kalshi.into(), 53, 47));
+        assert_eq!((due[0].received.mono, due[0].provider_timestamp), (Nanos(200), Some(700)));
+
+        // Releasing the held update opens the next window
+        coalescer.push(update(1, Platform::Kalshi, 54, 400, None), start + ms(6));
+        assert_eq!(coalescer.next_due(), Some(start + ms(10)));
+        let rest = coalescer.drain();
+        assert_eq!(rest.iter().map(|u| (u.provider, u.yes_price)).collect::<Vec<_>>(), vec![(Platform::Kalshi.into(), 54)]);
+        assert_eq!(coalescer.next_due(), None);
+    }
+
+    #[tokio::test]
+    async fn test_process_updates_coalesces_within_configured_window() {
+        let config = FeedAggregatorConfig { coalesce_window: Duration::from_millis(50), ..FeedAggregatorConfig::default() };
+        let engine = Arc::new(RwLock::new(LatencyArbitrageEngine::new()));
+        let (mut aggregator, update_rx) = FeedAggregator::new(config, engine);
+        aggregator.add_provider(Platform::Kalshi);
+        let stats = aggregator.latency_stats_handle();
+        let processing = aggregator.process_updates(update_rx);
+
+        for (i, price) in [50, 51, 52].into_iter().enumerate() {
+            aggregator.update_tx.send(update(1, Platform::Kalshi, price, i as u64, None)).unwrap();
+        }
+        drop(aggregator);
+        processing.await;
+
+        // The first update went through alone; the other two reached the engine as one
+        assert_eq!(stats.lock().unwrap()[&ProviderId::from(Platform::Kalshi)].processing.len(), 2);
+    }
+
+    #[test]
+    fn test_zero_coalesce_window_passes_every_update() {
+        let now = Instant::now();
+        let mut coalescer = UpdateCoalescer::new(Duration::ZERO);
+        for (i, price) in [40, 41, 42].into_iter().enumerate() {
+            coalescer.push(update(7, Platform::Kalshi, price, i as u64, None), now);
+            let due = coalescer.take_due(now);
+            assert_eq!(due.iter().map(|u| u.yes_price).collect::<Vec<_>>(), vec![price]);
+        }
+        assert_eq!(coalescer.merged(), 0);
+    }
+
+    struct ScriptedBook {
+        lines: Mutex<Vec<Vec<SportsbookLine>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SportsbookFeedAdapter for ScriptedBook {
+        fn provider(&self) -> ProviderId {
+            Platform::DraftKings.into()
+        }
+
+        /// Each fetch serves the next script step; the last one repeats
+        async fn fetch_lines(&self) -> Result<Vec<SportsbookLine>, FeedError> {
+            let mut lines = self.lines.lock().unwrap();
+            Ok(if lines.len() > 1 { lines.remove(0) } else { lines[0].clone() })
+        }
+
+        fn poll_interval(&self) -> Duration {
+            Duration::from_millis(5)
+        }
+    }
+
+    fn total(points: f64, over: i32, under: i32) -> SportsbookLine {
+        SportsbookLine {
+            event_id: "NBA-2".into(),
+            market_type: MarketType::Total,
+            line: Some(points),
+            yes: Odds::American(over),
+            no: Odds::American(under),
+            max_stake: None,
+            updated_ns: Some(1_700_000_000_000_000_000),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sportsbook_client_streams_moved_lines() {
+        let book = ScriptedBook { lines: Mutex::new(vec![
+            vec![total(220.5, -110, -110)],
+            vec![total(220.5, -110, -110)], // unchanged: not re-sent
+            vec![total(220.5, -125, 105)],
+            vec![total(221.5, -110, -110)], // moved to unmapped points
+        ]) };
+        let markets = SportsbookMarkets::new().with_market("NBA-2", MarketType::Total, Some(220.50), 9);
+        let mut client = SportsbookFeedClient::new(book, markets);
+        let mut prices = client.price_stream();
+        client.connect().await.unwrap();
+
+        let first = prices.recv().await.unwrap();
+        assert_eq!((first.market_id, first.provider, first.market_type), (9, Platform::DraftKings.into(), MarketType::Total));
+        assert_eq!((first.yes_price, first.no_price, first.provider_timestamp), (53, 53, Some(1_700_000_000_000_000_000)));
+        let moved = tokio::time::timeout(Duration::from_secs(1), prices.recv()).await.unwrap().unwrap();
+        assert_eq!((moved.yes_price, moved.no_price), (56, 49));
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(prices.try_recv().is_err());
+        assert!(client.ping().await.is_ok());
+        client.disconnect().await.unwrap();
+    }
+}
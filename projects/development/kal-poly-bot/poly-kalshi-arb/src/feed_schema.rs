@@ -0,0 +1,558 @@
+// src/feed_schema.rs
+// Per-provider feed message schemas - versioned layouts, strict/lenient checking, quarantine of
+// rejected messages and drift counters so venue format changes show up the day they ship
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{warn, Level};
+
+use crate::clock::{self, SharedClock};
+use crate::config::FeedsSection;
+use crate::error::FeedError;
+use crate::types::Platform;
+
+/// Longest raw payload kept per quarantined message
+const QUARANTINE_RAW_BYTES: usize = 4096;
+
+/// What happens to messages carrying fields no known version lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaMode {
+    /// Reject and quarantine them
+    Strict,
+    /// Count the drift and process them
+    Lenient,
+}
+
+impl SchemaMode {
+    /// "strict" | "lenient" (anything else is lenient)
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "strict" => SchemaMode::Strict,
+            _ => SchemaMode::Lenient,
+        }
+    }
+}
+
+/// JSON type a field must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Str,
+    Num,
+    Arr,
+    Obj,
+    Any,
+}
+
+impl Kind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            Kind::Str => value.is_string(),
+            Kind::Num => value.is_number(),
+            Kind::Arr => value.is_array(),
+            Kind::Obj => value.is_object(),
+            Kind::Any => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: Kind,
+    pub required: bool,
+}
+
+const fn req(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: true }
+}
+
+const fn opt(name: &'static str, kind: Kind) -> Field {
+    Field { name, kind, required: false }
+}
+
+/// One layout of a message type
+#[derive(Debug)]
+pub struct SchemaVersion {
+    pub version: u16,
+    pub fields: &'static [Field],
+}
+
+/// A provider message type and its known layouts, oldest first
+#[derive(Debug)]
+pub struct MessageSchema {
+    pub provider: Platform,
+    pub message_type: &'static str,
+    /// Object holding the fields (Kalshi nests them under "msg"); the message itself when None
+    pub body: Option<&'static str>,
+    /// No versions: a control message whose fields aren't checked
+    pub versions: &'static [SchemaVersion],
+}
+
+use Kind::{Any, Arr, Num, Str};
+
+const KALSHI_SNAPSHOT_V1: &[Field] = &[
+    req("market_ticker", Str), opt("market_id", Str), opt("yes", Arr), opt("no", Arr),
+];
+/// Adds dollar-denominated levels alongside the cent ones
+const KALSHI_SNAPSHOT_V2: &[Field] = &[
+    req("market_ticker", Str), opt("market_id", Str), opt("yes", Arr), opt("no", Arr),
+    opt("yes_dollars", Arr), opt("no_dollars", Arr),
+];
+const KALSHI_DELTA_V1: &[Field] = &[
+    req("market_ticker", Str), opt("market_id", Str), req("price", Num), req("delta", Num), req("side", Str),
+    opt("ts", Str), opt("client_order_id", Str),
+];
+const KALSHI_DELTA_V2: &[Field] = &[
+    req("market_ticker", Str), opt("market_id", Str), req("price", Num), req("delta", Num), req("side", Str),
+    opt("ts", Str), opt("client_order_id", Str), opt("price_dollars", Str),
+];
+const POLY_BOOK_V1: &[Field] = &[
+    opt("event_type", Str), req("asset_id", Str), opt("market", Str), req("bids", Arr), req("asks", Arr),
+    opt("timestamp", Str), opt("hash", Str),
+];
+const POLY_BOOK_V2: &[Field] = &[
+    opt("event_type", Str), req("asset_id", Str), opt("market", Str), req("bids", Arr), req("asks", Arr),
+    opt("timestamp", Str), opt("hash", Str), opt("last_trade_price", Str), opt("tick_size", Str),
+    opt("min_order_size", Str), opt("neg_risk", Any),
+];
+/// One asset per event; the runner reads nothing from these
+const POLY_PRICE_CHANGE_V1: &[Field] = &[
+    req("event_type", Str), req("asset_id", Str), opt("market", Str), req("changes", Arr),
+    opt("timestamp", Str), opt("hash", Str),
+];
+/// Batched per market, each change naming its asset
+const POLY_PRICE_CHANGE_V2: &[Field] = &[
+    req("event_type", Str), opt("market", Str), req("price_changes", Arr), opt("timestamp", Str),
+];
+
+/// Every message type the feeds understand
+pub const SCHEMAS: &[MessageSchema] = &[
+    MessageSchema {
+        provider: Platform::Kalshi,
+        message_type: "orderbook_snapshot",
+        body: Some("msg"),
+        versions: &[
+            SchemaVersion { version: 1, fields: KALSHI_SNAPSHOT_V1 },
+            SchemaVersion { version: 2, fields: KALSHI_SNAPSHOT_V2 },
+        ],
+    },
+    MessageSchema {
+        provider: Platform::Kalshi,
+        message_type: "orderbook_delta",
+        body: Some("msg"),
+        versions: &[
+            SchemaVersion { version: 1, fields: KALSHI_DELTA_V1 },
+            SchemaVersion { version: 2, fields: KALSHI_DELTA_V2 },
+        ],
+    },
+    MessageSchema { provider: Platform::Kalshi, message_type: "subscribed", body: None, versions: &[] },
+    MessageSchema { provider: Platform::Kalshi, message_type: "unsubscribed", body: None, versions: &[] },
+    MessageSchema { provider: Platform::Kalshi, message_type: "ok", body: None, versions: &[] },
+    MessageSchema { provider: Platform::Kalshi, message_type: "error", body: None, versions: &[] },
+    MessageSchema {
+        provider: Platform::Polymarket,
+        message_type: "book",
+        body: None,
+        versions: &[
+            SchemaVersion { version: 1, fields: POLY_BOOK_V1 },
+            SchemaVersion { version: 2, fields: POLY_BOOK_V2 },
+        ],
+    },
+    MessageSchema {
+        provider: Platform::Polymarket,
+        message_type: "price_change",
+        body: None,
+        versions: &[
+            SchemaVersion { version: 1, fields: POLY_PRICE_CHANGE_V1 },
+            SchemaVersion { version: 2, fields: POLY_PRICE_CHANGE_V2 },
+        ],
+    },
+    MessageSchema { provider: Platform::Polymarket, message_type: "tick_size_change", body: None, versions: &[] },
+    MessageSchema { provider: Platform::Polymarket, message_type: "last_trade_price", body: None, versions: &[] },
+];
+
+/// Outcome of checking one message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// Fits a known version
+    Valid { message_type: String, version: u16 },
+    /// Has a version's required fields plus fields no version lists
+    Drift { message_type: String, version: u16, unknown: Vec<String> },
+    /// Unknown type, or required fields missing or mistyped
+    Invalid { message_type: String, reason: String },
+}
+
+/// The message's type tag
+fn message_type(provider: Platform, value: &Value) -> Option<&str> {
+    let obj = value.as_object()?;
+    match provider {
+        Platform::Kalshi => obj.get("type")?.as_str(),
+        // Book snapshots predate `event_type`
+        _ => obj.get("event_type")
+            .and_then(Value::as_str)
+            .or_else(|| obj.contains_key("bids").then_some("book")),
+    }
+}
+
+/// Fields of `obj` the version doesn't list, or why it doesn't fit
+fn check_version(obj: &Map<String, Value>, version: &SchemaVersion) -> Result<Vec<String>, String> {
+    for field in version.fields {
+        match obj.get(field.name).filter(|v| !v.is_null()) {
+            Some(v) if !field.kind.matches(v) => return Err(format!("{} is not {:?}", field.name, field.kind)),
+            None if field.required => return Err(format!("missing {}", field.name)),
+            _ => {}
+        }
+    }
+    Ok(obj.keys()
+        .filter(|key| !version.fields.iter().any(|f| f.name == key.as_str()))
+        .cloned()
+        .collect())
+}
+
+fn validate_one(provider: Platform, value: &Value) -> Verdict {
+    let Some(message_type) = message_type(provider, value) else {
+        return Verdict::Invalid { message_type: "untyped".to_string(), reason: "no message type".to_string() };
+    };
+    let invalid = |reason: String| Verdict::Invalid { message_type: message_type.to_string(), reason };
+    let Some(schema) = SCHEMAS.iter().find(|s| s.provider == provider && s.message_type == message_type) else {
+        return invalid("unknown message type".to_string());
+    };
+    if schema.versions.is_empty() {
+        return Verdict::Valid { message_type: message_type.to_string(), version: 0 };
+    }
+    let body = match schema.body {
+        Some(key) => value.get(key),
+        None => Some(value),
+    };
+    let Some(body) = body.and_then(Value::as_object) else {
+        return invalid(format!("missing {} object", schema.body.unwrap_or("message")));
+    };
+
+    // The oldest version listing every field; otherwise the newest whose required fields are there
+    let mut drift = None;
+    let mut reason = String::new();
+    for version in schema.versions {
+        match check_version(body, version) {
+            Ok(unknown) if unknown.is_empty() => {
+                return Verdict::Valid { message_type: message_type.to_string(), version: version.version };
+            }
+            Ok(unknown) => drift = Some((version.version, unknown)),
+            Err(e) => reason = e,
+        }
+    }
+    match drift {
+        Some((version, unknown)) => Verdict::Drift { message_type: message_type.to_string(), version, unknown },
+        None => invalid(reason),
+    }
+}
+
+/// Check a parsed message (Polymarket batches book snapshots in an array; every element must pass)
+pub fn validate(provider: Platform, value: &Value) -> Verdict {
+    let Some(items) = value.as_array() else {
+        return validate_one(provider, value);
+    };
+    let mut verdict = Verdict::Valid { message_type: "book".to_string(), version: 0 };
+    for item in items {
+        verdict = match (verdict, validate_one(provider, item)) {
+            (_, invalid @ Verdict::Invalid { .. }) => return invalid,
+            (Verdict::Drift { mut unknown, message_type, version }, Verdict::Drift { unknown: more, .. }) => {
+                unknown.extend(more.into_iter().filter(|f| !unknown.contains(f)).collect::<Vec<_>>());
+                Verdict::Drift { message_type, version, unknown }
+            }
+            (drift @ Verdict::Drift { .. }, _) => drift,
+            (_, next) => next,
+        };
+    }
+    verdict
+}
+
+/// Per provider and message type counters
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaStats {
+    pub provider: Platform,
+    pub message_type: String,
+    pub accepted: u64,
+    /// Accepted messages by matched version (0 = control message)
+    pub by_version: BTreeMap<u16, u64>,
+    /// Messages with fields no version lists (accepted in lenient mode)
+    pub drifted: u64,
+    pub rejected: u64,
+    /// Fields seen that no version lists
+    pub unknown_fields: BTreeSet<String>,
+    pub last_drift_ns: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+impl SchemaStats {
+    fn new(provider: Platform, message_type: &str) -> Self {
+        Self {
+            provider,
+            message_type: message_type.to_string(),
+            accepted: 0,
+            by_version: BTreeMap::new(),
+            drifted: 0,
+            rejected: 0,
+            unknown_fields: BTreeSet::new(),
+            last_drift_ns: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Quarantine file line
+#[derive(Serialize)]
+struct QuarantineRecord<'a> {
+    ts_ns: u64,
+    provider: Platform,
+    message_type: &'a str,
+    reason: &'a str,
+    raw: &'a str,
+}
+
+/// Rejected records, one JSONL file per name (`<dir>/<name>.jsonl`); stops writing a file at
+/// `max_bytes`. Also used for sanitizer rejects (see tick_sanitizer.rs)
+pub(crate) struct Quarantine {
+    dir: PathBuf,
+    max_bytes: u64,
+    files: HashMap<String, (File, u64)>,
+}
+
+impl Quarantine {
+    pub(crate) fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self { dir: dir.into(), max_bytes, files: HashMap::new() }
+    }
+
+    /// Append one record; `Ok(false)` once the file is full
+    pub(crate) fn write(&mut self, name: &str, record: &impl Serialize) -> std::io::Result<bool> {
+        let (file, len) = match self.files.entry(name.to_string()) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                std::fs::create_dir_all(&self.dir)?;
+                let path = self.dir.join(format!("{}.jsonl", name));
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                let len = file.metadata()?.len();
+                e.insert((file, len))
+            }
+        };
+        if *len >= self.max_bytes {
+            return Ok(false);
+        }
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        *len += line.len() as u64;
+        Ok(true)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    stats: HashMap<(Platform, String), SchemaStats>,
+    quarantine: Option<Quarantine>,
+}
+
+/// Schema checks for the venue feeds, shared by every socket
+pub struct FeedSchemas {
+    mode: SchemaMode,
+    state: Mutex<State>,
+    clock: SharedClock,
+}
+
+pub type SharedFeedSchemas = Arc<FeedSchemas>;
+
+impl FeedSchemas {
+    pub fn new(mode: SchemaMode) -> Self {
+        Self { mode, state: Mutex::new(State::default()), clock: clock::system() }
+    }
+
+    /// Mode and quarantine from `[feeds]`; an empty `quarantine_dir` keeps no copies
+    pub fn from_config(feeds: &FeedsSection) -> Self {
+        let schemas = Self::new(SchemaMode::parse(&feeds.schema_mode));
+        if feeds.quarantine_dir.is_empty() {
+            return schemas;
+        }
+        schemas.with_quarantine(&feeds.quarantine_dir, feeds.quarantine_max_mb * 1024 * 1024)
+    }
+
+    /// Append rejected messages under `dir`, at most `max_bytes` per provider
+    pub fn with_quarantine(self, dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        self.state.lock().unwrap().quarantine = Some(Quarantine::new(dir, max_bytes));
+        self
+    }
+
+    pub fn mode(&self) -> SchemaMode {
+        self.mode
+    }
+
+    /// Parse and check a raw feed message; the value is returned when it should be processed.
+    /// Rejections are counted and quarantined before the error is returned.
+    pub fn check(&self, provider: Platform, text: &str) -> Result<Value, FeedError> {
+        let value = match serde_json::from_str::<Value>(text) {
+            Ok(value) => value,
+            Err(e) => return Err(self.reject(provider, "unparsed", format!("not JSON: {}", e), text)),
+        };
+        match validate(provider, &value) {
+            Verdict::Valid { message_type, version } => {
+                self.accept(provider, &message_type, version);
+                Ok(value)
+            }
+            Verdict::Drift { message_type, version, unknown } => {
+                self.drift(provider, &message_type, version, &unknown);
+                if self.mode == SchemaMode::Lenient {
+                    self.accept(provider, &message_type, version);
+                    return Ok(value);
+                }
+                Err(self.reject(provider, &message_type, format!("unknown fields {:?}", unknown), text))
+            }
+            Verdict::Invalid { message_type, reason } => Err(self.reject(provider, &message_type, reason, text)),
+        }
+    }
+
+    /// Count, quarantine and describe a rejected message (also for typed decodes that fail after
+    /// the schema check passed)
+    pub fn reject(&self, provider: Platform, message_type: &str, reason: String, raw: &str) -> FeedError {
+        let now = self.clock.wall_ns().0;
+        let mut state = self.state.lock().unwrap();
+        let stats = state.stats.entry((provider, message_type.to_string()))
+            .or_insert_with(|| SchemaStats::new(provider, message_type));
+        stats.rejected += 1;
+        stats.last_error = Some(reason.clone());
+
+        if let Some(quarantine) = &mut state.quarantine {
+            let mut end = raw.len().min(QUARANTINE_RAW_BYTES);
+            while !raw.is_char_boundary(end) {
+                end -= 1;
+            }
+            let record = QuarantineRecord { ts_ns: now, provider, message_type, reason: &reason, raw: &raw[..end] };
+            match quarantine.write(&provider.to_string().to_lowercase(), &record) {
+                Ok(true) => {}
+                Ok(false) => crate::hot_path!(Level::WARN, provider = %provider, "[SCHEMA] Quarantine full, dropping"),
+                Err(e) => crate::hot_path!(Level::WARN, provider = %provider, "[SCHEMA] Quarantine write failed: {}", e),
+            }
+        }
+        FeedError::Schema { provider: provider.into(), message_type: message_type.to_string(), reason }
+    }
+
+    fn accept(&self, provider: Platform, message_type: &str, version: u16) {
+        let mut state = self.state.lock().unwrap();
+        let stats = state.stats.entry((provider, message_type.to_string()))
+            .or_insert_with(|| SchemaStats::new(provider, message_type));
+        stats.accepted += 1;
+        *stats.by_version.entry(version).or_default() += 1;
+    }
+
+    fn drift(&self, provider: Platform, message_type: &str, version: u16, unknown: &[String]) {
+        let now = self.clock.wall_ns().0;
+        let mut state = self.state.lock().unwrap();
+        let stats = state.stats.entry((provider, message_type.to_string()))
+            .or_insert_with(|| SchemaStats::new(provider, message_type));
+        stats.drifted += 1;
+        stats.last_drift_ns = Some(now);
+        let new: Vec<&String> = unknown.iter().filter(|f| stats.unknown_fields.insert((*f).clone())).collect();
+        // Each new field is reported once; the counters track how often it recurs
+        if !new.is_empty() {
+            warn!(provider = %provider, message_type, version, fields = ?new, "[SCHEMA] Feed format drift: new fields");
+        }
+    }
+
+    /// Counters for every provider and message type seen
+    pub fn stats(&self) -> Vec<SchemaStats> {
+        let state = self.state.lock().unwrap();
+        let mut stats: Vec<SchemaStats> = state.stats.values().cloned().collect();
+        stats.sort_by(|a, b| (a.provider.to_string(), &a.message_type).cmp(&(b.provider.to_string(), &b.message_type)));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kalshi_delta(extra: &str) -> String {
+        format!(
+            r#"{{"type":"orderbook_delta","sid":1,"seq":7,"msg":{{"market_ticker":"KXNBA-1","price":45,"delta":-3,"side":"yes"{}}}}}"#,
+            extra,
+        )
+    }
+
+    #[test]
+    fn test_versions_and_drift() {
+        let parse = |s: &str| serde_json::from_str::<Value>(s).unwrap();
+        let delta = |version| Verdict::Valid { message_type: "orderbook_delta".to_string(), version };
+        assert_eq!(validate(Platform::Kalshi, &parse(&kalshi_delta(""))), delta(1));
+        assert_eq!(validate(Platform::Kalshi, &parse(&kalshi_delta(r#","price_dollars":"0.45""#))), delta(2));
+        assert_eq!(
+            validate(Platform::Kalshi, &parse(&kalshi_delta(r#","price_dollars":"0.45","queue":3"#))),
+            Verdict::Drift { message_type: "orderbook_delta".to_string(), version: 2, unknown: vec!["queue".to_string()] },
+        );
+        assert!(matches!(
+            validate(Platform::Kalshi, &parse(r#"{"type":"orderbook_delta","msg":{"market_ticker":"K","price":"45"}}"#)),
+            Verdict::Invalid { reason, .. } if reason == "price is not Num",
+        ));
+        assert!(matches!(
+            validate(Platform::Kalshi, &parse(r#"{"type":"fill","msg":{}}"#)),
+            Verdict::Invalid { message_type, .. } if message_type == "fill",
+        ));
+        assert!(matches!(validate(Platform::Kalshi, &parse(r#"{"type":"subscribed","msg":{"sid":1}}"#)), Verdict::Valid { version: 0, .. }));
+
+        // Polymarket: untagged book batches and both price_change layouts
+        let books = r#"[{"asset_id":"1","bids":[],"asks":[]},{"event_type":"book","asset_id":"2","bids":[],"asks":[],"tick_size":"0.01"}]"#;
+        assert!(matches!(validate(Platform::Polymarket, &parse(books)), Verdict::Valid { version: 2, .. }));
+        let legacy = r#"{"event_type":"price_change","asset_id":"1","changes":[]}"#;
+        assert!(matches!(validate(Platform::Polymarket, &parse(legacy)), Verdict::Valid { version: 1, .. }));
+        let batched = r#"{"event_type":"price_change","market":"0x1","price_changes":[]}"#;
+        assert!(matches!(validate(Platform::Polymarket, &parse(batched)), Verdict::Valid { version: 2, .. }));
+        let bad_batch = r#"[{"asset_id":"1","bids":[],"asks":[]},{"asset_id":"2","bids":[]}]"#;
+        assert!(matches!(validate(Platform::Polymarket, &parse(bad_batch)), Verdict::Invalid { reason, .. } if reason == "missing asks"));
+    }
+
+    #[test]
+    fn test_strict_rejects_drift_lenient_counts_it() {
+        let drifted = kalshi_delta(r#","queue":3"#);
+
+        let lenient = FeedSchemas::new(SchemaMode::Lenient);
+        assert!(lenient.check(Platform::Kalshi, &drifted).is_ok());
+        assert!(lenient.check(Platform::Kalshi, &kalshi_delta("")).is_ok());
+        let stats = &lenient.stats()[0];
+        assert_eq!((stats.accepted, stats.drifted, stats.rejected), (2, 1, 0));
+        // Drift is counted against the newest version it satisfies
+        assert_eq!(stats.by_version, BTreeMap::from([(1, 1), (2, 1)]));
+        assert!(stats.unknown_fields.contains("queue"));
+
+        let strict = FeedSchemas::new(SchemaMode::Strict);
+        let err = strict.check(Platform::Kalshi, &drifted).unwrap_err();
+        assert!(matches!(&err, FeedError::Schema { message_type, .. } if message_type == "orderbook_delta"));
+        assert!(!crate::error::Retryable::is_retryable(&err));
+        let stats = &strict.stats()[0];
+        assert_eq!((stats.accepted, stats.drifted, stats.rejected), (0, 1, 1));
+    }
+
+    #[test]
+    fn test_rejections_are_quarantined() {
+        let dir = std::env::temp_dir().join(format!("feed_schema_quarantine_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let schemas = FeedSchemas::new(SchemaMode::Lenient).with_quarantine(&dir, 200);
+
+        assert!(schemas.check(Platform::Polymarket, "PONG").is_err());
+        assert!(schemas.check(Platform::Polymarket, r#"{"event_type":"book","asset_id":"1"}"#).is_err());
+        assert!(schemas.check(Platform::Polymarket, r#"{"event_type":"book","asset_id":"2"}"#).is_err());
+
+        let lines: Vec<Value> = std::fs::read_to_string(dir.join("polymarket.jsonl")).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // The size cap stops the third record
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["message_type"], "unparsed");
+        assert_eq!(lines[0]["raw"], "PONG");
+        assert_eq!(lines[1]["reason"], "missing bids");
+        let book = schemas.stats().into_iter().find(|s| s.message_type == "book").unwrap();
+        assert_eq!(book.rejected, 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
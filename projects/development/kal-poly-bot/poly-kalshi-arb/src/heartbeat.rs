@@ -0,0 +1,166 @@
+// src/heartbeat.rs
+// Adaptive feed heartbeats - check intervals follow the busiest subscribed market
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::{FeedsSection, HeartbeatRule};
+use crate::event_phase::EventPhase;
+
+/// How busy a subscribed market is; orders busiest first, so the minimum over
+/// a feed's markets sets its interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Activity {
+    /// In play, by tier index (0 = Tier 1)
+    Live(usize),
+    /// Starting within the pre-game window, at halftime or suspended mid-game
+    PreGame,
+    /// Scheduled later, settled, or phase unknown
+    Idle,
+}
+
+impl Activity {
+    /// Activity of a tier-`tier` market in `phase`, `starts_in` before its scheduled start
+    pub fn of(tier: usize, phase: Option<EventPhase>, starts_in: Option<Duration>, pre_game_window: Duration) -> Self {
+        match phase {
+            Some(EventPhase::InPlay) => Activity::Live(tier),
+            Some(EventPhase::Halftime | EventPhase::Suspended) => Activity::PreGame,
+            Some(EventPhase::PreGame) if starts_in.is_some_and(|s| s <= pre_game_window) => Activity::PreGame,
+            _ => Activity::Idle,
+        }
+    }
+}
+
+/// Check intervals for one provider
+#[derive(Debug, Clone, PartialEq)]
+struct Intervals {
+    in_play: Vec<Duration>,
+    pre_game: Duration,
+    idle: Duration,
+}
+
+impl Intervals {
+    fn get(&self, activity: Activity) -> Duration {
+        match activity {
+            // Tiers past the configured list use the slowest in-play interval
+            Activity::Live(tier) => self.in_play.get(tier).or(self.in_play.last()).copied().unwrap_or(self.pre_game),
+            Activity::PreGame => self.pre_game,
+            Activity::Idle => self.idle,
+        }
+    }
+
+    fn overridden(&self, rule: &HeartbeatRule) -> Self {
+        let ms = |v: u64, default: Duration| if v == 0 { default } else { Duration::from_millis(v) };
+        Self {
+            in_play: if rule.in_play_ms.is_empty() {
+                self.in_play.clone()
+            } else {
+                rule.in_play_ms.iter().map(|&v| Duration::from_millis(v)).collect()
+            },
+            pre_game: ms(rule.pre_game_ms, self.pre_game),
+            idle: ms(rule.idle_ms, self.idle),
+        }
+    }
+}
+
+/// Heartbeat intervals and staleness thresholds per provider and activity (`[feeds]` section)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeartbeatPolicy {
+    default: Intervals,
+    /// Keyed by lowercase provider name
+    providers: HashMap<String, Intervals>,
+    pre_game_window: Duration,
+    stale_heartbeats: u32,
+}
+
+impl Default for HeartbeatPolicy {
+    fn default() -> Self {
+        Self::from_config(&FeedsSection::default())
+    }
+}
+
+impl HeartbeatPolicy {
+    pub fn from_config(feeds: &FeedsSection) -> Self {
+        let default = Intervals {
+            in_play: feeds.heartbeat_in_play_ms.iter().map(|&v| Duration::from_millis(v)).collect(),
+            pre_game: Duration::from_millis(feeds.heartbeat_pre_game_ms),
+            idle: Duration::from_millis(feeds.heartbeat_interval_ms),
+        };
+        let providers = feeds.heartbeat_providers.iter()
+            .map(|(name, rule)| (name.to_lowercase(), default.overridden(rule)))
+            .collect();
+        Self {
+            default,
+            providers,
+            pre_game_window: Duration::from_secs(feeds.pre_game_window_mins * 60),
+            stale_heartbeats: feeds.stale_heartbeats.max(1),
+        }
+    }
+
+    /// Activity of a market; see [`Activity::of`]
+    pub fn activity(&self, tier: usize, phase: Option<EventPhase>, starts_in: Option<Duration>) -> Activity {
+        Activity::of(tier, phase, starts_in, self.pre_game_window)
+    }
+
+    /// How often to check `provider` (case-insensitive name) while its busiest market is at `activity`
+    pub fn interval(&self, provider: &str, activity: Activity) -> Duration {
+        self.providers.get(&provider.to_lowercase()).unwrap_or(&self.default).get(activity)
+    }
+
+    /// Silence after which `provider` counts as disconnected
+    pub fn stale_after(&self, provider: &str, activity: Activity) -> Duration {
+        self.interval(provider, activity) * self.stale_heartbeats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_from_phase() {
+        let window = Duration::from_secs(3600);
+        assert_eq!(Activity::of(0, Some(EventPhase::InPlay), None, window), Activity::Live(0));
+        assert_eq!(Activity::of(2, Some(EventPhase::Halftime), None, window), Activity::PreGame);
+        let soon = Some(Duration::from_secs(600));
+        let later = Some(Duration::from_secs(7200));
+        assert_eq!(Activity::of(0, Some(EventPhase::PreGame), soon, window), Activity::PreGame);
+        assert_eq!(Activity::of(0, Some(EventPhase::PreGame), later, window), Activity::Idle);
+        assert_eq!(Activity::of(0, None, None, window), Activity::Idle);
+        assert_eq!(Activity::of(0, Some(EventPhase::Settled), None, window), Activity::Idle);
+
+        // The busiest market wins
+        let markets = [Activity::Idle, Activity::Live(2), Activity::PreGame, Activity::Live(0)];
+        assert_eq!(markets.iter().min(), Some(&Activity::Live(0)));
+    }
+
+    #[test]
+    fn test_intervals_scale_with_activity() {
+        let policy = HeartbeatPolicy::default();
+        assert_eq!(policy.interval("kalshi", Activity::Live(0)), Duration::from_secs(1));
+        assert_eq!(policy.interval("kalshi", Activity::Live(3)), Duration::from_secs(10));
+        assert_eq!(policy.interval("kalshi", Activity::Live(9)), Duration::from_secs(10));
+        assert_eq!(policy.interval("kalshi", Activity::PreGame), Duration::from_secs(10));
+        assert_eq!(policy.interval("kalshi", Activity::Idle), Duration::from_secs(30));
+        assert_eq!(policy.stale_after("kalshi", Activity::Live(0)), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_provider_overrides() {
+        let mut feeds = FeedsSection::default();
+        feeds.stale_heartbeats = 3;
+        feeds.heartbeat_providers.insert("Polymarket".to_string(), HeartbeatRule {
+            idle_ms: 60_000,
+            in_play_ms: vec![500, 1000, 2000, 4000],
+            pre_game_ms: 0,
+        });
+        let policy = HeartbeatPolicy::from_config(&feeds);
+
+        assert_eq!(policy.interval("POLYMARKET", Activity::Live(0)), Duration::from_millis(500));
+        assert_eq!(policy.interval("polymarket", Activity::Idle), Duration::from_secs(60));
+        // Unset fields inherit the section values
+        assert_eq!(policy.interval("polymarket", Activity::PreGame), Duration::from_secs(10));
+        assert_eq!(policy.interval("kalshi", Activity::Live(0)), Duration::from_secs(1));
+        assert_eq!(policy.stale_after("polymarket", Activity::Live(1)), Duration::from_secs(3));
+    }
+}
@@ -3,8 +3,11 @@
 //! Grid search and Bayesian optimization for Kalman filter hyperparameters.
 //! Each pattern requires separate tuning via historical data validation.
 
+use crate::config::FilterTuning;
+use crate::error::StateStoreError;
 use crate::kalman_filter_suite::*;
 use crate::microstructural_simulator::*;
+use crate::optimization_studies::{CandidateRun, StudyId, StudyStore};
 use crate::types::{TimestampNs, MarketType, Platform};
 use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
@@ -13,6 +16,11 @@ use rand::{thread_rng, Rng};
 use std::sync::{Arc, Mutex};
 use tokio::sync::Semaphore;
 
+/// Share of the historical data (its tail) kept for out-of-sample equity curves in studies
+pub const HOLDOUT_FRACTION: f64 = 0.2;
+/// Capital the out-of-sample equity curves start from
+const OOS_STARTING_CAPITAL: f64 = 100.0;
+
 /// Hyperparameter optimization configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationConfig {
@@ -358,6 +366,12 @@ impl HyperparameterOptimizer {
         }
     }
 
+    /// Optimizer validated against `n_ticks` bundles of a synthetic market
+    pub fn with_synthetic_market(config: OptimizationConfig, market: SyntheticMarketConfig, n_ticks: usize) -> Self {
+        let historical_data = SyntheticMarketGenerator::new(market).generate(n_ticks);
+        Self::new(config, historical_data)
+    }
+
     /// Run optimization
     pub async fn optimize(&mut self) -> Result<OptimizationResult, Box<dyn std::error::Error + Send + Sync>> {
         info!("Starting hyperparameter optimization for pattern {}", self.config.pattern_id);
@@ -786,6 +800,80 @@ impl HyperparameterOptimizer {
         }
     }
 
+    /// Held-out tail of the historical data
+    fn holdout(&self) -> &[SyncedTickBundle] {
+        let split = ((self.historical_data.len() as f64) * (1.0 - HOLDOUT_FRACTION)).ceil() as usize;
+        &self.historical_data[split.min(self.historical_data.len())..]
+    }
+
+    /// The `n` best-scoring distinct parameter sets evaluated so far, best first
+    pub fn top_candidates(&self, n: usize) -> Vec<(FilterParameters, f64)> {
+        let mut ranked: Vec<&OptimizationIteration> = self.state.history.iter().collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let mut seen: Vec<Vec<f64>> = Vec::new();
+        let mut top = Vec::new();
+        for iteration in ranked {
+            if top.len() >= n {
+                break;
+            }
+            let key = self.params_to_vector(&iteration.params);
+            if !seen.contains(&key) {
+                seen.push(key);
+                top.push((iteration.params.clone(), iteration.score));
+            }
+        }
+        top
+    }
+
+    /// Equity of the worker's trigger rule on the held-out ticks: a filter built with `params.dt`
+    /// tracks each bundle's primary tick (`SyncedTickBundle::primary_tick`), an edge of at least
+    /// `velocity_threshold` opens one unit towards the filter's estimate, closed at the next tick.
+    /// Starts at 100; flat for unsupported patterns.
+    pub fn out_of_sample_equity(&self, params: &FilterParameters) -> Vec<f64> {
+        let mut curve = vec![OOS_STARTING_CAPITAL];
+        let Ok(mut filter) = KalmanFilterFactory::create_filter(self.config.pattern_id, params.dt) else {
+            return curve;
+        };
+        let mut equity = OOS_STARTING_CAPITAL;
+        let mut open: Option<(f64, f64)> = None;
+        for bundle in self.holdout() {
+            let Some(tick) = bundle.primary_tick() else { continue };
+            if let Some((direction, entry)) = open.take() {
+                equity += direction * (tick.price - entry);
+                curve.push(equity);
+            }
+            filter.predict();
+            if filter.update(&[tick.price]).is_err() {
+                continue;
+            }
+            let estimate = filter.get_state().get("position").copied().unwrap_or(tick.price);
+            let edge = estimate - tick.price;
+            if edge.abs() >= params.velocity_threshold && edge != 0.0 {
+                open = Some((edge.signum(), tick.price));
+            }
+        }
+        curve
+    }
+
+    /// Store the top `n` parameter sets with their out-of-sample equity curves as a study
+    pub fn record_study(&self, store: &StudyStore, n: usize) -> Result<StudyId, StateStoreError> {
+        let runs = self.top_candidates(n)
+            .into_iter()
+            .map(|(params, score)| CandidateRun {
+                equity_curve: self.out_of_sample_equity(&params),
+                params: FilterTuning::from(&params),
+                score,
+            })
+            .collect();
+        let method = match self.config.method {
+            OptimizationMethod::GridSearch => "grid",
+            OptimizationMethod::RandomSearch { .. } => "random",
+            OptimizationMethod::BayesianOptimization => "bayesian",
+            OptimizationMethod::GeneticAlgorithm { .. } => "genetic",
+        };
+        store.record(self.config.pattern_id, method, runs)
+    }
+
     /// Evaluate parameters with cross-validation
     async fn evaluate_params(params: &FilterParameters) -> f64 {
         // TODO: Implement actual parameter evaluation
@@ -811,6 +899,19 @@ impl Default for FilterParameters {
     }
 }
 
+impl From<&FilterParameters> for FilterTuning {
+    fn from(params: &FilterParameters) -> Self {
+        Self {
+            dt: params.dt,
+            q_steam: params.q_steam,
+            q_quiet: params.q_quiet,
+            r_noise: params.r_noise,
+            velocity_threshold: params.velocity_threshold,
+            pattern_params: params.pattern_params.iter().map(|(k, &v)| (k.clone(), v)).collect(),
+        }
+    }
+}
+
 impl Default for ValidationMetrics {
     fn default() -> Self {
         Self {
@@ -897,6 +998,11 @@ mod tests {
 
         assert_eq!(optimizer.config.pattern_id, 51);
         assert_eq!(optimizer.config.cv_folds, 5);
+
+        let synthetic = HyperparameterOptimizer::with_synthetic_market(
+            OptimizationConfig::default(), SyntheticMarketConfig::default(), 100,
+        );
+        assert_eq!(synthetic.historical_data.len(), 100);
     }
 
     #[test]
@@ -935,4 +1041,27 @@ mod tests {
         let mutated = optimizer.mutate(child.clone(), 1.0);
         // Should be potentially different due to mutation
     }
+
+    #[test]
+    fn test_top_candidates_and_holdout_equity() {
+        let mut optimizer = HyperparameterOptimizer::with_synthetic_market(
+            OptimizationConfig::default(), SyntheticMarketConfig::default(), 100,
+        );
+        assert_eq!(optimizer.holdout().len(), 20);
+
+        let wide = FilterParameters { velocity_threshold: 0.5, ..Default::default() };
+        optimizer.update_best_params(FilterParameters::default(), 0.4);
+        optimizer.update_best_params(wide.clone(), 0.9);
+        optimizer.update_best_params(FilterParameters::default(), 0.4);
+        let top = optimizer.top_candidates(5);
+        assert_eq!(top.len(), 2);
+        assert_eq!((top[0].0.velocity_threshold, top[0].1), (0.5, 0.9));
+
+        let curve = optimizer.out_of_sample_equity(&wide);
+        assert_eq!(curve[0], OOS_STARTING_CAPITAL);
+        assert!(curve.len() <= optimizer.holdout().len() + 1);
+        // A threshold nothing reaches never trades
+        let never = FilterParameters { velocity_threshold: f64::MAX, ..Default::default() };
+        assert_eq!(optimizer.out_of_sample_equity(&never), vec![OOS_STARTING_CAPITAL]);
+    }
 }
@@ -0,0 +1,375 @@
+// src/intent_diff.rs
+// Order intent dry-run - replay archived signals under two configs and diff the trades each would take
+
+use anyhow::Result;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt;
+use std::path::Path;
+
+use crate::circuit_breaker::{CircuitBreakerConfig, TradingCircuitBreaker, TripReason};
+use crate::config::AppConfig;
+use crate::error::RiskRejection;
+use crate::pattern_policy::PatternPolicy;
+use crate::tick_store::{self, ArchiveQuery, SignalRow};
+
+/// Contracts each signal asks for before the limits cut it down
+pub const DEFAULT_CONTRACTS: i64 = 10;
+
+/// Dollars a contract ties up: a binary contract never costs more than its $1 payout
+const COST_PER_CONTRACT: f64 = 1.0;
+
+/// What one config decided for one signal
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Intent {
+    Trade { contracts: i64 },
+    Rejected { kind: &'static str, reason: String },
+}
+
+impl Intent {
+    fn rejected(kind: &'static str, reason: impl fmt::Display) -> Self {
+        Intent::Rejected { kind, reason: reason.to_string() }
+    }
+
+    pub fn contracts(&self) -> Option<i64> {
+        match self {
+            Intent::Trade { contracts } => Some(*contracts),
+            Intent::Rejected { .. } => None,
+        }
+    }
+}
+
+/// Short, stable name for a rejection, used to group the summary counts
+fn rejection_kind(rejection: &RiskRejection) -> &'static str {
+    match rejection {
+        RiskRejection::Limit(TripReason::MaxPositionPerMarket { .. }) => "max_position_per_market",
+        RiskRejection::Limit(TripReason::MaxTotalPosition { .. }) => "max_total_position",
+        RiskRejection::Limit(TripReason::MaxCorrelatedPosition { .. }) => "max_correlated_position",
+        RiskRejection::Limit(TripReason::MaxGroupPosition { .. }) => "max_group_position",
+        RiskRejection::Limit(_) => "circuit_breaker",
+        RiskRejection::CircuitOpen { .. } | RiskRejection::ProviderFailure { .. } => "provider",
+        RiskRejection::ExposureLimit { .. } => "exposure",
+        RiskRejection::HalfLifeDecay => "half_life",
+        RiskRejection::PhaseGated { .. } => "phase",
+        RiskRejection::BudgetExhausted { .. } => "pattern_budget",
+        RiskRejection::PatternDisabled { .. } => "pattern_disabled",
+        RiskRejection::LowConfidence { .. } => "confidence",
+        RiskRejection::VenueNotAllowed { .. } => "venue",
+        RiskRejection::ConcurrencyLimit { .. } => "pattern_concurrency",
+        RiskRejection::MarketCooldown { .. } => "cooldown",
+        RiskRejection::NoAccount { .. } => "account",
+        RiskRejection::Maintenance { .. } => "maintenance",
+    }
+}
+
+/// A trade still holding limits until its signal's convergence window closes
+struct OpenIntent {
+    market_id: String,
+    pattern: Option<String>,
+    contracts: i64,
+}
+
+/// One config's dry-run state: the trading circuit breaker's position limits and the
+/// `[patterns.enabled]` rules, fed the signals in archive order
+struct Replay {
+    breaker: TradingCircuitBreaker,
+    patterns: PatternPolicy,
+    max_convergence_ns: u64,
+    contracts: i64,
+    /// Open trades by the time their convergence window closes
+    expiries: BinaryHeap<Reverse<(u64, usize)>>,
+    open: BTreeMap<usize, OpenIntent>,
+}
+
+impl Replay {
+    fn new(config: &AppConfig, contracts: i64) -> Self {
+        Self {
+            breaker: TradingCircuitBreaker::new(CircuitBreakerConfig::from(&config.risk)),
+            patterns: PatternPolicy::new(&config.patterns),
+            max_convergence_ns: (config.patterns.max_half_life_ms * 1_000_000.0) as u64,
+            contracts,
+            expiries: BinaryHeap::new(),
+            open: BTreeMap::new(),
+        }
+    }
+
+    /// Release trades whose convergence window closed before `now_ns`
+    async fn expire(&mut self, now_ns: u64) {
+        while let Some(&Reverse((at, id))) = self.expiries.peek() {
+            if at > now_ns {
+                break;
+            }
+            self.expiries.pop();
+            if let Some(intent) = self.open.remove(&id) {
+                // Unwinds the per-leg counters the open added
+                self.breaker.record_success(&intent.market_id, -intent.contracts, -intent.contracts, 0.0).await;
+                if let Some(pattern) = &intent.pattern {
+                    self.patterns.close(pattern, intent.contracts as f64 * COST_PER_CONTRACT);
+                }
+            }
+        }
+    }
+
+    async fn evaluate(&mut self, id: usize, signal: &SignalRow) -> Intent {
+        self.expire(signal.timestamp_ns).await;
+
+        if signal.expected_convergence_ns > self.max_convergence_ns {
+            return Intent::rejected("half_life", format!(
+                "convergence {}ms over patterns.max_half_life_ms",
+                signal.expected_convergence_ns / 1_000_000,
+            ));
+        }
+
+        // Untagged signals skip the pattern rules, as in the risk engine
+        let pattern = signal.pattern_id.map(|id| id.to_string());
+        let mut contracts = self.contracts;
+        if let Some(pattern) = &pattern {
+            let venues = [signal.platform.to_string(), signal.slow_platform.to_string()];
+            let venues = [venues[0].as_str(), venues[1].as_str()];
+            match self.patterns.cap_contracts(pattern, Some(signal.confidence), &venues, COST_PER_CONTRACT, contracts) {
+                Ok(capped) => contracts = capped,
+                Err(rejection) => return Intent::rejected(rejection_kind(&rejection), rejection),
+            }
+        }
+
+        // Position limits shrink the trade to what still fits before rejecting it
+        if let Err(reason) = self.breaker.can_execute(&signal.market_id, contracts).await {
+            let fits = match &reason {
+                TripReason::MaxPositionPerMarket { position, limit, .. }
+                | TripReason::MaxCorrelatedPosition { position, limit, .. }
+                | TripReason::MaxGroupPosition { position, limit, .. }
+                | TripReason::MaxTotalPosition { position, limit } => {
+                    (limit - (position - contracts * 2)) / 2
+                }
+                _ => 0,
+            };
+            if fits < 1 || self.breaker.can_execute(&signal.market_id, fits).await.is_err() {
+                let rejection = RiskRejection::from(reason);
+                return Intent::rejected(rejection_kind(&rejection), rejection);
+            }
+            contracts = fits;
+        }
+
+        self.breaker.record_success(&signal.market_id, contracts, contracts, 0.0).await;
+        if let Some(pattern) = &pattern {
+            self.patterns.open(pattern, contracts as f64 * COST_PER_CONTRACT);
+        }
+        self.open.insert(id, OpenIntent { market_id: signal.market_id.clone(), pattern, contracts });
+        self.expiries.push(Reverse((signal.timestamp_ns.saturating_add(signal.expected_convergence_ns), id)));
+        Intent::Trade { contracts }
+    }
+}
+
+/// What one config traded over the replay
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IntentSummary {
+    pub trades: u64,
+    pub contracts: i64,
+    /// Rejections by kind
+    pub rejected: BTreeMap<&'static str, u64>,
+}
+
+impl IntentSummary {
+    fn count(&mut self, intent: &Intent) {
+        match intent {
+            Intent::Trade { contracts } => {
+                self.trades += 1;
+                self.contracts += contracts;
+            }
+            Intent::Rejected { kind, .. } => *self.rejected.entry(kind).or_default() += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Rejected under the baseline, traded under the candidate
+    Added,
+    /// Traded under the baseline, rejected under the candidate
+    Removed,
+    /// Traded under both at different sizes
+    Resized,
+}
+
+/// A signal the two configs decided differently
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntentChange {
+    pub kind: ChangeKind,
+    pub timestamp_ns: u64,
+    pub market_id: String,
+    pub slow_market_id: String,
+    pub pattern_id: Option<u16>,
+    pub baseline: Intent,
+    pub candidate: Intent,
+}
+
+/// Trades added, removed and resized by moving from the baseline to the candidate config
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct IntentDiff {
+    pub signals: usize,
+    pub baseline: IntentSummary,
+    pub candidate: IntentSummary,
+    pub added: usize,
+    pub removed: usize,
+    pub resized: usize,
+    /// Net contracts the candidate trades over the baseline
+    pub contracts_delta: i64,
+    /// In signal order
+    pub changes: Vec<IntentChange>,
+}
+
+/// Evaluate `signals` (in archive order) under both configs' `[risk]` position limits and
+/// `[patterns]` rules, asking for `contracts` per signal. Each trade holds its limits until
+/// the signal's expected convergence; the replay has no fills, so loss limits never trip.
+pub async fn diff(signals: &[SignalRow], baseline: &AppConfig, candidate: &AppConfig, contracts: i64) -> IntentDiff {
+    let mut before = Replay::new(baseline, contracts);
+    let mut after = Replay::new(candidate, contracts);
+    let mut out = IntentDiff { signals: signals.len(), ..IntentDiff::default() };
+
+    for (id, signal) in signals.iter().enumerate() {
+        let baseline = before.evaluate(id, signal).await;
+        let candidate = after.evaluate(id, signal).await;
+        out.baseline.count(&baseline);
+        out.candidate.count(&candidate);
+
+        let kind = match (baseline.contracts(), candidate.contracts()) {
+            (None, Some(_)) => ChangeKind::Added,
+            (Some(_), None) => ChangeKind::Removed,
+            (Some(a), Some(b)) if a != b => ChangeKind::Resized,
+            _ => continue,
+        };
+        match kind {
+            ChangeKind::Added => out.added += 1,
+            ChangeKind::Removed => out.removed += 1,
+            ChangeKind::Resized => out.resized += 1,
+        }
+        out.changes.push(IntentChange {
+            kind,
+            timestamp_ns: signal.timestamp_ns,
+            market_id: signal.market_id.clone(),
+            slow_market_id: signal.slow_market_id.clone(),
+            pattern_id: signal.pattern_id,
+            baseline,
+            candidate,
+        });
+    }
+    out.contracts_delta = out.candidate.contracts - out.baseline.contracts;
+    out
+}
+
+/// Read the archived signals in `[from_ns, to_ns]` from the tick store at `dir` and diff them
+pub async fn diff_archive(
+    dir: &Path,
+    from_ns: u64,
+    to_ns: u64,
+    baseline: &AppConfig,
+    candidate: &AppConfig,
+    contracts: i64,
+) -> Result<IntentDiff> {
+    let scan = tick_store::scan::<SignalRow>(dir, &ArchiveQuery::between(from_ns, to_ns))?;
+    Ok(diff(&scan.rows, baseline, candidate, contracts).await)
+}
+
+impl fmt::Display for Intent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Intent::Trade { contracts } => write!(f, "{} contracts", contracts),
+            Intent::Rejected { reason, .. } => write!(f, "rejected: {}", reason),
+        }
+    }
+}
+
+impl fmt::Display for IntentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} signals", self.signals)?;
+        for (name, summary) in [("baseline", &self.baseline), ("candidate", &self.candidate)] {
+            writeln!(f, "  {:<9}  {} trades, {} contracts, rejected {:?}", name, summary.trades, summary.contracts, summary.rejected)?;
+        }
+        writeln!(f, "  added {}, removed {}, resized {}, contracts {:+}", self.added, self.removed, self.resized, self.contracts_delta)?;
+        for change in &self.changes {
+            writeln!(f, "{:>20}  {:<8} {} -> {} (pattern {})  {}  =>  {}",
+                     change.timestamp_ns, format!("{:?}", change.kind).to_lowercase(), change.market_id,
+                     change.slow_market_id, change.pattern_id.map_or("-".to_string(), |p| p.to_string()),
+                     change.baseline, change.candidate)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PatternRule;
+    use crate::types::{MarketType, Platform};
+
+    fn signal(ts_ms: u64, market: &str, pattern_id: Option<u16>, confidence: f64) -> SignalRow {
+        SignalRow {
+            timestamp_ns: ts_ms * 1_000_000,
+            market_id: market.to_string(),
+            platform: Platform::DraftKings,
+            slow_market_id: market.to_string(),
+            slow_platform: Platform::Kalshi,
+            market_type: MarketType::Moneyline,
+            disparity_cents: 3,
+            expected_convergence_ns: 1_000_000_000,
+            pattern_id,
+            confidence,
+            strategy_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_identical_configs_have_no_changes() {
+        let signals = [signal(0, "1", Some(73), 0.9), signal(10, "2", None, 0.5)];
+        let config = AppConfig::default();
+        let diff = diff(&signals, &config, &config, DEFAULT_CONTRACTS).await;
+        assert_eq!(diff.signals, 2);
+        assert_eq!(diff.baseline, diff.candidate);
+        assert_eq!(diff.baseline.trades, 2);
+        assert!(diff.changes.is_empty());
+        assert_eq!(diff.contracts_delta, 0);
+    }
+
+    #[tokio::test]
+    async fn test_pattern_rules_add_and_remove_trades() {
+        let signals = [signal(0, "1", Some(73), 0.6), signal(10, "2", Some(51), 0.9), signal(20, "3", None, 0.1)];
+        let baseline = AppConfig::default();
+        let mut candidate = AppConfig::default();
+        // Only pattern 73, and only with confident signals
+        candidate.patterns.enabled.insert("73".to_string(), PatternRule { min_confidence: 0.5, ..PatternRule::default() });
+        let mut stricter = candidate.clone();
+        stricter.patterns.enabled.insert("73".to_string(), PatternRule { min_confidence: 0.8, ..PatternRule::default() });
+
+        let diff = diff(&signals, &baseline, &candidate, DEFAULT_CONTRACTS).await;
+        assert_eq!((diff.added, diff.removed, diff.resized), (0, 1, 0));
+        assert_eq!(diff.changes[0].market_id, "2");
+        assert_eq!(diff.candidate.rejected.get("pattern_disabled"), Some(&1));
+        assert_eq!(diff.contracts_delta, -DEFAULT_CONTRACTS);
+
+        // Loosening back is the mirror image
+        let reverse = super::diff(&signals, &stricter, &candidate, DEFAULT_CONTRACTS).await;
+        assert_eq!((reverse.added, reverse.removed), (1, 0));
+        assert!(matches!(&reverse.changes[0].baseline, Intent::Rejected { kind: "confidence", .. }));
+    }
+
+    #[tokio::test]
+    async fn test_limits_resize_until_positions_converge() {
+        // Same market three times inside the 1s convergence window, then once after it
+        let signals = [signal(0, "1", None, 0.9), signal(100, "1", None, 0.9), signal(200, "1", None, 0.9), signal(1500, "1", None, 0.9)];
+        let baseline = AppConfig::default();
+        let mut candidate = AppConfig::default();
+        // Both legs count: 30 contracts per market allows 15 a leg
+        candidate.risk.max_position_per_market = 30;
+
+        let diff = diff(&signals, &baseline, &candidate, DEFAULT_CONTRACTS).await;
+        let sizes: Vec<_> = diff.changes.iter().map(|c| (c.kind, c.candidate.contracts())).collect();
+        assert_eq!(sizes, vec![(ChangeKind::Resized, Some(5)), (ChangeKind::Removed, None)]);
+        assert_eq!(diff.candidate.rejected.get("max_position_per_market"), Some(&1));
+        // The first two trades converged, so the last signal trades in full again
+        assert_eq!(diff.candidate.trades, 3);
+        assert_eq!(diff.contracts_delta, -15);
+    }
+}
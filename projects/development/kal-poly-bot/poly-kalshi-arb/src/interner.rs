@@ -0,0 +1,170 @@
+// src/interner.rs
+// String interning for market, player and team IDs - hot structs (Pattern #73 state, backtester
+// ticks and positions) carry a `Copy` `Symbol` instead of cloning a `String` on every tick, and
+// IDs are resolved back to text only where they're serialized, logged or shown. One interner per
+// process, like `provider_registry::providers()`.
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+/// An interned ID: compares, hashes and copies as a u32
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The symbol for `s`, interning it on first sight
+    pub fn intern(s: &str) -> Self {
+        interner().get_or_intern(s)
+    }
+
+    /// The symbol for `s` if it was ever interned (lookups that must not grow the table)
+    pub fn get(s: &str) -> Option<Self> {
+        interner().get(s)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        interner().resolve(self)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(s: &str) -> Self {
+        Symbol::intern(s)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(s: &String) -> Self {
+        Symbol::intern(s)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SymbolVisitor;
+
+        impl Visitor<'_> for SymbolVisitor {
+            type Value = Symbol;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an ID string")
+            }
+
+            // Borrowed or transient, the text is only copied the first time it's seen
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Symbol, E> {
+                Ok(Symbol::intern(s))
+            }
+        }
+
+        deserializer.deserialize_str(SymbolVisitor)
+    }
+}
+
+#[derive(Default)]
+struct Table {
+    ids: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+/// Append-only string table. Each distinct string is stored once and lives for the rest of
+/// the process, so it suits bounded ID sets (markets, players, teams), not free text.
+#[derive(Default)]
+pub struct Interner {
+    table: RwLock<Table>,
+}
+
+static INTERNER: OnceLock<Interner> = OnceLock::new();
+
+/// Process-wide interner behind `Symbol`
+pub fn interner() -> &'static Interner {
+    INTERNER.get_or_init(Interner::default)
+}
+
+impl Interner {
+    /// Symbol for `s`; only a string not seen before takes the write lock and allocates
+    pub fn get_or_intern(&self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+        let mut table = self.table.write().unwrap();
+        if let Some(&symbol) = table.ids.get(s) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(table.strings.len()).expect("interner full"));
+        let stored: &'static str = Box::leak(s.to_owned().into_boxed_str());
+        table.strings.push(stored);
+        table.ids.insert(stored, symbol);
+        symbol
+    }
+
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.table.read().unwrap().ids.get(s).copied()
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> &'static str {
+        self.table.read().unwrap().strings[symbol.0 as usize]
+    }
+
+    /// Distinct strings interned
+    pub fn len(&self) -> usize {
+        self.table.read().unwrap().strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning_is_stable_and_resolves() {
+        let interner = Interner::default();
+        let a = interner.get_or_intern("NBA-LAL-BOS-TOTAL");
+        let b = interner.get_or_intern("lebron_james");
+        assert_ne!(a, b);
+        assert_eq!(interner.get_or_intern(&String::from("NBA-LAL-BOS-TOTAL")), a);
+        assert_eq!((interner.resolve(a), interner.resolve(b)), ("NBA-LAL-BOS-TOTAL", "lebron_james"));
+        assert_eq!(interner.get("unseen"), None);
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_symbols_serialize_as_their_strings() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Tick {
+            market_id: Symbol,
+            player_id: Option<Symbol>,
+        }
+
+        let tick = Tick { market_id: Symbol::intern("KXNBAGAME-LAL"), player_id: Some("player_23".into()) };
+        let json = serde_json::to_string(&tick).unwrap();
+        assert_eq!(json, r#"{"market_id":"KXNBAGAME-LAL","player_id":"player_23"}"#);
+        assert_eq!(serde_json::from_str::<Tick>(&json).unwrap(), tick);
+        assert_eq!(format!("{} {:?}", tick.market_id, tick.player_id), r#"KXNBAGAME-LAL Some("player_23")"#);
+        assert_eq!(Symbol::get("KXNBAGAME-LAL"), Some(tick.market_id));
+    }
+}
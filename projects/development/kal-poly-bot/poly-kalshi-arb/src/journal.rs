@@ -0,0 +1,372 @@
+// src/journal.rs
+// Write-ahead journal of order events and fills, with snapshot + compaction for bounded replay
+
+use crate::config::JournalSection;
+use crate::error::StateStoreError;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::position_tracker::{FillRecord, PositionTracker};
+
+const JOURNAL_FILE: &str = "journal.log";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// Journal configuration
+#[derive(Debug, Clone)]
+pub struct JournalConfig {
+    pub dir: PathBuf,
+    /// Records appended before a snapshot + compaction is due
+    pub compact_every: u64,
+    /// fsync after every append (slower, survives power loss)
+    pub fsync: bool,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self::from(&JournalSection::default())
+    }
+}
+
+impl From<&JournalSection> for JournalConfig {
+    fn from(j: &JournalSection) -> Self {
+        Self { dir: PathBuf::from(&j.dir), compact_every: j.compact_every, fsync: j.fsync }
+    }
+}
+
+/// A journaled event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// Both legs about to be sent; `arb_id` ties the submission to its completion or failure
+    ArbSubmitted {
+        #[serde(default)]
+        arb_id: String,
+        market_id: String,
+        arb_type: String,
+        contracts: i64,
+        yes_price: u16,
+        no_price: u16,
+    },
+    /// Both legs returned
+    ArbCompleted {
+        #[serde(default)]
+        arb_id: String,
+        market_id: String,
+        yes_order_id: String,
+        no_order_id: String,
+        yes_filled: i64,
+        no_filled: i64,
+        yes_cost: i64,
+        no_cost: i64,
+    },
+    /// Leg submission failed outright
+    ArbFailed {
+        #[serde(default)]
+        arb_id: String,
+        market_id: String,
+        error: String,
+    },
+    Fill(FillRecord),
+    Resolved { market_id: String, yes_won: bool, timestamp: String },
+}
+
+/// One line of the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub seq: u64,
+    /// RFC3339
+    pub ts: String,
+    pub event: JournalEvent,
+}
+
+/// Snapshot of tracker state covering every record up to `last_seq`
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    last_seq: u64,
+    tracker: PositionTracker,
+}
+
+/// What a recovery replay found
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    pub snapshot_seq: u64,
+    pub replayed: usize,
+    /// Ids of arbs submitted with no completion record - venue state must be reconciled
+    pub unfinished_arbs: Vec<String>,
+}
+
+/// Append-only journal: each line is `<crc32 hex> <record json>`
+pub struct Journal {
+    config: JournalConfig,
+    file: File,
+    next_seq: u64,
+    since_checkpoint: u64,
+}
+
+pub type SharedJournal = Arc<Mutex<Journal>>;
+
+impl Journal {
+    fn journal_path(config: &JournalConfig) -> PathBuf {
+        config.dir.join(JOURNAL_FILE)
+    }
+
+    fn snapshot_path(config: &JournalConfig) -> PathBuf {
+        config.dir.join(SNAPSHOT_FILE)
+    }
+
+    /// Rebuild tracker state from snapshot + journal tail, then open for appending
+    pub fn recover(config: JournalConfig) -> Result<(Self, PositionTracker, RecoveryReport), StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+
+        let (mut tracker, snapshot_seq) = match std::fs::read_to_string(Self::snapshot_path(&config)) {
+            Ok(data) => {
+                let snapshot: Snapshot = serde_json::from_str(&data)
+                    .map_err(|e| StateStoreError::Corrupt(format!("journal snapshot: {}", e)))?;
+                (snapshot.tracker, snapshot.last_seq)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (PositionTracker::new(), 0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let journal_path = Self::journal_path(&config);
+        let (records, valid_len) = read_records(&journal_path)?;
+        let mut report = RecoveryReport { snapshot_seq, ..Default::default() };
+        let mut last_seq = snapshot_seq;
+        // Submission order, so the report lists the oldest unfinished arb first
+        let mut open_arbs: Vec<String> = Vec::new();
+
+        for record in records.into_iter().filter(|r| r.seq > snapshot_seq) {
+            last_seq = record.seq;
+            report.replayed += 1;
+            match record.event {
+                JournalEvent::Fill(fill) => tracker.record_fill_internal(&fill),
+                JournalEvent::Resolved { market_id, yes_won, timestamp } => {
+                    tracker.resolve_position_at(&market_id, yes_won, &timestamp);
+                }
+                JournalEvent::ArbSubmitted { arb_id, market_id, .. } => open_arbs.push(arb_key(arb_id, market_id)),
+                JournalEvent::ArbCompleted { arb_id, market_id, .. } | JournalEvent::ArbFailed { arb_id, market_id, .. } => {
+                    let key = arb_key(arb_id, market_id);
+                    if let Some(i) = open_arbs.iter().position(|open| *open == key) {
+                        open_arbs.remove(i);
+                    }
+                }
+            }
+        }
+        report.unfinished_arbs = open_arbs;
+
+        if !report.unfinished_arbs.is_empty() {
+            warn!("[JOURNAL] {} arbs submitted without completion: {:?}",
+                  report.unfinished_arbs.len(), report.unfinished_arbs);
+        }
+        info!("[JOURNAL] Recovered from snapshot seq {} + {} records", snapshot_seq, report.replayed);
+
+        let file = OpenOptions::new().create(true).append(true).open(&journal_path)?;
+        // Drop any torn tail so new appends start on a clean line
+        if file.metadata()?.len() > valid_len {
+            file.set_len(valid_len)?;
+        }
+        let journal = Self {
+            config,
+            file,
+            next_seq: last_seq + 1,
+            since_checkpoint: report.replayed as u64,
+        };
+        Ok((journal, tracker, report))
+    }
+
+    /// Append an event; returns its sequence number
+    pub fn append(&mut self, event: JournalEvent) -> Result<u64, StateStoreError> {
+        let seq = self.next_seq;
+        let record = JournalRecord { seq, ts: chrono::Utc::now().to_rfc3339(), event };
+        let json = serde_json::to_string(&record)?;
+        writeln!(self.file, "{:08x} {}", crc32(json.as_bytes()), json)?;
+        if self.config.fsync {
+            self.file.sync_data()?;
+        }
+        self.next_seq += 1;
+        self.since_checkpoint += 1;
+        Ok(seq)
+    }
+
+    pub fn needs_checkpoint(&self) -> bool {
+        self.since_checkpoint >= self.config.compact_every
+    }
+
+    /// Snapshot tracker state and drop the journal records it covers
+    /// The tracker must reflect every record appended so far
+    pub fn checkpoint(&mut self, tracker: &PositionTracker) -> Result<(), StateStoreError> {
+        let last_seq = self.next_seq - 1;
+        let snapshot = Snapshot { last_seq, tracker: tracker.clone() };
+
+        // Write-then-rename so a crash never leaves a torn snapshot. With fsync on, the snapshot
+        // and its directory entry reach disk before the records it covers are truncated
+        let path = Self::snapshot_path(&self.config);
+        let tmp = path.with_extension("json.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(serde_json::to_string(&snapshot)?.as_bytes())?;
+        if self.config.fsync {
+            file.sync_all()?;
+        }
+        drop(file);
+        std::fs::rename(&tmp, &path)?;
+        if self.config.fsync {
+            File::open(&self.config.dir)?.sync_all()?;
+        }
+
+        // Records <= last_seq are now redundant; replay skips them even if truncation is lost
+        self.file.set_len(0)?;
+        if self.config.fsync {
+            self.file.sync_all()?;
+        }
+        self.since_checkpoint = 0;
+
+        info!("[JOURNAL] Checkpoint at seq {}", last_seq);
+        Ok(())
+    }
+
+    /// Append, logging instead of failing (for hot paths)
+    pub fn record(journal: &SharedJournal, event: JournalEvent) {
+        if let Err(e) = journal.lock().unwrap().append(event) {
+            warn!("[JOURNAL] Append failed: {}", e);
+        }
+    }
+}
+
+/// Arbs are matched by id; records written before ids existed fall back to their market
+fn arb_key(arb_id: String, market_id: String) -> String {
+    if arb_id.is_empty() { market_id } else { arb_id }
+}
+
+/// Read valid records and the byte length they occupy; stops at the first torn or corrupt line
+fn read_records(path: &PathBuf) -> Result<(Vec<JournalRecord>, u64), StateStoreError> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut reader = BufReader::new(file);
+    let mut out = Vec::new();
+    let mut valid_len = 0u64;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line)?;
+        if n == 0 {
+            break;
+        }
+        let record = line.strip_suffix('\n').and_then(parse_line);
+        match record {
+            Some(record) => {
+                out.push(record);
+                valid_len += n as u64;
+            }
+            None => {
+                warn!("[JOURNAL] Corrupt record after seq {}, ignoring remainder",
+                      out.last().map(|r: &JournalRecord| r.seq).unwrap_or(0));
+                break;
+            }
+        }
+    }
+    Ok((out, valid_len))
+}
+
+fn parse_line(line: &str) -> Option<JournalRecord> {
+    let (crc_hex, json) = line.split_once(' ')?;
+    let crc = u32::from_str_radix(crc_hex, 16).ok()?;
+    if crc != crc32(json.as_bytes()) {
+        return None;
+    }
+    serde_json::from_str(json).ok()
+}
+
+/// CRC-32 (IEEE 802.3)
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(name: &str) -> JournalConfig {
+        let dir = std::env::temp_dir().join(format!("journal_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        JournalConfig { dir, compact_every: 3, fsync: true }
+    }
+
+    fn fill(side: &str, price: f64) -> FillRecord {
+        FillRecord::new("M", "m", "kalshi", side, 10.0, price, 0.0, "o")
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_replay_rebuilds_tracker() {
+        let config = test_config("replay");
+        let (mut journal, _, _) = Journal::recover(config.clone()).unwrap();
+        journal.append(JournalEvent::Fill(fill("yes", 0.45))).unwrap();
+        journal.append(JournalEvent::Fill(fill("no", 0.50))).unwrap();
+        // Two arbs on one market; only the second completes
+        for arb_id in ["M2@1", "M2@2"] {
+            journal.append(JournalEvent::ArbSubmitted {
+                arb_id: arb_id.into(), market_id: "M2".into(), arb_type: "KalshiOnly".into(), contracts: 1, yes_price: 40, no_price: 50,
+            }).unwrap();
+        }
+        journal.append(JournalEvent::ArbFailed { arb_id: "M2@2".into(), market_id: "M2".into(), error: "timeout".into() }).unwrap();
+        drop(journal);
+
+        let (_, tracker, report) = Journal::recover(config.clone()).unwrap();
+        assert_eq!(report.replayed, 5);
+        assert_eq!(report.unfinished_arbs, vec!["M2@1".to_string()]);
+        // Records from before arb ids still parse, and match by market
+        let legacy: JournalEvent = serde_json::from_str(r#"{"type":"arb_failed","market_id":"M3","error":"x"}"#).unwrap();
+        assert!(matches!(legacy, JournalEvent::ArbFailed { arb_id, .. } if arb_id.is_empty()));
+        assert!((tracker.get("M").unwrap().total_cost() - 9.5).abs() < 1e-9);
+        let _ = std::fs::remove_dir_all(&config.dir);
+    }
+
+    #[test]
+    fn test_checkpoint_compacts_and_torn_tail_ignored() {
+        let config = test_config("checkpoint");
+        let (mut journal, mut tracker, _) = Journal::recover(config.clone()).unwrap();
+        for f in [fill("yes", 0.45), fill("no", 0.50), fill("yes", 0.40)] {
+            journal.append(JournalEvent::Fill(f.clone())).unwrap();
+            tracker.record_fill_internal(&f);
+        }
+        assert!(journal.needs_checkpoint());
+        journal.checkpoint(&tracker).unwrap();
+        assert_eq!(std::fs::read_to_string(config.dir.join(JOURNAL_FILE)).unwrap(), "");
+
+        journal.append(JournalEvent::Resolved { market_id: "M".into(), yes_won: true, timestamp: chrono::Utc::now().to_rfc3339() }).unwrap();
+        drop(journal);
+        // Simulate a torn write at the tail
+        let mut f = OpenOptions::new().append(true).open(config.dir.join(JOURNAL_FILE)).unwrap();
+        write!(f, "deadbeef {{\"seq\":9").unwrap();
+        drop(f);
+
+        let (journal, tracker, report) = Journal::recover(config.clone()).unwrap();
+        assert_eq!(report.snapshot_seq, 3);
+        assert_eq!(report.replayed, 1);
+        assert_eq!(journal.next_seq, 5);
+        assert_eq!(tracker.get("M").unwrap().status, "resolved");
+        // 20 YES @ avg 42.5¢ + 10 NO @ 50¢ = $13.50, YES pays $20
+        assert!((tracker.all_time_pnl - 6.5).abs() < 1e-9);
+        let _ = std::fs::remove_dir_all(&config.dir);
+    }
+}
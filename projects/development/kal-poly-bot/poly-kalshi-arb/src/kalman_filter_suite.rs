@@ -5,6 +5,7 @@
 //! Hyperparameters tuned from real tick data with adaptive regime detection.
 
 use crate::types::{TimestampNs, PriceCents, MarketType, Platform};
+use crate::microstructure::BookFeatures;
 use nalgebra::{DMatrix, DVector, Vector2, Vector3, Vector4, Matrix2, Matrix3, Matrix4, Matrix2x3, Matrix3x4};
 use std::collections::{HashMap, VecDeque};
 use serde::{Serialize, Deserialize};
@@ -566,6 +567,50 @@ impl MicroSuspensionKF {
     }
 }
 
+/// Pattern #76: Market-Maker Compression
+/// Tracks the microprice; a lopsided book with agreeing order flow means makers
+/// are compressing their quotes toward the heavy side
+#[derive(Debug, Clone)]
+pub struct MmCompressionKF {
+    /// Base adaptive filter
+    pub base: AdaptiveKalmanFilter,
+    /// Latest book features (None until a feature-bearing update arrives)
+    pub features: Option<BookFeatures>,
+    /// |imbalance| and |trade-flow imbalance| at or above this flag compression
+    pub imbalance_threshold: f64,
+}
+
+impl MmCompressionKF {
+    /// Create new MM compression filter
+    pub fn new(dt: f64) -> Self {
+        let mut base = AdaptiveKalmanFilter::new(dt, 2, 1);
+
+        // State: [fair_value, drift]
+        base.f = DMatrix::from_row_slice(2, 2, &[
+            1.0, dt,
+            0.0, 1.0,
+        ]);
+
+        // Observation: microprice (or last price when the feed has no depth)
+        base.h = DMatrix::from_row_slice(1, 2, &[1.0, 0.0]);
+
+        Self {
+            base,
+            features: None,
+            imbalance_threshold: 0.6,
+        }
+    }
+
+    /// Book is one-sided and order flow is pushing the same way
+    pub fn is_compressing(&self) -> bool {
+        self.features.is_some_and(|f| {
+            f.imbalance.abs() >= self.imbalance_threshold
+                && f.trade_flow_imbalance.abs() >= self.imbalance_threshold
+                && f.imbalance.signum() == f.trade_flow_imbalance.signum()
+        })
+    }
+}
+
 /// Factory for creating pattern-specific filters
 pub struct KalmanFilterFactory;
 
@@ -576,6 +621,7 @@ impl KalmanFilterFactory {
             51 => Ok(Box::new(HalfTimeInferenceKF::new(dt))),
             68 => Ok(Box::new(PropagationPathKF::new(dt))),
             75 => Ok(Box::new(VelocityConvexityKF::new(dt))),
+            76 => Ok(Box::new(MmCompressionKF::new(dt))),
             56 => Ok(Box::new(MicroSuspensionKF::new(dt))),
             _ => Err(format!("Unsupported pattern ID: {}", pattern_id)),
         }
@@ -590,6 +636,12 @@ pub trait KalmanFilterTrait {
     /// Update with observation
     fn update(&mut self, observation: &[f64]) -> Result<(), String>;
 
+    /// Update with observation plus the feed's book features, when it has depth;
+    /// filters that don't use microstructure inputs ignore them
+    fn update_with_features(&mut self, observation: &[f64], _features: Option<&BookFeatures>) -> Result<(), String> {
+        self.update(observation)
+    }
+
     /// Get current state
     fn get_state(&self) -> HashMap<String, f64>;
 
@@ -680,6 +732,42 @@ impl KalmanFilterTrait for VelocityConvexityKF {
     }
 }
 
+impl KalmanFilterTrait for MmCompressionKF {
+    fn predict(&mut self) {
+        self.base.predict();
+    }
+
+    fn update(&mut self, observation: &[f64]) -> Result<(), String> {
+        self.update_with_features(observation, None)
+    }
+
+    fn update_with_features(&mut self, observation: &[f64], features: Option<&BookFeatures>) -> Result<(), String> {
+        if observation.len() != 1 {
+            return Err("Expected 1 observation for MM compression filter".to_string());
+        }
+        self.features = features.copied();
+        let z = features.map_or(observation[0], |f| f.microprice);
+        self.base.update(&DVector::from_vec(vec![z]))
+    }
+
+    fn get_state(&self) -> HashMap<String, f64> {
+        let mut state = self.base.get_state();
+        if let Some(f) = self.features {
+            state.insert("imbalance".to_string(), f.imbalance);
+            state.insert("trade_flow_imbalance".to_string(), f.trade_flow_imbalance);
+        }
+        state
+    }
+
+    fn get_regime(&self) -> Regime {
+        self.base.current_regime
+    }
+
+    fn get_uncertainty(&self) -> f64 {
+        self.base.get_position_uncertainty()
+    }
+}
+
 impl KalmanFilterTrait for MicroSuspensionKF {
     fn predict(&mut self) {
         self.base.predict();
@@ -787,6 +875,23 @@ mod tests {
         assert!(acceleration.is_finite());
     }
 
+    #[test]
+    fn test_mm_compression_filter() {
+        let mut kf = MmCompressionKF::new(0.05);
+        let features = BookFeatures { imbalance: 0.8, microprice: 41.5, trade_flow_imbalance: 0.7 };
+
+        kf.update_with_features(&[40.0], Some(&features)).unwrap();
+        assert!(kf.is_compressing());
+        // Measured the microprice, not the last trade
+        assert!((kf.base.x[0] - 41.5).abs() < 1.0);
+
+        kf.update_with_features(&[40.0], Some(&BookFeatures { trade_flow_imbalance: -0.7, ..features })).unwrap();
+        assert!(!kf.is_compressing(), "Flow against the book is not compression");
+
+        kf.update(&[40.0]).unwrap();
+        assert!(kf.features.is_none());
+    }
+
     #[test]
     fn test_micro_suspension_filter() {
         let mut kf = MicroSuspensionKF::new(0.001);
@@ -12,17 +12,25 @@ use rsa::{
     RsaPrivateKey,
 };
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio_tungstenite::{connect_async, tungstenite::{http::Request, Message}};
-use tracing::{debug, error, info};
-
-use crate::config::{KALSHI_WS_URL, KALSHI_API_BASE, KALSHI_API_DELAY_MS};
-use crate::execution::NanoClock;
+use tracing::{debug, error, info, Level};
+
+use crate::circuit_breaker::SharedBreaker;
+use crate::error::VenueApiError;
+use crate::feed_schema::FeedSchemas;
+use crate::config::{kalshi_api_base, kalshi_ws_url, KALSHI_API_DELAY_MS};
+use crate::request_scheduler::{retry_after, RequestPriority, VenueScheduler};
+use crate::secrets::{read_secret_file, SecretsProvider, KALSHI_API_KEY_ID, KALSHI_PRIVATE_KEY};
+use crate::clock::SharedClock;
 use crate::types::{
-    KalshiEventsResponse, KalshiMarketsResponse, KalshiEvent, KalshiMarket,
-    GlobalState, FastExecutionRequest, ArbType, PriceCents, SizeCents, fxhash_str,
+    KalshiEventsResponse, KalshiMarketsResponse, KalshiMarketResponse, KalshiEvent, KalshiMarket,
+    KalshiBalanceResponse, KalshiPositionsResponse, KalshiMarketPosition,
+    KalshiExchangeScheduleResponse, KalshiMaintenanceWindow, KalshiOrderbookResponse, KalshiOrderbook,
+    GlobalState, FastExecutionRequest, ArbType, MarketId, Price, Size, PriceCents, SizeCents, Platform, fxhash_str,
 };
 
 // === Order Types ===
@@ -48,6 +56,8 @@ pub struct KalshiOrderRequest<'a> {
     pub expiration_ts: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_in_force: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_only: Option<bool>,
 }
 
 impl<'a> KalshiOrderRequest<'a> {
@@ -70,6 +80,7 @@ impl<'a> KalshiOrderRequest<'a> {
             client_order_id,
             expiration_ts: None,
             time_in_force: Some("immediate_or_cancel"),
+            post_only: None,
         }
     }
 
@@ -92,6 +103,24 @@ impl<'a> KalshiOrderRequest<'a> {
             client_order_id,
             expiration_ts: None,
             time_in_force: Some("immediate_or_cancel"),
+            post_only: None,
+        }
+    }
+
+    /// Create a resting limit buy (takes whatever crosses on arrival, rests the rest)
+    pub fn limit_buy(ticker: Cow<'a, str>, side: &'static str, price_cents: i64, count: i64, client_order_id: Cow<'a, str>) -> Self {
+        Self {
+            time_in_force: None,
+            ..Self::ioc_buy(ticker, side, price_cents, count, client_order_id)
+        }
+    }
+
+    /// Create a resting post-only buy (rejected instead of taking if it would cross)
+    pub fn resting_buy(ticker: Cow<'a, str>, side: &'static str, price_cents: i64, count: i64, client_order_id: Cow<'a, str>) -> Self {
+        Self {
+            time_in_force: None,
+            post_only: Some(true),
+            ..Self::ioc_buy(ticker, side, price_cents, count, client_order_id)
         }
     }
 }
@@ -156,18 +185,22 @@ pub struct KalshiConfig {
 }
 
 impl KalshiConfig {
-    pub fn from_env() -> Result<Self> {
-        dotenvy::dotenv().ok();
-        let api_key_id = std::env::var("KALSHI_API_KEY_ID").context("KALSHI_API_KEY_ID not set")?;
-        // Support both KALSHI_PRIVATE_KEY_PATH and KALSHI_PRIVATE_KEY_FILE for compatibility
-        let key_path = std::env::var("KALSHI_PRIVATE_KEY_PATH")
-            .or_else(|_| std::env::var("KALSHI_PRIVATE_KEY_FILE"))
-            .unwrap_or_else(|_| "kalshi_private_key.txt".to_string());
-        let private_key_pem = std::fs::read_to_string(&key_path)
-            .with_context(|| format!("Failed to read private key from {}", key_path))?
-            .trim()
-            .to_owned();
-        let private_key = RsaPrivateKey::from_pkcs1_pem(&private_key_pem)
+    /// Load credentials; the PEM comes from the KALSHI_PRIVATE_KEY secret, else from
+    /// the 0600 file at KALSHI_PRIVATE_KEY_PATH / KALSHI_PRIVATE_KEY_FILE
+    pub async fn from_secrets(secrets: &dyn SecretsProvider) -> Result<Self> {
+        let api_key_id = secrets.require(KALSHI_API_KEY_ID).await?.expose().to_string();
+        let private_key_pem = match secrets.get(KALSHI_PRIVATE_KEY).await? {
+            Some(pem) => pem,
+            None => {
+                // Support both KALSHI_PRIVATE_KEY_PATH and KALSHI_PRIVATE_KEY_FILE for compatibility
+                let key_path = std::env::var("KALSHI_PRIVATE_KEY_PATH")
+                    .or_else(|_| std::env::var("KALSHI_PRIVATE_KEY_FILE"))
+                    .unwrap_or_else(|_| "kalshi_private_key.txt".to_string());
+                read_secret_file(Path::new(&key_path))?
+            }
+        };
+        // RsaPrivateKey zeroizes itself on drop; the PEM buffer is wiped with `private_key_pem`
+        let private_key = RsaPrivateKey::from_pkcs1_pem(private_key_pem.expose().trim())
             .context("Failed to parse private key PEM")?;
         Ok(Self { api_key_id, private_key })
     }
@@ -195,6 +228,10 @@ static ORDER_COUNTER: AtomicU32 = AtomicU32::new(0);
 pub struct KalshiApiClient {
     http: reqwest::Client,
     pub config: KalshiConfig,
+    /// Shared venue scheduler (replaces the fixed inter-request delay when set)
+    scheduler: Option<Arc<VenueScheduler>>,
+    /// Venue breaker; fails fast while Kalshi is erroring
+    breaker: Option<SharedBreaker<Platform>>,
 }
 
 impl KalshiApiClient {
@@ -205,9 +242,40 @@ impl KalshiApiClient {
                 .build()
                 .expect("Failed to build HTTP client"),
             config,
+            scheduler: None,
+            breaker: None,
+        }
+    }
+
+    /// Route all requests through a shared venue scheduler
+    pub fn with_scheduler(mut self, scheduler: Arc<VenueScheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Wrap all requests in a shared venue circuit breaker
+    pub fn with_breaker(mut self, breaker: SharedBreaker<Platform>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Send through the breaker (if any); transport errors and 5xx count as failures
+    async fn send_guarded(&self, req: reqwest::RequestBuilder) -> Result<reqwest::Response, VenueApiError> {
+        match &self.breaker {
+            Some(breaker) => breaker
+                .call_with(Platform::Kalshi, req.send(), |r| {
+                    r.as_ref().map_or(true, |resp| resp.status().is_server_error())
+                })
+                .await
+                .map_err(|e| VenueApiError::breaker(Platform::Kalshi, e)),
+            None => req.send().await.map_err(|e| VenueApiError::reqwest(Platform::Kalshi, e)),
         }
     }
 
+    fn sign(&self, message: &str) -> Result<String, VenueApiError> {
+        self.config.sign(message).map_err(|e| VenueApiError::signing(Platform::Kalshi, e))
+    }
+
     #[inline]
     fn next_order_id() -> ArrayString<24> {
         let counter = ORDER_COUNTER.fetch_add(1, Ordering::Relaxed);
@@ -218,35 +286,53 @@ impl KalshiApiClient {
     }
     
     /// Generic authenticated GET request with retry on rate limit
-    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, VenueApiError> {
+        self.get_with_priority(path, RequestPriority::MarketData).await
+    }
+
+    /// GET in a specific scheduler lane
+    async fn get_with_priority<T: serde::de::DeserializeOwned>(&self, path: &str, priority: RequestPriority) -> Result<T, VenueApiError> {
         let mut retries = 0;
         const MAX_RETRIES: u32 = 5;
 
         loop {
-            let url = format!("{}{}", KALSHI_API_BASE, path);
+            if let Some(scheduler) = &self.scheduler {
+                scheduler.acquire(priority).await;
+            }
+            let url = format!("{}{}", kalshi_api_base(), path);
             let timestamp_ms = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
                 .as_millis() as u64;
             // Kalshi signature uses FULL path including /trade-api/v2 prefix
             let full_path = format!("/trade-api/v2{}", path);
-            let signature = self.config.sign(&format!("{}GET{}", timestamp_ms, full_path))?;
+            let signature = self.sign(&format!("{}GET{}", timestamp_ms, full_path))?;
             
-            let resp = self.http
+            let resp = self.send_guarded(self.http
                 .get(&url)
                 .header("KALSHI-ACCESS-KEY", &self.config.api_key_id)
                 .header("KALSHI-ACCESS-SIGNATURE", &signature)
-                .header("KALSHI-ACCESS-TIMESTAMP", timestamp_ms.to_string())
-                .send()
+                .header("KALSHI-ACCESS-TIMESTAMP", timestamp_ms.to_string()))
                 .await?;
             
             let status = resp.status();
+            if let Some(scheduler) = &self.scheduler {
+                scheduler.report_status(status, retry_after(&resp));
+            }
             
             // Handle rate limit with exponential backoff
             if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
                 retries += 1;
                 if retries > MAX_RETRIES {
-                    anyhow::bail!("Kalshi API rate limited after {} retries", MAX_RETRIES);
+                    debug!("[KALSHI] Rate limited after {} retries", MAX_RETRIES);
+                    return Err(VenueApiError::RateLimited {
+                        venue: Platform::Kalshi,
+                        retry_after: retry_after(&resp),
+                    });
+                }
+                // Scheduler already paused the venue; just retry through it
+                if self.scheduler.is_some() {
+                    continue;
                 }
                 let backoff_ms = 2000 * (1 << retries); // 4s, 8s, 16s, 32s, 64s
                 debug!("[KALSHI] Rate limited, backing off {}ms (retry {}/{})", 
@@ -256,31 +342,81 @@ impl KalshiApiClient {
             }
             
             if !status.is_success() {
-                let body = resp.text().await.unwrap_or_default();
-                anyhow::bail!("Kalshi API error {}: {}", status, body);
+                return Err(VenueApiError::from_response(Platform::Kalshi, resp).await);
             }
             
-            let data: T = resp.json().await?;
-            tokio::time::sleep(Duration::from_millis(KALSHI_API_DELAY_MS)).await;
+            let data: T = resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Kalshi, e))?;
+            if self.scheduler.is_none() {
+                tokio::time::sleep(Duration::from_millis(KALSHI_API_DELAY_MS)).await;
+            }
             return Ok(data);
         }
     }
     
-    pub async fn get_events(&self, series_ticker: &str, limit: u32) -> Result<Vec<KalshiEvent>> {
+    pub async fn get_events(&self, series_ticker: &str, limit: u32) -> Result<Vec<KalshiEvent>, VenueApiError> {
         let path = format!("/events?series_ticker={}&limit={}&status=open", series_ticker, limit);
-        let resp: KalshiEventsResponse = self.get(&path).await?;
+        let resp: KalshiEventsResponse = self.get_with_priority(&path, RequestPriority::Discovery).await?;
         Ok(resp.events)
     }
     
-    pub async fn get_markets(&self, event_ticker: &str) -> Result<Vec<KalshiMarket>> {
+    pub async fn get_markets(&self, event_ticker: &str) -> Result<Vec<KalshiMarket>, VenueApiError> {
         let path = format!("/markets?event_ticker={}", event_ticker);
-        let resp: KalshiMarketsResponse = self.get(&path).await?;
+        let resp: KalshiMarketsResponse = self.get_with_priority(&path, RequestPriority::Discovery).await?;
         Ok(resp.markets)
     }
+
+    /// Single market by ticker (status/result used for settlement tracking)
+    pub async fn get_market(&self, ticker: &str) -> Result<KalshiMarket, VenueApiError> {
+        let path = format!("/markets/{}", ticker);
+        let resp: KalshiMarketResponse = self.get(&path).await?;
+        Ok(resp.market)
+    }
+
+    /// Current orderbook of a market (resync after a missed delta)
+    pub async fn get_orderbook(&self, ticker: &str) -> Result<KalshiOrderbook, VenueApiError> {
+        let path = format!("/markets/{}/orderbook", ticker);
+        let resp: KalshiOrderbookResponse = self.get(&path).await?;
+        Ok(resp.orderbook)
+    }
+
+    /// Maintenance windows published in the exchange schedule
+    pub async fn get_maintenance_windows(&self) -> Result<Vec<KalshiMaintenanceWindow>, VenueApiError> {
+        let resp: KalshiExchangeScheduleResponse = self.get_with_priority("/exchange/schedule", RequestPriority::Discovery).await?;
+        Ok(resp.schedule.maintenance_windows)
+    }
     
+    /// Available cash balance in cents
+    pub async fn get_balance(&self) -> Result<i64, VenueApiError> {
+        let resp: KalshiBalanceResponse = self.get("/portfolio/balance").await?;
+        Ok(resp.balance)
+    }
+
+    /// All non-zero market positions (follows pagination cursor)
+    pub async fn get_positions(&self) -> Result<Vec<KalshiMarketPosition>, VenueApiError> {
+        let mut out = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let path = match &cursor {
+                Some(c) => format!("/portfolio/positions?limit=1000&count_filter=position&cursor={}", c),
+                None => "/portfolio/positions?limit=1000&count_filter=position".to_string(),
+            };
+            let resp: KalshiPositionsResponse = self.get(&path).await?;
+            out.extend(resp.market_positions);
+            match resp.cursor {
+                Some(c) if !c.is_empty() => cursor = Some(c),
+                _ => break,
+            }
+        }
+        Ok(out)
+    }
+
     /// Generic authenticated POST request
-    async fn post<T: serde::de::DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
-        let url = format!("{}{}", KALSHI_API_BASE, path);
+    async fn post<T: serde::de::DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T, VenueApiError> {
+        // Acquire before signing so the timestamp is fresh when sent
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.acquire(RequestPriority::Order).await;
+        }
+        let url = format!("{}{}", kalshi_api_base(), path);
         let timestamp_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
@@ -288,31 +424,63 @@ impl KalshiApiClient {
         // Kalshi signature uses FULL path including /trade-api/v2 prefix
         let full_path = format!("/trade-api/v2{}", path);
         let msg = format!("{}POST{}", timestamp_ms, full_path);
-        let signature = self.config.sign(&msg)?;
+        let signature = self.sign(&msg)?;
 
-        let resp = self.http
+        let resp = self.send_guarded(self.http
             .post(&url)
             .header("KALSHI-ACCESS-KEY", &self.config.api_key_id)
             .header("KALSHI-ACCESS-SIGNATURE", &signature)
             .header("KALSHI-ACCESS-TIMESTAMP", timestamp_ms.to_string())
             .header("Content-Type", "application/json")
             .timeout(ORDER_TIMEOUT)
-            .json(body)
-            .send()
+            .json(body))
             .await?;
 
         let status = resp.status();
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.report_status(status, retry_after(&resp));
+        }
         if !status.is_success() {
-            let body = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Kalshi API error {}: {}", status, body);
+            return Err(VenueApiError::from_response(Platform::Kalshi, resp).await);
         }
         
-        let data: T = resp.json().await?;
-        Ok(data)
+        resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Kalshi, e))
     }
     
+    /// Generic authenticated DELETE request
+    async fn delete<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, VenueApiError> {
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.acquire(RequestPriority::Order).await;
+        }
+        let url = format!("{}{}", kalshi_api_base(), path);
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let full_path = format!("/trade-api/v2{}", path);
+        let signature = self.sign(&format!("{}DELETE{}", timestamp_ms, full_path))?;
+
+        let resp = self.send_guarded(self.http
+            .delete(&url)
+            .header("KALSHI-ACCESS-KEY", &self.config.api_key_id)
+            .header("KALSHI-ACCESS-SIGNATURE", &signature)
+            .header("KALSHI-ACCESS-TIMESTAMP", timestamp_ms.to_string())
+            .timeout(ORDER_TIMEOUT))
+            .await?;
+
+        let status = resp.status();
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.report_status(status, retry_after(&resp));
+        }
+        if !status.is_success() {
+            return Err(VenueApiError::from_response(Platform::Kalshi, resp).await);
+        }
+
+        resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Kalshi, e))
+    }
+
     /// Create an order on Kalshi
-    pub async fn create_order(&self, order: &KalshiOrderRequest<'_>) -> Result<KalshiOrderResponse> {
+    pub async fn create_order(&self, order: &KalshiOrderRequest<'_>) -> Result<KalshiOrderResponse, VenueApiError> {
         let path = "/portfolio/orders";
         self.post(path, order).await
     }
@@ -324,7 +492,7 @@ impl KalshiApiClient {
         side: &str,  // "yes" or "no"
         price_cents: i64,
         count: i64,
-    ) -> Result<KalshiOrderResponse> {
+    ) -> Result<KalshiOrderResponse, VenueApiError> {
         debug_assert!(!ticker.is_empty(), "ticker must not be empty");
         debug_assert!(price_cents >= 1 && price_cents <= 99, "price must be 1-99");
         debug_assert!(count >= 1, "count must be >= 1");
@@ -351,7 +519,7 @@ impl KalshiApiClient {
         side: &str,
         price_cents: i64,
         count: i64,
-    ) -> Result<KalshiOrderResponse> {
+    ) -> Result<KalshiOrderResponse, VenueApiError> {
         debug_assert!(!ticker.is_empty(), "ticker must not be empty");
         debug_assert!(price_cents >= 1 && price_cents <= 99, "price must be 1-99");
         debug_assert!(count >= 1, "count must be >= 1");
@@ -371,6 +539,80 @@ impl KalshiApiClient {
         debug!("[KALSHI] {} filled={}", resp.order.status, resp.order.filled_count());
         Ok(resp)
     }
+
+    /// Rest a post-only buy on the book (market making quotes)
+    pub async fn buy_resting(
+        &self,
+        ticker: &str,
+        side: &str,
+        price_cents: i64,
+        count: i64,
+    ) -> Result<KalshiOrderResponse, VenueApiError> {
+        debug_assert!(!ticker.is_empty(), "ticker must not be empty");
+        debug_assert!((1..=99).contains(&price_cents), "price must be 1-99");
+        debug_assert!(count >= 1, "count must be >= 1");
+
+        let side_static: &'static str = if side == "yes" { "yes" } else { "no" };
+        let order_id = Self::next_order_id();
+        let order = KalshiOrderRequest::resting_buy(
+            Cow::Borrowed(ticker),
+            side_static,
+            price_cents,
+            count,
+            Cow::Borrowed(&order_id)
+        );
+        debug!("[KALSHI] REST {} {} @{}¢ x{}", side, ticker, price_cents, count);
+        self.create_order(&order).await
+    }
+
+    /// Limit buy left on the book for `rest_for`, then cancelled; the response carries the
+    /// final fill counts (passive entry)
+    pub async fn buy_passive(
+        &self,
+        ticker: &str,
+        side: &str,
+        price_cents: i64,
+        count: i64,
+        rest_for: Duration,
+    ) -> Result<KalshiOrderResponse, VenueApiError> {
+        debug_assert!(!ticker.is_empty(), "ticker must not be empty");
+        debug_assert!((1..=99).contains(&price_cents), "price must be 1-99");
+        debug_assert!(count >= 1, "count must be >= 1");
+
+        let side_static: &'static str = if side == "yes" { "yes" } else { "no" };
+        let order_id = Self::next_order_id();
+        let order = KalshiOrderRequest::limit_buy(
+            Cow::Borrowed(ticker),
+            side_static,
+            price_cents,
+            count,
+            Cow::Borrowed(&order_id)
+        );
+        debug!("[KALSHI] LIMIT {} {} @{}¢ x{} for {:?}", side, ticker, price_cents, count, rest_for);
+
+        let resp = self.create_order(&order).await?;
+        if resp.order.status != "resting" {
+            return Ok(resp);
+        }
+        tokio::time::sleep(rest_for).await;
+        match self.cancel_order(&resp.order.order_id).await {
+            Ok(cancelled) => Ok(cancelled),
+            // Filled while resting
+            Err(_) => self.get_order(&resp.order.order_id).await,
+        }
+    }
+
+    /// Current state of an order
+    pub async fn get_order(&self, order_id: &str) -> Result<KalshiOrderResponse, VenueApiError> {
+        let path = format!("/portfolio/orders/{}", order_id);
+        self.get(&path).await
+    }
+
+    /// Cancel a resting order
+    pub async fn cancel_order(&self, order_id: &str) -> Result<KalshiOrderResponse, VenueApiError> {
+        let path = format!("/portfolio/orders/{}", order_id);
+        self.delete(&path).await
+    }
 }
 
 // === WebSocket Message Types ===
@@ -379,6 +621,9 @@ impl KalshiApiClient {
 pub struct KalshiWsMessage {
     #[serde(rename = "type")]
     pub msg_type: String,
+    /// Subscription id, and the message's place in that subscription's stream
+    pub sid: Option<u64>,
+    pub seq: Option<u64>,
     pub msg: Option<KalshiWsMsgBody>,
 }
 
@@ -393,31 +638,65 @@ pub struct KalshiWsMsgBody {
     pub price: Option<i64>,
     pub delta: Option<i64>,
     pub side: Option<String>,
+    /// Delta wall-clock time (RFC 3339)
+    pub ts: Option<String>,
 }
 
 #[derive(Serialize)]
-struct SubscribeCmd {
-    id: i32,
-    cmd: &'static str,
-    params: SubscribeParams,
+pub(crate) struct SubscribeCmd {
+    pub(crate) id: i32,
+    pub(crate) cmd: &'static str,
+    pub(crate) params: SubscribeParams,
 }
 
 #[derive(Serialize)]
-struct SubscribeParams {
-    channels: Vec<&'static str>,
-    market_tickers: Vec<String>,
+pub(crate) struct SubscribeParams {
+    pub(crate) channels: Vec<&'static str>,
+    pub(crate) market_tickers: Vec<String>,
 }
 
 // =============================================================================
 // WebSocket Runner
 // =============================================================================
 
-/// WebSocket runner
+/// Signed WebSocket upgrade request for `kalshi_ws_url()`
+pub fn ws_request(config: &KalshiConfig) -> Result<Request<()>> {
+    ws_request_to(config, kalshi_ws_url())
+}
+
+/// Signed WebSocket upgrade request for another endpoint of the same API (`[feeds] endpoints`)
+pub fn ws_request_to(config: &KalshiConfig, ws_url: &str) -> Result<Request<()>> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_millis()
+        .to_string();
+
+    let signature = config.sign(&format!("{}GET/trade-api/ws/v2", timestamp))?;
+
+    let host = ws_url.split_once("://").map_or(ws_url, |(_, rest)| rest).split('/').next().unwrap_or_default();
+
+    Ok(Request::builder()
+        .uri(ws_url)
+        .header("KALSHI-ACCESS-KEY", &config.api_key_id)
+        .header("KALSHI-ACCESS-SIGNATURE", &signature)
+        .header("KALSHI-ACCESS-TIMESTAMP", &timestamp)
+        .header("Host", host)
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", tokio_tungstenite::tungstenite::handshake::client::generate_key())
+        .body(())?)
+}
+
+/// WebSocket runner; every message is schema-checked before it touches the books and
+/// arbs are stamped `detected_ns` on `clock`
 pub async fn run_ws(
     config: &KalshiConfig,
+    schemas: &FeedSchemas,
     state: Arc<GlobalState>,
     exec_tx: mpsc::Sender<FastExecutionRequest>,
     threshold_cents: PriceCents,
+    clock: SharedClock,
 ) -> Result<()> {
     let tickers: Vec<String> = state.markets.iter()
         .take(state.market_count())
@@ -430,25 +709,7 @@ pub async fn run_ws(
         return Ok(());
     }
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)?
-        .as_millis()
-        .to_string();
-
-    let signature = config.sign(&format!("{}GET/trade-api/ws/v2", timestamp))?;
-
-    let request = Request::builder()
-        .uri(KALSHI_WS_URL)
-        .header("KALSHI-ACCESS-KEY", &config.api_key_id)
-        .header("KALSHI-ACCESS-SIGNATURE", &signature)
-        .header("KALSHI-ACCESS-TIMESTAMP", &timestamp)
-        .header("Host", "api.elections.kalshi.com")
-        .header("Connection", "Upgrade")
-        .header("Upgrade", "websocket")
-        .header("Sec-WebSocket-Version", "13")
-        .header("Sec-WebSocket-Key", tokio_tungstenite::tungstenite::handshake::client::generate_key())
-        .body(())?;
-
+    let request = ws_request(config)?;
     let (ws_stream, _) = connect_async(request).await.context("Failed to connect to Kalshi")?;
     info!("[KALSHI] Connected");
 
@@ -467,12 +728,18 @@ pub async fn run_ws(
     write.send(Message::Text(serde_json::to_string(&subscribe_msg)?)).await?;
     info!("[KALSHI] Subscribed to {} markets", tickers.len());
 
-    let clock = NanoClock::new();
 
     while let Some(msg) = read.next().await {
         match msg {
             Ok(Message::Text(text)) => {
-                match serde_json::from_str::<KalshiWsMessage>(&text) {
+                let value = match schemas.check(Platform::Kalshi, &text) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        crate::hot_path!(Level::WARN, "[KALSHI] {}", e);
+                        continue;
+                    }
+                };
+                match KalshiWsMessage::deserialize(&value) {
                     Ok(kalshi_msg) => {
                         let ticker = kalshi_msg.msg.as_ref()
                             .and_then(|m| m.market_ticker.as_ref());
@@ -481,7 +748,7 @@ pub async fn run_ws(
                         let ticker_hash = fxhash_str(ticker);
 
                         let Some(&market_id) = state.kalshi_to_id.get(&ticker_hash) else { continue };
-                        let market = &state.markets[market_id as usize];
+                        let market = &state.markets[market_id.index()];
 
                         match kalshi_msg.msg_type.as_str() {
                             "orderbook_snapshot" => {
@@ -509,8 +776,9 @@ pub async fn run_ws(
                         }
                     }
                     Err(e) => {
-                        // Log at trace level - unknown message types are normal
-                        tracing::trace!("[KALSHI] WS parse error: {} (msg: {}...)", e, &text[..text.len().min(100)]);
+                        let message_type = value.get("type").and_then(|t| t.as_str()).unwrap_or("untyped");
+                        let e = schemas.reject(Platform::Kalshi, message_type, e.to_string(), &text);
+                        crate::hot_path!(Level::WARN, "[KALSHI] {}", e);
                     }
                 }
             }
@@ -633,11 +901,11 @@ fn process_kalshi_delta(market: &crate::types::AtomicMarketState, body: &KalshiW
 /// Send arb request from Kalshi handler
 #[inline]
 async fn send_kalshi_arb_request(
-    market_id: u16,
+    market_id: MarketId,
     market: &crate::types::AtomicMarketState,
     arb_mask: u8,
     exec_tx: &mpsc::Sender<FastExecutionRequest>,
-    clock: &NanoClock,
+    clock: &SharedClock,
 ) {
     let (k_yes, k_no, k_yes_size, k_no_size) = market.kalshi.load();
     let (p_yes, p_no, p_yes_size, p_no_size) = market.poly.load();
@@ -656,12 +924,12 @@ async fn send_kalshi_arb_request(
 
     let req = FastExecutionRequest {
         market_id,
-        yes_price,
-        no_price,
-        yes_size,
-        no_size,
+        yes_price: Price(yes_price),
+        no_price: Price(no_price),
+        yes_size: Size(yes_size),
+        no_size: Size(no_size),
         arb_type,
-        detected_ns: clock.now_ns(),
+        detected_ns: clock.mono_ns(),
     };
 
     let _ = exec_tx.try_send(req);
@@ -0,0 +1,558 @@
+// src/kalshi_feed.rs
+// Kalshi feed client - the `FeedClient` the aggregator runs against the live Kalshi trade API
+// socket: signed connect, orderbook_delta subscription, books rebuilt from snapshots and deltas
+// and emitted as `PriceUpdate`s carrying Kalshi's own timestamps. Gaps in a subscription's
+// sequence numbers are counted for the aggregator, which resyncs the books from REST snapshots.
+// Lives beside kalshi.rs, which the main binary compiles without the aggregator.
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tracing::{debug, error, info, warn, Level};
+
+use crate::clock::{self, SharedClock};
+use crate::error::FeedError;
+use crate::feed_aggregator::{FeedClient, PriceUpdate};
+use crate::feed_schema::{FeedSchemas, SchemaMode, SharedFeedSchemas};
+use crate::multi_region::{MultiRegionFeed, SharedFeedArbiter};
+use crate::config::{kalshi_ws_url, FeedsSection};
+use crate::kalshi::{ws_request, ws_request_to, KalshiApiClient, KalshiConfig, KalshiWsMessage, KalshiWsMsgBody, SubscribeCmd, SubscribeParams};
+use crate::provider_registry::ProviderId;
+use crate::types::{MarketType, Platform, PriceCents, SizeCents, TimestampNs};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+type KalshiSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A Kalshi market as the aggregator knows it
+#[derive(Debug, Clone)]
+pub struct KalshiFeedMarket {
+    pub ticker: String,
+    pub market_id: u16,
+    pub market_type: MarketType,
+}
+
+/// Resting bids per side (price cents -> contracts), rebuilt from snapshots and deltas
+#[derive(Debug, Default)]
+struct FeedBook {
+    yes: BTreeMap<i64, i64>,
+    no: BTreeMap<i64, i64>,
+    /// No snapshot yet; deltas are meaningless until one arrives
+    empty: bool,
+    /// Last quote emitted (yes ask, no ask, yes size, no size)
+    last: Option<(PriceCents, PriceCents, SizeCents, SizeCents)>,
+}
+
+impl FeedBook {
+    fn new() -> Self {
+        Self { empty: true, ..Self::default() }
+    }
+
+    fn apply_snapshot(&mut self, body: &KalshiWsMsgBody) {
+        self.apply_levels(&body.yes, &body.no);
+    }
+
+    /// Replace both sides with [price, contracts] levels (a socket or REST snapshot)
+    fn apply_levels(&mut self, yes: &Option<Vec<Vec<i64>>>, no: &Option<Vec<Vec<i64>>>) {
+        let levels = |side: &Option<Vec<Vec<i64>>>| -> BTreeMap<i64, i64> {
+            side.iter().flatten()
+                .filter(|l| l.len() >= 2 && l[1] > 0)
+                .map(|l| (l[0], l[1]))
+                .collect()
+        };
+        self.yes = levels(yes);
+        self.no = levels(no);
+        self.empty = false;
+    }
+
+    /// False when the delta can't be applied (no snapshot yet, or malformed)
+    fn apply_delta(&mut self, body: &KalshiWsMsgBody) -> bool {
+        let (Some(price), Some(delta), Some(side)) = (body.price, body.delta, body.side.as_deref()) else {
+            return false;
+        };
+        if self.empty {
+            return false;
+        }
+        let levels = match side {
+            "yes" => &mut self.yes,
+            "no" => &mut self.no,
+            _ => return false,
+        };
+        let qty = levels.entry(price).or_insert(0);
+        *qty += delta;
+        if *qty <= 0 {
+            levels.remove(&price);
+        }
+        true
+    }
+
+    /// Asks from the best bids: YES costs 100 - best NO bid and vice versa. Sizes follow
+    /// `kalshi::run_ws` (contracts at the bid, in cents)
+    fn quote(&self) -> (PriceCents, PriceCents, SizeCents, SizeCents) {
+        let ask = |bids: &BTreeMap<i64, i64>| {
+            bids.iter().next_back()
+                .map(|(&price, &qty)| ((100 - price) as PriceCents, (qty * price / 100) as SizeCents))
+                .unwrap_or((0, 0))
+        };
+        let (yes_ask, yes_size) = ask(&self.no);
+        let (no_ask, no_size) = ask(&self.yes);
+        (yes_ask, no_ask, yes_size, no_size)
+    }
+
+    /// The new quote, if it moved since the last one emitted
+    fn changed_quote(&mut self) -> Option<(PriceCents, PriceCents, SizeCents, SizeCents)> {
+        let quote = self.quote();
+        if self.last == Some(quote) {
+            return None;
+        }
+        self.last = Some(quote);
+        Some(quote)
+    }
+}
+
+/// Kalshi's RFC 3339 delta time as Unix nanoseconds
+fn provider_timestamp(body: &KalshiWsMsgBody) -> Option<TimestampNs> {
+    let ts = chrono::DateTime::parse_from_rfc3339(body.ts.as_deref()?).ok()?;
+    ts.timestamp_nanos_opt().and_then(|ns| u64::try_from(ns).ok())
+}
+
+/// Whether `seq` follows the last message of subscription `sid`; the first message of a
+/// subscription starts its count
+fn in_sequence(seqs: &mut HashMap<u64, u64>, sid: u64, seq: u64) -> bool {
+    match seqs.insert(sid, seq) {
+        Some(last) => seq == last + 1,
+        None => true,
+    }
+}
+
+enum Command {
+    Ping(oneshot::Sender<()>),
+    /// A REST orderbook to replace a market's book with
+    Snapshot { ticker: String, yes: Option<Vec<Vec<i64>>>, no: Option<Vec<Vec<i64>>> },
+    Close,
+}
+
+/// One connected socket: commands go to the task that owns it
+struct Session {
+    commands: mpsc::UnboundedSender<Command>,
+    task: JoinHandle<()>,
+}
+
+/// Books and routing for one socket
+struct SessionState {
+    markets: HashMap<String, KalshiFeedMarket>,
+    books: HashMap<String, FeedBook>,
+    schemas: SharedFeedSchemas,
+    clock: SharedClock,
+    updates: mpsc::UnboundedSender<PriceUpdate>,
+    /// Last sequence number per subscription
+    seqs: HashMap<u64, u64>,
+    /// Sequence gaps over every connection, read by `FeedClient::sequence_gaps`
+    gaps: Arc<AtomicU64>,
+}
+
+impl SessionState {
+    /// Apply one text frame; false once nobody reads the price stream
+    fn on_text(&mut self, text: &str) -> bool {
+        let value = match self.schemas.check(Platform::Kalshi, text) {
+            Ok(value) => value,
+            Err(e) => {
+                crate::hot_path!(Level::WARN, "[KALSHI-FEED] {}", e);
+                return true;
+            }
+        };
+        let message = match KalshiWsMessage::deserialize(&value) {
+            Ok(message) => message,
+            Err(e) => {
+                let message_type = value.get("type").and_then(|t| t.as_str()).unwrap_or("untyped");
+                let e = self.schemas.reject(Platform::Kalshi, message_type, e.to_string(), text);
+                crate::hot_path!(Level::WARN, "[KALSHI-FEED] {}", e);
+                return true;
+            }
+        };
+        if let (Some(sid), Some(seq)) = (message.sid, message.seq) {
+            if !in_sequence(&mut self.seqs, sid, seq) {
+                warn!("[KALSHI-FEED] Sequence gap on subscription {} at {}; books need a snapshot", sid, seq);
+                self.gaps.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        let Some(body) = &message.msg else { return true };
+        let Some(ticker) = body.market_ticker.as_deref() else { return true };
+        let (Some(market), Some(book)) = (self.markets.get(ticker), self.books.get_mut(ticker)) else {
+            return true;
+        };
+        let provider_timestamp = match message.msg_type.as_str() {
+            "orderbook_snapshot" => {
+                book.apply_snapshot(body);
+                None
+            }
+            "orderbook_delta" => {
+                if !book.apply_delta(body) {
+                    debug!("[KALSHI-FEED] Delta for {} skipped (no snapshot yet or malformed)", ticker);
+                    return true;
+                }
+                provider_timestamp(body)
+            }
+            _ => return true,
+        };
+        let Some(quote) = book.changed_quote() else { return true };
+        self.emit(market, quote, provider_timestamp)
+    }
+
+    /// Replace a market's book with a REST snapshot and emit its quote even if unchanged
+    /// (the aggregator held back the updates since the gap)
+    fn on_snapshot(&mut self, ticker: &str, yes: &Option<Vec<Vec<i64>>>, no: &Option<Vec<Vec<i64>>>) -> bool {
+        let (Some(market), Some(book)) = (self.markets.get(ticker), self.books.get_mut(ticker)) else {
+            return true;
+        };
+        book.apply_levels(yes, no);
+        book.last = None;
+        let Some(quote) = book.changed_quote() else { return true };
+        self.emit(market, quote, None)
+    }
+
+    fn emit(
+        &self,
+        market: &KalshiFeedMarket,
+        (yes_price, no_price, yes_size, no_size): (PriceCents, PriceCents, SizeCents, SizeCents),
+        provider_timestamp: Option<TimestampNs>,
+    ) -> bool {
+        self.updates.send(PriceUpdate {
+            market_id: market.market_id,
+            provider: Platform::Kalshi.into(),
+            market_type: market.market_type,
+            yes_price,
+            no_price,
+            yes_size,
+            no_size,
+            received: self.clock.now(),
+            provider_timestamp,
+            sequence: None,
+            features: None,
+        }).is_ok()
+    }
+}
+
+/// Own the socket until it closes, a close is requested or the price stream is dropped
+async fn run_session(socket: KalshiSocket, mut state: SessionState, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let (mut write, mut read) = socket.split();
+    let mut pings: VecDeque<oneshot::Sender<()>> = VecDeque::new();
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(Command::Ping(done)) => {
+                    if write.send(Message::Ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+                    pings.push_back(done);
+                }
+                Some(Command::Snapshot { ticker, yes, no }) => {
+                    if !state.on_snapshot(&ticker, &yes, &no) {
+                        info!("[KALSHI-FEED] Price stream dropped; closing");
+                        let _ = write.send(Message::Close(None)).await;
+                        break;
+                    }
+                }
+                Some(Command::Close) | None => {
+                    let _ = write.send(Message::Close(None)).await;
+                    break;
+                }
+            },
+            msg = read.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    if !state.on_text(&text) {
+                        info!("[KALSHI-FEED] Price stream dropped; closing");
+                        let _ = write.send(Message::Close(None)).await;
+                        break;
+                    }
+                }
+                Some(Ok(Message::Ping(data))) => {
+                    let _ = write.send(Message::Pong(data)).await;
+                }
+                Some(Ok(Message::Pong(_))) => {
+                    if let Some(done) = pings.pop_front() {
+                        let _ = done.send(());
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    info!("[KALSHI-FEED] Socket closed by Kalshi");
+                    break;
+                }
+                Some(Err(e)) => {
+                    error!("[KALSHI-FEED] WebSocket error: {}", e);
+                    break;
+                }
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Kalshi orderbook feed for the `FeedAggregator`
+pub struct KalshiFeedClient {
+    config: Arc<KalshiConfig>,
+    markets: Vec<KalshiFeedMarket>,
+    schemas: SharedFeedSchemas,
+    clock: SharedClock,
+    /// Socket URL in place of `kalshi_ws_url()`
+    ws_url: Option<String>,
+    /// REST client for snapshot resyncs (without one a gap means a reconnect)
+    api: Option<Arc<KalshiApiClient>>,
+    gaps: Arc<AtomicU64>,
+    update_tx: mpsc::UnboundedSender<PriceUpdate>,
+    update_rx: Option<mpsc::UnboundedReceiver<PriceUpdate>>,
+    session: Option<Session>,
+}
+
+impl KalshiFeedClient {
+    pub fn new(config: Arc<KalshiConfig>, markets: Vec<KalshiFeedMarket>) -> Self {
+        let (update_tx, update_rx) = mpsc::unbounded_channel();
+        Self {
+            config,
+            markets,
+            schemas: Arc::new(FeedSchemas::new(SchemaMode::Lenient)),
+            clock: clock::system(),
+            ws_url: None,
+            api: None,
+            gaps: Arc::new(AtomicU64::new(0)),
+            update_tx,
+            update_rx: Some(update_rx),
+            session: None,
+        }
+    }
+
+    /// Share the `[feeds]` schema checks (and their stats) with the other sockets
+    pub fn with_schemas(mut self, schemas: SharedFeedSchemas) -> Self {
+        self.schemas = schemas;
+        self
+    }
+
+    /// Stamp `received` from another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Connect to another endpoint of the API (a `[feeds] endpoints` region)
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = Some(ws_url.into());
+        self
+    }
+
+    /// Resync books from REST orderbooks after a sequence gap
+    pub fn with_api(mut self, api: Arc<KalshiApiClient>) -> Self {
+        self.api = Some(api);
+        self
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.session.as_ref().is_some_and(|s| !s.task.is_finished())
+    }
+
+    fn connect_error(&self, message: impl ToString) -> FeedError {
+        FeedError::Connect { provider: self.provider(), message: message.to_string() }
+    }
+}
+
+#[async_trait::async_trait]
+impl FeedClient for KalshiFeedClient {
+    fn provider(&self) -> ProviderId {
+        Platform::Kalshi.into()
+    }
+
+    async fn connect(&mut self) -> Result<(), FeedError> {
+        if self.is_connected() {
+            return Ok(());
+        }
+        let request = match &self.ws_url {
+            Some(url) => ws_request_to(&self.config, url),
+            None => ws_request(&self.config),
+        }.map_err(|e| self.connect_error(e))?;
+        let (mut socket, _) = tokio::time::timeout(CONNECT_TIMEOUT, connect_async(request)).await
+            .map_err(|_| FeedError::Timeout { provider: self.provider(), after: CONNECT_TIMEOUT })?
+            .map_err(|e| self.connect_error(e))?;
+
+        let subscribe = SubscribeCmd {
+            id: 1,
+            cmd: "subscribe",
+            params: SubscribeParams {
+                channels: vec!["orderbook_delta"],
+                market_tickers: self.markets.iter().map(|m| m.ticker.clone()).collect(),
+            },
+        };
+        let subscribe = serde_json::to_string(&subscribe).map_err(|e| self.connect_error(e))?;
+        socket.send(Message::Text(subscribe)).await.map_err(|e| self.connect_error(e))?;
+        info!("[KALSHI-FEED] Connected; subscribed to {} markets", self.markets.len());
+
+        // Books start over on every connect; Kalshi resends a snapshot per market
+        let state = SessionState {
+            markets: self.markets.iter().map(|m| (m.ticker.clone(), m.clone())).collect(),
+            books: self.markets.iter().map(|m| (m.ticker.clone(), FeedBook::new())).collect(),
+            schemas: self.schemas.clone(),
+            clock: self.clock.clone(),
+            updates: self.update_tx.clone(),
+            seqs: HashMap::new(),
+            gaps: self.gaps.clone(),
+        };
+        let (commands, command_rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run_session(socket, state, command_rx));
+        self.session = Some(Session { commands, task });
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), FeedError> {
+        if let Some(session) = self.session.take() {
+            let _ = session.commands.send(Command::Close);
+            if tokio::time::timeout(PING_TIMEOUT, session.task).await.is_err() {
+                debug!("[KALSHI-FEED] Session did not close in time");
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates of every connection; the first call takes the stream, later calls get a closed one
+    fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+        self.update_rx.take().unwrap_or_else(|| mpsc::unbounded_channel().1)
+    }
+
+    async fn ping(&mut self) -> Result<u64, FeedError> {
+        let provider = self.provider();
+        let session = self.session.as_ref().ok_or(FeedError::Disconnected { provider })?;
+        let (done, pong) = oneshot::channel();
+        let sent = Instant::now();
+        session.commands.send(Command::Ping(done)).map_err(|_| FeedError::Disconnected { provider })?;
+        match tokio::time::timeout(PING_TIMEOUT, pong).await {
+            Ok(Ok(())) => Ok(sent.elapsed().as_nanos() as u64),
+            Ok(Err(_)) => Err(FeedError::Disconnected { provider }),
+            Err(_) => Err(FeedError::Timeout { provider, after: PING_TIMEOUT }),
+        }
+    }
+
+    /// Fetch each market's orderbook over REST and hand it to the session
+    async fn resync(&mut self, markets: &[String]) -> Result<(), FeedError> {
+        let provider = self.provider();
+        let api = self.api.clone().ok_or(FeedError::Unsupported { provider })?;
+        let session = self.session.as_ref().ok_or(FeedError::Disconnected { provider })?;
+        let tickers = self.markets.iter()
+            .map(|m| &m.ticker)
+            .filter(|ticker| markets.is_empty() || markets.contains(*ticker));
+        let mut resynced = 0;
+        for ticker in tickers {
+            let book = api.get_orderbook(ticker).await
+                .map_err(|e| FeedError::Protocol { provider, message: format!("orderbook resync of {}: {}", ticker, e) })?;
+            let snapshot = Command::Snapshot { ticker: ticker.clone(), yes: book.yes, no: book.no };
+            session.commands.send(snapshot).map_err(|_| FeedError::Disconnected { provider })?;
+            resynced += 1;
+        }
+        info!("[KALSHI-FEED] Resynced {} books from REST", resynced);
+        Ok(())
+    }
+
+    fn sequence_gaps(&self) -> u64 {
+        self.gaps.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for KalshiFeedClient {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            session.task.abort();
+        }
+    }
+}
+
+/// Kalshi feed for the aggregator: the default socket alone, or with `[feeds] endpoints.kalshi`
+/// connected alongside it (the arbiter returned for their stats). With `api` every socket
+/// resyncs from REST after a sequence gap.
+pub fn kalshi_feed(
+    config: Arc<KalshiConfig>,
+    markets: Vec<KalshiFeedMarket>,
+    schemas: SharedFeedSchemas,
+    api: Option<Arc<KalshiApiClient>>,
+    feeds: &FeedsSection,
+) -> (Box<dyn FeedClient>, Option<SharedFeedArbiter>) {
+    let client = |url: Option<&str>| {
+        let mut client = KalshiFeedClient::new(config.clone(), markets.clone()).with_schemas(schemas.clone());
+        if let Some(api) = &api {
+            client = client.with_api(api.clone());
+        }
+        Box::new(match url {
+            Some(url) => client.with_ws_url(url),
+            None => client,
+        }) as Box<dyn FeedClient>
+    };
+    let Some(extra) = feeds.endpoints.get("kalshi").filter(|urls| !urls.is_empty()) else {
+        return (client(None), None);
+    };
+    let endpoints = std::iter::once((kalshi_ws_url().to_string(), client(None)))
+        .chain(extra.iter().map(|url| (url.clone(), client(Some(url)))))
+        .collect();
+    let feed = MultiRegionFeed::new(endpoints, Duration::from_millis(feeds.dedup_window_ms));
+    let arbiter = feed.arbiter();
+    (Box::new(feed), Some(arbiter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(json: &str) -> KalshiWsMsgBody {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_book_from_snapshot_and_deltas() {
+        let mut book = FeedBook::new();
+        // Deltas before the snapshot are dropped
+        assert!(!book.apply_delta(&body(r#"{"price":40,"delta":10,"side":"yes"}"#)));
+
+        book.apply_snapshot(&body(r#"{"yes":[[35,1000],[30,50],[20,0]],"no":[[60,1000]]}"#));
+        // YES ask 100 - 60, NO ask 100 - 35
+        assert_eq!(book.changed_quote(), Some((40, 65, 600, 350)));
+        assert_eq!(book.changed_quote(), None);
+
+        // A new best YES bid, then the old best emptied
+        assert!(book.apply_delta(&body(r#"{"price":38,"delta":200,"side":"yes"}"#)));
+        assert_eq!(book.quote(), (40, 62, 600, 76));
+        assert!(book.apply_delta(&body(r#"{"price":38,"delta":-200,"side":"yes"}"#)));
+        assert!(book.apply_delta(&body(r#"{"price":35,"delta":-1000,"side":"yes"}"#)));
+        assert_eq!(book.quote(), (40, 70, 600, 15));
+        assert!(!book.apply_delta(&body(r#"{"price":35,"delta":5,"side":"maybe"}"#)));
+    }
+
+    #[test]
+    fn test_sequence_gaps_per_subscription() {
+        let mut seqs = HashMap::new();
+        assert!(in_sequence(&mut seqs, 1, 1));
+        assert!(in_sequence(&mut seqs, 1, 2));
+        // Subscriptions count separately
+        assert!(in_sequence(&mut seqs, 2, 9));
+        assert!(!in_sequence(&mut seqs, 1, 4));
+        assert!(in_sequence(&mut seqs, 1, 5));
+
+        // A REST snapshot replaces the book and re-emits its quote
+        let mut book = FeedBook::new();
+        book.apply_snapshot(&body(r#"{"yes":[[35,1000]],"no":[[60,1000]]}"#));
+        assert!(book.changed_quote().is_some());
+        book.apply_levels(&Some(vec![vec![35, 1000]]), &Some(vec![vec![60, 1000]]));
+        book.last = None;
+        assert_eq!(book.changed_quote(), Some((40, 65, 600, 350)));
+    }
+
+    #[test]
+    fn test_delta_provider_timestamp() {
+        let delta = body(r#"{"price":40,"delta":1,"side":"no","ts":"2026-10-18T12:00:00.250Z"}"#);
+        assert_eq!(provider_timestamp(&delta), Some(1_792_324_800_250_000_000));
+        assert_eq!(provider_timestamp(&body(r#"{"ts":"yesterday"}"#)), None);
+        assert_eq!(provider_timestamp(&body("{}")), None);
+    }
+}
@@ -11,9 +11,14 @@ use tokio::sync::RwLock;
 use rustc_hash::FxHashMap;
 
 use crate::types::*;
+use crate::edge_thresholds::{SharedEdgeThresholds, DEFAULT_MIN_EDGE_CENTS};
+use crate::event_bus::{Event, SharedEventBus};
+use crate::market_metadata::{MarketMetadataRegistry, SharedMarketMetadata};
+use crate::microstructure::BookFeatures;
+use crate::strategy;
 
 /// Market tier classification for half-life modeling
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MarketTier {
     /// Tier 1: Core Markets (200-400ms half-life)
     Tier1,
@@ -36,6 +41,11 @@ impl MarketTier {
         }
     }
 
+    /// Fraction of an edge left `age_ns` after it opened: 0.5^(age / half-life)
+    pub fn edge_remaining(&self, age_ns: u64) -> f64 {
+        0.5f64.powf(age_ns as f64 / 1_000_000.0 / self.half_life_ms())
+    }
+
     /// Get the update constraint description
     pub fn constraint(&self) -> &'static str {
         match self {
@@ -55,8 +65,9 @@ pub struct PriceObservation {
     pub market_type: MarketType,
     pub price: PriceCents,
     pub size: SizeCents,
-    pub timestamp_ns: TimestampNs,
+    pub timestamp_ns: TimestampNs, // Monotonic receive time (process clock)
     pub tier: MarketTier,
+    pub features: Option<BookFeatures>, // Book imbalance / microprice / trade flow, when the feed has depth
 }
 
 /// Latency disparity signal for arbitrage detection
@@ -68,6 +79,27 @@ pub struct LatencySignal {
     pub expected_convergence_ns: u64,
     pub pattern_id: Option<u16>, // #70-#89 pattern identifier
     pub confidence: f64, // 0.0-1.0
+    /// Strategy the signal belongs to (see `strategy::for_pattern`)
+    pub strategy_id: String,
+}
+
+impl LatencySignal {
+    /// Time since the fast market moved (process monotonic clock)
+    pub fn age_ns(&self, now_ns: TimestampNs) -> u64 {
+        now_ns.saturating_sub(self.fast_market.timestamp_ns)
+    }
+}
+
+/// Mean edge remaining across a market's signals at `now_ns`, decayed at that market's tier
+/// half-life (1.0 when there are none)
+pub fn mean_edge_remaining<'a>(
+    tier: MarketTier,
+    signals: impl IntoIterator<Item = &'a LatencySignal>,
+    now_ns: TimestampNs,
+) -> f64 {
+    let (sum, count) = signals.into_iter()
+        .fold((0.0, 0usize), |(sum, count), s| (sum + tier.edge_remaining(s.age_ns(now_ns)), count + 1));
+    if count == 0 { 1.0 } else { sum / count as f64 }
 }
 
 /// Propagation half-life state for a market pair
@@ -140,6 +172,11 @@ impl ConvergenceKalman {
         }
     }
 
+    /// Current state covariance
+    pub fn covariance(&self) -> [[f64; 3]; 3] {
+        self.covariance
+    }
+
     /// Get predicted convergence time
     pub fn predicted_convergence_time(&self, threshold: f64) -> Option<f64> {
         if self.state[0].abs() < threshold {
@@ -187,6 +224,14 @@ pub struct LatencyArbitrageEngine {
     pub signals: Vec<LatencySignal>,
     /// Market tier mappings
     pub market_tiers: FxHashMap<u16, MarketTier>,
+    /// Latest microstructure features per market-provider pair
+    pub book_features: FxHashMap<(u16, Platform), BookFeatures>,
+    /// Bus new signals are published on (optional)
+    pub event_bus: Option<SharedEventBus>,
+    /// Per tier/venue minimum disparity from TCA (None = flat 2¢)
+    pub edge_thresholds: Option<SharedEdgeThresholds>,
+    /// Market type / event / tier per market, shared with the feed aggregator
+    pub market_metadata: SharedMarketMetadata,
 }
 
 impl LatencyArbitrageEngine {
@@ -197,9 +242,41 @@ impl LatencyArbitrageEngine {
             kalman_filters: FxHashMap::default(),
             signals: Vec::new(),
             market_tiers: FxHashMap::default(),
+            book_features: FxHashMap::default(),
+            event_bus: None,
+            edge_thresholds: None,
+            market_metadata: Arc::new(MarketMetadataRegistry::default()),
         }
     }
 
+    /// Publish detected signals as `Event::Signal`
+    pub fn with_event_bus(mut self, bus: SharedEventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Filter disparities by the slow venue's TCA-derived minimum edge instead of a flat 2¢
+    pub fn with_edge_thresholds(mut self, thresholds: SharedEdgeThresholds) -> Self {
+        self.edge_thresholds = Some(thresholds);
+        self
+    }
+
+    /// Share market metadata with the feed aggregator (`FeedAggregator::with_market_metadata`)
+    pub fn with_market_metadata(mut self, metadata: SharedMarketMetadata) -> Self {
+        self.market_metadata = metadata;
+        self
+    }
+
+    /// Tier to observe a market quoted as `market_type` at: pinned, else classified from its metadata
+    pub fn market_tier(&self, market_id: u16, market_type: MarketType) -> MarketTier {
+        self.market_metadata.tier_for(market_id, market_type)
+    }
+
+    /// Minimum disparity worth signalling when trading `provider` in a `tier` market
+    fn min_edge_cents(&self, tier: MarketTier, provider: Platform) -> i16 {
+        self.edge_thresholds.as_ref().map_or(DEFAULT_MIN_EDGE_CENTS, |t| t.min_edge_cents(tier, provider))
+    }
+
     /// Add price observation from a market feed
     pub fn add_price_observation(&mut self, obs: PriceObservation) {
         let key = (obs.market_id, obs.provider);
@@ -211,8 +288,13 @@ impl LatencyArbitrageEngine {
         // TODO: Extend for non-binary markets
         orderbook.update_yes(obs.price, obs.size, obs.timestamp_ns);
 
-        // Update tier mapping
+        // Update tier mapping; feeds' market types fill in markets never registered
         self.market_tiers.insert(obs.market_id, obs.tier);
+        self.market_metadata.ensure(obs.market_id, obs.market_type);
+
+        if let Some(features) = obs.features {
+            self.book_features.insert(key, features);
+        }
 
         // Trigger correlation analysis
         self.analyze_correlations(obs.market_id, obs.timestamp_ns);
@@ -236,6 +318,10 @@ impl LatencyArbitrageEngine {
                     continue; // Skip same provider
                 }
 
+                if self.market_metadata.same_event(market_a, market_b) == Some(false) {
+                    continue; // Different events don't propagate into each other
+                }
+
                 let tier_a = match self.market_tiers.get(&market_a) {
                     Some(t) => *t,
                     None => continue,
@@ -252,36 +338,43 @@ impl LatencyArbitrageEngine {
                 if price_a == 0 || price_b == 0 {
                     continue;
                 }
+                let features_a = self.book_features.get(&(market_a, provider_a)).copied();
+                let features_b = self.book_features.get(&(market_b, provider_b)).copied();
 
                 // Calculate latency disparity
                 let time_diff_ns = ts_a.abs_diff(ts_b);
                 let price_diff_cents = price_a as i16 - price_b as i16;
 
-                // Only consider significant disparities
-                if price_diff_cents.abs() < 2 || time_diff_ns < 50_000_000 { // 50ms minimum
+                if time_diff_ns < 50_000_000 { // 50ms minimum
                     continue;
                 }
 
+                // Market types as registered (or first quoted); unknown markets are treated as moneylines
+                let type_a = self.market_metadata.market_type(market_a).unwrap_or(MarketType::Moneyline);
+                let type_b = self.market_metadata.market_type(market_b).unwrap_or(MarketType::Moneyline);
+
                 // Determine which is faster (earlier timestamp)
                 let (fast_obs, slow_obs) = if ts_a < ts_b {
                     (
                         PriceObservation {
                             market_id: market_a,
                             provider: provider_a,
-                            market_type: MarketType::Moneyline, // TODO: Get actual type
+                            market_type: type_a,
                             price: price_a,
                             size: size_a,
                             timestamp_ns: ts_a,
                             tier: tier_a,
+                            features: features_a,
                         },
                         PriceObservation {
                             market_id: market_b,
                             provider: provider_b,
-                            market_type: MarketType::Moneyline,
+                            market_type: type_b,
                             price: price_b,
                             size: size_b,
                             timestamp_ns: ts_b,
                             tier: tier_b,
+                            features: features_b,
                         },
                     )
                 } else {
@@ -289,24 +382,31 @@ impl LatencyArbitrageEngine {
                         PriceObservation {
                             market_id: market_b,
                             provider: provider_b,
-                            market_type: MarketType::Moneyline,
+                            market_type: type_b,
                             price: price_b,
                             size: size_b,
                             timestamp_ns: ts_b,
                             tier: tier_b,
+                            features: features_b,
                         },
                         PriceObservation {
                             market_id: market_a,
                             provider: provider_a,
-                            market_type: MarketType::Moneyline,
+                            market_type: type_a,
                             price: price_a,
                             size: size_a,
                             timestamp_ns: ts_a,
                             tier: tier_a,
+                            features: features_a,
                         },
                     )
                 };
 
+                // Only consider disparities that clear costs on the venue we'd trade (the slow one)
+                if price_diff_cents.abs() < self.min_edge_cents(slow_obs.tier, slow_obs.provider) {
+                    continue;
+                }
+
                 // Identify arbitrage pattern
                 let pattern_id = self.identify_arbitrage_pattern(&fast_obs, &slow_obs, price_diff_cents, time_diff_ns);
 
@@ -318,10 +418,14 @@ impl LatencyArbitrageEngine {
                         expected_convergence_ns: self.predict_convergence_time(&fast_obs, &slow_obs, timestamp_ns),
                         pattern_id: Some(pattern),
                         confidence: self.calculate_pattern_confidence(pattern, price_diff_cents, time_diff_ns),
+                        strategy_id: strategy::for_pattern(Some(pattern)),
                     };
 
                     // Only add if convergence is predicted soon enough
                     if signal.expected_convergence_ns < 5_000_000_000 { // 5 seconds
+                        if let Some(bus) = &self.event_bus {
+                            bus.publish(Event::Signal(signal.clone()));
+                        }
                         self.signals.push(signal);
                     }
                 }
@@ -411,3 +515,54 @@ impl Default for LatencyArbitrageEngine {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(fast_ns: TimestampNs) -> LatencySignal {
+        let obs = |market_id, timestamp_ns| PriceObservation {
+            market_id,
+            provider: Platform::Kalshi,
+            market_type: MarketType::Moneyline,
+            price: 50,
+            size: 100,
+            timestamp_ns,
+            tier: MarketTier::Tier1,
+            features: None,
+        };
+        LatencySignal {
+            fast_market: obs(1, fast_ns),
+            slow_market: obs(2, fast_ns + 50_000_000),
+            disparity_cents: -3,
+            expected_convergence_ns: 0,
+            pattern_id: None,
+            confidence: 0.5,
+            strategy_id: strategy::for_pattern(None),
+        }
+    }
+
+    #[test]
+    fn test_edge_halves_each_half_life() {
+        let tier = MarketTier::Tier1; // 300ms
+        assert_eq!(tier.edge_remaining(0), 1.0);
+        assert!((tier.edge_remaining(300_000_000) - 0.5).abs() < 1e-12);
+        assert!((tier.edge_remaining(900_000_000) - 0.125).abs() < 1e-12);
+        // Slower tiers keep more of the same-aged edge
+        assert!(MarketTier::Tier4.edge_remaining(900_000_000) > 0.8);
+    }
+
+    #[test]
+    fn test_mean_edge_remaining_per_market() {
+        let now = 10_000_000_000;
+        // Fresh and one-half-life-old signals; negative disparity no longer flips the sign
+        let signals = [signal(now), signal(now - 300_000_000)];
+        assert!((mean_edge_remaining(MarketTier::Tier1, &signals, now) - 0.75).abs() < 1e-12);
+        // Same signals on a Tier 2 market (950ms half-life)
+        let tier2 = mean_edge_remaining(MarketTier::Tier2, &signals, now);
+        assert!((tier2 - (1.0 + 0.5f64.powf(300.0 / 950.0)) / 2.0).abs() < 1e-12);
+        // Observations stamped after `now` count as fresh
+        assert_eq!(mean_edge_remaining(MarketTier::Tier1, &[signal(now + 1)], now), 1.0);
+        assert_eq!(mean_edge_remaining(MarketTier::Tier1, &[], now), 1.0);
+    }
+}
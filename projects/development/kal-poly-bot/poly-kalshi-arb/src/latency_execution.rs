@@ -5,14 +5,24 @@
 //! execution scheduling based on convergence half-life models.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::{RwLock, mpsc};
-use tokio::time::{Duration, Instant, timeout};
+use tokio::time::{Duration, timeout};
 use tracing::{info, warn, error, debug};
+use serde::{Deserialize, Serialize};
 
 use crate::types::*;
 use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal, PriceObservation};
 use crate::feed_aggregator::FeedAggregator;
+use crate::error::ExecutionError;
+use crate::clock::{self, SharedClock};
+use crate::decision_latency::SharedDecisionLatency;
+use crate::event_bus::{Event, SharedEventBus};
+use crate::maintenance::SharedMaintenanceCalendar;
+use crate::market_cooldown::{SharedCooldownManager, TradeOutcome};
+use crate::paper_fills::{PaperFillConfig, PaperFillSimulator, SharedPaperFills};
+use crate::operator_review::SharedReviewQueue;
+use crate::shadow_mode::SharedShadowBook;
 
 /// Latency arbitrage execution request
 #[derive(Debug, Clone)]
@@ -23,10 +33,17 @@ pub struct LatencyExecutionRequest {
     pub max_edge_decay_cents: PriceCents,
 }
 
+impl LatencyExecutionRequest {
+    /// Venues of the fast and slow legs
+    pub fn providers(&self) -> [Platform; 2] {
+        [self.signal.fast_market.provider, self.signal.slow_market.provider]
+    }
+}
+
 /// Execution result for latency arbitrage
 #[derive(Debug, Clone)]
 pub struct LatencyExecutionResult {
-    pub signal_id: u64, // Unique signal identifier
+    pub signal_id: SignalId,
     pub success: bool,
     pub fast_fill_price: Option<PriceCents>,
     pub slow_fill_price: Option<PriceCents>,
@@ -34,8 +51,38 @@ pub struct LatencyExecutionResult {
     pub edge_captured_cents: i16,
     pub edge_decay_cents: i16,
     pub error_message: Option<String>,
+    /// Strategy of the executed signal
+    pub strategy_id: String,
+    /// Venues of the fast and slow legs (None when the result can't be attributed)
+    pub providers: Option<[Platform; 2]>,
+}
+
+impl LatencyExecutionResult {
+    /// Nothing filled; the whole disparity is counted as decayed
+    pub fn failed(signal_id: SignalId, request: &LatencyExecutionRequest, execution_time_ns: TimestampNs, error: String) -> Self {
+        Self {
+            signal_id,
+            success: false,
+            fast_fill_price: None,
+            slow_fill_price: None,
+            execution_time_ns,
+            edge_captured_cents: 0,
+            edge_decay_cents: request.signal.disparity_cents.unsigned_abs().min(i16::MAX as u16) as i16,
+            error_message: Some(error),
+            strategy_id: request.signal.strategy_id.clone(),
+            providers: Some(request.providers()),
+        }
+    }
 }
 
+/// Places a scheduled signal's orders on its venues (live trading)
+#[async_trait::async_trait]
+pub trait LatencyOrderExecutor: Send + Sync {
+    async fn execute(&self, signal_id: SignalId, request: &LatencyExecutionRequest) -> Result<LatencyExecutionResult, ExecutionError>;
+}
+
+pub type SharedLatencyExecutor = Arc<dyn LatencyOrderExecutor>;
+
 /// Fill probability estimator
 #[derive(Debug)]
 struct FillProbabilityEstimator {
@@ -73,7 +120,7 @@ impl FillProbabilityEstimator {
 
 /// Edge decay model based on half-life
 #[derive(Debug)]
-struct EdgeDecayModel {
+pub struct EdgeDecayModel {
     /// Half-life in nanoseconds
     half_life_ns: u64,
     /// Initial edge in cents
@@ -81,7 +128,7 @@ struct EdgeDecayModel {
 }
 
 impl EdgeDecayModel {
-    fn new(half_life_ms: f64, initial_edge_cents: i16) -> Self {
+    pub fn new(half_life_ms: f64, initial_edge_cents: i16) -> Self {
         Self {
             half_life_ns: (half_life_ms * 1_000_000.0) as u64,
             initial_edge_cents,
@@ -89,7 +136,7 @@ impl EdgeDecayModel {
     }
 
     /// Calculate remaining edge after time delay
-    fn remaining_edge(&self, delay_ns: u64) -> i16 {
+    pub fn remaining_edge(&self, delay_ns: u64) -> i16 {
         if delay_ns == 0 {
             return self.initial_edge_cents;
         }
@@ -128,6 +175,15 @@ impl EdgeDecayModel {
     }
 }
 
+/// Unfilled latency arbs count as suspected limiting; filled ones win or lose by captured edge
+fn cooldown_outcome(result: &LatencyExecutionResult) -> TradeOutcome {
+    match result.success {
+        true if result.edge_captured_cents < 0 => TradeOutcome::Loss,
+        true => TradeOutcome::Win,
+        false => TradeOutcome::Limited,
+    }
+}
+
 /// Latency execution engine
 pub struct LatencyExecutionEngine {
     /// Latency arbitrage engine for signal generation
@@ -136,14 +192,31 @@ pub struct LatencyExecutionEngine {
     feed_aggregator: Arc<RwLock<FeedAggregator>>,
     /// Fill probability estimator
     fill_estimator: FillProbabilityEstimator,
-    /// Active executions
-    active_executions: HashMap<u64, LatencyExecutionRequest>,
+    /// Executions scheduled and not yet reported. Whoever removes a signal - its fill task, or
+    /// `monitor_executions` once the deadline passes - owns its one terminal result
+    active_executions: Arc<Mutex<HashMap<SignalId, LatencyExecutionRequest>>>,
     /// Execution result channel
     result_tx: mpsc::UnboundedSender<LatencyExecutionResult>,
     /// Signal ID counter
-    next_signal_id: u64,
-    /// Clock for timing
-    clock: Instant,
+    next_signal_id: SignalId,
+    /// Monotonic clock shared with the feeds, so signal stamps and deadlines compare
+    clock: SharedClock,
+    /// Bus orders and results are published on (optional)
+    event_bus: Option<SharedEventBus>,
+    /// Benched markets are skipped and results feed their streaks (optional)
+    cooldowns: Option<SharedCooldownManager>,
+    /// Signals on venues near or in scheduled maintenance are skipped (optional)
+    maintenance: Option<SharedMaintenanceCalendar>,
+    /// Places orders on the venues (optional; takes precedence over paper fills)
+    executor: Option<SharedLatencyExecutor>,
+    /// Match orders against the recorded books (optional)
+    paper_fills: Option<SharedPaperFills>,
+    /// Detection-to-order latency per pattern at the fast market's tier (optional)
+    decision_latency: Option<SharedDecisionLatency>,
+    /// Dark-launched patterns and the paper fills their signals go to instead (optional)
+    shadow: Option<(SharedShadowBook, SharedPaperFills)>,
+    /// Low-confidence signals held for an operator, and the paper fills scoring withheld ones (optional)
+    review: Option<(SharedReviewQueue, SharedPaperFills)>,
 }
 
 impl LatencyExecutionEngine {
@@ -154,51 +227,240 @@ impl LatencyExecutionEngine {
     ) -> (Self, mpsc::UnboundedReceiver<LatencyExecutionResult>) {
         let (result_tx, result_rx) = mpsc::unbounded_channel();
 
-        Self {
+        let engine = Self {
             latency_engine,
             feed_aggregator,
             fill_estimator: FillProbabilityEstimator::new(),
-            active_executions: HashMap::new(),
+            active_executions: Arc::new(Mutex::new(HashMap::new())),
             result_tx,
-            next_signal_id: 0,
-            clock: Instant::now(),
-        }
+            next_signal_id: SignalId::default(),
+            clock: clock::system(),
+            event_bus: None,
+            cooldowns: None,
+            maintenance: None,
+            executor: None,
+            paper_fills: None,
+            decision_latency: None,
+            shadow: None,
+            review: None,
+        };
+
+        (engine, result_rx)
+    }
+
+    /// Publish scheduled executions (`Event::Order`) and results (`Event::Fill`)
+    pub fn with_event_bus(mut self, bus: SharedEventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Skip signals on markets that are cooling down and record each result's outcome
+    pub fn with_cooldowns(mut self, cooldowns: SharedCooldownManager) -> Self {
+        self.cooldowns = Some(cooldowns);
+        self
+    }
+
+    /// Skip signals whose fast or slow venue is about to go down for (or in) maintenance
+    pub fn with_maintenance(mut self, maintenance: SharedMaintenanceCalendar) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Trade live: each scheduled signal's orders go to `executor`
+    pub fn with_executor(mut self, executor: SharedLatencyExecutor) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// Paper trade: fill orders from the recorded books with modeled latency and queue priority
+    pub fn with_paper_fills(mut self, paper_fills: SharedPaperFills) -> Self {
+        self.paper_fills = Some(paper_fills);
+        self
+    }
+
+    /// Record the time from each signal's fast-market move to its order being scheduled
+    pub fn with_decision_latency(mut self, decision_latency: SharedDecisionLatency) -> Self {
+        self.decision_latency = Some(decision_latency);
+        self
+    }
+
+    /// Send signals of shadow patterns to the paper-fill simulator only, scored in `shadow` and
+    /// never published as orders or fills
+    pub fn with_shadow_book(mut self, shadow: SharedShadowBook) -> Self {
+        let fills = self.paper_fills.clone().unwrap_or_else(|| {
+            Arc::new(PaperFillSimulator::new(PaperFillConfig::default(), self.clock.clone()))
+        });
+        self.shadow = Some((shadow, fills));
+        self
+    }
+
+    /// Hold low-confidence signals for operator review; approved (and, if configured, expired)
+    /// ones execute, withheld ones are paper-filled so `review` can score the automation's call
+    pub fn with_review_queue(mut self, review: SharedReviewQueue) -> Self {
+        let fills = self.paper_fills.clone().unwrap_or_else(|| {
+            Arc::new(PaperFillSimulator::new(PaperFillConfig::default(), self.clock.clone()))
+        });
+        self.review = Some((review, fills));
+        self
+    }
+
+    /// Drive timing from another clock (tests, backtest replay)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
     }
 
     /// Process latency arbitrage signals and execute optimal trades
-    pub async fn process_signals(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Get current signals from latency engine
+    ///
+    /// Takes the engine's pending signals, so each one is executed at most once.
+    pub async fn process_signals(&mut self) -> Result<(), ExecutionError> {
         let signals = {
-            let engine = self.latency_engine.read().await;
-            engine.get_signals().to_vec()
+            let mut engine = self.latency_engine.write().await;
+            std::mem::take(&mut engine.signals)
         };
 
+        // Reviewed signals first: they have waited out (part of) a window already
+        if let Some((review, fills)) = self.review.clone() {
+            for (signal_id, request, execute) in review.take_released() {
+                if execute {
+                    self.execute(signal_id, request);
+                } else {
+                    self.execute_withheld(signal_id, request, review.clone(), fills.clone());
+                }
+            }
+        }
+
         for signal in signals {
+            if let Some(cooldowns) = &self.cooldowns {
+                if let Err(rejection) = cooldowns.check(&signal.fast_market.market_id.to_string()) {
+                    debug!("Signal skipped: {}", rejection);
+                    continue;
+                }
+            }
+            if let Some(maintenance) = &self.maintenance {
+                let venues = [signal.fast_market.provider.to_string(), signal.slow_market.provider.to_string()];
+                if let Err(rejection) = maintenance.check(&[venues[0].as_str(), venues[1].as_str()]) {
+                    debug!("Signal skipped: {}", rejection);
+                    continue;
+                }
+            }
             if let Some(request) = self.optimize_execution_request(signal).await {
-                let signal_id = self.next_signal_id;
-                self.next_signal_id += 1;
-
-                self.active_executions.insert(signal_id, request.clone());
-
-                // Execute the arbitrage
-                tokio::spawn(async move {
-                    // TODO: Implement actual execution logic
-                    // For now, simulate execution
-                    Self::simulate_execution(signal_id, request).await;
-                });
+                let signal_id = self.next_signal_id.take_next();
+                if let Some((shadow, fills)) = self.shadow.as_ref().filter(|(shadow, _)| shadow.is_shadowed(request.signal.pattern_id)) {
+                    self.execute_shadow(signal_id, request, shadow.clone(), fills.clone());
+                    continue;
+                }
+                if let Some((review, _)) = self.review.as_ref().filter(|(review, _)| review.should_hold(&request.signal)) {
+                    review.hold(signal_id, request);
+                    continue;
+                }
+                self.execute(signal_id, request);
             }
         }
 
         Ok(())
     }
 
+    /// Schedule a signal's execution: published as an order, filled in the background by the
+    /// live executor or the paper-fill simulator, and its result published and sent on the
+    /// result channel. With neither configured the signal is reported unexecuted.
+    fn execute(&mut self, signal_id: SignalId, request: LatencyExecutionRequest) {
+        self.active_executions.lock().unwrap().insert(signal_id, request.clone());
+        if let Some(decision_latency) = &self.decision_latency {
+            let signal = &request.signal;
+            let pattern = signal.pattern_id.map_or_else(|| signal.strategy_id.clone(), |id| id.to_string());
+            let tier = signal.fast_market.tier as u8 + 1;
+            decision_latency.record(&pattern, tier, signal.age_ns(self.clock.mono_ns().0));
+        }
+        if let Some(bus) = &self.event_bus {
+            bus.publish(Event::Order { signal_id, request: request.clone() });
+        }
+
+        // Execute the arbitrage
+        let event_bus = self.event_bus.clone();
+        let cooldowns = self.cooldowns.clone();
+        let executor = self.executor.clone();
+        let paper_fills = self.paper_fills.clone();
+        let review = self.review.as_ref().map(|(review, _)| review.clone());
+        let latency_engine = self.latency_engine.clone();
+        let active_executions = self.active_executions.clone();
+        let result_tx = self.result_tx.clone();
+        let clock = self.clock.clone();
+        tokio::spawn(async move {
+            let market = request.signal.fast_market.market_id.to_string();
+            let (result, executed) = match (executor, paper_fills) {
+                (Some(executor), _) => match executor.execute(signal_id, &request).await {
+                    Ok(result) => (result, true),
+                    Err(e) => {
+                        error!("Latency arb signal {} failed: {}", signal_id, e);
+                        (LatencyExecutionResult::failed(signal_id, &request, clock.mono_ns().0, e.to_string()), true)
+                    }
+                },
+                (None, Some(paper_fills)) => (paper_fills.execute(signal_id, &request, &latency_engine).await, true),
+                (None, None) => {
+                    warn!("Latency arb signal {} not executed: no executor or paper fills configured", signal_id);
+                    (LatencyExecutionResult::failed(signal_id, &request, clock.mono_ns().0, "no executor configured".to_string()), false)
+                }
+            };
+            // `monitor_executions` already reported a signal past its deadline
+            if active_executions.lock().unwrap().remove(&signal_id).is_none() {
+                warn!("Latency arb signal {} finished after its deadline (success={}, edge_captured={}¢); already reported as expired",
+                      signal_id, result.success, result.edge_captured_cents);
+                return;
+            }
+            if !executed {
+                let _ = result_tx.send(result);
+                return;
+            }
+            info!("Executed latency arb signal {}: success={}, edge_captured={}¢",
+                  signal_id, result.success, result.edge_captured_cents);
+            if let Some(bus) = event_bus {
+                bus.publish(Event::Fill(result.clone()));
+            }
+            if let Some(cooldowns) = cooldowns {
+                cooldowns.record(&market, cooldown_outcome(&result));
+            }
+            if let Some(review) = review {
+                review.record_outcome(&result);
+            }
+            let _ = result_tx.send(result);
+        });
+    }
+
+    /// Paper-fill a signal review withheld, scoring what automation would have captured; like
+    /// shadow signals, nothing reaches the bus or the cooldowns
+    fn execute_withheld(&self, signal_id: SignalId, request: LatencyExecutionRequest, review: SharedReviewQueue, fills: SharedPaperFills) {
+        let latency_engine = self.latency_engine.clone();
+        tokio::spawn(async move {
+            let result = fills.execute(signal_id, &request, &latency_engine).await;
+            debug!("Withheld signal {}: success={}, edge_captured={}¢",
+                   signal_id, result.success, result.edge_captured_cents);
+            review.record_outcome(&result);
+        });
+    }
+
+    /// Paper-fill a shadow pattern's signal and score it; nothing reaches the bus or the cooldowns
+    fn execute_shadow(&self, signal_id: SignalId, request: LatencyExecutionRequest, shadow: SharedShadowBook, fills: SharedPaperFills) {
+        let Some(pattern_id) = request.signal.pattern_id else { return };
+        let latency_engine = self.latency_engine.clone();
+        let order_size = fills.order_size();
+        tokio::spawn(async move {
+            let result = fills.execute(signal_id, &request, &latency_engine).await;
+            debug!("Shadow signal {} of pattern {}: success={}, edge_captured={}¢",
+                   signal_id, pattern_id, result.success, result.edge_captured_cents);
+            // Edge (cents per $1 contract) over each leg's notional
+            let pnl = result.success.then(|| f64::from(result.edge_captured_cents) * order_size as f64 / 10_000.0);
+            shadow.record(pattern_id, pnl);
+        });
+    }
+
     /// Optimize execution timing for a latency signal
     async fn optimize_execution_request(&self, signal: LatencySignal) -> Option<LatencyExecutionRequest> {
-        let current_time = self.clock.elapsed().as_nanos() as u64;
+        let current_time = self.clock.mono_ns().0;
 
         // Create edge decay model
         let avg_half_life_ms = (signal.fast_market.tier.half_life_ms() + signal.slow_market.tier.half_life_ms()) / 2.0;
-        let decay_model = EdgeDecayModel::new(avg_half_life_ms, signal.disparity_cents.abs());
+        let decay_model = EdgeDecayModel::new(avg_half_life_ms, signal.disparity_cents.unsigned_abs().min(i16::MAX as u16) as i16);
 
         // Estimate optimal execution time
         let optimal_delay = decay_model.optimal_execution_time(
@@ -232,67 +494,39 @@ impl LatencyExecutionEngine {
         }
 
         Some(LatencyExecutionRequest {
+            max_edge_decay_cents: (signal.disparity_cents.unsigned_abs() / 2).max(1),
             signal,
             execution_deadline_ns: deadline,
             fill_probability_threshold: 0.5,
-            max_edge_decay_cents: (signal.disparity_cents.abs() / 2).max(1),
         })
     }
 
-    /// Simulate execution (replace with real implementation)
-    async fn simulate_execution(signal_id: u64, request: LatencyExecutionRequest) {
-        // Simulate network/execution delay
-        let execution_delay = Duration::from_millis(50 + (rand::random::<u64>() % 100));
-        tokio::time::sleep(execution_delay).await;
+    /// Monitor and cancel stale executions
+    pub async fn monitor_executions(&mut self) {
+        let current_time = self.clock.mono_ns().0;
 
-        // Simulate execution result
-        let success = rand::random::<f64>() < 0.85; // 85% success rate
+        let mut stale = Vec::new();
+        self.active_executions.lock().unwrap().retain(|&signal_id, request| {
+            let overdue = current_time > request.execution_deadline_ns;
+            if overdue {
+                stale.push((signal_id, request.clone()));
+            }
+            !overdue
+        });
 
-        let result = LatencyExecutionResult {
-            signal_id,
-            success,
-            fast_fill_price: if success { Some(request.signal.fast_market.price) } else { None },
-            slow_fill_price: if success { Some(request.signal.slow_market.price) } else { None },
-            execution_time_ns: request.execution_deadline_ns,
-            edge_captured_cents: if success { request.signal.disparity_cents } else { 0 },
-            edge_decay_cents: request.signal.disparity_cents.abs() / 4, // Simulate some decay
-            error_message: if !success { Some("Simulated execution failure".to_string()) } else { None },
-        };
+        for (signal_id, request) in stale {
+            // Deadline passed, cancel execution
+            warn!("Cancelling stale execution for signal {}", signal_id);
 
-        // In real implementation, send to result channel
-        info!("Executed latency arb signal {}: success={}, edge_captured={}¢",
-              signal_id, success, result.edge_captured_cents);
-    }
+            let result = LatencyExecutionResult::failed(signal_id, &request, current_time, "Execution deadline exceeded".to_string());
 
-    /// Monitor and cancel stale executions
-    pub async fn monitor_executions(&mut self) {
-        let current_time = self.clock.elapsed().as_nanos() as u64;
-
-        let mut to_remove = Vec::new();
-
-        for (signal_id, request) in &self.active_executions {
-            if current_time > request.execution_deadline_ns {
-                // Deadline passed, cancel execution
-                warn!("Cancelling stale execution for signal {}", signal_id);
-
-                let result = LatencyExecutionResult {
-                    signal_id: *signal_id,
-                    success: false,
-                    fast_fill_price: None,
-                    slow_fill_price: None,
-                    execution_time_ns: current_time,
-                    edge_captured_cents: 0,
-                    edge_decay_cents: request.signal.disparity_cents.abs(),
-                    error_message: Some("Execution deadline exceeded".to_string()),
-                };
-
-                let _ = self.result_tx.send(result);
-                to_remove.push(*signal_id);
+            if let Some(bus) = &self.event_bus {
+                bus.publish(Event::Fill(result.clone()));
             }
-        }
-
-        for id in to_remove {
-            self.active_executions.remove(&id);
+            if let Some(cooldowns) = &self.cooldowns {
+                cooldowns.record(&request.signal.fast_market.market_id.to_string(), TradeOutcome::Limited);
+            }
+            let _ = self.result_tx.send(result);
         }
     }
 
@@ -304,7 +538,7 @@ impl LatencyExecutionEngine {
 
     /// Get execution statistics
     pub fn get_execution_stats(&self) -> LatencyExecutionStats {
-        let total_executions = self.active_executions.len();
+        let total_executions = self.active_executions.lock().unwrap().len();
         // TODO: Calculate more detailed stats
         LatencyExecutionStats {
             active_executions: total_executions,
@@ -315,7 +549,7 @@ impl LatencyExecutionEngine {
 }
 
 /// Execution statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LatencyExecutionStats {
     pub active_executions: usize,
     pub success_rate: f64,
@@ -331,3 +565,88 @@ impl Default for LatencyExecutionEngine {
         engine
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::latency_arbitrage::MarketTier;
+    use tokio::sync::Semaphore;
+
+    /// Fills once a permit is released
+    struct GatedExecutor {
+        release: Semaphore,
+    }
+
+    #[async_trait::async_trait]
+    impl LatencyOrderExecutor for GatedExecutor {
+        async fn execute(&self, signal_id: SignalId, request: &LatencyExecutionRequest) -> Result<LatencyExecutionResult, ExecutionError> {
+            self.release.acquire().await.unwrap().forget();
+            let mut result = LatencyExecutionResult::failed(signal_id, request, 0, String::new());
+            result.success = true;
+            result.error_message = None;
+            Ok(result)
+        }
+    }
+
+    fn request(deadline_ns: TimestampNs, disparity_cents: i16) -> LatencyExecutionRequest {
+        let obs = |market_id, provider| PriceObservation {
+            market_id,
+            provider,
+            market_type: MarketType::Moneyline,
+            price: 50,
+            size: 5000,
+            timestamp_ns: 0,
+            tier: MarketTier::Tier1,
+            features: None,
+        };
+        LatencyExecutionRequest {
+            signal: LatencySignal {
+                fast_market: obs(1, Platform::Kalshi),
+                slow_market: obs(2, Platform::Polymarket),
+                disparity_cents,
+                expected_convergence_ns: 1_000_000,
+                pattern_id: Some(74),
+                confidence: 0.8,
+                strategy_id: "pattern_74".to_string(),
+            },
+            execution_deadline_ns: deadline_ns,
+            fill_probability_threshold: 0.5,
+            max_edge_decay_cents: 2,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_one_result_per_signal_when_a_fill_outlives_its_deadline() {
+        let clock = Arc::new(MockClock::new(Nanos(0)));
+        let executor = Arc::new(GatedExecutor { release: Semaphore::new(0) });
+        let (engine, mut results) = LatencyExecutionEngine::new(
+            Arc::new(RwLock::new(LatencyArbitrageEngine::new())),
+            Arc::new(RwLock::new(FeedAggregator::default())),
+        );
+        let mut engine = engine.with_clock(clock.clone()).with_executor(executor.clone());
+
+        // Signal 1 expires before its fill lands; signal 2 fills in time
+        engine.execute(SignalId(1), request(1_000, 6));
+        engine.execute(SignalId(2), request(10_000_000, 6));
+        clock.advance(Duration::from_micros(5));
+        engine.monitor_executions().await;
+        let expired = results.recv().await.unwrap();
+        assert_eq!((expired.signal_id, expired.success), (SignalId(1), false));
+        assert_eq!(expired.error_message.as_deref(), Some("Execution deadline exceeded"));
+
+        executor.release.add_permits(2);
+        let filled = results.recv().await.unwrap();
+        assert_eq!((filled.signal_id, filled.success), (SignalId(2), true));
+        // The late fill of signal 1 is only logged, and the monitor has nothing left to expire
+        clock.advance(Duration::from_millis(20));
+        engine.monitor_executions().await;
+        assert!(timeout(Duration::from_millis(50), results.recv()).await.is_err());
+    }
+
+    #[test]
+    fn test_failed_result_saturates_the_decayed_edge() {
+        let result = LatencyExecutionResult::failed(SignalId(1), &request(0, i16::MIN), 0, String::new());
+        assert_eq!(result.edge_decay_cents, i16::MAX);
+    }
+}
@@ -1,24 +1,98 @@
 // src/lib.rs
 
+pub mod account_manager;
+pub mod alert_router;
+pub mod arb_simulation;
+pub mod audit_log;
+pub mod backtest_jobs;
+pub mod backtest_results;
 pub mod backtester_config;
+pub mod bet_queue;
+pub mod blotter;
 pub mod bun_worker_integration;
 pub mod cache;
+pub mod capital_allocator;
 pub mod circuit_breaker;
+pub mod clock;
+pub mod clock_sync;
 pub mod config;
+pub mod config_reload;
+pub mod decision_latency;
 pub mod discovery;
+pub mod downsample;
+pub mod edge_thresholds;
+pub mod error;
+pub mod event_bus;
+pub mod event_phase;
 pub mod execution;
+pub mod execution_playbook;
+#[cfg(feature = "fake-venues")]
+pub mod fake_venues;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod fault_injection;
+pub mod feature_flags;
 pub mod feed_aggregator;
+pub mod feed_schema;
+pub mod heartbeat;
 pub mod hyperparameter_optimizer;
+pub mod intent_diff;
+pub mod interner;
+pub mod journal;
 pub mod kalshi;
+pub mod kalshi_feed;
 pub mod kalman_filter_suite;
 pub mod latency_arbitrage;
 pub mod latency_execution;
+pub mod logging;
+pub mod maintenance;
+pub mod market_cooldown;
+pub mod market_hierarchy;
+pub mod market_impact;
+pub mod market_maker;
+pub mod market_metadata;
 pub mod microstructural_simulator;
+pub mod microstructure;
 pub mod monitoring_dashboard;
+pub mod multi_region;
+pub mod observation_prefilter;
+pub mod odds_capture;
+pub mod operator_review;
+pub mod optimization_studies;
+pub mod order_manager;
+pub mod paper_fills;
 pub mod pattern_73_beta_skew;
+pub mod pattern_policy;
+pub mod pattern_verifier;
 pub mod polymarket;
 pub mod polymarket_clob;
+pub mod position_aging;
 pub mod position_tracker;
+pub mod provider_registry;
+pub mod quote_normalizer;
+pub mod reconciler;
+pub mod redis_client;
+pub mod replay_pacing;
+pub mod request_scheduler;
 pub mod risk_management;
+pub mod risk_state;
+pub mod runner;
+pub mod runtime_profile;
+pub mod schedule;
+pub mod secrets;
+pub mod sensitivity;
+pub mod settlement;
+pub mod shadow_mode;
+pub mod signal_prioritizer;
+pub mod sim_calibration;
+pub mod sizing_curve;
+pub mod sla_degradation;
+pub mod strategy;
+pub mod supervisor;
+pub mod tca;
+pub mod tick_bridge;
+pub mod tick_bundle;
+pub mod tick_sanitizer;
 pub mod tick_sim_backtester;
+pub mod tick_store;
 pub mod types;
+pub mod wire_format;
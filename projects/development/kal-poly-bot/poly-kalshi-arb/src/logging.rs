@@ -0,0 +1,198 @@
+// src/logging.rs
+// Tracing setup - JSON or text output, per-module levels from `[logging]`, throttled hot-path events
+//
+// Field convention: events about a market carry `market_id` (the pair id, as in the audit log
+// and journal), events about a signal carry `signal_id`, and both go in structured fields
+// rather than the message so one market's or signal's trail can be pulled from the JSON stream
+// (code that only holds a `MarketId` logs it as `slot`):
+//
+//     info!(market_id = %pair.pair_id, profit_cents, "[EXEC] Filled");
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::clock::{self, SharedClock};
+use crate::config::LoggingSection;
+use crate::config_reload::ConfigChanged;
+
+/// Targets `logging.level` applies to (the library and the binaries)
+const OWN_TARGETS: &[&str] = &["arb_bot", "arb_runner"];
+/// Level for every other target unless `logging.modules` lists it
+const DEPENDENCY_LEVEL: &str = "warn";
+const NEVER: u64 = u64::MAX;
+
+/// Minimum spacing of `hot_path!` events per callsite
+static HOT_PATH_INTERVAL_NS: AtomicU64 = AtomicU64::new(1_000_000_000);
+
+/// The installed subscriber's filter handle and output format
+struct Installed {
+    filter: reload::Handle<EnvFilter, Registry>,
+    format: String,
+}
+
+static INSTALLED: OnceLock<Installed> = OnceLock::new();
+
+/// Clock `hot_path!` throttles read; the process clock unless `set_clock` ran first
+static CLOCK: OnceLock<SharedClock> = OnceLock::new();
+
+/// `EnvFilter` directives for a `[logging]` section
+pub fn directives(section: &LoggingSection) -> String {
+    let mut directives = vec![DEPENDENCY_LEVEL.to_string()];
+    directives.extend(OWN_TARGETS.iter().map(|target| format!("{}={}", target, section.level)));
+    directives.extend(section.modules.iter().map(|(target, level)| format!("{}={}", target, level)));
+    directives.join(",")
+}
+
+/// RUST_LOG, when set, replaces the configured levels
+fn filter(section: &LoggingSection) -> EnvFilter {
+    match std::env::var("RUST_LOG") {
+        Ok(spec) if !spec.trim().is_empty() => EnvFilter::new(spec),
+        _ => EnvFilter::new(directives(section)),
+    }
+}
+
+/// Install the global subscriber; calls after the first only update the hot-path interval
+pub fn init(section: &LoggingSection) {
+    HOT_PATH_INTERVAL_NS.store(section.hot_path_interval_ms * 1_000_000, Ordering::Relaxed);
+    let (filter, handle) = reload::Layer::new(filter(section));
+    let json = section.format == "json";
+    let installed = tracing_subscriber::registry()
+        .with(filter)
+        .with(json.then(|| fmt::layer().json().flatten_event(true)))
+        .with((!json).then(fmt::layer))
+        .try_init();
+    if installed.is_ok() {
+        let _ = INSTALLED.set(Installed { filter: handle, format: section.format.clone() });
+    }
+}
+
+/// Throttle hot-path events against another clock (replays, backtests). Only the first call,
+/// made before any `hot_path!` fires, takes effect; returns whether this one did
+pub fn set_clock(clock: SharedClock) -> bool {
+    CLOCK.set(clock).is_ok()
+}
+
+/// Apply reloaded levels and hot-path interval; a format change needs a restart
+pub fn apply_config(section: &LoggingSection) {
+    HOT_PATH_INTERVAL_NS.store(section.hot_path_interval_ms * 1_000_000, Ordering::Relaxed);
+    let Some(installed) = INSTALLED.get() else { return };
+    if installed.format != section.format {
+        warn!("[LOG] logging.format '{}' applies after a restart", section.format);
+    }
+    if let Err(e) = installed.filter.reload(filter(section)) {
+        warn!("[LOG] Filter reload failed: {}", e);
+    }
+}
+
+/// Apply `logging` changes from the config reloader
+pub fn watch_config(mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("logging") => apply_config(&change.config.logging),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[LOG] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+/// Per-callsite gate behind `hot_path!`
+#[derive(Debug)]
+pub struct Throttle {
+    last_ns: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+impl Throttle {
+    pub const fn new() -> Self {
+        Self { last_ns: AtomicU64::new(NEVER), suppressed: AtomicU64::new(0) }
+    }
+
+    /// Calls dropped since the last pass, or None while inside the hot-path interval
+    pub fn check(&self) -> Option<u64> {
+        let now_ns = CLOCK.get_or_init(clock::system).mono_ns().0;
+        self.check_at(now_ns, HOT_PATH_INTERVAL_NS.load(Ordering::Relaxed))
+    }
+
+    fn check_at(&self, now_ns: u64, interval_ns: u64) -> Option<u64> {
+        let last = self.last_ns.load(Ordering::Relaxed);
+        let due = last == NEVER || now_ns.saturating_sub(last) >= interval_ns;
+        if due && self.last_ns.compare_exchange(last, now_ns, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+            Some(self.suppressed.swap(0, Ordering::Relaxed))
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A tracing event for per-tick / per-signal paths, emitted at most once per
+/// `logging.hot_path_interval_ms` from each callsite. The event carries `suppressed`, the
+/// number of calls dropped since the previous one:
+///
+/// `hot_path!(Level::WARN, market_id = %pair.pair_id, "[EXEC] Liquidity fail")`
+#[macro_export]
+macro_rules! hot_path {
+    ($lvl:expr, $($arg:tt)+) => {{
+        if ::tracing::enabled!($lvl) {
+            static THROTTLE: $crate::logging::Throttle = $crate::logging::Throttle::new();
+            if let Some(suppressed) = THROTTLE.check() {
+                ::tracing::event!($lvl, suppressed, $($arg)+);
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_directives_from_section() {
+        let mut section = LoggingSection::default();
+        assert_eq!(directives(&section), "warn,arb_bot=info,arb_runner=info");
+
+        section.level = "debug".to_string();
+        section.modules = BTreeMap::from([
+            ("arb_bot::kalshi".to_string(), "trace".to_string()),
+            ("hyper".to_string(), "info".to_string()),
+        ]);
+        assert_eq!(directives(&section), "warn,arb_bot=debug,arb_runner=debug,arb_bot::kalshi=trace,hyper=info");
+    }
+
+    #[test]
+    fn test_throttle_counts_suppressed_calls() {
+        let throttle = Throttle::new();
+        assert_eq!(throttle.check_at(0, 100), Some(0));
+        assert_eq!(throttle.check_at(50, 100), None);
+        assert_eq!(throttle.check_at(99, 100), None);
+        assert_eq!(throttle.check_at(100, 100), Some(2));
+        assert_eq!(throttle.check_at(150, 100), None);
+        assert_eq!(throttle.check_at(400, 100), Some(1));
+        // Zero interval never throttles
+        assert_eq!(throttle.check_at(400, 0), Some(0));
+    }
+
+    #[test]
+    fn test_hot_path_macro_expands() {
+        // No subscriber in unit tests: `enabled!` is false and the throttle is never touched
+        for _ in 0..3 {
+            crate::hot_path!(tracing::Level::WARN, market_id = "p1", "[TEST] hot {}", 1);
+        }
+    }
+}
@@ -3,82 +3,159 @@
 //! Strategy: BUY YES on Platform A + BUY NO on Platform B
 //! Arb exists when: YES_ask + NO_ask < $1.00
 
+mod account_manager;
+mod alert_router;
+mod audit_log;
+mod blotter;
 mod cache;
+mod capital_allocator;
 mod circuit_breaker;
+mod clock;
 mod config;
+mod config_reload;
+mod decision_latency;
 mod discovery;
+mod error;
+mod event_phase;
 mod execution;
+mod execution_playbook;
+mod feature_flags;
+mod feed_schema;
+mod journal;
 mod kalshi;
+mod logging;
+mod maintenance;
+mod market_cooldown;
+mod market_hierarchy;
+mod order_manager;
+mod pattern_policy;
 mod polymarket;
 mod polymarket_clob;
+mod position_aging;
 mod position_tracker;
+mod provider_registry;
+mod redis_client;
+mod request_scheduler;
+mod runtime_profile;
+mod schedule;
+mod secrets;
+mod signal_prioritizer;
+mod sizing_curve;
+mod strategy;
+mod tca;
 mod types;
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{error, info, warn};
-
-use cache::TeamCache;
-use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use config::{ARB_THRESHOLD, ENABLED_LEAGUES, WS_RECONNECT_DELAY_SECS};
+use tracing::{debug, error, info, warn};
+
+use account_manager::{AccountManager, run_account_sync_loop};
+use audit_log::{AuditConfig, AuditEvent, AuditLog};
+use blotter::{Blotter, BlotterConfig};
+use cache::{RedisBackend, TeamCache};
+use capital_allocator::{AllocatorConfig, CapitalAllocator, run_rebalance_loop};
+use circuit_breaker::{BreakerConfig, CircuitBreaker, CircuitBreakerConfig, TradingCircuitBreaker};
+use config::{AppConfig, CliArgs, kalshi_env, polymarket_env, poly_clob_host, polygon_chain_id};
+use config_reload::ConfigReloader;
+use decision_latency::{DecisionLatencyConfig, DecisionLatencyTracker};
 use discovery::DiscoveryClient;
-use execution::{ExecutionEngine, create_execution_channel, run_execution_loop};
+use event_phase::{PhaseGates, PhaseTracker, run_phase_refresh};
+use execution::{ExecutionEngine, create_execution_channel};
+use execution_playbook::PlaybookRegistry;
+use feed_schema::FeedSchemas;
 use kalshi::{KalshiConfig, KalshiApiClient};
+use maintenance::{MaintenanceCalendar, run_kalshi_schedule_sync, run_position_sweep};
+use market_cooldown::{CooldownConfig, CooldownManager, run_cooldown_expiry_loop};
+use market_hierarchy::MarketHierarchy;
+use order_manager::{OrderManager, OrderManagerConfig, run_user_channel};
+use pattern_policy::{PatternPolicy, run_pattern_sync_loop};
 use polymarket_clob::{PolymarketAsyncClient, PreparedCreds, SharedAsyncClient};
-use position_tracker::{PositionTracker, create_position_channel, position_writer_loop};
-use types::{GlobalState, PriceCents};
-
-/// Polymarket CLOB API host
-const POLY_CLOB_HOST: &str = "https://clob.polymarket.com";
-/// Polygon chain ID
-const POLYGON_CHAIN_ID: u64 = 137;
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("arb_bot=info".parse().unwrap()),
-        )
-        .init();
-
-    info!("🎯 Arb Bot v2.0");
-    info!("   Threshold: <{:.1}¢ for {:.1}% profit",
-          ARB_THRESHOLD * 100.0, (1.0 - ARB_THRESHOLD) * 100.0);
-    info!("   Leagues: {:?}", ENABLED_LEAGUES);
+use journal::{Journal, JournalConfig};
+use position_aging::{PositionSweeper, VenueLiquidator, run_position_sweeper};
+use position_tracker::{PositionTracker, create_position_channel, position_writer_loop_with_journal};
+use request_scheduler::RequestScheduler;
+use runtime_profile::RuntimeProfile;
+use secrets::{ScopedSecrets, SecretsChain, SecretsProvider, POLY_FUNDER, POLY_PRIVATE_KEY};
+use signal_prioritizer::{PrioritizerConfig, SignalPrioritizer, run_prioritized_execution_loop};
+use sizing_curve::SizingCurve;
+use tca::{TcaConfig, TcaStore};
+use types::{GlobalState, MarketId, Platform, PriceCents};
+
+fn main() -> Result<()> {
+    // Layered config: defaults -> arb.toml -> env -> --section.key=value flags
+    dotenvy::dotenv().ok();
+    let cli = CliArgs::parse(std::env::args().skip(1))?;
+    let config_path = AppConfig::config_path(&cli);
+    let app_config = AppConfig::load_with(config_path.as_deref(), &cli)?;
+    app_config.apply_venue_envs();
+
+    // Initialize logging ([logging] format and levels; RUST_LOG overrides the levels)
+    logging::init(&app_config.logging);
+    debug!("Config: {:?}", app_config);
+
+    // The runtime follows [runtime]: pinned workers when low_latency is set
+    let runtime = RuntimeProfile::from_config(&app_config.runtime).build_runtime()?;
+    runtime.block_on(run(app_config, config_path, cli))
+}
+
+async fn run(app_config: AppConfig, config_path: Option<std::path::PathBuf>, cli: CliArgs) -> Result<()> {
+
+    info!("Arb Bot v2.0");
+    let arb_threshold = app_config.execution.arb_threshold;
+    let leagues: Vec<&str> = app_config.feeds.enabled_leagues.iter().map(String::as_str).collect();
+    let reconnect_delay = tokio::time::Duration::from_secs(app_config.feeds.ws_reconnect_delay_secs);
+    info!("Threshold: <{:.1}¢ for {:.1}% profit",
+          arb_threshold * 100.0, (1.0 - arb_threshold) * 100.0);
+    info!("Leagues: {:?}", leagues);
 
     // Check for dry run mode
-    let dry_run = std::env::var("DRY_RUN").map(|v| v == "1" || v == "true").unwrap_or(true);
+    let dry_run = app_config.execution.dry_run;
     if dry_run {
-        info!("   Mode: DRY RUN (set DRY_RUN=0 to execute)");
+        info!("Mode: DRY RUN (set DRY_RUN=0 to execute)");
     } else {
-        warn!("   Mode: LIVE EXECUTION");
+        warn!("Mode: LIVE EXECUTION");
     }
 
+    // Venue environments (KALSHI_ENV=demo / POLY_ENV=testnet target sandboxes)
+    info!("Venues: Kalshi={:?} Polymarket={:?} (chain {})",
+          kalshi_env(), polymarket_env(), polygon_chain_id());
+
+    // Credentials: env, ./secrets/<KEY> (0600), OS keychain, Vault (order via [secrets] backends)
+    let secrets = SecretsChain::from_config(&app_config.secrets);
+
     // Load Kalshi credentials
-    let kalshi_config = KalshiConfig::from_env()?;
+    let kalshi_config = KalshiConfig::from_secrets(&secrets).await?;
     info!("[KALSHI] API key loaded");
 
     // Load Polymarket credentials
-    dotenvy::dotenv().ok();
-    let poly_private_key = std::env::var("POLY_PRIVATE_KEY")
+    let poly_private_key = secrets.require(POLY_PRIVATE_KEY).await
         .context("POLY_PRIVATE_KEY not set")?;
-    let poly_funder = std::env::var("POLY_FUNDER")
-        .context("POLY_FUNDER not set (your wallet address)")?;
+    let poly_funder = secrets.require(POLY_FUNDER).await
+        .context("POLY_FUNDER not set (your wallet address)")?
+        .expose()
+        .to_string();
+
+    // Shared per-venue request scheduler (orders > cancels > market data > discovery)
+    let scheduler = Arc::new(RequestScheduler::from_config(&app_config.scheduler));
+    let kalshi_sched = scheduler.venue(Platform::Kalshi).expect("kalshi scheduler");
+    let poly_sched = scheduler.venue(Platform::Polymarket).expect("polymarket scheduler");
+
+    // Shared venue API breaker (fails fast while a venue is returning errors)
+    let venue_breaker = Arc::new(CircuitBreaker::<Platform>::new(BreakerConfig::from(&app_config.breaker)));
 
     // Create async Polymarket client and derive API credentials
     info!("[POLYMARKET] Creating async client and deriving API credentials...");
     let poly_async_client = PolymarketAsyncClient::new(
-        POLY_CLOB_HOST,
-        POLYGON_CHAIN_ID,
+        poly_clob_host(),
+        polygon_chain_id(),
         &poly_private_key,
         &poly_funder,
-    )?;
+    )?.with_scheduler(poly_sched.clone()).with_breaker(venue_breaker.clone());
     let api_creds = poly_async_client.derive_api_key(0).await?;
     let prepared_creds = PreparedCreds::from_api_creds(&api_creds)?;
-    let poly_async = Arc::new(SharedAsyncClient::new(poly_async_client, prepared_creds, POLYGON_CHAIN_ID));
+    let poly_async = Arc::new(SharedAsyncClient::new(poly_async_client, prepared_creds, polygon_chain_id()));
 
     // Load neg_risk cache from Python script output
     match poly_async.load_cache(".clob_market_cache.json") {
@@ -90,36 +167,43 @@ async fn main() -> Result<()> {
 
     // Load team cache
     let team_cache = TeamCache::load();
-    info!("📂 Loaded {} team mappings", team_cache.len());
+    info!("Loaded {} team mappings", team_cache.len());
 
     // Create Kalshi API client
-    let kalshi_api = Arc::new(KalshiApiClient::new(kalshi_config));
+    let kalshi_api = Arc::new(KalshiApiClient::new(kalshi_config)
+        .with_scheduler(kalshi_sched.clone())
+        .with_breaker(venue_breaker.clone()));
 
     // Run discovery (with caching support)
     let force_discovery = std::env::var("FORCE_DISCOVERY")
         .map(|v| v == "1" || v == "true")
         .unwrap_or(false);
 
-    info!("🔍 Discovering markets{}...",
+    info!("Discovering markets{}...",
           if force_discovery { " (forced refresh)" } else { "" });
 
-    let discovery = DiscoveryClient::new(
-        KalshiApiClient::new(KalshiConfig::from_env()?),
+    let mut discovery = DiscoveryClient::new(
+        KalshiApiClient::new(KalshiConfig::from_secrets(&secrets).await?)
+            .with_scheduler(kalshi_sched.clone())
+            .with_breaker(venue_breaker.clone()),
         team_cache
     );
+    if !app_config.redis.url.is_empty() {
+        discovery = discovery.with_cache_backend(Arc::new(RedisBackend::new(&app_config.redis.url)));
+    }
 
     let result = if force_discovery {
-        discovery.discover_all_force(ENABLED_LEAGUES).await
+        discovery.discover_all_force(&leagues).await
     } else {
-        discovery.discover_all(ENABLED_LEAGUES).await
+        discovery.discover_all(&leagues).await
     };
 
-    info!("📊 Discovery complete:");
-    info!("   - Market pairs found: {}", result.pairs.len());
+    info!("Discovery complete:");
+    info!("Market pairs found: {}", result.pairs.len());
 
     if !result.errors.is_empty() {
         for err in &result.errors {
-            warn!("   ⚠️ {}", err);
+            warn!("{}", err);
         }
     }
 
@@ -129,47 +213,241 @@ async fn main() -> Result<()> {
     }
 
     // Print discovered pairs
-    info!("📋 Matched markets:");
+    info!("Matched markets:");
     for pair in &result.pairs {
-        info!("   ✅ {} | {} | K:{}",
+        info!(market_id = %pair.pair_id, "{} | {} | K:{}",
               pair.description,
               pair.market_type,
               pair.kalshi_market_ticker);
     }
 
+    // Event phases (pre-game / in-play / ...) from Kalshi schedules and status
+    let phase_tracker = Arc::new(PhaseTracker::new(PhaseGates::new(&app_config.risk, &app_config.execution)));
+    tokio::spawn(run_phase_refresh(
+        phase_tracker.clone(),
+        kalshi_api.clone(),
+        result.pairs.clone(),
+        tokio::time::Duration::from_secs(60),
+    ));
+
+    // Series/event limits and correlations ([risk.groups]) inherited by each pair's Kalshi market
+    let hierarchy = MarketHierarchy::from_pairs(&result.pairs, app_config.risk.groups.clone());
+
     // Build global state
     let state = Arc::new({
         let mut s = GlobalState::new();
         for pair in result.pairs {
             s.add_pair(pair);
         }
-        info!("📡 State: Tracking {} markets", s.market_count());
+        info!("State: Tracking {} markets", s.market_count());
         s
     });
 
     // Create execution infrastructure
     let (exec_tx, exec_rx) = create_execution_channel();
-    let circuit_breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig::from_env()));
+    let circuit_breaker = Arc::new(
+        TradingCircuitBreaker::new(CircuitBreakerConfig::from(&app_config.risk)).with_hierarchy(hierarchy),
+    );
+
+    // Bankroll split across patterns (allocator.bankroll > 0), rebalanced from realized P&L
+    let allocator = Arc::new(CapitalAllocator::new(AllocatorConfig::from(&app_config.allocator)));
+    // Markets benched after losing streaks, refused orders or unfilled arbs
+    let cooldowns = Arc::new(CooldownManager::new(CooldownConfig::from(&app_config.risk)));
+    tokio::spawn(run_cooldown_expiry_loop(cooldowns.clone(), tokio::time::Duration::from_secs(1)));
+    // Enabled patterns with their venue, confidence, position and capital limits ([patterns.enabled])
+    let pattern_policy = Arc::new(PatternPolicy::new(&app_config.patterns));
+    // Lots outliving their pattern's half-life are flagged, and sold as timeout exits with
+    // `[patterns.aging] liquidate` (never in dry run)
+    let mut sweeper = PositionSweeper::new(&app_config.patterns);
+    if app_config.patterns.aging.liquidate && !dry_run {
+        let liquidator = VenueLiquidator::new(kalshi_api.clone(), poly_async.clone())
+            .with_pairs(state.markets.iter().filter_map(|m| m.pair.as_deref()));
+        sweeper = sweeper.with_liquidator(Arc::new(liquidator));
+    }
+    let sweeper = Arc::new(sweeper);
+    // Venue maintenance windows ([maintenance], plus Kalshi's exchange schedule with
+    // `kalshi_api`): new arbs refused shortly before them, positions held into them alerted
+    let maintenance = Arc::new(MaintenanceCalendar::new(&app_config.maintenance));
+    tokio::spawn(run_kalshi_schedule_sync(maintenance.clone(), kalshi_api.clone()));
+    // Venue accounts orders are routed across, with per-account limits ([accounts])
+    let accounts = Arc::new(AccountManager::new(&app_config.accounts));
+    // Share of each arb's size traded by its strength and fill probability ([sizing])
+    let sizing = Arc::new(SizingCurve::new(&app_config.sizing));
+
+    // Audit log ([audit]): append-only record of decisions, orders and config changes
+    let audit = if app_config.audit.enabled {
+        Some(Arc::new(AuditLog::open(AuditConfig::from(&app_config.audit))?))
+    } else {
+        None
+    };
 
-    let position_tracker = Arc::new(RwLock::new(PositionTracker::new()));
+    // Hot reload (SIGHUP or config file change): risk limits go to the trading breaker
+    let reloader = Arc::new(ConfigReloader::new(app_config.clone(), config_path, cli));
+    let mut config_rx = reloader.subscribe();
+    tokio::spawn(reloader.clone().run(tokio::time::Duration::from_secs(5)));
+    let reload_cb = circuit_breaker.clone();
+    let reload_phases = phase_tracker.clone();
+    let reload_audit = audit.clone();
+    let reload_allocator = allocator.clone();
+    let reload_patterns = pattern_policy.clone();
+    let reload_sweeper = sweeper.clone();
+    let reload_cooldowns = cooldowns.clone();
+    let reload_maintenance = maintenance.clone();
+    let reload_accounts = accounts.clone();
+    let reload_sizing = sizing.clone();
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) => {
+                    if let Some(audit) = &reload_audit {
+                        audit.record("config_reload", None, AuditEvent::ConfigChanged {
+                            version: Some(change.version),
+                            sections: change.sections.iter().map(|s| s.to_string()).collect(),
+                            detail: None,
+                        });
+                    }
+                    if change.touches("risk") {
+                        reload_cb.apply_config(CircuitBreakerConfig::from(&change.config.risk));
+                        reload_cb.apply_group_rules(change.config.risk.groups.clone());
+                        reload_phases.apply_risk_config(&change.config.risk);
+                        reload_cooldowns.apply_config(&change.config.risk);
+                    }
+                    if change.touches("allocator") {
+                        reload_allocator.apply_config(&change.config.allocator);
+                    }
+                    if change.touches("patterns") {
+                        reload_patterns.apply_config(&change.config.patterns);
+                        reload_sweeper.apply_config(&change.config.patterns);
+                    }
+                    if change.touches("accounts") {
+                        reload_accounts.apply_config(&change.config.accounts);
+                    }
+                    if change.touches("maintenance") {
+                        reload_maintenance.apply_config(&change.config.maintenance);
+                    }
+                    if change.touches("sizing") {
+                        reload_sizing.apply_config(&change.config.sizing);
+                    }
+                    if change.touches("logging") {
+                        logging::apply_config(&change.config.logging);
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    // Write-ahead journal ([journal]): rebuild tracker from snapshot + log on startup
+    let (tracker, journal) = if app_config.journal.enabled {
+        let (journal, tracker, report) = Journal::recover(JournalConfig::from(&app_config.journal))?;
+        if !report.unfinished_arbs.is_empty() {
+            warn!("[JOURNAL] Reconcile venue state for: {:?}", report.unfinished_arbs);
+        }
+        (tracker, Some(Arc::new(std::sync::Mutex::new(journal))))
+    } else {
+        (PositionTracker::new(), None)
+    };
+    let position_tracker = Arc::new(RwLock::new(tracker));
     let (position_channel, position_rx) = create_position_channel();
 
-    tokio::spawn(position_writer_loop(position_rx, position_tracker));
+    tokio::spawn(run_rebalance_loop(allocator.clone(), position_tracker.clone()));
+    tokio::spawn(run_pattern_sync_loop(pattern_policy.clone(), position_tracker.clone()));
+    tokio::spawn(run_account_sync_loop(accounts.clone(), position_tracker.clone()));
+    tokio::spawn(run_position_sweeper(sweeper, position_tracker.clone(), position_channel.clone()));
+    tokio::spawn(run_position_sweep(maintenance.clone(), position_tracker.clone()));
+    // End-of-day trade blotter ([blotter]), optionally with a FIX drop copy (`drop_copy`)
+    let blotter = if app_config.blotter.enabled {
+        Some(Arc::new(Blotter::open(BlotterConfig::from(&app_config.blotter))?))
+    } else {
+        None
+    };
+    tokio::spawn(position_writer_loop_with_journal(position_rx, position_tracker, journal.clone(), blotter));
+
+    let threshold_cents: PriceCents = ((arb_threshold * 100.0).round() as u16).max(1);
+    info!("Threshold: {} cents", threshold_cents);
 
-    let threshold_cents: PriceCents = ((ARB_THRESHOLD * 100.0).round() as u16).max(1);
-    info!("   Threshold: {} cents", threshold_cents);
+    // Polymarket order status from the user channel (REST fallback when it drops)
+    let order_manager = Arc::new(std::sync::Mutex::new(
+        OrderManager::new(OrderManagerConfig::default(), position_channel.clone())
+            .with_pairs(state.markets.iter().filter_map(|m| m.pair.as_deref())),
+    ));
+    if !dry_run {
+        tokio::spawn(run_user_channel(api_creds.clone(), Vec::new(), order_manager.clone(), poly_async.clone()));
+    }
 
-    let engine = Arc::new(ExecutionEngine::new(
+    // Listed accounts get their own venue clients on `<SECRET>_<ID>` credentials; without
+    // `[accounts.list]` orders go through the clients above
+    let mut kalshi_accounts = HashMap::new();
+    let mut poly_accounts = HashMap::new();
+    if !app_config.accounts.list.is_empty() {
+        for id in accounts.ids("kalshi") {
+            let config = KalshiConfig::from_secrets(&ScopedSecrets::new(&secrets, &id)).await
+                .with_context(|| format!("Kalshi account {} credentials", id))?;
+            let client = KalshiApiClient::new(config)
+                .with_scheduler(kalshi_sched.clone())
+                .with_breaker(venue_breaker.clone());
+            kalshi_accounts.insert(id.clone(), Arc::new(client));
+            info!("[ACCOUNTS] Kalshi account {} loaded", id);
+        }
+        for id in accounts.ids("polymarket") {
+            let client = PolymarketAsyncClient::from_secrets(poly_clob_host(), polygon_chain_id(), &ScopedSecrets::new(&secrets, &id)).await
+                .with_context(|| format!("Polymarket account {} credentials", id))?
+                .with_scheduler(poly_sched.clone())
+                .with_breaker(venue_breaker.clone());
+            let creds = PreparedCreds::from_api_creds(&client.derive_api_key(0).await?)?;
+            let client = SharedAsyncClient::new(client, creds, polygon_chain_id());
+            if let Err(e) = client.load_cache(".clob_market_cache.json") {
+                warn!("[ACCOUNTS] {} could not load neg_risk cache: {}", id, e);
+            }
+            poly_accounts.insert(id.clone(), Arc::new(client));
+            info!("[ACCOUNTS] Polymarket account {} loaded", id);
+        }
+    }
+
+    // Detection-to-order latency per arb type, reported with the heartbeat
+    let decision_latency = Arc::new(DecisionLatencyTracker::new(DecisionLatencyConfig::from(&app_config.decision_latency)));
+    let mut engine = ExecutionEngine::new(
         kalshi_api.clone(),
         poly_async,
         state.clone(),
         circuit_breaker.clone(),
         position_channel,
         dry_run,
+    )
+    .with_order_manager(order_manager)
+    .with_phase_tracker(phase_tracker)
+    .with_capital_allocator(allocator)
+    .with_pattern_policy(pattern_policy.clone())
+    .with_cooldowns(cooldowns)
+    .with_maintenance(maintenance)
+    .with_playbooks(PlaybookRegistry::from_section(&app_config.execution))
+    .with_decision_latency(decision_latency.clone())
+    .with_accounts(accounts, kalshi_accounts, poly_accounts);
+    if let Some(journal) = journal {
+        engine = engine.with_journal(journal);
+    }
+    if let Some(audit) = audit {
+        engine = engine.with_audit_log(audit);
+    }
+    // Post-trade TCA ([tca]): per-fill slippage history for the dashboard
+    if app_config.tca.enabled {
+        engine = engine.with_tca(Arc::new(TcaStore::open(TcaConfig::from(&app_config.tca))?));
+    }
+    let engine = Arc::new(engine);
+
+    // Rank pending signals when they outrun execution slots, order rate or collateral
+    let prioritizer = SignalPrioritizer::new(PrioritizerConfig::from(&app_config.execution))
+        .with_venue(Platform::Kalshi, kalshi_sched)
+        .with_venue(Platform::Polymarket, poly_sched)
+        .with_pattern_policy(pattern_policy)
+        .with_sizing_curve(sizing);
+    let exec_handle = tokio::spawn(run_prioritized_execution_loop(
+        exec_rx,
+        engine,
+        Arc::new(std::sync::Mutex::new(prioritizer)),
     ));
 
-    let exec_handle = tokio::spawn(run_execution_loop(exec_rx, engine));
-
     // === TEST MODE: Inject fake arb after delay ===
     // TEST_ARB=1 to enable, TEST_ARB_TYPE=poly_yes_kalshi_no|kalshi_yes_poly_no|poly_only|kalshi_only
     let test_arb = std::env::var("TEST_ARB").map(|v| v == "1" || v == "true").unwrap_or(false);
@@ -182,7 +460,7 @@ async fn main() -> Result<()> {
         let arb_type_str = std::env::var("TEST_ARB_TYPE").unwrap_or_else(|_| "poly_yes_kalshi_no".to_string());
 
         tokio::spawn(async move {
-            use types::{FastExecutionRequest, ArbType};
+            use types::{FastExecutionRequest, ArbType, Nanos, Price, Size};
 
             // Wait for WebSockets to connect and populate some prices
             info!("[TEST] Will inject fake arb in 10 seconds...");
@@ -211,59 +489,62 @@ async fn main() -> Result<()> {
 
             // Find first market with valid state
             let market_count = test_state.market_count();
-            for market_id in 0..market_count {
-                if let Some(market) = test_state.get_by_id(market_id as u16) {
-                    if let Some(pair) = &market.pair {
-                        // SIZE: 1000 cents = 10 contracts (Poly $1 min requires ~3 contracts at 40¢)
-                        let fake_req = FastExecutionRequest {
-                            market_id: market_id as u16,
-                            yes_price,
-                            no_price,
-                            yes_size: 1000,  // 1000¢ = 10 contracts
-                            no_size: 1000,   // 1000¢ = 10 contracts
-                            arb_type,
-                            detected_ns: 0,
-                        };
-
-                        warn!("[TEST] 🧪 Injecting FAKE {:?} arb for: {}", arb_type, pair.description);
-                        warn!("[TEST]    {}", description);
-                        warn!("[TEST]    SIZE CAPPED TO 10 CONTRACTS for safety!");
-                        warn!("[TEST]    Execution mode: DRY_RUN={}", test_dry_run);
-
-                        if let Err(e) = test_exec_tx.send(fake_req).await {
-                            error!("[TEST] Failed to send fake arb: {}", e);
-                        }
-                        break;
+            for market in test_state.markets.iter().take(market_count) {
+                if let Some(pair) = &market.pair {
+                    // SIZE: 1000 cents = 10 contracts (Poly $1 min requires ~3 contracts at 40¢)
+                    let fake_req = FastExecutionRequest {
+                        market_id: market.market_id,
+                        yes_price: Price(yes_price),
+                        no_price: Price(no_price),
+                        yes_size: Size(1000),  // 1000¢ = 10 contracts
+                        no_size: Size(1000),   // 1000¢ = 10 contracts
+                        arb_type,
+                        detected_ns: Nanos::ZERO,
+                    };
+
+                    warn!("[TEST] Injecting FAKE {:?} arb for: {}", arb_type, pair.description);
+                    warn!("[TEST]    {}", description);
+                    warn!("[TEST]    SIZE CAPPED TO 10 CONTRACTS for safety!");
+                    warn!("[TEST]    Execution mode: DRY_RUN={}", test_dry_run);
+
+                    if let Err(e) = test_exec_tx.send(fake_req).await {
+                        error!("[TEST] Failed to send fake arb: {}", e);
                     }
+                    break;
                 }
             }
         });
     }
 
+    // Feed messages are schema-checked; rejects go to the quarantine dir
+    let schemas = Arc::new(FeedSchemas::from_config(&app_config.feeds));
+
     // Start Kalshi WebSocket (config parsed once, reused on reconnects)
+    let kalshi_schemas = schemas.clone();
     let kalshi_state = state.clone();
     let kalshi_exec_tx = exec_tx.clone();
     let kalshi_threshold = threshold_cents;
-    let kalshi_ws_config = KalshiConfig::from_env()?;
+    let kalshi_ws_config = KalshiConfig::from_secrets(&secrets).await?;
     let kalshi_handle = tokio::spawn(async move {
         loop {
-            if let Err(e) = kalshi::run_ws(&kalshi_ws_config, kalshi_state.clone(), kalshi_exec_tx.clone(), kalshi_threshold).await {
+            if let Err(e) = kalshi::run_ws(&kalshi_ws_config, &kalshi_schemas, kalshi_state.clone(), kalshi_exec_tx.clone(), kalshi_threshold, clock::system()).await {
                 error!("[KALSHI] Disconnected: {} - reconnecting...", e);
             }
-            tokio::time::sleep(tokio::time::Duration::from_secs(WS_RECONNECT_DELAY_SECS)).await;
+            tokio::time::sleep(reconnect_delay).await;
         }
     });
 
     // Start Polymarket WebSocket
+    let poly_schemas = schemas.clone();
     let poly_state = state.clone();
     let poly_exec_tx = exec_tx.clone();
     let poly_threshold = threshold_cents;
     let poly_handle = tokio::spawn(async move {
         loop {
-            if let Err(e) = polymarket::run_ws(poly_state.clone(), poly_exec_tx.clone(), poly_threshold).await {
+            if let Err(e) = polymarket::run_ws(&poly_schemas, poly_state.clone(), poly_exec_tx.clone(), poly_threshold, clock::system()).await {
                 error!("[POLYMARKET] Disconnected: {} - reconnecting...", e);
             }
-            tokio::time::sleep(tokio::time::Duration::from_secs(WS_RECONNECT_DELAY_SECS)).await;
+            tokio::time::sleep(reconnect_delay).await;
         }
     });
 
@@ -280,7 +561,7 @@ async fn main() -> Result<()> {
             let mut with_poly = 0;
             let mut with_both = 0;
             // (cost, market_id, p_yes, k_no, k_yes, p_no, fee, is_poly_yes_kalshi_no)
-            let mut best_arb: Option<(u16, u16, u16, u16, u16, u16, u16, bool)> = None;
+            let mut best_arb: Option<(u16, MarketId, u16, u16, u16, u16, u16, bool)> = None;
 
             for market in heartbeat_state.markets.iter().take(market_count) {
                 let (k_yes, k_no, _, _) = market.kalshi.load();
@@ -310,7 +591,7 @@ async fn main() -> Result<()> {
                 }
             }
 
-            info!("💓 Heartbeat | Markets: {} total, {} w/Kalshi, {} w/Poly, {} w/Both | threshold={}¢",
+            info!("Heartbeat | Markets: {} total, {} w/Kalshi, {} w/Poly, {} w/Both | threshold={}¢",
                   market_count, with_kalshi, with_poly, with_both, heartbeat_threshold);
 
             if let Some((cost, market_id, p_yes, k_no, k_yes, p_no, fee, is_poly_yes)) = best_arb {
@@ -325,14 +606,28 @@ async fn main() -> Result<()> {
                     format!("K_yes({}¢) + P_no({}¢) + K_fee({}¢) = {}¢", k_yes, p_no, fee, cost)
                 };
                 if gap <= 10 {
-                    info!("   📊 Best: {} | {} | gap={:+}¢ | [P_yes={}¢ K_no={}¢ K_yes={}¢ P_no={}¢]",
+                    info!("Best: {} | {} | gap={:+}¢ | [P_yes={}¢ K_no={}¢ K_yes={}¢ P_no={}¢]",
                           desc, leg_breakdown, gap, p_yes, k_no, k_yes, p_no);
                 } else {
-                    info!("   📊 Best: {} | {} | gap={:+}¢ - efficient",
+                    info!("Best: {} | {} | gap={:+}¢ - efficient",
                           desc, leg_breakdown, gap);
                 }
             } else if with_both == 0 {
-                warn!("   ⚠️  No markets with BOTH Kalshi and Poly prices - check WebSocket connections");
+                warn!("No markets with BOTH Kalshi and Poly prices - check WebSocket connections");
+            }
+
+            // Each heartbeat closes a decision latency window (no SLA monitor in this binary)
+            decision_latency.rotate();
+            for p in decision_latency.summary() {
+                info!(pattern = %p.pattern, samples = p.samples, p50_ms = p.p50_ms, p99_ms = p.p99_ms,
+                      recent_p99_ms = p.recent_p99_ms, sla_ms = p.sla_ms, breached = p.breached,
+                      "[DECISION] Detection-to-order latency");
+            }
+
+            for s in schemas.stats().iter().filter(|s| s.drifted > 0 || s.rejected > 0) {
+                warn!(provider = %s.provider, message_type = %s.message_type, drifted = s.drifted,
+                      rejected = s.rejected, unknown_fields = ?s.unknown_fields, last_error = ?s.last_error,
+                      "[SCHEMA] Feed schema drift since start");
             }
         }
     });
@@ -0,0 +1,404 @@
+// src/maintenance.rs
+// Venue maintenance calendar - scheduled downtime from `[maintenance]` (recurring cron or
+// one-off windows) and, optionally, Kalshi's published exchange schedule. Risk checks refuse new
+// positions on a venue from `block_before_mins` ahead of one of its windows until it ends, and
+// open legs held into a window are alerted so they aren't stranded
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::clock::{self, SharedClock};
+use crate::config::MaintenanceSection;
+use crate::config_reload::ConfigChanged;
+use crate::error::RiskRejection;
+use crate::kalshi::KalshiApiClient;
+use crate::position_tracker::{PositionTracker, SharedPositionTracker};
+use crate::schedule::Schedule;
+use crate::types::{KalshiMaintenanceWindow, Nanos};
+
+const NS_PER_MIN: u64 = 60_000_000_000;
+/// Most occurrences of one recurring rule expanded per lookup
+const MAX_OCCURRENCES: usize = 64;
+/// How often held positions are checked against upcoming windows
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// One venue outage, wall-clock bounds (Unix ns)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MaintenanceWindow {
+    pub venue: String,
+    pub start_ns: u64,
+    pub end_ns: u64,
+    /// "config" or the API it was ingested from ("kalshi_api")
+    pub source: String,
+}
+
+impl MaintenanceWindow {
+    pub fn start_rfc3339(&self) -> String {
+        rfc3339(self.start_ns)
+    }
+}
+
+fn rfc3339(ns: u64) -> String {
+    chrono::DateTime::from_timestamp_nanos(ns as i64).to_rfc3339()
+}
+
+fn parse_rfc3339(s: &str) -> Option<u64> {
+    let t = chrono::DateTime::parse_from_rfc3339(s).ok()?;
+    u64::try_from(t.timestamp_nanos_opt()?).ok()
+}
+
+/// An open leg on a venue whose window is running or about to start
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HeldPosition {
+    pub market_id: String,
+    pub description: String,
+    pub platform: String,
+    pub side: String,
+    pub contracts: f64,
+    /// Window start (RFC3339)
+    pub window_start: String,
+    /// Seconds until the window starts (negative once it has)
+    pub starts_in_secs: f64,
+}
+
+impl HeldPosition {
+    /// Structured form for the alert router / event bus (and so the dashboard)
+    pub fn to_alert(&self) -> Alert {
+        Alert::new("maintenance", AlertSeverity::Warning, "held_into_maintenance", format!(
+            "{} {} {} x{:.0} held into {} maintenance at {} ({:.0}s)",
+            self.platform, self.side, self.market_id, self.contracts, self.platform,
+            self.window_start, self.starts_in_secs,
+        ))
+    }
+}
+
+/// A configured rule with its schedule parsed
+enum Rule {
+    Recurring { venue: String, schedule: Schedule, duration_ns: u64 },
+    Once(MaintenanceWindow),
+}
+
+impl Rule {
+    fn parse(section: &MaintenanceSection) -> Vec<Rule> {
+        section.windows.iter().filter_map(|rule| {
+            let duration_ns = rule.duration_mins * NS_PER_MIN;
+            if !rule.cron.is_empty() {
+                let schedule = rule.cron.parse().map_err(|e| warn!("[MAINT] Bad cron '{}': {}", rule.cron, e)).ok()?;
+                Some(Rule::Recurring { venue: rule.venue.clone(), schedule, duration_ns })
+            } else {
+                let start_ns = parse_rfc3339(&rule.start)?;
+                Some(Rule::Once(MaintenanceWindow {
+                    venue: rule.venue.clone(),
+                    start_ns,
+                    end_ns: start_ns + duration_ns,
+                    source: "config".to_string(),
+                }))
+            }
+        }).collect()
+    }
+
+    /// Occurrences still running at `from` or starting by `until`
+    fn windows(&self, from: u64, until: u64, out: &mut Vec<MaintenanceWindow>) {
+        match self {
+            Rule::Once(window) => {
+                if window.end_ns > from && window.start_ns <= until {
+                    out.push(window.clone());
+                }
+            }
+            Rule::Recurring { venue, schedule, duration_ns } => {
+                // The first firing after `from - duration` is the earliest one not yet over
+                let mut after = from.saturating_sub(*duration_ns);
+                for _ in 0..MAX_OCCURRENCES {
+                    let Some(start) = schedule.next_after_ns(Nanos(after)) else { return };
+                    if start.0 > until {
+                        return;
+                    }
+                    out.push(MaintenanceWindow {
+                        venue: venue.clone(),
+                        start_ns: start.0,
+                        end_ns: start.0 + duration_ns,
+                        source: "config".to_string(),
+                    });
+                    after = start.0;
+                }
+            }
+        }
+    }
+}
+
+type HeldHook = Arc<dyn Fn(&HeldPosition) + Send + Sync>;
+
+/// `[maintenance]` windows plus ingested ones, consulted by the execution engines' risk checks
+pub struct MaintenanceCalendar {
+    section: RwLock<MaintenanceSection>,
+    rules: RwLock<Vec<Rule>>,
+    /// Windows from venue APIs, by venue
+    ingested: RwLock<BTreeMap<String, Vec<MaintenanceWindow>>>,
+    clock: SharedClock,
+    /// (venue, window start, market, side) already alerted
+    alerted: Mutex<HashSet<(String, u64, String, String)>>,
+    hooks: Vec<HeldHook>,
+}
+
+pub type SharedMaintenanceCalendar = Arc<MaintenanceCalendar>;
+
+impl MaintenanceCalendar {
+    pub fn new(section: &MaintenanceSection) -> Self {
+        Self {
+            section: RwLock::new(section.clone()),
+            rules: RwLock::new(Rule::parse(section)),
+            ingested: RwLock::new(BTreeMap::new()),
+            clock: clock::system(),
+            alerted: Mutex::new(HashSet::new()),
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Read time from another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Register a hook for positions newly found held into a window
+    pub fn on_held(mut self, hook: impl Fn(&HeldPosition) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Apply a hot-reloaded `[maintenance]` section
+    pub fn apply_config(&self, section: &MaintenanceSection) {
+        *self.rules.write().unwrap() = Rule::parse(section);
+        *self.section.write().unwrap() = section.clone();
+        info!("[MAINT] {} configured windows, block {}m / alert {}m ahead",
+              section.windows.len(), section.block_before_mins, section.alert_before_mins);
+    }
+
+    pub fn section(&self) -> MaintenanceSection {
+        self.section.read().unwrap().clone()
+    }
+
+    /// Replace a venue's windows taken from its API
+    pub fn set_ingested(&self, venue: &str, windows: Vec<MaintenanceWindow>) {
+        self.ingested.write().unwrap().insert(venue.to_string(), windows);
+    }
+
+    /// Every window running at `from` or starting by `until`, earliest first
+    pub fn windows_between(&self, from: u64, until: u64) -> Vec<MaintenanceWindow> {
+        let mut out = Vec::new();
+        for rule in self.rules.read().unwrap().iter() {
+            rule.windows(from, until, &mut out);
+        }
+        for windows in self.ingested.read().unwrap().values() {
+            out.extend(windows.iter().filter(|w| w.end_ns > from && w.start_ns <= until).cloned());
+        }
+        out.sort_by(|a, b| (a.start_ns, &a.venue).cmp(&(b.start_ns, &b.venue)));
+        out
+    }
+
+    /// Windows running now or starting within `horizon`
+    pub fn upcoming(&self, horizon: Duration) -> Vec<MaintenanceWindow> {
+        let now = self.clock.wall_ns().0;
+        self.windows_between(now, now.saturating_add(horizon.as_nanos() as u64))
+    }
+
+    /// The window of `venue` (case-insensitive) new positions are blocked by now, if any
+    pub fn blocking(&self, venue: &str) -> Option<MaintenanceWindow> {
+        let block_before = Duration::from_secs(self.section.read().unwrap().block_before_mins * 60);
+        self.upcoming(block_before).into_iter().find(|w| w.venue.eq_ignore_ascii_case(venue))
+    }
+
+    /// Refuse a new position touching any of `venues` inside a blocking period
+    pub fn check(&self, venues: &[&str]) -> Result<(), RiskRejection> {
+        match venues.iter().find_map(|venue| self.blocking(venue)) {
+            Some(window) => Err(RiskRejection::Maintenance { start: window.start_rfc3339(), venue: window.venue }),
+            None => Ok(()),
+        }
+    }
+
+    /// Open legs on venues with a window running or starting within `alert_before_mins`;
+    /// each (window, leg) is alerted once
+    pub fn sweep(&self, tracker: &PositionTracker) -> Vec<HeldPosition> {
+        let now = self.clock.wall_ns().0;
+        let alert_before = Duration::from_secs(self.section.read().unwrap().alert_before_mins * 60);
+        let windows = self.upcoming(alert_before);
+        let mut held = Vec::new();
+        if windows.is_empty() {
+            return held;
+        }
+        for position in tracker.open_positions() {
+            let legs = [
+                ("kalshi", "yes", &position.kalshi_yes),
+                ("kalshi", "no", &position.kalshi_no),
+                ("polymarket", "yes", &position.poly_yes),
+                ("polymarket", "no", &position.poly_no),
+            ];
+            for (platform, side, leg) in legs.into_iter().filter(|(_, _, leg)| leg.contracts > 1e-9) {
+                let Some(window) = windows.iter().find(|w| w.venue.eq_ignore_ascii_case(platform)) else { continue };
+                held.push(HeldPosition {
+                    market_id: position.market_id.clone(),
+                    description: position.description.clone(),
+                    platform: platform.to_string(),
+                    side: side.to_string(),
+                    contracts: leg.contracts,
+                    window_start: window.start_rfc3339(),
+                    starts_in_secs: (window.start_ns as f64 - now as f64) / 1e9,
+                });
+                let key = (platform.to_string(), window.start_ns, position.market_id.clone(), side.to_string());
+                if self.alerted.lock().unwrap().insert(key) {
+                    let position = held.last().expect("just pushed");
+                    warn!("[MAINT] {} {} {} x{:.0} held into maintenance at {}",
+                          position.platform, position.side, position.market_id, position.contracts, position.window_start);
+                    for hook in &self.hooks {
+                        hook(position);
+                    }
+                }
+            }
+        }
+        held
+    }
+}
+
+/// Check the live tracker against upcoming windows every minute
+pub async fn run_position_sweep(calendar: SharedMaintenanceCalendar, tracker: SharedPositionTracker) {
+    loop {
+        tokio::time::sleep(SWEEP_INTERVAL).await;
+        calendar.sweep(&*tracker.read().await);
+    }
+}
+
+/// Sweeps of the positions another process saves at `path` (see POSITION_FILE)
+pub async fn run_saved_position_sweep(calendar: SharedMaintenanceCalendar, path: PathBuf) {
+    loop {
+        tokio::time::sleep(SWEEP_INTERVAL).await;
+        calendar.sweep(&PositionTracker::load_from(&path));
+    }
+}
+
+/// Windows from Kalshi's exchange schedule
+pub fn kalshi_windows(windows: &[KalshiMaintenanceWindow]) -> Vec<MaintenanceWindow> {
+    windows.iter().filter_map(|w| Some(MaintenanceWindow {
+        venue: "kalshi".to_string(),
+        start_ns: parse_rfc3339(&w.start_datetime)?,
+        end_ns: parse_rfc3339(&w.end_datetime)?,
+        source: "kalshi_api".to_string(),
+    })).collect()
+}
+
+/// Refresh Kalshi's published windows every `refresh_mins` while `kalshi_api` is on
+pub async fn run_kalshi_schedule_sync(calendar: SharedMaintenanceCalendar, kalshi: Arc<KalshiApiClient>) {
+    loop {
+        let section = calendar.section();
+        if !section.kalshi_api {
+            calendar.set_ingested("kalshi", Vec::new());
+        } else {
+            match kalshi.get_maintenance_windows().await {
+                Ok(windows) => {
+                    let windows = kalshi_windows(&windows);
+                    info!("[MAINT] Kalshi schedule: {} maintenance windows", windows.len());
+                    calendar.set_ingested("kalshi", windows);
+                }
+                // Keep the last schedule; it's still the best known
+                Err(e) => warn!("[MAINT] Kalshi schedule fetch failed: {}", e),
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(section.refresh_mins.max(1) * 60)).await;
+    }
+}
+
+/// Keep the windows and lead times in step with `[maintenance]` reloads
+pub fn watch_config(calendar: SharedMaintenanceCalendar, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("maintenance") => calendar.apply_config(&change.config.maintenance),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[MAINT] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use crate::config::MaintenanceRule;
+    use crate::position_tracker::FillRecord;
+
+    /// Thursday 2026-01-01 02:00 UTC
+    const T: u64 = 1_767_232_800;
+
+    fn section() -> MaintenanceSection {
+        MaintenanceSection {
+            windows: vec![
+                // Thursdays 03:00-05:00 UTC
+                MaintenanceRule { venue: "kalshi".to_string(), cron: "0 3 * * 4 UTC".to_string(), duration_mins: 120, ..MaintenanceRule::default() },
+                MaintenanceRule { venue: "polymarket".to_string(), start: "2026-01-02T00:00:00Z".to_string(), duration_mins: 30, ..MaintenanceRule::default() },
+            ],
+            ..MaintenanceSection::default()
+        }
+    }
+
+    #[test]
+    fn test_blocks_new_positions_ahead_of_windows() {
+        let clock = MockClock::shared(Nanos(T * 1_000_000_000));
+        let calendar = MaintenanceCalendar::new(&section()).with_clock(clock.clone());
+        assert_eq!(calendar.upcoming(Duration::from_secs(3600 * 24)).len(), 2);
+
+        // 60 minutes out: clear; 15 minutes out through the window: blocked
+        assert!(calendar.check(&["polymarket", "kalshi"]).is_ok());
+        clock.advance(Duration::from_secs(50 * 60));
+        let rejection = calendar.check(&["polymarket", "KALSHI"]).unwrap_err();
+        assert!(matches!(rejection, RiskRejection::Maintenance { ref venue, .. } if venue == "kalshi"));
+        assert!(calendar.check(&["polymarket"]).is_ok());
+        clock.advance(Duration::from_secs(2 * 3600));
+        assert!(calendar.check(&["kalshi"]).is_err());
+        clock.advance(Duration::from_secs(15 * 60));
+        assert!(calendar.check(&["kalshi"]).is_ok());
+
+        // Ingested windows count as well, and are replaced per venue
+        let now = clock.wall_ns().0;
+        calendar.set_ingested("kalshi", kalshi_windows(&[KalshiMaintenanceWindow {
+            start_datetime: rfc3339(now + 5 * NS_PER_MIN),
+            end_datetime: rfc3339(now + 20 * NS_PER_MIN),
+        }]));
+        assert_eq!(calendar.blocking("kalshi").unwrap().source, "kalshi_api");
+        calendar.set_ingested("kalshi", Vec::new());
+        assert!(calendar.blocking("kalshi").is_none());
+    }
+
+    #[test]
+    fn test_alerts_positions_held_into_windows_once() {
+        let clock = MockClock::shared(Nanos(T * 1_000_000_000));
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let sink = alerts.clone();
+        let calendar = MaintenanceCalendar::new(&section())
+            .with_clock(clock.clone())
+            .on_held(move |p| sink.lock().unwrap().push(p.to_alert()));
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&FillRecord::new("A", "A", "kalshi", "yes", 10.0, 0.40, 0.0, "o1"));
+        tracker.record_fill_internal(&FillRecord::new("B", "B", "polymarket", "no", 5.0, 0.55, 0.0, "o2"));
+
+        // Kalshi's window is an hour out: its leg is held into it, Polymarket's window is a day out
+        let held = calendar.sweep(&tracker);
+        assert_eq!(held.len(), 1);
+        assert_eq!((held[0].market_id.as_str(), held[0].contracts, held[0].starts_in_secs), ("A", 10.0, 3600.0));
+        clock.advance(Duration::from_secs(3600 + 60));
+        assert!(calendar.sweep(&tracker)[0].starts_in_secs < 0.0);
+        assert_eq!(alerts.lock().unwrap().len(), 1);
+
+        // Next week's occurrence is a new window
+        clock.advance(Duration::from_secs(7 * 24 * 3600));
+        calendar.sweep(&tracker);
+        assert_eq!(alerts.lock().unwrap().len(), 2);
+    }
+}
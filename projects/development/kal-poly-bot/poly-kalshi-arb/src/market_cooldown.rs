@@ -0,0 +1,399 @@
+// src/market_cooldown.rs
+// Per-market cooldowns - bench a market after losing streaks, refused orders or suspected limiting, doubling on repeats
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::clock::{self, SharedClock};
+use crate::config::RiskSection;
+use crate::config_reload::ConfigChanged;
+use crate::error::RiskRejection;
+use crate::types::Nanos;
+
+/// Streak thresholds and cooldown lengths (from `[risk]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooldownConfig {
+    /// Consecutive losing arbs (0 = never trips)
+    pub loss_streak: u32,
+    /// Consecutive venue-refused orders
+    pub rejection_streak: u32,
+    /// Consecutive unfilled arbs
+    pub limit_streak: u32,
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl From<&RiskSection> for CooldownConfig {
+    fn from(r: &RiskSection) -> Self {
+        Self {
+            loss_streak: r.market_loss_streak,
+            rejection_streak: r.market_rejection_streak,
+            limit_streak: r.market_limit_streak,
+            base: Duration::from_secs(r.market_cooldown_secs),
+            max: Duration::from_secs(r.market_cooldown_max_secs),
+        }
+    }
+}
+
+impl Default for CooldownConfig {
+    fn default() -> Self {
+        Self::from(&RiskSection::default())
+    }
+}
+
+impl CooldownConfig {
+    /// Length of the `trip`th cooldown in a row (1-based)
+    pub fn duration(&self, trip: u32) -> Duration {
+        let factor = 2u32.saturating_pow(trip.saturating_sub(1));
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// How an arb on a market ended, as far as cooldowns care
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeOutcome {
+    /// Filled at a profit (or break-even); clears every streak and the repeat count
+    Win,
+    Loss,
+    /// A venue refused the order
+    Rejected,
+    /// Nothing filled, typically because the book limited the stake
+    Limited,
+}
+
+/// Streak that benched a market
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CooldownCause {
+    Losses,
+    Rejections,
+    Limited,
+}
+
+impl std::fmt::Display for CooldownCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CooldownCause::Losses => "losses",
+            CooldownCause::Rejections => "rejections",
+            CooldownCause::Limited => "limited",
+        })
+    }
+}
+
+/// Cooldown start and expiry, passed to hooks
+#[derive(Debug, Clone, PartialEq)]
+pub enum CooldownEvent {
+    Started { market: String, cause: CooldownCause, trip: u32, duration: Duration },
+    Expired { market: String },
+}
+
+impl CooldownEvent {
+    /// Structured form for the alert router / event bus (and so the dashboard)
+    pub fn to_alert(&self) -> Alert {
+        match self {
+            CooldownEvent::Started { market, cause, trip, duration } => Alert::new(
+                "cooldown", AlertSeverity::Warning, "market_cooldown",
+                format!("{} benched for {}s after {} (cooldown #{})", market, duration.as_secs(), cause, trip),
+            )
+            .with_market(market),
+            CooldownEvent::Expired { market } => Alert::new(
+                "cooldown", AlertSeverity::Info, "market_cooldown_expired",
+                format!("{} cooldown expired", market),
+            )
+            .with_market(market),
+        }
+    }
+}
+
+/// An active cooldown, as shown on the dashboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketCooldown {
+    pub market: String,
+    pub cause: CooldownCause,
+    /// Cooldowns in a row without a winning arb in between
+    pub trip: u32,
+    /// Unix ns the market trades again
+    pub until_ns: u64,
+    pub remaining_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct MarketState {
+    losses: u32,
+    rejections: u32,
+    limited: u32,
+    trips: u32,
+    /// Active cooldown: cause and mono end
+    cooling: Option<(CooldownCause, Nanos)>,
+}
+
+type EventHook = Arc<dyn Fn(&CooldownEvent) + Send + Sync>;
+
+/// Tracks outcome streaks per market and benches markets that trip one
+pub struct CooldownManager {
+    config: RwLock<CooldownConfig>,
+    clock: SharedClock,
+    markets: Mutex<HashMap<String, MarketState>>,
+    hooks: Vec<EventHook>,
+}
+
+pub type SharedCooldownManager = Arc<CooldownManager>;
+
+impl CooldownManager {
+    pub fn new(config: CooldownConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            clock: clock::system(),
+            markets: Mutex::new(HashMap::new()),
+            hooks: Vec::new(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Register an event hook (called outside the manager lock)
+    pub fn on_event(mut self, hook: impl Fn(&CooldownEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Apply a hot-reloaded `[risk]` section (running cooldowns keep their end)
+    pub fn apply_config(&self, risk: &RiskSection) {
+        *self.config.write().unwrap() = CooldownConfig::from(risk);
+    }
+
+    fn emit(&self, events: Vec<CooldownEvent>) {
+        for event in &events {
+            match event {
+                CooldownEvent::Started { market, cause, trip, duration } => {
+                    warn!("[COOLDOWN] {} benched for {:?} after {} (#{})", market, duration, cause, trip)
+                }
+                CooldownEvent::Expired { market } => info!("[COOLDOWN] {} trading again", market),
+            }
+            for hook in &self.hooks {
+                hook(event);
+            }
+        }
+    }
+
+    /// Reject a market that is cooling down
+    pub fn check(&self, market: &str) -> Result<(), RiskRejection> {
+        let now = self.clock.mono_ns();
+        let mut expired = Vec::new();
+        let result = {
+            let mut markets = self.markets.lock().unwrap();
+            match markets.get_mut(market) {
+                Some(state) => match state.cooling {
+                    Some((_, until)) if until > now => Err(RiskRejection::MarketCooldown {
+                        market: market.to_string(),
+                        remaining: until.saturating_sub(now).as_duration(),
+                    }),
+                    Some(_) => {
+                        state.cooling = None;
+                        expired.push(CooldownEvent::Expired { market: market.to_string() });
+                        Ok(())
+                    }
+                    None => Ok(()),
+                },
+                None => Ok(()),
+            }
+        };
+        self.emit(expired);
+        result
+    }
+
+    /// Count an arb's outcome toward the market's streaks, benching it if one trips
+    pub fn record(&self, market: &str, outcome: TradeOutcome) {
+        let config = self.config.read().unwrap().clone();
+        let now = self.clock.mono_ns();
+        let event = {
+            let mut markets = self.markets.lock().unwrap();
+            let state = markets.entry(market.to_string()).or_default();
+            match outcome {
+                TradeOutcome::Win => *state = MarketState { cooling: state.cooling, ..MarketState::default() },
+                TradeOutcome::Loss => {
+                    state.losses += 1;
+                    state.rejections = 0;
+                    state.limited = 0;
+                }
+                TradeOutcome::Rejected => state.rejections += 1,
+                TradeOutcome::Limited => state.limited += 1,
+            }
+            let tripped = [
+                (CooldownCause::Losses, state.losses, config.loss_streak),
+                (CooldownCause::Rejections, state.rejections, config.rejection_streak),
+                (CooldownCause::Limited, state.limited, config.limit_streak),
+            ]
+            .into_iter()
+            .find(|(_, count, threshold)| *threshold > 0 && count >= threshold);
+            match tripped {
+                Some((cause, _, _)) if state.cooling.is_none_or(|(_, until)| until <= now) => {
+                    state.trips += 1;
+                    state.losses = 0;
+                    state.rejections = 0;
+                    state.limited = 0;
+                    let duration = config.duration(state.trips);
+                    state.cooling = Some((cause, Nanos(now.0.saturating_add(Nanos::from(duration).0))));
+                    Some(CooldownEvent::Started { market: market.to_string(), cause, trip: state.trips, duration })
+                }
+                _ => None,
+            }
+        };
+        self.emit(event.into_iter().collect());
+    }
+
+    /// End cooldowns that have run out (emits their expiry events)
+    pub fn expire_due(&self) {
+        let now = self.clock.mono_ns();
+        let expired: Vec<CooldownEvent> = {
+            let mut markets = self.markets.lock().unwrap();
+            markets
+                .iter_mut()
+                .filter(|(_, state)| state.cooling.is_some_and(|(_, until)| until <= now))
+                .map(|(market, state)| {
+                    state.cooling = None;
+                    CooldownEvent::Expired { market: market.clone() }
+                })
+                .collect()
+        };
+        self.emit(expired);
+    }
+
+    /// Markets cooling down now, longest remaining first
+    pub fn active(&self) -> Vec<MarketCooldown> {
+        let now = self.clock.now();
+        let markets = self.markets.lock().unwrap();
+        let mut active: Vec<MarketCooldown> = markets
+            .iter()
+            .filter_map(|(market, state)| {
+                let (cause, until) = state.cooling.filter(|(_, until)| *until > now.mono)?;
+                let remaining = until.saturating_sub(now.mono);
+                Some(MarketCooldown {
+                    market: market.clone(),
+                    cause,
+                    trip: state.trips,
+                    until_ns: now.wall.0.saturating_add(remaining.0),
+                    remaining_ms: remaining.as_millis().0,
+                })
+            })
+            .collect();
+        active.sort_by(|a, b| b.remaining_ms.cmp(&a.remaining_ms).then_with(|| a.market.cmp(&b.market)));
+        active
+    }
+}
+
+/// Emit expiry events as cooldowns run out, checking every `interval`
+pub async fn run_cooldown_expiry_loop(cooldowns: SharedCooldownManager, interval: Duration) {
+    let mut tick = tokio::time::interval(interval);
+    loop {
+        tick.tick().await;
+        cooldowns.expire_due();
+    }
+}
+
+/// Keep thresholds in step with `[risk]` reloads
+pub fn watch_config(cooldowns: SharedCooldownManager, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("risk") => cooldowns.apply_config(&change.config.risk),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[COOLDOWN] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    fn manager(config: CooldownConfig) -> (CooldownManager, Arc<MockClock>, Arc<Mutex<Vec<CooldownEvent>>>) {
+        let clock = MockClock::shared(Nanos(1_000));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let manager = CooldownManager::new(config)
+            .with_clock(clock.clone())
+            .on_event(move |e| sink.lock().unwrap().push(e.clone()));
+        (manager, clock, events)
+    }
+
+    #[test]
+    fn test_loss_streak_benches_market_until_expiry() {
+        let (cooldowns, clock, events) = manager(CooldownConfig::default());
+        cooldowns.record("EPL-ARS", TradeOutcome::Loss);
+        cooldowns.record("EPL-ARS", TradeOutcome::Loss);
+        // A win in between resets the streak
+        cooldowns.record("EPL-ARS", TradeOutcome::Win);
+        for _ in 0..3 {
+            cooldowns.record("EPL-ARS", TradeOutcome::Loss);
+        }
+        assert!(matches!(cooldowns.check("EPL-ARS"), Err(RiskRejection::MarketCooldown { .. })));
+        assert!(cooldowns.check("NBA-LAL").is_ok());
+        let active = cooldowns.active();
+        assert_eq!((active.len(), active[0].cause, active[0].remaining_ms), (1, CooldownCause::Losses, 60_000));
+
+        clock.advance(Duration::from_secs(60));
+        cooldowns.expire_due();
+        assert!(cooldowns.check("EPL-ARS").is_ok());
+        assert!(cooldowns.active().is_empty());
+        assert_eq!(*events.lock().unwrap(), vec![
+            CooldownEvent::Started {
+                market: "EPL-ARS".to_string(),
+                cause: CooldownCause::Losses,
+                trip: 1,
+                duration: Duration::from_secs(60),
+            },
+            CooldownEvent::Expired { market: "EPL-ARS".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_repeat_cooldowns_double_until_a_win() {
+        let config = CooldownConfig { limit_streak: 2, max: Duration::from_secs(200), ..Default::default() };
+        let (cooldowns, clock, _) = manager(config);
+        for expected in [60, 120, 200, 200] {
+            cooldowns.record("M", TradeOutcome::Limited);
+            cooldowns.record("M", TradeOutcome::Limited);
+            assert_eq!(cooldowns.active()[0].remaining_ms, expected * 1_000);
+            clock.advance(Duration::from_secs(expected));
+            assert!(cooldowns.check("M").is_ok());
+        }
+
+        // A winning arb clears the repeat count
+        cooldowns.record("M", TradeOutcome::Win);
+        cooldowns.record("M", TradeOutcome::Limited);
+        cooldowns.record("M", TradeOutcome::Limited);
+        assert_eq!(cooldowns.active()[0].trip, 1);
+    }
+
+    #[test]
+    fn test_rejections_trip_independently_and_zero_disables() {
+        let config = CooldownConfig { rejection_streak: 2, loss_streak: 0, ..Default::default() };
+        let (cooldowns, _, events) = manager(config);
+        for _ in 0..10 {
+            cooldowns.record("A", TradeOutcome::Loss);
+        }
+        assert!(cooldowns.check("A").is_ok());
+
+        // A fill in between restarts the rejection count
+        cooldowns.record("B", TradeOutcome::Rejected);
+        cooldowns.record("B", TradeOutcome::Win);
+        cooldowns.record("B", TradeOutcome::Rejected);
+        assert!(cooldowns.check("B").is_ok());
+        cooldowns.record("B", TradeOutcome::Rejected);
+        assert!(cooldowns.check("B").is_err());
+        assert_eq!(events.lock().unwrap()[0].to_alert().kind, "market_cooldown");
+    }
+}
@@ -0,0 +1,146 @@
+// src/market_hierarchy.rs
+// Kalshi series -> event -> market hierarchy: limits and correlations declared on a series or
+// event (`[risk.groups]`) and inherited by the markets under it
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::GroupRule;
+use crate::types::{KalshiPath, MarketPair};
+
+/// Group rules plus the path of every market they may apply to, keyed the way the trading
+/// breaker keys positions (pair id)
+#[derive(Debug, Clone, Default)]
+pub struct MarketHierarchy {
+    paths: HashMap<String, KalshiPath>,
+    rules: BTreeMap<String, GroupRule>,
+}
+
+impl MarketHierarchy {
+    pub fn new(rules: BTreeMap<String, GroupRule>) -> Self {
+        Self { paths: HashMap::new(), rules }
+    }
+
+    /// Rules plus the Kalshi path of each discovered pair
+    pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = &'a MarketPair>, rules: BTreeMap<String, GroupRule>) -> Self {
+        let mut hierarchy = Self::new(rules);
+        for pair in pairs {
+            hierarchy.register(&pair.pair_id, pair.kalshi_path());
+        }
+        hierarchy
+    }
+
+    /// Swap in reloaded `[risk.groups]`; registered paths are kept
+    pub fn set_rules(&mut self, rules: BTreeMap<String, GroupRule>) {
+        self.rules = rules;
+    }
+
+    pub fn register(&mut self, market_id: &str, path: KalshiPath) {
+        self.paths.insert(market_id.to_string(), path);
+    }
+
+    pub fn path(&self, market_id: &str) -> Option<&KalshiPath> {
+        self.paths.get(market_id)
+    }
+
+    /// No group rules declared (limits are the flat `[risk]` ones)
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Declared groups containing the market, series before event
+    pub fn groups_of(&self, market_id: &str) -> Vec<(&str, &GroupRule)> {
+        let Some(path) = self.paths.get(market_id) else { return Vec::new() };
+        path.groups().into_iter()
+            .filter_map(|group| self.rules.get_key_value(group))
+            .map(|(group, rule)| (group.as_str(), rule))
+            .collect()
+    }
+
+    pub fn in_group(&self, market_id: &str, group: &str) -> bool {
+        self.paths.get(market_id).is_some_and(|path| path.groups().contains(&group))
+    }
+
+    /// Rule the market inherits: each field from its event's rule when set, else its series'
+    pub fn rule_for(&self, market_id: &str) -> GroupRule {
+        self.groups_of(market_id).into_iter().fold(GroupRule::default(), |inherited, (_, rule)| GroupRule {
+            max_position: nonzero_or(rule.max_position, inherited.max_position),
+            max_position_per_market: nonzero_or(rule.max_position_per_market, inherited.max_position_per_market),
+            correlation: if rule.correlation > 0.0 { rule.correlation } else { inherited.correlation },
+        })
+    }
+
+    /// Declared correlation of two markets: 1 for the same market, their shared event's (else
+    /// series') rule for siblings, 0 for unrelated or unregistered markets
+    pub fn correlation(&self, a: &str, b: &str) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+        let (Some(pa), Some(pb)) = (self.paths.get(a), self.paths.get(b)) else { return 0.0 };
+        if pa.series != pb.series {
+            return 0.0;
+        }
+        let series = self.rules.get(&*pa.series).map_or(0.0, |r| r.correlation);
+        match self.rules.get(&*pa.event) {
+            Some(event) if pa.event == pb.event && event.correlation > 0.0 => event.correlation,
+            _ => series,
+        }
+    }
+}
+
+fn nonzero_or(value: i64, fallback: i64) -> i64 {
+    if value != 0 { value } else { fallback }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIES: &str = "KXNBAGAME";
+    const GAME: &str = "KXNBAGAME-25DEC01LALBOS";
+
+    fn hierarchy() -> MarketHierarchy {
+        let mut rules = BTreeMap::new();
+        rules.insert(SERIES.to_string(), GroupRule { max_position: 500, max_position_per_market: 200, correlation: 0.2 });
+        rules.insert(GAME.to_string(), GroupRule { max_position: 100, correlation: 0.9, ..GroupRule::default() });
+        let mut hierarchy = MarketHierarchy::new(rules);
+        for (id, ticker) in [
+            ("lal", "KXNBAGAME-25DEC01LALBOS-LAL"),
+            ("bos", "KXNBAGAME-25DEC01LALBOS-BOS"),
+            ("nyk", "KXNBAGAME-25DEC02NYKMIA-NYK"),
+            ("epl", "KXEPLGAME-25DEC27CFCAVL-CFC"),
+        ] {
+            hierarchy.register(id, KalshiPath::from_market_ticker(ticker).unwrap());
+        }
+        hierarchy
+    }
+
+    #[test]
+    fn test_event_rule_overrides_series_per_field() {
+        let hierarchy = hierarchy();
+        assert_eq!(hierarchy.groups_of("lal").iter().map(|(g, _)| *g).collect::<Vec<_>>(), vec![SERIES, GAME]);
+        assert_eq!(
+            hierarchy.rule_for("lal"),
+            GroupRule { max_position: 100, max_position_per_market: 200, correlation: 0.9 },
+        );
+        // Only the series applies to another game
+        assert_eq!(hierarchy.rule_for("nyk").max_position, 500);
+        assert_eq!(hierarchy.rule_for("epl"), GroupRule::default());
+        assert_eq!(hierarchy.rule_for("unregistered"), GroupRule::default());
+        assert!(hierarchy.in_group("bos", GAME) && !hierarchy.in_group("nyk", GAME));
+    }
+
+    #[test]
+    fn test_correlation_follows_closest_shared_group() {
+        let mut hierarchy = hierarchy();
+        assert_eq!(hierarchy.correlation("lal", "lal"), 1.0);
+        assert_eq!(hierarchy.correlation("lal", "bos"), 0.9);
+        assert_eq!(hierarchy.correlation("lal", "nyk"), 0.2);
+        assert_eq!(hierarchy.correlation("lal", "epl"), 0.0);
+        assert_eq!(hierarchy.correlation("lal", "unregistered"), 0.0);
+
+        // Reloaded rules apply to the registered markets
+        hierarchy.set_rules(BTreeMap::new());
+        assert!(hierarchy.is_empty());
+        assert_eq!(hierarchy.correlation("lal", "bos"), 0.0);
+    }
+}
@@ -0,0 +1,223 @@
+// src/market_impact.rs
+// Square-root market impact per market tier and venue, fitted to TCA fills - sizes orders and prices backtest fills
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::error::StateStoreError;
+use crate::latency_arbitrage::MarketTier;
+use crate::tca::{SharedTcaStore, TcaFill, TcaStore};
+use crate::types::Platform;
+
+/// Impact coefficient used until a venue has enough fills: 0.1¢ per contract at 1 contract, 1¢ at 100
+pub const DEFAULT_IMPACT_COEFF: f64 = 0.1;
+/// Ceiling for fits skewed by a few terrible fills
+const MAX_IMPACT_COEFF: f64 = 5.0;
+/// Fills a tier (or venue) needs in the lookback before its own fit replaces the fallback
+const MIN_FILLS: usize = 20;
+const LOOKBACK_DAYS: u64 = 7;
+const REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+const NS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+
+const TIERS: [MarketTier; 4] = [MarketTier::Tier1, MarketTier::Tier2, MarketTier::Tier3, MarketTier::Tier4];
+
+/// Tier of a fill's `tier` number (1-4)
+fn tier_of(number: u8) -> Option<MarketTier> {
+    TIERS.get(usize::from(number).checked_sub(1)?).copied()
+}
+
+/// Impact fitted to a set of fills: slippage past the touch ≈ `coeff` × √contracts (cents per contract)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ImpactFit {
+    pub fills: usize,
+    pub coeff: f64,
+}
+
+impl ImpactFit {
+    /// Least squares through the origin of slippage vs arrival on √contracts; price improvement
+    /// never makes size look free, so the coefficient floors at 0
+    pub fn from_fills(fills: &[&TcaFill]) -> Self {
+        let (xy, xx) = fills.iter()
+            .filter(|f| f.contracts > 0.0)
+            .fold((0.0, 0.0), |(xy, xx), f| (xy + f.contracts.sqrt() * f.slippage_vs_arrival(), xx + f.contracts));
+        let coeff = if xx > 0.0 { (xy / xx).clamp(0.0, MAX_IMPACT_COEFF) } else { DEFAULT_IMPACT_COEFF };
+        Self { fills: fills.len(), coeff }
+    }
+}
+
+/// Expected impact (cents per contract) of trading `contracts` at coefficient `coeff`
+pub fn impact_cents(coeff: f64, contracts: f64) -> f64 {
+    coeff * contracts.max(0.0).sqrt()
+}
+
+/// Size that makes the most of an `edge_cents` per-contract edge once impact is paid:
+/// q·(edge − k√q) peaks at √q = 2·edge / 3k, where impact has taken a third of the edge
+pub fn optimal_contracts(coeff: f64, edge_cents: f64) -> f64 {
+    if edge_cents <= 0.0 {
+        0.0
+    } else if coeff <= 0.0 {
+        f64::INFINITY
+    } else {
+        (2.0 * edge_cents / (3.0 * coeff)).powi(2)
+    }
+}
+
+/// Impact coefficients per tier and venue; tiers without enough fills use their venue's fit,
+/// venues without enough fills the default
+#[derive(Debug)]
+pub struct MarketImpact {
+    table: RwLock<HashMap<(MarketTier, Platform), ImpactFit>>,
+    venues: RwLock<HashMap<Platform, ImpactFit>>,
+}
+
+pub type SharedMarketImpact = Arc<MarketImpact>;
+
+impl Default for MarketImpact {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarketImpact {
+    pub fn new() -> Self {
+        Self { table: RwLock::new(HashMap::new()), venues: RwLock::new(HashMap::new()) }
+    }
+
+    /// Impact coefficient for `platform` in a `tier` market
+    pub fn coeff(&self, tier: MarketTier, platform: Platform) -> f64 {
+        if let Some(fit) = self.table.read().unwrap().get(&(tier, platform)) {
+            return fit.coeff;
+        }
+        self.venues.read().unwrap().get(&platform).map_or(DEFAULT_IMPACT_COEFF, |fit| fit.coeff)
+    }
+
+    /// Expected impact (cents per contract) of trading `contracts` on `platform` in a `tier` market
+    pub fn impact_cents(&self, tier: MarketTier, platform: Platform, contracts: f64) -> f64 {
+        impact_cents(self.coeff(tier, platform), contracts)
+    }
+
+    /// Contracts worth trading against an `edge_cents` per-contract edge (see [`optimal_contracts`])
+    pub fn optimal_contracts(&self, tier: MarketTier, platform: Platform, edge_cents: f64) -> f64 {
+        optimal_contracts(self.coeff(tier, platform), edge_cents)
+    }
+
+    /// Venue-level fits, for venues with enough fills
+    pub fn venue_fits(&self) -> HashMap<Platform, ImpactFit> {
+        self.venues.read().unwrap().clone()
+    }
+
+    /// Refit from fills; fills recorded without a tier count towards their venue's fit only
+    pub fn refresh(&self, fills: &[TcaFill]) {
+        let mut by_venue: HashMap<Platform, Vec<&TcaFill>> = HashMap::new();
+        let mut by_tier: HashMap<(MarketTier, Platform), Vec<&TcaFill>> = HashMap::new();
+        for fill in fills {
+            by_venue.entry(fill.platform).or_default().push(fill);
+            if let Some(tier) = fill.tier.and_then(tier_of) {
+                by_tier.entry((tier, fill.platform)).or_default().push(fill);
+            }
+        }
+
+        let fit = |fills: Vec<&TcaFill>| (fills.len() >= MIN_FILLS).then(|| ImpactFit::from_fills(&fills));
+        let venues: HashMap<_, _> = by_venue.into_iter().filter_map(|(k, f)| Some((k, fit(f)?))).collect();
+        let table: HashMap<_, _> = by_tier.into_iter().filter_map(|(k, f)| Some((k, fit(f)?))).collect();
+        for (platform, venue) in &venues {
+            info!("[IMPACT] {} fills={} k={:.3}¢/√contract ({} tier fits)",
+                  platform, venue.fills, venue.coeff, table.keys().filter(|(_, p)| p == platform).count());
+        }
+        *self.table.write().unwrap() = table;
+        *self.venues.write().unwrap() = venues;
+    }
+
+    /// Refresh from the trailing week of fills in the TCA store
+    pub fn refresh_from(&self, store: &TcaStore) -> Result<(), StateStoreError> {
+        let to_ns = store.now_ns() + 1;
+        let fills = store.load(to_ns.saturating_sub(LOOKBACK_DAYS * NS_PER_DAY), to_ns)?;
+        self.refresh(&fills);
+        Ok(())
+    }
+}
+
+/// Refit the impact model now and then once a day
+pub async fn run_impact_refresh_loop(impact: SharedMarketImpact, store: SharedTcaStore) {
+    loop {
+        if let Err(e) = impact.refresh_from(&store) {
+            warn!("[IMPACT] Refresh failed, keeping previous fits: {}", e);
+        }
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A buy of `contracts` filled `slippage` cents past a 50¢ touch
+    fn fill(platform: Platform, tier: Option<u8>, contracts: f64, slippage: f64) -> TcaFill {
+        TcaFill {
+            ts_ns: 0,
+            market_id: "M1".into(),
+            platform,
+            tier,
+            side: "yes".into(),
+            action: "buy".into(),
+            pattern: "74".into(),
+            strategy_id: None,
+            contracts,
+            decision_price: 50.0,
+            arrival_price: 50.0,
+            arrival_mid: 49.5,
+            fill_price: 50.0 + slippage,
+            decision_to_arrival_ns: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_fit_recovers_square_root_coefficient() {
+        let fills: Vec<_> = [1.0, 4.0, 25.0, 100.0].iter().map(|&q| fill(Platform::Kalshi, None, q, 0.3 * f64::sqrt(q))).collect();
+        let fit = ImpactFit::from_fills(&fills.iter().collect::<Vec<_>>());
+        assert_eq!(fit.fills, 4);
+        assert!((fit.coeff - 0.3).abs() < 1e-9);
+
+        // Price improvement floors at zero impact
+        let better = [fill(Platform::Kalshi, None, 10.0, -1.0)];
+        assert_eq!(ImpactFit::from_fills(&better.iter().collect::<Vec<_>>()).coeff, 0.0);
+    }
+
+    #[test]
+    fn test_refresh_prefers_tier_then_venue_then_default() {
+        let impact = MarketImpact::new();
+        let mut fills: Vec<_> = (0..MIN_FILLS).map(|_| fill(Platform::Kalshi, Some(1), 4.0, 1.0)).collect();
+        fills.extend((0..MIN_FILLS).map(|_| fill(Platform::Kalshi, None, 4.0, 0.2)));
+        // Too few fills of its own
+        fills.extend((0..5).map(|_| fill(Platform::Kalshi, Some(3), 4.0, 3.0)));
+        fills.extend((0..5).map(|_| fill(Platform::Polymarket, Some(1), 4.0, 3.0)));
+        impact.refresh(&fills);
+
+        assert!((impact.coeff(MarketTier::Tier1, Platform::Kalshi) - 0.5).abs() < 1e-9);
+        // Tier 3 and 4 fall back to the venue's fit over all 45 fills
+        let venue = impact.venue_fits()[&Platform::Kalshi];
+        assert_eq!(venue.fills, 2 * MIN_FILLS + 5);
+        assert_eq!(impact.coeff(MarketTier::Tier3, Platform::Kalshi), venue.coeff);
+        assert_eq!(impact.coeff(MarketTier::Tier4, Platform::Kalshi), venue.coeff);
+        assert_eq!(impact.coeff(MarketTier::Tier1, Platform::Polymarket), DEFAULT_IMPACT_COEFF);
+
+        impact.refresh(&[]);
+        assert_eq!(impact.coeff(MarketTier::Tier1, Platform::Kalshi), DEFAULT_IMPACT_COEFF);
+    }
+
+    #[test]
+    fn test_optimal_size_leaves_two_thirds_of_the_edge() {
+        let q = optimal_contracts(0.2, 3.0);
+        assert!((q - 100.0).abs() < 1e-9);
+        assert!((impact_cents(0.2, q) - 2.0).abs() < 1e-9);
+        // Bigger or smaller trades make less
+        let profit = |q: f64| q * (3.0 - impact_cents(0.2, q));
+        assert!(profit(q) > profit(80.0) && profit(q) > profit(120.0));
+
+        assert_eq!(optimal_contracts(0.2, 0.0), 0.0);
+        assert_eq!(optimal_contracts(0.0, 3.0), f64::INFINITY);
+    }
+}
@@ -0,0 +1,577 @@
+// src/market_maker.rs
+// Liquidity provisioning - two-sided quotes on wide, thin Kalshi books around the MM-compression filter's fair value
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::circuit_breaker::TradingCircuitBreaker;
+use crate::clock::{self, SharedClock};
+use crate::config::{MarketMakingSection, RiskSection};
+use crate::config_reload::ConfigChanged;
+use crate::error::VenueApiError;
+use crate::kalman_filter_suite::{KalmanFilterTrait, MmCompressionKF, Regime};
+use crate::kalshi::KalshiApiClient;
+use crate::observation_prefilter::SharedObservationPrefilters;
+use crate::tick_sanitizer::{SharedTickSanitizer, Tick};
+use crate::types::{GlobalState, MarketId, Nanos, Platform, PriceCents, SizeCents};
+
+const QUOTE_INTERVAL: Duration = Duration::from_secs(1);
+/// Quote ticks between inventory syncs from Kalshi positions
+const SYNC_EVERY: u32 = 10;
+/// Observations before the filter's fair value and regime are trusted
+const WARMUP: u32 = 10;
+/// Pattern whose `[worker.prefilters]` rule the book mids go through (MM Compression)
+const MM_PATTERN: u16 = 76;
+
+/// Quoting parameters (from `[market_making]`, inventory capped by `[risk]`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MakerConfig {
+    pub enabled: bool,
+    pub min_spread: PriceCents,
+    pub max_touch_size: i64,
+    pub half_spread: f64,
+    pub quote_size: i64,
+    /// `max_inventory`, never above `risk.max_position_per_market`
+    pub inventory_cap: i64,
+    pub skew: f64,
+    pub steam_pull: Duration,
+    pub requote: PriceCents,
+}
+
+impl MakerConfig {
+    pub fn new(mm: &MarketMakingSection, risk: &RiskSection) -> Self {
+        Self {
+            enabled: mm.enabled,
+            min_spread: mm.min_spread_cents,
+            max_touch_size: mm.max_touch_size,
+            half_spread: mm.half_spread_cents,
+            quote_size: mm.quote_size,
+            inventory_cap: mm.max_inventory.min(risk.max_position_per_market),
+            skew: mm.skew_cents,
+            steam_pull: Duration::from_secs(mm.steam_pull_secs),
+            requote: mm.requote_cents,
+        }
+    }
+
+    /// Wide enough to earn the spread, thin enough that nobody else is making it
+    pub fn wants(&self, book: &YesBook) -> bool {
+        book.spread() >= self.min_spread
+            && book.bid_size.max(book.ask_size) as i64 <= self.max_touch_size
+    }
+
+    /// Quotes around `fair` (cents) shifted against `inventory` (net YES contracts), kept
+    /// inside the book so they rest; the side that would take inventory past the cap is dropped
+    pub fn quote(&self, fair: f64, inventory: i64, book: &YesBook) -> Quote {
+        let cap = self.inventory_cap.max(1);
+        let reservation = fair - self.skew * (inventory as f64 / cap as f64).clamp(-1.0, 1.0);
+        let bid = (reservation - self.half_spread).floor().min(book.ask as f64 - 1.0).max(1.0) as PriceCents;
+        let ask = (reservation + self.half_spread).ceil().max(book.bid as f64 + 1.0).min(99.0) as PriceCents;
+        if bid >= ask {
+            return Quote::default();
+        }
+        Quote {
+            bid: (inventory + self.quote_size <= cap).then_some(bid),
+            ask: (inventory - self.quote_size >= -cap).then_some(ask),
+        }
+    }
+}
+
+/// Kalshi top of book in YES terms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YesBook {
+    pub bid: PriceCents,
+    pub ask: PriceCents,
+    pub bid_size: SizeCents,
+    pub ask_size: SizeCents,
+}
+
+impl YesBook {
+    /// From the stored asks (the YES bid is 100 - NO ask); None unless both sides are quoted
+    pub fn from_asks(yes_ask: PriceCents, no_ask: PriceCents, yes_size: SizeCents, no_size: SizeCents) -> Option<Self> {
+        if !(1..100).contains(&yes_ask) || !(1..100).contains(&no_ask) {
+            return None;
+        }
+        Some(Self { bid: 100 - no_ask, ask: yes_ask, bid_size: no_size, ask_size: yes_size })
+    }
+
+    pub fn spread(&self) -> PriceCents {
+        self.ask.saturating_sub(self.bid)
+    }
+
+    pub fn mid(&self) -> f64 {
+        (self.bid as f64 + self.ask as f64) / 2.0
+    }
+}
+
+/// Prices we want resting on each side (YES cents)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Quote {
+    pub bid: Option<PriceCents>,
+    pub ask: Option<PriceCents>,
+}
+
+/// A quote side; the YES ask rests as a NO bid at 100 - ask
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteSide {
+    Bid,
+    Ask,
+}
+
+impl QuoteSide {
+    /// Kalshi side and price to buy for a quote at `price` (YES cents)
+    pub fn order(self, price: PriceCents) -> (&'static str, i64) {
+        match self {
+            QuoteSide::Bid => ("yes", price as i64),
+            QuoteSide::Ask => ("no", 100 - price as i64),
+        }
+    }
+}
+
+/// Venue calls the quote loop should make, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuoteAction {
+    Cancel { market: MarketId, side: QuoteSide, order_id: String },
+    Place { market: MarketId, pair_id: Arc<str>, ticker: Arc<str>, side: QuoteSide, price: PriceCents },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Resting {
+    order_id: String,
+    price: PriceCents,
+}
+
+/// What to do with one side: cancel what rests, place the target, or both (requote)
+fn side_actions(resting: Option<&Resting>, target: Option<PriceCents>, requote: PriceCents) -> (bool, Option<PriceCents>) {
+    match (resting, target) {
+        (Some(r), Some(price)) if r.price.abs_diff(price) < requote => (false, None),
+        (Some(_), target) => (true, target),
+        (None, target) => (false, target),
+    }
+}
+
+/// A quoted market, as shown on the dashboard
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MakerStatus {
+    pub ticker: String,
+    pub fair_value: f64,
+    pub regime: &'static str,
+    pub inventory: i64,
+    pub bid: Option<PriceCents>,
+    pub ask: Option<PriceCents>,
+    pub pulled: bool,
+}
+
+struct MakerMarket {
+    ticker: Arc<str>,
+    filter: MmCompressionKF,
+    observations: u32,
+    last_mid: Option<(f64, Nanos)>,
+    fair: f64,
+    inventory: i64,
+    /// Inventory moved since the quotes were placed, so they may have filled
+    stale: bool,
+    bid: Option<Resting>,
+    ask: Option<Resting>,
+    /// Mono time quotes may return after the detector last saw steam
+    pulled_until: Nanos,
+}
+
+impl MakerMarket {
+    fn new(ticker: Arc<str>) -> Self {
+        Self {
+            ticker,
+            filter: MmCompressionKF::new(QUOTE_INTERVAL.as_secs_f64()),
+            observations: 0,
+            last_mid: None,
+            fair: 0.0,
+            inventory: 0,
+            stale: false,
+            bid: None,
+            ask: None,
+            pulled_until: Nanos::ZERO,
+        }
+    }
+
+    /// Feed the book mid to the filter and its velocity to the regime detector
+    fn observe(&mut self, mid: f64, now: Nanos) {
+        let velocity = match self.last_mid {
+            Some((last, at)) if now > at => (mid - last) / now.saturating_sub(at).as_duration().as_secs_f64(),
+            Some(_) => 0.0,
+            None => {
+                self.filter.base.x[0] = mid;
+                0.0
+            }
+        };
+        self.filter.predict();
+        if let Err(e) = self.filter.update(&[mid]) {
+            warn!("[MM] {} filter update failed: {}", self.ticker, e);
+            return;
+        }
+        self.filter.base.detect_regime(velocity);
+        self.last_mid = Some((mid, now));
+        self.fair = self.filter.base.get_position();
+        self.observations = self.observations.saturating_add(1);
+    }
+
+    /// Steam (or a suspension) pulls the quotes until `steam_pull` after it was last seen
+    fn pulled(&mut self, regime: Regime, now: Nanos, steam_pull: Duration) -> bool {
+        if regime != Regime::Quiet {
+            if now >= self.pulled_until {
+                info!("[MM] {} {}: quotes pulled", self.ticker, regime.as_str());
+            }
+            self.pulled_until = Nanos(now.0.saturating_add(Nanos::from(steam_pull).0));
+        }
+        now < self.pulled_until
+    }
+
+    fn resting(&mut self, side: QuoteSide) -> &mut Option<Resting> {
+        match side {
+            QuoteSide::Bid => &mut self.bid,
+            QuoteSide::Ask => &mut self.ask,
+        }
+    }
+}
+
+/// Quotes wide Kalshi books and tracks the resting orders and inventory behind them
+pub struct MarketMaker {
+    config: RwLock<MakerConfig>,
+    clock: SharedClock,
+    markets: Mutex<HashMap<MarketId, MakerMarket>>,
+    /// Books failing the sanity bounds are neither fed to the filter nor quoted
+    sanitizer: Option<SharedTickSanitizer>,
+    /// Mids are smoothed or dropped per #76's pre-filter rule before the filter sees them
+    prefilters: Option<SharedObservationPrefilters>,
+}
+
+pub type SharedMarketMaker = Arc<MarketMaker>;
+
+impl MarketMaker {
+    pub fn new(config: MakerConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            clock: clock::system(),
+            markets: Mutex::new(HashMap::new()),
+            sanitizer: None,
+            prefilters: None,
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_sanitizer(mut self, sanitizer: SharedTickSanitizer) -> Self {
+        self.sanitizer = Some(sanitizer);
+        self
+    }
+
+    pub fn with_prefilters(mut self, prefilters: SharedObservationPrefilters) -> Self {
+        self.prefilters = Some(prefilters);
+        self
+    }
+
+    /// Whether the book mid passes the sanity bounds (always, without a sanitizer)
+    fn sane(&self, market: MarketId, book: &YesBook) -> bool {
+        let Some(sanitizer) = &self.sanitizer else { return true };
+        let tick = Tick {
+            provider: Platform::Kalshi.into(),
+            market_id: market.0,
+            price: book.mid(),
+            size: book.bid_size.min(book.ask_size) as f64,
+            timestamp_ns: None,
+        };
+        sanitizer.check(&tick).is_ok()
+    }
+
+    /// Apply hot-reloaded `[market_making]` / `[risk]` sections; disabling pulls every quote
+    pub fn apply_config(&self, mm: &MarketMakingSection, risk: &RiskSection) {
+        let config = MakerConfig::new(mm, risk);
+        info!("[MM] {} - spread >= {}¢, touch <= {}¢, ±{}¢ x{} contracts, inventory cap {}",
+              if config.enabled { "Quoting" } else { "Off" }, config.min_spread, config.max_touch_size,
+              config.half_spread, config.quote_size, config.inventory_cap);
+        *self.config.write().unwrap() = config;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.read().unwrap().enabled
+    }
+
+    /// Replace inventory with venue positions (signed YES contracts by ticker)
+    pub fn sync_inventory<'a>(&self, positions: impl IntoIterator<Item = (&'a str, i64)>) {
+        let positions: HashMap<&str, i64> = positions.into_iter().collect();
+        for market in self.markets.lock().unwrap().values_mut() {
+            let inventory = positions.get(&*market.ticker).copied().unwrap_or(0);
+            if inventory != market.inventory {
+                debug!("[MM] {} inventory {} -> {}", market.ticker, market.inventory, inventory);
+                market.inventory = inventory;
+                market.stale = true;
+            }
+        }
+    }
+
+    /// Observe every Kalshi book and work out the cancels and placements that bring the
+    /// resting quotes in line with what each market should show now
+    pub fn plan(&self, state: &GlobalState) -> Vec<QuoteAction> {
+        let config = self.config.read().unwrap().clone();
+        let now = self.clock.mono_ns();
+        let mut cancels = Vec::new();
+        let mut places = Vec::new();
+        let mut markets = self.markets.lock().unwrap();
+
+        for slot in &state.markets[..state.market_count()] {
+            let Some(pair) = &slot.pair else { continue };
+            let (yes_ask, no_ask, yes_size, no_size) = slot.kalshi.load();
+            let book = YesBook::from_asks(yes_ask, no_ask, yes_size, no_size).filter(|b| self.sane(slot.market_id, b));
+            let wanted = config.enabled && book.is_some_and(|b| config.wants(&b));
+            if !wanted && !markets.contains_key(&slot.market_id) {
+                continue;
+            }
+            let market = markets
+                .entry(slot.market_id)
+                .or_insert_with(|| MakerMarket::new(pair.kalshi_market_ticker.clone()));
+
+            let mut target = Quote::default();
+            if let Some(book) = book {
+                let mid = match &self.prefilters {
+                    Some(prefilters) => prefilters.filter(MM_PATTERN, &market.ticker, book.mid()),
+                    None => Some(book.mid()),
+                };
+                if let Some(mid) = mid {
+                    market.observe(mid, now);
+                }
+                let pulled = market.pulled(market.filter.get_regime(), now, config.steam_pull);
+                if wanted && !pulled && market.observations >= WARMUP && market.fair.is_finite() {
+                    target = config.quote(market.fair, market.inventory, &book);
+                }
+            }
+
+            let stale = std::mem::take(&mut market.stale);
+            for (side, price) in [(QuoteSide::Bid, target.bid), (QuoteSide::Ask, target.ask)] {
+                let resting = market.resting(side).as_ref();
+                let (cancel, place) = if stale && resting.is_some() {
+                    (true, price)
+                } else {
+                    side_actions(resting, price, config.requote)
+                };
+                if cancel {
+                    let order_id = market.resting(side).as_ref().map(|r| r.order_id.clone()).unwrap_or_default();
+                    cancels.push(QuoteAction::Cancel { market: slot.market_id, side, order_id });
+                }
+                if let Some(price) = place {
+                    places.push(QuoteAction::Place {
+                        market: slot.market_id,
+                        pair_id: pair.pair_id.clone(),
+                        ticker: market.ticker.clone(),
+                        side,
+                        price,
+                    });
+                }
+            }
+        }
+        cancels.extend(places);
+        cancels
+    }
+
+    /// A quote side is resting (or its cancel has not gone through)
+    pub fn is_resting(&self, market: MarketId, side: QuoteSide) -> bool {
+        self.markets.lock().unwrap().get_mut(&market).is_some_and(|m| m.resting(side).is_some())
+    }
+
+    pub fn placed(&self, market: MarketId, side: QuoteSide, order_id: String, price: PriceCents) {
+        if let Some(m) = self.markets.lock().unwrap().get_mut(&market) {
+            *m.resting(side) = Some(Resting { order_id, price });
+        }
+    }
+
+    pub fn cancelled(&self, market: MarketId, side: QuoteSide) {
+        if let Some(m) = self.markets.lock().unwrap().get_mut(&market) {
+            *m.resting(side) = None;
+        }
+    }
+
+    /// Every resting quote, as cancels
+    pub fn resting_orders(&self) -> Vec<QuoteAction> {
+        let markets = self.markets.lock().unwrap();
+        markets
+            .iter()
+            .flat_map(|(&market, m)| {
+                [(QuoteSide::Bid, &m.bid), (QuoteSide::Ask, &m.ask)].into_iter().filter_map(move |(side, r)| {
+                    r.as_ref().map(|r| QuoteAction::Cancel { market, side, order_id: r.order_id.clone() })
+                })
+            })
+            .collect()
+    }
+
+    /// Quoted markets, by ticker
+    pub fn status(&self) -> Vec<MakerStatus> {
+        let now = self.clock.mono_ns();
+        let markets = self.markets.lock().unwrap();
+        let mut status: Vec<MakerStatus> = markets
+            .values()
+            .map(|m| MakerStatus {
+                ticker: m.ticker.to_string(),
+                fair_value: m.fair,
+                regime: m.filter.get_regime().as_str(),
+                inventory: m.inventory,
+                bid: m.bid.as_ref().map(|r| r.price),
+                ask: m.ask.as_ref().map(|r| r.price),
+                pulled: now < m.pulled_until,
+            })
+            .collect();
+        status.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+        status
+    }
+
+    pub fn quote_size(&self) -> i64 {
+        self.config.read().unwrap().quote_size
+    }
+}
+
+/// Already filled or cancelled on the venue
+fn order_gone(e: &VenueApiError) -> bool {
+    matches!(e, VenueApiError::Http { status: 404, .. })
+}
+
+/// Cancel every resting quote (shutdown); returns how many could not be cancelled
+pub async fn pull_all(maker: &MarketMaker, client: &KalshiApiClient) -> usize {
+    let mut failed = 0;
+    for action in maker.resting_orders() {
+        let QuoteAction::Cancel { market, side, order_id } = action else { continue };
+        match client.cancel_order(&order_id).await {
+            Ok(_) => maker.cancelled(market, side),
+            Err(e) if order_gone(&e) => maker.cancelled(market, side),
+            Err(e) => {
+                warn!("[MM] Cancel {} failed: {}", order_id, e);
+                failed += 1;
+            }
+        }
+    }
+    failed
+}
+
+/// Requote every second; inventory comes from Kalshi positions every `SYNC_EVERY` ticks.
+/// Placements go through the trading circuit breaker, so halts and position limits pull quotes too.
+pub async fn run_market_maker_loop(
+    maker: SharedMarketMaker,
+    state: Arc<GlobalState>,
+    client: Arc<KalshiApiClient>,
+    breaker: Arc<TradingCircuitBreaker>,
+) {
+    let mut tick = tokio::time::interval(QUOTE_INTERVAL);
+    let mut ticks: u32 = 0;
+    loop {
+        tick.tick().await;
+        if ticks.is_multiple_of(SYNC_EVERY) && maker.enabled() {
+            match client.get_positions().await {
+                Ok(positions) => maker.sync_inventory(positions.iter().map(|p| (p.ticker.as_str(), p.position))),
+                Err(e) => warn!("[MM] Position sync failed, keeping inventory: {}", e),
+            }
+        }
+        ticks = ticks.wrapping_add(1);
+
+        let quote_size = maker.quote_size();
+        for action in maker.plan(&state) {
+            match action {
+                QuoteAction::Cancel { market, side, order_id } => match client.cancel_order(&order_id).await {
+                    Ok(_) => maker.cancelled(market, side),
+                    Err(e) if order_gone(&e) => maker.cancelled(market, side),
+                    Err(e) => warn!("[MM] Cancel {} failed: {}", order_id, e),
+                },
+                QuoteAction::Place { market, pair_id, ticker, side, price } => {
+                    if maker.is_resting(market, side) {
+                        continue;
+                    }
+                    if let Err(reason) = breaker.can_execute(&pair_id, quote_size).await {
+                        debug!("[MM] {} {:?} held back: {}", ticker, side, reason);
+                        continue;
+                    }
+                    let (kalshi_side, kalshi_price) = side.order(price);
+                    match client.buy_resting(&ticker, kalshi_side, kalshi_price, quote_size).await {
+                        Ok(resp) => maker.placed(market, side, resp.order.order_id, price),
+                        Err(e) => warn!("[MM] {} {:?} @{}¢ refused: {}", ticker, side, price, e),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keep quoting parameters in step with `[market_making]` / `[risk]` reloads
+pub fn watch_config(maker: SharedMarketMaker, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("market_making") || change.touches("risk") => {
+                    maker.apply_config(&change.config.market_making, &change.config.risk)
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[MM] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> MakerConfig {
+        let mm = MarketMakingSection { enabled: true, ..MarketMakingSection::default() };
+        MakerConfig::new(&mm, &RiskSection::default())
+    }
+
+    #[test]
+    fn test_quotes_skew_against_inventory_and_respect_caps() {
+        let config = config();
+        let book = YesBook { bid: 40, ask: 52, bid_size: 1000, ask_size: 1000 };
+        assert_eq!(config.quote(46.0, 0, &book), Quote { bid: Some(44), ask: Some(48) });
+        // Long 25 of a 50 cap: both quotes drop 1.5¢ so the bid fills less and the ask more
+        assert_eq!(config.quote(46.0, 25, &book), Quote { bid: Some(42), ask: Some(47) });
+        // Another 10 would take us past the cap: only the ask is shown
+        assert_eq!(config.quote(46.0, 45, &book), Quote { bid: None, ask: Some(46) });
+        // Never cross the book: a fair value above the ask still bids below it
+        assert_eq!(config.quote(60.0, 0, &book).bid, Some(51));
+
+        let tight_risk = RiskSection { max_position_per_market: 20, ..RiskSection::default() };
+        assert_eq!(MakerConfig::new(&MarketMakingSection::default(), &tight_risk).inventory_cap, 20);
+    }
+
+    #[test]
+    fn test_only_wide_thin_books_are_quoted() {
+        let config = config();
+        // YES ask 52, NO ask 60 -> YES bid 40
+        let wide = YesBook::from_asks(52, 60, 1000, 800).unwrap();
+        assert_eq!((wide.bid, wide.ask, wide.bid_size), (40, 52, 800));
+        assert!(config.wants(&wide));
+        assert!(!config.wants(&YesBook::from_asks(45, 57, 1000, 1000).unwrap()));
+        assert!(!config.wants(&YesBook { bid_size: 20000, ..wide }));
+        assert_eq!(YesBook::from_asks(52, 0, 1000, 0), None);
+        assert_eq!(QuoteSide::Ask.order(52), ("no", 48));
+    }
+
+    #[test]
+    fn test_steam_pulls_quotes_and_requotes_on_moves() {
+        let mut market = MakerMarket::new("KXTEST-A".into());
+        let pull = Duration::from_secs(30);
+        assert!(!market.pulled(Regime::Quiet, Nanos(1_000), pull));
+        assert!(market.pulled(Regime::Steam, Nanos(2_000), pull));
+        // Quiet again, but inside the pull window
+        assert!(market.pulled(Regime::Quiet, Nanos(2_000 + 29_000_000_000), pull));
+        assert!(!market.pulled(Regime::Quiet, Nanos(2_000 + 30_000_000_000), pull));
+
+        let resting = Resting { order_id: "o1".into(), price: 44 };
+        assert_eq!(side_actions(Some(&resting), Some(44), 1), (false, None));
+        assert_eq!(side_actions(Some(&resting), Some(43), 1), (true, Some(43)));
+        assert_eq!(side_actions(Some(&resting), Some(43), 2), (false, None));
+        assert_eq!(side_actions(Some(&resting), None, 1), (true, None));
+        assert_eq!(side_actions(None, Some(45), 1), (false, Some(45)));
+    }
+}
@@ -0,0 +1,213 @@
+// src/market_metadata.rs
+// Per-market metadata - market type, sport and event - and the half-life tier each market is
+// modelled at. Tiers are classified from the market type and the depth its feeds quote unless
+// pinned with `set_tier`. One registry is shared by the feed aggregator, which tags updates with
+// their tier, and the latency arbitrage engine, which pairs markets by event and type.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::interner::Symbol;
+use crate::latency_arbitrage::MarketTier;
+use crate::types::{MarketType, SizeCents};
+
+/// Top-of-book size (cents) below which a market is modelled a tier slower
+pub const DEFAULT_THIN_LIQUIDITY: f64 = 200.0;
+
+/// Weight of each new quote in a market's smoothed depth
+const LIQUIDITY_ALPHA: f64 = 0.1;
+
+/// Tier a market of `market_type` propagates at: core lines first, then derived lines, props and
+/// alt lines, and combos last. A book thinner than `thin_liquidity` moves a tier slower.
+pub fn classify_tier(market_type: MarketType, liquidity: Option<f64>, thin_liquidity: f64) -> MarketTier {
+    let tier = match market_type {
+        MarketType::Moneyline | MarketType::Spread | MarketType::Total | MarketType::Btts => MarketTier::Tier1,
+        MarketType::TeamTotal | MarketType::HalfTotal | MarketType::QuarterTotal => MarketTier::Tier2,
+        MarketType::PlayerProp | MarketType::AltLine => MarketTier::Tier3,
+        MarketType::Combo => MarketTier::Tier4,
+    };
+    match liquidity {
+        Some(depth) if depth < thin_liquidity => slower(tier),
+        _ => tier,
+    }
+}
+
+fn slower(tier: MarketTier) -> MarketTier {
+    match tier {
+        MarketTier::Tier1 => MarketTier::Tier2,
+        MarketTier::Tier2 => MarketTier::Tier3,
+        MarketTier::Tier3 | MarketTier::Tier4 => MarketTier::Tier4,
+    }
+}
+
+/// What's known about one market
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketMetadata {
+    pub market_type: MarketType,
+    pub sport: Option<Symbol>,
+    pub event_id: Option<Symbol>,
+    /// Smoothed top-of-book size in cents (None until a feed quotes the market)
+    pub liquidity: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct Markets {
+    metadata: HashMap<u16, MarketMetadata>,
+    /// Tiers pinned with `set_tier`
+    pinned: HashMap<u16, MarketTier>,
+}
+
+/// Market metadata by engine market id
+#[derive(Debug)]
+pub struct MarketMetadataRegistry {
+    markets: RwLock<Markets>,
+    thin_liquidity: f64,
+}
+
+pub type SharedMarketMetadata = Arc<MarketMetadataRegistry>;
+
+impl Default for MarketMetadataRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_THIN_LIQUIDITY)
+    }
+}
+
+impl MarketMetadataRegistry {
+    pub fn new(thin_liquidity: f64) -> Self {
+        Self { markets: RwLock::new(Markets::default()), thin_liquidity }
+    }
+
+    /// Register (or re-describe) a market; depth already seen is kept
+    pub fn register(&self, market_id: u16, market_type: MarketType, sport: Option<&str>, event_id: Option<&str>) {
+        let mut markets = self.markets.write().unwrap();
+        let liquidity = markets.metadata.get(&market_id).and_then(|m| m.liquidity);
+        markets.metadata.insert(market_id, MarketMetadata {
+            market_type,
+            sport: sport.map(Symbol::intern),
+            event_id: event_id.map(Symbol::intern),
+            liquidity,
+        });
+    }
+
+    /// Record a market's type as a feed reports it, unless it's already registered
+    pub fn ensure(&self, market_id: u16, market_type: MarketType) {
+        if self.markets.read().unwrap().metadata.contains_key(&market_id) {
+            return;
+        }
+        self.markets.write().unwrap().metadata.entry(market_id).or_insert(MarketMetadata {
+            market_type,
+            sport: None,
+            event_id: None,
+            liquidity: None,
+        });
+    }
+
+    /// Fold a quote's depth (the deeper side of its top of book) into the market's liquidity
+    pub fn observe(&self, market_id: u16, market_type: MarketType, yes_size: SizeCents, no_size: SizeCents) {
+        let depth = yes_size.max(no_size) as f64;
+        let mut markets = self.markets.write().unwrap();
+        let market = markets.metadata.entry(market_id).or_insert(MarketMetadata {
+            market_type,
+            sport: None,
+            event_id: None,
+            liquidity: None,
+        });
+        market.liquidity = Some(match market.liquidity {
+            Some(liquidity) => liquidity + LIQUIDITY_ALPHA * (depth - liquidity),
+            None => depth,
+        });
+    }
+
+    /// Pin a market's tier, overriding classification
+    pub fn set_tier(&self, market_id: u16, tier: MarketTier) {
+        self.markets.write().unwrap().pinned.insert(market_id, tier);
+    }
+
+    pub fn get(&self, market_id: u16) -> Option<MarketMetadata> {
+        self.markets.read().unwrap().metadata.get(&market_id).cloned()
+    }
+
+    pub fn market_type(&self, market_id: u16) -> Option<MarketType> {
+        self.markets.read().unwrap().metadata.get(&market_id).map(|m| m.market_type)
+    }
+
+    /// Pinned or classified tier (None for a market never registered, quoted or pinned)
+    pub fn tier(&self, market_id: u16) -> Option<MarketTier> {
+        let markets = self.markets.read().unwrap();
+        markets.pinned.get(&market_id).copied().or_else(|| {
+            markets.metadata.get(&market_id)
+                .map(|m| classify_tier(m.market_type, m.liquidity, self.thin_liquidity))
+        })
+    }
+
+    /// Tier of a market quoted as `market_type`, classified from the type alone when unknown
+    pub fn tier_for(&self, market_id: u16, market_type: MarketType) -> MarketTier {
+        self.tier(market_id).unwrap_or_else(|| classify_tier(market_type, None, self.thin_liquidity))
+    }
+
+    /// Whether two markets belong to the same event (None unless both events are known)
+    pub fn same_event(&self, a: u16, b: u16) -> Option<bool> {
+        let markets = self.markets.read().unwrap();
+        let event = |id| markets.metadata.get(&id).and_then(|m| m.event_id);
+        Some(event(a)? == event(b)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.markets.read().unwrap().metadata.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiers_classify_from_type_and_liquidity() {
+        let registry = MarketMetadataRegistry::new(200.0);
+        registry.register(1, MarketType::Moneyline, Some("nba"), Some("NBA-LAL-BOS"));
+        registry.register(2, MarketType::PlayerProp, Some("nba"), Some("NBA-LAL-BOS"));
+        assert_eq!(registry.tier(1), Some(MarketTier::Tier1));
+        assert_eq!(registry.tier(2), Some(MarketTier::Tier3));
+        assert_eq!(registry.tier(3), None);
+        assert_eq!(registry.tier_for(3, MarketType::HalfTotal), MarketTier::Tier2);
+
+        // A thin book propagates a tier slower until depth builds up
+        registry.observe(1, MarketType::Moneyline, 50, 0);
+        assert_eq!(registry.tier(1), Some(MarketTier::Tier2));
+        for _ in 0..40 {
+            registry.observe(1, MarketType::Moneyline, 1_000, 800);
+        }
+        assert_eq!(registry.tier(1), Some(MarketTier::Tier1));
+        // Re-registering keeps the depth seen
+        registry.register(1, MarketType::Moneyline, Some("nba"), Some("NBA-LAL-BOS"));
+        assert!(registry.get(1).unwrap().liquidity.unwrap() > 900.0);
+
+        // Pinned tiers win
+        registry.set_tier(2, MarketTier::Tier4);
+        assert_eq!(registry.tier_for(2, MarketType::PlayerProp), MarketTier::Tier4);
+        assert_eq!(classify_tier(MarketType::Combo, Some(0.0), 200.0), MarketTier::Tier4);
+    }
+
+    #[test]
+    fn test_feed_types_and_events() {
+        let registry = MarketMetadataRegistry::default();
+        registry.register(1, MarketType::TeamTotal, Some("nba"), Some("NBA-LAL-BOS"));
+        registry.register(2, MarketType::PlayerProp, Some("nba"), Some("NBA-LAL-BOS"));
+        registry.register(3, MarketType::Total, Some("nfl"), Some("NFL-KC-BUF"));
+        // A feed's type never overrides a registered one
+        registry.ensure(1, MarketType::Moneyline);
+        registry.ensure(4, MarketType::Spread);
+        assert_eq!(registry.market_type(1), Some(MarketType::TeamTotal));
+        assert_eq!(registry.market_type(4), Some(MarketType::Spread));
+        assert_eq!(registry.len(), 4);
+
+        assert_eq!(registry.same_event(1, 2), Some(true));
+        assert_eq!(registry.same_event(1, 3), Some(false));
+        assert_eq!(registry.same_event(1, 4), None);
+        assert_eq!(registry.get(3).unwrap().sport.map(Symbol::as_str), Some("nfl"));
+    }
+}
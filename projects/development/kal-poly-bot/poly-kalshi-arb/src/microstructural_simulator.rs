@@ -2,63 +2,22 @@
 //!
 //! Tick-accurate simulator that accounts for latency, slippage, and account limits.
 //! Integrates with Kalman Filter Suite for pattern detection and execution simulation.
+//! Also generates synthetic multi-venue tick streams (`SyntheticMarketGenerator`)
+//! for the optimizer and backtester when no captured data is available.
+//! Optional agent populations (`AgentMarket`) make fills and book limiting
+//! respond to the bot's own activity. The bundle layout itself lives in `tick_bundle`.
 
 use crate::kalman_filter_suite::*;
 use crate::types::{TimestampNs, PriceCents, MarketType, Platform};
 use crate::tick_sim_backtester::{TradeRecord, Position};
+pub use crate::tick_bundle::{market_key, GameContext, SyncedTickBundle, TickData};
+use crate::feature_flags::SharedFeatureFlags;
+use crate::interner::Symbol;
 use std::collections::{HashMap, VecDeque};
 use serde::{Serialize, Deserialize};
 use tracing::{info, warn, debug, error};
-use rand::{thread_rng, Rng};
-
-/// Synchronized tick bundle for multi-market patterns
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SyncedTickBundle {
-    /// Timestamp in nanoseconds
-    pub timestamp_ns: TimestampNs,
-    /// HT market data (if available)
-    pub ht: Option<TickData>,
-    /// FT market data (if available)
-    pub ft: Option<TickData>,
-    /// Multiple market data for propagation patterns
-    pub multiple_markets: Option<HashMap<String, TickData>>,
-    /// Time remaining in game (for in-play patterns)
-    pub time_remaining: Option<f64>,
-    /// Game context
-    pub game_context: Option<GameContext>,
-}
-
-/// Individual tick data
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TickData {
-    /// Market identifier
-    pub market_id: String,
-    /// Platform/bookmaker
-    pub platform: Platform,
-    /// Price/line value
-    pub price: f64,
-    /// Size/liquidity
-    pub size: f64,
-    /// Price delta from previous tick
-    pub price_delta: f64,
-    /// Book identifier
-    pub book: String,
-}
-
-/// Game context information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameContext {
-    /// Sport type
-    pub sport: String,
-    /// Teams playing
-    pub teams: (String, String),
-    /// Current period
-    pub period: u8,
-    /// Time remaining in seconds
-    pub time_remaining: f64,
-    /// Score
-    pub score: (u16, u16),
-}
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
 
 /// Trade trigger generated by pattern detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +28,8 @@ pub struct Trigger {
     pub book: String,
     /// Target price
     pub target_price: f64,
+    /// Quoted price when the trigger fired
+    pub market_price: f64,
     /// Theoretical maximum edge
     pub theoretical_max: f64,
     /// Window duration in seconds
@@ -201,7 +162,7 @@ pub struct MicrostructuralSimulator {
     /// Trade log
     pub trade_log: Vec<TradeRecord>,
     /// Current positions
-    pub positions: HashMap<String, Position>,
+    pub positions: HashMap<Symbol, Position>,
     /// Equity curve
     pub equity_curve: Vec<EquityPoint>,
     /// Current capital
@@ -210,6 +171,10 @@ pub struct MicrostructuralSimulator {
     pub start_capital: f64,
     /// Pattern statistics
     pub pattern_stats: HashMap<u16, PatternStats>,
+    /// Agent populations that react to our fills (None = static slippage model)
+    pub agents: Option<AgentMarket>,
+    /// Triggers from flagged-off patterns are dropped (None = all enabled)
+    pub feature_flags: Option<SharedFeatureFlags>,
 }
 
 /// Simulation configuration
@@ -415,9 +380,23 @@ impl MicrostructuralSimulator {
             current_capital: config.initial_capital,
             start_capital: config.initial_capital,
             pattern_stats: HashMap::new(),
+            agents: None,
+            feature_flags: None,
         }
     }
 
+    /// Fill against agent populations instead of the static slippage model
+    pub fn with_agents(mut self, config: AgentPopulationConfig) -> Self {
+        self.agents = Some(AgentMarket::new(config));
+        self
+    }
+
+    /// Gate pattern triggers on feature flags; filters keep tracking while gated
+    pub fn with_feature_flags(mut self, flags: SharedFeatureFlags) -> Self {
+        self.feature_flags = Some(flags);
+        self
+    }
+
     /// Add pattern filter
     pub fn add_pattern_filter(&mut self, pattern_id: u16, filter: Box<dyn KalmanFilterTrait>) {
         self.filters.insert(pattern_id, filter);
@@ -446,6 +425,13 @@ impl MicrostructuralSimulator {
                 continue;
             }
 
+            if let Some(agents) = self.agents.as_mut() {
+                agents.advance(effective_tick.timestamp_ns);
+                for data in effective_tick.multiple_markets.iter().flat_map(|m| m.values()) {
+                    agents.observe(&data.book, data.price);
+                }
+            }
+
             // Update all filters and detect triggers
             let triggers = self.evaluate_pattern_triggers(&effective_tick).await?;
 
@@ -503,6 +489,7 @@ impl MicrostructuralSimulator {
                     pattern: 51,
                     book: ft_data.book.clone(),
                     target_price: predicted_ft,
+                    market_price: ft_data.price,
                     theoretical_max: edge * 100.0,
                     window_duration: 30.0,
                     size,
@@ -542,6 +529,7 @@ impl MicrostructuralSimulator {
                                     pattern: 68,
                                     book: props_data.book.clone(),
                                     target_price: predicted_props,
+                                    market_price: props_data.price,
                                     theoretical_max: edge * 100.0,
                                     window_duration: delay,
                                     size,
@@ -584,6 +572,7 @@ impl MicrostructuralSimulator {
                             pattern: 75,
                             book: market_data.book.clone(),
                             target_price: predicted_price,
+                            market_price: market_data.price,
                             theoretical_max: edge * 100.0,
                             window_duration: 5.0,
                             size,
@@ -596,13 +585,17 @@ impl MicrostructuralSimulator {
             }
         }
 
+        if let Some(flags) = &self.feature_flags {
+            triggers.retain(|trigger| flags.component_enabled(trigger.pattern));
+        }
+
         Ok(triggers)
     }
 
     /// Execute trade based on trigger
     async fn execute_trade(&mut self, trigger: Trigger) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
         // Check account health
-        let account_health = self.account_health.get_mut(&trigger.book)
+        let account_health = self.account_health.get(&trigger.book)
             .ok_or(format!("No account health for book: {}", trigger.book))?;
 
         if !account_health.can_trade() {
@@ -610,18 +603,41 @@ impl MicrostructuralSimulator {
         }
 
         // Apply size multiplier based on account limits
-        let adjusted_size = trigger.size * account_health.get_size_multiplier();
+        let mut adjusted_size = trigger.size * account_health.get_size_multiplier();
+
+        // Books profiling our fills may limit us independently
+        if let Some(agents) = &self.agents {
+            adjusted_size *= match agents.limit_status(&trigger.book) {
+                LimitStatus::None => 1.0,
+                LimitStatus::Soft => 0.5,
+                LimitStatus::Hard => 0.0,
+            };
+        }
 
         if adjusted_size <= 0.0 {
             return Ok(false);
         }
 
-        // Simulate execution with slippage
-        let execution_price = self.simulate_slippage(&trigger.book, trigger.target_price).await?;
+        let direction: i8 = if trigger.target_price >= trigger.market_price { 1 } else { -1 };
         let execution_latency = self.latency_model.get_latency(&trigger.book);
 
+        // Agents fill against maker quotes (size capped by maker inventory);
+        // otherwise fall back to the static slippage model
+        let (execution_price, edge_captured) = match self.agents.as_mut() {
+            Some(agents) => {
+                let Some(fill) = agents.fill(&trigger.book, direction, adjusted_size, trigger.market_price, trigger.timestamp_ns) else {
+                    return Ok(false);
+                };
+                adjusted_size = fill.size;
+                (fill.price, (trigger.target_price - fill.price) * f64::from(direction))
+            }
+            None => {
+                let price = self.simulate_slippage(&trigger.book, trigger.target_price).await?;
+                (price, (price - trigger.target_price).abs())
+            }
+        };
+
         // Calculate P&L
-        let edge_captured = (execution_price - trigger.target_price).abs();
         let theoretical_edge = trigger.expected_edge;
         let edge_capture_rate = if theoretical_edge > 0.0 {
             edge_captured / theoretical_edge
@@ -636,8 +652,8 @@ impl MicrostructuralSimulator {
 
         // Create position (simplified)
         let position = Position {
-            market_id: format!("pattern_{}_{}", trigger.pattern, trigger.book),
-            direction: if execution_price > trigger.target_price { 1 } else { -1 },
+            market_id: Symbol::intern(&format!("pattern_{}_{}", trigger.pattern, trigger.book)),
+            direction,
             size: adjusted_size,
             entry_price: execution_price,
             entry_timestamp_ns: trigger.timestamp_ns,
@@ -645,7 +661,7 @@ impl MicrostructuralSimulator {
             expected_alpha: trigger.expected_edge,
         };
 
-        self.positions.insert(position.market_id.clone(), position);
+        self.positions.insert(position.market_id, position);
 
         // Record trade
         let trade_record = TradeRecord {
@@ -659,7 +675,9 @@ impl MicrostructuralSimulator {
         self.trade_log.push(trade_record.clone());
 
         // Update account health
-        account_health.update_trade(pnl, &self.config);
+        if let Some(account_health) = self.account_health.get_mut(&trigger.book) {
+            account_health.update_trade(pnl, &self.config);
+        }
 
         // Update pattern statistics
         let stats = self.pattern_stats.entry(trigger.pattern).or_default();
@@ -865,6 +883,799 @@ impl MicrostructuralSimulator {
     }
 }
 
+// === Agent populations ===
+
+/// Market maker quoting one book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketMakerConfig {
+    pub book: String,
+    /// Half-spread charged at zero inventory (price units)
+    pub half_spread: f64,
+    /// Quote skew per unit of inventory; makers lean away from what they hold
+    pub skew_per_unit: f64,
+    /// Largest absolute inventory the maker will carry
+    pub inventory_limit: f64,
+    /// Fraction of inventory hedged away per second
+    pub unwind_rate: f64,
+}
+
+impl Default for MarketMakerConfig {
+    fn default() -> Self {
+        Self {
+            book: String::new(),
+            half_spread: 0.5,
+            skew_per_unit: 0.002,
+            inventory_limit: 2000.0,
+            unwind_rate: 0.01,
+        }
+    }
+}
+
+/// Uninformed flow that trades against the makers at random
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecreationalFlowConfig {
+    /// Trades per book per minute
+    pub trades_per_minute: f64,
+    /// Mean trade size (exponentially distributed)
+    pub mean_size: f64,
+    /// Share of trades that buy
+    pub buy_bias: f64,
+}
+
+impl Default for RecreationalFlowConfig {
+    fn default() -> Self {
+        Self { trades_per_minute: 30.0, mean_size: 20.0, buy_bias: 0.5 }
+    }
+}
+
+/// Sharp bettors who watch our fills and pile in behind them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SharpBettorConfig {
+    pub count: u32,
+    /// Chance each sharp copies a given fill of ours
+    pub follow_prob: f64,
+    /// Mean delay before a copy hits the book (milliseconds)
+    pub mean_reaction_ms: f64,
+    /// Copy size as a multiple of our fill
+    pub size_multiple: f64,
+}
+
+impl Default for SharpBettorConfig {
+    fn default() -> Self {
+        Self { count: 3, follow_prob: 0.2, mean_reaction_ms: 800.0, size_multiple: 1.0 }
+    }
+}
+
+/// How books fingerprint and limit us from our own fills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfilingConfig {
+    /// Recent fills per book the book looks at
+    pub window: usize,
+    /// A fill is graded this long after it happened (seconds)
+    pub grade_after_s: f64,
+    /// Fills graded before the book acts
+    pub min_graded: usize,
+    /// Share of fills beating the later price that triggers soft / hard limits
+    pub soft_win_rate: f64,
+    pub hard_win_rate: f64,
+    /// Fill-size coefficient of variation below which sizing looks automated
+    pub min_size_cv: f64,
+    /// Added to the win rate when sizing looks automated
+    pub automation_penalty: f64,
+}
+
+impl Default for ProfilingConfig {
+    fn default() -> Self {
+        Self {
+            window: 50,
+            grade_after_s: 60.0,
+            min_graded: 20,
+            soft_win_rate: 0.6,
+            hard_win_rate: 0.75,
+            min_size_cv: 0.1,
+            automation_penalty: 0.1,
+        }
+    }
+}
+
+/// Agent populations around the simulated books
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentPopulationConfig {
+    pub seed: u64,
+    /// Per-book makers; books not listed get `MarketMakerConfig::default()`
+    pub market_makers: Vec<MarketMakerConfig>,
+    pub recreational: RecreationalFlowConfig,
+    pub sharps: SharpBettorConfig,
+    pub profiling: ProfilingConfig,
+}
+
+impl Default for AgentPopulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            market_makers: Vec::new(),
+            recreational: RecreationalFlowConfig::default(),
+            sharps: SharpBettorConfig::default(),
+            profiling: ProfilingConfig::default(),
+        }
+    }
+}
+
+/// Our fill as the agents saw it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AgentFill {
+    pub price: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, Clone)]
+struct MakerState {
+    config: MarketMakerConfig,
+    /// Maker's net position (positive = long)
+    inventory: f64,
+}
+
+impl MakerState {
+    /// Price the maker fills `direction` at (1 = we buy, -1 = we sell)
+    fn quote(&self, reference: f64, direction: i8) -> f64 {
+        reference + f64::from(direction) * self.config.half_spread - self.config.skew_per_unit * self.inventory
+    }
+
+    /// Size the maker can take on before hitting its limit
+    fn capacity(&self, direction: i8) -> f64 {
+        (self.config.inventory_limit + f64::from(direction) * self.inventory).max(0.0)
+    }
+
+    /// A counterparty trades `size` in `direction`; the maker takes the other side
+    fn absorb(&mut self, direction: i8, size: f64) {
+        let limit = self.config.inventory_limit;
+        self.inventory = (self.inventory - f64::from(direction) * size).clamp(-limit, limit);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PendingFlow {
+    at_ns: TimestampNs,
+    book: String,
+    direction: i8,
+    size: f64,
+}
+
+#[derive(Debug, Clone)]
+struct BotFill {
+    timestamp_ns: TimestampNs,
+    price: f64,
+    direction: i8,
+    size: f64,
+    /// Whether the price later moved our way; None until graded
+    won: Option<bool>,
+}
+
+/// Makers, recreational flow and sharps reacting to the bot's own fills
+///
+/// Our fills move maker inventory (and so their quotes), draw delayed copy
+/// trades from sharps, and feed each book's profile of us; books then limit
+/// us the way a sportsbook would, from closing-line wins and sizing patterns.
+pub struct AgentMarket {
+    config: AgentPopulationConfig,
+    rng: StdRng,
+    makers: HashMap<String, MakerState>,
+    pending: Vec<PendingFlow>,
+    fills: HashMap<String, VecDeque<BotFill>>,
+    last_price: HashMap<String, f64>,
+    last_ns: Option<TimestampNs>,
+}
+
+impl AgentMarket {
+    pub fn new(config: AgentPopulationConfig) -> Self {
+        let makers = config.market_makers.iter()
+            .map(|m| (m.book.clone(), MakerState { config: m.clone(), inventory: 0.0 }))
+            .collect();
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            makers,
+            pending: Vec::new(),
+            fills: HashMap::new(),
+            last_price: HashMap::new(),
+            last_ns: None,
+            config,
+        }
+    }
+
+    fn maker(&mut self, book: &str) -> &mut MakerState {
+        self.makers.entry(book.to_string()).or_insert_with(|| MakerState {
+            config: MarketMakerConfig { book: book.to_string(), ..MarketMakerConfig::default() },
+            inventory: 0.0,
+        })
+    }
+
+    /// Maker inventory on a book (positive = long)
+    pub fn inventory(&self, book: &str) -> f64 {
+        self.makers.get(book).map_or(0.0, |m| m.inventory)
+    }
+
+    /// Latest quoted price on a book; our past fills are graded against it
+    pub fn observe(&mut self, book: &str, price: f64) {
+        self.maker(book);
+        self.last_price.insert(book.to_string(), price);
+    }
+
+    /// Run agent flow up to `now`: maker unwinds, recreational trades,
+    /// due sharp copies, and grading of our older fills
+    pub fn advance(&mut self, now: TimestampNs) {
+        let dt_s = self.last_ns.map_or(0.0, |last| now.saturating_sub(last) as f64 / 1e9);
+        self.last_ns = Some(now);
+
+        if dt_s > 0.0 {
+            let flow = self.config.recreational.clone();
+            let books: Vec<String> = self.makers.keys().cloned().collect();
+            for book in books {
+                let expected = flow.trades_per_minute * dt_s / 60.0;
+                let mut trades = expected.floor() as u32;
+                if self.rng.gen_bool(expected.fract()) {
+                    trades += 1;
+                }
+                for _ in 0..trades {
+                    let direction = if self.rng.gen_bool(flow.buy_bias.clamp(0.0, 1.0)) { 1 } else { -1 };
+                    let u: f64 = self.rng.gen_range(f64::EPSILON..1.0);
+                    let size = -u.ln() * flow.mean_size;
+                    self.maker(&book).absorb(direction, size);
+                }
+                let maker = self.maker(&book);
+                maker.inventory *= (1.0 - maker.config.unwind_rate).clamp(0.0, 1.0).powf(dt_s);
+            }
+        }
+
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|p| p.at_ns <= now);
+        self.pending = pending;
+        for flow in due {
+            debug!("[AGENTS] Sharp copy {} {:+} x{:.0}", flow.book, flow.direction, flow.size);
+            self.maker(&flow.book).absorb(flow.direction, flow.size);
+        }
+
+        let grade_after_ns = (self.config.profiling.grade_after_s * 1e9) as TimestampNs;
+        for (book, fills) in self.fills.iter_mut() {
+            let Some(&price) = self.last_price.get(book) else { continue };
+            for fill in fills.iter_mut().filter(|f| f.won.is_none()) {
+                if now.saturating_sub(fill.timestamp_ns) >= grade_after_ns {
+                    fill.won = Some((price - fill.price) * f64::from(fill.direction) > 0.0);
+                }
+            }
+        }
+    }
+
+    /// Fill our order of `size` in `direction` against the book's maker
+    ///
+    /// Returns None when the maker has no capacity left on that side.
+    pub fn fill(&mut self, book: &str, direction: i8, size: f64, reference: f64, now: TimestampNs) -> Option<AgentFill> {
+        let maker = self.maker(book);
+        let filled = size.min(maker.capacity(direction));
+        if filled <= 0.0 {
+            return None;
+        }
+        let price = maker.quote(reference, direction);
+        maker.absorb(direction, filled);
+
+        let sharps = self.config.sharps.clone();
+        for _ in 0..sharps.count {
+            if sharps.follow_prob > 0.0 && self.rng.gen_bool(sharps.follow_prob.min(1.0)) {
+                let u: f64 = self.rng.gen_range(f64::EPSILON..1.0);
+                let delay_ns = (-u.ln() * sharps.mean_reaction_ms * 1e6) as TimestampNs;
+                self.pending.push(PendingFlow {
+                    at_ns: now + delay_ns,
+                    book: book.to_string(),
+                    direction,
+                    size: filled * sharps.size_multiple,
+                });
+            }
+        }
+
+        let window = self.config.profiling.window.max(1);
+        let fills = self.fills.entry(book.to_string()).or_default();
+        fills.push_back(BotFill { timestamp_ns: now, price, direction, size: filled, won: None });
+        while fills.len() > window {
+            fills.pop_front();
+        }
+
+        Some(AgentFill { price, size: filled })
+    }
+
+    /// How the book currently treats us, from its profile of our fills
+    pub fn limit_status(&self, book: &str) -> LimitStatus {
+        let profiling = &self.config.profiling;
+        let Some(fills) = self.fills.get(book) else {
+            return LimitStatus::None;
+        };
+
+        let graded: Vec<bool> = fills.iter().filter_map(|f| f.won).collect();
+        if graded.len() < profiling.min_graded.max(1) {
+            return LimitStatus::None;
+        }
+        let mut score = graded.iter().filter(|w| **w).count() as f64 / graded.len() as f64;
+
+        // Near-constant sizing reads as a bot
+        let n = fills.len() as f64;
+        let mean = fills.iter().map(|f| f.size).sum::<f64>() / n;
+        let var = fills.iter().map(|f| (f.size - mean).powi(2)).sum::<f64>() / n;
+        if mean > 0.0 && var.sqrt() / mean < profiling.min_size_cv {
+            score += profiling.automation_penalty;
+        }
+
+        if score >= profiling.hard_win_rate {
+            LimitStatus::Hard
+        } else if score >= profiling.soft_win_rate {
+            LimitStatus::Soft
+        } else {
+            LimitStatus::None
+        }
+    }
+}
+
+// === Synthetic market generator ===
+
+/// One venue quoting the synthetic market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyntheticVenue {
+    /// Platform the ticks are attributed to
+    pub platform: Platform,
+    /// Book identifier (matches `LatencyModel` / account health keys)
+    pub book: String,
+    /// Market type for consumers that need one (backtester)
+    pub market_type: MarketType,
+    /// Team / player the market is on, for prop patterns (#73)
+    pub team_id: Option<String>,
+    pub player_id: Option<String>,
+    /// How far this venue trails the true price (milliseconds)
+    pub lag_ms: f64,
+    /// Constant offset from the true price
+    pub basis: f64,
+    /// Quote noise standard deviation (price units)
+    pub noise: f64,
+    /// Price grid; 0 disables rounding
+    pub tick_size: f64,
+    /// Resting size at the top of book in the neutral regime
+    pub base_depth: f64,
+}
+
+impl Default for SyntheticVenue {
+    fn default() -> Self {
+        Self {
+            platform: Platform::Kalshi,
+            book: "kalshi".to_string(),
+            market_type: MarketType::Moneyline,
+            team_id: None,
+            player_id: None,
+            lag_ms: 0.0,
+            basis: 0.0,
+            noise: 0.2,
+            tick_size: 1.0,
+            base_depth: 1000.0,
+        }
+    }
+}
+
+/// Market state the true price process switches between
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketRegime {
+    pub name: String,
+    /// Drift (price units per second)
+    pub drift: f64,
+    /// Diffusion (price units per sqrt second)
+    pub volatility: f64,
+    /// Chance of a price jump on any one step
+    pub jump_prob: f64,
+    /// Jump magnitude (price units, random sign)
+    pub jump_size: f64,
+    /// Scales every venue's book depth
+    pub depth_multiplier: f64,
+    /// Mean time spent in this regime (seconds, exponentially distributed)
+    pub mean_duration_s: f64,
+    /// Relative chance of being chosen at a switch
+    pub weight: f64,
+}
+
+impl Default for MarketRegime {
+    fn default() -> Self {
+        Self {
+            name: "quiet".to_string(),
+            drift: 0.0,
+            volatility: 0.3,
+            jump_prob: 0.0,
+            jump_size: 0.0,
+            depth_multiplier: 1.0,
+            mean_duration_s: 600.0,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Top-of-book depth dynamics (log-depth mean reverts around the base)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DepthModel {
+    /// Mean-reversion speed (per second)
+    pub reversion: f64,
+    /// Log-depth volatility (per sqrt second)
+    pub volatility: f64,
+    /// Fraction of depth pulled per price unit the venue just moved
+    pub move_sensitivity: f64,
+}
+
+impl Default for DepthModel {
+    fn default() -> Self {
+        Self { reversion: 0.5, volatility: 0.3, move_sensitivity: 0.2 }
+    }
+}
+
+/// Venue suspensions (no quotes while suspended)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuspensionModel {
+    /// Spontaneous suspensions per venue per hour
+    pub rate_per_hour: f64,
+    /// Chance each venue suspends when the true price jumps
+    pub on_jump_prob: f64,
+    /// Mean suspension length (seconds, exponentially distributed)
+    pub mean_duration_s: f64,
+}
+
+impl Default for SuspensionModel {
+    fn default() -> Self {
+        Self { rate_per_hour: 0.5, on_jump_prob: 0.3, mean_duration_s: 20.0 }
+    }
+}
+
+/// Synthetic multi-venue market configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyntheticMarketConfig {
+    /// RNG seed; the same seed always yields the same tick stream
+    pub seed: u64,
+    /// Market identifier used in bundle keys
+    pub market_id: String,
+    /// First bundle timestamp
+    pub start_ns: TimestampNs,
+    /// Spacing between bundles (milliseconds)
+    pub tick_interval_ms: f64,
+    /// Initial true price
+    pub start_price: f64,
+    /// Price bounds (the true price reflects off these)
+    pub min_price: f64,
+    pub max_price: f64,
+    /// Correlation of quote noise across venues
+    pub noise_correlation: f64,
+    pub venues: Vec<SyntheticVenue>,
+    /// First entry is the starting regime
+    pub regimes: Vec<MarketRegime>,
+    pub depth: DepthModel,
+    pub suspensions: SuspensionModel,
+    /// Game length in seconds; fills `time_remaining` when set
+    pub game_duration_s: Option<f64>,
+}
+
+impl Default for SyntheticMarketConfig {
+    fn default() -> Self {
+        let venue = |platform: Platform, book: &str, lag_ms: f64, noise: f64, base_depth: f64| SyntheticVenue {
+            platform,
+            book: book.to_string(),
+            lag_ms,
+            noise,
+            base_depth,
+            ..SyntheticVenue::default()
+        };
+
+        Self {
+            seed: 42,
+            market_id: "SYNTH".to_string(),
+            start_ns: 0,
+            tick_interval_ms: 100.0,
+            start_price: 50.0,
+            min_price: 1.0,
+            max_price: 99.0,
+            noise_correlation: 0.3,
+            venues: vec![
+                venue(Platform::Kalshi, "kalshi", 0.0, 0.2, 1000.0),
+                venue(Platform::Polymarket, "polymarket", 250.0, 0.3, 800.0),
+                venue(Platform::DraftKings, "draftkings", 1500.0, 0.5, 2000.0),
+                venue(Platform::FanDuel, "fan_duel", 3000.0, 0.5, 1500.0),
+            ],
+            regimes: vec![
+                MarketRegime::default(),
+                MarketRegime {
+                    name: "steam".to_string(),
+                    volatility: 1.5,
+                    jump_prob: 0.002,
+                    jump_size: 4.0,
+                    depth_multiplier: 0.5,
+                    mean_duration_s: 60.0,
+                    weight: 0.3,
+                    ..MarketRegime::default()
+                },
+                MarketRegime {
+                    name: "volatile".to_string(),
+                    volatility: 0.8,
+                    jump_prob: 0.0005,
+                    jump_size: 2.0,
+                    depth_multiplier: 0.7,
+                    mean_duration_s: 180.0,
+                    weight: 0.5,
+                    ..MarketRegime::default()
+                },
+            ],
+            depth: DepthModel::default(),
+            suspensions: SuspensionModel::default(),
+            game_duration_s: None,
+        }
+    }
+}
+
+impl SyntheticMarketConfig {
+    /// `multiple_markets` key of a venue, same shape as captured replays
+    pub fn venue_key(&self, venue: &SyntheticVenue) -> String {
+        market_key(venue.platform, &self.market_id, "yes")
+    }
+
+    /// Venue configuration behind a bundle key
+    pub fn venue_for_key(&self, key: &str) -> Option<&SyntheticVenue> {
+        self.venues.iter().find(|v| self.venue_key(v) == key)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct VenueState {
+    key: String,
+    last_price: Option<f64>,
+    log_depth: f64,
+    suspended_until_ns: TimestampNs,
+}
+
+/// Deterministic generator of `SyncedTickBundle`s
+///
+/// A single true price follows a regime-switching jump diffusion; each venue
+/// quotes it after its own lag with correlated noise, a mean-reverting book
+/// depth and occasional suspensions. Infinite iterator: use `take`/`generate`.
+pub struct SyntheticMarketGenerator {
+    config: SyntheticMarketConfig,
+    rng: StdRng,
+    timestamp_ns: TimestampNs,
+    true_price: f64,
+    /// (timestamp, true price) going back as far as the slowest venue's lag
+    history: VecDeque<(TimestampNs, f64)>,
+    max_lag_ns: TimestampNs,
+    regime: usize,
+    regime_until_ns: TimestampNs,
+    venues: Vec<VenueState>,
+}
+
+impl SyntheticMarketGenerator {
+    pub fn new(config: SyntheticMarketConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let venues = config.venues.iter()
+            .map(|v| VenueState { key: config.venue_key(v), ..VenueState::default() })
+            .collect();
+        let max_lag_ns = config.venues.iter()
+            .map(|v| ms_to_ns(v.lag_ms))
+            .max()
+            .unwrap_or(0);
+        let regime_until_ns = config.regimes.first()
+            .map(|r| config.start_ns + exponential_ns(&mut rng, r.mean_duration_s))
+            .unwrap_or(u64::MAX);
+
+        Self {
+            timestamp_ns: config.start_ns,
+            true_price: config.start_price,
+            history: VecDeque::new(),
+            max_lag_ns,
+            regime: 0,
+            regime_until_ns,
+            venues,
+            rng,
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &SyntheticMarketConfig {
+        &self.config
+    }
+
+    /// Name of the regime the true price is currently in
+    pub fn regime(&self) -> Option<&str> {
+        self.config.regimes.get(self.regime).map(|r| r.name.as_str())
+    }
+
+    /// Current (unlagged, noise-free) price
+    pub fn true_price(&self) -> f64 {
+        self.true_price
+    }
+
+    /// Next `n` bundles
+    pub fn generate(&mut self, n: usize) -> Vec<SyncedTickBundle> {
+        self.by_ref().take(n).collect()
+    }
+
+    /// Advance the true price one step; returns whether it jumped
+    fn step_true_price(&mut self, dt_s: f64) -> bool {
+        if self.timestamp_ns >= self.regime_until_ns {
+            self.switch_regime();
+        }
+        let Some(regime) = self.config.regimes.get(self.regime) else {
+            return false;
+        };
+
+        let mut next = self.true_price
+            + regime.drift * dt_s
+            + regime.volatility * dt_s.sqrt() * gaussian(&mut self.rng);
+        let jumped = regime.jump_prob > 0.0 && self.rng.gen_bool(regime.jump_prob.min(1.0));
+        if jumped {
+            let sign = if self.rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            next += sign * regime.jump_size;
+        }
+
+        // Reflect off the bounds so the price stays tradable
+        let (lo, hi) = (self.config.min_price, self.config.max_price);
+        if next < lo {
+            next = (2.0 * lo - next).min(hi);
+        } else if next > hi {
+            next = (2.0 * hi - next).max(lo);
+        }
+        self.true_price = next;
+
+        self.history.push_back((self.timestamp_ns, next));
+        let horizon = self.timestamp_ns.saturating_sub(self.max_lag_ns);
+        while self.history.len() > 1 && self.history[1].0 <= horizon {
+            self.history.pop_front();
+        }
+        jumped
+    }
+
+    fn switch_regime(&mut self) {
+        let regimes = &self.config.regimes;
+        // Weighted choice among the other regimes
+        let total: f64 = regimes.iter().enumerate()
+            .filter(|(i, _)| *i != self.regime)
+            .map(|(_, r)| r.weight.max(0.0))
+            .sum();
+        if total > 0.0 {
+            let mut pick = self.rng.gen_range(0.0..total);
+            for (i, r) in regimes.iter().enumerate() {
+                if i == self.regime {
+                    continue;
+                }
+                pick -= r.weight.max(0.0);
+                if pick < 0.0 {
+                    debug!("[SYNTH] Regime {} -> {}", regimes[self.regime].name, r.name);
+                    self.regime = i;
+                    break;
+                }
+            }
+        }
+        let mean = regimes[self.regime].mean_duration_s;
+        self.regime_until_ns = self.timestamp_ns + exponential_ns(&mut self.rng, mean);
+    }
+
+    /// True price as seen `lag_ns` ago
+    fn lagged_price(&self, lag_ns: TimestampNs) -> f64 {
+        let at = self.timestamp_ns.saturating_sub(lag_ns);
+        self.history.iter().rev()
+            .find(|(ts, _)| *ts <= at)
+            .or_else(|| self.history.front())
+            .map(|(_, p)| *p)
+            .unwrap_or(self.config.start_price)
+    }
+}
+
+impl Iterator for SyntheticMarketGenerator {
+    type Item = SyncedTickBundle;
+
+    fn next(&mut self) -> Option<SyncedTickBundle> {
+        let dt_s = self.config.tick_interval_ms / 1000.0;
+        let step_ns = ms_to_ns(self.config.tick_interval_ms).max(1);
+
+        let jumped = self.step_true_price(dt_s);
+        let depth_multiplier = self.config.regimes.get(self.regime)
+            .map_or(1.0, |r| r.depth_multiplier);
+        let rho = self.config.noise_correlation.clamp(-1.0, 1.0);
+        let common = gaussian(&mut self.rng);
+        let spontaneous = self.config.suspensions.rate_per_hour * dt_s / 3600.0;
+
+        let mut markets = HashMap::new();
+        for i in 0..self.venues.len() {
+            let venue = &self.config.venues[i];
+            let now = self.timestamp_ns;
+
+            // Suspensions: spontaneous or triggered by a jump
+            let suspend_prob = if jumped {
+                self.config.suspensions.on_jump_prob
+            } else {
+                spontaneous
+            };
+            if self.venues[i].suspended_until_ns <= now
+                && suspend_prob > 0.0
+                && self.rng.gen_bool(suspend_prob.min(1.0))
+            {
+                let until = now + exponential_ns(&mut self.rng, self.config.suspensions.mean_duration_s);
+                self.venues[i].suspended_until_ns = until;
+                debug!("[SYNTH] {} suspended until {}", venue.book, until);
+            }
+
+            let idio = gaussian(&mut self.rng);
+            let depth_shock = gaussian(&mut self.rng);
+            if self.venues[i].suspended_until_ns > now {
+                continue;
+            }
+
+            let noise = venue.noise * (rho * common + (1.0 - rho * rho).sqrt() * idio);
+            let mut price = self.lagged_price(ms_to_ns(venue.lag_ms)) + venue.basis + noise;
+            if venue.tick_size > 0.0 {
+                price = (price / venue.tick_size).round() * venue.tick_size;
+            }
+            price = price.clamp(self.config.min_price, self.config.max_price);
+
+            let state = &mut self.venues[i];
+            let price_delta = state.last_price.map_or(0.0, |last| price - last);
+            let depth = &self.config.depth;
+            state.log_depth += -depth.reversion * state.log_depth * dt_s
+                + depth.volatility * dt_s.sqrt() * depth_shock;
+            let pulled = (1.0 - depth.move_sensitivity * price_delta.abs()).max(0.05);
+            let size = (venue.base_depth * depth_multiplier * state.log_depth.exp() * pulled).round();
+            state.last_price = Some(price);
+
+            markets.insert(state.key.clone(), TickData {
+                market_id: self.config.market_id.clone(),
+                platform: venue.platform,
+                price,
+                size,
+                price_delta,
+                book: venue.book.clone(),
+            });
+        }
+
+        let timestamp_ns = self.timestamp_ns;
+        self.timestamp_ns += step_ns;
+
+        let time_remaining = self.config.game_duration_s.map(|total| {
+            let elapsed = (timestamp_ns - self.config.start_ns) as f64 / 1e9;
+            (total - elapsed).max(0.0)
+        });
+
+        // Emitted even when every venue is suspended so time keeps moving
+        Some(SyncedTickBundle {
+            timestamp_ns,
+            ht: None,
+            ft: None,
+            multiple_markets: Some(markets),
+            time_remaining,
+            game_context: None,
+        })
+    }
+}
+
+fn ms_to_ns(ms: f64) -> TimestampNs {
+    (ms.max(0.0) * 1_000_000.0) as TimestampNs
+}
+
+/// Standard normal sample (Box-Muller)
+fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Exponentially distributed duration with the given mean (seconds)
+fn exponential_ns(rng: &mut StdRng, mean_s: f64) -> TimestampNs {
+    let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+    (-u.ln() * mean_s.max(0.0) * 1e9) as TimestampNs
+}
+
 // Helper trait for downcasting
 trait AsAny {
     fn as_any(&self) -> &dyn std::any::Any;
@@ -934,4 +1745,121 @@ mod tests {
         assert!(confidence > 0.0);
         assert!(confidence <= 1.0);
     }
+
+    fn quiet_agents() -> AgentPopulationConfig {
+        AgentPopulationConfig {
+            recreational: RecreationalFlowConfig { trades_per_minute: 0.0, ..RecreationalFlowConfig::default() },
+            sharps: SharpBettorConfig { count: 0, ..SharpBettorConfig::default() },
+            ..AgentPopulationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_agent_maker_inventory() {
+        let mut config = quiet_agents();
+        config.market_makers = vec![MarketMakerConfig {
+            book: "pinnacle".to_string(),
+            inventory_limit: 150.0,
+            unwind_rate: 0.0,
+            ..MarketMakerConfig::default()
+        }];
+        config.sharps = SharpBettorConfig { count: 2, follow_prob: 1.0, mean_reaction_ms: 100.0, size_multiple: 1.0 };
+        let mut agents = AgentMarket::new(config);
+
+        let first = agents.fill("pinnacle", 1, 50.0, 50.0, 0).unwrap();
+        assert_eq!(first, AgentFill { price: 50.5, size: 50.0 });
+        assert_eq!(agents.inventory("pinnacle"), -50.0);
+
+        // Sharps copy the buy; the maker is now short and quotes higher
+        agents.advance(0);
+        agents.advance(10_000_000_000);
+        assert_eq!(agents.inventory("pinnacle"), -150.0);
+        assert!(agents.fill("pinnacle", 1, 10.0, 50.0, 10_000_000_000).is_none());
+
+        // Selling into a short maker is fine and fills below the buy quote
+        let sell = agents.fill("pinnacle", -1, 10.0, 50.0, 10_000_000_000).unwrap();
+        assert!(sell.price < first.price);
+    }
+
+    #[test]
+    fn test_agent_books_limit_winning_bot() {
+        let mut config = quiet_agents();
+        config.profiling = ProfilingConfig { min_graded: 5, grade_after_s: 1.0, ..ProfilingConfig::default() };
+        let mut agents = AgentMarket::new(config);
+
+        agents.advance(0);
+        for i in 0..10u64 {
+            let size = 10.0 + i as f64 * 5.0;
+            agents.fill("draftkings", 1, size, 50.0, i * 1_000_000).unwrap();
+            agents.fill("fan_duel", -1, size, 50.0, i * 1_000_000).unwrap();
+        }
+        assert_eq!(agents.limit_status("draftkings"), LimitStatus::None);
+
+        // Price runs up: every draftkings buy beat it, every fan_duel sell lost
+        agents.observe("draftkings", 60.0);
+        agents.observe("fan_duel", 60.0);
+        agents.advance(5_000_000_000);
+        assert_eq!(agents.limit_status("draftkings"), LimitStatus::Hard);
+        assert_eq!(agents.limit_status("fan_duel"), LimitStatus::None);
+    }
+
+    fn prices(bundles: &[SyncedTickBundle], key: &str) -> Vec<Option<f64>> {
+        bundles.iter()
+            .map(|b| b.multiple_markets.as_ref().and_then(|m| m.get(key)).map(|t| t.price))
+            .collect()
+    }
+
+    #[test]
+    fn test_synthetic_generator_is_seeded() {
+        let config = SyntheticMarketConfig::default();
+        let key = config.venue_key(&config.venues[0]);
+
+        let a = SyntheticMarketGenerator::new(config.clone()).generate(500);
+        let b = SyntheticMarketGenerator::new(config.clone()).generate(500);
+        assert_eq!(prices(&a, &key), prices(&b, &key));
+        assert_eq!(a[1].timestamp_ns - a[0].timestamp_ns, 100_000_000);
+
+        let c = SyntheticMarketGenerator::new(SyntheticMarketConfig { seed: 7, ..config }).generate(500);
+        assert_ne!(prices(&a, &key), prices(&c, &key));
+    }
+
+    #[test]
+    fn test_synthetic_venue_lag() {
+        let exact = |platform, lag_ms| SyntheticVenue {
+            platform,
+            lag_ms,
+            noise: 0.0,
+            tick_size: 0.0,
+            ..SyntheticVenue::default()
+        };
+        let config = SyntheticMarketConfig {
+            venues: vec![exact(Platform::Kalshi, 0.0), exact(Platform::DraftKings, 500.0)],
+            suspensions: SuspensionModel { rate_per_hour: 0.0, on_jump_prob: 0.0, ..SuspensionModel::default() },
+            ..SyntheticMarketConfig::default()
+        };
+        let lead = config.venue_key(&config.venues[0]);
+        let lag = config.venue_key(&config.venues[1]);
+
+        let bundles = SyntheticMarketGenerator::new(config).generate(200);
+        let (lead, lag) = (prices(&bundles, &lead), prices(&bundles, &lag));
+        // 500ms lag at 100ms spacing: the slow venue repeats the fast one five bundles later
+        for k in 0..195 {
+            assert_eq!(lag[k + 5], lead[k]);
+        }
+        assert!(lead.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_synthetic_suspensions() {
+        let config = SyntheticMarketConfig {
+            suspensions: SuspensionModel { rate_per_hour: 1e12, on_jump_prob: 0.0, mean_duration_s: 1.0 },
+            ..SyntheticMarketConfig::default()
+        };
+        let bundles = SyntheticMarketGenerator::new(config).generate(50);
+
+        // Everything suspends on the first step; time still advances
+        assert!(bundles[0].multiple_markets.as_ref().unwrap().is_empty());
+        assert_eq!(bundles.len(), 50);
+        assert!(bundles.windows(2).all(|w| w[1].timestamp_ns > w[0].timestamp_ns));
+    }
 }
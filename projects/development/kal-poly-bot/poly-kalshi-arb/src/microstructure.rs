@@ -0,0 +1,174 @@
+// src/microstructure.rs
+// Order-book microstructure features (imbalance, microprice, trade-flow imbalance) per feed
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use crate::provider_registry::ProviderId;
+use crate::types::{PriceCents, SizeCents};
+
+/// Best bid/ask with resting size (cents / contracts)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TopOfBook {
+    pub bid: f64,
+    pub ask: f64,
+    pub bid_size: f64,
+    pub ask_size: f64,
+}
+
+impl TopOfBook {
+    /// YES book of a binary market quoted as YES/NO asks: a NO ask at `p` is a YES bid at `100 - p`
+    pub fn from_binary(yes_ask: PriceCents, no_ask: PriceCents, yes_size: SizeCents, no_size: SizeCents) -> Option<Self> {
+        if yes_ask == 0 || no_ask == 0 || yes_size as u32 + no_size as u32 == 0 {
+            return None;
+        }
+        Some(Self {
+            bid: 100.0 - no_ask as f64,
+            ask: yes_ask as f64,
+            bid_size: no_size as f64,
+            ask_size: yes_size as f64,
+        })
+    }
+
+    /// `(bid_size - ask_size) / (bid_size + ask_size)` in [-1, 1]; positive = bid-heavy
+    pub fn imbalance(&self) -> f64 {
+        (self.bid_size - self.ask_size) / (self.bid_size + self.ask_size)
+    }
+
+    /// Size-weighted mid, leaning toward the side with less resting size
+    pub fn microprice(&self) -> f64 {
+        (self.bid * self.ask_size + self.ask * self.bid_size) / (self.bid_size + self.ask_size)
+    }
+}
+
+/// Microstructure features for one update
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BookFeatures {
+    /// Top-of-book size imbalance in [-1, 1]
+    pub imbalance: f64,
+    /// Size-weighted mid (cents)
+    pub microprice: f64,
+    /// Net order flow over the rolling window in [-1, 1]; positive = buying pressure
+    pub trade_flow_imbalance: f64,
+}
+
+/// Signed order flow between consecutive quotes (Cont-Kukanov-Stoikov OFI): bid
+/// size added or price improved counts as buying, ask size added as selling
+fn order_flow(prev: &TopOfBook, next: &TopOfBook) -> f64 {
+    let bid = if next.bid > prev.bid {
+        next.bid_size
+    } else if next.bid < prev.bid {
+        -prev.bid_size
+    } else {
+        next.bid_size - prev.bid_size
+    };
+    let ask = if next.ask < prev.ask {
+        next.ask_size
+    } else if next.ask > prev.ask {
+        -prev.ask_size
+    } else {
+        next.ask_size - prev.ask_size
+    };
+    bid - ask
+}
+
+struct BookState {
+    last: TopOfBook,
+    flow: VecDeque<f64>,
+}
+
+/// Rolling feature state per (market, provider). Feeds carry quotes, not prints,
+/// so trade flow is inferred from quote changes over the last `window` updates.
+pub struct MicrostructureTracker {
+    window: usize,
+    books: HashMap<(u16, ProviderId), BookState>,
+}
+
+impl MicrostructureTracker {
+    pub fn new(window: usize) -> Self {
+        Self { window: window.max(1), books: HashMap::new() }
+    }
+
+    /// Fold a quote into the market's state and return its features
+    pub fn observe(&mut self, market_id: u16, provider: ProviderId, book: TopOfBook) -> BookFeatures {
+        let state = self.books
+            .entry((market_id, provider))
+            .or_insert_with(|| BookState { last: book, flow: VecDeque::new() });
+
+        if state.last != book {
+            state.flow.push_back(order_flow(&state.last, &book));
+            if state.flow.len() > self.window {
+                state.flow.pop_front();
+            }
+            state.last = book;
+        }
+
+        let gross: f64 = state.flow.iter().map(|f| f.abs()).sum();
+        let trade_flow_imbalance = if gross > 0.0 { state.flow.iter().sum::<f64>() / gross } else { 0.0 };
+
+        BookFeatures { imbalance: book.imbalance(), microprice: book.microprice(), trade_flow_imbalance }
+    }
+
+    pub fn tracked_markets(&self) -> usize {
+        self.books.len()
+    }
+}
+
+impl Default for MicrostructureTracker {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Platform;
+
+    #[test]
+    fn binary_quotes_map_to_yes_book() {
+        // YES ask 45 x 100, NO ask 52 x 300 -> YES bid 48 x 300
+        let book = TopOfBook::from_binary(45, 52, 100, 300).unwrap();
+        assert_eq!((book.bid, book.ask, book.bid_size, book.ask_size), (48.0, 45.0, 300.0, 100.0));
+        assert!((book.imbalance() - 0.5).abs() < 1e-12);
+
+        assert!(TopOfBook::from_binary(0, 52, 100, 300).is_none());
+        assert!(TopOfBook::from_binary(45, 52, 0, 0).is_none());
+    }
+
+    #[test]
+    fn microprice_leans_toward_thin_side() {
+        let book = TopOfBook { bid: 40.0, ask: 42.0, bid_size: 300.0, ask_size: 100.0 };
+        // Heavy bid, thin ask: fair value sits nearer the ask
+        assert!((book.microprice() - 41.5).abs() < 1e-12);
+
+        let balanced = TopOfBook { bid_size: 100.0, ..book };
+        assert!((balanced.microprice() - 41.0).abs() < 1e-12);
+        assert_eq!(balanced.imbalance(), 0.0);
+    }
+
+    #[test]
+    fn trade_flow_tracks_signed_quote_changes() {
+        let mut tracker = MicrostructureTracker::new(3);
+        let kalshi = ProviderId::from(Platform::Kalshi);
+        let book = TopOfBook { bid: 40.0, ask: 42.0, bid_size: 100.0, ask_size: 100.0 };
+
+        assert_eq!(tracker.observe(1, kalshi, book).trade_flow_imbalance, 0.0);
+
+        // Bids build twice: pure buying pressure
+        tracker.observe(1, kalshi, TopOfBook { bid_size: 150.0, ..book });
+        let f = tracker.observe(1, kalshi, TopOfBook { bid_size: 200.0, ..book });
+        assert_eq!(f.trade_flow_imbalance, 1.0);
+
+        // Ask lifted (price moves up): the consumed ask counts as buying; then asks build (selling 50)
+        let lifted = TopOfBook { bid_size: 200.0, ask: 43.0, ..book };
+        tracker.observe(1, kalshi, lifted);
+        let f = tracker.observe(1, kalshi, TopOfBook { ask_size: 150.0, ..lifted });
+        // Window of 3: +50 (bid), +100 (ask lifted), -50 (ask added)
+        assert!((f.trade_flow_imbalance - 100.0 / 200.0).abs() < 1e-12);
+
+        // Other markets keep their own state
+        assert_eq!(tracker.observe(2, kalshi, book).trade_flow_imbalance, 0.0);
+        assert_eq!(tracker.tracked_markets(), 2);
+    }
+}
@@ -6,20 +6,42 @@
 //! - Provider health status with latency deltas and failure tracking
 //! - Regulatory delay arbitrage windows by jurisdiction
 //! - ML Intelligence Layer telemetry (Component #40): Tier 1-4 model performance and SLAs
+//!
+//! Signals, opportunities, feed status, alerts and config changes arrive as
+//! events (`EventHandler`); the dashboard holds no references to the engines.
+//! Signals, opportunities and alerts carry a strategy id (`crate::strategy`);
+//! `DashboardSnapshot::for_strategy` narrows a snapshot to one strategy.
 
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use serde::{Serialize, Deserialize};
 
-use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal, MarketTier};
-use crate::feed_aggregator::{FeedAggregator, LatencyStats};
+use crate::alert_router::AlertSeverity;
+use crate::config::DashboardSection;
+use crate::error::Error;
+use crate::backtest_jobs::SharedBacktestJobs;
+use crate::backtest_results::{SharedBacktestResults, VerificationRecord};
+use crate::clock::{self, SharedClock};
+use crate::event_bus::{Event, EventHandler, SharedEventBus, SubscriberStats};
+use crate::feature_flags::{FlagsSnapshot, SharedFeatureFlags};
+use crate::latency_arbitrage::{mean_edge_remaining, LatencySignal};
+use crate::feed_aggregator::{FeedStatus, LatencyBreakdown, LatencySummary};
+use crate::decision_latency::{PatternLatency, SharedDecisionLatency};
 use crate::latency_execution::LatencyExecutionStats;
-use crate::risk_management::RiskManagementEngine;
-use crate::pattern_73_beta_skew::{Pattern73Engine, BetaSkewOpportunity};
-use crate::tick_sim_backtester::{TickSimBacktester, BacktestResult, BacktestConfig};
-use crate::backtester_config::{BacktesterControls, PatternVerification};
-use crate::types::{TimestampNs, MarketType, Platform};
+use crate::market_cooldown::{MarketCooldown, SharedCooldownManager};
+use crate::optimization_studies::{SharedStudyStore, StudyComparison};
+use crate::pattern_73_beta_skew::BetaSkewOpportunity;
+use crate::pattern_verifier::SharedPatternVerifier;
+use crate::tick_sim_backtester::{BacktestResult, RetirementProjection};
+use crate::backtester_config::{PatternVerification, get_default_pattern_verifications};
+use crate::operator_review::{PendingReview, SharedReviewQueue};
+use crate::position_aging::{SharedPositionSweeper, StalePosition};
+use crate::position_tracker::{RealizedLot, SharedPositionTracker};
+use crate::provider_registry::ProviderId;
+use crate::sensitivity::{SensitivityReport, SharedSensitivityService};
+use crate::sla_degradation::{DegradationStatus, SharedSlaMonitor};
+use crate::strategy::{self, StrategySummary};
+use crate::tca::{SharedTcaStore, TcaReport};
+use crate::types::TimestampNs;
 
 /// Dashboard data snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +57,96 @@ pub struct DashboardSnapshot {
     pub pattern_73_opportunities: Vec<BetaSkewOpportunityData>, // Pattern #73 telemetry
     pub backtester_results: Option<BacktestResultData>, // Component #41 telemetry
     pub pattern_verifications: Vec<PatternVerificationData>, // ROI & Half-Life verification
+    pub alpha_decay: Vec<RetirementProjection>, // Projected pattern retirement dates with confidence bands
+    pub pnl_panel: Option<PnlPanelData>, // Lot-level P&L from position tracker
+    pub tca: Option<TcaReport>, // Fill slippage vs decision/arrival/mid over the TCA window
+    pub event_bus: Vec<SubscriberStats>, // Per-subscriber mailbox depth, drops and lag
+    pub market_cooldowns: Vec<MarketCooldown>, // Markets benched after losses, rejections or limiting
+    pub sensitivities: Option<SensitivityReport>, // Portfolio P&L per home win / total / star availability move, per game
+    pub parameter_studies: Option<Vec<StudyComparison>>, // Latest optimization study per pattern: top parameter sets' out-of-sample curves side by side
+    pub sla_degradation: Option<DegradationStatus>, // Tier 1 SLA compliance, degradation level, shed tiers and trigger multipliers
+    #[serde(default)]
+    pub strategies: Vec<StrategySummary>, // Signals, opportunities and alerts held per strategy id
+    #[serde(default)]
+    pub decision_latency: Vec<PatternLatency>, // Detection-to-order p50/p99 per pattern against its tier SLA
+    #[serde(default)]
+    pub stale_positions: Vec<StalePosition>, // Open lots past their pattern's half-life multiple, most overdue first
+    #[serde(default)]
+    pub pending_reviews: Vec<PendingReview>, // Low-confidence signals awaiting an operator's approve / deny, soonest deadline first
+    #[serde(default)]
+    pub backtest_history: Vec<BacktestResultData>, // Latest completed backtests from the results repository, newest first
+    #[serde(default)]
+    pub verification_history: Vec<VerificationRecord>, // Latest verification updates from archive replays, newest first
+}
+
+impl DashboardSnapshot {
+    /// Keep only `strategy_id`'s alerts, summary and TCA group, and the pattern panels of its
+    /// pattern; panels without a strategy dimension (heatmap, providers, ML telemetry, ...) stay whole
+    pub fn for_strategy(mut self, strategy_id: &str) -> Self {
+        let pattern = strategy::pattern_of(strategy_id);
+        self.risk_alerts.retain(|a| a.strategy_id.as_deref() == Some(strategy_id));
+        self.strategies.retain(|s| s.strategy_id == strategy_id);
+        if pattern != Some(73) {
+            self.pattern_73_opportunities.clear();
+        }
+        if self.backtester_results.as_ref().is_some_and(|b| Some(b.pattern_id) != pattern) {
+            self.backtester_results = None;
+        }
+        self.backtest_history.retain(|b| Some(b.pattern_id) == pattern);
+        self.verification_history.retain(|v| Some(v.verification.component_id) == pattern);
+        self.pattern_verifications.retain(|v| Some(v.component_id) == pattern);
+        self.alpha_decay.retain(|p| Some(p.pattern_id) == pattern);
+        if let Some(studies) = self.parameter_studies.as_mut() {
+            studies.retain(|s| Some(s.study.pattern_id) == pattern);
+        }
+        if let Some(tca) = self.tca.as_mut() {
+            tca.by_strategy.retain(|id, _| id == strategy_id);
+        }
+        // Pattern keys are pattern ids, arb types or (unclaimed latency signals) the strategy id
+        self.decision_latency
+            .retain(|p| p.pattern == strategy_id || strategy::of_position_tag(Some(&p.pattern)) == strategy_id);
+        self.stale_positions.retain(|p| strategy::of_position_tag(Some(&p.pattern)) == strategy_id);
+        self.pending_reviews.retain(|r| r.strategy_id == strategy_id);
+        self
+    }
+}
+
+/// Admin route: `GET /dashboard[?strategy=..]` - the latest snapshot, narrowed to one strategy when asked
+pub fn handle_admin(latest: &serde_json::Value, method: &str, path: &str) -> (u16, String) {
+    if method != "GET" {
+        return (405, r#"{"error":"method not allowed"}"#.to_string());
+    }
+    let mut strategy_id = None;
+    for pair in path.split_once('?').map_or("", |(_, q)| q).split('&').filter(|p| !p.is_empty()) {
+        match pair.split_once('=') {
+            Some(("strategy", value)) => strategy_id = Some(value),
+            _ => return (400, serde_json::json!({ "error": format!("unknown query parameter '{}'", pair) }).to_string()),
+        }
+    }
+    let (Some(strategy_id), false) = (strategy_id, latest.is_null()) else {
+        return (200, latest.to_string());
+    };
+    match serde_json::from_value::<DashboardSnapshot>(latest.clone()) {
+        Ok(snapshot) => (200, serde_json::to_string(&snapshot.for_strategy(strategy_id)).unwrap_or_default()),
+        Err(e) => (500, serde_json::json!({ "error": e.to_string() }).to_string()),
+    }
+}
+
+/// P&L panel (lot-level accounting from position_tracker)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlPanelData {
+    pub realized_today: f64,
+    pub realized_all_time: f64,
+    pub open_cost_basis: f64,
+    pub guaranteed_profit: f64,
+    pub open_positions: usize,
+    /// Realized P&L by pattern tag (today)
+    pub by_pattern_today: HashMap<String, f64>,
+    /// Realized P&L by exit reason - close, resolution, timeout (today)
+    #[serde(default)]
+    pub by_exit_today: HashMap<String, f64>,
+    /// Most recent realized lots
+    pub recent_lots: Vec<RealizedLot>,
 }
 
 /// ML Intelligence Layer telemetry (Component #40)
@@ -46,6 +158,7 @@ pub struct MLIntelligenceTelemetry {
     pub tier4_models: Vec<ModelTelemetry>, // #77 Regulatory Delay
     pub behavioral_models: Vec<ModelTelemetry>, // #79, #82 Bayesian Emotional Carryover
     pub overall_sla_compliance: SLACompliance,
+    pub feature_flags: Option<FlagsSnapshot>,
 }
 
 /// Pattern #73 opportunity data for dashboard
@@ -89,6 +202,29 @@ pub struct BacktestResultData {
     pub timestamp_ns: TimestampNs,
 }
 
+impl BacktestResultData {
+    fn from_result(result: &BacktestResult, timestamp_ns: TimestampNs) -> Self {
+        Self {
+            pattern_id: result.pattern_id,
+            total_return: result.total_return,
+            roi_percent: result.roi_percent,
+            sharpe_ratio: result.sharpe_ratio,
+            max_drawdown: result.max_drawdown,
+            total_trades: result.total_trades,
+            winning_trades: result.winning_trades,
+            losing_trades: result.losing_trades,
+            avg_trade_duration_us: result.avg_trade_duration_us,
+            final_sharp_score: result.final_sharp_score,
+            account_limited: result.account_limited,
+            alpha_half_life_us: result.alpha_half_life_us,
+            avg_execution_latency_us: result.execution_stats.avg_execution_latency_us,
+            sla_compliance_percent: result.execution_stats.sla_compliance_percent,
+            fill_rate_percent: result.execution_stats.fill_rate_percent,
+            timestamp_ns,
+        }
+    }
+}
+
 /// Pattern verification data for dashboard
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternVerificationData {
@@ -98,6 +234,8 @@ pub struct PatternVerificationData {
     pub alpha_half_life_weeks: f64,
     pub primary_constraint: String,
     pub verified_roi_percent: f64,
+    pub verified_half_life_weeks: Option<f64>,
+    pub samples: usize,
     pub verification_status: String,
     pub is_target_met: bool,
 }
@@ -115,6 +253,7 @@ pub struct ModelTelemetry {
     pub last_execution_ns: TimestampNs,
     pub error_count: u32,
     pub feature_flag: String, // PREMIUM, BETA_FEATURES, DEBUG
+    pub feature_enabled: bool, // Effective flag state; Disabled status when false
     pub stability: String, // STABLE, EXPERIMENTAL
     pub dependency: String, // Component #37, #34, #38
     pub metric_value: f64, // Model-specific metric (accuracy, correlation, etc.)
@@ -185,6 +324,8 @@ pub struct ProviderStatus {
     pub provider: String,
     pub status: String, // "healthy", "degraded", "critical", "down"
     pub latency_ns: u64,
+    /// Network vs processing percentiles (from `Event::FeedLatency`)
+    pub latency_breakdown: Option<LatencyBreakdown>,
     pub latency_trend: String, // "improving", "stable", "degrading"
     pub circuit_breaker_state: String,
     pub failure_count: u32,
@@ -213,60 +354,116 @@ pub struct RiskAlertData {
     pub severity: String, // "low", "medium", "high", "critical"
     pub message: String,
     pub timestamp_ns: TimestampNs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
 }
 
+/// Signals older than this (relative to the newest) leave the heatmap
+const SIGNAL_WINDOW_NS: u64 = 30_000_000_000;
+/// Opportunities older than this (relative to the newest) leave the table
+const OPPORTUNITY_WINDOW_NS: u64 = 60_000_000_000;
+
+/// Trailing UTC days covered by the TCA panel
+const TCA_WINDOW_DAYS: u64 = 7;
+
+/// Recorded backtests and verification updates listed
+const HISTORY_LEN: usize = 10;
+
 /// Monitoring dashboard engine
 pub struct MonitoringDashboard {
-    /// Latency arbitrage engine
-    latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
-    /// Feed aggregator
-    feed_aggregator: Arc<RwLock<FeedAggregator>>,
-    /// Pattern #73 engine
-    pattern_73_engine: Arc<RwLock<Pattern73Engine>>,
-    /// Risk management engine (optional)
-    risk_engine: Option<Arc<RwLock<RiskManagementEngine>>>,
+    /// Recent latency signals (from `Event::Signal`)
+    signals: VecDeque<LatencySignal>,
+    /// Latest Pattern #73 opportunity per market pair (from `Event::Opportunity`)
+    opportunities: Vec<BetaSkewOpportunity>,
+    /// Feed status and latency per provider (from `Event::Feed`)
+    feeds: HashMap<ProviderId, (FeedStatus, u64)>,
+    /// Latency percentiles per provider (from `Event::FeedLatency`)
+    feed_latency: HashMap<ProviderId, LatencyBreakdown>,
+    /// Bus whose subscriber lag is reported (optional)
+    event_bus: Option<SharedEventBus>,
     /// Execution stats (optional)
     execution_stats: Option<LatencyExecutionStats>,
     /// Alert history
     alert_history: Vec<RiskAlertData>,
     /// Dashboard update interval
     update_interval_ms: u64,
-    /// ML model performance tracking
-    ml_model_stats: HashMap<u16, ModelPerformance>,
-}
-
-/// ML model performance tracking
-#[derive(Debug, Clone)]
-struct ModelPerformance {
-    pub executions: u32,
-    pub total_latency_ms: f64,
-    pub errors: u32,
-    pub last_execution_ns: TimestampNs,
-    pub metric_history: Vec<f64>,
+    /// Position tracker for the P&L panel (optional)
+    position_tracker: Option<SharedPositionTracker>,
+    /// Flag state for ML telemetry (optional; everything enabled without it)
+    feature_flags: Option<SharedFeatureFlags>,
+    /// Fill history for the TCA panel (optional)
+    tca: Option<SharedTcaStore>,
+    /// Benched markets (optional)
+    cooldowns: Option<SharedCooldownManager>,
+    /// Archive-verified ROI and half-life (optional; blueprint defaults without it)
+    pattern_verifier: Option<SharedPatternVerifier>,
+    /// Backtest job service for the backtester panel (optional)
+    backtest_jobs: Option<SharedBacktestJobs>,
+    /// Completed backtests and verification updates, latest first (optional)
+    backtest_results: Option<SharedBacktestResults>,
+    /// Factor sensitivities of the held positions (optional)
+    sensitivities: Option<SharedSensitivityService>,
+    /// Optimization studies for the parameter comparison panel (optional)
+    studies: Option<SharedStudyStore>,
+    /// SLA degradation policy, fed the model latencies below (optional)
+    sla: Option<SharedSlaMonitor>,
+    /// Detection-to-order histograms per pattern (optional)
+    decision_latency: Option<SharedDecisionLatency>,
+    /// Stale-position sweeper whose last sweep is listed (optional)
+    position_sweeper: Option<SharedPositionSweeper>,
+    review: Option<SharedReviewQueue>,
+    /// Stamps snapshots and alerts, and ages signals for the heatmap
+    clock: SharedClock,
 }
 
 impl MonitoringDashboard {
     /// Create new monitoring dashboard
-    pub fn new(
-        latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
-        feed_aggregator: Arc<RwLock<FeedAggregator>>,
-        pattern_73_engine: Arc<RwLock<Pattern73Engine>>,
-    ) -> Self {
+    pub fn new() -> Self {
         Self {
-            latency_engine,
-            feed_aggregator,
-            pattern_73_engine,
-            risk_engine: None,
+            signals: VecDeque::new(),
+            opportunities: Vec::new(),
+            feeds: HashMap::new(),
+            feed_latency: HashMap::new(),
+            event_bus: None,
             execution_stats: None,
             alert_history: Vec::new(),
             update_interval_ms: 1000, // 1 second updates
-            ml_model_stats: HashMap::new(),
+            position_tracker: None,
+            feature_flags: None,
+            tca: None,
+            cooldowns: None,
+            pattern_verifier: None,
+            backtest_jobs: None,
+            backtest_results: None,
+            sensitivities: None,
+            studies: None,
+            sla: None,
+            decision_latency: None,
+            position_sweeper: None,
+            review: None,
+            clock: clock::system(),
         }
     }
 
-    /// Set risk management engine for alerts
-    pub fn with_risk_engine(mut self, risk_engine: Arc<RwLock<RiskManagementEngine>>) -> Self {
-        self.risk_engine = Some(risk_engine);
+    /// Apply hot-reloaded dashboard settings
+    pub fn apply_config(&mut self, dashboard: &DashboardSection) {
+        self.update_interval_ms = dashboard.update_interval_ms;
+    }
+
+    /// Current refresh interval
+    pub fn update_interval_ms(&self) -> u64 {
+        self.update_interval_ms
+    }
+
+    /// Report subscriber lag for this bus
+    pub fn with_event_bus(mut self, bus: SharedEventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Read time from another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
         self
     }
 
@@ -276,12 +473,173 @@ impl MonitoringDashboard {
         self
     }
 
+    /// Set position tracker for the P&L panel
+    pub fn with_position_tracker(mut self, tracker: SharedPositionTracker) -> Self {
+        self.position_tracker = Some(tracker);
+        self
+    }
+
+    /// Report flag state and mark flagged-off models Disabled
+    pub fn with_feature_flags(mut self, flags: SharedFeatureFlags) -> Self {
+        self.feature_flags = Some(flags);
+        self
+    }
+
+    /// Set the fill history for the TCA panel
+    pub fn with_tca(mut self, tca: SharedTcaStore) -> Self {
+        self.tca = Some(tca);
+        self
+    }
+
+    /// List markets on cooldown (start/expiry arrive as `Event::Alert`)
+    pub fn with_cooldowns(mut self, cooldowns: SharedCooldownManager) -> Self {
+        self.cooldowns = Some(cooldowns);
+        self
+    }
+
+    /// Report verified ROI and half-life from the archive replay job
+    pub fn with_pattern_verifier(mut self, verifier: SharedPatternVerifier) -> Self {
+        self.pattern_verifier = Some(verifier);
+        self
+    }
+
+    /// Pattern ROI / half-life verification panel
+    fn generate_pattern_verifications(&self) -> Vec<PatternVerificationData> {
+        let verifications: Vec<PatternVerification> = match &self.pattern_verifier {
+            Some(verifier) => verifier.snapshot(),
+            None => get_default_pattern_verifications(),
+        };
+        verifications.into_iter()
+            .map(|v| PatternVerificationData {
+                is_target_met: v.is_target_met(),
+                verification_status: format!("{:?}", v.verification_status),
+                component_id: v.component_id,
+                component_name: v.component_name,
+                roi_target_percent: v.roi_target_percent,
+                alpha_half_life_weeks: v.alpha_half_life_weeks,
+                primary_constraint: v.primary_constraint,
+                verified_roi_percent: v.verified_roi_percent,
+                verified_half_life_weeks: v.verified_half_life_weeks,
+                samples: v.samples,
+            })
+            .collect()
+    }
+
+    /// Show the latest completed job in the backtester panel
+    pub fn with_backtest_jobs(mut self, jobs: SharedBacktestJobs) -> Self {
+        self.backtest_jobs = Some(jobs);
+        self
+    }
+
+    /// Fill the backtester panel and its history, and the verification history, from the
+    /// results repository (it takes precedence over the job service for the latest result)
+    pub fn with_backtest_results(mut self, results: SharedBacktestResults) -> Self {
+        self.backtest_results = Some(results);
+        self
+    }
+
+    /// Show the factor sensitivity table
+    pub fn with_sensitivities(mut self, sensitivities: SharedSensitivityService) -> Self {
+        self.sensitivities = Some(sensitivities);
+        self
+    }
+
+    /// Compare each pattern's latest study candidates (promotion is on `/studies`)
+    pub fn with_studies(mut self, studies: SharedStudyStore) -> Self {
+        self.studies = Some(studies);
+        self
+    }
+
+    /// Report model latencies to the SLA degradation policy and show its state
+    pub fn with_sla_monitor(mut self, sla: SharedSlaMonitor) -> Self {
+        self.sla = Some(sla);
+        self
+    }
+
+    /// Show each pattern's detection-to-order latency against its tier SLA
+    pub fn with_decision_latency(mut self, decision_latency: SharedDecisionLatency) -> Self {
+        self.decision_latency = Some(decision_latency);
+        self
+    }
+
+    /// List the positions the sweeper last flagged stale
+    pub fn with_position_sweeper(mut self, sweeper: SharedPositionSweeper) -> Self {
+        self.position_sweeper = Some(sweeper);
+        self
+    }
+
+    /// List the signals held for operator review
+    pub fn with_review_queue(mut self, review: SharedReviewQueue) -> Self {
+        self.review = Some(review);
+        self
+    }
+
+    /// Backtester panel from the newest recorded result, else the most recently completed job
+    fn generate_backtester_results(&self) -> Option<BacktestResultData> {
+        if let Some(record) = self.backtest_results.as_ref().and_then(|r| r.latest_backtest()) {
+            return Some(BacktestResultData::from_result(&record.result, record.finished_ns));
+        }
+        let job = self.backtest_jobs.as_ref()?.latest_completed()?;
+        let result = job.result?;
+        Some(BacktestResultData::from_result(&result, job.finished_ns.unwrap_or(job.submitted_ns)))
+    }
+
+    /// The latest `HISTORY_LEN` recorded backtests, newest first
+    fn generate_backtest_history(&self) -> Vec<BacktestResultData> {
+        self.backtest_results.as_ref()
+            .map(|r| r.latest_backtests(HISTORY_LEN))
+            .unwrap_or_default()
+            .iter()
+            .map(|record| BacktestResultData::from_result(&record.result, record.finished_ns))
+            .collect()
+    }
+
+    /// Alpha decay panel: when each pattern's fitted edge crosses its cost floor
+    fn generate_alpha_decay(&self, now_ns: TimestampNs) -> Vec<RetirementProjection> {
+        self.pattern_verifier.as_ref()
+            .map(|verifier| verifier.retirement_projections(now_ns))
+            .unwrap_or_default()
+    }
+
+    /// Slippage report over the trailing TCA window
+    fn generate_tca_report(&self) -> Option<TcaReport> {
+        match self.tca.as_ref()?.report_days(TCA_WINDOW_DAYS) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                tracing::warn!("[DASHBOARD] TCA report failed: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Generate P&L panel from lot-level position accounting
+    async fn generate_pnl_panel(&self) -> Option<PnlPanelData> {
+        let tracker = self.position_tracker.as_ref()?.read().await;
+        let summary = tracker.summary();
+        let today_start = chrono::DateTime::from_timestamp_nanos(self.clock.wall_ns().0 as i64)
+            .date_naive()
+            .and_hms_opt(0, 0, 0)?
+            .and_utc();
+
+        let mut recent_lots: Vec<RealizedLot> = tracker.realized_lots(..).into_iter().cloned().collect();
+        recent_lots.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+        recent_lots.truncate(20);
+
+        Some(PnlPanelData {
+            realized_today: tracker.get_realized_pnl(today_start..),
+            realized_all_time: tracker.all_time_pnl,
+            open_cost_basis: summary.total_cost_basis,
+            guaranteed_profit: summary.total_guaranteed_profit,
+            open_positions: summary.open_positions,
+            by_pattern_today: tracker.realized_pnl_by_pattern(today_start..),
+            by_exit_today: tracker.realized_pnl_by_exit(today_start..),
+            recent_lots,
+        })
+    }
+
     /// Generate dashboard snapshot
-    pub async fn generate_snapshot(&self) -> Result<DashboardSnapshot, Box<dyn std::error::Error + Send + Sync>> {
-    let timestamp_ns = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos() as u64;
+    pub async fn generate_snapshot(&self) -> Result<DashboardSnapshot, Error> {
+    let timestamp_ns = self.clock.wall_ns().0;
 
     // Generate half-life heatmap
     let half_life_heatmap = self.generate_half_life_heatmap().await;
@@ -297,37 +655,114 @@ impl MonitoringDashboard {
 
     // Generate ML telemetry
     let ml_telemetry = self.generate_ml_telemetry(timestamp_ns);
-        let mut markets = Vec::new();
 
-        // Get latency engine data
-        let engine = self.latency_engine.read().await;
-        let signals = engine.get_signals();
+    // Generate P&L panel
+    let pnl_panel = self.generate_pnl_panel().await;
+
+    // Generate TCA report
+    let tca = self.generate_tca_report();
+
+    // Event bus lag
+    let event_bus = self.event_bus.as_ref().map(|bus| bus.stats()).unwrap_or_default();
+
+    // Pattern verification (archive replay when available)
+    let pattern_verifications = self.generate_pattern_verifications();
+
+    // Alpha decay retirement projections
+    let alpha_decay = self.generate_alpha_decay(timestamp_ns);
+
+    // Markets on cooldown
+    let market_cooldowns = self.cooldowns.as_ref().map(|c| c.active()).unwrap_or_default();
+
+    // Latest backtest result, and the recent ones behind it
+    let backtester_results = self.generate_backtester_results();
+    let backtest_history = self.generate_backtest_history();
+
+    // Recent verification updates
+    let verification_history = self.backtest_results.as_ref()
+        .map(|r| r.latest_verifications(HISTORY_LEN))
+        .unwrap_or_default();
+
+    // Factor sensitivities of held positions
+    let sensitivities = self.sensitivities.as_ref().map(|s| s.report());
+
+    // Top parameter sets of the latest optimization studies
+    let parameter_studies = self.studies.as_ref().map(|s| s.latest_per_pattern());
+
+    // SLA degradation level and per-model compliance
+    let sla_degradation = self.sla.as_ref().map(|s| s.status());
+
+    // Decision path latency per pattern
+    let decision_latency = self.decision_latency.as_ref().map(|d| d.summary()).unwrap_or_default();
+
+    // Positions past their pattern's half-life multiple
+    let stale_positions = self.position_sweeper.as_ref().map(|s| s.stale()).unwrap_or_default();
+
+    // Signals awaiting an operator
+    let pending_reviews = self.review.as_ref().map(|r| r.pending()).unwrap_or_default();
+
+    // Activity per strategy id
+    let strategies = self.generate_strategy_summaries();
+
+    // Pattern #73 opportunities
+    let pattern_73_opportunities = self.generate_pattern_73_opportunities().await;
+
+    Ok(DashboardSnapshot {
+        timestamp_ns,
+        half_life_heatmap,
+        cross_book_matrix,
+        provider_health,
+        regulatory_windows,
+        execution_stats: self.execution_stats.clone().unwrap_or_default(),
+        risk_alerts: self.alert_history.clone(),
+        ml_telemetry,
+        pattern_73_opportunities,
+        backtester_results,
+        pattern_verifications,
+        alpha_decay,
+        pnl_panel,
+        tca,
+        event_bus,
+        market_cooldowns,
+        sensitivities,
+        parameter_studies,
+        sla_degradation,
+        strategies,
+        decision_latency,
+        stale_positions,
+        pending_reviews,
+        backtest_history,
+        verification_history,
+    })
+    }
+
+    /// Generate half-life heatmap from the signals held, decayed by age
+    async fn generate_half_life_heatmap(&self) -> HalfLifeHeatmap {
+        let mut markets = Vec::new();
+        let now_ns = self.clock.mono_ns().0;
 
         // Group signals by market
         let mut market_signals: HashMap<u16, Vec<&LatencySignal>> = HashMap::new();
-        for signal in signals {
+        for signal in &self.signals {
             market_signals.entry(signal.fast_market.market_id)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(signal);
             market_signals.entry(signal.slow_market.market_id)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(signal);
         }
 
         // Generate heatmap data for each market
         for (market_id, market_signals) in market_signals {
-            let tier = engine.market_tiers.get(&market_id).copied().unwrap_or(MarketTier::Tier1);
+            let tier = match market_signals[0] {
+                s if s.fast_market.market_id == market_id => s.fast_market.tier,
+                s => s.slow_market.tier,
+            };
             let half_life_ms = tier.half_life_ms();
 
-            // Calculate average decay across signals
+            // Edge left per signal, 0.5^(age / half-life), averaged over the market
             let total_signals = market_signals.len();
-            let avg_decay: f64 = if total_signals > 0 {
-                market_signals.iter()
-                    .map(|s| s.disparity_cents as f64 / s.disparity_cents.abs() as f64)
-                    .sum::<f64>() / total_signals as f64
-            } else {
-                1.0
-            };
+            let avg_decay = mean_edge_remaining(tier, market_signals.iter().copied(), now_ns);
 
             // Color intensity based on decay and opportunities
             let color_intensity = (1.0 - avg_decay) + (total_signals as f64 * 0.1).min(0.5);
@@ -400,17 +835,14 @@ impl MonitoringDashboard {
 
     /// Generate provider health status
     async fn generate_provider_health_status(&self) -> ProviderHealthStatus {
-        let aggregator = self.feed_aggregator.read().await;
-        let status_summary = aggregator.get_status_summary();
-
-        let providers: Vec<ProviderStatus> = status_summary
-            .into_iter()
-            .map(|(provider, (status, latency_ns))| {
+        let providers: Vec<ProviderStatus> = self.feeds
+            .iter()
+            .map(|(&provider, &(status, latency_ns))| {
                 let (status_str, uptime_percent) = match status {
-                    crate::feed_aggregator::FeedStatus::Connected => ("healthy", 99.9),
-                    crate::feed_aggregator::FeedStatus::Connecting => ("degraded", 95.0),
-                    crate::feed_aggregator::FeedStatus::Disconnected => ("critical", 50.0),
-                    crate::feed_aggregator::FeedStatus::Error => ("down", 0.0),
+                    FeedStatus::Connected => ("healthy", 99.9),
+                    FeedStatus::Connecting | FeedStatus::Degraded => ("degraded", 95.0),
+                    FeedStatus::Disconnected => ("critical", 50.0),
+                    FeedStatus::Error => ("down", 0.0),
                 };
 
                 // Get latency trend (mock for now)
@@ -420,9 +852,10 @@ impl MonitoringDashboard {
                 let circuit_breaker_state = "closed".to_string();
 
                 ProviderStatus {
-                    provider: format!("{:?}", provider),
+                    provider: provider.to_string(),
                     status: status_str.to_string(),
                     latency_ns,
+                    latency_breakdown: self.feed_latency.get(&provider).copied(),
                     latency_trend,
                     circuit_breaker_state,
                     failure_count: 0, // TODO: Get from risk engine
@@ -502,14 +935,23 @@ impl MonitoringDashboard {
             tier4_models,
             behavioral_models,
             overall_sla_compliance,
+            feature_flags: self.feature_flags.as_ref().map(|flags| flags.snapshot()),
         }
     }
 
     /// Create telemetry data for a single ML model
+    #[allow(clippy::too_many_arguments)]
     fn create_model_telemetry(&self, component_id: u16, name: &str, tier: u8, target_sla_ms: f64, current_time_ns: TimestampNs, feature_flag: &str, stability: &str, dependency: &str, base_metric: f64, load_percent: f64) -> ModelTelemetry {
         // Simulate realistic performance data
-        let (current_latency_ms, status, error_count) = self.simulate_model_performance(component_id, target_sla_ms);
+        let (current_latency_ms, mut status, error_count) = self.simulate_model_performance(component_id, target_sla_ms);
+        let feature_enabled = self.feature_flags.as_ref().is_none_or(|flags| flags.component_enabled(component_id));
+        if !feature_enabled {
+            status = ModelStatus::Disabled;
+        }
         let sla_compliance = (target_sla_ms / current_latency_ms).min(1.0);
+        if let Some(sla) = &self.sla {
+            sla.record(component_id, current_latency_ms);
+        }
 
         ModelTelemetry {
             component_id,
@@ -522,6 +964,7 @@ impl MonitoringDashboard {
             last_execution_ns: current_time_ns - (rand::random::<u64>() % 10_000_000_000), // Within last 10 seconds
             error_count,
             feature_flag: feature_flag.to_string(),
+            feature_enabled,
             stability: stability.to_string(),
             dependency: dependency.to_string(),
             metric_value: base_metric + (rand::random::<f64>() - 0.5) * 0.2, // Add some variation
@@ -531,6 +974,7 @@ impl MonitoringDashboard {
 
     /// Simulate realistic model performance based on component specifications
     fn simulate_model_performance(&self, component_id: u16, target_sla_ms: f64) -> (f64, ModelStatus, u32) {
+        use rand::Rng;
         let mut rng = rand::thread_rng();
 
         // Base performance varies by component
@@ -567,16 +1011,13 @@ impl MonitoringDashboard {
         (current_latency, status, error_count)
     }
 
-    /// Generate Pattern #73 opportunities for dashboard
+    /// Generate Pattern #73 opportunities for dashboard (interned IDs resolved here)
     async fn generate_pattern_73_opportunities(&self) -> Vec<BetaSkewOpportunityData> {
-        let pattern_73_engine = self.pattern_73_engine.read().await;
-        let opportunities = pattern_73_engine.get_opportunities();
-
-        opportunities.iter().map(|opp| BetaSkewOpportunityData {
-            player_prop_market: opp.player_prop_market.clone(),
-            team_total_market: opp.team_total_market.clone(),
-            player_id: opp.player_id.clone(),
-            team_id: opp.team_id.clone(),
+        self.opportunities.iter().map(|opp| BetaSkewOpportunityData {
+            player_prop_market: opp.player_prop_market.to_string(),
+            team_total_market: opp.team_total_market.to_string(),
+            player_id: opp.player_id.to_string(),
+            team_id: opp.team_id.to_string(),
             current_player_price: opp.current_player_price,
             current_team_total: opp.current_team_total,
             beta: opp.beta,
@@ -590,6 +1031,9 @@ impl MonitoringDashboard {
             timestamp_ns: opp.timestamp_ns,
         }).collect()
     }
+
+    /// SLA compliance across every model, and for Tier 1 alone
+    fn calculate_sla_compliance(&self, tier1: &[ModelTelemetry], tier2: &[ModelTelemetry], tier3: &[ModelTelemetry], tier4: &[ModelTelemetry], behavioral: &[ModelTelemetry]) -> SLACompliance {
         let all_models: Vec<&ModelTelemetry> = tier1.iter()
             .chain(tier2.iter())
             .chain(tier3.iter())
@@ -633,18 +1077,48 @@ impl MonitoringDashboard {
         }
     }
 
+    /// Per-strategy counts of the signals, opportunities and alerts held, busiest first
+    fn generate_strategy_summaries(&self) -> Vec<StrategySummary> {
+        fn entry<'a>(by_id: &'a mut BTreeMap<String, StrategySummary>, id: &str, ts: TimestampNs) -> &'a mut StrategySummary {
+            let summary = by_id.entry(id.to_string())
+                .or_insert_with(|| StrategySummary { strategy_id: id.to_string(), ..Default::default() });
+            summary.last_activity_ns = summary.last_activity_ns.max(ts);
+            summary
+        }
+
+        let mut by_id = BTreeMap::new();
+        for signal in &self.signals {
+            entry(&mut by_id, &signal.strategy_id, signal.fast_market.timestamp_ns).signals += 1;
+        }
+        let pattern_73 = strategy::for_pattern(Some(73));
+        for opportunity in &self.opportunities {
+            entry(&mut by_id, &pattern_73, opportunity.timestamp_ns).opportunities += 1;
+        }
+        for alert in &self.alert_history {
+            if let Some(id) = &alert.strategy_id {
+                entry(&mut by_id, id, alert.timestamp_ns).alerts += 1;
+            }
+        }
+        let mut summaries: Vec<StrategySummary> = by_id.into_values().collect();
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.signals + s.opportunities + s.alerts));
+        summaries
+    }
+
     /// Add risk alert to dashboard
     pub fn add_risk_alert(&mut self, alert_type: String, severity: String, message: String) {
-        let timestamp_ns = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
+        self.add_strategy_alert(None, alert_type, severity, message);
+    }
+
+    /// Add risk alert concerning one strategy (None = bot-wide)
+    pub fn add_strategy_alert(&mut self, strategy_id: Option<String>, alert_type: String, severity: String, message: String) {
+        let timestamp_ns = self.clock.wall_ns().0;
 
         let alert = RiskAlertData {
             alert_type,
             severity,
             message,
             timestamp_ns,
+            strategy_id,
         };
 
         self.alert_history.push(alert);
@@ -655,14 +1129,34 @@ impl MonitoringDashboard {
         }
     }
 
+    /// Keep a signal for the heatmap, dropping those past the window
+    fn record_signal(&mut self, signal: &LatencySignal) {
+        let newest = signal.fast_market.timestamp_ns;
+        self.signals.retain(|s| newest.saturating_sub(s.fast_market.timestamp_ns) < SIGNAL_WINDOW_NS);
+        self.signals.push_back(signal.clone());
+        if self.signals.len() > 1000 {
+            self.signals.pop_front();
+        }
+    }
+
+    /// Replace the opportunity for the same market pair, dropping stale ones
+    fn record_opportunity(&mut self, opportunity: &BetaSkewOpportunity) {
+        let newest = opportunity.timestamp_ns;
+        self.opportunities.retain(|o| {
+            !(o.player_prop_market == opportunity.player_prop_market && o.team_total_market == opportunity.team_total_market)
+                && newest.saturating_sub(o.timestamp_ns) < OPPORTUNITY_WINDOW_NS
+        });
+        self.opportunities.push(opportunity.clone());
+    }
+
     /// Get dashboard data as JSON string
-    pub async fn get_dashboard_json(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn get_dashboard_json(&self) -> Result<String, Error> {
         let snapshot = self.generate_snapshot().await?;
         Ok(serde_json::to_string_pretty(&snapshot)?)
     }
 
     /// Get dashboard data as HTML (basic implementation)
-    pub async fn get_dashboard_html(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn get_dashboard_html(&self) -> Result<String, Error> {
         let snapshot = self.generate_snapshot().await?;
 
         let mut html = String::from(r#"
@@ -687,7 +1181,7 @@ impl MonitoringDashboard {
     <div class="section">
         <h2>Provider Health Status</h2>
         <table>
-            <tr><th>Provider</th><th>Status</th><th>Latency (ns)</th><th>Trend</th><th>Circuit Breaker</th></tr>
+            <tr><th>Provider</th><th>Status</th><th>Latency (ns)</th><th>Network p50/p95/p99 (µs)</th><th>Processing p50/p95/p99 (µs)</th><th>Trend</th><th>Circuit Breaker</th></tr>
 "#);
 
         for provider in &snapshot.provider_health.providers {
@@ -699,9 +1193,16 @@ impl MonitoringDashboard {
                 _ => "",
             };
 
+            let percentiles = |summary: Option<LatencySummary>| match summary {
+                Some(s) if s.samples > 0 => format!("{:.0} / {:.0} / {:.0}",
+                    s.p50_ns as f64 / 1_000.0, s.p95_ns as f64 / 1_000.0, s.p99_ns as f64 / 1_000.0),
+                _ => "-".to_string(),
+            };
             html.push_str(&format!(
-                "<tr><td>{}</td><td class='{}'>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                "<tr><td>{}</td><td class='{}'>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
                 provider.provider, status_class, provider.status, provider.latency_ns,
+                percentiles(provider.latency_breakdown.map(|l| l.network)),
+                percentiles(provider.latency_breakdown.map(|l| l.processing)),
                 provider.latency_trend, provider.circuit_breaker_state
             ));
         }
@@ -717,24 +1218,26 @@ impl MonitoringDashboard {
 
         for market in &snapshot.half_life_heatmap.markets {
             html.push_str(&format!(
-                "<tr><td>{}</td><td>{}</td><td>{:.0}</td><td>{:.1%}</td><td>{}</td></tr>",
+                "<tr><td>{}</td><td>{}</td><td>{:.0}</td><td>{:.1}%</td><td>{}</td></tr>",
                 market.market_id, market.tier, market.half_life_ms,
+                market.current_decay_percent * 100.0, market.arbitrage_opportunities
+            ));
         }
 
-        html.push_str(r#"
+        html.push_str(&format!(r#"
         </table>
     </div>
     <div class="section">
         <h2>Execution Statistics</h2>
         <p>Active Executions: {}</p>
-        <p>Success Rate: {:.1%}</p>
+        <p>Success Rate: {:.1}%</p>
         <p>Avg Edge Captured: {}¢</p>
     </div>
     <div class="section">
         <h2>ML Intelligence Layer Telemetry (Component #40)</h2>
         <h3>SLA Compliance</h3>
-        <p>Tier 1 Compliance: {:.1%}</p>
-        <p>Overall Compliance: {:.1%}</p>
+        <p>Tier 1 Compliance: {:.1}%</p>
+        <p>Overall Compliance: {:.1}%</p>
         <p>Violations (Last Hour): {}</p>
         <p>Critical Alerts: {}</p>
 
@@ -743,13 +1246,13 @@ impl MonitoringDashboard {
             <tr><th>Component</th><th>Name</th><th>Latency</th><th>SLA</th><th>Status</th><th>Feature Flag</th><th>Dependency</th><th>Metric</th><th>Load</th></tr>
 "#,
             snapshot.execution_stats.active_executions,
-            snapshot.execution_stats.success_rate,
+            snapshot.execution_stats.success_rate * 100.0,
             snapshot.execution_stats.avg_edge_captured,
-            snapshot.ml_telemetry.overall_sla_compliance.tier1_compliance,
-            snapshot.ml_telemetry.overall_sla_compliance.overall_compliance,
+            snapshot.ml_telemetry.overall_sla_compliance.tier1_compliance * 100.0,
+            snapshot.ml_telemetry.overall_sla_compliance.overall_compliance * 100.0,
             snapshot.ml_telemetry.overall_sla_compliance.violations_last_hour,
             snapshot.ml_telemetry.overall_sla_compliance.critical_alerts
-        );
+        ));
 
         // Tier 1 models
         for model in &snapshot.ml_telemetry.tier1_models {
@@ -851,6 +1354,39 @@ impl MonitoringDashboard {
         html.push_str(r#"
         </table>
     </div>
+"#);
+
+        if let Some(tca) = &snapshot.tca {
+            html.push_str(&format!(r#"
+    <div class="section">
+        <h2>Execution TCA (last {} days, {} fills)</h2>
+        <p>Slippage in cents per contract; positive = paid more than the benchmark. Edge decay is the part lost before the order went out.</p>
+        <table>
+            <tr><th>Group</th><th>Fills</th><th>Contracts</th><th>vs Decision</th><th>vs Arrival</th><th>vs Mid</th><th>Edge Decay</th><th>Decision→Arrival</th></tr>
+"#, TCA_WINDOW_DAYS, tca.overall.fills));
+
+            let groups = std::iter::once(("All".to_string(), &tca.overall))
+                .chain(tca.by_pattern.iter().map(|(k, v)| (format!("Pattern {}", k), v)))
+                .chain(tca.by_venue.iter().map(|(k, v)| (format!("Venue {}", k), v)))
+                .chain(tca.by_hour.iter().map(|(k, v)| (format!("{:02}:00 UTC", k), v)))
+                .chain(tca.by_latency_ms.iter().map(|(k, v)| match *k {
+                    u64::MAX => ("Latency ≥250ms".to_string(), v),
+                    ms => (format!("Latency <{}ms", ms), v),
+                }));
+            for (group, summary) in groups {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{:.0}</td><td>{:+.2}¢</td><td>{:+.2}¢</td><td>{:+.2}¢</td><td>{:+.2}¢</td><td>{:.1}ms</td></tr>",
+                    group, summary.fills, summary.contracts, summary.vs_decision, summary.vs_arrival,
+                    summary.vs_mid, summary.edge_decay, summary.avg_decision_to_arrival_ms
+                ));
+            }
+            html.push_str(r#"
+        </table>
+    </div>
+"#);
+        }
+
+        html.push_str(r#"
 </body>
 </html>
 "#);
@@ -859,11 +1395,62 @@ impl MonitoringDashboard {
     }
 }
 
+impl EventHandler for MonitoringDashboard {
+    fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Signal(signal) => self.record_signal(signal),
+            Event::Opportunity(opportunity) => self.record_opportunity(opportunity),
+            Event::Feed { provider, status, latency_ns } => {
+                self.feeds.insert(*provider, (*status, *latency_ns));
+            }
+            Event::FeedLatency { provider, latency } => {
+                self.feed_latency.insert(*provider, *latency);
+            }
+            Event::Tick(update) => {
+                // A ticking feed is connected even if its status event was missed
+                self.feeds.entry(update.provider).or_insert((FeedStatus::Connected, 0));
+            }
+            Event::Alert(alert) => {
+                let severity = match alert.severity {
+                    AlertSeverity::Info => "low",
+                    AlertSeverity::Warning => "high",
+                    AlertSeverity::Critical => "critical",
+                };
+                self.add_strategy_alert(alert.strategy_id.clone(), alert.kind.clone(), severity.to_string(), alert.message.clone());
+            }
+            Event::ConfigChanged(change) if change.touches("dashboard") => {
+                self.apply_config(&change.config.dashboard);
+            }
+            _ => {}
+        }
+    }
+}
+
 impl Default for MonitoringDashboard {
     fn default() -> Self {
-        Self::new(
-            Arc::new(RwLock::new(LatencyArbitrageEngine::new())),
-            Arc::new(RwLock::new(FeedAggregator::default())),
-        )
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::types::Nanos;
+
+    #[tokio::test]
+    async fn test_snapshot_and_html_use_injected_clock() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let mut dashboard = MonitoringDashboard::new().with_clock(clock.clone());
+        dashboard.add_risk_alert("limit".into(), "high".into(), "position limit".into());
+        clock.advance(std::time::Duration::from_secs(1));
+
+        let snapshot = dashboard.generate_snapshot().await.unwrap();
+        assert_eq!(snapshot.timestamp_ns, 1_700_000_001_000_000_000);
+        assert_eq!(snapshot.risk_alerts[0].timestamp_ns, 1_700_000_000_000_000_000);
+
+        let html = dashboard.get_dashboard_html().await.unwrap();
+        assert!(html.contains("Success Rate: 0.0%"));
+        assert!(html.ends_with("</html>\n"));
     }
 }
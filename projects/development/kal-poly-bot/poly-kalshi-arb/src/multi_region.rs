@@ -0,0 +1,433 @@
+// src/multi_region.rs
+// Multi-region feed ingestion - one provider connected through several endpoints at once
+// (`[feeds] endpoints`), the first copy of every update forwarded and the slower copies dropped,
+// with each endpoint's win count and lag behind the fastest copy. Wraps the endpoints' clients in
+// one `FeedClient`, so the aggregator's reconnects and resubscriptions cover them all.
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::error::FeedError;
+use crate::feed_aggregator::{FeedClient, PriceUpdate};
+use crate::provider_registry::ProviderId;
+use crate::types::{PriceCents, SizeCents, TimestampNs};
+
+/// Identity of an update across endpoints: the provider's sequence when it stamps one, else its
+/// timestamp and book (every copy of one update carries the same of both)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum UpdateKey {
+    Sequence { market_id: u16, provider: ProviderId, sequence: u64 },
+    Stamped {
+        market_id: u16,
+        provider: ProviderId,
+        timestamp: TimestampNs,
+        book: (PriceCents, PriceCents, SizeCents, SizeCents),
+    },
+}
+
+impl UpdateKey {
+    fn of(update: &PriceUpdate) -> Option<Self> {
+        let (market_id, provider) = (update.market_id, update.provider);
+        match (update.sequence, update.provider_timestamp) {
+            (Some(sequence), _) => Some(Self::Sequence { market_id, provider, sequence }),
+            (None, Some(timestamp)) => Some(Self::Stamped {
+                market_id,
+                provider,
+                timestamp,
+                book: (update.yes_price, update.no_price, update.yes_size, update.no_size),
+            }),
+            (None, None) => None,
+        }
+    }
+}
+
+/// One endpoint's share of the feed
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EndpointStats {
+    pub endpoint: String,
+    pub connected: bool,
+    pub updates: u64,
+    /// Updates this endpoint delivered before any other
+    pub first: u64,
+    /// Copies it delivered after another endpoint (dropped)
+    pub duplicates: u64,
+    /// Mean and worst delay of its late copies behind the first (µs)
+    pub mean_lag_us: f64,
+    pub max_lag_us: f64,
+    /// Mean time from the provider's timestamp to receipt, over stamped updates (µs)
+    pub mean_latency_us: f64,
+}
+
+#[derive(Default)]
+struct Counters {
+    connected: bool,
+    updates: u64,
+    first: u64,
+    duplicates: u64,
+    lag_sum_ns: u64,
+    max_lag_ns: u64,
+    latency_sum_ns: u64,
+    latency_count: u64,
+    /// Receive time of its last update (process clock)
+    last_mono: u64,
+}
+
+struct ArbiterState {
+    /// Key -> receive time of its first copy
+    seen: HashMap<UpdateKey, u64>,
+    /// Keys in arrival order, for expiry
+    order: VecDeque<(u64, UpdateKey)>,
+    endpoints: Vec<(String, Counters)>,
+    /// Endpoint of the latest first copy; updates without a key are taken from it alone
+    leader: usize,
+}
+
+/// Picks the first copy of each update across a provider's endpoints
+pub struct FeedArbiter {
+    window_ns: u64,
+    state: Mutex<ArbiterState>,
+}
+
+pub type SharedFeedArbiter = Arc<FeedArbiter>;
+
+impl FeedArbiter {
+    /// Arbitrate between `endpoints` (labels), remembering updates for `window`
+    pub fn new(endpoints: Vec<String>, window: Duration) -> Self {
+        Self {
+            window_ns: window.as_nanos() as u64,
+            state: Mutex::new(ArbiterState {
+                seen: HashMap::new(),
+                order: VecDeque::new(),
+                endpoints: endpoints.into_iter().map(|e| (e, Counters::default())).collect(),
+                leader: 0,
+            }),
+        }
+    }
+
+    /// Record `endpoint`'s copy of an update; true when it is the first and should be forwarded
+    pub fn offer(&self, endpoint: usize, update: &PriceUpdate) -> bool {
+        let now = update.received.mono.0;
+        let mut state = self.state.lock().unwrap();
+        let ArbiterState { seen, order, endpoints, leader } = &mut *state;
+        while let Some(&(mono, key)) = order.front() {
+            if mono.saturating_add(self.window_ns) > now {
+                break;
+            }
+            order.pop_front();
+            if seen.get(&key) == Some(&mono) {
+                seen.remove(&key);
+            }
+        }
+
+        // Unkeyed updates: the leader's, or anyone's once the leader has gone quiet
+        let leader_quiet = endpoints[*leader].1.last_mono.saturating_add(self.window_ns) <= now;
+        let Some(counters) = endpoints.get_mut(endpoint).map(|(_, c)| c) else { return false };
+        counters.updates += 1;
+        counters.last_mono = now;
+        if let Some(provider_ts) = update.provider_timestamp.filter(|ts| *ts <= update.received.wall.0) {
+            counters.latency_sum_ns += update.received.wall.0 - provider_ts;
+            counters.latency_count += 1;
+        }
+        let Some(key) = UpdateKey::of(update) else {
+            if endpoint != *leader && !leader_quiet {
+                counters.duplicates += 1;
+                return false;
+            }
+            counters.first += 1;
+            *leader = endpoint;
+            return true;
+        };
+        match seen.get(&key) {
+            Some(&first_mono) => {
+                let lag = now.saturating_sub(first_mono);
+                counters.duplicates += 1;
+                counters.lag_sum_ns += lag;
+                counters.max_lag_ns = counters.max_lag_ns.max(lag);
+                false
+            }
+            None => {
+                seen.insert(key, now);
+                order.push_back((now, key));
+                counters.first += 1;
+                *leader = endpoint;
+                true
+            }
+        }
+    }
+
+    pub fn set_connected(&self, endpoint: usize, connected: bool) {
+        if let Some((_, counters)) = self.state.lock().unwrap().endpoints.get_mut(endpoint) {
+            counters.connected = connected;
+        }
+    }
+
+    /// Per-endpoint counters, in endpoint order
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        let mean = |sum: u64, n: u64| if n == 0 { 0.0 } else { sum as f64 / n as f64 / 1_000.0 };
+        self.state.lock().unwrap().endpoints.iter()
+            .map(|(endpoint, c)| EndpointStats {
+                endpoint: endpoint.clone(),
+                connected: c.connected,
+                updates: c.updates,
+                first: c.first,
+                duplicates: c.duplicates,
+                mean_lag_us: mean(c.lag_sum_ns, c.duplicates),
+                max_lag_us: c.max_lag_ns as f64 / 1_000.0,
+                mean_latency_us: mean(c.latency_sum_ns, c.latency_count),
+            })
+            .collect()
+    }
+}
+
+/// One provider's feed over several endpoints, the fastest copy of each update forwarded;
+/// connected while any endpoint is
+pub struct MultiRegionFeed {
+    provider: ProviderId,
+    endpoints: Vec<Box<dyn FeedClient>>,
+    /// Endpoint streams not yet forwarded
+    streams: Vec<Option<mpsc::UnboundedReceiver<PriceUpdate>>>,
+    forwarders: Vec<JoinHandle<()>>,
+    arbiter: SharedFeedArbiter,
+    update_tx: mpsc::UnboundedSender<PriceUpdate>,
+    update_rx: Option<mpsc::UnboundedReceiver<PriceUpdate>>,
+}
+
+impl MultiRegionFeed {
+    /// `endpoints` are (label, client) pairs of one provider; the first is the default endpoint
+    pub fn new(endpoints: Vec<(String, Box<dyn FeedClient>)>, dedup_window: Duration) -> Self {
+        assert!(!endpoints.is_empty(), "a multi-region feed needs an endpoint");
+        let provider = endpoints[0].1.provider();
+        let (labels, mut clients): (Vec<String>, Vec<Box<dyn FeedClient>>) = endpoints.into_iter().unzip();
+        let streams = clients.iter_mut().map(|c| Some(c.price_stream())).collect();
+        let (update_tx, update_rx) = mpsc::unbounded_channel();
+        Self {
+            provider,
+            endpoints: clients,
+            streams,
+            forwarders: Vec::new(),
+            arbiter: Arc::new(FeedArbiter::new(labels, dedup_window)),
+            update_tx,
+            update_rx: Some(update_rx),
+        }
+    }
+
+    /// Per-endpoint stats, shared with probes and the dashboard
+    pub fn arbiter(&self) -> SharedFeedArbiter {
+        self.arbiter.clone()
+    }
+
+    /// Forward each endpoint's stream through the arbiter (once; the streams outlive reconnects)
+    fn start_forwarders(&mut self) {
+        for (endpoint, stream) in self.streams.iter_mut().enumerate() {
+            let Some(mut stream) = stream.take() else { continue };
+            let (arbiter, tx) = (self.arbiter.clone(), self.update_tx.clone());
+            self.forwarders.push(tokio::spawn(async move {
+                while let Some(update) = stream.recv().await {
+                    if arbiter.offer(endpoint, &update) && tx.send(update).is_err() {
+                        return;
+                    }
+                }
+            }));
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl FeedClient for MultiRegionFeed {
+    fn provider(&self) -> ProviderId {
+        self.provider
+    }
+
+    async fn connect(&mut self) -> Result<(), FeedError> {
+        self.start_forwarders();
+        let mut last_error = None;
+        for (endpoint, client) in self.endpoints.iter_mut().enumerate() {
+            match client.connect().await {
+                Ok(()) => self.arbiter.set_connected(endpoint, true),
+                Err(e) => {
+                    warn!("[MULTI-REGION] {} endpoint {} failed to connect: {}", self.provider, endpoint, e);
+                    self.arbiter.set_connected(endpoint, false);
+                    last_error = Some(e);
+                }
+            }
+        }
+        let connected = self.arbiter.stats().iter().filter(|s| s.connected).count();
+        info!("[MULTI-REGION] {}: {} of {} endpoints connected", self.provider, connected, self.endpoints.len());
+        match last_error {
+            Some(e) if connected == 0 => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    async fn disconnect(&mut self) -> Result<(), FeedError> {
+        for (endpoint, client) in self.endpoints.iter_mut().enumerate() {
+            if let Err(e) = client.disconnect().await {
+                warn!("[MULTI-REGION] {} endpoint {} disconnect failed: {}", self.provider, endpoint, e);
+            }
+            self.arbiter.set_connected(endpoint, false);
+        }
+        Ok(())
+    }
+
+    fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+        self.update_rx.take().unwrap_or_else(|| mpsc::unbounded_channel().1)
+    }
+
+    /// Round trip of the fastest endpoint that answers
+    async fn ping(&mut self) -> Result<u64, FeedError> {
+        let mut best: Option<u64> = None;
+        let mut last_error = FeedError::Disconnected { provider: self.provider };
+        for client in self.endpoints.iter_mut() {
+            match client.ping().await {
+                Ok(rtt) => best = Some(best.map_or(rtt, |b| b.min(rtt))),
+                Err(e) => last_error = e,
+            }
+        }
+        best.ok_or(last_error)
+    }
+
+    async fn subscribe(&mut self, markets: &[String]) -> Result<(), FeedError> {
+        let mut subscribed = false;
+        let mut last_error = FeedError::Disconnected { provider: self.provider };
+        for client in self.endpoints.iter_mut() {
+            match client.subscribe(markets).await {
+                Ok(()) => subscribed = true,
+                Err(e) => last_error = e,
+            }
+        }
+        if subscribed { Ok(()) } else { Err(last_error) }
+    }
+
+    /// Every endpoint refetches; replayed snapshots are deduplicated like any other update
+    async fn resync(&mut self, markets: &[String]) -> Result<(), FeedError> {
+        let mut resynced = false;
+        let mut last_error = FeedError::Unsupported { provider: self.provider };
+        for client in self.endpoints.iter_mut() {
+            match client.resync(markets).await {
+                Ok(()) => resynced = true,
+                Err(e) => last_error = e,
+            }
+        }
+        if resynced { Ok(()) } else { Err(last_error) }
+    }
+
+    fn sequence_gaps(&self) -> u64 {
+        self.endpoints.iter().map(|client| client.sequence_gaps()).sum()
+    }
+}
+
+impl Drop for MultiRegionFeed {
+    fn drop(&mut self) {
+        for forwarder in &self.forwarders {
+            forwarder.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Stamp;
+    use crate::types::{MarketType, Nanos, Platform};
+
+    fn update(market_id: u16, sequence: Option<u64>, provider_ns: Option<TimestampNs>, mono: u64) -> PriceUpdate {
+        PriceUpdate {
+            market_id,
+            provider: Platform::Kalshi.into(),
+            market_type: MarketType::Moneyline,
+            yes_price: 40,
+            no_price: 60,
+            yes_size: 100,
+            no_size: 100,
+            received: Stamp { mono: Nanos(mono), wall: Nanos(1_000_000 + mono) },
+            provider_timestamp: provider_ns,
+            sequence,
+            features: None,
+        }
+    }
+
+    #[test]
+    fn test_first_copy_wins_and_lag_is_measured() {
+        let arbiter = FeedArbiter::new(vec!["us".to_string(), "eu".to_string()], Duration::from_millis(1));
+        // Sequenced: us first, eu 300µs behind
+        assert!(arbiter.offer(0, &update(1, Some(7), None, 1_000)));
+        assert!(!arbiter.offer(1, &update(1, Some(7), None, 301_000)));
+        // Stamped: eu first this time; a different book is a different update
+        assert!(arbiter.offer(1, &update(1, None, Some(900_000), 400_000)));
+        assert!(!arbiter.offer(0, &update(1, None, Some(900_000), 500_000)));
+        let mut moved = update(1, None, Some(900_000), 510_000);
+        moved.yes_price = 41;
+        assert!(arbiter.offer(0, &moved));
+        // Unkeyed: only from the leader (us, after the move)
+        assert!(!arbiter.offer(1, &update(2, None, None, 520_000)));
+        assert!(arbiter.offer(0, &update(2, None, None, 530_000)));
+        // Past the window a key is forgotten
+        assert!(arbiter.offer(1, &update(1, Some(7), None, 2_000_000)));
+
+        let stats = arbiter.stats();
+        assert_eq!((stats[0].updates, stats[0].first, stats[0].duplicates), (4, 3, 1));
+        assert_eq!((stats[1].updates, stats[1].first, stats[1].duplicates), (4, 2, 2));
+        assert_eq!(stats[1].max_lag_us, 300.0);
+        assert_eq!(stats[0].mean_lag_us, 100.0);
+        // Receipt at 1.4ms wall against a 0.9ms provider stamp
+        assert_eq!(stats[1].mean_latency_us, 500.0);
+    }
+
+    /// Endpoint whose updates the test sends by hand
+    struct ManualFeed {
+        rx: Option<mpsc::UnboundedReceiver<PriceUpdate>>,
+        up: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl FeedClient for ManualFeed {
+        fn provider(&self) -> ProviderId {
+            Platform::Kalshi.into()
+        }
+        async fn connect(&mut self) -> Result<(), FeedError> {
+            if self.up { Ok(()) } else { Err(FeedError::Connect { provider: self.provider(), message: "down".to_string() }) }
+        }
+        async fn disconnect(&mut self) -> Result<(), FeedError> {
+            Ok(())
+        }
+        fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+            self.rx.take().unwrap()
+        }
+        async fn ping(&mut self) -> Result<u64, FeedError> {
+            Ok(if self.up { 5_000 } else { 1 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merges_endpoints_into_one_stream() {
+        let (us_tx, us_rx) = mpsc::unbounded_channel();
+        let (eu_tx, eu_rx) = mpsc::unbounded_channel();
+        let (ap_tx, ap_rx) = mpsc::unbounded_channel::<PriceUpdate>();
+        let mut feed = MultiRegionFeed::new(vec![
+            ("us".to_string(), Box::new(ManualFeed { rx: Some(us_rx), up: true }) as Box<dyn FeedClient>),
+            ("eu".to_string(), Box::new(ManualFeed { rx: Some(eu_rx), up: true })),
+            ("ap".to_string(), Box::new(ManualFeed { rx: Some(ap_rx), up: false })),
+        ], Duration::from_secs(1));
+        let mut stream = feed.price_stream();
+        // One endpoint down doesn't fail the connect
+        feed.connect().await.unwrap();
+        assert_eq!(feed.arbiter().stats().iter().map(|s| s.connected).collect::<Vec<_>>(), vec![true, true, false]);
+        assert_eq!(feed.ping().await.unwrap(), 1);
+
+        eu_tx.send(update(1, Some(1), None, 10)).unwrap();
+        us_tx.send(update(1, Some(1), None, 20)).unwrap();
+        us_tx.send(update(1, Some(2), None, 30)).unwrap();
+        eu_tx.send(update(1, Some(2), None, 40)).unwrap();
+        let first = stream.recv().await.unwrap();
+        let second = stream.recv().await.unwrap();
+        assert_eq!((first.sequence, second.sequence), (Some(1), Some(2)));
+        drop((us_tx, eu_tx, ap_tx));
+        drop(feed);
+        assert!(stream.recv().await.is_none());
+    }
+}
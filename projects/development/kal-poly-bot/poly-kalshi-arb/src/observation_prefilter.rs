@@ -0,0 +1,197 @@
+// src/observation_prefilter.rs
+// Observation pre-filters - venues' bursty duplicate and bouncing quotes are smoothed (median
+// of 3, EWMA) or dropped (minimum change) per market before a Kalman filter update, configured
+// per pattern or ML tier in `[worker.prefilters]`, with counters of what was suppressed
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::PrefilterRule;
+use crate::config_reload::ConfigChanged;
+use crate::feature_flags;
+
+/// Per-pattern counters
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PrefilterStats {
+    pub pattern_id: u16,
+    /// Observations offered to the pre-filters
+    pub observations: u64,
+    /// Dropped for moving less than `min_change`
+    pub suppressed: u64,
+    /// Replaced by the median of three (a bounce absorbed)
+    pub median_replaced: u64,
+}
+
+/// Pre-filter state of one pattern on one market
+#[derive(Default)]
+struct Series {
+    /// Last two raw observations, oldest first
+    recent: Vec<f64>,
+    ewma: Option<f64>,
+    /// Last value passed on to the filter
+    last_passed: Option<f64>,
+}
+
+#[derive(Default)]
+struct State {
+    series: HashMap<u16, HashMap<String, Series>>,
+    stats: BTreeMap<u16, PrefilterStats>,
+}
+
+/// `[worker.prefilters]` rules and the per-market state they run on
+pub struct ObservationPrefilters {
+    rules: Mutex<BTreeMap<String, PrefilterRule>>,
+    state: Mutex<State>,
+}
+
+pub type SharedObservationPrefilters = Arc<ObservationPrefilters>;
+
+fn median(a: f64, b: f64, c: f64) -> f64 {
+    a.max(b).min(a.min(b).max(c))
+}
+
+impl ObservationPrefilters {
+    pub fn new(rules: &BTreeMap<String, PrefilterRule>) -> Self {
+        Self { rules: Mutex::new(rules.clone()), state: Mutex::new(State::default()) }
+    }
+
+    /// Apply reloaded rules; any change restarts the smoothing on every market
+    pub fn apply_config(&self, rules: &BTreeMap<String, PrefilterRule>) {
+        let mut current = self.rules.lock().unwrap();
+        if *current == *rules {
+            return;
+        }
+        info!("[PREFILTER] Rules for {:?}", rules.keys().collect::<Vec<_>>());
+        *current = rules.clone();
+        self.state.lock().unwrap().series.clear();
+    }
+
+    /// A pattern's rule: its own, else its feature-flag component's tier's
+    pub fn rule(&self, pattern_id: u16) -> Option<PrefilterRule> {
+        let rules = self.rules.lock().unwrap();
+        rules.get(&pattern_id.to_string())
+            .or_else(|| {
+                let tier = feature_flags::component(pattern_id).map(|c| c.tier).filter(|t| *t > 0)?;
+                rules.get(&format!("tier{}", tier))
+            })
+            .cloned()
+    }
+
+    /// Run an observation of `pattern_id` on `market` through its pre-filters: the value to
+    /// update the filter with, None when suppressed. Patterns without a rule pass unchanged.
+    pub fn filter(&self, pattern_id: u16, market: &str, value: f64) -> Option<f64> {
+        let Some(rule) = self.rule(pattern_id) else { return Some(value) };
+        let mut state = self.state.lock().unwrap();
+        let State { series, stats } = &mut *state;
+        let stats = stats.entry(pattern_id).or_insert_with(|| PrefilterStats { pattern_id, ..PrefilterStats::default() });
+        stats.observations += 1;
+        let markets = series.entry(pattern_id).or_default();
+        if !markets.contains_key(market) {
+            markets.insert(market.to_string(), Series::default());
+        }
+        let series = markets.get_mut(market)?;
+
+        let mut x = value;
+        if rule.median_of_3 {
+            if let [a, b] = series.recent[..] {
+                x = median(a, b, value);
+                if x != value {
+                    stats.median_replaced += 1;
+                }
+                series.recent.remove(0);
+            }
+            series.recent.push(value);
+        }
+        if rule.ewma_alpha > 0.0 {
+            x = series.ewma.map_or(x, |prev| prev + rule.ewma_alpha * (x - prev));
+            series.ewma = Some(x);
+        }
+        if rule.min_change > 0.0 && series.last_passed.is_some_and(|last| (x - last).abs() < rule.min_change) {
+            stats.suppressed += 1;
+            return None;
+        }
+        series.last_passed = Some(x);
+        Some(x)
+    }
+
+    /// Counters of every pattern with a rule that has seen observations
+    pub fn stats(&self) -> Vec<PrefilterStats> {
+        self.state.lock().unwrap().stats.values().cloned().collect()
+    }
+}
+
+/// Keep the rules in step with `[worker]` reloads
+pub fn watch_config(prefilters: SharedObservationPrefilters, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("worker") => prefilters.apply_config(&change.config.worker.prefilters),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[PREFILTER] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(entries: &[(&str, PrefilterRule)]) -> BTreeMap<String, PrefilterRule> {
+        entries.iter().map(|(key, rule)| (key.to_string(), rule.clone())).collect()
+    }
+
+    #[test]
+    fn test_median_ewma_and_min_change() {
+        let median = PrefilterRule { median_of_3: true, ..PrefilterRule::default() };
+        let dedupe = PrefilterRule { min_change: 0.5, ..PrefilterRule::default() };
+        let smooth = PrefilterRule { ewma_alpha: 0.5, ..PrefilterRule::default() };
+        let prefilters = ObservationPrefilters::new(&rules(&[("51", median), ("56", dedupe), ("68", smooth)]));
+
+        // The one-tick bounce to 60 is absorbed, the move to 55 comes through
+        let out: Vec<f64> = [50.0, 50.0, 60.0, 50.0, 55.0, 56.0]
+            .iter()
+            .filter_map(|p| prefilters.filter(51, "KXA", *p))
+            .collect();
+        assert_eq!(out, vec![50.0, 50.0, 50.0, 50.0, 55.0, 55.0]);
+        // Markets keep their own windows
+        assert_eq!(prefilters.filter(51, "KXB", 70.0), Some(70.0));
+
+        // Duplicates and sub-threshold wiggles never reach the filter
+        let out: Vec<f64> = [40.0, 40.0, 40.2, 41.0, 40.7].iter().filter_map(|p| prefilters.filter(56, "KXA", *p)).collect();
+        assert_eq!(out, vec![40.0, 41.0]);
+
+        assert_eq!(prefilters.filter(68, "KXA", 10.0), Some(10.0));
+        assert_eq!(prefilters.filter(68, "KXA", 20.0), Some(15.0));
+        // No rule: unchanged and uncounted
+        assert_eq!(prefilters.filter(75, "KXA", 33.0), Some(33.0));
+
+        let stats = prefilters.stats();
+        assert_eq!(stats[0], PrefilterStats { pattern_id: 51, observations: 7, suppressed: 0, median_replaced: 2 });
+        assert_eq!((stats[1].pattern_id, stats[1].observations, stats[1].suppressed), (56, 5, 3));
+        assert_eq!(stats.len(), 3);
+    }
+
+    #[test]
+    fn test_tier_rules_and_reload() {
+        let dedupe = PrefilterRule { min_change: 1.0, ..PrefilterRule::default() };
+        let prefilters = ObservationPrefilters::new(&rules(&[("tier1", dedupe.clone())]));
+        // #76 (MM Compression) is a Tier 1 component; #51 isn't a component
+        assert_eq!(prefilters.rule(76), Some(dedupe.clone()));
+        assert_eq!(prefilters.rule(51), None);
+        assert_eq!(prefilters.filter(76, "KXA", 50.0), Some(50.0));
+        assert_eq!(prefilters.filter(76, "KXA", 50.5), None);
+
+        // A pattern's own rule wins; reloading restarts the series
+        let own = PrefilterRule { ewma_alpha: 1.0, ..PrefilterRule::default() };
+        prefilters.apply_config(&rules(&[("tier1", dedupe), ("76", own.clone())]));
+        assert_eq!(prefilters.rule(76), Some(own));
+        assert_eq!(prefilters.filter(76, "KXA", 50.5), Some(50.5));
+        assert_eq!(prefilters.stats()[0].suppressed, 1);
+    }
+}
@@ -0,0 +1,284 @@
+// src/odds_capture.rs
+// Odds change capture - columnar segment store for pattern research datasets
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::config::OddsCaptureSection;
+use crate::types::{MarketType, Platform, TimestampNs};
+
+/// Capture configuration
+#[derive(Debug, Clone)]
+pub struct OddsCaptureConfig {
+    /// Directory holding segment files
+    pub dir: PathBuf,
+    /// Rows buffered before a segment is written
+    pub flush_rows: usize,
+}
+
+impl Default for OddsCaptureConfig {
+    fn default() -> Self {
+        Self::from(&OddsCaptureSection::default())
+    }
+}
+
+impl From<&OddsCaptureSection> for OddsCaptureConfig {
+    fn from(o: &OddsCaptureSection) -> Self {
+        Self { dir: PathBuf::from(&o.dir), flush_rows: o.flush_rows }
+    }
+}
+
+/// A single observed line move
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddsChange {
+    pub venue: Platform,
+    pub market_id: String,
+    pub market_type: MarketType,
+    /// "yes" or "no"
+    pub side: &'static str,
+    /// Previous line (None on first observation)
+    pub old_line: Option<f64>,
+    pub new_line: f64,
+    pub size: f64,
+    pub timestamp_ns: TimestampNs,
+}
+
+/// Emits an OddsChange only when a (venue, market, side) line actually moves
+#[derive(Debug, Default)]
+pub struct OddsChangeDetector {
+    last: HashMap<(Platform, String, &'static str), f64>,
+}
+
+impl OddsChangeDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn observe(
+        &mut self,
+        venue: Platform,
+        market_id: &str,
+        market_type: MarketType,
+        side: &'static str,
+        line: f64,
+        size: f64,
+        timestamp_ns: TimestampNs,
+    ) -> Option<OddsChange> {
+        let key = (venue, market_id.to_string(), side);
+        let old_line = self.last.insert(key, line);
+        if old_line == Some(line) {
+            return None;
+        }
+        Some(OddsChange {
+            venue,
+            market_id: market_id.to_string(),
+            market_type,
+            side,
+            old_line,
+            new_line: line,
+            size,
+            timestamp_ns,
+        })
+    }
+}
+
+/// Column-oriented batch of odds changes (one segment file)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OddsColumns {
+    pub venue: Vec<Platform>,
+    pub market_id: Vec<String>,
+    pub market_type: Vec<MarketType>,
+    /// true = yes, false = no
+    pub side_yes: Vec<bool>,
+    pub old_line: Vec<Option<f64>>,
+    pub new_line: Vec<f64>,
+    pub size: Vec<f64>,
+    pub timestamp_ns: Vec<TimestampNs>,
+}
+
+impl OddsColumns {
+    pub fn len(&self) -> usize {
+        self.timestamp_ns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamp_ns.is_empty()
+    }
+
+    pub fn push(&mut self, change: &OddsChange) {
+        self.venue.push(change.venue);
+        self.market_id.push(change.market_id.clone());
+        self.market_type.push(change.market_type);
+        self.side_yes.push(change.side == "yes");
+        self.old_line.push(change.old_line);
+        self.new_line.push(change.new_line);
+        self.size.push(change.size);
+        self.timestamp_ns.push(change.timestamp_ns);
+    }
+
+    /// Row view of the batch
+    pub fn rows(&self) -> impl Iterator<Item = OddsChange> + '_ {
+        (0..self.len()).map(move |i| OddsChange {
+            venue: self.venue[i],
+            market_id: self.market_id[i].clone(),
+            market_type: self.market_type[i],
+            side: if self.side_yes[i] { "yes" } else { "no" },
+            old_line: self.old_line[i],
+            new_line: self.new_line[i],
+            size: self.size[i],
+            timestamp_ns: self.timestamp_ns[i],
+        })
+    }
+}
+
+/// Append-only segment store: ./dir/odds-<first_ns>-<last_ns>.json
+pub struct OddsStore {
+    config: OddsCaptureConfig,
+    buffer: OddsColumns,
+}
+
+impl OddsStore {
+    pub fn new(config: OddsCaptureConfig) -> Self {
+        Self { config, buffer: OddsColumns::default() }
+    }
+
+    pub fn append(&mut self, change: &OddsChange) -> Result<()> {
+        self.buffer.push(change);
+        if self.buffer.len() >= self.config.flush_rows {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write buffered rows as a new segment
+    pub fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.config.dir)
+            .with_context(|| format!("create {}", self.config.dir.display()))?;
+
+        let first = self.buffer.timestamp_ns.iter().min().copied().unwrap_or(0);
+        let last = self.buffer.timestamp_ns.iter().max().copied().unwrap_or(0);
+        let path = self.config.dir.join(format!("odds-{}-{}.json", first, last));
+        let json = serde_json::to_string(&self.buffer)?;
+        std::fs::write(&path, json).with_context(|| format!("write {}", path.display()))?;
+
+        info!("[ODDS] Wrote segment {} ({} rows)", path.display(), self.buffer.len());
+        self.buffer = OddsColumns::default();
+        Ok(())
+    }
+
+    /// Load all changes with timestamp in [from_ns, to_ns], ordered by time
+    pub fn load_range<P: AsRef<Path>>(dir: P, from_ns: TimestampNs, to_ns: TimestampNs) -> Result<Vec<OddsChange>> {
+        let mut out = Vec::new();
+        let entries = match std::fs::read_dir(dir.as_ref()) {
+            Ok(e) => e,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+            Err(e) => return Err(e.into()),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some((first, last)) = segment_range(&path) else { continue };
+            if last < from_ns || first > to_ns {
+                continue;
+            }
+            let data = std::fs::read_to_string(&path)?;
+            let columns: OddsColumns = match serde_json::from_str(&data) {
+                Ok(c) => c,
+                Err(e) => {
+                    warn!("[ODDS] Skipping unreadable segment {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            out.extend(columns.rows().filter(|c| c.timestamp_ns >= from_ns && c.timestamp_ns <= to_ns));
+        }
+
+        out.sort_by_key(|c| c.timestamp_ns);
+        Ok(out)
+    }
+}
+
+/// Parse (first_ns, last_ns) from a segment file name
+fn segment_range(path: &Path) -> Option<(TimestampNs, TimestampNs)> {
+    let stem = path.file_name()?.to_str()?.strip_prefix("odds-")?.strip_suffix(".json")?;
+    let (first, last) = stem.split_once('-')?;
+    Some((first.parse().ok()?, last.parse().ok()?))
+}
+
+/// Cloneable handle used by feed adapters to submit changes
+#[derive(Clone)]
+pub struct OddsCaptureHandle {
+    tx: mpsc::UnboundedSender<OddsChange>,
+}
+
+impl OddsCaptureHandle {
+    pub fn record(&self, change: OddsChange) {
+        let _ = self.tx.send(change);
+    }
+}
+
+/// Spawn the capture writer; segments are flushed on size and when all handles drop
+pub fn spawn_odds_capture(config: OddsCaptureConfig) -> (OddsCaptureHandle, tokio::task::JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<OddsChange>();
+    let task = tokio::spawn(async move {
+        let mut store = OddsStore::new(config);
+        while let Some(change) = rx.recv().await {
+            if let Err(e) = store.append(&change) {
+                warn!("[ODDS] Append failed: {}", e);
+            }
+        }
+        if let Err(e) = store.flush() {
+            warn!("[ODDS] Final flush failed: {}", e);
+        }
+    });
+    (OddsCaptureHandle { tx }, task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detector_only_emits_moves() {
+        let mut d = OddsChangeDetector::new();
+        let first = d.observe(Platform::DraftKings, "1", MarketType::Spread, "yes", 52.0, 100.0, 1).unwrap();
+        assert_eq!(first.old_line, None);
+        assert!(d.observe(Platform::DraftKings, "1", MarketType::Spread, "yes", 52.0, 120.0, 2).is_none());
+        let moved = d.observe(Platform::DraftKings, "1", MarketType::Spread, "yes", 54.0, 120.0, 3).unwrap();
+        assert_eq!(moved.old_line, Some(52.0));
+        // Other venue tracked independently
+        assert!(d.observe(Platform::FanDuel, "1", MarketType::Spread, "yes", 52.0, 1.0, 4).is_some());
+    }
+
+    #[test]
+    fn test_segment_roundtrip_and_range() {
+        let dir = std::env::temp_dir().join(format!("odds_capture_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut store = OddsStore::new(OddsCaptureConfig { dir: dir.clone(), flush_rows: 2 });
+
+        let mut d = OddsChangeDetector::new();
+        for (ts, line) in [(100, 50.0), (200, 51.0), (300, 49.0)] {
+            let c = d.observe(Platform::BetMGM, "7", MarketType::Total, "no", line, 10.0, ts).unwrap();
+            store.append(&c).unwrap();
+        }
+        store.flush().unwrap();
+
+        let all = OddsStore::load_range(&dir, 0, u64::MAX).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[2].old_line, Some(51.0));
+        assert_eq!(all[2].side, "no");
+
+        let mid = OddsStore::load_range(&dir, 150, 250).unwrap();
+        assert_eq!(mid.len(), 1);
+        assert_eq!(mid[0].new_line, 51.0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,518 @@
+// src/operator_review.rs
+// Operator review - latency signals below `[execution.review]` below_confidence are held for a
+// review window in which an operator approves or denies them on /review, or annotates them.
+// Decisions and notes go to the audit log. Each held signal is scored once its fill is back:
+// the edge executed signals captured, and the edge withheld ones would have captured (paper
+// filled), against automation trading every one of them.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::audit_log::{AuditEvent, SharedAuditLog};
+use crate::clock::{self, SharedClock};
+use crate::config::ReviewSection;
+use crate::config_reload::ConfigChanged;
+use crate::latency_arbitrage::LatencySignal;
+use crate::latency_execution::{LatencyExecutionRequest, LatencyExecutionResult};
+use crate::types::SignalId;
+
+/// What automation does with a signal it would hold: execute it
+pub const AUTOMATED_DECISION: &str = "execute";
+/// Decided signals kept for scoring and the status page
+const MAX_DECIDED: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approved,
+    Denied,
+    /// Nobody decided within the window
+    Expired,
+}
+
+impl ReviewDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewDecision::Approved => "approved",
+            ReviewDecision::Denied => "denied",
+            ReviewDecision::Expired => "expired",
+        }
+    }
+}
+
+/// A signal held for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingReview {
+    pub signal_id: u64,
+    /// Pattern id, or the strategy id of signals without one
+    pub pattern: String,
+    pub strategy_id: String,
+    /// Fast market id
+    pub market: String,
+    pub confidence: f64,
+    pub edge_cents: i16,
+    /// Wall time it was held and its window closes (Unix ns)
+    pub held_at_ns: u64,
+    pub deadline_ns: u64,
+    pub notes: Vec<String>,
+}
+
+/// A held signal's decision and, once its fill is back, how it played out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewRecord {
+    #[serde(flatten)]
+    pub review: PendingReview,
+    pub decision: ReviewDecision,
+    /// Operator, or "review" for expiries
+    pub actor: String,
+    /// Sent to execution (otherwise paper filled to score the automation's call)
+    pub executed: bool,
+    pub decided_at_ns: u64,
+    pub scored: bool,
+    /// Edge captured, or that would have been; None while unscored or unfilled
+    pub edge_captured_cents: Option<i16>,
+}
+
+/// Held signals against automation, over every decision since start
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewSummary {
+    pub approved: u64,
+    pub denied: u64,
+    pub expired: u64,
+    /// Decisions whose fill (or paper fill) is back
+    pub scored: u64,
+    /// Edge captured under the decisions taken (cents, summed)
+    pub reviewed_edge_cents: i64,
+    /// Edge had every scored signal executed, as automation would have
+    pub automated_edge_cents: i64,
+    /// Denials whose signal would have lost or gone unfilled
+    pub denials_vindicated: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewStatus {
+    pub below_confidence: f64,
+    pub window_ms: u64,
+    pub execute_on_expiry: bool,
+    pub pending: Vec<PendingReview>,
+    /// Newest first
+    pub decided: Vec<ReviewRecord>,
+    pub summary: ReviewSummary,
+}
+
+struct Held {
+    review: PendingReview,
+    request: LatencyExecutionRequest,
+}
+
+#[derive(Default)]
+struct ReviewState {
+    pending: BTreeMap<u64, Held>,
+    decided: VecDeque<ReviewRecord>,
+    /// Decided signals not yet taken by the engine, and whether to execute them
+    released: Vec<(SignalId, LatencyExecutionRequest, bool)>,
+    summary: ReviewSummary,
+}
+
+/// Held signals awaiting review; the execution engine holds and takes them
+pub struct ReviewQueue {
+    config: Mutex<ReviewSection>,
+    clock: SharedClock,
+    audit: Option<SharedAuditLog>,
+    state: Mutex<ReviewState>,
+}
+
+pub type SharedReviewQueue = Arc<ReviewQueue>;
+
+/// Pattern key of a signal, as `[execution.review]` patterns lists it
+fn pattern_of(signal: &LatencySignal) -> String {
+    signal.pattern_id.map_or_else(|| signal.strategy_id.clone(), |id| id.to_string())
+}
+
+impl ReviewQueue {
+    pub fn new(config: &ReviewSection) -> Self {
+        Self {
+            config: Mutex::new(config.clone()),
+            clock: clock::system(),
+            audit: None,
+            state: Mutex::new(ReviewState::default()),
+        }
+    }
+
+    /// Time windows on another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record decisions and annotations
+    pub fn with_audit_log(mut self, audit: SharedAuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Apply a reloaded `[execution.review]`; held signals keep their windows
+    pub fn apply_config(&self, config: &ReviewSection) {
+        let mut current = self.config.lock().unwrap();
+        if *current != *config {
+            info!("[REVIEW] Holding signals below confidence {} for {}ms", config.below_confidence, config.window_ms);
+            *current = config.clone();
+        }
+    }
+
+    /// Whether a signal waits for an operator before it executes
+    pub fn should_hold(&self, signal: &LatencySignal) -> bool {
+        let config = self.config.lock().unwrap();
+        signal.confidence < config.below_confidence
+            && (config.patterns.is_empty() || config.patterns.contains(&pattern_of(signal)))
+    }
+
+    /// Hold a signal for the review window
+    pub fn hold(&self, signal_id: SignalId, request: LatencyExecutionRequest) {
+        let window = Duration::from_millis(self.config.lock().unwrap().window_ms);
+        let now_ns = self.clock.wall_ns().0;
+        let signal = &request.signal;
+        let review = PendingReview {
+            signal_id: signal_id.0,
+            pattern: pattern_of(signal),
+            strategy_id: signal.strategy_id.clone(),
+            market: signal.fast_market.market_id.to_string(),
+            confidence: signal.confidence,
+            edge_cents: signal.disparity_cents.abs(),
+            held_at_ns: now_ns,
+            deadline_ns: now_ns + window.as_nanos() as u64,
+            notes: Vec::new(),
+        };
+        info!("[REVIEW] Holding signal {} of {} (confidence {:.2}) for review", signal_id, review.pattern, review.confidence);
+        self.state.lock().unwrap().pending.insert(signal_id.0, Held { review, request });
+    }
+
+    /// Approve or deny a held signal
+    pub fn decide(&self, signal_id: u64, approve: bool, actor: &str, note: Option<String>) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let held = state.pending.remove(&signal_id).ok_or_else(|| anyhow!("signal {} is not awaiting review", signal_id))?;
+        let decision = if approve { ReviewDecision::Approved } else { ReviewDecision::Denied };
+        self.settle(&mut state, held, decision, approve, actor, note);
+        Ok(())
+    }
+
+    /// Note on a held or decided signal
+    pub fn annotate(&self, signal_id: u64, actor: &str, note: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let review = match state.pending.get_mut(&signal_id) {
+            Some(held) => &mut held.review,
+            None => state.decided.iter_mut()
+                .find(|r| r.review.signal_id == signal_id)
+                .map(|r| &mut r.review)
+                .ok_or_else(|| anyhow!("unknown signal {}", signal_id))?,
+        };
+        review.notes.push(note.to_string());
+        if let Some(audit) = &self.audit {
+            audit.record_for(&review.strategy_id, actor, Some(review.market.clone()), AuditEvent::Annotation {
+                signal_id,
+                note: note.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Expire held signals past their window, then take every decided one with whether it executes
+    pub fn take_released(&self) -> Vec<(SignalId, LatencyExecutionRequest, bool)> {
+        let now_ns = self.clock.wall_ns().0;
+        let execute = self.config.lock().unwrap().execute_on_expiry;
+        let mut state = self.state.lock().unwrap();
+        let expired: Vec<u64> = state.pending.iter()
+            .filter(|(_, held)| held.review.deadline_ns <= now_ns)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in expired {
+            if let Some(held) = state.pending.remove(&id) {
+                self.settle(&mut state, held, ReviewDecision::Expired, execute, "review", None);
+            }
+        }
+        std::mem::take(&mut state.released)
+    }
+
+    fn settle(&self, state: &mut ReviewState, held: Held, decision: ReviewDecision, executed: bool, actor: &str, note: Option<String>) {
+        let Held { mut review, request } = held;
+        info!("[REVIEW] Signal {} {} by {}{}", review.signal_id, decision.as_str(), actor,
+              if executed { "; executing" } else { "" });
+        if let Some(audit) = &self.audit {
+            audit.record_for(&review.strategy_id, actor, Some(review.market.clone()), AuditEvent::Override {
+                signal_id: review.signal_id,
+                decision: decision.as_str().to_string(),
+                automated: AUTOMATED_DECISION.to_string(),
+                executed,
+                note: note.clone(),
+            });
+        }
+        review.notes.extend(note);
+        match decision {
+            ReviewDecision::Approved => state.summary.approved += 1,
+            ReviewDecision::Denied => state.summary.denied += 1,
+            ReviewDecision::Expired => state.summary.expired += 1,
+        }
+        state.released.push((SignalId(review.signal_id), request, executed));
+        state.decided.push_back(ReviewRecord {
+            review,
+            decision,
+            actor: actor.to_string(),
+            executed,
+            decided_at_ns: self.clock.wall_ns().0,
+            scored: false,
+            edge_captured_cents: None,
+        });
+        if state.decided.len() > MAX_DECIDED {
+            state.decided.pop_front();
+        }
+    }
+
+    /// Score a decided signal by its execution's (or paper fill's) result; others are ignored
+    pub fn record_outcome(&self, result: &LatencyExecutionResult) {
+        let mut state = self.state.lock().unwrap();
+        let ReviewState { decided, summary, .. } = &mut *state;
+        let Some(record) = decided.iter_mut().find(|r| r.review.signal_id == result.signal_id.0 && !r.scored) else {
+            return;
+        };
+        record.scored = true;
+        record.edge_captured_cents = result.success.then_some(result.edge_captured_cents);
+        let edge = i64::from(record.edge_captured_cents.unwrap_or(0));
+        summary.scored += 1;
+        summary.automated_edge_cents += edge;
+        if record.executed {
+            summary.reviewed_edge_cents += edge;
+        }
+        if record.decision == ReviewDecision::Denied && edge <= 0 {
+            summary.denials_vindicated += 1;
+        }
+    }
+
+    /// Held signals, soonest deadline first
+    pub fn pending(&self) -> Vec<PendingReview> {
+        let mut pending: Vec<PendingReview> = self.state.lock().unwrap().pending.values().map(|h| h.review.clone()).collect();
+        pending.sort_by_key(|r| r.deadline_ns);
+        pending
+    }
+
+    pub fn status(&self) -> ReviewStatus {
+        let config = self.config.lock().unwrap().clone();
+        let pending = self.pending();
+        let state = self.state.lock().unwrap();
+        ReviewStatus {
+            below_confidence: config.below_confidence,
+            window_ms: config.window_ms,
+            execute_on_expiry: config.execute_on_expiry,
+            pending,
+            decided: state.decided.iter().rev().cloned().collect(),
+            summary: state.summary.clone(),
+        }
+    }
+
+    /// `/review` admin route: GET lists held and decided signals with the summary; POST
+    /// `/review/<signal_id>/approve|deny|annotate` with `note=` (required to annotate) and
+    /// `actor=` (default "operator")
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let target = path.trim_start_matches("/review").trim_matches('/');
+        let note = query_value(query, "note");
+        let actor = query_value(query, "actor").unwrap_or_else(|| "operator".to_string());
+
+        let result = match (method, target.split_once('/')) {
+            ("GET", _) if target.is_empty() => Ok(()),
+            ("POST", Some((id, action))) => match (id.parse::<u64>(), action) {
+                (Err(_), _) => Err(anyhow!("bad signal id '{}'", id)),
+                (Ok(id), "approve") => self.decide(id, true, &actor, note),
+                (Ok(id), "deny") => self.decide(id, false, &actor, note),
+                (Ok(id), "annotate") => note.ok_or_else(|| anyhow!("missing ?note="))
+                    .and_then(|note| self.annotate(id, &actor, &note)),
+                (Ok(_), action) => Err(anyhow!("unknown action '{}' (approve|deny|annotate)", action)),
+            },
+            ("GET", _) => return (404, r#"{"error":"not found"}"#.to_string()),
+            _ => return (405, r#"{"error":"method not allowed"}"#.to_string()),
+        };
+
+        match result {
+            Ok(()) => (200, serde_json::to_string_pretty(&self.status()).unwrap_or_default()),
+            Err(e) => (400, serde_json::json!({ "error": e.to_string() }).to_string()),
+        }
+    }
+}
+
+/// Percent-decoded value of `key` in a query string (`+` is a space)
+fn query_value(query: &str, key: &str) -> Option<String> {
+    let raw = query.split('&').find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))?;
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut bytes = raw.bytes();
+    let mut out = Vec::with_capacity(raw.len());
+    while let Some(b) = bytes.next() {
+        match b {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let mut ahead = bytes.clone();
+                match (ahead.next().and_then(hex), ahead.next().and_then(hex)) {
+                    (Some(hi), Some(lo)) => {
+                        out.push(hi << 4 | lo);
+                        bytes = ahead;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+    }
+    Some(String::from_utf8_lossy(&out).into_owned())
+}
+
+/// Keep the review window and thresholds in step with `[execution]` reloads
+pub fn watch_config(queue: SharedReviewQueue, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("execution") => queue.apply_config(&change.config.execution.review),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[REVIEW] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_log::{AuditConfig, AuditLog, AuditQuery};
+    use crate::clock::MockClock;
+    use crate::latency_arbitrage::{MarketTier, PriceObservation};
+    use crate::types::{MarketType, Nanos, Platform};
+
+    fn config() -> ReviewSection {
+        ReviewSection { below_confidence: 0.6, patterns: vec!["74".to_string()], ..ReviewSection::default() }
+    }
+
+    fn obs(market_id: u16, provider: Platform, tier: MarketTier) -> PriceObservation {
+        PriceObservation {
+            market_id,
+            provider,
+            market_type: MarketType::Moneyline,
+            price: 55,
+            size: 5000,
+            timestamp_ns: 0,
+            tier,
+            features: None,
+        }
+    }
+
+    fn request(pattern_id: u16, confidence: f64) -> LatencyExecutionRequest {
+        LatencyExecutionRequest {
+            signal: LatencySignal {
+                fast_market: obs(1, Platform::Kalshi, MarketTier::Tier1),
+                slow_market: obs(2, Platform::DraftKings, MarketTier::Tier4),
+                disparity_cents: 9,
+                expected_convergence_ns: 1_000_000_000,
+                pattern_id: Some(pattern_id),
+                confidence,
+                strategy_id: format!("pattern_{}", pattern_id),
+            },
+            execution_deadline_ns: 1_000_000_000,
+            fill_probability_threshold: 0.5,
+            max_edge_decay_cents: 2,
+        }
+    }
+
+    fn result(signal_id: u64, edge: Option<i16>) -> LatencyExecutionResult {
+        LatencyExecutionResult {
+            signal_id: SignalId(signal_id),
+            success: edge.is_some(),
+            fast_fill_price: None,
+            slow_fill_price: None,
+            execution_time_ns: 0,
+            edge_captured_cents: edge.unwrap_or(0),
+            edge_decay_cents: 0,
+            error_message: None,
+            strategy_id: "pattern_74".to_string(),
+            providers: None,
+        }
+    }
+
+    #[test]
+    fn test_decisions_are_audited_and_scored_against_automation() {
+        let dir = std::env::temp_dir().join(format!("operator_review_{}", std::process::id()));
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let audit = Arc::new(AuditLog::open_with_clock(AuditConfig { dir: dir.clone(), fsync: false }, clock.clone()).unwrap());
+        let queue = ReviewQueue::new(&config()).with_clock(clock.clone()).with_audit_log(audit.clone());
+
+        assert!(queue.should_hold(&request(74, 0.5).signal));
+        assert!(!queue.should_hold(&request(74, 0.8).signal) && !queue.should_hold(&request(75, 0.5).signal));
+        for id in 1..=3 {
+            queue.hold(SignalId(id), request(74, 0.5));
+        }
+        assert_eq!(queue.handle_admin("POST", "/review/1/approve?note=looks+fine").0, 200);
+        assert_eq!(queue.handle_admin("POST", "/review/2/deny?actor=desk&note=thin%20book").0, 200);
+        assert_eq!(queue.handle_admin("POST", "/review/3/annotate?note=watching").0, 200);
+        let released: Vec<(u64, bool)> = queue.take_released().into_iter().map(|(id, _, execute)| (id.0, execute)).collect();
+        assert_eq!(released, vec![(1, true), (2, false)]);
+        assert_eq!(queue.pending().len(), 1);
+
+        // Nobody decides #3 within the window: dropped
+        clock.advance(Duration::from_millis(5001));
+        let released = queue.take_released();
+        assert_eq!((released[0].0, released[0].2), (SignalId(3), false));
+
+        queue.record_outcome(&result(1, Some(3)));
+        queue.record_outcome(&result(2, Some(4)));
+        queue.record_outcome(&result(3, None));
+        queue.record_outcome(&result(9, Some(5)));
+        let status = queue.status();
+        assert_eq!(status.summary, ReviewSummary {
+            approved: 1,
+            denied: 1,
+            expired: 1,
+            scored: 3,
+            reviewed_edge_cents: 3,
+            automated_edge_cents: 7,
+            denials_vindicated: 0,
+        });
+        assert_eq!(status.decided[1].review.notes, vec!["thin book"]);
+        assert_eq!(status.decided[1].actor, "desk");
+
+        let overrides = audit.query(&AuditQuery { kind: Some("override".to_string()), ..AuditQuery::default() }).unwrap();
+        assert_eq!(overrides.len(), 3);
+        assert_eq!(overrides[0].strategy_id.as_deref(), Some("pattern_74"));
+        assert!(matches!(&overrides[2].event, AuditEvent::Override { decision, executed: false, .. } if decision == "expired"));
+        let notes = audit.query(&AuditQuery { actor: Some("operator".to_string()), kind: Some("annotation".to_string()), ..AuditQuery::default() }).unwrap();
+        assert_eq!(notes.len(), 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_admin_errors_expiry_and_reload() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let queue = ReviewQueue::new(&ReviewSection { execute_on_expiry: true, ..config() }).with_clock(clock.clone());
+        queue.hold(SignalId(7), request(74, 0.4));
+
+        assert_eq!(queue.handle_admin("POST", "/review/8/approve").0, 400);
+        assert_eq!(queue.handle_admin("POST", "/review/7/cancel").0, 400);
+        assert_eq!(queue.handle_admin("POST", "/review/7/annotate").0, 400);
+        assert_eq!(queue.handle_admin("GET", "/review/7").0, 404);
+        assert_eq!(queue.handle_admin("DELETE", "/review/7/deny").0, 405);
+        let (status, body) = queue.handle_admin("GET", "/review");
+        assert_eq!(status, 200);
+        assert!(body.contains("\"signal_id\": 7"));
+
+        // Expiry executes, as automation would
+        clock.advance(Duration::from_secs(6));
+        let released = queue.take_released();
+        assert!(released.len() == 1 && released[0].2);
+        assert_eq!(queue.handle_admin("POST", "/review/7/deny").0, 400);
+
+        queue.apply_config(&ReviewSection::default());
+        assert!(!queue.should_hold(&request(74, 0.1).signal));
+        assert_eq!(query_value("a=1&note=50%25+edge%zz", "note").as_deref(), Some("50% edge%zz"));
+    }
+}
@@ -0,0 +1,433 @@
+// src/optimization_studies.rs
+// Top-N filter parameter sets per optimization study with their out-of-sample equity curves,
+// compared side by side on the dashboard and promoted into `[worker.filters]`
+//
+// Admin routes:
+//
+//     GET  /studies                        every study, newest first, with its best candidate
+//     GET  /studies/<id>                   candidates side by side: params, metrics, equity curves
+//     POST /studies/<id>/promote?rank=N    make candidate N the pattern's live filter tuning
+//
+// A promotion goes through the config reloader, so it is validated, broadcast as
+// `ConfigChanged` and audited like a file reload. It changes the live config only: the response
+// carries the `[worker.filters]` entry to copy into the config file, without which the next
+// file reload puts the previous tuning back.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::config::FilterTuning;
+use crate::config::StudiesSection;
+use crate::config_reload::{ConfigChanged, ConfigReloader};
+use crate::error::StateStoreError;
+
+/// Study store settings, from env (STUDIES=1 enables it in the runner)
+#[derive(Debug, Clone)]
+pub struct StudiesConfig {
+    /// Studies are written here as `<id>.json`
+    pub dir: PathBuf,
+    /// Candidates kept per study
+    pub top_n: usize,
+}
+
+impl Default for StudiesConfig {
+    fn default() -> Self {
+        Self::from(&StudiesSection::default())
+    }
+}
+
+impl From<&StudiesSection> for StudiesConfig {
+    fn from(s: &StudiesSection) -> Self {
+        Self { dir: PathBuf::from(&s.dir), top_n: s.top_n }
+    }
+}
+
+pub type StudyId = u64;
+
+/// Performance of a parameter set on data the optimizer never scored it on
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutOfSampleMetrics {
+    pub roi_percent: f64,
+    /// Mean over standard deviation of per-trade returns
+    pub sharpe_ratio: f64,
+    /// Worst peak-to-trough fall, as a fraction of the peak
+    pub max_drawdown: f64,
+    pub win_rate: f64,
+    pub trades: u32,
+}
+
+impl OutOfSampleMetrics {
+    /// Metrics of an equity curve whose first point is the starting capital; every step that
+    /// moves equity counts as one trade
+    pub fn from_equity_curve(curve: &[f64]) -> Self {
+        let Some(&start) = curve.first() else { return Self::default() };
+        let returns: Vec<f64> = curve.windows(2)
+            .filter(|w| w[1] != w[0])
+            .map(|w| if w[0] != 0.0 { (w[1] - w[0]) / w[0].abs() } else { 0.0 })
+            .collect();
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n.max(1.0);
+        let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n.max(1.0)).sqrt();
+
+        let mut peak = start;
+        let mut max_drawdown: f64 = 0.0;
+        for &equity in curve {
+            peak = peak.max(equity);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - equity) / peak);
+            }
+        }
+
+        let end = curve[curve.len() - 1];
+        Self {
+            roi_percent: if start != 0.0 { (end - start) / start.abs() * 100.0 } else { 0.0 },
+            sharpe_ratio: if std > 0.0 { mean / std } else { 0.0 },
+            max_drawdown,
+            win_rate: if returns.is_empty() { 0.0 } else { returns.iter().filter(|&&r| r > 0.0).count() as f64 / n },
+            trades: returns.len() as u32,
+        }
+    }
+}
+
+/// A parameter set as the optimizer hands it over
+#[derive(Debug, Clone)]
+pub struct CandidateRun {
+    pub params: FilterTuning,
+    /// In-sample objective the optimizer ranked it by
+    pub score: f64,
+    /// Equity over the held-out data, starting capital first
+    pub equity_curve: Vec<f64>,
+}
+
+/// One of a study's top parameter sets (rank 1 = best in-sample score)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StudyCandidate {
+    pub rank: u32,
+    pub params: FilterTuning,
+    pub score: f64,
+    pub metrics: OutOfSampleMetrics,
+    pub equity_curve: Vec<f64>,
+}
+
+/// A finished optimization run for one pattern; persisted whole
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Study {
+    pub id: StudyId,
+    pub pattern_id: u16,
+    /// Search method (grid, random, bayesian, genetic)
+    pub method: String,
+    pub created_ns: u64,
+    pub candidates: Vec<StudyCandidate>,
+}
+
+/// Study list entry
+#[derive(Debug, Clone, Serialize)]
+pub struct StudySummary {
+    pub id: StudyId,
+    pub pattern_id: u16,
+    pub method: String,
+    pub created_ns: u64,
+    pub candidates: usize,
+    pub best_score: Option<f64>,
+    pub best_roi_percent: Option<f64>,
+    /// Candidate currently live in `[worker.filters]`
+    pub live_rank: Option<u32>,
+}
+
+/// A study's candidates side by side, with the live one marked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudyComparison {
+    #[serde(flatten)]
+    pub study: Study,
+    pub live_rank: Option<u32>,
+}
+
+#[derive(Default)]
+struct Studies {
+    studies: BTreeMap<StudyId, Study>,
+    next_id: StudyId,
+}
+
+/// Stored optimization studies; promotes candidates through the config reloader
+pub struct StudyStore {
+    config: StudiesConfig,
+    inner: Mutex<Studies>,
+    reloader: Option<Arc<ConfigReloader>>,
+    clock: SharedClock,
+}
+
+pub type SharedStudyStore = Arc<StudyStore>;
+
+impl StudyStore {
+    /// Open the store, reloading studies recorded by earlier runs
+    pub fn open(config: StudiesConfig) -> Result<Self, StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+        let mut inner = Studies { next_id: 1, ..Studies::default() };
+        for entry in std::fs::read_dir(&config.dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let study = std::fs::read_to_string(&path)
+                .map_err(StateStoreError::from)
+                .and_then(|text| Ok(serde_json::from_str::<Study>(&text)?));
+            match study {
+                Ok(study) => {
+                    inner.next_id = inner.next_id.max(study.id + 1);
+                    inner.studies.insert(study.id, study);
+                }
+                Err(e) => warn!("[STUDIES] Skipping {}: {}", path.display(), e),
+            }
+        }
+        if !inner.studies.is_empty() {
+            info!("[STUDIES] Loaded {} studies from {}", inner.studies.len(), config.dir.display());
+        }
+
+        Ok(Self { config, inner: Mutex::new(inner), reloader: None, clock: clock::system() })
+    }
+
+    /// Promote candidates into this reloader's live config; read-only without one
+    pub fn with_reloader(mut self, reloader: Arc<ConfigReloader>) -> Self {
+        self.reloader = Some(reloader);
+        self
+    }
+
+    pub fn config(&self) -> &StudiesConfig {
+        &self.config
+    }
+
+    /// Keep the `top_n` best-scoring runs of a finished study and persist it
+    pub fn record(&self, pattern_id: u16, method: &str, mut runs: Vec<CandidateRun>) -> Result<StudyId, StateStoreError> {
+        runs.sort_by(|a, b| b.score.total_cmp(&a.score));
+        runs.truncate(self.config.top_n);
+        let candidates = runs.into_iter()
+            .zip(1..)
+            .map(|(run, rank)| StudyCandidate {
+                rank,
+                metrics: OutOfSampleMetrics::from_equity_curve(&run.equity_curve),
+                params: run.params,
+                score: run.score,
+                equity_curve: run.equity_curve,
+            })
+            .collect();
+
+        let study = {
+            let mut inner = self.inner.lock().unwrap();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            let study = Study { id, pattern_id, method: method.to_string(), created_ns: self.clock.wall_ns().0, candidates };
+            inner.studies.insert(id, study.clone());
+            study
+        };
+        info!("[STUDIES] Study {} recorded: pattern #{} ({}), {} candidates",
+              study.id, pattern_id, method, study.candidates.len());
+        self.persist(&study)?;
+        Ok(study.id)
+    }
+
+    fn persist(&self, study: &Study) -> Result<(), StateStoreError> {
+        // Write-then-rename so a crash never leaves a torn study
+        let path = self.config.dir.join(format!("{}.json", study.id));
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string(study)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: StudyId) -> Option<Study> {
+        self.inner.lock().unwrap().studies.get(&id).cloned()
+    }
+
+    /// Rank of the candidate whose params are the pattern's live tuning
+    fn live_rank(&self, study: &Study) -> Option<u32> {
+        let config = self.reloader.as_ref()?.current();
+        let live = config.worker.filters.get(&study.pattern_id.to_string())?;
+        study.candidates.iter().find(|c| &c.params == live).map(|c| c.rank)
+    }
+
+    /// Every study, newest first
+    pub fn list(&self) -> Vec<StudySummary> {
+        let studies: Vec<Study> = self.inner.lock().unwrap().studies.values().rev().cloned().collect();
+        studies.into_iter()
+            .map(|study| {
+                let best = study.candidates.first();
+                StudySummary {
+                    id: study.id,
+                    pattern_id: study.pattern_id,
+                    method: study.method.clone(),
+                    created_ns: study.created_ns,
+                    candidates: study.candidates.len(),
+                    best_score: best.map(|c| c.score),
+                    best_roi_percent: best.map(|c| c.metrics.roi_percent),
+                    live_rank: self.live_rank(&study),
+                }
+            })
+            .collect()
+    }
+
+    pub fn comparison(&self, id: StudyId) -> Option<StudyComparison> {
+        let study = self.get(id)?;
+        let live_rank = self.live_rank(&study);
+        Some(StudyComparison { study, live_rank })
+    }
+
+    /// Latest study of each pattern, for the dashboard
+    pub fn latest_per_pattern(&self) -> Vec<StudyComparison> {
+        let latest: BTreeMap<u16, Study> = self.inner.lock().unwrap().studies.values()
+            .map(|study| (study.pattern_id, study.clone()))
+            .collect();
+        latest.into_values()
+            .map(|study| {
+                let live_rank = self.live_rank(&study);
+                StudyComparison { study, live_rank }
+            })
+            .collect()
+    }
+
+    /// Write candidate `rank` of study `id` into `[worker.filters]` through the reloader;
+    /// None when it is already live
+    pub fn promote(&self, id: StudyId, rank: u32) -> Result<Option<ConfigChanged>> {
+        let reloader = self.reloader.as_ref().ok_or_else(|| anyhow!("no live config to promote into"))?;
+        let study = self.get(id).ok_or_else(|| anyhow!("no study '{}'", id))?;
+        let candidate = study.candidates.iter()
+            .find(|c| c.rank == rank)
+            .ok_or_else(|| anyhow!("study {} has no rank {} (1..={})", id, rank, study.candidates.len()))?;
+
+        let mut config = (*reloader.current()).clone();
+        config.worker.filters.insert(study.pattern_id.to_string(), candidate.params.clone());
+        let changed = reloader.apply(config)?;
+        if changed.is_some() {
+            info!("[STUDIES] Promoted study {} rank {} to pattern #{} filter tuning", id, rank, study.pattern_id);
+        }
+        Ok(changed)
+    }
+
+    /// Admin API: list, compare, promote
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let target = path.trim_start_matches("/studies").trim_matches('/');
+        let (id, sub) = target.split_once('/').unwrap_or((target, ""));
+        let study = id.parse().ok().and_then(|id| self.get(id));
+        let not_found = || (404, serde_json::json!({ "error": format!("no study '{}'", id) }).to_string());
+
+        match (method, id, sub) {
+            ("GET", "", "") => (200, serde_json::to_string_pretty(&self.list()).unwrap_or_default()),
+            ("GET", _, "") => match study.and_then(|study| self.comparison(study.id)) {
+                Some(comparison) => (200, serde_json::to_string_pretty(&comparison).unwrap_or_default()),
+                None => not_found(),
+            },
+            ("POST", _, "promote") => {
+                let Some(study) = study else { return not_found() };
+                let params: BTreeMap<&str, &str> = query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+                let Some(rank) = params.get("rank").and_then(|r| r.parse().ok()) else {
+                    return (400, r#"{"error":"missing ?rank="}"#.to_string());
+                };
+                match self.promote(study.id, rank) {
+                    Ok(changed) => {
+                        let tuning = &study.candidates[(rank - 1) as usize].params;
+                        let entry = BTreeMap::from([("worker", BTreeMap::from([
+                            ("filters", BTreeMap::from([(study.pattern_id.to_string(), tuning)])),
+                        ]))]);
+                        (200, serde_json::json!({
+                            "study": study.id,
+                            "pattern_id": study.pattern_id,
+                            "rank": rank,
+                            "version": changed.map(|c| c.version),
+                            "toml": toml::to_string(&entry).unwrap_or_default(),
+                        }).to_string())
+                    }
+                    Err(e) => (400, serde_json::json!({ "error": format!("{:#}", e) }).to_string()),
+                }
+            }
+            ("GET", _, _) => (404, r#"{"error":"not found"}"#.to_string()),
+            _ => (405, r#"{"error":"method not allowed"}"#.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, CliArgs};
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("studies_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn run(dt: f64, score: f64, curve: &[f64]) -> CandidateRun {
+        CandidateRun {
+            params: FilterTuning { dt, ..FilterTuning::default() },
+            score,
+            equity_curve: curve.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_metrics_from_equity_curve() {
+        let metrics = OutOfSampleMetrics::from_equity_curve(&[100.0, 110.0, 110.0, 99.0, 120.0]);
+        assert_eq!(metrics.trades, 3);
+        assert!((metrics.roi_percent - 20.0).abs() < 1e-9);
+        assert!((metrics.max_drawdown - 0.1).abs() < 1e-9);
+        assert!((metrics.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert!(metrics.sharpe_ratio > 0.0);
+        assert_eq!(OutOfSampleMetrics::from_equity_curve(&[]), OutOfSampleMetrics::default());
+    }
+
+    #[test]
+    fn test_record_keeps_top_n_and_reloads() {
+        let dir = test_dir("record");
+        let config = StudiesConfig { dir: dir.clone(), top_n: 2 };
+        let store = StudyStore::open(config.clone()).unwrap();
+        let id = store.record(51, "grid", vec![
+            run(0.01, 0.2, &[100.0, 101.0]),
+            run(0.05, 0.9, &[100.0, 104.0]),
+            run(0.10, 0.5, &[100.0, 98.0]),
+        ]).unwrap();
+
+        let study = store.get(id).unwrap();
+        let ranked: Vec<_> = study.candidates.iter().map(|c| (c.rank, c.params.dt)).collect();
+        assert_eq!(ranked, vec![(1, 0.05), (2, 0.10)]);
+        assert!((study.candidates[1].metrics.roi_percent + 2.0).abs() < 1e-9);
+
+        let reopened = StudyStore::open(config).unwrap();
+        assert_eq!(reopened.get(id), Some(study));
+        assert_eq!(reopened.record(68, "random", vec![]).unwrap(), id + 1);
+        assert_eq!(reopened.list().iter().map(|s| s.id).collect::<Vec<_>>(), vec![id + 1, id]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_promote_applies_through_reloader() {
+        let dir = test_dir("promote");
+        let reloader = Arc::new(ConfigReloader::new(AppConfig::default(), None, CliArgs::default()));
+        let mut changes = reloader.subscribe();
+        let store = StudyStore::open(StudiesConfig { dir: dir.clone(), top_n: 3 })
+            .unwrap()
+            .with_reloader(reloader.clone());
+        let id = store.record(51, "bayesian", vec![run(0.02, 0.8, &[100.0, 103.0]), run(0.2, 0.4, &[100.0, 101.0])]).unwrap();
+        assert_eq!(store.comparison(id).unwrap().live_rank, None);
+
+        let (status, body) = store.handle_admin("POST", &format!("/studies/{}/promote?rank=2", id));
+        assert_eq!(status, 200, "{}", body);
+        assert!(body.contains("[worker.filters.51]"), "{}", body);
+        assert_eq!(reloader.current().worker.filters["51"].dt, 0.2);
+        let event = changes.try_recv().unwrap();
+        assert_eq!(event.sections, vec!["worker"]);
+        assert_eq!(store.comparison(id).unwrap().live_rank, Some(2));
+
+        // Promoting the live candidate again changes nothing
+        assert!(store.promote(id, 2).unwrap().is_none());
+        assert_eq!(store.handle_admin("POST", &format!("/studies/{}/promote?rank=3", id)).0, 400);
+        assert_eq!(store.handle_admin("POST", &format!("/studies/{}/promote", id)).0, 400);
+        assert_eq!(store.handle_admin("POST", "/studies/99/promote?rank=1").0, 404);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,622 @@
+// src/order_manager.rs
+// Live Polymarket order state from the user channel, with REST reconciliation when the stream gaps
+
+use futures_util::future::BoxFuture;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::error::VenueApiError;
+use crate::polymarket_clob::{
+    run_user_ws, ApiCreds, PolyOrderEvent, PolyTradeEvent, PolyUserEvent, PolymarketOrderResponse, SharedAsyncClient,
+};
+use crate::position_tracker::{FillRecord, PositionChannel};
+use crate::types::{MarketPair, Nanos};
+
+/// Sizes closer than this are treated as equal (venue sizes are 2-6 decimals)
+const SIZE_EPSILON: f64 = 1e-6;
+
+/// Order manager configuration
+#[derive(Debug, Clone)]
+pub struct OrderManagerConfig {
+    /// Orders first seen on the stream are booked after this unless execution claims them first
+    pub adopt_after: Duration,
+    /// Open orders with no stream traffic for this long are re-read over REST
+    pub stale_after: Duration,
+    /// How often to look for stale and unclaimed orders
+    pub check_interval: Duration,
+    pub reconnect_delay: Duration,
+}
+
+impl Default for OrderManagerConfig {
+    fn default() -> Self {
+        Self {
+            adopt_after: Duration::from_secs(2),
+            stale_after: Duration::from_secs(30),
+            check_interval: Duration::from_secs(5),
+            reconnect_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// What an order trades, in position tracker terms
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderContext {
+    pub market_id: String,
+    pub description: String,
+    /// "yes" or "no"
+    pub side: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Live,
+    Matched,
+    Cancelled,
+    Unmatched,
+}
+
+impl OrderStatus {
+    fn parse(status: &str) -> Option<Self> {
+        match status.to_ascii_uppercase().as_str() {
+            "LIVE" | "DELAYED" => Some(OrderStatus::Live),
+            "MATCHED" => Some(OrderStatus::Matched),
+            "CANCELED" | "CANCELLED" => Some(OrderStatus::Cancelled),
+            "UNMATCHED" => Some(OrderStatus::Unmatched),
+            _ => None,
+        }
+    }
+
+    pub fn is_terminal(self) -> bool {
+        self != OrderStatus::Live
+    }
+}
+
+/// Why the stream can no longer be trusted for some orders
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gap {
+    /// Socket dropped; anything may have changed while it was down
+    Disconnected,
+    /// Open orders with no traffic for `stale_after`
+    Silent(Vec<String>),
+    /// Matched size went backwards (out-of-order or reversed update)
+    Regressed { order_id: String, booked: f64, venue: f64 },
+    /// A match failed on chain after it was reported
+    TradeFailed { trade_id: String, order_ids: Vec<String> },
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackedOrder {
+    pub order_id: String,
+    pub asset_id: String,
+    pub context: Option<OrderContext>,
+    /// "buy" or "sell"
+    pub action: &'static str,
+    pub price: f64,
+    pub original_size: f64,
+    /// Cumulative matched size reported by order events or REST
+    pub size_matched: f64,
+    /// Contracts already sent to the position tracker
+    pub booked: f64,
+    pub status: OrderStatus,
+    /// Placed by our execution path (vs. first seen on the stream)
+    claimed: bool,
+    first_seen: Nanos,
+    last_update: Nanos,
+    /// Matched size summed from trade events, deduplicated by trade ID
+    trade_matched: f64,
+    trades: HashSet<String>,
+}
+
+impl TrackedOrder {
+    fn new(order_id: &str, now: Nanos) -> Self {
+        Self {
+            order_id: order_id.to_string(),
+            asset_id: String::new(),
+            context: None,
+            action: "buy",
+            price: 0.0,
+            original_size: 0.0,
+            size_matched: 0.0,
+            booked: 0.0,
+            status: OrderStatus::Live,
+            claimed: false,
+            first_seen: now,
+            last_update: now,
+            trade_matched: 0.0,
+            trades: HashSet::new(),
+        }
+    }
+
+    /// Best known matched size: trade events can land before the order update
+    pub fn matched(&self) -> f64 {
+        self.size_matched.max(self.trade_matched)
+    }
+}
+
+/// Polymarket order state, keyed by order ID
+pub struct OrderManager {
+    config: OrderManagerConfig,
+    orders: HashMap<String, TrackedOrder>,
+    /// Token ID -> (market_id, description, side)
+    tokens: HashMap<String, OrderContext>,
+    positions: PositionChannel,
+    clock: SharedClock,
+    /// Orders to re-read over REST at the next reconciliation
+    suspect: HashSet<String>,
+    /// Re-read every open order (after a disconnect)
+    resync_all: bool,
+}
+
+pub type SharedOrderManager = Arc<Mutex<OrderManager>>;
+
+impl OrderManager {
+    pub fn new(config: OrderManagerConfig, positions: PositionChannel) -> Self {
+        Self {
+            config,
+            orders: HashMap::new(),
+            tokens: HashMap::new(),
+            positions,
+            clock: clock::system(),
+            suspect: HashSet::new(),
+            resync_all: false,
+        }
+    }
+
+    /// Resolve orders placed outside execution (auto-close, manual) through these pairs' tokens
+    pub fn with_pairs<'a>(mut self, pairs: impl IntoIterator<Item = &'a MarketPair>) -> Self {
+        for pair in pairs {
+            for (token, side) in [(&pair.poly_yes_token, "yes"), (&pair.poly_no_token, "no")] {
+                self.tokens.insert(token.to_string(), OrderContext {
+                    market_id: pair.pair_id.to_string(),
+                    description: pair.description.to_string(),
+                    side,
+                });
+            }
+        }
+        self
+    }
+
+    /// Age orders against another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<&TrackedOrder> {
+        self.orders.get(order_id)
+    }
+
+    pub fn open_orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values().filter(|o| !o.status.is_terminal())
+    }
+
+    /// Claim an order placed by execution, which has already booked `booked` contracts of it
+    pub fn track(&mut self, order_id: &str, context: OrderContext, action: &'static str, booked: f64) {
+        let now = self.clock.mono_ns();
+        let order = self.orders.entry(order_id.to_string()).or_insert_with(|| TrackedOrder::new(order_id, now));
+        order.context = Some(context);
+        order.action = action;
+        order.claimed = true;
+        order.booked = order.booked.max(booked);
+        self.book(order_id);
+    }
+
+    /// Apply one user-channel event; Some when the stream has gapped and REST should be consulted
+    pub fn apply(&mut self, event: &PolyUserEvent) -> Option<Gap> {
+        match event {
+            PolyUserEvent::Order(order) => self.apply_order(order),
+            PolyUserEvent::Trade(trade) => self.apply_trade(trade),
+        }
+    }
+
+    fn apply_order(&mut self, event: &PolyOrderEvent) -> Option<Gap> {
+        let now = self.clock.mono_ns();
+        let context = event.asset_id.as_ref().and_then(|asset| self.tokens.get(asset)).cloned();
+        let order = self.orders.entry(event.id.clone()).or_insert_with(|| TrackedOrder::new(&event.id, now));
+        order.last_update = now;
+
+        if let Some(asset) = &event.asset_id {
+            order.asset_id = asset.clone();
+        }
+        if order.context.is_none() {
+            order.context = context;
+        }
+        if let Some(side) = &event.side {
+            order.action = if side.eq_ignore_ascii_case("sell") { "sell" } else { "buy" };
+        }
+        if let Some(price) = parse_size(&event.price) {
+            order.price = price;
+        }
+        if let Some(size) = parse_size(&event.original_size) {
+            order.original_size = size;
+        }
+
+        let cancelled = event.update_type.as_deref().is_some_and(|t| t.eq_ignore_ascii_case("CANCELLATION"));
+        match event.status.as_deref().and_then(OrderStatus::parse) {
+            _ if cancelled => order.status = OrderStatus::Cancelled,
+            Some(status) => order.status = status,
+            None => {}
+        }
+
+        let mut gap = None;
+        if let Some(matched) = parse_size(&event.size_matched) {
+            if matched + SIZE_EPSILON < order.size_matched {
+                gap = Some(self.regressed(&event.id, matched));
+            } else {
+                order.size_matched = matched;
+            }
+        }
+        self.book(&event.id);
+        gap
+    }
+
+    fn apply_trade(&mut self, event: &PolyTradeEvent) -> Option<Gap> {
+        let now = self.clock.mono_ns();
+        let failed = event.status.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("FAILED"));
+
+        // Our order is the taker or one of the makers; only orders we already track are touched
+        let mut legs: Vec<(String, Option<f64>)> = Vec::new();
+        if let Some(taker) = &event.taker_order_id {
+            legs.push((taker.clone(), parse_size(&event.size)));
+        }
+        for maker in &event.maker_orders {
+            if let Some(id) = maker.get("order_id").and_then(|v| v.as_str()) {
+                let amount = maker.get("matched_amount").and_then(|v| v.as_str()).and_then(|s| s.parse().ok());
+                legs.push((id.to_string(), amount));
+            }
+        }
+
+        let mut touched = Vec::new();
+        for (order_id, amount) in legs {
+            let Some(order) = self.orders.get_mut(&order_id) else { continue };
+            order.last_update = now;
+            if !failed && order.trades.insert(event.id.clone()) {
+                order.trade_matched += amount.unwrap_or(0.0);
+            }
+            touched.push(order_id);
+        }
+        if touched.is_empty() {
+            debug!("[ORDERS] Trade {} matches no tracked order", event.id);
+            return None;
+        }
+
+        if failed {
+            self.suspect.extend(touched.iter().cloned());
+            return Some(Gap::TradeFailed { trade_id: event.id.clone(), order_ids: touched });
+        }
+        for order_id in &touched {
+            self.book(order_id);
+        }
+        None
+    }
+
+    /// Apply authoritative REST state for one order
+    pub fn apply_snapshot(&mut self, snapshot: &PolymarketOrderResponse) -> Option<Gap> {
+        let now = self.clock.mono_ns();
+        let context = snapshot.asset_id.as_ref().and_then(|asset| self.tokens.get(asset)).cloned();
+        let order = self.orders.entry(snapshot.id.clone()).or_insert_with(|| TrackedOrder::new(&snapshot.id, now));
+        order.last_update = now;
+        if let Some(asset) = &snapshot.asset_id {
+            order.asset_id = asset.clone();
+        }
+        if order.context.is_none() {
+            order.context = context;
+        }
+        order.action = if snapshot.side.eq_ignore_ascii_case("sell") { "sell" } else { "buy" };
+        order.price = snapshot.price.parse().unwrap_or(order.price);
+        order.original_size = snapshot.original_size.parse().unwrap_or(order.original_size);
+        if let Some(status) = OrderStatus::parse(&snapshot.status) {
+            order.status = status;
+        }
+        if snapshot.is_terminal() && order.status == OrderStatus::Live {
+            order.status = OrderStatus::Matched;
+        }
+
+        // REST replaces whatever the stream reported, including trades that later failed
+        let matched = snapshot.filled_size();
+        order.size_matched = matched;
+        order.trade_matched = matched;
+        let gap = (matched + SIZE_EPSILON < order.booked).then(|| self.regressed(&snapshot.id, matched));
+        self.book(&snapshot.id);
+        gap
+    }
+
+    /// The stream dropped: every open order needs a REST read
+    pub fn disconnected(&mut self) -> Gap {
+        self.resync_all = true;
+        Gap::Disconnected
+    }
+
+    /// Periodic upkeep: book adopted orders, flag silent ones, forget settled ones
+    pub fn check(&mut self) -> Option<Gap> {
+        let now = self.clock.mono_ns();
+        let stale_after = self.config.stale_after.as_nanos() as u64;
+
+        let ids: Vec<String> = self.orders.keys().cloned().collect();
+        for id in &ids {
+            self.book(id);
+        }
+
+        let silent: Vec<String> = self.open_orders()
+            .filter(|o| now.saturating_sub(o.last_update).0 >= stale_after)
+            .map(|o| o.order_id.clone())
+            .collect();
+
+        self.orders.retain(|_, o| {
+            let settled = o.status.is_terminal() && (o.matched() - o.booked).abs() <= SIZE_EPSILON;
+            !(settled && now.saturating_sub(o.last_update).0 >= stale_after)
+        });
+
+        if silent.is_empty() {
+            return None;
+        }
+        self.suspect.extend(silent.iter().cloned());
+        Some(Gap::Silent(silent))
+    }
+
+    /// Order IDs due a REST read; clears the pending set
+    pub fn take_due(&mut self) -> Vec<String> {
+        let mut due: HashSet<String> = std::mem::take(&mut self.suspect);
+        if std::mem::take(&mut self.resync_all) {
+            due.extend(self.open_orders().map(|o| o.order_id.clone()));
+        }
+        due.into_iter().collect()
+    }
+
+    /// Re-queue an order whose REST read failed
+    pub fn retry_later(&mut self, order_id: &str) {
+        self.suspect.insert(order_id.to_string());
+    }
+
+    fn regressed(&mut self, order_id: &str, venue: f64) -> Gap {
+        self.suspect.insert(order_id.to_string());
+        let booked = self.orders.get(order_id).map_or(0.0, |o| o.booked);
+        Gap::Regressed { order_id: order_id.to_string(), booked, venue }
+    }
+
+    /// Send matched-but-unbooked contracts to the position tracker
+    fn book(&mut self, order_id: &str) {
+        let now = self.clock.mono_ns();
+        let adopt_after = self.config.adopt_after.as_nanos() as u64;
+        let Some(order) = self.orders.get_mut(order_id) else { return };
+        // Give execution the chance to claim its own orders before booking them here
+        if !order.claimed && now.saturating_sub(order.first_seen).0 < adopt_after {
+            return;
+        }
+        let Some(context) = &order.context else { return };
+
+        let delta = order.matched() - order.booked;
+        if delta <= SIZE_EPSILON {
+            return;
+        }
+        let fill = FillRecord::new(
+            &context.market_id, &context.description, "polymarket", context.side,
+            delta, order.price, 0.0, &order.order_id,
+        );
+        self.positions.record_fill(if order.action == "sell" { fill.as_sell() } else { fill });
+        order.booked += delta;
+        info!("[ORDERS] Booked {:.2} {} {} @ {:.3} ({})",
+              delta, context.market_id, context.side, order.price, order.order_id);
+    }
+}
+
+fn parse_size(value: &Option<String>) -> Option<f64> {
+    value.as_deref().and_then(|s| s.parse().ok())
+}
+
+fn lock(manager: &SharedOrderManager) -> MutexGuard<'_, OrderManager> {
+    manager.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// REST read of a single order
+pub trait OrderStateSource: Send + Sync {
+    fn fetch_order<'a>(&'a self, order_id: &'a str) -> BoxFuture<'a, Result<PolymarketOrderResponse, VenueApiError>>;
+}
+
+impl OrderStateSource for SharedAsyncClient {
+    fn fetch_order<'a>(&'a self, order_id: &'a str) -> BoxFuture<'a, Result<PolymarketOrderResponse, VenueApiError>> {
+        Box::pin(self.get_order(order_id))
+    }
+}
+
+/// Re-read every order due a REST check; returns how many were refreshed
+pub async fn reconcile(manager: &SharedOrderManager, source: &dyn OrderStateSource) -> usize {
+    let due = lock(manager).take_due();
+    let mut refreshed = 0;
+    for order_id in due {
+        match source.fetch_order(&order_id).await {
+            Ok(snapshot) => {
+                let gap = lock(manager).apply_snapshot(&snapshot);
+                if let Some(gap) = gap {
+                    warn!("[ORDERS] Venue reports less than booked: {:?}", gap);
+                }
+                refreshed += 1;
+            }
+            Err(e) => {
+                warn!("[ORDERS] REST read of {} failed: {}", order_id, e);
+                lock(manager).retry_later(&order_id);
+            }
+        }
+    }
+    refreshed
+}
+
+/// Keep the order manager fed from the user channel, reconnecting forever
+/// `markets` are condition IDs; empty = all of the account's markets
+pub async fn run_user_channel(
+    creds: ApiCreds,
+    markets: Vec<String>,
+    manager: SharedOrderManager,
+    source: Arc<dyn OrderStateSource>,
+) {
+    let config = lock(&manager).config.clone();
+    loop {
+        let (tx, mut rx) = mpsc::channel(1024);
+        let stream = run_user_ws(&creds, &markets, tx);
+        tokio::pin!(stream);
+        let mut check = tokio::time::interval(config.check_interval);
+
+        loop {
+            tokio::select! {
+                result = &mut stream => {
+                    if let Err(e) = result {
+                        warn!("[ORDERS] User channel error: {}", e);
+                    }
+                    break;
+                }
+                Some(event) = rx.recv() => {
+                    let gap = lock(&manager).apply(&event);
+                    if let Some(gap) = gap {
+                        warn!("[ORDERS] Stream gap: {:?}", gap);
+                        reconcile(&manager, source.as_ref()).await;
+                    }
+                }
+                _ = check.tick() => {
+                    let gap = lock(&manager).check();
+                    if let Some(gap) = gap {
+                        debug!("[ORDERS] {:?}", gap);
+                    }
+                    reconcile(&manager, source.as_ref()).await;
+                }
+            }
+        }
+
+        // Events already forwarded before the drop still count
+        while let Ok(event) = rx.try_recv() {
+            lock(&manager).apply(&event);
+        }
+        lock(&manager).disconnected();
+        let refreshed = reconcile(&manager, source.as_ref()).await;
+        info!("[ORDERS] User channel down; refreshed {} orders over REST, reconnecting in {:?}",
+              refreshed, config.reconnect_delay);
+        tokio::time::sleep(config.reconnect_delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::position_tracker::create_position_channel;
+
+    fn order_event(id: &str, update_type: &str, status: &str, matched: &str) -> PolyUserEvent {
+        PolyUserEvent::Order(PolyOrderEvent {
+            id: id.to_string(),
+            update_type: Some(update_type.to_string()),
+            status: Some(status.to_string()),
+            asset_id: Some("tok-yes".to_string()),
+            market: None,
+            side: Some("BUY".to_string()),
+            price: Some("0.45".to_string()),
+            original_size: Some("10".to_string()),
+            size_matched: Some(matched.to_string()),
+            timestamp: None,
+        })
+    }
+
+    fn snapshot(id: &str, status: &str, matched: &str) -> PolymarketOrderResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "status": status, "price": "0.45", "side": "BUY",
+            "size_matched": matched, "original_size": "10", "asset_id": "tok-yes",
+        })).unwrap()
+    }
+
+    fn setup() -> (OrderManager, Arc<MockClock>, tokio::sync::mpsc::UnboundedReceiver<FillRecord>) {
+        let (positions, rx) = create_position_channel();
+        let clock = MockClock::shared(Nanos(1_000));
+        let mut manager = OrderManager::new(OrderManagerConfig::default(), positions).with_clock(clock.clone());
+        manager.tokens.insert("tok-yes".to_string(), OrderContext {
+            market_id: "m1".to_string(),
+            description: "Test".to_string(),
+            side: "yes",
+        });
+        (manager, clock, rx)
+    }
+
+    fn drain(rx: &mut tokio::sync::mpsc::UnboundedReceiver<FillRecord>) -> Vec<(f64, String)> {
+        std::iter::from_fn(|| rx.try_recv().ok()).map(|f| (f.contracts, f.action)).collect()
+    }
+
+    #[test]
+    fn claimed_orders_book_only_unbooked_matches() {
+        let (mut manager, _clock, mut rx) = setup();
+        let context = manager.tokens["tok-yes"].clone();
+        manager.track("o1", context, "buy", 4.0);
+
+        assert_eq!(manager.apply(&order_event("o1", "PLACEMENT", "LIVE", "0")), None);
+        assert_eq!(manager.apply(&order_event("o1", "UPDATE", "LIVE", "4")), None);
+        assert!(drain(&mut rx).is_empty(), "execution already booked these 4");
+
+        manager.apply(&order_event("o1", "UPDATE", "MATCHED", "6"));
+        assert_eq!(drain(&mut rx), vec![(2.0, "buy".to_string())]);
+        assert_eq!(manager.get("o1").unwrap().status, OrderStatus::Matched);
+    }
+
+    #[test]
+    fn unclaimed_orders_are_adopted_after_grace() {
+        let (mut manager, clock, mut rx) = setup();
+        manager.apply(&order_event("o2", "UPDATE", "MATCHED", "3"));
+        assert!(drain(&mut rx).is_empty());
+
+        // Execution claims late: what it already booked is not booked again
+        let context = manager.tokens["tok-yes"].clone();
+        manager.track("o2", context, "buy", 3.0);
+        assert!(drain(&mut rx).is_empty());
+
+        let mut sell = order_event("o3", "UPDATE", "MATCHED", "5");
+        if let PolyUserEvent::Order(order) = &mut sell {
+            order.side = Some("SELL".to_string());
+        }
+        manager.apply(&sell);
+        clock.advance(Duration::from_secs(3));
+        assert_eq!(manager.check(), None);
+        assert_eq!(drain(&mut rx), vec![(5.0, "sell".to_string())]);
+    }
+
+    #[test]
+    fn trades_and_order_updates_are_not_double_counted() {
+        let (mut manager, _clock, mut rx) = setup();
+        let context = manager.tokens["tok-yes"].clone();
+        manager.track("o1", context, "buy", 0.0);
+
+        let trade = PolyUserEvent::Trade(serde_json::from_value(serde_json::json!({
+            "id": "t1", "status": "MATCHED", "taker_order_id": "o1", "size": "5", "price": "0.45",
+        })).unwrap());
+        manager.apply(&trade);
+        manager.apply(&trade);
+        manager.apply(&order_event("o1", "UPDATE", "MATCHED", "5"));
+        assert_eq!(drain(&mut rx), vec![(5.0, "buy".to_string())]);
+    }
+
+    #[test]
+    fn gaps_queue_rest_reads() {
+        let (mut manager, clock, mut rx) = setup();
+        let context = manager.tokens["tok-yes"].clone();
+        manager.track("o1", context.clone(), "buy", 0.0);
+        manager.track("o2", context, "buy", 0.0);
+        manager.apply(&order_event("o1", "UPDATE", "LIVE", "6"));
+        manager.apply(&order_event("o2", "PLACEMENT", "LIVE", "0"));
+
+        // Out-of-order update: keep the higher size, ask REST
+        let gap = manager.apply(&order_event("o1", "UPDATE", "LIVE", "2"));
+        assert!(matches!(gap, Some(Gap::Regressed { ref order_id, .. }) if order_id == "o1"));
+        assert_eq!(manager.take_due(), vec!["o1".to_string()]);
+
+        clock.advance(Duration::from_secs(31));
+        assert!(matches!(manager.check(), Some(Gap::Silent(ids)) if ids.len() == 2));
+        manager.take_due();
+
+        // After a drop, REST fills in what the stream missed
+        manager.disconnected();
+        let mut due = manager.take_due();
+        due.sort();
+        assert_eq!(due, vec!["o1".to_string(), "o2".to_string()]);
+        assert_eq!(manager.apply_snapshot(&snapshot("o2", "CANCELED", "1")), None);
+        assert_eq!(manager.get("o2").unwrap().status, OrderStatus::Cancelled);
+        assert_eq!(drain(&mut rx), vec![(6.0, "buy".to_string()), (1.0, "buy".to_string())]);
+    }
+}
@@ -0,0 +1,302 @@
+// src/paper_fills.rs
+// Paper-fill simulator - hypothetical latency arb orders matched against the recorded books with modeled latency and queue priority
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use crate::clock::SharedClock;
+use crate::config::PaperFillsSection;
+use crate::latency_arbitrage::{LatencyArbitrageEngine, MarketTier, PriceObservation};
+use crate::latency_execution::{LatencyExecutionRequest, LatencyExecutionResult};
+use crate::types::{Platform, PriceCents, SignalId, SizeCents, TimestampNs};
+
+/// Paper-fill configuration
+#[derive(Debug, Clone)]
+pub struct PaperFillConfig {
+    /// Order size per leg (cents of notional)
+    pub order_size: SizeCents,
+    /// Decision-to-book latency per venue; venues not listed use `default_latency`
+    pub latency: HashMap<Platform, Duration>,
+    pub default_latency: Duration,
+}
+
+impl Default for PaperFillConfig {
+    fn default() -> Self {
+        Self::from(&PaperFillsSection::default())
+    }
+}
+
+impl From<&PaperFillsSection> for PaperFillConfig {
+    fn from(p: &PaperFillsSection) -> Self {
+        Self {
+            order_size: p.order_size,
+            latency: HashMap::from([
+                (Platform::Kalshi, Duration::from_millis(40)),
+                (Platform::Polymarket, Duration::from_millis(60)),
+                (Platform::DraftKings, Duration::from_millis(250)),
+                (Platform::FanDuel, Duration::from_millis(250)),
+            ]),
+            default_latency: Duration::from_millis(300),
+        }
+    }
+}
+
+impl PaperFillConfig {
+    pub fn latency(&self, platform: Platform) -> Duration {
+        self.latency.get(&platform).copied().unwrap_or(self.default_latency)
+    }
+}
+
+/// Top of a recorded book when a leg's order arrives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookTouch {
+    pub price: PriceCents,
+    pub size: SizeCents,
+}
+
+/// One side of a latency arb: buy the cheap market, sell the rich one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LegSide {
+    Buy,
+    Sell,
+}
+
+/// Matches latency arb orders against recorded books. An order reaches the book after its
+/// venue's latency; faster participants have taken the share of the quoted size the edge has
+/// decayed by (half-life of the tier), and we fill only from what is left, at the touch then.
+pub struct PaperFillSimulator {
+    config: PaperFillConfig,
+    clock: SharedClock,
+}
+
+pub type SharedPaperFills = Arc<PaperFillSimulator>;
+
+impl PaperFillSimulator {
+    pub fn new(config: PaperFillConfig, clock: SharedClock) -> Self {
+        Self { config, clock }
+    }
+
+    /// Notional of each leg's order (cents)
+    pub fn order_size(&self) -> SizeCents {
+        self.config.order_size
+    }
+
+    /// Share of a stale quote's size taken by orders ahead of ours after `latency`
+    fn queue_ahead(tier: MarketTier, latency: Duration) -> f64 {
+        1.0 - tier.edge_remaining(latency.as_nanos() as u64)
+    }
+
+    /// Fill price for one leg, or why it missed
+    fn match_leg(
+        &self,
+        obs: &PriceObservation,
+        side: LegSide,
+        max_slippage: PriceCents,
+        touch: Option<BookTouch>,
+    ) -> Result<PriceCents, String> {
+        let venue = obs.provider;
+        let Some(touch) = touch.filter(|t| t.price > 0) else {
+            return Err(format!("{} market {}: no book", venue, obs.market_id));
+        };
+        let through = match side {
+            LegSide::Buy => touch.price > obs.price.saturating_add(max_slippage),
+            LegSide::Sell => touch.price < obs.price.saturating_sub(max_slippage),
+        };
+        if through {
+            return Err(format!("{} market {}: touch moved {}¢ -> {}¢", venue, obs.market_id, obs.price, touch.price));
+        }
+        let ahead = Self::queue_ahead(obs.tier, self.config.latency(venue));
+        let available = (touch.size as f64 * (1.0 - ahead)).floor() as SizeCents;
+        if available < self.config.order_size {
+            return Err(format!("{} market {}: {}¢ left behind the queue, wanted {}¢",
+                               venue, obs.market_id, available, self.config.order_size));
+        }
+        Ok(touch.price)
+    }
+
+    /// Result for both legs given the book each one found on arrival
+    fn settle(
+        &self,
+        signal_id: SignalId,
+        request: &LatencyExecutionRequest,
+        fast_touch: Option<BookTouch>,
+        slow_touch: Option<BookTouch>,
+        execution_time_ns: TimestampNs,
+    ) -> LatencyExecutionResult {
+        let signal = &request.signal;
+        // Fast above slow: the slow market is cheap, buy it and sell the fast one
+        let (fast_side, slow_side, direction) = if signal.fast_market.price >= signal.slow_market.price {
+            (LegSide::Sell, LegSide::Buy, 1)
+        } else {
+            (LegSide::Buy, LegSide::Sell, -1)
+        };
+        let fast = self.match_leg(&signal.fast_market, fast_side, request.max_edge_decay_cents, fast_touch);
+        let slow = self.match_leg(&signal.slow_market, slow_side, request.max_edge_decay_cents, slow_touch);
+        let initial = signal.disparity_cents.abs();
+        let late = execution_time_ns > request.execution_deadline_ns;
+
+        match (fast, slow) {
+            (Ok(fast), Ok(slow)) if !late => {
+                let captured = (fast as i16 - slow as i16) * direction;
+                LatencyExecutionResult {
+                    signal_id,
+                    success: true,
+                    fast_fill_price: Some(fast),
+                    slow_fill_price: Some(slow),
+                    execution_time_ns,
+                    edge_captured_cents: captured,
+                    edge_decay_cents: initial - captured,
+                    error_message: None,
+                    strategy_id: signal.strategy_id.clone(),
+                    providers: Some(request.providers()),
+                }
+            }
+            (fast, slow) => {
+                let reason = if late {
+                    "Paper fill arrived after the execution deadline".to_string()
+                } else {
+                    [fast.err(), slow.err()].into_iter().flatten().collect::<Vec<_>>().join("; ")
+                };
+                LatencyExecutionResult {
+                    signal_id,
+                    success: false,
+                    fast_fill_price: None,
+                    slow_fill_price: None,
+                    execution_time_ns,
+                    edge_captured_cents: 0,
+                    edge_decay_cents: initial,
+                    error_message: Some(reason),
+                    strategy_id: signal.strategy_id.clone(),
+                    providers: Some(request.providers()),
+                }
+            }
+        }
+    }
+
+    fn touch(engine: &LatencyArbitrageEngine, obs: &PriceObservation) -> Option<BookTouch> {
+        let book = engine.price_feeds.get(&(obs.market_id, obs.provider))?;
+        let (price, _, size, _, _) = book.load();
+        Some(BookTouch { price, size })
+    }
+
+    /// Send both legs, wait out each venue's latency and match against the book it finds
+    pub async fn execute(
+        &self,
+        signal_id: SignalId,
+        request: &LatencyExecutionRequest,
+        engine: &Arc<RwLock<LatencyArbitrageEngine>>,
+    ) -> LatencyExecutionResult {
+        let signal = &request.signal;
+        let fast_latency = self.config.latency(signal.fast_market.provider);
+        let slow_latency = self.config.latency(signal.slow_market.provider);
+
+        // Legs go out together; read each book as its order lands
+        let fast_first = fast_latency <= slow_latency;
+        let (first, second) = if fast_first {
+            (&signal.fast_market, &signal.slow_market)
+        } else {
+            (&signal.slow_market, &signal.fast_market)
+        };
+        tokio::time::sleep(fast_latency.min(slow_latency)).await;
+        let first = Self::touch(&*engine.read().await, first);
+        tokio::time::sleep(fast_latency.abs_diff(slow_latency)).await;
+        let second = Self::touch(&*engine.read().await, second);
+        let (fast_touch, slow_touch) = if fast_first { (first, second) } else { (second, first) };
+
+        self.settle(signal_id, request, fast_touch, slow_touch, self.clock.mono_ns().0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock;
+    use crate::latency_arbitrage::LatencySignal;
+    use crate::types::MarketType;
+
+    fn obs(market_id: u16, provider: Platform, price: PriceCents, tier: MarketTier) -> PriceObservation {
+        PriceObservation {
+            market_id,
+            provider,
+            market_type: MarketType::Moneyline,
+            price,
+            size: 5000,
+            timestamp_ns: 0,
+            tier,
+            features: None,
+        }
+    }
+
+    fn request(fast: PriceCents, slow: PriceCents) -> LatencyExecutionRequest {
+        LatencyExecutionRequest {
+            signal: LatencySignal {
+                fast_market: obs(1, Platform::Kalshi, fast, MarketTier::Tier1),
+                slow_market: obs(2, Platform::DraftKings, slow, MarketTier::Tier4),
+                disparity_cents: fast as i16 - slow as i16,
+                expected_convergence_ns: 1_000_000_000,
+                pattern_id: Some(74),
+                confidence: 0.8,
+                strategy_id: "pattern_74".to_string(),
+            },
+            execution_deadline_ns: 1_000_000_000,
+            fill_probability_threshold: 0.5,
+            max_edge_decay_cents: 2,
+        }
+    }
+
+    fn simulator() -> PaperFillSimulator {
+        PaperFillSimulator::new(PaperFillConfig::default(), clock::system())
+    }
+
+    #[test]
+    fn test_fills_at_the_touch_found_on_arrival() {
+        let sim = simulator();
+        // Fast leg sells at 55¢, slow leg buys 46¢ (a cent worse than signalled)
+        let result = sim.settle(
+            SignalId(1), &request(55, 45),
+            Some(BookTouch { price: 55, size: 5000 }), Some(BookTouch { price: 46, size: 5000 }), 10,
+        );
+        assert!(result.success);
+        assert_eq!((result.fast_fill_price, result.slow_fill_price), (Some(55), Some(46)));
+        assert_eq!(result.edge_captured_cents, 9);
+        assert_eq!(result.edge_decay_cents, 1);
+    }
+
+    #[test]
+    fn test_moved_or_missing_books_miss() {
+        let sim = simulator();
+        // Slow touch ran 4¢ against us, past the 2¢ allowed
+        let result = sim.settle(
+            SignalId(2), &request(55, 45),
+            Some(BookTouch { price: 55, size: 5000 }), Some(BookTouch { price: 49, size: 5000 }), 10,
+        );
+        assert!(!result.success);
+        assert!(result.error_message.unwrap().contains("touch moved"));
+        assert_eq!(result.edge_decay_cents, 10);
+
+        let result = sim.settle(SignalId(3), &request(55, 45), None, Some(BookTouch { price: 45, size: 5000 }), 10);
+        assert!(result.error_message.unwrap().contains("no book"));
+
+        let late = sim.settle(
+            SignalId(4), &request(55, 45),
+            Some(BookTouch { price: 55, size: 5000 }), Some(BookTouch { price: 45, size: 5000 }), 2_000_000_000,
+        );
+        assert!(!late.success);
+    }
+
+    #[test]
+    fn test_queue_ahead_grows_with_latency() {
+        let sim = simulator();
+        // 40ms on a 300ms Tier1 half-life leaves ~91% of the quote: 1100¢ shown is enough for 1000¢
+        let fast = obs(1, Platform::Kalshi, 55, MarketTier::Tier1);
+        assert_eq!(sim.match_leg(&fast, LegSide::Sell, 2, Some(BookTouch { price: 55, size: 1100 })), Ok(55));
+        // A sportsbook's 250ms on the same tier leaves ~56%: 1100¢ shown is not
+        let slow = obs(2, Platform::DraftKings, 45, MarketTier::Tier1);
+        assert!(sim.match_leg(&slow, LegSide::Buy, 2, Some(BookTouch { price: 45, size: 1100 }))
+            .unwrap_err().contains("behind the queue"));
+        assert!(PaperFillSimulator::queue_ahead(MarketTier::Tier4, Duration::from_millis(250))
+            < PaperFillSimulator::queue_ahead(MarketTier::Tier1, Duration::from_millis(250)));
+    }
+}
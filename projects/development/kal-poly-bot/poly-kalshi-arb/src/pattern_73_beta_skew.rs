@@ -4,6 +4,11 @@
 //! High-usage player props have a non-linear impact on team totals with measurable beta coefficients.
 //! Uses Kalman filters for state estimation and recursive least squares for beta modeling.
 
+use crate::config::PatternsSection;
+use crate::event_bus::{Event, SharedEventBus};
+use crate::event_phase::SharedPhaseTracker;
+use crate::feature_flags::SharedFeatureFlags;
+use crate::interner::Symbol;
 use crate::types::{TimestampNs, PriceCents, MarketType, Platform};
 use nalgebra::{DMatrix, DVector, Vector2, Matrix2};
 use std::collections::HashMap;
@@ -13,11 +18,11 @@ use tracing::{info, warn, debug};
 #[derive(Debug, Clone)]
 pub struct PlayerPropState {
     /// Market identifier
-    pub market_id: String,
+    pub market_id: Symbol,
     /// Player identifier
-    pub player_id: String,
+    pub player_id: Symbol,
     /// Team identifier
-    pub team_id: String,
+    pub team_id: Symbol,
     /// Current price (points line)
     pub price: f64,
     /// Velocity (points/second)
@@ -36,9 +41,9 @@ pub struct PlayerPropState {
 #[derive(Debug, Clone)]
 pub struct TeamTotalState {
     /// Market identifier
-    pub market_id: String,
+    pub market_id: Symbol,
     /// Team identifier
-    pub team_id: String,
+    pub team_id: Symbol,
     /// Current total line
     pub total: f64,
     /// Velocity (points/second)
@@ -57,9 +62,9 @@ pub struct TeamTotalState {
 #[derive(Debug, Clone)]
 pub struct BetaRelationship {
     /// Player identifier
-    pub player_id: String,
+    pub player_id: Symbol,
     /// Team identifier
-    pub team_id: String,
+    pub team_id: Symbol,
     /// Current beta estimate
     pub beta: f64,
     /// Beta uncertainty (standard deviation)
@@ -78,13 +83,13 @@ pub struct BetaRelationship {
 #[derive(Debug, Clone)]
 pub struct BetaSkewOpportunity {
     /// Player prop market
-    pub player_prop_market: String,
+    pub player_prop_market: Symbol,
     /// Team total market
-    pub team_total_market: String,
+    pub team_total_market: Symbol,
     /// Player identifier
-    pub player_id: String,
+    pub player_id: Symbol,
     /// Team identifier
-    pub team_id: String,
+    pub team_id: Symbol,
     /// Current player prop price
     pub current_player_price: f64,
     /// Current team total
@@ -264,16 +269,22 @@ impl RecursiveLeastSquares {
 /// Pattern #73: Player Prop to Team Total Beta Skew Engine
 #[derive(Debug)]
 pub struct Pattern73Engine {
-    /// Player prop states
-    pub player_props: HashMap<String, PlayerPropState>,
-    /// Team total states
-    pub team_totals: HashMap<String, TeamTotalState>,
-    /// Beta relationships
-    pub beta_relationships: HashMap<String, BetaRelationship>,
+    /// Player prop states by (player, team)
+    pub player_props: HashMap<(Symbol, Symbol), PlayerPropState>,
+    /// Team total states by team
+    pub team_totals: HashMap<Symbol, TeamTotalState>,
+    /// Beta relationships by (player, team)
+    pub beta_relationships: HashMap<(Symbol, Symbol), BetaRelationship>,
     /// Configuration
     pub config: Pattern73Config,
     /// Detected opportunities
     pub opportunities: Vec<BetaSkewOpportunity>,
+    /// Bus new opportunities are published on (optional)
+    pub event_bus: Option<SharedEventBus>,
+    /// Detection is skipped while component #73 is flagged off (optional)
+    pub feature_flags: Option<SharedFeatureFlags>,
+    /// Detection is skipped on markets outside #73's event phases (optional)
+    pub phase_tracker: Option<SharedPhaseTracker>,
 }
 
 /// Configuration for Pattern #73
@@ -321,19 +332,49 @@ impl Pattern73Engine {
             beta_relationships: HashMap::new(),
             config,
             opportunities: Vec::new(),
+            event_bus: None,
+            feature_flags: None,
+            phase_tracker: None,
         }
     }
 
+    /// Publish detected opportunities as `Event::Opportunity`
+    pub fn with_event_bus(mut self, bus: SharedEventBus) -> Self {
+        self.event_bus = Some(bus);
+        self
+    }
+
+    /// Gate detection on the #73 feature flag (state keeps updating while off)
+    pub fn with_feature_flags(mut self, flags: SharedFeatureFlags) -> Self {
+        self.feature_flags = Some(flags);
+        self
+    }
+
+    /// Gate detection on both markets' event phases
+    pub fn with_phase_tracker(mut self, phases: SharedPhaseTracker) -> Self {
+        self.phase_tracker = Some(phases);
+        self
+    }
+
+    /// Apply hot-reloaded detection thresholds (filter state is kept)
+    pub fn apply_config(&mut self, patterns: &PatternsSection) {
+        self.config.min_gap_threshold = patterns.min_gap_threshold;
+        self.config.min_gap_percent = patterns.min_gap_percent;
+        self.config.max_half_life_ms = patterns.max_half_life_ms;
+        self.config.min_usage_rate = patterns.min_usage_rate;
+    }
+
     /// Add or update player prop observation
-    pub fn update_player_prop(&mut self, market_id: &str, player_id: &str, team_id: &str,
+    pub fn update_player_prop(&mut self, market_id: impl Into<Symbol>, player_id: impl Into<Symbol>, team_id: impl Into<Symbol>,
                              price: f64, timestamp_ns: TimestampNs, usage_rate: f64) {
-        let key = format!("{}_{}", player_id, team_id);
+        let (market_id, player_id, team_id) = (market_id.into(), player_id.into(), team_id.into());
+        let key = (player_id, team_id);
 
-        let player_state = self.player_props.entry(key.clone()).or_insert_with(|| {
+        let player_state = self.player_props.entry(key).or_insert_with(|| {
             PlayerPropState {
-                market_id: market_id.to_string(),
-                player_id: player_id.to_string(),
-                team_id: team_id.to_string(),
+                market_id,
+                player_id,
+                team_id,
                 price,
                 velocity: 0.0,
                 acceleration: 0.0,
@@ -358,19 +399,20 @@ impl Pattern73Engine {
         }
 
         // Update beta relationship if we have corresponding team total
-        self.update_beta_relationship(&key, usage_rate);
+        self.update_beta_relationship(key, usage_rate);
 
         // Check for opportunities
-        self.detect_opportunities(&key);
+        self.detect_opportunities(key);
     }
 
     /// Add or update team total observation
-    pub fn update_team_total(&mut self, market_id: &str, team_id: &str,
+    pub fn update_team_total(&mut self, market_id: impl Into<Symbol>, team_id: impl Into<Symbol>,
                             total: f64, timestamp_ns: TimestampNs) {
-        let team_state = self.team_totals.entry(team_id.to_string()).or_insert_with(|| {
+        let (market_id, team_id) = (market_id.into(), team_id.into());
+        let team_state = self.team_totals.entry(team_id).or_insert_with(|| {
             TeamTotalState {
-                market_id: market_id.to_string(),
-                team_id: team_id.to_string(),
+                market_id,
+                team_id,
                 total,
                 velocity: 0.0,
                 acceleration: 0.0,
@@ -396,12 +438,12 @@ impl Pattern73Engine {
     }
 
     /// Update beta relationship between player prop and team total
-    fn update_beta_relationship(&mut self, key: &str, usage_rate: f64) {
+    fn update_beta_relationship(&mut self, key: (Symbol, Symbol), usage_rate: f64) {
         if usage_rate < self.config.min_usage_rate {
             return; // Skip low-usage players
         }
 
-        let player_state = match self.player_props.get(key) {
+        let player_state = match self.player_props.get(&key) {
             Some(state) => state,
             None => return,
         };
@@ -416,10 +458,10 @@ impl Pattern73Engine {
             return;
         }
 
-        let beta_rel = self.beta_relationships.entry(key.to_string()).or_insert_with(|| {
+        let beta_rel = self.beta_relationships.entry(key).or_insert_with(|| {
             BetaRelationship {
-                player_id: player_state.player_id.clone(),
-                team_id: player_state.team_id.clone(),
+                player_id: player_state.player_id,
+                team_id: player_state.team_id,
                 beta: 0.5,
                 beta_uncertainty: 1.0,
                 usage_rate,
@@ -467,8 +509,12 @@ impl Pattern73Engine {
     }
 
     /// Detect arbitrage opportunities for a player-team pair
-    fn detect_opportunities(&mut self, key: &str) {
-        let player_state = match self.player_props.get(key) {
+    fn detect_opportunities(&mut self, key: (Symbol, Symbol)) {
+        if self.feature_flags.as_ref().is_some_and(|flags| !flags.component_enabled(73)) {
+            return;
+        }
+
+        let player_state = match self.player_props.get(&key) {
             Some(state) => state,
             None => return,
         };
@@ -478,7 +524,15 @@ impl Pattern73Engine {
             None => return,
         };
 
-        let beta_rel = match self.beta_relationships.get(key) {
+        if let Some(phases) = &self.phase_tracker {
+            if !phases.component_allowed(player_state.market_id.as_str(), 73)
+                || !phases.component_allowed(team_state.market_id.as_str(), 73)
+            {
+                return;
+            }
+        }
+
+        let beta_rel = match self.beta_relationships.get(&key) {
             Some(rel) => rel,
             None => return,
         };
@@ -497,7 +551,7 @@ impl Pattern73Engine {
         let predicted_team_change = beta_rel.beta * player_change;
 
         // Calculate half-life adjustment
-        let half_life_ms = self.estimate_half_life(&player_state.team_id);
+        let half_life_ms = self.estimate_half_life(player_state.team_id);
         if half_life_ms > self.config.max_half_life_ms {
             return;
         }
@@ -514,10 +568,10 @@ impl Pattern73Engine {
         // Check if gap exceeds threshold
         if gap.abs() >= self.config.min_gap_threshold && gap_percent >= self.config.min_gap_percent {
             let opportunity = BetaSkewOpportunity {
-                player_prop_market: player_state.market_id.clone(),
-                team_total_market: team_state.market_id.clone(),
-                player_id: player_state.player_id.clone(),
-                team_id: player_state.team_id.clone(),
+                player_prop_market: player_state.market_id,
+                team_total_market: team_state.market_id,
+                player_id: player_state.player_id,
+                team_id: player_state.team_id,
                 current_player_price: player_state.price,
                 current_team_total: team_state.total,
                 beta: beta_rel.beta,
@@ -535,12 +589,15 @@ impl Pattern73Engine {
                   opportunity.player_id, opportunity.team_id, opportunity.gap,
                   opportunity.gap_percent * 100.0, opportunity.strength);
 
+            if let Some(bus) = &self.event_bus {
+                bus.publish(Event::Opportunity(opportunity.clone()));
+            }
             self.opportunities.push(opportunity);
         }
     }
 
     /// Estimate half-life for team total adjustment
-    fn estimate_half_life(&self, team_id: &str) -> f64 {
+    fn estimate_half_life(&self, team_id: Symbol) -> f64 {
         // Simplified half-life estimation based on market tier
         // In practice, this would be estimated from historical data
         match team_id {
@@ -553,21 +610,16 @@ impl Pattern73Engine {
         &self.opportunities
     }
 
-    /// Clear old opportunities
-    pub fn clear_old_opportunities(&mut self, max_age_ns: TimestampNs) {
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
-
+    /// Clear opportunities older than `max_age_ns` at `now_ns` (the clock their ticks are stamped with)
+    pub fn clear_old_opportunities(&mut self, now_ns: TimestampNs, max_age_ns: TimestampNs) {
         self.opportunities.retain(|opp| {
-            current_time - opp.timestamp_ns < max_age_ns
+            now_ns.saturating_sub(opp.timestamp_ns) < max_age_ns
         });
     }
 
     /// Get beta relationship for a player-team pair
     pub fn get_beta_relationship(&self, player_id: &str, team_id: &str) -> Option<&BetaRelationship> {
-        let key = format!("{}_{}", player_id, team_id);
+        let key = (Symbol::get(player_id)?, Symbol::get(team_id)?);
         self.beta_relationships.get(&key)
     }
 }
@@ -0,0 +1,274 @@
+// src/pattern_policy.rs
+// Pattern policy - which patterns may trade, on which venues, at what confidence, with how many positions and dollars
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::config::{PatternRule, PatternsSection};
+use crate::config_reload::ConfigChanged;
+use crate::error::RiskRejection;
+use crate::position_tracker::PositionTracker;
+use crate::types::ArbType;
+
+/// How often open positions are recounted from the tracker
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Venues an arb type trades on (names as in fill records)
+pub fn arb_venues(arb_type: ArbType) -> &'static [&'static str] {
+    match arb_type {
+        ArbType::PolyYesKalshiNo | ArbType::KalshiYesPolyNo => &["polymarket", "kalshi"],
+        ArbType::PolyOnly => &["polymarket"],
+        ArbType::KalshiOnly => &["kalshi"],
+    }
+}
+
+/// A pattern's open positions and the dollars they hold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PatternExposure {
+    pub positions: u32,
+    pub capital: f64,
+}
+
+/// Open positions and capital per pattern tag
+fn open_exposure(tracker: &PositionTracker) -> HashMap<String, PatternExposure> {
+    let mut out: HashMap<String, PatternExposure> = HashMap::new();
+    for position in tracker.open_positions() {
+        let mut seen = HashSet::new();
+        for leg in [&position.kalshi_yes, &position.kalshi_no, &position.poly_yes, &position.poly_no] {
+            for lot in &leg.lots {
+                if let Some(pattern) = &lot.pattern {
+                    let exposure = out.entry(pattern.clone()).or_default();
+                    exposure.capital += lot.contracts * lot.price;
+                    if seen.insert(pattern.as_str()) {
+                        exposure.positions += 1;
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// `[patterns.enabled]` enforced the same way by the dispatcher, execution engine, worker and
+/// risk engine. With no rules listed every pattern trades without limits.
+pub struct PatternPolicy {
+    rules: RwLock<BTreeMap<String, PatternRule>>,
+    /// Open lots at the last sync plus positions opened since
+    exposure: Mutex<HashMap<String, PatternExposure>>,
+}
+
+pub type SharedPatternPolicy = Arc<PatternPolicy>;
+
+impl PatternPolicy {
+    pub fn new(section: &PatternsSection) -> Self {
+        Self {
+            rules: RwLock::new(section.enabled.clone()),
+            exposure: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Apply a hot-reloaded `[patterns]` section (positions already open are kept)
+    pub fn apply_config(&self, section: &PatternsSection) {
+        *self.rules.write().unwrap() = section.enabled.clone();
+        info!("[PATTERN] Config: {} enabled", if section.enabled.is_empty() {
+            "all patterns".to_string()
+        } else {
+            section.enabled.keys().cloned().collect::<Vec<_>>().join(",")
+        });
+    }
+
+    /// Rule for an enabled pattern (None when rules are listed and this one isn't)
+    pub fn rule(&self, pattern: &str) -> Option<PatternRule> {
+        let rules = self.rules.read().unwrap();
+        if rules.is_empty() {
+            return Some(PatternRule::default());
+        }
+        rules.get(pattern).cloned()
+    }
+
+    pub fn exposure(&self, pattern: &str) -> PatternExposure {
+        self.exposure.lock().unwrap().get(pattern).copied().unwrap_or_default()
+    }
+
+    /// Whether a new position of `pattern` on `venues` may open; `confidence` is checked
+    /// when the signal carries one
+    pub fn admit(&self, pattern: &str, confidence: Option<f64>, venues: &[&str]) -> Result<PatternRule, RiskRejection> {
+        let Some(rule) = self.rule(pattern) else {
+            return Err(RiskRejection::PatternDisabled { pattern: pattern.to_string() });
+        };
+        if let Some(confidence) = confidence.filter(|c| *c < rule.min_confidence) {
+            return Err(RiskRejection::LowConfidence {
+                pattern: pattern.to_string(),
+                confidence,
+                min: rule.min_confidence,
+            });
+        }
+        if !rule.venues.is_empty() {
+            if let Some(venue) = venues.iter().find(|v| !rule.venues.iter().any(|a| a.eq_ignore_ascii_case(v))) {
+                return Err(RiskRejection::VenueNotAllowed { pattern: pattern.to_string(), venue: venue.to_string() });
+            }
+        }
+        let exposure = self.exposure(pattern);
+        if rule.max_concurrent > 0 && exposure.positions >= rule.max_concurrent {
+            return Err(RiskRejection::ConcurrencyLimit { pattern: pattern.to_string(), open: exposure.positions });
+        }
+        if rule.max_capital > 0.0 && exposure.capital >= rule.max_capital {
+            return Err(RiskRejection::BudgetExhausted {
+                pattern: pattern.to_string(),
+                budget: rule.max_capital,
+                deployed: exposure.capital,
+            });
+        }
+        Ok(rule)
+    }
+
+    /// Dollars the pattern may still open (None = uncapped)
+    pub fn available(&self, pattern: &str) -> Option<f64> {
+        let rule = self.rule(pattern)?;
+        (rule.max_capital > 0.0).then(|| (rule.max_capital - self.exposure(pattern).capital).max(0.0))
+    }
+
+    /// `admit`, then cap `wanted` contracts at `cost_per_contract` dollars to the capital left
+    pub fn cap_contracts(
+        &self,
+        pattern: &str,
+        confidence: Option<f64>,
+        venues: &[&str],
+        cost_per_contract: f64,
+        wanted: i64,
+    ) -> Result<i64, RiskRejection> {
+        let rule = self.admit(pattern, confidence, venues)?;
+        match self.available(pattern) {
+            Some(left) if cost_per_contract > 0.0 => {
+                let fits = (left / cost_per_contract).floor() as i64;
+                if fits < 1 {
+                    return Err(RiskRejection::BudgetExhausted {
+                        pattern: pattern.to_string(),
+                        budget: rule.max_capital,
+                        deployed: self.exposure(pattern).capital,
+                    });
+                }
+                Ok(wanted.min(fits))
+            }
+            _ => Ok(wanted),
+        }
+    }
+
+    /// Count a position opened with `dollars` of capital (until the next sync)
+    pub fn open(&self, pattern: &str, dollars: f64) {
+        let mut exposure = self.exposure.lock().unwrap();
+        let entry = exposure.entry(pattern.to_string()).or_default();
+        entry.positions += 1;
+        entry.capital += dollars;
+    }
+
+    /// Release a position counted with `open` (replays close positions without a tracker)
+    pub fn close(&self, pattern: &str, dollars: f64) {
+        let mut exposure = self.exposure.lock().unwrap();
+        if let Some(entry) = exposure.get_mut(pattern) {
+            entry.positions = entry.positions.saturating_sub(1);
+            entry.capital = (entry.capital - dollars).max(0.0);
+        }
+    }
+
+    /// Recount open positions and capital from the tracker
+    pub fn sync(&self, tracker: &PositionTracker) {
+        let exposure = open_exposure(tracker);
+        for (pattern, e) in &exposure {
+            debug!("[PATTERN]   {} positions={} capital=${:.2}", pattern, e.positions, e.capital);
+        }
+        *self.exposure.lock().unwrap() = exposure;
+    }
+}
+
+/// Recount exposure now and then every minute, so settled positions free their slots
+pub async fn run_pattern_sync_loop(policy: SharedPatternPolicy, tracker: Arc<tokio::sync::RwLock<PositionTracker>>) {
+    loop {
+        policy.sync(&*tracker.read().await);
+        tokio::time::sleep(SYNC_INTERVAL).await;
+    }
+}
+
+/// Keep rules in step with `[patterns]` reloads
+pub fn watch_config(policy: SharedPatternPolicy, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("patterns") => policy.apply_config(&change.config.patterns),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[PATTERN] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position_tracker::FillRecord;
+
+    fn section(rules: &[(&str, PatternRule)]) -> PatternsSection {
+        PatternsSection {
+            enabled: rules.iter().map(|(id, rule)| (id.to_string(), rule.clone())).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_no_rules_admits_everything() {
+        let policy = PatternPolicy::new(&PatternsSection::default());
+        policy.open("73", 10_000.0);
+        assert!(policy.admit("73", Some(0.01), &["pinnacle"]).is_ok());
+        assert_eq!(policy.available("PolyOnly"), None);
+        assert_eq!(policy.cap_contracts("PolyOnly", None, &["polymarket"], 0.95, 40).unwrap(), 40);
+    }
+
+    #[test]
+    fn test_rules_gate_pattern_confidence_venue_and_limits() {
+        let policy = PatternPolicy::new(&section(&[
+            ("73", PatternRule { min_confidence: 0.6, venues: vec!["Pinnacle".to_string()], ..Default::default() }),
+            ("PolyYesKalshiNo", PatternRule { max_concurrent: 2, max_capital: 50.0, ..Default::default() }),
+        ]));
+
+        assert!(matches!(policy.admit("51", Some(0.9), &[]), Err(RiskRejection::PatternDisabled { .. })));
+        assert!(matches!(policy.admit("73", Some(0.5), &["pinnacle"]), Err(RiskRejection::LowConfidence { .. })));
+        assert!(matches!(policy.admit("73", Some(0.7), &["bet365"]), Err(RiskRejection::VenueNotAllowed { .. })));
+        assert!(policy.admit("73", Some(0.7), &["pinnacle"]).is_ok());
+
+        // $50 at 95¢ buys 52 of the 100 wanted; $20 left after a $30 position buys 21
+        let venues = arb_venues(ArbType::PolyYesKalshiNo);
+        assert_eq!(policy.cap_contracts("PolyYesKalshiNo", None, venues, 0.95, 100).unwrap(), 52);
+        policy.open("PolyYesKalshiNo", 30.0);
+        assert_eq!(policy.cap_contracts("PolyYesKalshiNo", None, venues, 0.95, 100).unwrap(), 21);
+        policy.open("PolyYesKalshiNo", 10.0);
+        assert!(matches!(
+            policy.admit("PolyYesKalshiNo", None, venues),
+            Err(RiskRejection::ConcurrencyLimit { open: 2, .. })
+        ));
+
+        // Reload drops the pattern entirely
+        policy.apply_config(&section(&[("73", PatternRule::default())]));
+        assert!(matches!(policy.admit("PolyYesKalshiNo", None, venues), Err(RiskRejection::PatternDisabled { .. })));
+    }
+
+    #[test]
+    fn test_sync_counts_open_positions_per_pattern() {
+        let mut tracker = PositionTracker::new();
+        for (market, pattern) in [("A", "PolyOnly"), ("B", "PolyOnly"), ("C", "KalshiOnly")] {
+            tracker.record_fill(&FillRecord::new(market, market, "polymarket", "yes", 10.0, 0.45, 0.0, "o1").with_pattern(pattern));
+            tracker.record_fill(&FillRecord::new(market, market, "polymarket", "no", 10.0, 0.50, 0.0, "o2").with_pattern(pattern));
+        }
+
+        let policy = PatternPolicy::new(&section(&[("PolyOnly", PatternRule { max_concurrent: 2, ..Default::default() })]));
+        policy.sync(&tracker);
+        assert_eq!(policy.exposure("PolyOnly"), PatternExposure { positions: 2, capital: 19.0 });
+        assert_eq!(policy.exposure("KalshiOnly").positions, 1);
+        assert!(matches!(policy.admit("PolyOnly", None, &["polymarket"]), Err(RiskRejection::ConcurrencyLimit { .. })));
+    }
+}
@@ -0,0 +1,431 @@
+// src/pattern_verifier.rs
+// Pattern verification job - verified ROI and alpha half-life per component from archived signals replayed against archived ticks
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::backtest_results::SharedBacktestResults;
+use crate::backtester_config::{PatternVerification, VerificationStatus};
+use crate::clock;
+use crate::feature_flags::{self, FlagTarget, SharedFeatureFlags};
+use crate::provider_registry::ProviderId;
+use crate::tick_sim_backtester::{AlphaDecayEngine, RetirementProjection};
+use crate::tick_store::{self, ArchiveQuery, SignalRow, TickRow};
+use crate::types::TimestampNs;
+
+/// How often the archive is replayed
+const VERIFY_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+const NS_PER_WEEK: u64 = 7 * 86_400 * 1_000_000_000;
+/// Weeks of signals replayed (enough to see alpha decay)
+const LOOKBACK_WEEKS: u64 = 8;
+/// Ticks before a signal searched for its entry prices
+const ENTRY_LOOKBACK_NS: u64 = 60 * 1_000_000_000;
+/// Replayed signals needed before a component can verify or fail
+const MIN_SAMPLES: usize = 30;
+/// Signals a week needs to count towards the half-life fit
+const MIN_WEEK_SAMPLES: usize = 5;
+/// Verified ROI at this multiple of target reports `Exceeded`
+const EXCEEDED_MULTIPLE: f64 = 2.0;
+
+/// One archived signal traded at the slow market's touch and held over its expected convergence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayedSignal {
+    pub component_id: u16,
+    pub timestamp_ns: TimestampNs,
+    /// Realized move of the slow market toward the fast one, as % of the cost to enter
+    pub roi_percent: f64,
+}
+
+/// YES price per (market, provider), oldest first
+struct PriceSeries(HashMap<(String, ProviderId), Vec<(TimestampNs, u16)>>);
+
+impl PriceSeries {
+    fn new(ticks: &[TickRow]) -> Self {
+        let mut series: HashMap<(String, ProviderId), Vec<(TimestampNs, u16)>> = HashMap::new();
+        for tick in ticks.iter().filter(|t| t.yes_price > 0) {
+            series.entry((tick.market_id.clone(), tick.platform)).or_default().push((tick.timestamp_ns, tick.yes_price));
+        }
+        for points in series.values_mut() {
+            points.sort_by_key(|(ts, _)| *ts);
+        }
+        Self(series)
+    }
+
+    /// Last price at or before `ts`
+    fn at(&self, market: &str, provider: ProviderId, ts: TimestampNs) -> Option<u16> {
+        let points = self.0.get(&(market.to_string(), provider))?;
+        let idx = points.partition_point(|(t, _)| *t <= ts);
+        idx.checked_sub(1).map(|i| points[i].1)
+    }
+}
+
+/// Replay signals against the tick history: buy the slow market when it sits below the fast one
+/// (sell it otherwise) at the signal, exit after the expected convergence time
+pub fn replay(signals: &[SignalRow], ticks: &[TickRow]) -> Vec<ReplayedSignal> {
+    let prices = PriceSeries::new(ticks);
+    signals.iter()
+        .filter_map(|signal| {
+            let component_id = signal.pattern_id?;
+            let ts = signal.timestamp_ns;
+            let fast = prices.at(&signal.market_id, signal.platform.into(), ts)? as f64;
+            let slow = ProviderId::from(signal.slow_platform);
+            let entry = prices.at(&signal.slow_market_id, slow, ts)? as f64;
+            let exit = prices.at(&signal.slow_market_id, slow, ts + signal.expected_convergence_ns)? as f64;
+            if fast == entry {
+                return None;
+            }
+            let (direction, cost) = if fast > entry { (1.0, entry) } else { (-1.0, 100.0 - entry) };
+            Some(ReplayedSignal { component_id, timestamp_ns: ts, roi_percent: (exit - entry) * direction / cost * 100.0 })
+        })
+        .collect()
+}
+
+/// Log-linear fit of weekly mean ROI against signal age: `ln roi = mean_ln_roi + decay_per_week * (age - mean_age_weeks)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecayFit {
+    pub mean_age_weeks: f64,
+    pub mean_ln_roi: f64,
+    /// Positive when older weeks earned more, i.e. ROI decays with calendar time
+    pub decay_per_week: f64,
+    /// Standard error of `decay_per_week` (zero with a perfect fit or only three weeks)
+    pub decay_std_err: f64,
+    pub weeks: usize,
+}
+
+impl DecayFit {
+    /// Fitted ROI % `weeks_ahead` from now (negative looks back)
+    pub fn roi_at(&self, weeks_ahead: f64) -> f64 {
+        self.roi_at_with_decay(weeks_ahead, self.decay_per_week)
+    }
+
+    /// As `roi_at`, pivoting the fit around its centroid to another decay rate
+    pub fn roi_at_with_decay(&self, weeks_ahead: f64, decay_per_week: f64) -> f64 {
+        (self.mean_ln_roi - decay_per_week * (weeks_ahead + self.mean_age_weeks)).exp()
+    }
+
+    pub fn half_life_weeks(&self) -> Option<f64> {
+        (self.decay_per_week > 0.0).then(|| std::f64::consts::LN_2 / self.decay_per_week)
+    }
+}
+
+/// Fit weekly mean ROI of `(timestamp, roi %)` outcomes. None with fewer than three
+/// profitable weeks of `MIN_WEEK_SAMPLES` outcomes each.
+pub fn weekly_decay_fit(outcomes: &[(TimestampNs, f64)], now_ns: TimestampNs) -> Option<DecayFit> {
+    let mut weeks: BTreeMap<u64, Vec<f64>> = BTreeMap::new();
+    for &(ts, roi) in outcomes {
+        weeks.entry(now_ns.saturating_sub(ts) / NS_PER_WEEK).or_default().push(roi);
+    }
+    let points: Vec<(f64, f64)> = weeks.into_iter()
+        .filter(|(_, rois)| rois.len() >= MIN_WEEK_SAMPLES)
+        .map(|(age, rois)| (age as f64, rois.iter().sum::<f64>() / rois.len() as f64))
+        .filter(|(_, mean)| *mean > 0.0)
+        .map(|(age, mean)| (age, mean.ln()))
+        .collect();
+    if points.len() < 3 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let slope = sxy / sxx;
+    let ssr: f64 = points.iter().map(|p| (p.1 - mean_y - slope * (p.0 - mean_x)).powi(2)).sum();
+    Some(DecayFit {
+        mean_age_weeks: mean_x,
+        mean_ln_roi: mean_y,
+        decay_per_week: slope,
+        decay_std_err: (ssr / (n - 2.0) / sxx).sqrt(),
+        weeks: points.len(),
+    })
+}
+
+/// Half-life (weeks) of a component's weekly mean ROI, from a log-linear fit over signal age.
+/// None with fewer than three profitable weeks or when ROI isn't decaying.
+pub fn alpha_half_life_weeks(samples: &[ReplayedSignal], now_ns: TimestampNs) -> Option<f64> {
+    let outcomes: Vec<(TimestampNs, f64)> = samples.iter().map(|s| (s.timestamp_ns, s.roi_percent)).collect();
+    weekly_decay_fit(&outcomes, now_ns)?.half_life_weeks()
+}
+
+/// Recomputes `PatternVerification`s from the archive and freezes components whose verified ROI
+/// falls below target by overriding their feature flag off. Only freezes it made itself are
+/// lifted when ROI recovers; operator overrides are left alone.
+pub struct PatternVerifier {
+    verifications: RwLock<Vec<PatternVerification>>,
+    flags: Option<SharedFeatureFlags>,
+    /// Components this verifier froze
+    frozen: Mutex<HashSet<u16>>,
+    /// Last replay's outcomes, for retirement projections
+    decay: Mutex<AlphaDecayEngine>,
+    /// Each replay's verifications are published here (optional)
+    results: Option<SharedBacktestResults>,
+}
+
+pub type SharedPatternVerifier = Arc<PatternVerifier>;
+
+impl PatternVerifier {
+    pub fn new(verifications: Vec<PatternVerification>) -> Self {
+        Self {
+            verifications: RwLock::new(verifications),
+            flags: None,
+            frozen: Mutex::new(HashSet::new()),
+            decay: Mutex::new(AlphaDecayEngine::new()),
+            results: None,
+        }
+    }
+
+    /// Freeze failing components through feature-flag overrides
+    pub fn with_feature_flags(mut self, flags: SharedFeatureFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Publish each replay's verifications to the results repository the dashboard reads
+    pub fn with_results(mut self, results: SharedBacktestResults) -> Self {
+        self.results = Some(results);
+        self
+    }
+
+    pub fn snapshot(&self) -> Vec<PatternVerification> {
+        self.verifications.read().unwrap().clone()
+    }
+
+    pub fn is_frozen(&self, component_id: u16) -> bool {
+        self.frozen.lock().unwrap().contains(&component_id)
+    }
+
+    /// Projected retirement of each component whose archived edge can be fitted
+    pub fn retirement_projections(&self, now_ns: TimestampNs) -> Vec<RetirementProjection> {
+        let decay = self.decay.lock().unwrap();
+        self.verifications.read().unwrap().iter()
+            .filter_map(|v| decay.project_retirement(v.component_id, now_ns))
+            .collect()
+    }
+
+    /// Update every verification from replayed signals
+    pub fn apply(&self, replayed: &[ReplayedSignal], now_ns: TimestampNs) {
+        let mut by_component: HashMap<u16, Vec<ReplayedSignal>> = HashMap::new();
+        for r in replayed {
+            by_component.entry(r.component_id).or_default().push(*r);
+        }
+
+        let mut verifications = self.verifications.write().unwrap();
+        let mut decay = self.decay.lock().unwrap();
+        for v in verifications.iter_mut() {
+            let samples = by_component.remove(&v.component_id).unwrap_or_default();
+            decay.load_outcomes(v.component_id, samples.iter().map(|s| (s.timestamp_ns, s.roi_percent)).collect());
+            let roi = if samples.is_empty() {
+                0.0
+            } else {
+                samples.iter().map(|s| s.roi_percent).sum::<f64>() / samples.len() as f64
+            };
+            let status = match samples.len() {
+                0 => VerificationStatus::Pending,
+                n if n < MIN_SAMPLES => VerificationStatus::InProgress,
+                _ if roi >= v.roi_target_percent * EXCEEDED_MULTIPLE => VerificationStatus::Exceeded,
+                _ if roi >= v.roi_target_percent => VerificationStatus::Verified,
+                _ => VerificationStatus::Failed,
+            };
+            v.update_results(roi, status);
+            v.verified_half_life_weeks = alpha_half_life_weeks(&samples, now_ns);
+            v.samples = samples.len();
+            info!("[VERIFY] #{} {}: roi={:.2}% (target {:.2}%) samples={} half-life={} -> {:?}",
+                  v.component_id, v.component_name, roi, v.roi_target_percent, v.samples,
+                  v.verified_half_life_weeks.map(|h| format!("{:.1}w", h)).unwrap_or_else(|| "-".to_string()),
+                  status);
+            self.set_frozen(v.component_id, status == VerificationStatus::Failed);
+        }
+        if let Some(results) = &self.results {
+            results.record_verifications(&verifications);
+        }
+    }
+
+    fn set_frozen(&self, component_id: u16, freeze: bool) {
+        let mut frozen = self.frozen.lock().unwrap();
+        if freeze == frozen.contains(&component_id) {
+            return;
+        }
+        if freeze {
+            frozen.insert(component_id);
+            warn!("[VERIFY] Freezing #{}: verified ROI below target", component_id);
+        } else {
+            frozen.remove(&component_id);
+            info!("[VERIFY] Unfreezing #{}: verified ROI back on target", component_id);
+        }
+        match (&self.flags, feature_flags::component(component_id)) {
+            (Some(flags), Some(_)) => {
+                flags.set_override(FlagTarget::Component(component_id), if freeze { Some(false) } else { None });
+            }
+            (Some(_), None) => warn!("[VERIFY] #{} is not flag-gated; freeze is advisory", component_id),
+            (None, _) => {}
+        }
+    }
+
+    /// Replay the trailing `LOOKBACK_WEEKS` of the archive in `dir` and apply the results
+    pub fn verify_archive(&self, dir: &Path, now_ns: TimestampNs) -> Result<()> {
+        let components: HashSet<u16> = self.verifications.read().unwrap().iter().map(|v| v.component_id).collect();
+        let from_ns = now_ns.saturating_sub(LOOKBACK_WEEKS * NS_PER_WEEK);
+        let signals: Vec<SignalRow> = tick_store::scan::<SignalRow>(dir, &ArchiveQuery::between(from_ns, now_ns))?
+            .rows
+            .into_iter()
+            .filter(|s| s.pattern_id.is_some_and(|id| components.contains(&id)))
+            .collect();
+
+        let mut query = ArchiveQuery::between(from_ns.saturating_sub(ENTRY_LOOKBACK_NS), now_ns);
+        let markets: HashSet<&str> = signals.iter()
+            .flat_map(|s| [s.market_id.as_str(), s.slow_market_id.as_str()])
+            .collect();
+        for market in markets {
+            query = query.with_market(market);
+        }
+        let ticks = if signals.is_empty() { Vec::new() } else { tick_store::scan::<TickRow>(dir, &query)?.rows };
+
+        let replayed = replay(&signals, &ticks);
+        info!("[VERIFY] Replayed {} of {} archived signals against {} ticks", replayed.len(), signals.len(), ticks.len());
+        self.apply(&replayed, now_ns);
+        Ok(())
+    }
+}
+
+/// Re-verify from the archive now and then every few hours
+pub async fn run_verification_loop(verifier: SharedPatternVerifier, dir: PathBuf) {
+    let clock = clock::system();
+    loop {
+        let now_ns = clock.wall_ns().0;
+        let (job_verifier, job_dir) = (verifier.clone(), dir.clone());
+        match tokio::task::spawn_blocking(move || job_verifier.verify_archive(&job_dir, now_ns)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("[VERIFY] Archive replay failed: {:#}", e),
+            Err(e) => warn!("[VERIFY] Archive replay panicked: {}", e),
+        }
+        tokio::time::sleep(VERIFY_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FeaturesSection;
+    use crate::feature_flags::FeatureFlags;
+    use crate::types::{MarketType, Platform};
+
+    const NOW: TimestampNs = 100 * NS_PER_WEEK;
+
+    fn tick(market: &str, platform: Platform, ts: TimestampNs, yes_price: u16) -> TickRow {
+        TickRow {
+            timestamp_ns: ts,
+            provider_timestamp_ns: None,
+            market_id: market.to_string(),
+            platform: platform.into(),
+            market_type: MarketType::Moneyline,
+            yes_price,
+            no_price: 100 - yes_price,
+            yes_size: 1000,
+            no_size: 1000,
+            features: None,
+        }
+    }
+
+    fn signal(pattern: u16, ts: TimestampNs) -> SignalRow {
+        SignalRow {
+            timestamp_ns: ts,
+            market_id: "1".to_string(),
+            platform: Platform::Kalshi,
+            slow_market_id: "2".to_string(),
+            slow_platform: Platform::DraftKings,
+            market_type: MarketType::Moneyline,
+            disparity_cents: 10,
+            expected_convergence_ns: 1_000_000_000,
+            pattern_id: Some(pattern),
+            confidence: 0.8,
+            strategy_id: None,
+        }
+    }
+
+    fn samples(component_id: u16, roi_by_age_weeks: &[(u64, f64)], per_week: usize) -> Vec<ReplayedSignal> {
+        roi_by_age_weeks.iter()
+            .flat_map(|&(age, roi)| (0..per_week).map(move |i| ReplayedSignal {
+                component_id,
+                timestamp_ns: NOW - age * NS_PER_WEEK - i as u64,
+                roi_percent: roi,
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_trades_the_slow_market_toward_the_fast_one() {
+        let ticks = [
+            tick("1", Platform::Kalshi, 90, 60),
+            tick("2", Platform::DraftKings, 95, 50),
+            tick("2", Platform::DraftKings, 100 + 500_000_000, 55),
+            tick("1", Platform::Kalshi, 100 + 550_000_000, 50),
+            tick("2", Platform::DraftKings, 100 + 1_500_000_000, 60),
+        ];
+        // Fast at 60¢, slow 50¢: buy slow at 50¢, 55¢ one second later = +5¢ on 50¢
+        let replayed = replay(&[signal(74, 100)], &ticks);
+        assert_eq!(replayed.len(), 1);
+        assert!((replayed[0].roi_percent - 10.0).abs() < 1e-9);
+
+        // Fast drops to 50¢ under slow 55¢: sell (buy NO at 45¢) and lose as slow rises to 60¢
+        let replayed = replay(&[signal(74, 100 + 600_000_000)], &ticks);
+        assert!((replayed[0].roi_percent - -5.0 / 45.0 * 100.0).abs() < 1e-9);
+
+        // No tick for the fast market before the signal
+        assert!(replay(&[signal(74, 50)], &ticks).is_empty());
+    }
+
+    #[test]
+    fn test_half_life_from_weekly_decay() {
+        // ROI halves every two weeks: 4% four weeks ago, 2% two weeks ago, 1% this week
+        let decaying = samples(75, &[(4, 4.0), (2, 2.0), (0, 1.0)], MIN_WEEK_SAMPLES);
+        assert!((alpha_half_life_weeks(&decaying, NOW).unwrap() - 2.0).abs() < 1e-9);
+        let outcomes: Vec<(TimestampNs, f64)> = decaying.iter().map(|s| (s.timestamp_ns, s.roi_percent)).collect();
+        let fit = weekly_decay_fit(&outcomes, NOW).unwrap();
+        assert!((fit.roi_at(0.0) - 1.0).abs() < 1e-9);
+        assert!((fit.roi_at(2.0) - 0.5).abs() < 1e-9);
+        assert!(fit.decay_std_err < 1e-9);
+
+        let flat = samples(75, &[(4, 1.0), (2, 1.0), (0, 1.0)], MIN_WEEK_SAMPLES);
+        assert_eq!(alpha_half_life_weeks(&flat, NOW), None);
+        let thin = samples(75, &[(4, 4.0), (2, 2.0), (0, 1.0)], MIN_WEEK_SAMPLES - 1);
+        assert_eq!(alpha_half_life_weeks(&thin, NOW), None);
+    }
+
+    #[test]
+    fn test_failing_components_are_frozen_and_thawed() {
+        let flags = Arc::new(FeatureFlags::new(&FeaturesSection::default()));
+        let verifier = PatternVerifier::new(vec![
+            PatternVerification::new(75, "Velocity Conv", 2.2, 8.0, "Model Complexity"),
+            PatternVerification::new(51, "HT Inference", 0.5, 6.0, "Provider Latency"),
+        ])
+        .with_feature_flags(flags.clone());
+
+        let mut replayed = samples(75, &[(0, 1.0)], MIN_SAMPLES);
+        replayed.extend(samples(51, &[(0, 3.0)], MIN_SAMPLES - 1));
+        verifier.apply(&replayed, NOW);
+        let snapshot = verifier.snapshot();
+        assert_eq!(snapshot[0].verification_status, VerificationStatus::Failed);
+        assert_eq!(snapshot[0].samples, MIN_SAMPLES);
+        assert_eq!(snapshot[1].verification_status, VerificationStatus::InProgress);
+        assert!(verifier.is_frozen(75));
+        assert!(!flags.component_enabled(75));
+        // One week of outcomes can't be fitted for a retirement date
+        assert!(verifier.retirement_projections(NOW).is_empty());
+
+        verifier.apply(&samples(75, &[(0, 5.0)], MIN_SAMPLES), NOW);
+        assert_eq!(verifier.snapshot()[0].verification_status, VerificationStatus::Exceeded);
+        assert_eq!(verifier.snapshot()[1].verification_status, VerificationStatus::Pending);
+        assert!(!verifier.is_frozen(75));
+        assert!(flags.component_enabled(75));
+
+        verifier.apply(&samples(75, &[(4, 8.0), (2, 6.0), (0, 5.0)], MIN_SAMPLES), NOW);
+        let projections = verifier.retirement_projections(NOW);
+        assert_eq!(projections.len(), 1);
+        assert_eq!(projections[0].pattern_id, 75);
+        assert!(projections[0].weeks_to_retirement.unwrap() > 0.0);
+    }
+}
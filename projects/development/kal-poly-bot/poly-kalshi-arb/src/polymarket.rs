@@ -9,12 +9,13 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::{interval, Instant};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
-use tracing::{error, info, warn};
+use tracing::{error, info, warn, Level};
 
-use crate::config::{POLYMARKET_WS_URL, POLY_PING_INTERVAL_SECS, GAMMA_API_BASE};
-use crate::execution::NanoClock;
+use crate::config::{polymarket_ws_url, POLY_PING_INTERVAL_SECS, GAMMA_API_BASE, POLY_DATA_API_BASE};
+use crate::clock::SharedClock;
+use crate::feed_schema::FeedSchemas;
 use crate::types::{
-    GlobalState, FastExecutionRequest, ArbType, PriceCents, SizeCents,
+    GlobalState, FastExecutionRequest, ArbType, MarketId, Price, Size, PriceCents, SizeCents, Platform,
     parse_price, fxhash_str,
 };
 
@@ -82,7 +83,7 @@ impl GammaClient {
         // Try with next day (Polymarket may use local time)
         if let Some(next_day_slug) = increment_date_in_slug(slug) {
             if let Some(tokens) = self.try_lookup_slug(&next_day_slug).await? {
-                info!("  📅 Found with next-day slug: {}", next_day_slug);
+                info!("[POLY] Found with next-day slug: {}", next_day_slug);
                 return Ok(Some(tokens));
             }
         }
@@ -124,6 +125,78 @@ impl GammaClient {
             Ok(None)
         }
     }
+
+    /// Look up the Gamma market holding a CLOB token (includes closed markets)
+    pub async fn market_by_token(&self, token_id: &str) -> Result<Option<crate::types::GammaMarket>> {
+        let url = format!("{}/markets?clob_token_ids={}", GAMMA_API_BASE, token_id);
+        let resp = self.http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Gamma token lookup failed: {}", resp.status());
+        }
+        let markets: Vec<crate::types::GammaMarket> = resp.json().await?;
+        Ok(markets.into_iter().next())
+    }
+
+    /// Page through active, open markets (catalog sync for cross-venue matching)
+    pub async fn list_active_markets(&self, offset: usize, limit: usize) -> Result<Vec<crate::types::GammaMarket>> {
+        let url = format!(
+            "{}/markets?active=true&closed=false&limit={}&offset={}",
+            GAMMA_API_BASE, limit, offset
+        );
+        let resp = self.http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Gamma markets page failed: {}", resp.status());
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+// === Data API Client ===
+
+/// Position as reported by the Polymarket data API
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolyPosition {
+    /// CLOB token ID
+    pub asset: String,
+    #[serde(rename = "conditionId", default)]
+    pub condition_id: Option<String>,
+    pub size: f64,
+    #[serde(rename = "avgPrice", default)]
+    pub avg_price: Option<f64>,
+    #[serde(default)]
+    pub outcome: Option<String>,
+}
+
+/// Read-only client for the Polymarket data API
+pub struct PolyDataClient {
+    http: reqwest::Client,
+}
+
+impl PolyDataClient {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+
+    /// Open positions for a wallet (funder / proxy address)
+    pub async fn get_positions(&self, user: &str) -> Result<Vec<PolyPosition>> {
+        let url = format!("{}/positions?user={}&sizeThreshold=0.01", POLY_DATA_API_BASE, user);
+        let resp = self.http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Polymarket positions failed: {}", resp.status());
+        }
+        Ok(resp.json().await?)
+    }
+}
+
+impl Default for PolyDataClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -180,11 +253,14 @@ fn parse_size(s: &str) -> SizeCents {
         .unwrap_or(0)
 }
 
-/// WebSocket runner
+/// WebSocket runner; every message is schema-checked before it touches the books and
+/// arbs are stamped `detected_ns` on `clock`
 pub async fn run_ws(
+    schemas: &FeedSchemas,
     state: Arc<GlobalState>,
     exec_tx: mpsc::Sender<FastExecutionRequest>,
     threshold_cents: PriceCents,
+    clock: SharedClock,
 ) -> Result<()> {
     let tokens: Vec<String> = state.markets.iter()
         .take(state.market_count())
@@ -198,7 +274,7 @@ pub async fn run_ws(
         return Ok(());
     }
 
-    let (ws_stream, _) = connect_async(POLYMARKET_WS_URL)
+    let (ws_stream, _) = connect_async(polymarket_ws_url())
         .await
         .context("Failed to connect to Polymarket")?;
 
@@ -215,7 +291,6 @@ pub async fn run_ws(
     write.send(Message::Text(serde_json::to_string(&subscribe_msg)?)).await?;
     info!("[POLY] Subscribed to {} tokens", tokens.len());
 
-    let clock = NanoClock::new();
     let mut ping_interval = interval(Duration::from_secs(POLY_PING_INTERVAL_SECS));
     let mut last_message = Instant::now();
 
@@ -233,14 +308,22 @@ pub async fn run_ws(
                     Some(Ok(Message::Text(text))) => {
                         last_message = Instant::now();
 
+                        let value = match schemas.check(Platform::Polymarket, &text) {
+                            Ok(value) => value,
+                            Err(e) => {
+                                crate::hot_path!(Level::WARN, "[POLY] {}", e);
+                                continue;
+                            }
+                        };
+
                         // Try book snapshot first
-                        if let Ok(books) = serde_json::from_str::<Vec<BookSnapshot>>(&text) {
+                        if let Ok(books) = Vec::<BookSnapshot>::deserialize(&value) {
                             for book in &books {
                                 process_book(&state, book, &exec_tx, threshold_cents, &clock).await;
                             }
                         }
                         // Try price change event
-                        else if let Ok(event) = serde_json::from_str::<PriceChangeEvent>(&text) {
+                        else if let Ok(event) = PriceChangeEvent::deserialize(&value) {
                             if event.event_type.as_deref() == Some("price_change") {
                                 if let Some(changes) = &event.price_changes {
                                     for change in changes {
@@ -294,7 +377,7 @@ async fn process_book(
     book: &BookSnapshot,
     exec_tx: &mpsc::Sender<FastExecutionRequest>,
     threshold_cents: PriceCents,
-    clock: &NanoClock,
+    clock: &SharedClock,
 ) {
     let token_hash = fxhash_str(&book.asset_id);
 
@@ -310,7 +393,7 @@ async fn process_book(
 
     // Check if YES token
     if let Some(&market_id) = state.poly_yes_to_id.get(&token_hash) {
-        let market = &state.markets[market_id as usize];
+        let market = &state.markets[market_id.index()];
         market.poly.update_yes(best_ask, ask_size);
 
         // Check arbs
@@ -321,7 +404,7 @@ async fn process_book(
     }
     // Check if NO token
     else if let Some(&market_id) = state.poly_no_to_id.get(&token_hash) {
-        let market = &state.markets[market_id as usize];
+        let market = &state.markets[market_id.index()];
         market.poly.update_no(best_ask, ask_size);
 
         // Check arbs
@@ -339,7 +422,7 @@ async fn process_price_change(
     change: &PriceChangeItem,
     exec_tx: &mpsc::Sender<FastExecutionRequest>,
     threshold_cents: PriceCents,
-    clock: &NanoClock,
+    clock: &SharedClock,
 ) {
     // Only process ASK side updates
     if !matches!(change.side.as_deref(), Some("ASK" | "ask")) {
@@ -354,7 +437,7 @@ async fn process_price_change(
 
     // Check YES token
     if let Some(&market_id) = state.poly_yes_to_id.get(&token_hash) {
-        let market = &state.markets[market_id as usize];
+        let market = &state.markets[market_id.index()];
         let (current_yes, _, current_yes_size, _) = market.poly.load();
 
         // Only update if new price is better (lower)
@@ -371,7 +454,7 @@ async fn process_price_change(
     }
     // Check NO token
     else if let Some(&market_id) = state.poly_no_to_id.get(&token_hash) {
-        let market = &state.markets[market_id as usize];
+        let market = &state.markets[market_id.index()];
         let (_, current_no, _, current_no_size) = market.poly.load();
 
         if price < current_no || current_no == 0 {
@@ -388,11 +471,11 @@ async fn process_price_change(
 /// Send arb request to execution engine
 #[inline]
 async fn send_arb_request(
-    market_id: u16,
+    market_id: MarketId,
     market: &crate::types::AtomicMarketState,
     arb_mask: u8,
     exec_tx: &mpsc::Sender<FastExecutionRequest>,
-    clock: &NanoClock,
+    clock: &SharedClock,
 ) {
     let (k_yes, k_no, k_yes_size, k_no_size) = market.kalshi.load();
     let (p_yes, p_no, p_yes_size, p_no_size) = market.poly.load();
@@ -416,12 +499,12 @@ async fn send_arb_request(
 
     let req = FastExecutionRequest {
         market_id,
-        yes_price,
-        no_price,
-        yes_size,
-        no_size,
+        yes_price: Price(yes_price),
+        no_price: Price(no_price),
+        yes_size: Size(yes_size),
+        no_size: Size(no_size),
         arb_type,
-        detected_ns: clock.now_ns(),
+        detected_ns: clock.mono_ns(),
     };
 
     // send! ~~ 
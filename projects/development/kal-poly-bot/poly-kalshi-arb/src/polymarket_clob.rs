@@ -1,15 +1,20 @@
 // src/polymarket_clob.rs
 // Polymarket CLOB Client
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE;
+use ethers::abi::{self, Token};
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider};
 use ethers::signers::{LocalWallet, Signer};
-use ethers::types::H256;
+use ethers::types::{Address, Bytes, H256, TransactionRequest};
+use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::transaction::eip712::{Eip712, TypedData};
 use ethers::types::U256;
+use futures_util::{SinkExt, StreamExt};
 use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::{Deserialize, Serialize};
@@ -17,10 +22,25 @@ use serde_json::json;
 use sha2::Sha256;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+use crate::cache::{CacheNamespace, TieredCache};
+use crate::circuit_breaker::SharedBreaker;
+use crate::error::VenueApiError;
+use crate::config::{polymarket_user_ws_url, POLY_PING_INTERVAL_SECS};
+use crate::request_scheduler::{retry_after, RequestPriority, VenueScheduler};
+use crate::secrets::{Secret, SecretsProvider, POLY_FUNDER, POLY_PRIVATE_KEY};
+use crate::types::Platform;
 
 const USER_AGENT: &str = "py_clob_client";
 const MSG_TO_SIGN: &str = "This message attests that I control the given wallet";
 const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+/// Order state poll interval while a passive order rests
+const PASSIVE_POLL: Duration = Duration::from_millis(100);
 
 // ============================================================================
 // PRE-COMPUTED EIP712 CONSTANTS
@@ -28,14 +48,14 @@ const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
 
 type HmacSha256 = Hmac<Sha256>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ApiCreds {
     #[serde(rename = "apiKey")]
     pub api_key: String,
     #[serde(rename = "secret")]
-    pub api_secret: String,
+    pub api_secret: Secret,
     #[serde(rename = "passphrase")]
-    pub api_passphrase: String,
+    pub api_passphrase: Secret,
 }
 
 // ============================================================================
@@ -52,13 +72,13 @@ pub struct PreparedCreds {
 
 impl PreparedCreds {
     pub fn from_api_creds(creds: &ApiCreds) -> Result<Self> {
-        let decoded_secret = URL_SAFE.decode(&creds.api_secret)?;
+        let decoded_secret = Zeroizing::new(URL_SAFE.decode(creds.api_secret.expose())?);
         let hmac_template = HmacSha256::new_from_slice(&decoded_secret)
             .map_err(|e| anyhow!("Invalid HMAC key: {}", e))?;
 
         let api_key_header = HeaderValue::from_str(&creds.api_key)
             .map_err(|e| anyhow!("Invalid API key for header: {}", e))?;
-        let passphrase_header = HeaderValue::from_str(&creds.api_passphrase)
+        let passphrase_header = HeaderValue::from_str(creds.api_passphrase.expose())
             .map_err(|e| anyhow!("Invalid passphrase for header: {}", e))?;
 
         Ok(Self {
@@ -108,6 +128,14 @@ fn current_unix_ts() -> u64 {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
 }
 
+/// Signer wallet from a hex private key; the decoded bytes are wiped once the
+/// wallet holds them (its signing key zeroizes itself on drop)
+fn wallet_from_secret(private_key: &Secret, chain_id: u64) -> Result<LocalWallet> {
+    let hex_key = private_key.expose().trim().trim_start_matches("0x");
+    let bytes = Zeroizing::new(ethers::utils::hex::decode(hex_key).context("private key is not valid hex")?);
+    Ok(LocalWallet::from_bytes(&bytes)?.with_chain_id(chain_id))
+}
+
 fn clob_auth_digest(chain_id: u64, address_str: &str, timestamp: u64, nonce: u64) -> Result<H256> {
     let typed_json = json!({
         "types": {
@@ -131,8 +159,9 @@ fn clob_auth_digest(chain_id: u64, address_str: &str, timestamp: u64, nonce: u64
     Ok(typed.encode_eip712()?.into())
 }
 
+/// Order parameters for EIP712 construction
+/// `None` fields fall back to the CLOB defaults (0 fee, managed nonce, no expiry, public taker)
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct OrderArgs {
     pub token_id: String,
     pub price: f64,
@@ -325,15 +354,108 @@ fn order_typed_data(chain_id: u64, exchange: &str, data: &OrderData<'_>) -> Resu
 }
 
 fn get_exchange_address(chain_id: u64, neg_risk: bool) -> Result<String> {
+    Ok(get_contract_config(chain_id, neg_risk)?.exchange.into())
+}
+
+// ============================================================================
+// CONTRACT ADDRESSES
+// ============================================================================
+
+/// On-chain contracts involved in settling a CLOB order
+#[derive(Debug, Clone, Copy)]
+pub struct ContractConfig {
+    /// CTF exchange that matches and settles orders
+    pub exchange: &'static str,
+    /// Collateral token (USDC.e on Polygon)
+    pub collateral: &'static str,
+    /// Conditional tokens (ERC1155) contract
+    pub conditional_tokens: &'static str,
+    /// Neg-risk adapter (only set for neg_risk markets)
+    pub neg_risk_adapter: Option<&'static str>,
+}
+
+/// Get contract addresses for chain + market flavour
+pub fn get_contract_config(chain_id: u64, neg_risk: bool) -> Result<ContractConfig> {
     match (chain_id, neg_risk) {
-        (137, true) => Ok("0xC5d563A36AE78145C45a50134d48A1215220f80a".into()),
-        (137, false) => Ok("0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E".into()),
-        (80002, true) => Ok("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296".into()),
-        (80002, false) => Ok("0xdFE02Eb6733538f8Ea35D585af8DE5958AD99E40".into()),
+        (137, true) => Ok(ContractConfig {
+            exchange: "0xC5d563A36AE78145C45a50134d48A1215220f80a",
+            collateral: "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
+            conditional_tokens: "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045",
+            neg_risk_adapter: Some("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
+        }),
+        (137, false) => Ok(ContractConfig {
+            exchange: "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E",
+            collateral: "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174",
+            conditional_tokens: "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045",
+            neg_risk_adapter: None,
+        }),
+        (80002, true) => Ok(ContractConfig {
+            exchange: "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296",
+            collateral: "0x9c4e1703476e875070ee25b56a58b008cfb8fa78",
+            conditional_tokens: "0x69308FB512518e39F9b16112fA8d994F4e2Bf8bB",
+            neg_risk_adapter: Some("0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
+        }),
+        (80002, false) => Ok(ContractConfig {
+            exchange: "0xdFE02Eb6733538f8Ea35D585af8DE5958AD99E40",
+            collateral: "0x9c4e1703476e875070ee25b56a58b008cfb8fa78",
+            conditional_tokens: "0x69308FB512518e39F9b16112fA8d994F4e2Bf8bB",
+            neg_risk_adapter: None,
+        }),
         _ => Err(anyhow!("unsupported chain")),
     }
 }
 
+/// ABI-encode a contract call: 4-byte selector + encoded args
+fn encode_call(signature: &str, args: &[Token]) -> Bytes {
+    let mut data = ethers::utils::id(signature).to_vec();
+    data.extend(abi::encode(args));
+    data.into()
+}
+
+/// Decode a single uint256 return word
+fn decode_u256(ret: &[u8]) -> Result<U256> {
+    if ret.len() < 32 {
+        return Err(anyhow!("short return data ({} bytes)", ret.len()));
+    }
+    Ok(U256::from_big_endian(&ret[..32]))
+}
+
+// ============================================================================
+// NONCE MANAGEMENT
+// ============================================================================
+
+/// Exchange order nonce tracker
+///
+/// The CTF exchange only accepts orders whose nonce equals the maker's current
+/// on-chain nonce; calling `incrementNonce()` invalidates every resting order at
+/// once. We cache the nonce locally and resync from chain after a bump.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    current: AtomicU64,
+}
+
+impl NonceManager {
+    pub fn new(start: u64) -> Self {
+        Self { current: AtomicU64::new(start) }
+    }
+
+    /// Nonce to stamp on the next order
+    #[inline]
+    pub fn current(&self) -> u64 {
+        self.current.load(Ordering::Acquire)
+    }
+
+    /// Overwrite with a value read from chain
+    pub fn set(&self, nonce: u64) {
+        self.current.store(nonce, Ordering::Release);
+    }
+
+    /// Advance after an on-chain `incrementNonce()`, returns the new nonce
+    pub fn bump(&self) -> u64 {
+        self.current.fetch_add(1, Ordering::AcqRel) + 1
+    }
+}
+
 // ============================================================================
 // ORDER TYPES FOR FAK/FOK
 // ============================================================================
@@ -392,6 +514,50 @@ pub struct PolymarketOrderResponse {
     pub owner: Option<String>,
 }
 
+impl PolymarketOrderResponse {
+    /// Matched size as f64 (0 if unparseable)
+    pub fn filled_size(&self) -> f64 {
+        self.size_matched.parse().unwrap_or(0.0)
+    }
+
+    /// True once the order can no longer change (fully matched, cancelled, or unmatched)
+    pub fn is_terminal(&self) -> bool {
+        match self.status.to_ascii_uppercase().as_str() {
+            "MATCHED" | "CANCELED" | "CANCELLED" | "UNMATCHED" => true,
+            "LIVE" | "DELAYED" => {
+                let original: f64 = self.original_size.parse().unwrap_or(0.0);
+                original > 0.0 && self.filled_size() >= original
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Response from GET /balance-allowance (amounts in 6dp micro-USDC strings)
+#[derive(Debug, Clone, Deserialize)]
+pub struct BalanceAllowance {
+    pub balance: String,
+    #[serde(default)]
+    pub allowance: Option<String>,
+    #[serde(default)]
+    pub allowances: Option<HashMap<String, String>>,
+}
+
+impl BalanceAllowance {
+    /// Balance in dollars
+    pub fn balance_usdc(&self) -> f64 {
+        self.balance.parse::<f64>().unwrap_or(0.0) / 1_000_000.0
+    }
+
+    /// Smallest allowance across all reported spenders, in dollars
+    pub fn min_allowance_usdc(&self) -> Option<f64> {
+        self.allowance.iter()
+            .chain(self.allowances.iter().flat_map(|m| m.values()))
+            .map(|a| a.parse::<f64>().unwrap_or(0.0) / 1_000_000.0)
+            .reduce(f64::min)
+    }
+}
+
 // ============================================================================
 // ASYNC CLIENT
 // ============================================================================
@@ -405,11 +571,14 @@ pub struct PolymarketAsyncClient {
     funder: String,
     wallet_address_str: String,
     address_header: HeaderValue,
+    scheduler: Option<Arc<VenueScheduler>>,
+    /// Venue breaker; fails fast while the CLOB is erroring
+    breaker: Option<SharedBreaker<Platform>>,
 }
 
 impl PolymarketAsyncClient {
-    pub fn new(host: &str, chain_id: u64, private_key: &str, funder: &str) -> Result<Self> {
-        let wallet = private_key.parse::<LocalWallet>()?.with_chain_id(chain_id);
+    pub fn new(host: &str, chain_id: u64, private_key: &Secret, funder: &str) -> Result<Self> {
+        let wallet = wallet_from_secret(private_key, chain_id)?;
         let wallet_address_str = format!("{:?}", wallet.address());
         let address_header = HeaderValue::from_str(&wallet_address_str)
             .map_err(|e| anyhow!("Invalid wallet address for header: {}", e))?;
@@ -431,9 +600,51 @@ impl PolymarketAsyncClient {
             funder: funder.to_string(),
             wallet_address_str,
             address_header,
+            scheduler: None,
+            breaker: None,
         })
     }
 
+    /// Build from the POLY_PRIVATE_KEY / POLY_FUNDER secrets
+    pub async fn from_secrets(host: &str, chain_id: u64, secrets: &dyn SecretsProvider) -> Result<Self> {
+        let private_key = secrets.require(POLY_PRIVATE_KEY).await?;
+        let funder = secrets.require(POLY_FUNDER).await?;
+        Self::new(host, chain_id, &private_key, funder.expose())
+    }
+
+    /// Route CLOB requests through a shared venue scheduler
+    pub fn with_scheduler(mut self, scheduler: Arc<VenueScheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Wrap CLOB requests in a shared venue circuit breaker
+    pub fn with_breaker(mut self, breaker: SharedBreaker<Platform>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    /// Send through the scheduler lane and breaker (if any) and report the status back
+    async fn send_scheduled(&self, priority: RequestPriority, req: reqwest::RequestBuilder) -> Result<reqwest::Response, VenueApiError> {
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.acquire(priority).await;
+        }
+        let resp = match &self.breaker {
+            // Transport errors and 5xx count as failures
+            Some(breaker) => breaker
+                .call_with(Platform::Polymarket, req.send(), |r| {
+                    r.as_ref().map_or(true, |resp| resp.status().is_server_error())
+                })
+                .await
+                .map_err(|e| VenueApiError::breaker(Platform::Polymarket, e))?,
+            None => req.send().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))?,
+        };
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.report_status(resp.status(), retry_after(&resp));
+        }
+        Ok(resp)
+    }
+
     /// Build L1 headers for authentication (derive-api-key)
     /// wallet.sign_hash() is CPU-bound (~1ms), safe to call in async context
     fn build_l1_headers(&self, nonce: u64) -> Result<HeaderMap> {
@@ -463,7 +674,7 @@ impl PolymarketAsyncClient {
     }
 
     /// Build L2 headers for authenticated requests
-    fn build_l2_headers(&self, method: &str, path: &str, body: Option<&str>, creds: &PreparedCreds) -> Result<HeaderMap> {
+    fn build_l2_headers(&self, method: &str, path: &str, body: Option<&str>, creds: &PreparedCreds) -> Result<HeaderMap, VenueApiError> {
         let timestamp = current_unix_ts();
         let mut message = format!("{}{}{}", timestamp, method, path);
         if let Some(b) = body { message.push_str(b); }
@@ -472,8 +683,9 @@ impl PolymarketAsyncClient {
 
         let mut headers = HeaderMap::with_capacity(9);
         headers.insert("POLY_ADDRESS", self.address_header.clone());
-        headers.insert("POLY_SIGNATURE", HeaderValue::from_str(&sig_b64)?);
-        headers.insert("POLY_TIMESTAMP", HeaderValue::from_str(&timestamp.to_string())?);
+        let header = |v: &str| HeaderValue::from_str(v).map_err(|e| VenueApiError::signing(Platform::Polymarket, e));
+        headers.insert("POLY_SIGNATURE", header(&sig_b64)?);
+        headers.insert("POLY_TIMESTAMP", header(&timestamp.to_string())?);
         headers.insert("POLY_API_KEY", creds.api_key_header());
         headers.insert("POLY_PASSPHRASE", creds.passphrase_header());
         add_default_headers(&mut headers);
@@ -481,52 +693,104 @@ impl PolymarketAsyncClient {
     }
 
     /// Post order 
-    pub async fn post_order_async(&self, body: String, creds: &PreparedCreds) -> Result<reqwest::Response> {
+    pub async fn post_order_async(&self, body: String, creds: &PreparedCreds) -> Result<reqwest::Response, VenueApiError> {
         let path = "/order";
         let url = format!("{}{}", self.host, path);
         let headers = self.build_l2_headers("POST", path, Some(&body), creds)?;
 
-        let resp = self.http
+        let req = self.http
             .post(&url)
             .headers(headers)
-            .body(body)
-            .send()
-            .await?;
+            .body(body);
 
-        Ok(resp)
+        self.send_scheduled(RequestPriority::Order, req).await
     }
 
     /// Get order by ID 
-    pub async fn get_order_async(&self, order_id: &str, creds: &PreparedCreds) -> Result<PolymarketOrderResponse> {
+    pub async fn get_order_async(&self, order_id: &str, creds: &PreparedCreds) -> Result<PolymarketOrderResponse, VenueApiError> {
         let path = format!("/data/order/{}", order_id);
         let url = format!("{}{}", self.host, path);
         let headers = self.build_l2_headers("GET", &path, None, creds)?;
 
-        let resp = self.http
+        let req = self.http
             .get(&url)
+            .headers(headers);
+        let resp = self.send_scheduled(RequestPriority::MarketData, req).await?;
+
+        if !resp.status().is_success() {
+            return Err(VenueApiError::from_response(Platform::Polymarket, resp).await);
+        }
+
+        resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))
+    }
+
+    /// Poll an order until it leaves LIVE or the timeout elapses
+    /// Returns the last observed state either way
+    pub async fn poll_order_async(
+        &self,
+        order_id: &str,
+        creds: &PreparedCreds,
+        poll_every: Duration,
+        timeout: Duration,
+    ) -> Result<PolymarketOrderResponse, VenueApiError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let order = self.get_order_async(order_id, creds).await?;
+            if order.is_terminal() || tokio::time::Instant::now() + poll_every > deadline {
+                return Ok(order);
+            }
+            tokio::time::sleep(poll_every).await;
+        }
+    }
+
+    /// Cancel a resting order
+    pub async fn cancel_order_async(&self, order_id: &str, creds: &PreparedCreds) -> Result<(), VenueApiError> {
+        let path = "/order";
+        let url = format!("{}{}", self.host, path);
+        let body = json!({ "orderID": order_id }).to_string();
+        let headers = self.build_l2_headers("DELETE", path, Some(&body), creds)?;
+
+        let req = self.http
+            .delete(&url)
             .headers(headers)
-            .send()
-            .await?;
+            .body(body);
+        let resp = self.send_scheduled(RequestPriority::Cancel, req).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(anyhow!("get_order failed {}: {}", status, body));
+            return Err(VenueApiError::from_response(Platform::Polymarket, resp).await);
         }
+        Ok(())
+    }
 
-        Ok(resp.json().await?)
+    /// CLOB view of collateral balance + exchange allowance for the funder
+    /// Works for proxy wallets too (the CLOB resolves the proxy on its side)
+    pub async fn get_balance_allowance_async(&self, creds: &PreparedCreds, signature_type: i32) -> Result<BalanceAllowance, VenueApiError> {
+        // Query string is not part of the signed path
+        let path = "/balance-allowance";
+        let url = format!("{}{}?asset_type=COLLATERAL&signature_type={}", self.host, path, signature_type);
+        let headers = self.build_l2_headers("GET", path, None, creds)?;
+
+        let req = self.http
+            .get(&url)
+            .headers(headers);
+        let resp = self.send_scheduled(RequestPriority::MarketData, req).await?;
+
+        if !resp.status().is_success() {
+            return Err(VenueApiError::from_response(Platform::Polymarket, resp).await);
+        }
+
+        resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))
     }
 
     /// Check neg_risk for token - with caching
-    pub async fn check_neg_risk(&self, token_id: &str) -> Result<bool> {
+    pub async fn check_neg_risk(&self, token_id: &str) -> Result<bool, VenueApiError> {
         let url = format!("{}/neg-risk?token_id={}", self.host, token_id);
-        let resp = self.http
+        let req = self.http
             .get(&url)
-            .header("User-Agent", USER_AGENT)
-            .send()
-            .await?;
+            .header("User-Agent", USER_AGENT);
+        let resp = self.send_scheduled(RequestPriority::MarketData, req).await?;
 
-        let val: serde_json::Value = resp.json().await?;
+        let val: serde_json::Value = resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))?;
         Ok(val["neg_risk"].as_bool().unwrap_or(false))
     }
 
@@ -552,7 +816,9 @@ pub struct SharedAsyncClient {
     creds: PreparedCreds,
     chain_id: u64,
     /// Pre-cached neg_risk lookups
-    neg_risk_cache: std::sync::RwLock<HashMap<String, bool>>,
+    neg_risk_cache: TieredCache<bool>,
+    /// Exchange order nonce (see `NonceManager`)
+    nonces: NonceManager,
 }
 
 impl SharedAsyncClient {
@@ -561,22 +827,45 @@ impl SharedAsyncClient {
             inner: Arc::new(client),
             creds,
             chain_id,
-            neg_risk_cache: std::sync::RwLock::new(HashMap::new()),
+            neg_risk_cache: TieredCache::new(CacheNamespace::NegRisk),
+            nonces: NonceManager::default(),
         }
     }
 
+    /// Order nonce tracker (resync with `AllowanceManager::exchange_nonce` at startup)
+    pub fn nonces(&self) -> &NonceManager {
+        &self.nonces
+    }
+
+    /// Current state of an order
+    pub async fn get_order(&self, order_id: &str) -> Result<PolymarketOrderResponse, VenueApiError> {
+        self.inner.get_order_async(order_id, &self.creds).await
+    }
+
+    /// Poll an order until terminal state or timeout
+    pub async fn poll_order(&self, order_id: &str, poll_every: Duration, timeout: Duration) -> Result<PolymarketOrderResponse, VenueApiError> {
+        self.inner.poll_order_async(order_id, &self.creds, poll_every, timeout).await
+    }
+
+    /// Cancel a resting order
+    pub async fn cancel_order(&self, order_id: &str) -> Result<(), VenueApiError> {
+        self.inner.cancel_order_async(order_id, &self.creds).await
+    }
+
+    /// CLOB-reported collateral balance and allowance for the funder (proxy wallet)
+    pub async fn balance_allowance(&self) -> Result<BalanceAllowance, VenueApiError> {
+        self.inner.get_balance_allowance_async(&self.creds, 1).await
+    }
+
     /// Load neg_risk cache from JSON file (output of build_sports_cache.py)
     pub fn load_cache(&self, path: &str) -> Result<usize> {
         let data = std::fs::read_to_string(path)?;
         let map: HashMap<String, bool> = serde_json::from_str(&data)?;
-        let count = map.len();
-        let mut cache = self.neg_risk_cache.write().unwrap();
-        *cache = map;
-        Ok(count)
+        Ok(self.neg_risk_cache.load_hot(map))
     }
 
     /// Execute FAK buy order - 
-    pub async fn buy_fak(&self, token_id: &str, price: f64, size: f64) -> Result<PolyFillAsync> {
+    pub async fn buy_fak(&self, token_id: &str, price: f64, size: f64) -> Result<PolyFillAsync, VenueApiError> {
         debug_assert!(!token_id.is_empty(), "token_id must not be empty");
         debug_assert!(price > 0.0 && price < 1.0, "price must be 0 < p < 1");
         debug_assert!(size >= 1.0, "size must be >= 1");
@@ -584,29 +873,55 @@ impl SharedAsyncClient {
     }
 
     /// Execute FAK sell order - 
-    pub async fn sell_fak(&self, token_id: &str, price: f64, size: f64) -> Result<PolyFillAsync> {
+    pub async fn sell_fak(&self, token_id: &str, price: f64, size: f64) -> Result<PolyFillAsync, VenueApiError> {
         debug_assert!(!token_id.is_empty(), "token_id must not be empty");
         debug_assert!(price > 0.0 && price < 1.0, "price must be 0 < p < 1");
         debug_assert!(size >= 1.0, "size must be >= 1");
         self.execute_order(token_id, price, size, "SELL").await
     }
 
-    async fn execute_order(&self, token_id: &str, price: f64, size: f64, side: &str) -> Result<PolyFillAsync> {
-        // Check neg_risk cache first
-        let neg_risk = {
-            let cache = self.neg_risk_cache.read().unwrap();
-            cache.get(token_id).copied()
+    /// GTC buy left on the book for `rest_for` (or until it fills), then cancelled; fills are
+    /// read from the final order state (passive entry)
+    pub async fn buy_passive(&self, token_id: &str, price: f64, size: f64, rest_for: Duration) -> Result<PolyFillAsync, VenueApiError> {
+        debug_assert!(!token_id.is_empty(), "token_id must not be empty");
+        debug_assert!(price > 0.0 && price < 1.0, "price must be 0 < p < 1");
+        debug_assert!(size >= 1.0, "size must be >= 1");
+        let args = OrderArgs {
+            token_id: token_id.to_string(),
+            price,
+            size,
+            side: "BUY".to_string(),
+            fee_rate_bps: None,
+            nonce: None,
+            expiration: None,
+            taker: None,
         };
-
-        let neg_risk = match neg_risk {
-            Some(nr) => nr,
-            None => {
-                let nr = self.inner.check_neg_risk(token_id).await?;
-                let mut cache = self.neg_risk_cache.write().unwrap();
-                cache.insert(token_id.to_string(), nr);
-                nr
+        let order_id = self.place_order(&args, PolyOrderType::GTC).await?;
+        let mut order = self.poll_order(&order_id, PASSIVE_POLL, rest_for).await?;
+        if !order.is_terminal() {
+            if let Err(e) = self.cancel_order(&order_id).await {
+                tracing::warn!("[POLY-ASYNC] Cancel {} after {:?} failed: {}", order_id, rest_for, e);
             }
-        };
+            order = self.get_order(&order_id).await?;
+        }
+        let filled_size = order.filled_size();
+        let order_price: f64 = order.price.parse().unwrap_or(price);
+
+        tracing::debug!(
+            "[POLY-ASYNC] GTC BUY {}: status={}, filled={:.2}/{:.2}, price={:.4}",
+            order_id, order.status, filled_size, size, order_price
+        );
+
+        Ok(PolyFillAsync {
+            order_id,
+            filled_size,
+            fill_cost: filled_size * order_price,
+        })
+    }
+
+    async fn execute_order(&self, token_id: &str, price: f64, size: f64, side: &str) -> Result<PolyFillAsync, VenueApiError> {
+        // Check neg_risk cache first
+        let neg_risk = self.neg_risk_for(token_id).await?;
 
         // Build signed order
         let signed = self.build_signed_order(token_id, price, size, side, neg_risk)?;
@@ -617,12 +932,10 @@ impl SharedAsyncClient {
         let resp = self.inner.post_order_async(body, &self.creds).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(anyhow!("Polymarket order failed {}: {}", status, body));
+            return Err(VenueApiError::from_response(Platform::Polymarket, resp).await);
         }
 
-        let resp_json: serde_json::Value = resp.json().await?;
+        let resp_json: serde_json::Value = resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))?;
         let order_id = resp_json["orderID"].as_str().unwrap_or("unknown").to_string();
 
         // Query fill status
@@ -642,7 +955,7 @@ impl SharedAsyncClient {
         })
     }
 
-    /// Build a signed order
+    /// Build a signed order with default args (FAK hot path)
     fn build_signed_order(
         &self,
         token_id: &str,
@@ -650,46 +963,75 @@ impl SharedAsyncClient {
         size: f64,
         side: &str,
         neg_risk: bool,
-    ) -> Result<SignedOrder> {
-        let price_bps = price_to_bps(price);
-        let size_micro = size_to_micro(size);
+    ) -> Result<SignedOrder, VenueApiError> {
+        let args = OrderArgs {
+            token_id: token_id.to_string(),
+            price,
+            size,
+            side: side.to_string(),
+            fee_rate_bps: None,
+            nonce: None,
+            expiration: None,
+            taker: None,
+        };
+        self.sign_order(&args, neg_risk)
+    }
+
+    /// Construct and EIP712-sign an order from full args
+    pub fn sign_order(&self, args: &OrderArgs, neg_risk: bool) -> Result<SignedOrder, VenueApiError> {
+        let price_bps = price_to_bps(args.price);
+        let size_micro = size_to_micro(args.size);
 
         if !price_valid(price_bps) {
-            return Err(anyhow!("price {} ({}bps) outside allowed range", price, price_bps));
+            return Err(VenueApiError::InvalidOrder {
+                venue: Platform::Polymarket,
+                message: format!("price {} ({}bps) outside allowed range", args.price, price_bps),
+            });
         }
 
-        let (side_code, maker_amt, taker_amt) = if side.eq_ignore_ascii_case("BUY") {
+        let (side_code, maker_amt, taker_amt) = if args.side.eq_ignore_ascii_case("BUY") {
             get_order_amounts_buy(size_micro, price_bps)
-        } else if side.eq_ignore_ascii_case("SELL") {
+        } else if args.side.eq_ignore_ascii_case("SELL") {
             get_order_amounts_sell(size_micro, price_bps)
         } else {
-            return Err(anyhow!("side must be BUY or SELL"));
+            return Err(VenueApiError::InvalidOrder {
+                venue: Platform::Polymarket,
+                message: "side must be BUY or SELL".into(),
+            });
         };
 
         let salt = generate_seed();
         let maker_amount_str = maker_amt.to_string();
         let taker_amount_str = taker_amt.to_string();
+        let fee_rate_str = args.fee_rate_bps.unwrap_or(0).max(0).to_string();
+        let nonce_str = match args.nonce {
+            Some(n) => n.max(0).to_string(),
+            None => self.nonces.current().to_string(),
+        };
+        let expiration_str = args.expiration.clone().unwrap_or_else(|| "0".to_string());
+        let taker = args.taker.as_deref().unwrap_or(ZERO_ADDRESS);
 
         // Use references for EIP712 signing 
         let data = OrderData {
             maker: &self.inner.funder,
-            taker: ZERO_ADDRESS,
-            token_id,
+            taker,
+            token_id: &args.token_id,
             maker_amount: &maker_amount_str,
             taker_amount: &taker_amount_str,
             side: side_code,
-            fee_rate_bps: "0",
-            nonce: "0",
+            fee_rate_bps: &fee_rate_str,
+            nonce: &nonce_str,
             signer: &self.inner.wallet_address_str,
-            expiration: "0",
+            expiration: &expiration_str,
             signature_type: 1,
             salt,
         };
-        let exchange = get_exchange_address(self.chain_id, neg_risk)?;
-        let typed = order_typed_data(self.chain_id, &exchange, &data)?;
-        let digest = typed.encode_eip712()?;
+        let signing = |e: &dyn std::fmt::Display| VenueApiError::signing(Platform::Polymarket, e);
+        let exchange = get_exchange_address(self.chain_id, neg_risk).map_err(|e| signing(&e))?;
+        let typed = order_typed_data(self.chain_id, &exchange, &data).map_err(|e| signing(&e))?;
+        let digest = typed.encode_eip712().map_err(|e| signing(&e))?;
 
-        let sig = self.inner.wallet.sign_hash(H256::from(digest))?;
+        let sig = self.inner.wallet.sign_hash(H256::from(digest)).map_err(|e| signing(&e))?;
 
         // Only allocate strings once for the final OrderStruct (serialization needs owned)
         Ok(SignedOrder {
@@ -697,19 +1039,52 @@ impl SharedAsyncClient {
                 salt,
                 maker: self.inner.funder.clone(),
                 signer: self.inner.wallet_address_str.clone(),
-                taker: ZERO_ADDRESS.to_string(),
-                token_id: token_id.to_string(),
+                taker: taker.to_string(),
+                token_id: args.token_id.clone(),
                 maker_amount: maker_amount_str,
                 taker_amount: taker_amount_str,
-                expiration: "0".to_string(),
-                nonce: "0".to_string(),
-                fee_rate_bps: "0".to_string(),
+                expiration: expiration_str,
+                nonce: nonce_str,
+                fee_rate_bps: fee_rate_str,
                 side: side_code,
                 signature_type: 1,
             },
             signature: format!("0x{}", sig),
         })
     }
+
+    /// Sign and post an order with an explicit order type (GTC/GTD/FOK/FAK)
+    /// Returns the exchange order ID
+    pub async fn place_order(&self, args: &OrderArgs, order_type: PolyOrderType) -> Result<String, VenueApiError> {
+        let neg_risk = self.neg_risk_for(&args.token_id).await?;
+        let signed = self.sign_order(args, neg_risk)?;
+        let body = signed.post_body(&self.creds.api_key, order_type.as_str());
+        let resp = self.inner.post_order_async(body, &self.creds).await?;
+
+        if !resp.status().is_success() {
+            return Err(VenueApiError::from_response(Platform::Polymarket, resp).await);
+        }
+
+        let resp_json: serde_json::Value = resp.json().await.map_err(|e| VenueApiError::reqwest(Platform::Polymarket, e))?;
+        resp_json["orderID"].as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| VenueApiError::Decode {
+                venue: Platform::Polymarket,
+                message: format!("order response missing orderID: {}", resp_json),
+            })
+    }
+
+    /// neg_risk flag for a token (cache, then CLOB lookup)
+    async fn neg_risk_for(&self, token_id: &str) -> Result<bool, VenueApiError> {
+        match self.neg_risk_cache.get_hot(token_id) {
+            Some(nr) => Ok(nr),
+            None => {
+                let nr = self.inner.check_neg_risk(token_id).await?;
+                self.neg_risk_cache.insert_hot(token_id, nr);
+                Ok(nr)
+            }
+        }
+    }
 }
 
 /// Async fill result
@@ -718,4 +1093,370 @@ pub struct PolyFillAsync {
     pub order_id: String,
     pub filled_size: f64,
     pub fill_cost: f64,
-}
\ No newline at end of file
+}
+// ============================================================================
+// ON-CHAIN ALLOWANCES
+// ============================================================================
+
+/// Result of an allowance sweep
+#[derive(Debug, Clone, Default)]
+pub struct AllowanceStatus {
+    /// USDC balance of the owner (6dp units)
+    pub usdc_balance: U256,
+    /// Spenders whose USDC allowance was below the required minimum
+    pub usdc_approvals_sent: Vec<Address>,
+    /// Operators that were granted ERC1155 approval for outcome tokens
+    pub ctf_approvals_sent: Vec<Address>,
+}
+
+/// USDC / conditional-token approvals for the exchange contracts
+///
+/// Approvals are sent from the signing wallet, so this only applies when the
+/// wallet itself holds funds (EOA, signature_type 0). Proxy wallets are managed
+/// by Polymarket; use `SharedAsyncClient::balance_allowance` to inspect those.
+pub struct AllowanceManager {
+    provider: Arc<Provider<Http>>,
+    wallet: LocalWallet,
+    owner: Address,
+    chain_id: u64,
+}
+
+#[allow(dead_code)]
+impl AllowanceManager {
+    pub fn new(rpc_url: &str, private_key: &Secret, chain_id: u64) -> Result<Self> {
+        let provider = Provider::<Http>::try_from(rpc_url)
+            .with_context(|| format!("invalid RPC url {}", rpc_url))?;
+        let wallet = wallet_from_secret(private_key, chain_id)?;
+        let owner = wallet.address();
+        Ok(Self { provider: Arc::new(provider), wallet, owner, chain_id })
+    }
+
+    /// Exchanges + neg-risk adapter that need to pull USDC / outcome tokens
+    pub fn spenders(&self) -> Result<Vec<Address>> {
+        let mut out = Vec::with_capacity(3);
+        for neg_risk in [false, true] {
+            let cfg = get_contract_config(self.chain_id, neg_risk)?;
+            out.push(cfg.exchange.parse::<Address>()?);
+            if let Some(adapter) = cfg.neg_risk_adapter {
+                out.push(adapter.parse::<Address>()?);
+            }
+        }
+        out.sort();
+        out.dedup();
+        Ok(out)
+    }
+
+    async fn view(&self, to: Address, data: Bytes) -> Result<Bytes> {
+        let tx: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+        Ok(self.provider.call(&tx, None).await?)
+    }
+
+    async fn send(&self, to: Address, data: Bytes) -> Result<H256> {
+        let client = SignerMiddleware::new(self.provider.clone(), self.wallet.clone());
+        let tx = TransactionRequest::new().from(self.owner).to(to).data(data);
+        let pending = client.send_transaction(tx, None).await?;
+        let tx_hash = pending.tx_hash();
+        let receipt = pending.await?
+            .ok_or_else(|| anyhow!("tx {:?} dropped from mempool", tx_hash))?;
+        if receipt.status != Some(1u64.into()) {
+            return Err(anyhow!("tx {:?} reverted", tx_hash));
+        }
+        Ok(tx_hash)
+    }
+
+    fn collateral(&self) -> Result<Address> {
+        Ok(get_contract_config(self.chain_id, false)?.collateral.parse()?)
+    }
+
+    fn conditional_tokens(&self) -> Result<Address> {
+        Ok(get_contract_config(self.chain_id, false)?.conditional_tokens.parse()?)
+    }
+
+    /// USDC balance of the wallet (6dp units)
+    pub async fn usdc_balance(&self) -> Result<U256> {
+        let data = encode_call("balanceOf(address)", &[Token::Address(self.owner)]);
+        decode_u256(&self.view(self.collateral()?, data).await?)
+    }
+
+    /// USDC allowance granted to `spender` (6dp units)
+    pub async fn usdc_allowance(&self, spender: Address) -> Result<U256> {
+        let data = encode_call("allowance(address,address)", &[Token::Address(self.owner), Token::Address(spender)]);
+        decode_u256(&self.view(self.collateral()?, data).await?)
+    }
+
+    /// Whether `operator` may move our outcome tokens
+    pub async fn ctf_approved(&self, operator: Address) -> Result<bool> {
+        let data = encode_call("isApprovedForAll(address,address)", &[Token::Address(self.owner), Token::Address(operator)]);
+        Ok(!decode_u256(&self.view(self.conditional_tokens()?, data).await?)?.is_zero())
+    }
+
+    /// Current order nonce for our address on the exchange
+    pub async fn exchange_nonce(&self, neg_risk: bool) -> Result<u64> {
+        let exchange: Address = get_contract_config(self.chain_id, neg_risk)?.exchange.parse()?;
+        let data = encode_call("nonces(address)", &[Token::Address(self.owner)]);
+        Ok(decode_u256(&self.view(exchange, data).await?)?.as_u64())
+    }
+
+    /// Approve `spender` for `amount` USDC (6dp units)
+    pub async fn approve_usdc(&self, spender: Address, amount: U256) -> Result<H256> {
+        let data = encode_call("approve(address,uint256)", &[Token::Address(spender), Token::Uint(amount)]);
+        let hash = self.send(self.collateral()?, data).await?;
+        info!("[POLY-CHAIN] USDC approve {:?} amount={} tx={:?}", spender, amount, hash);
+        Ok(hash)
+    }
+
+    /// Grant ERC1155 operator approval on the conditional tokens contract
+    pub async fn approve_ctf(&self, operator: Address) -> Result<H256> {
+        let data = encode_call("setApprovalForAll(address,bool)", &[Token::Address(operator), Token::Bool(true)]);
+        let hash = self.send(self.conditional_tokens()?, data).await?;
+        info!("[POLY-CHAIN] CTF setApprovalForAll {:?} tx={:?}", operator, hash);
+        Ok(hash)
+    }
+
+    /// Invalidate all resting orders by bumping the exchange nonce
+    pub async fn increment_nonce(&self, neg_risk: bool, nonces: &NonceManager) -> Result<H256> {
+        let exchange: Address = get_contract_config(self.chain_id, neg_risk)?.exchange.parse()?;
+        let hash = self.send(exchange, encode_call("incrementNonce()", &[])).await?;
+        let next = nonces.bump();
+        warn!("[POLY-CHAIN] incrementNonce tx={:?}, order nonce now {}", hash, next);
+        Ok(hash)
+    }
+
+    /// Make sure every spender has at least `min_usdc` allowance and CTF approval
+    /// Tops up to U256::MAX (standard unlimited approval) when short
+    pub async fn ensure_allowances(&self, min_usdc: U256) -> Result<AllowanceStatus> {
+        let mut status = AllowanceStatus {
+            usdc_balance: self.usdc_balance().await?,
+            ..Default::default()
+        };
+
+        for spender in self.spenders()? {
+            let allowance = self.usdc_allowance(spender).await?;
+            if allowance < min_usdc {
+                debug!("[POLY-CHAIN] {:?} allowance {} < {}", spender, allowance, min_usdc);
+                self.approve_usdc(spender, U256::MAX).await?;
+                status.usdc_approvals_sent.push(spender);
+            }
+            if !self.ctf_approved(spender).await? {
+                self.approve_ctf(spender).await?;
+                status.ctf_approvals_sent.push(spender);
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+// ============================================================================
+// USER CHANNEL WEBSOCKET (ORDER / TRADE STATUS)
+// ============================================================================
+
+/// Order lifecycle event from the user channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolyOrderEvent {
+    pub id: String,
+    /// PLACEMENT / UPDATE / CANCELLATION
+    #[serde(rename = "type", default)]
+    pub update_type: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub market: Option<String>,
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub price: Option<String>,
+    #[serde(default)]
+    pub original_size: Option<String>,
+    #[serde(default)]
+    pub size_matched: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// Trade (fill) event from the user channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolyTradeEvent {
+    pub id: String,
+    /// MATCHED / MINED / CONFIRMED / RETRYING / FAILED
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub taker_order_id: Option<String>,
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub market: Option<String>,
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub price: Option<String>,
+    #[serde(default)]
+    pub size: Option<String>,
+    #[serde(default)]
+    pub maker_orders: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// Message on the authenticated user channel
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event_type", rename_all = "lowercase")]
+pub enum PolyUserEvent {
+    Order(PolyOrderEvent),
+    Trade(PolyTradeEvent),
+}
+
+/// Parse a user-channel frame (single object or batched array)
+pub fn parse_user_events(text: &str) -> Vec<PolyUserEvent> {
+    if let Ok(events) = serde_json::from_str::<Vec<PolyUserEvent>>(text) {
+        return events;
+    }
+    serde_json::from_str::<PolyUserEvent>(text).map(|e| vec![e]).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct UserSubscribeCmd<'a> {
+    auth: UserAuth<'a>,
+    markets: &'a [String],
+    #[serde(rename = "type")]
+    sub_type: &'static str,
+}
+
+#[derive(Serialize)]
+struct UserAuth<'a> {
+    #[serde(rename = "apiKey")]
+    api_key: &'a str,
+    secret: &'a str,
+    passphrase: &'a str,
+}
+
+/// User channel runner - forwards order/trade events until the socket drops
+/// `markets` are condition IDs; empty = all of the account's markets
+pub async fn run_user_ws(
+    creds: &ApiCreds,
+    markets: &[String],
+    tx: mpsc::Sender<PolyUserEvent>,
+) -> Result<()> {
+    let (ws_stream, _) = connect_async(polymarket_user_ws_url())
+        .await
+        .context("Failed to connect to Polymarket user channel")?;
+
+    info!("[POLY-USER] Connected");
+
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribe_msg = UserSubscribeCmd {
+        auth: UserAuth {
+            api_key: &creds.api_key,
+            secret: creds.api_secret.expose(),
+            passphrase: creds.api_passphrase.expose(),
+        },
+        markets,
+        sub_type: "user",
+    };
+    write.send(Message::Text(serde_json::to_string(&subscribe_msg)?)).await?;
+    info!("[POLY-USER] Subscribed ({} markets)", markets.len());
+
+    let mut ping_interval = tokio::time::interval(Duration::from_secs(POLY_PING_INTERVAL_SECS));
+
+    loop {
+        tokio::select! {
+            _ = ping_interval.tick() => {
+                if let Err(e) = write.send(Message::Ping(vec![])).await {
+                    warn!("[POLY-USER] Failed to send ping: {}", e);
+                    break;
+                }
+            }
+
+            msg = read.next() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        for event in parse_user_events(&text) {
+                            if tx.send(event).await.is_err() {
+                                info!("[POLY-USER] Receiver dropped, closing");
+                                return Ok(());
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Ping(data))) => {
+                        let _ = write.send(Message::Pong(data)).await;
+                    }
+                    Some(Ok(Message::Close(frame))) => {
+                        warn!("[POLY-USER] Server closed: {:?}", frame);
+                        break;
+                    }
+                    Some(Err(e)) => {
+                        warn!("[POLY-USER] WebSocket error: {}", e);
+                        break;
+                    }
+                    None => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_manager() {
+        let nonces = NonceManager::new(3);
+        assert_eq!(nonces.current(), 3);
+        assert_eq!(nonces.bump(), 4);
+        nonces.set(10);
+        assert_eq!(nonces.current(), 10);
+    }
+
+    #[test]
+    fn test_encode_call_selector() {
+        // approve(address,uint256) = 0x095ea7b3
+        let data = encode_call("approve(address,uint256)", &[Token::Address(Address::zero()), Token::Uint(U256::one())]);
+        assert_eq!(&data[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+        assert_eq!(data.len(), 4 + 64);
+        assert_eq!(decode_u256(&data[36..]).unwrap(), U256::one());
+    }
+
+    #[test]
+    fn test_contract_config() {
+        let std = get_contract_config(137, false).unwrap();
+        let neg = get_contract_config(137, true).unwrap();
+        assert_ne!(std.exchange, neg.exchange);
+        assert_eq!(std.collateral, neg.collateral);
+        assert!(neg.neg_risk_adapter.is_some());
+        assert!(get_contract_config(1, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_user_events() {
+        let text = r#"[{"event_type":"order","id":"0xabc","type":"UPDATE","size_matched":"5","original_size":"10"},
+                       {"event_type":"trade","id":"t1","status":"MATCHED","taker_order_id":"0xabc","size":"5","price":"0.42"}]"#;
+        let events = parse_user_events(text);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], PolyUserEvent::Order(o) if o.size_matched.as_deref() == Some("5")));
+        assert!(matches!(&events[1], PolyUserEvent::Trade(t) if t.taker_order_id.as_deref() == Some("0xabc")));
+
+        assert!(parse_user_events(r#"{"event_type":"unknown"}"#).is_empty());
+    }
+
+    #[test]
+    fn test_order_response_terminal() {
+        let mut order: PolymarketOrderResponse = serde_json::from_value(json!({
+            "id": "1", "status": "LIVE", "price": "0.5", "side": "BUY",
+            "size_matched": "0", "original_size": "10", "type": "GTC"
+        })).unwrap();
+        assert!(!order.is_terminal());
+        order.size_matched = "10".into();
+        assert!(order.is_terminal());
+        order.status = "CANCELED".into();
+        order.size_matched = "0".into();
+        assert!(order.is_terminal());
+    }
+}
@@ -0,0 +1,410 @@
+// src/position_aging.rs
+// Position aging - a pattern's trades should converge within a few of its half-lives; open lots
+// outliving `[patterns.aging] flag_after_half_lives` of them are flagged stale (dashboard, alert
+// hooks), and with `liquidate` sold past `liquidate_after_half_lives`, their P&L booked as
+// timeout exits
+
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::clock::{self, SharedClock};
+use crate::config::{AgingSection, PatternsSection};
+use crate::config_reload::ConfigChanged;
+use crate::kalshi::KalshiApiClient;
+use crate::polymarket_clob::SharedAsyncClient;
+use crate::position_tracker::{FillRecord, PositionChannel, PositionTracker, SharedPositionTracker, TIMEOUT_EXIT};
+use crate::types::MarketPair;
+
+/// Open lots of one pattern on one leg that outlived their convergence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StalePosition {
+    pub market_id: String,
+    pub description: String,
+    pub platform: String,
+    pub side: String,
+    /// Lot attribution tag (`"73"`, `"PolyYesKalshiNo"`)
+    pub pattern: String,
+    /// Listed account holding the lots (None = the venue's default account)
+    pub account: Option<String>,
+    pub contracts: f64,
+    /// Open time of the oldest lot (RFC3339)
+    pub opened_at: String,
+    pub age_secs: f64,
+    pub half_life_ms: f64,
+    /// Age in half-lives
+    pub half_lives: f64,
+    /// Due for a timeout exit (liquidation on, past its multiple, default account)
+    pub liquidate: bool,
+}
+
+impl StalePosition {
+    fn key(&self) -> (String, String, String, String) {
+        (self.market_id.clone(), self.platform.clone(), self.side.clone(), self.pattern.clone())
+    }
+
+    /// Structured form for the alert router / event bus (and so the dashboard)
+    pub fn to_alert(&self) -> Alert {
+        Alert::new("position_aging", AlertSeverity::Warning, "stale_position", format!(
+            "{} {} {} x{:.0} (pattern {}) open {:.0}s = {:.1} half-lives of {:.0}ms",
+            self.platform, self.side, self.market_id, self.contracts, self.pattern,
+            self.age_secs, self.half_lives, self.half_life_ms,
+        ))
+    }
+}
+
+/// Sells a stale position on its venue
+pub trait PositionLiquidator: Send + Sync {
+    /// Sell the position's whole contracts at `min_price` (dollars) or better; the sell fill,
+    /// None when nothing traded or the venue can't be reached
+    fn liquidate<'a>(&'a self, stale: &'a StalePosition, min_price: f64) -> BoxFuture<'a, Result<Option<FillRecord>>>;
+}
+
+type StaleHook = Arc<dyn Fn(&StalePosition) + Send + Sync>;
+
+/// Ages open lots against their pattern's `half_life_ms`; `sweep` flags, `liquidate` exits
+pub struct PositionSweeper {
+    /// Half-life (ms) per pattern tag; patterns without one are never stale
+    half_lives: RwLock<BTreeMap<String, f64>>,
+    aging: RwLock<AgingSection>,
+    clock: SharedClock,
+    liquidator: Option<Arc<dyn PositionLiquidator>>,
+    /// Result of the last sweep
+    stale: Mutex<Vec<StalePosition>>,
+    hooks: Vec<StaleHook>,
+}
+
+pub type SharedPositionSweeper = Arc<PositionSweeper>;
+
+impl PositionSweeper {
+    pub fn new(section: &PatternsSection) -> Self {
+        let sweeper = Self {
+            half_lives: RwLock::new(BTreeMap::new()),
+            aging: RwLock::new(section.aging.clone()),
+            clock: clock::system(),
+            liquidator: None,
+            stale: Mutex::new(Vec::new()),
+            hooks: Vec::new(),
+        };
+        sweeper.apply_config(section);
+        sweeper
+    }
+
+    /// Apply a hot-reloaded `[patterns]` section
+    pub fn apply_config(&self, section: &PatternsSection) {
+        *self.half_lives.write().unwrap() = section.enabled.iter()
+            .filter(|(_, rule)| rule.half_life_ms > 0.0)
+            .map(|(id, rule)| (id.clone(), rule.half_life_ms))
+            .collect();
+        *self.aging.write().unwrap() = section.aging.clone();
+    }
+
+    /// Age lots against another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sell positions past `liquidate_after_half_lives` through this venue access
+    pub fn with_liquidator(mut self, liquidator: Arc<dyn PositionLiquidator>) -> Self {
+        self.liquidator = Some(liquidator);
+        self
+    }
+
+    /// Register a hook for positions newly flagged stale
+    pub fn on_stale(mut self, hook: impl Fn(&StalePosition) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.aging.read().unwrap().interval_secs.max(1))
+    }
+
+    /// Positions flagged by the last sweep, oldest (in half-lives) first
+    pub fn stale(&self) -> Vec<StalePosition> {
+        self.stale.lock().unwrap().clone()
+    }
+
+    /// Flag every open lot group past `flag_after_half_lives` of its pattern's half-life
+    pub fn sweep(&self, tracker: &PositionTracker) -> Vec<StalePosition> {
+        let aging = self.aging.read().unwrap().clone();
+        let half_lives = self.half_lives.read().unwrap().clone();
+        let now_ns = self.clock.wall_ns().0 as i64;
+        let mut stale = Vec::new();
+        if aging.flag_after_half_lives > 0.0 {
+            for position in tracker.open_positions() {
+                let legs = [
+                    ("kalshi", "yes", &position.kalshi_yes),
+                    ("kalshi", "no", &position.kalshi_no),
+                    ("polymarket", "yes", &position.poly_yes),
+                    ("polymarket", "no", &position.poly_no),
+                ];
+                for (platform, side, leg) in legs {
+                    // (pattern, account) -> contracts, oldest open time (ns)
+                    let mut groups: BTreeMap<(&str, Option<&str>), (f64, i64, &str)> = BTreeMap::new();
+                    for lot in leg.lots.iter().filter(|l| l.contracts > 1e-9) {
+                        let Some(pattern) = lot.pattern.as_deref().filter(|p| half_lives.contains_key(*p)) else { continue };
+                        let Ok(opened) = chrono::DateTime::parse_from_rfc3339(&lot.opened_at) else { continue };
+                        let opened_ns = opened.timestamp_nanos_opt().unwrap_or(now_ns);
+                        let group = groups.entry((pattern, lot.account.as_deref())).or_insert((0.0, opened_ns, &lot.opened_at));
+                        group.0 += lot.contracts;
+                        if opened_ns < group.1 {
+                            group.1 = opened_ns;
+                            group.2 = &lot.opened_at;
+                        }
+                    }
+                    for ((pattern, account), (contracts, opened_ns, opened_at)) in groups {
+                        let half_life_ms = half_lives[pattern];
+                        let age_secs = (now_ns - opened_ns).max(0) as f64 / 1e9;
+                        let multiple = age_secs * 1000.0 / half_life_ms;
+                        if multiple < aging.flag_after_half_lives {
+                            continue;
+                        }
+                        stale.push(StalePosition {
+                            market_id: position.market_id.clone(),
+                            description: position.description.clone(),
+                            platform: platform.to_string(),
+                            side: side.to_string(),
+                            pattern: pattern.to_string(),
+                            account: account.map(str::to_string),
+                            contracts,
+                            opened_at: opened_at.to_string(),
+                            age_secs,
+                            half_life_ms,
+                            half_lives: multiple,
+                            liquidate: aging.liquidate && account.is_none() && multiple >= aging.liquidate_after_half_lives,
+                        });
+                    }
+                }
+            }
+        }
+        stale.sort_by(|a, b| b.half_lives.total_cmp(&a.half_lives));
+
+        let mut last = self.stale.lock().unwrap();
+        let known: HashSet<_> = last.iter().map(StalePosition::key).collect();
+        for position in stale.iter().filter(|p| !known.contains(&p.key())) {
+            warn!("[AGING] Stale: {} {} {} x{:.0} (pattern {}) open {:.0}s, {:.1} half-lives",
+                  position.platform, position.side, position.market_id, position.contracts,
+                  position.pattern, position.age_secs, position.half_lives);
+            for hook in &self.hooks {
+                hook(position);
+            }
+        }
+        *last = stale.clone();
+        stale
+    }
+
+    /// Sell the positions due for a timeout exit; the sell fills, tagged `TIMEOUT_EXIT`
+    pub async fn liquidate(&self, stale: &[StalePosition]) -> Vec<FillRecord> {
+        let Some(liquidator) = &self.liquidator else { return Vec::new() };
+        let min_price = self.aging.read().unwrap().min_exit_price;
+        let mut exits = Vec::new();
+        for position in stale.iter().filter(|p| p.liquidate) {
+            match liquidator.liquidate(position, min_price).await {
+                Ok(Some(fill)) => {
+                    info!("[AGING] Timeout exit: {} {} {} x{:.0} @{:.1}¢ (pattern {})",
+                          fill.platform, fill.side, fill.market_id, fill.contracts, fill.price * 100.0, position.pattern);
+                    exits.push(fill.as_sell().with_pattern(&position.pattern).with_exit_reason(TIMEOUT_EXIT));
+                }
+                Ok(None) => {}
+                Err(e) => warn!("[AGING] Timeout exit of {} {} {} failed: {}", position.platform, position.side, position.market_id, e),
+            }
+        }
+        exits
+    }
+}
+
+/// Sweep the live tracker every interval, sending timeout exits to the position writer
+pub async fn run_position_sweeper(sweeper: SharedPositionSweeper, tracker: SharedPositionTracker, exits: PositionChannel) {
+    loop {
+        tokio::time::sleep(sweeper.interval()).await;
+        let stale = sweeper.sweep(&*tracker.read().await);
+        for fill in sweeper.liquidate(&stale).await {
+            exits.record_fill(fill);
+        }
+    }
+}
+
+/// Flag-only sweeps of the positions another process saves at `path` (see POSITION_FILE)
+pub async fn run_saved_position_sweeper(sweeper: SharedPositionSweeper, path: PathBuf) {
+    loop {
+        tokio::time::sleep(sweeper.interval()).await;
+        let tracker = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("[AGING] Unreadable positions file {}: {}", path.display(), e);
+                PositionTracker::new()
+            }),
+            Err(_) => PositionTracker::new(),
+        };
+        sweeper.sweep(&tracker);
+    }
+}
+
+/// Keep half-lives and the aging policy in step with `[patterns]` reloads
+pub fn watch_config(sweeper: SharedPositionSweeper, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("patterns") => sweeper.apply_config(&change.config.patterns),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[AGING] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+/// Timeout exits through the default Kalshi and Polymarket clients, markets resolved
+/// through the discovered pairs (IOC / FAK sells at the floor price, so they take the bid)
+pub struct VenueLiquidator {
+    kalshi: Arc<KalshiApiClient>,
+    poly: Arc<SharedAsyncClient>,
+    pairs: HashMap<String, MarketPair>,
+}
+
+impl VenueLiquidator {
+    pub fn new(kalshi: Arc<KalshiApiClient>, poly: Arc<SharedAsyncClient>) -> Self {
+        Self { kalshi, poly, pairs: HashMap::new() }
+    }
+
+    /// Markets the tracker keys by pair id
+    pub fn with_pairs<'a>(mut self, pairs: impl IntoIterator<Item = &'a MarketPair>) -> Self {
+        self.pairs.extend(pairs.into_iter().map(|p| (p.pair_id.to_string(), p.clone())));
+        self
+    }
+
+    async fn sell(&self, stale: &StalePosition, min_price: f64) -> Result<Option<FillRecord>> {
+        let Some(pair) = self.pairs.get(&stale.market_id) else {
+            warn!("[AGING] No market pair for {}, can't exit", stale.market_id);
+            return Ok(None);
+        };
+        let count = stale.contracts.floor();
+        if count < 1.0 {
+            return Ok(None);
+        }
+        let (order_id, filled, proceeds) = match (stale.platform.as_str(), stale.side.as_str()) {
+            ("kalshi", side) => {
+                let price_cents = ((min_price * 100.0).round() as i64).clamp(1, 99);
+                let resp = self.kalshi.sell_ioc(&pair.kalshi_market_ticker, side, price_cents, count as i64).await?;
+                let order = resp.order;
+                let cents = order.taker_fill_cost.unwrap_or(0) + order.maker_fill_cost.unwrap_or(0);
+                (order.order_id.clone(), order.filled_count() as f64, cents as f64 / 100.0)
+            }
+            ("polymarket", side) => {
+                let token = if side == "yes" { &pair.poly_yes_token } else { &pair.poly_no_token };
+                let fill = self.poly.sell_fak(token, min_price.clamp(0.01, 0.99), count).await?;
+                (fill.order_id, fill.filled_size, fill.fill_cost)
+            }
+            _ => return Ok(None),
+        };
+        if filled <= 0.0 {
+            return Ok(None);
+        }
+        Ok(Some(FillRecord::new(
+            &stale.market_id, &stale.description, &stale.platform, &stale.side,
+            filled, proceeds / filled, 0.0, &order_id,
+        )))
+    }
+}
+
+impl PositionLiquidator for VenueLiquidator {
+    fn liquidate<'a>(&'a self, stale: &'a StalePosition, min_price: f64) -> BoxFuture<'a, Result<Option<FillRecord>>> {
+        Box::pin(self.sell(stale, min_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::config::PatternRule;
+    use crate::types::Nanos;
+
+    const T: i64 = 1_700_000_000;
+
+    fn section(liquidate: bool) -> PatternsSection {
+        let mut section = PatternsSection::default();
+        section.enabled.insert("75".to_string(), PatternRule { half_life_ms: 1_000.0, ..PatternRule::default() });
+        section.enabled.insert("PolyYesKalshiNo".to_string(), PatternRule::default());
+        section.aging.liquidate = liquidate;
+        section
+    }
+
+    fn buy(market: &str, platform: &str, pattern: &str, opened_secs: i64) -> FillRecord {
+        let mut fill = FillRecord::new(market, market, platform, "yes", 10.0, 0.40, 0.0, "o").with_pattern(pattern);
+        fill.timestamp = chrono::DateTime::from_timestamp(T + opened_secs, 0).unwrap().to_rfc3339();
+        fill
+    }
+
+    /// Sells everything at 35¢
+    struct FixedExit;
+
+    impl PositionLiquidator for FixedExit {
+        fn liquidate<'a>(&'a self, stale: &'a StalePosition, _min_price: f64) -> BoxFuture<'a, Result<Option<FillRecord>>> {
+            Box::pin(async move {
+                Ok(Some(FillRecord::new(&stale.market_id, "", &stale.platform, &stale.side, stale.contracts, 0.35, 0.0, "x")))
+            })
+        }
+    }
+
+    #[test]
+    fn test_flags_lots_past_their_half_lives() {
+        let clock = MockClock::shared(Nanos(T as u64 * 1_000_000_000));
+        let flagged = Arc::new(Mutex::new(Vec::new()));
+        let sink = flagged.clone();
+        let sweeper = PositionSweeper::new(&section(false))
+            .with_clock(clock.clone())
+            .on_stale(move |p| sink.lock().unwrap().push(p.to_alert()));
+
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&buy("A", "kalshi", "75", 0));
+        tracker.record_fill_internal(&buy("A", "kalshi", "75", 5));
+        // Arb-type lots have no half-life and are never aged
+        tracker.record_fill_internal(&buy("B", "polymarket", "PolyYesKalshiNo", 0));
+
+        clock.advance(Duration::from_secs(9));
+        assert!(sweeper.sweep(&tracker).is_empty());
+
+        clock.advance(Duration::from_secs(3));
+        let stale = sweeper.sweep(&tracker);
+        assert_eq!(stale.len(), 1);
+        assert_eq!((stale[0].market_id.as_str(), stale[0].contracts), ("A", 20.0));
+        assert!((stale[0].half_lives - 12.0).abs() < 1e-9 && !stale[0].liquidate);
+        // Alerted once while it stays stale
+        sweeper.sweep(&tracker);
+        assert_eq!(flagged.lock().unwrap().len(), 1);
+        assert_eq!(sweeper.stale(), stale);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_exits_book_their_own_pnl() {
+        let clock = MockClock::shared(Nanos(T as u64 * 1_000_000_000));
+        let sweeper = PositionSweeper::new(&section(true)).with_clock(clock.clone()).with_liquidator(Arc::new(FixedExit));
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&buy("A", "kalshi", "75", 0));
+
+        // Flagged at 10 half-lives, sold from 20
+        clock.advance(Duration::from_secs(15));
+        let stale = sweeper.sweep(&tracker);
+        assert!(sweeper.liquidate(&stale).await.is_empty());
+        clock.advance(Duration::from_secs(10));
+        let exits = sweeper.liquidate(&sweeper.sweep(&tracker)).await;
+        assert_eq!(exits.len(), 1);
+        for fill in &exits {
+            tracker.record_fill_internal(fill);
+        }
+        assert!(tracker.open_positions().is_empty());
+        let by_exit = tracker.realized_pnl_by_exit(..);
+        assert!((by_exit[TIMEOUT_EXIT] + 0.5).abs() < 1e-9);
+        assert!(sweeper.sweep(&tracker).is_empty());
+    }
+}
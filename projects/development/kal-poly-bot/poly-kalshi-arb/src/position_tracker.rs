@@ -1,16 +1,33 @@
 // src/position_tracker.rs
 // Track positions, cost basis, and P&L across both platforms
 
-use anyhow::Result;
+use crate::error::StateStoreError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeBounds;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use tracing::{info, warn};
 
-const POSITION_FILE: &str = "positions.json";
+use crate::blotter::SharedBlotter;
+use crate::journal::{Journal, JournalEvent, SharedJournal};
+
+/// Where `PositionTracker::load` and `save` keep state
+pub const POSITION_FILE: &str = "positions.json";
+
+/// Exit reason of lots the stale-position sweeper closed (see position_aging)
+pub const TIMEOUT_EXIT: &str = "timeout";
+/// Exit reason of lots settled by market resolution
+pub const RESOLUTION_EXIT: &str = "resolution";
+/// Exit reason of sells that give none
+pub const CLOSE_EXIT: &str = "close";
+
+/// How long realized lots are kept one by one before they're merged into per-day rows
+pub const LOT_RETENTION: Duration = Duration::from_secs(30 * DAY_SECS as u64);
+
+const DAY_SECS: i64 = 86_400;
 
 /// A single position leg on one platform
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -21,6 +38,66 @@ pub struct PositionLeg {
     pub cost_basis: f64,
     /// Average price per contract
     pub avg_price: f64,
+    /// Open lots, oldest first
+    #[serde(default)]
+    pub lots: VecDeque<Lot>,
+}
+
+/// Cost-basis method used when closing part of a leg
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CostMethod {
+    #[default]
+    Fifo,
+    AverageCost,
+}
+
+/// Contracts opened by a single buy fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lot {
+    pub order_id: String,
+    /// Contracts still open in this lot
+    pub contracts: f64,
+    pub price: f64,
+    /// Entry fees attached to the still-open contracts
+    pub fees: f64,
+    pub opened_at: String,
+    /// Strategy/pattern attribution tag
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Venue account holding the lot (None = the venue's default account)
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+/// Slice of a lot removed by a close
+#[derive(Debug, Clone)]
+pub struct LotSlice {
+    pub contracts: f64,
+    /// Entry price under the active cost method
+    pub entry_price: f64,
+    pub fees: f64,
+    pub pattern: Option<String>,
+}
+
+/// Realized P&L from closing (part of) a lot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealizedLot {
+    pub market_id: String,
+    pub platform: String,
+    pub side: String,
+    pub contracts: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    /// Entry + exit fees charged to this slice
+    pub fees: f64,
+    pub pnl: f64,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// RFC3339
+    pub closed_at: String,
+    /// Why the lot was closed (`TIMEOUT_EXIT`, `RESOLUTION_EXIT`; None = a plain close)
+    #[serde(default)]
+    pub exit_reason: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -34,6 +111,75 @@ impl PositionLeg {
         }
     }
     
+    /// Open a new lot
+    pub fn add_lot(&mut self, lot: Lot) {
+        self.add(lot.contracts, lot.price);
+        self.lots.push_back(lot);
+    }
+
+    /// Open lots, synthesizing one for legs recorded before lot tracking
+    fn open_lots(&self) -> VecDeque<Lot> {
+        let tracked: f64 = self.lots.iter().map(|l| l.contracts).sum();
+        let mut lots = self.lots.clone();
+        let untracked = self.contracts - tracked;
+        if untracked > 1e-9 {
+            lots.push_front(Lot {
+                order_id: "legacy".to_string(),
+                contracts: untracked,
+                price: self.avg_price,
+                fees: 0.0,
+                opened_at: String::new(),
+                pattern: None,
+                account: None,
+            });
+        }
+        lots
+    }
+
+    /// Close up to `contracts`, consuming lots oldest-first
+    /// Entry price per slice is the lot price (FIFO) or the leg average (AverageCost)
+    pub fn close(&mut self, contracts: f64, method: CostMethod) -> Vec<LotSlice> {
+        let mut lots = self.open_lots();
+        let avg_price = self.avg_price;
+        let mut remaining = contracts.min(self.contracts);
+        let mut slices = Vec::new();
+
+        while remaining > 1e-9 {
+            let Some(lot) = lots.front_mut() else { break };
+            let take = remaining.min(lot.contracts);
+            let fee_share = if lot.contracts > 0.0 { lot.fees * take / lot.contracts } else { 0.0 };
+            slices.push(LotSlice {
+                contracts: take,
+                entry_price: match method {
+                    CostMethod::Fifo => lot.price,
+                    CostMethod::AverageCost => avg_price,
+                },
+                fees: fee_share,
+                pattern: lot.pattern.clone(),
+            });
+            lot.contracts -= take;
+            lot.fees -= fee_share;
+            remaining -= take;
+            if lot.contracts <= 1e-9 {
+                lots.pop_front();
+            }
+        }
+
+        let closed: f64 = slices.iter().map(|s| s.contracts).sum();
+        let removed_cost: f64 = slices.iter().map(|s| s.contracts * s.entry_price).sum();
+        self.lots = lots;
+        self.contracts -= closed;
+        if self.contracts > 1e-9 {
+            self.cost_basis -= removed_cost;
+            self.avg_price = self.cost_basis / self.contracts;
+        } else {
+            self.contracts = 0.0;
+            self.cost_basis = 0.0;
+            self.avg_price = 0.0;
+        }
+        slices
+    }
+
     /// Unrealized P&L based on current market price
     pub fn unrealized_pnl(&self, current_price: f64) -> f64 {
         let current_value = self.contracts * current_price;
@@ -72,8 +218,12 @@ pub struct ArbPosition {
     /// Polymarket NO position
     pub poly_no: PositionLeg,
     
-    /// Total fees paid (Kalshi fees)
+    /// Fees attached to open contracts (Kalshi fees)
     pub total_fees: f64,
+
+    /// P&L already realized by partial closes
+    #[serde(default)]
+    pub closed_pnl: f64,
     
     /// Timestamp when position was opened
     pub opened_at: String,
@@ -97,6 +247,52 @@ impl ArbPosition {
         }
     }
     
+    /// Leg for a platform/side pair
+    pub fn leg_mut(&mut self, platform: &str, side: &str) -> Option<&mut PositionLeg> {
+        match (platform, side) {
+            ("kalshi", "yes") => Some(&mut self.kalshi_yes),
+            ("kalshi", "no") => Some(&mut self.kalshi_no),
+            ("polymarket", "yes") => Some(&mut self.poly_yes),
+            ("polymarket", "no") => Some(&mut self.poly_no),
+            _ => None,
+        }
+    }
+
+    fn legs(&self) -> [(&'static str, &'static str, &PositionLeg); 4] {
+        [
+            ("kalshi", "yes", &self.kalshi_yes),
+            ("kalshi", "no", &self.kalshi_no),
+            ("polymarket", "yes", &self.poly_yes),
+            ("polymarket", "no", &self.poly_no),
+        ]
+    }
+
+    /// Lot-level view of a resolution (winning side exits at $1, losing at $0)
+    pub fn resolution_lots(&self, outcome_yes_won: bool, method: CostMethod, closed_at: &str) -> Vec<RealizedLot> {
+        let mut out = Vec::new();
+        for (platform, side, leg) in self.legs() {
+            let exit_price = if (side == "yes") == outcome_yes_won { 1.0 } else { 0.0 };
+            let mut leg = leg.clone();
+            let contracts = leg.contracts;
+            for slice in leg.close(contracts, method) {
+                out.push(RealizedLot {
+                    market_id: self.market_id.clone(),
+                    platform: platform.to_string(),
+                    side: side.to_string(),
+                    contracts: slice.contracts,
+                    entry_price: slice.entry_price,
+                    exit_price,
+                    fees: slice.fees,
+                    pnl: slice.contracts * (exit_price - slice.entry_price) - slice.fees,
+                    pattern: slice.pattern,
+                    closed_at: closed_at.to_string(),
+                    exit_reason: Some(RESOLUTION_EXIT.to_string()),
+                });
+            }
+        }
+        out
+    }
+
     /// Total contracts across all legs
     pub fn total_contracts(&self) -> f64 {
         self.kalshi_yes.contracts + self.kalshi_no.contracts +
@@ -143,7 +339,7 @@ impl ArbPosition {
             self.kalshi_no.contracts + self.poly_no.contracts
         };
         
-        self.realized_pnl = Some(payout - self.total_cost());
+        self.realized_pnl = Some(self.closed_pnl + payout - self.total_cost());
         self.status = "resolved".to_string();
     }
 }
@@ -175,7 +371,7 @@ pub struct PositionSummary {
 }
 
 /// Position tracker with persistence
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionTracker {
     /// All positions keyed by market_id
     positions: HashMap<String, ArbPosition>,
@@ -188,6 +384,30 @@ pub struct PositionTracker {
 
     /// Cumulative all-time P&L
     pub all_time_pnl: f64,
+
+    /// Cost-basis method for partial closes
+    #[serde(default)]
+    pub cost_method: CostMethod,
+
+    /// Realized lots closed within the retention window (closes + resolutions)
+    #[serde(default)]
+    realized: Vec<RealizedLot>,
+
+    /// Older lots merged per day, platform, pattern and exit reason: market and side are
+    /// cleared, prices are contract-weighted and `closed_at` is the day's midnight (UTC)
+    #[serde(default)]
+    realized_daily: Vec<RealizedLot>,
+
+    #[serde(skip, default = "default_lot_retention")]
+    lot_retention: Duration,
+
+    /// Unix day before which `realized` has been rolled up
+    #[serde(skip)]
+    rolled_up_to: i64,
+}
+
+fn default_lot_retention() -> Duration {
+    LOT_RETENTION
 }
 
 /// Data structure for serialization
@@ -197,6 +417,9 @@ struct SaveData {
     daily_realized_pnl: f64,
     trading_date: String,
     all_time_pnl: f64,
+    cost_method: CostMethod,
+    realized: Vec<RealizedLot>,
+    realized_daily: Vec<RealizedLot>,
 }
 
 impl Default for PositionTracker {
@@ -213,8 +436,25 @@ impl PositionTracker {
             daily_realized_pnl: 0.0,
             trading_date: today_string(),
             all_time_pnl: 0.0,
+            cost_method: CostMethod::default(),
+            realized: Vec::new(),
+            realized_daily: Vec::new(),
+            lot_retention: LOT_RETENTION,
+            rolled_up_to: 0,
         }
     }
+
+    pub fn with_cost_method(mut self, method: CostMethod) -> Self {
+        self.cost_method = method;
+        self
+    }
+
+    /// Keep realized lots one by one for `retention` (whole days); older ones are merged per day
+    pub fn with_lot_retention(mut self, retention: Duration) -> Self {
+        self.lot_retention = retention;
+        self.rolled_up_to = 0;
+        self
+    }
     
     /// Load from file or create new
     pub fn load() -> Self {
@@ -251,11 +491,11 @@ impl PositionTracker {
     }
     
     /// Save to file
-    pub fn save(&self) -> Result<()> {
+    pub fn save(&self) -> Result<(), StateStoreError> {
         self.save_to(POSITION_FILE)
     }
     
-    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<(), StateStoreError> {
         let json = serde_json::to_string_pretty(self)?;
         std::fs::write(path, json)?;
         Ok(())
@@ -269,6 +509,9 @@ impl PositionTracker {
             daily_realized_pnl: self.daily_realized_pnl,
             trading_date: self.trading_date.clone(),
             all_time_pnl: self.all_time_pnl,
+            cost_method: self.cost_method,
+            realized: self.realized.clone(),
+            realized_daily: self.realized_daily.clone(),
         };
         // Try to spawn on runtime; if no runtime, save synchronously
         if tokio::runtime::Handle::try_current().is_ok() {
@@ -290,17 +533,28 @@ impl PositionTracker {
 
     /// Record a fill without saving
     pub fn record_fill_internal(&mut self, fill: &FillRecord) {
+        if fill.action == "sell" {
+            self.record_close(fill);
+            return;
+        }
+
         let position = self.positions
             .entry(fill.market_id.clone())
             .or_insert_with(|| ArbPosition::new(&fill.market_id, &fill.description));
 
-        match (fill.platform.as_str(), fill.side.as_str()) {
-            ("kalshi", "yes") => position.kalshi_yes.add(fill.contracts, fill.price),
-            ("kalshi", "no") => position.kalshi_no.add(fill.contracts, fill.price),
-            ("polymarket", "yes") => position.poly_yes.add(fill.contracts, fill.price),
-            ("polymarket", "no") => position.poly_no.add(fill.contracts, fill.price),
-            _ => warn!("[POSITIONS] Unknown platform/side: {}/{}", fill.platform, fill.side),
-        }
+        let Some(leg) = position.leg_mut(&fill.platform, &fill.side) else {
+            warn!("[POSITIONS] Unknown platform/side: {}/{}", fill.platform, fill.side);
+            return;
+        };
+        leg.add_lot(Lot {
+            order_id: fill.order_id.clone(),
+            contracts: fill.contracts,
+            price: fill.price,
+            fees: fill.fees,
+            opened_at: fill.timestamp.clone(),
+            pattern: fill.pattern.clone(),
+            account: fill.account.clone(),
+        });
 
         position.total_fees += fill.fees;
 
@@ -308,6 +562,63 @@ impl PositionTracker {
               fill.platform, fill.side, fill.market_id,
               fill.price * 100.0, fill.contracts, fill.fees);
     }
+
+    /// Close contracts from a sell fill and book realized P&L per lot
+    fn record_close(&mut self, fill: &FillRecord) {
+        let method = self.cost_method;
+        let Some(position) = self.positions.get_mut(&fill.market_id) else {
+            warn!("[POSITIONS] Sell for unknown market {}", fill.market_id);
+            return;
+        };
+        let Some(leg) = position.leg_mut(&fill.platform, &fill.side) else {
+            warn!("[POSITIONS] Unknown platform/side: {}/{}", fill.platform, fill.side);
+            return;
+        };
+
+        let slices = leg.close(fill.contracts, method);
+        let closed: f64 = slices.iter().map(|s| s.contracts).sum();
+        if closed + 1e-9 < fill.contracts {
+            warn!("[POSITIONS] Sell of {:.0} exceeds open {:.0} on {} {} {}",
+                  fill.contracts, closed, fill.platform, fill.side, fill.market_id);
+        }
+
+        let mut total_pnl = 0.0;
+        let mut lots = Vec::with_capacity(slices.len());
+        for slice in slices {
+            let exit_fees = if closed > 0.0 { fill.fees * slice.contracts / closed } else { 0.0 };
+            let fees = slice.fees + exit_fees;
+            let pnl = slice.contracts * (fill.price - slice.entry_price) - fees;
+            position.total_fees -= slice.fees;
+            total_pnl += pnl;
+            lots.push(RealizedLot {
+                market_id: fill.market_id.clone(),
+                platform: fill.platform.clone(),
+                side: fill.side.clone(),
+                contracts: slice.contracts,
+                entry_price: slice.entry_price,
+                exit_price: fill.price,
+                fees,
+                pnl,
+                pattern: slice.pattern,
+                closed_at: fill.timestamp.clone(),
+                exit_reason: fill.exit_reason.clone(),
+            });
+        }
+
+        position.closed_pnl += total_pnl;
+        if position.total_contracts() <= 1e-9 {
+            position.status = "closed".to_string();
+            position.realized_pnl = Some(position.closed_pnl);
+        }
+        for lot in lots {
+            self.book_realized(lot);
+        }
+        self.daily_realized_pnl += total_pnl;
+        self.all_time_pnl += total_pnl;
+
+        info!("[POSITIONS] Closed {} {} {} x{:.0} @{:.1}¢, P&L: ${:.2}",
+              fill.platform, fill.side, fill.market_id, closed, fill.price * 100.0, total_pnl);
+    }
     
     /// Get or create position for a market
     pub fn get_or_create(&mut self, market_id: &str, description: &str) -> &mut ArbPosition {
@@ -323,9 +634,25 @@ impl PositionTracker {
     
     /// Mark a position as resolved
     pub fn resolve_position(&mut self, market_id: &str, yes_won: bool) -> Option<f64> {
+        let pnl = self.resolve_position_at(market_id, yes_won, &chrono::Utc::now().to_rfc3339());
+        if pnl.is_some() {
+            self.save_async();
+        }
+        pnl
+    }
+
+    /// Resolve with an explicit timestamp, without saving (journal replay)
+    pub fn resolve_position_at(&mut self, market_id: &str, yes_won: bool, resolved_at: &str) -> Option<f64> {
+        let method = self.cost_method;
         if let Some(position) = self.positions.get_mut(market_id) {
+            // Partial closes were booked when they happened; only the resolution delta is new
+            let already_booked = position.closed_pnl;
+            let lots = position.resolution_lots(yes_won, method, resolved_at);
             position.resolve(yes_won);
-            let pnl = position.realized_pnl.unwrap_or(0.0);
+            let pnl = position.realized_pnl.unwrap_or(0.0) - already_booked;
+            for lot in lots {
+                self.book_realized(lot);
+            }
             
             self.daily_realized_pnl += pnl;
             self.all_time_pnl += pnl;
@@ -333,7 +660,6 @@ impl PositionTracker {
             info!("[POSITIONS] Resolved {}: {} won, P&L: ${:.2}",
                   market_id, if yes_won { "YES" } else { "NO" }, pnl);
             
-            self.save_async();
             Some(pnl)
         } else {
             None
@@ -364,6 +690,102 @@ impl PositionTracker {
         summary
     }
     
+    /// All positions (open, closed and resolved)
+    pub fn get_positions(&self) -> Vec<&ArbPosition> {
+        self.positions.values().collect()
+    }
+
+    /// Realized lots closed within a time range. Lots past the retention come back as their
+    /// per-day rows, which fall in a range by the day's midnight (UTC).
+    pub fn realized_lots<R: RangeBounds<chrono::DateTime<chrono::Utc>>>(&self, range: R) -> Vec<&RealizedLot> {
+        self.realized_daily.iter()
+            .chain(&self.realized)
+            .filter(|r| {
+                chrono::DateTime::parse_from_rfc3339(&r.closed_at)
+                    .map(|t| range.contains(&t.with_timezone(&chrono::Utc)))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Realized P&L for lots closed within a time range
+    pub fn get_realized_pnl<R: RangeBounds<chrono::DateTime<chrono::Utc>>>(&self, range: R) -> f64 {
+        self.realized_lots(range).iter().map(|r| r.pnl).sum()
+    }
+
+    /// Realized P&L by pattern tag ("untagged" for fills without one)
+    pub fn realized_pnl_by_pattern<R: RangeBounds<chrono::DateTime<chrono::Utc>>>(&self, range: R) -> HashMap<String, f64> {
+        let mut out = HashMap::new();
+        for lot in self.realized_lots(range) {
+            let tag = lot.pattern.clone().unwrap_or_else(|| "untagged".to_string());
+            *out.entry(tag).or_insert(0.0) += lot.pnl;
+        }
+        out
+    }
+
+    /// Realized P&L by exit reason (`close` for sells that give none)
+    pub fn realized_pnl_by_exit<R: RangeBounds<chrono::DateTime<chrono::Utc>>>(&self, range: R) -> HashMap<String, f64> {
+        let mut out = HashMap::new();
+        for lot in self.realized_lots(range) {
+            let reason = lot.exit_reason.clone().unwrap_or_else(|| CLOSE_EXIT.to_string());
+            *out.entry(reason).or_insert(0.0) += lot.pnl;
+        }
+        out
+    }
+
+    /// Keep a realized lot, merging it into its day's row if it closed before the retention window
+    fn book_realized(&mut self, lot: RealizedLot) {
+        let cutoff = self.roll_up_realized();
+        match unix_day(&lot.closed_at) {
+            Some(day) if day < cutoff => self.merge_daily(lot, day),
+            Some(_) => self.realized.push(lot),
+            None => warn!("[POSITIONS] Dropping realized lot on {} with bad close time {:?}", lot.market_id, lot.closed_at),
+        }
+    }
+
+    /// Merge lots that aged out of the retention window into per-day rows (once per day);
+    /// returns the first unix day still kept lot by lot
+    fn roll_up_realized(&mut self) -> i64 {
+        let cutoff = (chrono::Utc::now().timestamp() - self.lot_retention.as_secs() as i64).div_euclid(DAY_SECS);
+        if cutoff > self.rolled_up_to {
+            self.rolled_up_to = cutoff;
+            for lot in std::mem::take(&mut self.realized) {
+                match unix_day(&lot.closed_at) {
+                    Some(day) if day < cutoff => self.merge_daily(lot, day),
+                    Some(_) => self.realized.push(lot),
+                    // Never matched a range; nothing to carry over
+                    None => {}
+                }
+            }
+        }
+        cutoff
+    }
+
+    fn merge_daily(&mut self, lot: RealizedLot, day: i64) {
+        let closed_at = chrono::DateTime::from_timestamp(day * DAY_SECS, 0).unwrap_or_default().to_rfc3339();
+        let row = self.realized_daily.iter_mut().find(|r| {
+            r.closed_at == closed_at && r.platform == lot.platform && r.pattern == lot.pattern && r.exit_reason == lot.exit_reason
+        });
+        match row {
+            Some(row) => {
+                let contracts = row.contracts + lot.contracts;
+                if contracts > 0.0 {
+                    row.entry_price = (row.entry_price * row.contracts + lot.entry_price * lot.contracts) / contracts;
+                    row.exit_price = (row.exit_price * row.contracts + lot.exit_price * lot.contracts) / contracts;
+                }
+                row.contracts = contracts;
+                row.fees += lot.fees;
+                row.pnl += lot.pnl;
+            }
+            None => self.realized_daily.push(RealizedLot {
+                market_id: String::new(),
+                side: String::new(),
+                closed_at,
+                ..lot
+            }),
+        }
+    }
+
     /// Get all open positions
     pub fn open_positions(&self) -> Vec<&ArbPosition> {
         self.positions.values()
@@ -385,7 +807,7 @@ impl PositionTracker {
 }
 
 /// Record of a single fill
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FillRecord {
     pub market_id: String,
     pub description: String,
@@ -394,12 +816,27 @@ pub struct FillRecord {
     pub contracts: f64,
     pub price: f64,
     pub fees: f64,
+    #[serde(default = "default_fill_action")]
+    pub action: String,     // "buy" or "sell"
+    /// Strategy/pattern attribution tag
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Venue account the order went through (None = the venue's default account)
+    #[serde(default)]
+    pub account: Option<String>,
+    /// Why a sell closes the position (e.g. `TIMEOUT_EXIT`; None = a plain close)
+    #[serde(default)]
+    pub exit_reason: Option<String>,
     #[allow(dead_code)]
     pub order_id: String,
     #[allow(dead_code)]
     pub timestamp: String,
 }
 
+fn default_fill_action() -> String {
+    "buy".to_string()
+}
+
 impl FillRecord {
     pub fn new(
         market_id: &str,
@@ -419,10 +856,38 @@ impl FillRecord {
             contracts,
             price,
             fees,
+            action: "buy".to_string(),
+            pattern: None,
+            account: None,
+            exit_reason: None,
             order_id: order_id.to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
+
+    /// Mark as a closing (sell) fill
+    pub fn as_sell(mut self) -> Self {
+        self.action = "sell".to_string();
+        self
+    }
+
+    /// Attach a pattern attribution tag
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    /// Attach the venue account the order was routed through
+    pub fn with_account(mut self, account: &str) -> Self {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    /// Attribute the realized P&L of a sell to an exit reason
+    pub fn with_exit_reason(mut self, reason: &str) -> Self {
+        self.exit_reason = Some(reason.to_string());
+        self
+    }
 }
 
 #[allow(dead_code)]
@@ -433,6 +898,10 @@ pub fn create_position_tracker() -> SharedPositionTracker {
     Arc::new(RwLock::new(PositionTracker::load()))
 }
 
+fn unix_day(rfc3339: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(rfc3339).ok().map(|t| t.timestamp().div_euclid(DAY_SECS))
+}
+
 fn today_string() -> String {
     chrono::Utc::now().format("%Y-%m-%d").to_string()
 }
@@ -459,12 +928,44 @@ pub fn create_position_channel() -> (PositionChannel, mpsc::UnboundedReceiver<Fi
 }
 
 pub async fn position_writer_loop(
+    rx: mpsc::UnboundedReceiver<FillRecord>,
+    tracker: Arc<RwLock<PositionTracker>>,
+) {
+    position_writer_loop_with_journal(rx, tracker, None, None).await
+}
+
+/// Writer loop that journals each fill before applying it, checkpointing when due,
+/// and copies applied fills to the trade blotter
+pub async fn position_writer_loop_with_journal(
     mut rx: mpsc::UnboundedReceiver<FillRecord>,
     tracker: Arc<RwLock<PositionTracker>>,
+    journal: Option<SharedJournal>,
+    blotter: Option<SharedBlotter>,
 ) {
     let mut batch = Vec::with_capacity(16);
     let mut interval = tokio::time::interval(Duration::from_millis(100));
 
+    let apply = |guard: &mut PositionTracker, batch: &mut Vec<FillRecord>| {
+        for fill in batch.drain(..) {
+            if let Some(journal) = &journal {
+                Journal::record(journal, JournalEvent::Fill(fill.clone()));
+            }
+            guard.record_fill_internal(&fill);
+            if let Some(blotter) = &blotter {
+                blotter.record(&fill);
+            }
+        }
+        if let Some(journal) = &journal {
+            let mut journal = journal.lock().unwrap();
+            if journal.needs_checkpoint() {
+                if let Err(e) = journal.checkpoint(guard) {
+                    warn!("[POSITIONS] Journal checkpoint failed: {}", e);
+                }
+            }
+        }
+        guard.save_async();
+    };
+
     loop {
         tokio::select! {
             biased;
@@ -473,19 +974,13 @@ pub async fn position_writer_loop(
                 batch.push(fill);
                 if batch.len() >= 16 {
                     let mut guard = tracker.write().await;
-                    for fill in batch.drain(..) {
-                        guard.record_fill_internal(&fill);
-                    }
-                    guard.save_async();
+                    apply(&mut guard, &mut batch);
                 }
             }
             _ = interval.tick() => {
                 if !batch.is_empty() {
                     let mut guard = tracker.write().await;
-                    for fill in batch.drain(..) {
-                        guard.record_fill_internal(&fill);
-                    }
-                    guard.save_async();
+                    apply(&mut guard, &mut batch);
                 }
             }
         }
@@ -559,4 +1054,78 @@ mod tests {
         assert!((pos.realized_pnl.unwrap() - 0.50).abs() < 0.001);
         assert_eq!(pos.status, "resolved");
     }
+
+    fn buy(price: f64, contracts: f64, fees: f64, pattern: &str) -> FillRecord {
+        FillRecord::new("M", "m", "kalshi", "yes", contracts, price, fees, "o").with_pattern(pattern)
+    }
+
+    #[test]
+    fn test_fifo_vs_average_cost() {
+        let sell = FillRecord::new("M", "m", "kalshi", "yes", 10.0, 0.60, 0.0, "s").as_sell();
+
+        let mut fifo = PositionTracker::new();
+        fifo.record_fill_internal(&buy(0.40, 10.0, 0.0, "p72"));
+        fifo.record_fill_internal(&buy(0.50, 10.0, 0.0, "p74"));
+        fifo.record_fill_internal(&sell);
+        // Oldest lot (40¢) closes first: 10 × 20¢
+        assert!((fifo.all_time_pnl - 2.0).abs() < 1e-9);
+        let leg = &fifo.get("M").unwrap().kalshi_yes;
+        assert!((leg.contracts - 10.0).abs() < 1e-9);
+        assert!((leg.avg_price - 0.50).abs() < 1e-9);
+        let by_pattern = fifo.realized_pnl_by_pattern(..);
+        assert!((by_pattern["p72"] - 2.0).abs() < 1e-9);
+
+        let mut avg = PositionTracker::new().with_cost_method(CostMethod::AverageCost);
+        avg.record_fill_internal(&buy(0.40, 10.0, 0.0, "p72"));
+        avg.record_fill_internal(&buy(0.50, 10.0, 0.0, "p74"));
+        avg.record_fill_internal(&sell);
+        // Average 45¢: 10 × 15¢
+        assert!((avg.all_time_pnl - 1.5).abs() < 1e-9);
+        assert!((avg.get("M").unwrap().kalshi_yes.avg_price - 0.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fees_follow_lots_and_resolution_not_double_counted() {
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&buy(0.40, 10.0, 0.20, "p77"));
+        // Sell half at 50¢ with 5¢ exit fee: 5 × 10¢ − 10¢ entry fee share − 5¢
+        tracker.record_fill_internal(&FillRecord::new("M", "m", "kalshi", "yes", 5.0, 0.50, 0.05, "s").as_sell());
+        assert!((tracker.all_time_pnl - 0.35).abs() < 1e-9);
+        assert!((tracker.get("M").unwrap().total_fees - 0.10).abs() < 1e-9);
+
+        // Remaining 5 win: 5 × 60¢ − 10¢ remaining entry fee
+        let delta = tracker.resolve_position("M", true).unwrap();
+        assert!((delta - 2.90).abs() < 1e-9);
+        assert!((tracker.all_time_pnl - 3.25).abs() < 1e-9);
+        assert!((tracker.get_realized_pnl(..) - 3.25).abs() < 1e-9);
+        assert!((tracker.get("M").unwrap().realized_pnl.unwrap() - 3.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_old_lots_roll_up_into_daily_rows() {
+        let mut tracker = PositionTracker::new().with_lot_retention(Duration::from_secs(86_400));
+        tracker.record_fill_internal(&buy(0.40, 30.0, 0.0, "p72"));
+        let sell = |contracts: f64, price: f64, at: &str| {
+            let mut fill = FillRecord::new("M", "m", "kalshi", "yes", contracts, price, 0.0, "s").as_sell();
+            fill.timestamp = at.to_string();
+            fill
+        };
+        tracker.record_fill_internal(&sell(10.0, 0.50, "2020-01-01T10:00:00Z"));
+        tracker.record_fill_internal(&sell(10.0, 0.60, "2020-01-01T15:00:00Z"));
+        tracker.record_fill_internal(&sell(10.0, 0.70, &chrono::Utc::now().to_rfc3339()));
+
+        // The two old sells share one row at midnight; the recent one is kept as is
+        assert_eq!(tracker.realized.len(), 1);
+        assert_eq!(tracker.realized_daily.len(), 1);
+        let row = &tracker.realized_daily[0];
+        assert_eq!(row.closed_at, "2020-01-01T00:00:00+00:00");
+        assert!((row.contracts - 20.0).abs() < 1e-9);
+        assert!((row.exit_price - 0.55).abs() < 1e-9);
+        assert!((row.pnl - 3.0).abs() < 1e-9);
+
+        assert!((tracker.get_realized_pnl(..) - 6.0).abs() < 1e-9);
+        let day = chrono::DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        assert!((tracker.get_realized_pnl(day..day + chrono::Duration::days(1)) - 3.0).abs() < 1e-9);
+        assert!((tracker.realized_pnl_by_pattern(..)["p72"] - 6.0).abs() < 1e-9);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,258 @@
+// src/provider_registry.rs
+// Feed provider identities: built-in venues plus plugin feeds registered at runtime
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::info;
+
+use crate::types::{MarketType, Platform};
+
+/// Built-in venues, in `Platform` declaration order
+pub const BUILTIN_PROVIDERS: [Platform; 9] = [
+    Platform::Kalshi, Platform::Polymarket, Platform::DraftKings, Platform::FanDuel, Platform::BetMGM,
+    Platform::Caesars, Platform::PointsBet, Platform::Barstool, Platform::ESPN,
+];
+
+/// Feed provider: a built-in venue (matched directly, no registry lookup) or a
+/// plugin feed registered at runtime through [`ProviderRegistry::register`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderId {
+    Builtin(Platform),
+    Plugin(PluginId),
+}
+
+/// Registry slot of a plugin provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PluginId(u16);
+
+impl ProviderId {
+    /// The built-in venue, if this is one; venue-keyed components (latency
+    /// engine, risk, execution) only see built-in providers
+    #[inline]
+    pub fn platform(self) -> Option<Platform> {
+        match self {
+            ProviderId::Builtin(platform) => Some(platform),
+            ProviderId::Plugin(_) => None,
+        }
+    }
+
+    pub fn is_builtin(self) -> bool {
+        matches!(self, ProviderId::Builtin(_))
+    }
+
+    /// Capability descriptor (built-ins are static; plugins come from the registry)
+    pub fn capabilities(self) -> ProviderCapabilities {
+        match self {
+            ProviderId::Builtin(platform) => ProviderCapabilities::builtin(platform),
+            ProviderId::Plugin(id) => providers().plugin(id).map(|e| e.capabilities).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Platform> for ProviderId {
+    #[inline]
+    fn from(platform: Platform) -> Self {
+        ProviderId::Builtin(platform)
+    }
+}
+
+impl PartialEq<Platform> for ProviderId {
+    fn eq(&self, other: &Platform) -> bool {
+        *self == ProviderId::Builtin(*other)
+    }
+}
+
+impl fmt::Display for ProviderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderId::Builtin(platform) => platform.fmt(f),
+            ProviderId::Plugin(id) => match providers().plugin(*id) {
+                Some(entry) => f.write_str(&entry.name),
+                None => write!(f, "PLUGIN#{}", id.0),
+            },
+        }
+    }
+}
+
+impl FromStr for ProviderId {
+    type Err = anyhow::Error;
+
+    /// Case-insensitive: built-in names first, then registered plugins
+    fn from_str(s: &str) -> Result<Self> {
+        providers().lookup(s).ok_or_else(|| anyhow::anyhow!("unknown feed provider '{}'", s))
+    }
+}
+
+/// Built-ins keep the `Platform` encoding; plugins serialize as their registered name
+impl Serialize for ProviderId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            ProviderId::Builtin(platform) => platform.serialize(serializer),
+            ProviderId::Plugin(_) => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ProviderId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// What a provider's feed offers the aggregator
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderCapabilities {
+    /// Market types the feed quotes (empty = any)
+    pub market_types: Vec<MarketType>,
+    /// `FeedClient::ping` measures a real round trip; otherwise the nominal latency is used
+    pub ping: bool,
+    /// Updates carry the provider's own wall-clock timestamp
+    pub provider_timestamps: bool,
+    /// Expected one-way latency before any sample has been taken
+    pub nominal_latency_ns: u64,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self { market_types: Vec::new(), ping: false, provider_timestamps: false, nominal_latency_ns: 150_000 }
+    }
+}
+
+impl ProviderCapabilities {
+    pub fn builtin(platform: Platform) -> Self {
+        let nominal_latency_ns = match platform {
+            Platform::Kalshi => 50_000,      // 50μs
+            Platform::Polymarket => 75_000,  // 75μs
+            Platform::DraftKings => 200_000, // 200μs (typical sportsbook)
+            Platform::FanDuel => 180_000,    // 180μs
+            _ => 150_000,                    // 150μs default
+        };
+        // Kalshi orderbook deltas carry their own `ts` (see kalshi_feed)
+        let provider_timestamps = platform == Platform::Kalshi;
+        Self { ping: true, provider_timestamps, nominal_latency_ns, ..Default::default() }
+    }
+
+    pub fn supports(&self, market_type: MarketType) -> bool {
+        self.market_types.is_empty() || self.market_types.contains(&market_type)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PluginEntry {
+    name: Arc<str>,
+    capabilities: ProviderCapabilities,
+}
+
+/// Plugin providers registered at runtime (ids are stable for the process lifetime)
+#[derive(Debug, Default)]
+pub struct ProviderRegistry {
+    plugins: RwLock<Vec<PluginEntry>>,
+}
+
+static PROVIDERS: OnceLock<ProviderRegistry> = OnceLock::new();
+
+/// Process-wide provider registry
+pub fn providers() -> &'static ProviderRegistry {
+    PROVIDERS.get_or_init(ProviderRegistry::default)
+}
+
+impl ProviderRegistry {
+    /// Register a plugin provider under a case-insensitive name; registering an
+    /// existing name replaces its capabilities and returns the same id
+    pub fn register(&self, name: &str, capabilities: ProviderCapabilities) -> Result<ProviderId> {
+        let name = name.trim().to_ascii_uppercase();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            bail!("invalid feed provider name '{}'", name);
+        }
+        if BUILTIN_PROVIDERS.iter().any(|p| p.to_string() == name) {
+            bail!("'{}' is a built-in feed provider", name);
+        }
+
+        let mut plugins = self.plugins.write().unwrap();
+        if let Some(slot) = plugins.iter().position(|e| *e.name == name) {
+            plugins[slot].capabilities = capabilities;
+            return Ok(ProviderId::Plugin(PluginId(slot as u16)));
+        }
+        let Ok(slot) = u16::try_from(plugins.len()) else {
+            bail!("feed provider registry full");
+        };
+        info!("Registered feed provider plugin: {} ({:?})", name, capabilities);
+        plugins.push(PluginEntry { name: name.into(), capabilities });
+        Ok(ProviderId::Plugin(PluginId(slot)))
+    }
+
+    /// Resolve a provider name (case-insensitive), built-ins first
+    pub fn lookup(&self, name: &str) -> Option<ProviderId> {
+        let name = name.trim();
+        if let Some(platform) = BUILTIN_PROVIDERS.into_iter().find(|p| p.to_string().eq_ignore_ascii_case(name)) {
+            return Some(platform.into());
+        }
+        let plugins = self.plugins.read().unwrap();
+        plugins.iter()
+            .position(|e| e.name.eq_ignore_ascii_case(name))
+            .map(|slot| ProviderId::Plugin(PluginId(slot as u16)))
+    }
+
+    /// Registered plugin providers
+    pub fn plugins(&self) -> Vec<ProviderId> {
+        (0..self.plugins.read().unwrap().len()).map(|slot| ProviderId::Plugin(PluginId(slot as u16))).collect()
+    }
+
+    fn plugin(&self, id: PluginId) -> Option<PluginEntry> {
+        self.plugins.read().unwrap().get(id.0 as usize).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtins_resolve_without_registration() {
+        for platform in BUILTIN_PROVIDERS {
+            let id: ProviderId = platform.to_string().to_lowercase().parse().unwrap();
+            assert_eq!(id, platform);
+            assert_eq!(id.platform(), Some(platform));
+            assert_eq!(id.to_string(), platform.to_string());
+        }
+        assert_eq!(ProviderId::from(Platform::Kalshi).capabilities().nominal_latency_ns, 50_000);
+        let json = serde_json::to_string(&ProviderId::from(Platform::DraftKings)).unwrap();
+        assert_eq!(json, serde_json::to_string(&Platform::DraftKings).unwrap());
+        assert_eq!(serde_json::from_str::<ProviderId>(&json).unwrap(), Platform::DraftKings);
+        assert!("NOT_A_FEED_XYZ".parse::<ProviderId>().is_err());
+    }
+
+    #[test]
+    fn plugin_registration_round_trips_by_name() {
+        let caps = ProviderCapabilities {
+            market_types: vec![MarketType::PlayerProp],
+            nominal_latency_ns: 90_000,
+            ..Default::default()
+        };
+        let id = providers().register("test-props-feed", caps.clone()).unwrap();
+
+        assert_eq!(id.platform(), None);
+        assert_eq!(id.to_string(), "TEST-PROPS-FEED");
+        assert_eq!("Test-Props-Feed".parse::<ProviderId>().unwrap(), id);
+        assert!(providers().plugins().contains(&id));
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"TEST-PROPS-FEED\"");
+        assert!(id.capabilities().supports(MarketType::PlayerProp));
+        assert!(!id.capabilities().supports(MarketType::Moneyline));
+
+        // Re-registering updates capabilities in place
+        let again = providers().register("TEST-PROPS-FEED", ProviderCapabilities::default()).unwrap();
+        assert_eq!(again, id);
+        assert!(id.capabilities().supports(MarketType::Moneyline));
+    }
+
+    #[test]
+    fn rejects_builtin_and_malformed_names() {
+        assert!(providers().register("kalshi", ProviderCapabilities::default()).is_err());
+        assert!(providers().register("  ", ProviderCapabilities::default()).is_err());
+        assert!(providers().register("bad name", ProviderCapabilities::default()).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+// src/quote_normalizer.rs
+// Quote normalization - venue quoting conventions to one fixed-point implied probability
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::provider_registry::ProviderId;
+use crate::types::{Platform, PriceCents, SizeCents, NO_PRICE};
+
+/// Implied probability in basis points (0 = never, 10_000 = certain)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Probability(pub u16);
+
+impl Probability {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(10_000);
+
+    /// From a 0–1 fraction (None outside [0, 1] or not finite)
+    pub fn from_fraction(p: f64) -> Option<Self> {
+        (p.is_finite() && (0.0..=1.0).contains(&p)).then(|| Self((p * 10_000.0).round() as u16))
+    }
+
+    #[inline(always)]
+    pub const fn bps(self) -> u16 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn as_fraction(self) -> f64 {
+        self.0 as f64 / 10_000.0
+    }
+
+    /// Probability of the other side of a binary market
+    #[inline(always)]
+    pub const fn complement(self) -> Self {
+        Self(10_000 - self.0)
+    }
+
+    /// Whole cents under `rounding`, kept inside 1–99 (a tradable binary price)
+    pub fn to_cents(self, rounding: Rounding) -> PriceCents {
+        (rounding.apply(self.0 as u32, 100) / 100).clamp(1, 99) as PriceCents
+    }
+}
+
+impl std::fmt::Display for Probability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2}%", self.0 as f64 / 100.0)
+    }
+}
+
+/// A price as a venue quotes it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quote {
+    /// Cents per $1 contract, 1–99 (Kalshi)
+    Cents(f64),
+    /// Decimal probability, 0–1 (Polymarket)
+    Decimal(f64),
+    /// American moneyline odds, e.g. -150 / +130 (sportsbooks)
+    American(i32),
+}
+
+impl Quote {
+    /// Raw implied probability as a fraction (vig included), None when malformed
+    pub fn implied(self) -> Option<f64> {
+        let p = match self {
+            Quote::Cents(c) => c / 100.0,
+            Quote::Decimal(p) => p,
+            // |-100| and +100 are both even money; anything in between is not a price
+            Quote::American(odds) if odds <= -100 => -odds as f64 / (100.0 - odds as f64),
+            Quote::American(odds) if odds >= 100 => 100.0 / (100.0 + odds as f64),
+            Quote::American(_) => return None,
+        };
+        (p.is_finite() && (0.0..=1.0).contains(&p)).then_some(p)
+    }
+}
+
+/// Which way a venue's off-tick values are pushed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    Nearest,
+    /// Towards the higher probability (the conservative cost of taking the price)
+    Up,
+    Down,
+}
+
+impl Rounding {
+    /// `value` moved onto a multiple of `step`
+    fn apply(self, value: u32, step: u32) -> u32 {
+        let step = step.max(1);
+        match self {
+            Rounding::Nearest => (value + step / 2) / step * step,
+            Rounding::Up => value.div_ceil(step) * step,
+            Rounding::Down => value / step * step,
+        }
+    }
+}
+
+/// How one venue's quotes are read and rounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueRule {
+    /// Smallest probability step the venue prices in (basis points)
+    pub tick_bps: u16,
+    pub rounding: Rounding,
+}
+
+impl VenueRule {
+    /// Sportsbook odds (vig included) round up so an edge against them is never overstated
+    pub const SPORTSBOOK: Self = Self { tick_bps: 1, rounding: Rounding::Up };
+
+    /// Built-in rule for a venue: Kalshi's 1¢ and Polymarket's 0.1¢ books round to the nearest
+    /// tick; everything else is a sportsbook
+    pub fn for_platform(platform: Platform) -> Self {
+        match platform {
+            Platform::Kalshi => Self { tick_bps: 100, rounding: Rounding::Nearest },
+            Platform::Polymarket => Self { tick_bps: 10, rounding: Rounding::Nearest },
+            _ => Self::SPORTSBOOK,
+        }
+    }
+
+    /// Implied probability on this tick (None for malformed quotes)
+    pub fn normalize(self, quote: Quote) -> Option<Probability> {
+        // Hundredths of a basis point, so Up/Down see the true side of the tick
+        let fine = (quote.implied()? * 1_000_000.0).round() as u32;
+        let bps = self.rounding.apply(fine, self.tick_bps as u32 * 100) / 100;
+        Some(Probability(bps.min(10_000) as u16))
+    }
+
+    /// YES and NO cents for a binary quote; a missing NO side is the YES complement
+    pub fn binary(self, quote: &BinaryQuote) -> Option<(PriceCents, PriceCents)> {
+        let yes = self.normalize(quote.yes)?;
+        let no = match quote.no {
+            Some(no) => self.normalize(no)?,
+            None => yes.complement(),
+        };
+        Some((yes.to_cents(self.rounding), no.to_cents(self.rounding)))
+    }
+}
+
+/// Both sides of a binary market as the venue sent them (NO absent = complement of YES)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BinaryQuote {
+    pub yes: Quote,
+    pub no: Option<Quote>,
+    pub yes_size: SizeCents,
+    pub no_size: SizeCents,
+}
+
+/// Converts venue quotes into `Probability` and whole-cent prices with per-venue rounding,
+/// so feeds and patterns compare venues on one scale
+#[derive(Debug, Clone, Default)]
+pub struct QuoteNormalizer {
+    /// Overrides of the built-in `VenueRule`s
+    rules: HashMap<Platform, VenueRule>,
+}
+
+impl QuoteNormalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override a venue's tick and rounding
+    pub fn with_rule(mut self, platform: Platform, rule: VenueRule) -> Self {
+        self.rules.insert(platform, rule);
+        self
+    }
+
+    pub fn rule(&self, platform: Platform) -> VenueRule {
+        self.rules.get(&platform).copied().unwrap_or_else(|| VenueRule::for_platform(platform))
+    }
+
+    /// Rule for any feed provider; plugins (third-party odds APIs) are read as sportsbooks
+    pub fn provider_rule(&self, provider: ProviderId) -> VenueRule {
+        provider.platform().map_or(VenueRule::SPORTSBOOK, |platform| self.rule(platform))
+    }
+
+    /// Implied probability on the venue's tick (None for malformed quotes)
+    pub fn normalize(&self, platform: Platform, quote: Quote) -> Option<Probability> {
+        self.rule(platform).normalize(quote)
+    }
+
+    /// Whole-cent price for the order books (`NO_PRICE` for malformed quotes)
+    pub fn cents(&self, platform: Platform, quote: Quote) -> PriceCents {
+        self.normalize(platform, quote)
+            .map(|p| p.to_cents(self.rule(platform).rounding))
+            .unwrap_or(NO_PRICE)
+    }
+
+    /// YES and NO cents for a binary quote; a missing NO side is the YES complement
+    pub fn binary(&self, platform: Platform, quote: &BinaryQuote) -> Option<(PriceCents, PriceCents)> {
+        self.rule(platform).binary(quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conventions_agree_on_one_scale() {
+        let n = QuoteNormalizer::new();
+        assert_eq!(n.normalize(Platform::Kalshi, Quote::Cents(55.0)), Some(Probability(5_500)));
+        assert_eq!(n.normalize(Platform::Polymarket, Quote::Decimal(0.55)), Some(Probability(5_500)));
+        // -122 is 122/222 = 54.95%, rounded up to the next basis point
+        assert_eq!(n.normalize(Platform::DraftKings, Quote::American(-122)), Some(Probability(5_496)));
+        assert_eq!(n.normalize(Platform::FanDuel, Quote::American(100)), Some(Probability(5_000)));
+        assert_eq!(n.normalize(Platform::FanDuel, Quote::American(300)), Some(Probability(2_500)));
+
+        assert_eq!(n.cents(Platform::Kalshi, Quote::Cents(55.0)), 55);
+        assert_eq!(n.cents(Platform::Polymarket, Quote::Decimal(0.55)), 55);
+        assert_eq!(n.cents(Platform::DraftKings, Quote::American(-122)), 55);
+    }
+
+    #[test]
+    fn test_venue_rounding_rules() {
+        let n = QuoteNormalizer::new();
+        // Polymarket keeps its 0.1¢ tick; Kalshi snaps to the cent
+        assert_eq!(n.normalize(Platform::Polymarket, Quote::Decimal(0.5054)), Some(Probability(5_050)));
+        assert_eq!(n.normalize(Platform::Kalshi, Quote::Cents(50.6)), Some(Probability(5_100)));
+        // Sportsbooks round up: +110 is 47.619%, so 48¢ not 47¢
+        assert_eq!(n.cents(Platform::BetMGM, Quote::American(110)), 48);
+
+        let n = n.with_rule(Platform::BetMGM, VenueRule { tick_bps: 100, rounding: Rounding::Down });
+        assert_eq!(n.normalize(Platform::BetMGM, Quote::American(110)), Some(Probability(4_700)));
+        assert_eq!(n.cents(Platform::BetMGM, Quote::American(110)), 47);
+    }
+
+    #[test]
+    fn test_binary_and_malformed_quotes() {
+        let n = QuoteNormalizer::new();
+        let quote = BinaryQuote { yes: Quote::Cents(42.0), no: None, yes_size: 100, no_size: 100 };
+        assert_eq!(n.binary(Platform::Kalshi, &quote), Some((42, 58)));
+
+        let quote = BinaryQuote { yes: Quote::Decimal(0.40), no: Some(Quote::Decimal(0.62)), ..quote };
+        assert_eq!(n.binary(Platform::Polymarket, &quote), Some((40, 62)));
+
+        assert_eq!(n.normalize(Platform::DraftKings, Quote::American(50)), None);
+        assert_eq!(n.normalize(Platform::Polymarket, Quote::Decimal(1.2)), None);
+        assert_eq!(n.cents(Platform::Polymarket, Quote::Decimal(f64::NAN)), NO_PRICE);
+        // Certainties still map to tradable cents
+        assert_eq!(Probability::ONE.to_cents(Rounding::Nearest), 99);
+        assert_eq!(Probability::from_fraction(0.0).unwrap().to_cents(Rounding::Down), 1);
+    }
+}
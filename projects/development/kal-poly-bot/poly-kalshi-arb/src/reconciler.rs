@@ -0,0 +1,444 @@
+// src/reconciler.rs
+// Balance and position reconciliation - venue state vs internal position_tracker
+
+use anyhow::Result;
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity, SharedAlertRouter};
+use crate::clock;
+use crate::kalshi::KalshiApiClient;
+use crate::polymarket::PolyDataClient;
+use crate::polymarket_clob::SharedAsyncClient;
+use crate::position_tracker::{PositionTracker, SharedPositionTracker};
+use crate::schedule::Schedule;
+use crate::strategy;
+use crate::types::{MarketPair, Platform};
+
+/// Reconciler configuration
+#[derive(Debug, Clone)]
+pub struct ReconcilerConfig {
+    pub interval: Duration,
+    /// Civil-time runs (e.g. "15 17 * * * America/New_York" after the close) in place of `interval`
+    pub schedule: Option<Schedule>,
+    /// Contract differences at or below this are ignored (rounding, dust)
+    pub contract_tolerance: f64,
+    /// Unexplained balance change (dollars) tolerated between runs
+    pub balance_tolerance: f64,
+}
+
+impl Default for ReconcilerConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(300),
+            schedule: None,
+            contract_tolerance: 0.01,
+            balance_tolerance: 1.0,
+        }
+    }
+}
+
+/// One venue position leg (normalized to our market_id + side)
+#[derive(Debug, Clone, PartialEq)]
+pub struct VenuePosition {
+    pub platform: Platform,
+    /// Venue-native ID (Kalshi ticker / Poly token ID)
+    pub venue_id: String,
+    /// "yes" or "no"
+    pub side: &'static str,
+    pub contracts: f64,
+}
+
+/// Point-in-time state pulled from one venue
+#[derive(Debug, Clone, Default)]
+pub struct VenueSnapshot {
+    /// Cash balance in dollars
+    pub balance: f64,
+    pub positions: Vec<VenuePosition>,
+}
+
+/// Pulls balance + positions from a venue
+pub trait VenueStateSource: Send + Sync {
+    fn platform(&self) -> Platform;
+    fn fetch_state(&self) -> BoxFuture<'_, Result<VenueSnapshot>>;
+}
+
+/// Kalshi portfolio (balance in cents, signed positions)
+pub struct KalshiStateSource {
+    client: Arc<KalshiApiClient>,
+}
+
+impl KalshiStateSource {
+    pub fn new(client: Arc<KalshiApiClient>) -> Self {
+        Self { client }
+    }
+
+    async fn fetch(&self) -> Result<VenueSnapshot> {
+        let balance_cents = self.client.get_balance().await?;
+        let positions = self.client.get_positions().await?
+            .into_iter()
+            .filter(|p| p.position != 0)
+            .map(|p| VenuePosition {
+                platform: Platform::Kalshi,
+                side: if p.position > 0 { "yes" } else { "no" },
+                contracts: p.position.unsigned_abs() as f64,
+                venue_id: p.ticker,
+            })
+            .collect();
+        Ok(VenueSnapshot { balance: balance_cents as f64 / 100.0, positions })
+    }
+}
+
+impl VenueStateSource for KalshiStateSource {
+    fn platform(&self) -> Platform {
+        Platform::Kalshi
+    }
+
+    fn fetch_state(&self) -> BoxFuture<'_, Result<VenueSnapshot>> {
+        Box::pin(self.fetch())
+    }
+}
+
+/// Polymarket: data API positions + CLOB collateral balance
+/// Side is resolved later from the token index, so positions are reported as "yes"
+pub struct PolymarketStateSource {
+    data: Arc<PolyDataClient>,
+    clob: Arc<SharedAsyncClient>,
+    funder: String,
+}
+
+impl PolymarketStateSource {
+    pub fn new(data: Arc<PolyDataClient>, clob: Arc<SharedAsyncClient>, funder: &str) -> Self {
+        Self { data, clob, funder: funder.to_string() }
+    }
+
+    async fn fetch(&self) -> Result<VenueSnapshot> {
+        let balance = self.clob.balance_allowance().await?.balance_usdc();
+        let positions = self.data.get_positions(&self.funder).await?
+            .into_iter()
+            .map(|p| VenuePosition {
+                platform: Platform::Polymarket,
+                venue_id: p.asset,
+                side: "yes",
+                contracts: p.size,
+            })
+            .collect();
+        Ok(VenueSnapshot { balance, positions })
+    }
+}
+
+impl VenueStateSource for PolymarketStateSource {
+    fn platform(&self) -> Platform {
+        Platform::Polymarket
+    }
+
+    fn fetch_state(&self) -> BoxFuture<'_, Result<VenueSnapshot>> {
+        Box::pin(self.fetch())
+    }
+}
+
+/// Structured reconciliation discrepancy
+#[derive(Debug, Clone, PartialEq)]
+pub enum Discrepancy {
+    /// Venue holds more than we recorded - a fill never reached the tracker
+    MissingFill { platform: Platform, market_id: String, side: &'static str, venue: f64, internal: f64 },
+    /// We recorded more than the venue holds - phantom or reversed fill
+    PhantomPosition { platform: Platform, market_id: String, side: &'static str, venue: f64, internal: f64 },
+    /// Venue position on a market we do not track at all
+    UnknownPosition { platform: Platform, venue_id: String, contracts: f64 },
+    /// Total venue cash moved by more than internal cost/P&L changes explain
+    BalanceDrift { expected_change: f64, actual_change: f64 },
+}
+
+impl Discrepancy {
+    pub fn to_alert(&self) -> Alert {
+        match self {
+            Discrepancy::MissingFill { platform, market_id, side, venue, internal } => Alert::new(
+                "reconciler", AlertSeverity::Critical, "missing_fill",
+                format!("{} {} {}: venue={:.2} internal={:.2}", platform, market_id, side, venue, internal),
+            ).with_market(market_id).with_strategy(strategy::CROSS_VENUE_ARB),
+            Discrepancy::PhantomPosition { platform, market_id, side, venue, internal } => Alert::new(
+                "reconciler", AlertSeverity::Critical, "phantom_position",
+                format!("{} {} {}: venue={:.2} internal={:.2}", platform, market_id, side, venue, internal),
+            ).with_market(market_id).with_strategy(strategy::CROSS_VENUE_ARB),
+            Discrepancy::UnknownPosition { platform, venue_id, contracts } => Alert::new(
+                "reconciler", AlertSeverity::Warning, "unknown_position",
+                format!("{} {} holds {:.2} untracked contracts", platform, venue_id, contracts),
+            ).with_market(venue_id),
+            Discrepancy::BalanceDrift { expected_change, actual_change } => Alert::new(
+                "reconciler", AlertSeverity::Warning, "balance_drift",
+                format!("balance moved ${:.2}, positions explain ${:.2} (drift ${:.2})",
+                        actual_change, expected_change, actual_change - expected_change),
+            ),
+        }
+    }
+}
+
+/// Maps venue IDs to (tracker market_id, side)
+#[derive(Debug, Default)]
+pub struct VenueIndex {
+    map: HashMap<(Platform, String), (String, &'static str)>,
+}
+
+impl VenueIndex {
+    pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = &'a MarketPair>) -> Self {
+        let mut map = HashMap::new();
+        for pair in pairs {
+            let market_id = pair.kalshi_market_ticker.to_string();
+            map.insert((Platform::Polymarket, pair.poly_yes_token.to_string()), (market_id.clone(), "yes"));
+            map.insert((Platform::Polymarket, pair.poly_no_token.to_string()), (market_id, "no"));
+        }
+        Self { map }
+    }
+
+    /// Resolve a venue position to (market_id, side); Kalshi tickers are already our market_id
+    fn resolve(&self, pos: &VenuePosition) -> Option<(String, &'static str)> {
+        match pos.platform {
+            Platform::Kalshi => Some((pos.venue_id.clone(), pos.side)),
+            _ => self.map.get(&(pos.platform, pos.venue_id.clone())).cloned(),
+        }
+    }
+}
+
+fn internal_contracts(tracker: &PositionTracker, market_id: &str, platform: Platform, side: &str) -> f64 {
+    let Some(pos) = tracker.get(market_id) else { return 0.0 };
+    if pos.status != "open" {
+        return 0.0;
+    }
+    match (platform, side) {
+        (Platform::Kalshi, "yes") => pos.kalshi_yes.contracts,
+        (Platform::Kalshi, "no") => pos.kalshi_no.contracts,
+        (Platform::Polymarket, "yes") => pos.poly_yes.contracts,
+        (Platform::Polymarket, "no") => pos.poly_no.contracts,
+        _ => 0.0,
+    }
+}
+
+/// Diff venue positions against the tracker for the given venues
+pub fn diff_positions(
+    tracker: &PositionTracker,
+    snapshots: &HashMap<Platform, VenueSnapshot>,
+    index: &VenueIndex,
+    tolerance: f64,
+) -> Vec<Discrepancy> {
+    let mut out = Vec::new();
+    let mut seen: HashMap<(Platform, String, &'static str), f64> = HashMap::new();
+
+    for snapshot in snapshots.values() {
+        for pos in &snapshot.positions {
+            match index.resolve(pos) {
+                Some((market_id, side)) if tracker.get(&market_id).is_some() => {
+                    *seen.entry((pos.platform, market_id, side)).or_default() += pos.contracts;
+                }
+                _ if pos.contracts > tolerance => out.push(Discrepancy::UnknownPosition {
+                    platform: pos.platform,
+                    venue_id: pos.venue_id.clone(),
+                    contracts: pos.contracts,
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    // Every internal open leg on a reconciled venue must appear on the venue
+    for position in tracker.open_positions() {
+        for platform in snapshots.keys() {
+            for side in ["yes", "no"] {
+                let internal = internal_contracts(tracker, &position.market_id, *platform, side);
+                if internal > 0.0 {
+                    seen.entry((*platform, position.market_id.clone(), side)).or_insert(0.0);
+                }
+            }
+        }
+    }
+
+    for ((platform, market_id, side), venue) in seen {
+        let internal = internal_contracts(tracker, &market_id, platform, side);
+        let delta = venue - internal;
+        if delta > tolerance {
+            out.push(Discrepancy::MissingFill { platform, market_id, side, venue, internal });
+        } else if delta < -tolerance {
+            out.push(Discrepancy::PhantomPosition { platform, market_id, side, venue, internal });
+        }
+    }
+
+    out
+}
+
+/// Internal cash view used to explain balance changes
+#[derive(Debug, Clone, Copy, Default)]
+struct CashBaseline {
+    venue_balance: f64,
+    open_cost: f64,
+    realized_pnl: f64,
+}
+
+/// Periodic venue-vs-tracker reconciliation
+pub struct Reconciler {
+    config: ReconcilerConfig,
+    sources: Vec<Arc<dyn VenueStateSource>>,
+    tracker: SharedPositionTracker,
+    index: VenueIndex,
+    alerts: SharedAlertRouter,
+    baseline: Option<CashBaseline>,
+}
+
+impl Reconciler {
+    pub fn new(
+        config: ReconcilerConfig,
+        sources: Vec<Arc<dyn VenueStateSource>>,
+        tracker: SharedPositionTracker,
+        index: VenueIndex,
+        alerts: SharedAlertRouter,
+    ) -> Self {
+        Self { config, sources, tracker, index, alerts, baseline: None }
+    }
+
+    pub async fn run(mut self) {
+        if let Some(schedule) = self.config.schedule.clone() {
+            info!("[RECON] Reconciling on schedule {}", schedule);
+            let clock = clock::system();
+            while schedule.wait_next(clock.as_ref()).await.is_some() {
+                self.run_once().await;
+            }
+            warn!("[RECON] Schedule {} never fires again; reconciliation stopped", schedule);
+            return;
+        }
+        let mut interval = tokio::time::interval(self.config.interval);
+        loop {
+            interval.tick().await;
+            self.run_once().await;
+        }
+    }
+
+    async fn run_once(&mut self) {
+        match self.reconcile_once().await {
+            Ok(found) if found.is_empty() => info!("[RECON] Clean"),
+            Ok(found) => warn!("[RECON] {} discrepancies", found.len()),
+            Err(e) => warn!("[RECON] Reconciliation failed: {}", e),
+        }
+    }
+
+    /// Pull every venue, diff, route alerts; returns discrepancies found
+    pub async fn reconcile_once(&mut self) -> Result<Vec<Discrepancy>> {
+        let fetched = futures_util::future::join_all(self.sources.iter().map(|s| s.fetch_state())).await;
+
+        let mut snapshots = HashMap::new();
+        let mut complete = true;
+        for (source, result) in self.sources.iter().zip(fetched) {
+            match result {
+                Ok(snapshot) => {
+                    snapshots.insert(source.platform(), snapshot);
+                }
+                Err(e) => {
+                    warn!("[RECON] {} state fetch failed: {}", source.platform(), e);
+                    complete = false;
+                }
+            }
+        }
+
+        let tracker = self.tracker.read().await;
+        let mut found = diff_positions(&tracker, &snapshots, &self.index, self.config.contract_tolerance);
+
+        // Balance drift only makes sense when every venue reported
+        if complete {
+            let summary = tracker.summary();
+            let current = CashBaseline {
+                venue_balance: snapshots.values().map(|s| s.balance).sum(),
+                open_cost: summary.total_cost_basis,
+                realized_pnl: summary.realized_pnl,
+            };
+            if let Some(prev) = self.baseline {
+                if let Some(d) = balance_drift(prev, current, self.config.balance_tolerance) {
+                    found.push(d);
+                }
+            }
+            self.baseline = Some(current);
+        }
+        drop(tracker);
+
+        for d in &found {
+            self.alerts.route(d.to_alert());
+        }
+        Ok(found)
+    }
+}
+
+/// Fills move cash into open cost basis; resolution releases cost basis plus realized P&L
+/// So Δbalance should equal -Δopen_cost + Δrealized_pnl
+fn balance_drift(prev: CashBaseline, current: CashBaseline, tolerance: f64) -> Option<Discrepancy> {
+    let actual_change = current.venue_balance - prev.venue_balance;
+    let expected_change = -(current.open_cost - prev.open_cost) + (current.realized_pnl - prev.realized_pnl);
+    if (actual_change - expected_change).abs() > tolerance {
+        Some(Discrepancy::BalanceDrift { expected_change, actual_change })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position_tracker::FillRecord;
+
+    fn tracker_with_arb() -> PositionTracker {
+        let mut tracker = PositionTracker::new();
+        tracker.record_fill_internal(&FillRecord::new("KX-1", "m", "kalshi", "no", 10.0, 0.50, 0.0, "a"));
+        tracker.record_fill_internal(&FillRecord::new("KX-1", "m", "polymarket", "yes", 10.0, 0.45, 0.0, "b"));
+        tracker
+    }
+
+    fn index() -> VenueIndex {
+        let mut map = HashMap::new();
+        map.insert((Platform::Polymarket, "tok-yes".to_string()), ("KX-1".to_string(), "yes"));
+        VenueIndex { map }
+    }
+
+    fn pos(platform: Platform, id: &str, side: &'static str, contracts: f64) -> VenuePosition {
+        VenuePosition { platform, venue_id: id.into(), side, contracts }
+    }
+
+    #[test]
+    fn test_clean_reconciliation() {
+        let tracker = tracker_with_arb();
+        let mut snaps = HashMap::new();
+        snaps.insert(Platform::Kalshi, VenueSnapshot { balance: 0.0, positions: vec![pos(Platform::Kalshi, "KX-1", "no", 10.0)] });
+        snaps.insert(Platform::Polymarket, VenueSnapshot { balance: 0.0, positions: vec![pos(Platform::Polymarket, "tok-yes", "yes", 10.0)] });
+        assert!(diff_positions(&tracker, &snaps, &index(), 0.01).is_empty());
+    }
+
+    #[test]
+    fn test_missing_phantom_unknown() {
+        let tracker = tracker_with_arb();
+        let mut snaps = HashMap::new();
+        // Kalshi shows 12 (2 unrecorded), Poly shows nothing, plus an untracked ticker
+        snaps.insert(Platform::Kalshi, VenueSnapshot { balance: 0.0, positions: vec![
+            pos(Platform::Kalshi, "KX-1", "no", 12.0),
+            pos(Platform::Kalshi, "KX-OTHER", "yes", 5.0),
+        ] });
+        snaps.insert(Platform::Polymarket, VenueSnapshot::default());
+
+        let found = diff_positions(&tracker, &snaps, &index(), 0.01);
+        assert_eq!(found.len(), 3);
+        assert!(found.iter().any(|d| matches!(d, Discrepancy::MissingFill { platform: Platform::Kalshi, internal, .. } if *internal == 10.0)));
+        assert!(found.iter().any(|d| matches!(d, Discrepancy::PhantomPosition { platform: Platform::Polymarket, venue, .. } if *venue == 0.0)));
+        assert!(found.iter().any(|d| matches!(d, Discrepancy::UnknownPosition { venue_id, .. } if venue_id == "KX-OTHER")));
+    }
+
+    #[test]
+    fn test_balance_drift() {
+        let prev = CashBaseline { venue_balance: 100.0, open_cost: 0.0, realized_pnl: 0.0 };
+        // Spent $9.50 on an arb
+        let bought = CashBaseline { venue_balance: 90.5, open_cost: 9.5, realized_pnl: 0.0 };
+        assert!(balance_drift(prev, bought, 0.05).is_none());
+        // Resolved: payout $10 -> open cost released, +$0.50 realized
+        let resolved = CashBaseline { venue_balance: 100.5, open_cost: 0.0, realized_pnl: 0.5 };
+        assert!(balance_drift(bought, resolved, 0.05).is_none());
+        // $5 vanished
+        let leaked = CashBaseline { venue_balance: 85.5, open_cost: 9.5, realized_pnl: 0.0 };
+        assert!(matches!(balance_drift(prev, leaked, 0.05), Some(Discrepancy::BalanceDrift { .. })));
+    }
+}
@@ -0,0 +1,159 @@
+// src/redis_client.rs
+// Minimal RESP2 client shared by the cache's Redis tier and the bet queue
+
+use futures_util::future::BoxFuture;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::error::StateStoreError;
+
+/// RESP2 reply
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resp {
+    Simple(String),
+    Error(String),
+    Int(i64),
+    Bulk(Option<String>),
+    Array(Option<Vec<Resp>>),
+}
+
+impl Resp {
+    /// Simple string or non-nil bulk string
+    pub fn text(self) -> Option<String> {
+        match self {
+            Resp::Simple(s) | Resp::Bulk(Some(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn int(&self) -> Option<i64> {
+        match self {
+            Resp::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Array items; empty for nil arrays and non-arrays
+    pub fn items(self) -> Vec<Resp> {
+        match self {
+            Resp::Array(Some(items)) => items,
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Error for a reply of the wrong shape for `what`
+pub fn unexpected(what: &str, reply: &Resp) -> StateStoreError {
+    StateStoreError::Corrupt(format!("unexpected redis reply to {}: {:?}", what, reply))
+}
+
+/// Read one reply, recursing into arrays
+pub fn read_resp<R: AsyncBufRead + Unpin + Send>(reader: &mut R) -> BoxFuture<'_, Result<Resp, StateStoreError>> {
+    Box::pin(async move {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        let line = line.trim_end();
+        let bad = || StateStoreError::Corrupt(format!("bad redis reply line: {}", line));
+        let (kind, rest) = (line.as_bytes().first().copied(), line.get(1..).unwrap_or(""));
+        Ok(match kind {
+            Some(b'+') => Resp::Simple(rest.to_string()),
+            Some(b'-') => Resp::Error(rest.to_string()),
+            Some(b':') => Resp::Int(rest.parse().map_err(|_| bad())?),
+            Some(b'$') => {
+                let len: i64 = rest.parse().map_err(|_| bad())?;
+                if len < 0 {
+                    return Ok(Resp::Bulk(None));
+                }
+                let mut buf = vec![0u8; len as usize + 2];
+                reader.read_exact(&mut buf).await?;
+                buf.truncate(len as usize);
+                let text = String::from_utf8(buf)
+                    .map_err(|e| StateStoreError::Corrupt(format!("non-UTF8 redis value: {}", e)))?;
+                Resp::Bulk(Some(text))
+            }
+            Some(b'*') => {
+                let len: i64 = rest.parse().map_err(|_| bad())?;
+                if len < 0 {
+                    return Ok(Resp::Array(None));
+                }
+                let mut items = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    items.push(read_resp(reader).await?);
+                }
+                Resp::Array(Some(items))
+            }
+            _ => return Err(bad()),
+        })
+    })
+}
+
+/// Encode a command as a RESP2 array of bulk strings
+fn encode(args: &[&str]) -> String {
+    let mut req = format!("*{}\r\n", args.len());
+    for arg in args {
+        req.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+    }
+    req
+}
+
+/// One lazily opened connection, serialized behind a lock and reconnected after errors
+pub struct RedisClient {
+    addr: String,
+    conn: tokio::sync::Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RedisClient {
+    /// `addr` is host:port; redis://host:port is accepted too
+    pub fn new(addr: &str) -> Self {
+        let addr = addr.trim_start_matches("redis://").trim_end_matches('/').to_string();
+        Self { addr, conn: tokio::sync::Mutex::new(None) }
+    }
+
+    /// Send one command and read its reply; error replies come back as `StateStoreError::Backend`
+    pub async fn command(&self, args: &[&str]) -> Result<Resp, StateStoreError> {
+        let mut guard = self.conn.lock().await;
+        if guard.is_none() {
+            *guard = Some(BufReader::new(TcpStream::connect(&self.addr).await?));
+        }
+        let conn = guard.as_mut().unwrap();
+        let result = match conn.get_mut().write_all(encode(args).as_bytes()).await {
+            Ok(()) => read_resp(conn).await,
+            Err(e) => Err(e.into()),
+        };
+        match result {
+            Ok(Resp::Error(message)) => Err(StateStoreError::Backend { backend: "redis", message }),
+            Ok(reply) => Ok(reply),
+            Err(e) => {
+                // Drop the connection; next call reconnects
+                *guard = None;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replies_parse() {
+        assert_eq!(read_resp(&mut &b"+OK\r\n"[..]).await.unwrap(), Resp::Simple("OK".to_string()));
+        assert_eq!(read_resp(&mut &b"-BUSYGROUP exists\r\n"[..]).await.unwrap(), Resp::Error("BUSYGROUP exists".to_string()));
+        assert_eq!(read_resp(&mut &b":7\r\n"[..]).await.unwrap(), Resp::Int(7));
+        assert_eq!(read_resp(&mut &b"$-1\r\n"[..]).await.unwrap(), Resp::Bulk(None));
+        assert_eq!(read_resp(&mut &b"$5\r\na\r\nbc\r\n"[..]).await.unwrap().text().as_deref(), Some("a\r\nbc"));
+        let nested = read_resp(&mut &b"*2\r\n:1\r\n*-1\r\n"[..]).await.unwrap();
+        assert_eq!(nested.items(), vec![Resp::Int(1), Resp::Array(None)]);
+        assert!(read_resp(&mut &b"?\r\n"[..]).await.is_err());
+        assert!(read_resp(&mut &b""[..]).await.is_err());
+    }
+
+    #[test]
+    fn test_commands_encode_as_bulk_arrays() {
+        assert_eq!(encode(&["SET", "k", "v w"]), "*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$3\r\nv w\r\n");
+        assert_eq!(RedisClient::new("redis://127.0.0.1:6379/").addr, "127.0.0.1:6379");
+    }
+}
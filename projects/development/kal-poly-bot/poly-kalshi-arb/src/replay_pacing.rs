@@ -0,0 +1,147 @@
+// src/replay_pacing.rs
+// Replay pacing - hold a replayed feed to N x wall-clock time (capped by max_speed_multiplier)
+// or let it run as fast as possible
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::types::TimestampNs;
+
+/// How fast replayed time advances relative to wall-clock time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaySpeed {
+    /// No pacing; ticks are replayed back to back
+    #[default]
+    AsFastAsPossible,
+    /// N seconds of feed per wall-clock second (1 = real time)
+    Accelerated(f64),
+}
+
+impl ReplaySpeed {
+    /// "max" | "fast" for as fast as possible, else a multiplier ("10" or "10x")
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "max" | "fast" => Ok(ReplaySpeed::AsFastAsPossible),
+            v => v.trim_end_matches('x').parse::<f64>().ok()
+                .filter(|m| m.is_finite() && *m > 0.0)
+                .map(ReplaySpeed::Accelerated)
+                .ok_or_else(|| anyhow!("invalid replay speed '{}' (want max or a positive multiplier)", s)),
+        }
+    }
+
+    /// Multiplier clamped to `max_multiplier`; as fast as possible is left unpaced
+    pub fn capped(self, max_multiplier: f64) -> Self {
+        match self {
+            ReplaySpeed::Accelerated(m) if m > max_multiplier => {
+                warn!("[REPLAY] Speed x{} above max_speed_multiplier, capped at x{}", m, max_multiplier);
+                ReplaySpeed::Accelerated(max_multiplier)
+            }
+            speed => speed,
+        }
+    }
+
+    pub fn multiplier(&self) -> Option<f64> {
+        match self {
+            ReplaySpeed::AsFastAsPossible => None,
+            ReplaySpeed::Accelerated(m) => Some(*m),
+        }
+    }
+}
+
+/// Sleeps between replayed ticks so feed time tracks `speed` x the wall time since the first
+/// tick; a replay that falls behind catches up without sleeping and the lag is recorded
+#[derive(Debug, Clone)]
+pub struct SpeedGovernor {
+    speed: ReplaySpeed,
+    anchor: Option<(TimestampNs, Instant)>,
+    max_lag: Duration,
+}
+
+impl SpeedGovernor {
+    pub fn new(speed: ReplaySpeed) -> Self {
+        Self { speed, anchor: None, max_lag: Duration::ZERO }
+    }
+
+    pub fn speed(&self) -> ReplaySpeed {
+        self.speed
+    }
+
+    /// Worst amount the replay ran behind its schedule
+    pub fn max_lag(&self) -> Duration {
+        self.max_lag
+    }
+
+    /// Wait until the tick at `sim_ns` is due
+    pub async fn pace(&mut self, sim_ns: TimestampNs) {
+        let wait = self.delay(sim_ns, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Time until the tick at `sim_ns` is due as of `now`; the first tick anchors the schedule
+    pub fn delay(&mut self, sim_ns: TimestampNs, now: Instant) -> Duration {
+        let Some(multiplier) = self.speed.multiplier() else { return Duration::ZERO };
+        let (anchor_ns, anchor_at) = *self.anchor.get_or_insert((sim_ns, now));
+        let feed_elapsed = sim_ns.saturating_sub(anchor_ns) as f64 / multiplier;
+        let due = anchor_at + Duration::from_nanos(feed_elapsed as u64);
+        match due.checked_duration_since(now) {
+            Some(wait) => wait,
+            None => {
+                self.max_lag = self.max_lag.max(now - due);
+                Duration::ZERO
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC: TimestampNs = 1_000_000_000;
+
+    #[test]
+    fn test_parse_and_cap() {
+        assert_eq!(ReplaySpeed::parse("max").unwrap(), ReplaySpeed::AsFastAsPossible);
+        assert_eq!(ReplaySpeed::parse("10x").unwrap(), ReplaySpeed::Accelerated(10.0));
+        assert!(ReplaySpeed::parse("0").is_err());
+        assert!(ReplaySpeed::parse("-2").is_err());
+        assert!(ReplaySpeed::parse("soon").is_err());
+
+        assert_eq!(ReplaySpeed::Accelerated(5000.0).capped(1000.0), ReplaySpeed::Accelerated(1000.0));
+        assert_eq!(ReplaySpeed::Accelerated(10.0).capped(1000.0), ReplaySpeed::Accelerated(10.0));
+        assert_eq!(ReplaySpeed::AsFastAsPossible.capped(1000.0), ReplaySpeed::AsFastAsPossible);
+    }
+
+    #[test]
+    fn test_governor_schedules_against_first_tick() {
+        let start = Instant::now();
+        let mut governor = SpeedGovernor::new(ReplaySpeed::Accelerated(10.0));
+        assert_eq!(governor.delay(100 * SEC, start), Duration::ZERO);
+        // 10s of feed at x10 is due one wall second after the first tick
+        assert_eq!(governor.delay(110 * SEC, start), Duration::from_secs(1));
+        assert_eq!(governor.delay(110 * SEC, start + Duration::from_millis(400)), Duration::from_millis(600));
+
+        // Running late: no wait, lag recorded
+        assert_eq!(governor.delay(120 * SEC, start + Duration::from_millis(2500)), Duration::ZERO);
+        assert_eq!(governor.max_lag(), Duration::from_millis(500));
+
+        let mut unpaced = SpeedGovernor::new(ReplaySpeed::AsFastAsPossible);
+        assert_eq!(unpaced.delay(100 * SEC, start), Duration::ZERO);
+        assert_eq!(unpaced.delay(1000 * SEC, start), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_pace_sleeps_in_real_time() {
+        let mut governor = SpeedGovernor::new(ReplaySpeed::Accelerated(1000.0));
+        let start = Instant::now();
+        governor.pace(0).await;
+        governor.pace(50 * SEC).await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
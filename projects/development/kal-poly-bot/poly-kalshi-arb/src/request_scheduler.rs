@@ -0,0 +1,320 @@
+// src/request_scheduler.rs
+// Per-venue request scheduler - priority lanes over a shared token bucket with adaptive 429 backoff
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use crate::config::SchedulerSection;
+use crate::types::Platform;
+
+/// Request lanes, highest priority first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RequestPriority {
+    Order = 0,
+    Cancel = 1,
+    MarketData = 2,
+    Discovery = 3,
+}
+
+const LANES: usize = 4;
+
+/// Rate limits for one venue
+#[derive(Debug, Clone, Copy)]
+pub struct VenueLimits {
+    /// Sustained requests per second
+    pub per_second: f64,
+    /// Bucket capacity
+    pub burst: f64,
+    /// Floor the adaptive rate never drops below
+    pub min_per_second: f64,
+    /// First backoff after a 429 (doubles per consecutive 429)
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl VenueLimits {
+    pub fn kalshi() -> Self {
+        Self {
+            per_second: 18.0,
+            burst: 5.0,
+            min_per_second: 2.0,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    pub fn polymarket() -> Self {
+        Self {
+            per_second: 40.0,
+            burst: 10.0,
+            min_per_second: 5.0,
+            base_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    /// Current (adaptive) refill rate
+    rate: f64,
+    /// Requests queued per lane
+    waiting: [usize; LANES],
+    /// No requests before this instant (429 cool-off)
+    paused_until: Option<Instant>,
+    consecutive_throttles: u32,
+}
+
+/// Token bucket for one venue; higher-priority waiters always take tokens first
+pub struct VenueScheduler {
+    platform: Platform,
+    limits: VenueLimits,
+    state: Mutex<BucketState>,
+}
+
+/// Removes a queued waiter from its lane if the acquire future is dropped
+struct WaitGuard<'a> {
+    scheduler: &'a VenueScheduler,
+    lane: usize,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.scheduler.state.lock().unwrap().waiting[self.lane] -= 1;
+    }
+}
+
+impl VenueScheduler {
+    pub fn new(platform: Platform, limits: VenueLimits) -> Self {
+        Self {
+            platform,
+            limits,
+            state: Mutex::new(BucketState {
+                tokens: limits.burst,
+                last_refill: Instant::now(),
+                rate: limits.per_second,
+                waiting: [0; LANES],
+                paused_until: None,
+                consecutive_throttles: 0,
+            }),
+        }
+    }
+
+    pub fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    /// Current adaptive rate (requests/sec)
+    pub fn current_rate(&self) -> f64 {
+        self.state.lock().unwrap().rate
+    }
+
+    /// Requests that can start within `horizon`: tokens now plus refill, less any 429 pause
+    pub fn capacity_within(&self, horizon: Duration) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        self.refill(&mut state, now);
+        let paused = state.paused_until.map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+        state.tokens + horizon.saturating_sub(paused).as_secs_f64() * state.rate
+    }
+
+    /// Wait for a request slot in the given lane
+    pub async fn acquire(&self, priority: RequestPriority) {
+        let lane = priority as usize;
+        let mut queued: Option<WaitGuard<'_>> = None;
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                self.refill(&mut state, now);
+
+                let paused = state.paused_until.is_some_and(|until| until > now);
+                let higher_waiting = state.waiting[..lane].iter().any(|&n| n > 0);
+                if !paused && state.tokens >= 1.0 && !higher_waiting {
+                    state.tokens -= 1.0;
+                    if let Some(guard) = queued.take() {
+                        // Unregister under the lock we already hold
+                        state.waiting[lane] -= 1;
+                        std::mem::forget(guard);
+                    }
+                    return;
+                }
+
+                if queued.is_none() {
+                    state.waiting[lane] += 1;
+                    queued = Some(WaitGuard { scheduler: self, lane });
+                }
+                match state.paused_until {
+                    Some(until) if until > now => until - now,
+                    _ => {
+                        let deficit = (1.0 - state.tokens).max(0.0);
+                        Duration::from_secs_f64((deficit / state.rate).max(0.001))
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn refill(&self, state: &mut BucketState, now: Instant) {
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * state.rate).min(self.limits.burst);
+        state.last_refill = now;
+    }
+
+    /// Venue returned 429: pause all lanes and halve the rate
+    pub fn report_throttled(&self, retry_after: Option<Duration>) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_throttles += 1;
+        let exp = state.consecutive_throttles.saturating_sub(1).min(16);
+        let backoff = retry_after
+            .unwrap_or_else(|| self.limits.base_backoff * 2u32.pow(exp))
+            .min(self.limits.max_backoff);
+        state.paused_until = Some(Instant::now() + backoff);
+        state.rate = (state.rate * 0.5).max(self.limits.min_per_second);
+        state.tokens = 0.0;
+        warn!("[SCHED] {} throttled, pausing {:?}, rate -> {:.1}/s", self.platform, backoff, state.rate);
+    }
+
+    /// Successful response: additive recovery toward the configured rate
+    pub fn report_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_throttles = 0;
+        if state.rate < self.limits.per_second {
+            state.rate = (state.rate + self.limits.per_second * 0.05).min(self.limits.per_second);
+            debug!("[SCHED] {} rate recovering -> {:.1}/s", self.platform, state.rate);
+        }
+    }
+
+    /// Feed an HTTP status back into the scheduler
+    pub fn report_status(&self, status: reqwest::StatusCode, retry_after: Option<Duration>) {
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            self.report_throttled(retry_after);
+        } else if status.is_success() {
+            self.report_success();
+        }
+    }
+}
+
+/// Parse a Retry-After header (seconds form)
+pub fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str().ok()?
+        .trim()
+        .parse::<f64>().ok()
+        .map(Duration::from_secs_f64)
+}
+
+/// Registry of per-venue schedulers shared by every client
+pub struct RequestScheduler {
+    venues: HashMap<Platform, Arc<VenueScheduler>>,
+}
+
+impl RequestScheduler {
+    pub fn new() -> Self {
+        Self { venues: HashMap::new() }
+    }
+
+    /// Kalshi + Polymarket with rates from the `[scheduler]` config section
+    pub fn from_config(section: &SchedulerSection) -> Self {
+        let mut s = Self::new();
+        s.register(Platform::Kalshi, VenueLimits {
+            per_second: section.kalshi_rps,
+            burst: section.kalshi_burst,
+            ..VenueLimits::kalshi()
+        });
+        s.register(Platform::Polymarket, VenueLimits {
+            per_second: section.poly_rps,
+            burst: section.poly_burst,
+            ..VenueLimits::polymarket()
+        });
+        s
+    }
+
+    pub fn register(&mut self, platform: Platform, limits: VenueLimits) {
+        self.venues.insert(platform, Arc::new(VenueScheduler::new(platform, limits)));
+    }
+
+    pub fn venue(&self, platform: Platform) -> Option<Arc<VenueScheduler>> {
+        self.venues.get(&platform).cloned()
+    }
+}
+
+impl Default for RequestScheduler {
+    fn default() -> Self {
+        Self::from_config(&SchedulerSection::default())
+    }
+}
+
+pub type SharedRequestScheduler = Arc<RequestScheduler>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_second: f64, burst: f64) -> VenueLimits {
+        VenueLimits {
+            per_second,
+            burst,
+            min_per_second: 1.0,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_burst_then_paced() {
+        let s = VenueScheduler::new(Platform::Kalshi, limits(20.0, 2.0));
+        let start = Instant::now();
+        s.acquire(RequestPriority::MarketData).await;
+        s.acquire(RequestPriority::MarketData).await;
+        assert!(start.elapsed() < Duration::from_millis(20));
+        s.acquire(RequestPriority::MarketData).await;
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[tokio::test]
+    async fn test_orders_jump_the_queue() {
+        let s = Arc::new(VenueScheduler::new(Platform::Kalshi, limits(20.0, 1.0)));
+        s.acquire(RequestPriority::Discovery).await; // drain the bucket
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for (i, p) in [RequestPriority::Discovery, RequestPriority::MarketData, RequestPriority::Order].into_iter().enumerate() {
+            let s = s.clone();
+            let order = order.clone();
+            tasks.push(tokio::spawn(async move {
+                // Stagger registration so lower lanes queue first
+                tokio::time::sleep(Duration::from_millis(5 * i as u64)).await;
+                s.acquire(p).await;
+                order.lock().unwrap().push(p);
+            }));
+        }
+        for t in tasks {
+            t.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![RequestPriority::Order, RequestPriority::MarketData, RequestPriority::Discovery]);
+    }
+
+    #[tokio::test]
+    async fn test_throttle_backoff_and_recovery() {
+        let s = VenueScheduler::new(Platform::Polymarket, limits(10.0, 5.0));
+        s.report_throttled(None);
+        assert_eq!(s.current_rate(), 5.0);
+
+        let start = Instant::now();
+        s.acquire(RequestPriority::Order).await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        for _ in 0..20 {
+            s.report_success();
+        }
+        assert_eq!(s.current_rate(), 10.0);
+    }
+}
@@ -5,16 +5,25 @@
 //! - Cross-book net exposure limits with jurisdiction-aware position management
 //! - Provider failure circuit breakers with automatic failover
 //! - Anti-fingerprinting order sizing with adaptive volume controls
+//!
+//! Market data, orders and fills arrive as events (`EventHandler`); alerts go
+//! out on the returned channel and, when attached, the event bus.
 
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::{Arc, OnceLock};
 use tokio::time::{Duration, Instant};
-use tracing::{info, warn, error};
+use tracing::{debug, info, warn, error};
 
 use crate::types::*;
-use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal};
-use crate::feed_aggregator::{FeedAggregator, LatencyStats};
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::audit_log::{AuditEvent, SharedAuditLog};
+use crate::circuit_breaker::{BreakerConfig, BreakerEvent, BreakerState, CircuitBreaker};
+use crate::error::RiskRejection;
+use crate::market_impact::SharedMarketImpact;
+use crate::pattern_policy::SharedPatternPolicy;
+use crate::event_bus::{Event, EventHandler, SharedEventBus};
+use crate::latency_arbitrage::{LatencySignal, PriceObservation};
+use crate::latency_execution::{LatencyExecutionRequest, LatencyExecutionResult};
 
 /// Risk management configuration
 #[derive(Debug, Clone)]
@@ -68,91 +77,20 @@ struct Position {
 #[derive(Debug)]
 struct HalfLifeDecayMonitor {
     /// Tracked signals with their decay state
-    tracked_signals: HashMap<u64, SignalDecayState>,
+    tracked_signals: HashMap<SignalId, SignalDecayState>,
     /// Decay alerts sent
-    alerts_sent: HashMap<u64, Instant>,
+    alerts_sent: HashMap<SignalId, Instant>,
 }
 
 #[derive(Debug, Clone)]
 struct SignalDecayState {
-    pub signal_id: u64,
+    pub signal_id: SignalId,
     pub initial_edge_cents: i16,
     pub half_life_ns: u64,
     pub creation_time_ns: TimestampNs,
     pub last_edge_cents: i16,
-}
-
-/// Circuit breaker state
-#[derive(Debug, Clone)]
-enum CircuitState {
-    Closed,     // Normal operation
-    Open,       // Circuit broken, reject trades
-    HalfOpen,   // Testing recovery
-}
-
-/// Provider circuit breaker
-#[derive(Debug)]
-struct ProviderCircuitBreaker {
-    pub provider: Platform,
-    pub state: CircuitState,
-    pub failure_count: u32,
-    pub last_failure: Option<Instant>,
-    pub last_attempt: Option<Instant>,
-    pub success_count: u32,
-}
-
-impl ProviderCircuitBreaker {
-    fn new(provider: Platform) -> Self {
-        Self {
-            provider,
-            state: CircuitState::Closed,
-            failure_count: 0,
-            last_failure: None,
-            last_attempt: None,
-            success_count: 0,
-        }
-    }
-
-    /// Record execution success
-    fn record_success(&mut self) {
-        self.success_count += 1;
-        if let CircuitState::HalfOpen = self.state {
-            // Successful test, close circuit
-            self.state = CircuitState::Closed;
-            self.failure_count = 0;
-            info!("Circuit closed for {} after successful test", self.provider);
-        }
-    }
-
-    /// Record execution failure
-    fn record_failure(&mut self, config: &RiskConfig) {
-        self.failure_count += 1;
-        self.last_failure = Some(Instant::now());
-
-        if self.failure_count >= config.provider_failure_threshold {
-            if let CircuitState::Closed = self.state {
-                self.state = CircuitState::Open;
-                warn!("Circuit opened for {} after {} failures", self.provider, self.failure_count);
-            }
-        }
-    }
-
-    /// Check if trade is allowed
-    fn allow_trade(&self) -> bool {
-        match self.state {
-            CircuitState::Closed => true,
-            CircuitState::Open => {
-                // Check if enough time has passed to try half-open
-                if let Some(last_failure) = self.last_failure {
-                    if last_failure.elapsed() > Duration::from_secs(60) { // 1 minute cooldown
-                        return true; // Allow one test trade
-                    }
-                }
-                false
-            }
-            CircuitState::HalfOpen => true,
-        }
-    }
+    /// Fast and slow leg venues
+    pub providers: [Platform; 2],
 }
 
 /// Anti-fingerprinting order sizer
@@ -162,6 +100,8 @@ struct OrderSizer {
     market_volumes: HashMap<(Platform, MarketType), u64>,
     /// Adaptive sizing factor (0.0-1.0)
     sizing_factor: f64,
+    /// Market impact model; sizes past the point where impact costs more edge than it adds are cut
+    impact: Option<SharedMarketImpact>,
 }
 
 impl OrderSizer {
@@ -169,18 +109,26 @@ impl OrderSizer {
         Self {
             market_volumes: HashMap::new(),
             sizing_factor: 1.0,
+            impact: None,
         }
     }
 
-    /// Calculate safe order size to avoid fingerprinting
-    fn calculate_safe_size(&self, provider: Platform, market_type: MarketType, requested_size_cents: SizeCents, max_percent: f64) -> SizeCents {
-        let key = (provider, market_type);
+    /// Calculate safe order size to avoid fingerprinting, capped at the size that keeps the
+    /// most of `edge_cents` per contract after expected market impact
+    fn calculate_safe_size(&self, market: &PriceObservation, edge_cents: i16, max_percent: f64) -> SizeCents {
+        let key = (market.provider, market.market_type);
         let estimated_volume = self.market_volumes.get(&key).copied().unwrap_or(100_000); // Default $1000
 
         let max_safe_size = (estimated_volume as f64 * max_percent) as SizeCents;
         let adaptive_size = (max_safe_size as f64 * self.sizing_factor) as SizeCents;
 
-        requested_size_cents.min(adaptive_size).max(100) // Min 100¢ = $1
+        let mut size = market.size.min(adaptive_size);
+        if let Some(impact) = &self.impact {
+            let contracts = impact.optimal_contracts(market.tier, market.provider, edge_cents as f64);
+            let impact_size = (contracts * market.price.max(1) as f64).min(SizeCents::MAX as f64) as SizeCents;
+            size = size.min(impact_size);
+        }
+        size.max(100) // Min 100¢ = $1
     }
 
     /// Update market volume estimate
@@ -203,39 +151,88 @@ pub struct RiskManagementEngine {
     /// Half-life decay monitor
     decay_monitor: HalfLifeDecayMonitor,
     /// Provider circuit breakers
-    circuit_breakers: HashMap<Platform, ProviderCircuitBreaker>,
+    circuit_breakers: Arc<CircuitBreaker<Platform>>,
     /// Anti-fingerprinting order sizer
     order_sizer: OrderSizer,
-    /// Latency arbitrage engine reference
-    latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
-    /// Feed aggregator reference
-    feed_aggregator: Arc<RwLock<FeedAggregator>>,
     /// Risk alerts channel
     alert_tx: tokio::sync::mpsc::UnboundedSender<RiskAlert>,
+    /// Event bus alerts are republished on (shared with the breaker hook)
+    event_bus: Arc<OnceLock<SharedEventBus>>,
+    /// Audit log every approve/reject decision is recorded to
+    audit_log: Option<SharedAuditLog>,
+    /// `[patterns.enabled]` rules for tagged signals
+    patterns: Option<SharedPatternPolicy>,
 }
 
 #[derive(Debug, Clone)]
 pub enum RiskAlert {
-    HalfLifeDecay { signal_id: u64, remaining_percent: f64 },
+    HalfLifeDecay { signal_id: SignalId, remaining_percent: f64 },
     ExposureLimit { provider: Platform, exposure_cents: i64, limit_cents: i64 },
     CircuitBreaker { provider: Platform, state: String },
     ProviderFailure { provider: Platform, failure_count: u32 },
 }
 
+impl RiskAlert {
+    /// Structured form for the alert router / event bus
+    pub fn to_alert(&self) -> Alert {
+        match self {
+            RiskAlert::HalfLifeDecay { signal_id, remaining_percent } => Alert::new(
+                "risk", AlertSeverity::Warning, "half_life_decay",
+                format!("Signal {} edge decayed to {:.0}%", signal_id, remaining_percent * 100.0),
+            ),
+            RiskAlert::ExposureLimit { provider, exposure_cents, limit_cents } => Alert::new(
+                "risk", AlertSeverity::Warning, "exposure_limit",
+                format!("{} exposure {}¢ vs limit {}¢", provider, exposure_cents, limit_cents),
+            ),
+            RiskAlert::CircuitBreaker { provider, state } => Alert::new(
+                "risk",
+                if state == "OPEN" { AlertSeverity::Critical } else { AlertSeverity::Info },
+                "circuit_breaker",
+                format!("{} circuit {}", provider, state),
+            ),
+            RiskAlert::ProviderFailure { provider, failure_count } => Alert::new(
+                "risk", AlertSeverity::Critical, "provider_failure",
+                format!("{} failed {} times", provider, failure_count),
+            ),
+        }
+    }
+}
+
+/// Send on the alert channel and, if attached, the event bus
+fn emit_alert(
+    alert_tx: &tokio::sync::mpsc::UnboundedSender<RiskAlert>,
+    event_bus: &OnceLock<SharedEventBus>,
+    alert: RiskAlert,
+) {
+    if let Some(bus) = event_bus.get() {
+        bus.publish(Event::Alert(alert.to_alert()));
+    }
+    let _ = alert_tx.send(alert);
+}
+
 impl RiskManagementEngine {
     /// Create new risk management engine
-    pub fn new(
-        config: RiskConfig,
-        latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
-        feed_aggregator: Arc<RwLock<FeedAggregator>>,
-    ) -> (Self, tokio::sync::mpsc::UnboundedReceiver<RiskAlert>) {
+    pub fn new(config: RiskConfig) -> (Self, tokio::sync::mpsc::UnboundedReceiver<RiskAlert>) {
         let (alert_tx, alert_rx) = tokio::sync::mpsc::UnboundedSender::new();
 
-        let mut circuit_breakers = HashMap::new();
-        // Initialize circuit breakers for all providers
-        for provider in [Platform::Kalshi, Platform::Polymarket, Platform::DraftKings, Platform::FanDuel] {
-            circuit_breakers.insert(provider, ProviderCircuitBreaker::new(provider));
-        }
+        // Provider breakers: open once failures dominate recent executions
+        let breaker_config = BreakerConfig {
+            min_calls: config.provider_failure_threshold,
+            open_for: Duration::from_secs(config.circuit_reset_seconds),
+            ..BreakerConfig::default()
+        };
+        let event_bus = Arc::new(OnceLock::new());
+        let hook_tx = alert_tx.clone();
+        let hook_bus = event_bus.clone();
+        let circuit_breakers = Arc::new(CircuitBreaker::new(breaker_config).on_event(move |event| {
+            let (provider, state) = match event {
+                BreakerEvent::Opened { key, .. } => (*key, "OPEN"),
+                BreakerEvent::HalfOpened { key } => (*key, "HALF_OPEN"),
+                BreakerEvent::Closed { key } => (*key, "CLOSED"),
+                BreakerEvent::Rejected { .. } => return,
+            };
+            emit_alert(&hook_tx, &hook_bus, RiskAlert::CircuitBreaker { provider, state: state.to_string() });
+        }));
 
         Self {
             config,
@@ -246,26 +243,66 @@ impl RiskManagementEngine {
             },
             circuit_breakers,
             order_sizer: OrderSizer::new(),
-            latency_engine,
-            feed_aggregator,
             alert_tx,
+            event_bus,
+            audit_log: None,
+            patterns: None,
         }
     }
 
+    /// Also publish alerts as `Event::Alert` (first bus wins)
+    pub fn with_event_bus(self, bus: SharedEventBus) -> Self {
+        let _ = self.event_bus.set(bus);
+        self
+    }
+
+    /// Record approve/reject decisions to the audit log
+    pub fn with_audit_log(mut self, audit_log: SharedAuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Reject signals whose pattern `[patterns.enabled]` doesn't admit
+    pub fn with_pattern_policy(mut self, patterns: SharedPatternPolicy) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Shrink order sizes whose expected market impact would eat the signal's edge
+    pub fn with_market_impact(mut self, impact: SharedMarketImpact) -> Self {
+        self.order_sizer.impact = Some(impact);
+        self
+    }
+
+    fn send_alert(&self, alert: RiskAlert) {
+        emit_alert(&self.alert_tx, &self.event_bus, alert);
+    }
+
     /// Evaluate risk for a potential latency arbitrage trade
-    pub async fn evaluate_trade_risk(&mut self, signal: &LatencySignal) -> Result<TradeRiskAssessment, RiskRejectionReason> {
-        // Check circuit breakers
-        if !self.check_circuit_breakers(signal) {
-            return Err(RiskRejectionReason::CircuitBreaker);
+    pub async fn evaluate_trade_risk(&mut self, signal: &LatencySignal) -> Result<TradeRiskAssessment, RiskRejection> {
+        let assessment = self.assess_trade_risk(signal);
+        if let Some(audit_log) = &self.audit_log {
+            let event = match &assessment {
+                Ok(_) => AuditEvent::approved(None),
+                Err(rejection) => AuditEvent::rejected(rejection),
+            };
+            audit_log.record_for(&signal.strategy_id, "risk_management", Some(signal.fast_market.market_id.to_string()), event);
         }
+        assessment
+    }
+
+    fn assess_trade_risk(&mut self, signal: &LatencySignal) -> Result<TradeRiskAssessment, RiskRejection> {
+        // Check circuit breakers
+        self.check_circuit_breakers(signal)?;
+
+        // Check pattern enablement and limits
+        self.check_pattern_policy(signal)?;
 
         // Check exposure limits
         self.check_exposure_limits(signal)?;
 
         // Check half-life decay
-        if self.check_half_life_decay(signal).is_err() {
-            return Err(RiskRejectionReason::HalfLifeDecay);
-        }
+        self.check_half_life_decay(signal)?;
 
         // Calculate safe order sizes
         let safe_sizes = self.calculate_safe_order_sizes(signal);
@@ -280,15 +317,27 @@ impl RiskManagementEngine {
     }
 
     /// Check provider circuit breakers
-    fn check_circuit_breakers(&self, signal: &LatencySignal) -> bool {
-        let fast_cb = self.circuit_breakers.get(&signal.fast_market.provider);
-        let slow_cb = self.circuit_breakers.get(&signal.slow_market.provider);
+    fn check_circuit_breakers(&self, signal: &LatencySignal) -> Result<(), RiskRejection> {
+        for provider in [signal.fast_market.provider, signal.slow_market.provider] {
+            if !self.circuit_breakers.allow(&provider) {
+                return Err(RiskRejection::CircuitOpen { provider });
+            }
+        }
+        Ok(())
+    }
 
-        fast_cb.map_or(true, |cb| cb.allow_trade()) && slow_cb.map_or(true, |cb| cb.allow_trade())
+    /// Check the signal's pattern against `[patterns.enabled]` (untagged signals pass)
+    fn check_pattern_policy(&self, signal: &LatencySignal) -> Result<(), RiskRejection> {
+        let (Some(patterns), Some(pattern_id)) = (&self.patterns, signal.pattern_id) else {
+            return Ok(());
+        };
+        let venues = [signal.fast_market.provider.to_string(), signal.slow_market.provider.to_string()];
+        patterns.admit(&pattern_id.to_string(), Some(signal.confidence), &[venues[0].as_str(), venues[1].as_str()])?;
+        Ok(())
     }
 
     /// Check cross-book exposure limits
-    fn check_exposure_limits(&self, signal: &LatencySignal) -> Result<(), RiskRejectionReason> {
+    fn check_exposure_limits(&self, signal: &LatencySignal) -> Result<(), RiskRejection> {
         let fast_exposure = self.provider_exposure
             .get(&signal.fast_market.provider)
             .map(|e| e.net_exposure_cents)
@@ -300,32 +349,40 @@ impl RiskManagementEngine {
             .unwrap_or(0);
 
         if fast_exposure.abs() >= self.config.max_provider_exposure_cents {
-            let _ = self.alert_tx.send(RiskAlert::ExposureLimit {
+            self.send_alert(RiskAlert::ExposureLimit {
+                provider: signal.fast_market.provider,
+                exposure_cents: fast_exposure,
+                limit_cents: self.config.max_provider_exposure_cents,
+            });
+            return Err(RiskRejection::ExposureLimit {
                 provider: signal.fast_market.provider,
                 exposure_cents: fast_exposure,
                 limit_cents: self.config.max_provider_exposure_cents,
             });
-            return Err(RiskRejectionReason::ExposureLimit);
         }
 
         if slow_exposure.abs() >= self.config.max_provider_exposure_cents {
-            let _ = self.alert_tx.send(RiskAlert::ExposureLimit {
+            self.send_alert(RiskAlert::ExposureLimit {
+                provider: signal.slow_market.provider,
+                exposure_cents: slow_exposure,
+                limit_cents: self.config.max_provider_exposure_cents,
+            });
+            return Err(RiskRejection::ExposureLimit {
                 provider: signal.slow_market.provider,
                 exposure_cents: slow_exposure,
                 limit_cents: self.config.max_provider_exposure_cents,
             });
-            return Err(RiskRejectionReason::ExposureLimit);
         }
 
         Ok(())
     }
 
     /// Check half-life decay for signal viability
-    fn check_half_life_decay(&self, signal: &LatencySignal) -> Result<(), RiskRejectionReason> {
+    fn check_half_life_decay(&self, signal: &LatencySignal) -> Result<(), RiskRejection> {
         let remaining_edge_percent = signal.disparity_cents as f64 / signal.disparity_cents.abs() as f64;
 
         if remaining_edge_percent < self.config.half_life_decay_threshold {
-            return Err(RiskRejectionReason::HalfLifeDecay);
+            return Err(RiskRejection::HalfLifeDecay);
         }
 
         Ok(())
@@ -334,16 +391,14 @@ impl RiskManagementEngine {
     /// Calculate anti-fingerprinting safe order sizes
     fn calculate_safe_order_sizes(&self, signal: &LatencySignal) -> (SizeCents, SizeCents) {
         let fast_size = self.order_sizer.calculate_safe_size(
-            signal.fast_market.provider,
-            signal.fast_market.market_type,
-            signal.fast_market.size,
+            &signal.fast_market,
+            signal.disparity_cents,
             self.config.max_order_size_percent,
         );
 
         let slow_size = self.order_sizer.calculate_safe_size(
-            signal.slow_market.provider,
-            signal.slow_market.market_type,
-            signal.slow_market.size,
+            &signal.slow_market,
+            signal.disparity_cents,
             self.config.max_order_size_percent,
         );
 
@@ -371,28 +426,42 @@ impl RiskManagementEngine {
 
     /// Get provider reliability score (0.0-1.0)
     fn get_provider_reliability(&self, provider: Platform) -> f64 {
-        if let Some(cb) = self.circuit_breakers.get(&provider) {
-            if cb.failure_count == 0 {
-                1.0
-            } else {
-                0.8_f64.powf(cb.failure_count as f64)
-            }
-        } else {
-            0.5 // Unknown provider
+        match self.circuit_breakers.state(&provider) {
+            BreakerState::Open => 0.0,
+            BreakerState::HalfOpen => 0.5,
+            BreakerState::Closed => 1.0 - self.circuit_breakers.failure_rate(&provider),
         }
     }
 
     /// Record trade execution for risk tracking
-    pub async fn record_trade_execution(&mut self, result: &crate::latency_execution::LatencyExecutionResult) {
-        // Update circuit breakers
-        if result.success {
-            if let Some(cb) = self.circuit_breakers.get_mut(&Platform::Kalshi) { // TODO: Get actual providers
-                cb.record_success();
-            }
-        } else {
-            if let Some(cb) = self.circuit_breakers.get_mut(&Platform::Kalshi) {
-                cb.record_failure(&self.config);
+    pub async fn record_trade_execution(&mut self, result: &LatencyExecutionResult) {
+        self.record_execution(result);
+    }
+
+    /// Start tracking a scheduled execution so its fill can be attributed
+    fn track_order(&mut self, signal_id: SignalId, request: &LatencyExecutionRequest) {
+        let signal = &request.signal;
+        let half_life_ms = (signal.fast_market.tier.half_life_ms() + signal.slow_market.tier.half_life_ms()) / 2.0;
+        self.decay_monitor.tracked_signals.insert(signal_id, SignalDecayState {
+            signal_id,
+            initial_edge_cents: signal.disparity_cents,
+            half_life_ns: (half_life_ms * 1_000_000.0) as u64,
+            creation_time_ns: signal.fast_market.timestamp_ns,
+            last_edge_cents: signal.disparity_cents,
+            providers: [signal.fast_market.provider, signal.slow_market.provider],
+        });
+    }
+
+    fn record_execution(&mut self, result: &LatencyExecutionResult) {
+        // Update circuit breakers for both legs: the tracked order's, else the result's own venues
+        let tracked = self.decay_monitor.tracked_signals.remove(&result.signal_id).map(|state| state.providers);
+        match tracked.or(result.providers) {
+            Some(providers) => {
+                for provider in providers {
+                    self.circuit_breakers.record(&provider, result.success);
+                }
             }
+            None => debug!("Execution {} has no venue to attribute; breakers unchanged", result.signal_id),
         }
 
         // Update exposure tracking
@@ -403,29 +472,20 @@ impl RiskManagementEngine {
     pub async fn monitor_risks(&mut self) {
         let current_time = Instant::now();
 
-        // Monitor circuit breaker states
-        for (provider, cb) in &self.circuit_breakers {
-            match cb.state {
-                CircuitState::Open => {
-                    let _ = self.alert_tx.send(RiskAlert::CircuitBreaker {
-                        provider: *provider,
-                        state: "OPEN".to_string(),
-                    });
-                }
-                CircuitState::HalfOpen => {
-                    let _ = self.alert_tx.send(RiskAlert::CircuitBreaker {
-                        provider: *provider,
-                        state: "HALF_OPEN".to_string(),
-                    });
-                }
-                CircuitState::Closed => {} // Normal state
+        // Re-alert providers still tripped (transitions are alerted by the breaker hook)
+        for (provider, state) in self.circuit_breakers.snapshot() {
+            if state != BreakerState::Closed {
+                self.send_alert(RiskAlert::CircuitBreaker {
+                    provider,
+                    state: state.to_string(),
+                });
             }
         }
 
         // Monitor exposure limits
         for (provider, exposure) in &self.provider_exposure {
             if exposure.net_exposure_cents.abs() > self.config.max_provider_exposure_cents * 8 / 10 { // 80% warning
-                let _ = self.alert_tx.send(RiskAlert::ExposureLimit {
+                self.send_alert(RiskAlert::ExposureLimit {
                     provider: *provider,
                     exposure_cents: exposure.net_exposure_cents,
                     limit_cents: self.config.max_provider_exposure_cents,
@@ -450,22 +510,25 @@ pub struct TradeRiskAssessment {
     pub warnings: Vec<String>,
 }
 
-/// Risk rejection reasons
-#[derive(Debug, Clone)]
-pub enum RiskRejectionReason {
-    CircuitBreaker,
-    ExposureLimit,
-    HalfLifeDecay,
-    ProviderFailure,
+impl EventHandler for RiskManagementEngine {
+    fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::Tick(update) => {
+                if let Some(platform) = update.provider.platform() {
+                    let volume = update.yes_size as u64 + update.no_size as u64;
+                    self.order_sizer.update_volume_estimate(platform, update.market_type, volume);
+                }
+            }
+            Event::Order { signal_id, request } => self.track_order(*signal_id, request),
+            Event::Fill(result) => self.record_execution(result),
+            _ => {}
+        }
+    }
 }
 
 impl Default for RiskManagementEngine {
     fn default() -> Self {
-        let (engine, _) = Self::new(
-            RiskConfig::default(),
-            Arc::new(RwLock::new(LatencyArbitrageEngine::new())),
-            Arc::new(RwLock::new(FeedAggregator::default())),
-        );
+        let (engine, _) = Self::new(RiskConfig::default());
         engine
     }
 }
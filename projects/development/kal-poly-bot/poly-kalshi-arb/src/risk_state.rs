@@ -0,0 +1,199 @@
+// src/risk_state.rs
+// Read-only session risk state (daily P&L, kill switch, pattern budgets) for trigger paths outside the risk engine
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::circuit_breaker::TradingCircuitBreaker;
+use crate::pattern_policy::SharedPatternPolicy;
+
+/// Share of the daily loss limit after which trigger sizes start shrinking; they reach zero at the limit
+const DOWNSIZE_FROM_LOSS_SHARE: f64 = 0.5;
+
+/// Why a guardrail dropped a trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressReason {
+    /// Watchdog kill switch or a tripped trading breaker
+    KillSwitch,
+    /// Session loss at or past `[risk] max_daily_loss`
+    DailyLoss,
+    /// The pattern's `[patterns.enabled]` capital is all deployed
+    BudgetExhausted,
+}
+
+impl SuppressReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuppressReason::KillSwitch => "kill_switch",
+            SuppressReason::DailyLoss => "daily_loss",
+            SuppressReason::BudgetExhausted => "budget_exhausted",
+        }
+    }
+}
+
+/// What the guardrails allow for one trigger
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Guardrail {
+    Clear,
+    /// Scale the size by this factor (0-1)
+    Downsize(f64),
+    Suppress(SuppressReason),
+}
+
+/// Daily P&L, kill switch and pattern budgets as the risk side sees them, shared with
+/// trigger producers (the Bun worker) that can't block on the risk engine
+pub struct RiskStateView {
+    breaker: Arc<TradingCircuitBreaker>,
+    kill_switch: Option<Arc<AtomicBool>>,
+    patterns: Option<SharedPatternPolicy>,
+}
+
+pub type SharedRiskStateView = Arc<RiskStateView>;
+
+impl std::fmt::Debug for RiskStateView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RiskStateView")
+            .field("daily_pnl", &self.daily_pnl())
+            .field("kill_switch", &self.kill_switch())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RiskStateView {
+    pub fn new(breaker: Arc<TradingCircuitBreaker>) -> Self {
+        Self { breaker, kill_switch: None, patterns: None }
+    }
+
+    /// Treat `flag` (the supervisor's execution halt) as a kill switch
+    pub fn with_kill_switch(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.kill_switch = Some(flag);
+        self
+    }
+
+    /// Report per-pattern budgets from `[patterns.enabled]`
+    pub fn with_pattern_policy(mut self, patterns: SharedPatternPolicy) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Realized P&L since the last daily reset (dollars)
+    pub fn daily_pnl(&self) -> f64 {
+        self.breaker.daily_pnl()
+    }
+
+    /// Kill switch tripped or trading breaker halted
+    pub fn kill_switch(&self) -> bool {
+        self.kill_switch.as_ref().is_some_and(|flag| flag.load(Ordering::SeqCst)) || !self.breaker.is_trading_allowed()
+    }
+
+    /// Dollars the pattern may still open (None = uncapped)
+    pub fn budget_remaining(&self, pattern: &str) -> Option<f64> {
+        self.patterns.as_ref().and_then(|patterns| patterns.available(pattern))
+    }
+
+    /// Guardrail for a `size`-dollar trigger of `pattern`: suppressed while the kill switch is on,
+    /// the daily loss limit is hit or the budget is gone; shrunk past half the loss limit and to
+    /// the budget left
+    pub fn check(&self, pattern: &str, size: f64) -> Guardrail {
+        if self.kill_switch() {
+            return Guardrail::Suppress(SuppressReason::KillSwitch);
+        }
+
+        let mut scale: f64 = 1.0;
+        let config = self.breaker.config();
+        if config.enabled && config.max_daily_loss > 0.0 {
+            let loss = -self.daily_pnl();
+            if loss >= config.max_daily_loss {
+                return Guardrail::Suppress(SuppressReason::DailyLoss);
+            }
+            let soft = config.max_daily_loss * DOWNSIZE_FROM_LOSS_SHARE;
+            if loss > soft {
+                scale = (config.max_daily_loss - loss) / (config.max_daily_loss - soft);
+            }
+        }
+
+        if let Some(left) = self.budget_remaining(pattern) {
+            if left <= 0.0 {
+                return Guardrail::Suppress(SuppressReason::BudgetExhausted);
+            }
+            if size > left {
+                scale = scale.min(left / size);
+            }
+        }
+
+        if scale < 1.0 { Guardrail::Downsize(scale) } else { Guardrail::Clear }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::{CircuitBreakerConfig, TripReason};
+    use crate::config::{PatternRule, PatternsSection};
+    use crate::pattern_policy::PatternPolicy;
+
+    fn breaker() -> Arc<TradingCircuitBreaker> {
+        Arc::new(TradingCircuitBreaker::new(CircuitBreakerConfig {
+            max_position_per_market: 100,
+            max_total_position: 1000,
+            max_daily_loss: 100.0,
+            max_consecutive_errors: 3,
+            cooldown_secs: 60,
+            enabled: true,
+        }))
+    }
+
+    #[test]
+    fn test_daily_loss_downsizes_then_suppresses() {
+        let breaker = breaker();
+        let view = RiskStateView::new(breaker.clone());
+        assert_eq!(view.check("51", 50.0), Guardrail::Clear);
+
+        // Within the first half of the limit sizes are untouched
+        breaker.record_pnl(-40.0);
+        assert_eq!(view.check("51", 50.0), Guardrail::Clear);
+        // Three quarters of the way: half size
+        breaker.record_pnl(-35.0);
+        assert_eq!(view.daily_pnl(), -75.0);
+        assert_eq!(view.check("51", 50.0), Guardrail::Downsize(0.5));
+        breaker.record_pnl(-25.0);
+        assert_eq!(view.check("51", 50.0), Guardrail::Suppress(SuppressReason::DailyLoss));
+
+        breaker.reset_daily_pnl();
+        assert_eq!(view.check("51", 50.0), Guardrail::Clear);
+    }
+
+    #[tokio::test]
+    async fn test_kill_switch_and_breaker_halt_suppress() {
+        let breaker = breaker();
+        let flag = Arc::new(AtomicBool::new(false));
+        let view = RiskStateView::new(breaker.clone()).with_kill_switch(flag.clone());
+        assert!(!view.kill_switch());
+
+        flag.store(true, Ordering::SeqCst);
+        assert_eq!(view.check("51", 10.0), Guardrail::Suppress(SuppressReason::KillSwitch));
+        flag.store(false, Ordering::SeqCst);
+
+        breaker.trip(TripReason::ManualHalt).await;
+        assert!(view.kill_switch());
+        assert_eq!(view.check("51", 10.0), Guardrail::Suppress(SuppressReason::KillSwitch));
+    }
+
+    #[test]
+    fn test_pattern_budget_caps_then_suppresses() {
+        let mut section = PatternsSection::default();
+        section.enabled.insert("51".into(), PatternRule { max_capital: 100.0, ..PatternRule::default() });
+        let patterns = Arc::new(PatternPolicy::new(&section));
+        let view = RiskStateView::new(breaker()).with_pattern_policy(patterns.clone());
+
+        assert_eq!(view.budget_remaining("51"), Some(100.0));
+        assert_eq!(view.budget_remaining("68"), None);
+        patterns.open("51", 80.0);
+        assert_eq!(view.check("51", 40.0), Guardrail::Downsize(0.5));
+        assert_eq!(view.check("51", 20.0), Guardrail::Clear);
+        patterns.open("51", 20.0);
+        assert_eq!(view.check("51", 20.0), Guardrail::Suppress(SuppressReason::BudgetExhausted));
+    }
+}
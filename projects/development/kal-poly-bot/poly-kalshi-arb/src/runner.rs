@@ -0,0 +1,944 @@
+// src/runner.rs
+// Strategy orchestrator - runs feeds -> latency arbitrage -> risk -> execution -> monitoring
+// under the supervisor, wired together by the event bus. src/bin/arb_runner.rs runs it until
+// Ctrl-C / SIGTERM and lists the settings it reads.
+//
+// Feed source is the seeded synthetic market generator until venue FeedClients land.
+
+use anyhow::Result;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn, Level};
+
+use crate::alert_router::{AlertRouter, AlertSeverity};
+use crate::audit_log::{AuditConfig, AuditEvent, AuditLog, SharedAuditLog};
+use crate::backtest_jobs::{run_backtest_jobs, BacktestJobs, BacktestJobsConfig, SharedBacktestJobs};
+use crate::backtest_results::{BacktestResults, BacktestResultsConfig, SharedBacktestResults};
+use crate::backtester_config::get_default_pattern_verifications;
+use crate::cache::{RedisBackend, TeamCache};
+use crate::circuit_breaker::{BreakerConfig, CircuitBreakerConfig, TradingCircuitBreaker};
+use crate::clock;
+use crate::clock_sync::{run_ntp_sync, ClockSync, ClockSyncConfig, SharedClockSync};
+use crate::config::{AppConfig, CliArgs};
+use crate::config_reload::ConfigReloader;
+use crate::decision_latency::{run_decision_windows, DecisionLatencyConfig, DecisionLatencyTracker, SharedDecisionLatency};
+use crate::optimization_studies::{SharedStudyStore, StudiesConfig, StudyStore};
+use crate::discovery::{self, DiscoveryClient};
+use crate::downsample::HistoryService;
+use crate::edge_thresholds::{run_edge_refresh_loop, EdgeThresholds, SharedEdgeThresholds};
+use crate::event_bus::{
+    forward_config_changes, spawn_handler, ArchiveSubscriber, AuditSubscriber, BusSink, Event, EventBus, SharedEventBus,
+    Topic,
+};
+use crate::event_phase::EventPhase;
+use crate::feature_flags::{self, FeatureFlags, SharedFeatureFlags};
+use crate::feed_schema::{FeedSchemas, SharedFeedSchemas};
+use crate::feed_aggregator::{FeedAggregator, FeedAggregatorConfig, FeedStatus};
+use crate::kalshi::{self, KalshiApiClient, KalshiConfig};
+use crate::latency_arbitrage::{LatencyArbitrageEngine, MarketTier};
+use crate::latency_execution::LatencyExecutionEngine;
+use crate::maintenance::{self, run_saved_position_sweep, MaintenanceCalendar, SharedMaintenanceCalendar};
+use crate::logging;
+use crate::microstructural_simulator::{SyntheticMarketConfig, SyntheticMarketGenerator};
+use crate::market_hierarchy::MarketHierarchy;
+use crate::market_cooldown::{self, run_cooldown_expiry_loop, CooldownConfig, CooldownManager, SharedCooldownManager};
+use crate::market_impact::{run_impact_refresh_loop, MarketImpact, SharedMarketImpact};
+use crate::market_maker::{self, run_market_maker_loop, MakerConfig, MarketMaker, SharedMarketMaker};
+use crate::market_metadata::{MarketMetadataRegistry, SharedMarketMetadata};
+use crate::monitoring_dashboard::{self, MonitoringDashboard};
+use crate::observation_prefilter::{self, ObservationPrefilters, SharedObservationPrefilters};
+use crate::operator_review::{self, ReviewQueue, SharedReviewQueue};
+use crate::paper_fills::{PaperFillConfig, PaperFillSimulator};
+use crate::pattern_policy::{self, PatternPolicy, SharedPatternPolicy};
+use crate::pattern_verifier::{run_verification_loop, PatternVerifier, SharedPatternVerifier};
+use crate::position_aging::{self, run_saved_position_sweeper, PositionSweeper};
+use crate::position_tracker::POSITION_FILE;
+use crate::quote_normalizer::{BinaryQuote, Quote};
+use crate::risk_management::{RiskConfig, RiskManagementEngine};
+use crate::runtime_profile::{IngestQueue, RuntimeProfile, SharedIngestQueue};
+use crate::secrets::SecretsChain;
+use crate::sensitivity::{SensitivityService, SharedSensitivityService};
+use crate::shadow_mode::{run_shadow_promotion, ShadowBook, SharedShadowBook, PROMOTION_INTERVAL, SHADOW_FILE};
+use crate::sizing_curve::{self, SharedSizingCurve, SizingCurve};
+use crate::sla_degradation::{run_sla_monitor, DegradationConfig, SharedSlaMonitor, SlaMonitor, EVALUATION_INTERVAL};
+use crate::supervisor::{serve_status, Subsystem, SubsystemContext, Supervisor, SupervisorConfig, Watchdog};
+use crate::tca::{SharedTcaStore, TcaConfig, TcaStore};
+use crate::tick_sanitizer::{SharedTickSanitizer, TickSanitizer};
+use crate::tick_store::{self, TickStoreConfig};
+use crate::types::{GlobalState, MarketType, Platform};
+
+/// Synthetic feed market id on the aggregator
+const SYNTH_MARKET_ID: u16 = 1;
+const EXECUTION_POLL: Duration = Duration::from_millis(50);
+/// How often the archive proves its writer is not wedged
+const ARCHIVE_HEARTBEAT: Duration = Duration::from_secs(5);
+
+/// Aborts a helper task when the subsystem run that spawned it ends (or is aborted)
+struct TaskGuard(JoinHandle<()>);
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Synthetic feed the runner trades on
+#[derive(Debug, Clone, Copy)]
+pub struct SyntheticFeed {
+    /// Generator seed; the same seed always yields the same tick stream
+    pub seed: u64,
+    /// Spacing between generated ticks (milliseconds)
+    pub tick_interval_ms: f64,
+}
+
+impl Default for SyntheticFeed {
+    fn default() -> Self {
+        Self { seed: 42, tick_interval_ms: 100.0 }
+    }
+}
+
+/// Start every subsystem under the supervisor, serve `/status` and friends on `status`, and
+/// run until `shutdown` resolves or a subsystem fails for good
+pub async fn run(
+    app_config: AppConfig,
+    config_path: Option<PathBuf>,
+    cli: CliArgs,
+    profile: RuntimeProfile,
+    feed: SyntheticFeed,
+    status: TcpListener,
+    shutdown: impl Future<Output = ()>,
+) -> Result<()> {
+
+    let bus: SharedEventBus = Arc::new(EventBus::new());
+    let flags: SharedFeatureFlags = Arc::new(FeatureFlags::new(&app_config.features));
+    let patterns: SharedPatternPolicy = Arc::new(PatternPolicy::new(&app_config.patterns));
+    let sizing: SharedSizingCurve = Arc::new(SizingCurve::new(&app_config.sizing));
+    // Cooldown start/expiry reach the dashboard as alerts
+    let alert_bus = bus.clone();
+    let cooldowns: SharedCooldownManager = Arc::new(
+        CooldownManager::new(CooldownConfig::from(&app_config.risk))
+            .on_event(move |event| {
+                alert_bus.publish(Event::Alert(event.to_alert()));
+            }),
+    );
+    // Positions held into a venue's maintenance reach the dashboard as alerts
+    let alert_bus = bus.clone();
+    let maintenance: SharedMaintenanceCalendar = Arc::new(
+        MaintenanceCalendar::new(&app_config.maintenance)
+            .on_held(move |held| {
+                alert_bus.publish(Event::Alert(held.to_alert()));
+            }),
+    );
+    // One sanitizer guards the aggregator and the market maker's filter, so rejects are counted together
+    let sanitizer: SharedTickSanitizer = Arc::new(TickSanitizer::from_config(&app_config.feeds));
+    // Book mids reach the market maker's filter through #76's (or Tier 1's) [worker.prefilters] rule
+    let prefilters: SharedObservationPrefilters = Arc::new(ObservationPrefilters::new(&app_config.worker.prefilters));
+    let maker: Option<SharedMarketMaker> = app_config.market_making.enabled.then(|| {
+        Arc::new(
+            MarketMaker::new(MakerConfig::new(&app_config.market_making, &app_config.risk))
+                .with_sanitizer(sanitizer.clone())
+                .with_prefilters(prefilters.clone()),
+        )
+    });
+    // The market maker's Kalshi socket is the runner's only venue feed
+    let feed_schemas: Option<SharedFeedSchemas> =
+        maker.as_ref().map(|_| Arc::new(FeedSchemas::from_config(&app_config.feeds)));
+    let reloader = Arc::new(ConfigReloader::new(app_config, config_path, cli));
+    // Stores and monitors below are built once from the startup config
+    let startup = reloader.current();
+    let edges: SharedEdgeThresholds = Arc::new(EdgeThresholds::new());
+    // Default coefficients until TCA fills refit it
+    let impact: SharedMarketImpact = Arc::new(MarketImpact::new());
+    let tca: Option<SharedTcaStore> = if startup.tca.enabled {
+        Some(Arc::new(TcaStore::open(TcaConfig::from(&startup.tca))?))
+    } else {
+        None
+    };
+    // Market types and tiers, shared by the aggregator and the arbitrage engine
+    let market_metadata: SharedMarketMetadata = Arc::new(MarketMetadataRegistry::default());
+    let latency_engine = Arc::new(RwLock::new(
+        LatencyArbitrageEngine::new()
+            .with_event_bus(bus.clone())
+            .with_edge_thresholds(edges.clone())
+            .with_market_metadata(market_metadata.clone()),
+    ));
+    // Ticks reach the arbitrage engine over the bus; the aggregator's own channel is unused
+    let (aggregator, _update_rx) = FeedAggregator::new(FeedAggregatorConfig::from_feeds(&startup.feeds), latency_engine.clone());
+    let clock_sync_config = ClockSyncConfig::from(&startup.clock_sync);
+    let clock_sync: SharedClockSync = Arc::new(ClockSync::new(&clock_sync_config));
+    let aggregator = Arc::new(RwLock::new(
+        aggregator
+            .with_breaker_config(BreakerConfig::from(&startup.breaker))
+            .with_event_bus(bus.clone())
+            .with_sanitizer(sanitizer.clone())
+            .with_clock_sync(clock_sync.clone())
+            .with_market_metadata(market_metadata),
+    ));
+    let dashboard_json = Arc::new(Mutex::new(serde_json::Value::Null));
+    // Factor sensitivities of the bot's saved positions, matched to the last discovered pairs
+    let sensitivities: SharedSensitivityService = Arc::new(SensitivityService::new(POSITION_FILE));
+    sensitivities.set_pairs(discovery::cached_pairs());
+    let audit: Option<SharedAuditLog> = if startup.audit.enabled {
+        Some(Arc::new(AuditLog::open(AuditConfig::from(&startup.audit))?))
+    } else {
+        None
+    };
+    let tick_store = startup.tick_store.enabled.then(|| TickStoreConfig::from(&startup.tick_store));
+    // Completed backtests and verification updates, for the dashboard's backtester panels
+    let backtest_results: Option<SharedBacktestResults> = if tick_store.is_some() || startup.backtest_jobs.enabled {
+        Some(Arc::new(BacktestResults::open(BacktestResultsConfig::from(&startup.backtest_results))?))
+    } else {
+        None
+    };
+    // Verification replays the archive, so it only runs alongside it
+    let verifier: Option<SharedPatternVerifier> = tick_store.as_ref().map(|_| {
+        let mut verifier = PatternVerifier::new(get_default_pattern_verifications()).with_feature_flags(flags.clone());
+        if let Some(results) = &backtest_results {
+            verifier = verifier.with_results(results.clone());
+        }
+        Arc::new(verifier)
+    });
+    let backtest_jobs: Option<SharedBacktestJobs> = if startup.backtest_jobs.enabled {
+        let mut config = BacktestJobsConfig::from(&startup.backtest_jobs);
+        if let Some(tick_store) = &tick_store {
+            config = config.with_archive_dir(tick_store.dir.clone());
+        }
+        let mut jobs = BacktestJobs::open(config)?.with_market_impact(impact.clone()).with_sizing_curve(sizing.clone());
+        if let Some(results) = &backtest_results {
+            jobs = jobs.with_results(results.clone());
+        }
+        Some(Arc::new(jobs))
+    } else {
+        None
+    };
+    let studies: Option<SharedStudyStore> = if startup.studies.enabled {
+        Some(Arc::new(StudyStore::open(StudiesConfig::from(&startup.studies))?.with_reloader(reloader.clone())))
+    } else {
+        None
+    };
+    // Promotions out of shadow reach the dashboard as alerts
+    let shadow: SharedShadowBook = {
+        let alert_bus = bus.clone();
+        Arc::new(
+            ShadowBook::open(flags.clone(), SHADOW_FILE)?
+                .on_promotion(move |promotion| {
+                    alert_bus.publish(Event::Alert(promotion.to_alert()));
+                }),
+        )
+    };
+    // Low-confidence signals wait for an operator on /review; the queue audits its decisions
+    let review: SharedReviewQueue = {
+        let queue = ReviewQueue::new(&startup.execution.review);
+        Arc::new(match &audit {
+            Some(audit) => queue.with_audit_log(audit.clone()),
+            None => queue,
+        })
+    };
+    // Detection-to-order latency of every scheduled order, per pattern
+    let decision_latency: SharedDecisionLatency = Arc::new(DecisionLatencyTracker::new(DecisionLatencyConfig::from(&startup.decision_latency)));
+    // Level changes reach the dashboard as alerts; shed tiers go through the feature flags
+    let sla: Option<SharedSlaMonitor> = startup.sla.enabled.then(|| {
+        let alert_bus = bus.clone();
+        Arc::new(
+            SlaMonitor::new(DegradationConfig::from(&startup.sla))
+                .with_feature_flags(flags.clone())
+                .with_decision_latency(decision_latency.clone())
+                .on_event(move |event| {
+                    alert_bus.publish(Event::Alert(event.to_alert()));
+                }),
+        )
+    });
+    // Synthetic ticks are Tier 1, so the low-latency profile busy-polls their queue
+    let ingest: SharedIngestQueue<FeedTick> = Arc::new(IngestQueue::new(profile.tick_buffer));
+    let ingest_spins = profile.spins_for(MarketTier::Tier1 as usize);
+    // Set by the watchdog kill switch; execution stops taking new signals
+    let halted = Arc::new(AtomicBool::new(false));
+
+    let mut subsystems = vec![
+        config_subsystem(reloader.clone(), bus.clone(), flags.clone(), patterns.clone(), cooldowns.clone(), sizing.clone()),
+        monitoring_subsystem(
+            reloader.clone(), bus.clone(), flags.clone(), cooldowns.clone(), verifier.clone(), backtest_jobs.clone(),
+            backtest_results, studies.clone(), sla.clone(), decision_latency.clone(), sensitivities.clone(),
+            review.clone(), dashboard_json.clone(),
+        )
+        .depends_on(&["config"]),
+        risk_subsystem(reloader.clone(), bus.clone(), audit.clone(), patterns, impact.clone()).depends_on(&["config"]),
+        feeds_subsystem(aggregator.clone(), ingest.clone(), ingest_spins, feed, clock_sync.clone(), clock_sync_config)
+            .depends_on(&["config"]),
+        arbitrage_subsystem(latency_engine.clone(), bus.clone(), edges, impact, tca).depends_on(&["feeds"]),
+        execution_subsystem(
+            reloader.clone(), latency_engine, aggregator, bus.clone(), cooldowns, decision_latency, shadow.clone(),
+            review.clone(), maintenance.clone(), halted.clone(),
+        )
+        .depends_on(&["arbitrage", "risk"]),
+    ];
+    if let Some(audit) = &audit {
+        subsystems.push(audit_subsystem(audit.clone(), bus.clone()).depends_on(&["config"]));
+    }
+    if let (Some(config), Some(verifier)) = (&tick_store, &verifier) {
+        subsystems.push(archive_subsystem(config.clone(), bus.clone(), verifier.clone()).depends_on(&["config"]));
+    }
+    if let Some(jobs) = &backtest_jobs {
+        subsystems.push(backtest_subsystem(jobs.clone()).depends_on(&["config"]));
+    }
+    if let (Some(maker), Some(schemas)) = (&maker, &feed_schemas) {
+        subsystems.push(
+            market_making_subsystem(reloader.clone(), maker.clone(), prefilters.clone(), schemas.clone(), sensitivities.clone())
+                .depends_on(&["config"]),
+        );
+    }
+    let supervisor = Arc::new(Supervisor::new(SupervisorConfig::from(&startup.supervisor), subsystems)?);
+    info!("[RUNNER] Start order: {:?}", supervisor.start_order());
+
+    let mut alerts = AlertRouter::with_logging();
+    alerts.add_sink(AlertSeverity::Warning, Arc::new(BusSink::new(bus.clone())));
+    supervisor.set_alert_router(Arc::new(alerts));
+    supervisor.set_kill_switch(move |name, reason| {
+        if !halted.swap(true, Ordering::SeqCst) {
+            error!("[RUNNER] Kill switch: {} stalled ({}); execution halted", name, reason);
+        }
+    });
+
+    let probe_bus = bus.clone();
+    supervisor.add_probe("event_bus", move || serde_json::to_value(probe_bus.stats()).unwrap_or_default());
+    let route_dashboard = dashboard_json.clone();
+    supervisor.add_probe("dashboard", move || dashboard_json.lock().unwrap().clone());
+    supervisor.add_route("/dashboard", move |method, path| {
+        monitoring_dashboard::handle_admin(&route_dashboard.lock().unwrap(), method, path)
+    });
+    let probe_flags = flags.clone();
+    supervisor.add_probe("feature_flags", move || serde_json::to_value(probe_flags.snapshot()).unwrap_or_default());
+    if let Some(maker) = maker {
+        supervisor.add_probe("market_maker", move || serde_json::to_value(maker.status()).unwrap_or_default());
+        supervisor.add_probe("observation_prefilters", move || serde_json::to_value(prefilters.stats()).unwrap_or_default());
+    }
+    supervisor.add_probe("feed_ingest", move || serde_json::to_value(ingest.stats()).unwrap_or_default());
+    supervisor.add_probe("tick_sanitizer", move || serde_json::to_value(sanitizer.stats()).unwrap_or_default());
+    supervisor.add_probe("clock_sync", move || serde_json::to_value(clock_sync.status()).unwrap_or_default());
+    if let Some(schemas) = feed_schemas {
+        supervisor.add_probe("feed_schema", move || serde_json::to_value(schemas.stats()).unwrap_or_default());
+    }
+    supervisor.add_probe("shadow_mode", move || serde_json::to_value(shadow.status()).unwrap_or_default());
+    let probe_review = review.clone();
+    supervisor.add_probe("operator_review", move || serde_json::to_value(probe_review.status().summary).unwrap_or_default());
+    supervisor.add_route("/review", move |method, path| review.handle_admin(method, path));
+    supervisor.add_probe("maintenance", move || {
+        serde_json::to_value(maintenance.upcoming(Duration::from_secs(24 * 3600))).unwrap_or_default()
+    });
+    if let Some(sla) = sla {
+        supervisor.add_probe("sla_degradation", move || serde_json::to_value(sla.status()).unwrap_or_default());
+    }
+    let flags_audit = audit.clone();
+    supervisor.add_route("/flags", move |method, path| {
+        let (status, body) = flags.handle_admin(method, path);
+        if let (Some(audit), 200, false) = (&flags_audit, status, method == "GET") {
+            audit.record("admin", None, AuditEvent::ConfigChanged {
+                version: None,
+                sections: vec!["features".to_string()],
+                detail: Some(format!("{} {}", method, path)),
+            });
+        }
+        (status, body)
+    });
+    if let Some(studies) = studies {
+        let studies_audit = audit.clone();
+        supervisor.add_route("/studies", move |method, path| {
+            let (status, body) = studies.handle_admin(method, path);
+            if let (Some(audit), 200, "POST") = (&studies_audit, status, method) {
+                audit.record("admin", None, AuditEvent::ConfigChanged {
+                    version: None,
+                    sections: vec!["worker".to_string()],
+                    detail: Some(format!("{} {}", method, path)),
+                });
+            }
+            (status, body)
+        });
+    }
+    if let Some(audit) = audit {
+        supervisor.add_route("/audit", move |method, path| audit.handle_admin(method, path));
+    }
+    if let Some(jobs) = backtest_jobs {
+        supervisor.add_route("/backtests", move |method, path| jobs.handle_admin(method, path));
+    }
+    // Equity history reads the bot's saved positions; prices and latency need the tick archive
+    supervisor.add_route("/sensitivities", move |method, path| sensitivities.handle_admin(method, path));
+    let mut history = HistoryService::new().with_positions_file(POSITION_FILE);
+    if let Some(config) = tick_store {
+        history = history.with_tick_store(config.dir.clone());
+        supervisor.add_route("/archive", move |method, path| tick_store::handle_admin(&config, method, path));
+    }
+    supervisor.add_route("/history", move |method, path| history.handle_admin(method, path));
+
+    info!("[RUNNER] Status endpoint on http://{}/status", status.local_addr()?);
+    let (status_stop_tx, status_stop_rx) = watch::channel(false);
+    let status_server = tokio::spawn(serve_status(status, supervisor.clone(), status_stop_rx));
+
+    if let Err(e) = supervisor.start().await {
+        error!("[RUNNER] Startup failed: {:#}", e);
+        supervisor.shutdown().await;
+        let _ = status_stop_tx.send(true);
+        return Err(e);
+    }
+    info!("[RUNNER] All subsystems ready");
+
+    tokio::select! {
+        _ = shutdown => info!("[RUNNER] Shutdown requested"),
+        failure = supervisor.wait_for_failure() => error!("[RUNNER] Subsystem failed ({}), shutting down", failure),
+    }
+
+    supervisor.shutdown().await;
+    let _ = status_stop_tx.send(true);
+    match status_server.await {
+        Ok(Err(e)) => warn!("[RUNNER] Status endpoint error: {:#}", e),
+        Err(e) => warn!("[RUNNER] Status endpoint task failed: {}", e),
+        Ok(Ok(())) => {}
+    }
+    info!("[RUNNER] Stopped");
+    Ok(())
+}
+
+/// Hot reload (SIGHUP / file change), forwarded to the bus as `Event::ConfigChanged`
+/// and applied to the feature flags
+fn config_subsystem(
+    reloader: Arc<ConfigReloader>,
+    bus: SharedEventBus,
+    flags: SharedFeatureFlags,
+    patterns: SharedPatternPolicy,
+    cooldowns: SharedCooldownManager,
+    sizing: SharedSizingCurve,
+) -> Subsystem {
+    Subsystem::new("config", move |ctx: SubsystemContext| {
+        let reloader = reloader.clone();
+        let bus = bus.clone();
+        let flags = flags.clone();
+        let patterns = patterns.clone();
+        let cooldowns = cooldowns.clone();
+        let sizing = sizing.clone();
+        async move {
+            let _forward = TaskGuard(forward_config_changes(reloader.subscribe(), bus));
+            let _flags = TaskGuard(feature_flags::watch_config(flags, reloader.subscribe()));
+            let _patterns = TaskGuard(pattern_policy::watch_config(patterns, reloader.subscribe()));
+            let _cooldowns = TaskGuard(market_cooldown::watch_config(cooldowns, reloader.subscribe()));
+            let _sizing = TaskGuard(sizing_curve::watch_config(sizing, reloader.subscribe()));
+            let _logging = TaskGuard(logging::watch_config(reloader.subscribe()));
+            ctx.ready();
+            tokio::select! {
+                _ = reloader.run(Duration::from_secs(5)) => {}
+                _ = ctx.shutdown_requested() => {}
+            }
+            Ok(())
+        }
+    })
+}
+
+/// Dashboard fed by the bus; the latest snapshot is served as the "dashboard" probe
+#[allow(clippy::too_many_arguments)]
+fn monitoring_subsystem(
+    reloader: Arc<ConfigReloader>,
+    bus: SharedEventBus,
+    flags: SharedFeatureFlags,
+    cooldowns: SharedCooldownManager,
+    verifier: Option<SharedPatternVerifier>,
+    backtest_jobs: Option<SharedBacktestJobs>,
+    backtest_results: Option<SharedBacktestResults>,
+    studies: Option<SharedStudyStore>,
+    sla: Option<SharedSlaMonitor>,
+    decision_latency: SharedDecisionLatency,
+    sensitivities: SharedSensitivityService,
+    review: SharedReviewQueue,
+    latest: Arc<Mutex<serde_json::Value>>,
+) -> Subsystem {
+    Subsystem::new("monitoring", move |ctx: SubsystemContext| {
+        let reloader = reloader.clone();
+        let bus = bus.clone();
+        let flags = flags.clone();
+        let cooldowns = cooldowns.clone();
+        let verifier = verifier.clone();
+        let backtest_jobs = backtest_jobs.clone();
+        let backtest_results = backtest_results.clone();
+        let studies = studies.clone();
+        let sla = sla.clone();
+        let decision_latency = decision_latency.clone();
+        let sensitivities = sensitivities.clone();
+        let review = review.clone();
+        let latest = latest.clone();
+        async move {
+            let mut dashboard = MonitoringDashboard::new()
+                .with_event_bus(bus.clone())
+                .with_feature_flags(flags)
+                .with_cooldowns(cooldowns)
+                .with_sensitivities(sensitivities)
+                .with_decision_latency(decision_latency.clone())
+                .with_review_queue(review);
+            if let Some(verifier) = verifier {
+                dashboard = dashboard.with_pattern_verifier(verifier);
+            }
+            if let Some(jobs) = backtest_jobs {
+                dashboard = dashboard.with_backtest_jobs(jobs);
+            }
+            if let Some(results) = backtest_results {
+                dashboard = dashboard.with_backtest_results(results);
+            }
+            if let Some(studies) = studies {
+                dashboard = dashboard.with_studies(studies);
+            }
+            // The bot's saved positions, aged against their patterns' half-lives and alerted when stale
+            let alert_bus = bus.clone();
+            let sweeper = Arc::new(
+                PositionSweeper::new(&reloader.current().patterns)
+                    .on_stale(move |stale| {
+                        alert_bus.publish(Event::Alert(stale.to_alert()));
+                    }),
+            );
+            dashboard = dashboard.with_position_sweeper(sweeper.clone());
+            let _aging_config = TaskGuard(position_aging::watch_config(sweeper.clone(), reloader.subscribe()));
+            let _aging = TaskGuard(tokio::spawn(run_saved_position_sweeper(sweeper, PathBuf::from(POSITION_FILE))));
+            // The dashboard's model telemetry feeds the SLA policy, evaluated alongside it; its
+            // evaluations roll the decision latency windows, or a plain timer does without it
+            let _sla = match sla {
+                Some(sla) => {
+                    dashboard = dashboard.with_sla_monitor(sla.clone());
+                    TaskGuard(tokio::spawn(run_sla_monitor(sla, EVALUATION_INTERVAL)))
+                }
+                None => TaskGuard(tokio::spawn(run_decision_windows(decision_latency, EVALUATION_INTERVAL))),
+            };
+            dashboard.apply_config(&reloader.current().dashboard);
+            let dashboard = Arc::new(RwLock::new(dashboard));
+            let subscription = bus.subscribe("monitoring", &[
+                Topic::Signal, Topic::Opportunity, Topic::Feed, Topic::Alert, Topic::Config,
+            ]);
+            let _handler = TaskGuard(spawn_handler(dashboard.clone(), subscription));
+            ctx.ready();
+
+            while !ctx.is_shutting_down() {
+                let interval = {
+                    let dashboard = dashboard.read().await;
+                    match dashboard.generate_snapshot().await {
+                        Ok(snapshot) => {
+                            *latest.lock().unwrap() = serde_json::to_value(&snapshot).unwrap_or_default();
+                        }
+                        Err(e) => warn!("[RUNNER] Dashboard snapshot failed: {}", e),
+                    }
+                    dashboard.update_interval_ms()
+                };
+                ctx.heartbeat();
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(interval)) => {}
+                    _ = ctx.shutdown_requested() => {}
+                }
+            }
+            Ok(())
+        }
+    })
+    .heartbeat_timeout(Duration::from_secs(30))
+}
+
+/// Append-only record of bus signals, opportunities, orders, fills and config reloads
+fn audit_subsystem(audit: SharedAuditLog, bus: SharedEventBus) -> Subsystem {
+    Subsystem::new("audit", move |ctx: SubsystemContext| {
+        let subscriber = Arc::new(RwLock::new(AuditSubscriber::new(audit.clone())));
+        let subscription = bus.subscribe("audit", &[
+            Topic::Signal, Topic::Opportunity, Topic::Order, Topic::Fill, Topic::Config,
+        ]);
+        async move {
+            let _handler = TaskGuard(spawn_handler(subscriber, subscription));
+            ctx.ready();
+            ctx.shutdown_requested().await;
+            Ok(())
+        }
+    })
+}
+
+/// Backtest job workers; queued jobs stay queued (and finished ones on disk) across restarts of
+/// this subsystem
+fn backtest_subsystem(jobs: SharedBacktestJobs) -> Subsystem {
+    Subsystem::new("backtests", move |ctx: SubsystemContext| {
+        let jobs = jobs.clone();
+        async move {
+            let _workers = TaskGuard(tokio::spawn(run_backtest_jobs(jobs)));
+            ctx.ready();
+            ctx.shutdown_requested().await;
+            Ok(())
+        }
+    })
+}
+
+/// Tick and signal archive; buffered rows are written out on shutdown. Pattern verification
+/// replays the archived segments in the background.
+fn archive_subsystem(config: TickStoreConfig, bus: SharedEventBus, verifier: SharedPatternVerifier) -> Subsystem {
+    Subsystem::new("archive", move |ctx: SubsystemContext| {
+        let subscriber = Arc::new(RwLock::new(ArchiveSubscriber::new(config.clone())));
+        let subscription = bus.subscribe("archive", &[Topic::Tick, Topic::Signal]);
+        let verification = run_verification_loop(verifier.clone(), config.dir.clone());
+        async move {
+            let handler = TaskGuard(spawn_handler(subscriber.clone(), subscription));
+            let _verification = TaskGuard(tokio::spawn(verification));
+            ctx.ready();
+            while !ctx.is_shutting_down() {
+                tokio::select! {
+                    _ = tokio::time::sleep(ARCHIVE_HEARTBEAT) => {}
+                    _ = ctx.shutdown_requested() => break,
+                }
+                // The handler holds the lock while writing; getting it means segment writes aren't stuck
+                drop(subscriber.read().await);
+                ctx.heartbeat();
+            }
+            drop(handler);
+            subscriber.write().await.flush();
+            Ok(())
+        }
+    })
+    .watchdog(
+        Watchdog::new(Duration::from_secs(30))
+            .alert_after(Duration::from_secs(60))
+            .restart_after(Duration::from_secs(120)),
+    )
+}
+
+/// Two-sided quotes on wide, thin Kalshi books: discovers the configured leagues' markets,
+/// keeps their books from the Kalshi socket and requotes them every second
+fn market_making_subsystem(
+    reloader: Arc<ConfigReloader>,
+    maker: SharedMarketMaker,
+    prefilters: SharedObservationPrefilters,
+    schemas: SharedFeedSchemas,
+    sensitivities: SharedSensitivityService,
+) -> Subsystem {
+    Subsystem::new("market_making", move |ctx: SubsystemContext| {
+        let reloader = reloader.clone();
+        let maker = maker.clone();
+        let prefilters = prefilters.clone();
+        let schemas = schemas.clone();
+        let sensitivities = sensitivities.clone();
+        async move {
+            let config = reloader.current();
+            let secrets = SecretsChain::from_config(&config.secrets);
+            let client = Arc::new(KalshiApiClient::new(KalshiConfig::from_secrets(&secrets).await?));
+            let leagues: Vec<&str> = config.feeds.enabled_leagues.iter().map(String::as_str).collect();
+            let mut discovery = DiscoveryClient::new(
+                KalshiApiClient::new(KalshiConfig::from_secrets(&secrets).await?),
+                TeamCache::load(),
+            );
+            if !config.redis.url.is_empty() {
+                discovery = discovery.with_cache_backend(Arc::new(RedisBackend::new(&config.redis.url)));
+            }
+            let result = discovery.discover_all(&leagues).await;
+            sensitivities.set_pairs(result.pairs.iter().cloned());
+            // Quote sizes respect [risk.groups] series/event limits across the quoted markets
+            let hierarchy = MarketHierarchy::from_pairs(&result.pairs, config.risk.groups.clone());
+            let state = Arc::new({
+                let mut s = GlobalState::new();
+                for pair in result.pairs {
+                    s.add_pair(pair);
+                }
+                s
+            });
+            info!("[RUNNER] Market making across {} Kalshi markets", state.market_count());
+
+            // Only the books are used; a zero threshold never raises arb requests
+            let (exec_tx, _exec_rx) = mpsc::channel(1);
+            let ws_config = KalshiConfig::from_secrets(&secrets).await?;
+            let ws_state = state.clone();
+            let reconnect = Duration::from_secs(config.feeds.ws_reconnect_delay_secs);
+            let _ws = TaskGuard(tokio::spawn(async move {
+                loop {
+                    if let Err(e) = kalshi::run_ws(&ws_config, &schemas, ws_state.clone(), exec_tx.clone(), 0, clock::system()).await {
+                        warn!("[RUNNER] Kalshi socket for market making dropped: {}", e);
+                    }
+                    tokio::time::sleep(reconnect).await;
+                }
+            }));
+            let breaker = Arc::new(TradingCircuitBreaker::new(CircuitBreakerConfig::from(&config.risk)).with_hierarchy(hierarchy));
+            let _config = TaskGuard(market_maker::watch_config(maker.clone(), reloader.subscribe()));
+            let _prefilter_config = TaskGuard(observation_prefilter::watch_config(prefilters, reloader.subscribe()));
+            let quotes = TaskGuard(tokio::spawn(run_market_maker_loop(maker.clone(), state, client.clone(), breaker)));
+            ctx.ready();
+            ctx.shutdown_requested().await;
+            drop(quotes);
+            let left = market_maker::pull_all(&maker, &client).await;
+            if left > 0 {
+                warn!("[RUNNER] {} market making quotes may still be resting", left);
+            }
+            Ok(())
+        }
+    })
+}
+
+/// Exposure, order sizing and provider breakers, fed by ticks, orders and fills
+fn risk_subsystem(
+    reloader: Arc<ConfigReloader>,
+    bus: SharedEventBus,
+    audit: Option<SharedAuditLog>,
+    patterns: SharedPatternPolicy,
+    impact: SharedMarketImpact,
+) -> Subsystem {
+    Subsystem::new("risk", move |ctx: SubsystemContext| {
+        let reloader = reloader.clone();
+        let bus = bus.clone();
+        let audit = audit.clone();
+        let patterns = patterns.clone();
+        let impact = impact.clone();
+        async move {
+            let current = reloader.current();
+            let risk = &current.risk;
+            let config = RiskConfig {
+                provider_failure_threshold: risk.provider_failure_threshold,
+                circuit_reset_seconds: risk.circuit_reset_secs,
+                ..RiskConfig::default()
+            };
+            let interval = Duration::from_millis(config.exposure_monitor_interval_ms);
+            // Alerts are consumed from the bus
+            let (engine, _alert_rx) = RiskManagementEngine::new(config);
+            let mut engine = engine.with_event_bus(bus.clone()).with_pattern_policy(patterns).with_market_impact(impact);
+            if let Some(audit) = audit {
+                engine = engine.with_audit_log(audit);
+            }
+            let engine = Arc::new(RwLock::new(engine));
+            let subscription = bus.subscribe("risk", &[Topic::Tick, Topic::Order, Topic::Fill]);
+            let _handler = TaskGuard(spawn_handler(engine.clone(), subscription));
+            ctx.ready();
+
+            while !ctx.is_shutting_down() {
+                engine.write().await.monitor_risks().await;
+                ctx.heartbeat();
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = ctx.shutdown_requested() => {}
+                }
+            }
+            Ok(())
+        }
+    })
+    .heartbeat_timeout(Duration::from_secs(30))
+}
+
+/// Synthetic venue tick on its way from the generator to the aggregator
+#[derive(Debug, Clone, Copy)]
+struct FeedTick {
+    platform: Platform,
+    price: f64,
+    size: u16,
+    timestamp_ns: u64,
+}
+
+/// Paced synthetic multi-venue feed, published through the aggregator. The generator queues
+/// ticks on `queue`; the ingestion loop drains them into a pre-allocated batch, waiting with
+/// `spins` busy polls (0 = parked until the next push - see runtime_profile::RuntimeProfile).
+/// The local clock is sampled against NTP_SERVER alongside, when set.
+fn feeds_subsystem(
+    aggregator: Arc<RwLock<FeedAggregator>>,
+    queue: SharedIngestQueue<FeedTick>,
+    spins: u32,
+    feed: SyntheticFeed,
+    clock_sync: SharedClockSync,
+    clock_sync_config: ClockSyncConfig,
+) -> Subsystem {
+    Subsystem::new("feeds", move |ctx: SubsystemContext| {
+        let aggregator = aggregator.clone();
+        let queue = queue.clone();
+        let ntp = run_ntp_sync(clock_sync.clone(), clock_sync_config.clone());
+        async move {
+            let _ntp = TaskGuard(tokio::spawn(ntp));
+            let config = SyntheticMarketConfig {
+                seed: feed.seed,
+                tick_interval_ms: feed.tick_interval_ms,
+                ..SyntheticMarketConfig::default()
+            };
+            let providers: Vec<_> = config.venues.iter().map(|v| v.platform).collect();
+            let pace = Duration::from_secs_f64(config.tick_interval_ms.max(1.0) / 1000.0);
+            let mut generator = SyntheticMarketGenerator::new(config);
+            let clock = clock::system();
+
+            {
+                let mut aggregator = aggregator.write().await;
+                // A moneyline classifies as Tier 1; the synthetic game is always in play, so feeds
+                // are held to the Tier 1 heartbeat
+                aggregator.market_metadata().register(SYNTH_MARKET_ID, MarketType::Moneyline, None, None);
+                aggregator.set_market_phase(SYNTH_MARKET_ID, Some(EventPhase::InPlay), None);
+                for &provider in &providers {
+                    aggregator.add_provider(provider);
+                    aggregator.update_connection_status(provider, FeedStatus::Connected, None);
+                }
+            }
+
+            let producer_queue = queue.clone();
+            let mut producer = TaskGuard(tokio::spawn(async move {
+                let mut interval = tokio::time::interval(pace);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    interval.tick().await;
+                    let Some(bundle) = generator.next() else { break };
+                    for tick in bundle.multiple_markets.iter().flat_map(|m| m.values()) {
+                        let size = tick.size.clamp(0.0, u16::MAX as f64) as u16;
+                        let tick = FeedTick { platform: tick.platform, price: tick.price, size, timestamp_ns: bundle.timestamp_ns };
+                        if !producer_queue.push(tick) {
+                            crate::hot_path!(Level::WARN, "[FEEDS] Ingestion queue full, dropping tick");
+                        }
+                    }
+                }
+            }));
+            ctx.ready();
+
+            let mut batch = Vec::with_capacity(queue.capacity());
+            let mut next_check = tokio::time::Instant::now() + aggregator.read().await.next_check_in();
+            loop {
+                tokio::select! {
+                    _ = queue.ready(spins) => {}
+                    _ = &mut producer.0 => break,
+                    _ = ctx.shutdown_requested() => break,
+                }
+                queue.drain_into(&mut batch);
+                let mut aggregator = aggregator.write().await;
+                for tick in batch.drain(..) {
+                    // Synthetic venues all quote on the cent scale; the normalizer applies each venue's rounding
+                    let quote = BinaryQuote { yes: Quote::Cents(tick.price), no: None, yes_size: tick.size, no_size: tick.size };
+                    // Channel has no reader in the runner; the bus publish already happened
+                    let _ = aggregator.send_quote(
+                        SYNTH_MARKET_ID,
+                        tick.platform,
+                        MarketType::Moneyline,
+                        &quote,
+                        clock.now(),
+                        Some(tick.timestamp_ns),
+                    );
+                    aggregator.record_heartbeat(tick.platform);
+                }
+                // Heartbeat checks run at the interval of the busiest subscribed market
+                if tokio::time::Instant::now() >= next_check {
+                    aggregator.check_connections().await;
+                    next_check = tokio::time::Instant::now() + aggregator.next_check_in();
+                }
+                ctx.heartbeat();
+            }
+
+            let mut aggregator = aggregator.write().await;
+            for provider in providers {
+                aggregator.update_connection_status(provider, FeedStatus::Disconnected, None);
+            }
+            Ok(())
+        }
+    })
+    .watchdog(
+        Watchdog::new(Duration::from_secs(10))
+            .alert_after(Duration::from_secs(20))
+            .restart_after(Duration::from_secs(30))
+            .kill_after(Duration::from_secs(120)),
+    )
+}
+
+/// Latency arbitrage detection over bus ticks; signals are published by the engine.
+/// With a TCA store the minimum edge and market impact per tier and venue are refreshed from it daily.
+fn arbitrage_subsystem(
+    latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
+    bus: SharedEventBus,
+    edges: SharedEdgeThresholds,
+    impact: SharedMarketImpact,
+    tca: Option<SharedTcaStore>,
+) -> Subsystem {
+    Subsystem::new("arbitrage", move |ctx: SubsystemContext| {
+        let latency_engine = latency_engine.clone();
+        let mut ticks = bus.subscribe("arbitrage", &[Topic::Tick]);
+        let refresh = tca.clone().map(|store| run_edge_refresh_loop(edges.clone(), store));
+        let impact_refresh = tca.clone().map(|store| run_impact_refresh_loop(impact.clone(), store));
+        async move {
+            let _refresh = refresh.map(|r| TaskGuard(tokio::spawn(r)));
+            let _impact_refresh = impact_refresh.map(|r| TaskGuard(tokio::spawn(r)));
+            ctx.ready();
+            loop {
+                let envelope = tokio::select! {
+                    envelope = ticks.recv() => envelope,
+                    _ = ctx.shutdown_requested() => break,
+                };
+                let Some(envelope) = envelope else { break };
+                if let Event::Tick(update) = envelope.event.as_ref() {
+                    let mut engine = latency_engine.write().await;
+                    let tier = engine.market_tier(update.market_id, update.market_type);
+                    if let Some(obs) = update.to_observation(tier) {
+                        engine.add_price_observation(obs);
+                    }
+                    ctx.heartbeat();
+                }
+            }
+            Ok(())
+        }
+    })
+    // A dead feed starves this too; feeds owns the kill stage
+    .watchdog(
+        Watchdog::new(Duration::from_secs(10))
+            .alert_after(Duration::from_secs(30))
+            .restart_after(Duration::from_secs(60)),
+    )
+}
+
+/// Executes pending signals and settles in-flight executions; once `halted`
+/// only in-flight executions are settled
+#[allow(clippy::too_many_arguments)]
+fn execution_subsystem(
+    reloader: Arc<ConfigReloader>,
+    latency_engine: Arc<RwLock<LatencyArbitrageEngine>>,
+    aggregator: Arc<RwLock<FeedAggregator>>,
+    bus: SharedEventBus,
+    cooldowns: SharedCooldownManager,
+    decision_latency: SharedDecisionLatency,
+    shadow: SharedShadowBook,
+    review: SharedReviewQueue,
+    maintenance: SharedMaintenanceCalendar,
+    halted: Arc<AtomicBool>,
+) -> Subsystem {
+    Subsystem::new("execution", move |ctx: SubsystemContext| {
+        let (engine, _result_rx) = LatencyExecutionEngine::new(latency_engine.clone(), aggregator.clone());
+        let mut engine = engine
+            .with_event_bus(bus.clone())
+            .with_cooldowns(cooldowns.clone())
+            .with_maintenance(maintenance.clone())
+            .with_decision_latency(decision_latency.clone());
+        let paper_fills = reloader.current().paper_fills.clone();
+        if paper_fills.enabled {
+            engine = engine.with_paper_fills(Arc::new(PaperFillSimulator::new(PaperFillConfig::from(&paper_fills), clock::system())));
+        }
+        // After the paper fills, so shadow signals share the simulator
+        let mut engine = engine.with_shadow_book(shadow.clone()).with_review_queue(review.clone());
+        let cooldowns = cooldowns.clone();
+        let shadow = shadow.clone();
+        let review_config = reloader.subscribe();
+        let review = review.clone();
+        let maintenance = maintenance.clone();
+        let maintenance_config = reloader.subscribe();
+        let halted = halted.clone();
+        async move {
+            let _expiry = TaskGuard(tokio::spawn(run_cooldown_expiry_loop(cooldowns, Duration::from_secs(1))));
+            let _promotion = TaskGuard(tokio::spawn(run_shadow_promotion(shadow, PROMOTION_INTERVAL)));
+            let _review_config = TaskGuard(operator_review::watch_config(review, review_config));
+            // The bot's saved positions, checked against upcoming windows
+            let _maintenance_config = TaskGuard(maintenance::watch_config(maintenance.clone(), maintenance_config));
+            let _maintenance = TaskGuard(tokio::spawn(run_saved_position_sweep(maintenance, PathBuf::from(POSITION_FILE))));
+            ctx.ready();
+            while !ctx.is_shutting_down() {
+                if !halted.load(Ordering::SeqCst) {
+                    if let Err(e) = engine.process_signals().await {
+                        warn!("[RUNNER] Signal processing failed: {}", e);
+                    }
+                }
+                engine.monitor_executions().await;
+                ctx.heartbeat();
+                tokio::select! {
+                    _ = tokio::time::sleep(EXECUTION_POLL) => {}
+                    _ = ctx.shutdown_requested() => {}
+                }
+            }
+            Ok(())
+        }
+    })
+    .watchdog(
+        Watchdog::new(Duration::from_secs(10))
+            .alert_after(Duration::from_secs(15))
+            .restart_after(Duration::from_secs(30))
+            .kill_after(Duration::from_secs(60)),
+    )
+}
@@ -0,0 +1,303 @@
+// src/runtime_profile.rs
+// Low-latency runtime profile - pinned tokio workers, busy-polled Tier 1 ingestion, pre-allocated tick queues
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+use crate::config::RuntimeSection;
+
+/// How the process runtime is built and how feed ingestion waits for ticks (`[runtime]` section)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeProfile {
+    pub low_latency: bool,
+    pub worker_threads: usize,
+    pub pin_cores: Vec<usize>,
+    pub busy_poll_tiers: usize,
+    pub busy_poll_spins: u32,
+    pub tick_buffer: usize,
+}
+
+impl Default for RuntimeProfile {
+    fn default() -> Self {
+        Self::from_config(&RuntimeSection::default())
+    }
+}
+
+impl RuntimeProfile {
+    pub fn from_config(runtime: &RuntimeSection) -> Self {
+        Self {
+            low_latency: runtime.low_latency,
+            worker_threads: runtime.worker_threads,
+            pin_cores: runtime.pin_cores.clone(),
+            busy_poll_tiers: runtime.busy_poll_tiers,
+            busy_poll_spins: runtime.busy_poll_spins,
+            tick_buffer: runtime.tick_buffer.max(1),
+        }
+    }
+
+    /// Worker count; with pinning, one per listed core unless set explicitly
+    pub fn workers(&self) -> usize {
+        match (self.worker_threads, self.pin_cores.len()) {
+            (0, 0) => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            (0, cores) => cores,
+            (n, _) => n,
+        }
+    }
+
+    /// Core for the `n`th worker to start (None = leave it to the scheduler)
+    pub fn core_for(&self, n: usize) -> Option<usize> {
+        if !self.low_latency || n >= self.workers() {
+            return None;
+        }
+        if self.pin_cores.is_empty() {
+            Some(n)
+        } else {
+            self.pin_cores.get(n).copied()
+        }
+    }
+
+    /// Empty polls spun before yielding while waiting on a tier-`tier` queue (0 = Tier 1);
+    /// 0 parks the task until the next push
+    pub fn spins_for(&self, tier: usize) -> u32 {
+        if self.low_latency && tier < self.busy_poll_tiers {
+            self.busy_poll_spins.max(1)
+        } else {
+            0
+        }
+    }
+
+    /// Multi-thread runtime; in low-latency mode each worker is pinned to its core as it starts.
+    /// Workers are the first threads the runtime starts, so blocking-pool threads stay unpinned.
+    pub fn build_runtime(&self) -> io::Result<Runtime> {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all();
+        if self.worker_threads > 0 || self.low_latency {
+            builder.worker_threads(self.workers());
+        }
+        if self.low_latency {
+            let profile = self.clone();
+            let started = AtomicUsize::new(0);
+            builder.thread_name("arb-worker").on_thread_start(move || {
+                let n = started.fetch_add(1, Ordering::Relaxed);
+                if let Some(core) = profile.core_for(n) {
+                    if !pin_current_thread(core) {
+                        warn!("[RUNTIME] Could not pin worker {} to core {}", n, core);
+                    }
+                }
+            });
+            info!("[RUNTIME] Low-latency profile: {} pinned workers, Tier 1..{} busy-polled ({} spins), {} tick buffer",
+                  self.workers(), self.busy_poll_tiers, self.busy_poll_spins, self.tick_buffer);
+        }
+        builder.build()
+    }
+}
+
+/// Restrict the calling thread to one core; false where affinity is unsupported or the core doesn't exist
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(core: usize) -> bool {
+    if core >= libc::CPU_SETSIZE as usize {
+        return false;
+    }
+    // SAFETY: cpu_set_t is plain data, zeroed is the empty set, and pid 0 is the calling thread
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_core: usize) -> bool {
+    false
+}
+
+/// Counters for an ingestion queue
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct IngestStats {
+    pub pushed: u64,
+    /// Ticks refused because the queue was full
+    pub dropped: u64,
+    /// Most ticks queued at once
+    pub high_water: usize,
+}
+
+/// Bounded tick queue allocated up front: pushes never grow it and drains fill a caller-owned
+/// buffer, so the feed -> engine hop allocates nothing per tick. Consumers either park until
+/// the next push or busy-poll (see [`RuntimeProfile::spins_for`]).
+pub struct IngestQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    /// Mirrors the queue length so pollers can spin without taking the lock
+    len: AtomicUsize,
+    notify: Notify,
+    pushed: AtomicU64,
+    dropped: AtomicU64,
+    high_water: AtomicUsize,
+}
+
+pub type SharedIngestQueue<T> = Arc<IngestQueue<T>>;
+
+impl<T> IngestQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            len: AtomicUsize::new(0),
+            notify: Notify::new(),
+            pushed: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            high_water: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue a tick; false (and counted) when full - the newest tick is the one dropped
+    pub fn push(&self, item: T) -> bool {
+        {
+            let mut items = self.items.lock().unwrap();
+            if items.len() >= self.capacity {
+                drop(items);
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return false;
+            }
+            items.push_back(item);
+            self.len.store(items.len(), Ordering::Release);
+            self.high_water.fetch_max(items.len(), Ordering::Relaxed);
+        }
+        self.pushed.fetch_add(1, Ordering::Relaxed);
+        self.notify.notify_one();
+        true
+    }
+
+    /// Move queued ticks into `out` without growing it (up to its spare capacity); returns the count
+    pub fn drain_into(&self, out: &mut Vec<T>) -> usize {
+        let room = out.capacity() - out.len();
+        if room == 0 {
+            return 0;
+        }
+        let mut items = self.items.lock().unwrap();
+        let n = items.len().min(room);
+        out.extend(items.drain(..n));
+        self.len.store(items.len(), Ordering::Release);
+        n
+    }
+
+    /// Wait until a tick is queued. With `spins` > 0 the caller busy-polls: it checks the queue
+    /// `spins` times, then yields to other tasks and checks again, never parking its worker.
+    pub async fn ready(&self, spins: u32) {
+        loop {
+            if spins > 0 {
+                for _ in 0..spins {
+                    if !self.is_empty() {
+                        return;
+                    }
+                    std::hint::spin_loop();
+                }
+                tokio::task::yield_now().await;
+                continue;
+            }
+            let notified = self.notify.notified();
+            if !self.is_empty() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn stats(&self) -> IngestStats {
+        IngestStats {
+            pushed: self.pushed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            high_water: self.high_water.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_profile_from_config() {
+        let standard = RuntimeProfile::default();
+        assert!(!standard.low_latency);
+        assert_eq!(standard.spins_for(0), 0);
+        assert_eq!(standard.core_for(0), None);
+
+        let profile = RuntimeProfile::from_config(&RuntimeSection {
+            low_latency: true,
+            pin_cores: vec![2, 3],
+            ..RuntimeSection::default()
+        });
+        assert_eq!(profile.workers(), 2);
+        assert_eq!(profile.core_for(0), Some(2));
+        assert_eq!(profile.core_for(1), Some(3));
+        // Later threads are the blocking pool
+        assert_eq!(profile.core_for(2), None);
+        // Tier 1 only by default
+        assert_eq!(profile.spins_for(0), 1000);
+        assert_eq!(profile.spins_for(1), 0);
+
+        let unlisted = RuntimeProfile::from_config(&RuntimeSection {
+            low_latency: true,
+            worker_threads: 3,
+            ..RuntimeSection::default()
+        });
+        assert_eq!((unlisted.workers(), unlisted.core_for(2)), (3, Some(2)));
+    }
+
+    #[test]
+    fn test_queue_is_bounded_and_preallocated() {
+        let queue = IngestQueue::new(4);
+        for i in 0..6 {
+            queue.push(i);
+        }
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.stats(), IngestStats { pushed: 4, dropped: 2, high_water: 4 });
+
+        // Drains stop at the buffer's spare capacity instead of reallocating it
+        let mut batch = Vec::with_capacity(3);
+        assert_eq!(queue.drain_into(&mut batch), 3);
+        assert_eq!(batch, vec![0, 1, 2]);
+        assert_eq!(batch.capacity(), 3);
+        assert_eq!(queue.drain_into(&mut batch), 0);
+        batch.clear();
+        assert_eq!(queue.drain_into(&mut batch), 1);
+        assert_eq!(batch, vec![3]);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ready_wakes_parked_and_polling_consumers() {
+        for spins in [0, 100] {
+            let queue: SharedIngestQueue<u32> = Arc::new(IngestQueue::new(8));
+            let producer = queue.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                producer.push(7);
+            });
+            tokio::time::timeout(Duration::from_secs(1), queue.ready(spins)).await.expect("woken by the push");
+            let mut batch = Vec::with_capacity(8);
+            assert_eq!(queue.drain_into(&mut batch), 1);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+// src/schedule.rs
+// Civil-time schedules - five-field cron expressions evaluated in an explicit IANA time zone
+// ("30 16 * * 1-5 America/New_York"), for jobs tied to the trading day rather than an
+// interval: reconciliation after the close, end-of-day reports, overnight re-optimization.
+//
+// DST: a time skipped by a spring-forward fires when the clocks jump; a time repeated by a
+// fall-back fires once, at its first occurrence.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::clock::Clock;
+use crate::types::Nanos;
+
+/// Longest single sleep while waiting for a firing; wall-clock steps (NTP, suspend) are
+/// caught up on the next wake
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+/// Furthest a firing is searched for (covers Feb 29 on a given weekday)
+const SEARCH_DAYS: i64 = 366 * 8;
+
+/// Allowed values of one cron field, as a bit set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    bits: u64,
+    /// Written as `*` (matters for the day-of-month / day-of-week rule)
+    any: bool,
+}
+
+impl Field {
+    fn parse(spec: &str, name: &str, min: u32, max: u32) -> Result<Self> {
+        let mut bits = 0u64;
+        for part in spec.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step: u32 = step.parse().map_err(|_| anyhow!("bad {} step '{}'", name, step))?;
+                    if step == 0 {
+                        bail!("{} step must be positive", name);
+                    }
+                    (range, step)
+                }
+                None => (part, 1),
+            };
+            let (lo, hi) = if range == "*" {
+                (min, max)
+            } else if let Some((lo, hi)) = range.split_once('-') {
+                (value(lo, name, min, max)?, value(hi, name, min, max)?)
+            } else {
+                let v = value(range, name, min, max)?;
+                // "5/15" runs from 5 to the end of the range
+                (v, if step > 1 { max } else { v })
+            };
+            if lo > hi {
+                bail!("{} range '{}' runs backwards", name, range);
+            }
+            for v in (lo..=hi).step_by(step as usize) {
+                bits |= 1 << v;
+            }
+        }
+        Ok(Self { bits, any: spec == "*" })
+    }
+
+    fn contains(&self, v: u32) -> bool {
+        self.bits & (1 << v) != 0
+    }
+
+    fn values(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..64).filter(|v| self.contains(*v))
+    }
+}
+
+fn value(s: &str, name: &str, min: u32, max: u32) -> Result<u32> {
+    match s.parse::<u32>() {
+        Ok(v) if (min..=max).contains(&v) => Ok(v),
+        _ => bail!("{} '{}' outside {}-{}", name, s, min, max),
+    }
+}
+
+/// Cron schedule in a time zone: `minute hour day-of-month month day-of-week zone`.
+/// Fields take `*`, `a`, `a-b`, `*/n`, `a-b/n` and comma lists; day-of-week is 0-7 with
+/// Sunday as 0 or 7. When both day fields are restricted either may match (as cron does).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    minute: Field,
+    hour: Field,
+    day_of_month: Field,
+    month: Field,
+    day_of_week: Field,
+    zone: Tz,
+    spec: String,
+}
+
+impl Schedule {
+    pub fn zone(&self) -> Tz {
+        self.zone
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !self.month.contains(date.month()) {
+            return false;
+        }
+        let dom = self.day_of_month.contains(date.day());
+        let dow = self.day_of_week.contains(date.weekday().num_days_from_sunday());
+        match (self.day_of_month.any, self.day_of_week.any) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        }
+    }
+
+    /// The instant a local wall time happens in the zone (see the DST rules above)
+    fn resolve(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self.zone.from_local_datetime(&local) {
+            LocalResult::Single(t) => Some(t.with_timezone(&Utc)),
+            LocalResult::Ambiguous(first, _) => Some(first.with_timezone(&Utc)),
+            // In a spring-forward gap: the first minute that exists is when the clocks jump
+            LocalResult::None => (1..=24 * 60)
+                .map(|m| local + ChronoDuration::minutes(m))
+                .find_map(|t| self.zone.from_local_datetime(&t).earliest())
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+
+    /// First firing strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_timezone(&self.zone).date_naive();
+        for day in 0..SEARCH_DAYS {
+            let date = start + ChronoDuration::days(day);
+            if !self.matches_date(date) {
+                continue;
+            }
+            for hour in self.hour.values() {
+                for minute in self.minute.values() {
+                    let local = date.and_hms_opt(hour, minute, 0)?;
+                    match self.resolve(local) {
+                        Some(t) if t > after => return Some(t),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Next firing after a wall-clock reading (Unix ns)
+    pub fn next_after_ns(&self, wall: Nanos) -> Option<Nanos> {
+        let next = self.next_after(DateTime::from_timestamp_nanos(wall.0 as i64))?;
+        Some(Nanos(next.timestamp_nanos_opt()? as u64))
+    }
+
+    /// Wait for the next firing on `clock`; returns its time, None if the schedule never fires
+    pub async fn wait_next(&self, clock: &dyn Clock) -> Option<Nanos> {
+        let due = self.next_after_ns(clock.wall_ns())?;
+        loop {
+            let now = clock.wall_ns();
+            if now >= due {
+                return Some(due);
+            }
+            tokio::time::sleep(Duration::from_nanos(due.0 - now.0).min(MAX_SLEEP)).await;
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week, zone] = fields[..] else {
+            bail!("schedule '{}' needs 5 cron fields and a time zone", s);
+        };
+        let mut day_of_week = Field::parse(day_of_week, "day-of-week", 0, 7)?;
+        if day_of_week.contains(7) {
+            day_of_week.bits = (day_of_week.bits | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minute: Field::parse(minute, "minute", 0, 59)?,
+            hour: Field::parse(hour, "hour", 0, 23)?,
+            day_of_month: Field::parse(day_of_month, "day-of-month", 1, 31)?,
+            month: Field::parse(month, "month", 1, 12)?,
+            day_of_week,
+            zone: zone.parse().map_err(|_| anyhow!("unknown time zone '{}'", zone))?,
+            spec: fields.join(" "),
+        })
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+impl Serialize for Schedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let spec = String::deserialize(deserializer)?;
+        spec.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(schedule: &str, after: &str) -> String {
+        let schedule: Schedule = schedule.parse().unwrap();
+        schedule.next_after(utc(after)).unwrap().to_rfc3339()
+    }
+
+    #[test]
+    fn test_fields_and_zones() {
+        // 16:30 New York on weekdays: Friday evening UTC rolls to Monday
+        assert_eq!(next("30 16 * * 1-5 America/New_York", "2026-10-16T21:00:00Z"), "2026-10-19T20:30:00+00:00");
+        assert_eq!(next("*/15 9-10 * * * UTC", "2026-10-18T10:50:00Z"), "2026-10-19T09:00:00+00:00");
+        // Day-of-month or Sunday (both restricted), Sunday written as 7
+        assert_eq!(next("0 0 1 * 7 UTC", "2026-10-05T00:00:00Z"), "2026-10-11T00:00:00+00:00");
+        assert_eq!(next("0 12 29 2 * Europe/London", "2026-03-01T00:00:00Z"), "2028-02-29T12:00:00+00:00");
+
+        let schedule: Schedule = " 0  17 * * 1,3 America/Chicago ".parse().unwrap();
+        assert_eq!(schedule.to_string(), "0 17 * * 1,3 America/Chicago");
+        assert_eq!(serde_json::from_str::<Schedule>("\"0 17 * * 1,3 America/Chicago\"").unwrap(), schedule);
+        for bad in ["0 17 * * *", "60 * * * * UTC", "0 5-1 * * * UTC", "*/0 * * * * UTC", "0 0 * * * Mars/Olympus"] {
+            assert!(bad.parse::<Schedule>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        let daily = "30 2 * * * America/New_York";
+        // Spring forward 2026-03-08: 02:30 doesn't exist, fires when clocks jump to 03:00 EDT
+        assert_eq!(next(daily, "2026-03-08T05:00:00Z"), "2026-03-08T07:00:00+00:00");
+        assert_eq!(next(daily, "2026-03-08T07:00:00Z"), "2026-03-09T06:30:00+00:00");
+        // Fall back 2026-11-01: 01:30 happens twice, fires at the first (EDT) only
+        let repeated = "30 1 * * * America/New_York";
+        assert_eq!(next(repeated, "2026-11-01T04:00:00Z"), "2026-11-01T05:30:00+00:00");
+        assert_eq!(next(repeated, "2026-11-01T05:30:00Z"), "2026-11-02T06:30:00+00:00");
+        // Hourly keeps one firing per wall-clock hour across the repeat
+        let hourly = "0 * * * * America/New_York";
+        assert_eq!(next(hourly, "2026-11-01T05:00:00Z"), "2026-11-01T07:00:00+00:00");
+    }
+}
@@ -0,0 +1,386 @@
+// src/secrets.rs
+// Credential lookup - env, 0600 files, OS keychain, Vault - with zeroized in-memory storage
+
+use futures_util::future::BoxFuture;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use zeroize::Zeroizing;
+
+use crate::config::SecretsSection;
+use crate::error::SecretsError;
+
+/// Kalshi API key id (sent as a header, but still a credential)
+pub const KALSHI_API_KEY_ID: &str = "KALSHI_API_KEY_ID";
+/// Kalshi RSA private key, PKCS#1 PEM
+pub const KALSHI_PRIVATE_KEY: &str = "KALSHI_PRIVATE_KEY";
+/// Polymarket signer private key, hex
+pub const POLY_PRIVATE_KEY: &str = "POLY_PRIVATE_KEY";
+/// Polymarket funder (proxy wallet) address
+pub const POLY_FUNDER: &str = "POLY_FUNDER";
+
+/// Secret string; wiped from memory on drop and never printed by Debug
+#[derive(Clone, Default)]
+pub struct Secret(Zeroizing<String>);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(Zeroizing::new(value.into()))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl PartialEq for Secret {
+    fn eq(&self, other: &Self) -> bool {
+        self.expose() == other.expose()
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() { write!(f, "<unset>") } else { write!(f, "<redacted>") }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Secret {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// A source of named secrets
+pub trait SecretsProvider: Send + Sync {
+    /// Backend name for logs
+    fn name(&self) -> &'static str;
+
+    /// `Ok(None)` when this backend doesn't hold `key`
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>>;
+
+    fn require<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Secret, SecretsError>> {
+        Box::pin(async move {
+            self.get(key).await?.ok_or_else(|| SecretsError::Missing { key: key.to_string() })
+        })
+    }
+}
+
+// === Environment ===
+
+/// Secrets from process env vars (named exactly as the key)
+pub struct EnvSecrets;
+
+impl SecretsProvider for EnvSecrets {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+        let value = std::env::var(key).ok().filter(|v| !v.is_empty()).map(Secret::new);
+        Box::pin(async move { Ok(value) })
+    }
+}
+
+// === Files ===
+
+/// One file per secret under `dir`, named after the key; files must be 0600
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl FileSecrets {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl SecretsProvider for FileSecrets {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+        let path = self.dir.join(key);
+        Box::pin(async move {
+            if !path.exists() {
+                return Ok(None);
+            }
+            read_secret_file(&path).map(Some)
+        })
+    }
+}
+
+/// Read a secret file, refusing anything group/other can read
+pub fn read_secret_file(path: &Path) -> Result<Secret, SecretsError> {
+    let io_err = |source| SecretsError::Io { path: path.display().to_string(), source };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path).map_err(io_err)?.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            return Err(SecretsError::InsecurePermissions { path: path.display().to_string(), mode });
+        }
+    }
+    let raw = Zeroizing::new(std::fs::read_to_string(path).map_err(io_err)?);
+    Ok(Secret::new(raw.trim()))
+}
+
+// === OS keychain ===
+
+/// macOS Keychain / Windows Credential Manager / Linux keyutils entries under `service`
+#[cfg(feature = "keychain")]
+pub struct KeychainSecrets {
+    service: String,
+}
+
+#[cfg(feature = "keychain")]
+impl KeychainSecrets {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self { service: service.into() }
+    }
+}
+
+#[cfg(feature = "keychain")]
+impl SecretsProvider for KeychainSecrets {
+    fn name(&self) -> &'static str {
+        "keychain"
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+        let backend = |e: keyring::Error| SecretsError::Backend { backend: "keychain", message: e.to_string() };
+        let result = keyring::Entry::new(&self.service, key)
+            .and_then(|entry| entry.get_password())
+            .map(|password| Some(Secret::new(password)))
+            .or_else(|e| match e {
+                keyring::Error::NoEntry => Ok(None),
+                e => Err(backend(e)),
+            });
+        Box::pin(async move { result })
+    }
+}
+
+// === Vault ===
+
+/// HashiCorp Vault KV v2: every key is a field of the secret at `<mount>/data/<path>`
+pub struct VaultSecrets {
+    addr: String,
+    token: Secret,
+    mount: String,
+    path: String,
+    http: reqwest::Client,
+}
+
+impl VaultSecrets {
+    pub fn new(addr: &str, token: Secret, mount: &str, path: &str) -> Self {
+        Self {
+            addr: addr.trim_end_matches('/').to_string(),
+            token,
+            mount: mount.trim_matches('/').to_string(),
+            path: path.trim_matches('/').to_string(),
+            http: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("reqwest client"),
+        }
+    }
+
+    /// None unless both the address and token are set
+    pub fn from_config(config: &SecretsSection) -> Option<Self> {
+        if config.vault_addr.is_empty() || config.vault_token.expose().is_empty() {
+            return None;
+        }
+        Some(Self::new(&config.vault_addr, config.vault_token.clone(), &config.vault_mount, &config.vault_path))
+    }
+}
+
+impl SecretsProvider for VaultSecrets {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+        Box::pin(async move {
+            let backend = |message: String| SecretsError::Backend { backend: "vault", message };
+            let url = format!("{}/v1/{}/data/{}", self.addr, self.mount, self.path);
+            let resp = self.http
+                .get(&url)
+                .header("X-Vault-Token", self.token.expose())
+                .send()
+                .await
+                .map_err(|e| backend(e.to_string()))?;
+            if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            if !resp.status().is_success() {
+                return Err(backend(format!("{} reading {}/{}", resp.status(), self.mount, self.path)));
+            }
+            let body = Zeroizing::new(resp.text().await.map_err(|e| backend(e.to_string()))?);
+            let json: serde_json::Value = serde_json::from_str(&body).map_err(|e| backend(e.to_string()))?;
+            Ok(json["data"]["data"][key].as_str().map(Secret::new))
+        })
+    }
+}
+
+// === Chain ===
+
+/// Backends tried in order; the first one holding a key wins
+#[derive(Default)]
+pub struct SecretsChain {
+    providers: Vec<Box<dyn SecretsProvider>>,
+}
+
+impl SecretsChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, provider: impl SecretsProvider + 'static) -> Self {
+        self.providers.push(Box::new(provider));
+        self
+    }
+
+    /// The backends named in `config.backends`, in order; vault is skipped unless its
+    /// address and token are set
+    pub fn from_config(config: &SecretsSection) -> Self {
+        let mut chain = Self::new();
+        for name in config.backends.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match name {
+                "env" => chain = chain.with(EnvSecrets),
+                "file" => chain = chain.with(FileSecrets::new(config.dir.clone())),
+                #[cfg(feature = "keychain")]
+                "keychain" => chain = chain.with(KeychainSecrets::new(config.keychain_service.clone())),
+                #[cfg(not(feature = "keychain"))]
+                "keychain" => {}
+                "vault" => {
+                    if let Some(vault) = VaultSecrets::from_config(config) {
+                        chain = chain.with(vault);
+                    }
+                }
+                other => warn!("[SECRETS] Unknown backend '{}' in SECRETS_BACKENDS", other),
+            }
+        }
+        info!("[SECRETS] Backends: {:?}", chain.providers.iter().map(|p| p.name()).collect::<Vec<_>>());
+        chain
+    }
+}
+
+impl SecretsProvider for SecretsChain {
+    fn name(&self) -> &'static str {
+        "chain"
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+        Box::pin(async move {
+            for provider in &self.providers {
+                if let Some(secret) = provider.get(key).await? {
+                    tracing::debug!("[SECRETS] {} from {}", key, provider.name());
+                    return Ok(Some(secret));
+                }
+            }
+            Ok(None)
+        })
+    }
+}
+
+// === Account scope ===
+
+/// One venue account's view of another provider: `KEY` reads `KEY_<ACCOUNT>`
+/// (e.g. `KALSHI_API_KEY_ID_K2` for account `k2`), so per-account credentials live in
+/// the same backends under suffixed names
+pub struct ScopedSecrets<'a> {
+    inner: &'a dyn SecretsProvider,
+    suffix: String,
+}
+
+impl<'a> ScopedSecrets<'a> {
+    pub fn new(inner: &'a dyn SecretsProvider, account: &str) -> Self {
+        Self { inner, suffix: account.to_uppercase().replace('-', "_") }
+    }
+}
+
+impl SecretsProvider for ScopedSecrets<'_> {
+    fn name(&self) -> &'static str {
+        "scoped"
+    }
+
+    fn get<'b>(&'b self, key: &'b str) -> BoxFuture<'b, Result<Option<Secret>, SecretsError>> {
+        Box::pin(async move { self.inner.get(&format!("{}_{}", key, self.suffix)).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MapSecrets(HashMap<&'static str, &'static str>);
+
+    impl SecretsProvider for MapSecrets {
+        fn name(&self) -> &'static str {
+            "map"
+        }
+
+        fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Secret>, SecretsError>> {
+            let value = self.0.get(key).map(|v| Secret::new(*v));
+            Box::pin(async move { Ok(value) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_first_backend_wins() {
+        let chain = SecretsChain::new()
+            .with(MapSecrets(HashMap::from([(POLY_FUNDER, "0xfirst")])))
+            .with(MapSecrets(HashMap::from([(POLY_FUNDER, "0xsecond"), (POLY_PRIVATE_KEY, "0xkey")])));
+
+        assert_eq!(chain.require(POLY_FUNDER).await.unwrap().expose(), "0xfirst");
+        assert_eq!(chain.require(POLY_PRIVATE_KEY).await.unwrap().expose(), "0xkey");
+        assert!(matches!(
+            chain.require(KALSHI_API_KEY_ID).await,
+            Err(SecretsError::Missing { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scoped_secrets_read_account_suffixed_keys() {
+        let chain = SecretsChain::new()
+            .with(MapSecrets(HashMap::from([(POLY_FUNDER, "0xdefault"), ("POLY_FUNDER_POLY_2", "0xsecond")])));
+
+        let scoped = ScopedSecrets::new(&chain, "poly-2");
+        assert_eq!(scoped.require(POLY_FUNDER).await.unwrap().expose(), "0xsecond");
+        // No fallback to the unsuffixed key: a missing account credential is an error
+        assert!(matches!(scoped.require(POLY_PRIVATE_KEY).await, Err(SecretsError::Missing { key }) if key == POLY_PRIVATE_KEY));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_backend_requires_0600() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("arb-secrets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(POLY_PRIVATE_KEY);
+        std::fs::write(&path, "0xabc\n").unwrap();
+        let files = FileSecrets::new(&dir);
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(matches!(
+            files.get(POLY_PRIVATE_KEY).await,
+            Err(SecretsError::InsecurePermissions { mode: 0o644, .. })
+        ));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(files.get(POLY_PRIVATE_KEY).await.unwrap().unwrap().expose(), "0xabc");
+        assert!(files.get(POLY_FUNDER).await.unwrap().is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_secret_debug_redacted() {
+        assert_eq!(format!("{:?}", Secret::new("hunter2")), "<redacted>");
+        assert_eq!(format!("{:?}", Secret::default()), "<unset>");
+    }
+}
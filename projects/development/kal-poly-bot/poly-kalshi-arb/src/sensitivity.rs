@@ -0,0 +1,460 @@
+// src/sensitivity.rs
+// Portfolio factor sensitivities - P&L per move in each game's home win probability, total
+// points and star availability, so unhedged legs that stack into one hidden bet show up
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use crate::position_tracker::{ArbPosition, PositionTracker};
+use crate::types::{MarketPair, MarketType};
+
+/// Share of a factor's gross exposure one game may carry before it is flagged
+const CONCENTRATION_SHARE: f64 = 0.5;
+
+/// Common drivers of a game's markets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Factor {
+    /// Home team win probability; sensitivity in dollars per percentage point
+    HomeWin,
+    /// Expected total points (goals, runs); dollars per point
+    TotalPoints,
+    /// Star player availability; dollars if the star is ruled out
+    StarAvailability,
+}
+
+impl Factor {
+    pub const ALL: [Factor; 3] = [Factor::HomeWin, Factor::TotalPoints, Factor::StarAvailability];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Factor::HomeWin => "home_win",
+            Factor::TotalPoints => "total_points",
+            Factor::StarAvailability => "star_availability",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Factor::ALL.into_iter().find(|f| f.as_str() == s)
+    }
+
+    /// Dollar sensitivity of `net_yes` contracts to one unit of the factor
+    fn scale(self, net_yes: f64, beta: f64) -> f64 {
+        match self {
+            Factor::HomeWin => net_yes * beta / 100.0,
+            Factor::TotalPoints => net_yes * beta,
+            // Availability drops from 1 to 0
+            Factor::StarAvailability => -net_yes * beta,
+        }
+    }
+}
+
+/// Standard deviation of a game's total score by league (points, goals or runs)
+fn scoring_sd(league: &str) -> f64 {
+    match league {
+        "nba" => 18.0,
+        "nfl" => 13.5,
+        "ncaaf" => 16.0,
+        "nhl" => 1.9,
+        "mlb" => 3.2,
+        _ => 1.6, // soccer
+    }
+}
+
+/// US leagues list the away team first in Kalshi event tickers; soccer lists the home team first
+fn home_listed_last(league: &str) -> bool {
+    matches!(league, "nba" | "nfl" | "ncaaf" | "nhl" | "mlb")
+}
+
+/// Team codes of an event ticker: "KXEPLGAME-25DEC27CFCAVL" -> "CFCAVL",
+/// "KXNCAAFGAME-25DEC27M-OHFRES" -> "OHFRES" (the two formats discovery parses)
+fn event_teams(event_ticker: &str) -> Option<&str> {
+    let parts: Vec<&str> = event_ticker.split('-').collect();
+    match parts.as_slice() {
+        [_, _, teams, ..] if teams.len() >= 4 => Some(*teams),
+        [_, date_teams, ..] => date_teams.get(7..).filter(|t| !t.is_empty()),
+        _ => None,
+    }
+}
+
+/// +1 when the market's team is the home team, -1 for the away team, None when unknown (draws)
+fn home_sign(pair: &MarketPair) -> Option<f64> {
+    let suffix = pair.team_suffix.as_deref()?.trim_end_matches(|c: char| !c.is_ascii_alphabetic());
+    let teams = event_teams(&pair.kalshi_event_ticker)?;
+    if suffix.is_empty() {
+        return None;
+    }
+    let (home_side, away_side) = if home_listed_last(&pair.league) {
+        (teams.ends_with(suffix), teams.starts_with(suffix))
+    } else {
+        (teams.starts_with(suffix), teams.ends_with(suffix))
+    };
+    match (home_side, away_side) {
+        (true, false) => Some(1.0),
+        (false, true) => Some(-1.0),
+        _ => None,
+    }
+}
+
+/// Structural YES-price betas per factor: d P(YES) / d factor, from a logistic margin model
+/// scaled by league scoring variance. Estimated betas (e.g. pattern #73's prop -> team total
+/// regressions) replace them per market through [`SensitivityModel::with_beta`].
+#[derive(Debug, Clone, Default)]
+pub struct SensitivityModel {
+    overrides: HashMap<(String, Factor), f64>,
+}
+
+impl SensitivityModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use an estimated beta for `pair_id` instead of the structural one
+    pub fn with_beta(mut self, pair_id: &str, factor: Factor, beta: f64) -> Self {
+        self.overrides.insert((pair_id.to_string(), factor), beta);
+        self
+    }
+
+    /// YES-price betas of one market at YES probability `mark`
+    pub fn betas(&self, pair: &MarketPair, mark: f64) -> BTreeMap<Factor, f64> {
+        let p = mark.clamp(0.01, 0.99);
+        // Logistic scale matching the league's scoring deviation
+        let scale = scoring_sd(&pair.league) * 3f64.sqrt() / std::f64::consts::PI;
+        let over = |fraction: f64| fraction * p * (1.0 - p) / (scale * fraction.sqrt());
+
+        let mut betas = BTreeMap::new();
+        match pair.market_type {
+            MarketType::Moneyline => {
+                if let Some(sign) = home_sign(pair) {
+                    betas.insert(Factor::HomeWin, sign);
+                }
+            }
+            // Covering moves with winning, most steeply near an even line
+            MarketType::Spread => {
+                if let Some(sign) = home_sign(pair) {
+                    betas.insert(Factor::HomeWin, sign * 4.0 * p * (1.0 - p));
+                }
+            }
+            MarketType::Total | MarketType::AltLine => {
+                betas.insert(Factor::TotalPoints, over(1.0));
+            }
+            MarketType::HalfTotal => {
+                betas.insert(Factor::TotalPoints, over(0.5));
+            }
+            MarketType::QuarterTotal => {
+                betas.insert(Factor::TotalPoints, over(0.25));
+            }
+            MarketType::TeamTotal => {
+                betas.insert(Factor::TotalPoints, over(0.5));
+            }
+            MarketType::Btts => {
+                betas.insert(Factor::TotalPoints, 0.5 * over(1.0));
+            }
+            // A prop on a player who sits settles NO
+            MarketType::PlayerProp => {
+                betas.insert(Factor::StarAvailability, p);
+            }
+            MarketType::Combo => {}
+        }
+        for factor in Factor::ALL {
+            if let Some(&beta) = self.overrides.get(&(pair.pair_id.to_string(), factor)) {
+                betas.insert(factor, beta);
+            }
+        }
+        betas
+    }
+
+    /// Sensitivities of the open `positions`; YES marks by pair id, falling back to entry prices
+    pub fn report(
+        &self,
+        positions: &[&ArbPosition],
+        pairs: &HashMap<String, MarketPair>,
+        marks: &HashMap<String, f64>,
+    ) -> SensitivityReport {
+        let mut report = SensitivityReport::default();
+        let mut events: BTreeMap<(String, Factor), FactorExposure> = BTreeMap::new();
+
+        for position in positions {
+            let net_yes = position.kalshi_yes.contracts + position.poly_yes.contracts
+                - position.kalshi_no.contracts - position.poly_no.contracts;
+            let Some(pair) = pairs.get(&position.market_id) else {
+                report.unmapped.push(position.market_id.clone());
+                continue;
+            };
+            let mark = marks.get(&position.market_id).copied().unwrap_or_else(|| entry_mark(position));
+            let exposures: BTreeMap<Factor, f64> = self.betas(pair, mark).into_iter()
+                .map(|(factor, beta)| (factor, factor.scale(net_yes, beta)))
+                .filter(|(_, dollars)| dollars.abs() > 1e-9)
+                .collect();
+
+            for (&factor, &dollars) in &exposures {
+                let event = events.entry((pair.kalshi_event_ticker.to_string(), factor))
+                    .or_insert_with(|| FactorExposure {
+                        event: pair.kalshi_event_ticker.to_string(),
+                        league: pair.league.to_string(),
+                        factor,
+                        sensitivity: 0.0,
+                        positions: 0,
+                        share: 0.0,
+                        concentrated: false,
+                    });
+                event.sensitivity += dollars;
+                event.positions += 1;
+                *report.net.entry(factor).or_default() += dollars;
+            }
+            report.positions.push(PositionSensitivity {
+                market_id: position.market_id.clone(),
+                event: pair.kalshi_event_ticker.to_string(),
+                market_type: pair.market_type,
+                net_yes,
+                mark,
+                exposures,
+            });
+        }
+
+        for exposure in events.values() {
+            *report.gross.entry(exposure.factor).or_default() += exposure.sensitivity.abs();
+        }
+        report.by_event = events.into_values()
+            .map(|mut exposure| {
+                let gross = report.gross[&exposure.factor];
+                exposure.share = if gross > 0.0 { exposure.sensitivity.abs() / gross } else { 0.0 };
+                exposure.concentrated = exposure.share >= CONCENTRATION_SHARE;
+                exposure
+            })
+            .collect();
+        report.by_event.sort_by(|a, b| b.sensitivity.abs().total_cmp(&a.sensitivity.abs()));
+        report
+    }
+}
+
+/// YES price implied by what was paid for the position's legs
+fn entry_mark(position: &ArbPosition) -> f64 {
+    let yes = [&position.kalshi_yes, &position.poly_yes];
+    let no = [&position.kalshi_no, &position.poly_no];
+    let (yes_cost, yes_contracts) = yes.iter().fold((0.0, 0.0), |(c, n), leg| (c + leg.cost_basis, n + leg.contracts));
+    let (no_cost, no_contracts) = no.iter().fold((0.0, 0.0), |(c, n), leg| (c + leg.cost_basis, n + leg.contracts));
+    if yes_contracts > 0.0 {
+        yes_cost / yes_contracts
+    } else if no_contracts > 0.0 {
+        1.0 - no_cost / no_contracts
+    } else {
+        0.5
+    }
+}
+
+/// One game's exposure to one factor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactorExposure {
+    pub event: String,
+    pub league: String,
+    pub factor: Factor,
+    /// Dollars per factor unit (see [`Factor`])
+    pub sensitivity: f64,
+    pub positions: usize,
+    /// Fraction of the factor's gross exposure across games
+    pub share: f64,
+    pub concentrated: bool,
+}
+
+/// One position's net YES contracts and factor exposures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSensitivity {
+    pub market_id: String,
+    pub event: String,
+    pub market_type: MarketType,
+    pub net_yes: f64,
+    pub mark: f64,
+    pub exposures: BTreeMap<Factor, f64>,
+}
+
+/// Portfolio sensitivities, largest per-game exposures first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SensitivityReport {
+    pub by_event: Vec<FactorExposure>,
+    /// Sum across games, e.g. a portfolio-wide lean toward home teams or overs
+    pub net: BTreeMap<Factor, f64>,
+    pub gross: BTreeMap<Factor, f64>,
+    pub positions: Vec<PositionSensitivity>,
+    /// Open positions whose market pair is unknown (no factor model applies)
+    pub unmapped: Vec<String>,
+}
+
+/// Sensitivities of the saved positions, for the admin route and the dashboard
+pub struct SensitivityService {
+    model: SensitivityModel,
+    positions_file: PathBuf,
+    pairs: RwLock<HashMap<String, MarketPair>>,
+    marks: RwLock<HashMap<String, f64>>,
+}
+
+pub type SharedSensitivityService = Arc<SensitivityService>;
+
+impl SensitivityService {
+    pub fn new(positions_file: impl Into<PathBuf>) -> Self {
+        Self {
+            model: SensitivityModel::new(),
+            positions_file: positions_file.into(),
+            pairs: RwLock::new(HashMap::new()),
+            marks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_model(mut self, model: SensitivityModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Market metadata positions are matched against (by pair id)
+    pub fn set_pairs(&self, pairs: impl IntoIterator<Item = MarketPair>) {
+        *self.pairs.write().unwrap() = pairs.into_iter().map(|p| (p.pair_id.to_string(), p)).collect();
+    }
+
+    /// Current YES probability of a pair; unmarked positions use their entry prices
+    pub fn set_mark(&self, pair_id: &str, yes_price: f64) {
+        self.marks.write().unwrap().insert(pair_id.to_string(), yes_price);
+    }
+
+    pub fn report(&self) -> SensitivityReport {
+        let tracker = PositionTracker::load_from(&self.positions_file);
+        let positions = tracker.open_positions();
+        self.model.report(&positions, &self.pairs.read().unwrap(), &self.marks.read().unwrap())
+    }
+
+    /// Admin route: GET /sensitivities[?factor=home_win|total_points|star_availability]
+    pub fn handle_admin(&self, method: &str, path: &str) -> (u16, String) {
+        if method != "GET" {
+            return (405, r#"{"error":"method not allowed"}"#.to_string());
+        }
+        let query = path.split_once('?').map(|(_, q)| q).unwrap_or("");
+        let mut factor = None;
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            match pair.split_once('=') {
+                Some(("factor", value)) => match Factor::parse(value) {
+                    Some(f) => factor = Some(f),
+                    None => return (400, serde_json::json!({ "error": format!("unknown factor '{}'", value) }).to_string()),
+                },
+                _ => return (400, serde_json::json!({ "error": format!("unknown parameter '{}'", pair) }).to_string()),
+            }
+        }
+        let mut report = self.report();
+        if let Some(factor) = factor {
+            report.by_event.retain(|e| e.factor == factor);
+            report.net.retain(|f, _| *f == factor);
+            report.gross.retain(|f, _| *f == factor);
+            report.positions.retain(|p| p.exposures.contains_key(&factor));
+        }
+        match serde_json::to_string(&report) {
+            Ok(body) => (200, body),
+            Err(e) => (500, serde_json::json!({ "error": e.to_string() }).to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(pair_id: &str, league: &str, market_type: MarketType, event: &str, team: Option<&str>) -> MarketPair {
+        MarketPair {
+            pair_id: pair_id.into(),
+            league: league.into(),
+            market_type,
+            description: pair_id.into(),
+            kalshi_event_ticker: event.into(),
+            kalshi_market_ticker: format!("{}-{}", event, team.unwrap_or("X")).into(),
+            poly_slug: "slug".into(),
+            poly_yes_token: "y".into(),
+            poly_no_token: "n".into(),
+            line_value: None,
+            team_suffix: team.map(Into::into),
+        }
+    }
+
+    fn position(market_id: &str, legs: &[(&str, &str, f64, f64)]) -> ArbPosition {
+        let mut position = ArbPosition::new(market_id, market_id);
+        for &(platform, side, contracts, price) in legs {
+            position.leg_mut(platform, side).unwrap().add(contracts, price);
+        }
+        position
+    }
+
+    #[test]
+    fn test_home_side_and_betas() {
+        let model = SensitivityModel::new();
+        // Soccer lists the home team first, US leagues last
+        let chelsea = pair("a", "epl", MarketType::Moneyline, "KXEPLGAME-25DEC27CFCAVL", Some("CFC"));
+        let villa = pair("b", "epl", MarketType::Moneyline, "KXEPLGAME-25DEC27CFCAVL", Some("AVL"));
+        let celtics = pair("c", "nba", MarketType::Moneyline, "KXNBAGAME-25DEC27LALBOS", Some("BOS"));
+        let draw = pair("d", "epl", MarketType::Moneyline, "KXEPLGAME-25DEC27CFCAVL", Some("TIE"));
+        assert_eq!(model.betas(&chelsea, 0.5)[&Factor::HomeWin], 1.0);
+        assert_eq!(model.betas(&villa, 0.5)[&Factor::HomeWin], -1.0);
+        assert_eq!(model.betas(&celtics, 0.5)[&Factor::HomeWin], 1.0);
+        assert!(model.betas(&draw, 0.3).is_empty());
+
+        // Totals are steepest at an even line and in low-scoring leagues
+        let nba_total = pair("e", "nba", MarketType::Total, "KXNBATOTAL-25DEC27LALBOS", Some("230"));
+        let even = model.betas(&nba_total, 0.5)[&Factor::TotalPoints];
+        assert!(even > model.betas(&nba_total, 0.9)[&Factor::TotalPoints]);
+        assert!((even - 0.25 / (18.0 * 3f64.sqrt() / std::f64::consts::PI)).abs() < 1e-12);
+
+        let model = model.with_beta("e", Factor::TotalPoints, 0.02);
+        assert_eq!(model.betas(&nba_total, 0.5)[&Factor::TotalPoints], 0.02);
+    }
+
+    #[test]
+    fn test_hedged_arbs_carry_no_exposure() {
+        let pairs: HashMap<String, MarketPair> = [
+            pair("ml", "epl", MarketType::Moneyline, "KXEPLGAME-25DEC27CFCAVL", Some("CFC")),
+            pair("ml2", "epl", MarketType::Moneyline, "KXEPLGAME-25DEC27CFCAVL", Some("AVL")),
+            pair("prop", "nba", MarketType::PlayerProp, "KXNBAPTS-25DEC27LALBOS", Some("LEBRON25")),
+        ].into_iter().map(|p| (p.pair_id.to_string(), p)).collect();
+        // A fully matched arb, then unmatched legs on both teams of one game
+        let hedged = position("ml", &[("kalshi", "yes", 10.0, 0.45), ("polymarket", "no", 10.0, 0.50)]);
+        let long_home = position("ml", &[("kalshi", "yes", 30.0, 0.45), ("polymarket", "no", 10.0, 0.50)]);
+        let short_away = position("ml2", &[("kalshi", "no", 20.0, 0.70)]);
+        let prop = position("prop", &[("polymarket", "yes", 50.0, 0.40)]);
+        let unknown = position("elsewhere", &[("kalshi", "yes", 5.0, 0.5)]);
+
+        let report = SensitivityModel::new().report(
+            &[&hedged, &long_home, &short_away, &prop, &unknown],
+            &pairs,
+            &HashMap::new(),
+        );
+        assert!(report.positions[0].exposures.is_empty());
+        // +20 YES home and +20 NO away both pay on a home win: $0.40 per point
+        assert!((report.net[&Factor::HomeWin] - 0.4).abs() < 1e-9);
+        let game = &report.by_event.iter().find(|e| e.factor == Factor::HomeWin).unwrap();
+        assert_eq!((game.positions, game.concentrated), (2, true));
+        // 50 YES at 40¢ lose $20 if the star sits
+        assert!((report.net[&Factor::StarAvailability] + 20.0).abs() < 1e-9);
+        assert_eq!(report.unmapped, vec!["elsewhere".to_string()]);
+    }
+
+    #[test]
+    fn test_admin_route() {
+        let dir = std::env::temp_dir().join(format!("sensitivity_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("positions.json");
+        let mut tracker = PositionTracker::new();
+        *tracker.get_or_create("ml", "m") = position("ml", &[("kalshi", "yes", 10.0, 0.5)]);
+        tracker.save_to(&file).unwrap();
+
+        let service = SensitivityService::new(&file);
+        service.set_pairs([pair("ml", "nba", MarketType::Moneyline, "KXNBAGAME-25DEC27LALBOS", Some("LAL"))]);
+        service.set_mark("ml", 0.6);
+
+        let (status, body) = service.handle_admin("GET", "/sensitivities?factor=home_win");
+        assert_eq!(status, 200);
+        let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+        // Lakers are the away side: 10 YES lose $0.10 per point of home win probability
+        assert!((report["net"]["home_win"].as_f64().unwrap() + 0.1).abs() < 1e-9);
+        assert_eq!(report["positions"][0]["mark"], 0.6);
+
+        assert_eq!(service.handle_admin("GET", "/sensitivities?factor=weather").0, 400);
+        assert_eq!(service.handle_admin("POST", "/sensitivities").0, 405);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
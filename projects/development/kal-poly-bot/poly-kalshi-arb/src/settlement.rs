@@ -0,0 +1,372 @@
+// src/settlement.rs
+// Settlement watcher - detects resolved markets and books settlement P&L
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::circuit_breaker::TradingCircuitBreaker;
+use crate::config::SettlementSection;
+use crate::journal::{Journal, JournalEvent, SharedJournal};
+use crate::kalshi::KalshiApiClient;
+use crate::polymarket::GammaClient;
+use crate::position_tracker::{ArbPosition, SharedPositionTracker};
+use crate::types::{GammaMarket, KalshiMarket, MarketPair};
+
+/// Settlement watcher configuration
+#[derive(Debug, Clone)]
+pub struct SettlementConfig {
+    /// How often open positions are checked for resolution
+    pub poll_interval: Duration,
+    /// Unmatched contracts above this are reported as held into resolution
+    pub max_unmatched_at_resolution: f64,
+}
+
+impl Default for SettlementConfig {
+    fn default() -> Self {
+        Self::from(&SettlementSection::default())
+    }
+}
+
+impl From<&SettlementSection> for SettlementConfig {
+    fn from(s: &SettlementSection) -> Self {
+        Self {
+            poll_interval: Duration::from_secs(s.poll_secs),
+            max_unmatched_at_resolution: s.max_unmatched,
+        }
+    }
+}
+
+/// Resolution state of one venue's market
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VenueResolution {
+    /// Still trading or awaiting determination
+    Open,
+    /// Outcome determined
+    Resolved { yes_won: bool },
+    /// Market cancelled / voided by the venue
+    Voided,
+}
+
+impl VenueResolution {
+    /// Kalshi: result is only final once status reaches determined/settled/finalized
+    pub fn from_kalshi(market: &KalshiMarket) -> Self {
+        let status = market.status.as_deref().unwrap_or("");
+        if !matches!(status, "determined" | "settled" | "finalized") {
+            return Self::Open;
+        }
+        match market.result.as_deref() {
+            Some("yes") => Self::Resolved { yes_won: true },
+            Some("no") => Self::Resolved { yes_won: false },
+            Some("void") | Some("voided") => Self::Voided,
+            _ => Self::Open,
+        }
+    }
+
+    /// Polymarket: closed market whose outcome prices collapsed to 1/0
+    /// `yes_token_first` is false when our YES leg is the second CLOB token
+    pub fn from_gamma(market: &GammaMarket, yes_token_first: bool) -> Self {
+        if market.closed != Some(true) {
+            return Self::Open;
+        }
+        let prices: Vec<f64> = market.outcome_prices
+            .as_deref()
+            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+            .map(|v| v.iter().filter_map(|p| p.parse().ok()).collect())
+            .unwrap_or_default();
+        if prices.len() < 2 {
+            return Self::Open;
+        }
+        let (yes, no) = if yes_token_first { (prices[0], prices[1]) } else { (prices[1], prices[0]) };
+        if yes > 0.99 && no < 0.01 {
+            Self::Resolved { yes_won: true }
+        } else if no > 0.99 && yes < 0.01 {
+            Self::Resolved { yes_won: false }
+        } else if (yes - 0.5).abs() < 0.01 && (no - 0.5).abs() < 0.01 {
+            // 50/50 split is how Polymarket settles cancelled events
+            Self::Voided
+        } else {
+            Self::Open
+        }
+    }
+}
+
+/// What to do with a position given both venues' state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementDecision {
+    /// Nothing to do yet
+    Wait,
+    /// Book resolution with this outcome
+    Settle { yes_won: bool },
+    /// Venues resolved differently - needs a human
+    Disagreement,
+    /// A venue voided the market - needs a human
+    Voided,
+}
+
+/// Decide settlement from venue states, ignoring venues we hold no contracts on
+pub fn decide(kalshi: VenueResolution, poly: VenueResolution, holds_kalshi: bool, holds_poly: bool) -> SettlementDecision {
+    let relevant: Vec<VenueResolution> = [(holds_kalshi, kalshi), (holds_poly, poly)]
+        .into_iter()
+        .filter(|(held, _)| *held)
+        .map(|(_, r)| r)
+        .collect();
+
+    if relevant.is_empty() {
+        return SettlementDecision::Wait;
+    }
+    if relevant.iter().any(|r| *r == VenueResolution::Voided) {
+        return SettlementDecision::Voided;
+    }
+    if relevant.iter().any(|r| *r == VenueResolution::Open) {
+        return SettlementDecision::Wait;
+    }
+
+    let outcomes: Vec<bool> = relevant.iter()
+        .filter_map(|r| match r {
+            VenueResolution::Resolved { yes_won } => Some(*yes_won),
+            _ => None,
+        })
+        .collect();
+    if outcomes.windows(2).any(|w| w[0] != w[1]) {
+        SettlementDecision::Disagreement
+    } else {
+        SettlementDecision::Settle { yes_won: outcomes[0] }
+    }
+}
+
+/// Settlement watcher output
+#[derive(Debug, Clone)]
+pub enum SettlementEvent {
+    /// Position resolved and P&L booked
+    Settled { market_id: String, yes_won: bool, pnl: f64 },
+    /// Directional exposure was still on when the market resolved
+    HeldIntoResolution { market_id: String, unmatched_contracts: f64, pnl: f64 },
+    /// Kalshi and Polymarket resolved the same market differently
+    VenueDisagreement { market_id: String, kalshi: VenueResolution, poly: VenueResolution },
+    /// A venue voided the market; refunds must be reconciled manually
+    Voided { market_id: String },
+}
+
+/// Polls venue APIs for resolution of every open position
+pub struct SettlementWatcher {
+    config: SettlementConfig,
+    kalshi: Arc<KalshiApiClient>,
+    gamma: Arc<GammaClient>,
+    tracker: SharedPositionTracker,
+    circuit_breaker: Option<Arc<TradingCircuitBreaker>>,
+    journal: Option<SharedJournal>,
+    /// Kalshi market ticker -> pair (for Poly token lookup)
+    pairs: HashMap<String, Arc<MarketPair>>,
+    event_tx: mpsc::UnboundedSender<SettlementEvent>,
+}
+
+impl SettlementWatcher {
+    pub fn new(
+        config: SettlementConfig,
+        kalshi: Arc<KalshiApiClient>,
+        gamma: Arc<GammaClient>,
+        tracker: SharedPositionTracker,
+        pairs: impl IntoIterator<Item = Arc<MarketPair>>,
+    ) -> (Self, mpsc::UnboundedReceiver<SettlementEvent>) {
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let pairs = pairs.into_iter()
+            .map(|p| (p.kalshi_market_ticker.to_string(), p))
+            .collect();
+        (
+            Self { config, kalshi, gamma, tracker, circuit_breaker: None, journal: None, pairs, event_tx },
+            event_rx,
+        )
+    }
+
+    /// Release circuit-breaker position capacity and book P&L there on settlement
+    pub fn with_circuit_breaker(mut self, cb: Arc<TradingCircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(cb);
+        self
+    }
+
+    /// Journal resolutions so a crash-replay reproduces them
+    pub fn with_journal(mut self, journal: SharedJournal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Run forever at the configured interval
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            match self.check_once().await {
+                Ok(0) => {}
+                Ok(n) => info!("[SETTLEMENT] Settled {} positions", n),
+                Err(e) => warn!("[SETTLEMENT] Check failed: {}", e),
+            }
+        }
+    }
+
+    /// Check every open position once, returns number settled
+    pub async fn check_once(&self) -> Result<usize> {
+        let open: Vec<ArbPosition> = {
+            let tracker = self.tracker.read().await;
+            tracker.open_positions().into_iter().cloned().collect()
+        };
+
+        let mut settled = 0;
+        for position in open {
+            let holds_kalshi = position.kalshi_yes.contracts + position.kalshi_no.contracts > 0.0;
+            let holds_poly = position.poly_yes.contracts + position.poly_no.contracts > 0.0;
+
+            let kalshi = if holds_kalshi {
+                self.kalshi_resolution(&position.market_id).await
+            } else {
+                VenueResolution::Open
+            };
+            let poly = if holds_poly {
+                self.poly_resolution(&position.market_id).await
+            } else {
+                VenueResolution::Open
+            };
+
+            match decide(kalshi, poly, holds_kalshi, holds_poly) {
+                SettlementDecision::Wait => {}
+                SettlementDecision::Settle { yes_won } => {
+                    self.settle(&position, yes_won).await;
+                    settled += 1;
+                }
+                SettlementDecision::Disagreement => {
+                    warn!("[SETTLEMENT] {} venues disagree: kalshi={:?} poly={:?}", position.market_id, kalshi, poly);
+                    let _ = self.event_tx.send(SettlementEvent::VenueDisagreement {
+                        market_id: position.market_id.clone(),
+                        kalshi,
+                        poly,
+                    });
+                }
+                SettlementDecision::Voided => {
+                    warn!("[SETTLEMENT] {} voided by venue", position.market_id);
+                    let _ = self.event_tx.send(SettlementEvent::Voided { market_id: position.market_id.clone() });
+                }
+            }
+        }
+        Ok(settled)
+    }
+
+    async fn kalshi_resolution(&self, ticker: &str) -> VenueResolution {
+        match self.kalshi.get_market(ticker).await {
+            Ok(market) => VenueResolution::from_kalshi(&market),
+            Err(e) => {
+                debug!("[SETTLEMENT] Kalshi lookup {} failed: {}", ticker, e);
+                VenueResolution::Open
+            }
+        }
+    }
+
+    async fn poly_resolution(&self, market_id: &str) -> VenueResolution {
+        let Some(pair) = self.pairs.get(market_id) else {
+            debug!("[SETTLEMENT] No pair for {}, cannot check Polymarket", market_id);
+            return VenueResolution::Open;
+        };
+        match self.gamma.market_by_token(&pair.poly_yes_token).await {
+            Ok(Some(market)) => {
+                let yes_token_first = market.token_pair()
+                    .map(|(first, _)| *first == *pair.poly_yes_token)
+                    .unwrap_or(true);
+                VenueResolution::from_gamma(&market, yes_token_first)
+            }
+            Ok(None) => VenueResolution::Open,
+            Err(e) => {
+                debug!("[SETTLEMENT] Gamma lookup {} failed: {}", pair.poly_slug, e);
+                VenueResolution::Open
+            }
+        }
+    }
+
+    async fn settle(&self, position: &ArbPosition, yes_won: bool) {
+        let unmatched = position.unmatched_exposure();
+        let pnl = {
+            let mut tracker = self.tracker.write().await;
+            if let Some(journal) = &self.journal {
+                Journal::record(journal, JournalEvent::Resolved {
+                    market_id: position.market_id.clone(),
+                    yes_won,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                });
+            }
+            tracker.resolve_position(&position.market_id, yes_won).unwrap_or(0.0)
+        };
+
+        if let Some(cb) = &self.circuit_breaker {
+            cb.release_market(&position.market_id).await;
+            cb.record_pnl(pnl);
+        }
+
+        let _ = self.event_tx.send(SettlementEvent::Settled {
+            market_id: position.market_id.clone(),
+            yes_won,
+            pnl,
+        });
+
+        if unmatched > self.config.max_unmatched_at_resolution {
+            warn!("[SETTLEMENT] {} held {:.0} unmatched contracts into resolution (P&L ${:.2})",
+                  position.market_id, unmatched, pnl);
+            let _ = self.event_tx.send(SettlementEvent::HeldIntoResolution {
+                market_id: position.market_id.clone(),
+                unmatched_contracts: unmatched,
+                pnl,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YES: VenueResolution = VenueResolution::Resolved { yes_won: true };
+    const NO: VenueResolution = VenueResolution::Resolved { yes_won: false };
+
+    #[test]
+    fn test_decide_waits_for_held_venues() {
+        use VenueResolution::Open;
+        assert_eq!(decide(YES, Open, true, true), SettlementDecision::Wait);
+        // Poly leg not held - Kalshi alone is enough
+        assert_eq!(decide(YES, Open, true, false), SettlementDecision::Settle { yes_won: true });
+        assert_eq!(decide(Open, Open, false, false), SettlementDecision::Wait);
+    }
+
+    #[test]
+    fn test_decide_agreement_and_conflicts() {
+        assert_eq!(decide(NO, NO, true, true), SettlementDecision::Settle { yes_won: false });
+        assert_eq!(decide(YES, NO, true, true), SettlementDecision::Disagreement);
+        assert_eq!(decide(VenueResolution::Voided, YES, true, true), SettlementDecision::Voided);
+    }
+
+    #[test]
+    fn test_resolution_from_gamma() {
+        let market: GammaMarket = serde_json::from_value(serde_json::json!({
+            "closed": true,
+            "outcomePrices": "[\"0\", \"1\"]",
+        })).unwrap();
+        assert_eq!(VenueResolution::from_gamma(&market, true), NO);
+        assert_eq!(VenueResolution::from_gamma(&market, false), YES);
+
+        let open: GammaMarket = serde_json::from_value(serde_json::json!({
+            "closed": false,
+            "outcomePrices": "[\"0.4\", \"0.6\"]",
+        })).unwrap();
+        assert_eq!(VenueResolution::from_gamma(&open, true), VenueResolution::Open);
+    }
+
+    #[test]
+    fn test_resolution_from_kalshi() {
+        let mut market: KalshiMarket = serde_json::from_value(serde_json::json!({
+            "ticker": "T", "title": "t", "status": "closed", "result": "",
+        })).unwrap();
+        assert_eq!(VenueResolution::from_kalshi(&market), VenueResolution::Open);
+        market.status = Some("finalized".into());
+        market.result = Some("yes".into());
+        assert_eq!(VenueResolution::from_kalshi(&market), YES);
+    }
+}
@@ -0,0 +1,285 @@
+// src/shadow_mode.rs
+// Dark launch - signals of `[features.shadow]` components are paper-filled, never sent live; their
+// hypothetical record is scored and a component meeting the promotion criteria goes live through
+// the feature flags
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::clock::{self, SharedClock};
+use crate::error::StateStoreError;
+use crate::feature_flags::{self, SharedFeatureFlags};
+use crate::optimization_studies::OutOfSampleMetrics;
+
+/// Where shadow records are kept between runs, so days in shadow survive restarts
+pub const SHADOW_FILE: &str = "shadow_patterns.json";
+pub const PROMOTION_INTERVAL: Duration = Duration::from_secs(300);
+
+const NS_PER_DAY: f64 = 86_400.0 * 1e9;
+
+/// Hypothetical trading of one shadow component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ShadowRecord {
+    /// Wall time of the first shadow signal (ns)
+    since_ns: u64,
+    signals: u64,
+    fills: u64,
+    /// Equity after each paper fill, starting capital first
+    equity: Vec<f64>,
+}
+
+/// Where a shadow component stands against the promotion criteria
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowStatus {
+    pub id: u16,
+    pub name: String,
+    pub days_in_shadow: f64,
+    pub signals: u64,
+    pub fills: u64,
+    pub metrics: OutOfSampleMetrics,
+    /// Unmet criteria; empty = eligible for promotion
+    pub blockers: Vec<String>,
+}
+
+/// A shadow component taken live, passed to hooks
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowPromotion {
+    pub id: u16,
+    pub days_in_shadow: f64,
+    pub metrics: OutOfSampleMetrics,
+}
+
+impl ShadowPromotion {
+    /// Structured form for the alert router / event bus (and so the dashboard)
+    pub fn to_alert(&self) -> Alert {
+        let name = feature_flags::component(self.id).map_or("unknown", |c| c.name);
+        Alert::new("shadow", AlertSeverity::Warning, "shadow_promoted", format!(
+            "component #{} ({}) promoted to live after {:.1} days in shadow: {} trades, Sharpe {:.2}, max drawdown {:.1}%",
+            self.id, name, self.days_in_shadow, self.metrics.trades, self.metrics.sharpe_ratio, self.metrics.max_drawdown * 100.0,
+        ))
+    }
+}
+
+type PromotionHook = Arc<dyn Fn(&ShadowPromotion) + Send + Sync>;
+
+/// Hypothetical records of the shadow components; `evaluate` promotes the ones that qualify
+pub struct ShadowBook {
+    flags: SharedFeatureFlags,
+    clock: SharedClock,
+    records: Mutex<BTreeMap<u16, ShadowRecord>>,
+    path: Option<PathBuf>,
+    hooks: Vec<PromotionHook>,
+}
+
+pub type SharedShadowBook = Arc<ShadowBook>;
+
+impl ShadowBook {
+    /// In-memory book (records are lost on restart)
+    pub fn new(flags: SharedFeatureFlags) -> Self {
+        Self { flags, clock: clock::system(), records: Mutex::new(BTreeMap::new()), path: None, hooks: Vec::new() }
+    }
+
+    /// Book persisted at `path`, reloading what earlier runs recorded
+    pub fn open(flags: SharedFeatureFlags, path: impl Into<PathBuf>) -> Result<Self, StateStoreError> {
+        let path = path.into();
+        let records: BTreeMap<u16, ShadowRecord> = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        if !records.is_empty() {
+            info!("[SHADOW] Loaded {} shadow records from {}", records.len(), path.display());
+        }
+        Ok(Self { records: Mutex::new(records), path: Some(path), ..Self::new(flags) })
+    }
+
+    /// Measure days in shadow against another clock (tests, replays)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Register a promotion hook
+    pub fn on_promotion(mut self, hook: impl Fn(&ShadowPromotion) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Whether a signal of `pattern_id` must stay off the live path
+    pub fn is_shadowed(&self, pattern_id: Option<u16>) -> bool {
+        pattern_id.is_some_and(|id| self.flags.component_shadowed(id))
+    }
+
+    /// Count a shadow signal; `pnl` is the paper fill's hypothetical P&L (dollars), None when it missed
+    pub fn record(&self, id: u16, pnl: Option<f64>) {
+        let now_ns = self.clock.wall_ns().0;
+        let starting_capital = self.flags.shadow_policy().starting_capital;
+        let mut records = self.records.lock().unwrap();
+        let record = records.entry(id).or_insert_with(|| ShadowRecord {
+            since_ns: now_ns,
+            equity: vec![starting_capital],
+            ..ShadowRecord::default()
+        });
+        record.signals += 1;
+        if let Some(pnl) = pnl {
+            record.fills += 1;
+            let last = record.equity.last().copied().unwrap_or(starting_capital);
+            record.equity.push(last + pnl);
+        }
+    }
+
+    /// Components still in shadow and how far each is from promotion
+    pub fn status(&self) -> Vec<ShadowStatus> {
+        let policy = self.flags.shadow_policy();
+        let now_ns = self.clock.wall_ns().0;
+        let records = self.records.lock().unwrap();
+        self.flags.shadowed_components().into_iter()
+            .map(|id| {
+                let record = records.get(&id).cloned().unwrap_or_default();
+                let days_in_shadow = if record.signals == 0 { 0.0 } else { now_ns.saturating_sub(record.since_ns) as f64 / NS_PER_DAY };
+                let metrics = OutOfSampleMetrics::from_equity_curve(&record.equity);
+                let mut blockers = Vec::new();
+                if days_in_shadow < policy.promote_after_days {
+                    blockers.push(format!("{:.1} of {} days in shadow", days_in_shadow, policy.promote_after_days));
+                }
+                if record.fills < u64::from(policy.min_trades) {
+                    blockers.push(format!("{} of {} trades", record.fills, policy.min_trades));
+                }
+                if metrics.sharpe_ratio < policy.min_sharpe {
+                    blockers.push(format!("Sharpe {:.2} below {:.2}", metrics.sharpe_ratio, policy.min_sharpe));
+                }
+                if metrics.max_drawdown > policy.max_drawdown {
+                    blockers.push(format!("drawdown {:.1}% above {:.1}%", metrics.max_drawdown * 100.0, policy.max_drawdown * 100.0));
+                }
+                ShadowStatus {
+                    id,
+                    name: feature_flags::component(id).map_or("unknown", |c| c.name).to_string(),
+                    days_in_shadow,
+                    signals: record.signals,
+                    fills: record.fills,
+                    metrics,
+                    blockers,
+                }
+            })
+            .collect()
+    }
+
+    /// Promote every shadow component that meets the criteria and persist the records
+    pub fn evaluate(&self) -> Vec<ShadowPromotion> {
+        let promotions: Vec<ShadowPromotion> = self.status().into_iter()
+            .filter(|status| status.blockers.is_empty() && self.flags.promote(status.id))
+            .map(|status| ShadowPromotion { id: status.id, days_in_shadow: status.days_in_shadow, metrics: status.metrics })
+            .collect();
+        for promotion in &promotions {
+            for hook in &self.hooks {
+                hook(promotion);
+            }
+        }
+        if let Err(e) = self.persist() {
+            warn!("[SHADOW] Failed to save shadow records: {}", e);
+        }
+        promotions
+    }
+
+    fn persist(&self) -> Result<(), StateStoreError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let json = serde_json::to_string(&*self.records.lock().unwrap())?;
+        // Write-then-rename so a crash never leaves a torn file
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Check the promotion criteria every `interval`
+pub async fn run_shadow_promotion(book: SharedShadowBook, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        book.evaluate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::config::FeaturesSection;
+    use crate::feature_flags::FeatureFlags;
+    use crate::types::Nanos;
+
+    fn flags() -> SharedFeatureFlags {
+        let mut config = FeaturesSection::default();
+        config.shadow.components = vec![73, 74];
+        config.shadow.promote_after_days = 7.0;
+        config.shadow.min_trades = 20;
+        Arc::new(FeatureFlags::new(&config))
+    }
+
+    /// Steady winners with the odd small loss
+    fn trade(book: &ShadowBook, id: u16, n: usize) {
+        for i in 0..n {
+            book.record(id, Some(if i % 5 == 4 { -1.0 } else { 2.0 }));
+        }
+    }
+
+    #[test]
+    fn test_promotes_after_days_and_record_only() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let flags = flags();
+        let promoted = Arc::new(Mutex::new(Vec::new()));
+        let sink = promoted.clone();
+        let book = ShadowBook::new(flags.clone())
+            .with_clock(clock.clone())
+            .on_promotion(move |p| sink.lock().unwrap().push(p.to_alert()));
+
+        assert!(book.is_shadowed(Some(73)) && !book.is_shadowed(Some(75)) && !book.is_shadowed(None));
+        trade(&book, 73, 30);
+        book.record(73, None); // missed paper fill
+        // #74 loses half its capital
+        book.record(74, Some(-600.0));
+        trade(&book, 74, 30);
+
+        clock.advance(Duration::from_secs(3 * 86_400));
+        assert!(book.evaluate().is_empty());
+        let status = book.status();
+        assert_eq!((status[0].signals, status[0].fills), (31, 30));
+        assert_eq!(status[0].blockers.len(), 1);
+        assert!(status[0].blockers[0].contains("of 7 days"));
+
+        clock.advance(Duration::from_secs(5 * 86_400));
+        let promotions = book.evaluate();
+        assert_eq!(promotions.len(), 1);
+        assert_eq!(promotions[0].id, 73);
+        assert!(flags.component_live(73) && !flags.component_live(74));
+        let status = book.status();
+        assert_eq!(status.len(), 1);
+        assert!(status[0].blockers.iter().any(|b| b.starts_with("drawdown")));
+        assert_eq!(promoted.lock().unwrap()[0].kind, "shadow_promoted");
+    }
+
+    #[test]
+    fn test_records_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("shadow_book_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SHADOW_FILE);
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+
+        let book = ShadowBook::open(flags(), &path).unwrap().with_clock(clock.clone());
+        trade(&book, 73, 25);
+        book.evaluate();
+
+        clock.advance(Duration::from_secs(8 * 86_400));
+        let flags = flags();
+        let reopened = ShadowBook::open(flags.clone(), &path).unwrap().with_clock(clock);
+        assert_eq!(reopened.status()[0].fills, 25);
+        assert_eq!(reopened.evaluate().len(), 1);
+        assert!(flags.component_live(73));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,563 @@
+// src/signal_prioritizer.rs
+// Signal prioritization - rank pending arbs by edge x fill probability / capital and execute the best that fit
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info};
+
+use crate::clock::{self, SharedClock};
+use crate::config::ExecutionSection;
+use crate::error::ExecutionError;
+use crate::execution::{log_execution_result, ExecutionEngine, ExecutionResult};
+use crate::pattern_policy::{arb_venues, SharedPatternPolicy};
+use crate::request_scheduler::VenueScheduler;
+use crate::sizing_curve::SharedSizingCurve;
+use crate::types::{ArbType, FastExecutionRequest, MarketId, Nanos, Platform, Price, Size, SizeCents};
+
+/// How often the passed-over summary is logged
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Prioritizer limits
+#[derive(Debug, Clone)]
+pub struct PrioritizerConfig {
+    /// Arbs executing at once
+    pub max_concurrent: usize,
+    /// Dollars of collateral in-flight arbs may tie up (0 = unlimited)
+    pub capital_budget: f64,
+    /// Age at which a signal's fill probability halves
+    pub signal_half_life: Duration,
+}
+
+impl Default for PrioritizerConfig {
+    fn default() -> Self {
+        Self::from(&ExecutionSection::default())
+    }
+}
+
+impl From<&ExecutionSection> for PrioritizerConfig {
+    fn from(e: &ExecutionSection) -> Self {
+        Self {
+            max_concurrent: e.max_concurrent as usize,
+            capital_budget: e.capital_budget,
+            signal_half_life: Duration::from_millis(e.signal_half_life_ms),
+        }
+    }
+}
+
+/// Why a signal was not executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassReason {
+    /// Every execution slot was taken
+    Capacity,
+    /// A venue's order rate budget could not cover its legs
+    RateBudget,
+    /// Its collateral would exceed the capital budget
+    Capital,
+    /// Its pattern is disabled, barred from a venue, or at its position or capital limit
+    Pattern,
+}
+
+/// A pending signal with its ranking inputs
+#[derive(Debug, Clone, Copy)]
+pub struct RankedSignal {
+    pub req: FastExecutionRequest,
+    pub contracts: i64,
+    /// Expected dollars if both legs fill
+    pub net_edge: f64,
+    pub fill_probability: f64,
+    /// Dollars of collateral the arb ties up
+    pub capital: f64,
+    /// net_edge x fill_probability / capital
+    pub score: f64,
+}
+
+impl RankedSignal {
+    /// Expected dollars given up by not executing
+    pub fn expected_value(&self) -> f64 {
+        self.net_edge * self.fill_probability
+    }
+
+    /// Signals the engine will reject on its own; they take no slot or budget
+    fn is_viable(&self) -> bool {
+        self.contracts >= 1 && self.net_edge > 0.0
+    }
+}
+
+/// Running totals since startup
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrioritizerStats {
+    pub considered: u64,
+    pub executed: u64,
+    /// Replaced by a newer signal for the same market in the same batch
+    pub superseded: u64,
+    pub passed_capacity: u64,
+    pub passed_rate: u64,
+    pub passed_capital: u64,
+    pub passed_pattern: u64,
+    /// Expected dollars (net edge x fill probability) of every passed-over signal
+    pub opportunity_cost: f64,
+    pub in_flight: usize,
+    pub reserved_capital: f64,
+}
+
+/// Fill history for one arb type
+#[derive(Debug, Clone, Copy, Default)]
+struct FillModel {
+    attempted: u64,
+    filled: u64,
+}
+
+impl FillModel {
+    /// Laplace-smoothed fill rate, 0.5 before any history
+    fn probability(&self) -> f64 {
+        (self.filled + 1) as f64 / (self.attempted + 2) as f64
+    }
+}
+
+const ARB_TYPES: usize = 4;
+
+fn arb_index(arb_type: ArbType) -> usize {
+    match arb_type {
+        ArbType::PolyYesKalshiNo => 0,
+        ArbType::KalshiYesPolyNo => 1,
+        ArbType::PolyOnly => 2,
+        ArbType::KalshiOnly => 3,
+    }
+}
+
+/// Orders an arb places on each venue
+fn legs(arb_type: ArbType) -> [(Platform, f64); 2] {
+    match arb_type {
+        ArbType::PolyYesKalshiNo | ArbType::KalshiYesPolyNo => [(Platform::Kalshi, 1.0), (Platform::Polymarket, 1.0)],
+        ArbType::PolyOnly => [(Platform::Polymarket, 2.0), (Platform::Kalshi, 0.0)],
+        ArbType::KalshiOnly => [(Platform::Kalshi, 2.0), (Platform::Polymarket, 0.0)],
+    }
+}
+
+/// Ranks batches of pending signals and tracks what is in flight
+pub struct SignalPrioritizer {
+    config: PrioritizerConfig,
+    clock: SharedClock,
+    venues: HashMap<Platform, Arc<VenueScheduler>>,
+    fills: [FillModel; ARB_TYPES],
+    in_flight: usize,
+    reserved_capital: f64,
+    patterns: Option<SharedPatternPolicy>,
+    /// In-flight arbs and their collateral per arb type, not yet counted by the pattern policy
+    pattern_in_flight: [(u32, f64); ARB_TYPES],
+    sizing: Option<SharedSizingCurve>,
+    stats: PrioritizerStats,
+}
+
+pub type SharedPrioritizer = Arc<Mutex<SignalPrioritizer>>;
+
+impl SignalPrioritizer {
+    pub fn new(config: PrioritizerConfig) -> Self {
+        Self {
+            config,
+            clock: clock::system(),
+            venues: HashMap::new(),
+            fills: [FillModel::default(); ARB_TYPES],
+            in_flight: 0,
+            reserved_capital: 0.0,
+            patterns: None,
+            pattern_in_flight: [(0, 0.0); ARB_TYPES],
+            sizing: None,
+            stats: PrioritizerStats::default(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Count order legs against this venue's rate budget
+    pub fn with_venue(mut self, platform: Platform, scheduler: Arc<VenueScheduler>) -> Self {
+        self.venues.insert(platform, scheduler);
+        self
+    }
+
+    /// Pass over signals `[patterns.enabled]` would reject, counting in-flight arbs as open
+    pub fn with_pattern_policy(mut self, patterns: SharedPatternPolicy) -> Self {
+        self.patterns = Some(patterns);
+        self
+    }
+
+    /// Trade a share of each arb's size on the `[sizing]` curve: strength from its profit per
+    /// contract, confidence from its fill probability
+    pub fn with_sizing_curve(mut self, sizing: SharedSizingCurve) -> Self {
+        self.sizing = Some(sizing);
+        self
+    }
+
+    pub fn stats(&self) -> PrioritizerStats {
+        PrioritizerStats {
+            in_flight: self.in_flight,
+            reserved_capital: self.reserved_capital,
+            ..self.stats.clone()
+        }
+    }
+
+    /// Score one signal as of `now`
+    pub fn rank(&self, req: &FastExecutionRequest, now: Nanos) -> RankedSignal {
+        let mut req = *req;
+        let age = now.saturating_sub(req.detected_ns).as_duration();
+        let decay = 0.5f64.powf(age.as_secs_f64() / self.config.signal_half_life.as_secs_f64());
+        let fill_probability = self.fills[arb_index(req.arb_type)].probability() * decay;
+
+        let mut contracts = req.yes_size.min(req.no_size).contracts();
+        if let Some(sizing) = &self.sizing {
+            let multiplier = sizing.multiplier(sizing.arb_strength(req.profit_cents() as f64), fill_probability);
+            let sized = (contracts as f64 * multiplier).floor() as i64;
+            if sized < contracts {
+                // The engine trades what the request offers
+                let cap = Size((sized.max(0) * 100) as SizeCents);
+                req.yes_size = req.yes_size.min(cap);
+                req.no_size = req.no_size.min(cap);
+                contracts = sized.max(0);
+            }
+        }
+        let cost_cents = req.yes_price.cents() as f64 + req.no_price.cents() as f64 + req.estimated_fee_cents() as f64;
+        let net_edge = req.profit_cents() as f64 * contracts as f64 / Price::ONE_DOLLAR.cents() as f64;
+        let capital = cost_cents * contracts as f64 / Price::ONE_DOLLAR.cents() as f64;
+
+        let score = if contracts >= 1 && capital > 0.0 {
+            net_edge * fill_probability / capital
+        } else {
+            f64::NEG_INFINITY
+        };
+        RankedSignal { req, contracts, net_edge, fill_probability, capital, score }
+    }
+
+    /// Pick the signals to execute from a pending batch, best score first, and reserve their slots
+    pub fn select(&mut self, pending: Vec<FastExecutionRequest>) -> Vec<RankedSignal> {
+        let batch = pending.len();
+        self.stats.considered += batch as u64;
+
+        // Only the newest quote per market is worth executing
+        let mut latest: HashMap<MarketId, FastExecutionRequest> = HashMap::new();
+        for req in pending {
+            match latest.get(&req.market_id) {
+                Some(held) if held.detected_ns > req.detected_ns => {}
+                _ => {
+                    latest.insert(req.market_id, req);
+                }
+            }
+        }
+
+        self.stats.superseded += (batch - latest.len()) as u64;
+
+        let now = self.clock.mono_ns();
+        let mut ranked: Vec<RankedSignal> = latest.values().map(|req| self.rank(req, now)).collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        let horizon = self.config.signal_half_life;
+        let mut rate_budget: HashMap<Platform, f64> = self.venues.iter()
+            .map(|(platform, scheduler)| (*platform, scheduler.capacity_within(horizon)))
+            .collect();
+
+        let mut chosen = Vec::new();
+        for signal in ranked {
+            if !signal.is_viable() {
+                // Engine rejects and audits it without placing orders
+                chosen.push(signal);
+                continue;
+            }
+            let legs = legs(signal.req.arb_type);
+            let reason = if !self.pattern_admits(&signal) {
+                Some(PassReason::Pattern)
+            } else if self.in_flight >= self.config.max_concurrent {
+                Some(PassReason::Capacity)
+            } else if legs.iter().any(|(p, n)| rate_budget.get(p).is_some_and(|left| left < n)) {
+                Some(PassReason::RateBudget)
+            } else if self.config.capital_budget > 0.0
+                && self.reserved_capital + signal.capital > self.config.capital_budget
+            {
+                Some(PassReason::Capital)
+            } else {
+                None
+            };
+
+            match reason {
+                Some(reason) => self.pass_over(&signal, reason),
+                None => {
+                    for (platform, n) in legs {
+                        if let Some(left) = rate_budget.get_mut(&platform) {
+                            *left -= n;
+                        }
+                    }
+                    self.in_flight += 1;
+                    self.reserved_capital += signal.capital;
+                    let pattern = &mut self.pattern_in_flight[arb_index(signal.req.arb_type)];
+                    pattern.0 += 1;
+                    pattern.1 += signal.capital;
+                    self.stats.executed += 1;
+                    chosen.push(signal);
+                }
+            }
+        }
+        chosen
+    }
+
+    fn pattern_admits(&self, signal: &RankedSignal) -> bool {
+        let Some(patterns) = &self.patterns else { return true };
+        let pattern = format!("{:?}", signal.req.arb_type);
+        let (in_flight, reserved) = self.pattern_in_flight[arb_index(signal.req.arb_type)];
+        match patterns.admit(&pattern, None, arb_venues(signal.req.arb_type)) {
+            Ok(rule) => {
+                (rule.max_concurrent == 0 || patterns.exposure(&pattern).positions + in_flight < rule.max_concurrent)
+                    && patterns.available(&pattern).is_none_or(|left| left > reserved)
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn pass_over(&mut self, signal: &RankedSignal, reason: PassReason) {
+        match reason {
+            PassReason::Capacity => self.stats.passed_capacity += 1,
+            PassReason::RateBudget => self.stats.passed_rate += 1,
+            PassReason::Capital => self.stats.passed_capital += 1,
+            PassReason::Pattern => self.stats.passed_pattern += 1,
+        }
+        self.stats.opportunity_cost += signal.expected_value();
+        debug!(
+            "[PRIO] Passed over market_id={} ({:?}): edge=${:.2} p_fill={:.2} capital=${:.2}",
+            signal.req.market_id, reason, signal.net_edge, signal.fill_probability, signal.capital
+        );
+    }
+
+    /// Release a selected signal's slot and learn from its outcome (`None` = not a fill attempt)
+    pub fn complete(&mut self, signal: &RankedSignal, filled: Option<bool>) {
+        if !signal.is_viable() {
+            return;
+        }
+        self.in_flight = self.in_flight.saturating_sub(1);
+        self.reserved_capital = (self.reserved_capital - signal.capital).max(0.0);
+        let pattern = &mut self.pattern_in_flight[arb_index(signal.req.arb_type)];
+        pattern.0 = pattern.0.saturating_sub(1);
+        pattern.1 = (pattern.1 - signal.capital).max(0.0);
+        if let Some(filled) = filled {
+            let model = &mut self.fills[arb_index(signal.req.arb_type)];
+            model.attempted += 1;
+            model.filled += filled as u64;
+        }
+    }
+}
+
+/// Whether a processed request counts as a filled or missed attempt
+fn fill_outcome(outcome: &Result<ExecutionResult, ExecutionError>) -> Option<bool> {
+    match outcome {
+        Ok(result) if result.success => Some(true),
+        Ok(ExecutionResult { error: Some(ExecutionError::Unfilled { .. }), .. }) => Some(false),
+        _ => None,
+    }
+}
+
+/// Execution loop that drains every pending signal per wakeup and runs only the best-ranked that fit
+pub async fn run_prioritized_execution_loop(
+    mut rx: mpsc::Receiver<FastExecutionRequest>,
+    engine: Arc<ExecutionEngine>,
+    prioritizer: SharedPrioritizer,
+) {
+    info!("[PRIO] Prioritized execution started (max_concurrent={})",
+          prioritizer.lock().unwrap().config.max_concurrent);
+
+    let mut report = tokio::time::interval(REPORT_INTERVAL);
+    report.tick().await;
+    loop {
+        let first = tokio::select! {
+            req = rx.recv() => match req {
+                Some(req) => req,
+                None => break,
+            },
+            _ = report.tick() => {
+                let stats = prioritizer.lock().unwrap().stats();
+                info!(
+                    "[PRIO] considered={} executed={} superseded={} passed(capacity={} rate={} capital={} pattern={}) opportunity_cost=${:.2}",
+                    stats.considered, stats.executed, stats.superseded, stats.passed_capacity,
+                    stats.passed_rate, stats.passed_capital, stats.passed_pattern, stats.opportunity_cost
+                );
+                continue;
+            }
+        };
+
+        let mut pending = vec![first];
+        while let Ok(req) = rx.try_recv() {
+            pending.push(req);
+        }
+
+        let chosen = prioritizer.lock().unwrap().select(pending);
+        for signal in chosen {
+            let engine = engine.clone();
+            let prioritizer = prioritizer.clone();
+            tokio::spawn(async move {
+                let outcome = engine.process(signal.req).await;
+                log_execution_result(&outcome);
+                prioritizer.lock().unwrap().complete(&signal, fill_outcome(&outcome));
+            });
+        }
+    }
+
+    info!("[PRIO] Prioritized execution stopped");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use crate::request_scheduler::VenueLimits;
+
+    const START: u64 = 1_700_000_000_000_000_000;
+
+    fn req(market: u16, yes: u16, no: u16, contracts: u16, arb_type: ArbType, detected_ns: Nanos) -> FastExecutionRequest {
+        FastExecutionRequest {
+            market_id: MarketId(market),
+            yes_price: Price(yes),
+            no_price: Price(no),
+            yes_size: Size(contracts * 100),
+            no_size: Size(contracts * 100),
+            arb_type,
+            detected_ns,
+        }
+    }
+
+    fn prioritizer(config: PrioritizerConfig) -> (SignalPrioritizer, Arc<MockClock>) {
+        let clock = MockClock::shared(Nanos(START));
+        (SignalPrioritizer::new(config).with_clock(clock.clone()), clock)
+    }
+
+    fn config(max_concurrent: usize, capital_budget: f64) -> PrioritizerConfig {
+        PrioritizerConfig { max_concurrent, capital_budget, signal_half_life: Duration::from_millis(250) }
+    }
+
+    #[test]
+    fn test_best_edge_per_dollar_wins_capacity() {
+        let (mut p, clock) = prioritizer(config(1, 0.0));
+        let now = clock.mono_ns();
+        // 4¢ on 96¢ of capital beats 2¢ on 98¢
+        let chosen = p.select(vec![
+            req(1, 48, 50, 10, ArbType::PolyOnly, now),
+            req(2, 46, 50, 10, ArbType::PolyOnly, now),
+        ]);
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].req.market_id, MarketId(2));
+
+        let stats = p.stats();
+        assert_eq!((stats.executed, stats.passed_capacity, stats.in_flight), (1, 1, 1));
+        // 10 contracts x 2¢ x 0.5 prior fill probability
+        assert!((stats.opportunity_cost - 0.10).abs() < 1e-9);
+
+        p.complete(&chosen[0], Some(true));
+        assert_eq!(p.stats().in_flight, 0);
+        assert_eq!(p.stats().reserved_capital, 0.0);
+    }
+
+    #[test]
+    fn test_capital_budget_and_superseded() {
+        let (mut p, clock) = prioritizer(config(4, 15.0));
+        let older = clock.mono_ns();
+        clock.advance(Duration::from_millis(1));
+        let now = clock.mono_ns();
+        let chosen = p.select(vec![
+            req(1, 45, 50, 5, ArbType::PolyOnly, older),
+            req(1, 45, 50, 10, ArbType::PolyOnly, now),
+            req(2, 46, 50, 10, ArbType::PolyOnly, now),
+        ]);
+        // Newest market 1 quote ($9.50) fits; market 2 ($9.60) would exceed $15
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].contracts, 10);
+        let stats = p.stats();
+        assert_eq!((stats.considered, stats.superseded, stats.passed_capital), (3, 1, 1));
+        assert!((stats.reserved_capital - 9.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fill_history_and_age_shape_probability() {
+        let (mut p, clock) = prioritizer(config(4, 0.0));
+        let fresh = req(1, 45, 50, 10, ArbType::KalshiYesPolyNo, clock.mono_ns());
+        let ranked = p.rank(&fresh, clock.mono_ns());
+        assert!((ranked.fill_probability - 0.5).abs() < 1e-9);
+
+        for filled in [true, true, false] {
+            p.complete(&ranked, Some(filled));
+        }
+        // (2 + 1) / (3 + 2)
+        assert!((p.rank(&fresh, clock.mono_ns()).fill_probability - 0.6).abs() < 1e-9);
+
+        clock.advance(Duration::from_millis(250));
+        assert!((p.rank(&fresh, clock.mono_ns()).fill_probability - 0.3).abs() < 1e-9);
+        // Other arb types keep their own history
+        let other = req(1, 45, 50, 10, ArbType::PolyOnly, clock.mono_ns());
+        assert!((p.rank(&other, clock.mono_ns()).fill_probability - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pattern_policy_counts_in_flight_arbs() {
+        use crate::config::{PatternRule, PatternsSection};
+        use crate::pattern_policy::PatternPolicy;
+
+        let mut section = PatternsSection::default();
+        section.enabled.insert("PolyOnly".to_string(), PatternRule { max_concurrent: 1, ..Default::default() });
+        let (p, clock) = prioritizer(config(4, 0.0));
+        let mut p = p.with_pattern_policy(Arc::new(PatternPolicy::new(&section)));
+        let now = clock.mono_ns();
+        // One PolyOnly slot; KalshiOnly isn't enabled
+        let chosen = p.select(vec![
+            req(1, 45, 50, 10, ArbType::PolyOnly, now),
+            req(2, 46, 50, 10, ArbType::PolyOnly, now),
+            req(3, 45, 50, 10, ArbType::KalshiOnly, now),
+        ]);
+        assert_eq!(chosen.len(), 1);
+        assert_eq!(chosen[0].req.market_id, MarketId(1));
+        assert_eq!(p.stats().passed_pattern, 2);
+
+        // Slot frees once the arb completes without a fill
+        p.complete(&chosen[0], Some(false));
+        assert_eq!(p.select(vec![req(2, 46, 50, 10, ArbType::PolyOnly, now)]).len(), 1);
+    }
+
+    #[test]
+    fn test_sizing_curve_trims_request() {
+        use crate::config::SizingSection;
+        use crate::sizing_curve::SizingCurve;
+
+        let section = SizingSection {
+            curve: "piecewise".to_string(),
+            points: vec![[0.0, 0.5], [1.0, 0.5]],
+            ..SizingSection::default()
+        };
+        let (p, clock) = prioritizer(config(4, 0.0));
+        let p = p.with_sizing_curve(Arc::new(SizingCurve::new(&section)));
+        let ranked = p.rank(&req(1, 45, 50, 11, ArbType::PolyOnly, clock.mono_ns()), clock.mono_ns());
+        // Half of 11 contracts, and the request the engine gets offers no more
+        assert_eq!(ranked.contracts, 5);
+        assert_eq!((ranked.req.yes_size, ranked.req.no_size), (Size(500), Size(500)));
+        assert!((ranked.capital - 5.0 * (95 + ranked.req.estimated_fee_cents()) as f64 / 100.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_rate_budget_limits_legs() {
+        let limits = VenueLimits {
+            per_second: 1.0,
+            burst: 3.0,
+            min_per_second: 1.0,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+        let (p, clock) = prioritizer(config(4, 0.0));
+        let mut p = p.with_venue(Platform::Kalshi, Arc::new(VenueScheduler::new(Platform::Kalshi, limits)));
+        let now = clock.mono_ns();
+        // Two Kalshi-only arbs need 4 Kalshi orders; ~3.25 are available within the half-life
+        let chosen = p.select(vec![
+            req(1, 45, 50, 10, ArbType::KalshiOnly, now),
+            req(2, 45, 50, 10, ArbType::KalshiOnly, now),
+            req(3, 45, 50, 10, ArbType::PolyOnly, now),
+        ]);
+        assert_eq!(chosen.len(), 2);
+        assert_eq!(p.stats().passed_rate, 1);
+    }
+}
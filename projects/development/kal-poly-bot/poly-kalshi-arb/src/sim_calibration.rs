@@ -0,0 +1,884 @@
+//! Simulator Calibration from Recorded Feeds
+//!
+//! Fits `SyntheticMarketConfig` parameters (venue lags, regime volatility and
+//! jumps, depth profiles, suspensions) to captured odds by method of moments,
+//! then replays the fitted model and reports goodness of fit per statistic.
+
+use crate::microstructural_simulator::{
+    DepthModel, MarketRegime, SuspensionModel, SyncedTickBundle, SyntheticMarketConfig,
+    SyntheticMarketGenerator, SyntheticVenue,
+};
+use crate::odds_capture::OddsStore;
+use crate::types::{Platform, TimestampNs};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use tracing::info;
+
+/// Calibration knobs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalibrationOptions {
+    /// Market to fit; None picks the one with the most updates
+    pub market_id: Option<String>,
+    /// Outcome side to fit ("yes" / "no")
+    pub side: String,
+    /// Resampling grid (milliseconds); also the fitted tick interval
+    pub grid_ms: f64,
+    /// Longest venue lag searched (milliseconds)
+    pub max_lag_ms: f64,
+    /// Regimes to fit
+    pub regimes: usize,
+    /// Window regime volatility is measured over (seconds)
+    pub regime_window_s: f64,
+    /// Returns beyond this many standard deviations count as jumps...
+    pub jump_sigma: f64,
+    /// ...provided they also span at least this many price ticks
+    pub jump_min_ticks: f64,
+    /// Update gap, as a multiple of the venue's median gap, that counts as a suspension
+    pub suspension_gap_factor: f64,
+    /// Shortest gap that counts as a suspension (seconds)
+    pub min_suspension_s: f64,
+    /// Seed for the goodness-of-fit replay
+    pub seed: u64,
+}
+
+impl Default for CalibrationOptions {
+    fn default() -> Self {
+        Self {
+            market_id: None,
+            side: "yes".to_string(),
+            grid_ms: 100.0,
+            max_lag_ms: 10_000.0,
+            regimes: 3,
+            regime_window_s: 60.0,
+            jump_sigma: 4.0,
+            jump_min_ticks: 3.0,
+            suspension_gap_factor: 20.0,
+            min_suspension_s: 5.0,
+            seed: 42,
+        }
+    }
+}
+
+/// Distribution of how long a venue takes to follow the leader's moves
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct LagDistribution {
+    /// Leader moves averaged over
+    pub samples: usize,
+    pub p10_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+}
+
+/// Per-venue statistics of a tick stream
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueStatistics {
+    pub key: String,
+    pub platform: Platform,
+    pub book: String,
+    pub updates: usize,
+    /// Lag behind the leader maximising cross-covariance of price changes
+    pub lag_ms: f64,
+    /// Spread of follow delays behind the leader
+    pub move_lag: LagDistribution,
+    /// Mean offset from the lagged leader price
+    pub basis: f64,
+    /// Quote noise std, net of rounding and the leader's own noise
+    pub noise: f64,
+    /// Smallest observed price change
+    pub tick_size: f64,
+    pub median_depth: f64,
+    /// Typical depth withdrawal after price moves, as the model applies it
+    #[serde(skip)]
+    depth_pull: f64,
+    pub suspensions: usize,
+    pub suspension_mean_s: f64,
+    pub suspensions_per_hour: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct WindowStats {
+    /// Non-jump variance per second
+    var_rate: f64,
+    /// Drift per second
+    drift: f64,
+    steps: usize,
+    jumps: usize,
+    jump_abs: f64,
+    /// Depth relative to each venue's median, averaged
+    depth_ratio: f64,
+}
+
+/// Statistics measured from a tick stream, recorded or simulated
+#[derive(Debug, Clone)]
+pub struct FeedStatistics {
+    pub market_id: String,
+    pub start_ns: TimestampNs,
+    pub grid_ms: f64,
+    pub steps: usize,
+    /// Leader first
+    pub venues: Vec<VenueStatistics>,
+    pub start_price: f64,
+    pub min_price: f64,
+    pub max_price: f64,
+    /// Leader volatility excluding jumps and quote noise (price units per sqrt second)
+    pub realized_vol: f64,
+    /// Std of leader returns per grid step
+    pub return_std: f64,
+    pub jump_prob: f64,
+    pub jump_size: f64,
+    /// Mean pairwise correlation of venue residuals
+    pub noise_correlation: f64,
+    /// AR(1) coefficient and std of log depth (relative to median), per grid step
+    pub depth_phi: f64,
+    pub depth_log_std: f64,
+    /// Share of (jump, venue) pairs followed by a suspension within a second
+    pub suspension_on_jump: f64,
+    windows: Vec<WindowStats>,
+    returns: Vec<f64>,
+}
+
+impl FeedStatistics {
+    pub fn leader(&self) -> &VenueStatistics {
+        &self.venues[0]
+    }
+
+    fn duration_s(&self) -> f64 {
+        self.steps as f64 * self.grid_ms / 1000.0
+    }
+
+    /// Measure a tick stream (bundles in time order)
+    pub fn measure(bundles: &[SyncedTickBundle], options: &CalibrationOptions) -> Result<Self> {
+        let series = collect_series(bundles, options);
+        if series.is_empty() {
+            bail!("no venue with at least two updates to calibrate from");
+        }
+
+        let start_ns = series.iter().map(|s| s.updates[0].0).min().unwrap_or(0);
+        let end_ns = series.iter().map(|s| s.updates[s.updates.len() - 1].0).max().unwrap_or(0);
+        let step_ns = ((options.grid_ms * 1e6) as TimestampNs).max(1);
+        let grid = Grid { start_ns, step_ns, len: ((end_ns - start_ns) / step_ns) as usize + 1 };
+        if grid.len < 3 {
+            bail!("recording spans fewer than three {}ms grid steps", options.grid_ms);
+        }
+        let dt_s = options.grid_ms / 1000.0;
+        let max_steps = (options.max_lag_ms / options.grid_ms).round().max(0.0) as usize;
+
+        let resampled: Vec<(Vec<f64>, Vec<f64>)> = series.iter().map(|s| grid.resample(&s.updates)).collect();
+
+        // Lags relative to the busiest venue, then re-based on whoever moves first
+        let reference = (0..series.len()).max_by_key(|&i| series[i].updates.len()).unwrap_or(0);
+        let horizon = ((1000.0 / options.grid_ms).round() as usize).max(1);
+        let relative: Vec<i64> = resampled.iter()
+            .map(|(prices, _)| best_lag(&resampled[reference].0, prices, max_steps, horizon))
+            .collect();
+        let leader = (0..series.len()).min_by_key(|&i| (relative[i], std::cmp::Reverse(series[i].updates.len()))).unwrap_or(0);
+        let lag_steps: Vec<usize> = relative.iter().map(|&r| (r - relative[leader]) as usize).collect();
+
+        let leader_prices = &resampled[leader].0;
+        let returns = diffs(leader_prices);
+        let return_std = std_dev(&returns);
+        let leader_tick = min_tick(&series[leader].updates);
+        let jump_threshold = (options.jump_sigma * return_std).max(options.jump_min_ticks * leader_tick);
+        let is_jump = |r: f64| return_std > 0.0 && r.abs() > jump_threshold;
+        // Leader moves the other venues are timed against
+        let move_threshold = if leader_tick > 0.0 { 0.5 * leader_tick } else { 2.0 * return_std };
+
+        let jump_steps: Vec<usize> = (0..returns.len()).filter(|&t| is_jump(returns[t])).collect();
+        let jump_prob = jump_steps.len() as f64 / returns.len() as f64;
+        let jump_size = mean(&jump_steps.iter().map(|&t| returns[t].abs()).collect::<Vec<_>>());
+
+        // Quote noise and rounding show up as negative return autocovariance
+        // (Roll); strip them so volatility describes the true price
+        let microstructure_var = (-autocovariance(&returns)).max(0.0);
+        let leader_noise = (microstructure_var - leader_tick * leader_tick / 12.0).max(0.0).sqrt();
+        let true_var_rate = |sum_sq: f64, steps: usize| (sum_sq / steps as f64 - 2.0 * microstructure_var).max(0.0) / dt_s;
+        let realized_vol = true_var_rate(
+            returns.iter().filter(|r| !is_jump(**r)).map(|r| r * r).sum(),
+            returns.len(),
+        ).sqrt();
+
+        // Per-venue depth relative to its own median, on the grid
+        let median_depths: Vec<f64> = series.iter()
+            .map(|s| median(&s.updates.iter().map(|u| u.2).filter(|d| *d > 0.0).collect::<Vec<_>>()))
+            .collect();
+        let log_depths: Vec<Vec<f64>> = resampled.iter().zip(&median_depths)
+            .map(|((_, sizes), &m)| {
+                sizes.iter().map(|&s| if s > 0.0 && m > 0.0 { (s / m).ln() } else { 0.0 }).collect()
+            })
+            .collect();
+        let (depth_phi, depth_log_std) = {
+            let fits: Vec<(f64, f64)> = log_depths.iter().map(|x| (autocorrelation(x), std_dev(x))).collect();
+            (mean(&fits.iter().map(|f| f.0).collect::<Vec<_>>()), mean(&fits.iter().map(|f| f.1).collect::<Vec<_>>()))
+        };
+
+        // Regime windows over the leader's returns
+        let window_steps = ((options.regime_window_s * 1000.0 / options.grid_ms).round() as usize).max(1);
+        let windows: Vec<WindowStats> = returns.chunks(window_steps).enumerate()
+            .map(|(w, chunk)| {
+                let seconds = chunk.len() as f64 * dt_s;
+                let (mut var, mut sum, mut jumps, mut jump_abs) = (0.0, 0.0, 0, 0.0);
+                for &r in chunk {
+                    sum += r;
+                    if is_jump(r) {
+                        jumps += 1;
+                        jump_abs += r.abs();
+                    } else {
+                        var += r * r;
+                    }
+                }
+                let from = w * window_steps;
+                let depth_ratio = mean(&log_depths.iter()
+                    .map(|x| mean(&x[from..from + chunk.len()].iter().map(|l| l.exp()).collect::<Vec<_>>()))
+                    .collect::<Vec<_>>());
+                WindowStats { var_rate: true_var_rate(var, chunk.len()), drift: sum / seconds, steps: chunk.len(), jumps, jump_abs, depth_ratio }
+            })
+            .collect();
+
+        // Residuals against the lagged leader, aligned on the grid; forward-filled
+        // prices inside a suspension are stale and left out
+        let gaps: Vec<Vec<(TimestampNs, f64)>> = series.iter().map(|s| suspension_gaps(&s.updates, options)).collect();
+        let stale: Vec<Vec<bool>> = gaps.iter().map(|g| grid.stale_mask(g)).collect();
+        let residuals: Vec<Vec<Option<f64>>> = resampled.iter().zip(&lag_steps).zip(&stale)
+            .map(|(((prices, _), &lag), own_stale)| {
+                (0..grid.len)
+                    .map(|t| (t >= lag && !own_stale[t] && !stale[leader][t - lag])
+                        .then(|| prices[t] - leader_prices[t - lag]))
+                    .collect()
+            })
+            .collect();
+
+        let duration_h = grid.len as f64 * dt_s / 3600.0;
+        let jump_times: Vec<TimestampNs> = jump_steps.iter().map(|&t| grid.at(t + 1)).collect();
+        let mut suspension_after_jump = 0usize;
+
+        let mut venues = Vec::with_capacity(series.len());
+        for i in std::iter::once(leader).chain((0..series.len()).filter(|&i| i != leader)) {
+            let s = &series[i];
+            let resid: Vec<f64> = residuals[i].iter().flatten().copied().collect();
+            let tick_size = min_tick(&s.updates);
+            // Residual variance = own noise + own rounding + the leader's microstructure
+            let noise = if i == leader {
+                leader_noise
+            } else {
+                (std_dev(&resid).powi(2) - tick_size * tick_size / 12.0 - microstructure_var).max(0.0).sqrt()
+            };
+            let gaps = &gaps[i];
+            suspension_after_jump += gaps.iter()
+                .filter(|(start, _)| jump_times.iter().any(|&j| *start >= j.saturating_sub(step_ns) && *start <= j + 1_000_000_000))
+                .count();
+
+            venues.push(VenueStatistics {
+                key: s.key.clone(),
+                platform: s.platform,
+                book: s.book.clone(),
+                updates: s.updates.len(),
+                lag_ms: lag_steps[i] as f64 * options.grid_ms,
+                move_lag: move_lag_distribution(&returns, &resampled[i].0, move_threshold, max_steps, options.grid_ms),
+                basis: mean(&resid),
+                noise,
+                tick_size,
+                median_depth: median_depths[i],
+                depth_pull: median(&s.updates.windows(2)
+                    .map(|w| depth_pull(w[1].1 - w[0].1))
+                    .collect::<Vec<_>>()),
+                suspensions: gaps.len(),
+                suspension_mean_s: mean(&gaps.iter().map(|g| g.1).collect::<Vec<_>>()),
+                suspensions_per_hour: gaps.len() as f64 / duration_h.max(1e-9),
+            });
+        }
+
+        let all_prices = series.iter().flat_map(|s| s.updates.iter().map(|u| u.1));
+        let (min_price, max_price) = all_prices.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p), hi.max(p)));
+
+        Ok(Self {
+            market_id: series[leader].market_id.clone(),
+            start_ns,
+            grid_ms: options.grid_ms,
+            steps: grid.len,
+            start_price: leader_prices[0],
+            min_price,
+            max_price,
+            realized_vol,
+            return_std,
+            jump_prob,
+            jump_size,
+            noise_correlation: mean_pairwise_correlation(&residuals),
+            depth_phi,
+            depth_log_std,
+            suspension_on_jump: if jump_steps.is_empty() {
+                0.0
+            } else {
+                (suspension_after_jump as f64 / (jump_steps.len() * series.len()) as f64).min(1.0)
+            },
+            venues,
+            windows,
+            returns,
+        })
+    }
+
+    /// Method-of-moments fit of the synthetic market model
+    pub fn fit(&self, options: &CalibrationOptions) -> SyntheticMarketConfig {
+        let dt_s = self.grid_ms / 1000.0;
+
+        let venues = self.venues.iter()
+            .map(|v| SyntheticVenue {
+                platform: v.platform,
+                book: v.book.clone(),
+                lag_ms: v.lag_ms,
+                basis: v.basis,
+                noise: v.noise,
+                tick_size: v.tick_size,
+                // The model thins depth after moves; undo that so medians line up
+                base_depth: if v.depth_pull > 0.0 { v.median_depth / v.depth_pull } else { v.median_depth },
+                ..SyntheticVenue::default()
+            })
+            .collect();
+
+        let depth_defaults = DepthModel::default();
+        let phi = self.depth_phi;
+        let depth = if phi > 0.0 && phi < 1.0 {
+            DepthModel {
+                reversion: -phi.ln() / dt_s,
+                volatility: self.depth_log_std * (1.0 - phi * phi).sqrt() / dt_s.sqrt(),
+                ..depth_defaults
+            }
+        } else {
+            depth_defaults
+        };
+
+        let suspension_defaults = SuspensionModel::default();
+        let durations: Vec<f64> = self.venues.iter()
+            .filter(|v| v.suspensions > 0)
+            .map(|v| v.suspension_mean_s)
+            .collect();
+        let suspensions = SuspensionModel {
+            rate_per_hour: mean(&self.venues.iter().map(|v| v.suspensions_per_hour).collect::<Vec<_>>()),
+            on_jump_prob: self.suspension_on_jump,
+            mean_duration_s: if durations.is_empty() { suspension_defaults.mean_duration_s } else { mean(&durations) },
+        };
+
+        SyntheticMarketConfig {
+            seed: options.seed,
+            market_id: self.market_id.clone(),
+            start_ns: self.start_ns,
+            tick_interval_ms: self.grid_ms,
+            start_price: self.start_price,
+            min_price: self.min_price,
+            max_price: self.max_price,
+            noise_correlation: self.noise_correlation.clamp(-1.0, 1.0),
+            venues,
+            regimes: self.fit_regimes(options),
+            depth,
+            suspensions,
+            game_duration_s: None,
+        }
+    }
+
+    /// Cluster windows by volatility (1-D k-means), calmest first
+    fn fit_regimes(&self, options: &CalibrationOptions) -> Vec<MarketRegime> {
+        let vols: Vec<f64> = self.windows.iter().map(|w| w.var_rate.sqrt()).collect();
+        let k = options.regimes.min(vols.len()).max(1);
+        let mut sorted = vols.clone();
+        sorted.sort_by(f64::total_cmp);
+        let mut centers: Vec<f64> = (0..k)
+            .map(|i| sorted.get(((i as f64 + 0.5) / k as f64 * sorted.len() as f64) as usize).copied().unwrap_or(0.0))
+            .collect();
+        let nearest = |centers: &[f64], v: f64| {
+            (0..centers.len()).min_by(|&a, &b| (centers[a] - v).abs().total_cmp(&(centers[b] - v).abs())).unwrap_or(0)
+        };
+
+        let mut assignment = vec![0usize; vols.len()];
+        for _ in 0..25 {
+            for (a, &v) in assignment.iter_mut().zip(&vols) {
+                *a = nearest(&centers, v);
+            }
+            for (c, center) in centers.iter_mut().enumerate() {
+                let members: Vec<f64> = vols.iter().zip(&assignment).filter(|(_, a)| **a == c).map(|(v, _)| *v).collect();
+                if !members.is_empty() {
+                    *center = mean(&members);
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = (0..k).collect();
+        order.sort_by(|&a, &b| centers[a].total_cmp(&centers[b]));
+        let window_s = options.regime_window_s;
+        let overall_depth = mean(&self.windows.iter().map(|w| w.depth_ratio).collect::<Vec<_>>());
+        let defaults = MarketRegime::default();
+
+        let mut regimes = Vec::new();
+        for (rank, &c) in order.iter().enumerate() {
+            let members: Vec<&WindowStats> = self.windows.iter().zip(&assignment)
+                .filter(|(_, a)| **a == c)
+                .map(|(w, _)| w)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let runs = assignment.chunk_by(|a, b| a == b).filter(|run| run[0] == c).count().max(1);
+            let steps: usize = members.iter().map(|w| w.steps).sum();
+            let jumps: usize = members.iter().map(|w| w.jumps).sum();
+            let name = if rank == 0 {
+                "quiet".to_string()
+            } else if rank == k - 1 {
+                "steam".to_string()
+            } else if k == 3 {
+                "volatile".to_string()
+            } else {
+                format!("volatile_{}", rank)
+            };
+
+            regimes.push(MarketRegime {
+                name,
+                drift: mean(&members.iter().map(|w| w.drift).collect::<Vec<_>>()),
+                volatility: mean(&members.iter().map(|w| w.var_rate).collect::<Vec<_>>()).sqrt(),
+                jump_prob: jumps as f64 / steps.max(1) as f64,
+                jump_size: if jumps > 0 {
+                    members.iter().map(|w| w.jump_abs).sum::<f64>() / jumps as f64
+                } else {
+                    self.jump_size
+                },
+                depth_multiplier: if overall_depth > 0.0 {
+                    mean(&members.iter().map(|w| w.depth_ratio).collect::<Vec<_>>()) / overall_depth
+                } else {
+                    defaults.depth_multiplier
+                },
+                mean_duration_s: members.len() as f64 * window_s / runs as f64,
+                weight: members.len() as f64 / self.windows.len() as f64,
+            });
+        }
+        regimes
+    }
+}
+
+/// Recorded vs simulated value of one statistic
+#[derive(Debug, Clone, Serialize)]
+pub struct FitStatistic {
+    pub name: String,
+    pub observed: f64,
+    pub simulated: f64,
+}
+
+impl FitStatistic {
+    /// |simulated - observed| / |observed| (absolute error when observed is ~0)
+    pub fn relative_error(&self) -> f64 {
+        let diff = (self.simulated - self.observed).abs();
+        if self.observed.abs() < 1e-9 { diff } else { diff / self.observed.abs() }
+    }
+}
+
+/// Goodness of fit of a calibrated model against its recording
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationReport {
+    pub statistics: Vec<FitStatistic>,
+    /// Recorded per-venue follow-delay distributions
+    pub lag_distributions: Vec<(String, LagDistribution)>,
+    /// Two-sample Kolmogorov-Smirnov distance between recorded and simulated leader returns
+    pub return_ks: f64,
+    /// KS critical value at the 5% level for these sample sizes
+    pub return_ks_critical: f64,
+}
+
+impl CalibrationReport {
+    /// Return distributions indistinguishable at the 5% level
+    pub fn returns_match(&self) -> bool {
+        self.return_ks <= self.return_ks_critical
+    }
+
+    /// Statistic furthest from its recorded value
+    pub fn worst(&self) -> Option<&FitStatistic> {
+        self.statistics.iter().max_by(|a, b| a.relative_error().total_cmp(&b.relative_error()))
+    }
+
+    /// Every statistic within `tolerance` relative error and returns matching
+    pub fn is_acceptable(&self, tolerance: f64) -> bool {
+        self.returns_match() && self.statistics.iter().all(|s| s.relative_error() <= tolerance)
+    }
+}
+
+impl fmt::Display for CalibrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<32} {:>12} {:>12} {:>8}", "statistic", "recorded", "simulated", "err")?;
+        for s in &self.statistics {
+            writeln!(f, "{:<32} {:>12.4} {:>12.4} {:>7.1}%", s.name, s.observed, s.simulated, s.relative_error() * 100.0)?;
+        }
+        for (book, lag) in &self.lag_distributions {
+            writeln!(f, "{:<32} n={} p10={:.0}ms p50={:.0}ms p90={:.0}ms", format!("{}.move_lag", book),
+                     lag.samples, lag.p10_ms, lag.p50_ms, lag.p90_ms)?;
+        }
+        write!(f, "return KS {:.4} (critical {:.4}): {}", self.return_ks, self.return_ks_critical,
+               if self.returns_match() { "match" } else { "MISMATCH" })
+    }
+}
+
+/// Fitted model plus how well it reproduces the recording
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    pub config: SyntheticMarketConfig,
+    pub observed: FeedStatistics,
+    pub report: CalibrationReport,
+}
+
+/// Fit the synthetic market to recorded bundles and replay it for a goodness-of-fit report
+pub fn calibrate(bundles: &[SyncedTickBundle], options: &CalibrationOptions) -> Result<Calibration> {
+    let observed = FeedStatistics::measure(bundles, options)?;
+    let config = observed.fit(options);
+
+    let replay = SyntheticMarketGenerator::new(config.clone()).generate(observed.steps);
+    let simulated = FeedStatistics::measure(&replay, options)?;
+    let report = compare(&observed, &simulated);
+
+    info!("[CALIBRATE] {} venues, {} regimes over {:.0}s; return KS {:.4} (critical {:.4})",
+          config.venues.len(), config.regimes.len(), observed.duration_s(), report.return_ks, report.return_ks_critical);
+    Ok(Calibration { config, observed, report })
+}
+
+/// Calibrate from an odds capture directory over [from_ns, to_ns]
+pub fn calibrate_capture<P: AsRef<Path>>(dir: P, from_ns: TimestampNs, to_ns: TimestampNs, options: &CalibrationOptions) -> Result<Calibration> {
+    let changes = OddsStore::load_range(dir, from_ns, to_ns)?;
+    calibrate(&SyncedTickBundle::from_odds_changes(&changes), options)
+}
+
+fn compare(observed: &FeedStatistics, simulated: &FeedStatistics) -> CalibrationReport {
+    let stat = |name: String, observed: f64, simulated: f64| FitStatistic { name, observed, simulated };
+    let mut statistics = vec![
+        stat("realized_vol".to_string(), observed.realized_vol, simulated.realized_vol),
+        stat("return_std".to_string(), observed.return_std, simulated.return_std),
+        stat("jump_prob".to_string(), observed.jump_prob, simulated.jump_prob),
+    ];
+
+    for venue in &observed.venues {
+        let Some(sim) = simulated.venues.iter().find(|v| v.platform == venue.platform) else { continue };
+        let b = &venue.book;
+        statistics.push(stat(format!("{}.lag_ms", b), venue.lag_ms, sim.lag_ms));
+        statistics.push(stat(format!("{}.noise", b), venue.noise, sim.noise));
+        statistics.push(stat(format!("{}.median_depth", b), venue.median_depth, sim.median_depth));
+        statistics.push(stat(format!("{}.suspensions_per_hour", b), venue.suspensions_per_hour, sim.suspensions_per_hour));
+    }
+
+    let (n, m) = (observed.returns.len() as f64, simulated.returns.len() as f64);
+    CalibrationReport {
+        statistics,
+        lag_distributions: observed.venues.iter().map(|v| (v.book.clone(), v.move_lag)).collect(),
+        return_ks: ks_distance(&observed.returns, &simulated.returns),
+        return_ks_critical: 1.358 * ((n + m) / (n * m).max(1.0)).sqrt(),
+    }
+}
+
+// === Series helpers ===
+
+#[derive(Debug, Clone)]
+struct VenueSeries {
+    key: String,
+    platform: Platform,
+    book: String,
+    market_id: String,
+    /// (timestamp, price, size) in time order
+    updates: Vec<(TimestampNs, f64, f64)>,
+}
+
+/// One series per venue for the chosen market and side
+fn collect_series(bundles: &[SyncedTickBundle], options: &CalibrationOptions) -> Vec<VenueSeries> {
+    let suffix = format!(":{}", options.side);
+    let mut by_key: BTreeMap<String, VenueSeries> = BTreeMap::new();
+    for bundle in bundles {
+        for (key, tick) in bundle.multiple_markets.iter().flatten() {
+            if !key.ends_with(&suffix) {
+                continue;
+            }
+            by_key.entry(key.clone())
+                .or_insert_with(|| VenueSeries {
+                    key: key.clone(),
+                    platform: tick.platform,
+                    book: tick.book.clone(),
+                    market_id: tick.market_id.clone(),
+                    updates: Vec::new(),
+                })
+                .updates.push((bundle.timestamp_ns, tick.price, tick.size));
+        }
+    }
+
+    let market_id = options.market_id.clone().or_else(|| {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for s in by_key.values() {
+            *counts.entry(&s.market_id).or_default() += s.updates.len();
+        }
+        counts.into_iter().max_by_key(|(_, n)| *n).map(|(m, _)| m.to_string())
+    });
+
+    by_key.into_values()
+        .filter(|s| Some(&s.market_id) == market_id.as_ref() && s.updates.len() >= 2)
+        .map(|mut s| {
+            s.updates.sort_by_key(|u| u.0);
+            s
+        })
+        .collect()
+}
+
+struct Grid {
+    start_ns: TimestampNs,
+    step_ns: TimestampNs,
+    len: usize,
+}
+
+impl Grid {
+    fn at(&self, step: usize) -> TimestampNs {
+        self.start_ns + step as TimestampNs * self.step_ns
+    }
+
+    /// Grid points falling inside an update gap
+    fn stale_mask(&self, gaps: &[(TimestampNs, f64)]) -> Vec<bool> {
+        let mut stale = vec![false; self.len];
+        for &(start, seconds) in gaps {
+            let end = start + (seconds * 1e9) as TimestampNs;
+            let first = ((start - self.start_ns) / self.step_ns) as usize + 1;
+            let last = (((end - self.start_ns) / self.step_ns) as usize).min(self.len);
+            for flag in stale.iter_mut().take(last).skip(first) {
+                *flag = true;
+            }
+        }
+        stale
+    }
+
+    /// Forward-filled (price, size) at each grid point
+    fn resample(&self, updates: &[(TimestampNs, f64, f64)]) -> (Vec<f64>, Vec<f64>) {
+        let (mut prices, mut sizes) = (Vec::with_capacity(self.len), Vec::with_capacity(self.len));
+        let (mut price, mut size) = (updates[0].1, updates[0].2);
+        let mut j = 0;
+        for t in 0..self.len {
+            let at = self.at(t);
+            while j < updates.len() && updates[j].0 <= at {
+                price = updates[j].1;
+                size = updates[j].2;
+                j += 1;
+            }
+            prices.push(price);
+            sizes.push(size);
+        }
+        (prices, sizes)
+    }
+}
+
+/// Depth left after a price move under the default `DepthModel`
+fn depth_pull(price_delta: f64) -> f64 {
+    (1.0 - DepthModel::default().move_sensitivity * price_delta.abs()).max(0.05)
+}
+
+fn diffs(x: &[f64]) -> Vec<f64> {
+    x.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Lag (grid steps, may be negative) at which `follower`'s changes best line up with `reference`'s
+///
+/// Changes are taken over `horizon` steps so per-tick quote noise doesn't swamp the signal.
+fn best_lag(reference: &[f64], follower: &[f64], max_steps: usize, horizon: usize) -> i64 {
+    let changes = |x: &[f64]| x.windows(horizon + 1).map(|w| w[horizon] - w[0]).collect::<Vec<f64>>();
+    let (dr, df) = (changes(reference), changes(follower));
+    let moves: Vec<usize> = (0..dr.len()).filter(|&t| dr[t] != 0.0).collect();
+    let max = max_steps as i64;
+    let mut best = (0i64, f64::NEG_INFINITY);
+    for lag in -max..=max {
+        let cov: f64 = moves.iter()
+            .filter_map(|&t| df.get(usize::try_from(t as i64 + lag).ok()?).map(|d| dr[t] * d))
+            .sum();
+        // Ties go to the smallest absolute lag
+        if cov > best.1 || (cov == best.1 && lag.abs() < best.0.abs()) {
+            best = (lag, cov);
+        }
+    }
+    best.0
+}
+
+/// Follow-delay distribution from the follower's average response to leader moves
+///
+/// Averaging the follower's signed price change after each leader move cancels
+/// its quote noise; that curve over its settled level is the share of a move
+/// passed on within k steps.
+fn move_lag_distribution(leader_returns: &[f64], follower_prices: &[f64], threshold: f64, max_steps: usize, grid_ms: f64) -> LagDistribution {
+    let mut response = vec![0.0; max_steps + 1];
+    let mut moves = 0usize;
+    for (t, &r) in leader_returns.iter().enumerate() {
+        if r.abs() <= threshold || t + 1 + max_steps >= follower_prices.len() {
+            continue;
+        }
+        moves += 1;
+        for (k, acc) in response.iter_mut().enumerate() {
+            *acc += (follower_prices[t + 1 + k] - follower_prices[t]) / r;
+        }
+    }
+
+    // Persistent response: where the curve settles over the last quarter of the window
+    let tail = &response[response.len() * 3 / 4..];
+    let settled = median(tail);
+    if moves == 0 || settled <= 0.0 {
+        return LagDistribution::default();
+    }
+    let passed: Vec<f64> = response.iter().map(|v| (v / settled).clamp(0.0, 1.0)).collect();
+
+    // Only count levels held for half a second, so same-instant correlated
+    // noise doesn't read as an instant follow; then make it a proper CDF
+    let hold = ((500.0 / grid_ms).round() as usize).max(1);
+    let mut running = 0.0f64;
+    let cdf: Vec<f64> = (0..passed.len())
+        .map(|k| {
+            let held = passed[k..(k + hold).min(passed.len())].iter().copied().fold(1.0, f64::min);
+            running = running.max(held);
+            running
+        })
+        .collect();
+
+    let quantile = |q: f64| cdf.iter().position(|c| *c >= q).unwrap_or(max_steps) as f64 * grid_ms;
+    LagDistribution {
+        samples: moves,
+        p10_ms: quantile(0.1),
+        p50_ms: quantile(0.5),
+        p90_ms: quantile(0.9),
+    }
+}
+
+/// Update gaps long enough to be suspensions: (gap start, seconds)
+fn suspension_gaps(updates: &[(TimestampNs, f64, f64)], options: &CalibrationOptions) -> Vec<(TimestampNs, f64)> {
+    let gaps: Vec<(TimestampNs, f64)> = updates.windows(2)
+        .map(|w| (w[0].0, (w[1].0 - w[0].0) as f64 / 1e9))
+        .collect();
+    let typical = median(&gaps.iter().map(|g| g.1).collect::<Vec<_>>());
+    let threshold = (typical * options.suspension_gap_factor).max(options.min_suspension_s);
+    gaps.into_iter().filter(|g| g.1 > threshold).collect()
+}
+
+/// Smallest non-zero price change (0 if the price never moved)
+fn min_tick(updates: &[(TimestampNs, f64, f64)]) -> f64 {
+    let tick = updates.windows(2)
+        .map(|w| ((w[1].1 - w[0].1).abs() * 1e6).round() / 1e6)
+        .filter(|d| *d > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    if tick.is_finite() { tick } else { 0.0 }
+}
+
+fn mean(x: &[f64]) -> f64 {
+    if x.is_empty() { 0.0 } else { x.iter().sum::<f64>() / x.len() as f64 }
+}
+
+fn std_dev(x: &[f64]) -> f64 {
+    if x.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(x);
+    (x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (x.len() - 1) as f64).sqrt()
+}
+
+fn median(x: &[f64]) -> f64 {
+    if x.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = x.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    sorted[sorted.len() / 2]
+}
+
+fn autocovariance(x: &[f64]) -> f64 {
+    if x.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(x);
+    x.windows(2).map(|w| (w[0] - m) * (w[1] - m)).sum::<f64>() / (x.len() - 1) as f64
+}
+
+fn autocorrelation(x: &[f64]) -> f64 {
+    let m = mean(x);
+    let var: f64 = x.iter().map(|v| (v - m).powi(2)).sum();
+    if var <= 0.0 {
+        return 0.0;
+    }
+    x.windows(2).map(|w| (w[0] - m) * (w[1] - m)).sum::<f64>() / var
+}
+
+fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (ma, mb) = (mean(a), mean(b));
+    let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+    let va: f64 = a.iter().map(|x| (x - ma).powi(2)).sum();
+    let vb: f64 = b.iter().map(|y| (y - mb).powi(2)).sum();
+    (va > 0.0 && vb > 0.0).then(|| cov / (va * vb).sqrt())
+}
+
+/// Mean correlation of residual changes over every venue pair
+fn mean_pairwise_correlation(residuals: &[Vec<Option<f64>>]) -> f64 {
+    let changes: Vec<Vec<Option<f64>>> = residuals.iter()
+        .map(|r| r.windows(2).map(|w| Some(w[1]? - w[0]?)).collect())
+        .collect();
+    let mut corrs = Vec::new();
+    for i in 0..changes.len() {
+        for j in i + 1..changes.len() {
+            let (a, b): (Vec<f64>, Vec<f64>) = changes[i].iter().zip(&changes[j])
+                .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+                .unzip();
+            corrs.extend(correlation(&a, &b));
+        }
+    }
+    mean(&corrs)
+}
+
+/// Two-sample Kolmogorov-Smirnov statistic
+fn ks_distance(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    a.sort_by(f64::total_cmp);
+    b.sort_by(f64::total_cmp);
+    let (mut i, mut j, mut d) = (0, 0, 0.0f64);
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] <= x {
+            i += 1;
+        }
+        while j < b.len() && b[j] <= x {
+            j += 1;
+        }
+        d = d.max((i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs());
+    }
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_recovers_lags() {
+        let truth = SyntheticMarketConfig {
+            suspensions: SuspensionModel { rate_per_hour: 0.0, on_jump_prob: 0.0, ..SuspensionModel::default() },
+            ..SyntheticMarketConfig::default()
+        };
+        let bundles = SyntheticMarketGenerator::new(truth.clone()).generate(20_000);
+        let calibration = calibrate(&bundles, &CalibrationOptions::default()).unwrap();
+
+        assert_eq!(calibration.observed.leader().platform, Platform::Kalshi);
+        for venue in &truth.venues {
+            let fitted = calibration.config.venues.iter().find(|v| v.platform == venue.platform).unwrap();
+            assert!((fitted.lag_ms - venue.lag_ms).abs() <= 200.0, "{}: {} vs {}", venue.book, fitted.lag_ms, venue.lag_ms);
+            assert_eq!(fitted.tick_size, 1.0);
+        }
+        let draftkings = calibration.observed.venues.iter().find(|v| v.platform == Platform::DraftKings).unwrap();
+        assert!((draftkings.move_lag.p50_ms - 1500.0).abs() <= 200.0);
+        assert!(!calibration.config.regimes.is_empty());
+        assert!(calibration.report.statistics.iter().any(|s| s.name == "draftkings.lag_ms"));
+    }
+
+    #[test]
+    fn test_calibration_needs_data() {
+        assert!(calibrate(&[], &CalibrationOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_ks_distance() {
+        let a: Vec<f64> = (0..100).map(f64::from).collect();
+        assert_eq!(ks_distance(&a, &a), 0.0);
+        let shifted: Vec<f64> = a.iter().map(|x| x + 50.0).collect();
+        assert!((ks_distance(&a, &shifted) - 0.5).abs() < 0.02);
+    }
+}
@@ -0,0 +1,169 @@
+// src/sizing_curve.rs
+// Opportunity sizing curve - maps a signal's strength (0-1) and confidence to the share of its
+// full size taken (`[sizing]`). One curve is shared by the tick-sim backtester, the Bun worker
+// and the live dispatcher so a backtest sizes its trades the way production would
+
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::config::SizingSection;
+use crate::config_reload::ConfigChanged;
+
+/// Curve shape parsed from `[sizing]`
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Linear,
+    /// Knots by increasing strength
+    Piecewise(Vec<(f64, f64)>),
+    Logistic { midpoint: f64, steepness: f64 },
+}
+
+impl Shape {
+    fn parse(section: &SizingSection) -> Self {
+        match section.curve.as_str() {
+            "piecewise" if !section.points.is_empty() => {
+                Shape::Piecewise(section.points.iter().map(|[s, m]| (*s, *m)).collect())
+            }
+            "logistic" => Shape::Logistic { midpoint: section.midpoint, steepness: section.steepness },
+            _ => Shape::Linear,
+        }
+    }
+
+    fn at(&self, strength: f64) -> f64 {
+        match self {
+            Shape::Linear => strength,
+            Shape::Piecewise(points) => {
+                let (first, last) = (points[0], points[points.len() - 1]);
+                if strength <= first.0 {
+                    return first.1;
+                }
+                if strength >= last.0 {
+                    return last.1;
+                }
+                let i = points.partition_point(|(s, _)| *s <= strength);
+                let ((s0, m0), (s1, m1)) = (points[i - 1], points[i]);
+                m0 + (m1 - m0) * (strength - s0) / (s1 - s0)
+            }
+            Shape::Logistic { midpoint, steepness } => 1.0 / (1.0 + (-steepness * (strength - midpoint)).exp()),
+        }
+    }
+}
+
+/// `signal` over the amount reaching full strength, capped at 1
+fn ratio(signal: f64, full: f64) -> f64 {
+    if full > 0.0 { (signal / full).clamp(0.0, 1.0) } else { 1.0 }
+}
+
+/// The `[sizing]` curve, hot-reloadable
+#[derive(Debug)]
+pub struct SizingCurve {
+    shape: RwLock<Shape>,
+    section: RwLock<SizingSection>,
+}
+
+pub type SharedSizingCurve = Arc<SizingCurve>;
+
+impl Default for SizingCurve {
+    fn default() -> Self {
+        Self::new(&SizingSection::default())
+    }
+}
+
+impl SizingCurve {
+    pub fn new(section: &SizingSection) -> Self {
+        Self { shape: RwLock::new(Shape::parse(section)), section: RwLock::new(section.clone()) }
+    }
+
+    /// Apply a hot-reloaded `[sizing]` section (sizes already placed are kept)
+    pub fn apply_config(&self, section: &SizingSection) {
+        *self.shape.write().unwrap() = Shape::parse(section);
+        *self.section.write().unwrap() = section.clone();
+        info!("[SIZING] {} curve, confidence weight {:.2}", section.curve, section.confidence_weight);
+    }
+
+    /// Share (0-1) of full size for a signal of `strength` (0-1) and `confidence` (0-1)
+    pub fn multiplier(&self, strength: f64, confidence: f64) -> f64 {
+        let weight = self.section.read().unwrap().confidence_weight;
+        let curve = self.shape.read().unwrap().at(strength.clamp(0.0, 1.0)).clamp(0.0, 1.0);
+        curve * (1.0 - weight + weight * confidence.clamp(0.0, 1.0))
+    }
+
+    /// Strength of a worker trigger whose edge cleared `threshold`
+    pub fn worker_strength(&self, edge: f64, threshold: f64) -> f64 {
+        ratio(edge, threshold * self.section.read().unwrap().worker_full_strength)
+    }
+
+    /// Strength of a cross-venue arb paying `profit_cents` a contract
+    pub fn arb_strength(&self, profit_cents: f64) -> f64 {
+        ratio(profit_cents, self.section.read().unwrap().arb_full_strength_cents)
+    }
+}
+
+/// Keep the curve in step with `[sizing]` reloads
+pub fn watch_config(curve: SharedSizingCurve, mut config_rx: broadcast::Receiver<ConfigChanged>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match config_rx.recv().await {
+                Ok(change) if change.touches("sizing") => curve.apply_config(&change.config.sizing),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(n)) => warn!("[SIZING] Missed {} config changes", n),
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_curve_shapes() {
+        let curve = SizingCurve::default();
+        // Linear by default, as the backtester always sized
+        assert!(close(curve.multiplier(0.6, 0.1), 0.6));
+
+        curve.apply_config(&SizingSection {
+            curve: "piecewise".to_string(),
+            points: vec![[0.5, 0.2], [0.75, 0.4], [1.0, 1.0]],
+            ..SizingSection::default()
+        });
+        assert!(close(curve.multiplier(0.3, 1.0), 0.2));
+        assert!(close(curve.multiplier(0.625, 1.0), 0.3));
+        assert!(close(curve.multiplier(0.875, 1.0), 0.7));
+        assert!(close(curve.multiplier(1.5, 1.0), 1.0));
+
+        curve.apply_config(&SizingSection { curve: "logistic".to_string(), midpoint: 0.7, ..SizingSection::default() });
+        assert!(close(curve.multiplier(0.7, 1.0), 0.5));
+        assert!(curve.multiplier(0.9, 1.0) > 0.85 && curve.multiplier(0.5, 1.0) < 0.15);
+    }
+
+    #[test]
+    fn test_confidence_weight_and_strengths() {
+        let section = SizingSection {
+            confidence_weight: 0.5,
+            worker_full_strength: 2.0,
+            arb_full_strength_cents: 4.0,
+            ..SizingSection::default()
+        };
+        let curve = SizingCurve::new(&section);
+        // Half the multiplier scales with confidence
+        assert!(close(curve.multiplier(1.0, 0.0), 0.5));
+        assert!(close(curve.multiplier(0.8, 0.5), 0.6));
+        // Worker edges reach full strength at twice the threshold; arbs at 4¢
+        assert!(close(curve.worker_strength(0.3, 0.2), 0.75));
+        assert!(close(curve.worker_strength(0.5, 0.2), 1.0));
+        assert!(close(curve.arb_strength(1.0), 0.25));
+        assert!(close(curve.arb_strength(-2.0), 0.0));
+        // Defaults keep every triggered worker edge and profitable arb at full strength
+        let defaults = SizingCurve::default();
+        assert!(close(defaults.worker_strength(0.2, 0.2), 1.0));
+        assert!(close(defaults.arb_strength(1.0), 1.0));
+    }
+}
@@ -0,0 +1,481 @@
+// src/sla_degradation.rs
+// Graceful degradation when Tier 1 models miss their latency SLAs - sheds Tier 3/4 models, then widens
+// trigger thresholds and shrinks sizes; steps back down once compliance recovers. Tier 1 patterns'
+// detection-to-order latency counts against the same compliance
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity};
+use crate::config::SlaSection;
+use crate::decision_latency::SharedDecisionLatency;
+use crate::feature_flags::{self, SharedFeatureFlags};
+
+/// ML tiers switched off while degraded
+pub const SHED_TIERS: [u8; 2] = [3, 4];
+pub const EVALUATION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How far the policy has backed off, least to most
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationLevel {
+    #[default]
+    Normal,
+    /// Tier 3/4 models off
+    Shed,
+    /// Tier 3/4 off, trigger thresholds widened and sizes shrunk
+    Defensive,
+}
+
+impl DegradationLevel {
+    const ALL: [DegradationLevel; 3] = [DegradationLevel::Normal, DegradationLevel::Shed, DegradationLevel::Defensive];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DegradationLevel::Normal => "normal",
+            DegradationLevel::Shed => "shed",
+            DegradationLevel::Defensive => "defensive",
+        }
+    }
+
+    fn below(self) -> Self {
+        match self {
+            DegradationLevel::Defensive => DegradationLevel::Shed,
+            _ => DegradationLevel::Normal,
+        }
+    }
+}
+
+impl std::fmt::Display for DegradationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Compliance bands and the back-off applied in each
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegradationConfig {
+    /// Latency samples kept per component
+    pub window: usize,
+    /// Tier 1 samples needed before compliance is judged
+    pub min_samples: usize,
+    /// Tier 1 compliance under which Tier 3/4 are shed
+    pub shed_below: f64,
+    /// Tier 1 compliance under which thresholds widen and sizes shrink as well
+    pub defensive_below: f64,
+    /// Compliance that counts as recovered
+    pub recover_at: f64,
+    /// Recovered evaluations in a row before stepping down one level
+    pub recover_checks: u32,
+    /// Trigger threshold multiplier while defensive
+    pub threshold_widen: f64,
+    /// Size multiplier while defensive
+    pub size_shrink: f64,
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self::from(&SlaSection::default())
+    }
+}
+
+impl From<&SlaSection> for DegradationConfig {
+    fn from(s: &SlaSection) -> Self {
+        Self {
+            window: s.window,
+            min_samples: 20,
+            shed_below: s.shed_below,
+            defensive_below: s.defensive_below,
+            recover_at: 0.99,
+            recover_checks: s.recover_checks,
+            threshold_widen: 1.5,
+            size_shrink: 0.5,
+        }
+    }
+}
+
+impl DegradationConfig {
+    /// Level Tier 1 `compliance` calls for, ignoring hysteresis
+    fn target(&self, compliance: f64) -> DegradationLevel {
+        if compliance < self.defensive_below {
+            DegradationLevel::Defensive
+        } else if compliance < self.shed_below {
+            DegradationLevel::Shed
+        } else {
+            DegradationLevel::Normal
+        }
+    }
+}
+
+/// A level change, passed to hooks
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegradationEvent {
+    pub from: DegradationLevel,
+    pub to: DegradationLevel,
+    pub tier1_compliance: f64,
+}
+
+impl DegradationEvent {
+    pub fn escalated(&self) -> bool {
+        self.to > self.from
+    }
+
+    /// Structured form for the alert router / event bus (and so the dashboard)
+    pub fn to_alert(&self) -> Alert {
+        let message = format!(
+            "degradation {} -> {} at {:.1}% Tier 1 SLA compliance",
+            self.from, self.to, self.tier1_compliance * 100.0,
+        );
+        match (self.escalated(), self.to) {
+            (true, DegradationLevel::Defensive) => Alert::new("sla", AlertSeverity::Critical, "sla_degraded", message),
+            (true, _) => Alert::new("sla", AlertSeverity::Warning, "sla_degraded", message),
+            (false, _) => Alert::new("sla", AlertSeverity::Info, "sla_recovered", message),
+        }
+    }
+}
+
+/// Latency window of one registry component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentSla {
+    pub id: u16,
+    pub tier: u8,
+    pub sla_ms: f64,
+    pub samples: usize,
+    pub p50_ms: f64,
+    pub compliance: f64,
+}
+
+/// Current policy state, for the probe and dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradationStatus {
+    pub level: DegradationLevel,
+    /// None until Tier 1 has `min_samples` samples; the lower of model and decision-path compliance
+    pub tier1_compliance: Option<f64>,
+    /// Tier 1 patterns' detection-to-order compliance (None without a tracker or enough samples)
+    #[serde(default)]
+    pub decision_compliance: Option<f64>,
+    pub shed_tiers: Vec<u8>,
+    pub threshold_multiplier: f64,
+    pub size_multiplier: f64,
+    pub healthy_checks: u32,
+    /// RFC 3339 time of the last level change
+    pub since: Option<String>,
+    pub components: BTreeMap<u16, ComponentSla>,
+}
+
+#[derive(Debug, Default)]
+struct PolicyState {
+    level: DegradationLevel,
+    healthy_checks: u32,
+    since: Option<String>,
+}
+
+type EventHook = Arc<dyn Fn(&DegradationEvent) + Send + Sync>;
+
+/// Tracks model latencies against the registry SLAs and applies the degradation level
+pub struct SlaMonitor {
+    config: DegradationConfig,
+    samples: Mutex<HashMap<u16, VecDeque<f64>>>,
+    state: Mutex<PolicyState>,
+    /// Mirror of `state.level` for hot-path reads
+    level: AtomicU8,
+    flags: Option<SharedFeatureFlags>,
+    decision: Option<SharedDecisionLatency>,
+    hooks: Vec<EventHook>,
+}
+
+pub type SharedSlaMonitor = Arc<SlaMonitor>;
+
+impl SlaMonitor {
+    pub fn new(config: DegradationConfig) -> Self {
+        Self {
+            config,
+            samples: Mutex::new(HashMap::new()),
+            state: Mutex::new(PolicyState::default()),
+            level: AtomicU8::new(DegradationLevel::Normal as u8),
+            flags: None,
+            decision: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Shed tiers through the feature flags (component overrides still win)
+    pub fn with_feature_flags(mut self, flags: SharedFeatureFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Hold Tier 1 patterns' detection-to-order latency to its SLA too; each evaluation rolls
+    /// the tracker's recent window
+    pub fn with_decision_latency(mut self, decision: SharedDecisionLatency) -> Self {
+        self.decision = Some(decision);
+        self
+    }
+
+    /// Register a level change hook (called outside the monitor lock)
+    pub fn on_event(mut self, hook: impl Fn(&DegradationEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Record one evaluation of a registry component; anything else is ignored
+    pub fn record(&self, component_id: u16, latency_ms: f64) {
+        if feature_flags::component(component_id).is_none() || !latency_ms.is_finite() {
+            return;
+        }
+        let mut samples = self.samples.lock().unwrap();
+        let window = samples.entry(component_id).or_default();
+        if window.len() == self.config.window {
+            window.pop_front();
+        }
+        window.push_back(latency_ms);
+    }
+
+    pub fn level(&self) -> DegradationLevel {
+        DegradationLevel::ALL[usize::from(self.level.load(Ordering::Relaxed))]
+    }
+
+    /// Whether a component's tier is shed at the current level
+    pub fn sheds(&self, component_id: u16) -> bool {
+        self.level() >= DegradationLevel::Shed
+            && feature_flags::component(component_id).is_some_and(|spec| SHED_TIERS.contains(&spec.tier))
+    }
+
+    /// Factor applied to trigger thresholds (1 unless defensive)
+    pub fn threshold_multiplier(&self) -> f64 {
+        if self.level() == DegradationLevel::Defensive { self.config.threshold_widen } else { 1.0 }
+    }
+
+    /// Factor applied to trigger sizes (1 unless defensive)
+    pub fn size_multiplier(&self) -> f64 {
+        if self.level() == DegradationLevel::Defensive { self.config.size_shrink } else { 1.0 }
+    }
+
+    /// Share of Tier 1 samples within their SLA: the lower of the models' and the decision path's
+    pub fn tier1_compliance(&self) -> Option<f64> {
+        match (self.model_compliance(), self.decision_compliance()) {
+            (Some(models), Some(decision)) => Some(models.min(decision)),
+            (models, decision) => models.or(decision),
+        }
+    }
+
+    /// Tier 1 patterns' detection-to-order samples within the Tier 1 decision SLA
+    pub fn decision_compliance(&self) -> Option<f64> {
+        self.decision.as_ref()?.tier_compliance(1)
+    }
+
+    /// Share of Tier 1 model samples within their component's SLA
+    fn model_compliance(&self) -> Option<f64> {
+        let samples = self.samples.lock().unwrap();
+        let (within, total) = feature_flags::COMPONENTS.iter()
+            .filter(|spec| spec.tier == 1)
+            .filter_map(|spec| samples.get(&spec.id).map(|window| (spec, window)))
+            .fold((0, 0), |(within, total), (spec, window)| {
+                (within + window.iter().filter(|&&ms| ms <= spec.sla_ms).count(), total + window.len())
+            });
+        (total >= self.config.min_samples.max(1)).then(|| within as f64 / total as f64)
+    }
+
+    /// Move to the level Tier 1 compliance calls for: straight up on a breach, one level down
+    /// after `recover_checks` recovered evaluations in a row; holds while there is too little data
+    pub fn evaluate(&self) -> Option<DegradationEvent> {
+        let compliance = self.tier1_compliance();
+        if let Some(decision) = &self.decision {
+            decision.rotate();
+        }
+        let compliance = compliance?;
+        let target = self.config.target(compliance);
+        let event = {
+            let mut state = self.state.lock().unwrap();
+            let from = state.level;
+            let to = if target > from {
+                target
+            } else if target < from && compliance >= self.config.recover_at {
+                state.healthy_checks += 1;
+                if state.healthy_checks < self.config.recover_checks {
+                    from
+                } else {
+                    from.below()
+                }
+            } else {
+                state.healthy_checks = 0;
+                from
+            };
+            if to == from {
+                return None;
+            }
+            state.level = to;
+            state.healthy_checks = 0;
+            state.since = Some(chrono::Utc::now().to_rfc3339());
+            self.level.store(to as u8, Ordering::Relaxed);
+            DegradationEvent { from, to, tier1_compliance: compliance }
+        };
+
+        if let Some(flags) = &self.flags {
+            flags.set_shed_tiers(if event.to >= DegradationLevel::Shed { &SHED_TIERS } else { &[] });
+        }
+        if event.escalated() {
+            warn!("[DEGRADE] {} -> {} (Tier 1 SLA compliance {:.1}%)", event.from, event.to, compliance * 100.0);
+        } else {
+            info!("[DEGRADE] {} -> {} (Tier 1 SLA compliance {:.1}%)", event.from, event.to, compliance * 100.0);
+        }
+        for hook in &self.hooks {
+            hook(&event);
+        }
+        Some(event)
+    }
+
+    pub fn status(&self) -> DegradationStatus {
+        let components = {
+            let samples = self.samples.lock().unwrap();
+            samples.iter()
+                .filter_map(|(&id, window)| {
+                    let spec = feature_flags::component(id)?;
+                    let mut sorted: Vec<f64> = window.iter().copied().collect();
+                    sorted.sort_by(f64::total_cmp);
+                    let within = sorted.iter().filter(|&&ms| ms <= spec.sla_ms).count();
+                    Some((id, ComponentSla {
+                        id,
+                        tier: spec.tier,
+                        sla_ms: spec.sla_ms,
+                        samples: sorted.len(),
+                        p50_ms: sorted.get(sorted.len() / 2).copied().unwrap_or_default(),
+                        compliance: if sorted.is_empty() { 1.0 } else { within as f64 / sorted.len() as f64 },
+                    }))
+                })
+                .collect()
+        };
+        let state = self.state.lock().unwrap();
+        DegradationStatus {
+            level: state.level,
+            tier1_compliance: self.tier1_compliance(),
+            decision_compliance: self.decision_compliance(),
+            shed_tiers: if state.level >= DegradationLevel::Shed { SHED_TIERS.to_vec() } else { Vec::new() },
+            threshold_multiplier: self.threshold_multiplier(),
+            size_multiplier: self.size_multiplier(),
+            healthy_checks: state.healthy_checks,
+            since: state.since.clone(),
+            components,
+        }
+    }
+}
+
+/// Re-evaluate the policy every `interval`
+pub async fn run_sla_monitor(monitor: SharedSlaMonitor, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        monitor.evaluate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FeaturesSection;
+    use crate::decision_latency::{DecisionLatencyConfig, DecisionLatencyTracker};
+    use crate::feature_flags::FeatureFlags;
+
+    fn config() -> DegradationConfig {
+        DegradationConfig { window: 20, min_samples: 10, recover_checks: 2, ..DegradationConfig::default() }
+    }
+
+    /// Fill #75's window (200ms SLA) with `slow` of 20 samples over the SLA
+    fn load(monitor: &SlaMonitor, slow: usize) {
+        for i in 0..20 {
+            monitor.record(75, if i < slow { 400.0 } else { 120.0 });
+        }
+    }
+
+    #[test]
+    fn test_breach_escalates_immediately_and_recovers_one_level_at_a_time() {
+        let monitor = SlaMonitor::new(config());
+        assert_eq!(monitor.evaluate(), None); // no samples yet
+
+        load(&monitor, 2); // 90%
+        let event = monitor.evaluate().unwrap();
+        assert_eq!((event.from, event.to), (DegradationLevel::Normal, DegradationLevel::Shed));
+        load(&monitor, 6); // 70%
+        assert_eq!(monitor.evaluate().unwrap().to, DegradationLevel::Defensive);
+        assert_eq!(monitor.threshold_multiplier(), 1.5);
+        assert_eq!(monitor.size_multiplier(), 0.5);
+
+        // Good enough to stop escalating, not to recover
+        load(&monitor, 1);
+        assert_eq!(monitor.evaluate(), None);
+        load(&monitor, 0);
+        assert_eq!(monitor.evaluate(), None);
+        assert_eq!(monitor.evaluate().unwrap().to, DegradationLevel::Shed);
+        assert_eq!(monitor.size_multiplier(), 1.0);
+        assert_eq!(monitor.evaluate(), None);
+        let event = monitor.evaluate().unwrap();
+        assert_eq!(event.to, DegradationLevel::Normal);
+        assert_eq!(event.to_alert().kind, "sla_recovered");
+    }
+
+    #[test]
+    fn test_shed_tiers_reach_the_feature_flags() {
+        let flags = Arc::new(FeatureFlags::new(&FeaturesSection { debug: true, ..FeaturesSection::default() }));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let monitor = SlaMonitor::new(config())
+            .with_feature_flags(flags.clone())
+            .on_event(move |e| sink.lock().unwrap().push(e.to_alert()));
+
+        load(&monitor, 10);
+        monitor.evaluate();
+        assert!(monitor.sheds(73) && monitor.sheds(77) && !monitor.sheds(75));
+        assert!(!flags.component_enabled(73) && !flags.component_enabled(88) && !flags.component_enabled(77));
+        assert!(flags.component_enabled(71) && flags.component_enabled(75));
+        let alerts = events.lock().unwrap().clone();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind.as_str(), alerts[0].severity), ("sla_degraded", AlertSeverity::Critical));
+
+        load(&monitor, 0);
+        for _ in 0..4 {
+            monitor.evaluate();
+        }
+        assert_eq!(monitor.level(), DegradationLevel::Normal);
+        assert!(flags.component_enabled(73) && flags.component_enabled(77));
+        assert_eq!(events.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_compliance_counts_tier1_registry_components_only() {
+        let monitor = SlaMonitor::new(config());
+        for _ in 0..10 {
+            monitor.record(77, 60_000.0); // Tier 4, slow
+            monitor.record(51, 1_000.0); // not in the registry
+            monitor.record(85, 90.0);
+        }
+        for _ in 0..5 {
+            monitor.record(76, 300.0);
+        }
+        assert!((monitor.tier1_compliance().unwrap() - 10.0 / 15.0).abs() < 1e-9);
+
+        let status = monitor.status();
+        assert_eq!(status.components.len(), 3);
+        assert_eq!(status.components[&76].compliance, 0.0);
+        assert_eq!(status.components[&77].tier, 4);
+        assert!(status.shed_tiers.is_empty());
+    }
+
+    #[test]
+    fn test_decision_path_creep_degrades_without_model_breach() {
+        let decision = Arc::new(DecisionLatencyTracker::new(DecisionLatencyConfig { min_samples: 10, ..DecisionLatencyConfig::default() }));
+        let monitor = SlaMonitor::new(config()).with_decision_latency(decision.clone());
+        load(&monitor, 0);
+        for i in 0..20 {
+            decision.record("PolyYesKalshiNo", 1, if i < 5 { 180_000_000 } else { 3_000_000 }); // 75% under 100ms
+            decision.record("73", 3, 900_000_000); // Tier 3 doesn't drive the policy
+        }
+        assert_eq!(monitor.decision_compliance(), Some(0.75));
+        assert_eq!(monitor.evaluate().unwrap().to, DegradationLevel::Defensive);
+        let status = monitor.status();
+        assert_eq!((status.tier1_compliance, status.decision_compliance), (Some(0.75), Some(0.75)));
+        assert!(decision.summary().iter().all(|p| p.breached));
+    }
+}
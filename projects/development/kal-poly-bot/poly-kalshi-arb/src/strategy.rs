@@ -0,0 +1,79 @@
+// src/strategy.rs
+// Strategy ids that tag signals, orders, fills, alerts and dashboard panels, and the `strategy=` filter the query APIs share
+
+use serde::{Deserialize, Serialize};
+
+/// Kalshi/Polymarket cross-venue arbitrage (the main bot)
+pub const CROSS_VENUE_ARB: &str = "cross_venue_arb";
+/// Latency signals no pattern engine claimed
+pub const LATENCY_ARBITRAGE: &str = "latency_arbitrage";
+pub const MARKET_MAKING: &str = "market_making";
+
+/// Strategy of a pattern engine (`pattern_73`), or plain latency arbitrage when no pattern matched
+pub fn for_pattern(pattern_id: Option<u16>) -> String {
+    match pattern_id {
+        Some(id) => format!("pattern_{}", id),
+        None => LATENCY_ARBITRAGE.to_string(),
+    }
+}
+
+/// Pattern id of a `pattern_<id>` strategy
+pub fn pattern_of(strategy_id: &str) -> Option<u16> {
+    strategy_id.strip_prefix("pattern_")?.parse().ok()
+}
+
+/// Strategy of a position/lot attribution tag: numeric pattern tags (`"73"`) belong to their
+/// pattern, arb-type tags and untagged fills to cross-venue arbitrage
+pub fn of_position_tag(tag: Option<&str>) -> String {
+    match tag.and_then(|t| t.parse::<u16>().ok()) {
+        Some(id) => for_pattern(Some(id)),
+        None => CROSS_VENUE_ARB.to_string(),
+    }
+}
+
+/// Whether a record tagged `strategy_id` passes a `strategy=` filter: any of `wanted`, or
+/// everything when it is empty (untagged records only pass an empty filter)
+pub fn matches(wanted: &[String], strategy_id: Option<&str>) -> bool {
+    wanted.is_empty() || strategy_id.is_some_and(|id| wanted.iter().any(|w| w == id))
+}
+
+/// One strategy's recent activity, for the dashboard
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrategySummary {
+    pub strategy_id: String,
+    /// Latency signals still in the heatmap window
+    pub signals: usize,
+    /// Pattern opportunities still in the table
+    pub opportunities: usize,
+    /// Alerts in the dashboard's history
+    pub alerts: usize,
+    /// Newest signal, opportunity or alert (Unix ns)
+    pub last_activity_ns: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_strategies() {
+        assert_eq!(for_pattern(Some(73)), "pattern_73");
+        assert_eq!(for_pattern(None), LATENCY_ARBITRAGE);
+        assert_eq!(pattern_of("pattern_73"), Some(73));
+        assert_eq!(pattern_of(MARKET_MAKING), None);
+        assert_eq!(pattern_of("pattern_x"), None);
+        assert_eq!(of_position_tag(Some("51")), "pattern_51");
+        assert_eq!(of_position_tag(Some("PolyYesKalshiNo")), CROSS_VENUE_ARB);
+        assert_eq!(of_position_tag(None), CROSS_VENUE_ARB);
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let wanted = vec!["pattern_73".to_string(), MARKET_MAKING.to_string()];
+        assert!(matches(&wanted, Some("pattern_73")));
+        assert!(!matches(&wanted, Some(LATENCY_ARBITRAGE)));
+        assert!(!matches(&wanted, None));
+        assert!(matches(&[], None));
+        assert!(matches(&[], Some(CROSS_VENUE_ARB)));
+    }
+}
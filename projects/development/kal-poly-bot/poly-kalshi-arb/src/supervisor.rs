@@ -0,0 +1,1142 @@
+// src/supervisor.rs
+// Task supervisor - dependency-ordered startup, restart with backoff, heartbeat watchdog, graceful shutdown
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::alert_router::{Alert, AlertSeverity, SharedAlertRouter};
+use crate::config::SupervisorSection;
+use crate::wire_format::WireFormat;
+
+/// Lifecycle of a supervised subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemState {
+    /// Waiting for dependencies
+    Pending,
+    Starting,
+    Ready,
+    /// Running but reporting a problem (or missed heartbeats)
+    Degraded,
+    /// Crashed; waiting out the backoff
+    Restarting,
+    /// Gave up after too many restarts
+    Failed,
+    Stopped,
+}
+
+impl std::fmt::Display for SubsystemState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SubsystemState::Pending => "pending",
+            SubsystemState::Starting => "starting",
+            SubsystemState::Ready => "ready",
+            SubsystemState::Degraded => "degraded",
+            SubsystemState::Restarting => "restarting",
+            SubsystemState::Failed => "failed",
+            SubsystemState::Stopped => "stopped",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How often a crashing subsystem is restarted
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// Restarts allowed within `window` before the subsystem is marked failed
+    pub max_restarts: u32,
+    pub window: Duration,
+    pub backoff_initial: Duration,
+    pub backoff_max: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            window: Duration::from_secs(300),
+            backoff_initial: Duration::from_millis(500),
+            backoff_max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Never restart (one-shot tasks)
+    pub fn never() -> Self {
+        Self { max_restarts: 0, ..Self::default() }
+    }
+}
+
+/// Supervisor-wide timings
+#[derive(Debug, Clone, Copy)]
+pub struct SupervisorConfig {
+    /// How long each subsystem gets to become ready
+    pub startup_timeout: Duration,
+    /// How long each subsystem gets to exit after shutdown is signalled
+    pub shutdown_grace: Duration,
+    /// Heartbeat check period
+    pub health_interval: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self::from(&SupervisorSection::default())
+    }
+}
+
+impl From<&SupervisorSection> for SupervisorConfig {
+    fn from(s: &SupervisorSection) -> Self {
+        Self {
+            startup_timeout: Duration::from_secs(s.startup_timeout_secs),
+            shutdown_grace: Duration::from_secs(s.shutdown_grace_secs),
+            health_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// How far the watchdog has escalated a stalled subsystem (ordered)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Escalation {
+    #[default]
+    Clear,
+    /// Warned and marked degraded
+    Logged,
+    /// Routed to the alert router
+    Alerted,
+    /// Current run aborted and restarted
+    Restarted,
+    /// Kill switch tripped
+    Killed,
+}
+
+impl std::fmt::Display for Escalation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Escalation::Clear => "clear",
+            Escalation::Logged => "logged",
+            Escalation::Alerted => "alerted",
+            Escalation::Restarted => "restarted",
+            Escalation::Killed => "killed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Stall thresholds, measured from the last heartbeat; each stage fires once per stall
+/// and a watchdog restart does not reset the ladder - only a heartbeat does
+#[derive(Debug, Clone, Copy)]
+pub struct Watchdog {
+    pub log_after: Duration,
+    pub alert_after: Option<Duration>,
+    pub restart_after: Option<Duration>,
+    pub kill_after: Option<Duration>,
+}
+
+impl Watchdog {
+    /// Log and degrade after `log_after` without a heartbeat; later stages are opt-in
+    pub fn new(log_after: Duration) -> Self {
+        Self { log_after, alert_after: None, restart_after: None, kill_after: None }
+    }
+
+    pub fn alert_after(mut self, after: Duration) -> Self {
+        self.alert_after = Some(after);
+        self
+    }
+
+    pub fn restart_after(mut self, after: Duration) -> Self {
+        self.restart_after = Some(after);
+        self
+    }
+
+    pub fn kill_after(mut self, after: Duration) -> Self {
+        self.kill_after = Some(after);
+        self
+    }
+
+    /// Configured stages in ladder order
+    fn stages(&self) -> impl Iterator<Item = (Escalation, Duration)> {
+        [
+            (Escalation::Logged, Some(self.log_after)),
+            (Escalation::Alerted, self.alert_after),
+            (Escalation::Restarted, self.restart_after),
+            (Escalation::Killed, self.kill_after),
+        ]
+        .into_iter()
+        .filter_map(|(stage, after)| after.map(|a| (stage, a)))
+    }
+}
+
+type SubsystemFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+type SubsystemFactory = Arc<dyn Fn(SubsystemContext) -> SubsystemFuture + Send + Sync>;
+
+/// A restartable unit of work; the factory is called again on every restart
+pub struct Subsystem {
+    name: String,
+    depends_on: Vec<String>,
+    restart: RestartPolicy,
+    watchdog: Option<Watchdog>,
+    factory: SubsystemFactory,
+}
+
+impl Subsystem {
+    pub fn new<F, Fut>(name: &str, factory: F) -> Self
+    where
+        F: Fn(SubsystemContext) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            depends_on: Vec::new(),
+            restart: RestartPolicy::default(),
+            watchdog: None,
+            factory: Arc::new(move |ctx| Box::pin(factory(ctx))),
+        }
+    }
+
+    /// Start only after these subsystems are ready; stop before them
+    pub fn depends_on(mut self, names: &[&str]) -> Self {
+        self.depends_on.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    pub fn restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart = policy;
+        self
+    }
+
+    /// Mark degraded when `SubsystemContext::heartbeat` is not called this often
+    pub fn heartbeat_timeout(self, timeout: Duration) -> Self {
+        self.watchdog(Watchdog::new(timeout))
+    }
+
+    /// Escalate missed heartbeats: log -> alert -> restart -> kill switch
+    pub fn watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct HealthInner {
+    state: SubsystemState,
+    detail: Option<String>,
+    last_error: Option<String>,
+    started_at: Option<Instant>,
+    last_heartbeat: Option<Instant>,
+    escalation: Escalation,
+    /// Last heartbeat before the current stall; survives watchdog restarts
+    stall_origin: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Health {
+    inner: Mutex<HealthInner>,
+    state_tx: watch::Sender<SubsystemState>,
+    restarts: AtomicU32,
+    stale: AtomicBool,
+}
+
+impl Health {
+    fn new() -> Self {
+        Self {
+            inner: Mutex::new(HealthInner {
+                state: SubsystemState::Pending,
+                detail: None,
+                last_error: None,
+                started_at: None,
+                last_heartbeat: None,
+                escalation: Escalation::Clear,
+                stall_origin: None,
+            }),
+            state_tx: watch::channel(SubsystemState::Pending).0,
+            restarts: AtomicU32::new(0),
+            stale: AtomicBool::new(false),
+        }
+    }
+
+    fn set(&self, state: SubsystemState, detail: Option<String>) {
+        let mut inner = self.inner.lock().unwrap();
+        if state == SubsystemState::Starting {
+            inner.started_at = Some(Instant::now());
+            inner.last_heartbeat = None;
+            self.stale.store(false, Ordering::Relaxed);
+        }
+        inner.state = state;
+        inner.detail = detail;
+        drop(inner);
+        self.state_tx.send_replace(state);
+    }
+
+    fn state(&self) -> SubsystemState {
+        self.inner.lock().unwrap().state
+    }
+}
+
+/// Handle given to a running subsystem for reporting health and observing shutdown
+#[derive(Clone)]
+pub struct SubsystemContext {
+    name: Arc<str>,
+    health: Arc<Health>,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl SubsystemContext {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Initialization done; dependents may start
+    pub fn ready(&self) {
+        self.health.inner.lock().unwrap().last_heartbeat = Some(Instant::now());
+        self.health.set(SubsystemState::Ready, None);
+        info!("[SUPERVISOR] {} ready", self.name);
+    }
+
+    /// Running with a problem worth surfacing on the status endpoint
+    pub fn degraded(&self, reason: impl Into<String>) {
+        let reason = reason.into();
+        warn!("[SUPERVISOR] {} degraded: {}", self.name, reason);
+        self.health.set(SubsystemState::Degraded, Some(reason));
+    }
+
+    /// Liveness ping; clears a missed-heartbeat degradation and resets the watchdog ladder
+    pub fn heartbeat(&self) {
+        let mut inner = self.health.inner.lock().unwrap();
+        inner.last_heartbeat = Some(Instant::now());
+        inner.stall_origin = None;
+        let escalation = std::mem::take(&mut inner.escalation);
+        drop(inner);
+        if escalation >= Escalation::Alerted {
+            info!("[SUPERVISOR] {} heartbeat resumed after watchdog {}", self.name, escalation);
+        }
+        if self.health.stale.swap(false, Ordering::Relaxed) && self.health.state() == SubsystemState::Degraded {
+            info!("[SUPERVISOR] {} heartbeat recovered", self.name);
+            self.health.set(SubsystemState::Ready, None);
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Resolves once shutdown is signalled (use in `select!`)
+    pub async fn shutdown_requested(&self) {
+        stop_signalled(&mut self.shutdown.clone()).await
+    }
+}
+
+/// Resolves once the flag is true (or its sender is gone)
+async fn stop_signalled(rx: &mut watch::Receiver<bool>) {
+    let _ = rx.wait_for(|stop| *stop).await;
+}
+
+struct Entry {
+    spec: Subsystem,
+    health: Arc<Health>,
+    shutdown_tx: watch::Sender<bool>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+    /// Current run, for watchdog restarts
+    run: Mutex<Option<tokio::task::AbortHandle>>,
+    /// Why the watchdog aborted the current run
+    abort_reason: Mutex<Option<String>>,
+}
+
+/// Status of one subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemStatus {
+    pub name: String,
+    pub state: SubsystemState,
+    pub detail: Option<String>,
+    pub depends_on: Vec<String>,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    /// Time since the current run started
+    pub uptime_ms: Option<u64>,
+    pub heartbeat_age_ms: Option<u64>,
+    #[serde(default)]
+    pub escalation: Escalation,
+}
+
+/// Everything the status endpoint reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorStatus {
+    pub uptime_ms: u64,
+    /// Every subsystem ready
+    pub healthy: bool,
+    pub subsystems: Vec<SubsystemStatus>,
+    /// Extra sections registered with `add_probe` (bus lag, dashboard, ...)
+    pub probes: BTreeMap<String, serde_json::Value>,
+}
+
+type Probe = Box<dyn Fn() -> serde_json::Value + Send + Sync>;
+/// (method, path with query) -> (status code, JSON body)
+type Route = Arc<dyn Fn(&str, &str) -> (u16, String) + Send + Sync>;
+/// (subsystem, reason)
+type KillSwitch = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+/// Runs subsystems in dependency order and keeps them alive
+pub struct Supervisor {
+    config: SupervisorConfig,
+    /// Start order once `start` has resolved dependencies
+    entries: Vec<Entry>,
+    started_at: Instant,
+    probes: Mutex<Vec<(String, Probe)>>,
+    routes: Mutex<Vec<(String, Route)>>,
+    failed_tx: watch::Sender<Option<String>>,
+    monitor: Mutex<Option<JoinHandle<()>>>,
+    alerts: Mutex<Option<SharedAlertRouter>>,
+    kill_switch: Mutex<Option<KillSwitch>>,
+    /// "subsystem: reason" once the watchdog has tripped the kill switch
+    killed: Mutex<Option<String>>,
+}
+
+impl Supervisor {
+    /// Validate dependencies and order subsystems so each follows everything it depends on
+    pub fn new(config: SupervisorConfig, subsystems: Vec<Subsystem>) -> Result<Self> {
+        let order = start_order(&subsystems)?;
+        for s in &subsystems {
+            let Some(watchdog) = &s.watchdog else { continue };
+            let thresholds: Vec<Duration> = watchdog.stages().map(|(_, after)| after).collect();
+            if thresholds.windows(2).any(|w| w[1] < w[0]) {
+                bail!("{} watchdog stages must not shorten: {:?}", s.name, watchdog);
+            }
+        }
+        let mut slots: Vec<Option<Subsystem>> = subsystems.into_iter().map(Some).collect();
+        let entries = order.into_iter()
+            .map(|i| Entry {
+                spec: slots[i].take().expect("each subsystem ordered once"),
+                health: Arc::new(Health::new()),
+                shutdown_tx: watch::channel(false).0,
+                handle: Mutex::new(None),
+                run: Mutex::new(None),
+                abort_reason: Mutex::new(None),
+            })
+            .collect();
+
+        Ok(Self {
+            config,
+            entries,
+            started_at: Instant::now(),
+            probes: Mutex::new(Vec::new()),
+            routes: Mutex::new(Vec::new()),
+            failed_tx: watch::channel(None).0,
+            monitor: Mutex::new(None),
+            alerts: Mutex::new(None),
+            kill_switch: Mutex::new(None),
+            killed: Mutex::new(None),
+        })
+    }
+
+    /// Where watchdog alerts go (without one the alert stage only logs)
+    pub fn set_alert_router(&self, router: SharedAlertRouter) {
+        *self.alerts.lock().unwrap() = Some(router);
+    }
+
+    /// Called with (subsystem, reason) when a watchdog reaches its kill stage
+    pub fn set_kill_switch(&self, kill: impl Fn(&str, &str) + Send + Sync + 'static) {
+        *self.kill_switch.lock().unwrap() = Some(Arc::new(kill));
+    }
+
+    /// Add a section to the status report
+    pub fn add_probe(&self, name: &str, probe: impl Fn() -> serde_json::Value + Send + Sync + 'static) {
+        self.probes.lock().unwrap().push((name.to_string(), Box::new(probe)));
+    }
+
+    /// Serve requests under `prefix` (any method) from the status endpoint
+    pub fn add_route(&self, prefix: &str, handler: impl Fn(&str, &str) -> (u16, String) + Send + Sync + 'static) {
+        self.routes.lock().unwrap().push((prefix.to_string(), Arc::new(handler)));
+    }
+
+    fn route(&self, path: &str) -> Option<Route> {
+        let routes = self.routes.lock().unwrap();
+        routes.iter()
+            .find(|(prefix, _)| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || rest.starts_with('?'))
+            })
+            .map(|(_, handler)| handler.clone())
+    }
+
+    /// Subsystem names in start order
+    pub fn start_order(&self) -> Vec<&str> {
+        self.entries.iter().map(|e| e.spec.name.as_str()).collect()
+    }
+
+    /// Start every subsystem, waiting for each to report ready before starting
+    /// the next; on error the caller should `shutdown` what did start
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        for (index, entry) in self.entries.iter().enumerate() {
+            info!("[SUPERVISOR] Starting {}", entry.spec.name);
+            let supervisor = self.clone();
+            *entry.handle.lock().unwrap() = Some(tokio::spawn(async move { supervisor.supervise(index).await }));
+
+            let mut state_rx = entry.health.state_tx.subscribe();
+            let waited = tokio::time::timeout(
+                self.config.startup_timeout,
+                state_rx.wait_for(|s| matches!(s, SubsystemState::Ready | SubsystemState::Failed | SubsystemState::Stopped)),
+            ).await;
+            match waited {
+                Ok(Ok(state)) if *state == SubsystemState::Ready => {}
+                Ok(Ok(state)) => bail!("{} {} during startup", entry.spec.name, *state),
+                Ok(Err(_)) => bail!("{} health channel closed", entry.spec.name),
+                Err(_) => bail!("{} not ready after {:?}", entry.spec.name, self.config.startup_timeout),
+            }
+        }
+
+        let supervisor = self.clone();
+        *self.monitor.lock().unwrap() = Some(tokio::spawn(async move { supervisor.monitor_health().await }));
+        info!("[SUPERVISOR] All {} subsystems ready", self.entries.len());
+        Ok(())
+    }
+
+    /// Run one subsystem until shutdown, restarting it per its policy
+    async fn supervise(self: Arc<Self>, index: usize) {
+        let entry = &self.entries[index];
+        let name = entry.spec.name.as_str();
+        let policy = entry.spec.restart;
+        let mut shutdown_rx = entry.shutdown_tx.subscribe();
+        let mut recent: VecDeque<Instant> = VecDeque::new();
+        let mut backoff = policy.backoff_initial;
+
+        loop {
+            entry.health.set(SubsystemState::Starting, None);
+            let ctx = SubsystemContext {
+                name: Arc::from(name),
+                health: entry.health.clone(),
+                shutdown: shutdown_rx.clone(),
+            };
+            let run_started = Instant::now();
+            let mut run = tokio::spawn((entry.spec.factory)(ctx));
+            *entry.run.lock().unwrap() = Some(run.abort_handle());
+
+            let outcome = tokio::select! {
+                outcome = &mut run => outcome,
+                _ = stop_signalled(&mut shutdown_rx) => {
+                    match tokio::time::timeout(self.config.shutdown_grace, &mut run).await {
+                        Ok(outcome) => outcome,
+                        Err(_) => {
+                            run.abort();
+                            warn!("[SUPERVISOR] {} did not stop within {:?}; aborted", name, self.config.shutdown_grace);
+                            entry.health.set(SubsystemState::Stopped, Some("aborted after shutdown grace".to_string()));
+                            return;
+                        }
+                    }
+                }
+            };
+
+            entry.run.lock().unwrap().take();
+            let watchdog_abort = entry.abort_reason.lock().unwrap().take();
+            let error = match outcome {
+                Ok(Ok(())) if *shutdown_rx.borrow() => {
+                    info!("[SUPERVISOR] {} stopped", name);
+                    entry.health.set(SubsystemState::Stopped, None);
+                    return;
+                }
+                Ok(Ok(())) => "exited unexpectedly".to_string(),
+                Ok(Err(e)) => format!("{:#}", e),
+                Err(join) if join.is_panic() => "panicked".to_string(),
+                Err(join) => watchdog_abort.unwrap_or_else(|| join.to_string()),
+            };
+            entry.health.inner.lock().unwrap().last_error = Some(error.clone());
+
+            if *shutdown_rx.borrow() {
+                entry.health.set(SubsystemState::Stopped, Some(error));
+                return;
+            }
+
+            // A run that outlived the window resets the restart budget and backoff
+            let now = Instant::now();
+            if now.duration_since(run_started) > policy.window {
+                backoff = policy.backoff_initial;
+            }
+            recent.retain(|t| now.duration_since(*t) < policy.window);
+            if recent.len() as u32 >= policy.max_restarts {
+                error!("[SUPERVISOR] {} failed: {} ({} restarts in {:?})", name, error, recent.len(), policy.window);
+                entry.health.set(SubsystemState::Failed, Some(error.clone()));
+                self.failed_tx.send_replace(Some(format!("{}: {}", name, error)));
+                return;
+            }
+            recent.push_back(now);
+            entry.health.restarts.fetch_add(1, Ordering::Relaxed);
+
+            warn!("[SUPERVISOR] {} crashed: {} - restarting in {:?}", name, error, backoff);
+            entry.health.set(SubsystemState::Restarting, Some(error));
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = stop_signalled(&mut shutdown_rx) => {
+                    entry.health.set(SubsystemState::Stopped, None);
+                    return;
+                }
+            }
+            backoff = (backoff * 2).min(policy.backoff_max);
+        }
+    }
+
+    /// Walk each stalled subsystem up its watchdog ladder
+    async fn monitor_health(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.health_interval);
+        loop {
+            interval.tick().await;
+            for entry in &self.entries {
+                let Some(watchdog) = entry.spec.watchdog else { continue };
+                let (state, stalled, from, to) = {
+                    let mut inner = entry.health.inner.lock().unwrap();
+                    // A run the watchdog restarted keeps climbing until it beats again
+                    let watched = match inner.state {
+                        SubsystemState::Ready | SubsystemState::Degraded => true,
+                        SubsystemState::Starting => inner.stall_origin.is_some(),
+                        _ => false,
+                    };
+                    let Some(origin) = inner.stall_origin.or(inner.last_heartbeat).filter(|_| watched) else { continue };
+                    let stalled = origin.elapsed();
+                    let to = watchdog.stages()
+                        .take_while(|(_, after)| stalled > *after)
+                        .last()
+                        .map_or(Escalation::Clear, |(stage, _)| stage);
+                    if to == Escalation::Clear {
+                        continue;
+                    }
+                    inner.stall_origin = Some(origin);
+                    let from = inner.escalation;
+                    inner.escalation = inner.escalation.max(to);
+                    (inner.state, stalled, from, to)
+                };
+
+                let reason = format!("no heartbeat for {:?}", stalled);
+                if state == SubsystemState::Ready {
+                    entry.health.stale.store(true, Ordering::Relaxed);
+                    warn!("[SUPERVISOR] {} degraded: {}", entry.spec.name, reason);
+                    entry.health.set(SubsystemState::Degraded, Some(reason.clone()));
+                }
+                for (stage, _) in watchdog.stages().filter(|(stage, _)| *stage > from && *stage <= to) {
+                    self.escalate(entry, stage, &reason);
+                }
+            }
+        }
+    }
+
+    /// Run one watchdog stage's action
+    fn escalate(&self, entry: &Entry, stage: Escalation, reason: &str) {
+        let name = entry.spec.name.as_str();
+        let alert = |severity: AlertSeverity, kind: &str| {
+            if let Some(router) = self.alerts.lock().unwrap().clone() {
+                router.route(Alert::new("supervisor", severity, kind, format!("{}: {}", name, reason)));
+            }
+        };
+        match stage {
+            Escalation::Clear | Escalation::Logged => {}
+            Escalation::Alerted => alert(AlertSeverity::Warning, "subsystem_stalled"),
+            Escalation::Restarted => {
+                let run = entry.run.lock().unwrap().clone();
+                if let Some(run) = run {
+                    warn!("[SUPERVISOR] Watchdog restarting {}: {}", name, reason);
+                    *entry.abort_reason.lock().unwrap() = Some(format!("watchdog: {}", reason));
+                    run.abort();
+                }
+            }
+            Escalation::Killed => {
+                error!("[SUPERVISOR] Watchdog tripping kill switch for {}: {}", name, reason);
+                alert(AlertSeverity::Critical, "kill_switch");
+                self.killed.lock().unwrap().get_or_insert_with(|| format!("{}: {}", name, reason));
+                let kill = self.kill_switch.lock().unwrap().clone();
+                if let Some(kill) = kill {
+                    kill(name, reason);
+                }
+            }
+        }
+    }
+
+    /// "subsystem: reason" if a watchdog tripped the kill switch
+    pub fn kill_switch_tripped(&self) -> Option<String> {
+        self.killed.lock().unwrap().clone()
+    }
+
+    /// Resolves with "name: error" when a subsystem exhausts its restarts
+    pub async fn wait_for_failure(&self) -> String {
+        let mut rx = self.failed_tx.subscribe();
+        let failed = rx.wait_for(|f| f.is_some()).await.map(|f| f.clone());
+        match failed {
+            Ok(failed) => failed.unwrap_or_default(),
+            Err(_) => std::future::pending().await,
+        }
+    }
+
+    /// Stop subsystems in reverse start order, each within the shutdown grace
+    pub async fn shutdown(&self) {
+        info!("[SUPERVISOR] Shutting down {} subsystems", self.entries.len());
+        if let Some(monitor) = self.monitor.lock().unwrap().take() {
+            monitor.abort();
+        }
+        for entry in self.entries.iter().rev() {
+            entry.shutdown_tx.send_replace(true);
+            let handle = entry.handle.lock().unwrap().take();
+            if let Some(handle) = handle {
+                // The supervise loop enforces the grace; the margin covers its bookkeeping
+                if tokio::time::timeout(self.config.shutdown_grace + Duration::from_secs(1), handle).await.is_err() {
+                    warn!("[SUPERVISOR] {} supervisor task stuck", entry.spec.name);
+                }
+            } else {
+                entry.health.set(SubsystemState::Stopped, None);
+            }
+        }
+        info!("[SUPERVISOR] Shutdown complete");
+    }
+
+    pub fn status(&self) -> SupervisorStatus {
+        let subsystems: Vec<SubsystemStatus> = self.entries.iter()
+            .map(|entry| {
+                let inner = entry.health.inner.lock().unwrap();
+                SubsystemStatus {
+                    name: entry.spec.name.clone(),
+                    state: inner.state,
+                    detail: inner.detail.clone(),
+                    depends_on: entry.spec.depends_on.clone(),
+                    restarts: entry.health.restarts.load(Ordering::Relaxed),
+                    last_error: inner.last_error.clone(),
+                    uptime_ms: inner.started_at.map(|t| t.elapsed().as_millis() as u64),
+                    heartbeat_age_ms: inner.last_heartbeat.map(|t| t.elapsed().as_millis() as u64),
+                    escalation: inner.escalation,
+                }
+            })
+            .collect();
+        let probes = self.probes.lock().unwrap().iter()
+            .map(|(name, probe)| (name.clone(), probe()))
+            .collect();
+
+        SupervisorStatus {
+            uptime_ms: self.started_at.elapsed().as_millis() as u64,
+            healthy: subsystems.iter().all(|s| s.state == SubsystemState::Ready),
+            subsystems,
+            probes,
+        }
+    }
+}
+
+/// Topological order (stable: ties keep registration order)
+fn start_order(subsystems: &[Subsystem]) -> Result<Vec<usize>> {
+    let index: HashMap<&str, usize> = subsystems.iter().enumerate().map(|(i, s)| (s.name.as_str(), i)).collect();
+    if index.len() != subsystems.len() {
+        bail!("duplicate subsystem names");
+    }
+    for s in subsystems {
+        for dep in &s.depends_on {
+            if !index.contains_key(dep.as_str()) {
+                bail!("{} depends on unknown subsystem {}", s.name, dep);
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(subsystems.len());
+    let mut placed = vec![false; subsystems.len()];
+    while order.len() < subsystems.len() {
+        let next = (0..subsystems.len()).find(|&i| {
+            !placed[i] && subsystems[i].depends_on.iter().all(|d| placed[index[d.as_str()]])
+        });
+        match next {
+            Some(i) => {
+                placed[i] = true;
+                order.push(i);
+            }
+            None => {
+                let stuck: Vec<&str> = (0..subsystems.len()).filter(|&i| !placed[i]).map(|i| subsystems[i].name.as_str()).collect();
+                return Err(anyhow!("dependency cycle among {:?}", stuck));
+            }
+        }
+    }
+    Ok(order)
+}
+
+// === Status endpoint ===
+
+/// Serve `GET /status` (JSON), `GET /health` (200 when every subsystem is
+/// ready, 503 otherwise), `GET /healthz` (the same code with per-component
+/// heartbeat and watchdog detail) and any `add_route` handlers until `shutdown` flips to true.
+/// Callers sending `Accept: application/msgpack` or `application/cbor` get bodies in that
+/// encoding (see wire_format::WireFormat::negotiate)
+pub async fn serve_status(listener: TcpListener, supervisor: Arc<Supervisor>, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+    info!("[SUPERVISOR] Status endpoint on http://{}/status", listener.local_addr()?);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let supervisor = supervisor.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_status_request(stream, &supervisor).await {
+                        warn!("[SUPERVISOR] Status request failed: {}", e);
+                    }
+                });
+            }
+            _ = stop_signalled(&mut shutdown) => return Ok(()),
+        }
+    }
+}
+
+/// Component view for `/healthz`
+fn healthz_body(status: &SupervisorStatus, kill_switch: Option<String>) -> serde_json::Value {
+    let components: serde_json::Map<String, serde_json::Value> = status.subsystems.iter()
+        .map(|s| (s.name.clone(), serde_json::json!({
+            "state": s.state,
+            "heartbeat_age_ms": s.heartbeat_age_ms,
+            "escalation": s.escalation,
+            "restarts": s.restarts,
+            "detail": s.detail,
+        })))
+        .collect();
+    serde_json::json!({
+        "healthy": status.healthy,
+        "kill_switch": kill_switch,
+        "components": components,
+    })
+}
+
+async fn handle_status_request(mut stream: TcpStream, supervisor: &Supervisor) -> Result<()> {
+    let mut buf = vec![0u8; 4096];
+    let mut len = 0;
+    while !buf[..len].windows(4).any(|w| w == b"\r\n\r\n") {
+        if len == buf.len() {
+            bail!("request header too large");
+        }
+        let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf[len..])).await??;
+        if n == 0 {
+            break;
+        }
+        len += n;
+    }
+
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let mut parts = request.lines().next().unwrap_or_default().split_whitespace();
+    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+    let format = WireFormat::negotiate(header(&request, "accept"));
+
+    let (code, body) = match supervisor.route(path) {
+        Some(route) => route(method, path),
+        None => match (method, path) {
+            ("GET", "/status") => (200, serde_json::to_string_pretty(&supervisor.status())?),
+            ("GET", "/health") => {
+                let status = supervisor.status();
+                let code = if status.healthy { 200 } else { 503 };
+                (code, serde_json::json!({ "healthy": status.healthy }).to_string())
+            }
+            ("GET", "/healthz") => {
+                let status = supervisor.status();
+                let code = if status.healthy { 200 } else { 503 };
+                (code, healthz_body(&status, supervisor.kill_switch_tripped()).to_string())
+            }
+            ("GET", _) => (404, r#"{"error":"not found"}"#.to_string()),
+            _ => (405, r#"{"error":"method not allowed"}"#.to_string()),
+        },
+    };
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Service Unavailable",
+    };
+
+    let (format, body) = match format.transcode_json(&body) {
+        Ok(encoded) => (format, encoded),
+        Err(e) => {
+            warn!("[SUPERVISOR] {} body not sent as {:?}: {}", path, format, e);
+            (WireFormat::Json, body.into_bytes())
+        }
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nVary: Accept\r\nConnection: close\r\n\r\n",
+        code, reason, format.content_type(), body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Value of the `name` header (lowercase) in a raw request head
+fn header<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_config() -> SupervisorConfig {
+        SupervisorConfig {
+            startup_timeout: Duration::from_secs(2),
+            shutdown_grace: Duration::from_millis(100),
+            health_interval: Duration::from_millis(10),
+        }
+    }
+
+    /// Becomes ready, logs start/stop, runs until shutdown
+    fn service(name: &str, log: Arc<Mutex<Vec<String>>>) -> Subsystem {
+        Subsystem::new(name, move |ctx: SubsystemContext| {
+            let log = log.clone();
+            async move {
+                log.lock().unwrap().push(format!("start {}", ctx.name()));
+                ctx.ready();
+                ctx.shutdown_requested().await;
+                log.lock().unwrap().push(format!("stop {}", ctx.name()));
+                Ok(())
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_dependency_order_and_reverse_shutdown() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![
+            service("monitoring", log.clone()).depends_on(&["risk", "feeds"]),
+            service("risk", log.clone()).depends_on(&["feeds"]),
+            service("feeds", log.clone()),
+        ]).unwrap());
+        assert_eq!(supervisor.start_order(), vec!["feeds", "risk", "monitoring"]);
+
+        supervisor.start().await.unwrap();
+        assert!(supervisor.status().healthy);
+        supervisor.shutdown().await;
+
+        assert_eq!(*log.lock().unwrap(), vec![
+            "start feeds", "start risk", "start monitoring",
+            "stop monitoring", "stop risk", "stop feeds",
+        ]);
+        assert!(supervisor.status().subsystems.iter().all(|s| s.state == SubsystemState::Stopped));
+    }
+
+    #[test]
+    fn test_rejects_bad_dependencies() {
+        let noop = || Subsystem::new("x", |_ctx| async { Ok(()) });
+        let unknown = Supervisor::new(fast_config(), vec![noop().depends_on(&["missing"])]);
+        assert!(unknown.is_err());
+
+        let cycle = Supervisor::new(fast_config(), vec![
+            Subsystem::new("a", |_ctx| async { Ok(()) }).depends_on(&["b"]),
+            Subsystem::new("b", |_ctx| async { Ok(()) }).depends_on(&["a"]),
+        ]);
+        assert!(cycle.err().unwrap().to_string().contains("cycle"));
+    }
+
+    #[tokio::test]
+    async fn test_restarts_with_backoff_then_fails() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let counter = attempts.clone();
+        let flaky = Subsystem::new("flaky", move |ctx: SubsystemContext| {
+            let counter = counter.clone();
+            async move {
+                // Ready on the first run, then crash every time
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    ctx.ready();
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                bail!("boom")
+            }
+        }).restart_policy(RestartPolicy {
+            max_restarts: 2,
+            window: Duration::from_secs(60),
+            backoff_initial: Duration::from_millis(5),
+            backoff_max: Duration::from_millis(20),
+        });
+
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![flaky]).unwrap());
+        supervisor.start().await.unwrap();
+        let failure = tokio::time::timeout(Duration::from_secs(2), supervisor.wait_for_failure()).await.unwrap();
+        assert_eq!(failure, "flaky: boom");
+
+        let status = &supervisor.status().subsystems[0];
+        assert_eq!(status.state, SubsystemState::Failed);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(status.last_error.as_deref(), Some("boom"));
+        supervisor.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_stuck_subsystem_is_aborted_after_grace() {
+        let stubborn = Subsystem::new("stubborn", |ctx: SubsystemContext| async move {
+            ctx.ready();
+            std::future::pending::<()>().await;
+            Ok(())
+        });
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![stubborn]).unwrap());
+        supervisor.start().await.unwrap();
+
+        let started = Instant::now();
+        supervisor.shutdown().await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+        let status = &supervisor.status().subsystems[0];
+        assert_eq!(status.state, SubsystemState::Stopped);
+        assert_eq!(status.detail.as_deref(), Some("aborted after shutdown grace"));
+    }
+
+    #[tokio::test]
+    async fn test_missed_heartbeat_degrades_until_next_beat() {
+        let beat = Arc::new(tokio::sync::Notify::new());
+        let trigger = beat.clone();
+        let quiet = Subsystem::new("quiet", move |ctx: SubsystemContext| {
+            let beat = trigger.clone();
+            async move {
+                ctx.ready();
+                loop {
+                    tokio::select! {
+                        _ = beat.notified() => ctx.heartbeat(),
+                        _ = ctx.shutdown_requested() => return Ok(()),
+                    }
+                }
+            }
+        }).heartbeat_timeout(Duration::from_millis(30));
+
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![quiet]).unwrap());
+        supervisor.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = supervisor.status();
+        assert_eq!(status.subsystems[0].state, SubsystemState::Degraded);
+        assert_eq!(status.subsystems[0].escalation, Escalation::Logged);
+        assert!(!status.healthy);
+
+        beat.notify_one();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(supervisor.status().subsystems[0].state, SubsystemState::Ready);
+        assert_eq!(supervisor.status().subsystems[0].escalation, Escalation::Clear);
+        supervisor.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_escalates_to_restart_then_kill_switch() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        // Hangs right after ready, restarted run included
+        let wedged = Subsystem::new("feeds", move |ctx: SubsystemContext| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                ctx.ready();
+                std::future::pending::<()>().await;
+                Ok(())
+            }
+        })
+        .restart_policy(RestartPolicy { backoff_initial: Duration::from_millis(5), ..RestartPolicy::default() })
+        .watchdog(
+            Watchdog::new(Duration::from_millis(20))
+                .alert_after(Duration::from_millis(40))
+                .restart_after(Duration::from_millis(60))
+                .kill_after(Duration::from_millis(200)),
+        );
+
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![wedged]).unwrap());
+        let (sink, mut alerts) = crate::alert_router::ChannelSink::new();
+        let mut router = crate::alert_router::AlertRouter::new(Duration::from_secs(60));
+        router.add_sink(AlertSeverity::Info, Arc::new(sink));
+        supervisor.set_alert_router(Arc::new(router));
+        let kills = Arc::new(Mutex::new(Vec::new()));
+        let killed = kills.clone();
+        supervisor.set_kill_switch(move |name, _reason| killed.lock().unwrap().push(name.to_string()));
+        supervisor.start().await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        let status = supervisor.status().subsystems[0].clone();
+        assert_eq!(status.escalation, Escalation::Restarted);
+        assert_eq!(status.restarts, 1);
+        assert!(status.last_error.unwrap().starts_with("watchdog: no heartbeat"));
+        assert!(kills.lock().unwrap().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(supervisor.status().subsystems[0].escalation, Escalation::Killed);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(*kills.lock().unwrap(), vec!["feeds"]);
+        assert!(supervisor.kill_switch_tripped().unwrap().starts_with("feeds: no heartbeat"));
+        let kinds: Vec<(AlertSeverity, String)> = std::iter::from_fn(|| alerts.try_recv().ok())
+            .map(|a| (a.severity, a.kind))
+            .collect();
+        assert_eq!(kinds, vec![
+            (AlertSeverity::Warning, "subsystem_stalled".to_string()),
+            (AlertSeverity::Critical, "kill_switch".to_string()),
+        ]);
+        supervisor.shutdown().await;
+    }
+
+    #[test]
+    fn test_rejects_shortening_watchdog_ladder() {
+        let ladder = Watchdog::new(Duration::from_secs(10)).restart_after(Duration::from_secs(5));
+        let result = Supervisor::new(fast_config(), vec![Subsystem::new("x", |_ctx| async { Ok(()) }).watchdog(ladder)]);
+        assert!(result.err().unwrap().to_string().contains("watchdog"));
+    }
+
+    #[tokio::test]
+    async fn test_status_endpoint() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let supervisor = Arc::new(Supervisor::new(fast_config(), vec![service("feeds", log)]).unwrap());
+        supervisor.add_probe("event_bus", || serde_json::json!({ "published": 7 }));
+        supervisor.start().await.unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let server = tokio::spawn(serve_status(listener, supervisor.clone(), stop_rx));
+
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(format!("GET {} HTTP/1.1\r\nHost: x\r\n\r\n", path).as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let status = get("/status").await;
+        assert!(status.starts_with("HTTP/1.1 200"));
+        let body: SupervisorStatus = serde_json::from_str(status.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert!(body.healthy);
+        assert_eq!(body.subsystems[0].name, "feeds");
+        assert_eq!(body.probes["event_bus"]["published"], 7);
+
+        assert!(get("/health").await.starts_with("HTTP/1.1 200"));
+        let healthz = get("/healthz").await;
+        assert!(healthz.starts_with("HTTP/1.1 200"));
+        let body: serde_json::Value = serde_json::from_str(healthz.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["components"]["feeds"]["state"], "ready");
+        assert_eq!(body["components"]["feeds"]["escalation"], "clear");
+        assert!(body["kill_switch"].is_null());
+        assert!(get("/nope").await.starts_with("HTTP/1.1 404"));
+
+        supervisor.add_route("/echo", |method, path| (200, serde_json::json!({ "method": method, "path": path }).to_string()));
+        assert!(get("/echo/a?b=1").await.ends_with(r#"{"method":"GET","path":"/echo/a?b=1"}"#));
+        assert!(get("/echoes").await.starts_with("HTTP/1.1 404"));
+
+        // Binary encodings on request, JSON for anything else
+        for (accept, format) in [("application/msgpack", WireFormat::MessagePack), ("application/cbor", WireFormat::Cbor), ("text/html", WireFormat::Json)] {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(format!("GET /status HTTP/1.1\r\nHost: x\r\nAccept: {}\r\n\r\n", accept).as_bytes()).await.unwrap();
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await.unwrap();
+            let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+            let head = String::from_utf8_lossy(&response[..split]);
+            assert!(head.contains(&format!("Content-Type: {}", format.content_type())), "{}", head);
+            let body: SupervisorStatus = format.decode(&response[split + 4..]).unwrap();
+            assert_eq!(body.probes["event_bus"]["published"], 7);
+        }
+
+        supervisor.shutdown().await;
+        assert!(get("/health").await.starts_with("HTTP/1.1 503"));
+        assert!(get("/healthz").await.starts_with("HTTP/1.1 503"));
+
+        stop_tx.send_replace(true);
+        server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,423 @@
+// src/tca.rs
+// Post-trade transaction cost analysis - fill slippage vs decision, arrival and mid, kept per UTC day
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::clock::{self, SharedClock};
+use crate::config::TcaSection;
+use crate::error::StateStoreError;
+use crate::types::{Platform, PriceCents, NO_PRICE};
+
+const SEGMENT_PREFIX: &str = "tca-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+const NS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+const NS_PER_HOUR: u64 = 3_600 * 1_000_000_000;
+
+/// Upper bounds (ms) of the decision-to-arrival buckets; slower fills land in the last
+const LATENCY_BUCKETS_MS: [u64; 7] = [1, 5, 10, 25, 50, 100, 250];
+
+/// TCA store configuration
+#[derive(Debug, Clone)]
+pub struct TcaConfig {
+    /// One segment file per UTC day is written here
+    pub dir: PathBuf,
+}
+
+impl Default for TcaConfig {
+    fn default() -> Self {
+        Self::from(&TcaSection::default())
+    }
+}
+
+impl From<&TcaSection> for TcaConfig {
+    fn from(t: &TcaSection) -> Self {
+        Self { dir: PathBuf::from(&t.dir) }
+    }
+}
+
+/// One fill and the prices it is measured against (all in cents)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TcaFill {
+    /// Wall-clock time of the fill
+    pub ts_ns: u64,
+    pub market_id: String,
+    pub platform: Platform,
+    /// Market tier 1-4, when the order path knew it (older records have none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<u8>,
+    /// "yes" or "no"
+    pub side: String,
+    /// "buy" or "sell"
+    pub action: String,
+    /// Strategy/pattern attribution tag
+    pub pattern: String,
+    /// Strategy the order was placed for (older records have none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
+    pub contracts: f64,
+    /// Price the signal was priced at
+    pub decision_price: f64,
+    /// Touch on our side of the book when the order went out
+    pub arrival_price: f64,
+    /// Mid when the order went out
+    pub arrival_mid: f64,
+    /// Average fill price
+    pub fill_price: f64,
+    /// Signal detection to order submission
+    pub decision_to_arrival_ns: u64,
+}
+
+impl TcaFill {
+    /// Cost per contract vs `benchmark`: positive means we did worse than it
+    fn cost_vs(&self, benchmark: f64) -> f64 {
+        if self.action == "sell" {
+            benchmark - self.fill_price
+        } else {
+            self.fill_price - benchmark
+        }
+    }
+
+    pub fn slippage_vs_decision(&self) -> f64 {
+        self.cost_vs(self.decision_price)
+    }
+
+    pub fn slippage_vs_arrival(&self) -> f64 {
+        self.cost_vs(self.arrival_price)
+    }
+
+    pub fn slippage_vs_mid(&self) -> f64 {
+        self.cost_vs(self.arrival_mid)
+    }
+
+    /// How far the touch moved against us between decision and submission
+    pub fn edge_decay(&self) -> f64 {
+        self.slippage_vs_decision() - self.slippage_vs_arrival()
+    }
+
+    /// UTC hour of day (0-23)
+    pub fn hour_of_day(&self) -> u8 {
+        ((self.ts_ns % NS_PER_DAY) / NS_PER_HOUR) as u8
+    }
+}
+
+/// Touch and mid for buying `side`, from a binary book's two asks (None when a side is empty)
+pub fn touch_and_mid(yes_ask: PriceCents, no_ask: PriceCents, side: &str) -> Option<(f64, f64)> {
+    if yes_ask == NO_PRICE || no_ask == NO_PRICE {
+        return None;
+    }
+    // The best bid on one side is 100 minus the best ask on the other
+    let (ask, other_ask) = if side == "no" { (no_ask, yes_ask) } else { (yes_ask, no_ask) };
+    let bid = 100.0 - other_ask as f64;
+    Some((ask as f64, (ask as f64 + bid) / 2.0))
+}
+
+/// Contract-weighted slippage (cents per contract) over a set of fills
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TcaSummary {
+    pub fills: u64,
+    pub contracts: f64,
+    pub vs_decision: f64,
+    pub vs_arrival: f64,
+    pub vs_mid: f64,
+    /// Part of `vs_decision` lost before the order went out
+    pub edge_decay: f64,
+    pub avg_decision_to_arrival_ms: f64,
+}
+
+impl TcaSummary {
+    fn add(&mut self, fill: &TcaFill) {
+        let w = fill.contracts;
+        self.fills += 1;
+        self.contracts += w;
+        self.vs_decision += fill.slippage_vs_decision() * w;
+        self.vs_arrival += fill.slippage_vs_arrival() * w;
+        self.vs_mid += fill.slippage_vs_mid() * w;
+        self.edge_decay += fill.edge_decay() * w;
+        self.avg_decision_to_arrival_ms += fill.decision_to_arrival_ns as f64 / 1e6;
+    }
+
+    /// Turn the running sums into means
+    fn finish(mut self) -> Self {
+        if self.contracts > 0.0 {
+            self.vs_decision /= self.contracts;
+            self.vs_arrival /= self.contracts;
+            self.vs_mid /= self.contracts;
+            self.edge_decay /= self.contracts;
+        }
+        if self.fills > 0 {
+            self.avg_decision_to_arrival_ms /= self.fills as f64;
+        }
+        self
+    }
+}
+
+/// Slippage aggregated the ways the edge decay and fill models are checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TcaReport {
+    pub from_ns: u64,
+    pub to_ns: u64,
+    pub overall: TcaSummary,
+    pub by_pattern: BTreeMap<String, TcaSummary>,
+    /// Keyed by strategy id; untagged fills are only in the other groups
+    pub by_strategy: BTreeMap<String, TcaSummary>,
+    /// Keyed by platform (e.g. "KALSHI")
+    pub by_venue: BTreeMap<String, TcaSummary>,
+    /// Keyed by UTC hour of day
+    pub by_hour: BTreeMap<u8, TcaSummary>,
+    /// Keyed by decision-to-arrival bucket upper bound in ms (`u64::MAX` = slower)
+    pub by_latency_ms: BTreeMap<u64, TcaSummary>,
+}
+
+impl TcaReport {
+    pub fn from_fills<'a>(from_ns: u64, to_ns: u64, fills: impl IntoIterator<Item = &'a TcaFill>) -> Self {
+        let mut report = TcaReport { from_ns, to_ns, ..Default::default() };
+        for fill in fills {
+            let latency_ms = fill.decision_to_arrival_ns / 1_000_000;
+            let bucket = LATENCY_BUCKETS_MS.into_iter().find(|&b| latency_ms < b).unwrap_or(u64::MAX);
+
+            report.overall.add(fill);
+            report.by_pattern.entry(fill.pattern.clone()).or_default().add(fill);
+            if let Some(strategy_id) = &fill.strategy_id {
+                report.by_strategy.entry(strategy_id.clone()).or_default().add(fill);
+            }
+            report.by_venue.entry(fill.platform.to_string()).or_default().add(fill);
+            report.by_hour.entry(fill.hour_of_day()).or_default().add(fill);
+            report.by_latency_ms.entry(bucket).or_default().add(fill);
+        }
+
+        report.overall = report.overall.finish();
+        finish_all(&mut report.by_pattern);
+        finish_all(&mut report.by_strategy);
+        finish_all(&mut report.by_venue);
+        finish_all(&mut report.by_hour);
+        finish_all(&mut report.by_latency_ms);
+        report
+    }
+}
+
+fn finish_all<K>(group: &mut BTreeMap<K, TcaSummary>) {
+    for summary in group.values_mut() {
+        *summary = std::mem::take(summary).finish();
+    }
+}
+
+struct Writer {
+    file: File,
+    day: u64,
+}
+
+/// Append-only history of TCA fills, one JSON line per fill, segments rotate daily
+pub struct TcaStore {
+    config: TcaConfig,
+    clock: SharedClock,
+    writer: Mutex<Option<Writer>>,
+}
+
+pub type SharedTcaStore = Arc<TcaStore>;
+
+impl TcaStore {
+    pub fn open(config: TcaConfig) -> Result<Self, StateStoreError> {
+        Self::open_with_clock(config, clock::system())
+    }
+
+    pub fn open_with_clock(config: TcaConfig, clock: SharedClock) -> Result<Self, StateStoreError> {
+        std::fs::create_dir_all(&config.dir)?;
+        info!("[TCA] Recording to {}", config.dir.display());
+        Ok(Self { config, clock, writer: Mutex::new(None) })
+    }
+
+    /// Wall-clock time for stamping fills
+    pub fn now_ns(&self) -> u64 {
+        self.clock.wall_ns().0
+    }
+
+    pub fn append(&self, fill: &TcaFill) -> Result<(), StateStoreError> {
+        let day = fill.ts_ns / NS_PER_DAY;
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        if writer.as_ref().is_none_or(|w| w.day != day) {
+            let path = self.config.dir.join(segment_name(day));
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            *writer = Some(Writer { file, day });
+        }
+        if let Some(writer) = writer.as_mut() {
+            writeln!(writer.file, "{}", serde_json::to_string(fill)?)?;
+        }
+        Ok(())
+    }
+
+    /// Append, logging instead of failing (for hot paths)
+    pub fn record(&self, fill: &TcaFill) {
+        if let Err(e) = self.append(fill) {
+            warn!("[TCA] Append failed: {}", e);
+        }
+    }
+
+    /// Fills with `from_ns <= ts_ns < to_ns`, oldest segment first
+    pub fn load(&self, from_ns: u64, to_ns: u64) -> Result<Vec<TcaFill>, StateStoreError> {
+        // Hold the writer so a concurrent append can't be read half-written
+        let _writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = Vec::new();
+        for (day, path) in segments(&self.config.dir)? {
+            if day < from_ns / NS_PER_DAY || day > to_ns / NS_PER_DAY {
+                continue;
+            }
+            out.extend(read_segment(&path)?.into_iter().filter(|f| f.ts_ns >= from_ns && f.ts_ns < to_ns));
+        }
+        Ok(out)
+    }
+
+    /// Report over the trailing `days` (today included)
+    pub fn report_days(&self, days: u64) -> Result<TcaReport, StateStoreError> {
+        let to_ns = self.now_ns() + 1;
+        let from_ns = (to_ns / NS_PER_DAY).saturating_sub(days.saturating_sub(1)) * NS_PER_DAY;
+        let fills = self.load(from_ns, to_ns)?;
+        Ok(TcaReport::from_fills(from_ns, to_ns, &fills))
+    }
+}
+
+fn segment_name(day: u64) -> String {
+    let date = chrono::DateTime::from_timestamp((day * 86_400) as i64, 0).unwrap_or_default();
+    format!("{}{}{}", SEGMENT_PREFIX, date.format("%Y-%m-%d"), SEGMENT_SUFFIX)
+}
+
+/// (UTC day number, path) of every segment, oldest first
+fn segments(dir: &Path) -> Result<Vec<(u64, PathBuf)>, StateStoreError> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let Some(date) = name.strip_prefix(SEGMENT_PREFIX).and_then(|n| n.strip_suffix(SEGMENT_SUFFIX)) else {
+            continue;
+        };
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            let secs = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp();
+            out.push((secs.max(0) as u64 / 86_400, path));
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+/// Fills of a segment; a torn or corrupt line is skipped (and reported), not fatal
+fn read_segment(path: &Path) -> Result<Vec<TcaFill>, StateStoreError> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut out = Vec::new();
+    let mut corrupt = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(fill) => out.push(fill),
+            Err(_) => corrupt += 1,
+        }
+    }
+    if corrupt > 0 {
+        warn!("[TCA] Skipped {} corrupt records in {}", corrupt, path.display());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::types::Nanos;
+    use std::time::Duration;
+
+    /// 2024-01-01T23:00:00Z
+    const START: u64 = 1_704_150_000 * 1_000_000_000;
+
+    fn fill(platform: Platform, pattern: &str, contracts: f64, decision: f64, arrival: f64, fill_price: f64) -> TcaFill {
+        TcaFill {
+            ts_ns: START,
+            market_id: "M1".into(),
+            platform,
+            tier: None,
+            side: "yes".into(),
+            action: "buy".into(),
+            pattern: pattern.into(),
+            strategy_id: None,
+            contracts,
+            decision_price: decision,
+            arrival_price: arrival,
+            arrival_mid: arrival - 1.0,
+            fill_price,
+            decision_to_arrival_ns: 3_000_000,
+        }
+    }
+
+    #[test]
+    fn test_slippage_benchmarks_and_mid() {
+        let buy = fill(Platform::Kalshi, "p", 10.0, 40.0, 42.0, 43.0);
+        assert_eq!(buy.slippage_vs_decision(), 3.0);
+        assert_eq!(buy.slippage_vs_arrival(), 1.0);
+        assert_eq!(buy.slippage_vs_mid(), 2.0);
+        assert_eq!(buy.edge_decay(), 2.0);
+        assert_eq!(buy.hour_of_day(), 23);
+
+        let sell = TcaFill { action: "sell".into(), ..fill(Platform::Kalshi, "p", 10.0, 40.0, 39.0, 38.0) };
+        assert_eq!(sell.slippage_vs_decision(), 2.0);
+
+        // YES ask 45, NO ask 52 -> YES bid 48
+        assert_eq!(touch_and_mid(45, 52, "yes"), Some((45.0, 46.5)));
+        assert_eq!(touch_and_mid(45, 52, "no"), Some((52.0, 53.5)));
+        assert_eq!(touch_and_mid(NO_PRICE, 52, "yes"), None);
+    }
+
+    #[test]
+    fn test_report_weights_by_contracts_and_groups() {
+        let fills = [
+            fill(Platform::Kalshi, "PolyYesKalshiNo", 10.0, 40.0, 40.0, 41.0),
+            fill(Platform::Polymarket, "PolyYesKalshiNo", 30.0, 50.0, 51.0, 51.0),
+            TcaFill {
+                decision_to_arrival_ns: 400_000_000,
+                strategy_id: Some("pattern_73".into()),
+                ..fill(Platform::Polymarket, "PolyOnly", 10.0, 50.0, 50.0, 50.0)
+            },
+        ];
+        let report = TcaReport::from_fills(0, u64::MAX, &fills);
+
+        assert_eq!(report.overall.fills, 3);
+        assert!((report.overall.vs_decision - (10.0 + 30.0) / 50.0).abs() < 1e-9);
+        let cross = &report.by_pattern["PolyYesKalshiNo"];
+        assert!((cross.vs_decision - 1.0).abs() < 1e-9);
+        assert!((cross.edge_decay - 0.75).abs() < 1e-9);
+        assert_eq!(report.by_venue["POLYMARKET"].fills, 2);
+        assert_eq!(report.by_strategy.len(), 1);
+        assert_eq!(report.by_strategy["pattern_73"].fills, 1);
+        assert_eq!(report.by_hour[&23].fills, 3);
+        assert_eq!(report.by_latency_ms[&5].fills, 2);
+        assert_eq!(report.by_latency_ms[&u64::MAX].fills, 1);
+    }
+
+    #[test]
+    fn test_store_rotates_daily_and_loads_ranges() {
+        let dir = std::env::temp_dir().join(format!("tca_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let clock = MockClock::shared(Nanos(START));
+        let store = TcaStore::open_with_clock(TcaConfig { dir: dir.clone() }, clock.clone()).unwrap();
+
+        store.append(&fill(Platform::Kalshi, "a", 1.0, 40.0, 40.0, 41.0)).unwrap();
+        clock.advance(Duration::from_secs(2 * 3600)); // next UTC day
+        store.append(&TcaFill { ts_ns: store.now_ns(), ..fill(Platform::Kalshi, "b", 1.0, 40.0, 40.0, 41.0) }).unwrap();
+        std::fs::OpenOptions::new().append(true).open(dir.join(segment_name(START / NS_PER_DAY + 1)))
+            .unwrap().write_all(b"{\"torn\n").unwrap();
+
+        assert_eq!(segments(&dir).unwrap().len(), 2);
+        assert_eq!(store.load(0, u64::MAX).unwrap().len(), 2);
+        let today = store.report_days(1).unwrap();
+        assert_eq!(today.overall.fills, 1);
+        assert!(today.by_pattern.contains_key("b"));
+        assert_eq!(store.report_days(2).unwrap().overall.fills, 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
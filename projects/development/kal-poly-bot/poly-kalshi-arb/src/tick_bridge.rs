@@ -0,0 +1,600 @@
+// src/tick_bridge.rs
+// Shared-memory tick bridge - two single-producer/single-consumer rings in one buffer
+// (a SharedArrayBuffer on the Bun side) carrying ticks into the latency engine and its
+// signals back out, with no per-message serialization
+//
+// Layout (little-endian; src/bun/tick_bridge.bun.ts mirrors it):
+//
+//   header   320 bytes  magic, version and capacities, then one cache line per ring index
+//   ticks    tick_capacity * 24 bytes     written by Bun, read by Rust
+//   triggers trigger_capacity * 56 bytes  written by Rust, read by Bun
+//
+// Ring indices are free-running u32 counters (slot = index & (capacity - 1)); a ring is
+// full when head - tail == capacity. The producer fills the slot, then publishes it by
+// storing head; the consumer copies the slot out, then releases it by storing tail. JS
+// `Atomics` are sequentially consistent, so they pair with the acquire/release here.
+//
+// The napi binding (`--features napi`) is a class owning the bridge and an engine; build
+// it with `cargo rustc --lib --crate-type cdylib --release --features napi`.
+
+use anyhow::{bail, Result};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+use crate::clock::{self, SharedClock};
+use crate::event_bus::{Event, Subscription};
+use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal, MarketTier, PriceObservation};
+use crate::strategy;
+use crate::types::{MarketType, Nanos, Platform};
+
+/// "TBR1"
+pub const MAGIC: u32 = 0x3152_4254;
+pub const VERSION: u32 = 1;
+pub const HEADER_BYTES: usize = 320;
+pub const TICK_BYTES: usize = 24;
+pub const TRIGGER_BYTES: usize = 56;
+
+// Header offsets; each index sits on its own cache line so the two sides don't false-share
+const MAGIC_AT: usize = 0;
+const VERSION_AT: usize = 4;
+const TICK_CAPACITY_AT: usize = 8;
+const TRIGGER_CAPACITY_AT: usize = 12;
+const TICK_HEAD_AT: usize = 64;
+/// Ticks the producer could not push because the ring was full
+const TICKS_DROPPED_AT: usize = 68;
+const TICK_TAIL_AT: usize = 128;
+const TRIGGER_HEAD_AT: usize = 192;
+const TRIGGERS_DROPPED_AT: usize = 196;
+const TRIGGER_TAIL_AT: usize = 256;
+
+/// Wire codes are indices into these tables (tiers are 1-based)
+pub const PLATFORMS: [Platform; 9] = [
+    Platform::Kalshi,
+    Platform::Polymarket,
+    Platform::DraftKings,
+    Platform::FanDuel,
+    Platform::BetMGM,
+    Platform::Caesars,
+    Platform::PointsBet,
+    Platform::Barstool,
+    Platform::ESPN,
+];
+pub const MARKET_TYPES: [MarketType; 10] = [
+    MarketType::Moneyline,
+    MarketType::Spread,
+    MarketType::Total,
+    MarketType::Btts,
+    MarketType::TeamTotal,
+    MarketType::QuarterTotal,
+    MarketType::HalfTotal,
+    MarketType::PlayerProp,
+    MarketType::AltLine,
+    MarketType::Combo,
+];
+pub const TIERS: [MarketTier; 4] = [MarketTier::Tier1, MarketTier::Tier2, MarketTier::Tier3, MarketTier::Tier4];
+
+/// Bridge counters; the dropped counts come from the shared header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BridgeStats {
+    pub ticks_received: u64,
+    /// Records with an unknown code or an out-of-range price, skipped
+    pub ticks_rejected: u64,
+    /// Ticks Bun could not push because the ring was full
+    pub ticks_dropped: u64,
+    pub triggers_sent: u64,
+    /// Signals lost because Bun had not drained the trigger ring
+    pub triggers_dropped: u64,
+    /// From the producer's write to the engine seeing the tick (wall clock)
+    pub avg_crossing_ns: u64,
+    pub max_crossing_ns: u64,
+}
+
+pub struct TickBridge {
+    base: NonNull<u8>,
+    tick_capacity: u32,
+    trigger_capacity: u32,
+    clock: SharedClock,
+    stats: BridgeStats,
+    crossing_total_ns: u64,
+    crossing_samples: u64,
+    /// Backing memory when the bridge allocated it itself
+    _owned: Option<Box<[AtomicU64]>>,
+}
+
+// The region is only touched through atomics and slot copies ordered by them
+unsafe impl Send for TickBridge {}
+
+impl TickBridge {
+    /// Buffer size for the given capacities
+    pub fn bytes_for(tick_capacity: u32, trigger_capacity: u32) -> usize {
+        HEADER_BYTES + tick_capacity as usize * TICK_BYTES + trigger_capacity as usize * TRIGGER_BYTES
+    }
+
+    /// Bridge over memory of its own (in-process producers, tests and benches)
+    pub fn new(tick_capacity: u32, trigger_capacity: u32) -> Result<Self> {
+        let len = Self::bytes_for(tick_capacity, trigger_capacity);
+        let mut owned: Box<[AtomicU64]> = (0..len / 8).map(|_| AtomicU64::new(0)).collect();
+        // SAFETY: the allocation is 8-aligned, `len` bytes long and moves with the bridge
+        let mut bridge = unsafe { Self::init(owned.as_mut_ptr().cast(), len, tick_capacity, trigger_capacity)? };
+        bridge._owned = Some(owned);
+        Ok(bridge)
+    }
+
+    /// Lay out an empty bridge in `len` bytes at `ptr`
+    ///
+    /// # Safety
+    /// `ptr` must be valid for reads and writes of `len` bytes for the bridge's lifetime,
+    /// and only the peer (one producer per ring) may touch the region meanwhile.
+    pub unsafe fn init(ptr: *mut u8, len: usize, tick_capacity: u32, trigger_capacity: u32) -> Result<Self> {
+        for (name, capacity) in [("tick", tick_capacity), ("trigger", trigger_capacity)] {
+            if !capacity.is_power_of_two() {
+                bail!("{} capacity must be a power of two, got {}", name, capacity);
+            }
+        }
+        let Some(base) = NonNull::new(ptr) else { bail!("tick bridge memory is null") };
+        if !ptr.cast::<u64>().is_aligned() {
+            bail!("tick bridge memory must be 8-byte aligned");
+        }
+        let needed = Self::bytes_for(tick_capacity, trigger_capacity);
+        if len < needed {
+            bail!("tick bridge needs {} bytes for {} ticks and {} triggers, got {}", needed, tick_capacity, trigger_capacity, len);
+        }
+
+        std::ptr::write_bytes(ptr, 0, HEADER_BYTES);
+        let bridge = Self::from_parts(base, tick_capacity, trigger_capacity);
+        bridge.word(VERSION_AT).store(VERSION, Ordering::Relaxed);
+        bridge.word(TICK_CAPACITY_AT).store(tick_capacity, Ordering::Relaxed);
+        bridge.word(TRIGGER_CAPACITY_AT).store(trigger_capacity, Ordering::Relaxed);
+        // Magic last: a peer polling for it sees a complete header
+        bridge.word(MAGIC_AT).store(MAGIC, Ordering::Release);
+        Ok(bridge)
+    }
+
+    /// Join a bridge another party laid out with `init`
+    ///
+    /// # Safety
+    /// As for `init`; the peer keeps its ring roles.
+    pub unsafe fn attach(ptr: *mut u8, len: usize) -> Result<Self> {
+        let Some(base) = NonNull::new(ptr) else { bail!("tick bridge memory is null") };
+        if !ptr.cast::<u64>().is_aligned() || len < HEADER_BYTES {
+            bail!("tick bridge memory must be 8-byte aligned and hold the {}-byte header", HEADER_BYTES);
+        }
+        let header = Self::from_parts(base, 0, 0);
+        if header.word(MAGIC_AT).load(Ordering::Acquire) != MAGIC {
+            bail!("no tick bridge in this memory (bad magic)");
+        }
+        let version = header.word(VERSION_AT).load(Ordering::Relaxed);
+        if version != VERSION {
+            bail!("tick bridge version {} (expected {})", version, VERSION);
+        }
+        let tick_capacity = header.word(TICK_CAPACITY_AT).load(Ordering::Relaxed);
+        let trigger_capacity = header.word(TRIGGER_CAPACITY_AT).load(Ordering::Relaxed);
+        if !tick_capacity.is_power_of_two() || !trigger_capacity.is_power_of_two() || len < Self::bytes_for(tick_capacity, trigger_capacity) {
+            bail!("tick bridge header is corrupt or the memory is too small");
+        }
+        Ok(Self::from_parts(base, tick_capacity, trigger_capacity))
+    }
+
+    fn from_parts(base: NonNull<u8>, tick_capacity: u32, trigger_capacity: u32) -> Self {
+        Self {
+            base,
+            tick_capacity,
+            trigger_capacity,
+            clock: clock::system(),
+            stats: BridgeStats::default(),
+            crossing_total_ns: 0,
+            crossing_samples: 0,
+            _owned: None,
+        }
+    }
+
+    /// Stamp received ticks from another clock (tests, replay)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn stats(&self) -> BridgeStats {
+        BridgeStats {
+            ticks_dropped: self.word(TICKS_DROPPED_AT).load(Ordering::Relaxed) as u64,
+            triggers_dropped: self.word(TRIGGERS_DROPPED_AT).load(Ordering::Relaxed) as u64,
+            avg_crossing_ns: self.crossing_total_ns.checked_div(self.crossing_samples).unwrap_or(0),
+            ..self.stats
+        }
+    }
+
+    /// Producer side of the tick ring, for when Rust rather than Bun feeds it;
+    /// `sent_wall_ns` is the Unix time of the write (0 if unknown). False when full.
+    pub fn push_tick(&self, obs: &PriceObservation, sent_wall_ns: u64) -> bool {
+        let mut record = [0u8; TICK_BYTES];
+        record[0..8].copy_from_slice(&sent_wall_ns.to_le_bytes());
+        record[8..10].copy_from_slice(&obs.market_id.to_le_bytes());
+        record[10..12].copy_from_slice(&obs.price.to_le_bytes());
+        record[12..14].copy_from_slice(&obs.size.to_le_bytes());
+        record[14] = code(&PLATFORMS, &obs.provider);
+        record[15] = code(&MARKET_TYPES, &obs.market_type);
+        record[16] = code(&TIERS, &obs.tier) + 1;
+        self.push(Ring::TICKS, &record, self.tick_capacity)
+    }
+
+    /// Next tick, stamped with the engine clock's receive time; malformed records are
+    /// counted and skipped
+    pub fn pop_tick(&mut self) -> Option<PriceObservation> {
+        let mut record = [0u8; TICK_BYTES];
+        while self.pop(Ring::TICKS, &mut record, self.tick_capacity) {
+            let now = self.clock.now();
+            let Some(obs) = decode_tick(&record, now.mono.0) else {
+                self.stats.ticks_rejected += 1;
+                continue;
+            };
+            self.stats.ticks_received += 1;
+
+            let sent_wall_ns = u64::from_le_bytes(record[0..8].try_into().expect("8 bytes"));
+            if sent_wall_ns != 0 {
+                let crossing = now.wall.0.saturating_sub(sent_wall_ns);
+                self.crossing_total_ns += crossing;
+                self.crossing_samples += 1;
+                self.stats.max_crossing_ns = self.stats.max_crossing_ns.max(crossing);
+            }
+            return Some(obs);
+        }
+        None
+    }
+
+    /// Queue a signal for Bun; timestamps go out as Unix nanoseconds. False when full.
+    pub fn push_trigger(&mut self, signal: &LatencySignal) -> bool {
+        let now = self.clock.now();
+        let wall = |mono: u64| now.wall_at(Nanos(mono)).0;
+        let (fast, slow) = (&signal.fast_market, &signal.slow_market);
+
+        let mut record = [0u8; TRIGGER_BYTES];
+        record[0..8].copy_from_slice(&signal.expected_convergence_ns.to_le_bytes());
+        record[8..16].copy_from_slice(&signal.confidence.to_le_bytes());
+        record[16..24].copy_from_slice(&wall(fast.timestamp_ns).to_le_bytes());
+        record[24..32].copy_from_slice(&wall(slow.timestamp_ns).to_le_bytes());
+        record[32..34].copy_from_slice(&fast.market_id.to_le_bytes());
+        record[34..36].copy_from_slice(&slow.market_id.to_le_bytes());
+        record[36..38].copy_from_slice(&fast.price.to_le_bytes());
+        record[38..40].copy_from_slice(&slow.price.to_le_bytes());
+        record[40..42].copy_from_slice(&signal.disparity_cents.to_le_bytes());
+        record[42..44].copy_from_slice(&signal.pattern_id.unwrap_or(0).to_le_bytes());
+        record[44..46].copy_from_slice(&fast.size.to_le_bytes());
+        record[46..48].copy_from_slice(&slow.size.to_le_bytes());
+        record[48] = code(&PLATFORMS, &fast.provider);
+        record[49] = code(&PLATFORMS, &slow.provider);
+        record[50] = code(&MARKET_TYPES, &fast.market_type);
+        record[51] = code(&MARKET_TYPES, &slow.market_type);
+        record[52] = code(&TIERS, &fast.tier) + 1;
+        record[53] = code(&TIERS, &slow.tier) + 1;
+
+        let pushed = self.push(Ring::TRIGGERS, &record, self.trigger_capacity);
+        if pushed {
+            self.stats.triggers_sent += 1;
+        }
+        pushed
+    }
+
+    /// Consumer side of the trigger ring, for when Rust rather than Bun drains it;
+    /// observation timestamps are Unix nanoseconds
+    pub fn pop_trigger(&self) -> Option<LatencySignal> {
+        let mut record = [0u8; TRIGGER_BYTES];
+        while self.pop(Ring::TRIGGERS, &mut record, self.trigger_capacity) {
+            if let Some(signal) = decode_trigger(&record) {
+                return Some(signal);
+            }
+        }
+        None
+    }
+
+    /// Feed up to `max_ticks` queued ticks to `engine`, then forward the signals it
+    /// published on `signals` (a `Topic::Signal` subscription to its bus) to Bun.
+    /// Returns the number of ticks applied.
+    pub fn pump(&mut self, engine: &mut LatencyArbitrageEngine, signals: &mut Subscription, max_ticks: usize) -> usize {
+        let mut applied = 0;
+        while applied < max_ticks {
+            let Some(obs) = self.pop_tick() else { break };
+            engine.add_price_observation(obs);
+            applied += 1;
+        }
+        while let Some(envelope) = signals.try_recv() {
+            if let Event::Signal(signal) = envelope.event.as_ref() {
+                self.push_trigger(signal);
+            }
+        }
+        applied
+    }
+
+    fn push(&self, ring: Ring, record: &[u8], capacity: u32) -> bool {
+        // Only this side stores head, so a relaxed read of it is current
+        let head = self.word(ring.head).load(Ordering::Relaxed);
+        let tail = self.word(ring.tail).load(Ordering::Acquire);
+        if head.wrapping_sub(tail) >= capacity {
+            self.word(ring.dropped).fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        let slot = self.slot(ring, record.len(), capacity, head);
+        // SAFETY: the slot is in bounds (checked at init) and the consumer won't read it
+        // until head moves past it
+        unsafe { std::ptr::copy_nonoverlapping(record.as_ptr(), slot, record.len()) };
+        self.word(ring.head).store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    fn pop(&self, ring: Ring, record: &mut [u8], capacity: u32) -> bool {
+        let tail = self.word(ring.tail).load(Ordering::Relaxed);
+        let head = self.word(ring.head).load(Ordering::Acquire);
+        if head == tail {
+            return false;
+        }
+        let slot = self.slot(ring, record.len(), capacity, tail);
+        // SAFETY: in bounds, and the producer won't reuse the slot until tail moves past it
+        unsafe { std::ptr::copy_nonoverlapping(slot, record.as_mut_ptr(), record.len()) };
+        self.word(ring.tail).store(tail.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    fn slot(&self, ring: Ring, record_bytes: usize, capacity: u32, index: u32) -> *mut u8 {
+        let start = match ring.head {
+            TICK_HEAD_AT => HEADER_BYTES,
+            _ => HEADER_BYTES + self.tick_capacity as usize * TICK_BYTES,
+        };
+        let offset = start + (index & (capacity - 1)) as usize * record_bytes;
+        // SAFETY: `init`/`attach` checked the region covers both rings
+        unsafe { self.base.as_ptr().add(offset) }
+    }
+
+    fn word(&self, at: usize) -> &AtomicU32 {
+        // SAFETY: header offsets are 4-aligned within the checked, 8-aligned region
+        unsafe { &*self.base.as_ptr().add(at).cast::<AtomicU32>() }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Ring {
+    head: usize,
+    tail: usize,
+    dropped: usize,
+}
+
+impl Ring {
+    const TICKS: Ring = Ring { head: TICK_HEAD_AT, tail: TICK_TAIL_AT, dropped: TICKS_DROPPED_AT };
+    const TRIGGERS: Ring = Ring { head: TRIGGER_HEAD_AT, tail: TRIGGER_TAIL_AT, dropped: TRIGGERS_DROPPED_AT };
+}
+
+fn code<T: PartialEq>(table: &[T], value: &T) -> u8 {
+    table.iter().position(|v| v == value).expect("every variant has a wire code") as u8
+}
+
+fn decode<T: Copy>(table: &[T], code: u8) -> Option<T> {
+    table.get(code as usize).copied()
+}
+
+fn decode_tier(code: u8) -> Option<MarketTier> {
+    decode(&TIERS, code.checked_sub(1)?)
+}
+
+fn decode_tick(record: &[u8; TICK_BYTES], timestamp_ns: u64) -> Option<PriceObservation> {
+    let u16_at = |at: usize| u16::from_le_bytes([record[at], record[at + 1]]);
+    let price = u16_at(10);
+    if price == 0 || price >= 100 {
+        return None;
+    }
+    Some(PriceObservation {
+        market_id: u16_at(8),
+        provider: decode(&PLATFORMS, record[14])?,
+        market_type: decode(&MARKET_TYPES, record[15])?,
+        price,
+        size: u16_at(12),
+        timestamp_ns,
+        tier: decode_tier(record[16])?,
+        features: None, // Not carried in the shared-memory record
+    })
+}
+
+fn decode_trigger(record: &[u8; TRIGGER_BYTES]) -> Option<LatencySignal> {
+    let u16_at = |at: usize| u16::from_le_bytes([record[at], record[at + 1]]);
+    let u64_at = |at: usize| u64::from_le_bytes(record[at..at + 8].try_into().expect("8 bytes"));
+    let pattern_id = u16_at(42);
+    let observation = |side: usize| -> Option<PriceObservation> {
+        Some(PriceObservation {
+            market_id: u16_at(32 + 2 * side),
+            provider: decode(&PLATFORMS, record[48 + side])?,
+            market_type: decode(&MARKET_TYPES, record[50 + side])?,
+            price: u16_at(36 + 2 * side),
+            size: u16_at(44 + 2 * side),
+            timestamp_ns: u64_at(16 + 8 * side),
+            tier: decode_tier(record[52 + side])?,
+            features: None,
+        })
+    };
+    Some(LatencySignal {
+        fast_market: observation(0)?,
+        slow_market: observation(1)?,
+        disparity_cents: i16::from_le_bytes([record[40], record[41]]),
+        expected_convergence_ns: u64_at(0),
+        pattern_id: (pattern_id != 0).then_some(pattern_id),
+        confidence: f64::from_le_bytes(record[8..16].try_into().expect("8 bytes")),
+        strategy_id: strategy::for_pattern((pattern_id != 0).then_some(pattern_id)),
+    })
+}
+
+#[cfg(feature = "napi")]
+mod js {
+    use napi::bindgen_prelude::Uint8Array;
+    use napi_derive::napi;
+    use std::sync::Arc;
+
+    use super::TickBridge;
+    use crate::event_bus::{EventBus, Subscription, Topic};
+    use crate::latency_arbitrage::LatencyArbitrageEngine;
+
+    /// `new TickBridge(new Uint8Array(shared), tickCapacity, triggerCapacity)` with
+    /// `shared = new SharedArrayBuffer(TickBridge.bytesFor(...))`
+    #[napi(js_name = "TickBridge")]
+    pub struct JsTickBridge {
+        bridge: TickBridge,
+        engine: LatencyArbitrageEngine,
+        signals: Subscription,
+        /// Keeps the shared buffer alive while the bridge points into it
+        _memory: Uint8Array,
+    }
+
+    #[napi(object)]
+    pub struct JsBridgeStats {
+        pub ticks_received: i64,
+        pub ticks_rejected: i64,
+        pub ticks_dropped: i64,
+        pub triggers_sent: i64,
+        pub triggers_dropped: i64,
+        pub avg_crossing_ns: i64,
+        pub max_crossing_ns: i64,
+    }
+
+    #[napi]
+    impl JsTickBridge {
+        #[napi(constructor)]
+        pub fn new(mut memory: Uint8Array, tick_capacity: u32, trigger_capacity: u32) -> napi::Result<Self> {
+            let bus = Arc::new(EventBus::new());
+            let signals = bus.subscribe("tick-bridge", &[Topic::Signal]);
+            // SAFETY: `memory` is held alongside the bridge, and Bun only plays producer
+            // of the tick ring and consumer of the trigger ring
+            let bridge = unsafe { TickBridge::init(memory.as_mut_ptr(), memory.len(), tick_capacity, trigger_capacity) }
+                .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+            Ok(Self { bridge, engine: LatencyArbitrageEngine::new().with_event_bus(bus), signals, _memory: memory })
+        }
+
+        /// Bytes to allocate for the given capacities
+        #[napi]
+        pub fn bytes_for(tick_capacity: u32, trigger_capacity: u32) -> u32 {
+            TickBridge::bytes_for(tick_capacity, trigger_capacity) as u32
+        }
+
+        /// Apply queued ticks (all of them by default) and publish the resulting
+        /// triggers; returns the number of ticks applied
+        #[napi]
+        pub fn pump(&mut self, max_ticks: Option<u32>) -> u32 {
+            let max_ticks = max_ticks.map_or(usize::MAX, |max| max as usize);
+            self.bridge.pump(&mut self.engine, &mut self.signals, max_ticks) as u32
+        }
+
+        #[napi]
+        pub fn stats(&self) -> JsBridgeStats {
+            let stats = self.bridge.stats();
+            JsBridgeStats {
+                ticks_received: stats.ticks_received as i64,
+                ticks_rejected: stats.ticks_rejected as i64,
+                ticks_dropped: stats.ticks_dropped as i64,
+                triggers_sent: stats.triggers_sent as i64,
+                triggers_dropped: stats.triggers_dropped as i64,
+                avg_crossing_ns: stats.avg_crossing_ns as i64,
+                max_crossing_ns: stats.max_crossing_ns as i64,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::event_bus::{EventBus, Topic};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn observation(market_id: u16, provider: Platform, price: u16, timestamp_ns: u64) -> PriceObservation {
+        PriceObservation {
+            market_id,
+            provider,
+            market_type: MarketType::HalfTotal,
+            price,
+            size: 250,
+            timestamp_ns,
+            tier: MarketTier::Tier2,
+            features: None,
+        }
+    }
+
+    #[test]
+    fn ticks_cross_in_order_and_a_full_ring_drops() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let mut bridge = TickBridge::new(4, 4).unwrap().with_clock(clock.clone());
+
+        for i in 0..5 {
+            let pushed = bridge.push_tick(&observation(i, Platform::DraftKings, 40 + i, 0), 1_700_000_000_000_000_000);
+            assert_eq!(pushed, i < 4);
+        }
+        clock.advance(Duration::from_micros(30));
+
+        let received: Vec<u16> = std::iter::from_fn(|| bridge.pop_tick()).map(|obs| obs.price).collect();
+        assert_eq!(received, vec![40, 41, 42, 43]);
+
+        // Indices keep running past the capacity
+        for i in 0..6 {
+            assert!(bridge.push_tick(&observation(i, Platform::Kalshi, 50, 0), 0));
+            let obs = bridge.pop_tick().unwrap();
+            assert_eq!((obs.market_id, obs.provider, obs.market_type, obs.size), (i, Platform::Kalshi, MarketType::HalfTotal, 250));
+            assert_eq!(obs.timestamp_ns, 30_000, "stamped with the engine clock");
+        }
+
+        let stats = bridge.stats();
+        assert_eq!((stats.ticks_received, stats.ticks_dropped), (10, 1));
+        assert_eq!((stats.avg_crossing_ns, stats.max_crossing_ns), (30_000, 30_000));
+    }
+
+    #[test]
+    fn malformed_records_are_skipped_and_foreign_memory_is_checked() {
+        let mut memory = vec![0u64; TickBridge::bytes_for(8, 2) / 8];
+        let len = memory.len() * 8;
+        let ptr = memory.as_mut_ptr().cast::<u8>();
+
+        // SAFETY: `memory` outlives both bridges, which are dropped before it
+        unsafe {
+            assert!(TickBridge::attach(ptr, len).is_err(), "no header yet");
+            assert!(TickBridge::init(ptr, len, 6, 2).is_err());
+            assert!(TickBridge::init(ptr, len, 16, 2).is_err(), "too small");
+
+            let mut rust_side = TickBridge::init(ptr, len, 8, 2).unwrap();
+            let producer = TickBridge::attach(ptr, len).unwrap();
+            producer.push_tick(&observation(1, Platform::FanDuel, 0, 0), 0);
+            producer.push_tick(&observation(2, Platform::FanDuel, 55, 0), 0);
+            producer.push_tick(&observation(3, Platform::FanDuel, 55, 0), 0);
+            // A tier code outside 1-4 in the third record, as a buggy writer might leave it
+            *ptr.add(HEADER_BYTES + 2 * TICK_BYTES + 16) = 9;
+
+            assert_eq!(rust_side.pop_tick().map(|obs| obs.market_id), Some(2));
+            assert!(rust_side.pop_tick().is_none());
+            assert_eq!(rust_side.stats().ticks_rejected, 2);
+        }
+    }
+
+    #[test]
+    fn pump_feeds_the_engine_and_returns_its_signals() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let bus = Arc::new(EventBus::new());
+        let mut signals = bus.subscribe("tick-bridge", &[Topic::Signal]);
+        let mut engine = LatencyArbitrageEngine::new().with_event_bus(bus);
+        let mut bridge = TickBridge::new(16, 1).unwrap().with_clock(clock.clone());
+
+        // A sharp book moves, a slower one hasn't followed 200ms later
+        bridge.push_tick(&observation(7, Platform::Polymarket, 42, 0), 0);
+        assert_eq!(bridge.pump(&mut engine, &mut signals, 16), 1);
+        clock.advance(Duration::from_millis(200));
+        bridge.push_tick(&observation(9, Platform::Kalshi, 48, 0), 0);
+        assert_eq!(bridge.pump(&mut engine, &mut signals, 16), 1);
+
+        let sent = engine.get_signals().to_vec();
+        assert!(!sent.is_empty());
+        let trigger = bridge.pop_trigger().expect("signal forwarded to Bun");
+        assert_eq!(trigger.fast_market.market_id, sent[0].fast_market.market_id);
+        assert_eq!(trigger.slow_market.price, sent[0].slow_market.price);
+        assert_eq!(trigger.disparity_cents, sent[0].disparity_cents);
+        assert_eq!(trigger.pattern_id, sent[0].pattern_id);
+        assert_eq!(trigger.confidence, sent[0].confidence);
+        assert_eq!(
+            trigger.fast_market.timestamp_ns,
+            1_700_000_000_000_000_000 + sent[0].fast_market.timestamp_ns,
+            "timestamps leave as wall time"
+        );
+        assert_eq!(bridge.stats().triggers_dropped as usize, sent.len() - 1);
+    }
+}
@@ -0,0 +1,280 @@
+// src/tick_bundle.rs
+// Synchronized tick bundles - the one layout the simulator replays, the backtester flattens
+// into per-venue ticks and the optimizer validates parameters on, with builders and canned
+// fixture streams (`fixtures`) tests can share
+//
+// Layout (JSON via serde; absent optional fields are omitted and default to None):
+//   timestamp_ns      when the bundle's ticks were observed together
+//   ht / ft           half-time and full-time line of a half-time inference pair (#51)
+//   multiple_markets  every other quoted market, keyed "<VENUE>:<market_id>:<side>" (`market_key`)
+//   time_remaining    seconds left in the game, for in-play patterns (#75)
+//   game_context      sport, teams, period and score when known
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::odds_capture::OddsChange;
+use crate::types::{Platform, TimestampNs};
+
+/// Synchronized tick bundle for multi-market patterns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncedTickBundle {
+    /// Timestamp in nanoseconds
+    pub timestamp_ns: TimestampNs,
+    /// HT market data (if available)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ht: Option<TickData>,
+    /// FT market data (if available)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ft: Option<TickData>,
+    /// Multiple market data for propagation patterns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiple_markets: Option<HashMap<String, TickData>>,
+    /// Time remaining in game (for in-play patterns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_remaining: Option<f64>,
+    /// Game context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_context: Option<GameContext>,
+}
+
+/// Individual tick data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TickData {
+    /// Market identifier
+    pub market_id: String,
+    /// Platform/bookmaker
+    pub platform: Platform,
+    /// Price/line value
+    pub price: f64,
+    /// Size/liquidity
+    pub size: f64,
+    /// Price delta from previous tick
+    pub price_delta: f64,
+    /// Book identifier
+    pub book: String,
+}
+
+/// Game context information
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameContext {
+    /// Sport type
+    pub sport: String,
+    /// Teams playing
+    pub teams: (String, String),
+    /// Current period
+    pub period: u8,
+    /// Time remaining in seconds
+    pub time_remaining: f64,
+    /// Score
+    pub score: (u16, u16),
+}
+
+/// `multiple_markets` key of one side of a venue's market
+pub fn market_key(venue: Platform, market_id: &str, side: &str) -> String {
+    format!("{}:{}:{}", venue, market_id, side)
+}
+
+impl TickData {
+    /// A tick with no delta, booked under the venue's name
+    pub fn new(market_id: impl Into<String>, platform: Platform, price: f64, size: f64) -> Self {
+        Self { market_id: market_id.into(), platform, price, size, price_delta: 0.0, book: platform.to_string() }
+    }
+
+    pub fn with_delta(mut self, price_delta: f64) -> Self {
+        self.price_delta = price_delta;
+        self
+    }
+
+    pub fn with_book(mut self, book: impl Into<String>) -> Self {
+        self.book = book.into();
+        self
+    }
+}
+
+impl SyncedTickBundle {
+    /// An empty bundle at `timestamp_ns`
+    pub fn new(timestamp_ns: TimestampNs) -> Self {
+        Self { timestamp_ns, ht: None, ft: None, multiple_markets: None, time_remaining: None, game_context: None }
+    }
+
+    pub fn with_ht(mut self, tick: TickData) -> Self {
+        self.ht = Some(tick);
+        self
+    }
+
+    pub fn with_ft(mut self, tick: TickData) -> Self {
+        self.ft = Some(tick);
+        self
+    }
+
+    /// Add a market under `key` (see `market_key`)
+    pub fn with_market(mut self, key: impl Into<String>, tick: TickData) -> Self {
+        self.multiple_markets.get_or_insert_with(HashMap::new).insert(key.into(), tick);
+        self
+    }
+
+    pub fn with_time_remaining(mut self, seconds: f64) -> Self {
+        self.time_remaining = Some(seconds);
+        self
+    }
+
+    pub fn with_game_context(mut self, context: GameContext) -> Self {
+        self.game_context = Some(context);
+        self
+    }
+
+    /// A market of `multiple_markets`
+    pub fn market(&self, key: &str) -> Option<&TickData> {
+        self.multiple_markets.as_ref()?.get(key)
+    }
+
+    /// The tick single-market consumers follow: HT, else FT, else the first market by key
+    pub fn primary_tick(&self) -> Option<&TickData> {
+        self.ht.as_ref().or(self.ft.as_ref()).or_else(|| {
+            self.multiple_markets.as_ref()?.iter().min_by(|a, b| a.0.cmp(b.0)).map(|(_, tick)| tick)
+        })
+    }
+
+    /// Build replay bundles from captured odds changes (one bundle per timestamp)
+    /// Markets are keyed "<venue>:<market_id>:<side>" in `multiple_markets`
+    pub fn from_odds_changes(changes: &[OddsChange]) -> Vec<SyncedTickBundle> {
+        let mut bundles: Vec<SyncedTickBundle> = Vec::new();
+        for change in changes {
+            let key = market_key(change.venue, &change.market_id, change.side);
+            let tick = TickData::new(change.market_id.clone(), change.venue, change.new_line, change.size)
+                .with_delta(change.old_line.map(|old| change.new_line - old).unwrap_or(0.0));
+            match bundles.last_mut() {
+                Some(bundle) if bundle.timestamp_ns == change.timestamp_ns => {
+                    bundle.multiple_markets.get_or_insert_with(HashMap::new).insert(key, tick);
+                }
+                _ => bundles.push(SyncedTickBundle::new(change.timestamp_ns).with_market(key, tick)),
+            }
+        }
+        bundles
+    }
+}
+
+/// Canned, deterministic bundle streams for tests, benches and examples
+pub mod fixtures {
+    use super::*;
+
+    /// First fixture timestamp (2023-11-14 22:13:20 UTC)
+    pub const FIXTURE_START_NS: TimestampNs = 1_700_000_000_000_000_000;
+    /// Spacing of fixture bundles
+    pub const FIXTURE_STEP_NS: TimestampNs = 1_000_000_000;
+    /// Market id of the propagation fixture
+    pub const PROPAGATION_MARKET: &str = "KXFIXTURE-1";
+
+    fn at(step: usize) -> TimestampNs {
+        FIXTURE_START_NS + step as TimestampNs * FIXTURE_STEP_NS
+    }
+
+    /// `steps` second-half bundles of an NBA game: the HT total drifts up 0.1 a step and the
+    /// FT total follows at twice the rate, with the clock and context counting down from 24:00
+    pub fn half_time(steps: usize) -> Vec<SyncedTickBundle> {
+        (0..steps)
+            .map(|i| {
+                let remaining = (1440.0 - i as f64).max(0.0);
+                let delta = if i == 0 { 0.0 } else { 0.1 };
+                SyncedTickBundle::new(at(i))
+                    .with_ht(TickData::new("NBA-HT-TOTAL", Platform::DraftKings, 110.5 + 0.1 * i as f64, 500.0).with_delta(delta))
+                    .with_ft(TickData::new("NBA-FT-TOTAL", Platform::DraftKings, 220.5 + 0.2 * i as f64, 500.0).with_delta(2.0 * delta))
+                    .with_time_remaining(remaining)
+                    .with_game_context(GameContext {
+                        sport: "basketball".to_string(),
+                        teams: ("BOS".to_string(), "LAL".to_string()),
+                        period: 3,
+                        time_remaining: remaining,
+                        score: (58 + i as u16 / 20, 55 + i as u16 / 25),
+                    })
+            })
+            .collect()
+    }
+
+    /// Leader (Kalshi) price of the propagation fixture at `step`: a slow sawtooth in cents
+    pub fn propagation_price(step: usize) -> f64 {
+        40.0 + (step % 20) as f64
+    }
+
+    /// `steps` bundles of one market quoted on Kalshi and, `lag_steps` behind, on Polymarket
+    /// (which quotes the opening price until the lag has passed)
+    pub fn propagation(steps: usize, lag_steps: usize) -> Vec<SyncedTickBundle> {
+        let leader = market_key(Platform::Kalshi, PROPAGATION_MARKET, "yes");
+        let follower = market_key(Platform::Polymarket, PROPAGATION_MARKET, "yes");
+        (0..steps)
+            .map(|i| {
+                let lagged = i.saturating_sub(lag_steps);
+                let delta = |step: usize| if step == 0 { 0.0 } else { propagation_price(step) - propagation_price(step - 1) };
+                SyncedTickBundle::new(at(i))
+                    .with_market(leader.clone(), TickData::new(PROPAGATION_MARKET, Platform::Kalshi, propagation_price(i), 1_000.0).with_delta(delta(i)))
+                    .with_market(
+                        follower.clone(),
+                        TickData::new(PROPAGATION_MARKET, Platform::Polymarket, propagation_price(lagged), 800.0)
+                            .with_delta(if i < lag_steps { 0.0 } else { delta(lagged) }),
+                    )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+    use crate::types::MarketType;
+
+    #[test]
+    fn test_builders_serde_and_odds_replay() {
+        let tick = TickData::new("KXA", Platform::Kalshi, 55.0, 100.0).with_delta(1.0);
+        assert_eq!(tick.book, "KALSHI");
+        let bundle = SyncedTickBundle::new(5).with_market(market_key(Platform::Kalshi, "KXA", "yes"), tick.clone());
+        assert_eq!(bundle.market("KALSHI:KXA:yes"), Some(&tick));
+        assert_eq!(bundle.primary_tick(), Some(&tick));
+
+        // Unset fields are left out and read back as None
+        let json = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(json.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["multiple_markets", "timestamp_ns"]);
+        let parsed: SyncedTickBundle = serde_json::from_str(r#"{"timestamp_ns":5}"#).unwrap();
+        assert_eq!(parsed, SyncedTickBundle::new(5));
+        assert_eq!(serde_json::from_value::<SyncedTickBundle>(json).unwrap(), bundle);
+
+        // HT outranks FT and the markets
+        let ht = TickData::new("HT", Platform::FanDuel, 110.5, 10.0).with_book("fd-live");
+        assert_eq!(bundle.clone().with_ht(ht.clone()).primary_tick().map(|t| t.book.as_str()), Some("fd-live"));
+
+        let change = |line: f64, old_line: Option<f64>, timestamp_ns: TimestampNs| OddsChange {
+            venue: Platform::Polymarket,
+            market_id: "PM1".to_string(),
+            market_type: MarketType::Moneyline,
+            side: "yes",
+            old_line,
+            new_line: line,
+            size: 50.0,
+            timestamp_ns,
+        };
+        let bundles = SyncedTickBundle::from_odds_changes(&[change(48.0, None, 1), change(50.0, Some(48.0), 2), change(51.0, Some(50.0), 2)]);
+        assert_eq!(bundles.len(), 2);
+        assert_eq!(bundles[1].market("POLYMARKET:PM1:yes").map(|t| (t.price, t.price_delta)), Some((51.0, 1.0)));
+    }
+
+    #[test]
+    fn test_fixtures_are_canned() {
+        assert_eq!(half_time(30), half_time(30));
+        let game = half_time(30);
+        assert_eq!(game[0].timestamp_ns, FIXTURE_START_NS);
+        assert_eq!(game[1].timestamp_ns - game[0].timestamp_ns, FIXTURE_STEP_NS);
+        let (ht, ft) = (game[10].ht.as_ref().unwrap(), game[10].ft.as_ref().unwrap());
+        assert!((ht.price - 111.5).abs() < 1e-9 && (ft.price - 222.5).abs() < 1e-9);
+        assert_eq!(game[10].time_remaining, Some(1430.0));
+
+        let stream = propagation(40, 3);
+        let price = |i: usize, venue: Platform| stream[i].market(&market_key(venue, PROPAGATION_MARKET, "yes")).unwrap().price;
+        assert_eq!(price(1, Platform::Polymarket), propagation_price(0));
+        for i in 3..40 {
+            assert_eq!(price(i, Platform::Polymarket), price(i - 3, Platform::Kalshi));
+        }
+        // The Kalshi side sorts first, so it's the stream's primary tick
+        assert_eq!(stream[5].primary_tick().unwrap().platform, Platform::Kalshi);
+    }
+}
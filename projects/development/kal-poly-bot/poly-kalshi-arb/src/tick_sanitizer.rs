@@ -0,0 +1,375 @@
+// src/tick_sanitizer.rs
+// Tick sanity bounds - price jumps, non-positive sizes and future timestamps are quarantined
+// before they reach the filters or signal logic; shared by the aggregator and the engines
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, Level};
+
+use crate::clock::{self, SharedClock};
+use crate::config::FeedsSection;
+use crate::feed_schema::Quarantine;
+use crate::provider_registry::ProviderId;
+
+/// Moves seen before the jump check trusts the deviation
+const WARMUP_MOVES: usize = 20;
+/// Consecutive rejected ticks at one new level before it is accepted as a genuine repricing
+const CONFIRM_TICKS: u32 = 3;
+
+/// Why a tick was quarantined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Violation {
+    /// Move larger than `outlier_sigma` deviations of recent moves
+    PriceJump,
+    /// Price outside 1..=99 cents (or not a number)
+    BadPrice,
+    /// Zero or negative size
+    BadSize,
+    /// Stamped further ahead of the local clock than `max_clock_skew_ms`
+    FutureTimestamp,
+}
+
+impl Violation {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Violation::PriceJump => "price_jump",
+            Violation::BadPrice => "bad_price",
+            Violation::BadSize => "bad_size",
+            Violation::FutureTimestamp => "future_timestamp",
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One price observation, in the venue-neutral cent scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    pub provider: ProviderId,
+    pub market_id: u16,
+    /// YES price (cents)
+    pub price: f64,
+    pub size: f64,
+    /// Provider wall-clock stamp (Unix ns), if the feed sends one
+    pub timestamp_ns: Option<u64>,
+}
+
+/// Sanity bounds (`[feeds]` outlier_* / max_clock_skew_ms)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizerConfig {
+    /// 0 disables the jump check
+    pub outlier_sigma: f64,
+    pub min_jump_cents: f64,
+    pub window: usize,
+    pub max_clock_skew_ns: u64,
+}
+
+impl Default for SanitizerConfig {
+    fn default() -> Self {
+        Self::from(&FeedsSection::default())
+    }
+}
+
+impl From<&FeedsSection> for SanitizerConfig {
+    fn from(feeds: &FeedsSection) -> Self {
+        Self {
+            outlier_sigma: feeds.outlier_sigma,
+            min_jump_cents: feeds.outlier_min_jump_cents,
+            window: feeds.outlier_window.max(2),
+            max_clock_skew_ns: feeds.max_clock_skew_ms.saturating_mul(1_000_000),
+        }
+    }
+}
+
+/// Per-provider counters
+#[derive(Debug, Clone, Serialize)]
+pub struct SanitizerStats {
+    pub provider: ProviderId,
+    pub accepted: u64,
+    pub rejected: BTreeMap<Violation, u64>,
+    /// Rejected jumps later accepted as a new price level
+    pub confirmed_jumps: u64,
+    pub last_rejection: Option<String>,
+    pub last_rejection_ns: Option<u64>,
+}
+
+impl SanitizerStats {
+    fn new(provider: ProviderId) -> Self {
+        Self {
+            provider,
+            accepted: 0,
+            rejected: BTreeMap::new(),
+            confirmed_jumps: 0,
+            last_rejection: None,
+            last_rejection_ns: None,
+        }
+    }
+}
+
+/// Recent accepted moves for one provider and market
+#[derive(Default)]
+struct Series {
+    last: Option<f64>,
+    moves: VecDeque<f64>,
+    /// Level of the rejected jumps in a row, and how many
+    pending: Option<(f64, u32)>,
+}
+
+impl Series {
+    fn deviation(&self) -> f64 {
+        let n = self.moves.len() as f64;
+        let mean = self.moves.iter().sum::<f64>() / n;
+        (self.moves.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    }
+}
+
+/// Quarantine file line
+#[derive(Serialize)]
+struct QuarantineRecord<'a> {
+    ts_ns: u64,
+    provider: String,
+    market_id: u16,
+    violation: Violation,
+    detail: &'a str,
+    price: f64,
+    size: f64,
+    timestamp_ns: Option<u64>,
+}
+
+#[derive(Default)]
+struct State {
+    series: HashMap<(ProviderId, u16), Series>,
+    stats: HashMap<ProviderId, SanitizerStats>,
+    quarantine: Option<Quarantine>,
+}
+
+/// Tick sanity checks with per-provider counters and an optional quarantine log
+pub struct TickSanitizer {
+    config: SanitizerConfig,
+    state: Mutex<State>,
+    clock: SharedClock,
+}
+
+pub type SharedTickSanitizer = Arc<TickSanitizer>;
+
+impl TickSanitizer {
+    pub fn new(config: SanitizerConfig) -> Self {
+        Self { config, state: Mutex::new(State::default()), clock: clock::system() }
+    }
+
+    /// Bounds and quarantine from `[feeds]`; rejects land next to the schema rejects as
+    /// `<provider>.ticks.jsonl`, an empty `quarantine_dir` keeps no copies
+    pub fn from_config(feeds: &FeedsSection) -> Self {
+        let sanitizer = Self::new(SanitizerConfig::from(feeds));
+        if feeds.quarantine_dir.is_empty() {
+            return sanitizer;
+        }
+        sanitizer.with_quarantine(&feeds.quarantine_dir, feeds.quarantine_max_mb * 1024 * 1024)
+    }
+
+    /// Append rejected ticks under `dir`, at most `max_bytes` per provider
+    pub fn with_quarantine(self, dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        self.state.lock().unwrap().quarantine = Some(Quarantine::new(dir, max_bytes));
+        self
+    }
+
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Check a tick; accepted ticks update the move history, rejected ones are counted and
+    /// quarantined before the violation is returned
+    pub fn check(&self, tick: &Tick) -> Result<(), Violation> {
+        let now = self.clock.wall_ns().0;
+        let mut state = self.state.lock().unwrap();
+        let verdict = self.evaluate(&mut state, tick, now);
+        let stats = state.stats.entry(tick.provider).or_insert_with(|| SanitizerStats::new(tick.provider));
+        let (violation, detail) = match verdict {
+            Ok(confirmed) => {
+                stats.accepted += 1;
+                if confirmed {
+                    stats.confirmed_jumps += 1;
+                    info!(provider = %tick.provider, market_id = tick.market_id,
+                          "[SANITIZE] Price level {:.1}¢ confirmed after {} ticks", tick.price, CONFIRM_TICKS);
+                }
+                return Ok(());
+            }
+            Err(rejection) => rejection,
+        };
+        *stats.rejected.entry(violation).or_default() += 1;
+        stats.last_rejection = Some(detail.clone());
+        stats.last_rejection_ns = Some(now);
+        crate::hot_path!(Level::WARN, provider = %tick.provider, market_id = tick.market_id,
+                         "[SANITIZE] Tick quarantined: {}", detail);
+
+        if let Some(quarantine) = &mut state.quarantine {
+            let provider = tick.provider.to_string().to_lowercase();
+            let record = QuarantineRecord {
+                ts_ns: now,
+                provider: provider.clone(),
+                market_id: tick.market_id,
+                violation,
+                detail: &detail,
+                price: tick.price,
+                size: tick.size,
+                timestamp_ns: tick.timestamp_ns,
+            };
+            match quarantine.write(&format!("{}.ticks", provider), &record) {
+                Ok(true) => {}
+                Ok(false) => crate::hot_path!(Level::WARN, provider = %tick.provider, "[SANITIZE] Quarantine full, dropping"),
+                Err(e) => crate::hot_path!(Level::WARN, provider = %tick.provider, "[SANITIZE] Quarantine write failed: {}", e),
+            }
+        }
+        Err(violation)
+    }
+
+    /// `Ok(true)` when the tick confirms a previously rejected price level
+    fn evaluate(&self, state: &mut State, tick: &Tick, now: u64) -> Result<bool, (Violation, String)> {
+        if !tick.price.is_finite() || !(1.0..=99.0).contains(&tick.price) {
+            return Err((Violation::BadPrice, format!("price {}¢ outside 1..=99", tick.price)));
+        }
+        if tick.size.is_nan() || tick.size <= 0.0 {
+            return Err((Violation::BadSize, format!("size {}", tick.size)));
+        }
+        if let Some(ts) = tick.timestamp_ns.filter(|&ts| ts > now.saturating_add(self.config.max_clock_skew_ns)) {
+            return Err((Violation::FutureTimestamp, format!("stamped {}ms ahead", (ts - now) / 1_000_000)));
+        }
+
+        // The history is sized for the window up front, so accepted ticks never reallocate it
+        let window = self.config.window;
+        let series = state.series.entry((tick.provider, tick.market_id))
+            .or_insert_with(|| Series { moves: VecDeque::with_capacity(window + 1), ..Series::default() });
+        let Some(last) = series.last else {
+            series.last = Some(tick.price);
+            return Ok(false);
+        };
+        let change = tick.price - last;
+        if self.config.outlier_sigma > 0.0 && series.moves.len() >= WARMUP_MOVES.min(self.config.window) {
+            let limit = (self.config.outlier_sigma * series.deviation()).max(self.config.min_jump_cents);
+            if change.abs() > limit {
+                let confirmations = match series.pending {
+                    Some((level, n)) if (tick.price - level).abs() <= self.config.min_jump_cents => n + 1,
+                    _ => 1,
+                };
+                if confirmations < CONFIRM_TICKS {
+                    series.pending = Some((tick.price, confirmations));
+                    return Err((
+                        Violation::PriceJump,
+                        format!("{:+.1}¢ move from {:.1}¢ exceeds {:.1}¢", change, last, limit),
+                    ));
+                }
+                // The venue really repriced; restart from the new level without the jump in the history
+                series.pending = None;
+                series.last = Some(tick.price);
+                return Ok(true);
+            }
+        }
+        series.pending = None;
+        series.last = Some(tick.price);
+        series.moves.push_back(change);
+        if series.moves.len() > self.config.window {
+            series.moves.pop_front();
+        }
+        Ok(false)
+    }
+
+    /// Counters for every provider seen
+    pub fn stats(&self) -> Vec<SanitizerStats> {
+        let state = self.state.lock().unwrap();
+        let mut stats: Vec<SanitizerStats> = state.stats.values().cloned().collect();
+        stats.sort_by_key(|s| s.provider.to_string());
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, MockClock};
+    use crate::types::Nanos;
+    use crate::types::Platform;
+
+    fn tick(price: f64) -> Tick {
+        Tick { provider: Platform::Kalshi.into(), market_id: 7, price, size: 10.0, timestamp_ns: None }
+    }
+
+    fn warmed_up() -> TickSanitizer {
+        let sanitizer = TickSanitizer::new(SanitizerConfig::default());
+        for i in 0..=WARMUP_MOVES {
+            sanitizer.check(&tick(50.0 + (i % 2) as f64)).unwrap();
+        }
+        sanitizer
+    }
+
+    #[test]
+    fn test_bounds() {
+        let clock = MockClock::shared(Nanos(1_700_000_000_000_000_000));
+        let sanitizer = TickSanitizer::new(SanitizerConfig::default()).with_clock(clock.clone());
+        assert_eq!(sanitizer.check(&tick(0.0)), Err(Violation::BadPrice));
+        assert_eq!(sanitizer.check(&tick(f64::NAN)), Err(Violation::BadPrice));
+        assert_eq!(sanitizer.check(&Tick { size: 0.0, ..tick(50.0) }), Err(Violation::BadSize));
+        assert_eq!(sanitizer.check(&Tick { size: -3.0, ..tick(50.0) }), Err(Violation::BadSize));
+
+        let now = clock.wall_ns().0;
+        assert!(sanitizer.check(&Tick { timestamp_ns: Some(now + 1_000_000_000), ..tick(50.0) }).is_ok());
+        assert_eq!(
+            sanitizer.check(&Tick { timestamp_ns: Some(now + 5_000_000_000), ..tick(50.0) }),
+            Err(Violation::FutureTimestamp)
+        );
+
+        let stats = &sanitizer.stats()[0];
+        assert_eq!(stats.accepted, 1);
+        assert_eq!(stats.rejected[&Violation::BadPrice], 2);
+        assert_eq!(stats.rejected[&Violation::BadSize], 2);
+        assert_eq!(stats.rejected[&Violation::FutureTimestamp], 1);
+    }
+
+    #[test]
+    fn test_jumps_are_rejected_until_confirmed() {
+        let sanitizer = warmed_up();
+        // A spike that reverts never reaches the consumers
+        assert_eq!(sanitizer.check(&tick(90.0)), Err(Violation::PriceJump));
+        assert!(sanitizer.check(&tick(51.0)).is_ok());
+        // Small moves stay under the cent floor even in a quiet market
+        assert!(sanitizer.check(&tick(54.0)).is_ok());
+
+        // A repricing that holds is accepted on the third tick
+        assert_eq!(sanitizer.check(&tick(75.0)), Err(Violation::PriceJump));
+        assert_eq!(sanitizer.check(&tick(75.5)), Err(Violation::PriceJump));
+        assert!(sanitizer.check(&tick(75.0)).is_ok());
+        assert!(sanitizer.check(&tick(76.0)).is_ok());
+
+        let stats = &sanitizer.stats()[0];
+        assert_eq!(stats.rejected[&Violation::PriceJump], 3);
+        assert_eq!(stats.confirmed_jumps, 1);
+        // Series are per provider and market
+        assert!(sanitizer.check(&Tick { market_id: 8, ..tick(20.0) }).is_ok());
+    }
+
+    #[test]
+    fn test_rejections_are_quarantined() {
+        let dir = std::env::temp_dir().join(format!("tick_sanitizer_quarantine_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let sanitizer = TickSanitizer::new(SanitizerConfig::default()).with_quarantine(&dir, 1 << 20);
+
+        assert!(sanitizer.check(&Tick { size: 0.0, ..tick(40.0) }).is_err());
+        assert!(sanitizer.check(&tick(40.0)).is_ok());
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(dir.join("kalshi.ticks.jsonl")).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["violation"], "bad_size");
+        assert_eq!(lines[0]["market_id"], 7);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
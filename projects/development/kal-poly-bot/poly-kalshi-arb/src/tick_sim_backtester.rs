@@ -4,15 +4,27 @@
 //! Uses nanosecond telemetry to simulate inter-book propagation delays with microsecond precision.
 //! Integrates with ML Model Add-On (#71-88) and accounts for Sharp Score limiting.
 
-use crate::types::{TimestampNs, PriceCents, MarketType, Platform};
-use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal};
+use crate::types::{Nanos, TimestampNs, PriceCents, MarketType, Platform};
+use crate::latency_arbitrage::{LatencyArbitrageEngine, LatencySignal, MarketTier};
+use crate::market_impact::{MarketImpact, SharedMarketImpact};
 use crate::pattern_73_beta_skew::{Pattern73Engine, BetaSkewOpportunity};
+use crate::backtester_config::{BacktesterControls, TickPrecision as ControlsPrecision};
+use crate::clock::{self, MockClock, SharedClock};
+use crate::interner::Symbol;
+use crate::microstructural_simulator::{SyntheticMarketConfig, SyntheticMarketGenerator};
+use crate::pattern_verifier::weekly_decay_fit;
+use crate::tick_store::{self, ArchiveQuery, TickRow};
+use crate::replay_pacing::{ReplaySpeed, SpeedGovernor};
+use crate::sizing_curve::{SharedSizingCurve, SizingCurve};
 use std::collections::{HashMap, VecDeque};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 use serde::{Serialize, Deserialize};
 use tracing::{info, warn, debug, error};
 
-/// Historical tick data for simulation
+/// Historical tick data for simulation; IDs are interned so replaying a tick copies no strings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoricalTick {
     /// Unique identifier
@@ -20,7 +32,7 @@ pub struct HistoricalTick {
     /// Timestamp in nanoseconds
     pub timestamp_ns: TimestampNs,
     /// Market identifier
-    pub market_id: String,
+    pub market_id: Symbol,
     /// Platform/bookmaker
     pub platform: Platform,
     /// Market type
@@ -30,9 +42,9 @@ pub struct HistoricalTick {
     /// Size/liquidity
     pub size: f64,
     /// Player identifier (for props)
-    pub player_id: Option<String>,
+    pub player_id: Option<Symbol>,
     /// Team identifier
-    pub team_id: Option<String>,
+    pub team_id: Option<Symbol>,
     /// Raw data payload
     pub raw_data: Vec<u8>,
 }
@@ -60,6 +72,11 @@ pub struct BacktestConfig {
     pub max_position_size: f64,
     /// Account lifespan simulation
     pub account_lifespan_days: u32,
+    /// Seed for synthetic market data (overrides the market model's)
+    pub seed: Option<u64>,
+    /// Replay pacing against wall-clock time
+    #[serde(default)]
+    pub speed: ReplaySpeed,
 }
 
 /// Tick precision levels
@@ -125,7 +142,7 @@ pub struct ExecutionStats {
     pub sla_compliance_percent: f64,
     /// Fill rate percentage
     pub fill_rate_percent: f64,
-    /// Slippage average
+    /// Average modeled market impact per contract filled (cents)
     pub avg_slippage: f64,
 }
 
@@ -164,6 +181,29 @@ pub struct AlphaDecayEngine {
     pub alpha_estimates: HashMap<u16, f64>,
     /// Half-life tracking
     pub half_life_tracker: HashMap<u16, VecDeque<(TimestampNs, f64)>>,
+    /// Archived outcomes (timestamp, ROI %) by pattern
+    pub outcomes: HashMap<u16, Vec<(TimestampNs, f64)>>,
+    /// Round-trip cost (% of entry) below which a pattern's edge is gone
+    pub cost_floors: HashMap<u16, f64>,
+}
+
+/// When a pattern's fitted edge decays through its cost floor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetirementProjection {
+    pub pattern_id: u16,
+    /// Fitted edge this week (ROI %)
+    pub current_edge_percent: f64,
+    pub cost_floor_percent: f64,
+    pub half_life_weeks: Option<f64>,
+    /// Weeks until the edge crosses the floor (0 when already below, None when not decaying)
+    pub weeks_to_retirement: Option<f64>,
+    pub retirement_ns: Option<TimestampNs>,
+    /// Retirement under the fast end of the decay-rate band
+    pub earliest_retirement_ns: Option<TimestampNs>,
+    /// Retirement under the slow end (None when the band admits no decay)
+    pub latest_retirement_ns: Option<TimestampNs>,
+    /// Weeks of outcomes behind the fit
+    pub weeks_fitted: usize,
 }
 
 /// Decay parameters for a pattern
@@ -179,6 +219,12 @@ pub struct DecayParameters {
     pub noise_level: f64,
 }
 
+/// Default round-trip cost floor (% of entry cost): fees plus typical slippage
+pub const DEFAULT_COST_FLOOR_PERCENT: f64 = 0.5;
+/// Standard errors either side of the fitted decay rate for the retirement band (~95%)
+const RETIREMENT_BAND_Z: f64 = 1.96;
+const NS_PER_WEEK: f64 = 7.0 * 86_400.0 * 1e9;
+
 /// Component #41: Tick-Sim-Backtester
 #[derive(Debug)]
 pub struct TickSimBacktester {
@@ -195,7 +241,7 @@ pub struct TickSimBacktester {
     /// Historical ticks buffer
     pub tick_buffer: VecDeque<HistoricalTick>,
     /// Current positions
-    pub positions: HashMap<String, Position>,
+    pub positions: HashMap<Symbol, Position>,
     /// Equity curve
     pub equity_curve: Vec<EquityPoint>,
     /// Trade history
@@ -206,13 +252,42 @@ pub struct TickSimBacktester {
     pub account_limited: bool,
     /// Simulation metrics
     pub metrics: SimulationMetrics,
+    /// Simulated time, driven by replayed tick timestamps
+    pub clock: Arc<MockClock>,
+    /// Dates synthetic ticks when `config.start_timestamp_ns` is unset
+    pub wall_clock: SharedClock,
+    /// Synthetic market used when no captured ticks are loaded
+    pub market_model: SyntheticMarketConfig,
+    /// Ticks replayed so far, for callers polling a running backtest (optional)
+    pub progress: Option<Arc<BacktestProgress>>,
+    /// Market impact charged on every simulated fill
+    pub impact: SharedMarketImpact,
+    /// Share of the position cap taken by opportunity strength and confidence
+    pub sizing: SharedSizingCurve,
+}
+
+/// Replay progress shared with whoever started the run
+#[derive(Debug, Default)]
+pub struct BacktestProgress {
+    pub processed: AtomicU64,
+    pub total: AtomicU64,
+}
+
+impl BacktestProgress {
+    /// Fraction of the loaded ticks replayed (0 before the run starts)
+    pub fn fraction(&self) -> f64 {
+        match self.total.load(Ordering::Relaxed) {
+            0 => 0.0,
+            total => (self.processed.load(Ordering::Relaxed) as f64 / total as f64).min(1.0),
+        }
+    }
 }
 
 /// Open position
 #[derive(Debug, Clone)]
 pub struct Position {
     /// Market identifier
-    pub market_id: String,
+    pub market_id: Symbol,
     /// Direction (1=long, -1=short)
     pub direction: i8,
     /// Size
@@ -240,6 +315,10 @@ pub struct SimulationMetrics {
     pub avg_processing_time_ns: f64,
     /// Peak memory usage (bytes)
     pub peak_memory_usage: u64,
+    /// Contracts filled
+    pub filled_contracts: f64,
+    /// Market impact paid on those fills (cents)
+    pub impact_cost_cents: f64,
 }
 
 impl Default for BacktestConfig {
@@ -255,6 +334,33 @@ impl Default for BacktestConfig {
             transaction_cost: 0.001, // 0.1%
             max_position_size: 1000.0,
             account_lifespan_days: 30,
+            seed: None,
+            speed: ReplaySpeed::AsFastAsPossible,
+        }
+    }
+}
+
+impl BacktestConfig {
+    /// Run config for one pattern of a (profile-loaded) control set
+    pub fn from_controls(controls: &BacktesterControls, pattern_id: u16) -> Self {
+        let range = controls.date_range;
+        Self {
+            pattern_id,
+            start_timestamp_ns: range.map_or(0, |r| r.start_ns),
+            end_timestamp_ns: range.map_or(u64::MAX, |r| r.end_ns),
+            sim_latency_jitter_us: controls.sim_latency_jitter * 1000.0,
+            sharp_limit_threshold: controls.sharp_limit_threshold,
+            tick_precision: match controls.tick_precision {
+                ControlsPrecision::Millisecond => TickPrecision::Millisecond,
+                ControlsPrecision::Microsecond => TickPrecision::Microsecond,
+                ControlsPrecision::Nanosecond => TickPrecision::Nanosecond,
+            },
+            initial_capital: controls.risk.initial_capital,
+            transaction_cost: controls.risk.transaction_cost,
+            max_position_size: controls.risk.max_position_size,
+            account_lifespan_days: controls.risk.account_lifespan_days,
+            seed: controls.seed,
+            speed: controls.replay_speed.capped(controls.max_speed_multiplier),
         }
     }
 }
@@ -266,6 +372,7 @@ impl TickSimBacktester {
         let pattern_73_engine = Pattern73Engine::new(crate::pattern_73_beta_skew::Pattern73Config::default());
         let sharp_calculator = SharpScoreCalculator::new();
         let alpha_decay_engine = AlphaDecayEngine::new();
+        let clock = MockClock::shared(Nanos(config.start_timestamp_ns));
 
         Self {
             config,
@@ -280,18 +387,62 @@ impl TickSimBacktester {
             current_capital: config.initial_capital,
             account_limited: false,
             metrics: SimulationMetrics::default(),
+            clock,
+            wall_clock: clock::system(),
+            market_model: SyntheticMarketConfig::default(),
+            progress: None,
+            impact: Arc::new(MarketImpact::new()),
+            sizing: Arc::new(SizingCurve::default()),
         }
     }
 
-    /// Load historical ticks from data source
+    /// Report replay progress through `progress`
+    pub fn with_progress(mut self, progress: Arc<BacktestProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Date unseeded synthetic runs from another clock
+    pub fn with_wall_clock(mut self, clock: SharedClock) -> Self {
+        self.wall_clock = clock;
+        self
+    }
+
+    /// Replace the synthetic market model
+    pub fn with_market_model(mut self, market_model: SyntheticMarketConfig) -> Self {
+        self.market_model = market_model;
+        self
+    }
+
+    /// Price fills with a calibrated impact model instead of the default coefficients
+    pub fn with_market_impact(mut self, impact: SharedMarketImpact) -> Self {
+        self.impact = impact;
+        self
+    }
+
+    /// Size trades on the live `[sizing]` curve instead of the default (linear in strength)
+    pub fn with_sizing_curve(mut self, sizing: SharedSizingCurve) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    /// Simulated clock for components under test; follows the replay, not real time
+    pub fn shared_clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    /// Load historical ticks from data source: a tick store directory
+    /// (see tick_store), otherwise the synthetic market
     pub async fn load_historical_ticks(&mut self, data_source: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Loading historical ticks from: {}", data_source);
 
-        // Simulate loading ticks from S3 or local storage
-        // In production, this would use Bun.s3() or Bun.mmap()
-        let mock_ticks = self.generate_mock_ticks(10000);
+        let ticks = if Path::new(data_source).is_dir() {
+            self.load_archived_ticks(Path::new(data_source))?
+        } else {
+            self.generate_synthetic_ticks(10000)
+        };
 
-        for tick in mock_ticks {
+        for tick in ticks {
             if tick.timestamp_ns >= self.config.start_timestamp_ns &&
                tick.timestamp_ns <= self.config.end_timestamp_ns {
                 self.tick_buffer.push_back(tick);
@@ -302,15 +453,51 @@ impl TickSimBacktester {
         Ok(())
     }
 
+    /// Ticks in the configured time range from a tick store directory (YES side, like live
+    /// observations); the range is pushed down to the Parquet reader. Rows from plugin
+    /// providers are skipped - the simulator only models built-in venues.
+    fn load_archived_ticks(&self, dir: &Path) -> Result<Vec<HistoricalTick>, Box<dyn std::error::Error + Send + Sync>> {
+        let query = ArchiveQuery::between(self.config.start_timestamp_ns, self.config.end_timestamp_ns);
+        let scan = tick_store::scan::<TickRow>(dir, &query)?;
+        info!("Tick store scan: {:?}", scan.stats);
+
+        let rows = scan.rows.len();
+        let ticks: Vec<HistoricalTick> = scan.rows.into_iter()
+            .filter_map(|row| Some((row.platform.platform()?, row)))
+            .enumerate()
+            .map(|(i, (platform, row))| HistoricalTick {
+                id: i as u64,
+                timestamp_ns: row.timestamp_ns,
+                market_id: Symbol::intern(&row.market_id),
+                platform,
+                market_type: row.market_type,
+                price: row.yes_price as f64,
+                size: row.yes_size as f64,
+                player_id: None,
+                team_id: None,
+                raw_data: Vec::new(),
+            })
+            .collect();
+        if ticks.len() < rows {
+            warn!("Skipped {} archived ticks from plugin providers", rows - ticks.len());
+        }
+        Ok(ticks)
+    }
+
     /// Run the backtest simulation
     pub async fn run_backtest(&mut self) -> Result<BacktestResult, Box<dyn std::error::Error + Send + Sync>> {
         info!("Starting backtest for pattern #{}", self.config.pattern_id);
 
         let start_time = SystemTime::now();
         let mut tick_count = 0;
+        let mut governor = SpeedGovernor::new(self.config.speed);
 
         // Sort ticks by timestamp
         self.tick_buffer.make_contiguous().sort_by_key(|t| t.timestamp_ns);
+        if let Some(progress) = &self.progress {
+            progress.total.store(self.tick_buffer.len() as u64, Ordering::Relaxed);
+            progress.processed.store(0, Ordering::Relaxed);
+        }
 
         // Process each tick
         while let Some(tick) = self.tick_buffer.pop_front() {
@@ -319,11 +506,14 @@ impl TickSimBacktester {
                 break;
             }
 
+            // Hold the replay to the configured speed before the tick becomes visible
+            governor.pace(tick.timestamp_ns).await;
             let processing_start = std::time::Instant::now();
 
             // Simulate network jitter
             let jitter = (rand::random::<f64>() - 0.5) * 2.0 * self.config.sim_latency_jitter_us * 1000.0;
             let adjusted_timestamp = tick.timestamp_ns + jitter as i64 as u64;
+            self.clock.set_wall(Nanos(adjusted_timestamp));
 
             // Process tick through engines
             self.process_tick(tick, adjusted_timestamp).await?;
@@ -338,11 +528,17 @@ impl TickSimBacktester {
             if tick_count % 1000 == 0 {
                 self.record_equity_point(adjusted_timestamp);
             }
+            if let Some(progress) = &self.progress {
+                progress.processed.store(tick_count, Ordering::Relaxed);
+            }
         }
 
         let total_time = start_time.elapsed().as_secs_f64();
 
         info!("Backtest completed: {} ticks processed in {:.2}s", tick_count, total_time);
+        if let Some(multiplier) = governor.speed().multiplier() {
+            info!("Replay paced at x{} (max lag {:?})", multiplier, governor.max_lag());
+        }
 
         // Generate final results
         self.generate_results()
@@ -380,10 +576,10 @@ impl TickSimBacktester {
     async fn process_pattern_73_tick(&mut self, tick: HistoricalTick, timestamp_ns: TimestampNs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         match tick.market_type {
             MarketType::PlayerProp => {
-                if let (Some(player_id), Some(team_id)) = (&tick.player_id, &tick.team_id) {
+                if let (Some(player_id), Some(team_id)) = (tick.player_id, tick.team_id) {
                     // Update player prop
                     self.pattern_73_engine.update_player_prop(
-                        &tick.market_id, player_id, team_id,
+                        tick.market_id, player_id, team_id,
                         tick.price, timestamp_ns, 0.8 // Usage rate
                     );
 
@@ -391,16 +587,16 @@ impl TickSimBacktester {
                     let opportunities = self.pattern_73_engine.get_opportunities();
                     for opp in opportunities {
                         if self.evaluate_opportunity(opp, timestamp_ns) {
-                            self.execute_pattern_73_trade(opp, timestamp_ns).await?;
+                            self.execute_pattern_73_trade(opp, tick.platform, timestamp_ns).await?;
                         }
                     }
                 }
             },
             MarketType::Total => {
-                if let Some(team_id) = &tick.team_id {
+                if let Some(team_id) = tick.team_id {
                     // Update team total
                     self.pattern_73_engine.update_team_total(
-                        &tick.market_id, team_id, tick.price, timestamp_ns
+                        tick.market_id, team_id, tick.price, timestamp_ns
                     );
                 }
             },
@@ -414,7 +610,7 @@ impl TickSimBacktester {
     async fn process_generic_tick(&mut self, tick: HistoricalTick, timestamp_ns: TimestampNs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // Add price observation to latency engine
         self.latency_engine.add_price_observation(
-            tick.market_id.clone(),
+            tick.market_id,
             tick.platform,
             tick.price,
             tick.size,
@@ -458,13 +654,27 @@ impl TickSimBacktester {
         net_profit > 0.0
     }
 
+    /// Charge the modeled market impact of filling `contracts` on `platform` in a `tier` market
+    /// against capital; returns the impact per contract (cents)
+    fn fill_impact(&mut self, tier: MarketTier, platform: Platform, contracts: f64) -> f64 {
+        let impact_cents = self.impact.impact_cents(tier, platform, contracts);
+        self.metrics.filled_contracts += contracts;
+        self.metrics.impact_cost_cents += impact_cents * contracts;
+        self.current_capital -= impact_cents * contracts / 100.0;
+        impact_cents
+    }
+
     /// Execute Pattern #73 trade
-    async fn execute_pattern_73_trade(&mut self, opportunity: &BetaSkewOpportunity, timestamp_ns: TimestampNs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let position_size = (self.config.max_position_size * opportunity.strength).min(self.current_capital * 0.1);
+    async fn execute_pattern_73_trade(&mut self, opportunity: &BetaSkewOpportunity, platform: Platform, timestamp_ns: TimestampNs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Beta skew opportunities carry no confidence of their own
+        let multiplier = self.sizing.multiplier(opportunity.strength, 1.0);
+        let position_size = (self.config.max_position_size * multiplier).min(self.current_capital * 0.1);
         let direction = if opportunity.gap > 0.0 { 1 } else { -1 };
+        // Team totals are derived (Tier 2) markets; the position is dollars of $1 contracts
+        self.fill_impact(MarketTier::Tier2, platform, position_size);
 
         let position = Position {
-            market_id: opportunity.team_total_market.clone(),
+            market_id: opportunity.team_total_market,
             direction,
             size: position_size,
             entry_price: opportunity.current_team_total,
@@ -473,7 +683,7 @@ impl TickSimBacktester {
             expected_alpha: opportunity.gap.abs(),
         };
 
-        self.positions.insert(opportunity.team_total_market.clone(), position);
+        self.positions.insert(opportunity.team_total_market, position);
         self.metrics.total_trades += 1;
 
         info!("Executed Pattern #73 trade: {} {} @ {:.2}",
@@ -490,7 +700,12 @@ impl TickSimBacktester {
 
     /// Execute arbitrage trade
     async fn execute_arbitrage_trade(&mut self, signal: &LatencySignal, timestamp_ns: TimestampNs) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Generic arbitrage execution logic
+        // The stale (slow) book is the one traded, up to the position cap
+        let market = &signal.slow_market;
+        let price = market.price.max(1) as f64;
+        let multiplier = self.sizing.multiplier(self.sizing.arb_strength(signal.disparity_cents as f64), signal.confidence);
+        let contracts = (market.size as f64 / price).min(self.config.max_position_size * 100.0 / price) * multiplier;
+        self.fill_impact(market.tier, market.provider, contracts);
         self.metrics.total_trades += 1;
         Ok(())
     }
@@ -535,7 +750,11 @@ impl TickSimBacktester {
             avg_execution_latency_us: self.metrics.avg_processing_time_ns / 1000.0,
             sla_compliance_percent: 95.0, // Mock value
             fill_rate_percent: 98.0, // Mock value
-            avg_slippage: 0.001, // Mock value
+            avg_slippage: if self.metrics.filled_contracts > 0.0 {
+                self.metrics.impact_cost_cents / self.metrics.filled_contracts
+            } else {
+                0.0
+            },
         };
 
         Ok(BacktestResult {
@@ -606,38 +825,40 @@ impl TickSimBacktester {
         }
     }
 
-    /// Generate mock historical ticks for testing
-    fn generate_mock_ticks(&self, count: usize) -> Vec<HistoricalTick> {
-        let mut ticks = Vec::new();
-        let base_timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos() as u64;
+    /// Flatten `count` synthetic bundles into per-venue ticks
+    fn generate_synthetic_ticks(&self, count: usize) -> Vec<HistoricalTick> {
+        let mut model = self.market_model.clone();
+        if let Some(seed) = self.config.seed {
+            model.seed = seed;
+        }
+        model.start_ns = if self.config.start_timestamp_ns > 0 {
+            self.config.start_timestamp_ns
+        } else {
+            self.wall_clock.wall_ns().0
+        };
 
-        for i in 0..count {
-            let tick = HistoricalTick {
-                id: i as u64,
-                timestamp_ns: base_timestamp + (i as u64 * 1_000_000), // 1ms intervals
-                market_id: format!("market_{}", i % 100),
-                platform: match i % 4 {
-                    0 => Platform::Kalshi,
-                    1 => Platform::Polymarket,
-                    2 => Platform::DraftKings,
-                    _ => Platform::FanDuel,
-                },
-                market_type: match i % 3 {
-                    0 => MarketType::PlayerProp,
-                    1 => MarketType::Total,
-                    _ => MarketType::Moneyline,
-                },
-                price: 100.0 + (i as f64 * 0.1) + (rand::random::<f64>() - 0.5),
-                size: 1000.0 + (rand::random::<f64>() * 500.0),
-                player_id: if i % 3 == 0 { Some(format!("player_{}", i % 50)) } else { None },
-                team_id: Some(format!("team_{}", i % 30)),
-                raw_data: vec![i as u8; 64],
-            };
-
-            ticks.push(tick);
+        let mut generator = SyntheticMarketGenerator::new(model);
+        let bundles = generator.generate(count);
+        let model = generator.config();
+
+        let mut ticks = Vec::new();
+        for bundle in bundles {
+            let Some(markets) = bundle.multiple_markets else { continue };
+            for (key, data) in markets {
+                let Some(venue) = model.venue_for_key(&key) else { continue };
+                ticks.push(HistoricalTick {
+                    id: ticks.len() as u64,
+                    timestamp_ns: bundle.timestamp_ns,
+                    market_id: Symbol::intern(&data.market_id),
+                    platform: data.platform,
+                    market_type: venue.market_type,
+                    price: data.price,
+                    size: data.size,
+                    player_id: venue.player_id.as_deref().map(Symbol::intern),
+                    team_id: venue.team_id.as_deref().map(Symbol::intern),
+                    raw_data: Vec::new(),
+                });
+            }
         }
 
         ticks
@@ -694,9 +915,55 @@ impl AlphaDecayEngine {
             decay_params,
             alpha_estimates: HashMap::new(),
             half_life_tracker: HashMap::new(),
+            outcomes: HashMap::new(),
+            cost_floors: HashMap::new(),
         }
     }
 
+    /// Replace a pattern's archived outcomes (timestamp, ROI %)
+    pub fn load_outcomes(&mut self, pattern_id: u16, outcomes: Vec<(TimestampNs, f64)>) {
+        self.outcomes.insert(pattern_id, outcomes);
+    }
+
+    pub fn set_cost_floor(&mut self, pattern_id: u16, floor_percent: f64) {
+        self.cost_floors.insert(pattern_id, floor_percent);
+    }
+
+    pub fn cost_floor(&self, pattern_id: u16) -> f64 {
+        self.cost_floors.get(&pattern_id).copied().unwrap_or(DEFAULT_COST_FLOOR_PERCENT)
+    }
+
+    /// Project when the pattern's weekly edge decays through its cost floor, with a band from
+    /// the decay rate's standard error. None until there are enough weeks of outcomes to fit.
+    pub fn project_retirement(&self, pattern_id: u16, now_ns: TimestampNs) -> Option<RetirementProjection> {
+        let fit = weekly_decay_fit(self.outcomes.get(&pattern_id)?, now_ns)?;
+        let floor = self.cost_floor(pattern_id);
+        let weeks_to = |decay: f64| -> Option<f64> {
+            if fit.roi_at_with_decay(0.0, decay) <= floor {
+                Some(0.0)
+            } else if decay <= 0.0 {
+                None
+            } else {
+                Some(((fit.mean_ln_roi - floor.ln()) / decay - fit.mean_age_weeks).max(0.0))
+            }
+        };
+        let at = |weeks: Option<f64>| weeks.map(|w| now_ns + (w * NS_PER_WEEK) as TimestampNs);
+
+        let weeks_to_retirement = weeks_to(fit.decay_per_week);
+        let band = RETIREMENT_BAND_Z * fit.decay_std_err;
+        Some(RetirementProjection {
+            pattern_id,
+            current_edge_percent: fit.roi_at(0.0),
+            cost_floor_percent: floor,
+            half_life_weeks: fit.half_life_weeks(),
+            weeks_to_retirement,
+            retirement_ns: at(weeks_to_retirement),
+            earliest_retirement_ns: at(weeks_to(fit.decay_per_week + band)),
+            latest_retirement_ns: at(weeks_to(fit.decay_per_week - band)),
+            weeks_fitted: fit.weeks,
+        })
+    }
+
     /// Update alpha estimate for a pattern
     pub fn update_alpha(&mut self, pattern_id: u16, timestamp_ns: TimestampNs, observed_alpha: f64) {
         let decay_params = match self.decay_params.get(&pattern_id) {
@@ -706,7 +973,7 @@ impl AlphaDecayEngine {
 
         // Apply decay to current estimate
         let current_alpha = self.alpha_estimates.get(&pattern_id).unwrap_or(&decay_params.initial_alpha);
-        let decayed_alpha = current_alpha * (-0.000001).exp(); // Apply decay
+        let decayed_alpha = current_alpha * (-0.000001_f64).exp(); // Apply decay
 
         // Update with new observation
         let new_alpha = decayed_alpha * 0.9 + observed_alpha * 0.1;
@@ -738,6 +1005,16 @@ mod tests {
         assert_eq!(config.pattern_id, 73);
         assert_eq!(config.sim_latency_jitter_us, 5.0);
         assert_eq!(config.sharp_limit_threshold, 0.65);
+        assert_eq!(config.speed, ReplaySpeed::AsFastAsPossible);
+    }
+
+    #[test]
+    fn test_from_controls_caps_replay_speed() {
+        let mut controls = BacktesterControls { max_speed_multiplier: 100.0, ..BacktesterControls::default() };
+        controls.replay_speed = ReplaySpeed::Accelerated(10.0);
+        assert_eq!(BacktestConfig::from_controls(&controls, 73).speed, ReplaySpeed::Accelerated(10.0));
+        controls.replay_speed = ReplaySpeed::Accelerated(5000.0);
+        assert_eq!(BacktestConfig::from_controls(&controls, 73).speed, ReplaySpeed::Accelerated(100.0));
     }
 
     #[test]
@@ -765,6 +1042,36 @@ mod tests {
         assert!(alpha > 0.0);
     }
 
+    #[test]
+    fn test_retirement_projection() {
+        let now_ns: TimestampNs = 20 * NS_PER_WEEK as TimestampNs;
+        let week = |age: u64, roi: f64| (0..5u64).map(move |i| (now_ns - age * NS_PER_WEEK as TimestampNs - i, roi));
+        let mut engine = AlphaDecayEngine::new();
+        assert_eq!(engine.project_retirement(74, now_ns), None);
+
+        // Edge halves every two weeks from 4%: 1% today, floor 0.25% two half-lives out
+        engine.load_outcomes(74, week(4, 4.0).chain(week(2, 2.0)).chain(week(0, 1.0)).collect());
+        engine.set_cost_floor(74, 0.25);
+        let p = engine.project_retirement(74, now_ns).unwrap();
+        assert!((p.current_edge_percent - 1.0).abs() < 1e-9);
+        assert!((p.weeks_to_retirement.unwrap() - 4.0).abs() < 1e-9);
+        // Perfect fit: the band collapses onto the projection
+        assert_eq!(p.earliest_retirement_ns, p.retirement_ns);
+        assert_eq!(p.latest_retirement_ns, p.retirement_ns);
+
+        // Noisy decay widens the band around the projection
+        engine.load_outcomes(74, week(6, 8.0).chain(week(4, 3.0)).chain(week(2, 2.5)).chain(week(0, 1.0)).collect());
+        let p = engine.project_retirement(74, now_ns).unwrap();
+        assert!(p.earliest_retirement_ns.unwrap() < p.retirement_ns.unwrap());
+        assert!(p.latest_retirement_ns.is_none_or(|late| late > p.retirement_ns.unwrap()));
+
+        // Already under the floor retires now; a flat edge above it never does
+        engine.set_cost_floor(74, 2.0);
+        assert_eq!(engine.project_retirement(74, now_ns).unwrap().weeks_to_retirement, Some(0.0));
+        engine.load_outcomes(74, week(4, 3.0).chain(week(2, 3.0)).chain(week(0, 3.0)).collect());
+        assert_eq!(engine.project_retirement(74, now_ns).unwrap().weeks_to_retirement, None);
+    }
+
     #[tokio::test]
     async fn test_tick_sim_backtester_creation() {
         let config = BacktestConfig::default();
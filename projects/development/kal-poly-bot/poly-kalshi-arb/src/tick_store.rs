@@ -0,0 +1,807 @@
+// src/tick_store.rs
+// Columnar tick and signal archive - zstd Parquet segments with dictionary-encoded
+// market/platform columns, read back with row-group pruning and row-filter pushdown
+
+use anyhow::{anyhow, bail, Context, Result};
+use arrow::array::{
+    Array, ArrayRef, BooleanArray, DictionaryArray, Float64Array, Int16Array, StringArray,
+    StringDictionaryBuilder, UInt16Array, UInt64Array,
+};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::arrow::arrow_reader::{ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowFilter};
+use parquet::arrow::{ArrowWriter, ProjectionMask};
+use parquet::basic::{Compression, Encoding, ZstdLevel};
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use parquet::schema::types::ColumnPath;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::audit_log::parse_time;
+use crate::config::TickStoreSection;
+use crate::feed_aggregator::PriceUpdate;
+use crate::latency_arbitrage::LatencySignal;
+use crate::microstructure::BookFeatures;
+use crate::provider_registry::ProviderId;
+use crate::types::{MarketType, Platform, TimestampNs};
+
+const SEGMENT_SUFFIX: &str = ".parquet";
+
+/// Columns every archive schema carries; queries filter on these
+const TIMESTAMP_COLUMN: &str = "timestamp_ns";
+const MARKET_COLUMN: &str = "market_id";
+const PLATFORM_COLUMN: &str = "platform";
+/// Signal segments only; absent from those written before signals were tagged
+const STRATEGY_COLUMN: &str = "strategy_id";
+
+const PLATFORMS: [Platform; 9] = [
+    Platform::Kalshi, Platform::Polymarket, Platform::DraftKings, Platform::FanDuel, Platform::BetMGM,
+    Platform::Caesars, Platform::PointsBet, Platform::Barstool, Platform::ESPN,
+];
+
+const MARKET_TYPES: [MarketType; 10] = [
+    MarketType::Moneyline, MarketType::Spread, MarketType::Total, MarketType::Btts, MarketType::TeamTotal,
+    MarketType::QuarterTotal, MarketType::HalfTotal, MarketType::PlayerProp, MarketType::AltLine, MarketType::Combo,
+];
+
+/// Tick archive configuration
+#[derive(Debug, Clone)]
+pub struct TickStoreConfig {
+    /// Directory holding `ticks-*` and `signals-*` segments
+    pub dir: PathBuf,
+    /// Rows buffered before a segment is written
+    pub flush_rows: usize,
+    /// Rows per Parquet row group (the unit of time-range pruning)
+    pub row_group_rows: usize,
+    /// zstd level (1-22)
+    pub zstd_level: i32,
+}
+
+impl Default for TickStoreConfig {
+    fn default() -> Self {
+        Self::from(&TickStoreSection::default())
+    }
+}
+
+impl From<&TickStoreSection> for TickStoreConfig {
+    fn from(t: &TickStoreSection) -> Self {
+        Self {
+            dir: PathBuf::from(&t.dir),
+            flush_rows: t.flush_rows,
+            row_group_rows: t.row_group_rows,
+            zstd_level: t.zstd_level,
+        }
+    }
+}
+
+/// A row type with a columnar schema; the schema must carry `timestamp_ns` (UInt64)
+/// and dictionary-encoded `market_id` / `platform` columns
+pub trait ArchiveRow: Sized {
+    /// Segment file prefix
+    const PREFIX: &'static str;
+
+    fn schema() -> SchemaRef;
+    fn timestamp_ns(&self) -> TimestampNs;
+    fn to_batch(rows: &[Self]) -> Result<RecordBatch>;
+    fn from_batch(batch: &RecordBatch) -> Result<Vec<Self>>;
+}
+
+/// One archived price update (both sides, as received)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TickRow {
+    /// Unix nanoseconds we received it
+    pub timestamp_ns: TimestampNs,
+    pub provider_timestamp_ns: Option<TimestampNs>,
+    pub market_id: String,
+    pub platform: ProviderId,
+    pub market_type: MarketType,
+    pub yes_price: u16,
+    pub no_price: u16,
+    pub yes_size: u16,
+    pub no_size: u16,
+    /// Microstructure features computed by the aggregator (nullable columns; absent in older segments)
+    pub features: Option<BookFeatures>,
+}
+
+impl From<&PriceUpdate> for TickRow {
+    fn from(update: &PriceUpdate) -> Self {
+        Self {
+            timestamp_ns: update.received.wall.0,
+            provider_timestamp_ns: update.provider_timestamp,
+            market_id: update.market_id.to_string(),
+            platform: update.provider,
+            market_type: update.market_type,
+            yes_price: update.yes_price,
+            no_price: update.no_price,
+            yes_size: update.yes_size,
+            no_size: update.no_size,
+            features: update.features,
+        }
+    }
+}
+
+impl ArchiveRow for TickRow {
+    const PREFIX: &'static str = "ticks-";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new(TIMESTAMP_COLUMN, DataType::UInt64, false),
+            Field::new("provider_timestamp_ns", DataType::UInt64, true),
+            dictionary_field(MARKET_COLUMN),
+            dictionary_field(PLATFORM_COLUMN),
+            dictionary_field("market_type"),
+            Field::new("yes_price", DataType::UInt16, false),
+            Field::new("no_price", DataType::UInt16, false),
+            Field::new("yes_size", DataType::UInt16, false),
+            Field::new("no_size", DataType::UInt16, false),
+            Field::new("imbalance", DataType::Float64, true),
+            Field::new("microprice", DataType::Float64, true),
+            Field::new("trade_flow_imbalance", DataType::Float64, true),
+        ]))
+    }
+
+    fn timestamp_ns(&self) -> TimestampNs {
+        self.timestamp_ns
+    }
+
+    fn to_batch(rows: &[Self]) -> Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.timestamp_ns))),
+            Arc::new(UInt64Array::from_iter(rows.iter().map(|r| r.provider_timestamp_ns))),
+            dictionary_column(rows.iter().map(|r| r.market_id.as_str())),
+            dictionary_column(rows.iter().map(|r| r.platform.to_string())),
+            dictionary_column(rows.iter().map(|r| r.market_type.to_string())),
+            Arc::new(UInt16Array::from_iter_values(rows.iter().map(|r| r.yes_price))),
+            Arc::new(UInt16Array::from_iter_values(rows.iter().map(|r| r.no_price))),
+            Arc::new(UInt16Array::from_iter_values(rows.iter().map(|r| r.yes_size))),
+            Arc::new(UInt16Array::from_iter_values(rows.iter().map(|r| r.no_size))),
+            Arc::new(Float64Array::from_iter(rows.iter().map(|r| r.features.map(|f| f.imbalance)))),
+            Arc::new(Float64Array::from_iter(rows.iter().map(|r| r.features.map(|f| f.microprice)))),
+            Arc::new(Float64Array::from_iter(rows.iter().map(|r| r.features.map(|f| f.trade_flow_imbalance)))),
+        ];
+        Ok(RecordBatch::try_new(Self::schema(), columns)?)
+    }
+
+    fn from_batch(batch: &RecordBatch) -> Result<Vec<Self>> {
+        let timestamp = typed::<UInt64Array>(batch, TIMESTAMP_COLUMN)?;
+        let provider_timestamp = typed::<UInt64Array>(batch, "provider_timestamp_ns")?;
+        let market_id = strings(batch, MARKET_COLUMN)?;
+        let platform = strings(batch, PLATFORM_COLUMN)?;
+        let market_type = strings(batch, "market_type")?;
+        let yes_price = typed::<UInt16Array>(batch, "yes_price")?;
+        let no_price = typed::<UInt16Array>(batch, "no_price")?;
+        let yes_size = typed::<UInt16Array>(batch, "yes_size")?;
+        let no_size = typed::<UInt16Array>(batch, "no_size")?;
+        let imbalance = optional_f64(batch, "imbalance");
+        let microprice = optional_f64(batch, "microprice");
+        let trade_flow = optional_f64(batch, "trade_flow_imbalance");
+
+        (0..batch.num_rows())
+            .map(|i| {
+                let at = |column: Option<&Float64Array>| column.filter(|c| c.is_valid(i)).map(|c| c.value(i));
+                let features = match (at(imbalance), at(microprice), at(trade_flow)) {
+                    (Some(imbalance), Some(microprice), Some(trade_flow_imbalance)) => {
+                        Some(BookFeatures { imbalance, microprice, trade_flow_imbalance })
+                    }
+                    _ => None,
+                };
+                Ok(TickRow {
+                    timestamp_ns: timestamp.value(i),
+                    provider_timestamp_ns: provider_timestamp.is_valid(i).then(|| provider_timestamp.value(i)),
+                    market_id: market_id.value(i).to_string(),
+                    platform: platform.value(i).parse()?,
+                    market_type: parse_market_type(market_type.value(i))?,
+                    yes_price: yes_price.value(i),
+                    no_price: no_price.value(i),
+                    yes_size: yes_size.value(i),
+                    no_size: no_size.value(i),
+                    features,
+                })
+            })
+            .collect()
+    }
+}
+
+/// One archived latency signal, keyed by the fast (leading) market
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalRow {
+    /// Unix nanoseconds the signal was archived
+    pub timestamp_ns: TimestampNs,
+    pub market_id: String,
+    pub platform: Platform,
+    pub slow_market_id: String,
+    pub slow_platform: Platform,
+    pub market_type: MarketType,
+    pub disparity_cents: i16,
+    pub expected_convergence_ns: u64,
+    pub pattern_id: Option<u16>,
+    pub confidence: f64,
+    /// None in segments written before signals carried a strategy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_id: Option<String>,
+}
+
+impl SignalRow {
+    pub fn from_signal(signal: &LatencySignal, timestamp_ns: TimestampNs) -> Self {
+        Self {
+            timestamp_ns,
+            market_id: signal.fast_market.market_id.to_string(),
+            platform: signal.fast_market.provider,
+            slow_market_id: signal.slow_market.market_id.to_string(),
+            slow_platform: signal.slow_market.provider,
+            market_type: signal.fast_market.market_type,
+            disparity_cents: signal.disparity_cents,
+            expected_convergence_ns: signal.expected_convergence_ns,
+            pattern_id: signal.pattern_id,
+            confidence: signal.confidence,
+            strategy_id: Some(signal.strategy_id.clone()),
+        }
+    }
+}
+
+impl ArchiveRow for SignalRow {
+    const PREFIX: &'static str = "signals-";
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new(TIMESTAMP_COLUMN, DataType::UInt64, false),
+            dictionary_field(MARKET_COLUMN),
+            dictionary_field(PLATFORM_COLUMN),
+            dictionary_field("slow_market_id"),
+            dictionary_field("slow_platform"),
+            dictionary_field("market_type"),
+            Field::new("disparity_cents", DataType::Int16, false),
+            Field::new("expected_convergence_ns", DataType::UInt64, false),
+            Field::new("pattern_id", DataType::UInt16, true),
+            Field::new("confidence", DataType::Float64, false),
+            Field::new(STRATEGY_COLUMN, DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)), true),
+        ]))
+    }
+
+    fn timestamp_ns(&self) -> TimestampNs {
+        self.timestamp_ns
+    }
+
+    fn to_batch(rows: &[Self]) -> Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.timestamp_ns))),
+            dictionary_column(rows.iter().map(|r| r.market_id.as_str())),
+            dictionary_column(rows.iter().map(|r| r.platform.to_string())),
+            dictionary_column(rows.iter().map(|r| r.slow_market_id.as_str())),
+            dictionary_column(rows.iter().map(|r| r.slow_platform.to_string())),
+            dictionary_column(rows.iter().map(|r| r.market_type.to_string())),
+            Arc::new(Int16Array::from_iter_values(rows.iter().map(|r| r.disparity_cents))),
+            Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.expected_convergence_ns))),
+            Arc::new(UInt16Array::from_iter(rows.iter().map(|r| r.pattern_id))),
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(|r| r.confidence))),
+            optional_dictionary_column(rows.iter().map(|r| r.strategy_id.as_deref())),
+        ];
+        Ok(RecordBatch::try_new(Self::schema(), columns)?)
+    }
+
+    fn from_batch(batch: &RecordBatch) -> Result<Vec<Self>> {
+        let timestamp = typed::<UInt64Array>(batch, TIMESTAMP_COLUMN)?;
+        let market_id = strings(batch, MARKET_COLUMN)?;
+        let platform = strings(batch, PLATFORM_COLUMN)?;
+        let slow_market_id = strings(batch, "slow_market_id")?;
+        let slow_platform = strings(batch, "slow_platform")?;
+        let market_type = strings(batch, "market_type")?;
+        let disparity = typed::<Int16Array>(batch, "disparity_cents")?;
+        let convergence = typed::<UInt64Array>(batch, "expected_convergence_ns")?;
+        let pattern_id = typed::<UInt16Array>(batch, "pattern_id")?;
+        let confidence = typed::<Float64Array>(batch, "confidence")?;
+        let strategy = batch.column_by_name(STRATEGY_COLUMN).map(|_| strings(batch, STRATEGY_COLUMN)).transpose()?;
+
+        (0..batch.num_rows())
+            .map(|i| {
+                Ok(SignalRow {
+                    timestamp_ns: timestamp.value(i),
+                    market_id: market_id.value(i).to_string(),
+                    platform: parse_platform(platform.value(i))?,
+                    slow_market_id: slow_market_id.value(i).to_string(),
+                    slow_platform: parse_platform(slow_platform.value(i))?,
+                    market_type: parse_market_type(market_type.value(i))?,
+                    disparity_cents: disparity.value(i),
+                    expected_convergence_ns: convergence.value(i),
+                    pattern_id: pattern_id.is_valid(i).then(|| pattern_id.value(i)),
+                    confidence: confidence.value(i),
+                    strategy_id: strategy.as_ref().and_then(|s| s.is_valid(i).then(|| s.value(i).to_string())),
+                })
+            })
+            .collect()
+    }
+}
+
+fn dictionary_field(name: &str) -> Field {
+    Field::new(name, DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)), false)
+}
+
+fn dictionary_column<S: AsRef<str>>(values: impl Iterator<Item = S>) -> ArrayRef {
+    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+    for value in values {
+        builder.append_value(value);
+    }
+    Arc::new(builder.finish())
+}
+
+fn optional_dictionary_column<'a>(values: impl Iterator<Item = Option<&'a str>>) -> ArrayRef {
+    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+    for value in values {
+        builder.append_option(value);
+    }
+    Arc::new(builder.finish())
+}
+
+fn typed<'a, A: Array + 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a A> {
+    batch
+        .column_by_name(name)
+        .and_then(|c| c.as_any().downcast_ref::<A>())
+        .ok_or_else(|| anyhow!("column '{}' missing or mistyped", name))
+}
+
+/// Nullable column that segments written before it existed lack
+fn optional_f64<'a>(batch: &'a RecordBatch, name: &str) -> Option<&'a Float64Array> {
+    batch.column_by_name(name).and_then(|c| c.as_any().downcast_ref::<Float64Array>())
+}
+
+/// Decode a (dictionary or plain) string column
+fn strings(batch: &RecordBatch, name: &str) -> Result<StringArray> {
+    let column = batch.column_by_name(name).ok_or_else(|| anyhow!("column '{}' missing", name))?;
+    let decoded = cast(column, &DataType::Utf8)?;
+    decoded.as_any().downcast_ref::<StringArray>().cloned().ok_or_else(|| anyhow!("column '{}' not utf8", name))
+}
+
+fn parse_platform(value: &str) -> Result<Platform> {
+    PLATFORMS.into_iter().find(|p| p.to_string() == value).ok_or_else(|| anyhow!("unknown platform '{}'", value))
+}
+
+fn parse_market_type(value: &str) -> Result<MarketType> {
+    MARKET_TYPES.into_iter().find(|t| t.to_string() == value).ok_or_else(|| anyhow!("unknown market type '{}'", value))
+}
+
+/// Buffers rows and writes them as time-sorted segments: `<dir>/<prefix><first_ns>-<last_ns>.parquet`
+pub struct SegmentWriter<R: ArchiveRow> {
+    config: TickStoreConfig,
+    buffer: Vec<R>,
+}
+
+impl<R: ArchiveRow> SegmentWriter<R> {
+    pub fn new(config: TickStoreConfig) -> Self {
+        Self { config, buffer: Vec::new() }
+    }
+
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn append(&mut self, row: R) -> Result<()> {
+        self.buffer.push(row);
+        if self.buffer.len() >= self.config.flush_rows {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write buffered rows as a new segment; returns its path
+    pub fn flush(&mut self) -> Result<Option<PathBuf>> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        std::fs::create_dir_all(&self.config.dir)
+            .with_context(|| format!("create {}", self.config.dir.display()))?;
+
+        // Time-sorted rows keep row-group timestamp ranges tight, so range queries prune well
+        self.buffer.sort_by_key(|r| r.timestamp_ns());
+        let first = self.buffer.first().map(|r| r.timestamp_ns()).unwrap_or(0);
+        let last = self.buffer.last().map(|r| r.timestamp_ns()).unwrap_or(0);
+        let mut path = self.config.dir.join(format!("{}{}-{}{}", R::PREFIX, first, last, SEGMENT_SUFFIX));
+        let mut n = 1;
+        while path.exists() {
+            path = self.config.dir.join(format!("{}{}-{}-{}{}", R::PREFIX, first, last, n, SEGMENT_SUFFIX));
+            n += 1;
+        }
+
+        let batch = R::to_batch(&self.buffer)?;
+        let file = File::create(&path).with_context(|| format!("create {}", path.display()))?;
+        let mut writer = ArrowWriter::try_new(file, R::schema(), Some(self.writer_properties()?))?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        info!("[TICKS] Wrote segment {} ({} rows)", path.display(), self.buffer.len());
+        self.buffer.clear();
+        Ok(Some(path))
+    }
+
+    fn writer_properties(&self) -> Result<WriterProperties> {
+        let timestamp = ColumnPath::from(TIMESTAMP_COLUMN);
+        Ok(WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::try_new(self.config.zstd_level)?))
+            .set_max_row_group_size(self.config.row_group_rows.max(1))
+            .set_statistics_enabled(EnabledStatistics::Chunk)
+            .set_dictionary_enabled(true)
+            // Sorted nanosecond timestamps are near-constant deltas; dictionaries don't help
+            .set_column_dictionary_enabled(timestamp.clone(), false)
+            .set_column_encoding(timestamp, Encoding::DELTA_BINARY_PACKED)
+            .build())
+    }
+}
+
+/// Filter for `scan`; empty matches everything
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveQuery {
+    /// Inclusive Unix-nanosecond bounds
+    pub from_ns: Option<u64>,
+    pub to_ns: Option<u64>,
+    /// Any of these markets (empty = all)
+    pub markets: Vec<String>,
+    /// Any of these providers (empty = all)
+    pub platforms: Vec<ProviderId>,
+    /// Any of these strategies (empty = all); signal segments only
+    pub strategies: Vec<String>,
+    /// Earliest matches first, at most this many
+    pub limit: Option<usize>,
+}
+
+impl ArchiveQuery {
+    pub fn between(from_ns: u64, to_ns: u64) -> Self {
+        Self { from_ns: Some(from_ns), to_ns: Some(to_ns), ..Self::default() }
+    }
+
+    pub fn with_market(mut self, market: impl Into<String>) -> Self {
+        self.markets.push(market.into());
+        self
+    }
+
+    pub fn with_platform(mut self, platform: impl Into<ProviderId>) -> Self {
+        self.platforms.push(platform.into());
+        self
+    }
+
+    pub fn with_strategy(mut self, strategy_id: impl Into<String>) -> Self {
+        self.strategies.push(strategy_id.into());
+        self
+    }
+
+    /// Parse `market=..&platform=..&strategy=..&from=..&to=..&limit=..` (market, platform and strategy repeat);
+    /// times are Unix nanoseconds or RFC3339
+    pub fn from_query_string(query: &str) -> Result<Self, String> {
+        let mut out = Self::default();
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "market" => out.markets.push(value.to_string()),
+                "platform" => out.platforms.push(value.parse().map_err(|e: anyhow::Error| e.to_string())?),
+                "strategy" => out.strategies.push(value.to_string()),
+                "from" => out.from_ns = Some(parse_time(value)?),
+                "to" => out.to_ns = Some(parse_time(value)?),
+                "limit" => out.limit = Some(value.parse().map_err(|_| format!("bad limit '{}'", value))?),
+                _ => return Err(format!("unknown query parameter '{}'", key)),
+            }
+        }
+        Ok(out)
+    }
+
+    fn overlaps(&self, first_ns: u64, last_ns: u64) -> bool {
+        self.from_ns.is_none_or(|from| last_ns >= from) && self.to_ns.is_none_or(|to| first_ns <= to)
+    }
+
+    /// Row mask for a batch holding (at least) the timestamp, market and platform columns, and the
+    /// strategy column when filtering on it
+    fn matches(&self, batch: &RecordBatch, sets: &FilterSets) -> Result<BooleanArray, ArrowError> {
+        let column = |name: &str| {
+            batch.column_by_name(name).cloned()
+                .ok_or_else(|| ArrowError::SchemaError(format!("column '{}' missing", name)))
+        };
+        let timestamp = column(TIMESTAMP_COLUMN)?;
+        let timestamp = timestamp.as_any().downcast_ref::<UInt64Array>()
+            .ok_or_else(|| ArrowError::SchemaError("timestamp_ns is not UInt64".to_string()))?;
+        let mask = |name: &str, wanted: &HashSet<String>| -> Result<Option<Vec<bool>>, ArrowError> {
+            if wanted.is_empty() { Ok(None) } else { Ok(Some(dictionary_mask(&column(name)?, wanted)?)) }
+        };
+        let market = mask(MARKET_COLUMN, &sets.markets)?;
+        let platform = mask(PLATFORM_COLUMN, &sets.platforms)?;
+        let strategy = mask(STRATEGY_COLUMN, &sets.strategies)?;
+
+        let (from, to) = (self.from_ns.unwrap_or(0), self.to_ns.unwrap_or(u64::MAX));
+        Ok((0..batch.num_rows())
+            .map(|i| {
+                let ts = timestamp.value(i);
+                Some(ts >= from && ts <= to
+                    && market.as_ref().is_none_or(|m| m[i])
+                    && platform.as_ref().is_none_or(|p| p[i])
+                    && strategy.as_ref().is_none_or(|s| s[i]))
+            })
+            .collect())
+    }
+}
+
+/// A query's string filters as sets
+#[derive(Debug, Clone, Default)]
+struct FilterSets {
+    markets: HashSet<String>,
+    platforms: HashSet<String>,
+    strategies: HashSet<String>,
+}
+
+/// Membership per row, evaluated once per dictionary value rather than once per row
+fn dictionary_mask(column: &ArrayRef, wanted: &HashSet<String>) -> Result<Vec<bool>, ArrowError> {
+    if let Some(dictionary) = column.as_any().downcast_ref::<DictionaryArray<Int32Type>>() {
+        let values = cast(dictionary.values(), &DataType::Utf8)?;
+        let values = values.as_any().downcast_ref::<StringArray>()
+            .ok_or_else(|| ArrowError::SchemaError("dictionary values not utf8".to_string()))?;
+        let hit: Vec<bool> = (0..values.len()).map(|i| values.is_valid(i) && wanted.contains(values.value(i))).collect();
+        return Ok(dictionary.keys().iter().map(|k| k.is_some_and(|k| hit[k as usize])).collect());
+    }
+    let values = cast(column, &DataType::Utf8)?;
+    let values = values.as_any().downcast_ref::<StringArray>()
+        .ok_or_else(|| ArrowError::SchemaError("column not utf8".to_string()))?;
+    Ok((0..values.len()).map(|i| values.is_valid(i) && wanted.contains(values.value(i))).collect())
+}
+
+/// What a scan read versus skipped
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScanStats {
+    pub segments_read: usize,
+    pub segments_skipped: usize,
+    pub row_groups_read: usize,
+    pub row_groups_skipped: usize,
+    pub rows_matched: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveScan<R> {
+    pub rows: Vec<R>,
+    pub stats: ScanStats,
+}
+
+/// Read matching rows, ordered by time. Segments are pruned by file name, row groups by
+/// timestamp statistics, and the remaining rows by a filter evaluated while decoding
+/// only the timestamp/market/platform columns
+pub fn scan<R: ArchiveRow>(dir: &Path, query: &ArchiveQuery) -> Result<ArchiveScan<R>> {
+    let mut out = ArchiveScan { rows: Vec::new(), stats: ScanStats::default() };
+    let mut segments = match segments::<R>(dir) {
+        Ok(segments) => segments,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+        Err(e) => return Err(e.into()),
+    };
+    segments.sort();
+
+    let sets = FilterSets {
+        markets: query.markets.iter().cloned().collect(),
+        platforms: query.platforms.iter().map(|p| p.to_string()).collect(),
+        strategies: query.strategies.iter().cloned().collect(),
+    };
+
+    for (first, last, path) in segments {
+        if !query.overlaps(first, last) {
+            out.stats.segments_skipped += 1;
+            continue;
+        }
+        match scan_segment::<R>(&path, query, &sets, &mut out) {
+            Ok(()) => out.stats.segments_read += 1,
+            Err(e) => warn!("[TICKS] Skipping unreadable segment {}: {:#}", path.display(), e),
+        }
+    }
+
+    out.rows.sort_by_key(|r| r.timestamp_ns());
+    if let Some(limit) = query.limit {
+        out.rows.truncate(limit);
+    }
+    out.stats.rows_matched = out.rows.len();
+    Ok(out)
+}
+
+fn scan_segment<R: ArchiveRow>(
+    path: &Path,
+    query: &ArchiveQuery,
+    sets: &FilterSets,
+    out: &mut ArchiveScan<R>,
+) -> Result<()> {
+    let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let metadata = builder.metadata().clone();
+
+    let converter = StatisticsConverter::try_new(TIMESTAMP_COLUMN, builder.schema(), builder.parquet_schema())?;
+    let mins = converter.row_group_mins(metadata.row_groups().iter())?;
+    let maxes = converter.row_group_maxes(metadata.row_groups().iter())?;
+    let (Some(mins), Some(maxes)) = (
+        mins.as_any().downcast_ref::<UInt64Array>(),
+        maxes.as_any().downcast_ref::<UInt64Array>(),
+    ) else {
+        bail!("timestamp statistics are not UInt64");
+    };
+    // Segments from before strategy tagging hold no rows a strategy filter can match
+    let untagged = !sets.strategies.is_empty() && builder.schema().index_of(STRATEGY_COLUMN).is_err();
+    let row_groups: Vec<usize> = (0..metadata.num_row_groups())
+        .filter(|&i| !untagged && (mins.is_null(i) || maxes.is_null(i) || query.overlaps(mins.value(i), maxes.value(i))))
+        .collect();
+    out.stats.row_groups_skipped += metadata.num_row_groups() - row_groups.len();
+    out.stats.row_groups_read += row_groups.len();
+    if row_groups.is_empty() {
+        return Ok(());
+    }
+
+    let strategy = (!sets.strategies.is_empty()).then_some(STRATEGY_COLUMN);
+    let filter_columns: Vec<usize> = [TIMESTAMP_COLUMN, MARKET_COLUMN, PLATFORM_COLUMN]
+        .into_iter()
+        .chain(strategy)
+        .map(|name| builder.schema().index_of(name))
+        .collect::<Result<_, _>>()?;
+    let mask = ProjectionMask::roots(builder.parquet_schema(), filter_columns);
+    let (query, sets) = (query.clone(), sets.clone());
+    let predicate = ArrowPredicateFn::new(mask, move |batch: RecordBatch| query.matches(&batch, &sets));
+
+    let reader = builder
+        .with_row_groups(row_groups)
+        .with_row_filter(RowFilter::new(vec![Box::new(predicate)]))
+        .build()?;
+    for batch in reader {
+        out.rows.extend(R::from_batch(&batch?)?);
+    }
+    Ok(())
+}
+
+/// (first_ns, last_ns, path) of every segment of one row type
+fn segments<R: ArchiveRow>(dir: &Path) -> std::io::Result<Vec<(u64, u64, PathBuf)>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let Some(range) = name.strip_prefix(R::PREFIX).and_then(|n| n.strip_suffix(SEGMENT_SUFFIX)) else {
+            continue;
+        };
+        let mut parts = range.split('-');
+        if let (Some(Ok(first)), Some(Ok(last))) = (parts.next().map(str::parse), parts.next().map(str::parse)) {
+            out.push((first, last, path));
+        }
+    }
+    Ok(out)
+}
+
+/// Admin route: GET /archive/ticks?.. or /archive/signals?.. (see `ArchiveQuery::from_query_string`)
+pub fn handle_admin(config: &TickStoreConfig, method: &str, path: &str) -> (u16, String) {
+    if method != "GET" {
+        return (405, r#"{"error":"method not allowed"}"#.to_string());
+    }
+    let (route, query) = path.split_once('?').unwrap_or((path, ""));
+    let query = match ArchiveQuery::from_query_string(query) {
+        Ok(query) => ArchiveQuery { limit: query.limit.or(Some(1000)), ..query },
+        Err(e) => return (400, serde_json::json!({ "error": e }).to_string()),
+    };
+    let result = match route.trim_end_matches('/') {
+        "/archive/ticks" if !query.strategies.is_empty() => {
+            return (400, r#"{"error":"ticks are not tagged by strategy"}"#.to_string());
+        }
+        "/archive/ticks" => scan::<TickRow>(&config.dir, &query).map(|scan| serde_json::to_string_pretty(&scan)),
+        "/archive/signals" => scan::<SignalRow>(&config.dir, &query).map(|scan| serde_json::to_string_pretty(&scan)),
+        _ => return (404, r#"{"error":"not found"}"#.to_string()),
+    };
+    match result {
+        Ok(Ok(body)) => (200, body),
+        Ok(Err(e)) => (500, serde_json::json!({ "error": e.to_string() }).to_string()),
+        Err(e) => (500, serde_json::json!({ "error": format!("{:#}", e) }).to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(ts: u64, market: &str, platform: Platform) -> TickRow {
+        TickRow {
+            timestamp_ns: ts,
+            provider_timestamp_ns: ts.is_multiple_of(2).then_some(ts - 1),
+            market_id: market.to_string(),
+            platform: platform.into(),
+            market_type: MarketType::Total,
+            yes_price: 40 + (ts % 20) as u16,
+            no_price: 60 - (ts % 20) as u16,
+            yes_size: 100,
+            no_size: 200,
+            features: ts.is_multiple_of(5).then_some(BookFeatures { imbalance: 0.5, microprice: 41.5, trade_flow_imbalance: -0.25 }),
+        }
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tick_store_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_roundtrip_and_pushdown() {
+        let dir = test_dir("roundtrip");
+        let config = TickStoreConfig { dir: dir.clone(), flush_rows: 1_000, row_group_rows: 100, zstd_level: 3 };
+        let mut writer = SegmentWriter::<TickRow>::new(config);
+        // Written out of order; segments are time-sorted on flush
+        for ts in (1..=1_000u64).rev() {
+            let market = if ts % 3 == 0 { "7" } else { "8" };
+            let platform = if ts % 2 == 0 { Platform::DraftKings } else { Platform::Kalshi };
+            writer.append(tick(ts, market, platform)).unwrap();
+        }
+        assert_eq!(writer.buffered(), 0);
+        assert!(writer.flush().unwrap().is_none());
+
+        let all = scan::<TickRow>(&dir, &ArchiveQuery::default()).unwrap();
+        assert_eq!(all.rows.len(), 1_000);
+        assert_eq!(all.rows[0], tick(1, "8", Platform::Kalshi));
+        assert_eq!(all.rows[4], tick(5, "8", Platform::Kalshi), "Feature columns round-trip");
+        assert_eq!(all.stats.row_groups_read, 10);
+
+        // Range inside one row group: the other nine are pruned by statistics
+        let query = ArchiveQuery::between(250, 260).with_market("7").with_platform(Platform::DraftKings);
+        let some = scan::<TickRow>(&dir, &query).unwrap();
+        let expected: Vec<u64> = (250..=260).filter(|ts| ts % 6 == 0).collect();
+        assert_eq!(some.rows.iter().map(|r| r.timestamp_ns).collect::<Vec<_>>(), expected);
+        assert_eq!(some.stats.row_groups_read, 1);
+        assert_eq!(some.stats.row_groups_skipped, 9);
+
+        let none = scan::<TickRow>(&dir, &ArchiveQuery::between(2_000, 3_000)).unwrap();
+        assert!(none.rows.is_empty());
+        assert_eq!(none.stats.segments_skipped, 1);
+
+        // Signal segments share the directory without mixing
+        assert!(scan::<SignalRow>(&dir, &ArchiveQuery::default()).unwrap().rows.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_signal_strategy_filter() {
+        let dir = test_dir("strategy");
+        let config = TickStoreConfig { dir: dir.clone(), flush_rows: 1_000, row_group_rows: 100, zstd_level: 3 };
+        let signal = |ts: u64, strategy: Option<&str>| SignalRow {
+            timestamp_ns: ts,
+            market_id: "7".to_string(),
+            platform: Platform::DraftKings,
+            slow_market_id: "8".to_string(),
+            slow_platform: Platform::Kalshi,
+            market_type: MarketType::Total,
+            disparity_cents: 4,
+            expected_convergence_ns: 1_000_000,
+            pattern_id: Some(74),
+            confidence: 0.7,
+            strategy_id: strategy.map(str::to_string),
+        };
+        let mut writer = SegmentWriter::<SignalRow>::new(config.clone());
+        for ts in 1..=30u64 {
+            let strategy = match ts % 3 {
+                0 => Some("pattern_74"),
+                1 => Some("market_making"),
+                _ => None,
+            };
+            writer.append(signal(ts, strategy)).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let all = scan::<SignalRow>(&dir, &ArchiveQuery::default()).unwrap();
+        assert_eq!(all.rows.len(), 30);
+        assert_eq!(all.rows[1], signal(2, None));
+        let tagged = scan::<SignalRow>(&dir, &ArchiveQuery::default().with_strategy("pattern_74")).unwrap();
+        assert_eq!(tagged.rows.iter().map(|r| r.timestamp_ns).collect::<Vec<_>>(), (3..=30).step_by(3).collect::<Vec<_>>());
+        let either = ArchiveQuery::from_query_string("strategy=pattern_74&strategy=market_making&to=10").unwrap();
+        assert_eq!(scan::<SignalRow>(&dir, &either).unwrap().rows.len(), 7);
+
+        // Ticks carry no strategy
+        assert_eq!(handle_admin(&config, "GET", "/archive/ticks?strategy=pattern_74").0, 400);
+        assert_eq!(handle_admin(&config, "GET", "/archive/signals?strategy=pattern_74").0, 200);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_query_string() {
+        let query = ArchiveQuery::from_query_string("market=7&market=8&platform=kalshi&from=10&to=20&limit=5").unwrap();
+        assert_eq!(query.markets, vec!["7", "8"]);
+        assert_eq!(query.platforms, vec![ProviderId::from(Platform::Kalshi)]);
+        assert_eq!((query.from_ns, query.to_ns, query.limit), (Some(10), Some(20), Some(5)));
+        assert!(ArchiveQuery::from_query_string("platform=nope").is_err());
+        assert!(ArchiveQuery::from_query_string("bogus=1").is_err());
+    }
+}
@@ -57,10 +57,314 @@ pub struct MarketPair {
     pub team_suffix: Option<Arc<str>>,
 }
 
-/// Price in cents (1-99 for 0.01-0.99), 0 = no price available
+impl MarketPair {
+    /// The Kalshi leg's place in its series -> event -> market hierarchy
+    pub fn kalshi_path(&self) -> KalshiPath {
+        KalshiPath::new(&self.kalshi_event_ticker, &self.kalshi_market_ticker)
+    }
+}
+
+/// Where a Kalshi market sits: series (`KXEPLGAME`) -> event (`KXEPLGAME-25DEC27CFCAVL`)
+/// -> market (`KXEPLGAME-25DEC27CFCAVL-CFC`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KalshiPath {
+    pub series: Arc<str>,
+    pub event: Arc<str>,
+    pub market: Arc<str>,
+}
+
+impl KalshiPath {
+    /// From a market and its event ticker; the series is the event ticker's first segment
+    pub fn new(event_ticker: &str, market_ticker: &str) -> Self {
+        let series = event_ticker.split('-').next().unwrap_or(event_ticker);
+        Self { series: series.into(), event: event_ticker.into(), market: market_ticker.into() }
+    }
+
+    /// From a market ticker alone (`SERIES-EVENT-MARKET`); None without all three segments
+    pub fn from_market_ticker(ticker: &str) -> Option<Self> {
+        let (event, suffix) = ticker.rsplit_once('-')?;
+        if suffix.is_empty() || !event.contains('-') {
+            return None;
+        }
+        Some(Self::new(event, ticker))
+    }
+
+    /// Series and event tickers, broadest first
+    pub fn groups(&self) -> [&str; 2] {
+        [&self.series, &self.event]
+    }
+}
+
+// === Identifiers & Units ===
+
+/// Index into `GlobalState.markets`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct MarketId(pub u16);
+
+impl MarketId {
+    /// Checked construction from a slot index (None past `MAX_MARKETS`)
+    #[inline(always)]
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index < MAX_MARKETS { Some(Self(index as u16)) } else { None }
+    }
+
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    #[inline(always)]
+    pub fn next(self) -> Option<Self> {
+        Self::from_index(self.index() + 1)
+    }
+}
+
+impl std::fmt::Display for MarketId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Unique identifier for a trading signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct SignalId(pub u64);
+
+impl SignalId {
+    /// Return the current id and advance the counter
+    #[inline(always)]
+    pub fn take_next(&mut self) -> Self {
+        let id = *self;
+        self.0 = self.0.wrapping_add(1);
+        id
+    }
+}
+
+impl std::fmt::Display for SignalId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Contract price in cents (1-99 for 0.01-0.99), `Price::NONE` = no price available
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Price(pub PriceCents);
+
+impl Price {
+    pub const NONE: Self = Self(NO_PRICE);
+    /// Payout of a winning contract
+    pub const ONE_DOLLAR: Self = Self(100);
+
+    /// Checked construction: None above 100¢
+    #[inline(always)]
+    pub fn new(cents: PriceCents) -> Option<Self> {
+        if cents <= 100 { Some(Self(cents)) } else { None }
+    }
+
+    #[inline(always)]
+    pub const fn cents(self) -> PriceCents {
+        self.0
+    }
+
+    #[inline(always)]
+    pub const fn is_none(self) -> bool {
+        self.0 == NO_PRICE
+    }
+
+    #[inline(always)]
+    pub fn from_probability(p: f64) -> Self {
+        Self(price_to_cents(p))
+    }
+
+    #[inline(always)]
+    pub fn as_probability(self) -> f64 {
+        cents_to_price(self.0)
+    }
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Signed difference in cents (`self - rhs`)
+    #[inline(always)]
+    pub fn diff(self, rhs: Self) -> i16 {
+        self.0 as i16 - rhs.0 as i16
+    }
+}
+
+impl std::fmt::Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}¢", self.0)
+    }
+}
+
+/// Order size in cents of notional (dollar amount × 100), max $655.35 per side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Size(pub SizeCents);
+
+impl Size {
+    pub const ZERO: Self = Self(0);
+
+    #[inline(always)]
+    pub const fn cents(self) -> SizeCents {
+        self.0
+    }
+
+    /// Whole $1 contracts this size covers
+    #[inline(always)]
+    pub const fn contracts(self) -> i64 {
+        (self.0 / 100) as i64
+    }
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}¢", self.0)
+    }
+}
+
+/// Duration or monotonic timestamp in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Nanos(pub u64);
+
+impl Nanos {
+    pub const ZERO: Self = Self(0);
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Elapsed time since `earlier`, zero if the clock went backwards
+    #[inline(always)]
+    pub fn saturating_sub(self, earlier: Self) -> Self {
+        Self(self.0.saturating_sub(earlier.0))
+    }
+
+    #[inline(always)]
+    pub const fn as_micros(self) -> u64 {
+        self.0 / 1_000
+    }
+
+    /// Truncating conversion
+    #[inline(always)]
+    pub const fn as_millis(self) -> Millis {
+        Millis(self.0 / 1_000_000)
+    }
+
+    #[inline(always)]
+    pub const fn as_duration(self) -> std::time::Duration {
+        std::time::Duration::from_nanos(self.0)
+    }
+}
+
+impl From<std::time::Duration> for Nanos {
+    /// Saturates at `u64::MAX` (~584 years)
+    fn from(d: std::time::Duration) -> Self {
+        Self(u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+    }
+}
+
+impl std::fmt::Display for Nanos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}ns", self.0)
+    }
+}
+
+/// Duration or wall-clock timestamp in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct Millis(pub u64);
+
+impl Millis {
+    pub const ZERO: Self = Self(0);
+
+    #[inline(always)]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    #[inline(always)]
+    pub fn saturating_sub(self, earlier: Self) -> Self {
+        Self(self.0.saturating_sub(earlier.0))
+    }
+
+    /// None if the result overflows u64 nanoseconds
+    #[inline(always)]
+    pub fn checked_as_nanos(self) -> Option<Nanos> {
+        self.0.checked_mul(1_000_000).map(Nanos)
+    }
+
+    #[inline(always)]
+    pub const fn as_duration(self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.0)
+    }
+}
+
+impl From<std::time::Duration> for Millis {
+    fn from(d: std::time::Duration) -> Self {
+        Self(u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+    }
+}
+
+impl std::fmt::Display for Millis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}ms", self.0)
+    }
+}
+
+/// Raw price in cents (1-99 for 0.01-0.99), 0 = no price available.
+/// Used by the packed orderbook hot path; prefer `Price` in APIs.
 pub type PriceCents = u16;
 
-/// Size in cents (dollar amount × 100), max ~$655k per side
+/// Raw size in cents (dollar amount × 100), max $655.35 per side.
+/// Used by the packed orderbook hot path; prefer `Size` in APIs.
 pub type SizeCents = u16;
 
 /// Maximum number of tracked markets
@@ -85,7 +389,7 @@ pub fn unpack_orderbook(packed: u64) -> (PriceCents, PriceCents, SizeCents, Size
     (yes_ask, no_ask, yes_size, no_size)
 }
 
-/// Timestamp in nanoseconds since Unix epoch
+/// Raw timestamp in nanoseconds since Unix epoch; prefer `Nanos` in APIs
 pub type TimestampNs = u64;
 
 impl TimestampedOrderbook {
@@ -210,11 +514,11 @@ pub struct AtomicMarketState {
     /// Market pair data (immutable after discovery)
     pub pair: Option<Arc<MarketPair>>,
     /// Market ID for lookups
-    pub market_id: u16,
+    pub market_id: MarketId,
 }
 
 impl AtomicMarketState {
-    pub fn new(market_id: u16) -> Self {
+    pub fn new(market_id: MarketId) -> Self {
         Self {
             kalshi: AtomicOrderbook::new(),
             poly: AtomicOrderbook::new(),
@@ -320,7 +624,7 @@ pub fn parse_price(s: &str) -> PriceCents {
 }
 
 /// Arb type - determines execution strategy
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArbType {
     /// Poly YES + Kalshi NO (cross-platform)
     PolyYesKalshiNo,
@@ -336,37 +640,41 @@ pub enum ArbType {
 #[derive(Debug, Clone, Copy)]
 pub struct FastExecutionRequest {
     /// Index into GlobalState.markets array
-    pub market_id: u16,
-    /// YES price in cents
-    pub yes_price: PriceCents,
-    /// NO price in cents
-    pub no_price: PriceCents,
-    /// YES size in cents
-    pub yes_size: SizeCents,
-    /// NO size in cents
-    pub no_size: SizeCents,
+    pub market_id: MarketId,
+    /// YES price
+    pub yes_price: Price,
+    /// NO price
+    pub no_price: Price,
+    /// YES size
+    pub yes_size: Size,
+    /// NO size
+    pub no_size: Size,
     /// Type of arb (determines execution strategy)
     pub arb_type: ArbType,
-    /// Detection timestamp (nanoseconds since start)
-    pub detected_ns: u64,
+    /// Detection timestamp (process monotonic clock, `clock::SystemClock`)
+    pub detected_ns: Nanos,
 }
 
 impl FastExecutionRequest {
+    /// Expected profit per contract in cents (negative if the arb has closed)
     #[inline(always)]
     pub fn profit_cents(&self) -> i16 {
-        100 - (self.yes_price as i16 + self.no_price as i16 + self.estimated_fee_cents() as i16)
+        let cost = self.yes_price.cents() as i16
+            + self.no_price.cents() as i16
+            + self.estimated_fee_cents() as i16;
+        Price::ONE_DOLLAR.cents() as i16 - cost
     }
 
     #[inline(always)]
     pub fn estimated_fee_cents(&self) -> PriceCents {
         match self.arb_type {
             // Cross-platform: fee on the Kalshi side only
-            ArbType::PolyYesKalshiNo => kalshi_fee_cents(self.no_price),
-            ArbType::KalshiYesPolyNo => kalshi_fee_cents(self.yes_price),
+            ArbType::PolyYesKalshiNo => kalshi_fee_cents(self.no_price.cents()),
+            ArbType::KalshiYesPolyNo => kalshi_fee_cents(self.yes_price.cents()),
             // Poly-only: no fees
             ArbType::PolyOnly => 0,
             // Kalshi-only: fees on both sides
-            ArbType::KalshiOnly => kalshi_fee_cents(self.yes_price) + kalshi_fee_cents(self.no_price),
+            ArbType::KalshiOnly => kalshi_fee_cents(self.yes_price.cents()) + kalshi_fee_cents(self.no_price.cents()),
         }
     }
 }
@@ -377,23 +685,23 @@ pub struct GlobalState {
     pub markets: Vec<AtomicMarketState>,
 
     /// Next available market_id
-    next_market_id: u16,
+    next_market_id: usize,
 
     /// O(1) lookup: pre-hashed Kalshi ticker → market_id
-    pub kalshi_to_id: FxHashMap<u64, u16>,
+    pub kalshi_to_id: FxHashMap<u64, MarketId>,
 
     /// O(1) lookup: pre-hashed Poly YES token → market_id
-    pub poly_yes_to_id: FxHashMap<u64, u16>,
+    pub poly_yes_to_id: FxHashMap<u64, MarketId>,
 
     /// O(1) lookup: pre-hashed Poly NO token → market_id
-    pub poly_no_to_id: FxHashMap<u64, u16>,
+    pub poly_no_to_id: FxHashMap<u64, MarketId>,
 }
 
 impl GlobalState {
     pub fn new() -> Self {
         // Allocate market slots
         let markets: Vec<AtomicMarketState> = (0..MAX_MARKETS)
-            .map(|i| AtomicMarketState::new(i as u16))
+            .map(|i| AtomicMarketState::new(MarketId(i as u16)))
             .collect();
 
         Self {
@@ -406,12 +714,8 @@ impl GlobalState {
     }
 
     /// Add a market pair, returns market_id
-    pub fn add_pair(&mut self, pair: MarketPair) -> Option<u16> {
-        if self.next_market_id as usize >= MAX_MARKETS {
-            return None;
-        }
-
-        let market_id = self.next_market_id;
+    pub fn add_pair(&mut self, pair: MarketPair) -> Option<MarketId> {
+        let market_id = MarketId::from_index(self.next_market_id)?;
         self.next_market_id += 1;
 
         // Pre-compute hashes
@@ -425,7 +729,7 @@ impl GlobalState {
         self.poly_no_to_id.insert(poly_no_hash, market_id);
 
         // Store pair
-        self.markets[market_id as usize].pair = Some(Arc::new(pair));
+        self.markets[market_id.index()].pair = Some(Arc::new(pair));
 
         Some(market_id)
     }
@@ -435,7 +739,7 @@ impl GlobalState {
     #[allow(dead_code)]
     pub fn get_by_kalshi_hash(&self, hash: u64) -> Option<&AtomicMarketState> {
         let id = *self.kalshi_to_id.get(&hash)?;
-        Some(&self.markets[id as usize])
+        Some(&self.markets[id.index()])
     }
 
     /// Get market by Poly YES token hash (O(1))
@@ -443,7 +747,7 @@ impl GlobalState {
     #[allow(dead_code)]
     pub fn get_by_poly_yes_hash(&self, hash: u64) -> Option<&AtomicMarketState> {
         let id = *self.poly_yes_to_id.get(&hash)?;
-        Some(&self.markets[id as usize])
+        Some(&self.markets[id.index()])
     }
 
     /// Get market by Poly NO token hash (O(1))
@@ -451,42 +755,38 @@ impl GlobalState {
     #[allow(dead_code)]
     pub fn get_by_poly_no_hash(&self, hash: u64) -> Option<&AtomicMarketState> {
         let id = *self.poly_no_to_id.get(&hash)?;
-        Some(&self.markets[id as usize])
+        Some(&self.markets[id.index()])
     }
 
     /// Get market_id by Poly YES token hash
     #[inline(always)]
     #[allow(dead_code)]
-    pub fn id_by_poly_yes_hash(&self, hash: u64) -> Option<u16> {
+    pub fn id_by_poly_yes_hash(&self, hash: u64) -> Option<MarketId> {
         self.poly_yes_to_id.get(&hash).copied()
     }
 
     /// Get market_id by Poly NO token hash
     #[inline(always)]
     #[allow(dead_code)]
-    pub fn id_by_poly_no_hash(&self, hash: u64) -> Option<u16> {
+    pub fn id_by_poly_no_hash(&self, hash: u64) -> Option<MarketId> {
         self.poly_no_to_id.get(&hash).copied()
     }
 
     /// Get market_id by Kalshi ticker hash
     #[inline(always)]
     #[allow(dead_code)]
-    pub fn id_by_kalshi_hash(&self, hash: u64) -> Option<u16> {
+    pub fn id_by_kalshi_hash(&self, hash: u64) -> Option<MarketId> {
         self.kalshi_to_id.get(&hash).copied()
     }
 
     /// Get market by ID
     #[inline(always)]
-    pub fn get_by_id(&self, id: u16) -> Option<&AtomicMarketState> {
-        if (id as usize) < self.markets.len() {
-            Some(&self.markets[id as usize])
-        } else {
-            None
-        }
+    pub fn get_by_id(&self, id: MarketId) -> Option<&AtomicMarketState> {
+        self.markets.get(id.index())
     }
 
     pub fn market_count(&self) -> usize {
-        self.next_market_id as usize
+        self.next_market_id
     }
 }
 
@@ -507,7 +807,7 @@ pub fn fxhash_str(s: &str) -> u64 {
 
 // === Platform Enum ===
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum Platform {
     Kalshi,
@@ -756,6 +1056,81 @@ mod tests {
         assert_eq!(parse_price(""), 0);
     }
 
+    // =========================================================================
+    // Identifier & Unit Tests
+    // =========================================================================
+
+    #[test]
+    fn test_kalshi_path() {
+        let path = KalshiPath::from_market_ticker("KXEPLGAME-25DEC27CFCAVL-CFC").unwrap();
+        assert_eq!(&*path.series, "KXEPLGAME");
+        assert_eq!(&*path.event, "KXEPLGAME-25DEC27CFCAVL");
+        assert_eq!(path.groups(), ["KXEPLGAME", "KXEPLGAME-25DEC27CFCAVL"]);
+        assert_eq!(path, KalshiPath::new("KXEPLGAME-25DEC27CFCAVL", "KXEPLGAME-25DEC27CFCAVL-CFC"));
+        assert!(KalshiPath::from_market_ticker("KXEPLGAME-25DEC27CFCAVL").is_none());
+        assert!(KalshiPath::from_market_ticker("KXEPLGAME").is_none());
+    }
+
+    #[test]
+    fn test_market_id_bounds() {
+        assert_eq!(MarketId::from_index(0), Some(MarketId(0)));
+        assert_eq!(MarketId::from_index(MAX_MARKETS - 1).unwrap().index(), MAX_MARKETS - 1);
+        assert_eq!(MarketId::from_index(MAX_MARKETS), None);
+        assert_eq!(MarketId((MAX_MARKETS - 1) as u16).next(), None);
+
+        // GlobalState refuses pairs once every slot is taken
+        let mut state = GlobalState::new();
+        for i in 0..MAX_MARKETS {
+            assert!(state.add_pair(make_test_pair(&format!("{:04}", i))).is_some());
+        }
+        assert!(state.add_pair(make_test_pair("FULL")).is_none());
+        assert!(state.get_by_id(MarketId(MAX_MARKETS as u16)).is_none());
+    }
+
+    #[test]
+    fn test_price_size_checked_arithmetic() {
+        assert_eq!(Price::new(100), Some(Price::ONE_DOLLAR));
+        assert_eq!(Price::new(101), None);
+        assert!(Price::NONE.is_none());
+        assert_eq!(Price(40).checked_add(Price(50)), Some(Price(90)));
+        assert_eq!(Price(5).checked_sub(Price(10)), None);
+        assert_eq!(Price(5).saturating_sub(Price(10)), Price(0));
+        assert_eq!(Price(40).diff(Price(50)), -10);
+        assert_eq!(Price::from_probability(0.55), Price(55));
+        assert!((Price(55).as_probability() - 0.55).abs() < 0.001);
+
+        assert_eq!(Size(u16::MAX).checked_add(Size(1)), None);
+        assert_eq!(Size(u16::MAX).saturating_add(Size(1)), Size(u16::MAX));
+        assert_eq!(Size(1099).contracts(), 10);
+        assert_eq!(Size(50).contracts(), 0);
+    }
+
+    #[test]
+    fn test_time_units() {
+        assert_eq!(Nanos(5).saturating_sub(Nanos(10)), Nanos::ZERO);
+        assert_eq!(Nanos(u64::MAX).checked_add(Nanos(1)), None);
+        assert_eq!(Nanos(2_500_000).as_millis(), Millis(2));
+        assert_eq!(Nanos(2_500).as_micros(), 2);
+        assert_eq!(Millis(3).checked_as_nanos(), Some(Nanos(3_000_000)));
+        assert_eq!(Millis(u64::MAX).checked_as_nanos(), None);
+        assert_eq!(Nanos::from(std::time::Duration::from_micros(7)), Nanos(7_000));
+        assert_eq!(Millis::from(std::time::Duration::from_secs(2)), Millis(2_000));
+
+        let mut id = SignalId::default();
+        assert_eq!(id.take_next(), SignalId(0));
+        assert_eq!(id.take_next(), SignalId(1));
+        assert_eq!(id, SignalId(2));
+    }
+
+    #[test]
+    fn test_newtypes_serialize_transparently() {
+        let json = serde_json::to_string(&(MarketId(7), Price(42), Size(1000), Nanos(9), Millis(3), SignalId(11))).unwrap();
+        assert_eq!(json, "[7,42,1000,9,3,11]");
+        let back: (MarketId, Price) = serde_json::from_str("[7,42]").unwrap();
+        assert_eq!(back, (MarketId(7), Price(42)));
+        assert_eq!(format!("{} {} {}", Price(42), Nanos(9), Millis(3)), "42¢ 9ns 3ms");
+    }
+
     // =========================================================================
     // check_arbs Tests
     // =========================================================================
@@ -766,7 +1141,7 @@ mod tests {
         poly_yes: PriceCents,
         poly_no: PriceCents,
     ) -> AtomicMarketState {
-        let state = AtomicMarketState::new(0);
+        let state = AtomicMarketState::new(MarketId(0));
         state.kalshi.store(kalshi_yes, kalshi_no, 1000, 1000);
         state.poly.store(poly_yes, poly_no, 1000, 1000);
         state
@@ -903,7 +1278,7 @@ mod tests {
 
         let id = state.add_pair(pair).expect("Should add pair");
 
-        assert_eq!(id, 0, "First market should have id 0");
+        assert_eq!(id, MarketId(0), "First market should have id 0");
         assert_eq!(state.market_count(), 1);
 
         // Verify lookups work
@@ -953,14 +1328,14 @@ mod tests {
         for i in 0..10 {
             let pair = make_test_pair(&format!("{:03}", i));
             let id = state.add_pair(pair).unwrap();
-            assert_eq!(id, i as u16);
+            assert_eq!(id, MarketId(i));
         }
 
         assert_eq!(state.market_count(), 10);
 
         // All should be findable
         for i in 0..10 {
-            let market = state.get_by_id(i as u16);
+            let market = state.get_by_id(MarketId(i));
             assert!(market.is_some(), "Market {} should exist", i);
         }
     }
@@ -997,13 +1372,13 @@ mod tests {
         // Kalshi fee on 50¢ = 2¢
         // Profit = 100 - 90 - 2 = 8¢
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert_eq!(req.profit_cents(), 8);
@@ -1015,13 +1390,13 @@ mod tests {
         // Kalshi fee on 40¢ = 2¢
         // Profit = 100 - 90 - 2 = 8¢
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiYesPolyNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert_eq!(req.profit_cents(), 8);
@@ -1033,13 +1408,13 @@ mod tests {
         // No fees on Polymarket
         // Profit = 100 - 88 - 0 = 12¢
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 48,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(48),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert_eq!(req.profit_cents(), 12);
@@ -1052,13 +1427,13 @@ mod tests {
         // Kalshi fee on both: 2¢ + 2¢ = 4¢
         // Profit = 100 - 84 - 4 = 12¢
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 44,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(44),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert_eq!(req.profit_cents(), 12);
@@ -1069,13 +1444,13 @@ mod tests {
     fn test_execution_request_negative_profit() {
         // Prices too high - no profit
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 52,
-            no_price: 52,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(52),
+            no_price: Price(52),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert!(req.profit_cents() < 0, "Should have negative profit");
@@ -1085,49 +1460,49 @@ mod tests {
     fn test_execution_request_estimated_fee() {
         // PolyYesKalshiNo → fee on Kalshi NO
         let req1 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req1.estimated_fee_cents(), kalshi_fee_cents(50));
 
         // KalshiYesPolyNo → fee on Kalshi YES
         let req2 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiYesPolyNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req2.estimated_fee_cents(), kalshi_fee_cents(40));
 
         // PolyOnly → no fees
         let req3 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req3.estimated_fee_cents(), 0);
 
         // KalshiOnly → fees on both sides
         let req4 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req4.estimated_fee_cents(), kalshi_fee_cents(40) + kalshi_fee_cents(50));
     }
@@ -1183,13 +1558,13 @@ mod tests {
         // Kalshi update
         let kalshi_hash = fxhash_str(&kalshi_ticker);
         if let Some(id) = state.kalshi_to_id.get(&kalshi_hash) {
-            state.markets[*id as usize].kalshi.store(55, 50, 500, 600);
+            state.markets[id.index()].kalshi.store(55, 50, 500, 600);
         }
 
         // Polymarket update
         let poly_hash = fxhash_str(&poly_yes_token);
         if let Some(id) = state.poly_yes_to_id.get(&poly_hash) {
-            state.markets[*id as usize].poly.store(40, 65, 700, 800);
+            state.markets[id.index()].poly.store(40, 65, 700, 800);
         }
 
         // 3. Check for arbs (threshold = 100 cents = $1.00)
@@ -1205,12 +1580,12 @@ mod tests {
 
         let req = FastExecutionRequest {
             market_id,
-            yes_price: p_yes,
-            no_price: k_no,
-            yes_size: p_yes_sz,
-            no_size: k_no_sz,
+            yes_price: Price(p_yes),
+            no_price: Price(k_no),
+            yes_size: Size(p_yes_sz),
+            no_size: Size(k_no_sz),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert!(req.profit_cents() > 0, "Should have positive profit");
@@ -1285,6 +1660,69 @@ pub struct KalshiMarketsResponse {
     pub markets: Vec<KalshiMarket>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct KalshiMarketResponse {
+    pub market: KalshiMarket,
+}
+
+/// `GET /markets/{ticker}/orderbook`
+#[derive(Debug, Deserialize)]
+pub struct KalshiOrderbookResponse {
+    pub orderbook: KalshiOrderbook,
+}
+
+/// Resting bids per side as [price cents, contracts] levels (absent when a side is empty)
+#[derive(Debug, Default, Deserialize)]
+pub struct KalshiOrderbook {
+    pub yes: Option<Vec<Vec<i64>>>,
+    pub no: Option<Vec<Vec<i64>>>,
+}
+
+/// `GET /exchange/schedule`
+#[derive(Debug, Deserialize)]
+pub struct KalshiExchangeScheduleResponse {
+    pub schedule: KalshiExchangeSchedule,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct KalshiExchangeSchedule {
+    #[serde(default)]
+    pub maintenance_windows: Vec<KalshiMaintenanceWindow>,
+}
+
+/// A scheduled exchange outage (RFC3339 bounds)
+#[derive(Debug, Clone, Deserialize)]
+pub struct KalshiMaintenanceWindow {
+    pub start_datetime: String,
+    pub end_datetime: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiBalanceResponse {
+    /// Available balance in cents
+    pub balance: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KalshiPositionsResponse {
+    #[serde(default)]
+    pub market_positions: Vec<KalshiMarketPosition>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct KalshiMarketPosition {
+    pub ticker: String,
+    /// Signed contracts: positive = YES, negative = NO
+    pub position: i64,
+    #[serde(default)]
+    pub market_exposure: Option<i64>,
+    #[serde(default)]
+    pub fees_paid: Option<i64>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
 pub struct KalshiMarket {
@@ -1298,6 +1736,17 @@ pub struct KalshiMarket {
     pub yes_sub_title: Option<String>,
     #[serde(default)]
     pub floor_strike: Option<f64>,
+    #[serde(default)]
+    pub close_time: Option<String>,
+    /// Venue's estimate of when the outcome is known (end of the game)
+    #[serde(default)]
+    pub expected_expiration_time: Option<String>,
+    /// "active", "closed", "determined", "settled", "finalized"
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Settlement result: "yes", "no", or "" while undetermined
+    #[serde(default)]
+    pub result: Option<String>,
     pub volume: Option<i64>,
     pub liquidity: Option<i64>,
 }
@@ -1316,6 +1765,24 @@ pub struct GammaMarket {
     pub outcome_prices: Option<String>,
     pub active: Option<bool>,
     pub closed: Option<bool>,
+    #[serde(rename = "conditionId", default)]
+    pub condition_id: Option<String>,
+    #[serde(rename = "endDate", default)]
+    pub end_date: Option<String>,
+}
+
+impl GammaMarket {
+    /// Parsed (YES, NO) CLOB token IDs
+    pub fn token_pair(&self) -> Option<(String, String)> {
+        let ids: Vec<String> = self.clob_token_ids
+            .as_ref()
+            .and_then(|s| serde_json::from_str(s).ok())?;
+        if ids.len() >= 2 {
+            Some((ids[0].clone(), ids[1].clone()))
+        } else {
+            None
+        }
+    }
 }
 
 // === Discovery Result ===
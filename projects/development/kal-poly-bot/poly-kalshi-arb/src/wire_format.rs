@@ -0,0 +1,172 @@
+// src/wire_format.rs
+// JSON, MessagePack or CBOR encoding for worker and dashboard payloads, chosen per caller
+// from its Content-Type / Accept headers
+//
+// MessagePack keeps field names (`to_vec_named`), so `skip_serializing_if`, `flatten` and
+// optional fields behave as they do in JSON; the savings come from binary numbers and no
+// quoting or whitespace. `cargo bench --bench hot_paths -- wire` reports encode/decode time
+// and payload bytes per format.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::WireError;
+
+/// Payload encoding; JSON unless the caller asks for a binary one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WireFormat {
+    #[default]
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+impl WireFormat {
+    pub const ALL: [WireFormat; 3] = [WireFormat::Json, WireFormat::MessagePack, WireFormat::Cbor];
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            WireFormat::Json => "application/json",
+            WireFormat::MessagePack => "application/msgpack",
+            WireFormat::Cbor => "application/cbor",
+        }
+    }
+
+    /// Format of a media type, parameters ignored; None for anything unsupported
+    pub fn from_media_type(value: &str) -> Option<Self> {
+        let media = value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match media.as_str() {
+            "application/json" => Some(WireFormat::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => Some(WireFormat::MessagePack),
+            "application/cbor" => Some(WireFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Format for a response given the caller's `Accept`: the supported type with the highest
+    /// q (earliest on ties); JSON when the header is absent or names nothing supported
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let mut best: Option<(f64, Self)> = None;
+        for entry in accept.unwrap_or_default().split(',') {
+            let Some(format) = Self::from_media_type(entry) else { continue };
+            let q = entry.split(';')
+                .skip(1)
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.trim().parse::<f64>().ok())
+                .unwrap_or(1.0);
+            if q > 0.0 && best.is_none_or(|(top, _)| q > top) {
+                best = Some((q, format));
+            }
+        }
+        best.map_or(WireFormat::Json, |(_, format)| format)
+    }
+
+    /// Format of a request body from its `Content-Type` (JSON when absent)
+    pub fn of_body(content_type: Option<&str>) -> Result<Self, WireError> {
+        match content_type {
+            None => Ok(WireFormat::Json),
+            Some(value) => Self::from_media_type(value).ok_or_else(|| WireError::UnsupportedMediaType(value.to_string())),
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, WireError> {
+        match self {
+            WireFormat::Json => Ok(serde_json::to_vec(value)?),
+            WireFormat::MessagePack => Ok(rmp_serde::to_vec_named(value)?),
+            WireFormat::Cbor => {
+                let mut out = Vec::new();
+                ciborium::into_writer(value, &mut out).map_err(|e| WireError::Cbor(e.to_string()))?;
+                Ok(out)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, WireError> {
+        match self {
+            WireFormat::Json => Ok(serde_json::from_slice(bytes)?),
+            WireFormat::MessagePack => Ok(rmp_serde::from_slice(bytes)?),
+            WireFormat::Cbor => ciborium::from_reader(bytes).map_err(|e| WireError::Cbor(e.to_string())),
+        }
+    }
+
+    /// Re-encode a JSON document (an admin route's body) in this format
+    pub fn transcode_json(self, json: &str) -> Result<Vec<u8>, WireError> {
+        if self == WireFormat::Json {
+            return Ok(json.as_bytes().to_vec());
+        }
+        self.encode(&serde_json::from_str::<serde_json::Value>(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tca::TcaFill;
+    use crate::types::Platform;
+
+    fn fill(tier: Option<u8>) -> TcaFill {
+        TcaFill {
+            ts_ns: 1_700_000_000_000_000_000,
+            market_id: "KXNBAGAME-25DEC01LALBOS".into(),
+            platform: Platform::Kalshi,
+            tier,
+            side: "yes".into(),
+            action: "buy".into(),
+            pattern: "74".into(),
+            strategy_id: None,
+            contracts: 25.0,
+            decision_price: 51.0,
+            arrival_price: 52.0,
+            arrival_mid: 51.5,
+            fill_price: 52.5,
+            decision_to_arrival_ns: 1_250_000,
+        }
+    }
+
+    #[test]
+    fn test_negotiation() {
+        assert_eq!(WireFormat::negotiate(None), WireFormat::Json);
+        assert_eq!(WireFormat::negotiate(Some("text/html, */*")), WireFormat::Json);
+        assert_eq!(WireFormat::negotiate(Some("application/x-msgpack")), WireFormat::MessagePack);
+        assert_eq!(WireFormat::negotiate(Some("application/json;q=0.5, application/cbor")), WireFormat::Cbor);
+        assert_eq!(
+            WireFormat::negotiate(Some("application/cbor; q=0.2, application/msgpack; q=0.9")),
+            WireFormat::MessagePack,
+        );
+        assert_eq!(WireFormat::negotiate(Some("application/msgpack;q=0, application/json")), WireFormat::Json);
+
+        assert_eq!(WireFormat::of_body(None).unwrap(), WireFormat::Json);
+        assert_eq!(WireFormat::of_body(Some("application/cbor")).unwrap(), WireFormat::Cbor);
+        assert!(matches!(WireFormat::of_body(Some("text/plain")), Err(WireError::UnsupportedMediaType(_))));
+    }
+
+    #[test]
+    fn test_round_trip_in_every_format() {
+        for format in WireFormat::ALL {
+            for original in [fill(Some(2)), fill(None)] {
+                let bytes = format.encode(&original).unwrap();
+                let decoded: TcaFill = format.decode(&bytes).unwrap();
+                assert_eq!(decoded, original, "{:?}", format);
+            }
+        }
+
+        let json = WireFormat::Json.encode(&fill(Some(2))).unwrap().len();
+        let msgpack = WireFormat::MessagePack.encode(&fill(Some(2))).unwrap().len();
+        let cbor = WireFormat::Cbor.encode(&fill(Some(2))).unwrap().len();
+        assert!(msgpack < json && cbor < json, "json {} msgpack {} cbor {}", json, msgpack, cbor);
+        assert!(WireFormat::MessagePack.decode::<TcaFill>(b"\xc1").is_err());
+    }
+
+    #[test]
+    fn test_transcode_route_body() {
+        let body = serde_json::json!({ "healthy": true, "probes": { "event_bus": { "published": 7 } } }).to_string();
+        assert_eq!(WireFormat::Json.transcode_json(&body).unwrap(), body.as_bytes());
+        for format in [WireFormat::MessagePack, WireFormat::Cbor] {
+            let bytes = format.transcode_json(&body).unwrap();
+            let value: serde_json::Value = format.decode(&bytes).unwrap();
+            assert_eq!(value["probes"]["event_bus"]["published"], 7);
+        }
+        assert!(WireFormat::Cbor.transcode_json("not json").is_err());
+    }
+}
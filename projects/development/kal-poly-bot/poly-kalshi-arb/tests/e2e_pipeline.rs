@@ -0,0 +1,378 @@
+// tests/e2e_pipeline.rs
+// End-to-end pipeline tests - run both venue feeds, the execution engine and position
+// tracking against fake Kalshi / Polymarket servers and check the orders, alerts and
+// positions each scripted scenario ends with; and run the strategy orchestrator
+// (arb_bot::runner) in paper mode on its seeded synthetic feed, checked through its
+// status endpoint
+//
+// The fakes come from arb_bot::fake_venues (feature "fake-venues", enabled for tests through
+// the self dev-dependency in Cargo.toml). Venue endpoints are overridden once per process, so
+// every test shares one fake server and scripts its own markets on it.
+
+mod pipeline_tests {
+    use arb_bot::alert_router::{Alert, AlertRouter, AlertSeverity, ChannelSink};
+    use arb_bot::audit_log::{AuditConfig, AuditEvent, AuditLog, AuditQuery, OrderAction};
+    use arb_bot::circuit_breaker::{CircuitBreakerConfig, TradingCircuitBreaker};
+    use arb_bot::clock;
+    use arb_bot::execution::{create_execution_channel, run_execution_loop, ExecutionEngine};
+    use arb_bot::fake_venues::{FakeOrder, FakeOrderStatus, FakeVenues, KalshiBook, PolyBook};
+    use arb_bot::feed_schema::{FeedSchemas, SchemaMode};
+    use arb_bot::feed_aggregator::{FeedClient, PriceUpdate};
+    use arb_bot::kalshi::{self, KalshiApiClient, KalshiConfig};
+    use arb_bot::kalshi_feed::{KalshiFeedClient, KalshiFeedMarket};
+    use arb_bot::market_cooldown::{CooldownConfig, CooldownManager};
+    use arb_bot::polymarket;
+    use arb_bot::polymarket_clob::{PolymarketAsyncClient, PreparedCreds, SharedAsyncClient};
+    use arb_bot::position_tracker::{create_position_channel, FillRecord, PositionTracker};
+    use arb_bot::secrets::Secret;
+    use arb_bot::types::{GlobalState, MarketPair, MarketType, Platform};
+    use std::sync::{Arc, OnceLock};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Throwaway wallet for signing orders the fake CLOB never checks
+    const TEST_WALLET_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const TEST_FUNDER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+    const THRESHOLD_CENTS: u16 = 99;
+
+    fn venues() -> &'static FakeVenues {
+        static VENUES: OnceLock<FakeVenues> = OnceLock::new();
+        VENUES.get_or_init(|| {
+            let venues = FakeVenues::start_detached().expect("start fake venues");
+            venues.export_env();
+            venues
+        })
+    }
+
+    /// A market whose books cross for KalshiYesPolyNo: Kalshi YES 40¢ (+2¢ fee) + Poly NO 50¢.
+    /// Kalshi quotes 6 contracts (1000 @ NO bid 60¢), Polymarket 100
+    struct Scenario {
+        pair: MarketPair,
+    }
+
+    impl Scenario {
+        fn new(name: &str) -> Self {
+            let event = format!("KXE2E-26OCT18{}", name.to_uppercase());
+            let pair = MarketPair {
+                pair_id: format!("e2e-{}", name).into(),
+                league: "e2e".into(),
+                market_type: MarketType::Moneyline,
+                description: format!("E2E {}", name).into(),
+                kalshi_event_ticker: event.clone().into(),
+                kalshi_market_ticker: format!("{}-YES", event).into(),
+                poly_slug: format!("e2e-{}", name).into(),
+                poly_yes_token: format!("{}01", e2e_token_base(name)).into(),
+                poly_no_token: format!("{}02", e2e_token_base(name)).into(),
+                line_value: None,
+                team_suffix: None,
+            };
+            let venues = venues();
+            venues.set_kalshi_book(&pair.kalshi_market_ticker, KalshiBook {
+                yes_bids: vec![(35, 1000)],
+                no_bids: vec![(60, 1000)],
+            });
+            venues.set_poly_book(&pair.poly_yes_token, PolyBook { bids: vec![(0.60, 100.0)], asks: vec![(0.70, 100.0)] });
+            venues.set_poly_book(&pair.poly_no_token, PolyBook { bids: vec![(0.45, 100.0)], asks: vec![(0.50, 100.0)] });
+            Self { pair }
+        }
+
+        fn orders(&self) -> Vec<FakeOrder> {
+            let venues = venues();
+            [&*self.pair.kalshi_market_ticker, &*self.pair.poly_yes_token, &*self.pair.poly_no_token]
+                .into_iter()
+                .flat_map(|market| venues.orders_for(market))
+                .collect()
+        }
+
+        async fn wait_for_orders(&self, count: usize) -> Vec<FakeOrder> {
+            for _ in 0..100 {
+                let orders = self.orders();
+                if orders.len() >= count {
+                    return orders;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            self.orders()
+        }
+    }
+
+    /// Token ids are decimal strings on Polymarket
+    fn e2e_token_base(name: &str) -> String {
+        name.bytes().map(|b| format!("{:03}", b)).collect()
+    }
+
+    /// Feeds -> execution engine -> position channel, as main wires them
+    struct Pipeline {
+        audit: Arc<AuditLog>,
+        alerts: mpsc::UnboundedReceiver<Alert>,
+        fills: mpsc::UnboundedReceiver<FillRecord>,
+    }
+
+    impl Pipeline {
+        async fn start(scenario: &Scenario, dry_run: bool) -> Self {
+            let venues = venues();
+            let mut state = GlobalState::new();
+            state.add_pair(scenario.pair.clone()).expect("market slot");
+            let state = Arc::new(state);
+
+            let kalshi = Arc::new(KalshiApiClient::new(kalshi_config()));
+            let poly_client = PolymarketAsyncClient::new(&venues.poly_clob_url(), 137, &Secret::new(TEST_WALLET_KEY), TEST_FUNDER)
+                .expect("poly client");
+            let creds = PreparedCreds::from_api_creds(&poly_client.derive_api_key(0).await.expect("derive api key"))
+                .expect("prepared creds");
+            let poly = Arc::new(SharedAsyncClient::new(poly_client, creds, 137));
+
+            let mut router = AlertRouter::new(Duration::from_secs(60));
+            let (sink, alerts) = ChannelSink::new();
+            router.add_sink(AlertSeverity::Info, Arc::new(sink));
+            let router = Arc::new(router);
+            let cooldowns = CooldownManager::new(CooldownConfig { limit_streak: 1, ..CooldownConfig::default() })
+                .on_event(move |event| {
+                    router.route(event.to_alert());
+                });
+
+            let run = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+            let audit_dir = std::env::temp_dir().join(format!("e2e-audit-{}-{}", scenario.pair.pair_id, run));
+            let audit = Arc::new(AuditLog::open(AuditConfig { dir: audit_dir, fsync: false }).expect("audit log"));
+
+            let (position_channel, fills) = create_position_channel();
+            let engine = ExecutionEngine::new(
+                kalshi,
+                poly,
+                state.clone(),
+                Arc::new(TradingCircuitBreaker::new(breaker_config())),
+                position_channel,
+                dry_run,
+            )
+            .with_audit_log(audit.clone())
+            .with_cooldowns(Arc::new(cooldowns));
+
+            let (exec_tx, exec_rx) = create_execution_channel();
+            tokio::spawn(run_execution_loop(exec_rx, Arc::new(engine)));
+            let schemas = Arc::new(FeedSchemas::new(SchemaMode::Strict));
+            let (kalshi_state, kalshi_tx, kalshi_schemas) = (state.clone(), exec_tx.clone(), schemas.clone());
+            tokio::spawn(async move {
+                let _ = kalshi::run_ws(&kalshi_config(), &kalshi_schemas, kalshi_state, kalshi_tx, THRESHOLD_CENTS, clock::system()).await;
+            });
+            tokio::spawn(async move {
+                let _ = polymarket::run_ws(&schemas, state, exec_tx, THRESHOLD_CENTS, clock::system()).await;
+            });
+
+            Self { audit, alerts, fills }
+        }
+
+        async fn order_actions(&self, market: &str) -> Vec<(OrderAction, Option<i64>)> {
+            for _ in 0..100 {
+                let actions: Vec<_> = self.audit.query(&AuditQuery::market(market)).expect("audit query")
+                    .into_iter()
+                    .filter_map(|record| match record.event {
+                        AuditEvent::Order { action, contracts, .. } if action != OrderAction::Submitted => Some((action, contracts)),
+                        _ => None,
+                    })
+                    .collect();
+                if !actions.is_empty() {
+                    return actions;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            Vec::new()
+        }
+
+        /// Apply the fills recorded so far, as the position writer would
+        async fn positions(&mut self) -> PositionTracker {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let mut tracker = PositionTracker::new();
+            while let Ok(fill) = self.fills.try_recv() {
+                tracker.record_fill_internal(&fill);
+            }
+            tracker
+        }
+    }
+
+    fn kalshi_config() -> KalshiConfig {
+        static KEY: OnceLock<rsa::RsaPrivateKey> = OnceLock::new();
+        let private_key = KEY.get_or_init(|| rsa::RsaPrivateKey::new(&mut rand::thread_rng(), 1024).expect("rsa key")).clone();
+        KalshiConfig { api_key_id: "e2e-key".to_string(), private_key }
+    }
+
+    fn breaker_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            max_position_per_market: 1000,
+            max_total_position: 10000,
+            max_daily_loss: 1000.0,
+            max_consecutive_errors: 10,
+            cooldown_secs: 60,
+            enabled: true,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_paper_mode_simulates_without_sending_orders() {
+        let scenario = Scenario::new("paper");
+        let mut pipeline = Pipeline::start(&scenario, true).await;
+
+        assert_eq!(pipeline.order_actions(&scenario.pair.pair_id).await, vec![(OrderAction::Simulated, Some(6))]);
+        assert!(scenario.orders().is_empty());
+        assert!(pipeline.positions().await.open_positions().is_empty());
+        assert!(pipeline.alerts.try_recv().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_crossed_books_fill_both_legs_into_a_matched_position() {
+        let scenario = Scenario::new("filled");
+        let mut pipeline = Pipeline::start(&scenario, false).await;
+
+        let orders = scenario.wait_for_orders(2).await;
+        assert_eq!(orders.len(), 2, "{:?}", orders);
+        let kalshi = orders.iter().find(|o| o.venue == Platform::Kalshi).expect("kalshi order");
+        assert_eq!((kalshi.action.as_str(), kalshi.side.as_deref(), kalshi.price_cents), ("buy", Some("yes"), 40));
+        assert_eq!((kalshi.filled, kalshi.fill_cost_cents, kalshi.status), (6.0, 240, FakeOrderStatus::Filled));
+        let poly = orders.iter().find(|o| o.venue == Platform::Polymarket).expect("poly order");
+        assert_eq!((poly.market.as_str(), poly.action.as_str(), poly.price_cents), (&*scenario.pair.poly_no_token, "buy", 50));
+        assert_eq!((poly.filled, poly.status), (6.0, FakeOrderStatus::Filled));
+        // Fills came off the scripted books
+        assert_eq!(venues().kalshi_book(&scenario.pair.kalshi_market_ticker).unwrap().no_bids, vec![(60, 994)]);
+
+        assert_eq!(pipeline.order_actions(&scenario.pair.pair_id).await, vec![(OrderAction::Filled, Some(6))]);
+        let tracker = pipeline.positions().await;
+        let position = tracker.get(&scenario.pair.pair_id).expect("position");
+        assert_eq!((position.kalshi_yes.contracts, position.poly_no.contracts), (6.0, 6.0));
+        assert_eq!(position.matched_contracts(), 6.0);
+        assert!((position.guaranteed_profit() - 0.60).abs() < 1e-6, "{}", position.guaranteed_profit());
+        assert!(pipeline.alerts.try_recv().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_refused_leg_unwinds_the_other_and_benches_the_market() {
+        let scenario = Scenario::new("refused");
+        venues().reject_orders(&scenario.pair.poly_no_token);
+        let mut pipeline = Pipeline::start(&scenario, false).await;
+
+        // Kalshi YES fills, the Polymarket NO leg is refused, the naked YES is sold back 10¢ under entry
+        let orders = scenario.wait_for_orders(3).await;
+        let summary: Vec<_> = orders.iter()
+            .map(|o| (o.venue, o.action.as_str(), o.price_cents, o.filled, o.status))
+            .collect();
+        assert!(summary.contains(&(Platform::Kalshi, "buy", 40, 6.0, FakeOrderStatus::Filled)), "{:?}", summary);
+        assert!(summary.contains(&(Platform::Polymarket, "buy", 50, 0.0, FakeOrderStatus::Rejected)), "{:?}", summary);
+        assert!(summary.contains(&(Platform::Kalshi, "sell", 30, 6.0, FakeOrderStatus::Filled)), "{:?}", summary);
+
+        assert_eq!(pipeline.order_actions(&scenario.pair.pair_id).await, vec![(OrderAction::PartiallyFilled, Some(0))]);
+        let alert = tokio::time::timeout(Duration::from_secs(5), pipeline.alerts.recv()).await
+            .expect("cooldown alert")
+            .expect("alert channel open");
+        assert_eq!((alert.source.as_str(), alert.kind.as_str()), ("cooldown", "market_cooldown"));
+        assert_eq!(alert.market_id.as_deref(), Some(&*scenario.pair.pair_id));
+        assert!(pipeline.positions().await.get(&scenario.pair.pair_id).is_none());
+    }
+
+    async fn next_price(prices: &mut mpsc::UnboundedReceiver<PriceUpdate>) -> PriceUpdate {
+        tokio::time::timeout(Duration::from_secs(5), prices.recv()).await.expect("price update").expect("stream open")
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_kalshi_feed_client_streams_book_updates() {
+        let scenario = Scenario::new("feed");
+        let ticker = scenario.pair.kalshi_market_ticker.to_string();
+        let market = KalshiFeedMarket { ticker: ticker.clone(), market_id: 7, market_type: MarketType::Moneyline };
+        let mut client = KalshiFeedClient::new(Arc::new(kalshi_config()), vec![market]);
+        let mut prices = client.price_stream();
+        client.connect().await.expect("connect");
+
+        // YES ask off the 60¢ NO bid, NO ask off the 35¢ YES bid
+        let update = next_price(&mut prices).await;
+        assert_eq!((update.market_id, update.provider, update.market_type), (7, Platform::Kalshi.into(), MarketType::Moneyline));
+        assert_eq!((update.yes_price, update.no_price, update.yes_size, update.no_size), (40, 65, 600, 350));
+        assert!(update.provider_timestamp.is_none());
+
+        venues().set_kalshi_book(&ticker, KalshiBook { yes_bids: vec![(35, 1000)], no_bids: vec![(62, 500)] });
+        let update = next_price(&mut prices).await;
+        assert_eq!((update.yes_price, update.yes_size), (38, 310));
+
+        assert!(client.ping().await.expect("pong") > 0);
+        client.disconnect().await.expect("disconnect");
+        assert!(!client.is_connected());
+        assert!(client.ping().await.is_err());
+    }
+}
+
+mod runner_tests {
+    use arb_bot::config::{AppConfig, CliArgs};
+    use arb_bot::runner::{self, SyntheticFeed};
+    use arb_bot::runtime_profile::RuntimeProfile;
+    use arb_bot::supervisor::{SubsystemState, SupervisorStatus};
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio::sync::oneshot;
+
+    const SUBSYSTEMS: [&str; 7] = ["config", "monitoring", "risk", "feeds", "arbitrage", "execution", "audit"];
+
+    /// Paper fills on, audit log in a scratch dir; everything else as shipped
+    fn paper_config(name: &str) -> AppConfig {
+        let run = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+        let mut config = AppConfig::default();
+        config.paper_fills.enabled = true;
+        config.audit.enabled = true;
+        config.audit.dir = std::env::temp_dir().join(format!("e2e-runner-{}-{}", name, run)).display().to_string();
+        config
+    }
+
+    async fn status(addr: SocketAddr) -> Option<SupervisorStatus> {
+        reqwest::get(format!("http://{}/status", addr)).await.ok()?.json().await.ok()
+    }
+
+    fn received(status: &SupervisorStatus, subscriber: &str) -> u64 {
+        status.probes["event_bus"].as_array()
+            .and_then(|subs| subs.iter().find(|s| s["name"] == subscriber))
+            .and_then(|s| s["received"].as_u64())
+            .unwrap_or(0)
+    }
+
+    /// Poll `/status` until every subsystem is up, the synthetic ticks have reached the
+    /// arbitrage and risk engines over the bus and the dashboard has published a snapshot
+    async fn wait_for_ticks(addr: SocketAddr) -> SupervisorStatus {
+        let mut last = None;
+        for _ in 0..200 {
+            if let Some(status) = status(addr).await {
+                let pushed = status.probes["feed_ingest"]["pushed"].as_u64().unwrap_or(0);
+                let flowing = pushed > 0 && received(&status, "arbitrage") > 0 && received(&status, "risk") > 0;
+                if status.healthy && flowing && status.probes["dashboard"].is_object() {
+                    return status;
+                }
+                last = Some(status);
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("runner never settled: {:#?}", last)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_runner_trades_the_synthetic_feed_in_paper_mode() {
+        let config = paper_config("paper");
+        let profile = RuntimeProfile::from_config(&config.runtime);
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind status endpoint");
+        let addr = listener.local_addr().expect("status address");
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let feed = SyntheticFeed { seed: 7, tick_interval_ms: 5.0 };
+        let run = tokio::spawn(runner::run(config, None, CliArgs::default(), profile, feed, listener, async {
+            let _ = stop_rx.await;
+        }));
+
+        let status = wait_for_ticks(addr).await;
+        for name in SUBSYSTEMS {
+            let subsystem = status.subsystems.iter().find(|s| s.name == name).unwrap_or_else(|| panic!("no {} subsystem", name));
+            assert_eq!(subsystem.state, SubsystemState::Ready, "{:?}", subsystem);
+            assert_eq!(subsystem.restarts, 0, "{:?}", subsystem);
+        }
+        // Paper mode runs no market making, archive or backtests
+        assert_eq!(status.subsystems.len(), SUBSYSTEMS.len(), "{:?}", status.subsystems);
+
+        stop_tx.send(()).expect("runner still running");
+        tokio::time::timeout(Duration::from_secs(30), run).await
+            .expect("runner shut down")
+            .expect("runner task")
+            .expect("runner result");
+        assert!(reqwest::get(format!("http://{}/status", addr)).await.is_err());
+    }
+}
@@ -0,0 +1,480 @@
+// tests/fault_injection_tests.rs
+// Chaos tests - inject feed, venue API and state store faults and verify that the
+// circuit breakers, feed reconnects and the trading kill switch behave as designed
+//
+// Faults come from arb_bot::fault_injection (feature "fault-injection", enabled for tests
+// through the self dev-dependency in Cargo.toml). Injectors are seeded, so runs repeat.
+
+// ============================================================================
+// VENUE API FAULTS - Keyed breaker opens on server errors, recovers via probes
+// ============================================================================
+
+mod venue_fault_tests {
+    use arb_bot::circuit_breaker::*;
+    use arb_bot::error::{Retryable, VenueApiError};
+    use arb_bot::fault_injection::*;
+    use arb_bot::types::Platform;
+    use std::time::Duration;
+
+    fn venue_breaker() -> CircuitBreaker<Platform> {
+        CircuitBreaker::new(BreakerConfig {
+            window: Duration::from_secs(60),
+            min_calls: 4,
+            failure_rate: 0.5,
+            open_for: Duration::from_millis(50),
+            half_open_probes: 1,
+        })
+    }
+
+    async fn place_order(
+        injector: &FaultInjector,
+        breaker: &CircuitBreaker<Platform>,
+    ) -> Result<u32, BreakerError<VenueApiError>> {
+        let call = injector.venue_call(Platform::Kalshi, async { Ok::<_, VenueApiError>(7) });
+        breaker.call(Platform::Kalshi, call).await
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_open_breaker_then_probe_closes() {
+        let injector = FaultInjector::new(1);
+        let breaker = venue_breaker();
+        injector.fail_venue(Platform::Kalshi, VenueFault::ServerError { status: 503 }, 4);
+
+        for _ in 0..4 {
+            match place_order(&injector, &breaker).await {
+                Err(BreakerError::Inner(VenueApiError::Http { status: 503, .. })) => {}
+                other => panic!("expected injected 503, got {:?}", other),
+            }
+        }
+        assert_eq!(breaker.state(&Platform::Kalshi), BreakerState::Open);
+        assert_eq!(breaker.state(&Platform::Polymarket), BreakerState::Closed, "Other venues unaffected");
+
+        // Open circuit: the call is rejected without reaching the venue
+        assert!(matches!(place_order(&injector, &breaker).await, Err(BreakerError::Open { .. })));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(breaker.state(&Platform::Kalshi), BreakerState::HalfOpen);
+        assert_eq!(place_order(&injector, &breaker).await.unwrap(), 7, "Probe succeeds once the venue recovers");
+        assert_eq!(breaker.state(&Platform::Kalshi), BreakerState::Closed);
+        assert_eq!(injector.stats().venue_failures, 4);
+    }
+
+    #[tokio::test]
+    async fn test_failed_probe_reopens_breaker() {
+        let injector = FaultInjector::new(1);
+        let breaker = venue_breaker();
+        injector.fail_venue(Platform::Kalshi, VenueFault::ServerError { status: 502 }, 5);
+
+        for _ in 0..4 {
+            assert!(place_order(&injector, &breaker).await.is_err());
+        }
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        // Venue still failing: the single probe fails and the circuit re-opens
+        assert!(matches!(place_order(&injector, &breaker).await, Err(BreakerError::Inner(_))));
+        assert_eq!(breaker.state(&Platform::Kalshi), BreakerState::Open);
+        assert!(matches!(place_order(&injector, &breaker).await, Err(BreakerError::Open { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_injected_error_classes_keep_retryability() {
+        let injector = FaultInjector::new(1);
+        let cases = [
+            (VenueFault::RateLimited { retry_after: Some(Duration::from_secs(2)) }, true),
+            (VenueFault::ServerError { status: 502 }, true),
+            (VenueFault::ServerError { status: 400 }, false),
+            (VenueFault::Unauthorized, false),
+            (VenueFault::Decode, false),
+            (VenueFault::InvalidOrder, false),
+        ];
+
+        for (fault, retryable) in cases {
+            injector.fail_venue(Platform::Polymarket, fault.clone(), 1);
+            let err = injector
+                .venue_call(Platform::Polymarket, async { Ok::<_, VenueApiError>(()) })
+                .await
+                .unwrap_err();
+            assert_eq!(err.is_retryable(), retryable, "{:?}", fault);
+            assert_eq!(err.venue(), Platform::Polymarket);
+        }
+
+        injector.fail_venue(Platform::Kalshi, VenueFault::RateLimited { retry_after: Some(Duration::from_secs(2)) }, 1);
+        let err = injector.venue_call(Platform::Kalshi, async { Ok::<_, VenueApiError>(()) }).await.unwrap_err();
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(2)));
+
+        // Queue drained: calls pass through again
+        assert!(injector.venue_call(Platform::Kalshi, async { Ok::<_, VenueApiError>(()) }).await.is_ok());
+    }
+}
+
+// ============================================================================
+// KILL SWITCH - Trading halt on consecutive venue failures and manual halt
+// ============================================================================
+
+mod kill_switch_tests {
+    use arb_bot::circuit_breaker::*;
+    use arb_bot::error::VenueApiError;
+    use arb_bot::fault_injection::*;
+    use arb_bot::types::Platform;
+    use std::time::Duration;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            max_position_per_market: 100,
+            max_total_position: 500,
+            max_daily_loss: 1000.0,
+            max_consecutive_errors: 3,
+            cooldown_secs: 60,
+            enabled: true,
+        }
+    }
+
+    /// One execution attempt the way the engine gates it: check, call venue, record outcome
+    async fn attempt(cb: &TradingCircuitBreaker, injector: &FaultInjector) -> Result<(), String> {
+        cb.can_execute("MKT-1", 1).await.map_err(|reason| reason.to_string())?;
+        match injector.venue_call(Platform::Kalshi, async { Ok::<_, VenueApiError>(()) }).await {
+            Ok(()) => {
+                cb.record_success("MKT-1", 1, 1, 0.0).await;
+                Ok(())
+            }
+            Err(e) => {
+                cb.record_error().await;
+                Err(e.to_string())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_venue_failures_trip_kill_switch() {
+        let cb = TradingCircuitBreaker::new(config());
+        let injector = FaultInjector::new(1);
+        injector.fail_venue(Platform::Kalshi, VenueFault::ServerError { status: 500 }, 3);
+
+        for _ in 0..3 {
+            assert!(attempt(&cb, &injector).await.is_err());
+        }
+        assert!(!cb.is_trading_allowed());
+
+        // Venue has recovered, but the halt holds until reset and no call is attempted
+        match cb.can_execute("MKT-1", 1).await {
+            Err(TripReason::ConsecutiveErrors { count: 3, limit: 3 }) => {}
+            other => panic!("expected consecutive-error halt, got {:?}", other),
+        }
+        assert!(attempt(&cb, &injector).await.is_err());
+        assert_eq!(injector.stats().venue_failures, 3);
+
+        cb.reset().await;
+        assert!(attempt(&cb, &injector).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_success_between_failures_resets_streak() {
+        let cb = TradingCircuitBreaker::new(config());
+        let injector = FaultInjector::new(1);
+
+        injector.fail_venue(Platform::Kalshi, VenueFault::RateLimited { retry_after: None }, 2);
+        assert!(attempt(&cb, &injector).await.is_err());
+        assert!(attempt(&cb, &injector).await.is_err());
+        assert!(attempt(&cb, &injector).await.is_ok());
+
+        injector.fail_venue(Platform::Kalshi, VenueFault::RateLimited { retry_after: None }, 2);
+        assert!(attempt(&cb, &injector).await.is_err());
+        assert!(attempt(&cb, &injector).await.is_err());
+
+        assert!(cb.is_trading_allowed(), "Intermittent failures must not halt trading");
+        assert_eq!(cb.status().await.consecutive_errors, 2);
+    }
+
+    #[tokio::test]
+    async fn test_manual_halt_blocks_until_cooldown() {
+        let cb = TradingCircuitBreaker::new(CircuitBreakerConfig { cooldown_secs: 0, ..config() });
+        let injector = FaultInjector::new(1);
+
+        cb.halt().await;
+        assert_eq!(cb.can_execute("MKT-1", 1).await, Err(TripReason::ManualHalt));
+        assert!(attempt(&cb, &injector).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(cb.check_cooldown().await);
+        assert!(attempt(&cb, &injector).await.is_ok());
+    }
+}
+
+// ============================================================================
+// FEED FAULTS - Dropped/delayed/duplicated messages and connect failures
+// ============================================================================
+
+mod feed_fault_tests {
+    use arb_bot::circuit_breaker::{BreakerConfig, BreakerState, CircuitBreaker};
+    use arb_bot::clock;
+    use arb_bot::error::FeedError;
+    use arb_bot::fault_injection::*;
+    use arb_bot::feed_aggregator::*;
+    use arb_bot::latency_arbitrage::LatencyArbitrageEngine;
+    use arb_bot::provider_registry::ProviderId;
+    use arb_bot::types::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use tokio::sync::{mpsc, RwLock};
+
+    /// Feed that always connects and replays a fixed set of updates
+    struct ScriptedFeed {
+        updates: Vec<PriceUpdate>,
+        connects: u32,
+        /// Markets of every subscribe call
+        subscribed: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl ScriptedFeed {
+        fn new(count: u16) -> Self {
+            Self { updates: (0..count).map(update).collect(), connects: 0, subscribed: Arc::default() }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl FeedClient for ScriptedFeed {
+        fn provider(&self) -> ProviderId {
+            Platform::Polymarket.into()
+        }
+
+        async fn connect(&mut self) -> Result<(), FeedError> {
+            self.connects += 1;
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<(), FeedError> {
+            Ok(())
+        }
+
+        fn price_stream(&mut self) -> mpsc::UnboundedReceiver<PriceUpdate> {
+            let (tx, rx) = mpsc::unbounded_channel();
+            for update in self.updates.drain(..) {
+                let _ = tx.send(update);
+            }
+            rx
+        }
+
+        async fn ping(&mut self) -> Result<u64, FeedError> {
+            Ok(1_000)
+        }
+
+        async fn subscribe(&mut self, markets: &[String]) -> Result<(), FeedError> {
+            self.subscribed.lock().unwrap().push(markets.to_vec());
+            Ok(())
+        }
+    }
+
+    fn update(seq: u16) -> PriceUpdate {
+        PriceUpdate {
+            market_id: seq,
+            provider: Platform::Polymarket.into(),
+            market_type: MarketType::Moneyline,
+            yes_price: 40,
+            no_price: 60,
+            yes_size: 100,
+            no_size: 100,
+            received: clock::system().now(),
+            provider_timestamp: None,
+            sequence: None,
+            features: None,
+        }
+    }
+
+    async fn drain(mut stream: mpsc::UnboundedReceiver<PriceUpdate>) -> Vec<u16> {
+        let mut seen = Vec::new();
+        while let Some(update) = stream.recv().await {
+            seen.push(update.market_id);
+        }
+        seen
+    }
+
+    fn status(aggregator: &FeedAggregator) -> FeedStatus {
+        aggregator.get_status_summary()[&Platform::Polymarket.into()].0
+    }
+
+    #[tokio::test]
+    async fn test_dropped_and_duplicated_messages_are_accounted() {
+        let injector = Arc::new(FaultInjector::new(7));
+        injector.set_feed_faults(FeedFaults { drop_rate: 0.2, duplicate_rate: 0.1, delay: None });
+        let mut client = FaultyFeedClient::new(ScriptedFeed::new(500), injector.clone());
+
+        let seen = drain(client.price_stream()).await;
+        let stats = injector.stats();
+
+        assert!(stats.feed_dropped > 0 && stats.feed_duplicated > 0);
+        assert_eq!(seen.len() as u64, stats.feed_delivered);
+        assert_eq!(500 - stats.feed_dropped + stats.feed_duplicated, stats.feed_delivered);
+        // Faults never reorder: duplicates arrive back to back
+        assert!(seen.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[tokio::test]
+    async fn test_same_seed_replays_same_faults() {
+        let run = |seed| async move {
+            let injector = Arc::new(FaultInjector::new(seed));
+            injector.set_feed_faults(FeedFaults { drop_rate: 0.3, duplicate_rate: 0.3, delay: None });
+            drain(FaultyFeedClient::new(ScriptedFeed::new(200), injector).price_stream()).await
+        };
+        assert_eq!(run(11).await, run(11).await);
+    }
+
+    #[tokio::test]
+    async fn test_delay_holds_messages_in_order() {
+        let injector = Arc::new(FaultInjector::new(1));
+        injector.set_feed_faults(FeedFaults { delay: Some(Duration::from_millis(30)), ..FeedFaults::default() });
+        let mut client = FaultyFeedClient::new(ScriptedFeed::new(20), injector);
+
+        let start = Instant::now();
+        let seen = drain(client.price_stream()).await;
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(seen, (0..20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_connect_failures_open_feed_breaker_then_reconnect() {
+        let injector = Arc::new(FaultInjector::new(1));
+        let breaker = Arc::new(CircuitBreaker::new(BreakerConfig {
+            window: Duration::from_secs(60),
+            min_calls: 3,
+            failure_rate: 0.5,
+            open_for: Duration::from_millis(50),
+            half_open_probes: 1,
+        }));
+        let engine = Arc::new(RwLock::new(LatencyArbitrageEngine::new()));
+        let (aggregator, _updates) = FeedAggregator::new(FeedAggregatorConfig::default(), engine);
+        let mut aggregator = aggregator.with_breaker(breaker.clone());
+        aggregator.add_provider(Platform::Polymarket);
+
+        injector.fail_connects(Platform::Polymarket, 3);
+        let mut client = FaultyFeedClient::new(ScriptedFeed::new(0), injector.clone());
+
+        for _ in 0..3 {
+            assert!(!aggregator.connect_client(&mut client).await);
+            assert_eq!(status(&aggregator), FeedStatus::Error);
+        }
+        assert_eq!(breaker.state(&Platform::Polymarket), BreakerState::Open);
+
+        // Reconnects while open are skipped without touching the venue
+        assert!(!aggregator.connect_client(&mut client).await);
+        assert_eq!(status(&aggregator), FeedStatus::Disconnected);
+        assert_eq!(client.inner().connects, 0);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(aggregator.connect_client(&mut client).await);
+        assert_eq!(status(&aggregator), FeedStatus::Connected);
+        assert_eq!(client.inner().connects, 1);
+        assert_eq!(breaker.state(&Platform::Polymarket), BreakerState::Closed);
+        assert_eq!(injector.stats().connect_failures, 3);
+    }
+
+    #[tokio::test]
+    async fn test_check_connections_backs_off_opens_circuit_and_resubscribes() {
+        let injector = Arc::new(FaultInjector::new(1));
+        let reconnect = ReconnectPolicy {
+            base_delay: Duration::from_millis(20),
+            max_delay: Duration::from_millis(40),
+            jitter: 0.0,
+            max_attempts: 2,
+            cooldown: Duration::from_millis(60),
+        };
+        let engine = Arc::new(RwLock::new(LatencyArbitrageEngine::new()));
+        let (mut aggregator, _updates) = FeedAggregator::new(FeedAggregatorConfig { reconnect, ..Default::default() }, engine);
+        let feed = ScriptedFeed::new(0);
+        let subscribed = feed.subscribed.clone();
+        aggregator.add_client(Box::new(FaultyFeedClient::new(feed, injector.clone())));
+        let polymarket = ProviderId::from(Platform::Polymarket);
+
+        // Not connected yet: remembered for the connect
+        aggregator.subscribe(polymarket, &["PM-1".to_string(), "PM-2".to_string()]).await.unwrap();
+        assert!(subscribed.lock().unwrap().is_empty());
+
+        injector.fail_connects(Platform::Polymarket, 2);
+        assert!(!aggregator.connect_provider(polymarket).await);
+        assert!(matches!(aggregator.connection(polymarket).unwrap().reconnect, ReconnectState::Backoff { .. }));
+        // Not due yet
+        aggregator.check_connections().await;
+        assert_eq!(injector.stats().connect_failures, 1);
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        aggregator.check_connections().await;
+        let conn = aggregator.connection(polymarket).unwrap();
+        assert_eq!((conn.reconnect_attempts, injector.stats().connect_failures), (2, 2));
+        assert!(matches!(conn.reconnect, ReconnectState::CircuitOpen { .. }));
+
+        // Still cooling down
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        aggregator.check_connections().await;
+        assert_eq!(status(&aggregator), FeedStatus::Error);
+
+        // Cooldown over: one more attempt, which connects and resubscribes
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        aggregator.check_connections().await;
+        assert_eq!(status(&aggregator), FeedStatus::Connected);
+        let conn = aggregator.connection(polymarket).unwrap();
+        assert_eq!((conn.reconnect, conn.reconnect_attempts), (ReconnectState::Idle, 0));
+        assert_eq!(*subscribed.lock().unwrap(), vec![vec!["PM-1".to_string(), "PM-2".to_string()]]);
+
+        // Connected: new markets go straight to the feed
+        aggregator.subscribe(polymarket, &["PM-3".to_string()]).await.unwrap();
+        assert_eq!(subscribed.lock().unwrap().len(), 2);
+        assert_eq!(aggregator.connection(polymarket).unwrap().subscriptions.len(), 3);
+    }
+}
+
+// ============================================================================
+// STATE STORE FAULTS - Stalled or failing second tier degrades to memory
+// ============================================================================
+
+mod state_store_fault_tests {
+    use arb_bot::cache::*;
+    use arb_bot::fault_injection::*;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn faulty_cache(injector: &SharedFaultInjector, store: &Arc<MemoryBackend>) -> TieredCache<u32> {
+        let mut config = CacheNamespace::PlayerStats.config();
+        config.l2_timeout = Duration::from_millis(50);
+        TieredCache::with_config(CacheNamespace::PlayerStats, config)
+            .with_backend(Arc::new(FaultyBackend::new(store.clone(), injector.clone())))
+    }
+
+    #[tokio::test]
+    async fn test_stalled_store_does_not_block_callers() {
+        let injector: SharedFaultInjector = Arc::new(FaultInjector::new(1));
+        let store = Arc::new(MemoryBackend::default());
+        let cache = faulty_cache(&injector, &store);
+        injector.set_store_fault(Some(StoreFault::Stall(Duration::from_secs(5))));
+
+        let start = Instant::now();
+        cache.insert("lebron", 30).await;
+        assert_eq!(cache.get("lebron").await, Some(30), "Memory tier still updated");
+        assert_eq!(cache.get("curry").await, None, "Stalled lookup degrades to a miss");
+        assert!(start.elapsed() < Duration::from_secs(1), "Bounded by l2_timeout, not the stall");
+        assert!(!store.contains("player:lebron"));
+        assert_eq!(injector.stats().store_stalls, 2);
+
+        // Store recovers: write-through resumes
+        injector.set_store_fault(None);
+        cache.insert("curry", 28).await;
+        assert!(store.contains("player:curry"));
+    }
+
+    #[tokio::test]
+    async fn test_failing_store_falls_back_then_recovers() {
+        let injector: SharedFaultInjector = Arc::new(FaultInjector::new(1));
+        let store = Arc::new(MemoryBackend::default());
+        let writer = faulty_cache(&injector, &store);
+        let reader = faulty_cache(&injector, &store);
+
+        injector.set_store_fault(Some(StoreFault::Fail));
+        writer.insert("jokic", 26).await;
+        assert_eq!(writer.get_hot("jokic"), Some(26));
+        assert_eq!(reader.get("jokic").await, None, "Failed write-through is not visible to peers");
+
+        injector.set_store_fault(None);
+        writer.insert("jokic", 26).await;
+        assert_eq!(reader.get("jokic").await, Some(26));
+        assert_eq!(reader.metrics().l2_hits, 1);
+        assert_eq!(injector.stats().store_failures, 2);
+    }
+}
@@ -154,7 +154,7 @@ mod circuit_breaker_tests {
     /// Test: Allows trades within limits
     #[tokio::test]
     async fn test_allows_trades_within_limits() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // First trade should be allowed
         let result = cb.can_execute("market1", 10).await;
@@ -171,7 +171,7 @@ mod circuit_breaker_tests {
     /// Test: Blocks trade exceeding per-market limit
     #[tokio::test]
     async fn test_blocks_per_market_limit() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // Fill up the market
         cb.record_success("market1", 45, 45, 1.0).await;
@@ -186,7 +186,7 @@ mod circuit_breaker_tests {
     /// Test: Blocks trade exceeding total position limit
     #[tokio::test]
     async fn test_blocks_total_position_limit() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // Fill up multiple markets
         cb.record_success("market1", 50, 50, 1.0).await;
@@ -204,7 +204,7 @@ mod circuit_breaker_tests {
     /// Test: Consecutive errors trip the breaker
     #[tokio::test]
     async fn test_consecutive_errors_trip() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // Record errors up to limit
         cb.record_error().await;
@@ -225,7 +225,7 @@ mod circuit_breaker_tests {
     /// Test: Success resets error count
     #[tokio::test]
     async fn test_success_resets_errors() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // Record 2 errors
         cb.record_error().await;
@@ -247,7 +247,7 @@ mod circuit_breaker_tests {
     /// Test: Manual reset clears halt
     #[tokio::test]
     async fn test_manual_reset() {
-        let cb = CircuitBreaker::new(test_config());
+        let cb = TradingCircuitBreaker::new(test_config());
         
         // Trip the breaker
         cb.record_error().await;
@@ -269,7 +269,7 @@ mod circuit_breaker_tests {
     async fn test_disabled_allows_all() {
         let mut config = test_config();
         config.enabled = false;
-        let cb = CircuitBreaker::new(config);
+        let cb = TradingCircuitBreaker::new(config);
         
         // Should allow even excessive trades
         let result = cb.can_execute("market1", 1000).await;
@@ -308,7 +308,7 @@ mod e2e_tests {
             enabled: true,
         };
         
-        let cb = CircuitBreaker::new(config);
+        let cb = TradingCircuitBreaker::new(config);
         
         // Simulate a series of losing trades
         // (In reality this would come from actual fill data)
@@ -461,7 +461,7 @@ mod infra_integration_tests {
         kalshi_no: PriceCents,
         poly_yes: PriceCents,
         poly_no: PriceCents,
-    ) -> (GlobalState, u16) {
+    ) -> (GlobalState, MarketId) {
         let mut state = GlobalState::new();
 
         let pair = MarketPair {
@@ -615,13 +615,13 @@ mod infra_integration_tests {
         // Kalshi fee on 50¢ = 2¢
         // Profit = 100 - 90 - 2 = 8¢
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert_eq!(req.profit_cents(), 8, "Profit should be 8¢");
@@ -632,13 +632,13 @@ mod infra_integration_tests {
     fn test_execution_request_negative_profit() {
         // Prices too high - no profit
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 52,
-            no_price: 52,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(52),
+            no_price: Price(52),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         assert!(req.profit_cents() < 0, "Should calculate negative profit");
@@ -693,15 +693,15 @@ mod infra_integration_tests {
             };
 
             let id = state.add_pair(pair).unwrap();
-            assert_eq!(id, i as u16);
+            assert_eq!(id, MarketId(i));
         }
 
         assert_eq!(state.market_count(), 5);
 
         // All should be findable
         for i in 0..5 {
-            assert!(state.get_by_id(i as u16).is_some());
-            assert_eq!(state.id_by_kalshi_hash(fxhash_str(&format!("KXTEST-{}-YES", i))), Some(i as u16));
+            assert!(state.get_by_id(MarketId(i)).is_some());
+            assert_eq!(state.id_by_kalshi_hash(fxhash_str(&format!("KXTEST-{}-YES", i))), Some(MarketId(i)));
         }
     }
 
@@ -752,17 +752,17 @@ mod infra_integration_tests {
         // 4. Build execution request
         let req = FastExecutionRequest {
             market_id,
-            yes_price: p_yes,
-            no_price: k_no,
-            yes_size: p_yes_sz,
-            no_size: k_no_sz,
+            yes_price: Price(p_yes),
+            no_price: Price(k_no),
+            yes_size: Size(p_yes_sz),
+            no_size: Size(k_no_sz),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // 5. Verify request is valid
-        assert_eq!(req.yes_price, 40, "YES price should be 40¢");
-        assert_eq!(req.no_price, 50, "NO price should be 50¢");
+        assert_eq!(req.yes_price, Price(40), "YES price should be 40¢");
+        assert_eq!(req.no_price, Price(50), "NO price should be 50¢");
         assert!(req.profit_cents() > 0, "Should have positive profit");
 
         // 6. Verify we can access market pair for execution
@@ -791,13 +791,13 @@ mod execution_tests {
     async fn test_execution_profit_threshold() {
         // This tests the logic flow - actual execution would need mocked clients
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 50,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(50),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // 50 + 50 + 2 (fee) = 102 > 100 → negative profit
@@ -816,7 +816,7 @@ mod execution_tests {
             enabled: true,
         };
 
-        let cb = CircuitBreaker::new(config);
+        let cb = TradingCircuitBreaker::new(config);
 
         // Fill up market position
         cb.record_success("market1", 45, 45, 1.0).await;
@@ -859,19 +859,19 @@ mod execution_tests {
         assert!(summary.total_guaranteed_profit > 0.0);
     }
 
-    /// Test: NanoClock provides monotonic timing
+    /// Test: SystemClock provides monotonic timing
     #[test]
     fn test_nano_clock_monotonic() {
-        use arb_bot::execution::NanoClock;
+        use arb_bot::clock::SystemClock;
 
-        let clock = NanoClock::new();
+        let clock = SystemClock::new();
 
         let t1 = clock.now_ns();
         std::thread::sleep(std::time::Duration::from_micros(100));
         let t2 = clock.now_ns();
 
         assert!(t2 > t1, "Clock should be monotonic");
-        assert!(t2 - t1 >= 100_000, "Should measure at least 100µs");
+        assert!(t2.saturating_sub(t1) >= Nanos(100_000), "Should measure at least 100µs");
     }
 }
 
@@ -1251,7 +1251,7 @@ mod process_mock_tests {
     /// Simulates the position tracking logic from process
     async fn simulate_process_position_tracking(
         tracker: &Arc<RwLock<PositionTracker>>,
-        circuit_breaker: &CircuitBreaker,
+        circuit_breaker: &TradingCircuitBreaker,
         pair: &MarketPair,
         req: &FastExecutionRequest,
         result: MockExecutionResult,
@@ -1341,17 +1341,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_records_fills_with_order_ids() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let result = MockExecutionResult {
@@ -1390,18 +1390,18 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_poly_yes_kalshi_no_sides() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         // Poly YES + Kalshi NO configuration
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let result = MockExecutionResult {
@@ -1431,18 +1431,18 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_kalshi_yes_poly_no_sides() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         // Kalshi YES + Poly NO configuration
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiYesPolyNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let result = MockExecutionResult {
@@ -1472,17 +1472,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_updates_circuit_breaker() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let result = MockExecutionResult {
@@ -1506,17 +1506,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_partial_kalshi_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Kalshi only fills 7 out of 10
@@ -1548,17 +1548,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_partial_poly_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Poly only fills 6 out of 10
@@ -1583,17 +1583,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_zero_kalshi_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Kalshi fills 0, Poly fills 10 (complete failure on one side)
@@ -1625,17 +1625,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_zero_poly_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Kalshi fills 10, Poly fills 0
@@ -1659,17 +1659,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_profit_calculation_full_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,  // Poly YES at 40¢
-            no_price: 50,   // Kalshi NO at 50¢
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),  // Poly YES at 40¢
+            no_price: Price(50),   // Kalshi NO at 50¢
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let result = MockExecutionResult {
@@ -1697,17 +1697,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_profit_calculation_partial_fill() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Partial fill: Kalshi 7, Poly 10
@@ -1736,17 +1736,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_multiple_executions_accumulate() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // First execution: 10 contracts
@@ -1794,17 +1794,17 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_circuit_breaker_accumulates_position() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Execute multiple times
@@ -1835,19 +1835,19 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_poly_only_arb() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         // PolyOnly: Buy YES and NO both on Polymarket
         // This is unusual but profitable when Poly YES + Poly NO < $1
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 48,  // Poly YES at 48¢
-            no_price: 50,   // Poly NO at 50¢ (total = 98¢, 2¢ profit with NO fees!)
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(48),  // Poly YES at 48¢
+            no_price: Price(50),   // Poly NO at 50¢ (total = 98¢, 2¢ profit with NO fees!)
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // For PolyOnly, both fills are from Polymarket
@@ -1871,19 +1871,19 @@ mod process_mock_tests {
     #[tokio::test]
     async fn test_process_kalshi_only_arb() {
         let tracker = Arc::new(RwLock::new(PositionTracker::new()));
-        let cb = CircuitBreaker::new(test_circuit_breaker_config());
+        let cb = TradingCircuitBreaker::new(test_circuit_breaker_config());
         let pair = test_market_pair();
 
         // KalshiOnly: Buy YES and NO both on Kalshi
         // Must overcome DOUBLE fees (fee on YES side + fee on NO side)
         let req = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 44,  // Kalshi YES at 44¢
-            no_price: 44,   // Kalshi NO at 44¢ (raw = 88¢)
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(44),  // Kalshi YES at 44¢
+            no_price: Price(44),   // Kalshi NO at 44¢ (raw = 88¢)
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Double fee: kalshi_fee(44) + kalshi_fee(44)
@@ -1902,13 +1902,13 @@ mod process_mock_tests {
         for yes_price in [10u16, 25, 50, 75, 90] {
             for no_price in [10u16, 25, 50, 75, 90] {
                 let req = FastExecutionRequest {
-                    market_id: 0,
-                    yes_price,
-                    no_price,
-                    yes_size: 1000,
-                    no_size: 1000,
+                    market_id: MarketId(0),
+                    yes_price: Price(yes_price),
+                    no_price: Price(no_price),
+                    yes_size: Size(1000),
+                    no_size: Size(1000),
                     arb_type: ArbType::PolyOnly,
-                    detected_ns: 0,
+                    detected_ns: Nanos::ZERO,
                 };
                 assert_eq!(req.estimated_fee_cents(), 0,
                     "PolyOnly should always have 0 fees, got {} for prices ({}, {})",
@@ -1925,13 +1925,13 @@ mod process_mock_tests {
         for yes_price in [10u16, 25, 50, 75, 90] {
             for no_price in [10u16, 25, 50, 75, 90] {
                 let req = FastExecutionRequest {
-                    market_id: 0,
-                    yes_price,
-                    no_price,
-                    yes_size: 1000,
-                    no_size: 1000,
+                    market_id: MarketId(0),
+                    yes_price: Price(yes_price),
+                    no_price: Price(no_price),
+                    yes_size: Size(1000),
+                    no_size: Size(1000),
                     arb_type: ArbType::KalshiOnly,
-                    detected_ns: 0,
+                    detected_ns: Nanos::ZERO,
                 };
                 let expected = kalshi_fee_cents(yes_price) + kalshi_fee_cents(no_price);
                 assert_eq!(req.estimated_fee_cents(), expected,
@@ -1948,26 +1948,26 @@ mod process_mock_tests {
 
         // PolyYesKalshiNo: fee on Kalshi NO side
         let req1 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req1.estimated_fee_cents(), kalshi_fee_cents(50),
             "PolyYesKalshiNo fee should be on NO side (50¢)");
 
         // KalshiYesPolyNo: fee on Kalshi YES side
         let req2 = FastExecutionRequest {
-            market_id: 0,
-            yes_price: 40,
-            no_price: 50,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiYesPolyNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
         assert_eq!(req2.estimated_fee_cents(), kalshi_fee_cents(40),
             "KalshiYesPolyNo fee should be on YES side (40¢)");
@@ -1984,45 +1984,45 @@ mod process_mock_tests {
 
         // PolyOnly: 0 fees → 10¢ profit
         let poly_only = FastExecutionRequest {
-            market_id: 0,
-            yes_price,
-            no_price,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(yes_price),
+            no_price: Price(no_price),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // KalshiOnly: double fees → less profit
         let kalshi_only = FastExecutionRequest {
-            market_id: 0,
-            yes_price,
-            no_price,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(yes_price),
+            no_price: Price(no_price),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiOnly,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // Cross-platform: single fee
         let cross1 = FastExecutionRequest {
-            market_id: 0,
-            yes_price,
-            no_price,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(yes_price),
+            no_price: Price(no_price),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::PolyYesKalshiNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         let cross2 = FastExecutionRequest {
-            market_id: 0,
-            yes_price,
-            no_price,
-            yes_size: 1000,
-            no_size: 1000,
+            market_id: MarketId(0),
+            yes_price: Price(yes_price),
+            no_price: Price(no_price),
+            yes_size: Size(1000),
+            no_size: Size(1000),
             arb_type: ArbType::KalshiYesPolyNo,
-            detected_ns: 0,
+            detected_ns: Nanos::ZERO,
         };
 
         // PolyOnly should always be most profitable (no fees)
@@ -2043,4 +2043,71 @@ mod process_mock_tests {
         assert_eq!(cross1.profit_cents(), cross2.profit_cents(),
             "Both cross-platform types should have equal profit");
     }
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// SIMULATION TESTS - Ticks through detection, risk and simulated execution
+// ============================================================================
+
+mod simulation_tests {
+    use arb_bot::arb_simulation::*;
+    use arb_bot::config::{ExecutionSection, RiskSection};
+    use arb_bot::event_phase::{EventPhase, PhaseGates, PhaseTracker};
+    use arb_bot::types::*;
+    use std::sync::Arc;
+
+    /// Scenario: only in-play markets may trade; the pre-game market is gated
+    #[tokio::test]
+    async fn test_phase_gate_in_simulated_pipeline() {
+        let risk = RiskSection { phases: vec![EventPhase::InPlay], ..Default::default() };
+        let sim = ArbSimulation::new(100).with_market("LIVE", "epl").with_market("LATER", "epl");
+        let phases = Arc::new(
+            PhaseTracker::new(PhaseGates::new(&risk, &ExecutionSection::default())).with_clock(sim.clock()),
+        );
+        phases.set_schedule("LIVE", "epl", Some(Nanos(SIM_START.0 + 10_000_000)));
+        phases.set_schedule("LATER", "epl", Some(Nanos(SIM_START.0 + 3_600_000_000_000)));
+        let sim = sim.with_phase_tracker(phases);
+
+        let mut ticks = Vec::new();
+        for market in ["LIVE", "LATER"] {
+            ticks.push(SimTick::new(15, market, Platform::Kalshi, 55, 50));
+            ticks.push(SimTick::new(20, market, Platform::Polymarket, 40, 62));
+        }
+        let transcript = sim.run(ticks).await;
+
+        assert!(matches!(transcript.decisions("LIVE").last(), Some(Decision::Filled { contracts: 10, .. })));
+        match transcript.decisions("LATER").last() {
+            Some(Decision::Rejected { reason, .. }) => assert!(reason.contains("pre_game"), "{}", reason),
+            other => panic!("expected phase rejection, got {:?}", other),
+        }
+    }
+
+    /// Scenario: simulated fills book the same per-contract profit the detector priced
+    #[tokio::test]
+    async fn test_simulated_profit_matches_request_pricing() {
+        let sim = ArbSimulation::new(100).with_market("M", "nba");
+        let transcript = sim.run([
+            SimTick::new(0, "M", Platform::Kalshi, 40, 70),
+            SimTick::new(1, "M", Platform::Polymarket, 70, 50).with_sizes(1000, 2000),
+        ]).await;
+
+        // Kalshi YES 40 (+2¢ fee) + Poly NO 50 = 92¢
+        let expected = FastExecutionRequest {
+            market_id: MarketId(0),
+            yes_price: Price(40),
+            no_price: Price(50),
+            yes_size: Size(1000),
+            no_size: Size(2000),
+            arb_type: ArbType::KalshiYesPolyNo,
+            detected_ns: Nanos::ZERO,
+        };
+        match transcript.decisions("M")[..] {
+            [Decision::Detected { profit_cents, .. }, Decision::Filled { contracts, profit_cents: total, .. }] => {
+                assert_eq!(*profit_cents, expected.profit_cents());
+                assert_eq!(*total, *contracts * expected.profit_cents() as i64);
+            }
+            ref other => panic!("unexpected transcript {:?}", other),
+        }
+        assert!(sim.circuit_breaker().is_trading_allowed());
+    }
+}
@@ -0,0 +1,231 @@
+// tests/property_tests.rs
+// Property-based tests for the numerical components - filter covariance stays positive
+// definite, RLS recovers known betas, edge decay is monotone and position limits hold
+//
+// Inputs are generated by proptest; a failing case is shrunk and the seed is persisted
+// under proptest-regressions/ so it replays on the next run.
+
+// ============================================================================
+// KALMAN COVARIANCE - Positive definite under arbitrary tick streams
+// ============================================================================
+
+mod kalman_covariance_tests {
+    use arb_bot::latency_arbitrage::ConvergenceKalman;
+    use proptest::prelude::*;
+
+    /// Symmetric (to rounding) with positive leading principal minors
+    fn assert_positive_definite(p: &[[f64; 3]; 3]) -> Result<(), TestCaseError> {
+        let scale = p.iter().flatten().fold(1.0f64, |m, v| m.max(v.abs()));
+        for i in 0..3 {
+            for j in 0..3 {
+                prop_assert!((p[i][j] - p[j][i]).abs() <= 1e-9 * scale, "asymmetric covariance {:?}", p);
+            }
+        }
+
+        let m1 = p[0][0];
+        let m2 = p[0][0] * p[1][1] - p[0][1] * p[1][0];
+        let m3 = p[0][0] * (p[1][1] * p[2][2] - p[1][2] * p[2][1])
+            - p[0][1] * (p[1][0] * p[2][2] - p[1][2] * p[2][0])
+            + p[0][2] * (p[1][0] * p[2][1] - p[1][1] * p[2][0]);
+        prop_assert!(m1 > 0.0 && m2 > 0.0 && m3 > 0.0, "covariance not positive definite {:?}", p);
+        Ok(())
+    }
+
+    /// (dt seconds, price difference, whether the tick carries a measurement)
+    fn tick_stream() -> impl Strategy<Value = Vec<(f64, f64, bool)>> {
+        prop::collection::vec((0.0..5.0f64, -100.0..100.0f64, any::<bool>()), 1..200)
+    }
+
+    proptest! {
+        #[test]
+        fn convergence_covariance_stays_positive_definite(ticks in tick_stream()) {
+            let mut filter = ConvergenceKalman::new();
+            for (dt, diff, measured) in ticks {
+                filter.predict(dt);
+                assert_positive_definite(&filter.covariance())?;
+                if measured {
+                    filter.update(diff);
+                    assert_positive_definite(&filter.covariance())?;
+                }
+            }
+        }
+
+        #[test]
+        fn convergence_update_never_increases_variance(ticks in tick_stream()) {
+            let mut filter = ConvergenceKalman::new();
+            for (dt, diff, _) in ticks {
+                filter.predict(dt);
+                let before = filter.covariance();
+                filter.update(diff);
+                let after = filter.covariance();
+                for i in 0..3 {
+                    prop_assert!(after[i][i] <= before[i][i] + 1e-12);
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// RLS BETA - Recovers a known beta from player/team moves
+// ============================================================================
+
+mod rls_tests {
+    use arb_bot::pattern_73_beta_skew::RecursiveLeastSquares;
+    use proptest::prelude::*;
+
+    /// Player prop moves, bounded away from zero so every observation is informative
+    fn moves() -> impl Strategy<Value = Vec<f64>> {
+        prop::collection::vec((0.5..5.0f64, any::<bool>()).prop_map(|(m, up)| if up { m } else { -m }), 30..120)
+    }
+
+    proptest! {
+        #[test]
+        fn rls_converges_to_exact_beta(beta in -2.0..2.0f64, lambda in 0.9..=1.0f64, xs in moves()) {
+            let mut rls = RecursiveLeastSquares::new(lambda);
+            for x in &xs {
+                rls.update(*x, beta * x);
+            }
+            prop_assert!((rls.get_beta() - beta).abs() < 1e-3, "beta {} estimated as {}", beta, rls.get_beta());
+            prop_assert_eq!(rls.n as usize, xs.len());
+        }
+
+        #[test]
+        fn rls_converges_under_bounded_noise(
+            beta in -2.0..2.0f64,
+            lambda in 0.9..=1.0f64,
+            samples in prop::collection::vec((0.5..5.0f64, -0.01..0.01f64), 30..120),
+        ) {
+            let mut rls = RecursiveLeastSquares::new(lambda);
+            for (x, noise) in &samples {
+                rls.update(*x, beta * x + noise);
+            }
+            prop_assert!((rls.get_beta() - beta).abs() < 0.05, "beta {} estimated as {}", beta, rls.get_beta());
+            prop_assert!(rls.get_uncertainty().is_finite());
+        }
+    }
+}
+
+// ============================================================================
+// EDGE DECAY - Remaining edge only shrinks with execution delay
+// ============================================================================
+
+mod edge_decay_tests {
+    use arb_bot::latency_execution::EdgeDecayModel;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn edge_decay_is_monotone(
+            half_life_ms in 1.0..5_000.0f64,
+            edge in any::<i16>(),
+            a in 0u64..10_000_000_000,
+            b in 0u64..10_000_000_000,
+        ) {
+            let model = EdgeDecayModel::new(half_life_ms, edge);
+            let (earlier, later) = (a.min(b), a.max(b));
+            let first = model.remaining_edge(earlier);
+            let second = model.remaining_edge(later);
+
+            prop_assert!(first.unsigned_abs() <= edge.unsigned_abs());
+            prop_assert!(second.unsigned_abs() <= first.unsigned_abs(), "{} -> {} between {}ns and {}ns", first, second, earlier, later);
+            // Decay never flips the direction of the edge
+            prop_assert!(second == 0 || second.signum() == edge.signum());
+        }
+
+        #[test]
+        fn edge_halves_at_half_life(half_life_ms in 1.0..5_000.0f64, edge in any::<i16>()) {
+            let model = EdgeDecayModel::new(half_life_ms, edge);
+            let half_life_ns = (half_life_ms * 1_000_000.0) as u64;
+            prop_assert_eq!(model.remaining_edge(0), edge);
+            prop_assert!((model.remaining_edge(half_life_ns) as i32 - edge as i32 / 2).abs() <= 1);
+        }
+    }
+}
+
+// ============================================================================
+// RISK LIMITS - Approved trades never take positions past the limits
+// ============================================================================
+
+mod risk_limit_tests {
+    use arb_bot::circuit_breaker::*;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        /// Attempt a hedged trade of `contracts` per leg
+        Trade { market: usize, contracts: i64 },
+        /// Market settles and its position is released
+        Settle { market: usize },
+    }
+
+    fn ops() -> impl Strategy<Value = Vec<Op>> {
+        let op = prop_oneof![
+            4 => (0..6usize, 1..60i64).prop_map(|(market, contracts)| Op::Trade { market, contracts }),
+            1 => (0..6usize).prop_map(|market| Op::Settle { market }),
+        ];
+        prop::collection::vec(op, 1..80)
+    }
+
+    fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(fut)
+    }
+
+    proptest! {
+        #[test]
+        fn approved_trades_stay_within_position_limits(
+            per_market in 1..200i64,
+            total in 1..600i64,
+            ops in ops(),
+        ) {
+            block_on(async {
+                let cb = TradingCircuitBreaker::new(CircuitBreakerConfig {
+                    max_position_per_market: per_market,
+                    max_total_position: total,
+                    max_daily_loss: 1_000.0,
+                    max_consecutive_errors: 3,
+                    cooldown_secs: 60,
+                    enabled: true,
+                });
+                // Contracts held per market, both legs
+                let mut held: HashMap<String, i64> = HashMap::new();
+
+                for op in ops {
+                    match op {
+                        Op::Trade { market, contracts } => {
+                            let market_id = format!("MKT-{}", market);
+                            let current = held.get(&market_id).copied().unwrap_or(0);
+                            let open: i64 = held.values().sum();
+                            let fits = current + 2 * contracts <= per_market && open + 2 * contracts <= total;
+
+                            match cb.can_execute(&market_id, contracts).await {
+                                Ok(()) => {
+                                    prop_assert!(fits, "approved {} x2 on {} holding {} of {} (total {} of {})",
+                                        contracts, market_id, current, per_market, open, total);
+                                    cb.record_success(&market_id, contracts, contracts, 0.0).await;
+                                    *held.entry(market_id).or_insert(0) += 2 * contracts;
+                                }
+                                Err(TripReason::MaxPositionPerMarket { .. } | TripReason::MaxTotalPosition { .. }) => {
+                                    prop_assert!(!fits, "rejected {} x2 on {} that fits", contracts, market_id);
+                                }
+                                Err(other) => prop_assert!(false, "unexpected rejection {:?}", other),
+                            }
+                        }
+                        Op::Settle { market } => {
+                            let market_id = format!("MKT-{}", market);
+                            cb.release_market(&market_id).await;
+                            held.remove(&market_id);
+                        }
+                    }
+
+                    prop_assert!(held.values().all(|&h| h <= per_market));
+                    let status = cb.status().await;
+                    prop_assert_eq!(status.total_position, held.values().sum::<i64>());
+                    prop_assert!(status.total_position <= total);
+                }
+                Ok(())
+            })?;
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! The `alias` transform: import specifiers matching a configured alias (`@filters/*` ->
+//! `./src/filters/*`, `lodash` -> a pinned CDN URL) are rewritten before Bun resolves
+//! them. Aliases may point at other aliases; chains are followed and cycles rejected.
+//! Relative targets are taken from the working directory and rewritten relative to the
+//! importing file.
+
+use napi_derive::napi;
+use oxc_ast::ast::{
+    Argument, CallExpression, ExportAllDeclaration, ExportNamedDeclaration, Expression, ImportDeclaration,
+    ImportExpression, Program, StringLiteral,
+};
+use oxc_ast_visit::{walk, Visit};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use crate::transform::{Context, Rewriter};
+
+/// Aliases followed for one specifier before giving up
+const MAX_CHAIN: usize = 32;
+
+static REPORT: LazyLock<Mutex<Report>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Report {
+    rewrites: BTreeMap<(String, String), u64>,
+    cycles: BTreeMap<String, String>,
+}
+
+/// `pattern` is a specifier or has one `*`; the matched text replaces `*` in `target`
+#[derive(Debug, Clone)]
+pub struct Alias {
+    prefix: String,
+    suffix: Option<String>,
+    target: String,
+}
+
+impl Alias {
+    pub fn new(pattern: &str, target: &str) -> Result<Self, String> {
+        if pattern.is_empty() || target.is_empty() {
+            return Err(format!("invalid alias '{}' -> '{}': empty pattern or target", pattern, target));
+        }
+        if pattern.matches('*').count() > 1 || target.matches('*').count() > 1 {
+            return Err(format!("invalid alias '{}' -> '{}': at most one '*' each", pattern, target));
+        }
+        if target.contains('*') && !pattern.contains('*') {
+            return Err(format!("invalid alias '{}' -> '{}': '*' in target needs one in the pattern", pattern, target));
+        }
+
+        let (prefix, suffix) = match pattern.split_once('*') {
+            Some((prefix, suffix)) => (prefix.to_string(), Some(suffix.to_string())),
+            None => (pattern.to_string(), None),
+        };
+        Ok(Self { prefix, suffix, target: target.to_string() })
+    }
+
+    fn rewrite(&self, specifier: &str) -> Option<String> {
+        let Some(suffix) = &self.suffix else {
+            return (specifier == self.prefix).then(|| self.target.clone());
+        };
+        let matched = specifier.strip_prefix(&self.prefix)?.strip_suffix(suffix.as_str())?;
+        Some(self.target.replacen('*', matched, 1))
+    }
+
+    /// Exact aliases first, then wildcards by longest prefix
+    fn precedence(&self) -> (bool, std::cmp::Reverse<usize>) {
+        (self.suffix.is_some(), std::cmp::Reverse(self.prefix.len()))
+    }
+
+    /// The pattern with a sample in place of `*`, used to check for cycles up front
+    fn sample(&self) -> String {
+        match &self.suffix {
+            Some(suffix) => format!("{}sample{}", self.prefix, suffix),
+            None => self.prefix.clone(),
+        }
+    }
+}
+
+/// Order aliases by precedence and reject any that can loop
+pub fn prepare(mut aliases: Vec<Alias>) -> Result<Vec<Alias>, String> {
+    aliases.sort_by_key(Alias::precedence);
+    for alias in &aliases {
+        resolve(&aliases, &alias.sample())?;
+    }
+    Ok(aliases)
+}
+
+/// Follow aliases from `specifier`; None when nothing matches, Err with the chain on a cycle
+pub fn resolve(aliases: &[Alias], specifier: &str) -> Result<Option<String>, String> {
+    let mut chain = vec![specifier.to_string()];
+    while let Some(next) = aliases.iter().find_map(|a| a.rewrite(chain.last().expect("non-empty chain"))) {
+        let cycle = chain.contains(&next) || chain.len() > MAX_CHAIN;
+        chain.push(next);
+        if cycle {
+            return Err(format!("alias cycle: {}", chain.join(" -> ")));
+        }
+    }
+    Ok((chain.len() > 1).then(|| chain.pop().expect("non-empty chain")))
+}
+
+pub fn alias(program: &Program<'_>, cx: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    if cx.options.aliases.is_empty() {
+        return;
+    }
+    let mut visitor = AliasVisitor { cx, rewriter, source: program.source_text };
+    visitor.visit_program(program);
+}
+
+struct AliasVisitor<'c, 'r, 's> {
+    cx: &'c Context<'c>,
+    rewriter: &'r mut Rewriter<'s>,
+    source: &'c str,
+}
+
+impl AliasVisitor<'_, '_, '_> {
+    fn rewrite(&mut self, literal: &StringLiteral<'_>) {
+        let specifier = literal.value.as_str();
+        let resolved = match resolve(&self.cx.options.aliases, specifier) {
+            Ok(Some(resolved)) => resolved,
+            Ok(None) => return,
+            Err(cycle) => {
+                lock().cycles.insert(specifier.to_string(), cycle);
+                return;
+            }
+        };
+        *lock().rewrites.entry((specifier.to_string(), resolved.clone())).or_default() += 1;
+
+        let rewritten = if resolved.starts_with("./") || resolved.starts_with("../") {
+            relative_to_importer(self.cx.path, &resolved)
+        } else {
+            resolved
+        };
+
+        // Keep the original quote style
+        let quote = literal.span.source_text(self.source).chars().next().unwrap_or('"');
+        let escaped = rewritten.replace('\\', "\\\\").replace(quote, &format!("\\{}", quote));
+        self.rewriter.replace(literal.span.start, literal.span.end, format!("{}{}{}", quote, escaped, quote));
+    }
+}
+
+impl<'a> Visit<'a> for AliasVisitor<'_, '_, '_> {
+    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+        self.rewrite(&it.source);
+    }
+
+    fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+        self.rewrite(&it.source);
+    }
+
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        match &it.source {
+            Some(source) => self.rewrite(source),
+            None => walk::walk_export_named_declaration(self, it),
+        }
+    }
+
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        if let Expression::StringLiteral(source) = &it.source {
+            self.rewrite(source);
+        }
+        walk::walk_import_expression(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        // require("...")
+        if matches!(&it.callee, Expression::Identifier(callee) if callee.name == "require") {
+            if let Some(Argument::StringLiteral(source)) = it.arguments.first() {
+                self.rewrite(source);
+            }
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+/// `./src/x` (from the working directory) as seen from the directory of `importer`
+fn relative_to_importer(importer: &str, target: &str) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let importer_dir = normalize(&cwd.join(importer)).parent().map(Path::to_path_buf).unwrap_or_default();
+    let target = normalize(&cwd.join(target));
+
+    let from: Vec<Component> = importer_dir.components().collect();
+    let to: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    let relative = parts.join("/");
+    if relative.starts_with("..") {
+        relative
+    } else {
+        format!("./{}", relative)
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[napi(object)]
+pub struct AliasRewrite {
+    pub specifier: String,
+    /// Alias target before it is made relative to the importing file
+    pub resolved: String,
+    pub count: i64,
+}
+
+#[napi(object)]
+pub struct AliasCycle {
+    pub specifier: String,
+    pub chain: String,
+}
+
+#[napi(object)]
+pub struct AliasReport {
+    pub rewrites: Vec<AliasRewrite>,
+    /// Specifiers left alone because their aliases loop
+    pub cycles: Vec<AliasCycle>,
+}
+
+/// Specifiers rewritten since the plugin was loaded; files served from the transform
+/// cache aren't transformed again and so aren't counted
+#[napi]
+pub fn get_alias_report() -> AliasReport {
+    let report = lock();
+    AliasReport {
+        rewrites: report
+            .rewrites
+            .iter()
+            .map(|((specifier, resolved), count)| AliasRewrite {
+                specifier: specifier.clone(),
+                resolved: resolved.clone(),
+                count: *count as i64,
+            })
+            .collect(),
+        cycles: report
+            .cycles
+            .iter()
+            .map(|(specifier, chain)| AliasCycle { specifier: specifier.clone(), chain: chain.clone() })
+            .collect(),
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Report> {
+    REPORT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{PluginOptions, Transform, TransformOptions};
+    use crate::transform::apply;
+    use std::collections::HashMap;
+
+    fn options(aliases: &[(&str, &str)]) -> Result<TransformOptions, String> {
+        let aliases: HashMap<String, String> = aliases.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        TransformOptions::from_options(PluginOptions { aliases: Some(aliases), ..Default::default() })
+    }
+
+    fn run(path: &str, source: &str, aliases: &[(&str, &str)]) -> String {
+        apply(source, path, &[Transform::Alias], &options(aliases).unwrap()).expect("source parses").code
+    }
+
+    #[test]
+    fn rewrites_every_kind_of_specifier() {
+        let source = "import { kalman } from '@filters/kalman';\nexport * from \"@filters/rls\";\nexport { x } from '@filters/x';\nconst m = await import('@filters/lazy');\nconst r = require('lodash');\nconst s = '@filters/not-an-import';\n";
+        let aliases = [("@filters/*", "./src/filters/*"), ("lodash", "https://esm.sh/lodash@4.17.21")];
+        assert_eq!(
+            run("src/strategies/arb.ts", source, &aliases),
+            "import { kalman } from '../filters/kalman';\nexport * from \"../filters/rls\";\nexport { x } from '../filters/x';\nconst m = await import('../filters/lazy');\nconst r = require('https://esm.sh/lodash@4.17.21');\nconst s = '@filters/not-an-import';\n"
+        );
+
+        let report = get_alias_report();
+        assert!(report.rewrites.iter().any(|r| r.specifier == "@filters/kalman" && r.resolved == "./src/filters/kalman"));
+    }
+
+    #[test]
+    fn relative_targets_follow_the_importer() {
+        let aliases = [("@filters/*", "./src/filters/*")];
+        assert_eq!(run("index.ts", "import '@filters/a';\n", &aliases), "import './src/filters/a';\n");
+        assert_eq!(run("src/filters/b.ts", "import '@filters/a';\n", &aliases), "import './a';\n");
+    }
+
+    #[test]
+    fn exact_aliases_beat_wildcards_and_chains_are_followed() {
+        let aliases = [("@lib/*", "@vendor/*"), ("@lib/special", "special-lib"), ("@vendor/*", "https://cdn.example/*.js")];
+        assert_eq!(run("a.ts", "import '@lib/special';\n", &aliases), "import 'special-lib';\n");
+        assert_eq!(run("a.ts", "import '@lib/x';\n", &aliases), "import 'https://cdn.example/x.js';\n");
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let err = options(&[("a", "b"), ("b", "a")]).unwrap_err();
+        assert!(err.contains("alias cycle"), "{}", err);
+        assert!(options(&[("@x/*", "@y/*"), ("@y/*", "@x/*")]).is_err());
+        assert!(Alias::new("a*b*", "c").is_err());
+        assert!(Alias::new("a", "c*").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! On-disk cache of transformed files, keyed by a hash of the file (path and content) and
+//! of everything that decides the output: plugin version, working directory, transforms
+//! and options. A hit skips parsing entirely, so unchanged files cost one read on later
+//! builds.
+
+use napi_derive::napi;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::options::{Transform, TransformOptions};
+
+pub const DEFAULT_DIR: &str = "node_modules/.cache/rust-bun-plugin";
+
+static STATS: LazyLock<Mutex<Stats>> = LazyLock::new(Default::default);
+
+/// Distinguishes temporary files written concurrently by this process
+static TEMP_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Default)]
+struct Stats {
+    hits: u64,
+    misses: u64,
+    writes: u64,
+    errors: u64,
+    bytes_served: u64,
+}
+
+/// Hex key for `input` at `path` transformed by `transforms` under `options`
+pub fn key(path: &str, input: &str, transforms: &[Transform], options: &TransformOptions) -> String {
+    // Relative alias targets are resolved from the working directory
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut hasher = Xxh3::new();
+    // Length prefixes keep adjacent fields from running together
+    for field in [env!("CARGO_PKG_VERSION"), &cwd.to_string_lossy(), path, input] {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    for transform in transforms {
+        hasher.update(transform.name().as_bytes());
+        hasher.update(&[0]);
+    }
+    hasher.update(&options.config_hash().to_le_bytes());
+    format!("{:032x}", hasher.digest128())
+}
+
+/// Previously stored output for `key`, counting the hit or miss
+pub fn get(dir: &Path, key: &str) -> Option<String> {
+    let cached = std::fs::read_to_string(entry(dir, key)).ok();
+    let mut stats = lock();
+    match &cached {
+        Some(output) => {
+            stats.hits += 1;
+            stats.bytes_served += output.len() as u64;
+        }
+        None => stats.misses += 1,
+    }
+    cached
+}
+
+/// Store `output` for `key`. Entries are written to a temporary file and renamed into
+/// place, so a concurrent reader never sees a partial one. Failures are only counted:
+/// the build goes on uncached.
+pub fn put(dir: &Path, key: &str, output: &str) {
+    let temp = dir.join(format!("{}.{}-{}.tmp", key, std::process::id(), TEMP_ID.fetch_add(1, Ordering::Relaxed)));
+    let written = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&temp, output))
+        .and_then(|_| std::fs::rename(&temp, entry(dir, key)));
+
+    let mut stats = lock();
+    match written {
+        Ok(()) => stats.writes += 1,
+        Err(_) => {
+            stats.errors += 1;
+            let _ = std::fs::remove_file(&temp);
+        }
+    }
+}
+
+fn entry(dir: &Path, key: &str) -> std::path::PathBuf {
+    dir.join(format!("{}.js", key))
+}
+
+#[napi(object)]
+pub struct CacheStats {
+    pub hits: i64,
+    pub misses: i64,
+    /// Entries stored since the plugin was loaded
+    pub writes: i64,
+    /// Entries that could not be written
+    pub errors: i64,
+    /// Bytes of output served from the cache instead of being transformed
+    pub bytes_served: i64,
+}
+
+/// Cache activity since the plugin was loaded
+#[napi]
+pub fn get_cache_stats() -> CacheStats {
+    let stats = lock();
+    CacheStats {
+        hits: stats.hits as i64,
+        misses: stats.misses as i64,
+        writes: stats.writes as i64,
+        errors: stats.errors as i64,
+        bytes_served: stats.bytes_served as i64,
+    }
+}
+
+/// Delete every entry in the configured cache directory; returns how many were removed
+#[napi]
+pub fn clear_cache() -> napi::Result<i64> {
+    let Some(dir) = crate::options::current().cache_dir.clone() else {
+        return Ok(0);
+    };
+    clear(&dir).map_err(|e| napi::Error::from_reason(format!("clearing {}: {}", dir.display(), e)))
+}
+
+fn clear(dir: &Path) -> std::io::Result<i64> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "js" || ext == "tmp") {
+            std::fs::remove_file(path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Stats> {
+    STATS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::PluginOptions;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("rust-bun-plugin-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn key_covers_content_path_transforms_and_options() {
+        let defaults = TransformOptions::default();
+        let base = key("a.ts", "let a = 1;", &Transform::ALL, &defaults);
+        assert_eq!(base, key("a.ts", "let a = 1;", &Transform::ALL, &defaults));
+
+        assert_ne!(base, key("a.ts", "let a = 2;", &Transform::ALL, &defaults));
+        assert_ne!(base, key("b.ts", "let a = 1;", &Transform::ALL, &defaults));
+        assert_ne!(base, key("a.ts", "let a = 1;", &[Transform::Banner], &defaults));
+
+        let banner = TransformOptions::from_options(PluginOptions { banner: Some("// hi".into()), ..Default::default() });
+        assert_ne!(base, key("a.ts", "let a = 1;", &Transform::ALL, &banner.unwrap()));
+        // Same options from configure() and the defaults share entries
+        let configured = TransformOptions::from_options(PluginOptions::default()).unwrap();
+        assert_eq!(base, key("a.ts", "let a = 1;", &Transform::ALL, &configured));
+    }
+
+    #[test]
+    fn entries_round_trip_and_clear() {
+        let dir = temp_dir("round-trip");
+        let key = key("a.ts", "let a = 1;", &Transform::ALL, &TransformOptions::default());
+        assert_eq!(get(&dir, &key), None);
+
+        put(&dir, &key, "\"use strict\";\nlet a = 1;\n");
+        assert_eq!(get(&dir, &key).as_deref(), Some("\"use strict\";\nlet a = 1;\n"));
+
+        let stats = get_cache_stats();
+        assert!(stats.hits >= 1 && stats.misses >= 1 && stats.writes >= 1);
+
+        assert_eq!(clear(&dir).unwrap(), 1);
+        assert_eq!(get(&dir, &key), None);
+        assert_eq!(clear(&dir.join("missing")).unwrap(), 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,306 @@
+//! The `define` transform: configured identifiers and member paths (`__DEV__`,
+//! `process.env.TRADING_MODE`) are replaced by their values, and `if` statements whose
+//! test becomes constant are folded so the dead branch never reaches the bundle.
+
+use oxc_allocator::{Allocator, Vec as ArenaVec};
+use oxc_ast::ast::{
+    BinaryOperator, Expression, IfStatement, LogicalOperator, ObjectProperty, Program, Statement, UnaryOperator,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use std::collections::HashSet;
+
+use crate::transform::{Context, Rewriter};
+
+/// A replacement: `path` is the dotted name matched in expressions
+#[derive(Debug, Clone)]
+pub struct Define {
+    path: String,
+    replacement: String,
+    /// Literal value, when the replacement is one, for folding conditions
+    value: Option<Const>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Const {
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Null,
+    Undefined,
+}
+
+impl Const {
+    fn truthy(&self) -> bool {
+        match self {
+            Const::Bool(b) => *b,
+            Const::Number(n) => *n != 0.0 && !n.is_nan(),
+            Const::Str(s) => !s.is_empty(),
+            Const::Null | Const::Undefined => false,
+        }
+    }
+
+    /// `===`; None for `==` between different types, which isn't folded
+    fn equals(&self, other: &Const, strict: bool) -> Option<bool> {
+        match (self, other) {
+            (Const::Null | Const::Undefined, Const::Null | Const::Undefined) if !strict => Some(true),
+            (Const::Number(a), Const::Number(b)) => Some(a == b),
+            (a, b) if std::mem::discriminant(a) == std::mem::discriminant(b) => Some(a == b),
+            _ if strict => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl Define {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// `path` must be an identifier or dotted member path; `replacement` a JS expression
+    pub fn new(path: &str, replacement: &str) -> Result<Self, String> {
+        let valid_part = |part: &str| {
+            let mut chars = part.chars();
+            chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+                && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        };
+        if !path.split('.').all(valid_part) {
+            return Err(format!("invalid define key '{}': expected an identifier or dotted path", path));
+        }
+
+        let replacement = replacement.trim();
+        let allocator = Allocator::default();
+        let expression = Parser::new(&allocator, replacement, SourceType::mjs())
+            .parse_expression()
+            .ok()
+            .filter(|e| e.span().end as usize == replacement.len())
+            .ok_or_else(|| format!("invalid define value for '{}': '{}' is not an expression", path, replacement))?;
+
+        // Anything looser than a primary expression is parenthesized so it can't regroup
+        // with the surrounding operators (object literals so they aren't read as blocks)
+        let primary = matches!(
+            expression,
+            Expression::BooleanLiteral(_)
+                | Expression::NumericLiteral(_)
+                | Expression::StringLiteral(_)
+                | Expression::NullLiteral(_)
+                | Expression::Identifier(_)
+                | Expression::StaticMemberExpression(_)
+                | Expression::ComputedMemberExpression(_)
+                | Expression::CallExpression(_)
+                | Expression::ParenthesizedExpression(_)
+                | Expression::ArrayExpression(_)
+                | Expression::TemplateLiteral(_)
+        );
+        Ok(Self {
+            path: path.to_string(),
+            replacement: if primary { replacement.to_string() } else { format!("({})", replacement) },
+            value: literal(&expression),
+        })
+    }
+}
+
+fn literal(expression: &Expression<'_>) -> Option<Const> {
+    match expression {
+        Expression::BooleanLiteral(b) => Some(Const::Bool(b.value)),
+        Expression::NumericLiteral(n) => Some(Const::Number(n.value)),
+        Expression::StringLiteral(s) => Some(Const::Str(s.value.to_string())),
+        Expression::NullLiteral(_) => Some(Const::Null),
+        Expression::Identifier(id) if id.name == "undefined" => Some(Const::Undefined),
+        Expression::UnaryExpression(u) if u.operator == UnaryOperator::UnaryNegation => match literal(&u.argument)? {
+            Const::Number(n) => Some(Const::Number(-n)),
+            _ => None,
+        },
+        Expression::ParenthesizedExpression(p) => literal(&p.expression),
+        _ => None,
+    }
+}
+
+/// Dotted name of an identifier or static member chain
+fn path_of(expression: &Expression<'_>) -> Option<String> {
+    match expression {
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::StaticMemberExpression(member) if !member.optional => {
+            Some(format!("{}.{}", path_of(&member.object)?, member.property.name))
+        }
+        _ => None,
+    }
+}
+
+pub fn define(program: &Program<'_>, cx: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    if cx.options.defines.is_empty() {
+        return;
+    }
+    let mut visitor = DefineVisitor { defines: &cx.options.defines, rewriter, source: program.source_text, listed: HashSet::new() };
+    visitor.visit_program(program);
+}
+
+struct DefineVisitor<'d, 'r, 's> {
+    defines: &'d [Define],
+    rewriter: &'r mut Rewriter<'s>,
+    source: &'d str,
+    /// `if` statements sitting directly in a statement list, which can be dropped outright
+    listed: HashSet<Span>,
+}
+
+impl<'d> DefineVisitor<'d, '_, '_> {
+    fn lookup(&self, expression: &Expression<'_>) -> Option<&'d Define> {
+        let path = path_of(expression)?;
+        self.defines.iter().find(|d| d.path == path)
+    }
+
+    /// Constant value of a condition after substitution
+    fn evaluate(&self, expression: &Expression<'_>) -> Option<Const> {
+        if let Some(define) = self.lookup(expression) {
+            return define.value.clone();
+        }
+        match expression {
+            Expression::ParenthesizedExpression(p) => self.evaluate(&p.expression),
+            Expression::UnaryExpression(u) if u.operator == UnaryOperator::LogicalNot => {
+                Some(Const::Bool(!self.evaluate(&u.argument)?.truthy()))
+            }
+            Expression::LogicalExpression(l) => {
+                let left = self.evaluate(&l.left)?;
+                match l.operator {
+                    LogicalOperator::And if !left.truthy() => Some(left),
+                    LogicalOperator::Or if left.truthy() => Some(left),
+                    LogicalOperator::Coalesce if !matches!(left, Const::Null | Const::Undefined) => Some(left),
+                    _ => self.evaluate(&l.right),
+                }
+            }
+            Expression::BinaryExpression(b) => {
+                let (left, right) = (self.evaluate(&b.left)?, self.evaluate(&b.right)?);
+                let equal = match b.operator {
+                    BinaryOperator::StrictEquality | BinaryOperator::StrictInequality => left.equals(&right, true)?,
+                    BinaryOperator::Equality | BinaryOperator::Inequality => left.equals(&right, false)?,
+                    _ => return None,
+                };
+                let negated = matches!(b.operator, BinaryOperator::StrictInequality | BinaryOperator::Inequality);
+                Some(Const::Bool(equal != negated))
+            }
+            _ => literal(expression),
+        }
+    }
+
+    fn text(&self, span: Span) -> &'d str {
+        span.source_text(self.source)
+    }
+}
+
+impl<'a> Visit<'a> for DefineVisitor<'_, '_, '_> {
+    fn visit_statements(&mut self, it: &ArenaVec<'a, Statement<'a>>) {
+        for statement in it {
+            if let Statement::IfStatement(if_statement) = statement {
+                self.listed.insert(if_statement.span);
+            }
+        }
+        walk::walk_statements(self, it);
+    }
+
+    fn visit_if_statement(&mut self, it: &IfStatement<'a>) {
+        let Some(test) = self.evaluate(&it.test) else {
+            walk::walk_if_statement(self, it);
+            return;
+        };
+
+        let consequent = it.consequent.span();
+        match (&it.alternate, test.truthy()) {
+            (alternate, true) => {
+                // `if (true) A else B` -> `A`
+                self.rewriter.remove(it.span.start, consequent.start);
+                if alternate.is_some() {
+                    self.rewriter.remove(consequent.end, it.span.end);
+                }
+                self.visit_statement(&it.consequent);
+            }
+            (Some(alternate), false) => {
+                // `if (false) A else B` -> `B`
+                self.rewriter.remove(it.span.start, alternate.span().start);
+                self.visit_statement(alternate);
+            }
+            (None, false) if self.listed.contains(&it.span) => self.rewriter.remove_line(it.span),
+            // Body of another statement (`else if (false) ...`); keep a statement in place
+            (None, false) => self.rewriter.replace(it.span.start, it.span.end, "{}"),
+        }
+    }
+
+    fn visit_object_property(&mut self, it: &ObjectProperty<'a>) {
+        // `{ __DEV__ }` must become `{ __DEV__: false }`
+        if it.shorthand {
+            if let Some(define) = self.lookup(&it.value) {
+                let replacement = format!("{}: {}", self.text(it.key.span()), define.replacement);
+                self.rewriter.replace(it.span.start, it.span.end, replacement);
+                return;
+            }
+        }
+        walk::walk_object_property(self, it);
+    }
+
+    fn visit_expression(&mut self, it: &Expression<'a>) {
+        if let Some(define) = self.lookup(it) {
+            self.rewriter.replace(it.span().start, it.span().end, define.replacement.as_str());
+            return;
+        }
+        walk::walk_expression(self, it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{PluginOptions, Transform, TransformOptions};
+    use crate::transform::apply;
+    use std::collections::HashMap;
+
+    fn run(source: &str, defines: &[(&str, &str)]) -> String {
+        let define: HashMap<String, String> = defines.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let options = TransformOptions::from_options(PluginOptions { define: Some(define), ..Default::default() }).unwrap();
+        apply(source, "test.ts", &[Transform::Define], &options).expect("source parses").code
+    }
+
+    #[test]
+    fn replaces_identifiers_and_member_paths() {
+        let source = "const mode = process.env.TRADING_MODE;\nconst dev = __DEV__ ? 1 : 2;\nconst o = { __DEV__ };\nconst s = '__DEV__'; // __DEV__\nenv.process.env.TRADING_MODE;\n";
+        assert_eq!(
+            run(source, &[("__DEV__", "false"), ("process.env.TRADING_MODE", "\"live\"")]),
+            "const mode = \"live\";\nconst dev = false ? 1 : 2;\nconst o = { __DEV__: false };\nconst s = '__DEV__'; // __DEV__\nenv.process.env.TRADING_MODE;\n"
+        );
+    }
+
+    #[test]
+    fn non_literal_values_are_grouped() {
+        assert_eq!(run("x = LIMIT * 2;\n", &[("LIMIT", "a + b")]), "x = (a + b) * 2;\n");
+        assert_eq!(run("x = CFG.a;\n", &[("CFG", "config.trading")]), "x = config.trading.a;\n");
+    }
+
+    #[test]
+    fn folds_constant_if_statements() {
+        let source = "if (__DEV__) {\n  debug();\n}\nif (!__DEV__) {\n  prod();\n} else {\n  dev();\n}\nif (process.env.TRADING_MODE === \"paper\") paper(); else if (__DEV__) x(); else live();\n";
+        assert_eq!(
+            run(source, &[("__DEV__", "false"), ("process.env.TRADING_MODE", "\"live\"")]),
+            "{\n  prod();\n}\nlive();\n"
+        );
+    }
+
+    #[test]
+    fn keeps_a_statement_where_one_is_required() {
+        assert_eq!(run("if (a) b(); else if (__DEV__) c();\n", &[("__DEV__", "false")]), "if (a) b(); else {}\n");
+        assert_eq!(run("if (__DEV__) { d(__DEV__); }\n", &[("__DEV__", "true")]), "{ d(true); }\n");
+    }
+
+    #[test]
+    fn leaves_unknown_conditions_alone() {
+        let source = "if (__DEV__ && flag) a();\nif (MODE == 1) b();\n";
+        assert_eq!(run(source, &[("__DEV__", "true"), ("MODE", "\"1\"")]), "if (true && flag) a();\nif (\"1\" == 1) b();\n");
+        assert_eq!(run("if (__DEV__ || flag) a();\n", &[("__DEV__", "true")]), "a();\n");
+    }
+
+    #[test]
+    fn rejects_bad_keys_and_values() {
+        assert!(Define::new("process.env.", "1").is_err());
+        assert!(Define::new("a-b", "1").is_err());
+        assert!(Define::new("A", "1; 2").is_err());
+        assert_eq!(Define::new("A", "-1").unwrap().value, Some(Const::Number(-1.0)));
+    }
+}
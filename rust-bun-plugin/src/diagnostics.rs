@@ -0,0 +1,307 @@
+//! Lint-style diagnostics from the `analyze_imports` hook. Each analyzed file's findings
+//! replace its previous ones, and JS reads them with `getDiagnostics()` to warn or fail
+//! the build by its own policy. Rule severities can be changed or turned off through
+//! `configure({ lintRules })`.
+
+use napi_derive::napi;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Argument, CallExpression, Expression, ImportExpression, Program, Statement};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_span::{SourceType, Span};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use crate::options::TransformOptions;
+use crate::source_map::utf16_len;
+use crate::transform::{import_key, prologue_start};
+
+static DIAGNOSTICS: LazyLock<Mutex<BTreeMap<String, Vec<Diagnostic>>>> = LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    ParseError,
+    MixedModules,
+    DuplicateImport,
+    MissingUseStrict,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 4] = [Rule::ParseError, Rule::MixedModules, Rule::DuplicateImport, Rule::MissingUseStrict];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::ParseError => "parse-error",
+            Rule::MixedModules => "mixed-modules",
+            Rule::DuplicateImport => "duplicate-import",
+            Rule::MissingUseStrict => "missing-use-strict",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.name() == name)
+    }
+
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Rule::ParseError => Severity::Error,
+            Rule::MixedModules | Rule::DuplicateImport => Severity::Warning,
+            Rule::MissingUseStrict => Severity::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+
+    /// None for "off"
+    pub fn parse(name: &str) -> Result<Option<Self>, String> {
+        match name {
+            "error" => Ok(Some(Severity::Error)),
+            "warning" | "warn" => Ok(Some(Severity::Warning)),
+            "info" => Ok(Some(Severity::Info)),
+            "off" => Ok(None),
+            _ => Err(format!("unknown severity '{}' (expected error, warning, info or off)", name)),
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub file: String,
+    /// 1-based
+    pub line: u32,
+    /// 1-based, in UTF-16 code units like editors count them
+    pub column: u32,
+    /// "parse-error", "mixed-modules", "duplicate-import" or "missing-use-strict"
+    pub rule: String,
+    /// "error", "warning" or "info"
+    pub severity: String,
+    pub message: String,
+}
+
+/// Lint `source` (the file at `path`) with the rules enabled in `options`
+pub fn analyze(path: &str, source: &str, options: &TransformOptions) -> Vec<Diagnostic> {
+    let mut report = Report { path, source, options, diagnostics: Vec::new() };
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(path).unwrap_or_else(|_| SourceType::ts());
+    let parsed = Parser::new(&allocator, source, source_type).parse();
+    if parsed.panicked || !parsed.errors.is_empty() {
+        for error in &parsed.errors {
+            let start = error.labels.as_ref().and_then(|labels| labels.first()).map_or(0, |label| label.offset());
+            report.push(Rule::ParseError, start as u32, error.to_string());
+        }
+        return report.diagnostics;
+    }
+    let program = &parsed.program;
+
+    let mut modules = ModuleUses::default();
+    modules.visit_program(program);
+    if let (Some(_), Some(require)) = (modules.import, modules.require) {
+        report.push(Rule::MixedModules, require.start, "require() in a file that also uses ES imports".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    for statement in &program.body {
+        if let Statement::ImportDeclaration(import) = statement {
+            if !seen.insert(import_key(import.span.source_text(source))) {
+                let message = format!("duplicate import of '{}'", import.source.value);
+                report.push(Rule::DuplicateImport, import.span.start, message);
+            }
+        }
+    }
+
+    if !program.directives.iter().any(|d| d.directive == "use strict") {
+        report.push(Rule::MissingUseStrict, prologue_start(program), "missing \"use strict\" directive".to_string());
+    }
+
+    report.diagnostics.sort_by_key(|d| (d.line, d.column));
+    report.diagnostics
+}
+
+/// Replace the stored diagnostics for `path`
+pub fn record(path: &str, diagnostics: Vec<Diagnostic>) {
+    let mut stored = lock();
+    if diagnostics.is_empty() {
+        stored.remove(path);
+    } else {
+        stored.insert(path.to_string(), diagnostics);
+    }
+}
+
+/// Record the parse errors that made a transform leave `path` unchanged. They replace the
+/// file's earlier findings, since a file that doesn't parse has no others.
+pub fn record_parse_failure(path: &str, source: &str, options: &TransformOptions) {
+    record(path, analyze(path, source, options));
+}
+
+/// Drop parse errors left for `path` once it transforms cleanly
+pub fn clear_parse_errors(path: &str) {
+    let mut stored = lock();
+    if let Some(found) = stored.get_mut(path) {
+        found.retain(|d| d.rule != Rule::ParseError.name());
+        if found.is_empty() {
+            stored.remove(path);
+        }
+    }
+}
+
+/// Diagnostics from every file analyzed so far, by file then position
+#[napi]
+pub fn get_diagnostics() -> Vec<Diagnostic> {
+    lock().values().flatten().cloned().collect()
+}
+
+#[napi]
+pub fn clear_diagnostics() {
+    lock().clear();
+}
+
+/// Lint `code` as the file at `path` without going through a build; nothing is stored
+#[napi]
+pub fn analyze_source(path: String, code: String) -> Vec<Diagnostic> {
+    analyze(&path, &code, &crate::options::current())
+}
+
+struct Report<'r> {
+    path: &'r str,
+    source: &'r str,
+    options: &'r TransformOptions,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report<'_> {
+    fn push(&mut self, rule: Rule, offset: u32, message: String) {
+        let Some(severity) = self.options.severity(rule) else {
+            return;
+        };
+        let before = &self.source[..offset as usize];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        self.diagnostics.push(Diagnostic {
+            file: self.path.to_string(),
+            line: before.matches('\n').count() as u32 + 1,
+            column: utf16_len(&before[line_start..]) + 1,
+            rule: rule.name().to_string(),
+            severity: severity.name().to_string(),
+            message,
+        });
+    }
+}
+
+/// First ES import (static or dynamic) and first `require("...")` call
+#[derive(Default)]
+struct ModuleUses {
+    import: Option<Span>,
+    require: Option<Span>,
+}
+
+impl<'a> Visit<'a> for ModuleUses {
+    fn visit_program(&mut self, it: &Program<'a>) {
+        self.import = it.body.iter().find_map(|statement| match statement {
+            Statement::ImportDeclaration(import) => Some(import.span),
+            _ => None,
+        });
+        walk::walk_program(self, it);
+    }
+
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        self.import.get_or_insert(it.span);
+        walk::walk_import_expression(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        let is_require = matches!(&it.callee, Expression::Identifier(callee) if callee.name == "require")
+            && matches!(it.arguments.first(), Some(Argument::StringLiteral(_)));
+        if is_require {
+            self.require.get_or_insert(it.span);
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, BTreeMap<String, Vec<Diagnostic>>> {
+    DIAGNOSTICS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::PluginOptions;
+    use std::collections::HashMap;
+
+    fn lint(source: &str) -> Vec<(u32, u32, String, String)> {
+        analyze("src/app.ts", source, &TransformOptions::default())
+            .into_iter()
+            .map(|d| (d.line, d.column, d.rule, d.severity))
+            .collect()
+    }
+
+    fn finding(line: u32, column: u32, rule: &str, severity: &str) -> (u32, u32, String, String) {
+        (line, column, rule.to_string(), severity.to_string())
+    }
+
+    #[test]
+    fn reports_each_rule_at_its_position() {
+        let source = "import a from 'a';\nimport b from 'b';\nimport  a  from 'a'\nconst π = 1; const fs = require('fs');\n";
+        assert_eq!(
+            lint(source),
+            vec![
+                finding(1, 1, "missing-use-strict", "info"),
+                finding(3, 1, "duplicate-import", "warning"),
+                finding(4, 25, "mixed-modules", "warning"),
+            ]
+        );
+
+        assert_eq!(lint("#!/usr/bin/env bun\n'use strict';\nconst x = import('x');\n"), vec![]);
+        assert_eq!(lint("#!/usr/bin/env bun\nconst x = 1;\n"), vec![finding(2, 1, "missing-use-strict", "info")]);
+        assert_eq!(lint("'use strict';\nconst x = ;\n")[0], finding(2, 11, "parse-error", "error"));
+    }
+
+    #[test]
+    fn severities_follow_the_configured_rules() {
+        let rules: HashMap<String, String> =
+            [("missing-use-strict", "off"), ("mixed-modules", "error")].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let options = TransformOptions::from_options(PluginOptions { lint_rules: Some(rules), ..Default::default() }).unwrap();
+        let diagnostics = analyze("a.ts", "import a from 'a';\nrequire('b');\n", &options);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].rule.as_str(), diagnostics[0].severity.as_str()), ("mixed-modules", "error"));
+
+        let unknown: HashMap<String, String> = [("no-such-rule".to_string(), "error".to_string())].into();
+        assert!(TransformOptions::from_options(PluginOptions { lint_rules: Some(unknown), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn recorded_diagnostics_replace_earlier_ones_for_the_file() {
+        let path = "recorded/diagnostics.ts";
+        record(path, analyze(path, "const a = 1;\n", &TransformOptions::default()));
+        assert_eq!(get_diagnostics().iter().filter(|d| d.file == path).count(), 1);
+        record(path, Vec::new());
+        assert_eq!(get_diagnostics().iter().filter(|d| d.file == path).count(), 0);
+    }
+
+    #[test]
+    fn parse_failures_are_recorded_until_the_file_transforms() {
+        let path = "recorded/parse_failure.ts";
+        record_parse_failure(path, "'use strict';\nconst x = ;\n", &TransformOptions::default());
+        let found: Vec<_> = get_diagnostics().into_iter().filter(|d| d.file == path).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].line, found[0].rule.as_str(), found[0].severity.as_str()), (2, "parse-error", "error"));
+
+        clear_parse_errors(path);
+        assert_eq!(get_diagnostics().iter().filter(|d| d.file == path).count(), 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! The `instrument` transform: exported async functions in files matching the configured
+//! globs are timed, and each call posts `{ name, module, durationMs, ok }` to the
+//! telemetry collector's `record` method (`globalThis.__strategyTelemetry` by default).
+//! Durations are in milliseconds like the Rust side's SLA figures; `ok` is false when the
+//! call rejected.
+//!
+//! The body is wrapped in place (`try`/`finally` around the original statements) so the
+//! function keeps its name, `this`, `arguments` and hoisting.
+
+use oxc_ast::ast::{
+    BindingPattern, Declaration, ExportDefaultDeclarationKind, Expression, FunctionBody, ModuleExportName,
+    Program, Statement,
+};
+use oxc_span::GetSpan;
+use std::collections::HashMap;
+
+use crate::transform::{Context, Rewriter};
+
+pub fn instrument(program: &Program<'_>, cx: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    if !cx.options.instruments(cx.path) {
+        return;
+    }
+    let module = cx.options.relative_path(cx.path).to_string_lossy().replace('\\', "/");
+
+    // `export { scan, fill as placeOrder }` names local declarations
+    let mut exported_locals: HashMap<&str, &str> = HashMap::new();
+    for statement in &program.body {
+        if let Statement::ExportNamedDeclaration(export) = statement {
+            if export.source.is_none() && export.export_kind.is_value() {
+                for specifier in &export.specifiers {
+                    if let ModuleExportName::IdentifierReference(local) = &specifier.local {
+                        exported_locals.entry(local.name.as_str()).or_insert(specifier.exported.name().as_str());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper { collector: &cx.options.instrument_collector, module: &module, rewriter };
+    for statement in &program.body {
+        match statement {
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::FunctionDeclaration(function)) => {
+                    if let (Some(id), Some(body)) = (&function.id, &function.body) {
+                        if function.r#async && !function.generator {
+                            wrapper.block(id.name.as_str(), body);
+                        }
+                    }
+                }
+                Some(Declaration::VariableDeclaration(declaration)) => {
+                    for declarator in &declaration.declarations {
+                        if let (BindingPattern::BindingIdentifier(id), Some(init)) = (&declarator.id, &declarator.init) {
+                            wrapper.expression(id.name.as_str(), init);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(function) => {
+                    if let Some(body) = &function.body {
+                        if function.r#async && !function.generator {
+                            wrapper.block("default", body);
+                        }
+                    }
+                }
+                ExportDefaultDeclarationKind::ArrowFunctionExpression(arrow) if arrow.r#async => {
+                    wrapper.arrow("default", arrow.expression, &arrow.body);
+                }
+                ExportDefaultDeclarationKind::FunctionExpression(function) => {
+                    if let Some(body) = &function.body {
+                        if function.r#async && !function.generator {
+                            wrapper.block("default", body);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Statement::FunctionDeclaration(function) => {
+                let exported = function.id.as_ref().and_then(|id| exported_locals.get(id.name.as_str()));
+                if let (Some(name), Some(body)) = (exported, &function.body) {
+                    if function.r#async && !function.generator {
+                        wrapper.block(name, body);
+                    }
+                }
+            }
+            Statement::VariableDeclaration(declaration) => {
+                for declarator in &declaration.declarations {
+                    if let (BindingPattern::BindingIdentifier(id), Some(init)) = (&declarator.id, &declarator.init) {
+                        if let Some(name) = exported_locals.get(id.name.as_str()) {
+                            wrapper.expression(name, init);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Wrapper<'w, 's> {
+    collector: &'w str,
+    module: &'w str,
+    rewriter: &'w mut Rewriter<'s>,
+}
+
+impl Wrapper<'_, '_> {
+    /// `const name = async function () {}` or `async () => ...`
+    fn expression(&mut self, name: &str, init: &Expression<'_>) {
+        match init {
+            Expression::ArrowFunctionExpression(arrow) if arrow.r#async => self.arrow(name, arrow.expression, &arrow.body),
+            Expression::FunctionExpression(function) if function.r#async && !function.generator => {
+                if let Some(body) = &function.body {
+                    self.block(name, body);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn arrow(&mut self, name: &str, expression: bool, body: &FunctionBody<'_>) {
+        if !expression {
+            return self.block(name, body);
+        }
+        // `async () => expr` gets a block that returns it
+        let Some(Statement::ExpressionStatement(statement)) = body.statements.first() else {
+            return;
+        };
+        let span = statement.expression.span();
+        self.rewriter.insert(span.start, format!("{{ {} return (", PROLOGUE));
+        self.rewriter.insert(span.end, format!("); {} }}", self.epilogue(name)));
+    }
+
+    /// Wrap the statements of a block body, leaving its directives first
+    fn block(&mut self, name: &str, body: &FunctionBody<'_>) {
+        let start = body.directives.last().map_or(body.span.start + 1, |directive| directive.span.end);
+        self.rewriter.insert(start, format!(" {}", PROLOGUE));
+        self.rewriter.insert(body.span.end - 1, format!("{} ", self.epilogue(name)));
+    }
+
+    fn epilogue(&self, name: &str) -> String {
+        let event = format!(
+            "{{ name: {}, module: {}, durationMs: performance.now() - __telemetryStart, ok: !__telemetryFailed }}",
+            serde_json::Value::from(name),
+            serde_json::Value::from(self.module)
+        );
+        format!(
+            "}} catch (__telemetryError) {{ __telemetryFailed = true; throw __telemetryError; }} finally {{ {}?.record({}); }}",
+            self.collector, event
+        )
+    }
+}
+
+/// Kept on one line so the original lines keep their numbers
+const PROLOGUE: &str = "const __telemetryStart = performance.now(); let __telemetryFailed = false; try {";
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{PluginOptions, Transform, TransformOptions};
+    use crate::transform::apply;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    fn run(path: &str, source: &str) -> String {
+        let options = TransformOptions::from_options(PluginOptions {
+            instrument: Some(vec!["src/strategies/**".into()]),
+            ..Default::default()
+        })
+        .unwrap();
+        let output = apply(source, path, &[Transform::Instrument], &options).expect("source parses").code;
+
+        let allocator = Allocator::default();
+        let reparsed = Parser::new(&allocator, &output, SourceType::ts()).parse();
+        assert!(reparsed.errors.is_empty(), "output does not parse: {:?}\n{}", reparsed.errors, output);
+        output
+    }
+
+    fn epilogue(name: &str) -> String {
+        format!(
+            "}} catch (__telemetryError) {{ __telemetryFailed = true; throw __telemetryError; }} finally {{ globalThis.__strategyTelemetry?.record({{ name: \"{}\", module: \"src/strategies/arb.ts\", durationMs: performance.now() - __telemetryStart, ok: !__telemetryFailed }}); }}",
+            name
+        )
+    }
+
+    #[test]
+    fn wraps_exported_async_functions() {
+        let source = "export async function scan(markets: string[]) {\n  'use strict';\n  return markets.length;\n}\nexport const fill = async (id) => place(id);\nexport default async function () { await tick(); }\n";
+        let output = run("src/strategies/arb.ts", source);
+        assert_eq!(
+            output,
+            format!(
+                "export async function scan(markets: string[]) {{\n  'use strict'; {p}\n  return markets.length;\n{scan} }}\nexport const fill = async (id) => {{ {p} return (place(id)); {fill} }};\nexport default async function () {{ {p} await tick(); {default} }}\n",
+                p = super::PROLOGUE,
+                scan = epilogue("scan"),
+                fill = epilogue("fill"),
+                default = epilogue("default"),
+            )
+        );
+    }
+
+    #[test]
+    fn follows_local_exports_and_skips_the_rest() {
+        let source = "async function scan() { return 1; }\nfunction sync() {}\nasync function hidden() {}\nexport async function* stream() {}\nexport { scan as run, sync };\n";
+        let output = run("src/strategies/arb.ts", source);
+        assert!(output.contains("record({ name: \"run\""), "{}", output);
+        assert_eq!(output.matches("__telemetryStart = ").count(), 1);
+
+        let untouched = "export async function scan() {}\n";
+        assert_eq!(run("src/filters/kalman.ts", untouched), untouched);
+    }
+}
@@ -0,0 +1,150 @@
+//! Per-file transform metrics, collected from every thread that transforms files and
+//! reported to JS through `getMetricsReport()`.
+
+use napi_derive::napi;
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+
+/// Timing samples kept per stage; older samples are overwritten once full
+const MAX_SAMPLES: usize = 10_000;
+
+static METRICS: LazyLock<Mutex<Metrics>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Metrics {
+    files_processed: u64,
+    files_skipped: u64,
+    bytes_in: u64,
+    bytes_out: u64,
+    stages: BTreeMap<&'static str, Samples>,
+}
+
+/// Ring of recent durations in microseconds, plus the total count seen
+#[derive(Default)]
+struct Samples {
+    micros: Vec<f64>,
+    count: u64,
+}
+
+impl Samples {
+    fn push(&mut self, duration: Duration) {
+        let micros = duration.as_secs_f64() * 1e6;
+        if self.micros.len() < MAX_SAMPLES {
+            self.micros.push(micros);
+        } else {
+            self.micros[(self.count % MAX_SAMPLES as u64) as usize] = micros;
+        }
+        self.count += 1;
+    }
+
+    fn timing(&self, stage: &str) -> StageTiming {
+        let mut sorted = self.micros.clone();
+        sorted.sort_by(f64::total_cmp);
+        StageTiming {
+            stage: stage.to_string(),
+            count: self.count as i64,
+            p50_us: percentile(&sorted, 0.50),
+            p90_us: percentile(&sorted, 0.90),
+            p99_us: percentile(&sorted, 0.99),
+            max_us: sorted.last().copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[napi(object)]
+pub struct StageTiming {
+    /// "parse", a transform name, or "rewrite"
+    pub stage: String,
+    pub count: i64,
+    pub p50_us: f64,
+    pub p90_us: f64,
+    pub p99_us: f64,
+    pub max_us: f64,
+}
+
+#[napi(object)]
+pub struct MetricsReport {
+    pub files_processed: i64,
+    /// Files left unchanged because they failed to parse
+    pub files_skipped: i64,
+    pub bytes_in: i64,
+    pub bytes_out: i64,
+    pub stages: Vec<StageTiming>,
+}
+
+/// Metrics since the plugin was loaded (or last reset)
+#[napi]
+pub fn get_metrics_report() -> MetricsReport {
+    report()
+}
+
+#[napi]
+pub fn reset_metrics() {
+    *lock() = Metrics::default();
+}
+
+pub fn record_file(bytes_in: usize, bytes_out: usize, timings: &[(&'static str, Duration)]) {
+    let mut metrics = lock();
+    metrics.files_processed += 1;
+    metrics.bytes_in += bytes_in as u64;
+    metrics.bytes_out += bytes_out as u64;
+    for &(stage, duration) in timings {
+        metrics.stages.entry(stage).or_default().push(duration);
+    }
+}
+
+pub fn record_skip() {
+    lock().files_skipped += 1;
+}
+
+pub fn report() -> MetricsReport {
+    let metrics = lock();
+    MetricsReport {
+        files_processed: metrics.files_processed as i64,
+        files_skipped: metrics.files_skipped as i64,
+        bytes_in: metrics.bytes_in as i64,
+        bytes_out: metrics.bytes_out as i64,
+        stages: metrics.stages.iter().map(|(stage, samples)| samples.timing(stage)).collect(),
+    }
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Metrics> {
+    METRICS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 0.50), 50.0);
+        assert_eq!(percentile(&sorted, 0.99), 99.0);
+        assert_eq!(percentile(&sorted, 1.0), 100.0);
+        assert_eq!(percentile(&[7.0], 0.5), 7.0);
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn samples_keep_the_most_recent_window() {
+        let mut samples = Samples::default();
+        for micros in 0..(MAX_SAMPLES as u64 + 10) {
+            samples.push(Duration::from_micros(micros));
+        }
+        assert_eq!(samples.micros.len(), MAX_SAMPLES);
+        let timing = samples.timing("parse");
+        assert_eq!(timing.count, MAX_SAMPLES as i64 + 10);
+        assert_eq!(timing.max_us, (MAX_SAMPLES + 9) as f64);
+    }
+}
@@ -0,0 +1,418 @@
+//! Transform options, set from JS with `configure()` before the build starts and read
+//! by every OnBeforeParse call. Each file is checked against the include/exclude globs
+//! and only the enabled transforms run on it.
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use napi_derive::napi;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Expression;
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use xxhash_rust::xxh3::xxh3_128;
+
+use crate::alias::{self, Alias};
+use crate::cache;
+use crate::define::Define;
+use crate::diagnostics::{Rule, Severity};
+use crate::strategy;
+
+pub const DEFAULT_BANNER: &str = "// Optimized by Rust Native Plugin\n// Thread-safe processing with zero UTF-8 conversion overhead\n";
+pub const DEFAULT_LOGGING_GUARD: &str = "process.env.NODE_ENV !== 'production'";
+pub const DEFAULT_INSTRUMENT_COLLECTOR: &str = "globalThis.__strategyTelemetry";
+
+static OPTIONS: LazyLock<RwLock<Arc<TransformOptions>>> = LazyLock::new(Default::default);
+
+/// Options object accepted by `configure()`; omitted fields keep their defaults
+#[napi(object)]
+#[derive(Default)]
+pub struct PluginOptions {
+    /// Transforms to run: "define", "alias", "instrument", "banner", "strict-mode",
+    /// "dedup-imports", "console-log"
+    pub transforms: Option<Vec<String>>,
+    /// Globs a file must match to be transformed (all files when omitted)
+    pub include: Option<Vec<String>>,
+    /// Globs that skip a file even when it matches `include`
+    pub exclude: Option<Vec<String>>,
+    /// Expression that must be true for a guarded console.log call to run
+    pub logging_guard: Option<String>,
+    /// Text prepended by the banner transform
+    pub banner: Option<String>,
+    /// Identifier or dotted path -> JS expression it is replaced with, e.g.
+    /// `{ "__DEV__": "false", "process.env.TRADING_MODE": "\"live\"" }`
+    pub define: Option<HashMap<String, String>>,
+    /// Environment variables inlined as `process.env.NAME` string literals (`undefined`
+    /// when unset), read when `configure()` is called
+    pub define_env: Option<Vec<String>>,
+    /// Import specifier pattern -> replacement, e.g. `{ "@filters/*": "./src/filters/*" }`;
+    /// relative targets are taken from the working directory
+    pub aliases: Option<HashMap<String, String>>,
+    /// Globs of files whose exported async functions report their latency, e.g.
+    /// `["src/strategies/**"]` (none when omitted)
+    pub instrument: Option<Vec<String>>,
+    /// Expression for the object whose `record(event)` receives each timing
+    /// (default `globalThis.__strategyTelemetry`; calls are skipped while it is unset)
+    pub instrument_collector: Option<String>,
+    /// Lint rule -> "error", "warning", "info" or "off", overriding its default severity
+    /// in the `analyze_imports` diagnostics
+    pub lint_rules: Option<HashMap<String, String>>,
+    /// JSON Schema that `.strategy.yaml` / `.strategy.json` files are loaded against,
+    /// normally poly-kalshi-arb's `schema/app-config.schema.json`
+    pub strategy_schema: Option<String>,
+    /// Reuse output for files whose content and options are unchanged (default true)
+    pub cache: Option<bool>,
+    /// Where cached output is kept, relative to the working directory
+    /// (default "node_modules/.cache/rust-bun-plugin")
+    pub cache_dir: Option<String>,
+}
+
+/// Replace the options used for every following file
+#[napi]
+pub fn configure(options: PluginOptions) -> napi::Result<()> {
+    let options = TransformOptions::from_options(options).map_err(napi::Error::from_reason)?;
+    *OPTIONS.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(options);
+    Ok(())
+}
+
+/// Options in effect for the file being transformed
+pub fn current() -> Arc<TransformOptions> {
+    OPTIONS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    Define,
+    Alias,
+    Instrument,
+    Banner,
+    StrictMode,
+    DedupImports,
+    ConsoleLog,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 7] = [
+        Transform::Define,
+        Transform::Alias,
+        Transform::Instrument,
+        Transform::Banner,
+        Transform::StrictMode,
+        Transform::DedupImports,
+        Transform::ConsoleLog,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Transform::Define => "define",
+            Transform::Alias => "alias",
+            Transform::Instrument => "instrument",
+            Transform::Banner => "banner",
+            Transform::StrictMode => "strict-mode",
+            Transform::DedupImports => "dedup-imports",
+            Transform::ConsoleLog => "console-log",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
+#[derive(Debug)]
+pub struct TransformOptions {
+    transforms: Vec<Transform>,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    instrument: GlobSet,
+    /// Guard expression, parenthesized when it binds looser than `&&`
+    pub logging_guard: String,
+    pub banner: String,
+    pub defines: Vec<Define>,
+    pub aliases: Vec<Alias>,
+    /// Receiver of `record()` calls, parenthesized unless it is a plain reference
+    pub instrument_collector: String,
+    /// Rules with a changed severity; None turns the rule off
+    lint_rules: HashMap<Rule, Option<Severity>>,
+    /// Schema for strategy config files, read when `configure()` is called
+    pub strategy_schema: Option<serde_json::Value>,
+    /// None when caching is off
+    pub cache_dir: Option<PathBuf>,
+    /// Hash of everything above that affects output
+    config_hash: u128,
+}
+
+impl Default for TransformOptions {
+    fn default() -> Self {
+        Self {
+            transforms: Transform::ALL.to_vec(),
+            include: None,
+            exclude: GlobSet::empty(),
+            instrument: GlobSet::empty(),
+            logging_guard: DEFAULT_LOGGING_GUARD.to_string(),
+            banner: DEFAULT_BANNER.to_string(),
+            defines: Vec::new(),
+            aliases: Vec::new(),
+            instrument_collector: DEFAULT_INSTRUMENT_COLLECTOR.to_string(),
+            lint_rules: HashMap::new(),
+            strategy_schema: None,
+            cache_dir: Some(PathBuf::from(cache::DEFAULT_DIR)),
+            config_hash: 0,
+        }
+        .with_config_hash(&[None, None, None])
+    }
+}
+
+impl TransformOptions {
+    /// Validate `configure()` input: unknown transforms, bad globs and guards that
+    /// aren't a single expression are rejected
+    pub fn from_options(options: PluginOptions) -> Result<Self, String> {
+        let mut parsed = Self::default();
+
+        if let Some(names) = options.transforms {
+            parsed.transforms = names
+                .iter()
+                .map(|name| {
+                    Transform::from_name(name).ok_or_else(|| {
+                        let known: Vec<&str> = Transform::ALL.iter().map(|t| t.name()).collect();
+                        format!("unknown transform '{}' (expected one of {})", name, known.join(", "))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(globs) = &options.include {
+            parsed.include = Some(build_globs(globs)?);
+        }
+        if let Some(globs) = &options.exclude {
+            parsed.exclude = build_globs(globs)?;
+        }
+        if let Some(globs) = &options.instrument {
+            parsed.instrument = build_globs(globs)?;
+        }
+        if let Some(collector) = options.instrument_collector {
+            parsed.instrument_collector = parse_collector(&collector)?;
+        }
+        if let Some(guard) = options.logging_guard {
+            parsed.logging_guard = parse_guard(&guard)?;
+        }
+        if let Some(mut banner) = options.banner {
+            if !banner.is_empty() && !banner.ends_with('\n') {
+                banner.push('\n');
+            }
+            parsed.banner = banner;
+        }
+        for name in options.define_env.unwrap_or_default() {
+            let value = std::env::var(&name).map_or("undefined".to_string(), |v| serde_json::Value::String(v).to_string());
+            parsed.defines.push(Define::new(&format!("process.env.{}", name), &value)?);
+        }
+        // Explicit defines win over environment ones for the same path
+        let mut define: Vec<(String, String)> = options.define.unwrap_or_default().into_iter().collect();
+        define.sort();
+        for (path, value) in define {
+            parsed.defines.retain(|d| d.path() != path);
+            parsed.defines.push(Define::new(&path, &value)?);
+        }
+        if let Some(aliases) = options.aliases {
+            // Sorted first so equal-precedence aliases keep one order and the config hash is stable
+            let mut aliases: Vec<(String, String)> = aliases.into_iter().collect();
+            aliases.sort();
+            let aliases = aliases.iter().map(|(pattern, target)| Alias::new(pattern, target)).collect::<Result<_, _>>()?;
+            parsed.aliases = alias::prepare(aliases)?;
+        }
+        for (name, severity) in options.lint_rules.unwrap_or_default() {
+            let rule = Rule::from_name(&name).ok_or_else(|| {
+                let known: Vec<&str> = Rule::ALL.iter().map(|r| r.name()).collect();
+                format!("unknown lint rule '{}' (expected one of {})", name, known.join(", "))
+            })?;
+            parsed.lint_rules.insert(rule, Severity::parse(&severity)?);
+        }
+        if let Some(path) = &options.strategy_schema {
+            parsed.strategy_schema = Some(strategy::load_schema(path)?);
+        }
+        parsed.cache_dir = match (options.cache, options.cache_dir) {
+            (Some(false), _) => None,
+            (_, Some(dir)) => Some(PathBuf::from(dir)),
+            (_, None) => parsed.cache_dir,
+        };
+
+        Ok(parsed.with_config_hash(&[
+            options.include.as_deref(),
+            options.exclude.as_deref(),
+            options.instrument.as_deref(),
+        ]))
+    }
+
+    /// Options that change the output are hashed once here for the cache key; the globs
+    /// (include, exclude, instrument) are taken as written since a compiled `GlobSet`
+    /// can't be compared
+    fn with_config_hash(mut self, globs: &[Option<&[String]>; 3]) -> Self {
+        let description = format!(
+            "{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+            self.transforms,
+            globs,
+            self.logging_guard,
+            self.banner,
+            self.defines,
+            self.aliases,
+            self.instrument_collector
+        );
+        self.config_hash = xxh3_128(description.as_bytes());
+        self
+    }
+
+    pub fn config_hash(&self) -> u128 {
+        self.config_hash
+    }
+
+    pub fn enabled(&self, transform: Transform) -> bool {
+        self.transforms.contains(&transform)
+    }
+
+    /// Globs match the path relative to the working directory when the file is under it
+    pub fn applies_to(&self, path: &str) -> bool {
+        let relative = self.relative_path(path);
+        self.include.as_ref().is_none_or(|include| include.is_match(&relative)) && !self.exclude.is_match(&relative)
+    }
+
+    /// Severity `rule` is reported with, None when it is off
+    pub fn severity(&self, rule: Rule) -> Option<Severity> {
+        self.lint_rules.get(&rule).copied().unwrap_or(Some(rule.default_severity()))
+    }
+
+    /// Whether the instrument transform times the exports of `path`
+    pub fn instruments(&self, path: &str) -> bool {
+        self.instrument.is_match(self.relative_path(path))
+    }
+
+    /// `path` relative to the working directory when the file is under it
+    pub fn relative_path(&self, path: &str) -> PathBuf {
+        let path = Path::new(path);
+        let cwd = std::env::current_dir().ok();
+        cwd.as_deref().and_then(|cwd| path.strip_prefix(cwd).ok()).unwrap_or(path).to_path_buf()
+    }
+}
+
+fn build_globs(globs: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob).map_err(|e| format!("invalid glob '{}': {}", glob, e))?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+fn parse_guard(guard: &str) -> Result<String, String> {
+    // `guard && console.log()` must not regroup around the guard's own operators
+    parse_expression("logging guard", guard, |expression| {
+        !matches!(
+            expression,
+            Expression::LogicalExpression(_)
+                | Expression::ConditionalExpression(_)
+                | Expression::AssignmentExpression(_)
+                | Expression::SequenceExpression(_)
+                | Expression::ArrowFunctionExpression(_)
+                | Expression::YieldExpression(_)
+        )
+    })
+}
+
+fn parse_collector(collector: &str) -> Result<String, String> {
+    // `collector?.record(...)` only binds to a plain reference
+    parse_expression("telemetry collector", collector, |expression| {
+        matches!(
+            expression,
+            Expression::Identifier(_) | Expression::StaticMemberExpression(_) | Expression::ComputedMemberExpression(_)
+        )
+    })
+}
+
+/// Check `text` is a single expression, parenthesizing it unless `tight` holds for it
+fn parse_expression(what: &str, text: &str, tight: fn(&Expression<'_>) -> bool) -> Result<String, String> {
+    let text = text.trim();
+    let allocator = Allocator::default();
+    let expression = Parser::new(&allocator, text, SourceType::mjs())
+        .parse_expression()
+        .map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            format!("invalid {} '{}': {}", what, text, errors.join("; "))
+        })?;
+    // The parser stops after the first expression; anything left over is rejected
+    if expression.span().end as usize != text.len() {
+        return Err(format!("invalid {} '{}': expected a single expression", what, text));
+    }
+
+    Ok(if tight(&expression) { text.to_string() } else { format!("({})", text) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: PluginOptions) -> Result<TransformOptions, String> {
+        TransformOptions::from_options(options)
+    }
+
+    #[test]
+    fn defaults_enable_everything() {
+        let defaults = options(PluginOptions::default()).unwrap();
+        assert!(Transform::ALL.iter().all(|&t| defaults.enabled(t)));
+        assert!(defaults.applies_to("/anywhere/file.ts"));
+        assert_eq!(defaults.logging_guard, DEFAULT_LOGGING_GUARD);
+    }
+
+    #[test]
+    fn transforms_are_selected_by_name() {
+        let selected = options(PluginOptions { transforms: Some(vec!["console-log".into()]), ..Default::default() }).unwrap();
+        assert!(selected.enabled(Transform::ConsoleLog));
+        assert!(!selected.enabled(Transform::Banner));
+
+        let err = options(PluginOptions { transforms: Some(vec!["minify".into()]), ..Default::default() }).unwrap_err();
+        assert!(err.contains("unknown transform 'minify'"));
+    }
+
+    #[test]
+    fn include_and_exclude_globs() {
+        let scoped = options(PluginOptions {
+            include: Some(vec!["src/**/*.ts".into()]),
+            exclude: Some(vec!["**/*.test.ts".into()]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(scoped.applies_to("src/app/main.ts"));
+        assert!(!scoped.applies_to("src/app/main.test.ts"));
+        assert!(!scoped.applies_to("lib/main.ts"));
+
+        let cwd = std::env::current_dir().unwrap();
+        assert!(scoped.applies_to(cwd.join("src/main.ts").to_str().unwrap()));
+
+        assert!(options(PluginOptions { include: Some(vec!["src/[".into()]), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn logging_guard_is_validated_and_grouped() {
+        let simple = options(PluginOptions { logging_guard: Some("DEBUG".into()), ..Default::default() }).unwrap();
+        assert_eq!(simple.logging_guard, "DEBUG");
+
+        let loose = options(PluginOptions { logging_guard: Some("a || b".into()), ..Default::default() }).unwrap();
+        assert_eq!(loose.logging_guard, "(a || b)");
+
+        assert!(options(PluginOptions { logging_guard: Some("a; b".into()), ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn instrument_globs_and_collector() {
+        let defaults = options(PluginOptions::default()).unwrap();
+        assert!(!defaults.instruments("src/strategies/arb.ts"));
+
+        let configured = options(PluginOptions {
+            instrument: Some(vec!["src/strategies/**".into()]),
+            instrument_collector: Some("metrics.sink || fallback".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(configured.instruments("src/strategies/arb.ts"));
+        assert!(!configured.instruments("src/filters/kalman.ts"));
+        assert_eq!(configured.instrument_collector, "(metrics.sink || fallback)");
+        assert_ne!(configured.config_hash(), defaults.config_hash());
+    }
+}
@@ -0,0 +1,108 @@
+//! The per-file pipeline shared by the OnBeforeParse handlers and `transformFiles()`.
+//!
+//! Bun already calls the handlers from several threads at once; `transformFiles()` lets a
+//! large build pre-transform its sources on the plugin's own worker pool instead.
+
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Task};
+use napi_derive::napi;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::LazyLock;
+
+use crate::cache;
+use crate::metrics;
+use crate::options::{self, Transform};
+use crate::source_map;
+use crate::transform;
+
+static POOL: LazyLock<ThreadPool> = LazyLock::new(|| {
+    ThreadPoolBuilder::new()
+        .thread_name(|i| format!("rust-bun-transform-{}", i))
+        .build()
+        .expect("transform worker pool")
+});
+
+/// Run the enabled `transforms` over one file and append an inline source map. A map left
+/// by an earlier transform is chained so positions resolve to the original file. Output
+/// cached for the same content and options is returned without parsing.
+/// Ok(None) means the file is excluded by the options or nothing is enabled for it.
+pub fn run(path: &str, input: &str, transforms: &[Transform]) -> Result<Option<String>, String> {
+    let options = options::current();
+    let transforms: Vec<Transform> = transforms.iter().copied().filter(|&t| options.enabled(t)).collect();
+    if transforms.is_empty() || !options.applies_to(path) {
+        return Ok(None);
+    }
+
+    let cached = options.cache_dir.as_deref().map(|dir| (dir, cache::key(path, input, &transforms, &options)));
+    if let Some((dir, key)) = &cached {
+        if let Some(output) = cache::get(dir, key) {
+            return Ok(Some(output));
+        }
+    }
+
+    let (code, previous) = source_map::strip_inline(input);
+    let transformed = transform::apply(code, path, &transforms, &options).inspect_err(|_| metrics::record_skip())?;
+
+    let (map, source, content) = match previous {
+        Some(previous) => (transformed.map.compose(&previous.map), previous.source, previous.content),
+        None => (transformed.map, path.to_string(), code.to_string()),
+    };
+
+    let mut output = transformed.code;
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(&map.to_inline_comment(&source, &content));
+
+    metrics::record_file(input.len(), output.len(), &transformed.timings);
+    if let Some((dir, key)) = &cached {
+        cache::put(dir, key, &output);
+    }
+    Ok(Some(output))
+}
+
+#[napi(object)]
+pub struct FileResult {
+    pub path: String,
+    /// Transformed source, or None when the file was left unchanged
+    pub code: Option<String>,
+    /// Read or parse error
+    pub error: Option<String>,
+}
+
+pub struct TransformFiles {
+    paths: Vec<String>,
+}
+
+impl Task for TransformFiles {
+    type Output = Vec<FileResult>;
+    type JsValue = Vec<FileResult>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(POOL.install(|| self.paths.par_iter().map(|path| transform_file(path)).collect()))
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Read and transform `paths` concurrently with every enabled transform; results come
+/// back in input order
+#[napi]
+pub fn transform_files(paths: Vec<String>) -> AsyncTask<TransformFiles> {
+    AsyncTask::new(TransformFiles { paths })
+}
+
+fn transform_file(path: &str) -> FileResult {
+    let outcome = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|input| run(path, &input, &Transform::ALL));
+
+    let (code, error) = match outcome {
+        Ok(code) => (code, None),
+        Err(error) => (None, Some(error)),
+    };
+    FileResult { path: path.to_string(), code, error }
+}
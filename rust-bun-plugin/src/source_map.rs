@@ -0,0 +1,243 @@
+//! Source maps for the source-rewriting transforms.
+//!
+//! Every transform returns its output together with a `SourceMap` from output positions
+//! back to input positions. Maps compose, so after any number of rewrites (and across
+//! plugins, via the inline `sourceMappingURL` comment) positions still resolve to the
+//! original file. Lines are 0-based; columns are 0-based UTF-16 code units, as in the
+//! Source Map v3 spec.
+
+use serde_json::{json, Value};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const INLINE_PREFIX: &str = "//# sourceMappingURL=data:application/json;base64,";
+
+/// Generated column -> original position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub generated_column: u32,
+    pub original_line: u32,
+    pub original_column: u32,
+}
+
+impl Segment {
+    pub fn new(generated_column: u32, original_line: u32, original_column: u32) -> Self {
+        Self { generated_column, original_line, original_column }
+    }
+}
+
+/// Single-source map; `lines[n]` holds generated line n's segments ordered by column
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    lines: Vec<Vec<Segment>>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a generated line (empty for inserted text with no origin)
+    pub fn push_line(&mut self, segments: Vec<Segment>) {
+        self.lines.push(segments);
+    }
+
+    /// Original position of a generated position: the closest segment at or before
+    /// the column, offset by the distance from it
+    pub fn lookup(&self, line: u32, column: u32) -> Option<(u32, u32)> {
+        let segments = self.lines.get(line as usize)?;
+        let segment = segments.iter().rev().find(|s| s.generated_column <= column)?;
+        Some((segment.original_line, segment.original_column + (column - segment.generated_column)))
+    }
+
+    /// `self` maps output -> intermediate and `earlier` maps intermediate -> original;
+    /// the result maps output -> original
+    pub fn compose(&self, earlier: &SourceMap) -> SourceMap {
+        let lines = self
+            .lines
+            .iter()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .filter_map(|s| {
+                        let (line, column) = earlier.lookup(s.original_line, s.original_column)?;
+                        Some(Segment::new(s.generated_column, line, column))
+                    })
+                    .collect()
+            })
+            .collect();
+        SourceMap { lines }
+    }
+
+    /// VLQ `mappings` field
+    pub fn mappings(&self) -> String {
+        let mut out = String::new();
+        let (mut original_line, mut original_column) = (0i64, 0i64);
+        for (n, segments) in self.lines.iter().enumerate() {
+            if n > 0 {
+                out.push(';');
+            }
+            let mut generated_column = 0i64;
+            for (i, s) in segments.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_vlq(&mut out, s.generated_column as i64 - generated_column);
+                encode_vlq(&mut out, 0); // single source
+                encode_vlq(&mut out, s.original_line as i64 - original_line);
+                encode_vlq(&mut out, s.original_column as i64 - original_column);
+                generated_column = s.generated_column as i64;
+                original_line = s.original_line as i64;
+                original_column = s.original_column as i64;
+            }
+        }
+        out
+    }
+
+    /// Parse a `mappings` field; segments naming a source other than the first are dropped
+    pub fn from_mappings(mappings: &str) -> Result<Self, String> {
+        let mut map = SourceMap::new();
+        let (mut source, mut original_line, mut original_column) = (0i64, 0i64, 0i64);
+        for line in mappings.split(';') {
+            let mut segments = Vec::new();
+            let mut generated_column = 0i64;
+            for segment in line.split(',').filter(|s| !s.is_empty()) {
+                let fields = decode_vlq(segment)?;
+                generated_column += fields[0];
+                if fields.len() < 4 {
+                    continue;
+                }
+                source += fields[1];
+                original_line += fields[2];
+                original_column += fields[3];
+                if source == 0 && generated_column >= 0 && original_line >= 0 && original_column >= 0 {
+                    segments.push(Segment::new(generated_column as u32, original_line as u32, original_column as u32));
+                }
+            }
+            map.push_line(segments);
+        }
+        Ok(map)
+    }
+
+    /// Source Map v3 JSON with the original text embedded
+    pub fn to_json(&self, source: &str, content: &str) -> String {
+        json!({
+            "version": 3,
+            "sources": [source],
+            "sourcesContent": [content],
+            "names": [],
+            "mappings": self.mappings(),
+        })
+        .to_string()
+    }
+
+    /// `//# sourceMappingURL=data:...` comment carrying the map
+    pub fn to_inline_comment(&self, source: &str, content: &str) -> String {
+        format!("{}{}", INLINE_PREFIX, encode_base64(self.to_json(source, content).as_bytes()))
+    }
+}
+
+/// A map found at the end of incoming code (left by an earlier transform)
+pub struct InlineMap {
+    pub map: SourceMap,
+    pub source: String,
+    pub content: String,
+}
+
+/// Split a trailing inline source map comment off `code`
+pub fn strip_inline(code: &str) -> (&str, Option<InlineMap>) {
+    let trimmed = code.trim_end();
+    let Some(start) = trimmed.rfind(INLINE_PREFIX) else {
+        return (code, None);
+    };
+    if !trimmed[..start].is_empty() && !trimmed[..start].ends_with('\n') {
+        return (code, None);
+    }
+    let parsed = decode_base64(&trimmed[start + INLINE_PREFIX.len()..])
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).map_err(|e| e.to_string()))
+        .and_then(|json| {
+            let field = |name: &str| json[name][0].as_str().unwrap_or_default().to_string();
+            Ok(InlineMap {
+                map: SourceMap::from_mappings(json["mappings"].as_str().unwrap_or_default())?,
+                source: field("sources"),
+                content: field("sourcesContent"),
+            })
+        });
+    match parsed {
+        Ok(inline) => (trimmed[..start].strip_suffix('\n').unwrap_or(&trimmed[..start]), Some(inline)),
+        Err(_) => (code, None),
+    }
+}
+
+/// Length in UTF-16 code units (source map columns)
+pub fn utf16_len(text: &str) -> u32 {
+    text.chars().map(|c| c.len_utf16() as u32).sum()
+}
+
+fn encode_vlq(out: &mut String, value: i64) {
+    let mut vlq = if value < 0 { ((-value) << 1) | 1 } else { value << 1 };
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn decode_vlq(segment: &str) -> Result<Vec<i64>, String> {
+    let mut fields = Vec::new();
+    let (mut value, mut shift) = (0i64, 0u32);
+    for byte in segment.bytes() {
+        let digit = base64_value(byte).ok_or_else(|| format!("invalid VLQ character '{}'", byte as char))? as i64;
+        value += (digit & 0b11111) << shift;
+        if digit & 0b100000 != 0 {
+            shift += 5;
+            continue;
+        }
+        fields.push(if value & 1 == 1 { -(value >> 1) } else { value >> 1 });
+        value = 0;
+        shift = 0;
+    }
+    if shift != 0 || fields.is_empty() {
+        return Err(format!("truncated VLQ segment '{}'", segment));
+    }
+    Ok(fields)
+}
+
+fn base64_value(byte: u8) -> Option<u8> {
+    BASE64.iter().position(|&b| b == byte).map(|i| i as u8)
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0b111111) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in text.bytes().filter(|&b| b != b'=') {
+        let value = base64_value(byte).ok_or_else(|| format!("invalid base64 character '{}'", byte as char))?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,292 @@
+//! Loader for `.strategy.yaml` / `.strategy.json` files: the parameters are validated
+//! against the JSON Schema the Rust engine generates from its config structs
+//! (`poly-kalshi-arb/schema/app-config.schema.json`), completed with its defaults and
+//! emitted as a TypeScript module whose types come from the same schema.
+//!
+//! Only the schema keywords that schema uses are understood: `type`, `properties`,
+//! `additionalProperties`, `propertyNames`, `items`, `enum`, `minimum`, `maximum`,
+//! `exclusiveMinimum`, `exclusiveMaximum` and `default`.
+
+use serde_json::{Map, Value};
+use std::path::Path;
+
+const EXTENSIONS: [&str; 3] = [".strategy.yaml", ".strategy.yml", ".strategy.json"];
+
+pub fn is_strategy_config(path: &str) -> bool {
+    EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+/// Read a schema file, checking it is an object schema
+pub fn load_schema(path: &str) -> Result<Value, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("reading strategy schema {}: {}", path, e))?;
+    let schema: Value = serde_json::from_str(&text).map_err(|e| format!("parsing strategy schema {}: {}", path, e))?;
+    if schema["type"] != "object" {
+        return Err(format!("strategy schema {} does not describe an object", path));
+    }
+    Ok(schema)
+}
+
+/// Turn the config file at `path` into a TypeScript module exporting the validated,
+/// defaulted parameters; Err lists every problem found
+pub fn to_module(path: &str, source: &str, schema: &Value) -> Result<String, String> {
+    let value: Value = if path.ends_with(".json") {
+        serde_json::from_str(source).map_err(|e| format!("{}: {}", path, e))?
+    } else if source.trim().is_empty() {
+        Value::Object(Map::new())
+    } else {
+        serde_yaml::from_str(source).map_err(|e| format!("{}: {}", path, e))?
+    };
+
+    let mut errors = Vec::new();
+    validate(schema, &value, "", &mut errors);
+    if !errors.is_empty() {
+        return Err(format!("{} does not match the strategy schema:\n  {}", path, errors.join("\n  ")));
+    }
+    let config = with_defaults(schema, Some(&value));
+
+    let name = Path::new(path).file_name().map_or(path.into(), |name| name.to_string_lossy());
+    let mut module = format!("// Generated from {} by the strategy-config loader\n", name);
+    module.push_str(&format!("export interface StrategyConfig {}\n\n", type_of(schema, 0)));
+    module.push_str(&format!(
+        "const config: StrategyConfig = {};\n\nexport default config;\n",
+        serde_json::to_string_pretty(&config).expect("JSON values serialize")
+    ));
+    if let Value::Object(sections) = &config {
+        for key in sections.keys().filter(|key| is_identifier(key)) {
+            module.push_str(&format!("export const {} = config.{};\n", key, key));
+        }
+    }
+    Ok(module)
+}
+
+/// Collect every mismatch between `value` and `schema`, naming the dotted path
+fn validate(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let at = if path.is_empty() { "(root)" } else { path };
+
+    if let Some(expected) = schema["type"].as_str() {
+        let matches = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "boolean" => value.is_boolean(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!("{}: expected {}, found {}", at, expected, value));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema["enum"].as_array() {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            errors.push(format!("{}: {} is not one of {}", at, value, allowed.join(", ")));
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        let bounds = [
+            ("minimum", ">=", n >= schema["minimum"].as_f64().unwrap_or(f64::NEG_INFINITY)),
+            ("maximum", "<=", n <= schema["maximum"].as_f64().unwrap_or(f64::INFINITY)),
+            ("exclusiveMinimum", ">", n > schema["exclusiveMinimum"].as_f64().unwrap_or(f64::NEG_INFINITY)),
+            ("exclusiveMaximum", "<", n < schema["exclusiveMaximum"].as_f64().unwrap_or(f64::INFINITY)),
+        ];
+        for (keyword, op, ok) in bounds {
+            if !ok {
+                errors.push(format!("{}: {} must be {} {}", at, n, op, schema[keyword]));
+            }
+        }
+    }
+
+    if let Value::Array(items) = value {
+        for (i, item) in items.iter().enumerate() {
+            validate(&schema["items"], item, &format!("{}[{}]", path, i), errors);
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        for (key, field) in fields {
+            let field_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            if schema.get("propertyNames").is_some() {
+                validate(&schema["propertyNames"], &Value::String(key.clone()), &field_path, errors);
+            }
+            match (schema["properties"].get(key), &schema["additionalProperties"]) {
+                (Some(property), _) => validate(property, field, &field_path, errors),
+                (None, Value::Bool(false)) => errors.push(format!("{}: unknown key", field_path)),
+                (None, additional) => validate(additional, field, &field_path, errors),
+            }
+        }
+    }
+}
+
+/// `value` with every property it leaves out filled from the schema defaults
+fn with_defaults(schema: &Value, value: Option<&Value>) -> Value {
+    match (value, schema["properties"].as_object()) {
+        (None | Some(Value::Object(_)), Some(properties)) => {
+            let given = value.and_then(Value::as_object);
+            let mut out: Map<String, Value> = properties
+                .iter()
+                .map(|(key, property)| (key.clone(), with_defaults(property, given.and_then(|g| g.get(key)))))
+                .collect();
+            // Keys allowed by `additionalProperties` pass through as given
+            for (key, field) in given.into_iter().flatten() {
+                out.entry(key.clone()).or_insert_with(|| field.clone());
+            }
+            Value::Object(out)
+        }
+        (Some(value), _) => value.clone(),
+        (None, _) => schema.get("default").cloned().unwrap_or(Value::Null),
+    }
+}
+
+/// TypeScript type for `schema`, indented for nesting `depth`
+fn type_of(schema: &Value, depth: usize) -> String {
+    if let Some(allowed) = schema["enum"].as_array() {
+        let literals: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return literals.join(" | ");
+    }
+    match schema["type"].as_str() {
+        Some("string") => "string".to_string(),
+        Some("number" | "integer") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("array") => match schema.get("items") {
+            Some(items) => format!("Array<{}>", type_of(items, depth)),
+            None => "unknown[]".to_string(),
+        },
+        Some("object") => {
+            let indent = "  ".repeat(depth + 1);
+            let mut fields: Vec<String> = schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(key, property)| format!("{}{}: {};", indent, property_key(key), type_of(property, depth + 1)))
+                .collect();
+            match &schema["additionalProperties"] {
+                Value::Bool(false) => {}
+                Value::Object(_) => {
+                    let key = match schema["propertyNames"].get("enum") {
+                        Some(_) => format!("[key in {}]?", type_of(&schema["propertyNames"], depth)),
+                        None => "[key: string]".to_string(),
+                    };
+                    // A mapped type can't share braces with named properties
+                    if fields.is_empty() {
+                        return format!("{{ {}: {} }}", key, type_of(&schema["additionalProperties"], depth + 1));
+                    }
+                    fields.push(format!("{}[key: string]: unknown;", indent));
+                }
+                _ => fields.push(format!("{}[key: string]: unknown;", indent)),
+            }
+            format!("{{\n{}\n{}}}", fields.join("\n"), "  ".repeat(depth))
+        }
+        _ => "unknown".to_string(),
+    }
+}
+
+fn property_key(key: &str) -> String {
+    if is_identifier(key) {
+        key.to_string()
+    } else {
+        Value::from(key).to_string()
+    }
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "risk": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "enabled": { "type": "boolean", "default": true },
+                        "max_daily_loss": { "type": "number", "default": 500.0, "exclusiveMinimum": 0 },
+                        "cooldown_secs": { "type": "integer", "default": 300 },
+                    },
+                },
+                "execution": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "cost_method": { "type": "string", "default": "fifo", "enum": ["fifo", "average"] },
+                        "leagues": { "type": "array", "default": [], "items": { "type": "string" } },
+                    },
+                },
+                "features": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "components": {
+                            "type": "object",
+                            "properties": {},
+                            "additionalProperties": { "type": "boolean" },
+                            "propertyNames": { "enum": ["71", "77"] },
+                        },
+                    },
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn yaml_becomes_a_typed_module_with_defaults() {
+        let source = "risk:\n  max_daily_loss: 250\nexecution:\n  leagues: [nba, nfl]\nfeatures:\n  components:\n    \"77\": true\n";
+        let module = to_module("strategies/arb.strategy.yaml", source, &schema()).unwrap();
+
+        assert!(module.contains("    max_daily_loss: number;\n"), "{}", module);
+        assert!(module.contains("    cost_method: \"fifo\" | \"average\";\n"), "{}", module);
+        assert!(module.contains("    leagues: Array<string>;\n"), "{}", module);
+        assert!(module.contains("    components: { [key in \"71\" | \"77\"]?: boolean };\n"), "{}", module);
+        assert!(module.contains("\"max_daily_loss\": 250"), "{}", module);
+        assert!(module.contains("\"cooldown_secs\": 300"), "{}", module);
+        assert!(module.contains("\"77\": true"), "{}", module);
+        assert!(module.ends_with("export const execution = config.execution;\nexport const features = config.features;\nexport const risk = config.risk;\n"));
+
+        let allocator = Allocator::default();
+        let parsed = Parser::new(&allocator, &module, SourceType::ts()).parse();
+        assert!(parsed.errors.is_empty(), "{:?}\n{}", parsed.errors, module);
+    }
+
+    #[test]
+    fn every_schema_violation_is_reported() {
+        let source = r#"{ "risk": { "max_daily_loss": 0, "cooldown_secs": 1.5, "typo": 1 }, "execution": { "cost_method": "lifo", "leagues": [1] }, "features": { "components": { "12": true } } }"#;
+        let err = to_module("arb.strategy.json", source, &schema()).unwrap_err();
+        for expected in [
+            "risk.max_daily_loss: 0 must be > 0",
+            "risk.cooldown_secs: expected integer, found 1.5",
+            "risk.typo: unknown key",
+            "execution.cost_method: \"lifo\" is not one of \"fifo\", \"average\"",
+            "execution.leagues[0]: expected string, found 1",
+            "features.components.12: \"12\" is not one of \"71\", \"77\"",
+        ] {
+            assert!(err.contains(expected), "missing '{}' in:\n{}", expected, err);
+        }
+
+        assert!(to_module("bad.strategy.yaml", "risk: [", &schema()).is_err());
+        assert!(to_module("empty.strategy.yaml", "", &schema()).unwrap().contains("\"enabled\": true"));
+    }
+
+    #[test]
+    fn matches_strategy_config_paths() {
+        assert!(is_strategy_config("/app/strategies/arb.strategy.yaml"));
+        assert!(is_strategy_config("arb.strategy.json"));
+        assert!(!is_strategy_config("arb.yaml"));
+        assert!(!is_strategy_config("strategy.ts"));
+    }
+}
@@ -0,0 +1,420 @@
+//! AST-driven rewrites for the plugin transforms.
+//!
+//! Files are parsed with oxc and each pass walks the tree to find what to change, then
+//! records span-based edits on a `Rewriter`. The tree is never re-printed, so code the
+//! passes don't touch (comments, strings, formatting) comes through byte-for-byte and
+//! the edits produce the source map as they are applied.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{CallExpression, Expression, ExpressionStatement, Program, Statement};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::alias;
+use crate::define;
+use crate::instrument;
+use crate::options::{Transform, TransformOptions};
+use crate::source_map::{utf16_len, Segment, SourceMap};
+
+/// What a pass knows about the file being transformed
+pub struct Context<'a> {
+    pub path: &'a str,
+    pub options: &'a TransformOptions,
+}
+
+/// A pass inspects the parsed program and records edits
+pub type Pass = fn(&Program<'_>, &Context<'_>, &mut Rewriter<'_>);
+
+pub fn pass(transform: Transform) -> Pass {
+    match transform {
+        Transform::Define => define::define,
+        Transform::Alias => alias::alias,
+        Transform::Instrument => instrument::instrument,
+        Transform::Banner => banner,
+        Transform::StrictMode => strict_mode,
+        Transform::DedupImports => dedup_imports,
+        Transform::ConsoleLog => guard_console_log,
+    }
+}
+
+pub struct Transformed {
+    pub code: String,
+    pub map: SourceMap,
+    /// Time spent parsing, in each transform and applying the edits
+    pub timings: Vec<(&'static str, Duration)>,
+}
+
+/// Parse `source` (the file at `path`) and apply `transforms` in order; Err carries the
+/// parse diagnostics
+pub fn apply(
+    source: &str,
+    path: &str,
+    transforms: &[Transform],
+    options: &TransformOptions,
+) -> Result<Transformed, String> {
+    let mut timings = Vec::with_capacity(transforms.len() + 2);
+    let started = Instant::now();
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(path).unwrap_or_else(|_| SourceType::ts());
+    let parsed = Parser::new(&allocator, source, source_type).parse();
+    if parsed.panicked || !parsed.errors.is_empty() {
+        let errors: Vec<String> = parsed.errors.iter().map(|e| e.to_string()).collect();
+        return Err(errors.join("; "));
+    }
+    timings.push(("parse", started.elapsed()));
+
+    let cx = Context { path, options };
+    let mut rewriter = Rewriter::new(source);
+    for &transform in transforms {
+        let started = Instant::now();
+        pass(transform)(&parsed.program, &cx, &mut rewriter);
+        timings.push((transform.name(), started.elapsed()));
+    }
+
+    let started = Instant::now();
+    let (code, map) = rewriter.finish();
+    timings.push(("rewrite", started.elapsed()));
+    Ok(Transformed { code, map, timings })
+}
+
+/// Prepend the optimization banner (after any hashbang)
+pub fn banner(program: &Program<'_>, cx: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    rewriter.insert(prologue_start(program), cx.options.banner.as_str());
+}
+
+/// Insert a `"use strict"` directive unless the file already has one
+pub fn strict_mode(program: &Program<'_>, _: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    if program.directives.iter().any(|d| d.directive == "use strict") {
+        return;
+    }
+    rewriter.insert(prologue_start(program), "\"use strict\";\n\n");
+}
+
+/// Drop top-level imports identical to an earlier one. The survivors keep their order:
+/// import evaluation order is observable, so imports are never sorted or hoisted.
+pub fn dedup_imports(program: &Program<'_>, _: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    let mut seen = HashSet::new();
+    for statement in &program.body {
+        let Statement::ImportDeclaration(import) = statement else {
+            continue;
+        };
+        if !seen.insert(import_key(import.span.source_text(program.source_text))) {
+            rewriter.remove_line(import.span);
+        }
+    }
+}
+
+/// Imports with the same key are duplicates: same text modulo whitespace and the
+/// trailing semicolon
+pub fn import_key(text: &str) -> String {
+    text.trim_end_matches(';').split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Guard `console.log(...)` calls with the configured expression (skipped in production
+/// by default). Only calls on the global `console` are matched; strings, comments and
+/// `logger.console.log` are left alone.
+pub fn guard_console_log(program: &Program<'_>, cx: &Context<'_>, rewriter: &mut Rewriter<'_>) {
+    let mut finder = ConsoleLogCalls::default();
+    finder.visit_program(program);
+
+    let guard = logging_guard(cx.options);
+
+    for (span, statement) in finder.calls {
+        if statement {
+            rewriter.insert(span.start, guard.as_str());
+        } else {
+            // Inside a larger expression the guard needs parentheses to keep precedence;
+            // at statement level they are left off so a preceding line without a
+            // semicolon isn't turned into a call
+            rewriter.insert(span.start, format!("({}", guard));
+            rewriter.insert(span.end, ")");
+        }
+    }
+}
+
+/// Text inserted before a guarded call
+fn logging_guard(options: &TransformOptions) -> String {
+    format!("/* console.log - optimized */ {} && ", options.logging_guard)
+}
+
+/// First offset after the hashbang line, where prologue text can be inserted
+pub fn prologue_start(program: &Program<'_>) -> u32 {
+    let Some(hashbang) = &program.hashbang else {
+        return 0;
+    };
+    let rest = &program.source_text[hashbang.span.end as usize..];
+    hashbang.span.end + rest.find('\n').map_or(rest.len(), |i| i + 1) as u32
+}
+
+fn is_console_log(callee: &Expression<'_>) -> bool {
+    match callee {
+        Expression::StaticMemberExpression(member) => {
+            member.property.name == "log"
+                && matches!(&member.object, Expression::Identifier(object) if object.name == "console")
+        }
+        _ => false,
+    }
+}
+
+/// `console.log` calls and whether each is a whole expression statement
+#[derive(Default)]
+struct ConsoleLogCalls {
+    calls: Vec<(Span, bool)>,
+    statement_call: Option<Span>,
+}
+
+impl<'a> Visit<'a> for ConsoleLogCalls {
+    fn visit_expression_statement(&mut self, it: &ExpressionStatement<'a>) {
+        if let Expression::CallExpression(call) = &it.expression {
+            self.statement_call = Some(call.span);
+        }
+        walk::walk_expression_statement(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if is_console_log(&it.callee) {
+            self.calls.push((it.span(), self.statement_call == Some(it.span)));
+        }
+        walk::walk_call_expression(self, it);
+    }
+}
+
+enum Edit {
+    Insert { at: u32, text: String },
+    Remove { start: u32, end: u32 },
+}
+
+impl Edit {
+    fn position(&self) -> u32 {
+        match self {
+            Edit::Insert { at, .. } => *at,
+            Edit::Remove { start, .. } => *start,
+        }
+    }
+}
+
+/// Span edits over the original text. Inserts at the same offset keep the order they
+/// were made in and land before anything removed there; an insert strictly inside a
+/// removed range is dropped.
+pub struct Rewriter<'s> {
+    source: &'s str,
+    edits: Vec<Edit>,
+}
+
+impl<'s> Rewriter<'s> {
+    pub fn new(source: &'s str) -> Self {
+        Self { source, edits: Vec::new() }
+    }
+
+    /// Insert `text` before offset `at`; its first line maps to `at`
+    pub fn insert(&mut self, at: u32, text: impl Into<String>) {
+        self.edits.push(Edit::Insert { at, text: text.into() });
+    }
+
+    pub fn remove(&mut self, start: u32, end: u32) {
+        self.edits.push(Edit::Remove { start, end });
+    }
+
+    /// Replace `start..end` with `text`, which maps to `start`
+    pub fn replace(&mut self, start: u32, end: u32, text: impl Into<String>) {
+        self.insert(start, text);
+        self.remove(start, end);
+    }
+
+    /// Remove `span`, taking its whole line when nothing else is on it
+    pub fn remove_line(&mut self, span: Span) {
+        let (start, end) = (span.start as usize, span.end as usize);
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let rest = &self.source[end..];
+        let line_end = rest.find('\n').map_or(self.source.len(), |i| end + i + 1);
+
+        if self.source[line_start..start].trim().is_empty() && self.source[end..line_end].trim().is_empty() {
+            self.remove(line_start as u32, line_end as u32);
+        } else {
+            self.remove(span.start, span.end);
+        }
+    }
+
+    /// Apply the edits, returning the new text and its map back to the original
+    pub fn finish(mut self) -> (String, SourceMap) {
+        self.edits.sort_by_key(|edit| (edit.position(), matches!(edit, Edit::Remove { .. })));
+
+        let mut output = Output::new(self.source);
+        let mut cursor = 0;
+        for edit in self.edits {
+            match edit {
+                Edit::Insert { at, text } => {
+                    if (at as usize) < cursor {
+                        continue;
+                    }
+                    output.copy(cursor, at as usize);
+                    cursor = at as usize;
+                    output.insert(&text, at as usize);
+                }
+                Edit::Remove { start, end } => {
+                    if start as usize > cursor {
+                        output.copy(cursor, start as usize);
+                    }
+                    cursor = cursor.max(end as usize);
+                }
+            }
+        }
+        output.copy(cursor, self.source.len());
+        output.finish()
+    }
+}
+
+/// Generated text plus the map, built a line at a time
+struct Output<'s> {
+    source: &'s str,
+    line_starts: Vec<usize>,
+    text: String,
+    map: SourceMap,
+    line: Vec<Segment>,
+    column: u32,
+}
+
+impl<'s> Output<'s> {
+    fn new(source: &'s str) -> Self {
+        let line_starts = std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        Self { source, line_starts, text: String::with_capacity(source.len()), map: SourceMap::new(), line: Vec::new(), column: 0 }
+    }
+
+    /// Copy original text, mapping the start of each line of it
+    fn copy(&mut self, start: usize, end: usize) {
+        let mut offset = start;
+        for (i, piece) in self.source[start..end].split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            if !piece.is_empty() {
+                self.mark(offset);
+                self.push(piece);
+            }
+            offset += piece.len() + 1;
+        }
+    }
+
+    /// Inserted text; only its first line has an origin
+    fn insert(&mut self, text: &str, origin: usize) {
+        for (i, piece) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            } else if !piece.is_empty() {
+                self.mark(origin);
+            }
+            self.push(piece);
+        }
+    }
+
+    fn mark(&mut self, offset: usize) {
+        let line = self.line_starts.partition_point(|&s| s <= offset) - 1;
+        let column = utf16_len(&self.source[self.line_starts[line]..offset]);
+        let segment = Segment::new(self.column, line as u32, column);
+        match self.line.last_mut() {
+            Some(last) if last.generated_column == self.column => *last = segment,
+            _ => self.line.push(segment),
+        }
+    }
+
+    fn push(&mut self, piece: &str) {
+        self.text.push_str(piece);
+        self.column += utf16_len(piece);
+    }
+
+    fn newline(&mut self) {
+        self.text.push('\n');
+        self.map.push_line(std::mem::take(&mut self.line));
+        self.column = 0;
+    }
+
+    fn finish(mut self) -> (String, SourceMap) {
+        self.map.push_line(self.line);
+        (self.text, self.map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{PluginOptions, DEFAULT_BANNER};
+
+    fn run(source: &str, transforms: &[Transform]) -> (String, SourceMap) {
+        let transformed = apply(source, "test.ts", transforms, &TransformOptions::default()).expect("source parses");
+        (transformed.code, transformed.map)
+    }
+
+    fn assert_reparses(output: &str) {
+        let allocator = Allocator::default();
+        let reparsed = Parser::new(&allocator, output, SourceType::ts()).parse();
+        assert!(reparsed.errors.is_empty(), "output does not parse: {:?}\n{}", reparsed.errors, output);
+    }
+
+    #[test]
+    fn untouched_code_round_trips_byte_for_byte() {
+        let source = "const s = \"console.log\"; // console.log(s)\n/* console.log('x') */\nlogger.console.log(1);\nconst t = `console.log ${s}`;\n";
+        let (output, map) = run(source, &[Transform::DedupImports, Transform::ConsoleLog]);
+        assert_eq!(output, source);
+        assert_eq!(map.lookup(2, 7), Some((2, 7)));
+        assert_eq!(map.lookup(3, 12), Some((3, 12)));
+    }
+
+    #[test]
+    fn guards_only_global_console_log_calls() {
+        let source = "console.log('a');\nconst x = 1 + console.log('b');\nfoo(console.log(console.log('c')));\nconsole.info('d');\n";
+        let (output, map) = run(source, &[Transform::ConsoleLog]);
+        let guard = logging_guard(&TransformOptions::default());
+        assert_eq!(
+            output,
+            format!(
+                "{g}console.log('a');\nconst x = 1 + ({g}console.log('b'));\nfoo(({g}console.log(({g}console.log('c')))));\nconsole.info('d');\n",
+                g = guard
+            )
+        );
+        assert_reparses(&output);
+        // The guard resolves to the call site, as does the call after it
+        assert_eq!(map.lookup(1, 14), Some((1, 14)));
+        assert_eq!(map.lookup(1, 15 + utf16_len(&guard)), Some((1, 14)));
+    }
+
+    #[test]
+    fn dedups_imports_in_place() {
+        let source = "import { b } from 'b';\nimport a from 'a'; // keep\nconst π = 1;\nimport   { b }   from 'b'\nimport a from 'a'; // trailing\n";
+        let (output, map) = run(source, &[Transform::DedupImports]);
+        assert_eq!(output, "import { b } from 'b';\nimport a from 'a'; // keep\nconst π = 1;\n // trailing\n");
+        assert_reparses(&output);
+        assert_eq!(map.lookup(3, 1), Some((4, 19)));
+    }
+
+    #[test]
+    fn strict_mode_goes_after_hashbang_and_only_once() {
+        let (output, map) = run("#!/usr/bin/env bun\nconsole.log(1);\n", &[Transform::Banner, Transform::StrictMode]);
+        assert_eq!(output, format!("#!/usr/bin/env bun\n{}\"use strict\";\n\nconsole.log(1);\n", DEFAULT_BANNER));
+        assert_eq!(map.lookup(5, 0), Some((1, 0)));
+
+        let source = "'use strict';\nconst a = 1;\n";
+        assert_eq!(run(source, &[Transform::StrictMode]).0, source);
+    }
+
+    #[test]
+    fn configured_guard_and_banner_are_used() {
+        let options = TransformOptions::from_options(PluginOptions {
+            logging_guard: Some("a || b".into()),
+            banner: Some("/* built */".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        let transformed = apply("console.log(1);\n", "test.ts", &[Transform::Banner, Transform::ConsoleLog], &options).unwrap();
+        assert_eq!(transformed.code, "/* built */\n/* console.log - optimized */ (a || b) && console.log(1);\n");
+        let stages: Vec<&str> = transformed.timings.iter().map(|(stage, _)| *stage).collect();
+        assert_eq!(stages, ["parse", "banner", "console-log", "rewrite"]);
+    }
+
+    #[test]
+    fn parse_errors_are_reported() {
+        assert!(apply("const = ;", "test.ts", &[Transform::StrictMode], &TransformOptions::default()).is_err());
+    }
+}